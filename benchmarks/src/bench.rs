@@ -1,3 +1,4 @@
 mod routing;
+mod files;
 
-criterion::criterion_main!(routing::routing);
+criterion::criterion_main!(routing::routing, files::files);
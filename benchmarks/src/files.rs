@@ -0,0 +1,106 @@
+use std::collections::hash_set::HashSet;
+use std::io::{Read, Write};
+use std::net::{Ipv4Addr, TcpStream};
+use std::path::PathBuf;
+use std::sync::mpsc;
+
+use criterion::{criterion_group, Criterion, Throughput};
+
+use rocket::{get, routes, config, Config, Request, Shutdown, State};
+use rocket::fairing::AdHoc;
+use rocket::fs::NamedFile;
+use rocket::response::{self, Responder, Response};
+
+const FILE_SIZE: usize = 16 * 1024 * 1024;
+
+/// A `NamedFile`'s file in an ordinary sized body, which the server writes out
+/// through the body's `AsyncRead` implementation, in the chunks `NamedFile`
+/// uses.
+struct ThroughReader(NamedFile);
+
+impl<'r> Responder<'r, 'static> for ThroughReader {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        Response::build()
+            .sized_body(None, self.0.take_file())
+            .max_chunk_size(64 * 1024)
+            .ok()
+    }
+}
+
+#[get("/reader")]
+async fn reader(path: &State<PathBuf>) -> Option<ThroughReader> {
+    NamedFile::open(path.inner()).await.ok().map(ThroughReader)
+}
+
+#[get("/file")]
+async fn file(path: &State<PathBuf>) -> Option<NamedFile> {
+    NamedFile::open(path.inner()).await.ok()
+}
+
+/// A file of `FILE_SIZE` bytes, removed when dropped.
+struct TempFile(PathBuf);
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Launches a server for `file`, returning its port and shutdown handle.
+fn launch(file: &TempFile) -> (u16, Shutdown) {
+    let config = Config {
+        profile: Config::RELEASE_PROFILE,
+        port: 0,
+        log_level: rocket::config::LogLevel::Off,
+        cli_colors: false,
+        shutdown: config::Shutdown {
+            ctrlc: false,
+            #[cfg(unix)]
+            signals: HashSet::new(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let (tx, rx) = mpsc::sync_channel(1);
+    let rocket = rocket::custom(config)
+        .manage(file.0.clone())
+        .mount("/", routes![reader, file])
+        .attach(AdHoc::on_liftoff("Port", move |rocket| Box::pin(async move {
+            let _ = tx.send((rocket.config().port, rocket.shutdown()));
+        })));
+
+    std::thread::spawn(move || rocket::execute(rocket.launch()));
+    rx.recv().expect("launched")
+}
+
+/// Fetches `path` over a new HTTP/1.1 connection, returning the number of
+/// bytes in the response.
+fn fetch(port: u16, path: &str) -> usize {
+    let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).expect("connected");
+    let request = format!("GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n", path);
+    stream.write_all(request.as_bytes()).expect("wrote request");
+
+    let mut response = Vec::with_capacity(FILE_SIZE + 1024);
+    stream.read_to_end(&mut response).expect("read response");
+    assert!(response.len() > FILE_SIZE);
+    response.len()
+}
+
+/// Compares writing a `NamedFile` out through its body's `AsyncRead`
+/// implementation against the server's file fast path.
+pub fn bench_file_responses(c: &mut Criterion) {
+    let file = TempFile(std::env::temp_dir().join(format!("rocket-bench-{}", std::process::id())));
+    std::fs::write(&file.0, vec![b'a'; FILE_SIZE]).expect("wrote file");
+
+    let (port, shutdown) = launch(&file);
+    let mut group = c.benchmark_group("files");
+    group.throughput(Throughput::Bytes(FILE_SIZE as u64));
+    group.sample_size(20);
+    group.bench_function("NamedFile (reader)", |b| b.iter(|| fetch(port, "/reader")));
+    group.bench_function("NamedFile (fast path)", |b| b.iter(|| fetch(port, "/file")));
+    group.finish();
+    shutdown.notify();
+}
+
+criterion_group!(files, bench_file_responses);
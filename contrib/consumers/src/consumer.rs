@@ -0,0 +1,139 @@
+use std::fmt;
+
+use rocket::{Rocket, Orbit};
+
+use crate::ConsumerFairing;
+
+/// A source of messages, such as an AMQP queue, a Kafka topic, or a Redis
+/// stream, and the processing of each.
+///
+/// A `Consumer` is run by its [`ConsumerFairing`], created with
+/// [`Consumer::fairing()`]. At liftoff, the fairing calls
+/// [`start()`](Consumer::start()), then repeatedly awaits
+/// [`next()`](Consumer::next()) and [`process()`](Consumer::process())es each
+/// message until shutdown is requested or the source is closed. Finally, it
+/// calls [`drain()`](Consumer::drain()). See the [crate docs](crate) for the
+/// full lifecycle.
+///
+/// # Example
+///
+/// A consumer of jobs sent through a `tokio` channel, recording each in a
+/// managed `Log`:
+///
+/// ```rust
+/// use std::sync::{Arc, Mutex};
+///
+/// use rocket::{Rocket, Orbit};
+/// use rocket::tokio::sync::mpsc::Receiver;
+/// use rocket_consumers::Consumer;
+///
+/// #[derive(Clone, Default)]
+/// struct Log(Arc<Mutex<Vec<String>>>);
+///
+/// struct Jobs {
+///     receiver: Receiver<String>,
+///     log: Option<Log>,
+/// }
+///
+/// #[rocket::async_trait]
+/// impl Consumer for Jobs {
+///     type Message = String;
+///     type Error = &'static str;
+///
+///     async fn start(&mut self, rocket: &Rocket<Orbit>) -> Result<(), Self::Error> {
+///         self.log = Some(rocket.state::<Log>().cloned().ok_or("missing `Log`")?);
+///         Ok(())
+///     }
+///
+///     async fn next(&mut self) -> Option<Result<String, Self::Error>> {
+///         self.receiver.recv().await.map(Ok)
+///     }
+///
+///     async fn process(&mut self, job: String) -> Result<(), Self::Error> {
+///         let log = self.log.as_ref().ok_or("not started")?;
+///         log.0.lock().unwrap().push(job);
+///         Ok(())
+///     }
+/// }
+/// ```
+#[rocket::async_trait]
+pub trait Consumer: Send + Sized + 'static {
+    /// The type of message consumed.
+    type Message: Send;
+
+    /// The type of error returned while connecting to, receiving from, or
+    /// processing messages from the source.
+    type Error: fmt::Display + Send;
+
+    /// Prepares the consumer to receive messages. Called once, at liftoff,
+    /// with the launched `Rocket` instance.
+    ///
+    /// This is where a consumer should connect to its source and retrieve any
+    /// managed state, like a database pool, it needs to process messages. If
+    /// `start()` fails, the consumer is never run and its health check fails.
+    ///
+    /// The default implementation does nothing.
+    async fn start(&mut self, rocket: &Rocket<Orbit>) -> Result<(), Self::Error> {
+        let _ = rocket;
+        Ok(())
+    }
+
+    /// Receives the next message from the source. Returns `None` if the source
+    /// has been closed, after which the consumer is drained and stops.
+    ///
+    /// An error is treated as transient: it is logged, the consumer's health
+    /// check fails, and `next()` is called again after a backoff period.
+    ///
+    /// This method must be _cancel safe_: it is cancelled when shutdown is
+    /// requested, so a message must not be lost if the future is dropped
+    /// before it resolves.
+    async fn next(&mut self) -> Option<Result<Self::Message, Self::Error>>;
+
+    /// Processes, and if necessary acknowledges, `message`.
+    ///
+    /// Processing is never cancelled by a shutdown request: a message being
+    /// processed when shutdown is requested completes, subject to Rocket's
+    /// grace and mercy periods, before the consumer is drained. An error is
+    /// logged; the consumer then continues with the next message.
+    async fn process(&mut self, message: Self::Message) -> Result<(), Self::Error>;
+
+    /// Drains the consumer before it stops: called once, after the last
+    /// message has been processed, when shutdown is requested or the source
+    /// is closed.
+    ///
+    /// This is where a consumer should, for instance, return unprocessed
+    /// prefetched messages to the broker, commit offsets, and close its
+    /// connection. The default implementation does nothing.
+    async fn drain(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Returns a fairing, named `name`, that runs this consumer.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// # use rocket::tokio::sync::mpsc::{channel, Receiver};
+    /// use rocket_consumers::Consumer;
+    ///
+    /// # struct Jobs(Receiver<String>);
+    /// # #[rocket::async_trait]
+    /// # impl Consumer for Jobs {
+    /// #     type Message = String;
+    /// #     type Error = &'static str;
+    /// #     async fn next(&mut self) -> Option<Result<String, Self::Error>> {
+    /// #         self.0.recv().await.map(Ok)
+    /// #     }
+    /// #     async fn process(&mut self, _: String) -> Result<(), Self::Error> { Ok(()) }
+    /// # }
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     # let (_, receiver) = channel(16);
+    ///     rocket::build().attach(Jobs(receiver).fairing("jobs"))
+    /// }
+    /// ```
+    fn fairing(self, name: &'static str) -> ConsumerFairing<Self> {
+        ConsumerFairing::new(name, self)
+    }
+}
@@ -0,0 +1,192 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rocket::{Rocket, Build, Orbit, Shutdown};
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::health::{Health, Check};
+use rocket::tokio::{self, time::sleep};
+use rocket::yansi::Paint;
+
+use crate::Consumer;
+
+/// Fairing that runs a [`Consumer`] under Rocket's lifecycle.
+///
+/// Created with [`Consumer::fairing()`]. On ignition, the fairing registers a
+/// readiness [health check](rocket::health) named after the consumer. At
+/// liftoff, it starts the consumer and runs it as a
+/// [background task](rocket::Rocket::spawn_task()) until shutdown, when the
+/// consumer is drained. See the [crate docs](crate) for details.
+pub struct ConsumerFairing<C: Consumer> {
+    name: &'static str,
+    consumer: Mutex<Option<C>>,
+    status: Arc<Mutex<Status>>,
+    max_backoff: Duration,
+}
+
+/// The state of a running consumer, as reported by its health check.
+#[derive(Debug, Clone)]
+enum Status {
+    Pending,
+    Running,
+    Failing(String),
+    Failed(String),
+    Draining,
+    Stopped,
+}
+
+impl Status {
+    fn check(&self) -> rocket::health::Result {
+        match self {
+            Status::Running => Ok(()),
+            Status::Pending => Err("consumer has not started".into()),
+            Status::Failing(e) => Err(format!("consumer is failing: {}", e).into()),
+            Status::Failed(e) => Err(format!("consumer failed to start: {}", e).into()),
+            Status::Draining => Err("consumer is draining".into()),
+            Status::Stopped => Err("consumer has stopped".into()),
+        }
+    }
+}
+
+impl<C: Consumer> ConsumerFairing<C> {
+    /// The delay before the first retry after [`Consumer::next()`] fails.
+    const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+
+    /// The default maximum delay between retries.
+    const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+    pub(crate) fn new(name: &'static str, consumer: C) -> Self {
+        ConsumerFairing {
+            name,
+            consumer: Mutex::new(Some(consumer)),
+            status: Arc::new(Mutex::new(Status::Pending)),
+            max_backoff: Self::DEFAULT_MAX_BACKOFF,
+        }
+    }
+
+    /// Sets the maximum delay between retries after [`Consumer::next()`]
+    /// fails. The delay starts at 100ms and doubles after every consecutive
+    /// failure up to `max`, 30 seconds by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// # use rocket::tokio::sync::mpsc::{channel, Receiver};
+    /// use rocket_consumers::Consumer;
+    ///
+    /// # struct Jobs(Receiver<String>);
+    /// # #[rocket::async_trait]
+    /// # impl Consumer for Jobs {
+    /// #     type Message = String;
+    /// #     type Error = &'static str;
+    /// #     async fn next(&mut self) -> Option<Result<String, Self::Error>> {
+    /// #         self.0.recv().await.map(Ok)
+    /// #     }
+    /// #     async fn process(&mut self, _: String) -> Result<(), Self::Error> { Ok(()) }
+    /// # }
+    /// # let (_, receiver) = channel(16);
+    /// let fairing = Jobs(receiver).fairing("jobs").max_backoff(Duration::from_secs(5));
+    /// ```
+    pub fn max_backoff(mut self, max: Duration) -> Self {
+        self.max_backoff = max;
+        self
+    }
+
+    fn set_status(status: &Mutex<Status>, new: Status) {
+        *status.lock().expect("consumer status lock") = new;
+    }
+
+    /// Runs `consumer` until `shutdown` resolves or its source is closed, then
+    /// drains it.
+    async fn run(
+        name: &'static str,
+        mut consumer: C,
+        status: Arc<Mutex<Status>>,
+        max_backoff: Duration,
+        mut shutdown: Shutdown,
+    ) {
+        let mut backoff = Self::INITIAL_BACKOFF;
+        loop {
+            let next = tokio::select! {
+                biased;
+
+                _ = &mut shutdown => break,
+                next = consumer.next() => next,
+            };
+
+            match next {
+                Some(Ok(message)) => {
+                    backoff = Self::INITIAL_BACKOFF;
+                    Self::set_status(&status, Status::Running);
+                    if let Err(e) = consumer.process(message).await {
+                        warn!("Consumer {} failed to process a message.", Paint::white(name));
+                        warn_!("{}", e);
+                    }
+                }
+                Some(Err(e)) => {
+                    warn!("Consumer {} failed to receive a message.", Paint::white(name));
+                    warn_!("{}", e);
+                    info_!("Retrying in {}ms.", backoff.as_millis());
+                    Self::set_status(&status, Status::Failing(e.to_string()));
+                    tokio::select! {
+                        biased;
+
+                        _ = &mut shutdown => break,
+                        _ = sleep(backoff) => {},
+                    }
+
+                    backoff = std::cmp::min(backoff * 2, max_backoff);
+                }
+                None => {
+                    info!("Consumer {} source was closed.", Paint::white(name));
+                    break;
+                }
+            }
+        }
+
+        Self::set_status(&status, Status::Draining);
+        if let Err(e) = consumer.drain().await {
+            warn!("Consumer {} failed to drain.", Paint::white(name));
+            warn_!("{}", e);
+        }
+
+        Self::set_status(&status, Status::Stopped);
+    }
+}
+
+#[rocket::async_trait]
+impl<C: Consumer> Fairing for ConsumerFairing<C> {
+    fn info(&self) -> Info {
+        Info { name: "Consumer", kind: Kind::Ignite | Kind::Liftoff }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let status = self.status.clone();
+        let check = Check::readiness(self.name, move |_| {
+            let result = status.lock().expect("consumer status lock").check();
+            Box::pin(async move { result })
+        });
+
+        Ok(Health::register(rocket, check))
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        let mut consumer = match self.consumer.lock().expect("consumer lock").take() {
+            Some(consumer) => consumer,
+            None => return,
+        };
+
+        if let Err(e) = consumer.start(rocket).await {
+            error!("Consumer {} failed to start.", Paint::white(self.name));
+            error_!("{}", e);
+            Self::set_status(&self.status, Status::Failed(e.to_string()));
+            return;
+        }
+
+        info!("Consumer {} started.", Paint::white(self.name));
+        Self::set_status(&self.status, Status::Running);
+        let (status, shutdown) = (self.status.clone(), rocket.shutdown());
+        let task = Self::run(self.name, consumer, status, self.max_backoff, shutdown);
+        rocket.spawn_task(self.name, task);
+    }
+}
@@ -0,0 +1,99 @@
+//! Message queue consumers running under Rocket's lifecycle.
+//!
+//! This crate runs message consumers, for AMQP queues, Kafka topics, Redis
+//! streams, or any other source, alongside a Rocket application: consumers
+//! are started at liftoff with access to managed state, drained gracefully at
+//! shutdown, and reported in the application's
+//! [health checks](rocket::health). The crate is broker-agnostic: a source is
+//! integrated by implementing the [`Consumer`] trait atop its client library.
+//!
+//! # Usage
+//!
+//!   1. Depend on `rocket_consumers`:
+//!
+//!      ```toml
+//!      [dependencies.rocket_consumers]
+//!      version = "0.1.0-rc.2"
+//!      ```
+//!
+//!   2. Implement [`Consumer`] and attach the consumer's
+//!      [fairing](Consumer::fairing()):
+//!
+//!      ```rust
+//!      # #[macro_use] extern crate rocket;
+//!      use rocket::{Rocket, Orbit};
+//!      use rocket::tokio::sync::mpsc::{channel, Receiver};
+//!      use rocket_consumers::Consumer;
+//!
+//!      # #[derive(Clone)] struct Db;
+//!      # impl Db { async fn insert(&self, _: &str) -> Result<(), &'static str> { Ok(()) } }
+//!      struct Orders {
+//!          receiver: Receiver<String>,
+//!          db: Option<Db>,
+//!      }
+//!
+//!      #[rocket::async_trait]
+//!      impl Consumer for Orders {
+//!          type Message = String;
+//!          type Error = &'static str;
+//!
+//!          async fn start(&mut self, rocket: &Rocket<Orbit>) -> Result<(), Self::Error> {
+//!              self.db = Some(rocket.state::<Db>().cloned().ok_or("missing `Db`")?);
+//!              Ok(())
+//!          }
+//!
+//!          async fn next(&mut self) -> Option<Result<String, Self::Error>> {
+//!              self.receiver.recv().await.map(Ok)
+//!          }
+//!
+//!          async fn process(&mut self, order: String) -> Result<(), Self::Error> {
+//!              self.db.as_ref().ok_or("not started")?.insert(&order).await
+//!          }
+//!      }
+//!
+//!      #[launch]
+//!      fn rocket() -> _ {
+//!          # let (_, receiver) = channel(16);
+//!          let orders = Orders { receiver, db: None };
+//!          rocket::build()
+//!              .manage(Db)
+//!              .attach(orders.fairing("orders"))
+//!      }
+//!      ```
+//!
+//! # Lifecycle
+//!
+//! A consumer attached via its [`ConsumerFairing`] proceeds as follows:
+//!
+//!   1. **Ignition:** a readiness health check named after the consumer is
+//!      [registered](rocket::health::Health::register()). It is served if a
+//!      [`Health`](rocket::health::Health) fairing is attached.
+//!
+//!   2. **Liftoff:** [`Consumer::start()`] is called with the launched
+//!      `Rocket`. If it fails, the error is logged and the consumer never
+//!      runs. Otherwise, the consumer is spawned as a
+//!      [background task](rocket::Rocket::spawn_task()).
+//!
+//!   3. **Running:** [`Consumer::next()`] and [`Consumer::process()`] are
+//!      called in turn, one message at a time. Errors from `process()` are
+//!      logged. Errors from `next()` are logged and retried with exponential
+//!      backoff, [capped](ConsumerFairing::max_backoff()) at 30 seconds by
+//!      default.
+//!
+//!   4. **Draining:** once shutdown is requested, a pending `next()` is
+//!      cancelled, a message being processed is allowed to complete, and
+//!      [`Consumer::drain()`] is called. The same occurs if `next()` returns
+//!      `None`. Like all background tasks, draining is subject to Rocket's
+//!      grace and mercy periods.
+//!
+//! The health check passes only while the consumer is running and its last
+//! call to `next()` succeeded.
+
+#[macro_use]
+extern crate rocket;
+
+mod consumer;
+mod fairing;
+
+pub use self::consumer::Consumer;
+pub use self::fairing::ConsumerFairing;
@@ -0,0 +1,128 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rocket::{Rocket, Orbit};
+use rocket::health::Health;
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use rocket::tokio::sync::mpsc::{channel, Sender, Receiver};
+use rocket::tokio::time::sleep;
+use rocket_consumers::Consumer;
+
+#[derive(Clone, Default)]
+struct Log(Arc<Mutex<Vec<String>>>);
+
+impl Log {
+    fn entries(&self) -> Vec<String> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+struct Jobs {
+    receiver: Receiver<Result<String, &'static str>>,
+    log: Option<Log>,
+    fail_start: bool,
+}
+
+#[rocket::async_trait]
+impl Consumer for Jobs {
+    type Message = String;
+    type Error = &'static str;
+
+    async fn start(&mut self, rocket: &Rocket<Orbit>) -> Result<(), Self::Error> {
+        if self.fail_start {
+            return Err("connection refused");
+        }
+
+        self.log = Some(rocket.state::<Log>().cloned().ok_or("missing `Log`")?);
+        Ok(())
+    }
+
+    async fn next(&mut self) -> Option<Result<String, Self::Error>> {
+        self.receiver.recv().await
+    }
+
+    async fn process(&mut self, job: String) -> Result<(), Self::Error> {
+        sleep(Duration::from_millis(10)).await;
+        self.log.as_ref().unwrap().0.lock().unwrap().push(job);
+        Ok(())
+    }
+
+    async fn drain(&mut self) -> Result<(), Self::Error> {
+        self.log.as_ref().unwrap().0.lock().unwrap().push("drained".into());
+        Ok(())
+    }
+}
+
+async fn client(fail_start: bool) -> (Client, Sender<Result<String, &'static str>>, Log) {
+    let (sender, receiver) = channel(16);
+    let jobs = Jobs { receiver, log: None, fail_start };
+    let rocket = rocket::build()
+        .manage(Log::default())
+        .attach(Health::new())
+        .attach(jobs.fairing("jobs").max_backoff(Duration::from_millis(50)));
+
+    let client = Client::debug(rocket).await.unwrap();
+    let log = client.rocket().state::<Log>().cloned().unwrap();
+    (client, sender, log)
+}
+
+async fn readyz(client: &Client) -> (Status, String) {
+    let response = client.get("/readyz").dispatch().await;
+    (response.status(), response.into_string().await.unwrap())
+}
+
+#[rocket::async_test]
+async fn consumes_and_drains() {
+    let (client, sender, log) = client(false).await;
+    assert_eq!(readyz(&client).await.0, Status::Ok);
+
+    sender.send(Ok("a".into())).await.unwrap();
+    sender.send(Ok("b".into())).await.unwrap();
+    sleep(Duration::from_millis(100)).await;
+    assert_eq!(log.entries(), vec!["a", "b"]);
+
+    client.terminate().await;
+    assert_eq!(log.entries(), vec!["a", "b", "drained"]);
+}
+
+#[rocket::async_test]
+async fn drains_when_source_closes() {
+    let (client, sender, log) = client(false).await;
+    sender.send(Ok("a".into())).await.unwrap();
+    drop(sender);
+
+    sleep(Duration::from_millis(100)).await;
+    assert_eq!(log.entries(), vec!["a", "drained"]);
+
+    let (status, body) = readyz(&client).await;
+    assert_eq!(status, Status::ServiceUnavailable);
+    assert!(body.contains("[-]jobs failed: consumer has stopped"));
+}
+
+#[rocket::async_test]
+async fn receive_errors_fail_health_until_recovery() {
+    let (client, sender, log) = client(false).await;
+    sender.send(Err("broker unavailable")).await.unwrap();
+    sleep(Duration::from_millis(20)).await;
+
+    let (status, body) = readyz(&client).await;
+    assert_eq!(status, Status::ServiceUnavailable);
+    assert!(body.contains("[-]jobs failed: consumer is failing: broker unavailable"));
+
+    sender.send(Ok("a".into())).await.unwrap();
+    sleep(Duration::from_millis(200)).await;
+    assert_eq!(log.entries(), vec!["a"]);
+    assert_eq!(readyz(&client).await.0, Status::Ok);
+}
+
+#[rocket::async_test]
+async fn start_failure_is_reported() {
+    let (client, _sender, log) = client(true).await;
+    let (status, body) = readyz(&client).await;
+    assert_eq!(status, Status::ServiceUnavailable);
+    assert!(body.contains("[-]jobs failed: consumer failed to start: connection refused"));
+
+    client.terminate().await;
+    assert!(log.entries().is_empty());
+}
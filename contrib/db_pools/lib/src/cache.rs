@@ -0,0 +1,268 @@
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use deadpool_redis::redis::{AsyncCommands, RedisError};
+use deadpool_redis::{Pool, PoolError};
+use rocket::{warn_, Ignite, Phase, Rocket, Sentinel};
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::serde::{Serialize, DeserializeOwned};
+use rocket::http::Status;
+
+use crate::Database;
+
+/// A request guard providing a typed cache atop a Redis [`Database`].
+///
+/// For a database type `Db` wrapping a [`deadpool_redis::Pool`], a request
+/// guard of `Cache<Db>` provides methods to get and set values of any type
+/// that implements [`Serialize`] and [`DeserializeOwned`], stored in Redis as
+/// JSON, with an expiry. A `Cache` can also be retrieved outside of a request
+/// with [`Cache::fetch()`].
+///
+/// The guard succeeds if the database was initialized by the
+/// [`Initializer`](crate::Initializer) fairing; it does not itself retrieve a
+/// connection. Otherwise, it fails with status `InternalServerError`. A
+/// [`Sentinel`] guards this condition.
+///
+/// # Example
+///
+/// Cache the result of an expensive query for a minute:
+///
+/// ```rust
+/// # #[cfg(feature = "deadpool_redis")] mod _inner {
+/// # use rocket::get;
+/// use std::time::Duration;
+///
+/// use rocket::serde::{Serialize, Deserialize, json::Json};
+/// use rocket_db_pools::{deadpool_redis, Database, Cache};
+///
+/// #[derive(Database)]
+/// #[database("redis")]
+/// struct Redis(deadpool_redis::Pool);
+///
+/// #[derive(Serialize, Deserialize)]
+/// # #[serde(crate = "rocket::serde")]
+/// struct Task { id: i64, description: String }
+///
+/// # async fn all_tasks() -> Vec<Task> { vec![] }
+/// #[get("/tasks")]
+/// async fn tasks(cache: Cache<Redis>) -> Json<Vec<Task>> {
+///     let tasks = cache.get_or_set("tasks:all", Duration::from_secs(60), all_tasks());
+///     Json(tasks.await)
+/// }
+/// # }
+/// ```
+pub struct Cache<D: Database<Pool = Pool>>(Pool, PhantomData<fn() -> D>);
+
+/// An error that occurs while accessing a [`Cache`].
+#[derive(Debug)]
+pub enum CacheError {
+    /// A connection could not be retrieved from the pool.
+    Get(PoolError),
+
+    /// Redis failed to execute a command.
+    Redis(RedisError),
+
+    /// A value could not be serialized or deserialized.
+    Serde(serde_json::Error),
+}
+
+impl<D: Database<Pool = Pool>> Cache<D> {
+    /// Returns a `Cache` for the initialized database `D` in `rocket`, or
+    /// `None` if it has not been initialized. See [`Database::fetch()`] for
+    /// when this is the case.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "deadpool_redis")] mod _inner {
+    /// use rocket::{Rocket, Orbit};
+    /// use rocket_db_pools::{deadpool_redis, Database, Cache};
+    ///
+    /// #[derive(Database)]
+    /// #[database("redis")]
+    /// struct Redis(deadpool_redis::Pool);
+    ///
+    /// async fn invalidate(rocket: &Rocket<Orbit>) {
+    ///     if let Some(cache) = Cache::<Redis>::fetch(rocket) {
+    ///         let _ = cache.remove("tasks:all").await;
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn fetch<P: Phase>(rocket: &Rocket<P>) -> Option<Self> {
+        D::fetch(rocket).map(|db| Cache((**db).clone(), PhantomData))
+    }
+
+    /// Retrieves the value cached at `key`, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "deadpool_redis")] mod _inner {
+    /// # use rocket_db_pools::{deadpool_redis, Database, Cache};
+    /// # #[derive(Database)]
+    /// # #[database("redis")]
+    /// # struct Redis(deadpool_redis::Pool);
+    /// async fn visits(cache: &Cache<Redis>) -> usize {
+    ///     cache.get("visits").await.ok().flatten().unwrap_or(0)
+    /// }
+    /// # }
+    /// ```
+    pub async fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, CacheError> {
+        let mut conn = self.0.get().await?;
+        let bytes: Option<Vec<u8>> = conn.get(key).await?;
+        match bytes {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Caches `value` at `key` for `ttl`, rounded up to the nearest second.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "deadpool_redis")] mod _inner {
+    /// # use rocket_db_pools::{deadpool_redis, Database, Cache};
+    /// # #[derive(Database)]
+    /// # #[database("redis")]
+    /// # struct Redis(deadpool_redis::Pool);
+    /// use std::time::Duration;
+    ///
+    /// async fn remember(cache: &Cache<Redis>, name: &str) {
+    ///     let _ = cache.set("last_name", name, Duration::from_secs(3600)).await;
+    /// }
+    /// # }
+    /// ```
+    pub async fn set<T>(&self, key: &str, value: &T, ttl: Duration) -> Result<(), CacheError>
+        where T: Serialize + ?Sized
+    {
+        let bytes = serde_json::to_vec(value)?;
+        let secs = ttl.as_secs() + (ttl.subsec_nanos() > 0) as u64;
+        let mut conn = self.0.get().await?;
+        conn.set_ex::<_, _, ()>(key, bytes, secs.max(1) as usize).await?;
+        Ok(())
+    }
+
+    /// Removes the value cached at `key`, if any.
+    pub async fn remove(&self, key: &str) -> Result<(), CacheError> {
+        let mut conn = self.0.get().await?;
+        conn.del::<_, ()>(key).await?;
+        Ok(())
+    }
+
+    /// Returns the value cached at `key` if there is one. Otherwise, awaits
+    /// `fut` and caches its output at `key` for `ttl` before returning it.
+    ///
+    /// The cache is best-effort: if the cached value can't be retrieved or the
+    /// new value can't be cached, the error is logged and the output of `fut`
+    /// is returned regardless. To only cache successful results, use
+    /// [`Cache::try_get_or_set()`].
+    pub async fn get_or_set<T, F>(&self, key: &str, ttl: Duration, fut: F) -> T
+        where T: Serialize + DeserializeOwned, F: Future<Output = T>
+    {
+        let fut = async { Ok::<_, std::convert::Infallible>(fut.await) };
+        match self.try_get_or_set(key, ttl, fut).await {
+            Ok(value) => value,
+            Err(e) => match e {},
+        }
+    }
+
+    /// Like [`Cache::get_or_set()`], but for a fallible `fut`: an `Ok` output
+    /// is cached while an `Err` is returned without being cached.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "deadpool_redis")] mod _inner {
+    /// # use rocket::get;
+    /// use std::time::Duration;
+    ///
+    /// use rocket::serde::json::Json;
+    /// use rocket_db_pools::{deadpool_redis, Database, Cache};
+    ///
+    /// #[derive(Database)]
+    /// #[database("redis")]
+    /// struct Redis(deadpool_redis::Pool);
+    ///
+    /// # async fn count_users() -> std::io::Result<usize> { Ok(0) }
+    /// #[get("/users/count")]
+    /// async fn count(cache: Cache<Redis>) -> std::io::Result<Json<usize>> {
+    ///     let ttl = Duration::from_secs(30);
+    ///     cache.try_get_or_set("users:count", ttl, count_users()).await.map(Json)
+    /// }
+    /// # }
+    /// ```
+    pub async fn try_get_or_set<T, E, F>(&self, key: &str, ttl: Duration, fut: F) -> Result<T, E>
+        where T: Serialize + DeserializeOwned, F: Future<Output = Result<T, E>>
+    {
+        match self.get(key).await {
+            Ok(Some(value)) => return Ok(value),
+            Ok(None) => {},
+            Err(e) => warn_!("failed to read cache key `{}`: {}", key, e),
+        }
+
+        let value = fut.await?;
+        if let Err(e) = self.set(key, &value, ttl).await {
+            warn_!("failed to write cache key `{}`: {}", key, e);
+        }
+
+        Ok(value)
+    }
+}
+
+#[rocket::async_trait]
+impl<'r, D: Database<Pool = Pool>> FromRequest<'r> for Cache<D> {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match Cache::fetch(req.rocket()) {
+            Some(cache) => Outcome::Success(cache),
+            None => Outcome::Failure((Status::InternalServerError, ())),
+        }
+    }
+}
+
+impl<D: Database<Pool = Pool>> Sentinel for Cache<D> {
+    fn abort(rocket: &Rocket<Ignite>) -> bool {
+        D::fetch(rocket).is_none()
+    }
+}
+
+impl<D: Database<Pool = Pool>> Clone for Cache<D> {
+    fn clone(&self) -> Self {
+        Cache(self.0.clone(), PhantomData)
+    }
+}
+
+impl fmt::Display for CacheError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CacheError::Get(e) => write!(f, "failed to get cache connection: {}", e),
+            CacheError::Redis(e) => write!(f, "cache command failed: {}", e),
+            CacheError::Serde(e) => write!(f, "failed to (de)serialize cached value: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for CacheError {}
+
+impl From<PoolError> for CacheError {
+    fn from(e: PoolError) -> Self {
+        CacheError::Get(e)
+    }
+}
+
+impl From<RedisError> for CacheError {
+    fn from(e: RedisError) -> Self {
+        CacheError::Redis(e)
+    }
+}
+
+impl From<serde_json::Error> for CacheError {
+    fn from(e: serde_json::Error) -> Self {
+        CacheError::Serde(e)
+    }
+}
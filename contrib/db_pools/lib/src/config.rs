@@ -20,6 +20,8 @@ use rocket::serde::{Deserialize, Serialize};
 /// max_connections = 1024
 /// connect_timeout = 5
 /// idle_timeout = 120
+/// connect_at_launch = true
+/// shutdown_timeout = 2
 /// ```
 ///
 /// Alternatively, a custom provider can be used. For example, a custom `Figment`
@@ -36,12 +38,24 @@ use rocket::serde::{Deserialize, Serialize};
 ///             max_connections: 1024,
 ///             connect_timeout: 3,
 ///             idle_timeout: None,
+///             connect_at_launch: false,
+///             shutdown_timeout: 2,
 ///         }));
 ///
 ///     rocket::custom(figment)
 /// }
 /// ```
 ///
+/// The `url`, which often embeds a password, can instead be written as a
+/// [secret reference] to be resolved from a secret store at ignition:
+///
+/// ```toml
+/// [release.databases.db_name]
+/// url = { secret = "vault:secret/data/app#database_url" }
+/// ```
+///
+/// [secret reference]: rocket::config::SecretProvider
+///
 /// For general information on configuration in Rocket, see [`rocket::config`].
 /// For higher-level details on configuring a database, see the [crate-level
 /// docs](crate#configuration).
@@ -80,4 +94,36 @@ pub struct Config {
     ///
     /// _Default:_ `None`.
     pub idle_timeout: Option<u64>,
+    /// Whether to establish connections during ignition.
+    ///
+    /// When `true`, [`min_connections`](Config::min_connections) connections,
+    /// or one if unset, to the database and to each of its replicas are
+    /// established before the application launches. If a connection cannot be
+    /// established, or the value is not a boolean, ignition fails. This
+    /// ensures that misconfiguration, such as an invalid URL or invalid
+    /// credentials, is detected at launch instead of on the first request.
+    ///
+    /// _Default:_ `false`.
+    #[serde(default)]
+    pub connect_at_launch: bool,
+    /// Number of seconds to wait, on shutdown, for checked out connections to
+    /// be returned to the pool.
+    ///
+    /// On shutdown, the pool stops handing out connections and is closed. The
+    /// pool then waits for connections held by request guards, such as
+    /// [`Connection`](crate::Connection), to be returned before shutdown
+    /// proceeds. If the timeout, or Rocket's shutdown grace period, elapses
+    /// first, the remaining connections are dropped as they are returned. A
+    /// warning is logged at ignition if the timeout exceeds the grace period.
+    ///
+    /// _Default:_ `2`, matching the default
+    /// [grace period](rocket::config::Shutdown::grace).
+    #[serde(default = "Config::default_shutdown_timeout")]
+    pub shutdown_timeout: u64,
+}
+
+impl Config {
+    fn default_shutdown_timeout() -> u64 {
+        2
+    }
 }
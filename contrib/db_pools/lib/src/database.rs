@@ -1,15 +1,20 @@
 use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
+use std::time::Duration;
 
-use rocket::{error, info_, Build, Ignite, Phase, Rocket, Sentinel, Orbit};
+use rocket::{error, info_, warn, Build, Ignite, Phase, Rocket, Sentinel, Orbit};
 use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::health::{Health, Check};
 use rocket::request::{FromRequest, Outcome, Request};
 use rocket::http::Status;
+use rocket::tokio::time::timeout;
 
 use rocket::yansi::Paint;
 use rocket::figment::providers::Serialized;
 
-use crate::Pool;
+use crate::{Pool, PoolStats};
+use crate::replica::Replicas;
+use crate::metrics::{Checkout, Metrics};
 
 /// Derivable trait which ties a database [`Pool`] with a configuration name.
 ///
@@ -128,6 +133,43 @@ pub trait Database: From<Self::Pool> + DerefMut<Target = Self::Pool> + Send + Sy
         info_!("`{}` fairing must be attached prior to using this database.", fairing);
         None
     }
+
+    /// Returns a snapshot of the state of the database's primary pool if the
+    /// database is initialized in `rocket` and its pool reports its state via
+    /// [`Pool::stats()`].
+    ///
+    /// Unlike [`Pool::stats()`], the snapshot includes counters of the
+    /// connections retrieved by the [`Connection`] and [`Read`](crate::Read)
+    /// request guards, from the primary pool and its replicas alike: how many
+    /// were acquired, how many timed out, how many failed otherwise, and the
+    /// total time spent waiting for them. Connections retrieved directly via
+    /// [`Pool::get()`] aren't counted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "sqlx_sqlite")] mod _inner {
+    /// use rocket::{Rocket, Orbit};
+    /// use rocket_db_pools::{sqlx, Database};
+    ///
+    /// #[derive(Database)]
+    /// #[database("sqlite_db")]
+    /// struct Db(sqlx::SqlitePool);
+    ///
+    /// fn report(rocket: &Rocket<Orbit>) {
+    ///     if let Some(stats) = Db::stats(rocket) {
+    ///         println!("{} acquired, {} timed out", stats.acquired, stats.timeouts);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    fn stats<P: Phase>(rocket: &Rocket<P>) -> Option<PoolStats> {
+        let stats = <Self::Pool as Pool>::stats(Self::fetch(rocket)?)?;
+        match Metrics::<Self>::fetch(rocket) {
+            Some(metrics) => Some(metrics.record(stats)),
+            None => Some(stats),
+        }
+    }
 }
 
 /// A [`Fairing`] which initializes a [`Database`] and its connection pool.
@@ -149,6 +191,17 @@ pub trait Database: From<Self::Pool> + DerefMut<Target = Self::Pool> + Send + Sy
 ///   4. Stores the database instance in managed storage, retrievable via
 ///      [`Database::fetch()`].
 ///
+///   5. Calls [`Pool::init()`] for each URL in `replicas`, if any, and stores
+///      the resulting pools for use by the [`Read`](crate::Read) guard.
+///
+///   6. If [`connect_at_launch`](crate::Config::connect_at_launch) is set,
+///      establishes [`min_connections`](crate::Config::min_connections), or
+///      at least one, connections to every pool, failing ignition on error.
+///
+/// On shutdown, the fairing closes every pool, then waits up to
+/// [`shutdown_timeout`](crate::Config::shutdown_timeout) seconds for
+/// connections that are still held by request guards to be returned.
+///
 /// The name of the fairing itself is `Initializer<D>`, with `D` replaced with
 /// the type name `D` unless a name is explicitly provided via
 /// [`Self::with_name()`].
@@ -194,7 +247,7 @@ pub struct Initializer<D: Database>(Option<&'static str>, PhantomData<fn() -> D>
 /// }
 /// # }
 /// ```
-pub struct Connection<D: Database>(<D::Pool as Pool>::Connection);
+pub struct Connection<D: Database>(Checkout<<D::Pool as Pool>::Connection>);
 
 impl<D: Database> Initializer<D> {
     /// Returns a database initializer fairing for `D`.
@@ -220,7 +273,8 @@ impl<D: Database> Connection<D> {
     ///
     /// Note that `Connection<D>` derefs to the internal connection type, so
     /// using this method is likely unnecessary. See [deref](Connection#deref)
-    /// for examples.
+    /// for examples. The returned connection is no longer awaited on
+    /// [shutdown](crate::Config::shutdown_timeout).
     ///
     /// # Example
     ///
@@ -241,7 +295,7 @@ impl<D: Database> Connection<D> {
     /// # }
     /// ```
     pub fn into_inner(self) -> <D::Pool as Pool>::Connection {
-        self.0
+        self.0.into_inner()
     }
 }
 
@@ -262,20 +316,114 @@ impl<D: Database> Fairing for Initializer<D> {
         let figment = rocket.figment()
             .focus(&format!("databases.{}", D::NAME))
             .merge(Serialized::default("max_connections", workers * 4))
-            .merge(Serialized::default("connect_timeout", 5));
+            .merge(Serialized::default("connect_timeout", 5))
+            .merge(Serialized::default("shutdown_timeout", 2));
 
-        match <D::Pool>::init(&figment).await {
-            Ok(pool) => Ok(rocket.manage(D::from(pool))),
+        let grace = rocket.figment()
+            .extract_inner::<u32>("shutdown.grace")
+            .unwrap_or_else(|_| rocket::Config::default().shutdown.grace);
+
+        match figment.extract_inner::<u64>("shutdown_timeout") {
+            Ok(secs) if secs > u64::from(grace) => {
+                warn!("database `{}` shutdown timeout ({}s) exceeds the shutdown grace period ({}s)",
+                    D::NAME, secs, grace);
+                info_!("connections are awaited on shutdown only for the grace period");
+            }
+            _ => {}
+        }
+
+        let pool = match <D::Pool>::init(&figment).await {
+            Ok(pool) => pool,
             Err(e) => {
                 error!("failed to initialize database: {}", e);
-                Err(rocket)
+                return Err(rocket);
+            }
+        };
+
+        let replica_urls = match figment.extract_inner::<Vec<String>>("replicas") {
+            Ok(urls) => urls,
+            Err(e) if e.missing() => vec![],
+            Err(e) => {
+                error!("invalid database replica configuration: {}", e);
+                return Err(rocket);
+            }
+        };
+
+        let mut replicas = Vec::with_capacity(replica_urls.len());
+        for url in replica_urls {
+            match <D::Pool>::init(&figment.clone().merge(("url", &url))).await {
+                Ok(replica) => replicas.push(replica),
+                Err(e) => {
+                    error!("failed to initialize database replica `{}`: {}", url, e);
+                    return Err(rocket);
+                }
+            }
+        }
+
+        let connect_at_launch = match figment.extract_inner::<bool>("connect_at_launch") {
+            Ok(connect_at_launch) => connect_at_launch,
+            Err(e) if e.missing() => false,
+            Err(e) => {
+                error!("invalid database `connect_at_launch` configuration: {}", e);
+                return Err(rocket);
+            }
+        };
+
+        if connect_at_launch {
+            // Hold the connections so that each retrieval opens a new one.
+            let warm = figment.extract_inner::<u32>("min_connections").unwrap_or(1).max(1);
+            let mut connections = Vec::with_capacity(warm as usize);
+            for pool in std::iter::once(&pool).chain(&replicas) {
+                for _ in 0..warm {
+                    match pool.get().await {
+                        Ok(conn) => connections.push(conn),
+                        Err(e) => {
+                            error!("failed to connect to database `{}` at launch: {}", D::NAME, e);
+                            info_!("check the database's `url` and that the database is reachable");
+                            return Err(rocket);
+                        }
+                    }
+                }
+
+                connections.clear();
             }
         }
+
+        let check = Check::readiness(D::NAME, |rocket| Box::pin(async move {
+            let db = D::fetch(rocket).ok_or("database is not initialized")?;
+            db.get().await.map(drop).map_err(|e| e.to_string().into())
+        }));
+
+        let rocket = rocket.manage(D::from(pool))
+            .manage(Replicas::<D>::new(replicas))
+            .manage(Metrics::<D>::new());
+
+        Ok(Health::register(rocket, check))
     }
 
     async fn on_shutdown(&self, rocket: &Rocket<Orbit>) {
-        if let Some(db) = D::fetch(rocket) {
-            db.close().await;
+        let secs = rocket.figment()
+            .extract_inner::<u64>(&format!("databases.{}.shutdown_timeout", D::NAME))
+            .unwrap_or(2);
+
+        let drain = async {
+            if let Some(db) = D::fetch(rocket) {
+                db.close().await;
+            }
+
+            if let Some(replicas) = Replicas::<D>::fetch(rocket) {
+                for pool in replicas.pools() {
+                    pool.close().await;
+                }
+            }
+
+            if let Some(metrics) = Metrics::<D>::fetch(rocket) {
+                metrics.returned().await;
+            }
+        };
+
+        if timeout(Duration::from_secs(secs), drain).await.is_err() {
+            warn!("database `{}` connections were not returned within {}s", D::NAME, secs);
         }
     }
 }
@@ -285,12 +433,14 @@ impl<'r, D: Database> FromRequest<'r> for Connection<D> {
     type Error = Option<<D::Pool as Pool>::Error>;
 
     async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
-        match D::fetch(req.rocket()) {
-            Some(db) => match db.get().await {
-                Ok(conn) => Outcome::Success(Connection(conn)),
-                Err(e) => Outcome::Failure((Status::ServiceUnavailable, Some(e))),
-            },
-            None => Outcome::Failure((Status::InternalServerError, None)),
+        let (db, metrics) = match (D::fetch(req.rocket()), Metrics::<D>::fetch(req.rocket())) {
+            (Some(db), Some(metrics)) => (db, metrics),
+            _ => return Outcome::Failure((Status::InternalServerError, None)),
+        };
+
+        match metrics.get(db).await {
+            Ok(conn) => Outcome::Success(Connection(conn)),
+            Err(e) => Outcome::Failure((Status::ServiceUnavailable, Some(e))),
         }
     }
 }
@@ -98,8 +98,9 @@
 //!
 //! # Supported Drivers
 //!
-//! At present, this crate supports _three_ drivers: [`deadpool`], [`sqlx`], and
-//! [`mongodb`]. Each driver may support multiple databases. Drivers have a
+//! At present, this crate supports _four_ drivers: [`deadpool`], [`sqlx`],
+//! [`mongodb`], and [`diesel`]. Each driver may support multiple databases.
+//! Drivers have a
 //! varying degree of support for graceful shutdown, affected by the
 //! `Type::init()` fairing on Rocket shutdown.
 //!
@@ -142,6 +143,43 @@
 //!
 //! Graceful shutdown is not supported.
 //!
+//! ## `diesel` (v2)
+//!
+//! | Database | Feature           | [`Pool`] Type           | [`Connection`] Deref               |
+//! |----------|-------------------|-------------------------|------------------------------------|
+//! | Postgres | `diesel_postgres` | [`diesel::PgPool`]      | [`diesel::AsyncPgConnection`]      |
+//! | MySQL    | `diesel_mysql`    | [`diesel::MysqlPool`]   | [`diesel::AsyncMysqlConnection`]   |
+//!
+//! Pools are provided by [`diesel_async`] via `deadpool` (v0.9). Queries are
+//! executed asynchronously, without the need for a blocking thread. Import
+//! [`diesel::prelude`] to use `async` query methods:
+//!
+//! ```rust
+//! # #[cfg(feature = "diesel_postgres")] mod _inner {
+//! # use rocket::get;
+//! use rocket_db_pools::{Database, Connection};
+//! use rocket_db_pools::diesel::{self, PgPool, prelude::*};
+//!
+//! #[derive(Database)]
+//! #[database("diesel_postgres")]
+//! struct Db(PgPool);
+//!
+//! #[get("/")]
+//! async fn one(mut db: Connection<Db>) -> Option<String> {
+//!     diesel::select(1.into_sql::<diesel::sql_types::Integer>())
+//!         .get_result::<i32>(&mut *db)
+//!         .await
+//!         .ok()
+//!         .map(|n| n.to_string())
+//! }
+//! # }
+//! ```
+//!
+//! On shutdown, new connections are denied. Shutdown _does not_ wait for
+//! connections to be returned.
+//!
+//! [`diesel_async`]: https://docs.rs/diesel-async/0.3
+//!
 //! ## Enabling Additional Driver Features
 //!
 //! Only the minimal features for each driver crate are enabled by
@@ -176,6 +214,8 @@
 //! max_connections = 1024
 //! connect_timeout = 5
 //! idle_timeout = 120
+//! connect_at_launch = true
+//! shutdown_timeout = 2
 //! ```
 //!
 //! Or via environment variables:
@@ -186,9 +226,52 @@
 //!
 //! See [`Config`] for details on configuration parameters.
 //!
+//! ## Read Replicas
+//!
+//! A database may additionally be configured with an array of read `replicas`
+//! URLs. Each replica is initialized with the same configuration as the
+//! primary database save for the `url`:
+//!
+//! ```toml
+//! [default.databases.db_name]
+//! url = "postgres://primary.db/app"
+//! replicas = ["postgres://replica-1.db/app", "postgres://replica-2.db/app"]
+//! ```
+//!
+//! Use the [`Read`] request guard to retrieve a connection to a replica and
+//! the [`Write`] request guard, an alias of [`Connection`], to retrieve a
+//! connection to the primary.
+//!
 //! **Note:** `deadpool` drivers do not support and thus ignore the
 //! `min_connections` value.
 //!
+//! ## Caching
+//!
+//! With the `deadpool_redis` feature enabled, a Redis database can double as
+//! a cache via the [`Cache`] request guard, which stores any serializable
+//! value, as JSON, with an expiry:
+//!
+//! ```rust
+//! # #[cfg(feature = "deadpool_redis")] mod _inner {
+//! # use rocket::get;
+//! # async fn expensive_query() -> Vec<String> { vec![] }
+//! use std::time::Duration;
+//!
+//! use rocket::serde::json::Json;
+//! use rocket_db_pools::{deadpool_redis, Database, Cache};
+//!
+//! #[derive(Database)]
+//! #[database("redis")]
+//! struct Redis(deadpool_redis::Pool);
+//!
+//! #[get("/")]
+//! async fn index(cache: Cache<Redis>) -> Json<Vec<String>> {
+//!     let ttl = Duration::from_secs(60);
+//!     Json(cache.get_or_set("index", ttl, expensive_query()).await)
+//! }
+//! # }
+//! ```
+//!
 //! ## Driver Defaults
 //!
 //! Some drivers provide configuration defaults different from the underyling
@@ -235,14 +318,52 @@ pub use rocket;
 #[cfg(feature = "mongodb")] pub use mongodb;
 #[cfg(feature = "sqlx")] pub use sqlx;
 
+/// Re-export of `diesel` with prelude types overridden with `async` variants
+/// from `diesel_async`.
+///
+/// Use `rocket_db_pools::diesel::prelude::*` in place of `diesel::prelude::*`
+/// to bring `async` versions of diesel's query methods, like
+/// [`RunQueryDsl`](diesel_async::RunQueryDsl), into scope.
+#[cfg(feature = "diesel")]
+pub mod diesel {
+    pub use diesel::*;
+    pub use diesel_async::{AsyncConnection, RunQueryDsl, SaveChangesDsl, UpdateAndFetchResults};
+    pub use diesel_async::pooled_connection::{AsyncDieselConnectionManager, deadpool};
+
+    #[cfg(feature = "diesel_postgres")]
+    pub use diesel_async::AsyncPgConnection;
+
+    #[cfg(feature = "diesel_mysql")]
+    pub use diesel_async::AsyncMysqlConnection;
+
+    /// The prelude of `diesel` with `async` methods from `diesel_async`.
+    pub mod prelude {
+        pub use diesel::prelude::*;
+        pub use diesel_async::{AsyncConnection, RunQueryDsl, SaveChangesDsl};
+    }
+
+    /// Type alias for an `async` pool of Postgres connections for `diesel`.
+    #[cfg(feature = "diesel_postgres")]
+    pub type PgPool = deadpool::Pool<AsyncPgConnection>;
+
+    /// Type alias for an `async` pool of MySQL connections for `diesel`.
+    #[cfg(feature = "diesel_mysql")]
+    pub type MysqlPool = deadpool::Pool<AsyncMysqlConnection>;
+}
+
 mod database;
 mod error;
 mod pool;
 mod config;
+mod replica;
+mod metrics;
+#[cfg(feature = "deadpool_redis")] mod cache;
 
 pub use self::database::{Connection, Database, Initializer};
+pub use self::replica::{Read, Write};
 pub use self::error::Error;
-pub use self::pool::Pool;
+pub use self::pool::{Pool, PoolStats};
 pub use self::config::Config;
+#[cfg(feature = "deadpool_redis")] pub use self::cache::{Cache, CacheError};
 
 pub use rocket_db_pools_codegen::*;
@@ -0,0 +1,128 @@
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use rocket::{Phase, Rocket};
+use rocket::tokio::sync::Notify;
+
+use crate::{Database, Pool, PoolStats};
+
+/// Counters of the connections retrieved by the request guards of the
+/// database `D`, from its primary pool or its replicas, stored in managed
+/// state.
+pub(crate) struct Metrics<D: Database> {
+    acquired: AtomicU64,
+    timeouts: AtomicU64,
+    errors: AtomicU64,
+    wait_nanos: AtomicU64,
+    checked_out: Arc<CheckedOut>,
+    _db: PhantomData<fn() -> D>,
+}
+
+/// The number of connections held by request guards.
+#[derive(Default)]
+struct CheckedOut {
+    count: AtomicUsize,
+    returned: Notify,
+}
+
+/// A connection held by a request guard. Fields are dropped in declaration
+/// order, so the connection is returned to its pool before it's released.
+pub(crate) struct Checkout<C> {
+    conn: C,
+    _release: Release,
+}
+
+struct Release(Arc<CheckedOut>);
+
+impl<D: Database> Metrics<D> {
+    pub(crate) fn new() -> Self {
+        Metrics {
+            acquired: AtomicU64::new(0),
+            timeouts: AtomicU64::new(0),
+            errors: AtomicU64::new(0),
+            wait_nanos: AtomicU64::new(0),
+            checked_out: Arc::default(),
+            _db: PhantomData,
+        }
+    }
+
+    pub(crate) fn fetch<P: Phase>(rocket: &Rocket<P>) -> Option<&Self> {
+        rocket.state()
+    }
+
+    /// Retrieves a connection from `pool`, recording how long the retrieval
+    /// took and whether it succeeded, timed out, or failed otherwise.
+    pub(crate) async fn get(
+        &self,
+        pool: &D::Pool
+    ) -> Result<Checkout<<D::Pool as Pool>::Connection>, <D::Pool as Pool>::Error> {
+        let start = Instant::now();
+        let result = pool.get().await;
+        let counter = match &result {
+            Ok(_) => &self.acquired,
+            Err(e) if <D::Pool>::is_timeout(e) => &self.timeouts,
+            Err(_) => &self.errors,
+        };
+
+        let nanos = u64::try_from(start.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        counter.fetch_add(1, Ordering::Relaxed);
+        self.wait_nanos.fetch_add(nanos, Ordering::Relaxed);
+        result.map(|conn| {
+            self.checked_out.count.fetch_add(1, Ordering::AcqRel);
+            Checkout { conn, _release: Release(self.checked_out.clone()) }
+        })
+    }
+
+    /// Resolves once every connection held by a request guard is returned.
+    pub(crate) async fn returned(&self) {
+        loop {
+            let returned = self.checked_out.returned.notified();
+            if self.checked_out.count.load(Ordering::Acquire) == 0 {
+                return;
+            }
+
+            returned.await;
+        }
+    }
+
+    /// Returns `stats` with the counters filled in.
+    pub(crate) fn record(&self, stats: PoolStats) -> PoolStats {
+        PoolStats {
+            acquired: self.acquired.load(Ordering::Relaxed),
+            timeouts: self.timeouts.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            wait_time: Duration::from_nanos(self.wait_nanos.load(Ordering::Relaxed)),
+            ..stats
+        }
+    }
+}
+
+impl<C> Checkout<C> {
+    pub(crate) fn into_inner(self) -> C {
+        self.conn
+    }
+}
+
+impl<C> Deref for Checkout<C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        &self.conn
+    }
+}
+
+impl<C> DerefMut for Checkout<C> {
+    fn deref_mut(&mut self) -> &mut C {
+        &mut self.conn
+    }
+}
+
+impl Drop for Release {
+    fn drop(&mut self) {
+        self.0.count.fetch_sub(1, Ordering::AcqRel);
+        self.0.returned.notify_waiters();
+    }
+}
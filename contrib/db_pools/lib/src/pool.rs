@@ -116,7 +116,7 @@ use {std::time::Duration, crate::{Error, Config}};
 #[rocket::async_trait]
 pub trait Pool: Sized + Send + Sync + 'static {
     /// The connection type managed by this pool, returned by [`Self::get()`].
-    type Connection;
+    type Connection: Send;
 
     /// The error type returned by [`Self::init()`] and [`Self::get()`].
     type Error: std::error::Error;
@@ -150,12 +150,104 @@ pub trait Pool: Sized + Send + Sync + 'static {
     /// The returned future may either resolve when all connections are known to
     /// have closed or at any point prior. Details are implementation specific.
     async fn close(&self);
+
+    /// Returns a snapshot of the pool's current state, if the underlying
+    /// driver exposes it. The connection retrieval counters of the snapshot,
+    /// such as [`PoolStats::timeouts`], are zero; they are tracked by and
+    /// reported via [`Database::stats()`](crate::Database::stats()).
+    ///
+    /// The default implementation returns `None`. Implementations for
+    /// `deadpool` and `sqlx` pools provided by this crate return `Some`.
+    fn stats(&self) -> Option<PoolStats> {
+        None
+    }
+
+    /// Returns `true` if `error`, as returned by [`Self::get()`], indicates
+    /// that no connection became available before the pool's timeout elapsed,
+    /// as opposed to another failure such as an unreachable database.
+    ///
+    /// The default implementation returns `false`. Implementations for
+    /// `deadpool` and `sqlx` pools provided by this crate recognize their
+    /// timeout errors.
+    fn is_timeout(_error: &Self::Error) -> bool {
+        false
+    }
+}
+
+/// A snapshot of the state of a database connection pool.
+///
+/// Returned by [`Database::stats()`], which includes counters of the
+/// connections retrieved by the [`Connection`] and [`Read`] request guards,
+/// and by [`Pool::stats()`], which doesn't:
+///
+/// ```rust
+/// # #[cfg(feature = "sqlx_sqlite")] mod _inner {
+/// # use rocket::{Rocket, Orbit};
+/// use rocket_db_pools::{sqlx, Database};
+///
+/// #[derive(Database)]
+/// #[database("sqlite_logs")]
+/// struct Logs(sqlx::SqlitePool);
+///
+/// fn report(rocket: &Rocket<Orbit>) {
+///     if let Some(stats) = Logs::stats(rocket) {
+///         println!("{} of {} connections in use", stats.in_use, stats.connections);
+///         println!("{} timeouts, {:?} spent waiting", stats.timeouts, stats.wait_time);
+///     }
+/// }
+/// # }
+/// ```
+///
+/// [`Database::stats()`]: crate::Database::stats()
+/// [`Connection`]: crate::Connection
+/// [`Read`]: crate::Read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PoolStats {
+    /// The maximum number of connections in the pool, if known. `sqlx` pools
+    /// do not report this value.
+    pub max_size: Option<usize>,
+    /// The number of connections currently open, both idle and in use.
+    pub connections: usize,
+    /// The number of open connections that are idle.
+    pub idle: usize,
+    /// The number of open connections that are checked out.
+    pub in_use: usize,
+    /// The total number of connections successfully retrieved by request
+    /// guards.
+    pub acquired: u64,
+    /// The total number of retrievals by request guards that timed out
+    /// waiting for a connection.
+    pub timeouts: u64,
+    /// The total number of retrievals by request guards that failed for any
+    /// other reason, for instance because the database is unreachable.
+    pub errors: u64,
+    /// The cumulative time spent in retrievals by request guards, including
+    /// those that timed out or failed.
+    pub wait_time: Duration,
+}
+
+impl PoolStats {
+    /// A snapshot with the given gauges and zeroed counters.
+    #[cfg(any(feature = "deadpool", feature = "sqlx"))]
+    fn new(max_size: Option<usize>, connections: usize, idle: usize) -> Self {
+        PoolStats {
+            max_size,
+            connections,
+            idle,
+            in_use: connections.saturating_sub(idle),
+            acquired: 0,
+            timeouts: 0,
+            errors: 0,
+            wait_time: Duration::ZERO,
+        }
+    }
 }
 
 #[cfg(feature = "deadpool")]
 mod deadpool_postgres {
     use deadpool::{managed::{Manager, Pool, PoolError, Object, BuildError}, Runtime};
-    use super::{Duration, Error, Config, Figment};
+    use super::{Duration, Error, Config, Figment, PoolStats};
 
     pub trait DeadManager: Manager + Sized + Send + Sync + 'static {
         fn new(config: &Config) -> Result<Self, Self::Error>;
@@ -175,6 +267,15 @@ mod deadpool_postgres {
         }
     }
 
+    #[cfg(feature = "diesel")]
+    impl<C> DeadManager for diesel_async::pooled_connection::AsyncDieselConnectionManager<C>
+        where C: diesel_async::pooled_connection::PoolableConnection + 'static
+    {
+        fn new(config: &Config) -> Result<Self, Self::Error> {
+            Ok(Self::new(config.url.as_str()))
+        }
+    }
+
     #[rocket::async_trait]
     impl<M: DeadManager, C: From<Object<M>>> crate::Pool for Pool<M, C>
         where M::Type: Send, C: Send + Sync + 'static, M::Error: std::error::Error
@@ -204,13 +305,23 @@ mod deadpool_postgres {
         async fn close(&self) {
             <Pool<M, C>>::close(self)
         }
+
+        fn stats(&self) -> Option<PoolStats> {
+            let status = self.status();
+            let idle = status.available.max(0) as usize;
+            Some(PoolStats::new(Some(status.max_size), status.size, idle))
+        }
+
+        fn is_timeout(error: &Self::Error) -> bool {
+            matches!(error, Error::Get(PoolError::Timeout(_)))
+        }
     }
 }
 
 #[cfg(feature = "sqlx")]
 mod sqlx {
     use sqlx::ConnectOptions;
-    use super::{Duration, Error, Config, Figment};
+    use super::{Duration, Error, Config, Figment, PoolStats};
     use rocket::config::LogLevel;
 
     type Options<D> = <<D as sqlx::Database>::Connection as sqlx::Connection>::Options;
@@ -261,6 +372,14 @@ mod sqlx {
         async fn close(&self) {
             <sqlx::Pool<D>>::close(self).await;
         }
+
+        fn stats(&self) -> Option<PoolStats> {
+            Some(PoolStats::new(None, self.size() as usize, self.num_idle()))
+        }
+
+        fn is_timeout(error: &Self::Error) -> bool {
+            matches!(error, Error::Get(sqlx::Error::PoolTimedOut))
+        }
     }
 }
 
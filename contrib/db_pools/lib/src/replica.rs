@@ -0,0 +1,141 @@
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::{Ignite, Phase, Rocket, Sentinel};
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::http::Status;
+
+use crate::{Connection, Database, Pool};
+use crate::metrics::{Checkout, Metrics};
+
+/// A request guard which retrieves a connection to the primary [`Database`].
+///
+/// This is an alias of [`Connection<D>`], which always connects to the
+/// database's primary `url`. It exists to make the intent of a handler clear
+/// when a database is configured with [read replicas](Read).
+pub type Write<D> = Connection<D>;
+
+/// A request guard which retrieves a connection to a read replica of a
+/// [`Database`].
+///
+/// Read replicas are configured via the `replicas` key, an array of URLs, in
+/// the database's configuration. Every replica is initialized with the same
+/// configuration as the primary database, other than the `url`:
+///
+/// ```toml
+/// [default.databases.db_name]
+/// url = "postgres://primary.db/app"
+/// replicas = ["postgres://replica-1.db/app", "postgres://replica-2.db/app"]
+/// ```
+///
+/// Each `Read<D>` guard checks out a connection from the next replica in a
+/// round-robin fashion. If no replicas are configured, a connection to the
+/// primary database is retrieved instead. Otherwise, the guard succeeds and
+/// fails exactly as [`Connection<D>`] does, and like it, dereferences to the
+/// native database connection type.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "sqlx_postgres")] mod _inner {
+/// # use rocket::{get, post};
+/// use rocket_db_pools::{sqlx, Database, Read, Write};
+///
+/// #[derive(Database)]
+/// #[database("db_name")]
+/// struct Db(sqlx::PgPool);
+///
+/// #[get("/")]
+/// async fn list(db: Read<Db>) {
+///     // query a replica via `&mut *db`
+/// }
+///
+/// #[post("/")]
+/// async fn create(db: Write<Db>) {
+///     // modify the primary via `&mut *db`
+/// }
+/// # }
+/// ```
+pub struct Read<D: Database>(Checkout<<D::Pool as Pool>::Connection>);
+
+/// The replica pools of the database `D`, stored in managed state.
+pub(crate) struct Replicas<D: Database> {
+    pools: Vec<D::Pool>,
+    next: AtomicUsize,
+    _db: PhantomData<fn() -> D>,
+}
+
+impl<D: Database> Replicas<D> {
+    pub(crate) fn new(pools: Vec<D::Pool>) -> Self {
+        Replicas { pools, next: AtomicUsize::new(0), _db: PhantomData }
+    }
+
+    pub(crate) fn fetch<P: Phase>(rocket: &Rocket<P>) -> Option<&Self> {
+        rocket.state()
+    }
+
+    pub(crate) fn pools(&self) -> &[D::Pool] {
+        &self.pools
+    }
+
+    fn next(&self) -> Option<&D::Pool> {
+        match self.pools.len() {
+            0 => None,
+            n => Some(&self.pools[self.next.fetch_add(1, Ordering::Relaxed) % n]),
+        }
+    }
+}
+
+impl<D: Database> Read<D> {
+    /// Returns the internal connection value. See [`Connection::into_inner()`].
+    pub fn into_inner(self) -> <D::Pool as Pool>::Connection {
+        self.0.into_inner()
+    }
+}
+
+#[rocket::async_trait]
+impl<'r, D: Database> FromRequest<'r> for Read<D> {
+    type Error = Option<<D::Pool as Pool>::Error>;
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let replica = Replicas::<D>::fetch(req.rocket()).and_then(|r| r.next());
+        let pool = match replica {
+            Some(pool) => pool,
+            None => match D::fetch(req.rocket()) {
+                Some(db) => &**db,
+                None => return Outcome::Failure((Status::InternalServerError, None)),
+            }
+        };
+
+        let metrics = match Metrics::<D>::fetch(req.rocket()) {
+            Some(metrics) => metrics,
+            None => return Outcome::Failure((Status::InternalServerError, None)),
+        };
+
+        match metrics.get(pool).await {
+            Ok(conn) => Outcome::Success(Read(conn)),
+            Err(e) => Outcome::Failure((Status::ServiceUnavailable, Some(e))),
+        }
+    }
+}
+
+impl<D: Database> Sentinel for Read<D> {
+    fn abort(rocket: &Rocket<Ignite>) -> bool {
+        D::fetch(rocket).is_none()
+    }
+}
+
+impl<D: Database> Deref for Read<D> {
+    type Target = <D::Pool as Pool>::Connection;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<D: Database> DerefMut for Read<D> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "deadpool_redis")]
+
+use std::time::Duration;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::{get, routes, State};
+use rocket::figment::Figment;
+use rocket::local::blocking::Client;
+use rocket_db_pools::{deadpool_redis, Cache, Database};
+
+#[derive(Database)]
+#[database("redis")]
+struct Redis(deadpool_redis::Pool);
+
+#[get("/")]
+async fn count(cache: Cache<Redis>, calls: &State<AtomicUsize>) -> String {
+    let value = cache.get_or_set("count", Duration::from_secs(60), async {
+        calls.fetch_add(1, Ordering::SeqCst) + 1
+    });
+
+    value.await.to_string()
+}
+
+#[get("/fallible")]
+async fn fallible(cache: Cache<Redis>) -> Result<String, String> {
+    let ttl = Duration::from_secs(60);
+    cache.try_get_or_set::<String, _, _>("fallible", ttl, async { Err("failed".into()) }).await
+}
+
+fn client() -> Client {
+    // Nothing listens on port 1: every cache operation fails.
+    let figment = Figment::from(rocket::Config::debug_default())
+        .merge(("databases.redis.url", "redis://127.0.0.1:1"))
+        .merge(("databases.redis.connect_timeout", 1));
+
+    let rocket = rocket::custom(figment)
+        .attach(Redis::init())
+        .manage(AtomicUsize::new(0))
+        .mount("/", routes![count, fallible]);
+
+    Client::debug(rocket).unwrap()
+}
+
+#[test]
+fn cache_falls_back_when_unavailable() {
+    let client = client();
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "1");
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "2");
+}
+
+#[test]
+fn cache_propagates_errors() {
+    let client = client();
+    assert_eq!(client.get("/fallible").dispatch().into_string().unwrap(), "failed");
+}
+
+#[test]
+fn cache_is_unavailable_without_database() {
+    let rocket = rocket::build().mount("/", routes![count]);
+    let error = Client::debug(rocket).unwrap_err();
+    assert!(matches!(error.kind(), rocket::error::ErrorKind::SentinelAborts(_)));
+}
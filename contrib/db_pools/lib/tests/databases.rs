@@ -65,3 +65,263 @@ check_types_match!(
     mongodb::Client,
     mongodb::Client,
 );
+
+check_types_match!(
+    "diesel_postgres",
+    diesel_postgres,
+    rocket_db_pools::diesel::PgPool,
+    rocket_db_pools::diesel::deadpool::Object<rocket_db_pools::diesel::AsyncPgConnection>,
+);
+
+check_types_match!(
+    "diesel_mysql",
+    diesel_mysql,
+    rocket_db_pools::diesel::MysqlPool,
+    rocket_db_pools::diesel::deadpool::Object<rocket_db_pools::diesel::AsyncMysqlConnection>,
+);
+
+#[cfg(feature = "sqlx_sqlite")]
+mod replica_test {
+    use rocket::figment::Figment;
+    use rocket::local::asynchronous::Client;
+    use rocket_db_pools::{sqlx, Database, Read, Write};
+
+    #[derive(Database)]
+    #[database("replicated")]
+    struct Db(sqlx::SqlitePool);
+
+    #[rocket::post("/")]
+    async fn write(mut db: Write<Db>) {
+        sqlx::query("CREATE TABLE t (x INTEGER)").execute(&mut *db).await.unwrap();
+    }
+
+    #[rocket::get("/")]
+    async fn read(mut db: Read<Db>) -> String {
+        let tables: i64 = sqlx::query_scalar("SELECT count(*) FROM sqlite_master")
+            .fetch_one(&mut *db)
+            .await
+            .unwrap();
+
+        tables.to_string()
+    }
+
+    async fn client(replicas: &[&str]) -> Client {
+        // Every in-memory connection is a distinct database; limit each pool
+        // to one connection to keep state across requests.
+        let figment = Figment::from(rocket::Config::debug_default())
+            .merge(("databases.replicated.url", "sqlite::memory:"))
+            .merge(("databases.replicated.max_connections", 1))
+            .merge(("databases.replicated.replicas", replicas));
+
+        let rocket = rocket::custom(figment)
+            .attach(Db::init())
+            .mount("/", rocket::routes![read, write]);
+
+        Client::untracked(rocket).await.unwrap()
+    }
+
+    #[rocket::async_test]
+    async fn read_uses_replicas() {
+        let client = client(&["sqlite::memory:"]).await;
+        client.post("/").dispatch().await;
+        let tables = client.get("/").dispatch().await.into_string().await;
+        assert_eq!(tables.unwrap(), "0");
+    }
+
+    #[rocket::async_test]
+    async fn read_falls_back_to_primary() {
+        let client = client(&[]).await;
+        client.post("/").dispatch().await;
+        let tables = client.get("/").dispatch().await.into_string().await;
+        assert_eq!(tables.unwrap(), "1");
+    }
+}
+
+#[cfg(feature = "sqlx_sqlite")]
+mod stats_test {
+    use std::time::Duration;
+
+    use rocket::figment::Figment;
+    use rocket::http::Status;
+    use rocket::local::asynchronous::Client;
+    use rocket_db_pools::{sqlx, Database, Connection};
+
+    #[derive(Database)]
+    #[database("counted")]
+    struct Db(sqlx::SqlitePool);
+
+    #[rocket::get("/")]
+    async fn index(_db: Connection<Db>) { }
+
+    #[rocket::async_test]
+    async fn guards_record_retrievals() {
+        let figment = Figment::from(rocket::Config::debug_default())
+            .merge(("databases.counted.url", "sqlite::memory:"))
+            .merge(("databases.counted.max_connections", 1))
+            .merge(("databases.counted.connect_timeout", 1));
+
+        let rocket = rocket::custom(figment)
+            .attach(Db::init())
+            .mount("/", rocket::routes![index]);
+
+        let client = Client::untracked(rocket).await.unwrap();
+        let stats = Db::stats(client.rocket()).unwrap();
+        assert_eq!((stats.acquired, stats.timeouts, stats.errors), (0, 0, 0));
+
+        assert_eq!(client.get("/").dispatch().await.status(), Status::Ok);
+        let stats = Db::stats(client.rocket()).unwrap();
+        assert_eq!((stats.acquired, stats.timeouts, stats.errors), (1, 0, 0));
+
+        // The only connection is held, so the guard times out.
+        let held = Db::fetch(client.rocket()).unwrap().acquire().await.unwrap();
+        let response = client.get("/").dispatch().await;
+        assert_eq!(response.status(), Status::ServiceUnavailable);
+        drop(held);
+
+        let stats = Db::stats(client.rocket()).unwrap();
+        assert_eq!((stats.acquired, stats.timeouts, stats.errors), (1, 1, 0));
+        assert!(stats.wait_time >= Duration::from_secs(1));
+
+        // Direct retrievals aren't counted; neither is `Pool::stats()`.
+        let db = Db::fetch(client.rocket()).unwrap();
+        let pool_stats = rocket_db_pools::Pool::stats(&**db).unwrap();
+        assert_eq!((pool_stats.acquired, pool_stats.timeouts), (0, 0));
+    }
+}
+
+#[cfg(feature = "deadpool_postgres")]
+mod connect_at_launch_test {
+    use rocket::figment::Figment;
+    use rocket_db_pools::{deadpool_postgres, Database};
+
+    #[derive(Database)]
+    #[database("unreachable")]
+    struct Db(deadpool_postgres::Pool);
+
+    fn rocket(connect_at_launch: bool) -> rocket::Rocket<rocket::Build> {
+        let figment = Figment::from(rocket::Config::debug_default())
+            .merge(("databases.unreachable.url", "postgres://user@127.0.0.1:1/db"))
+            .merge(("databases.unreachable.connect_timeout", 1))
+            .merge(("databases.unreachable.connect_at_launch", connect_at_launch));
+
+        rocket::custom(figment).attach(Db::init())
+    }
+
+    #[rocket::async_test]
+    async fn lazy_connect_ignites() {
+        assert!(rocket(false).ignite().await.is_ok());
+    }
+
+    #[rocket::async_test]
+    async fn connect_at_launch_fails_ignition() {
+        use rocket::error::ErrorKind::FailedFairings;
+
+        let error = rocket(true).ignite().await.unwrap_err();
+        assert!(matches!(error.kind(), FailedFairings(f) if f.len() == 1));
+    }
+}
+
+#[cfg(feature = "sqlx_sqlite")]
+mod warm_up_test {
+    use rocket::figment::Figment;
+    use rocket_db_pools::{sqlx, Database, Pool};
+
+    #[derive(Database)]
+    #[database("warm")]
+    struct Db(sqlx::SqlitePool);
+
+    fn rocket(connect_at_launch: impl rocket::serde::Serialize) -> rocket::Rocket<rocket::Build> {
+        let figment = Figment::from(rocket::Config::debug_default())
+            .merge(("databases.warm.url", "sqlite::memory:"))
+            .merge(("databases.warm.min_connections", 3))
+            .merge(("databases.warm.connect_at_launch", connect_at_launch));
+
+        rocket::custom(figment).attach(Db::init())
+    }
+
+    #[rocket::async_test]
+    async fn connect_at_launch_opens_min_connections() {
+        let rocket = rocket(true).ignite().await.unwrap();
+        let stats = Db::fetch(&rocket).unwrap().stats().unwrap();
+        assert!(stats.connections >= 3);
+    }
+
+    #[rocket::async_test]
+    async fn invalid_connect_at_launch_fails_ignition() {
+        use rocket::error::ErrorKind::FailedFairings;
+
+        let error = rocket("yes").ignite().await.unwrap_err();
+        assert!(matches!(error.kind(), FailedFairings(f) if f.len() == 1));
+    }
+}
+
+#[cfg(feature = "sqlx_sqlite")]
+mod shutdown_test {
+    use std::time::{Duration, Instant};
+
+    use rocket::figment::Figment;
+    use rocket::local::asynchronous::Client;
+    use rocket::request::FromRequest;
+    use rocket_db_pools::{sqlx, Connection, Database};
+
+    #[derive(Database)]
+    #[database("draining")]
+    struct Db(sqlx::SqlitePool);
+
+    async fn client(shutdown_timeout: u64) -> Client {
+        let figment = Figment::from(rocket::Config::debug_default())
+            .merge(("databases.draining.url", "sqlite::memory:"))
+            .merge(("databases.draining.shutdown_timeout", shutdown_timeout));
+
+        Client::untracked(rocket::custom(figment).attach(Db::init())).await.unwrap()
+    }
+
+    #[rocket::async_test]
+    async fn shutdown_waits_for_connections() {
+        let client = client(5).await;
+        let db = Db::fetch(client.rocket()).unwrap();
+        let pool = (**db).clone();
+        let conn = db.acquire().await.unwrap();
+        rocket::tokio::spawn(async move {
+            rocket::tokio::time::sleep(Duration::from_millis(250)).await;
+            drop(conn);
+        });
+
+        let start = Instant::now();
+        client.terminate().await;
+        assert!(start.elapsed() >= Duration::from_millis(250));
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(pool.is_closed());
+        assert_eq!(pool.size(), 0);
+    }
+
+    #[rocket::async_test]
+    async fn shutdown_waits_for_guards() {
+        let client = client(5).await;
+        let request = client.get("/");
+        let conn = Connection::<Db>::from_request(&request).await.unwrap();
+        rocket::tokio::spawn(async move {
+            rocket::tokio::time::sleep(Duration::from_millis(250)).await;
+            drop(conn);
+        });
+
+        let start = Instant::now();
+        client.terminate().await;
+        assert!(start.elapsed() >= Duration::from_millis(250));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[rocket::async_test]
+    async fn shutdown_times_out() {
+        let client = client(1).await;
+        let db = Db::fetch(client.rocket()).unwrap();
+        let pool = (**db).clone();
+        let conn = db.acquire().await.unwrap();
+
+        let start = Instant::now();
+        client.terminate().await;
+        assert!(start.elapsed() >= Duration::from_secs(1));
+        assert!(pool.is_closed());
+        drop(conn);
+    }
+}
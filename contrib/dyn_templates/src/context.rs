@@ -69,6 +69,7 @@ impl Context {
             return None;
         }
 
+        engines.prepare_fragments();
         for (name, engine_ext) in engines.templates() {
             if !templates.contains_key(name) {
                 let data_type = Path::new(name).extension()
@@ -88,24 +89,33 @@ impl Context {
 #[cfg(not(debug_assertions))]
 mod manager {
     use std::ops::Deref;
-    use crate::Context;
+    use std::sync::RwLock;
+
+    use super::{Callback, Context};
 
     /// Wraps a Context. With `cfg(debug_assertions)` active, this structure
     /// additionally provides a method to reload the context at runtime.
-    pub(crate) struct ContextManager(Context);
+    pub(crate) struct ContextManager(RwLock<Context>);
 
     impl ContextManager {
         pub fn new(ctxt: Context) -> ContextManager {
-            ContextManager(ctxt)
+            ContextManager(RwLock::new(ctxt))
         }
 
-        pub fn context<'a>(&'a self) -> impl Deref<Target=Context> + 'a {
-            &self.0
+        pub fn context(&self) -> impl Deref<Target=Context> + '_ {
+            self.0.read().unwrap()
         }
 
         pub fn is_reloading(&self) -> bool {
             false
         }
+
+        /// Reinitializes all templates from disk and runs the user's
+        /// customization callback again.
+        pub fn reload(&self, callback: &Callback) {
+            let root = self.context().root.clone();
+            super::reload(&root, callback, &mut *self.0.write().unwrap());
+        }
     }
 }
 
@@ -171,15 +181,27 @@ mod manager {
 
             if let Some(true) = templates_changes {
                 info_!("Change detected: reloading templates.");
-                let root = self.context().root.clone();
-                if let Some(new_ctxt) = Context::initialize(&root, &callback) {
-                    *self.context_mut() = new_ctxt;
-                } else {
-                    warn_!("An error occurred while reloading templates.");
-                    warn_!("Existing templates will remain active.");
-                };
+                self.reload(callback);
             }
         }
+
+        /// Reinitializes all templates from disk and runs the user's
+        /// customization callback again.
+        pub fn reload(&self, callback: &Callback) {
+            let root = self.context().root.clone();
+            super::reload(&root, callback, &mut *self.context_mut());
+        }
+    }
+}
+
+/// Replaces `ctxt` with templates reinitialized from `root`, leaving `ctxt` as
+/// is if reinitialization fails.
+fn reload(root: &Path, callback: &Callback, ctxt: &mut Context) {
+    if let Some(new_ctxt) = Context::initialize(root, callback) {
+        *ctxt = new_ctxt;
+    } else {
+        warn_!("An error occurred while reloading templates.");
+        warn_!("Existing templates will remain active.");
     }
 }
 
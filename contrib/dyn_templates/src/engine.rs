@@ -7,12 +7,29 @@ use crate::TemplateInfo;
 
 #[cfg(feature = "tera")] use crate::tera::Tera;
 #[cfg(feature = "handlebars")] use crate::handlebars::Handlebars;
+#[cfg(feature = "minijinja")] use crate::minijinja::Environment;
 
 pub(crate) trait Engine: Send + Sync + Sized + 'static {
     const EXT: &'static str;
 
     fn init<'a>(templates: impl Iterator<Item = (&'a str, &'a Path)>) -> Option<Self>;
     fn render<C: Serialize>(&self, name: &str, context: C) -> Option<String>;
+    fn render_fragment<C: Serialize>(&self, name: &str, fragment: &str, context: C)
+        -> Option<String>;
+
+    /// Prepares the fragments of all registered templates for rendering. Called
+    /// once all templates, including customizations, have been registered.
+    fn prepare_fragments(&mut self) {}
+}
+
+/// Separates a template name from a fragment name in the names of prepared
+/// fragments registered with an engine.
+pub(crate) const FRAGMENT_SEPARATOR: char = '#';
+
+/// Returns the name under which fragment `fragment` of template `name` is
+/// registered with an engine that requires preparing fragments.
+pub(crate) fn fragment_name(name: &str, fragment: &str) -> String {
+    format!("{}{}{}", name, FRAGMENT_SEPARATOR, fragment)
 }
 
 /// A structure exposing access to templating engines.
@@ -64,12 +81,19 @@ pub struct Engines {
     /// `rocket_dyn_templates::handlebars` to avoid version mismatches.
     #[cfg(feature = "handlebars")]
     pub handlebars: Handlebars<'static>,
+    /// The MiniJinja templating engine's `Environment`. This field is only
+    /// available when the `minijinja` feature is enabled. When calling methods
+    /// on the `Environment` instance, ensure you use types imported from
+    /// `rocket_dyn_templates::minijinja` to avoid version mismatches.
+    #[cfg(feature = "minijinja")]
+    pub minijinja: Environment<'static>,
 }
 
 impl Engines {
     pub(crate) const ENABLED_EXTENSIONS: &'static [&'static str] = &[
         #[cfg(feature = "tera")] Tera::EXT,
         #[cfg(feature = "handlebars")] Handlebars::EXT,
+        #[cfg(feature = "minijinja")] Environment::EXT,
     ];
 
     pub(crate) fn init(templates: &HashMap<String, TemplateInfo>) -> Option<Engines> {
@@ -93,24 +117,54 @@ impl Engines {
                 Some(hb) => hb,
                 None => return None
             },
+            #[cfg(feature = "minijinja")]
+            minijinja: match inner::<Environment<'static>>(templates) {
+                Some(env) => env,
+                None => return None
+            },
         })
     }
 
+    pub(crate) fn prepare_fragments(&mut self) {
+        #[cfg(feature = "tera")] self.tera.prepare_fragments();
+        #[cfg(feature = "handlebars")] self.handlebars.prepare_fragments();
+        #[cfg(feature = "minijinja")] self.minijinja.prepare_fragments();
+    }
+
     pub(crate) fn render<C: Serialize>(
         &self,
         name: &str,
+        fragment: Option<&str>,
         info: &TemplateInfo,
         context: C
     ) -> Option<String> {
+        fn inner<E: Engine, C: Serialize>(
+            engine: &E,
+            name: &str,
+            fragment: Option<&str>,
+            context: C
+        ) -> Option<String> {
+            match fragment {
+                Some(fragment) => engine.render_fragment(name, fragment, context),
+                None => engine.render(name, context),
+            }
+        }
+
         #[cfg(feature = "tera")] {
             if info.engine_ext == Tera::EXT {
-                return Engine::render(&self.tera, name, context);
+                return inner(&self.tera, name, fragment, context);
             }
         }
 
         #[cfg(feature = "handlebars")] {
             if info.engine_ext == Handlebars::EXT {
-                return Engine::render(&self.handlebars, name, context);
+                return inner(&self.handlebars, name, fragment, context);
+            }
+        }
+
+        #[cfg(feature = "minijinja")] {
+            if info.engine_ext == Environment::EXT {
+                return inner(&self.minijinja, name, fragment, context);
             }
         }
 
@@ -119,24 +173,20 @@ impl Engines {
 
     /// Returns iterator over template (name, engine_extension).
     pub(crate) fn templates(&self) -> impl Iterator<Item = (&str, &'static str)> {
-        #[cfg(all(feature = "tera", feature = "handlebars"))] {
-            self.tera.get_template_names()
-                .map(|name| (name, Tera::EXT))
-                .chain(self.handlebars.get_templates().keys()
-                    .map(|name| (name.as_str(), Handlebars::EXT)))
-        }
+        let templates = std::iter::empty();
 
-        #[cfg(all(feature = "tera", not(feature = "handlebars")))] {
-            self.tera.get_template_names().map(|name| (name, Tera::EXT))
-        }
+        #[cfg(feature = "tera")]
+        let templates = templates.chain(self.tera.get_template_names()
+            .map(|name| (name, Tera::EXT)));
 
-        #[cfg(all(feature = "handlebars", not(feature = "tera")))] {
-            self.handlebars.get_templates().keys()
-                .map(|name| (name.as_str(), Handlebars::EXT))
-        }
+        #[cfg(feature = "handlebars")]
+        let templates = templates.chain(self.handlebars.get_templates().keys()
+            .map(|name| (name.as_str(), Handlebars::EXT)));
 
-        #[cfg(not(any(feature = "tera", feature = "handlebars")))] {
-            None.into_iter()
-        }
+        #[cfg(feature = "minijinja")]
+        let templates = templates.chain(self.minijinja.templates()
+            .map(|(name, _)| (name, Environment::EXT)));
+
+        templates.filter(|(name, _)| !name.contains(FRAGMENT_SEPARATOR))
     }
 }
@@ -3,11 +3,13 @@ use crate::context::{Callback, ContextManager};
 
 use rocket::{Rocket, Build, Orbit};
 use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::health::{Health, Check};
 
 /// The TemplateFairing initializes the template system on attach, running
 /// custom_callback after templates have been loaded. In debug mode, the fairing
 /// checks for modifications to templates before every request and reloads them
-/// if necessary.
+/// if necessary. In any mode, templates are reloaded when Rocket's
+/// configuration is.
 pub struct TemplateFairing {
     /// The user-provided customization callback, allowing the use of
     /// functionality specific to individual template engines. In debug mode,
@@ -18,7 +20,7 @@ pub struct TemplateFairing {
 #[rocket::async_trait]
 impl Fairing for TemplateFairing {
     fn info(&self) -> Info {
-        let kind = Kind::Ignite | Kind::Liftoff;
+        let kind = Kind::Ignite | Kind::Liftoff | Kind::ConfigReload;
         #[cfg(debug_assertions)] let kind = kind | Kind::Request;
 
         Info { kind, name: "Templating" }
@@ -46,7 +48,12 @@ impl Fairing for TemplateFairing {
         };
 
         if let Some(ctxt) = Context::initialize(&path, &self.callback) {
-            Ok(rocket.manage(ContextManager::new(ctxt)))
+            let check = Check::readiness("templates", |rocket| Box::pin(async move {
+                rocket.state::<ContextManager>().map(drop).ok_or_else(|| "not initialized".into())
+            }));
+
+            let rocket = rocket.manage(ContextManager::new(ctxt));
+            Ok(Health::register(rocket, check))
         } else {
             error_!("Template initialization failed. Aborting launch.");
             Err(rocket)
@@ -72,4 +79,12 @@ impl Fairing for TemplateFairing {
         cm.reload_if_needed(&self.callback);
     }
 
+    async fn on_config_reload(&self, rocket: &Rocket<Orbit>) {
+        let cm = rocket.state::<ContextManager>()
+            .expect("Template ContextManager registered in on_ignite");
+
+        info_!("Configuration reloaded: reloading templates.");
+        cm.reload(&self.callback);
+    }
+
 }
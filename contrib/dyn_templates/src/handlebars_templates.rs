@@ -2,8 +2,9 @@ use std::path::Path;
 
 use rocket::serde::Serialize;
 
-use crate::engine::Engine;
+use crate::engine::{Engine, fragment_name};
 pub use crate::handlebars::Handlebars;
+use crate::handlebars::{JsonValue, template::{Template, TemplateElement, Parameter}};
 
 impl Engine for Handlebars<'static> {
     const EXT: &'static str = "hbs";
@@ -33,4 +34,63 @@ impl Engine for Handlebars<'static> {
             .map_err(|e| error_!("Handlebars: {}", e))
             .ok()
     }
+
+    fn render_fragment<C: Serialize>(&self, name: &str, partial: &str, context: C)
+        -> Option<String>
+    {
+        if self.get_template(name).is_none() {
+            error_!("Handlebars template '{}' does not exist.", name);
+            return None;
+        }
+
+        let fragment = fragment_name(name, partial);
+        if self.get_template(&fragment).is_none() {
+            error_!("Handlebars template '{}' has no inline partial '{}'.", name, partial);
+            return None;
+        }
+
+        Engine::render(self, &fragment, context)
+    }
+
+    /// Registers the body of every inline partial (`{{#*inline "name"}}`) of
+    /// every template as a template of its own.
+    fn prepare_fragments(&mut self) {
+        fn inline_partials<'t>(template: &'t Template, partials: &mut Vec<(&'t str, &'t Template)>) {
+            for element in &template.elements {
+                let decorator = match element {
+                    TemplateElement::DecoratorBlock(d) | TemplateElement::PartialBlock(d) => d,
+                    TemplateElement::HelperBlock(h) => {
+                        h.template.iter().chain(h.inverse.iter())
+                            .for_each(|t| inline_partials(t, partials));
+
+                        continue;
+                    }
+                    _ => continue,
+                };
+
+                if let Some(template) = &decorator.template {
+                    if decorator.name == Parameter::Name("inline".into()) {
+                        if let Some(Parameter::Literal(JsonValue::String(name))) = decorator.params.first() {
+                            partials.push((name, template));
+                        }
+                    }
+
+                    inline_partials(template, partials);
+                }
+            }
+        }
+
+        let mut fragments = vec![];
+        for (name, template) in self.get_templates() {
+            let mut partials = vec![];
+            inline_partials(template, &mut partials);
+            for (partial, template) in partials {
+                fragments.push((fragment_name(name, partial), template.clone()));
+            }
+        }
+
+        for (name, template) in fragments {
+            self.register_template(&name, template);
+        }
+    }
 }
@@ -3,7 +3,7 @@
 //! This crate adds support for dynamic template rendering to Rocket. It
 //! automatically discovers templates, provides a `Responder` to render
 //! templates, and automatically reloads templates when compiled in debug mode.
-//! At present, it supports [Handlebars] and [Tera].
+//! At present, it supports [Handlebars], [Tera], and [MiniJinja].
 //!
 //! # Usage
 //!
@@ -13,12 +13,12 @@
 //!      ```toml
 //!      [dependencies.rocket_dyn_templates]
 //!      version = "0.1.0-rc.2"
-//!      features = ["handlebars", "tera"]
+//!      features = ["handlebars", "tera", "minijinja"]
 //!      ```
 //!
-//!   1. Write your template files in Handlebars (`.hbs`) and/or Tera (`.tera`)
-//!      in the configurable `template_dir` directory (default:
-//!      `{rocket_root}/templates`).
+//!   1. Write your template files in Handlebars (`.hbs`), Tera (`.tera`),
+//!      and/or MiniJinja (`.j2`) in the configurable `template_dir` directory
+//!      (default: `{rocket_root}/templates`).
 //!
 //!   2. Attach `Template::fairing()` return a `Template` using
 //!      `Template::render()`, supplying the name of the template file **minus
@@ -43,8 +43,8 @@
 //!
 //! Templates discovered by Rocket are _renamed_ from their file name to their
 //! file name **without the last two extensions**. As such, refer to a template
-//! with file name `foo.html.hbs` or `foo.html.tera` as `foo`. See
-//! [Discovery](#discovery) for more.
+//! with file name `foo.html.hbs`, `foo.html.tera`, or `foo.html.j2` as `foo`.
+//! See [Discovery](#discovery) for more.
 //!
 //! Templates that are _not_ discovered by Rocket, such as those registered
 //! directly via [`Template::custom()`], are _not_ renamed. Use the name with
@@ -77,9 +77,11 @@
 //! |--------------|---------|-----------|
 //! | [Tera]       | 1       | `.tera`   |
 //! | [Handlebars] | 4       | `.hbs`    |
+//! | [MiniJinja]  | 2       | `.j2`     |
 //!
 //! [Tera]: https://docs.rs/crate/tera/1
 //! [Handlebars]: https://docs.rs/crate/handlebars/4
+//! [MiniJinja]: https://docs.rs/crate/minijinja/2
 //!
 //! Any file that ends with one of these extension will be discovered and
 //! rendered with the corresponding templating engine. The _name_ of the
@@ -94,12 +96,13 @@
 //! | {template_dir}/index.hbs                      | index                 |
 //! | {template_dir}/dir/index.hbs                  | dir/index             |
 //! | {template_dir}/dir/index.html.tera            | dir/index             |
+//! | {template_dir}/dir/index.html.j2              | dir/index             |
 //! | {template_dir}/index.template.html.hbs        | index.template        |
 //! | {template_dir}/subdir/index.template.html.hbs | subdir/index.template |
 //!
 //! The recommended naming scheme is to use two extensions: one for the file
 //! type, and one for the template extension. This means that template
-//! extensions should look like: `.html.hbs`, `.html.tera`, `.xml.hbs`, etc.
+//! extensions should look like: `.html.hbs`, `.html.tera`, `.xml.j2`, etc.
 //!
 //! ## Template Fairing and Customization
 //!
@@ -118,19 +121,42 @@
 //! Templates are typically rendered indirectly via [`Template::render()`] which
 //! returns a `Template` responder which renders the template at response time.
 //! To render a template directly into a `String`, use [`Metadata::render()`]
-//! instead.
+//! instead. To render only a named part of a template, such as a block, use
+//! [`Template::render_fragment()`].
 //!
 //! Both methods take in a template name and context to use while rendering. The
 //! context can be any [`Serialize`] type that serializes to an `Object` (a
 //! dictionary) value. The [`context!`] macro may be used to create inline
 //! `Serialize`-able context objects.
 //!
+//! When rendered via the `Template` responder, the context additionally
+//! contains the request's [`CspNonce`] as `csp_nonce`, unless it already
+//! defines a `csp_nonce` value. Use it to allow inline scripts and styles under
+//! a [`Csp`](rocket::shield::Csp) policy with nonce sources:
+//!
+//! ```html
+//! <script nonce="{{ csp_nonce }}">/* .. */</script>
+//! ```
+//!
+//! [`CspNonce`]: rocket::shield::CspNonce
+//!
+//! Fairings and request guards can contribute values, including
+//! [request-scoped](rocket::request::Scope) values, to the context of every
+//! template rendered in response to a request with [`Template::inject()`].
+//!
 //! ## Automatic Reloading
 //!
 //! In debug mode (without the `--release` flag passed to `cargo`), templates
 //! will be automatically reloaded from disk if any changes have been made to
 //! the templates directory since the previous request. In release builds,
-//! template reloading is disabled to improve performance and cannot be enabled.
+//! automatic reloading is disabled to improve performance and cannot be
+//! enabled.
+//!
+//! In all builds, templates are reloaded from disk whenever Rocket's
+//! configuration is reloaded via [`Rocket::refresh_config()`] or, if enabled,
+//! on `SIGHUP`.
+//!
+//! [`Rocket::refresh_config()`]: rocket::Rocket::refresh_config()
 
 #![doc(html_root_url = "https://api.rocket.rs/v0.5-rc/rocket_dyn_templates")]
 #![doc(html_favicon_url = "https://rocket.rs/images/favicon.ico")]
@@ -138,8 +164,8 @@
 
 #[macro_use] extern crate rocket;
 
-#[cfg(not(any(feature = "tera", feature = "handlebars")))]
-compile_error!("at least one of \"tera\" or \"handlebars\" features must be enabled");
+#[cfg(not(any(feature = "tera", feature = "handlebars", feature = "minijinja")))]
+compile_error!("at least one of \"tera\", \"handlebars\", or \"minijinja\" features must be enabled");
 
 /// The tera templating engine library, reexported.
 #[doc(inline)]
@@ -157,6 +183,14 @@ pub use handlebars_ as handlebars;
 #[cfg(feature = "handlebars")]
 mod handlebars_templates;
 
+/// The minijinja templating engine library, reexported.
+#[doc(inline)]
+#[cfg(feature = "minijinja")]
+pub use minijinja_ as minijinja;
+
+#[cfg(feature = "minijinja")]
+mod minijinja_templates;
+
 mod engine;
 mod fairing;
 mod context;
@@ -170,6 +204,7 @@ use self::context::{Context, ContextManager};
 
 use std::borrow::Cow;
 use std::path::PathBuf;
+use std::sync::Mutex;
 
 #[doc(hidden)]
 pub use rocket::serde;
@@ -179,11 +214,14 @@ use rocket::request::Request;
 use rocket::fairing::Fairing;
 use rocket::response::{self, Responder};
 use rocket::http::{ContentType, Status};
-use rocket::figment::{value::Value, error::Error};
+use rocket::shield::CspNonce;
+use rocket::figment::{value::{Value, Dict}, error::Error};
 use rocket::serde::Serialize;
 
 const DEFAULT_TEMPLATE_DIR: &str = "templates";
 
+const CSP_NONCE: &str = "csp_nonce";
+
 /// Responder that renders a dynamic template.
 ///
 /// `Template` serves as a _proxy_ type for rendering a template and _does not_
@@ -194,9 +232,13 @@ const DEFAULT_TEMPLATE_DIR: &str = "templates";
 #[derive(Debug)]
 pub struct Template {
     name: Cow<'static, str>,
+    fragment: Option<Cow<'static, str>>,
     value: Result<Value, Error>
 }
 
+/// Request-local context injected via [`Template::inject()`].
+struct Injected(Mutex<Dict>);
+
 #[derive(Debug)]
 pub(crate) struct TemplateInfo {
     /// The complete path, including `template_dir`, to this template, if any.
@@ -342,7 +384,56 @@ impl Template {
     pub fn render<S, C>(name: S, context: C) -> Template
         where S: Into<Cow<'static, str>>, C: Serialize
     {
-        Template { name: name.into(), value: Value::serialize(context) }
+        Template { name: name.into(), fragment: None, value: Value::serialize(context) }
+    }
+
+    /// Render only the fragment named `fragment` of the template named `name`
+    /// with the context `context`.
+    ///
+    /// A fragment is a named part of a template: a `{% block %}` in Tera and
+    /// MiniJinja templates, including blocks inherited from parent templates,
+    /// and an inline partial (`{{#*inline "name"}}`) in Handlebars templates.
+    /// Rendering a fragment allows an endpoint, such as one responding to an
+    /// [htmx](https://htmx.org) request, to return only part of a page without
+    /// duplicating that part in a template of its own.
+    ///
+    /// The `Content-Type` of the response is that of the template `name`. If
+    /// the template has no such fragment, rendering fails with an error.
+    ///
+    /// # Example
+    ///
+    /// Given a template `index.html.tera` with a `task_list` block:
+    ///
+    /// ```html
+    /// <h1>Tasks</h1>
+    /// {% block task_list %}
+    /// <ul id="tasks">
+    ///   {% for task in tasks %}<li>{{ task }}</li>{% endfor %}
+    /// </ul>
+    /// {% endblock task_list %}
+    /// ```
+    ///
+    /// A route can render the entire page while another renders only the list:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_dyn_templates::{Template, context};
+    ///
+    /// #[get("/")]
+    /// fn index() -> Template {
+    ///     Template::render("index", context! { tasks: ["Write", "Test"] })
+    /// }
+    ///
+    /// #[get("/tasks")]
+    /// fn tasks() -> Template {
+    ///     Template::render_fragment("index", "task_list", context! { tasks: ["Write", "Test"] })
+    /// }
+    /// ```
+    #[inline]
+    pub fn render_fragment<S, F, C>(name: S, fragment: F, context: C) -> Template
+        where S: Into<Cow<'static, str>>, F: Into<Cow<'static, str>>, C: Serialize
+    {
+        Template { fragment: Some(fragment.into()), ..Template::render(name, context) }
     }
 
     /// Render the template named `name` with the context `context` into a
@@ -392,6 +483,110 @@ impl Template {
         Template::render(name, context).finalize(&ctxt).ok().map(|v| v.1)
     }
 
+    /// Injects the entries of `context` into the context of every `Template`
+    /// rendered in response to `req`.
+    ///
+    /// This is the one way to contribute request-local values, such as the
+    /// current user, a CSRF token, or a value in the request's
+    /// [`Scope`](rocket::request::Scope), to every template without each
+    /// handler assembling them into its own context. `context` can be of any
+    /// type that implements `Serialize` and serializes to a dictionary; other
+    /// values are logged and ignored. `context` is serialized immediately, so
+    /// later changes to the values it was built from aren't observed.
+    ///
+    /// Values in a template's own context take precedence over injected
+    /// values. When multiple injections provide the same key, the latest wins.
+    /// Injected values are only applied by the `Template` responder, not by
+    /// [`Template::show()`] or [`Metadata::render()`].
+    ///
+    /// # Example
+    ///
+    /// Injecting the current user, if any, via a fairing:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::fairing::AdHoc;
+    /// use rocket_dyn_templates::{Template, context};
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build()
+    ///         .attach(Template::fairing())
+    ///         .attach(AdHoc::on_request("Current User", |req, _| Box::pin(async move {
+    ///             let user = req.cookies().get("user").map(|c| c.value().to_string());
+    ///             Template::inject(req, context! { user });
+    ///         })))
+    /// }
+    /// ```
+    ///
+    /// Injecting a value from the request's [`Scope`](rocket::request::Scope):
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::fairing::AdHoc;
+    /// use rocket_dyn_templates::{Template, context};
+    ///
+    /// struct Tenant(String);
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build()
+    ///         .attach(Template::fairing())
+    ///         .attach(AdHoc::on_request("Tenant", |req, _| Box::pin(async move {
+    ///             let host = req.host().map(|h| h.domain().to_string());
+    ///             req.scope().provide(Tenant(host.unwrap_or_default()));
+    ///             if let Some(tenant) = req.scope().get::<Tenant>() {
+    ///                 Template::inject(req, context! { tenant: &tenant.0 });
+    ///             }
+    ///         })))
+    /// }
+    /// ```
+    ///
+    /// Injecting values from a request guard:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::request::{self, Request, FromRequest};
+    /// use rocket_dyn_templates::{Template, context};
+    ///
+    /// struct Admin;
+    ///
+    /// #[rocket::async_trait]
+    /// impl<'r> FromRequest<'r> for Admin {
+    ///     type Error = ();
+    ///
+    ///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+    ///         # /*
+    ///         ...
+    ///         # */
+    ///         Template::inject(req, context! { admin: true });
+    ///         request::Outcome::Success(Admin)
+    ///     }
+    /// }
+    ///
+    /// #[get("/dashboard")]
+    /// fn dashboard(_admin: Admin) -> Template {
+    ///     // The context of `dashboard` contains `admin: true`.
+    ///     Template::render("dashboard", context! { title: "Dashboard" })
+    /// }
+    /// ```
+    pub fn inject<C: Serialize>(req: &Request<'_>, context: C) {
+        let dict = match Value::serialize(context) {
+            Ok(Value::Dict(_, dict)) => dict,
+            Ok(value) => {
+                error_!("Injected template context is not a dictionary: {:?}.", value);
+                return;
+            }
+            Err(e) => {
+                error_!("Injected template context failed to serialize: {}.", e);
+                return;
+            }
+        };
+
+        let injected = req.local_cache(|| Injected(Mutex::new(Dict::new())));
+        injected.0.lock().expect("injected context lock").extend(dict);
+    }
+
     /// Actually render this template given a template context. This method is
     /// called by the `Template` `Responder` implementation as well as
     /// `Template::show()`.
@@ -411,8 +606,13 @@ impl Template {
             Status::InternalServerError
         })?;
 
-        let string = ctxt.engines.render(name, &info, value).ok_or_else(|| {
-            error_!("Template '{}' failed to render.", name);
+        let fragment = self.fragment.as_deref();
+        let string = ctxt.engines.render(name, fragment, &info, value).ok_or_else(|| {
+            match fragment {
+                Some(frag) => error_!("Template '{}' fragment '{}' failed to render.", name, frag),
+                None => error_!("Template '{}' failed to render.", name),
+            }
+
             Status::InternalServerError
         })?;
 
@@ -434,7 +634,20 @@ impl<'r> Responder<'r, 'static> for Template {
                 Status::InternalServerError
             })?;
 
-        self.finalize(&ctxt.context())?.respond_to(req)
+        let mut template = self;
+        if let Ok(Value::Dict(_, dict)) = &mut template.value {
+            let injected = req.local_cache(|| Injected(Mutex::new(Dict::new())));
+            for (key, value) in injected.0.lock().expect("injected context lock").iter() {
+                dict.entry(key.clone()).or_insert_with(|| value.clone());
+            }
+
+            if !dict.contains_key(CSP_NONCE) {
+                let nonce = CspNonce::of(req).as_str();
+                dict.insert(CSP_NONCE.into(), nonce.into());
+            }
+        }
+
+        template.finalize(&ctxt.context())?.respond_to(req)
     }
 }
 
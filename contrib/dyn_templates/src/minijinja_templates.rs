@@ -0,0 +1,119 @@
+use std::path::Path;
+use std::error::Error;
+use std::collections::HashSet;
+
+use rocket::serde::Serialize;
+
+use crate::engine::Engine;
+
+pub use crate::minijinja::Environment;
+use crate::minijinja::{AutoEscape, default_auto_escape_callback};
+
+impl Engine for Environment<'static> {
+    const EXT: &'static str = "j2";
+
+    fn init<'a>(templates: impl Iterator<Item = (&'a str, &'a Path)>) -> Option<Self> {
+        let templates: Vec<_> = templates.collect();
+
+        // Discovered templates are named without their extensions, so escaping
+        // is decided by their file name. Templates are compiled with the auto
+        // escape callback in effect when they're added, so set it first.
+        let html_templates: HashSet<String> = templates.iter()
+            .filter(|(_, path)| {
+                let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+                default_auto_escape_callback(file_name) == AutoEscape::Html
+            })
+            .map(|(name, _)| name.to_string())
+            .collect();
+
+        let mut env = Environment::new();
+        env.set_auto_escape_callback(move |name| match html_templates.contains(name) {
+            true => AutoEscape::Html,
+            false => default_auto_escape_callback(name),
+        });
+
+        let mut ok = true;
+        for (name, path) in templates {
+            let source = match std::fs::read_to_string(path) {
+                Ok(source) => source,
+                Err(e) => {
+                    error!("MiniJinja template '{}' failed to load.", name);
+                    error_!("{}", e);
+                    info_!("Template path: '{}'.", path.to_string_lossy());
+                    ok = false;
+                    continue;
+                }
+            };
+
+            if let Err(e) = env.add_template_owned(name.to_string(), source) {
+                error!("MiniJinja template '{}' failed to register.", name);
+
+                let mut error = Some(&e as &dyn Error);
+                while let Some(err) = error {
+                    error_!("{}", err);
+                    error = err.source();
+                }
+
+                info_!("Template path: '{}'.", path.to_string_lossy());
+                ok = false;
+            }
+        }
+
+        ok.then(|| env)
+    }
+
+    fn render_fragment<C: Serialize>(&self, name: &str, block: &str, context: C)
+        -> Option<String>
+    {
+        let template = match self.get_template(name) {
+            Ok(template) => template,
+            Err(_) => {
+                error_!("MiniJinja template '{}' does not exist.", name);
+                return None;
+            }
+        };
+
+        let result = template.render_captured(context)
+            .and_then(|mut captured| captured.with_state_mut(|state| state.render_block(block)));
+
+        match result {
+            Ok(string) => Some(string),
+            Err(e) => {
+                error_!("Error rendering block '{}' of MiniJinja template '{}'.", block, name);
+
+                let mut error = Some(&e as &dyn Error);
+                while let Some(err) = error {
+                    error_!("{}", err);
+                    error = err.source();
+                }
+
+                None
+            }
+        }
+    }
+
+    fn render<C: Serialize>(&self, name: &str, context: C) -> Option<String> {
+        let template = match self.get_template(name) {
+            Ok(template) => template,
+            Err(_) => {
+                error_!("MiniJinja template '{}' does not exist.", name);
+                return None;
+            }
+        };
+
+        match template.render(context) {
+            Ok(string) => Some(string),
+            Err(e) => {
+                error_!("Error rendering MiniJinja template '{}'.", name);
+
+                let mut error = Some(&e as &dyn Error);
+                while let Some(err) = error {
+                    error_!("{}", err);
+                    error = err.source();
+                }
+
+                None
+            }
+        }
+    }
+}
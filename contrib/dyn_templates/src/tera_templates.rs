@@ -3,9 +3,10 @@ use std::error::Error;
 
 use rocket::serde::Serialize;
 
-use crate::engine::Engine;
+use crate::engine::{Engine, fragment_name};
 
 pub use crate::tera::{Context, Tera};
+use crate::tera::{Template, ast::{Node, Block}};
 
 impl Engine for Tera {
     const EXT: &'static str = "tera";
@@ -36,6 +37,62 @@ impl Engine for Tera {
         }
     }
 
+    fn render_fragment<C: Serialize>(&self, name: &str, block: &str, context: C)
+        -> Option<String>
+    {
+        if self.get_template(name).is_err() {
+            error_!("Tera template '{}' does not exist.", name);
+            return None;
+        }
+
+        let fragment = fragment_name(name, block);
+        if self.get_template(&fragment).is_err() {
+            error_!("Tera template '{}' has no block '{}'.", name, block);
+            return None;
+        }
+
+        Engine::render(self, &fragment, context)
+    }
+
+    /// Tera can't render a single block of a template, so each block is
+    /// registered as a template of its own: one whose body is just the block,
+    /// and whose parents are the corresponding fragments of the original's
+    /// parents, so that `super()` resolves as it would in the original.
+    fn prepare_fragments(&mut self) {
+        fn fragment(template: &Template, block: &Block) -> Template {
+            let mut fragment = template.clone();
+            fragment.name = fragment_name(&template.name, &block.name);
+            fragment.path = template.path.clone().or_else(|| Some(template.name.clone()));
+            fragment.ast = vec![Node::Block(Default::default(), block.clone(), Default::default())];
+            fragment.parent = template.parent.as_ref().map(|p| fragment_name(p, &block.name));
+            fragment.parents = template.parents.iter()
+                .map(|p| fragment_name(p, &block.name))
+                .collect();
+
+            fragment
+        }
+
+        let mut fragments = vec![];
+        for template in self.templates.values() {
+            let parents: Vec<_> = template.parents.iter()
+                .filter_map(|name| self.templates.get(name))
+                .collect();
+
+            // A template's blocks include those it only inherits.
+            let blocks = std::iter::once(template).chain(parents.iter().copied())
+                .flat_map(|t| t.blocks_definitions.values().filter_map(|d| d.first()));
+
+            for (_, block) in blocks {
+                fragments.push(fragment(template, block));
+                fragments.extend(parents.iter().map(|parent| fragment(parent, block)));
+            }
+        }
+
+        for fragment in fragments {
+            self.templates.entry(fragment.name.clone()).or_insert(fragment);
+        }
+    }
+
     fn render<C: Serialize>(&self, name: &str, context: C) -> Option<String> {
         if self.get_template(name).is_err() {
             error_!("Tera template '{}' does not exist.", name);
@@ -252,6 +252,143 @@ mod tera_tests {
     //     assert_eq!(template, Some(EXPECTED.into()));
     // }
 
+    #[test]
+    fn test_tera_csp_nonce() {
+        use rocket::local::blocking::Client;
+        use rocket::shield::{Shield, Csp, Directive, Source};
+
+        #[get("/csp")]
+        fn csp() -> Template {
+            Template::render("tera/csp_nonce", context! {})
+        }
+
+        let csp = Csp::default().directive(Directive::ScriptSrc, Source::Nonce);
+        let rocket = rocket().mount("/", routes![csp]).attach(Shield::new().enable(csp));
+        let client = Client::debug(rocket).unwrap();
+
+        let response = client.get("/csp").dispatch();
+        let header = response.headers().get_one("Content-Security-Policy").unwrap();
+        let nonce = header.split("'nonce-").nth(1).unwrap().trim_end_matches('\'');
+        let expected = format!("<script nonce=\"{}\"></script>\n", nonce);
+        let body = response.into_string().unwrap().replace("&#x2F;", "/");
+        assert_eq!(body, expected);
+    }
+
+    #[test]
+    fn test_tera_injected_context() {
+        use rocket::local::blocking::Client;
+        use rocket::fairing::AdHoc;
+        use rocket::request::{self, Request};
+
+        struct Role;
+
+        #[rocket::async_trait]
+        impl<'r> FromRequest<'r> for Role {
+            type Error = ();
+
+            async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+                Template::inject(req, context! { role: "admin", user: "alice" });
+                request::Outcome::Success(Role)
+            }
+        }
+
+        #[get("/inject")]
+        fn inject(_role: Role) -> Template {
+            Template::render("tera/inject", context! { title: "own" })
+        }
+
+        #[get("/inject/bare")]
+        fn bare() -> Template {
+            Template::render("tera/inject", context! { role: "none" })
+        }
+
+        let rocket = rocket()
+            .mount("/", routes![inject, bare])
+            .attach(AdHoc::on_request("Inject", |req, _| Box::pin(async move {
+                Template::inject(req, context! { user: "bob", title: "injected" });
+                Template::inject(req, 42);
+            })));
+
+        let client = Client::debug(rocket).unwrap();
+        let response = client.get("/inject").dispatch();
+        assert_eq!(response.into_string().unwrap(), "alice: own (admin)");
+
+        let response = client.get("/inject/bare").dispatch();
+        assert_eq!(response.into_string().unwrap(), "bob: injected (none)");
+    }
+
+    #[test]
+    fn test_tera_scoped_context() {
+        use rocket::local::blocking::Client;
+        use rocket::fairing::AdHoc;
+
+        struct User(&'static str);
+
+        #[get("/scoped")]
+        fn scoped() -> Template {
+            Template::render("tera/inject", context! { role: "guest" })
+        }
+
+        #[get("/scoped/own")]
+        fn own() -> Template {
+            Template::render("tera/inject", context! { role: "guest", title: "own" })
+        }
+
+        let rocket = rocket()
+            .mount("/", routes![scoped, own])
+            .attach(AdHoc::on_request("Scope", |req, _| Box::pin(async move {
+                req.scope().provide(User("carol"));
+                let user = req.scope().get::<User>().unwrap().0;
+                Template::inject(req, context! { user, title: "scoped" });
+            })));
+
+        let client = Client::debug(rocket).unwrap();
+        let response = client.get("/scoped").dispatch();
+        assert_eq!(response.into_string().unwrap(), "carol: scoped (guest)");
+
+        let response = client.get("/scoped/own").dispatch();
+        assert_eq!(response.into_string().unwrap(), "carol: own (guest)");
+
+        client.override_scoped(User("dave"));
+        let response = client.get("/scoped").dispatch();
+        assert_eq!(response.into_string().unwrap(), "dave: scoped (guest)");
+    }
+
+    #[test]
+    fn test_tera_fragments() {
+        use rocket::local::blocking::Client;
+
+        #[get("/fragment/<block>")]
+        fn fragment(block: &str) -> Template {
+            let context = context! { title: "_test_", content: "<script />" };
+            Template::render_fragment("tera/html_test", block.to_string(), context)
+        }
+
+        #[get("/super")]
+        fn super_fragment() -> Template {
+            Template::render_fragment("tera/super", "footer", context! {})
+        }
+
+        let rocket = rocket().mount("/", routes![fragment, super_fragment]);
+        let client = Client::debug(rocket).unwrap();
+
+        let response = client.get("/fragment/content").dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::HTML));
+        assert_eq!(response.into_string().unwrap(), "\n&lt;script &#x2F;&gt;\n");
+
+        let response = client.get("/fragment/head").dispatch();
+        assert_eq!(response.into_string().unwrap(), "\nh_start\ntitle: _test_\nh_end\n");
+
+        let response = client.get("/super").dispatch();
+        assert_eq!(response.into_string().unwrap(), "footer");
+
+        let response = client.get("/fragment/missing").dispatch();
+        assert_eq!(response.status(), Status::InternalServerError);
+
+        let md = client.get("/tera/html_test%23content").dispatch();
+        assert_eq!(md.status(), Status::NotFound);
+    }
+
     #[test]
     fn test_template_metadata_with_tera() {
         use rocket::local::blocking::Client;
@@ -272,6 +409,129 @@ mod tera_tests {
     }
 }
 
+#[cfg(feature = "minijinja")]
+mod minijinja_tests {
+    use super::*;
+    use std::collections::HashMap;
+    use rocket::http::{ContentType, Status};
+    use rocket::request::FromRequest;
+
+    const UNESCAPED_EXPECTED: &'static str
+        = "\nh_start\ntitle: _test_\nh_end\n\n\n<script />\n\nfoot";
+    const ESCAPED_EXPECTED: &'static str
+        = "\nh_start\ntitle: _test_\nh_end\n\n\n&lt;script &#x2f;&gt;\n\nfoot";
+
+    #[async_test]
+    async fn test_minijinja_templates() {
+        use rocket::local::asynchronous::Client;
+
+        let client = Client::debug(rocket()).await.unwrap();
+        let req = client.get("/");
+        let metadata = Metadata::from_request(&req).await.unwrap();
+
+        let mut map = HashMap::new();
+        map.insert("title", "_test_");
+        map.insert("content", "<script />");
+
+        // Test with a txt file, which shouldn't escape.
+        let template = Template::show(client.rocket(), "j2/txt_test", &map);
+        let md_rendered = metadata.render("j2/txt_test", &map);
+        assert_eq!(template, Some(UNESCAPED_EXPECTED.into()));
+        assert_eq!(md_rendered, Some((ContentType::Text, UNESCAPED_EXPECTED.into())));
+
+        // Now with an HTML file, which should.
+        let template = Template::show(client.rocket(), "j2/html_test", &map);
+        let md_rendered = metadata.render("j2/html_test", &map);
+        assert_eq!(template, Some(ESCAPED_EXPECTED.into()));
+        assert_eq!(md_rendered, Some((ContentType::HTML, ESCAPED_EXPECTED.into())));
+    }
+
+    #[test]
+    fn test_minijinja_custom() {
+        use rocket::local::blocking::Client;
+
+        let rocket = rocket::custom(Config::figment().merge(("template_dir", template_root())))
+            .mount("/", routes![template_check])
+            .attach(Template::try_custom(|engines| {
+                engines.minijinja.add_filter("shout", |s: String| s.to_uppercase());
+                engines.minijinja.add_template("j2/custom.html", "<b>{{ name | shout }}</b>")?;
+                Ok(())
+            }));
+
+        let client = Client::debug(rocket).unwrap();
+        let template = Template::show(client.rocket(), "j2/custom.html", context! { name: "<hi>" });
+        assert_eq!(template, Some("<b>&lt;HI&gt;</b>".into()));
+
+        let response = client.get("/j2/custom.html").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    #[test]
+    fn test_minijinja_csp_nonce() {
+        use rocket::local::blocking::Client;
+        use rocket::shield::{Shield, Csp, Directive, Source};
+
+        #[get("/csp")]
+        fn csp() -> Template {
+            Template::render("j2/csp_nonce", context! {})
+        }
+
+        let csp = Csp::default().directive(Directive::ScriptSrc, Source::Nonce);
+        let rocket = rocket().mount("/", routes![csp]).attach(Shield::new().enable(csp));
+        let client = Client::debug(rocket).unwrap();
+
+        let response = client.get("/csp").dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::HTML));
+        let header = response.headers().get_one("Content-Security-Policy").unwrap();
+        let nonce = header.split("'nonce-").nth(1).unwrap().trim_end_matches('\'');
+        let expected = format!("<script nonce=\"{}\"></script>", nonce);
+        let body = response.into_string().unwrap().replace("&#x2f;", "/");
+        assert_eq!(body, expected);
+    }
+
+    #[test]
+    fn test_minijinja_fragments() {
+        use rocket::local::blocking::Client;
+
+        #[get("/fragment/<block>")]
+        fn fragment(block: &str) -> Template {
+            let context = context! { title: "_test_", content: "<script />" };
+            Template::render_fragment("j2/html_test", block.to_string(), context)
+        }
+
+        let client = Client::debug(rocket().mount("/", routes![fragment])).unwrap();
+
+        let response = client.get("/fragment/content").dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::HTML));
+        assert_eq!(response.into_string().unwrap(), "\n&lt;script &#x2f;&gt;\n");
+
+        let response = client.get("/fragment/head").dispatch();
+        assert_eq!(response.into_string().unwrap(), "\nh_start\ntitle: _test_\nh_end\n");
+
+        let response = client.get("/fragment/missing").dispatch();
+        assert_eq!(response.status(), Status::InternalServerError);
+    }
+
+    #[test]
+    fn test_template_metadata_with_minijinja() {
+        use rocket::local::blocking::Client;
+
+        let client = Client::debug(rocket()).unwrap();
+
+        let response = client.get("/j2/txt_test").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = client.get("/j2/html_test").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = client.get("/j2/not_existing").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+
+        let response = client.get("/hbs/txt_test").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
+
 #[cfg(feature = "handlebars")]
 mod handlebars_tests {
     use super::*;
@@ -316,6 +576,26 @@ mod handlebars_tests {
     //     assert_eq!(template, Some(EXPECTED.into()));
     // }
 
+    #[test]
+    fn test_handlebars_fragments() {
+        use rocket::local::blocking::Client;
+
+        #[get("/fragment/<partial>")]
+        fn fragment(partial: &str) -> Template {
+            let context = context! { content: "<script /> hi" };
+            Template::render_fragment("hbs/fragments", partial.to_string(), context)
+        }
+
+        let client = Client::debug(rocket().mount("/", routes![fragment])).unwrap();
+
+        let response = client.get("/fragment/item").dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::HTML));
+        assert_eq!(response.into_string().unwrap(), "<li>&lt;script /&gt; hi</li>");
+
+        let response = client.get("/fragment/missing").dispatch();
+        assert_eq!(response.status(), Status::InternalServerError);
+    }
+
     #[test]
     fn test_template_metadata_with_handlebars() {
         use rocket::local::blocking::Client;
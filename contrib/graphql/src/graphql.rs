@@ -0,0 +1,276 @@
+use std::future::ready;
+
+use async_graphql::Executor;
+use async_graphql::http::{GraphiQLSource, GraphQLPlaygroundConfig, playground_source};
+use async_graphql::http::{WebSocket as Subscriptions, WebSocketProtocols, WsMessage};
+use rocket::{Request, Data, Route};
+use rocket::futures::{Stream, StreamExt};
+use rocket::http::Method;
+use rocket::outcome::Outcome::*;
+use rocket::request::FromRequest;
+use rocket::data::FromData;
+use rocket::response::{self, Responder, content::RawHtml};
+use rocket::route::{Handler, Outcome};
+use rocket::ws::{self, WebSocket, MessageStream, Message, frame::{CloseFrame, CloseCode}};
+
+use crate::{GraphQLRequest, GraphQLBatchRequest};
+
+/// A mountable handler serving a GraphQL schema.
+///
+/// `GraphQL` can be mounted at any path, `/graphql` by convention, with
+/// [`Rocket::mount()`](rocket::Rocket::mount()). It responds to:
+///
+///   * `POST` requests with a single or batched [`GraphQLBatchRequest`] body.
+///   * `GET` requests with a [`GraphQLRequest`] in the query string.
+///   * `GET` WebSocket upgrade requests, serving subscriptions over the
+///     `graphql-transport-ws` or legacy `graphql-ws` protocol, unless
+///     [disabled](GraphQL::subscriptions()).
+///   * `GET` requests without a query string with a [GraphiQL] or
+///     [Playground] IDE, if [enabled](GraphQL::ide()).
+///
+/// [GraphiQL]: https://github.com/graphql/graphiql
+/// [Playground]: https://github.com/graphql/graphql-playground
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+/// use rocket_graphql::{GraphQL, Ide};
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn add(&self, a: i32, b: i32) -> i32 { a + b }
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+///     rocket::build().mount("/graphql", GraphQL::new(schema).ide(Ide::GraphiQL))
+/// }
+/// ```
+#[derive(Clone)]
+pub struct GraphQL<E> {
+    executor: E,
+    subscriptions: bool,
+    ide: Option<Ide>,
+    rank: isize,
+}
+
+/// A GraphQL IDE, served by [`GraphQL`] if [enabled](GraphQL::ide()).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Ide {
+    /// The [GraphiQL](https://github.com/graphql/graphiql) IDE.
+    GraphiQL,
+    /// The [GraphQL Playground](https://github.com/graphql/graphql-playground)
+    /// IDE.
+    Playground,
+}
+
+impl<E: Executor> GraphQL<E> {
+    /// The default rank of the routes.
+    const DEFAULT_RANK: isize = 10;
+
+    /// Returns a `GraphQL` handler serving queries, mutations, and
+    /// subscriptions of `executor`, typically a `Schema`. No IDE is served.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+    /// use rocket_graphql::GraphQL;
+    ///
+    /// # struct Query;
+    /// # #[Object] impl Query { async fn answer(&self) -> i32 { 42 } }
+    /// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    /// let rocket = rocket::build().mount("/graphql", GraphQL::new(schema));
+    /// ```
+    pub fn new(executor: E) -> Self {
+        GraphQL { executor, subscriptions: true, ide: None, rank: Self::DEFAULT_RANK }
+    }
+
+    /// Sets whether subscriptions are served over WebSocket connections.
+    /// Enabled by default.
+    pub fn subscriptions(mut self, enabled: bool) -> Self {
+        self.subscriptions = enabled;
+        self
+    }
+
+    /// Serves `ide` in response to `GET` requests without a query string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+    /// use rocket_graphql::{GraphQL, Ide};
+    ///
+    /// # struct Query;
+    /// # #[Object] impl Query { async fn answer(&self) -> i32 { 42 } }
+    /// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    /// let graphql = GraphQL::new(schema).ide(Ide::Playground);
+    /// ```
+    pub fn ide(mut self, ide: Ide) -> Self {
+        self.ide = Some(ide);
+        self
+    }
+
+    /// Sets the rank of the mounted routes to `rank`. The default rank is
+    /// `10`.
+    pub fn rank(mut self, rank: isize) -> Self {
+        self.rank = rank;
+        self
+    }
+
+    async fn get<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        if self.subscriptions {
+            if let Success(ws) = WebSocket::from_request(req).await {
+                return match subscription(self.executor.clone(), req, ws) {
+                    Some(subscription) => Outcome::from(req, subscription),
+                    None => Outcome::Failure(rocket::http::Status::BadRequest),
+                };
+            }
+        }
+
+        match GraphQLRequest::from_request(req).await {
+            Success(request) => Outcome::from(req, request.execute(&self.executor).await),
+            Failure((status, e)) => {
+                warn_!("Invalid GraphQL request: {}", e);
+                Outcome::Failure(status)
+            }
+            Forward(()) => match self.ide {
+                Some(ide) => Outcome::from(req, ide.page(req.uri().path().as_str())),
+                None => Outcome::Forward(data),
+            },
+        }
+    }
+
+    async fn post<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        match GraphQLBatchRequest::from_data(req, data).await {
+            Success(request) => Outcome::from(req, request.execute(&self.executor).await),
+            Failure((status, e)) => {
+                warn_!("Invalid GraphQL request: {}", e);
+                Outcome::Failure(status)
+            }
+            Forward(data) => Outcome::Forward(data),
+        }
+    }
+}
+
+impl Ide {
+    /// Returns the IDE's page for a GraphQL endpoint at `endpoint`, also
+    /// used for subscriptions.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::response::content::RawHtml;
+    /// use rocket_graphql::Ide;
+    ///
+    /// #[get("/graphiql")]
+    /// fn graphiql() -> RawHtml<String> {
+    ///     Ide::GraphiQL.page("/graphql")
+    /// }
+    /// ```
+    pub fn page(self, endpoint: &str) -> RawHtml<String> {
+        RawHtml(match self {
+            Ide::GraphiQL => GraphiQLSource::build()
+                .endpoint(endpoint)
+                .subscription_endpoint(endpoint)
+                .finish(),
+            Ide::Playground => {
+                let config = GraphQLPlaygroundConfig::new(endpoint)
+                    .subscription_endpoint(endpoint);
+
+                playground_source(config)
+            }
+        })
+    }
+}
+
+/// A WebSocket responder which echoes the negotiated subprotocol.
+struct Subscription<S> {
+    stream: MessageStream<'static, S>,
+    protocol: Option<WebSocketProtocols>,
+}
+
+/// Returns a responder serving subscriptions of `executor` over `ws`, or
+/// `None` if the client only supports unknown subprotocols.
+fn subscription<E: Executor>(
+    executor: E,
+    req: &Request<'_>,
+    ws: WebSocket,
+) -> Option<Subscription<impl Stream<Item = ws::result::Result<Message>>>> {
+    let requested: Vec<_> = req.headers().get("Sec-WebSocket-Protocol")
+        .flat_map(|v| v.split(','))
+        .map(|v| v.trim())
+        .collect();
+
+    let protocol = match requested.is_empty() {
+        true => None,
+        false => Some(requested.iter().find_map(|p| p.parse().ok())?),
+    };
+
+    let stream = ws.stream(move |incoming| {
+        let incoming = incoming
+            .take_while(|message| ready(message.is_ok()))
+            .filter_map(|message| ready(match message {
+                Ok(Message::Text(text)) => Some(text.into_bytes()),
+                Ok(Message::Binary(bytes)) => Some(bytes),
+                _ => None,
+            }));
+
+        let protocol = protocol.unwrap_or(WebSocketProtocols::GraphQLWS);
+        Subscriptions::new(executor, incoming, protocol).map(message).map(Ok)
+    });
+
+    Some(Subscription { stream, protocol })
+}
+
+/// Converts an outgoing `async-graphql` message into a WebSocket message.
+fn message(message: WsMessage) -> Message {
+    match message {
+        WsMessage::Text(text) => Message::Text(text),
+        WsMessage::Close(code, reason) => Message::Close(Some(CloseFrame {
+            code: CloseCode::from(code),
+            reason: reason.into(),
+        })),
+    }
+}
+
+impl<'r, S> Responder<'r, 'static> for Subscription<S>
+    where S: Stream<Item = ws::result::Result<Message>> + Send + 'static
+{
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let mut response = self.stream.respond_to(req)?;
+        if let Some(protocol) = self.protocol {
+            response.set_raw_header("Sec-WebSocket-Protocol", protocol.sec_websocket_protocol());
+        }
+
+        Ok(response)
+    }
+}
+
+#[rocket::async_trait]
+impl<E: Executor> Handler for GraphQL<E> {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        match req.method() {
+            Method::Post => self.post(req, data).await,
+            _ => self.get(req, data).await,
+        }
+    }
+}
+
+impl<E: Executor> From<GraphQL<E>> for Vec<Route> {
+    fn from(graphql: GraphQL<E>) -> Self {
+        [Method::Get, Method::Post].into_iter()
+            .map(|method| {
+                let mut route = Route::ranked(graphql.rank, method, "/", graphql.clone());
+                route.name = Some(format!("GraphQL: {}", method).into());
+                route
+            })
+            .collect()
+    }
+}
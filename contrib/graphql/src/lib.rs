@@ -0,0 +1,124 @@
+//! GraphQL support for Rocket via [`async-graphql`].
+//!
+//! This crate serves [`async-graphql`] schemas from Rocket applications. It
+//! provides:
+//!
+//!   * [`GraphQL`], a mountable handler serving queries and mutations over
+//!     `GET` and `POST`, batched requests, subscriptions over
+//!     [WebSockets](rocket::ws), and optionally a GraphiQL or Playground
+//!     [`Ide`].
+//!   * [`GraphQLRequest`] and [`GraphQLBatchRequest`], data and request guards
+//!     for GraphQL requests, and [`GraphQLResponse`], their responder, for use
+//!     in custom routes.
+//!
+//! [`async-graphql`]: https://docs.rs/async-graphql
+//!
+//! # Usage
+//!
+//!   1. Depend on `rocket_graphql`:
+//!
+//!      ```toml
+//!      [dependencies.rocket_graphql]
+//!      version = "0.1.0-rc.2"
+//!      ```
+//!
+//!   2. Build a schema and mount a [`GraphQL`] handler serving it:
+//!
+//!      ```rust
+//!      # #[macro_use] extern crate rocket;
+//!      use rocket_graphql::{GraphQL, Ide};
+//!      use rocket_graphql::async_graphql::{EmptyMutation, Object, Schema, Subscription};
+//!      use rocket_graphql::async_graphql::futures_util::{stream, Stream};
+//!
+//!      struct Query;
+//!
+//!      #[Object]
+//!      impl Query {
+//!          /// Returns the sum of `a` and `b`.
+//!          async fn add(&self, a: i32, b: i32) -> i32 { a + b }
+//!      }
+//!
+//!      struct Countdown;
+//!
+//!      #[Subscription]
+//!      impl Countdown {
+//!          /// Counts down from `from` to `0`.
+//!          async fn countdown(&self, from: u32) -> impl Stream<Item = u32> {
+//!              stream::iter((0..=from).rev())
+//!          }
+//!      }
+//!
+//!      #[launch]
+//!      fn rocket() -> _ {
+//!          let schema = Schema::new(Query, EmptyMutation, Countdown);
+//!          rocket::build().mount("/graphql", GraphQL::new(schema).ide(Ide::GraphiQL))
+//!      }
+//!      ```
+//!
+//! With the above, queries can be sent to `/graphql` as `GET` or `POST`
+//! requests, subscriptions are served over WebSocket connections to
+//! `/graphql`, and GraphiQL is served at `/graphql`.
+//!
+//! # Custom Routes
+//!
+//! To access request guards or managed state in resolvers, use
+//! [`GraphQLRequest`] in a custom route and add [context
+//! data](GraphQLRequest::data()) to the request before executing it:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::State;
+//! use rocket::http::CookieJar;
+//! use rocket_graphql::{GraphQLRequest, GraphQLResponse};
+//! use rocket_graphql::async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Schema};
+//!
+//! struct Query;
+//!
+//! #[Object]
+//! impl Query {
+//!     async fn user(&self, ctx: &Context<'_>) -> Option<String> {
+//!         ctx.data_opt::<String>().cloned()
+//!     }
+//! }
+//!
+//! type AppSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+//!
+//! #[post("/graphql", data = "<request>")]
+//! async fn graphql(
+//!     schema: &State<AppSchema>,
+//!     jar: &CookieJar<'_>,
+//!     request: GraphQLRequest
+//! ) -> GraphQLResponse {
+//!     let request = match jar.get("user") {
+//!         Some(user) => request.data(user.value().to_string()),
+//!         None => request,
+//!     };
+//!
+//!     request.execute(schema.inner()).await
+//! }
+//! ```
+//!
+//! # Limits
+//!
+//! Request bodies are limited by the `graphql` [limit](rocket::data::Limits),
+//! which defaults to the `json` limit's default of 1MiB. Larger bodies fail
+//! with `413 Payload Too Large`. To change it, set the limit in `Rocket.toml`:
+//!
+//! ```toml
+//! [default.limits]
+//! graphql = "4MiB"
+//! ```
+
+#[macro_use]
+extern crate rocket;
+
+mod graphql;
+mod request;
+mod response;
+
+pub use self::graphql::{GraphQL, Ide};
+pub use self::request::{GraphQLRequest, GraphQLBatchRequest};
+pub use self::response::GraphQLResponse;
+
+/// The `async-graphql` crate, re-exported.
+pub use async_graphql;
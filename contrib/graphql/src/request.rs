@@ -0,0 +1,205 @@
+use std::any::Any;
+
+use async_graphql::http::{parse_query_string, receive_batch_body, MultipartOptions};
+use async_graphql::{BatchRequest, Executor, ParseRequestError, Request as Query};
+use rocket::data::{self, Data, FromData, Limits};
+use rocket::request::{self, FromRequest, Request};
+use rocket::http::Status;
+use rocket::outcome::Outcome::*;
+
+use crate::GraphQLResponse;
+
+/// A data and request guard for a single GraphQL request.
+///
+/// As a data guard, `GraphQLRequest` parses a `POST` request body in JSON or
+/// as a [multipart request] with file uploads, failing with `400 Bad Request`
+/// if the body contains a batch. As a request guard, it parses the request's
+/// query string as in a `GET` request, forwarding if there is no query string.
+/// In both cases, it fails with `400 Bad Request` if the request is invalid.
+///
+/// The body is limited to the `graphql` [limit](rocket::data::Limits) or, if
+/// it is unset, to 1MiB. Larger bodies fail with `413 Payload Too Large`.
+///
+/// [multipart request]: https://github.com/jaydenseric/graphql-multipart-request-spec
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+/// use rocket::State;
+/// use rocket_graphql::{GraphQLRequest, GraphQLResponse};
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn answer(&self) -> i32 { 42 }
+/// }
+///
+/// type AppSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+///
+/// #[get("/graphql")]
+/// async fn query(schema: &State<AppSchema>, request: GraphQLRequest) -> GraphQLResponse {
+///     request.execute(schema.inner()).await
+/// }
+///
+/// #[post("/graphql", data = "<request>")]
+/// async fn mutate(schema: &State<AppSchema>, request: GraphQLRequest) -> GraphQLResponse {
+///     request.execute(schema.inner()).await
+/// }
+/// ```
+#[derive(Debug)]
+pub struct GraphQLRequest(pub Query);
+
+/// A data guard for a single or batched GraphQL request.
+///
+/// Like [`GraphQLRequest`], but additionally accepts a JSON array of requests
+/// in a `POST` request body. As a request guard, it parses the query string
+/// into a single request.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+/// use rocket::State;
+/// use rocket_graphql::{GraphQLBatchRequest, GraphQLResponse};
+///
+/// # struct Query;
+/// # #[Object] impl Query { async fn answer(&self) -> i32 { 42 } }
+/// type AppSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+///
+/// #[post("/graphql", data = "<request>")]
+/// async fn graphql(schema: &State<AppSchema>, request: GraphQLBatchRequest) -> GraphQLResponse {
+///     request.execute(schema.inner()).await
+/// }
+/// ```
+#[derive(Debug)]
+pub struct GraphQLBatchRequest(pub BatchRequest);
+
+impl GraphQLRequest {
+    /// Adds `data` to the request's context data, retrievable by resolvers
+    /// with `Context::data()`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+    /// use rocket::State;
+    /// use rocket::http::CookieJar;
+    /// use rocket_graphql::{GraphQLRequest, GraphQLResponse};
+    ///
+    /// # struct Query;
+    /// # #[Object] impl Query { async fn answer(&self) -> i32 { 42 } }
+    /// # type AppSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+    /// struct User(String);
+    ///
+    /// #[post("/graphql", data = "<request>")]
+    /// async fn graphql(
+    ///     schema: &State<AppSchema>,
+    ///     jar: &CookieJar<'_>,
+    ///     request: GraphQLRequest
+    /// ) -> GraphQLResponse {
+    ///     let user = jar.get("user").map(|c| User(c.value().to_string()));
+    ///     request.data(user).execute(schema.inner()).await
+    /// }
+    /// ```
+    pub fn data<D: Any + Send + Sync>(mut self, data: D) -> Self {
+        self.0 = self.0.data(data);
+        self
+    }
+
+    /// Executes the request with `executor`, typically a `Schema`.
+    pub async fn execute<E: Executor>(self, executor: &E) -> GraphQLResponse {
+        executor.execute(self.0).await.into()
+    }
+}
+
+impl GraphQLBatchRequest {
+    /// Adds `data` to the context data of every request in the batch,
+    /// retrievable by resolvers with `Context::data()`.
+    pub fn data<D: Any + Clone + Send + Sync>(mut self, data: D) -> Self {
+        self.0 = self.0.data(data);
+        self
+    }
+
+    /// Executes the requests with `executor`, typically a `Schema`.
+    pub async fn execute<E: Executor>(self, executor: &E) -> GraphQLResponse {
+        executor.execute_batch(self.0).await.into()
+    }
+}
+
+/// Returns the status code for a request that failed to parse with `error`.
+fn status(error: &ParseRequestError) -> Status {
+    match error {
+        ParseRequestError::PayloadTooLarge => Status::PayloadTooLarge,
+        ParseRequestError::Io(_) => Status::InternalServerError,
+        _ => Status::BadRequest,
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromData<'r> for GraphQLBatchRequest {
+    type Error = ParseRequestError;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+        let limit = req.limits().get("graphql").unwrap_or(Limits::JSON);
+        let result = async {
+            let body = data.open(limit).into_bytes().await?;
+            if !body.is_complete() {
+                return Err(ParseRequestError::PayloadTooLarge);
+            }
+
+            let content_type = req.content_type().map(|ct| ct.to_string());
+            let options = MultipartOptions::default().max_file_size(limit.as_u64() as usize);
+            receive_batch_body(content_type, body.as_slice(), options).await
+        };
+
+        match result.await {
+            Ok(batch) => Success(GraphQLBatchRequest(batch)),
+            Err(e) => Failure((status(&e), e)),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromData<'r> for GraphQLRequest {
+    type Error = ParseRequestError;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+        let batch = rocket::outcome::try_outcome!(GraphQLBatchRequest::from_data(req, data).await);
+        match batch.0.into_single() {
+            Ok(request) => Success(GraphQLRequest(request)),
+            Err(e) => Failure((status(&e), e)),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for GraphQLRequest {
+    type Error = ParseRequestError;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let query = match req.uri().query() {
+            Some(query) => query,
+            None => return Forward(()),
+        };
+
+        match parse_query_string(query.as_str()) {
+            Ok(request) => Success(GraphQLRequest(request)),
+            Err(e) => Failure((Status::BadRequest, e)),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for GraphQLBatchRequest {
+    type Error = ParseRequestError;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        GraphQLRequest::from_request(req).await.map(|r| GraphQLBatchRequest(r.0.into()))
+    }
+}
+
@@ -0,0 +1,57 @@
+use std::io::Cursor;
+
+use async_graphql::{BatchResponse, Response as QueryResponse};
+use rocket::request::Request;
+use rocket::response::{self, Responder, Response};
+use rocket::http::ContentType;
+
+/// A responder for the response to a single or batched GraphQL request.
+///
+/// The response is serialized as JSON with a status of `200 OK`, as per the
+/// GraphQL over HTTP specification, even if it contains errors. The
+/// `Cache-Control` header is set according to the cache control of the
+/// executed query, and any HTTP headers set by resolvers are included.
+///
+/// A `GraphQLResponse` is returned by [`GraphQLRequest::execute()`] and
+/// [`GraphQLBatchRequest::execute()`], and can also be created from an
+/// `async_graphql::Response` or `BatchResponse` via `From`.
+///
+/// [`GraphQLRequest::execute()`]: crate::GraphQLRequest::execute()
+/// [`GraphQLBatchRequest::execute()`]: crate::GraphQLBatchRequest::execute()
+#[derive(Debug)]
+pub struct GraphQLResponse(pub BatchResponse);
+
+impl From<BatchResponse> for GraphQLResponse {
+    fn from(response: BatchResponse) -> Self {
+        GraphQLResponse(response)
+    }
+}
+
+impl From<QueryResponse> for GraphQLResponse {
+    fn from(response: QueryResponse) -> Self {
+        GraphQLResponse(response.into())
+    }
+}
+
+impl<'r> Responder<'r, 'static> for GraphQLResponse {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        let body = serde_json::to_string(&self.0).map_err(|e| {
+            error_!("GraphQL response failed to serialize: {}", e);
+            rocket::http::Status::InternalServerError
+        })?;
+
+        let mut response = Response::build();
+        response.header(ContentType::JSON).sized_body(body.len(), Cursor::new(body));
+        if let Some(cache_control) = self.0.cache_control().value() {
+            response.raw_header("Cache-Control", cache_control);
+        }
+
+        for (name, value) in self.0.http_headers_iter() {
+            if let Ok(value) = value.to_str() {
+                response.raw_header_adjoin(name.as_str().to_string(), value.to_string());
+            }
+        }
+
+        response.ok()
+    }
+}
@@ -0,0 +1,156 @@
+#[macro_use] extern crate rocket;
+
+use rocket::State;
+use rocket::config::Config;
+use rocket::data::{Limits, ToByteUnit};
+use rocket::http::{ContentType, Status, Header};
+use rocket::local::blocking::Client;
+use rocket::serde::json::{json, Value};
+
+use rocket_graphql::{GraphQL, GraphQLRequest, GraphQLResponse, Ide};
+use rocket_graphql::async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Schema};
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn add(&self, a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    async fn user(&self, ctx: &Context<'_>) -> Option<String> {
+        ctx.data_opt::<String>().cloned()
+    }
+}
+
+type AppSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+fn schema() -> AppSchema {
+    Schema::new(Query, EmptyMutation, EmptySubscription)
+}
+
+#[post("/custom", data = "<request>")]
+async fn custom(schema: &State<AppSchema>, request: GraphQLRequest) -> GraphQLResponse {
+    request.data("bob".to_string()).execute(schema.inner()).await
+}
+
+fn client(graphql: GraphQL<AppSchema>) -> Client {
+    let config = Config {
+        limits: Limits::default().limit("graphql", 64.bytes()),
+        ..Config::debug_default()
+    };
+
+    let rocket = rocket::custom(config)
+        .manage(schema())
+        .mount("/graphql", graphql)
+        .mount("/", routes![custom]);
+
+    Client::debug(rocket).unwrap()
+}
+
+#[test]
+fn post_and_get_queries() {
+    let client = client(GraphQL::new(schema()));
+
+    let response = client.post("/graphql")
+        .header(ContentType::JSON)
+        .body(r#"{ "query": "{ add(a: 1, b: 2) }" }"#)
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+    assert_eq!(response.into_json::<Value>().unwrap(), json!({ "data": { "add": 3 } }));
+
+    let response = client.get("/graphql?query=%7B%20add(a%3A%202%2C%20b%3A%203)%20%7D").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_json::<Value>().unwrap(), json!({ "data": { "add": 5 } }));
+
+    let response = client.post("/graphql")
+        .header(ContentType::JSON)
+        .body(r#"{ "query": "{ missing }" }"#)
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+    let body = response.into_json::<Value>().unwrap();
+    assert!(body["errors"][0]["message"].as_str().unwrap().contains("missing"));
+}
+
+#[test]
+fn batched_queries() {
+    let client = client(GraphQL::new(schema()));
+    let response = client.post("/graphql")
+        .header(ContentType::JSON)
+        .body(r#"[{ "query": "{ add(a: 1, b: 1) }" }, { "query": "{ user }" }]"#)
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_json::<Value>().unwrap(), json!([
+        { "data": { "add": 2 } },
+        { "data": { "user": null } }
+    ]));
+}
+
+#[test]
+fn invalid_requests() {
+    let client = client(GraphQL::new(schema()));
+
+    let response = client.post("/graphql").header(ContentType::JSON).body("{").dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = client.get("/graphql?query=%7B%20add%20%7D&variables=%7B").dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let query = format!(r#"{{ "query": "{{ add(a: 1, b: 2) }}", "x": "{}" }}"#, "a".repeat(64));
+    let response = client.post("/graphql").header(ContentType::JSON).body(query).dispatch();
+    assert_eq!(response.status(), Status::PayloadTooLarge);
+
+    let response = client.post("/custom")
+        .header(ContentType::JSON)
+        .body(r#"[{ "query": "{ user }" }, { "query": "{ user }" }]"#)
+        .dispatch();
+
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+#[test]
+fn custom_route_context_data() {
+    let client = client(GraphQL::new(schema()));
+    let response = client.post("/custom")
+        .header(ContentType::JSON)
+        .body(r#"{ "query": "{ user }" }"#)
+        .dispatch();
+
+    assert_eq!(response.into_json::<Value>().unwrap(), json!({ "data": { "user": "bob" } }));
+}
+
+#[test]
+fn ide_pages() {
+    let client = client(GraphQL::new(schema()));
+    assert_eq!(client.get("/graphql").dispatch().status(), Status::NotFound);
+
+    let client = self::client(GraphQL::new(schema()).ide(Ide::GraphiQL));
+    let response = client.get("/graphql").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+    let page = response.into_string().unwrap();
+    assert!(page.contains("graphiql"));
+    assert!(page.contains("/graphql"));
+
+    let client = self::client(GraphQL::new(schema()).ide(Ide::Playground));
+    let page = client.get("/graphql").dispatch().into_string().unwrap();
+    assert!(page.contains("GraphQL Playground"));
+}
+
+#[test]
+fn websocket_upgrade_requires_known_protocol() {
+    let client = client(GraphQL::new(schema()));
+    let response = client.get("/graphql")
+        .header(Header::new("Connection", "upgrade"))
+        .header(Header::new("Upgrade", "websocket"))
+        .header(Header::new("Sec-WebSocket-Version", "13"))
+        .header(Header::new("Sec-WebSocket-Key", "dGhlIHNhbXBsZSBub25jZQ=="))
+        .header(Header::new("Sec-WebSocket-Protocol", "unknown"))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::BadRequest);
+}
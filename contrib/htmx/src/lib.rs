@@ -0,0 +1,62 @@
+//! [HTMX] request guards and responders for Rocket.
+//!
+//! HTMX communicates with the server through `HX-*` request and response
+//! headers. This crate provides typed access to them:
+//!
+//!   * [`HxRequest`], a request guard for requests made by HTMX, exposing the
+//!     `HX-*` request headers, and [`HxTarget`], a request guard for the `id`
+//!     of the element targeted by a request.
+//!   * [`HxResponse`], a responder that wraps another and adds response
+//!     headers such as `HX-Trigger` and `HX-Push-Url`, triggering
+//!     [`HxEvent`]s on the client, and [`HxRedirect`], a responder for
+//!     client-side redirects via `HX-Redirect`.
+//!
+//! [HTMX]: https://htmx.org
+//!
+//! # Usage
+//!
+//!   1. Depend on `rocket_htmx`:
+//!
+//!      ```toml
+//!      [dependencies.rocket_htmx]
+//!      version = "0.1.0-rc.2"
+//!      ```
+//!
+//!   2. Use the guards and responders in your routes:
+//!
+//!      ```rust
+//!      # #[macro_use] extern crate rocket;
+//!      use rocket::response::content::RawHtml;
+//!      use rocket_htmx::{HxRequest, HxResponse};
+//!
+//!      const LIST: &str = "<ul id=\"tasks\"><li>Fix the sink.</li></ul>";
+//!
+//!      #[get("/tasks")]
+//!      fn tasks(hx: Option<HxRequest<'_>>) -> RawHtml<String> {
+//!          match hx {
+//!              Some(_) => RawHtml(LIST.into()),
+//!              None => RawHtml(format!("<html><body>{}</body></html>", LIST)),
+//!          }
+//!      }
+//!
+//!      #[delete("/tasks")]
+//!      fn clear(_hx: HxRequest<'_>) -> HxResponse<RawHtml<&'static str>> {
+//!          HxResponse::new(RawHtml("<ul id=\"tasks\"></ul>"))
+//!              .trigger("tasksCleared")
+//!              .push_url(uri!(tasks))
+//!      }
+//!
+//!      #[launch]
+//!      fn rocket() -> _ {
+//!          rocket::build().mount("/", routes![tasks, clear])
+//!      }
+//!      ```
+
+#[macro_use]
+extern crate rocket;
+
+mod request;
+mod response;
+
+pub use self::request::{HxRequest, HxTarget};
+pub use self::response::{HxEvent, HxResponse, HxRedirect};
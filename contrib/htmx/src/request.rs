@@ -0,0 +1,171 @@
+use std::fmt;
+use std::ops::Deref;
+use std::convert::Infallible;
+
+use rocket::request::{self, FromRequest, Request};
+use rocket::outcome::IntoOutcome;
+use rocket::http::HeaderMap;
+
+/// A request guard for requests made by HTMX.
+///
+/// Succeeds if the request has an `HX-Request: true` header and forwards
+/// otherwise. The remaining `HX-*` request headers are available through the
+/// guard's methods. As the guard forwards, `Option<HxRequest>` can be used to
+/// serve the same route to HTMX and regular requests:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_htmx::HxRequest;
+///
+/// #[get("/tasks")]
+/// fn tasks(hx: Option<HxRequest<'_>>) -> &'static str {
+///     match hx {
+///         // Only the task list is swapped in by HTMX.
+///         Some(hx) if !hx.boosted() => "<ul><li>Fix the sink.</li></ul>",
+///         _ => "<html><body><ul><li>Fix the sink.</li></ul></body></html>",
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HxRequest<'r> {
+    boosted: bool,
+    current_url: Option<&'r str>,
+    history_restore_request: bool,
+    prompt: Option<&'r str>,
+    target: Option<&'r str>,
+    trigger: Option<&'r str>,
+    trigger_name: Option<&'r str>,
+}
+
+/// Returns `true` if the header `name` is present and `true`.
+fn flag(headers: &HeaderMap<'_>, name: &str) -> bool {
+    headers.get_one(name) == Some("true")
+}
+
+impl<'r> HxRequest<'r> {
+    /// Returns `true` if the request was made by an element using
+    /// `hx-boost`, as indicated by the `HX-Boosted` header.
+    pub fn boosted(&self) -> bool {
+        self.boosted
+    }
+
+    /// Returns the browser's current URL, the value of the `HX-Current-URL`
+    /// header, if there is one.
+    pub fn current_url(&self) -> Option<&'r str> {
+        self.current_url
+    }
+
+    /// Returns `true` if the request is for history restoration after a miss
+    /// in the local history cache, as indicated by the
+    /// `HX-History-Restore-Request` header.
+    pub fn history_restore_request(&self) -> bool {
+        self.history_restore_request
+    }
+
+    /// Returns the user's response to an `hx-prompt`, the value of the
+    /// `HX-Prompt` header, if there is one.
+    pub fn prompt(&self) -> Option<&'r str> {
+        self.prompt
+    }
+
+    /// Returns the `id` of the target element, the value of the `HX-Target`
+    /// header, if there is one. See also [`HxTarget`].
+    pub fn target(&self) -> Option<&'r str> {
+        self.target
+    }
+
+    /// Returns the `id` of the triggered element, the value of the
+    /// `HX-Trigger` header, if there is one.
+    pub fn trigger(&self) -> Option<&'r str> {
+        self.trigger
+    }
+
+    /// Returns the `name` of the triggered element, the value of the
+    /// `HX-Trigger-Name` header, if there is one.
+    pub fn trigger_name(&self) -> Option<&'r str> {
+        self.trigger_name
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for HxRequest<'r> {
+    type Error = Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let headers = req.headers();
+        flag(headers, "HX-Request")
+            .then(|| HxRequest {
+                boosted: flag(headers, "HX-Boosted"),
+                current_url: headers.get_one("HX-Current-URL"),
+                history_restore_request: flag(headers, "HX-History-Restore-Request"),
+                prompt: headers.get_one("HX-Prompt"),
+                target: headers.get_one("HX-Target"),
+                trigger: headers.get_one("HX-Trigger"),
+                trigger_name: headers.get_one("HX-Trigger-Name"),
+            })
+            .or_forward(())
+    }
+}
+
+/// A request guard for the `id` of the element targeted by an HTMX request.
+///
+/// Succeeds with the value of the `HX-Target` header of an [`HxRequest`] and
+/// forwards if the request wasn't made by HTMX or has no target.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_htmx::HxTarget;
+///
+/// #[get("/search", rank = 1)]
+/// fn results(target: HxTarget<'_>) -> String {
+///     format!("<div id=\"{}\">No results.</div>", target)
+/// }
+///
+/// #[get("/search", rank = 2)]
+/// fn page() -> &'static str {
+///     "<html><body><div id=\"results\">No results.</div></body></html>"
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HxTarget<'r>(&'r str);
+
+impl<'r> HxTarget<'r> {
+    /// Returns the target's `id` as a string slice.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_htmx::HxTarget;
+    ///
+    /// fn f(target: HxTarget<'_>) {
+    ///     let id: &str = target.as_str();
+    /// }
+    /// ```
+    pub fn as_str(&self) -> &'r str {
+        self.0
+    }
+}
+
+impl Deref for HxTarget<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl fmt::Display for HxTarget<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for HxTarget<'r> {
+    type Error = Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        HxRequest::from_request(req).await
+            .and_then(|hx| hx.target().map(HxTarget).or_forward(()))
+    }
+}
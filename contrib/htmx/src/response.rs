@@ -0,0 +1,347 @@
+use std::borrow::Cow;
+use std::convert::TryInto;
+
+use rocket::request::Request;
+use rocket::response::{self, Responder, Response};
+use rocket::http::{Header, Status, uri::Reference};
+use rocket::serde::Serialize;
+use rocket::serde::json::{self, Value, serde_json::Map};
+
+/// An event triggered on the client by an [`HxResponse`].
+///
+/// An event has a name and, optionally, a `detail` which is serialized as JSON
+/// and made available to event listeners as `event.detail`. An `HxEvent`
+/// without a detail can be created from a string via `From`.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::serde::json::json;
+/// use rocket_htmx::HxEvent;
+///
+/// let event = HxEvent::new("taskAdded").detail(json!({ "id": 10 }));
+/// let event: HxEvent = "taskAdded".into();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct HxEvent {
+    name: Cow<'static, str>,
+    detail: Result<Option<Value>, String>,
+}
+
+impl HxEvent {
+    /// Returns an event named `name` without a detail.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_htmx::HxEvent;
+    ///
+    /// let event = HxEvent::new("taskAdded");
+    /// ```
+    pub fn new<N: Into<Cow<'static, str>>>(name: N) -> Self {
+        HxEvent { name: name.into(), detail: Ok(None) }
+    }
+
+    /// Sets the event's detail to `detail`, serialized as JSON. If `detail`
+    /// fails to serialize, the response the event is triggered by fails with
+    /// `500 Internal Server Error`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::serde::Serialize;
+    /// use rocket_htmx::HxEvent;
+    ///
+    /// #[derive(Serialize)]
+    /// # #[serde(crate = "rocket::serde")]
+    /// struct Task { id: usize, done: bool }
+    ///
+    /// let event = HxEvent::new("taskAdded").detail(Task { id: 10, done: false });
+    /// ```
+    pub fn detail<T: Serialize>(mut self, detail: T) -> Self {
+        self.detail = json::to_value(detail).map(Some).map_err(|e| e.to_string());
+        self
+    }
+
+    /// Returns the name of the event.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_htmx::HxEvent;
+    ///
+    /// let event = HxEvent::new("taskAdded");
+    /// assert_eq!(event.name(), "taskAdded");
+    /// ```
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl From<&'static str> for HxEvent {
+    fn from(name: &'static str) -> Self {
+        HxEvent::new(name)
+    }
+}
+
+impl From<String> for HxEvent {
+    fn from(name: String) -> Self {
+        HxEvent::new(name)
+    }
+}
+
+/// Returns the value of a trigger header for `events`: a comma-separated list
+/// of names if no event has a detail, and a JSON object mapping names to
+/// details, or `null`, otherwise.
+fn trigger_header(events: &[HxEvent]) -> Result<String, String> {
+    if events.iter().all(|e| e.detail == Ok(None)) {
+        let names: Vec<_> = events.iter().map(|e| e.name()).collect();
+        return Ok(names.join(", "));
+    }
+
+    let mut map = Map::new();
+    for event in events {
+        let detail = event.detail.clone()
+            .map_err(|e| format!("detail of '{}' failed to serialize: {}", event.name, e))?;
+
+        map.insert(event.name.to_string(), detail.unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Object(map).to_string())
+}
+
+/// A responder that wraps another and adds HTMX response headers.
+///
+/// An `HxResponse` sets the status and body of the wrapped responder and adds
+/// the headers configured by its builder methods:
+///
+///   * [`trigger()`](HxResponse::trigger()),
+///     [`trigger_after_settle()`](HxResponse::trigger_after_settle()), and
+///     [`trigger_after_swap()`](HxResponse::trigger_after_swap()) set
+///     `HX-Trigger`, `HX-Trigger-After-Settle`, and `HX-Trigger-After-Swap`.
+///   * [`push_url()`](HxResponse::push_url()) and
+///     [`replace_url()`](HxResponse::replace_url()) set `HX-Push-Url` and
+///     `HX-Replace-Url`.
+///   * [`redirect()`](HxResponse::redirect()) and
+///     [`refresh()`](HxResponse::refresh()) set `HX-Redirect` and
+///     `HX-Refresh`.
+///   * [`retarget()`](HxResponse::retarget()),
+///     [`reswap()`](HxResponse::reswap()), and
+///     [`reselect()`](HxResponse::reselect()) set `HX-Retarget`,
+///     `HX-Reswap`, and `HX-Reselect`.
+///
+/// Events triggered more than once with the same timing are combined into a
+/// single header. URIs are validated as in [`Redirect`]: if a URI is invalid,
+/// an error is logged and the response fails with `500 Internal Server
+/// Error`.
+///
+/// [`Redirect`]: rocket::response::Redirect
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::serde::json::json;
+/// use rocket_htmx::{HxEvent, HxResponse};
+///
+/// #[post("/tasks/<id>/done")]
+/// fn done(id: usize) -> HxResponse<&'static str> {
+///     HxResponse::new("<li class=\"done\">Fix the sink.</li>")
+///         .trigger(HxEvent::new("taskDone").detail(json!({ "id": id })))
+///         .trigger_after_settle("refreshCounts")
+///         .push_url(uri!(done(id)))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct HxResponse<R> {
+    responder: R,
+    headers: Vec<Header<'static>>,
+    triggers: [Vec<HxEvent>; 3],
+    error: Option<String>,
+}
+
+impl<R> HxResponse<R> {
+    /// The names of the trigger headers, in the order of `triggers`.
+    const TRIGGER_HEADERS: [&'static str; 3] =
+        ["HX-Trigger", "HX-Trigger-After-Settle", "HX-Trigger-After-Swap"];
+
+    /// Returns an `HxResponse` wrapping `responder` without any HTMX headers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_htmx::HxResponse;
+    ///
+    /// let response = HxResponse::new("<p>Hello!</p>");
+    /// ```
+    pub fn new(responder: R) -> Self {
+        HxResponse { responder, headers: vec![], triggers: Default::default(), error: None }
+    }
+
+    fn header<V: Into<Cow<'static, str>>>(mut self, name: &'static str, value: V) -> Self {
+        self.headers.push(Header::new(name, value));
+        self
+    }
+
+    fn uri_header<U>(mut self, name: &'static str, uri: U) -> Self
+        where U: TryInto<Reference<'static>>
+    {
+        match uri.try_into() {
+            Ok(uri) => self.header(name, uri.to_string()),
+            Err(_) => {
+                self.error.get_or_insert_with(|| format!("Invalid URI used for '{}'.", name));
+                self
+            }
+        }
+    }
+
+    /// Triggers `event` on the client as soon as the response is received.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_htmx::{HxEvent, HxResponse};
+    ///
+    /// let response = HxResponse::new(())
+    ///     .trigger("taskAdded")
+    ///     .trigger(HxEvent::new("notify").detail("Task added."));
+    /// ```
+    pub fn trigger<E: Into<HxEvent>>(mut self, event: E) -> Self {
+        self.triggers[0].push(event.into());
+        self
+    }
+
+    /// Triggers `event` on the client after the settling step.
+    pub fn trigger_after_settle<E: Into<HxEvent>>(mut self, event: E) -> Self {
+        self.triggers[1].push(event.into());
+        self
+    }
+
+    /// Triggers `event` on the client after the swap step.
+    pub fn trigger_after_swap<E: Into<HxEvent>>(mut self, event: E) -> Self {
+        self.triggers[2].push(event.into());
+        self
+    }
+
+    /// Pushes `uri` into the browser's history.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_htmx::HxResponse;
+    ///
+    /// let response = HxResponse::new("<p>Page 2</p>").push_url("/tasks?page=2");
+    /// ```
+    pub fn push_url<U: TryInto<Reference<'static>>>(self, uri: U) -> Self {
+        self.uri_header("HX-Push-Url", uri)
+    }
+
+    /// Replaces the current URL in the browser's location bar with `uri`.
+    pub fn replace_url<U: TryInto<Reference<'static>>>(self, uri: U) -> Self {
+        self.uri_header("HX-Replace-Url", uri)
+    }
+
+    /// Performs a client-side redirect to `uri`, reloading the page. See also
+    /// [`HxRedirect`].
+    pub fn redirect<U: TryInto<Reference<'static>>>(self, uri: U) -> Self {
+        self.uri_header("HX-Redirect", uri)
+    }
+
+    /// Performs a full refresh of the page on the client.
+    pub fn refresh(self) -> Self {
+        self.header("HX-Refresh", "true")
+    }
+
+    /// Swaps the response into the elements matching the CSS `selector`
+    /// instead of the request's target.
+    pub fn retarget<S: Into<Cow<'static, str>>>(self, selector: S) -> Self {
+        self.header("HX-Retarget", selector)
+    }
+
+    /// Swaps the response in as specified by `swap`, an `hx-swap` value such
+    /// as `outerHTML` or `beforeend`, instead of as specified by the request's
+    /// element.
+    pub fn reswap<S: Into<Cow<'static, str>>>(self, swap: S) -> Self {
+        self.header("HX-Reswap", swap)
+    }
+
+    /// Swaps in only the part of the response matching the CSS `selector`.
+    pub fn reselect<S: Into<Cow<'static, str>>>(self, selector: S) -> Self {
+        self.header("HX-Reselect", selector)
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for HxResponse<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        if let Some(error) = self.error {
+            error_!("{}", error);
+            return Err(Status::InternalServerError);
+        }
+
+        let mut response = self.responder.respond_to(req)?;
+        for header in self.headers {
+            response.set_header(header);
+        }
+
+        let triggers = Self::TRIGGER_HEADERS.iter().zip(self.triggers.iter());
+        for (name, events) in triggers.filter(|(_, events)| !events.is_empty()) {
+            let value = trigger_header(events).map_err(|e| {
+                error_!("Invalid '{}' event: {}", name, e);
+                Status::InternalServerError
+            })?;
+
+            response.set_raw_header(*name, value);
+        }
+
+        Ok(response)
+    }
+}
+
+/// A responder that redirects an HTMX request on the client.
+///
+/// HTMX doesn't expose `3xx` redirects of its requests to the page; instead,
+/// they are followed transparently and the target is swapped in. An
+/// `HxRedirect` responds with `200 OK` and an `HX-Redirect` header, causing
+/// HTMX to navigate the browser to the URI instead. URIs are validated as in
+/// [`Redirect`](rocket::response::Redirect).
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_htmx::HxRedirect;
+///
+/// #[post("/logout")]
+/// fn logout() -> HxRedirect {
+///     HxRedirect::to(uri!("/login"))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct HxRedirect(Option<Reference<'static>>);
+
+impl HxRedirect {
+    /// Returns an `HxRedirect` to `uri`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_htmx::HxRedirect;
+    ///
+    /// let redirect = HxRedirect::to("/login");
+    /// ```
+    pub fn to<U: TryInto<Reference<'static>>>(uri: U) -> HxRedirect {
+        HxRedirect(uri.try_into().ok())
+    }
+}
+
+impl<'r> Responder<'r, 'static> for HxRedirect {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        match self.0 {
+            Some(uri) => Response::build().raw_header("HX-Redirect", uri.to_string()).ok(),
+            None => {
+                error_!("Invalid URI used for redirect.");
+                Err(Status::InternalServerError)
+            }
+        }
+    }
+}
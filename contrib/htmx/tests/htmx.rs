@@ -0,0 +1,177 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+use rocket::serde::json::{json, Value};
+
+use rocket_htmx::{HxEvent, HxRequest, HxResponse, HxRedirect, HxTarget};
+
+#[get("/")]
+fn index(hx: Option<HxRequest<'_>>) -> String {
+    match hx {
+        Some(hx) => format!("hx: boosted={} current={:?} restore={} prompt={:?} trigger={:?}/{:?}",
+            hx.boosted(), hx.current_url(), hx.history_restore_request(), hx.prompt(),
+            hx.trigger(), hx.trigger_name()),
+        None => "page".into(),
+    }
+}
+
+#[get("/target", rank = 1)]
+fn target(target: HxTarget<'_>) -> String {
+    format!("target: {}", target)
+}
+
+#[get("/target", rank = 2)]
+fn no_target() -> &'static str {
+    "no target"
+}
+
+#[get("/triggers?<detail>")]
+fn triggers(detail: bool) -> HxResponse<&'static str> {
+    let response = HxResponse::new("triggered").trigger("a");
+    let response = match detail {
+        true => response.trigger(HxEvent::new("b").detail(json!({ "id": 1 }))),
+        false => response.trigger("b"),
+    };
+
+    response.trigger_after_swap(String::from("c"))
+}
+
+#[get("/headers")]
+fn headers() -> HxResponse<(Status, &'static str)> {
+    HxResponse::new((Status::Created, "created"))
+        .push_url(uri!(index))
+        .replace_url("/replaced?x=1")
+        .retarget("#tasks")
+        .reswap("outerHTML")
+        .reselect(".task")
+        .refresh()
+}
+
+#[get("/bad_uri")]
+fn bad_uri() -> HxResponse<&'static str> {
+    HxResponse::new("never").push_url("/<invalid>")
+}
+
+#[get("/redirect")]
+fn redirect() -> HxRedirect {
+    HxRedirect::to(uri!(index))
+}
+
+#[get("/bad_redirect")]
+fn bad_redirect() -> HxRedirect {
+    HxRedirect::to("a b")
+}
+
+fn client() -> Client {
+    let routes = routes![
+        index, target, no_target, triggers, headers, bad_uri, redirect, bad_redirect
+    ];
+
+    Client::debug_with(routes).unwrap()
+}
+
+#[test]
+fn hx_request_guard() {
+    let client = client();
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "page");
+
+    let response = client.get("/").header(Header::new("HX-Request", "false")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "page");
+
+    let response = client.get("/").header(Header::new("HX-Request", "true")).dispatch();
+    assert_eq!(response.into_string().unwrap(),
+        "hx: boosted=false current=None restore=false prompt=None trigger=None/None");
+
+    let response = client.get("/")
+        .header(Header::new("HX-Request", "true"))
+        .header(Header::new("HX-Boosted", "true"))
+        .header(Header::new("HX-Current-URL", "http://example.com/tasks"))
+        .header(Header::new("HX-History-Restore-Request", "true"))
+        .header(Header::new("HX-Prompt", "yes"))
+        .header(Header::new("HX-Trigger", "delete-1"))
+        .header(Header::new("HX-Trigger-Name", "delete"))
+        .dispatch();
+
+    assert_eq!(response.into_string().unwrap(),
+        "hx: boosted=true current=Some(\"http://example.com/tasks\") restore=true \
+        prompt=Some(\"yes\") trigger=Some(\"delete-1\")/Some(\"delete\")");
+}
+
+#[test]
+fn hx_target_guard() {
+    let client = client();
+    let response = client.get("/target").header(Header::new("HX-Target", "tasks")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "no target");
+
+    let response = client.get("/target").header(Header::new("HX-Request", "true")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "no target");
+
+    let response = client.get("/target")
+        .header(Header::new("HX-Request", "true"))
+        .header(Header::new("HX-Target", "tasks"))
+        .dispatch();
+
+    assert_eq!(response.into_string().unwrap(), "target: tasks");
+}
+
+#[test]
+fn trigger_headers() {
+    let client = client();
+    let response = client.get("/triggers?detail=false").dispatch();
+    assert_eq!(response.headers().get_one("HX-Trigger"), Some("a, b"));
+    assert_eq!(response.headers().get_one("HX-Trigger-After-Swap"), Some("c"));
+    assert_eq!(response.headers().get_one("HX-Trigger-After-Settle"), None);
+    assert_eq!(response.into_string().unwrap(), "triggered");
+
+    let response = client.get("/triggers?detail=true").dispatch();
+    let trigger = response.headers().get_one("HX-Trigger").unwrap();
+    let trigger: Value = rocket::serde::json::from_str(trigger).unwrap();
+    assert_eq!(trigger, json!({ "a": null, "b": { "id": 1 } }));
+    assert_eq!(response.headers().get_one("HX-Trigger-After-Swap"), Some("c"));
+}
+
+#[test]
+fn response_headers() {
+    let client = client();
+    let response = client.get("/headers").dispatch();
+    assert_eq!(response.status(), Status::Created);
+
+    let headers = response.headers();
+    assert_eq!(headers.get_one("HX-Push-Url"), Some("/"));
+    assert_eq!(headers.get_one("HX-Replace-Url"), Some("/replaced?x=1"));
+    assert_eq!(headers.get_one("HX-Retarget"), Some("#tasks"));
+    assert_eq!(headers.get_one("HX-Reswap"), Some("outerHTML"));
+    assert_eq!(headers.get_one("HX-Reselect"), Some(".task"));
+    assert_eq!(headers.get_one("HX-Refresh"), Some("true"));
+    assert_eq!(response.into_string().unwrap(), "created");
+
+    let response = client.get("/bad_uri").dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+}
+
+#[test]
+fn hx_redirect() {
+    let client = client();
+    let response = client.get("/redirect").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.headers().get_one("HX-Redirect"), Some("/"));
+    assert_eq!(response.headers().get_one("Location"), None);
+
+    let response = client.get("/bad_redirect").dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+}
+
+#[test]
+fn event_detail_serialization_failure() {
+    use std::collections::HashMap;
+
+    let mut detail = HashMap::new();
+    detail.insert((1, 2), "tuple keys can't be serialized to JSON");
+
+    let client = Client::debug_with(vec![]).unwrap();
+    let request = client.get("/");
+    let response = HxResponse::new("body").trigger(HxEvent::new("x").detail(detail));
+    let result = rocket::response::Responder::respond_to(response, request.inner());
+    assert_eq!(result.unwrap_err(), Status::InternalServerError);
+}
@@ -0,0 +1,284 @@
+use std::{fmt, fs, io};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use fluent_bundle::{FluentArgs, FluentError, FluentResource};
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::resolver::ResolverError;
+use fluent_bundle::FluentError::ResolverError as Unresolved;
+use fluent_langneg::{negotiate_languages, accepted_languages, NegotiationStrategy};
+use fluent_syntax::ast::Entry;
+use fluent_syntax::parser::ParserError;
+use unic_langid::LanguageIdentifier;
+
+/// A collection of [Fluent] message bundles, one per language.
+///
+/// A catalog is loaded from a directory containing one subdirectory per
+/// language, named by the language's [BCP 47] tag, each containing any number
+/// of Fluent (`.ftl`) files:
+///
+/// ```text
+/// locales/
+/// ├── en-US/
+/// │   ├── main.ftl
+/// │   └── errors.ftl
+/// └── fr/
+///     └── main.ftl
+/// ```
+///
+/// Catalogs are typically loaded by the [`I18n`](crate::I18n) fairing, which
+/// places the catalog in managed state, and used through the
+/// [`Locale`](crate::Locale) request guard.
+///
+/// Messages are looked up in a list of languages, usually negotiated from a
+/// request's `Accept-Language` header, in order, falling back to the
+/// catalog's default language. Unicode directionality isolation marks are
+/// not inserted around interpolated values.
+///
+/// [Fluent]: https://projectfluent.org
+/// [BCP 47]: https://www.rfc-editor.org/info/bcp47
+pub struct Catalog {
+    default: LanguageIdentifier,
+    languages: Vec<LanguageIdentifier>,
+    bundles: HashMap<LanguageIdentifier, FluentBundle<FluentResource>>,
+    messages: HashMap<LanguageIdentifier, HashMap<String, String>>,
+}
+
+/// An error loading a [`Catalog`].
+#[derive(Debug)]
+pub enum Error {
+    /// A directory or file couldn't be read.
+    Io(PathBuf, io::Error),
+    /// A language directory isn't named by a valid language tag.
+    Language(PathBuf),
+    /// A Fluent file failed to parse.
+    Parse(PathBuf, Vec<ParserError>),
+    /// A Fluent file redefines a message or term of its language.
+    Resource(PathBuf, Vec<FluentError>),
+    /// There is no directory for the default language.
+    MissingDefault(LanguageIdentifier),
+}
+
+impl Catalog {
+    /// Loads a catalog from the language directories in `dir`. Messages
+    /// missing in a language are looked up in `default`, which must be one of
+    /// the languages in `dir`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rocket_i18n::Catalog;
+    ///
+    /// let catalog = Catalog::load("locales", "en-US".parse().unwrap()).unwrap();
+    /// ```
+    pub fn load<P: AsRef<Path>>(dir: P, default: LanguageIdentifier) -> Result<Catalog, Error> {
+        let mut bundles = HashMap::new();
+        let mut messages = HashMap::new();
+        for entry in read_dir(dir.as_ref())? {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+
+            let language: LanguageIdentifier = entry.file_name().to_str()
+                .and_then(|name| name.parse().ok())
+                .ok_or_else(|| Error::Language(path.clone()))?;
+
+            let mut files: Vec<_> = read_dir(&path)?.into_iter()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().map_or(false, |ext| ext == "ftl"))
+                .collect();
+
+            files.sort();
+
+            let mut bundle = FluentBundle::new_concurrent(vec![language.clone()]);
+            bundle.set_use_isolating(false);
+
+            let mut ids = vec![];
+            for file in files {
+                let source = fs::read_to_string(&file).map_err(|e| Error::Io(file.clone(), e))?;
+                let resource = FluentResource::try_new(source)
+                    .map_err(|(_, errors)| Error::Parse(file.clone(), errors))?;
+
+                ids.extend(resource.entries().filter_map(|entry| match entry {
+                    Entry::Message(message) => Some(message.id.name.to_string()),
+                    _ => None,
+                }));
+
+                bundle.add_resource(resource).map_err(|errors| Error::Resource(file, errors))?;
+            }
+
+            messages.insert(language.clone(), static_messages(&bundle, ids));
+            bundles.insert(language, bundle);
+        }
+
+        if !bundles.contains_key(&default) {
+            return Err(Error::MissingDefault(default));
+        }
+
+        let mut languages: Vec<_> = bundles.keys().cloned().collect();
+        languages.sort_by_key(|language| language.to_string());
+        Ok(Catalog { default, languages, bundles, messages })
+    }
+
+    /// Returns the catalog's default language.
+    pub fn default_language(&self) -> &LanguageIdentifier {
+        &self.default
+    }
+
+    /// Returns the languages in the catalog, sorted by tag.
+    pub fn languages(&self) -> &[LanguageIdentifier] {
+        &self.languages
+    }
+
+    /// Returns the languages in the catalog matching the `Accept-Language`
+    /// header value `accept_language`, in order of preference, followed by
+    /// the default language.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rocket_i18n::Catalog;
+    ///
+    /// # let catalog = Catalog::load("locales", "en-US".parse().unwrap()).unwrap();
+    /// // Given a catalog with `en-US`, `fr`, and `de` and default `en-US`:
+    /// let languages = catalog.negotiate("fr-CA, de;q=0.5");
+    /// assert_eq!(languages, ["fr", "de", "en-US"]);
+    /// ```
+    pub fn negotiate(&self, accept_language: &str) -> Vec<LanguageIdentifier> {
+        let requested = accepted_languages::parse(accept_language);
+        let strategy = NegotiationStrategy::Filtering;
+        negotiate_languages(&requested, &self.languages, Some(&self.default), strategy)
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Formats the message `id` with `args` in the first of `languages`
+    /// defining it, or in the default language if none do. An attribute of a
+    /// message is formatted if `id` is of the form `message.attribute`.
+    /// Returns `None` if the message isn't defined.
+    ///
+    /// Errors while formatting, such as missing arguments, are logged, and
+    /// the value in error is replaced by its name.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rocket_i18n::{Catalog, FluentArgs};
+    ///
+    /// # let catalog = Catalog::load("locales", "en-US".parse().unwrap()).unwrap();
+    /// let mut args = FluentArgs::new();
+    /// args.set("name", "Bob");
+    ///
+    /// let languages = catalog.negotiate("fr");
+    /// let message = catalog.message(&languages, "hello", Some(&args));
+    /// assert_eq!(message.unwrap(), "Bonjour, Bob !");
+    /// ```
+    pub fn message(
+        &self,
+        languages: &[LanguageIdentifier],
+        id: &str,
+        args: Option<&FluentArgs<'_>>,
+    ) -> Option<String> {
+        let (id, attribute) = match id.split_once('.') {
+            Some((id, attribute)) => (id, Some(attribute)),
+            None => (id, None),
+        };
+
+        languages.iter()
+            .chain(Some(&self.default))
+            .filter_map(|language| self.bundles.get(language))
+            .find_map(|bundle| {
+                let message = bundle.get_message(id)?;
+                let pattern = match attribute {
+                    Some(attribute) => message.get_attribute(attribute)?.value(),
+                    None => message.value()?,
+                };
+
+                let mut errors = vec![];
+                let value = bundle.format_pattern(pattern, args, &mut errors);
+                for error in errors {
+                    warn_!("Error formatting message '{}': {}", id, error);
+                }
+
+                Some(value.into_owned())
+            })
+    }
+
+    /// Returns the values of all messages that don't take arguments, keyed by
+    /// message ID, in the first of `languages` defining them, or in the
+    /// default language if none do. Attributes are not included.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rocket_i18n::Catalog;
+    ///
+    /// # let catalog = Catalog::load("locales", "en-US".parse().unwrap()).unwrap();
+    /// let languages = catalog.negotiate("fr");
+    /// let messages = catalog.messages(&languages);
+    /// assert_eq!(messages["goodbye"], "Au revoir !");
+    /// ```
+    pub fn messages(&self, languages: &[LanguageIdentifier]) -> HashMap<&str, &str> {
+        let mut messages = HashMap::new();
+        let chain = languages.iter().chain(Some(&self.default));
+        for language_messages in chain.rev().filter_map(|language| self.messages.get(language)) {
+            messages.extend(language_messages.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+        }
+
+        messages
+    }
+}
+
+fn read_dir(dir: &Path) -> Result<Vec<fs::DirEntry>, Error> {
+    fs::read_dir(dir)
+        .and_then(|entries| entries.collect())
+        .map_err(|e| Error::Io(dir.into(), e))
+}
+
+/// Formats the messages `ids` of `bundle` that can be formatted without
+/// arguments.
+fn static_messages(
+    bundle: &FluentBundle<FluentResource>,
+    ids: Vec<String>
+) -> HashMap<String, String> {
+    ids.into_iter()
+        .filter_map(|id| {
+            let pattern = bundle.get_message(&id)?.value()?;
+            let mut errors = vec![];
+            let value = bundle.format_pattern(pattern, None, &mut errors);
+            let missing_args = errors.iter()
+                .any(|e| matches!(e, Unresolved(ResolverError::Reference(_))));
+
+            (!missing_args).then(|| (id, value.into_owned()))
+        })
+        .collect()
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn list<E: fmt::Display>(errors: &[E]) -> String {
+            errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("; ")
+        }
+
+        match self {
+            Error::Io(path, e) => write!(f, "failed to read {}: {}", path.display(), e),
+            Error::Language(path) => write!(f, "invalid language tag: {}", path.display()),
+            Error::Parse(path, errors) => write!(f, "{}: {}", path.display(), list(errors)),
+            Error::Resource(path, errors) => write!(f, "{}: {}", path.display(), list(errors)),
+            Error::MissingDefault(language) => {
+                write!(f, "missing directory for default language '{}'", language)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use std::borrow::Cow;
+
+use rocket::{Rocket, Build, Orbit};
+use rocket::fairing::{self, Fairing, Info, Kind};
+use unic_langid::LanguageIdentifier;
+
+use crate::Catalog;
+
+/// Fairing that loads the message [`Catalog`] used by the [`Locale`] guard.
+///
+/// On ignition, the catalog is loaded from the directory set by the
+/// `locale_dir` configuration parameter, `locales` by default, relative to the
+/// configuration file or the current working directory. Launch is aborted if
+/// the catalog fails to load. The catalog is then available as managed state.
+///
+/// With the `templates` feature enabled, the fairing also exposes messages to
+/// [`rocket_dyn_templates`] by [injecting] the following into the context of
+/// every template rendered in response to a request:
+///
+///   * `locale`: the most preferred negotiated language's tag, as in
+///     [`Locale::language()`].
+///   * `t`: a map from message IDs to the values of all messages that don't
+///     take arguments, as in [`Locale::messages()`].
+///
+/// [`Locale`]: crate::Locale
+/// [`Locale::language()`]: crate::Locale::language()
+/// [`Locale::messages()`]: crate::Locale::messages()
+/// [`rocket_dyn_templates`]: https://api.rocket.rs/v0.5-rc/rocket_dyn_templates/
+/// [injecting]: https://api.rocket.rs/v0.5-rc/rocket_dyn_templates/struct.Template.html#method.inject
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_i18n::{I18n, Locale, t};
+///
+/// #[get("/")]
+/// fn index(locale: Locale<'_>) -> String {
+///     t!(locale, "welcome")
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .attach(I18n::new().default_language("en-US"))
+///         .mount("/", routes![index])
+/// }
+/// ```
+pub struct I18n {
+    default: Cow<'static, str>,
+}
+
+impl I18n {
+    /// The default directory for message catalogs: `locales`.
+    const DEFAULT_LOCALE_DIR: &'static str = "locales";
+
+    /// Returns a fairing loading a catalog with the default language `en-US`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_i18n::I18n;
+    ///
+    /// let i18n = I18n::new();
+    /// ```
+    pub fn new() -> I18n {
+        I18n { default: "en-US".into() }
+    }
+
+    /// Sets the catalog's default language to the language with the tag
+    /// `language`, in which messages missing from negotiated languages are
+    /// looked up. An invalid tag aborts launch.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_i18n::I18n;
+    ///
+    /// let i18n = I18n::new().default_language("fr");
+    /// ```
+    pub fn default_language<L: Into<Cow<'static, str>>>(mut self, language: L) -> I18n {
+        self.default = language.into();
+        self
+    }
+}
+
+impl Default for I18n {
+    fn default() -> Self {
+        I18n::new()
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for I18n {
+    fn info(&self) -> Info {
+        let kind = Kind::Ignite | Kind::Liftoff | Kind::Singleton;
+        #[cfg(feature = "templates")] let kind = kind | Kind::Request;
+
+        Info { kind, name: "I18n" }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        use rocket::figment::value::magic::RelativePathBuf;
+
+        let configured_dir = rocket.figment()
+            .extract_inner::<RelativePathBuf>("locale_dir")
+            .map(|path| path.relative());
+
+        let path = match configured_dir {
+            Ok(dir) => dir,
+            Err(e) if e.missing() => Self::DEFAULT_LOCALE_DIR.into(),
+            Err(e) => {
+                rocket::config::pretty_print_error(e);
+                return Err(rocket);
+            }
+        };
+
+        let default = match self.default.parse::<LanguageIdentifier>() {
+            Ok(default) => default,
+            Err(e) => {
+                error!("Invalid default language '{}': {}.", self.default, e);
+                return Err(rocket);
+            }
+        };
+
+        match Catalog::load(&path, default) {
+            Ok(catalog) => Ok(rocket.manage(catalog)),
+            Err(e) => {
+                error!("Failed to load message catalog from '{}'.", path.display());
+                error_!("{}", e);
+                Err(rocket)
+            }
+        }
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        use rocket::{log::PaintExt, yansi::Paint};
+
+        let catalog = rocket.state::<Catalog>()
+            .expect("I18n Catalog registered in on_ignite");
+
+        let languages: Vec<_> = catalog.languages().iter().map(|l| l.to_string()).collect();
+        info!("{}{}:", Paint::emoji("🌐 "), Paint::magenta("I18n"));
+        info_!("default language: {}", Paint::white(catalog.default_language()));
+        info_!("languages: {}", Paint::white(languages.join(", ")));
+    }
+
+    #[cfg(feature = "templates")]
+    async fn on_request(&self, req: &mut rocket::Request<'_>, _: &mut rocket::Data<'_>) {
+        use rocket::request::FromRequest;
+        use rocket::serde::Serialize;
+
+        #[derive(Serialize)]
+        #[serde(crate = "rocket::serde")]
+        struct Injected<'r> {
+            locale: String,
+            t: std::collections::HashMap<&'r str, &'r str>,
+        }
+
+        if let rocket::outcome::Outcome::Success(locale) = crate::Locale::from_request(req).await {
+            let injected = Injected { locale: locale.language().to_string(), t: locale.messages() };
+            rocket_dyn_templates::Template::inject(req, injected);
+        }
+    }
+}
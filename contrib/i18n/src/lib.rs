@@ -0,0 +1,125 @@
+//! Internationalization for Rocket via [Fluent].
+//!
+//! This crate localizes Rocket applications with messages written in
+//! [Fluent], negotiated from the `Accept-Language` request header. It
+//! provides:
+//!
+//!   * [`I18n`], a fairing loading a [`Catalog`] of messages from a directory
+//!     of Fluent files at launch.
+//!   * [`Locale`], a request guard for the languages preferred by the client,
+//!     used to format messages in the best available language.
+//!   * [`t!`], a macro formatting a message with arguments.
+//!   * With the `templates` feature, [automatic exposure](#templates) of
+//!     messages to templates rendered by [`rocket_dyn_templates`].
+//!
+//! [Fluent]: https://projectfluent.org
+//! [`rocket_dyn_templates`]: https://api.rocket.rs/v0.5-rc/rocket_dyn_templates/
+//!
+//! # Usage
+//!
+//!   1. Depend on `rocket_i18n`, enabling the `templates` feature to use
+//!      messages in templates:
+//!
+//!      ```toml
+//!      [dependencies.rocket_i18n]
+//!      version = "0.1.0-rc.2"
+//!      features = ["templates"]
+//!      ```
+//!
+//!   2. Write messages in a directory per language in `locales/`, or the
+//!      directory set by the `locale_dir` configuration parameter:
+//!
+//!      ```text
+//!      # locales/en-US/main.ftl
+//!      welcome = Welcome!
+//!      hello = Hello, { $name }!
+//!
+//!      # locales/fr/main.ftl
+//!      welcome = Bienvenue !
+//!      hello = Bonjour, { $name } !
+//!      ```
+//!
+//!   3. Attach the [`I18n`] fairing and format messages with a [`Locale`]:
+//!
+//!      ```rust
+//!      # #[macro_use] extern crate rocket;
+//!      use rocket_i18n::{I18n, Locale, t};
+//!
+//!      #[get("/hello/<name>")]
+//!      fn hello(locale: Locale<'_>, name: &str) -> String {
+//!          t!(locale, "hello", name = name)
+//!      }
+//!
+//!      #[launch]
+//!      fn rocket() -> _ {
+//!          rocket::build()
+//!              .attach(I18n::new().default_language("en-US"))
+//!              .mount("/", routes![hello])
+//!      }
+//!      ```
+//!
+//! A request with `Accept-Language: fr-CA, en;q=0.8` to `/hello/Bob` is
+//! answered with `Bonjour, Bob !`. Messages missing in French are looked up in
+//! the default language, `en-US`.
+//!
+//! # Templates
+//!
+//! With the `templates` feature enabled, the [`I18n`] fairing injects the
+//! negotiated language as `locale` and all messages that don't take arguments
+//! as `t` into the context of templates. In Tera, for instance:
+//!
+//! ```text
+//! <html lang="{{ locale }}">
+//!   <h1>{{ t.welcome }}</h1>
+//! </html>
+//! ```
+//!
+//! Messages that take arguments can be formatted in the route with [`t!`] and
+//! passed to the template in its context.
+
+#[macro_use]
+extern crate rocket;
+
+mod catalog;
+mod locale;
+mod fairing;
+
+pub use self::catalog::{Catalog, Error};
+pub use self::locale::Locale;
+pub use self::fairing::I18n;
+
+pub use fluent_bundle::{FluentArgs, FluentValue};
+pub use unic_langid::LanguageIdentifier;
+
+/// Formats a message in the languages of a [`Locale`].
+///
+/// The first argument is a [`Locale`], the second is the ID of the message,
+/// optionally of the form `message.attribute`, and the remaining arguments
+/// are the message's arguments as `name = value` pairs, where `value` is any
+/// type convertible into a [`FluentValue`], such as strings and numbers.
+/// Expands to a `String` returned by [`Locale::message()`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_i18n::{Locale, t};
+///
+/// #[get("/cart/<items>")]
+/// fn cart(locale: Locale<'_>, items: usize) -> String {
+///     let title = t!(locale, "cart-title");
+///     let count = t!(locale, "cart-items", count = items, user = "Bob");
+///     format!("{}: {}", title, count)
+/// }
+/// ```
+#[macro_export]
+macro_rules! t {
+    ($locale:expr, $id:expr $(,)?) => {
+        $locale.message($id, None)
+    };
+    ($locale:expr, $id:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = $crate::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $locale.message($id, Some(&args))
+    }};
+}
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use rocket::http::Status;
+use rocket::request::{self, FromRequest, Request};
+use unic_langid::LanguageIdentifier;
+
+use crate::{Catalog, FluentArgs};
+
+/// A request guard for the languages preferred by the client.
+///
+/// The languages in the request's `Accept-Language` header are negotiated
+/// against those in the [`Catalog`] managed by the [`I18n`](crate::I18n)
+/// fairing, falling back to the catalog's default language. The guard always
+/// succeeds unless the fairing isn't attached, in which case it fails with
+/// `500 Internal Server Error`. Negotiation happens at most once per request.
+///
+/// Messages are formatted with [`Locale::message()`] or, more succinctly,
+/// the [`t!`](crate::t!) macro.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_i18n::{Locale, t};
+///
+/// #[get("/hello/<name>")]
+/// fn hello(locale: Locale<'_>, name: &str) -> String {
+///     t!(locale, "hello", name = name)
+/// }
+///
+/// #[get("/language")]
+/// fn language(locale: Locale<'_>) -> String {
+///     locale.language().to_string()
+/// }
+/// ```
+#[derive(Clone, Copy)]
+pub struct Locale<'r> {
+    catalog: &'r Catalog,
+    languages: &'r [LanguageIdentifier],
+}
+
+/// The languages negotiated for a request, cached in request-local state.
+struct Negotiated(Vec<LanguageIdentifier>);
+
+impl<'r> Locale<'r> {
+    /// Returns the most preferred of the negotiated languages.
+    pub fn language(&self) -> &'r LanguageIdentifier {
+        self.languages.first().unwrap_or_else(|| self.catalog.default_language())
+    }
+
+    /// Returns the negotiated languages in order of preference, ending with
+    /// the catalog's default language.
+    pub fn languages(&self) -> &'r [LanguageIdentifier] {
+        self.languages
+    }
+
+    /// Returns the catalog messages are looked up in.
+    pub fn catalog(&self) -> &'r Catalog {
+        self.catalog
+    }
+
+    /// Formats the message `id` with `args` in the negotiated languages. If
+    /// the message isn't defined, a warning is logged and `id` is returned.
+    /// See [`Catalog::message()`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_i18n::{Locale, FluentArgs};
+    ///
+    /// fn greet(locale: Locale<'_>) -> String {
+    ///     let mut args = FluentArgs::new();
+    ///     args.set("name", "Bob");
+    ///     locale.message("hello", Some(&args))
+    /// }
+    /// ```
+    pub fn message(&self, id: &str, args: Option<&FluentArgs<'_>>) -> String {
+        self.catalog.message(self.languages, id, args).unwrap_or_else(|| {
+            warn_!("Message '{}' is not defined in {:?}.", id, self.languages);
+            id.to_string()
+        })
+    }
+
+    /// Returns the values of all messages that don't take arguments in the
+    /// negotiated languages. See [`Catalog::messages()`] for details.
+    pub fn messages(&self) -> HashMap<&'r str, &'r str> {
+        self.catalog.messages(self.languages)
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Locale<'r> {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        let catalog = match req.rocket().state::<Catalog>() {
+            Some(catalog) => catalog,
+            None => {
+                error_!("Uninitialized message catalog: missing fairing.");
+                info_!("To use `Locale`, you must attach `I18n::fairing()`.");
+                return request::Outcome::Failure((Status::InternalServerError, ()));
+            }
+        };
+
+        let negotiated = req.local_cache(|| {
+            let accept_language = req.headers().get_one("Accept-Language").unwrap_or("");
+            Negotiated(catalog.negotiate(accept_language))
+        });
+
+        request::Outcome::Success(Locale { catalog, languages: &negotiated.0 })
+    }
+}
@@ -0,0 +1,115 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build};
+use rocket::error::ErrorKind;
+use rocket::figment::Figment;
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+
+use rocket_i18n::{Catalog, Error, I18n, Locale, t};
+
+const LOCALES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/locales");
+
+#[get("/hello/<name>")]
+fn hello(locale: Locale<'_>, name: &str) -> String {
+    t!(locale, "hello", name = name)
+}
+
+#[get("/cart/<count>")]
+fn cart(locale: Locale<'_>, count: usize) -> String {
+    t!(locale, "cart-items", count = count)
+}
+
+#[get("/<id>")]
+fn message(locale: Locale<'_>, id: &str) -> String {
+    format!("{}: {}", locale.language(), t!(locale, id))
+}
+
+fn rocket(locale_dir: &str) -> Rocket<Build> {
+    let figment = Figment::from(rocket::Config::debug_default())
+        .merge(("locale_dir", locale_dir));
+
+    rocket::custom(figment)
+        .attach(I18n::new())
+        .mount("/", routes![hello, cart, message])
+}
+
+fn get(client: &Client, uri: &str, accept_language: &str) -> String {
+    client.get(uri.to_string())
+        .header(Header::new("Accept-Language", accept_language.to_string()))
+        .dispatch()
+        .into_string()
+        .unwrap()
+}
+
+#[test]
+fn catalog_negotiation() {
+    let catalog = Catalog::load(LOCALES, "en-US".parse().unwrap()).unwrap();
+    let tags = |languages: Vec<_>| languages.iter().map(|l| format!("{}", l)).collect::<Vec<_>>();
+
+    assert_eq!(tags(catalog.languages().to_vec()), ["de", "en-US", "fr"]);
+    assert_eq!(tags(catalog.negotiate("fr-CA, de;q=0.5")), ["fr", "de", "en-US"]);
+    assert_eq!(tags(catalog.negotiate("en")), ["en-US"]);
+    assert_eq!(tags(catalog.negotiate("es")), ["en-US"]);
+    assert_eq!(tags(catalog.negotiate("")), ["en-US"]);
+
+    let french = catalog.negotiate("fr");
+    assert_eq!(catalog.message(&french, "login.title", None).unwrap(),
+        "Connectez-vous à votre compte");
+    assert_eq!(catalog.message(&french, "only-english", None).unwrap(), "Only in English.");
+    assert_eq!(catalog.message(&french, "not-found", None).unwrap(), "Page not found.");
+    assert!(catalog.message(&french, "missing", None).is_none());
+    assert!(catalog.message(&french, "login.missing", None).is_none());
+
+    let messages = catalog.messages(&french);
+    assert_eq!(messages["welcome"], "Bienvenue !");
+    assert_eq!(messages["login"], "Connexion");
+    assert_eq!(messages["only-english"], "Only in English.");
+    assert!(!messages.contains_key("hello"));
+    assert!(!messages.contains_key("cart-items"));
+}
+
+#[test]
+fn catalog_errors() {
+    let broken = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/broken");
+    assert!(matches!(Catalog::load(broken, "en-US".parse().unwrap()), Err(Error::Parse(..))));
+    assert!(matches!(Catalog::load(LOCALES, "es".parse().unwrap()),
+        Err(Error::MissingDefault(..))));
+    assert!(matches!(Catalog::load("/does/not/exist", "en-US".parse().unwrap()),
+        Err(Error::Io(..))));
+}
+
+#[test]
+fn locale_guard_and_macro() {
+    let client = Client::debug(rocket(LOCALES)).unwrap();
+    assert_eq!(get(&client, "/hello/Bob", "fr-CA, en;q=0.8"), "Bonjour, Bob !");
+    assert_eq!(get(&client, "/hello/Bob", "de"), "Hello, Bob!");
+    assert_eq!(get(&client, "/cart/1", "fr"), "Un article");
+    assert_eq!(get(&client, "/cart/3", "en-US"), "3 items");
+    assert_eq!(get(&client, "/welcome", "de-AT"), "de: Willkommen!");
+    assert_eq!(get(&client, "/welcome", "es"), "en-US: Welcome!");
+    assert_eq!(get(&client, "/missing", "fr"), "fr: missing");
+
+    let response = client.get("/welcome").dispatch();
+    assert_eq!(response.into_string().unwrap(), "en-US: Welcome!");
+}
+
+#[test]
+fn default_language_and_launch_errors() {
+    let rocket = rocket(LOCALES).attach(I18n::new().default_language("fr"));
+    let client = Client::debug(rocket).unwrap();
+    assert_eq!(get(&client, "/welcome", "es"), "fr: Bienvenue !");
+
+    let rocket = self::rocket(LOCALES).attach(I18n::new().default_language("not a language"));
+    let error = Client::debug(rocket).expect_err("invalid default language");
+    assert!(matches!(error.kind(), ErrorKind::FailedFairings(..)));
+
+    let broken = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/broken");
+    let error = Client::debug(self::rocket(broken)).expect_err("broken catalog");
+    assert!(matches!(error.kind(), ErrorKind::FailedFairings(..)));
+
+    let rocket = rocket::build().mount("/", routes![message]);
+    let client = Client::debug(rocket).unwrap();
+    assert_eq!(client.get("/welcome").dispatch().status(), Status::InternalServerError);
+}
+
@@ -0,0 +1 @@
+welcome = Willkommen!
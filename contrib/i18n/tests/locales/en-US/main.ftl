@@ -0,0 +1,9 @@
+welcome = Welcome!
+hello = Hello, { $name }!
+cart-items = { $count ->
+    [one] One item
+   *[other] { $count } items
+}
+login = Log in
+    .title = Log in to your account
+only-english = Only in English.
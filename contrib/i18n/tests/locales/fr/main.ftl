@@ -0,0 +1,8 @@
+welcome = Bienvenue !
+hello = Bonjour, { $name } !
+cart-items = { $count ->
+    [one] Un article
+   *[other] { $count } articles
+}
+login = Connexion
+    .title = Connectez-vous à votre compte
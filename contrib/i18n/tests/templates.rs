@@ -0,0 +1,38 @@
+#![cfg(feature = "templates")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::figment::Figment;
+use rocket::http::Header;
+use rocket::local::blocking::Client;
+
+use rocket_dyn_templates::{Template, context};
+use rocket_i18n::{I18n, Locale, t};
+
+#[get("/<name>")]
+fn page(locale: Locale<'_>, name: &str) -> Template {
+    Template::render("index", context! { greeting: t!(locale, "hello", name = name) })
+}
+
+#[test]
+fn messages_injected_into_templates() {
+    let figment = Figment::from(rocket::Config::debug_default())
+        .merge(("locale_dir", concat!(env!("CARGO_MANIFEST_DIR"), "/tests/locales")))
+        .merge(("template_dir", concat!(env!("CARGO_MANIFEST_DIR"), "/tests/templates")));
+
+    let rocket = rocket::custom(figment)
+        .attach(I18n::new())
+        .attach(Template::fairing())
+        .mount("/", routes![page]);
+
+    let client = Client::debug(rocket).unwrap();
+    let response = client.get("/Bob").header(Header::new("Accept-Language", "fr")).dispatch();
+    assert_eq!(response.into_string().unwrap().trim(),
+        "<html lang=\"fr\"><h1>Bienvenue !</h1><p>Only in English.</p>\
+        <p>Bonjour, Bob !</p></html>");
+
+    let response = client.get("/Bob").header(Header::new("Accept-Language", "en")).dispatch();
+    assert_eq!(response.into_string().unwrap().trim(),
+        "<html lang=\"en-US\"><h1>Welcome!</h1><p>Only in English.</p>\
+        <p>Hello, Bob!</p></html>");
+}
@@ -0,0 +1,244 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use rocket::{Rocket, Build, Request, Response};
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::http::{Header, Status};
+
+use crate::{Key, Error};
+use crate::verifier::Verifier;
+
+/// Fairing that enables JWT authentication via the [`Jwt`] guard.
+///
+/// The fairing configures the keys that verify tokens and the claims tokens
+/// must have. Tokens without an `exp` claim, or with an `exp` or `nbf` claim
+/// outside the [leeway](JwtAuth::leeway()), are rejected. If an
+/// [issuer](JwtAuth::issuer()) or [audience](JwtAuth::audience()) is set,
+/// tokens must also have matching `iss` or `aud` claims. The fairing also adds
+/// a `WWW-Authenticate` header to responses for requests that failed to
+/// authenticate.
+///
+/// Launch fails if the fairing has no keys.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_jwt::{JwtAuth, Jwt, Key};
+///
+/// #[get("/")]
+/// fn index(token: Jwt) -> String {
+///     format!("claims: {}", token.claims())
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     let auth = JwtAuth::new()
+///         .key(Key::hmac("a very secret key"))
+///         .issuer("https://auth.example.com")
+///         .audience("api");
+///
+///     rocket::build()
+///         .attach(auth)
+///         .mount("/", routes![index])
+/// }
+/// ```
+///
+/// [`Jwt`]: crate::Jwt
+#[derive(Debug, Default)]
+pub struct JwtAuth {
+    keys: Vec<Key>,
+    #[cfg(feature = "jwks")]
+    jwks: Option<String>,
+    issuer: Option<String>,
+    audience: Option<String>,
+    leeway: Option<Duration>,
+}
+
+/// The `WWW-Authenticate` challenge for a request that failed to
+/// authenticate, cached in the request's local cache.
+#[derive(Default)]
+pub(crate) struct Challenge(Mutex<Option<String>>);
+
+impl Challenge {
+    pub(crate) fn set(req: &Request<'_>, error: &Error) {
+        let challenge = match error {
+            Error::Missing => "Bearer".to_string(),
+            Error::Claims(_) => r#"Bearer error="insufficient_scope""#.to_string(),
+            e => format!(r#"Bearer error="invalid_token", error_description="{}""#, e),
+        };
+
+        *req.local_cache(Challenge::default).0.lock().expect("challenge lock") = Some(challenge);
+    }
+
+    fn take(req: &Request<'_>) -> Option<String> {
+        req.local_cache(Challenge::default).0.lock().expect("challenge lock").take()
+    }
+}
+
+impl JwtAuth {
+    /// The default leeway: 60 seconds.
+    pub const DEFAULT_LEEWAY: Duration = Duration::from_secs(60);
+
+    /// Returns a fairing without keys and without issuer or audience
+    /// requirements.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_jwt::{JwtAuth, Key};
+    ///
+    /// let auth = JwtAuth::new().key(Key::hmac("a very secret key"));
+    /// ```
+    pub fn new() -> JwtAuth {
+        JwtAuth::default()
+    }
+
+    /// Adds a key that verifies tokens. If a token names a key ID, only keys
+    /// with that ID or without an ID are tried.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_jwt::{JwtAuth, Key};
+    ///
+    /// let auth = JwtAuth::new()
+    ///     .key(Key::hmac("current secret").with_id("2"))
+    ///     .key(Key::hmac("previous secret").with_id("1"));
+    /// ```
+    pub fn key(mut self, key: Key) -> JwtAuth {
+        self.keys.push(key);
+        self
+    }
+
+    /// Verifies tokens with the keys in the JSON Web Key Set at `url`, in
+    /// addition to any other keys.
+    ///
+    /// The key set is fetched when first needed and refetched after an hour,
+    /// or sooner when a token names an unknown key ID, but at most once every
+    /// 30 seconds. If a fetch fails, the previously fetched keys continue to
+    /// be used. Launch fails if `url` isn't a valid `http` or `https` URL.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_jwt::JwtAuth;
+    ///
+    /// let auth = JwtAuth::new().jwks("https://auth.example.com/.well-known/jwks.json");
+    /// ```
+    #[cfg(feature = "jwks")]
+    pub fn jwks<U: Into<String>>(mut self, url: U) -> JwtAuth {
+        self.jwks = Some(url.into());
+        self
+    }
+
+    /// Requires tokens to have an `iss` claim equal to `issuer`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_jwt::{JwtAuth, Key};
+    ///
+    /// let auth = JwtAuth::new()
+    ///     .key(Key::hmac("a very secret key"))
+    ///     .issuer("https://auth.example.com");
+    /// ```
+    pub fn issuer<S: Into<String>>(mut self, issuer: S) -> JwtAuth {
+        self.issuer = Some(issuer.into());
+        self
+    }
+
+    /// Requires tokens to have an `aud` claim equal to or containing
+    /// `audience`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_jwt::{JwtAuth, Key};
+    ///
+    /// let auth = JwtAuth::new()
+    ///     .key(Key::hmac("a very secret key"))
+    ///     .audience("api");
+    /// ```
+    pub fn audience<S: Into<String>>(mut self, audience: S) -> JwtAuth {
+        self.audience = Some(audience.into());
+        self
+    }
+
+    /// Sets the clock skew tolerated when validating the `exp` and `nbf`
+    /// claims. Defaults to [`JwtAuth::DEFAULT_LEEWAY`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use rocket_jwt::{JwtAuth, Key};
+    ///
+    /// let auth = JwtAuth::new()
+    ///     .key(Key::hmac("a very secret key"))
+    ///     .leeway(Duration::ZERO);
+    /// ```
+    pub fn leeway(mut self, leeway: Duration) -> JwtAuth {
+        self.leeway = Some(leeway);
+        self
+    }
+
+    fn verifier(&self) -> Result<Verifier, String> {
+        #[cfg(feature = "jwks")]
+        let jwks = self.jwks.as_deref().map(crate::jwks::Jwks::new).transpose()?;
+
+        #[cfg(feature = "jwks")]
+        let has_keys = !self.keys.is_empty() || jwks.is_some();
+
+        #[cfg(not(feature = "jwks"))]
+        let has_keys = !self.keys.is_empty();
+
+        if !has_keys {
+            return Err("no keys were configured".into());
+        }
+
+        Ok(Verifier {
+            keys: self.keys.clone(),
+            #[cfg(feature = "jwks")]
+            jwks,
+            issuer: self.issuer.clone(),
+            audience: self.audience.clone(),
+            leeway: self.leeway.unwrap_or(JwtAuth::DEFAULT_LEEWAY),
+        })
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for JwtAuth {
+    fn info(&self) -> Info {
+        Info {
+            name: "JWT Authentication",
+            kind: Kind::Ignite | Kind::Response | Kind::Singleton,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        match self.verifier() {
+            Ok(verifier) => Ok(rocket.manage(verifier)),
+            Err(e) => {
+                error!("Invalid JWT authentication configuration: {}", e);
+                Err(rocket)
+            }
+        }
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let status = res.status();
+        if status != Status::Unauthorized && status != Status::Forbidden {
+            return;
+        }
+
+        if res.headers().contains("WWW-Authenticate") {
+            return;
+        }
+
+        if let Some(challenge) = Challenge::take(req) {
+            res.set_header(Header::new("WWW-Authenticate", challenge));
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use std::fmt;
+use std::ops::Deref;
+
+use rocket::http::Status;
+use rocket::request::{self, Request, FromRequest};
+use rocket::serde::de::DeserializeOwned;
+use rocket::serde::json::{self, Value, serde_json};
+
+use crate::fairing::Challenge;
+use crate::verifier::Verifier;
+
+/// Request guard that authenticates a request via a JSON Web Token.
+///
+/// The guard reads a token from the request's `Authorization: Bearer` header,
+/// verifies its signature against the keys configured in the attached
+/// [`JwtAuth`](crate::JwtAuth) fairing, validates its registered claims, and
+/// deserializes its claims into a `C`, by default a JSON [`Value`].
+///
+/// The guard fails with `401 Unauthorized` if the request has no token or if
+/// the token is invalid, and the fairing adds a `WWW-Authenticate` header to
+/// the response. If the token is valid but its claims can't be deserialized
+/// into a `C`, the guard fails with `403 Forbidden` and an [`Error::Claims`].
+/// This makes it possible to require claims, such as a role, by their type.
+/// If the fairing isn't attached, the guard fails with `500 Internal Server
+/// Error` and an [`Error::Unmanaged`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::serde::Deserialize;
+/// use rocket_jwt::Jwt;
+///
+/// #[derive(Deserialize)]
+/// # #[serde(crate = "rocket::serde")]
+/// struct User {
+///     sub: String,
+/// }
+///
+/// #[get("/me")]
+/// fn me(user: Jwt<User>) -> String {
+///     format!("Hello, {}!", user.sub)
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Jwt<C = Value>(C);
+
+/// An error returned by the [`Jwt`] guard.
+#[derive(Debug)]
+pub enum Error {
+    /// The request has no bearer token.
+    Missing,
+    /// The token isn't a well-formed JWT.
+    Malformed,
+    /// The token's algorithm is unsupported, or no key verifies its signature.
+    Signature,
+    /// The token has expired or has no expiration time.
+    Expired,
+    /// The token isn't valid yet.
+    Immature,
+    /// The token's issuer isn't the configured issuer.
+    Issuer,
+    /// The token's audience doesn't include the configured audience.
+    Audience,
+    /// The token's claims couldn't be deserialized.
+    Claims(serde_json::Error),
+    /// The [`JwtAuth`](crate::JwtAuth) fairing isn't attached.
+    Unmanaged,
+}
+
+impl<C> Jwt<C> {
+    /// Returns a reference to the token's claims.
+    pub fn claims(&self) -> &C {
+        &self.0
+    }
+
+    /// Consumes `self`, returning the token's claims.
+    pub fn into_inner(self) -> C {
+        self.0
+    }
+}
+
+impl<C> Deref for Jwt<C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        &self.0
+    }
+}
+
+/// Returns the bearer token in the `Authorization` header of `req`.
+fn bearer<'r>(req: &'r Request<'_>) -> Option<&'r str> {
+    let value = req.headers().get_one("Authorization")?.trim();
+    let (scheme, token) = value.split_at(value.find(' ')?);
+    match scheme.eq_ignore_ascii_case("Bearer") {
+        true => Some(token.trim()).filter(|t| !t.is_empty()),
+        false => None,
+    }
+}
+
+#[rocket::async_trait]
+impl<'r, C: DeserializeOwned + Send> FromRequest<'r> for Jwt<C> {
+    type Error = Error;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Error> {
+        let verifier = match req.rocket().state::<Verifier>() {
+            Some(verifier) => verifier,
+            None => {
+                error_!("Missing JWT verifier state: missing fairing.");
+                info_!("To use `Jwt`, you must attach a `JwtAuth` fairing.");
+                return request::Outcome::Failure((Status::InternalServerError, Error::Unmanaged));
+            }
+        };
+
+        let token = match bearer(req) {
+            Some(token) => token,
+            None => {
+                Challenge::set(req, &Error::Missing);
+                return request::Outcome::Failure((Status::Unauthorized, Error::Missing));
+            }
+        };
+
+        let claims = match verifier.verify(token).await {
+            Ok(claims) => claims,
+            Err(e) => {
+                Challenge::set(req, &e);
+                return request::Outcome::Failure((Status::Unauthorized, e));
+            }
+        };
+
+        match json::from_value(Value::Object(claims)) {
+            Ok(claims) => request::Outcome::Success(Jwt(claims)),
+            Err(e) => {
+                let e = Error::Claims(e);
+                Challenge::set(req, &e);
+                request::Outcome::Failure((Status::Forbidden, e))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Missing => write!(f, "missing bearer token"),
+            Error::Malformed => write!(f, "malformed token"),
+            Error::Signature => write!(f, "invalid token signature"),
+            Error::Expired => write!(f, "token has expired"),
+            Error::Immature => write!(f, "token is not yet valid"),
+            Error::Issuer => write!(f, "invalid token issuer"),
+            Error::Audience => write!(f, "invalid token audience"),
+            Error::Claims(e) => write!(f, "invalid token claims: {}", e),
+            Error::Unmanaged => write!(f, "JWT fairing is not attached"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Claims(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use hyper::{Body, Request, Uri};
+use hyper::client::conn;
+use rocket::serde::json::{self, Value};
+use rocket::tokio::io::{AsyncRead, AsyncWrite};
+use rocket::tokio::sync::RwLock;
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+use tokio_rustls::rustls::{ClientConfig, OwnedTrustAnchor, RootCertStore, ServerName};
+
+use crate::Key;
+
+type FetchError = Box<dyn std::error::Error + Send + Sync>;
+
+/// How long fetched keys are used before they're fetched again.
+const REFRESH: Duration = Duration::from_secs(60 * 60);
+
+/// The minimum time between fetches, bounding the rate at which requests with
+/// unknown key IDs can trigger fetches.
+const MIN_REFRESH: Duration = Duration::from_secs(30);
+
+/// How long to wait for a fetch to complete.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The largest key set that will be fetched, in bytes.
+const MAX_SIZE: usize = 1 << 20;
+
+/// A JSON Web Key Set fetched from, and periodically refreshed from, a URL.
+pub(crate) struct Jwks {
+    url: Uri,
+    cache: RwLock<Cache>,
+}
+
+#[derive(Default)]
+struct Cache {
+    keys: Arc<Vec<Key>>,
+    fetched: Option<Instant>,
+}
+
+impl Cache {
+    fn is_fresh(&self, kid: Option<&str>) -> bool {
+        let known = |kid| self.keys.iter().any(|k| k.id() == Some(kid));
+        match self.fetched {
+            Some(at) if at.elapsed() < MIN_REFRESH => true,
+            Some(at) => at.elapsed() < REFRESH && kid.map_or(true, known),
+            None => false,
+        }
+    }
+}
+
+impl Jwks {
+    pub(crate) fn new(url: &str) -> Result<Jwks, String> {
+        let url: Uri = url.parse().map_err(|e| format!("invalid JWKS URL: {}", e))?;
+        match (url.scheme_str(), url.host()) {
+            (Some("https") | Some("http"), Some(_)) => {},
+            _ => return Err(format!("invalid JWKS URL: {}: expected http(s) URL", url)),
+        }
+
+        Ok(Jwks { url, cache: RwLock::default() })
+    }
+
+    /// Returns the keys in the set, fetching them first if they're stale or
+    /// if none has the ID `kid`. If fetching fails, the previously fetched
+    /// keys, if any, are returned.
+    pub(crate) async fn keys(&self, kid: Option<&str>) -> Arc<Vec<Key>> {
+        let cache = self.cache.read().await;
+        if cache.is_fresh(kid) {
+            return cache.keys.clone();
+        }
+
+        drop(cache);
+        let mut cache = self.cache.write().await;
+        if cache.is_fresh(kid) {
+            return cache.keys.clone();
+        }
+
+        cache.fetched = Some(Instant::now());
+        match rocket::tokio::time::timeout(TIMEOUT, fetch(&self.url)).await {
+            Ok(Ok(keys)) => cache.keys = Arc::new(keys),
+            Ok(Err(e)) => error_!("Failed to fetch JWKS from {}: {}", self.url, e),
+            Err(_) => error_!("Failed to fetch JWKS from {}: timed out", self.url),
+        }
+
+        cache.keys.clone()
+    }
+}
+
+/// Parses a JSON Web Key Set, skipping keys that aren't supported.
+pub(crate) fn parse(jwks: &str) -> Result<Vec<Key>, FetchError> {
+    let set: Value = json::from_str(jwks)?;
+    let keys = set.get("keys")
+        .and_then(Value::as_array)
+        .ok_or("key set is missing a `keys` array")?;
+
+    Ok(keys.iter().filter_map(|jwk| Key::from_jwk_value(jwk).ok()).collect())
+}
+
+async fn fetch(url: &Uri) -> Result<Vec<Key>, FetchError> {
+    let host = url.host().ok_or("missing host")?;
+    let https = url.scheme_str() == Some("https");
+    let port = url.port_u16().unwrap_or(if https { 443 } else { 80 });
+    let path = url.path_and_query().map_or("/", |p| p.as_str());
+    let request = Request::get(path)
+        .header(hyper::header::HOST, url.authority().map_or(host, |a| a.as_str()))
+        .header(hyper::header::ACCEPT, "application/json")
+        .body(Body::empty())?;
+
+    let stream = TcpStream::connect((host, port)).await?;
+    let body = match https {
+        true => {
+            let name = ServerName::try_from(host)?;
+            let stream = tls_connector().connect(name, stream).await?;
+            send(stream, request).await?
+        }
+        false => send(stream, request).await?,
+    };
+
+    parse(&body)
+}
+
+async fn send<S>(io: S, request: Request<Body>) -> Result<String, FetchError>
+    where S: AsyncRead + AsyncWrite + Unpin + Send + 'static
+{
+    let (mut sender, connection) = conn::handshake(io).await?;
+    rocket::tokio::spawn(connection);
+
+    let response = sender.send_request(request).await?;
+    if !response.status().is_success() {
+        return Err(format!("unexpected status {}", response.status()).into());
+    }
+
+    let mut body = response.into_body();
+    let mut bytes = Vec::new();
+    while let Some(chunk) = hyper::body::HttpBody::data(&mut body).await {
+        bytes.extend_from_slice(&chunk?);
+        if bytes.len() > MAX_SIZE {
+            return Err("key set is too large".into());
+        }
+    }
+
+    Ok(String::from_utf8(bytes)?)
+}
+
+fn tls_connector() -> TlsConnector {
+    let mut roots = RootCertStore::empty();
+    roots.add_server_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.0.iter().map(|ta| {
+        OwnedTrustAnchor::from_subject_spki_name_constraints(
+            ta.subject,
+            ta.spki,
+            ta.name_constraints,
+        )
+    }));
+
+    let config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+
+    TlsConnector::from(Arc::new(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_key_sets() {
+        let keys = parse(r#"{ "keys": [
+            { "kty": "EC", "kid": "ec", "crv": "P-256",
+              "x": "AOwlzdmAFPnWEChgB5h_yINR5qqde_dvPi-S9hOV6rc",
+              "y": "yb1HwAURs1EAlnVFgmIAUHEuXpTxhN4cHAfFKIbJrHs" },
+            { "kty": "OKP", "kid": "ed", "crv": "Ed25519", "x": "AAAA" },
+            { "kty": "oct", "kid": "secret", "k": "c2VjcmV0" }
+        ] }"#).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].id(), Some("ec"));
+
+        assert!(parse(r#"{ "keys": [] }"#).unwrap().is_empty());
+        assert!(parse(r#"{ "key": [] }"#).is_err());
+        assert!(parse("keys").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_urls() {
+        assert!(Jwks::new("https://example.com/.well-known/jwks.json").is_ok());
+        assert!(Jwks::new("http://localhost:8000/jwks").is_ok());
+        assert!(Jwks::new("ftp://example.com/jwks").is_err());
+        assert!(Jwks::new("/jwks").is_err());
+    }
+}
@@ -0,0 +1,377 @@
+use std::fmt;
+
+use ring::{hmac, signature};
+use rocket::serde::json::Value;
+
+/// A signature algorithm, as named in a token's `alg` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    /// HMAC using SHA-256.
+    HS256,
+    /// HMAC using SHA-384.
+    HS384,
+    /// HMAC using SHA-512.
+    HS512,
+    /// RSASSA-PKCS1-v1_5 using SHA-256.
+    RS256,
+    /// RSASSA-PKCS1-v1_5 using SHA-384.
+    RS384,
+    /// RSASSA-PKCS1-v1_5 using SHA-512.
+    RS512,
+    /// RSASSA-PSS using SHA-256.
+    PS256,
+    /// RSASSA-PSS using SHA-384.
+    PS384,
+    /// RSASSA-PSS using SHA-512.
+    PS512,
+    /// ECDSA using P-256 and SHA-256.
+    ES256,
+    /// ECDSA using P-384 and SHA-384.
+    ES384,
+}
+
+impl Algorithm {
+    /// Parses an algorithm from its name. Returns `None` for unsupported
+    /// algorithms, including `none`.
+    pub(crate) fn parse(name: &str) -> Option<Algorithm> {
+        use Algorithm::*;
+
+        Some(match name {
+            "HS256" => HS256, "HS384" => HS384, "HS512" => HS512,
+            "RS256" => RS256, "RS384" => RS384, "RS512" => RS512,
+            "PS256" => PS256, "PS384" => PS384, "PS512" => PS512,
+            "ES256" => ES256, "ES384" => ES384,
+            _ => return None,
+        })
+    }
+}
+
+/// The curve of an elliptic curve key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Curve {
+    P256,
+    P384,
+}
+
+#[derive(Clone)]
+enum Material {
+    Hmac(Vec<u8>),
+    Rsa { n: Vec<u8>, e: Vec<u8> },
+    /// An uncompressed curve point.
+    Ec { curve: Curve, point: Vec<u8> },
+}
+
+/// A key for verifying token signatures.
+///
+/// A key is one of:
+///
+///   * An HMAC secret, created with [`Key::hmac()`], which verifies tokens
+///     signed with `HS256`, `HS384`, or `HS512`.
+///   * An RSA public key, created with [`Key::from_pem()`] or
+///     [`Key::from_jwk()`], which verifies tokens signed with `RS256`,
+///     `RS384`, `RS512`, `PS256`, `PS384`, or `PS512`.
+///   * An ECDSA P-256 or P-384 public key, created with [`Key::from_pem()`]
+///     or [`Key::from_jwk()`], which verifies tokens signed with `ES256` or
+///     `ES384`, respectively.
+///
+/// A key only verifies tokens signed with an algorithm of its kind, so a
+/// public key can't be misused as an HMAC secret. A key may have an ID, which
+/// is matched against the `kid` header of tokens.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket_jwt::Key;
+///
+/// let key = Key::hmac("a very secret secret").with_id("2022-10");
+/// assert_eq!(key.id(), Some("2022-10"));
+/// ```
+#[derive(Clone)]
+pub struct Key {
+    id: Option<String>,
+    material: Material,
+}
+
+/// An error parsing a [`Key`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyError(String);
+
+impl Key {
+    /// Returns an HMAC key with secret `secret`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_jwt::Key;
+    ///
+    /// let key = Key::hmac(b"a very secret secret");
+    /// ```
+    pub fn hmac<S: AsRef<[u8]>>(secret: S) -> Key {
+        Key { id: None, material: Material::Hmac(secret.as_ref().to_vec()) }
+    }
+
+    /// Parses a PEM-encoded RSA or ECDSA public key, that is, a
+    /// `SubjectPublicKeyInfo` in a `PUBLIC KEY` block.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_jwt::Key;
+    ///
+    /// let pem = "-----BEGIN PUBLIC KEY-----
+    /// MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEAOwlzdmAFPnWEChgB5h/yINR5qqd
+    /// e/dvPi+S9hOV6rfJvUfABRGzUQCWdUWCYgBQcS5elPGE3hwcB8Uohsmsew==
+    /// -----END PUBLIC KEY-----";
+    ///
+    /// assert!(Key::from_pem(pem).is_ok());
+    /// assert!(Key::from_pem("not a key").is_err());
+    /// ```
+    pub fn from_pem(pem: &str) -> Result<Key, KeyError> {
+        let body = pem.trim()
+            .strip_prefix("-----BEGIN PUBLIC KEY-----")
+            .and_then(|pem| pem.strip_suffix("-----END PUBLIC KEY-----"))
+            .ok_or_else(|| KeyError::new("expected a PEM `PUBLIC KEY` block"))?;
+
+        let body: String = body.split_whitespace().collect();
+        let der = base64::decode(body).map_err(|_| KeyError::new("invalid PEM base64"))?;
+        let material = parse_spki(&der).ok_or_else(|| KeyError::new("invalid public key"))?;
+        Ok(Key { id: None, material })
+    }
+
+    /// Parses an RSA or EC public key in JSON Web Key format. The key's
+    /// `kid`, if any, becomes its ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_jwt::Key;
+    ///
+    /// let jwk = r#"{
+    ///     "kty": "EC",
+    ///     "crv": "P-256",
+    ///     "kid": "1",
+    ///     "x": "AOwlzdmAFPnWEChgB5h_yINR5qqde_dvPi-S9hOV6rc",
+    ///     "y": "yb1HwAURs1EAlnVFgmIAUHEuXpTxhN4cHAfFKIbJrHs"
+    /// }"#;
+    ///
+    /// let key = Key::from_jwk(jwk).unwrap();
+    /// assert_eq!(key.id(), Some("1"));
+    /// ```
+    pub fn from_jwk(jwk: &str) -> Result<Key, KeyError> {
+        let value = rocket::serde::json::from_str(jwk)
+            .map_err(|e| KeyError(format!("invalid JWK JSON: {}", e)))?;
+
+        Key::from_jwk_value(&value)
+    }
+
+    pub(crate) fn from_jwk_value(jwk: &Value) -> Result<Key, KeyError> {
+        let field = |name: &str| -> Result<Vec<u8>, KeyError> {
+            let value = jwk.get(name).and_then(|v| v.as_str())
+                .ok_or_else(|| KeyError(format!("JWK is missing `{}`", name)))?;
+
+            base64::decode_config(value, base64::URL_SAFE_NO_PAD)
+                .map_err(|_| KeyError(format!("JWK `{}` is not valid base64url", name)))
+        };
+
+        let material = match jwk.get("kty").and_then(|v| v.as_str()) {
+            Some("RSA") => Material::Rsa { n: strip_zeros(field("n")?), e: strip_zeros(field("e")?) },
+            Some("EC") => {
+                let curve = match jwk.get("crv").and_then(|v| v.as_str()) {
+                    Some("P-256") => Curve::P256,
+                    Some("P-384") => Curve::P384,
+                    crv => return Err(KeyError(format!("unsupported JWK curve {:?}", crv))),
+                };
+
+                let mut point = vec![0x04];
+                point.extend(field("x")?);
+                point.extend(field("y")?);
+                Material::Ec { curve, point }
+            }
+            kty => return Err(KeyError(format!("unsupported JWK key type {:?}", kty))),
+        };
+
+        let id = jwk.get("kid").and_then(|v| v.as_str()).map(String::from);
+        Ok(Key { id, material })
+    }
+
+    /// Sets the ID of the key.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_jwt::Key;
+    ///
+    /// let key = Key::hmac("secret").with_id("primary");
+    /// ```
+    pub fn with_id<S: Into<String>>(mut self, id: S) -> Key {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Returns the ID of the key, if it has one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_jwt::Key;
+    ///
+    /// assert_eq!(Key::hmac("secret").id(), None);
+    /// assert_eq!(Key::hmac("secret").with_id("primary").id(), Some("primary"));
+    /// ```
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// Returns `true` if the key verifies signatures made with `alg`.
+    pub(crate) fn supports(&self, alg: Algorithm) -> bool {
+        use Algorithm::*;
+
+        match &self.material {
+            Material::Hmac(_) => matches!(alg, HS256 | HS384 | HS512),
+            Material::Rsa { .. } => matches!(alg, RS256 | RS384 | RS512 | PS256 | PS384 | PS512),
+            Material::Ec { curve: Curve::P256, .. } => alg == ES256,
+            Material::Ec { curve: Curve::P384, .. } => alg == ES384,
+        }
+    }
+
+    /// Returns `true` if `sig` is a valid signature of `msg` made with `alg`
+    /// by this key.
+    pub(crate) fn verify(&self, alg: Algorithm, msg: &[u8], sig: &[u8]) -> bool {
+        use Algorithm::*;
+
+        if !self.supports(alg) {
+            return false;
+        }
+
+        match &self.material {
+            Material::Hmac(secret) => {
+                let alg = match alg {
+                    HS256 => hmac::HMAC_SHA256,
+                    HS384 => hmac::HMAC_SHA384,
+                    _ => hmac::HMAC_SHA512,
+                };
+
+                hmac::verify(&hmac::Key::new(alg, secret), msg, sig).is_ok()
+            }
+            Material::Rsa { n, e } => {
+                let params = match alg {
+                    RS256 => &signature::RSA_PKCS1_2048_8192_SHA256,
+                    RS384 => &signature::RSA_PKCS1_2048_8192_SHA384,
+                    RS512 => &signature::RSA_PKCS1_2048_8192_SHA512,
+                    PS256 => &signature::RSA_PSS_2048_8192_SHA256,
+                    PS384 => &signature::RSA_PSS_2048_8192_SHA384,
+                    _ => &signature::RSA_PSS_2048_8192_SHA512,
+                };
+
+                signature::RsaPublicKeyComponents { n, e }.verify(params, msg, sig).is_ok()
+            }
+            Material::Ec { curve, point } => {
+                let alg = match curve {
+                    Curve::P256 => &signature::ECDSA_P256_SHA256_FIXED,
+                    Curve::P384 => &signature::ECDSA_P384_SHA384_FIXED,
+                };
+
+                signature::UnparsedPublicKey::new(alg, point).verify(msg, sig).is_ok()
+            }
+        }
+    }
+}
+
+impl KeyError {
+    fn new(msg: &str) -> KeyError {
+        KeyError(msg.into())
+    }
+}
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for KeyError { }
+
+impl fmt::Debug for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.material {
+            Material::Hmac(_) => "HMAC",
+            Material::Rsa { .. } => "RSA",
+            Material::Ec { curve: Curve::P256, .. } => "EC P-256",
+            Material::Ec { curve: Curve::P384, .. } => "EC P-384",
+        };
+
+        f.debug_struct("Key")
+            .field("id", &self.id)
+            .field("kind", &kind)
+            .finish_non_exhaustive()
+    }
+}
+
+fn strip_zeros(mut bytes: Vec<u8>) -> Vec<u8> {
+    let zeros = bytes.iter().take_while(|b| **b == 0).count();
+    bytes.drain(..zeros);
+    bytes
+}
+
+/// Reads a DER TLV from `input`, returning its tag, contents, and the rest of
+/// `input`.
+fn read_tlv(input: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, input) = input.split_first()?;
+    let (&len, mut input) = input.split_first()?;
+    let len = match len {
+        0..=0x7f => len as usize,
+        0x81..=0x84 => {
+            let n = (len & 0x7f) as usize;
+            let (bytes, rest) = (input.get(..n)?, input.get(n..)?);
+            input = rest;
+            bytes.iter().fold(0, |acc, b| (acc << 8) | *b as usize)
+        }
+        _ => return None,
+    };
+
+    Some((tag, input.get(..len)?, input.get(len..)?))
+}
+
+const SEQUENCE: u8 = 0x30;
+const INTEGER: u8 = 0x02;
+const BIT_STRING: u8 = 0x03;
+const OID: u8 = 0x06;
+
+const RSA_ENCRYPTION: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+const EC_PUBLIC_KEY: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+const P256: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07];
+const P384: &[u8] = &[0x2b, 0x81, 0x04, 0x00, 0x22];
+
+/// Reads a DER TLV with tag `tag` from `input`, returning its contents and the
+/// rest of `input`.
+fn expect_tlv(tag: u8, input: &[u8]) -> Option<(&[u8], &[u8])> {
+    read_tlv(input).filter(|(t, ..)| *t == tag).map(|(_, contents, rest)| (contents, rest))
+}
+
+/// Parses a DER-encoded `SubjectPublicKeyInfo` containing an RSA or EC key.
+fn parse_spki(der: &[u8]) -> Option<Material> {
+    let (spki, _) = expect_tlv(SEQUENCE, der)?;
+    let (alg, spki) = expect_tlv(SEQUENCE, spki)?;
+    let (key, _) = expect_tlv(BIT_STRING, spki)?;
+    let (oid, params) = expect_tlv(OID, alg)?;
+    let key = key.strip_prefix(&[0])?;
+
+    match oid {
+        RSA_ENCRYPTION => {
+            let (key, _) = expect_tlv(SEQUENCE, key)?;
+            let (n, key) = expect_tlv(INTEGER, key)?;
+            let (e, _) = expect_tlv(INTEGER, key)?;
+            Some(Material::Rsa { n: strip_zeros(n.to_vec()), e: strip_zeros(e.to_vec()) })
+        }
+        EC_PUBLIC_KEY => {
+            let curve = match expect_tlv(OID, params)?.0 {
+                P256 => Curve::P256,
+                P384 => Curve::P384,
+                _ => return None,
+            };
+
+            Some(Material::Ec { curve, point: key.to_vec() })
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,85 @@
+//! JSON Web Token bearer authentication for Rocket.
+//!
+//! This crate authenticates requests via [JSON Web Tokens] sent in an
+//! `Authorization: Bearer` header. The [`Jwt`] request guard verifies a
+//! token's signature against the [`Key`]s configured in the [`JwtAuth`]
+//! fairing, validates its expiration, issuer, and audience, and deserializes
+//! its claims into any type that implements
+//! [`Deserialize`](rocket::serde::Deserialize).
+//!
+//! [JSON Web Tokens]: https://www.rfc-editor.org/rfc/rfc7519
+//!
+//! # Usage
+//!
+//!   1. Depend on `rocket_jwt`:
+//!
+//!      ```toml
+//!      [dependencies.rocket_jwt]
+//!      version = "0.1.0-rc.2"
+//!      ```
+//!
+//!   2. Attach a [`JwtAuth`] fairing and add a [`Jwt`] request guard to every
+//!      route that requires authentication:
+//!
+//!      ```rust
+//!      # #[macro_use] extern crate rocket;
+//!      use rocket::serde::Deserialize;
+//!      use rocket_jwt::{JwtAuth, Jwt, Key};
+//!
+//!      #[derive(Deserialize)]
+//!      # #[serde(crate = "rocket::serde")]
+//!      struct Claims {
+//!          sub: String,
+//!      }
+//!
+//!      #[get("/")]
+//!      fn index(token: Jwt<Claims>) -> String {
+//!          format!("Hello, {}!", token.sub)
+//!      }
+//!
+//!      #[launch]
+//!      fn rocket() -> _ {
+//!          rocket::build()
+//!              .attach(JwtAuth::new().key(Key::hmac("a very secret key")))
+//!              .mount("/", routes![index])
+//!      }
+//!      ```
+//!
+//! Requests without a valid token fail with `401 Unauthorized`, and the
+//! fairing adds a `WWW-Authenticate` header describing the failure to the
+//! response. Requests with a valid token whose claims can't be deserialized,
+//! as when a required claim is missing, fail with `403 Forbidden`.
+//!
+//! ## Keys
+//!
+//! Tokens can be signed with HMAC secrets, RSA keys, or ECDSA P-256 and P-384
+//! keys; see [`Key`] for the supported algorithms. Each key only verifies
+//! tokens signed with an algorithm of its own kind, and unsigned tokens are
+//! always rejected.
+//!
+//! ## Features
+//!
+//! The `jwks` feature adds [`JwtAuth::jwks()`], which fetches keys from an
+//! identity provider's JSON Web Key Set URL, caching and periodically
+//! refreshing them:
+//!
+//! ```toml
+//! [dependencies.rocket_jwt]
+//! version = "0.1.0-rc.2"
+//! features = ["jwks"]
+//! ```
+
+#[macro_use]
+extern crate rocket;
+
+mod key;
+mod verifier;
+mod fairing;
+mod guard;
+
+#[cfg(feature = "jwks")]
+mod jwks;
+
+pub use self::key::{Key, KeyError, Algorithm};
+pub use self::fairing::JwtAuth;
+pub use self::guard::{Jwt, Error};
@@ -0,0 +1,183 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rocket::serde::json::{self, Value};
+use rocket::serde::json::serde_json::Map;
+
+use crate::{Algorithm, Key, Error};
+
+#[cfg(feature = "jwks")]
+use crate::jwks::Jwks;
+
+/// The state managed by [`JwtAuth`](crate::JwtAuth) and used by the `Jwt`
+/// guard to verify tokens.
+pub(crate) struct Verifier {
+    pub(crate) keys: Vec<Key>,
+    #[cfg(feature = "jwks")]
+    pub(crate) jwks: Option<Jwks>,
+    pub(crate) issuer: Option<String>,
+    pub(crate) audience: Option<String>,
+    pub(crate) leeway: Duration,
+}
+
+impl Verifier {
+    /// Verifies the compact-serialized JWS `token` and validates its
+    /// registered claims, returning all of its claims.
+    pub(crate) async fn verify(&self, token: &str) -> Result<Map<String, Value>, Error> {
+        let mut parts = token.split('.');
+        let (header, payload, signature) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(h), Some(p), Some(s)) if parts.next().is_none() => (h, p, s),
+            _ => return Err(Error::Malformed),
+        };
+
+        let header_json = decode_object(header)?;
+        let alg = header_json.get("alg")
+            .and_then(Value::as_str)
+            .and_then(Algorithm::parse)
+            .ok_or(Error::Signature)?;
+
+        let kid = header_json.get("kid").and_then(Value::as_str);
+        let signature = decode(signature)?;
+        let message = &token.as_bytes()[..header.len() + 1 + payload.len()];
+        if !self.verify_signature(alg, kid, message, &signature).await {
+            return Err(Error::Signature);
+        }
+
+        let claims = decode_object(payload)?;
+        self.validate(&claims, now())?;
+        Ok(claims)
+    }
+
+    async fn verify_signature(
+        &self,
+        alg: Algorithm,
+        kid: Option<&str>,
+        message: &[u8],
+        signature: &[u8],
+    ) -> bool {
+        let matches = |key: &Key| {
+            kid.map_or(true, |kid| key.id().map_or(true, |id| id == kid))
+                && key.verify(alg, message, signature)
+        };
+
+        if self.keys.iter().any(matches) {
+            return true;
+        }
+
+        #[cfg(feature = "jwks")]
+        if let Some(jwks) = &self.jwks {
+            return jwks.keys(kid).await.iter().any(matches);
+        }
+
+        false
+    }
+
+    /// Validates the registered `exp`, `nbf`, `iss`, and `aud` claims at
+    /// `now`, in seconds since the Unix epoch.
+    fn validate(&self, claims: &Map<String, Value>, now: f64) -> Result<(), Error> {
+        let leeway = self.leeway.as_secs_f64();
+        match claims.get("exp").map(Value::as_f64) {
+            Some(Some(exp)) if now < exp + leeway => {},
+            Some(Some(_)) | None => return Err(Error::Expired),
+            Some(None) => return Err(Error::Malformed),
+        }
+
+        match claims.get("nbf").map(Value::as_f64) {
+            Some(Some(nbf)) if now + leeway < nbf => return Err(Error::Immature),
+            Some(None) => return Err(Error::Malformed),
+            _ => {},
+        }
+
+        if let Some(issuer) = &self.issuer {
+            if claims.get("iss").and_then(Value::as_str) != Some(issuer.as_str()) {
+                return Err(Error::Issuer);
+            }
+        }
+
+        if let Some(audience) = &self.audience {
+            let valid = match claims.get("aud") {
+                Some(Value::String(aud)) => aud == audience,
+                Some(Value::Array(auds)) => auds.iter().any(|aud| aud.as_str() == Some(audience.as_str())),
+                _ => false,
+            };
+
+            if !valid {
+                return Err(Error::Audience);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn now() -> f64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0.0, |d| d.as_secs_f64())
+}
+
+fn decode(part: &str) -> Result<Vec<u8>, Error> {
+    base64::decode_config(part, base64::URL_SAFE_NO_PAD).map_err(|_| Error::Malformed)
+}
+
+fn decode_object(part: &str) -> Result<Map<String, Value>, Error> {
+    match json::from_slice(&decode(part)?) {
+        Ok(Value::Object(map)) => Ok(map),
+        _ => Err(Error::Malformed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verifier() -> Verifier {
+        Verifier {
+            keys: vec![],
+            #[cfg(feature = "jwks")]
+            jwks: None,
+            issuer: Some("issuer".into()),
+            audience: Some("api".into()),
+            leeway: Duration::from_secs(60),
+        }
+    }
+
+    fn claims(value: Value) -> Map<String, Value> {
+        match value {
+            Value::Object(map) => map,
+            _ => unreachable!("claims are objects"),
+        }
+    }
+
+    #[test]
+    fn validates_times() {
+        let v = verifier();
+        let at = |exp: Value, nbf: Value| {
+            let claims = claims(json::json!({ "exp": exp, "nbf": nbf, "iss": "issuer", "aud": "api" }));
+            v.validate(&claims, 1000.0)
+        };
+
+        assert!(at(1001.into(), 999.into()).is_ok());
+        assert!(at(1000.into(), 1000.into()).is_ok());
+        assert!(at(950.into(), 1050.into()).is_ok());
+        assert!(matches!(at(940.into(), 999.into()), Err(Error::Expired)));
+        assert!(matches!(at(1100.into(), 1061.into()), Err(Error::Immature)));
+        assert!(matches!(at("soon".into(), 999.into()), Err(Error::Malformed)));
+
+        let claims = claims(json::json!({ "iss": "issuer", "aud": "api" }));
+        assert!(matches!(v.validate(&claims, 1000.0), Err(Error::Expired)));
+    }
+
+    #[test]
+    fn validates_issuer_and_audience() {
+        let v = verifier();
+        let check = |iss: Value, aud: Value| {
+            let claims = claims(json::json!({ "exp": 2000, "iss": iss, "aud": aud }));
+            v.validate(&claims, 1000.0)
+        };
+
+        assert!(check("issuer".into(), "api".into()).is_ok());
+        assert!(check("issuer".into(), json::json!(["web", "api"])).is_ok());
+        assert!(matches!(check("other".into(), "api".into()), Err(Error::Issuer)));
+        assert!(matches!(check(Value::Null, "api".into()), Err(Error::Issuer)));
+        assert!(matches!(check("issuer".into(), "web".into()), Err(Error::Audience)));
+        assert!(matches!(check("issuer".into(), json::json!(["web"])), Err(Error::Audience)));
+    }
+}
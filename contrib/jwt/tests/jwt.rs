@@ -0,0 +1,317 @@
+#[macro_use] extern crate rocket;
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ring::{hmac, rand, signature};
+use rocket::{Rocket, Build};
+use rocket::http::{Header, Status};
+use rocket::local::blocking::{Client, LocalResponse};
+use rocket::serde::{Deserialize, json::{json, Value}};
+use rocket_jwt::{JwtAuth, Jwt, Key};
+
+const SECRET: &str = "a very secret key";
+
+#[derive(Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct Admin {
+    sub: String,
+    #[allow(dead_code)]
+    admin: bool,
+}
+
+#[get("/")]
+fn index(token: Jwt) -> String {
+    token.claims()["sub"].as_str().unwrap_or("").to_string()
+}
+
+#[get("/admin")]
+fn admin(token: Jwt<Admin>) -> String {
+    format!("admin {}", token.sub)
+}
+
+fn rocket(auth: JwtAuth) -> Rocket<Build> {
+    rocket::build()
+        .attach(auth)
+        .mount("/", routes![index, admin])
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
+
+fn encode(value: &[u8]) -> String {
+    base64::encode_config(value, base64::URL_SAFE_NO_PAD)
+}
+
+/// Returns a token with header `header` and claims `claims` signed by `sign`.
+fn token<F: FnOnce(&[u8]) -> Vec<u8>>(header: Value, claims: Value, sign: F) -> String {
+    let message = format!("{}.{}", encode(header.to_string().as_bytes()), encode(claims.to_string().as_bytes()));
+    let signature = sign(message.as_bytes());
+    format!("{}.{}", message, encode(&signature))
+}
+
+fn hs256(claims: Value) -> String {
+    token(json!({ "alg": "HS256", "typ": "JWT" }), claims, |msg| {
+        let key = hmac::Key::new(hmac::HMAC_SHA256, SECRET.as_bytes());
+        hmac::sign(&key, msg).as_ref().to_vec()
+    })
+}
+
+fn claims() -> Value {
+    json!({ "sub": "bob", "exp": now() + 300 })
+}
+
+fn get<'c>(client: &'c Client, uri: &'static str, token: &str) -> LocalResponse<'c> {
+    client.get(uri)
+        .header(Header::new("Authorization", format!("Bearer {}", token)))
+        .dispatch()
+}
+
+fn challenge(response: &LocalResponse<'_>) -> Option<String> {
+    response.headers().get_one("WWW-Authenticate").map(String::from)
+}
+
+#[test]
+fn hmac_tokens() {
+    let client = Client::debug(rocket(JwtAuth::new().key(Key::hmac(SECRET)))).unwrap();
+    let response = get(&client, "/", &hs256(claims()));
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(challenge(&response), None);
+    assert_eq!(response.into_string().unwrap(), "bob");
+
+    let response = client.get("/")
+        .header(Header::new("Authorization", format!("bearer  {}", hs256(claims()))))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+}
+
+#[test]
+fn missing_and_malformed_tokens() {
+    let client = Client::debug(rocket(JwtAuth::new().key(Key::hmac(SECRET)))).unwrap();
+    let response = client.get("/").dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(challenge(&response).unwrap(), "Bearer");
+
+    let response = client.get("/").header(Header::new("Authorization", "Basic Ym9iOmJvYg==")).dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(challenge(&response).unwrap(), "Bearer");
+
+    for token in &["", "abc", "a.b.c", "a.b.c.d"] {
+        let response = get(&client, "/", token);
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+
+    let mut token = hs256(claims());
+    token.push('A');
+    let response = get(&client, "/", &token);
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert!(challenge(&response).unwrap().contains(r#"error="invalid_token""#));
+}
+
+#[test]
+fn rejects_forged_tokens() {
+    let client = Client::debug(rocket(JwtAuth::new().key(Key::hmac(SECRET)))).unwrap();
+    let forged = token(json!({ "alg": "HS256" }), claims(), |msg| {
+        let key = hmac::Key::new(hmac::HMAC_SHA256, b"another key");
+        hmac::sign(&key, msg).as_ref().to_vec()
+    });
+
+    assert_eq!(get(&client, "/", &forged).status(), Status::Unauthorized);
+
+    let unsigned = token(json!({ "alg": "none" }), claims(), |_| vec![]);
+    assert_eq!(get(&client, "/", &unsigned).status(), Status::Unauthorized);
+
+    // A token signed with the secret, but claiming a public key algorithm.
+    let confused = token(json!({ "alg": "RS256" }), claims(), |msg| {
+        let key = hmac::Key::new(hmac::HMAC_SHA256, SECRET.as_bytes());
+        hmac::sign(&key, msg).as_ref().to_vec()
+    });
+
+    assert_eq!(get(&client, "/", &confused).status(), Status::Unauthorized);
+}
+
+#[test]
+fn validates_claims() {
+    let auth = JwtAuth::new().key(Key::hmac(SECRET)).issuer("auth").audience("api");
+    let client = Client::debug(rocket(auth)).unwrap();
+    let check = |claims: Value| get(&client, "/", &hs256(claims)).status();
+
+    let now = now();
+    assert_eq!(check(json!({ "exp": now + 60, "iss": "auth", "aud": "api" })), Status::Ok);
+    assert_eq!(check(json!({ "exp": now + 60, "iss": "auth", "aud": ["web", "api"] })), Status::Ok);
+    assert_eq!(check(json!({ "exp": now - 30, "iss": "auth", "aud": "api" })), Status::Ok);
+
+    let expired = get(&client, "/", &hs256(json!({ "exp": now - 120, "iss": "auth", "aud": "api" })));
+    assert_eq!(expired.status(), Status::Unauthorized);
+    assert!(challenge(&expired).unwrap().contains("expired"));
+
+    assert_eq!(check(json!({ "iss": "auth", "aud": "api" })), Status::Unauthorized);
+    assert_eq!(check(json!({ "exp": now + 600, "nbf": now + 300, "iss": "auth", "aud": "api" })), Status::Unauthorized);
+    assert_eq!(check(json!({ "exp": now + 60, "iss": "other", "aud": "api" })), Status::Unauthorized);
+    assert_eq!(check(json!({ "exp": now + 60, "aud": "api" })), Status::Unauthorized);
+    assert_eq!(check(json!({ "exp": now + 60, "iss": "auth", "aud": "web" })), Status::Unauthorized);
+    assert_eq!(check(json!({ "exp": now + 60, "iss": "auth" })), Status::Unauthorized);
+}
+
+#[test]
+fn leeway() {
+    let auth = JwtAuth::new().key(Key::hmac(SECRET)).leeway(std::time::Duration::ZERO);
+    let client = Client::debug(rocket(auth)).unwrap();
+    let token = hs256(json!({ "sub": "bob", "exp": now() - 1 }));
+    assert_eq!(get(&client, "/", &token).status(), Status::Unauthorized);
+}
+
+#[test]
+fn claims_mismatch_is_forbidden() {
+    let client = Client::debug(rocket(JwtAuth::new().key(Key::hmac(SECRET)))).unwrap();
+    let response = get(&client, "/admin", &hs256(claims()));
+    assert_eq!(response.status(), Status::Forbidden);
+    assert_eq!(challenge(&response).unwrap(), r#"Bearer error="insufficient_scope""#);
+
+    let token = hs256(json!({ "sub": "alice", "admin": true, "exp": now() + 60 }));
+    let response = get(&client, "/admin", &token);
+    assert_eq!(response.into_string().unwrap(), "admin alice");
+}
+
+#[test]
+fn ecdsa_tokens() {
+    let rng = rand::SystemRandom::new();
+    let alg = &signature::ECDSA_P256_SHA256_FIXED_SIGNING;
+    let pkcs8 = signature::EcdsaKeyPair::generate_pkcs8(alg, &rng).unwrap();
+    let pair = signature::EcdsaKeyPair::from_pkcs8(alg, pkcs8.as_ref()).unwrap();
+
+    let point = signature::KeyPair::public_key(&pair).as_ref();
+    let jwk = json!({
+        "kty": "EC",
+        "crv": "P-256",
+        "kid": "ec",
+        "x": encode(&point[1..33]),
+        "y": encode(&point[33..]),
+    });
+
+    let key = Key::from_jwk(&jwk.to_string()).unwrap();
+    let client = Client::debug(rocket(JwtAuth::new().key(Key::hmac(SECRET)).key(key))).unwrap();
+    let sign = |kid: &str| token(json!({ "alg": "ES256", "kid": kid }), claims(), |msg| {
+        pair.sign(&rng, msg).unwrap().as_ref().to_vec()
+    });
+
+    assert_eq!(get(&client, "/", &sign("ec")).status(), Status::Ok);
+    assert_eq!(get(&client, "/", &sign("other")).status(), Status::Unauthorized);
+}
+
+#[test]
+fn rsa_tokens() {
+    let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures");
+    let der = std::fs::read(fixtures.join("rsa.der")).unwrap();
+    let pem = std::fs::read_to_string(fixtures.join("rsa.pub.pem")).unwrap();
+    let pair = signature::RsaKeyPair::from_der(&der).unwrap();
+
+    let client = Client::debug(rocket(JwtAuth::new().key(Key::from_pem(&pem).unwrap()))).unwrap();
+    let rng = rand::SystemRandom::new();
+    let sign = |name: &str, padding: &'static dyn signature::RsaEncoding| {
+        token(json!({ "alg": name }), claims(), |msg| {
+            let mut signature = vec![0; pair.public_modulus_len()];
+            pair.sign(padding, &rng, msg, &mut signature).unwrap();
+            signature
+        })
+    };
+
+    assert_eq!(get(&client, "/", &sign("RS256", &signature::RSA_PKCS1_SHA256)).status(), Status::Ok);
+    assert_eq!(get(&client, "/", &sign("RS512", &signature::RSA_PKCS1_SHA512)).status(), Status::Ok);
+    assert_eq!(get(&client, "/", &sign("PS256", &signature::RSA_PSS_SHA256)).status(), Status::Ok);
+    assert_eq!(get(&client, "/", &sign("RS384", &signature::RSA_PKCS1_SHA256)).status(), Status::Unauthorized);
+}
+
+#[test]
+fn requires_keys() {
+    let error = Client::debug(rocket(JwtAuth::new())).unwrap_err();
+    assert!(matches!(error.kind(), rocket::error::ErrorKind::FailedFairings(_)));
+}
+
+#[test]
+fn unmanaged() {
+    let rocket = rocket::build().mount("/", routes![index]);
+    let client = Client::debug(rocket).unwrap();
+    assert_eq!(get(&client, "/", &hs256(claims())).status(), Status::InternalServerError);
+}
+
+#[cfg(feature = "jwks")]
+mod jwks {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    /// Serves `jwks` over HTTP on a random port, returning the URL of the key
+    /// set and a count of the requests served.
+    fn serve(jwks: String) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/.well-known/jwks.json", listener.local_addr().unwrap());
+        let count = Arc::new(AtomicUsize::new(0));
+        let served = count.clone();
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut buf = [0; 4096];
+                let _ = stream.read(&mut buf);
+                served.fetch_add(1, Ordering::SeqCst);
+                let _ = write!(stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                    Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    jwks.len(), jwks);
+            }
+        });
+
+        (url, count)
+    }
+
+    #[test]
+    fn fetches_and_caches_key_sets() {
+        let rng = rand::SystemRandom::new();
+        let alg = &signature::ECDSA_P256_SHA256_FIXED_SIGNING;
+        let pkcs8 = signature::EcdsaKeyPair::generate_pkcs8(alg, &rng).unwrap();
+        let pair = signature::EcdsaKeyPair::from_pkcs8(alg, pkcs8.as_ref()).unwrap();
+        let point = signature::KeyPair::public_key(&pair).as_ref();
+        let jwks = json!({
+            "keys": [{
+                "kty": "EC",
+                "crv": "P-256",
+                "kid": "ec",
+                "x": encode(&point[1..33]),
+                "y": encode(&point[33..]),
+            }]
+        });
+
+        let (url, count) = serve(jwks.to_string());
+        let client = Client::debug(rocket(JwtAuth::new().jwks(url))).unwrap();
+        let sign = |kid: &str| token(json!({ "alg": "ES256", "kid": kid }), claims(), |msg| {
+            pair.sign(&rng, msg).unwrap().as_ref().to_vec()
+        });
+
+        assert_eq!(get(&client, "/", &sign("ec")).status(), Status::Ok);
+        assert_eq!(get(&client, "/", &sign("ec")).status(), Status::Ok);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        // Unknown key IDs don't trigger fetches more often than allowed.
+        assert_eq!(get(&client, "/", &sign("other")).status(), Status::Unauthorized);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn unreachable_key_sets() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/jwks", listener.local_addr().unwrap());
+        drop(listener);
+
+        let client = Client::debug(rocket(JwtAuth::new().jwks(url))).unwrap();
+        assert_eq!(get(&client, "/", &hs256(claims())).status(), Status::Unauthorized);
+    }
+
+    #[test]
+    fn invalid_urls() {
+        let error = Client::debug(rocket(JwtAuth::new().jwks("ftp://localhost/jwks"))).unwrap_err();
+        assert!(matches!(error.kind(), rocket::error::ErrorKind::FailedFairings(_)));
+    }
+}
@@ -0,0 +1,68 @@
+//! Prometheus request metrics for Rocket.
+//!
+//! This crate records metrics about the requests an application handles and
+//! serves them in the [Prometheus text exposition format], ready to be scraped
+//! by Prometheus or any compatible collector.
+//!
+//! # Usage
+//!
+//!   1. Depend on `rocket_metrics`:
+//!
+//!      ```toml
+//!      [dependencies.rocket_metrics]
+//!      version = "0.1.0-rc.2"
+//!      ```
+//!
+//!   2. Attach a [`Metrics`] fairing and mount a clone of it at the path
+//!      metrics should be served from:
+//!
+//!      ```rust
+//!      # #[macro_use] extern crate rocket;
+//!      use rocket_metrics::Metrics;
+//!
+//!      #[get("/")]
+//!      fn index() -> &'static str {
+//!          "Hello, world!"
+//!      }
+//!
+//!      #[launch]
+//!      fn rocket() -> _ {
+//!          let metrics = Metrics::new();
+//!          rocket::build()
+//!              .attach(metrics.clone())
+//!              .mount("/", routes![index])
+//!              .mount("/metrics", metrics)
+//!      }
+//!      ```
+//!
+//! # Metrics
+//!
+//! The following metrics are recorded. Their names are prefixed with a
+//! [namespace](Metrics::namespace()), `rocket` by default.
+//!
+//!   * `rocket_http_requests_total` - a counter of requests handled
+//!   * `rocket_http_request_duration_seconds` - a histogram of request
+//!     latencies, with [configurable](Metrics::buckets()) buckets
+//!   * `rocket_http_requests_in_flight` - a gauge of requests being handled
+//!
+//! The first two are labeled with the request's `method`, the URI of the
+//! `route` that handled the request, such as `/user/<id>`, and the response's
+//! `status` code. Requests not handled by any route, such as those that result
+//! in a `404`, have an empty `route` label. Labeling by route URI rather than
+//! by request URI keeps the number of distinct series bounded. The in-flight
+//! gauge is labeled only by `method` since a request's route and status aren't
+//! known until it's been handled.
+//!
+//! A request's latency is measured from when the `Metrics` fairing's request
+//! callback runs to when its response callback runs. It thus includes request
+//! fairings attached after `Metrics`, routing, guards, handlers, and catchers,
+//! but not the writing of the response body. Requests to the mounted `Metrics`
+//! endpoint are themselves recorded.
+//!
+//! [Prometheus text exposition format]:
+//! https://prometheus.io/docs/instrumenting/exposition_formats/#text-based-format
+
+mod registry;
+mod metrics;
+
+pub use self::metrics::Metrics;
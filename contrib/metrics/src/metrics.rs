@@ -0,0 +1,207 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use rocket::{Request, Response, Data, Route};
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::{Method, ContentType};
+use rocket::route::{Handler, Outcome};
+
+use crate::registry::{Registry, Labels};
+
+/// Fairing that records request metrics and handler that serves them.
+///
+/// Attach a `Metrics` to record metrics and mount a clone of it to serve them
+/// at `GET` requests to the mount point:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_metrics::Metrics;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     let metrics = Metrics::new();
+///     rocket::build()
+///         .attach(metrics.clone())
+///         .mount("/metrics", metrics)
+/// }
+/// ```
+///
+/// All clones of a `Metrics` share the same metrics. See the [crate
+/// docs](crate#metrics) for the metrics that are recorded.
+#[derive(Clone)]
+pub struct Metrics {
+    registry: Arc<Registry>,
+}
+
+/// The default latency histogram buckets, in seconds.
+const DEFAULT_BUCKETS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// A request's in-flight status, cached in its local cache. The request is
+/// no longer in flight once it reaches the response fairing or, if it never
+/// does, such as when the client disconnects, when it's dropped.
+struct InFlight {
+    registry: Option<Arc<Registry>>,
+    method: &'static str,
+    started: Instant,
+    done: AtomicBool,
+}
+
+impl InFlight {
+    fn finish(&self) -> bool {
+        let was_done = self.done.swap(true, Ordering::AcqRel);
+        if let (false, Some(registry)) = (was_done, &self.registry) {
+            registry.finish(self.method);
+            return true;
+        }
+
+        false
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+impl Metrics {
+    /// Returns a new `Metrics` with the namespace `rocket` and the default
+    /// latency buckets: `0.005`, `0.01`, `0.025`, `0.05`, `0.1`, `0.25`, `0.5`,
+    /// `1`, `2.5`, `5`, and `10` seconds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_metrics::Metrics;
+    ///
+    /// let metrics = Metrics::new();
+    /// ```
+    pub fn new() -> Metrics {
+        Metrics::with("rocket", DEFAULT_BUCKETS.to_vec())
+    }
+
+    fn with(namespace: &str, buckets: Vec<f64>) -> Metrics {
+        Metrics { registry: Arc::new(Registry::new(namespace.into(), buckets)) }
+    }
+
+    /// Sets the namespace that prefixes the name of every metric. Discards
+    /// any recorded metrics.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `namespace` is not a valid Prometheus metric name: a
+    /// non-empty string of ASCII letters, digits, and `_` that does not start
+    /// with a digit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_metrics::Metrics;
+    ///
+    /// // Metrics are named `app_http_requests_total` and so on.
+    /// let metrics = Metrics::new().namespace("app");
+    /// ```
+    pub fn namespace(self, namespace: &str) -> Metrics {
+        let valid = namespace.chars().enumerate()
+            .all(|(i, c)| c == '_' || c.is_ascii_alphabetic() || (i > 0 && c.is_ascii_digit()));
+
+        assert!(valid && !namespace.is_empty(), "invalid metrics namespace: {:?}", namespace);
+        Metrics::with(namespace, self.registry.buckets().to_vec())
+    }
+
+    /// Sets the upper bounds, in seconds, of the latency histogram buckets.
+    /// The `+Inf` bucket is always included. Discards any recorded metrics.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bounds are not finite and strictly increasing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_metrics::Metrics;
+    ///
+    /// let metrics = Metrics::new().buckets(&[0.01, 0.1, 1.0]);
+    /// ```
+    pub fn buckets(self, buckets: &[f64]) -> Metrics {
+        assert!(buckets.iter().all(|b| b.is_finite()), "buckets must be finite");
+        assert!(buckets.windows(2).all(|w| w[0] < w[1]), "buckets must be strictly increasing");
+        Metrics::with(self.registry.namespace(), buckets.to_vec())
+    }
+
+    /// Renders the recorded metrics in the Prometheus text exposition format.
+    /// This is the body of the response to a request to a mounted `Metrics`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_metrics::Metrics;
+    ///
+    /// let metrics = Metrics::new();
+    /// assert!(metrics.render().contains("# TYPE rocket_http_requests_total counter"));
+    /// ```
+    pub fn render(&self) -> String {
+        self.registry.render()
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Metrics::new()
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for Metrics {
+    fn info(&self) -> Info {
+        Info {
+            name: "Metrics",
+            kind: Kind::Request | Kind::Response | Kind::Singleton,
+        }
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _: &mut Data<'_>) {
+        let method = req.method().as_str();
+        self.registry.start(method);
+        req.local_cache(|| InFlight {
+            registry: Some(self.registry.clone()),
+            method,
+            started: Instant::now(),
+            done: AtomicBool::new(false),
+        });
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let in_flight = req.local_cache(|| InFlight {
+            registry: None,
+            method: req.method().as_str(),
+            started: Instant::now(),
+            done: AtomicBool::new(false),
+        });
+
+        if in_flight.finish() {
+            self.registry.observe(Labels {
+                method: in_flight.method,
+                route: req.route().map(|r| r.uri.as_str().to_string()).unwrap_or_default(),
+                status: res.status().code,
+            }, in_flight.started.elapsed());
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl Handler for Metrics {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
+        let content_type = ContentType::new("text", "plain").with_params(("version", "0.0.4"));
+        Outcome::from(req, (content_type, self.render()))
+    }
+}
+
+impl From<Metrics> for Vec<Route> {
+    fn from(metrics: Metrics) -> Self {
+        let mut route = Route::new(Method::Get, "/", metrics);
+        route.name = Some("Metrics".into());
+        vec![route]
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// The metrics recorded by a [`Metrics`](crate::Metrics) fairing.
+pub(crate) struct Registry {
+    namespace: String,
+    buckets: Vec<f64>,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    requests: BTreeMap<Labels, Series>,
+    in_flight: BTreeMap<&'static str, u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Labels {
+    pub(crate) method: &'static str,
+    pub(crate) route: String,
+    pub(crate) status: u16,
+}
+
+/// The count, latency sum, and per-bucket, non-cumulative latency counts of
+/// requests with the same labels.
+struct Series {
+    count: u64,
+    sum: f64,
+    buckets: Vec<u64>,
+}
+
+impl Registry {
+    pub(crate) fn new(namespace: String, buckets: Vec<f64>) -> Registry {
+        Registry { namespace, buckets, state: Mutex::default() }
+    }
+
+    pub(crate) fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    pub(crate) fn buckets(&self) -> &[f64] {
+        &self.buckets
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        match self.state.lock() {
+            Ok(state) => state,
+            Err(e) => e.into_inner(),
+        }
+    }
+
+    pub(crate) fn start(&self, method: &'static str) {
+        *self.state().in_flight.entry(method).or_default() += 1;
+    }
+
+    pub(crate) fn finish(&self, method: &'static str) {
+        if let Some(count) = self.state().in_flight.get_mut(method) {
+            *count = count.saturating_sub(1);
+        }
+    }
+
+    pub(crate) fn observe(&self, labels: Labels, latency: Duration) {
+        let secs = latency.as_secs_f64();
+        let bucket = self.buckets.iter().position(|le| secs <= *le);
+        let mut state = self.state();
+        let series = state.requests.entry(labels).or_insert_with(|| Series {
+            count: 0,
+            sum: 0.0,
+            buckets: vec![0; self.buckets.len()],
+        });
+
+        series.count += 1;
+        series.sum += secs;
+        if let Some(i) = bucket {
+            series.buckets[i] += 1;
+        }
+    }
+
+    /// Renders the metrics in the Prometheus text exposition format.
+    pub(crate) fn render(&self) -> String {
+        let state = self.state();
+        let ns = &self.namespace;
+        let mut out = String::new();
+
+        let _ = writeln!(out, "# HELP {}_http_requests_total Total number of HTTP requests.", ns);
+        let _ = writeln!(out, "# TYPE {}_http_requests_total counter", ns);
+        for (labels, series) in &state.requests {
+            let _ = writeln!(out, "{}_http_requests_total{{{}}} {}", ns, labels, series.count);
+        }
+
+        let name = format!("{}_http_request_duration_seconds", ns);
+        let _ = writeln!(out, "# HELP {} HTTP request latency in seconds.", name);
+        let _ = writeln!(out, "# TYPE {} histogram", name);
+        for (labels, series) in &state.requests {
+            let mut cumulative = 0;
+            for (le, count) in self.buckets.iter().zip(&series.buckets) {
+                cumulative += count;
+                let _ = writeln!(out, "{}_bucket{{{},le=\"{}\"}} {}", name, labels, le, cumulative);
+            }
+
+            let _ = writeln!(out, "{}_bucket{{{},le=\"+Inf\"}} {}", name, labels, series.count);
+            let _ = writeln!(out, "{}_sum{{{}}} {}", name, labels, series.sum);
+            let _ = writeln!(out, "{}_count{{{}}} {}", name, labels, series.count);
+        }
+
+        let name = format!("{}_http_requests_in_flight", ns);
+        let _ = writeln!(out, "# HELP {} Number of HTTP requests being handled.", name);
+        let _ = writeln!(out, "# TYPE {} gauge", name);
+        for (method, count) in &state.in_flight {
+            let _ = writeln!(out, "{}{{method=\"{}\"}} {}", name, method, count);
+        }
+
+        out
+    }
+}
+
+impl fmt::Display for Labels {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "method=\"{}\",route=\"", self.method)?;
+        for c in self.route.chars() {
+            match c {
+                '\\' => f.write_str("\\\\")?,
+                '"' => f.write_str("\\\"")?,
+                '\n' => f.write_str("\\n")?,
+                c => f.write_char(c)?,
+            }
+        }
+
+        write!(f, "\",status=\"{}\"", self.status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::{Registry, Labels};
+
+    fn labels(route: &str, status: u16) -> Labels {
+        Labels { method: "GET", route: route.into(), status }
+    }
+
+    #[test]
+    fn test_histogram_buckets() {
+        let registry = Registry::new("app".into(), vec![0.1, 1.0]);
+        registry.observe(labels("/", 200), Duration::from_millis(50));
+        registry.observe(labels("/", 200), Duration::from_millis(500));
+        registry.observe(labels("/", 200), Duration::from_secs(5));
+
+        let out = registry.render();
+        let labels = r#"method="GET",route="/",status="200""#;
+        assert!(out.contains(&format!("app_http_requests_total{{{}}} 3\n", labels)));
+        assert!(out.contains(&format!(
+            "app_http_request_duration_seconds_bucket{{{},le=\"0.1\"}} 1\n", labels)));
+        assert!(out.contains(&format!(
+            "app_http_request_duration_seconds_bucket{{{},le=\"1\"}} 2\n", labels)));
+        assert!(out.contains(&format!(
+            "app_http_request_duration_seconds_bucket{{{},le=\"+Inf\"}} 3\n", labels)));
+        assert!(out.contains(&format!(
+            "app_http_request_duration_seconds_sum{{{}}} 5.55\n", labels)));
+    }
+
+    #[test]
+    fn test_escapes_labels() {
+        let labels = labels("/\"a\"\\b", 404);
+        assert_eq!(labels.to_string(), r#"method="GET",route="/\"a\"\\b",status="404""#);
+    }
+
+    #[test]
+    fn test_in_flight() {
+        let registry = Registry::new("app".into(), vec![]);
+        registry.start("GET");
+        registry.start("GET");
+        registry.start("POST");
+        registry.finish("GET");
+        registry.finish("POST");
+
+        let out = registry.render();
+        assert!(out.contains("app_http_requests_in_flight{method=\"GET\"} 1\n"));
+        assert!(out.contains("app_http_requests_in_flight{method=\"POST\"} 0\n"));
+    }
+}
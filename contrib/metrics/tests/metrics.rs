@@ -0,0 +1,92 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket_metrics::Metrics;
+
+#[get("/hello/<name>")]
+fn hello(name: &str) -> String {
+    format!("Hello, {}!", name)
+}
+
+#[get("/fail")]
+fn fail() -> Status {
+    Status::InternalServerError
+}
+
+fn client(metrics: Metrics) -> Client {
+    let rocket = rocket::build()
+        .attach(metrics.clone())
+        .mount("/", routes![hello, fail])
+        .mount("/metrics", metrics);
+
+    Client::debug(rocket).unwrap()
+}
+
+fn scrape(client: &Client) -> String {
+    let response = client.get("/metrics").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    let content_type = response.content_type().unwrap();
+    assert_eq!(content_type, ContentType::Plain);
+    assert_eq!(content_type.param("version"), Some("0.0.4"));
+    response.into_string().unwrap()
+}
+
+#[test]
+fn records_requests() {
+    let client = client(Metrics::new());
+    client.get("/hello/Bob").dispatch();
+    client.get("/hello/Alice").dispatch();
+    client.get("/fail").dispatch();
+    client.get("/missing").dispatch();
+
+    let metrics = scrape(&client);
+    let hello = r#"method="GET",route="/hello/<name>",status="200""#;
+    let fail = r#"method="GET",route="/fail",status="500""#;
+    let missing = r#"method="GET",route="",status="404""#;
+    assert!(metrics.contains("# TYPE rocket_http_requests_total counter\n"));
+    assert!(metrics.contains(&format!("rocket_http_requests_total{{{}}} 2\n", hello)));
+    assert!(metrics.contains(&format!("rocket_http_requests_total{{{}}} 1\n", fail)));
+    assert!(metrics.contains(&format!("rocket_http_requests_total{{{}}} 1\n", missing)));
+
+    assert!(metrics.contains("# TYPE rocket_http_request_duration_seconds histogram\n"));
+    assert!(metrics.contains(&format!(
+        "rocket_http_request_duration_seconds_bucket{{{},le=\"+Inf\"}} 2\n", hello)));
+    assert!(metrics.contains(&format!(
+        "rocket_http_request_duration_seconds_count{{{}}} 2\n", hello)));
+
+    // The scrape itself is in flight while metrics are rendered.
+    assert!(metrics.contains("# TYPE rocket_http_requests_in_flight gauge\n"));
+    assert!(metrics.contains("rocket_http_requests_in_flight{method=\"GET\"} 1\n"));
+
+    let metrics = scrape(&client);
+    let scrape = r#"method="GET",route="/metrics",status="200""#;
+    assert!(metrics.contains(&format!("rocket_http_requests_total{{{}}} 1\n", scrape)));
+}
+
+#[test]
+fn configures_metrics() {
+    let client = client(Metrics::new().namespace("app").buckets(&[0.5, 60.0]));
+    client.get("/hello/Bob").dispatch();
+
+    let metrics = scrape(&client);
+    let labels = r#"method="GET",route="/hello/<name>",status="200""#;
+    assert!(metrics.contains(&format!("app_http_requests_total{{{}}} 1\n", labels)));
+    assert!(metrics.contains(&format!(
+        "app_http_request_duration_seconds_bucket{{{},le=\"60\"}} 1\n", labels)));
+    assert!(!metrics.contains("le=\"0.005\""));
+    assert!(!metrics.contains("rocket_"));
+}
+
+#[test]
+#[should_panic(expected = "invalid metrics namespace")]
+fn rejects_invalid_namespaces() {
+    let _ = Metrics::new().namespace("1app");
+}
+
+#[test]
+#[should_panic(expected = "strictly increasing")]
+fn rejects_unordered_buckets() {
+    let _ = Metrics::new().buckets(&[1.0, 0.5]);
+}
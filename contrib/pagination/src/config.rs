@@ -0,0 +1,35 @@
+use rocket::serde::{Deserialize, Serialize};
+
+/// Pagination configuration: the default and maximum page sizes.
+///
+/// The configuration is read from the `pagination` configuration parameter by
+/// [`Pagination::fairing()`](crate::Pagination::fairing()). Both fields are
+/// optional:
+///
+/// ```toml
+/// [default.pagination]
+/// per_page = 25
+/// max_per_page = 250
+/// ```
+///
+/// A `per_page` of `0` or one greater than `max_per_page` is a configuration
+/// error that aborts launch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(crate = "rocket::serde", default)]
+pub struct Config {
+    /// The page size used when a request doesn't specify `per_page`.
+    ///
+    /// _Default:_ `20`.
+    pub per_page: u64,
+    /// The largest page size a request may ask for. Larger requested sizes
+    /// are reduced to this size.
+    ///
+    /// _Default:_ `100`.
+    pub max_per_page: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config { per_page: 20, max_per_page: 100 }
+    }
+}
@@ -0,0 +1,176 @@
+use std::fmt;
+
+use rocket::{Rocket, Ignite, Sentinel};
+use rocket::fairing::{AdHoc, Fairing};
+use rocket::http::Status;
+use rocket::request::{self, FromRequest, Request};
+
+use crate::Config;
+
+/// A request guard for the pagination parameters of a request.
+///
+/// The guard reads the following query parameters, all of which are optional:
+///
+///   * `page`: the 1-based number of the requested page. Defaults to `1`.
+///   * `per_page`: the number of items per page. Defaults to the configured
+///     [`per_page`](Config::per_page) and is reduced to the configured
+///     [`max_per_page`](Config::max_per_page) if it's larger.
+///   * `cursor`: an opaque cursor for keyset pagination.
+///
+/// If `page` or `per_page` is present but is not a positive integer, the guard
+/// fails with status `400 Bad Request` and an [`Error`].
+///
+/// The page sizes are configured by [`Pagination::fairing()`], which must be
+/// attached for the guard to be used; launch is aborted otherwise.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_pagination::Pagination;
+///
+/// #[get("/items")]
+/// fn items(pagination: Pagination<'_>) -> String {
+///     format!("items {} through {}", pagination.offset(),
+///         pagination.offset() + pagination.limit() - 1)
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pagination<'r> {
+    page: u64,
+    per_page: u64,
+    cursor: Option<&'r str>,
+}
+
+/// The error returned by the [`Pagination`] guard when a query parameter is
+/// invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The `page` parameter is not a positive integer.
+    InvalidPage,
+    /// The `per_page` parameter is not a positive integer.
+    InvalidPerPage,
+}
+
+impl Pagination<'_> {
+    /// Returns a fairing that reads the [`Config`] from the `pagination`
+    /// configuration parameter and makes it available to the guard. If the
+    /// parameter is missing, the default configuration is used.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_pagination::Pagination;
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build().attach(Pagination::fairing())
+    /// }
+    /// ```
+    pub fn fairing() -> impl Fairing {
+        AdHoc::try_on_ignite("Pagination", |rocket| async {
+            let config = match rocket.figment().extract_inner::<Config>("pagination") {
+                Ok(config) => config,
+                Err(e) if e.missing() => Config::default(),
+                Err(e) => {
+                    rocket::config::pretty_print_error(e);
+                    return Err(rocket);
+                }
+            };
+
+            if config.per_page == 0 || config.per_page > config.max_per_page {
+                error!("Invalid pagination configuration: `per_page` must be in [1, {}].",
+                    config.max_per_page);
+
+                return Err(rocket);
+            }
+
+            Ok(rocket.manage(config))
+        })
+    }
+
+    /// Returns the 1-based number of the requested page.
+    pub fn page(&self) -> u64 {
+        self.page
+    }
+
+    /// Returns the number of items per page.
+    pub fn per_page(&self) -> u64 {
+        self.per_page
+    }
+
+    /// Returns the requested cursor, the value of the `cursor` query parameter,
+    /// if there is one.
+    pub fn cursor(&self) -> Option<&str> {
+        self.cursor
+    }
+
+    /// Returns the number of items preceding the requested page:
+    /// `(page - 1) * per_page`.
+    pub fn offset(&self) -> u64 {
+        (self.page - 1).saturating_mul(self.per_page)
+    }
+
+    /// Returns the maximum number of items on the requested page. This is an
+    /// alias for [`Pagination::per_page()`] for use alongside
+    /// [`Pagination::offset()`].
+    pub fn limit(&self) -> u64 {
+        self.per_page
+    }
+}
+
+/// Parses the positive integer query parameter `name`, if it's present.
+fn positive(req: &Request<'_>, name: &str) -> Result<Option<u64>, ()> {
+    match req.query_value::<u64>(name) {
+        Some(Ok(0)) | Some(Err(_)) => Err(()),
+        Some(Ok(n)) => Ok(Some(n)),
+        None => Ok(None),
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Pagination<'r> {
+    type Error = Error;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Error> {
+        let config = req.rocket().state::<Config>().copied().unwrap_or_default();
+        let page = match positive(req, "page") {
+            Ok(page) => page.unwrap_or(1),
+            Err(()) => return request::Outcome::Failure((Status::BadRequest, Error::InvalidPage)),
+        };
+
+        let per_page = match positive(req, "per_page") {
+            Ok(per_page) => per_page.unwrap_or(config.per_page).min(config.max_per_page),
+            Err(()) => return request::Outcome::Failure((Status::BadRequest, Error::InvalidPerPage)),
+        };
+
+        let cursor = req.query_value::<&str>("cursor").and_then(|r| r.ok());
+        request::Outcome::Success(Pagination { page, per_page, cursor })
+    }
+}
+
+impl Sentinel for Pagination<'_> {
+    fn abort(rocket: &Rocket<Ignite>) -> bool {
+        if rocket.state::<Config>().is_none() {
+            let guard = rocket::yansi::Paint::default("Pagination").bold();
+            let fairing = rocket::yansi::Paint::default("Pagination::fairing()").bold();
+            error!("requested `{}` guard without attaching `{}`.", guard, fairing);
+            info_!("To use the pagination guard, you must attach `{}`.", fairing);
+            return true;
+        }
+
+        false
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidPage => write!(f, "`page` must be a positive integer"),
+            Error::InvalidPerPage => write!(f, "`per_page` must be a positive integer"),
+        }
+    }
+}
+
+impl std::error::Error for Error { }
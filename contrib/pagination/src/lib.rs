@@ -0,0 +1,96 @@
+//! Pagination request guard and responder for Rocket.
+//!
+//! List endpoints typically accept the same query parameters, clamp them
+//! against the same limits, and advertise neighbouring pages the same way.
+//! This crate does so once:
+//!
+//!   * [`Pagination`], a request guard that parses the `page`, `per_page`, and
+//!     `cursor` query parameters, applying the configured default and maximum
+//!     page size.
+//!   * [`Page`], a responder that wraps another and adds a `Link` header to
+//!     the first, previous, next, and last pages, and, when known, an
+//!     `X-Total-Count` header with the total number of items.
+//!
+//! # Usage
+//!
+//!   1. Depend on `rocket_pagination`:
+//!
+//!      ```toml
+//!      [dependencies.rocket_pagination]
+//!      version = "0.1.0-rc.2"
+//!      ```
+//!
+//!   2. Attach [`Pagination::fairing()`] and use the guard and responder in
+//!      list routes:
+//!
+//!      ```rust
+//!      # #[macro_use] extern crate rocket;
+//!      use rocket::serde::json::Json;
+//!      use rocket_pagination::{Pagination, Page};
+//!
+//!      #[get("/items")]
+//!      fn items(pagination: Pagination<'_>) -> Page<Json<Vec<u64>>> {
+//!          let total = 1000;
+//!          let items = (pagination.offset()..total).take(pagination.limit() as usize);
+//!          Page::new(Json(items.collect()), &pagination).total(total)
+//!      }
+//!
+//!      #[launch]
+//!      fn rocket() -> _ {
+//!          rocket::build()
+//!              .attach(Pagination::fairing())
+//!              .mount("/", routes![items])
+//!      }
+//!      ```
+//!
+//!      A request to `/items?page=3&per_page=10` now responds with items `20`
+//!      through `29` and the following headers:
+//!
+//!      ```text
+//!      Link: </items?page=1&per_page=10>; rel="first", </items?page=2&per_page=10>; rel="prev",
+//!            </items?page=4&per_page=10>; rel="next", </items?page=100&per_page=10>; rel="last"
+//!      X-Total-Count: 1000
+//!      ```
+//!
+//! # Configuration
+//!
+//! The default and maximum page sizes are read from the `pagination`
+//! configuration parameter by [`Pagination::fairing()`]:
+//!
+//! ```toml
+//! [default.pagination]
+//! per_page = 25
+//! max_per_page = 250
+//! ```
+//!
+//! See [`Config`] for details.
+//!
+//! # Cursors
+//!
+//! For keyset pagination, the guard exposes the opaque `cursor` query
+//! parameter via [`Pagination::cursor()`] and [`Page::next_cursor()`]
+//! advertises the next page's cursor in the `Link` header:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket_pagination::{Pagination, Page};
+//!
+//! #[get("/events")]
+//! fn events(pagination: Pagination<'_>) -> Page<String> {
+//!     let after: u64 = pagination.cursor().and_then(|c| c.parse().ok()).unwrap_or(0);
+//!     let events: Vec<_> = (after + 1..).take(pagination.limit() as usize).collect();
+//!     let last = events.last().copied().unwrap_or(after);
+//!     Page::new(format!("{:?}", events), &pagination).next_cursor(last.to_string())
+//! }
+//! ```
+
+#[macro_use]
+extern crate rocket;
+
+mod config;
+mod guard;
+mod page;
+
+pub use self::config::Config;
+pub use self::guard::{Pagination, Error};
+pub use self::page::Page;
@@ -0,0 +1,171 @@
+use rocket::http::RawStr;
+use rocket::request::Request;
+use rocket::response::{self, Responder};
+
+use crate::Pagination;
+
+/// A responder for one page of a paginated list.
+///
+/// `Page` wraps another responder `R`, typically the page's items, and adds
+/// the following headers to its response:
+///
+///   * `Link`: links to related pages with relations `first`, `prev`, `next`,
+///     and `last`. Links are to the request's path and query, with the
+///     pagination parameters replaced. Only the links that can be determined
+///     are included: `next` and `last` require the [total](Page::total()) or,
+///     for `next`, the [next cursor](Page::next_cursor()).
+///   * `X-Total-Count`: the total number of items, if it's known.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_pagination::{Pagination, Page};
+///
+/// #[get("/names")]
+/// fn names(pagination: Pagination<'_>) -> Page<String> {
+///     let all = ["Ada", "Grace", "Barbara", "Frances", "Radia"];
+///     let page = all.iter()
+///         .skip(pagination.offset() as usize)
+///         .take(pagination.limit() as usize);
+///
+///     let names = page.copied().collect::<Vec<_>>().join(", ");
+///     Page::new(names, &pagination).total(all.len() as u64)
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Page<R> {
+    inner: R,
+    page: u64,
+    per_page: u64,
+    total: Option<u64>,
+    next_cursor: Option<String>,
+}
+
+impl<R> Page<R> {
+    /// Wraps `inner`, the contents of the page requested with `pagination`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_pagination::{Pagination, Page};
+    ///
+    /// #[get("/")]
+    /// fn index(pagination: Pagination<'_>) -> Page<&'static str> {
+    ///     Page::new("nothing to see here", &pagination)
+    /// }
+    /// ```
+    pub fn new(inner: R, pagination: &Pagination<'_>) -> Self {
+        Page {
+            inner,
+            page: pagination.page(),
+            per_page: pagination.per_page(),
+            total: None,
+            next_cursor: None,
+        }
+    }
+
+    /// Sets the total number of items in the list, enabling the `next` and
+    /// `last` links and the `X-Total-Count` header.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_pagination::{Pagination, Page};
+    ///
+    /// #[get("/")]
+    /// fn index(pagination: Pagination<'_>) -> Page<&'static str> {
+    ///     Page::new("nothing to see here", &pagination).total(0)
+    /// }
+    /// ```
+    pub fn total(mut self, total: u64) -> Self {
+        self.total = Some(total);
+        self
+    }
+
+    /// Sets the cursor of the next page, which is linked to with the `cursor`
+    /// query parameter set to `cursor` in place of `page`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_pagination::{Pagination, Page};
+    ///
+    /// #[get("/")]
+    /// fn index(pagination: Pagination<'_>) -> Page<&'static str> {
+    ///     Page::new("first events", &pagination).next_cursor("evt_1234")
+    /// }
+    /// ```
+    pub fn next_cursor<C: Into<String>>(mut self, cursor: C) -> Self {
+        self.next_cursor = Some(cursor.into());
+        self
+    }
+
+    /// Returns the number of the last page, if the total is known. A list with
+    /// no items has one, empty, page.
+    fn last_page(&self) -> Option<u64> {
+        let total = self.total?;
+        Some(((total + self.per_page - 1) / self.per_page).max(1))
+    }
+
+    /// Returns the value of the `Link` header for a response to `req`.
+    fn links(&self, req: &Request<'_>) -> Vec<String> {
+        let uri = req.uri();
+        let retained: Vec<&str> = uri.query()
+            .map(|q| q.raw_segments().collect::<Vec<_>>())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|seg| !seg.is_empty())
+            .filter(|seg| {
+                let name = seg.as_str().split('=').next().unwrap_or_default();
+                !matches!(name, "page" | "per_page" | "cursor")
+            })
+            .map(|seg| seg.as_str())
+            .collect();
+
+        let link = |param: String, rel: &str| {
+            let mut query = retained.clone();
+            let per_page = format!("per_page={}", self.per_page);
+            query.push(&param);
+            query.push(&per_page);
+            format!("<{}?{}>; rel=\"{}\"", uri.path(), query.join("&"), rel)
+        };
+
+        let mut links = vec![link("page=1".into(), "first")];
+        if self.page > 1 {
+            links.push(link(format!("page={}", self.page - 1), "prev"));
+        }
+
+        if let Some(cursor) = &self.next_cursor {
+            let cursor = RawStr::new(cursor).percent_encode();
+            links.push(link(format!("cursor={}", cursor), "next"));
+        } else if let Some(last) = self.last_page() {
+            if self.page < last {
+                links.push(link(format!("page={}", self.page + 1), "next"));
+            }
+        }
+
+        if let Some(last) = self.last_page() {
+            links.push(link(format!("page={}", last), "last"));
+        }
+
+        links
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Page<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let links = self.links(req).join(", ");
+        let total = self.total;
+        let mut response = self.inner.respond_to(req)?;
+        response.set_raw_header("Link", links);
+        if let Some(total) = total {
+            response.set_raw_header("X-Total-Count", total.to_string());
+        }
+
+        Ok(response)
+    }
+}
@@ -0,0 +1,117 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build, Config};
+use rocket::error::ErrorKind;
+use rocket::figment::Figment;
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use rocket::serde::json::Json;
+use rocket_pagination::{Pagination, Page};
+
+#[get("/params")]
+fn params(pagination: Pagination<'_>) -> String {
+    format!("{} {} {:?}", pagination.page(), pagination.per_page(), pagination.cursor())
+}
+
+#[get("/items")]
+fn items(pagination: Pagination<'_>) -> Page<Json<Vec<u64>>> {
+    let items = (pagination.offset()..45).take(pagination.limit() as usize);
+    Page::new(Json(items.collect()), &pagination).total(45)
+}
+
+#[get("/events")]
+fn events(pagination: Pagination<'_>) -> Page<&'static str> {
+    Page::new("events", &pagination).next_cursor("a b&c")
+}
+
+fn rocket(figment: Figment) -> Rocket<Build> {
+    rocket::custom(figment)
+        .attach(Pagination::fairing())
+        .mount("/", routes![params, items, events])
+}
+
+fn client() -> Client {
+    Client::debug(rocket(Config::figment())).unwrap()
+}
+
+fn get(client: &Client, uri: &str) -> (Status, String) {
+    let response = client.get(uri.to_string()).dispatch();
+    (response.status(), response.into_string().unwrap_or_default())
+}
+
+#[test]
+fn parses_parameters_with_defaults() {
+    let client = client();
+    assert_eq!(get(&client, "/params"), (Status::Ok, "1 20 None".into()));
+    assert_eq!(get(&client, "/params?page=3&per_page=5"), (Status::Ok, "3 5 None".into()));
+    assert_eq!(get(&client, "/params?per_page=1000"), (Status::Ok, "1 100 None".into()));
+    assert_eq!(get(&client, "/params?cursor=a%20b"), (Status::Ok, "1 20 Some(\"a b\")".into()));
+}
+
+#[test]
+fn rejects_invalid_parameters() {
+    let client = client();
+    assert_eq!(get(&client, "/params?page=0").0, Status::BadRequest);
+    assert_eq!(get(&client, "/params?page=-1").0, Status::BadRequest);
+    assert_eq!(get(&client, "/params?per_page=0").0, Status::BadRequest);
+    assert_eq!(get(&client, "/params?per_page=many").0, Status::BadRequest);
+}
+
+#[test]
+fn reads_configuration() {
+    let figment = Config::figment()
+        .merge(("pagination", rocket::figment::util::map!["per_page" => 7, "max_per_page" => 10]));
+
+    let client = Client::debug(rocket(figment)).unwrap();
+    assert_eq!(get(&client, "/params"), (Status::Ok, "1 7 None".into()));
+    assert_eq!(get(&client, "/params?per_page=11"), (Status::Ok, "1 10 None".into()));
+}
+
+#[test]
+fn invalid_configuration_aborts_launch() {
+    let figment = Config::figment()
+        .merge(("pagination", rocket::figment::util::map!["per_page" => 50, "max_per_page" => 10]));
+
+    let error = Client::debug(rocket(figment)).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::FailedFairings(_)));
+}
+
+#[test]
+fn guard_requires_fairing() {
+    let rocket = rocket::build().mount("/", routes![params]);
+    let error = Client::debug(rocket).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::SentinelAborts(_)));
+}
+
+#[test]
+fn page_links_and_total() {
+    let client = client();
+    let response = client.get("/items?kind=x&page=2&per_page=10").dispatch();
+    assert_eq!(response.headers().get_one("X-Total-Count"), Some("45"));
+    assert_eq!(response.headers().get_one("Link"), Some(
+        "</items?kind=x&page=1&per_page=10>; rel=\"first\", \
+        </items?kind=x&page=1&per_page=10>; rel=\"prev\", \
+        </items?kind=x&page=3&per_page=10>; rel=\"next\", \
+        </items?kind=x&page=5&per_page=10>; rel=\"last\""
+    ));
+
+    let items: Vec<u64> = response.into_json().unwrap();
+    assert_eq!(items, (10..20).collect::<Vec<_>>());
+
+    let response = client.get("/items?page=5&per_page=10").dispatch();
+    let link = response.headers().get_one("Link").unwrap();
+    assert!(!link.contains("rel=\"next\""));
+    assert!(link.contains("</items?page=5&per_page=10>; rel=\"last\""));
+    assert_eq!(response.into_json::<Vec<u64>>().unwrap(), (40..45).collect::<Vec<_>>());
+}
+
+#[test]
+fn page_cursor_link() {
+    let client = client();
+    let response = client.get("/events?per_page=5").dispatch();
+    assert_eq!(response.headers().get_one("X-Total-Count"), None);
+    assert_eq!(response.headers().get_one("Link"), Some(
+        "</events?page=1&per_page=5>; rel=\"first\", \
+        </events?cursor=a%20b%26c&per_page=5>; rel=\"next\""
+    ));
+}
@@ -0,0 +1,81 @@
+//! Protocol Buffers support for Rocket.
+//!
+//! This crate provides [`Protobuf<T>`], a data guard and responder that
+//! (de)serializes any [`prost`] message `T` to and from the Protocol Buffers
+//! binary encoding with content type `application/x-protobuf`. Messages are
+//! typically generated from `.proto` files by [`prost-build`] or written by
+//! hand with `#[derive(prost::Message)]`.
+//!
+//! [`prost`]: https://docs.rs/prost
+//! [`prost-build`]: https://docs.rs/prost-build
+//!
+//! # Usage
+//!
+//!   1. Depend on `rocket_protobuf` and `prost`:
+//!
+//!      ```toml
+//!      [dependencies]
+//!      rocket_protobuf = "0.1.0-rc.2"
+//!      prost = "0.12"
+//!      ```
+//!
+//!   2. Use [`Protobuf<T>`] as a data guard and responder:
+//!
+//!      ```rust
+//!      # #[macro_use] extern crate rocket;
+//!      use rocket_protobuf::Protobuf;
+//!
+//!      #[derive(Clone, PartialEq, prost::Message)]
+//!      struct Point {
+//!          #[prost(int32, tag = "1")]
+//!          x: i32,
+//!          #[prost(int32, tag = "2")]
+//!          y: i32,
+//!      }
+//!
+//!      #[post("/flip", format = "application/x-protobuf", data = "<point>")]
+//!      fn flip(point: Protobuf<Point>) -> Protobuf<Point> {
+//!          Protobuf(Point { x: point.y, y: point.x })
+//!      }
+//!      ```
+//!
+//! ## Errors
+//!
+//! A request body that can't be decoded fails the data guard with `400 Bad
+//! Request`, and one that exceeds the configured limit fails it with `413
+//! Payload Too Large`. To respond with a description of the failure, use
+//! `Result<Protobuf<T>, Error>` as the data guard and return the [`Error`],
+//! which is a responder:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket_protobuf::{Protobuf, Error};
+//!
+//! #[derive(Clone, PartialEq, prost::Message)]
+//! struct Point {
+//!     #[prost(int32, tag = "1")]
+//!     x: i32,
+//! }
+//!
+//! #[post("/", data = "<point>")]
+//! fn index(point: Result<Protobuf<Point>, Error>) -> Result<String, Error> {
+//!     Ok(point?.x.to_string())
+//! }
+//! ```
+//!
+//! ## Limits
+//!
+//! Request bodies are limited by the `protobuf` limit, 1MiB by default. To
+//! change it, configure the limit in `Rocket.toml`:
+//!
+//! ```toml
+//! [default.limits]
+//! protobuf = "4 MiB"
+//! ```
+
+#[macro_use]
+extern crate rocket;
+
+mod protobuf;
+
+pub use self::protobuf::{Protobuf, Error};
@@ -0,0 +1,197 @@
+use std::{fmt, io};
+use std::ops::{Deref, DerefMut};
+
+use prost::{Message, DecodeError};
+use rocket::data::{ByteUnit, Data, FromData, Outcome};
+use rocket::http::{ContentType, Status};
+use rocket::request::Request;
+use rocket::response::{self, Responder};
+
+/// The Protocol Buffers guard: easily consume and return Protobuf messages.
+///
+/// ## Sending Protobuf
+///
+/// To respond with an encoded message, return a `Protobuf<T>`, where `T` is a
+/// [`prost::Message`]. The `Content-Type` of the response is set to
+/// `application/x-protobuf` automatically.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_protobuf::Protobuf;
+///
+/// #[derive(Clone, PartialEq, prost::Message)]
+/// struct User {
+///     #[prost(uint64, tag = "1")]
+///     id: u64,
+/// }
+///
+/// #[get("/users/<id>")]
+/// fn user(id: u64) -> Protobuf<User> {
+///     Protobuf(User { id })
+/// }
+/// ```
+///
+/// ## Receiving Protobuf
+///
+/// To decode a request body, add a `data` route argument of type
+/// `Protobuf<T>`, where `T` is a [`prost::Message`] that implements
+/// `Default`, as messages derived with `prost` do.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_protobuf::Protobuf;
+///
+/// #[derive(Clone, PartialEq, prost::Message)]
+/// struct User {
+///     #[prost(string, tag = "1")]
+///     name: String,
+/// }
+///
+/// #[post("/users", format = "application/x-protobuf", data = "<user>")]
+/// fn new_user(user: Protobuf<User>) {
+///     /* ... */
+/// }
+/// ```
+///
+/// The guard fails with `413 Payload Too Large` if the body exceeds the
+/// `protobuf` limit, [`Protobuf::DEFAULT_LIMIT`] by default, and with `400 Bad
+/// Request` if it can't be read or decoded. In either case, the guard's
+/// [`Error`] describes the failure.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Protobuf<T>(pub T);
+
+/// An error returned by the [`Protobuf`] data guard.
+///
+/// As a responder, an `Error` responds with its status, `413 Payload Too
+/// Large` for [`Error::TooLarge`] and `400 Bad Request` otherwise, and a plain
+/// text description of the error.
+#[derive(Debug)]
+pub enum Error {
+    /// The request body exceeds the `protobuf` limit.
+    TooLarge,
+    /// An I/O error occurred while reading the request body.
+    Io(io::Error),
+    /// The request body isn't a valid encoding of the message.
+    Decode(DecodeError),
+}
+
+impl<T> Protobuf<T> {
+    /// The default limit on the size of request bodies: 1MiB.
+    pub const DEFAULT_LIMIT: ByteUnit = ByteUnit::Mebibyte(1);
+
+    /// Consumes the `Protobuf` wrapper and returns the wrapped message.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_protobuf::Protobuf;
+    ///
+    /// let message = Protobuf(String::from("Hello"));
+    /// assert_eq!(message.into_inner(), "Hello");
+    /// ```
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl Error {
+    /// Returns the status with which the [`Protobuf`] guard fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::Status;
+    /// use rocket_protobuf::Error;
+    ///
+    /// assert_eq!(Error::TooLarge.status(), Status::PayloadTooLarge);
+    /// ```
+    pub fn status(&self) -> Status {
+        match self {
+            Error::TooLarge => Status::PayloadTooLarge,
+            Error::Io(_) | Error::Decode(_) => Status::BadRequest,
+        }
+    }
+}
+
+fn content_type() -> ContentType {
+    ContentType::new("application", "x-protobuf")
+}
+
+#[rocket::async_trait]
+impl<'r, T: Message + Default> FromData<'r> for Protobuf<T> {
+    type Error = Error;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+        let limit = req.limits().get("protobuf").unwrap_or(Protobuf::<T>::DEFAULT_LIMIT);
+        let result = match data.open(limit).into_bytes().await {
+            Ok(bytes) if bytes.is_complete() => T::decode(&bytes[..]).map_err(Error::Decode),
+            Ok(_) => Err(Error::TooLarge),
+            Err(e) => Err(Error::Io(e)),
+        };
+
+        match result {
+            Ok(message) => Outcome::Success(Protobuf(message)),
+            Err(e) => {
+                error_!("Protobuf data guard failed: {}.", e);
+                Outcome::Failure((e.status(), e))
+            }
+        }
+    }
+}
+
+/// Encodes the wrapped message. Returns a response with Content-Type
+/// `application/x-protobuf` and a fixed-size body with the encoded message.
+impl<'r, T: Message> Responder<'r, 'static> for Protobuf<T> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        (content_type(), self.0.encode_to_vec()).respond_to(req)
+    }
+}
+
+impl<'r> Responder<'r, 'static> for Error {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        (self.status(), self.to_string()).respond_to(req)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::TooLarge => f.write_str("protobuf data exceeds the `protobuf` limit"),
+            Error::Io(e) => write!(f, "failed to read protobuf data: {}", e),
+            Error::Decode(e) => write!(f, "invalid protobuf data: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::TooLarge => None,
+            Error::Io(e) => Some(e),
+            Error::Decode(e) => Some(e),
+        }
+    }
+}
+
+impl<T> From<T> for Protobuf<T> {
+    fn from(value: T) -> Self {
+        Protobuf(value)
+    }
+}
+
+impl<T> Deref for Protobuf<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Protobuf<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
@@ -0,0 +1,73 @@
+#[macro_use] extern crate rocket;
+
+use prost::Message;
+use rocket::{Rocket, Build, Config};
+use rocket::data::{Limits, ToByteUnit};
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket_protobuf::{Protobuf, Error};
+
+#[derive(Clone, PartialEq, Message)]
+struct Point {
+    #[prost(int32, tag = "1")]
+    x: i32,
+    #[prost(int32, tag = "2")]
+    y: i32,
+    #[prost(string, tag = "3")]
+    label: String,
+}
+
+#[post("/flip", format = "application/x-protobuf", data = "<point>")]
+fn flip(point: Protobuf<Point>) -> Protobuf<Point> {
+    Protobuf(Point { x: point.y, y: point.x, label: point.label.clone() })
+}
+
+#[post("/label", data = "<point>")]
+fn label(point: Result<Protobuf<Point>, Error>) -> Result<String, Error> {
+    Ok(point?.into_inner().label)
+}
+
+fn rocket() -> Rocket<Build> {
+    let limits = Limits::default().limit("protobuf", 32.bytes());
+    rocket::custom(Config { limits, ..Config::debug_default() }).mount("/", routes![flip, label])
+}
+
+fn protobuf() -> ContentType {
+    ContentType::new("application", "x-protobuf")
+}
+
+#[test]
+fn round_trip() {
+    let client = Client::debug(rocket()).unwrap();
+    let point = Point { x: 1, y: -2, label: "a".into() };
+    let response = client.post("/flip")
+        .header(protobuf())
+        .body(point.encode_to_vec())
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(protobuf()));
+
+    let flipped = Point::decode(&response.into_bytes().unwrap()[..]).unwrap();
+    assert_eq!(flipped, Point { x: -2, y: 1, label: "a".into() });
+}
+
+#[test]
+fn decode_failures() {
+    let client = Client::debug(rocket()).unwrap();
+    let response = client.post("/flip").header(protobuf()).body([0x08]).dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = client.post("/label").body([0x1a, 0x05, b'a']).dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    assert!(response.into_string().unwrap().starts_with("invalid protobuf data"));
+
+    let point = Point { label: "a".repeat(64), ..Point::default() };
+    let response = client.post("/label").body(point.encode_to_vec()).dispatch();
+    assert_eq!(response.status(), Status::PayloadTooLarge);
+    assert!(response.into_string().unwrap().contains("`protobuf` limit"));
+
+    let point = Point { label: "ok".into(), ..Point::default() };
+    let response = client.post("/label").body(point.encode_to_vec()).dispatch();
+    assert_eq!(response.into_string().unwrap(), "ok");
+}
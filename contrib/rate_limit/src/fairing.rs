@@ -0,0 +1,210 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use rocket::{Rocket, Build, Request, Response, Data, response};
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::http::{Header, Status};
+
+use crate::{Quota, Key, Store, MemoryStore, Decision, Error};
+
+/// Fairing that enables rate limiting via the [`RateLimited`] guard or, when
+/// [global](RateLimit::global()), of every request.
+///
+/// The fairing limits requests to routes with a [`RateLimited`] guard to a
+/// [`Quota`] per [`Key`], as recorded in a [`Store`]. It also adds a
+/// `Retry-After` header to responses for requests that were limited.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_rate_limit::{RateLimit, RateLimited, Quota, Key};
+///
+/// #[post("/login")]
+/// fn login(_limit: RateLimited) { /* .. */ }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     let limit = RateLimit::new(Quota::per_minute(5)).key(Key::ip_and_route());
+///     rocket::build()
+///         .attach(limit)
+///         .mount("/", routes![login])
+/// }
+/// ```
+///
+/// [`RateLimited`]: crate::RateLimited
+pub struct RateLimit(Limiter);
+
+/// The state managed by [`RateLimit`] and used by the `RateLimited` guard.
+#[derive(Clone)]
+pub(crate) struct Limiter {
+    pub(crate) quota: Quota,
+    pub(crate) key: Key,
+    pub(crate) store: Arc<dyn Store>,
+    pub(crate) global: bool,
+}
+
+/// The number of tokens remaining after a request was charged by a global
+/// limit, cached in the request's local cache.
+struct Charged(Option<u32>);
+
+/// The `Retry-After` value, in seconds, for a limited request, cached in the
+/// request's local cache.
+#[derive(Default)]
+pub(crate) struct RetryAfter(AtomicU64);
+
+impl RetryAfter {
+    pub(crate) fn set(req: &Request<'_>, retry_after: Duration) {
+        let secs = retry_after.as_secs() + (retry_after.subsec_nanos() > 0) as u64;
+        req.local_cache(RetryAfter::default).0.store(secs.max(1), Ordering::Release);
+    }
+
+    fn get(req: &Request<'_>) -> Option<u64> {
+        let secs = req.local_cache(RetryAfter::default).0.load(Ordering::Acquire);
+        (secs > 0).then(|| secs)
+    }
+}
+
+impl RateLimit {
+    /// Returns a fairing limiting requests to `quota` per client IP, keeping
+    /// buckets in a [`MemoryStore`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_rate_limit::{RateLimit, Quota};
+    ///
+    /// let limit = RateLimit::new(Quota::per_second(10));
+    /// ```
+    pub fn new(quota: Quota) -> RateLimit {
+        let store = Arc::new(MemoryStore::new());
+        RateLimit(Limiter { quota, key: Key::default(), store, global: false })
+    }
+
+    /// Sets the key identifying the bucket a request is charged against.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_rate_limit::{RateLimit, Quota, Key};
+    ///
+    /// let limit = RateLimit::new(Quota::per_second(10)).key(Key::route());
+    /// ```
+    pub fn key(mut self, key: Key) -> RateLimit {
+        self.0.key = key;
+        self
+    }
+
+    /// Sets the store in which buckets are kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_rate_limit::{RateLimit, Quota, MemoryStore};
+    ///
+    /// let limit = RateLimit::new(Quota::per_second(10)).store(MemoryStore::new());
+    /// ```
+    pub fn store<S: Store>(mut self, store: S) -> RateLimit {
+        self.0.store = Arc::new(store);
+        self
+    }
+
+    /// Limits every request, not only those to routes with a [`RateLimited`]
+    /// guard.
+    ///
+    /// Requests are charged before they are routed, and limited requests fail
+    /// with `429 Too Many Requests` without being routed. Because there is no
+    /// matched route at that point, keys involving the route, such as
+    /// [`Key::route()`], can't be determined, so the corresponding requests
+    /// aren't limited. A [`RateLimited`] guard on a globally limited route
+    /// doesn't charge the request again.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_rate_limit::{RateLimit, Quota};
+    ///
+    /// let limit = RateLimit::new(Quota::per_second(10)).global();
+    /// ```
+    ///
+    /// [`RateLimited`]: crate::RateLimited
+    pub fn global(mut self) -> RateLimit {
+        self.0.global = true;
+        self
+    }
+}
+
+impl Limiter {
+    /// Charges `req` against its bucket, returning the number of remaining
+    /// tokens or `None` if `req` has no key and isn't limited.
+    pub(crate) async fn charge(&self, req: &Request<'_>) -> Result<Option<u32>, Error> {
+        if self.global {
+            return Ok(req.local_cache(|| Charged(None)).0);
+        }
+
+        self.acquire(req).await
+    }
+
+    async fn acquire(&self, req: &Request<'_>) -> Result<Option<u32>, Error> {
+        let key = match self.key.extract(req) {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+
+        match self.store.acquire(&key, &self.quota).await {
+            Ok(Decision::Allow { remaining }) => Ok(Some(remaining)),
+            Ok(Decision::Deny { retry_after }) => {
+                RetryAfter::set(req, retry_after);
+                Err(Error::Limited(retry_after))
+            }
+            Err(e) => {
+                error_!("Rate limit store failed: {}", e);
+                Err(Error::Store(e))
+            }
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for RateLimit {
+    fn info(&self) -> Info {
+        Info {
+            name: "Rate Limit",
+            kind: Kind::Ignite | Kind::Filter | Kind::Response | Kind::Singleton,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        Ok(rocket.manage(self.0.clone()))
+    }
+
+    async fn on_filter<'r>(
+        &self,
+        req: &'r Request<'_>,
+        _: &mut Data<'r>
+    ) -> Option<response::Result<'r>> {
+        if !self.0.global {
+            return None;
+        }
+
+        match self.0.acquire(req).await {
+            Ok(remaining) => {
+                req.local_cache(|| Charged(remaining));
+                None
+            }
+            Err(Error::Limited(_)) => Some(Err(Status::TooManyRequests)),
+            Err(_) => Some(Err(Status::InternalServerError)),
+        }
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        if res.status() != Status::TooManyRequests || res.headers().contains("Retry-After") {
+            return;
+        }
+
+        if let Some(secs) = RetryAfter::get(req) {
+            res.set_header(Header::new("Retry-After", secs.to_string()));
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::fmt;
+use std::time::Duration;
+
+use rocket::http::Status;
+use rocket::request::{self, Request, FromRequest};
+
+use crate::StoreError;
+use crate::fairing::Limiter;
+
+/// Request guard that limits the rate of requests to a route.
+///
+/// The guard charges the request against its bucket, as configured by the
+/// attached [`RateLimit`](crate::RateLimit) fairing, and succeeds if a token
+/// was available. Otherwise, it fails with `429 Too Many Requests` and an
+/// [`Error::Limited`], and the fairing adds a `Retry-After` header to the
+/// response.
+///
+/// If the bucket's store fails, the guard fails with `500 Internal Server
+/// Error` and an [`Error::Store`]. If the fairing isn't attached, the guard
+/// fails with `500 Internal Server Error` and an [`Error::Unmanaged`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_rate_limit::RateLimited;
+///
+/// #[get("/search?<q>")]
+/// fn search(q: &str, limit: RateLimited) -> String {
+///     match limit.remaining() {
+///         Some(n) => format!("results for {} ({} searches left)", q, n),
+///         None => format!("results for {}", q),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimited {
+    remaining: Option<u32>,
+}
+
+impl RateLimited {
+    /// The number of requests the client can make before being limited, or
+    /// `None` if the request has no [key](crate::Key) and isn't limited.
+    pub fn remaining(&self) -> Option<u32> {
+        self.remaining
+    }
+}
+
+/// An error returned by the [`RateLimited`] guard.
+#[derive(Debug)]
+pub enum Error {
+    /// The request was limited. A request can be made after the duration.
+    Limited(Duration),
+    /// The store failed to charge the request.
+    Store(StoreError),
+    /// The [`RateLimit`](crate::RateLimit) fairing isn't attached.
+    Unmanaged,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Limited(d) => write!(f, "rate limited: retry after {:?}", d),
+            Error::Store(e) => write!(f, "rate limit store error: {}", e),
+            Error::Unmanaged => write!(f, "rate limit fairing is not attached"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Store(e) => Some(&**e),
+            _ => None,
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RateLimited {
+    type Error = Error;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Error> {
+        let limiter = match req.rocket().state::<Limiter>() {
+            Some(limiter) => limiter,
+            None => {
+                error_!("Missing rate limiter state: missing fairing.");
+                info_!("To use `RateLimited`, you must attach a `RateLimit` fairing.");
+                return request::Outcome::Failure((Status::InternalServerError, Error::Unmanaged));
+            }
+        };
+
+        match limiter.charge(req).await {
+            Ok(remaining) => request::Outcome::Success(RateLimited { remaining }),
+            Err(e @ Error::Limited(_)) => request::Outcome::Failure((Status::TooManyRequests, e)),
+            Err(e) => request::Outcome::Failure((Status::InternalServerError, e)),
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::fmt;
+use std::sync::Arc;
+
+use rocket::Request;
+
+type Extractor = dyn Fn(&Request<'_>) -> Option<String> + Send + Sync;
+
+/// Identifies the bucket a request is charged against.
+///
+/// Requests with the same key share a bucket. If a key can't be determined for
+/// a request, the request is not limited.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket_rate_limit::{RateLimit, Quota, Key};
+///
+/// // Limit every client on every route independently.
+/// let limit = RateLimit::new(Quota::per_second(5)).key(Key::ip_and_route());
+///
+/// // Limit requests by their `X-Api-Key` header.
+/// let api_key = Key::custom(|req| req.headers().get_one("X-Api-Key").map(String::from));
+/// let limit = RateLimit::new(Quota::per_minute(100)).key(api_key);
+/// ```
+#[derive(Clone)]
+pub struct Key(Kind);
+
+#[derive(Clone)]
+enum Kind {
+    Ip,
+    Route,
+    IpAndRoute,
+    Custom(Arc<Extractor>),
+}
+
+impl Key {
+    /// Keys requests by the [client's IP](Request::client_ip()). This is the
+    /// default.
+    pub fn ip() -> Key {
+        Key(Kind::Ip)
+    }
+
+    /// Keys requests by the matched route's method and URI, so that all
+    /// clients share a single bucket per route.
+    pub fn route() -> Key {
+        Key(Kind::Route)
+    }
+
+    /// Keys requests by the client's IP and the matched route, so that every
+    /// client has a bucket for every route.
+    pub fn ip_and_route() -> Key {
+        Key(Kind::IpAndRoute)
+    }
+
+    /// Keys requests by the value returned from `f`. Requests for which `f`
+    /// returns `None` are not limited.
+    pub fn custom<F>(f: F) -> Key
+        where F: Fn(&Request<'_>) -> Option<String> + Send + Sync + 'static
+    {
+        Key(Kind::Custom(Arc::new(f)))
+    }
+
+    /// Returns the key for `req`, if any.
+    pub(crate) fn extract(&self, req: &Request<'_>) -> Option<String> {
+        let route = || req.route().map(|r| format!("{} {}", r.method, r.uri));
+        match &self.0 {
+            Kind::Ip => req.client_ip().map(|ip| ip.to_string()),
+            Kind::Route => route(),
+            Kind::IpAndRoute => Some(format!("{} {}", req.client_ip()?, route()?)),
+            Kind::Custom(f) => f(req),
+        }
+    }
+}
+
+impl Default for Key {
+    fn default() -> Self {
+        Key::ip()
+    }
+}
+
+impl fmt::Debug for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Kind::Ip => f.write_str("Key::ip()"),
+            Kind::Route => f.write_str("Key::route()"),
+            Kind::IpAndRoute => f.write_str("Key::ip_and_route()"),
+            Kind::Custom(_) => f.write_str("Key::custom(..)"),
+        }
+    }
+}
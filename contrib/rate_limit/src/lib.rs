@@ -0,0 +1,89 @@
+//! Token-bucket request rate limiting for Rocket.
+//!
+//! This crate limits the rate at which clients can make requests. Every
+//! request is charged against a bucket identified by a [`Key`], such as the
+//! client's IP address or the matched route. Each bucket holds up to a
+//! [`Quota`]'s burst of tokens and is steadily refilled at the quota's rate.
+//! Requests that find their bucket empty fail with `429 Too Many Requests` and
+//! a `Retry-After` header indicating, in seconds, when a token will next be
+//! available.
+//!
+//! # Usage
+//!
+//!   1. Depend on `rocket_rate_limit`:
+//!
+//!      ```toml
+//!      [dependencies.rocket_rate_limit]
+//!      version = "0.1.0-rc.2"
+//!      ```
+//!
+//!   2. Attach a [`RateLimit`] fairing and add a [`RateLimited`] request guard
+//!      to every route that should be limited:
+//!
+//!      ```rust
+//!      # #[macro_use] extern crate rocket;
+//!      use rocket_rate_limit::{RateLimit, RateLimited, Quota};
+//!
+//!      #[get("/")]
+//!      fn index(_limit: RateLimited) -> &'static str {
+//!          "Hello, world!"
+//!      }
+//!
+//!      #[launch]
+//!      fn rocket() -> _ {
+//!          rocket::build()
+//!              .attach(RateLimit::new(Quota::per_minute(60)))
+//!              .mount("/", routes![index])
+//!      }
+//!      ```
+//!
+//! By default, the limit is enforced by the guard: a route without a
+//! `RateLimited` guard is never limited. The fairing manages the limiter's
+//! state and adds the `Retry-After` header to limited responses, including
+//! those produced by a custom `429` catcher. To limit every request instead,
+//! make the limit [global](RateLimit::global()):
+//!
+//! ```rust
+//! use rocket_rate_limit::{RateLimit, Quota};
+//!
+//! let limit = RateLimit::new(Quota::per_minute(60)).global();
+//! ```
+//!
+//! ## Keys
+//!
+//! By default, buckets are keyed by the [client's IP](Key::ip()). Use
+//! [`RateLimit::key()`] to key buckets by [route](Key::route()), by [IP and
+//! route](Key::ip_and_route()), or by any [custom](Key::custom()) property of
+//! a request, such as an API token:
+//!
+//! ```rust
+//! use rocket_rate_limit::{RateLimit, Quota, Key};
+//!
+//! let limit = RateLimit::new(Quota::per_second(10).with_burst(50))
+//!     .key(Key::custom(|req| req.headers().get_one("X-Api-Key").map(String::from)));
+//! ```
+//!
+//! A request for which a key can't be determined, like a request with no known
+//! client IP when keyed by IP, is not limited.
+//!
+//! ## Stores
+//!
+//! Buckets are kept in a [`MemoryStore`] by default, which is local to one
+//! instance of an application. To share limits between instances, implement
+//! [`Store`] atop a shared backend like Redis and supply it via
+//! [`RateLimit::store()`].
+
+#[macro_use]
+extern crate rocket;
+
+mod quota;
+mod key;
+mod store;
+mod fairing;
+mod guard;
+
+pub use self::quota::Quota;
+pub use self::key::Key;
+pub use self::store::{Store, StoreError, Decision, MemoryStore};
+pub use self::fairing::RateLimit;
+pub use self::guard::{RateLimited, Error};
@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+/// The rate at which requests are allowed.
+///
+/// A quota allows `count` requests every `period`, replenishing one token
+/// every `period / count`. Up to [`burst`](Quota::with_burst()) requests,
+/// `count` by default, can be made in immediate succession.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use rocket_rate_limit::Quota;
+///
+/// // 10 requests per second, in bursts of up to 10.
+/// let quota = Quota::per_second(10);
+/// assert_eq!(quota.burst(), 10);
+/// assert_eq!(quota.interval(), Duration::from_millis(100));
+///
+/// // 100 requests per hour, in bursts of up to 5.
+/// let quota = Quota::new(100, Duration::from_secs(3600)).with_burst(5);
+/// assert_eq!(quota.burst(), 5);
+/// assert_eq!(quota.interval(), Duration::from_secs(36));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Quota {
+    interval: Duration,
+    burst: u32,
+}
+
+impl Quota {
+    /// Returns a quota allowing `count` requests every `period`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` or `period` is zero.
+    pub fn new(count: u32, period: Duration) -> Quota {
+        assert!(count > 0, "quota count must be non-zero");
+        assert!(!period.is_zero(), "quota period must be non-zero");
+        Quota { interval: period / count, burst: count }
+    }
+
+    /// Returns a quota allowing `count` requests every second.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is zero.
+    pub fn per_second(count: u32) -> Quota {
+        Quota::new(count, Duration::from_secs(1))
+    }
+
+    /// Returns a quota allowing `count` requests every minute.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is zero.
+    pub fn per_minute(count: u32) -> Quota {
+        Quota::new(count, Duration::from_secs(60))
+    }
+
+    /// Returns a quota allowing `count` requests every hour.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is zero.
+    pub fn per_hour(count: u32) -> Quota {
+        Quota::new(count, Duration::from_secs(60 * 60))
+    }
+
+    /// Sets the maximum number of requests that can be made in immediate
+    /// succession, the capacity of the bucket, to `burst`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `burst` is zero.
+    pub fn with_burst(mut self, burst: u32) -> Quota {
+        assert!(burst > 0, "quota burst must be non-zero");
+        self.burst = burst;
+        self
+    }
+
+    /// The maximum number of requests that can be made in immediate succession.
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+
+    /// The time it takes to replenish a single token.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// The time it takes to replenish every token in an empty bucket.
+    pub(crate) fn window(&self) -> Duration {
+        self.interval * self.burst
+    }
+}
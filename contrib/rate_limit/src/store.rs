@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::Quota;
+
+/// An error produced by a [`Store`].
+pub type StoreError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The outcome of charging a request against its bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    /// The request is allowed. `remaining` more requests can be made before
+    /// the bucket is empty.
+    Allow {
+        /// The number of tokens left in the bucket.
+        remaining: u32,
+    },
+    /// The request is denied. A token will be available after `retry_after`.
+    Deny {
+        /// The time until the next token is available.
+        retry_after: Duration,
+    },
+}
+
+/// A backend that keeps track of buckets.
+///
+/// A store charges requests against a bucket identified by a key, taking a
+/// token from the bucket if one is available. The store is responsible for
+/// refilling buckets according to the [`Quota`], and for doing so atomically
+/// with respect to concurrent requests for the same key.
+///
+/// The [`MemoryStore`], used by default, keeps buckets in memory and is local
+/// to a single instance of an application. A store backed by a shared
+/// database, like Redis, allows several instances of an application to share
+/// limits. Such a store might keep a bucket's state in a single value and
+/// update it with a server-side script so that the update is atomic.
+///
+/// # Example
+///
+/// A store that exempts a client from limits and delegates all other requests
+/// to a `MemoryStore`:
+///
+/// ```rust
+/// use rocket_rate_limit::{Store, StoreError, Decision, MemoryStore, Quota};
+///
+/// struct Exempt {
+///     client: String,
+///     inner: MemoryStore,
+/// }
+///
+/// #[rocket::async_trait]
+/// impl Store for Exempt {
+///     async fn acquire(&self, key: &str, quota: &Quota) -> Result<Decision, StoreError> {
+///         if key == self.client {
+///             return Ok(Decision::Allow { remaining: quota.burst() });
+///         }
+///
+///         self.inner.acquire(key, quota).await
+///     }
+/// }
+/// ```
+#[rocket::async_trait]
+pub trait Store: Send + Sync + 'static {
+    /// Takes a token from the bucket identified by `key`, which is refilled
+    /// according to `quota`, returning whether the request is allowed.
+    async fn acquire(&self, key: &str, quota: &Quota) -> Result<Decision, StoreError>;
+}
+
+/// An in-memory [`Store`].
+///
+/// Buckets are tracked using the generic cell rate algorithm, which requires
+/// a single timestamp per key. Buckets that have been fully replenished are
+/// equivalent to absent ones and are periodically removed, so memory use is
+/// proportional to the number of recently limited keys.
+#[derive(Debug)]
+pub struct MemoryStore {
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    /// Map from key to the time at which the key's bucket is full.
+    buckets: HashMap<String, Instant>,
+    /// The number of buckets at which to next remove full buckets.
+    sweep_at: usize,
+}
+
+impl MemoryStore {
+    const MIN_SWEEP: usize = 1024;
+
+    /// Returns a new, empty store.
+    pub fn new() -> MemoryStore {
+        let state = State { buckets: HashMap::new(), sweep_at: Self::MIN_SWEEP };
+        MemoryStore { state: Mutex::new(state) }
+    }
+
+    fn acquire_at(&self, key: &str, quota: &Quota, now: Instant) -> Decision {
+        let mut state = self.state.lock().expect("rate limit store lock");
+        if state.buckets.len() >= state.sweep_at {
+            state.buckets.retain(|_, full_at| *full_at > now);
+            state.sweep_at = std::cmp::max(Self::MIN_SWEEP, state.buckets.len() * 2);
+        }
+
+        let full_at = state.buckets.get(key).map_or(now, |t| std::cmp::max(*t, now));
+        let new_full_at = full_at + quota.interval();
+        let debt = new_full_at - now;
+        if debt > quota.window() {
+            return Decision::Deny { retry_after: debt - quota.window() };
+        }
+
+        state.buckets.insert(key.into(), new_full_at);
+        let remaining = (quota.window() - debt).as_nanos() / quota.interval().as_nanos();
+        Decision::Allow { remaining: remaining as u32 }
+    }
+}
+
+impl Default for MemoryStore {
+    fn default() -> Self {
+        MemoryStore::new()
+    }
+}
+
+#[rocket::async_trait]
+impl Store for MemoryStore {
+    async fn acquire(&self, key: &str, quota: &Quota) -> Result<Decision, StoreError> {
+        Ok(self.acquire_at(key, quota, Instant::now()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{MemoryStore, Decision};
+    use crate::Quota;
+
+    #[test]
+    fn test_token_bucket() {
+        let store = MemoryStore::new();
+        let quota = Quota::per_second(2).with_burst(3);
+        let start = Instant::now();
+
+        for remaining in [2, 1, 0] {
+            assert_eq!(store.acquire_at("a", &quota, start), Decision::Allow { remaining });
+        }
+
+        let retry_after = Duration::from_millis(500);
+        assert_eq!(store.acquire_at("a", &quota, start), Decision::Deny { retry_after });
+        assert_eq!(store.acquire_at("b", &quota, start), Decision::Allow { remaining: 2 });
+
+        let later = start + Duration::from_millis(250);
+        let retry_after = Duration::from_millis(250);
+        assert_eq!(store.acquire_at("a", &quota, later), Decision::Deny { retry_after });
+
+        let later = start + Duration::from_millis(500);
+        assert_eq!(store.acquire_at("a", &quota, later), Decision::Allow { remaining: 0 });
+
+        let later = start + Duration::from_secs(10);
+        assert_eq!(store.acquire_at("a", &quota, later), Decision::Allow { remaining: 2 });
+    }
+
+    #[test]
+    fn test_sweeps_full_buckets() {
+        let store = MemoryStore::new();
+        let quota = Quota::per_second(1);
+        let start = Instant::now();
+
+        for i in 0..MemoryStore::MIN_SWEEP {
+            store.acquire_at(&i.to_string(), &quota, start);
+        }
+
+        let later = start + Duration::from_secs(2);
+        store.acquire_at("new", &quota, later);
+        assert_eq!(store.state.lock().unwrap().buckets.len(), 1);
+    }
+}
@@ -0,0 +1,139 @@
+#[macro_use] extern crate rocket;
+
+use std::net::SocketAddr;
+
+use rocket::{Rocket, Build, Request};
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+use rocket_rate_limit::{RateLimit, RateLimited, Quota, Key};
+use rocket_rate_limit::{Store, StoreError, Decision};
+
+#[get("/")]
+fn index(limit: RateLimited) -> String {
+    match limit.remaining() {
+        Some(n) => n.to_string(),
+        None => "unlimited".into(),
+    }
+}
+
+#[get("/other")]
+fn other(limit: RateLimited) -> String {
+    index(limit)
+}
+
+#[get("/free")]
+fn free() -> &'static str {
+    "free"
+}
+
+#[catch(429)]
+fn too_many(_: &Request<'_>) -> &'static str {
+    "slow down"
+}
+
+fn rocket(limit: RateLimit) -> Rocket<Build> {
+    rocket::build()
+        .attach(limit)
+        .mount("/", routes![index, other, free])
+}
+
+fn addr(ip: &str) -> SocketAddr {
+    format!("{}:8000", ip).parse().unwrap()
+}
+
+fn get(client: &Client, uri: &'static str, ip: &str) -> (Status, String) {
+    let response = client.get(uri).remote(addr(ip)).dispatch();
+    (response.status(), response.into_string().unwrap())
+}
+
+#[test]
+fn limits_by_ip() {
+    let client = Client::debug(rocket(RateLimit::new(Quota::per_hour(2)))).unwrap();
+
+    assert_eq!(get(&client, "/", "1.1.1.1"), (Status::Ok, "1".into()));
+    assert_eq!(get(&client, "/other", "1.1.1.1"), (Status::Ok, "0".into()));
+
+    let response = client.get("/").remote(addr("1.1.1.1")).dispatch();
+    assert_eq!(response.status(), Status::TooManyRequests);
+    let retry_after: u64 = response.headers().get_one("Retry-After").unwrap().parse().unwrap();
+    assert!(retry_after > 1700 && retry_after <= 1800);
+
+    assert_eq!(get(&client, "/", "2.2.2.2"), (Status::Ok, "1".into()));
+    assert_eq!(get(&client, "/free", "1.1.1.1"), (Status::Ok, "free".into()));
+
+    // Requests without a client IP aren't limited.
+    let response = client.get("/").dispatch();
+    assert_eq!(response.into_string().unwrap(), "unlimited");
+}
+
+#[test]
+fn limits_by_route() {
+    let limit = RateLimit::new(Quota::per_hour(1)).key(Key::route());
+    let client = Client::debug(rocket(limit)).unwrap();
+
+    assert_eq!(get(&client, "/", "1.1.1.1").0, Status::Ok);
+    assert_eq!(get(&client, "/", "2.2.2.2").0, Status::TooManyRequests);
+    assert_eq!(get(&client, "/other", "2.2.2.2").0, Status::Ok);
+
+    let limit = RateLimit::new(Quota::per_hour(1)).key(Key::ip_and_route());
+    let client = Client::debug(rocket(limit)).unwrap();
+
+    assert_eq!(get(&client, "/", "1.1.1.1").0, Status::Ok);
+    assert_eq!(get(&client, "/", "1.1.1.1").0, Status::TooManyRequests);
+    assert_eq!(get(&client, "/", "2.2.2.2").0, Status::Ok);
+    assert_eq!(get(&client, "/other", "1.1.1.1").0, Status::Ok);
+}
+
+#[test]
+fn limits_by_custom_key() {
+    let key = Key::custom(|req| req.headers().get_one("X-Api-Key").map(String::from));
+    let limit = RateLimit::new(Quota::per_hour(1)).key(key);
+    let client = Client::debug(rocket(limit).register("/", catchers![too_many])).unwrap();
+
+    let request = |key: &'static str| client.get("/").header(Header::new("X-Api-Key", key));
+    assert_eq!(request("a").dispatch().status(), Status::Ok);
+    assert_eq!(request("b").dispatch().status(), Status::Ok);
+
+    let response = request("a").dispatch();
+    assert_eq!(response.status(), Status::TooManyRequests);
+    assert!(response.headers().get_one("Retry-After").is_some());
+    assert_eq!(response.into_string().unwrap(), "slow down");
+
+    let response = client.get("/").dispatch();
+    assert_eq!(response.into_string().unwrap(), "unlimited");
+}
+
+#[test]
+fn global_limits_every_request() {
+    let limit = RateLimit::new(Quota::per_hour(3)).global();
+    let client = Client::debug(rocket(limit)).unwrap();
+
+    assert_eq!(get(&client, "/free", "1.1.1.1"), (Status::Ok, "free".into()));
+    assert_eq!(get(&client, "/", "1.1.1.1"), (Status::Ok, "1".into()));
+    assert_eq!(get(&client, "/404", "1.1.1.1").0, Status::NotFound);
+
+    let response = client.get("/free").remote(addr("1.1.1.1")).dispatch();
+    assert_eq!(response.status(), Status::TooManyRequests);
+    assert!(response.headers().get_one("Retry-After").is_some());
+
+    assert_eq!(get(&client, "/", "2.2.2.2"), (Status::Ok, "2".into()));
+}
+
+struct Offline;
+
+#[rocket::async_trait]
+impl Store for Offline {
+    async fn acquire(&self, _: &str, _: &Quota) -> Result<Decision, StoreError> {
+        Err("store is offline".into())
+    }
+}
+
+#[test]
+fn store_errors_and_missing_fairing() {
+    let limit = RateLimit::new(Quota::per_hour(1)).store(Offline);
+    let client = Client::debug(rocket(limit)).unwrap();
+    assert_eq!(get(&client, "/", "1.1.1.1").0, Status::InternalServerError);
+
+    let client = Client::debug(rocket::build().mount("/", routes![index])).unwrap();
+    assert_eq!(get(&client, "/", "1.1.1.1").0, Status::InternalServerError);
+}
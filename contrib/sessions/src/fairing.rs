@@ -0,0 +1,182 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::time::Duration;
+
+use rocket::{Rocket, Build, Request, Response};
+use rocket::fairing::{self, Fairing, Info, Kind};
+
+use crate::{Store, MemoryStore};
+
+/// Fairing that enables sessions via the [`Session`] guard.
+///
+/// The fairing configures where session data is kept, for how long, and in
+/// which cookie, and saves modified sessions to their store once a response
+/// has been produced.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use std::time::Duration;
+/// use rocket_sessions::{Sessions, Session};
+///
+/// #[get("/")]
+/// fn index(session: Session<'_>) -> String {
+///     let visits = session.get::<usize>("visits").unwrap_or(0) + 1;
+///     session.set("visits", visits).unwrap();
+///     format!("visit #{}", visits)
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     let sessions = Sessions::new()
+///         .ttl(Duration::from_secs(60 * 60))
+///         .cookie_name("sid");
+///
+///     rocket::build()
+///         .attach(sessions)
+///         .mount("/", routes![index])
+/// }
+/// ```
+///
+/// [`Session`]: crate::Session
+pub struct Sessions(Manager);
+
+/// The state managed by [`Sessions`] and used by the `Session` guard.
+#[derive(Clone)]
+pub(crate) struct Manager {
+    pub(crate) backend: Backend,
+    pub(crate) ttl: Duration,
+    pub(crate) cookie_name: Cow<'static, str>,
+}
+
+/// Where session data is kept.
+#[derive(Clone)]
+pub(crate) enum Backend {
+    /// In the session cookie itself.
+    Cookie,
+    /// In a store, keyed by an ID kept in the session cookie.
+    Store(Arc<dyn Store>),
+}
+
+impl Sessions {
+    /// The default time-to-live of a session: one day.
+    const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+    /// The default name of the session cookie: `rocket_session`.
+    const DEFAULT_COOKIE_NAME: &'static str = "rocket_session";
+
+    /// Returns a fairing keeping sessions in a [`MemoryStore`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_sessions::Sessions;
+    ///
+    /// let sessions = Sessions::new();
+    /// ```
+    pub fn new() -> Sessions {
+        Sessions::with_backend(Backend::Store(Arc::new(MemoryStore::new())))
+    }
+
+    /// Returns a fairing keeping session data in the session cookie.
+    ///
+    /// The cookie is a [private cookie], so its contents are encrypted and
+    /// authenticated with the application's secret key. The sealed contents
+    /// include the session's expiry, so an expired cookie is treated as an
+    /// empty session even if the client keeps sending it. Cookie-backed
+    /// sessions require no server-side storage but are limited in size:
+    /// browsers reject cookies larger than 4KiB, so a modification that would
+    /// produce a larger cookie is logged as an error and the cookie isn't
+    /// updated. They also can't be revoked before they expire: a client can
+    /// replay an old, unexpired cookie.
+    ///
+    /// [private cookie]: rocket::http::CookieJar#private-cookies
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_sessions::Sessions;
+    ///
+    /// let sessions = Sessions::cookie();
+    /// ```
+    pub fn cookie() -> Sessions {
+        Sessions::with_backend(Backend::Cookie)
+    }
+
+    fn with_backend(backend: Backend) -> Sessions {
+        Sessions(Manager {
+            backend,
+            ttl: Self::DEFAULT_TTL,
+            cookie_name: Self::DEFAULT_COOKIE_NAME.into(),
+        })
+    }
+
+    /// Sets the store in which sessions are kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_sessions::{Sessions, MemoryStore};
+    ///
+    /// let sessions = Sessions::new().store(MemoryStore::new());
+    /// ```
+    pub fn store<S: Store>(mut self, store: S) -> Sessions {
+        self.0.backend = Backend::Store(Arc::new(store));
+        self
+    }
+
+    /// Sets the duration after which a session that hasn't been modified
+    /// expires. Defaults to one day.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use rocket_sessions::Sessions;
+    ///
+    /// let sessions = Sessions::new().ttl(Duration::from_secs(15 * 60));
+    /// ```
+    pub fn ttl(mut self, ttl: Duration) -> Sessions {
+        self.0.ttl = ttl;
+        self
+    }
+
+    /// Sets the name of the session cookie. Defaults to `rocket_session`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_sessions::Sessions;
+    ///
+    /// let sessions = Sessions::new().cookie_name("sid");
+    /// ```
+    pub fn cookie_name<N: Into<Cow<'static, str>>>(mut self, name: N) -> Sessions {
+        self.0.cookie_name = name.into();
+        self
+    }
+}
+
+impl Default for Sessions {
+    fn default() -> Self {
+        Sessions::new()
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for Sessions {
+    fn info(&self) -> Info {
+        Info {
+            name: "Sessions",
+            kind: Kind::Ignite | Kind::Response | Kind::Singleton,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        Ok(rocket.manage(self.0.clone()))
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, _: &mut Response<'r>) {
+        crate::session::persist(req, &self.0).await;
+    }
+}
@@ -0,0 +1,67 @@
+//! Server-side session management for Rocket.
+//!
+//! This crate associates data with a client across requests. A client's
+//! [`Session`] is a map from string keys to serializable values, retrieved
+//! with the `Session` request guard and kept either in a [`Store`], keyed by a
+//! random ID in the session cookie, or in the session cookie itself. Session
+//! cookies are [private cookies], encrypted and authenticated with the
+//! application's [secret key].
+//!
+//! [private cookies]: rocket::http::CookieJar#private-cookies
+//! [secret key]: rocket::Config::secret_key
+//!
+//! # Usage
+//!
+//!   1. Depend on `rocket_sessions`:
+//!
+//!      ```toml
+//!      [dependencies.rocket_sessions]
+//!      version = "0.1.0-rc.2"
+//!      ```
+//!
+//!   2. Attach a [`Sessions`] fairing and use the [`Session`] request guard:
+//!
+//!      ```rust
+//!      # #[macro_use] extern crate rocket;
+//!      use rocket_sessions::{Sessions, Session};
+//!
+//!      #[get("/")]
+//!      fn index(session: Session<'_>) -> String {
+//!          let visits = session.get::<usize>("visits").unwrap_or(0) + 1;
+//!          session.set("visits", visits).unwrap();
+//!          format!("visit #{}", visits)
+//!      }
+//!
+//!      #[launch]
+//!      fn rocket() -> _ {
+//!          rocket::build()
+//!              .attach(Sessions::new())
+//!              .mount("/", routes![index])
+//!      }
+//!      ```
+//!
+//! Sessions are loaded on first use and, if modified, saved once a response
+//! has been produced. Sessions expire after they haven't been modified for a
+//! [time-to-live](Sessions::ttl()), by default one day. To prevent session
+//! fixation, [regenerate](Session::regenerate()) a session's ID when its
+//! client's privileges change, and [destroy](Session::destroy()) it when its
+//! client logs out.
+//!
+//! ## Stores
+//!
+//! Sessions are kept in a [`MemoryStore`] by default, which is local to one
+//! instance of an application. To share sessions between instances or keep
+//! them across restarts, implement [`Store`] atop a shared backend like Redis
+//! or a database and supply it via [`Sessions::store()`]. Alternatively,
+//! [`Sessions::cookie()`] keeps session data in the session cookie itself.
+
+#[macro_use]
+extern crate rocket;
+
+mod store;
+mod fairing;
+mod session;
+
+pub use self::store::{Store, StoreError, MemoryStore};
+pub use self::fairing::Sessions;
+pub use self::session::{Session, Error};
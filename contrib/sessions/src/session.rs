@@ -0,0 +1,386 @@
+use std::fmt;
+use std::sync::{Mutex, MutexGuard};
+
+use rocket::Request;
+use rocket::http::{Cookie, CookieJar, SameSite, Status};
+use rocket::request::{self, FromRequest};
+use rocket::serde::{Serialize, de::DeserializeOwned};
+use rocket::serde::json::{self, Value, serde_json};
+use rocket::time::OffsetDateTime;
+use rocket::tokio::sync::OnceCell;
+
+use crate::StoreError;
+use crate::fairing::{Manager, Backend};
+
+/// Request guard that retrieves the client's session.
+///
+/// A session is a map from string keys to values of any type that implements
+/// [`Serialize`] and [`Deserialize`](rocket::serde::Deserialize). A client's
+/// session is loaded the first time it's retrieved during a request: from the
+/// session cookie, for sessions kept in [cookies](crate::Sessions::cookie()),
+/// or from the [`Store`](crate::Store) via the ID in the session cookie. A
+/// client without a valid, unexpired session is given a new, empty session. A
+/// session is only saved, and the session cookie only set, once the session
+/// is modified.
+///
+/// Modifications are saved to the store after the response has been
+/// produced. A session expires, and is removed from its store, after it
+/// hasn't been modified for the configured [time-to-live](crate::Sessions::ttl()).
+///
+/// If the store fails to load the session, the guard fails with `500 Internal
+/// Server Error` and an [`Error::Store`]. If the [`Sessions`](crate::Sessions)
+/// fairing isn't attached, the guard fails with `500 Internal Server Error`
+/// and an [`Error::Unmanaged`].
+///
+/// # Privilege Changes
+///
+/// To prevent session fixation, [regenerate](Session::regenerate()) the
+/// session's ID whenever the privileges of its client change, as when the
+/// client logs in. When the client logs out, [destroy](Session::destroy())
+/// the session:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_sessions::Session;
+///
+/// #[post("/login")]
+/// fn login(session: Session<'_>) {
+///     // .. verify credentials ..
+///     session.regenerate();
+///     session.set("user", "bob").unwrap();
+/// }
+///
+/// #[post("/logout")]
+/// fn logout(session: Session<'_>) {
+///     session.destroy();
+/// }
+/// ```
+pub struct Session<'r> {
+    state: &'r State,
+    jar: &'r CookieJar<'r>,
+    manager: &'r Manager,
+}
+
+/// An error returned by the [`Session`] guard.
+#[derive(Debug)]
+pub enum Error {
+    /// The store failed to load the session.
+    Store(StoreError),
+    /// The [`Sessions`](crate::Sessions) fairing isn't attached.
+    Unmanaged,
+}
+
+/// The session of a request, loaded lazily and cached in the request's local
+/// cache.
+#[derive(Default)]
+struct Slot(OnceCell<State>);
+
+struct State(Mutex<Inner>);
+
+struct Inner {
+    /// The ID of the session in the store, if it has one.
+    id: Option<String>,
+    data: serde_json::Map<String, Value>,
+    /// Whether the session was modified since it was loaded or saved.
+    modified: bool,
+    /// The ID of a destroyed or regenerated session to remove from the store.
+    stale: Option<String>,
+}
+
+impl<'r> Session<'r> {
+    /// Returns the value for `key`, if there is one and it can be deserialized
+    /// as a `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_sessions::Session;
+    ///
+    /// #[get("/")]
+    /// fn index(session: Session<'_>) -> String {
+    ///     match session.get::<String>("user") {
+    ///         Some(user) => format!("Hello, {}!", user),
+    ///         None => "Hello, stranger!".into(),
+    ///     }
+    /// }
+    /// ```
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let value = self.inner().data.get(key).cloned()?;
+        json::from_value(value).ok()
+    }
+
+    /// Sets the value for `key` to `value`, replacing any existing value.
+    /// Fails if `value` can't be serialized.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_sessions::Session;
+    ///
+    /// #[post("/theme/<theme>")]
+    /// fn theme(session: Session<'_>, theme: &str) {
+    ///     session.set("theme", theme).expect("strings serialize");
+    /// }
+    /// ```
+    pub fn set<T: Serialize>(&self, key: &str, value: T) -> Result<(), serde_json::Error> {
+        let value = json::to_value(value)?;
+        let mut inner = self.inner();
+        inner.data.insert(key.into(), value);
+        self.modified(&mut inner);
+        Ok(())
+    }
+
+    /// Removes the value for `key`, returning `true` if there was one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_sessions::Session;
+    ///
+    /// #[delete("/theme")]
+    /// fn reset_theme(session: Session<'_>) -> &'static str {
+    ///     match session.remove("theme") {
+    ///         true => "theme reset",
+    ///         false => "no theme was set",
+    ///     }
+    /// }
+    /// ```
+    pub fn remove(&self, key: &str) -> bool {
+        let mut inner = self.inner();
+        let removed = inner.data.remove(key).is_some();
+        if removed {
+            self.modified(&mut inner);
+        }
+
+        removed
+    }
+
+    /// Removes all values from the session.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_sessions::Session;
+    ///
+    /// #[post("/reset")]
+    /// fn reset(session: Session<'_>) {
+    ///     session.clear();
+    /// }
+    /// ```
+    pub fn clear(&self) {
+        let mut inner = self.inner();
+        if !inner.data.is_empty() {
+            inner.data.clear();
+            self.modified(&mut inner);
+        }
+    }
+
+    /// Replaces the session's ID with a new, random ID, keeping its data. The
+    /// session under the old ID is removed from the store. For sessions kept
+    /// in cookies, this refreshes the cookie.
+    ///
+    /// See [privilege changes](#privilege-changes) for when to call this
+    /// method.
+    pub fn regenerate(&self) {
+        let mut inner = self.inner();
+        if let Some(id) = inner.id.take() {
+            inner.stale = Some(id);
+        }
+
+        self.modified(&mut inner);
+    }
+
+    /// Removes all values from the session, removes the session from the
+    /// store, and removes the session cookie. Setting a value afterwards
+    /// starts a new session.
+    ///
+    /// See [privilege changes](#privilege-changes) for when to call this
+    /// method.
+    pub fn destroy(&self) {
+        let mut inner = self.inner();
+        if let Some(id) = inner.id.take() {
+            inner.stale = Some(id);
+        }
+
+        inner.data.clear();
+        inner.modified = false;
+        self.jar.remove_private(Cookie::named(self.manager.cookie_name.clone()));
+    }
+
+    fn inner(&self) -> MutexGuard<'r, Inner> {
+        self.state.0.lock().expect("session lock")
+    }
+
+    /// Marks the session as modified and sets the session cookie.
+    fn modified(&self, inner: &mut Inner) {
+        inner.modified = true;
+        let expires = OffsetDateTime::now_utc() + self.manager.ttl;
+        let value = match self.manager.backend {
+            Backend::Cookie => {
+                let data = Value::Object(inner.data.clone());
+                json::json!({ "exp": expires.unix_timestamp(), "data": data }).to_string()
+            }
+            Backend::Store(_) => inner.id.get_or_insert_with(generate_id).clone(),
+        };
+
+        let name = &self.manager.cookie_name;
+        if sealed_len(name, &value) > MAX_COOKIE_SIZE {
+            error_!("Session cookie `{}` exceeds {} bytes and was not set.", name, MAX_COOKIE_SIZE);
+            info_!("Store less data in the session or use a session store.");
+            return;
+        }
+
+        let cookie = Cookie::build(name.clone(), value)
+            .path("/")
+            .same_site(SameSite::Lax)
+            .http_only(true)
+            .expires(expires)
+            .finish();
+
+        self.jar.add_private(cookie);
+    }
+}
+
+/// The largest cookie, name and value, that browsers are required to accept.
+const MAX_COOKIE_SIZE: usize = 4096;
+
+/// Returns the size of the cookie named `name` with `value` once sealed as a
+/// private cookie: the base64 encoding of a 12-byte nonce, the encrypted value,
+/// and a 16-byte tag.
+fn sealed_len(name: &str, value: &str) -> usize {
+    name.len() + (12 + value.len() + 16 + 2) / 3 * 4
+}
+
+/// The payload of a session kept in a cookie.
+#[derive(rocket::serde::Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct Sealed {
+    /// The UNIX timestamp after which the session has expired.
+    exp: i64,
+    data: serde_json::Map<String, Value>,
+}
+
+impl Sealed {
+    /// Returns the session's data if `payload` is valid and hasn't expired.
+    fn open(payload: &str) -> Option<serde_json::Map<String, Value>> {
+        let sealed: Sealed = json::from_str(payload).ok()?;
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        (sealed.exp > now).then(|| sealed.data)
+    }
+}
+
+/// Generates a random, 256-bit session ID.
+fn generate_id() -> String {
+    let bytes: [u8; 32] = rand::random();
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Loads the session of `req` from the cookie or store.
+async fn load(req: &Request<'_>, manager: &Manager) -> Result<State, StoreError> {
+    let cookie = req.cookies().get_private(&manager.cookie_name);
+    let (id, data) = match (&manager.backend, cookie) {
+        (Backend::Cookie, Some(cookie)) => (None, Sealed::open(cookie.value())),
+        (Backend::Store(store), Some(cookie)) => match store.load(cookie.value()).await? {
+            Some(data) => (Some(cookie.value().to_string()), json::from_str(&data).ok()),
+            None => (None, None),
+        },
+        (_, None) => (None, None),
+    };
+
+    let data = data.unwrap_or_default();
+    let inner = Inner { id, data, modified: false, stale: None };
+    Ok(State(Mutex::new(inner)))
+}
+
+/// Saves the session of `req`, if it was loaded and modified, to its store.
+pub(crate) async fn persist(req: &Request<'_>, manager: &Manager) {
+    let store = match &manager.backend {
+        Backend::Store(store) => store,
+        Backend::Cookie => return,
+    };
+
+    let state = match req.local_cache(Slot::default).0.get() {
+        Some(state) => state,
+        None => return,
+    };
+
+    let (stale, save) = {
+        let mut inner = state.0.lock().expect("session lock");
+        let save = match (inner.modified, &inner.id) {
+            (true, Some(id)) => Some((id.clone(), Value::Object(inner.data.clone()).to_string())),
+            _ => None,
+        };
+
+        inner.modified = false;
+        (inner.stale.take(), save)
+    };
+
+    if let Some(id) = stale {
+        if let Err(e) = store.remove(&id).await {
+            error_!("Failed to remove session: {}", e);
+        }
+    }
+
+    if let Some((id, data)) = save {
+        if let Err(e) = store.save(&id, &data, manager.ttl).await {
+            error_!("Failed to save session: {}", e);
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Session<'r> {
+    type Error = Error;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Error> {
+        let manager = match req.rocket().state::<Manager>() {
+            Some(manager) => manager,
+            None => {
+                error_!("Missing session state: missing fairing.");
+                info_!("To use `Session`, you must attach a `Sessions` fairing.");
+                return request::Outcome::Failure((Status::InternalServerError, Error::Unmanaged));
+            }
+        };
+
+        let slot = req.local_cache(Slot::default);
+        match slot.0.get_or_try_init(|| load(req, manager)).await {
+            Ok(state) => request::Outcome::Success(Session { state, jar: req.cookies(), manager }),
+            Err(e) => {
+                error_!("Session store failed: {}", e);
+                request::Outcome::Failure((Status::InternalServerError, Error::Store(e)))
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Session<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.inner();
+        f.debug_struct("Session")
+            .field("data", &inner.data)
+            .field("modified", &inner.modified)
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Store(e) => write!(f, "session store error: {}", e),
+            Error::Unmanaged => write!(f, "sessions fairing is not attached"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Store(e) => Some(&**e),
+            _ => None,
+        }
+    }
+}
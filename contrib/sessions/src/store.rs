@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// An error produced by a [`Store`].
+pub type StoreError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A backend that keeps session data.
+///
+/// A store maps session IDs to the serialized data of the corresponding
+/// session. Session data is saved with a time-to-live after which the store
+/// must no longer return it. IDs are random, 256-bit values generated by
+/// [`Sessions`](crate::Sessions), so a store needn't guard against guessable
+/// IDs.
+///
+/// The [`MemoryStore`], used by default, keeps sessions in memory: they are
+/// local to a single instance of an application and lost when it stops. A
+/// store backed by a shared database, like Redis or a SQL database accessed
+/// via `rocket_db_pools`, allows several instances of an application to share
+/// sessions. Such a store might save data with the database's own expiration
+/// mechanism, like Redis' `SET key value EX ttl`.
+///
+/// # Example
+///
+/// A store that logs every session it saves and delegates to a `MemoryStore`:
+///
+/// ```rust
+/// use std::time::Duration;
+/// use rocket_sessions::{Store, StoreError, MemoryStore};
+///
+/// struct Logged(MemoryStore);
+///
+/// #[rocket::async_trait]
+/// impl Store for Logged {
+///     async fn load(&self, id: &str) -> Result<Option<String>, StoreError> {
+///         self.0.load(id).await
+///     }
+///
+///     async fn save(&self, id: &str, data: &str, ttl: Duration) -> Result<(), StoreError> {
+///         println!("saving {} bytes", data.len());
+///         self.0.save(id, data, ttl).await
+///     }
+///
+///     async fn remove(&self, id: &str) -> Result<(), StoreError> {
+///         self.0.remove(id).await
+///     }
+/// }
+/// ```
+#[rocket::async_trait]
+pub trait Store: Send + Sync + 'static {
+    /// Returns the data of the unexpired session with ID `id`, if there is
+    /// one.
+    async fn load(&self, id: &str) -> Result<Option<String>, StoreError>;
+
+    /// Saves `data` as the data of the session with ID `id`, replacing any
+    /// existing data. The session expires after `ttl`.
+    async fn save(&self, id: &str, data: &str, ttl: Duration) -> Result<(), StoreError>;
+
+    /// Removes the session with ID `id`, if there is one.
+    async fn remove(&self, id: &str) -> Result<(), StoreError>;
+}
+
+/// An in-memory [`Store`].
+///
+/// Expired sessions are never returned and are periodically removed, so
+/// memory use is proportional to the number of live sessions.
+#[derive(Debug)]
+pub struct MemoryStore {
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    /// Map from session ID to the session's data and expiration time.
+    sessions: HashMap<String, (String, Instant)>,
+    /// The number of sessions at which to next remove expired sessions.
+    sweep_at: usize,
+}
+
+impl MemoryStore {
+    const MIN_SWEEP: usize = 1024;
+
+    /// Returns a new, empty store.
+    pub fn new() -> MemoryStore {
+        let state = State { sessions: HashMap::new(), sweep_at: Self::MIN_SWEEP };
+        MemoryStore { state: Mutex::new(state) }
+    }
+
+    fn load_at(&self, id: &str, now: Instant) -> Option<String> {
+        let state = self.state.lock().expect("session store lock");
+        state.sessions.get(id)
+            .filter(|(_, expires)| *expires > now)
+            .map(|(data, _)| data.clone())
+    }
+
+    fn save_at(&self, id: &str, data: &str, ttl: Duration, now: Instant) {
+        let mut state = self.state.lock().expect("session store lock");
+        if state.sessions.len() >= state.sweep_at {
+            state.sessions.retain(|_, (_, expires)| *expires > now);
+            state.sweep_at = std::cmp::max(Self::MIN_SWEEP, state.sessions.len() * 2);
+        }
+
+        state.sessions.insert(id.into(), (data.into(), now + ttl));
+    }
+}
+
+impl Default for MemoryStore {
+    fn default() -> Self {
+        MemoryStore::new()
+    }
+}
+
+#[rocket::async_trait]
+impl Store for MemoryStore {
+    async fn load(&self, id: &str) -> Result<Option<String>, StoreError> {
+        Ok(self.load_at(id, Instant::now()))
+    }
+
+    async fn save(&self, id: &str, data: &str, ttl: Duration) -> Result<(), StoreError> {
+        self.save_at(id, data, ttl, Instant::now());
+        Ok(())
+    }
+
+    async fn remove(&self, id: &str) -> Result<(), StoreError> {
+        self.state.lock().expect("session store lock").sessions.remove(id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::MemoryStore;
+
+    #[test]
+    fn test_expiration() {
+        let store = MemoryStore::new();
+        let start = Instant::now();
+        store.save_at("a", "data", Duration::from_secs(10), start);
+
+        assert_eq!(store.load_at("a", start).as_deref(), Some("data"));
+        assert_eq!(store.load_at("b", start), None);
+
+        let later = start + Duration::from_secs(9);
+        assert_eq!(store.load_at("a", later).as_deref(), Some("data"));
+
+        let later = start + Duration::from_secs(10);
+        assert_eq!(store.load_at("a", later), None);
+    }
+
+    #[test]
+    fn test_sweeps_expired_sessions() {
+        let store = MemoryStore::new();
+        let start = Instant::now();
+        let ttl = Duration::from_secs(1);
+        for i in 0..MemoryStore::MIN_SWEEP {
+            store.save_at(&i.to_string(), "data", ttl, start);
+        }
+
+        let later = start + Duration::from_secs(2);
+        store.save_at("new", "data", ttl, later);
+        let state = store.state.lock().unwrap();
+        assert_eq!(state.sessions.len(), 1);
+        assert_eq!(state.sweep_at, MemoryStore::MIN_SWEEP);
+    }
+}
@@ -0,0 +1,168 @@
+#[macro_use] extern crate rocket;
+
+use std::time::Duration;
+
+use rocket::{Rocket, Build};
+use rocket::http::{Cookie, Status};
+use rocket::local::blocking::{Client, LocalResponse};
+use rocket_sessions::{Sessions, Session, Store, StoreError};
+
+#[get("/")]
+fn get(session: Session<'_>) -> String {
+    session.get::<String>("user").unwrap_or_else(|| "none".into())
+}
+
+#[get("/count")]
+fn count(session: Session<'_>) -> String {
+    let count = session.get::<usize>("count").unwrap_or(0) + 1;
+    session.set("count", count).unwrap();
+    count.to_string()
+}
+
+#[post("/login/<user>")]
+fn login(session: Session<'_>, user: &str) {
+    session.regenerate();
+    session.set("user", user).unwrap();
+}
+
+#[post("/big/<len>")]
+fn big(session: Session<'_>, len: usize) {
+    session.set("big", "x".repeat(len)).unwrap();
+}
+
+#[post("/logout")]
+fn logout(session: Session<'_>) {
+    session.destroy();
+}
+
+fn rocket(sessions: Sessions) -> Rocket<Build> {
+    rocket::build()
+        .attach(sessions)
+        .mount("/", routes![get, count, login, big, logout])
+}
+
+fn session_cookie(response: &LocalResponse<'_>) -> Option<Cookie<'static>> {
+    response.cookies().get("rocket_session").cloned()
+}
+
+fn check_round_trip(sessions: Sessions) {
+    let client = Client::tracked(rocket(sessions)).unwrap();
+    let response = client.get("/").dispatch();
+    assert!(session_cookie(&response).is_none());
+    assert_eq!(response.into_string().unwrap(), "none");
+
+    for i in 1..=3 {
+        assert_eq!(client.get("/count").dispatch().into_string().unwrap(), i.to_string());
+    }
+
+    client.post("/login/bob").dispatch();
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "bob");
+    assert_eq!(client.get("/count").dispatch().into_string().unwrap(), "4");
+
+    client.post("/logout").dispatch();
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "none");
+    assert_eq!(client.get("/count").dispatch().into_string().unwrap(), "1");
+}
+
+#[test]
+fn memory_store_round_trip() {
+    check_round_trip(Sessions::new());
+}
+
+#[test]
+fn cookie_round_trip() {
+    check_round_trip(Sessions::cookie());
+}
+
+#[test]
+fn regenerate_and_destroy_invalidate_ids() {
+    let client = Client::untracked(rocket(Sessions::new())).unwrap();
+    let original = session_cookie(&client.get("/count").dispatch()).unwrap();
+
+    let response = client.post("/login/bob").cookie(original.clone()).dispatch();
+    let regenerated = session_cookie(&response).unwrap();
+
+    let response = client.get("/").cookie(original).dispatch();
+    assert_eq!(response.into_string().unwrap(), "none");
+
+    let response = client.get("/").cookie(regenerated.clone()).dispatch();
+    assert_eq!(response.into_string().unwrap(), "bob");
+
+    let response = client.post("/logout").cookie(regenerated.clone()).dispatch();
+    assert_eq!(session_cookie(&response).unwrap().value(), "");
+
+    let response = client.get("/").cookie(regenerated).dispatch();
+    assert_eq!(response.into_string().unwrap(), "none");
+}
+
+#[test]
+fn sessions_expire() {
+    let client = Client::tracked(rocket(Sessions::new().ttl(Duration::ZERO))).unwrap();
+    assert_eq!(client.get("/count").dispatch().into_string().unwrap(), "1");
+    assert_eq!(client.get("/count").dispatch().into_string().unwrap(), "1");
+}
+
+#[test]
+fn replayed_cookie_sessions_expire() {
+    let client = Client::untracked(rocket(Sessions::cookie().ttl(Duration::ZERO))).unwrap();
+    let cookie = session_cookie(&client.get("/count").dispatch()).unwrap();
+    let response = client.get("/count").cookie(cookie).dispatch();
+    assert_eq!(response.into_string().unwrap(), "1");
+
+    let client = Client::untracked(rocket(Sessions::cookie())).unwrap();
+    let cookie = session_cookie(&client.get("/count").dispatch()).unwrap();
+    let response = client.get("/count").cookie(cookie).dispatch();
+    assert_eq!(response.into_string().unwrap(), "2");
+}
+
+#[test]
+fn oversized_cookie_sessions_are_not_set() {
+    let client = Client::tracked(rocket(Sessions::cookie())).unwrap();
+    let response = client.post("/big/1024").dispatch();
+    assert!(session_cookie(&response).is_some());
+
+    let response = client.post("/big/4096").dispatch();
+    assert!(session_cookie(&response).is_none());
+}
+
+#[test]
+fn custom_cookie_name() {
+    let client = Client::tracked(rocket(Sessions::new().cookie_name("sid"))).unwrap();
+    let response = client.get("/count").dispatch();
+    assert!(response.cookies().get("sid").is_some());
+    assert!(session_cookie(&response).is_none());
+    assert_eq!(client.get("/count").dispatch().into_string().unwrap(), "2");
+}
+
+struct Failing;
+
+#[rocket::async_trait]
+impl Store for Failing {
+    async fn load(&self, _: &str) -> Result<Option<String>, StoreError> {
+        Err("unavailable".into())
+    }
+
+    async fn save(&self, _: &str, _: &str, _: Duration) -> Result<(), StoreError> {
+        Err("unavailable".into())
+    }
+
+    async fn remove(&self, _: &str) -> Result<(), StoreError> {
+        Err("unavailable".into())
+    }
+}
+
+#[test]
+fn store_failures() {
+    let client = Client::tracked(rocket(Sessions::new().store(Failing))).unwrap();
+
+    // Without a session cookie, the store isn't consulted when loading.
+    assert_eq!(client.get("/count").dispatch().status(), Status::Ok);
+    assert_eq!(client.get("/count").dispatch().status(), Status::InternalServerError);
+}
+
+#[test]
+fn unmanaged() {
+    let rocket = rocket::build().mount("/", routes![get]);
+    let client = Client::debug(rocket).unwrap();
+    assert_eq!(client.get("/").dispatch().status(), Status::InternalServerError);
+}
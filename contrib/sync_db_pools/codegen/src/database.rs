@@ -94,6 +94,14 @@ pub fn database_attr(attr: TokenStream, input: TokenStream) -> Result<TokenStrea
                 <#pool>::pool(&__rocket)
             }
 
+            /// Returns a snapshot of the state of the connection pool backing
+            /// connections of type `Self`, if it has been initialized.
+            pub fn stats<P: #rocket::Phase>(
+                __rocket: &#rocket::Rocket<P>
+            ) -> Option<#root::PoolStats> {
+                <#pool>::pool(&__rocket).map(|__pool| __pool.stats())
+            }
+
             /// Runs the provided function `__f` in an async-safe blocking
             /// thread.
             pub async fn run<F, R>(&self, __f: F) -> R
@@ -120,6 +128,18 @@ pub fn database_attr(attr: TokenStream, input: TokenStream) -> Result<TokenStrea
             }
         }
 
+        impl #root::Database for #guard_type {
+            type Connection = #conn_type;
+
+            fn from_connection(__c: #conn) -> Self {
+                Self(__c)
+            }
+
+            fn connection(&self) -> &#conn {
+                &self.0
+            }
+        }
+
         impl #rocket::Sentinel for #guard_type {
             fn abort(__r: &#rocket::Rocket<#rocket::Ignite>) -> bool {
                 <#conn>::abort(__r)
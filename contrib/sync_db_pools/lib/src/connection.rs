@@ -1,8 +1,11 @@
 use std::marker::PhantomData;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 
-use rocket::{Phase, Rocket, Ignite, Sentinel};
-use rocket::fairing::{AdHoc, Fairing};
+use rocket::{Phase, Rocket, Build, Ignite, Orbit, Sentinel, Response};
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::health::{Health, Check};
 use rocket::request::{Request, Outcome, FromRequest};
 use rocket::outcome::IntoOutcome;
 use rocket::http::Status;
@@ -11,6 +14,10 @@ use rocket::tokio::sync::{OwnedSemaphorePermit, Semaphore, Mutex};
 use rocket::tokio::time::timeout;
 
 use crate::{Config, Poolable, Error};
+use crate::transaction::Transactions;
+
+/// The message of an `r2d2::Error` without an underlying connection error.
+const TIMED_OUT: &str = "timed out waiting for connection";
 
 /// Unstable internal details of generated code for the #[database] attribute.
 ///
@@ -19,9 +26,11 @@ use crate::{Config, Poolable, Error};
 #[doc(hidden)]
 pub struct ConnectionPool<K, C: Poolable> {
     config: Config,
-    // This is an 'Option' so that we can drop the pool in a 'spawn_blocking'.
-    pool: Option<r2d2::Pool<C::Manager>>,
+    // This is an 'Option' so that we can drop the pool in a 'spawn_blocking',
+    // either on shutdown or when the last clone of `self` is dropped.
+    pool: Arc<std::sync::Mutex<Option<r2d2::Pool<C::Manager>>>>,
     semaphore: Arc<Semaphore>,
+    counters: Arc<Counters>,
     _marker: PhantomData<fn() -> K>,
 }
 
@@ -31,11 +40,97 @@ impl<K, C: Poolable> Clone for ConnectionPool<K, C> {
             config: self.config.clone(),
             pool: self.pool.clone(),
             semaphore: self.semaphore.clone(),
+            counters: self.counters.clone(),
             _marker: PhantomData
         }
     }
 }
 
+/// A snapshot of the state of a database connection pool.
+///
+/// Retrieved via the `stats()` method generated by the `#[database]`
+/// attribute:
+///
+/// ```rust
+/// # #[cfg(feature = "diesel_sqlite_pool")] {
+/// use rocket::{Rocket, Orbit};
+/// use rocket_sync_db_pools::{database, diesel};
+///
+/// #[database("my_db")]
+/// struct MyDatabase(diesel::SqliteConnection);
+///
+/// fn report(rocket: &Rocket<Orbit>) {
+///     if let Some(stats) = MyDatabase::stats(rocket) {
+///         println!("{} of {} connections in use", stats.in_use, stats.max_size);
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PoolStats {
+    /// The maximum number of connections in the pool, i.e, `pool_size`.
+    pub max_size: u32,
+    /// The number of connections currently open, both idle and in use.
+    pub connections: u32,
+    /// The number of open connections that are idle.
+    pub idle: u32,
+    /// The number of open connections that are checked out.
+    pub in_use: u32,
+    /// The total number of connections successfully retrieved.
+    pub acquired: u64,
+    /// The total number of connection retrievals that timed out while the
+    /// pool was healthy, i.e, because every connection was in use.
+    pub timeouts: u64,
+    /// The total number of connection retrievals that failed because the
+    /// pool was unable to open a connection to the database.
+    pub errors: u64,
+    /// The cumulative time spent waiting for connections to be retrieved,
+    /// including retrievals that timed out.
+    pub wait_time: Duration,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    acquired: AtomicU64,
+    timeouts: AtomicU64,
+    errors: AtomicU64,
+    wait_nanos: AtomicU64,
+}
+
+/// The outcome of a connection retrieval.
+enum Retrieval {
+    Acquired,
+    TimedOut,
+    Failed,
+}
+
+impl<C> From<&Result<C, r2d2::Error>> for Retrieval {
+    fn from(result: &Result<C, r2d2::Error>) -> Self {
+        // `r2d2` reports every failure as a timeout but includes the error
+        // that prevented it from opening a connection, if there was one.
+        match result {
+            Ok(_) => Retrieval::Acquired,
+            Err(e) if e.to_string() == TIMED_OUT => Retrieval::TimedOut,
+            Err(_) => Retrieval::Failed,
+        }
+    }
+}
+
+impl Counters {
+    fn record(&self, start: Instant, retrieval: Retrieval) {
+        let counter = match retrieval {
+            Retrieval::Acquired => &self.acquired,
+            Retrieval::TimedOut => &self.timeouts,
+            Retrieval::Failed => &self.errors,
+        };
+
+        let nanos = u64::try_from(start.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        counter.fetch_add(1, Ordering::Relaxed);
+        self.wait_nanos.fetch_add(nanos, Ordering::Relaxed);
+    }
+}
+
 /// Unstable internal details of generated code for the #[database] attribute.
 ///
 /// This type is implemented here instead of in generated code to ensure all
@@ -68,45 +163,110 @@ macro_rules! dberr {
     });
 }
 
+/// The fairing returned by `ConnectionPool::fairing()`.
+///
+/// Initializes the pool on ignition, finishes any transactions started via the
+/// `Transaction` guard once a response has been generated, and drains the pool
+/// on shutdown.
+struct PoolFairing<K, C> {
+    name: &'static str,
+    db: &'static str,
+    _marker: PhantomData<fn() -> (K, C)>,
+}
+
+#[rocket::async_trait]
+impl<K: 'static, C: Poolable> Fairing for PoolFairing<K, C> {
+    fn info(&self) -> Info {
+        Info { name: self.name, kind: Kind::Ignite | Kind::Response | Kind::Shutdown }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let db = self.db;
+        let grace = rocket.figment()
+            .extract_inner::<u32>("shutdown.grace")
+            .unwrap_or_else(|_| rocket::Config::default().shutdown.grace);
+
+        let rocket = run_blocking(move || {
+            let config = match Config::from(db, &rocket) {
+                Ok(config) => config,
+                Err(e) => dberr!("config", db, "{}", e, rocket),
+            };
+
+            if config.shutdown_timeout > u64::from(grace) {
+                rocket::warn!("database pool `{}` shutdown timeout ({}s) exceeds the \
+                    shutdown grace period ({}s)", db, config.shutdown_timeout, grace);
+                warn_!("connections are awaited on shutdown only for the grace period");
+            }
+
+            let pool_size = config.pool_size;
+            match C::pool(db, &rocket) {
+                Ok(pool) => Ok(rocket.manage(ConnectionPool::<K, C> {
+                    config,
+                    pool: Arc::new(std::sync::Mutex::new(Some(pool))),
+                    semaphore: Arc::new(Semaphore::new(pool_size as usize)),
+                    counters: Arc::new(Counters::default()),
+                    _marker: PhantomData,
+                })),
+                Err(Error::Config(e)) => dberr!("config", db, "{}", e, rocket),
+                Err(Error::Pool(e)) => dberr!("pool init", db, "{}", e, rocket),
+                Err(Error::Custom(e)) => dberr!("pool manager", db, "{:?}", e, rocket),
+            }
+        }).await?;
+
+        let check = Check::readiness(db, |rocket| Box::pin(async move {
+            let pool = ConnectionPool::<K, C>::pool(rocket).ok_or("database is not initialized")?;
+            pool.get().await.map(drop).ok_or_else(|| "failed to get a connection".into())
+        }));
+
+        Ok(Health::register(rocket, check))
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let commit = res.status().code < 400;
+        if !Transactions::<K>::finish(req, commit).await {
+            res.set_status(Status::InternalServerError);
+        }
+    }
+
+    async fn on_shutdown(&self, rocket: &Rocket<Orbit>) {
+        if let Some(pool) = ConnectionPool::<K, C>::pool(rocket) {
+            pool.shutdown().await;
+        }
+    }
+}
+
 impl<K: 'static, C: Poolable> ConnectionPool<K, C> {
     pub fn fairing(fairing_name: &'static str, db: &'static str) -> impl Fairing {
-        AdHoc::try_on_ignite(fairing_name, move |rocket| async move {
-            run_blocking(move || {
-                let config = match Config::from(db, &rocket) {
-                    Ok(config) => config,
-                    Err(e) => dberr!("config", db, "{}", e, rocket),
-                };
-
-                let pool_size = config.pool_size;
-                match C::pool(db, &rocket) {
-                    Ok(pool) => Ok(rocket.manage(ConnectionPool::<K, C> {
-                        config,
-                        pool: Some(pool),
-                        semaphore: Arc::new(Semaphore::new(pool_size as usize)),
-                        _marker: PhantomData,
-                    })),
-                    Err(Error::Config(e)) => dberr!("config", db, "{}", e, rocket),
-                    Err(Error::Pool(e)) => dberr!("pool init", db, "{}", e, rocket),
-                    Err(Error::Custom(e)) => dberr!("pool manager", db, "{:?}", e, rocket),
-                }
-            }).await
-        })
+        PoolFairing::<K, C> { name: fairing_name, db, _marker: PhantomData }
     }
 
     pub async fn get(&self) -> Option<Connection<K, C>> {
-        let duration = std::time::Duration::from_secs(self.config.timeout as u64);
+        let start = Instant::now();
+        let duration = Duration::from_secs(self.config.timeout as u64);
         let permit = match timeout(duration, self.semaphore.clone().acquire_owned()).await {
-            Ok(p) => p.expect("internal invariant broken: semaphore should not be closed"),
+            Ok(Ok(permit)) => permit,
+            Ok(Err(_)) => {
+                error_!("database connection pool has been shut down");
+                return None;
+            }
             Err(_) => {
                 error_!("database connection retrieval timed out");
+                self.counters.record(start, Retrieval::TimedOut);
                 return None;
             }
         };
 
-        let pool = self.pool.as_ref().cloned()
-            .expect("internal invariant broken: self.pool is Some");
+        let pool = match self.r2d2_pool() {
+            Some(pool) => pool,
+            None => {
+                error_!("database connection pool has been shut down");
+                return None;
+            }
+        };
 
-        match run_blocking(move || pool.get_timeout(duration)).await {
+        let result = run_blocking(move || pool.get_timeout(duration)).await;
+        self.counters.record(start, Retrieval::from(&result));
+        match result {
             Ok(c) => Some(Connection {
                 connection: Arc::new(Mutex::new(Some(c))),
                 permit: Some(permit),
@@ -119,6 +279,49 @@ impl<K: 'static, C: Poolable> ConnectionPool<K, C> {
         }
     }
 
+    pub fn stats(&self) -> PoolStats {
+        let (connections, idle) = match self.r2d2_pool() {
+            Some(pool) => (pool.state().connections, pool.state().idle_connections),
+            None => (0, 0),
+        };
+
+        PoolStats {
+            max_size: self.config.pool_size,
+            connections,
+            idle,
+            in_use: connections.saturating_sub(idle),
+            acquired: self.counters.acquired.load(Ordering::Relaxed),
+            timeouts: self.counters.timeouts.load(Ordering::Relaxed),
+            errors: self.counters.errors.load(Ordering::Relaxed),
+            wait_time: Duration::from_nanos(self.counters.wait_nanos.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// Stops handing out new connections, waits up to `shutdown_timeout`
+    /// seconds for checked out connections to be returned, and then closes
+    /// all of the idle connections in the pool.
+    pub async fn shutdown(&self) {
+        // Since the semaphore is fair, acquiring every permit both waits for
+        // outstanding connections and blocks any new checkouts until closed.
+        let duration = Duration::from_secs(self.config.shutdown_timeout);
+        let permits = self.semaphore.acquire_many(self.config.pool_size);
+        let permits = timeout(duration, permits).await;
+        if permits.is_err() {
+            warn_!("database connections were not returned within {}s",
+                self.config.shutdown_timeout);
+        }
+
+        // Close before releasing the permits so no waiter can acquire them.
+        self.semaphore.close();
+        drop(permits);
+        let pool = self.pool.lock().expect("pool lock poisoned").take();
+        run_blocking(move || drop(pool)).await;
+    }
+
+    fn r2d2_pool(&self) -> Option<r2d2::Pool<C::Manager>> {
+        self.pool.lock().expect("pool lock poisoned").clone()
+    }
+
     #[inline]
     pub async fn get_one<P: Phase>(rocket: &Rocket<P>) -> Option<Connection<K, C>> {
         match Self::pool(rocket) {
@@ -194,8 +397,10 @@ impl<K, C: Poolable> Drop for Connection<K, C> {
 
 impl<K, C: Poolable> Drop for ConnectionPool<K, C> {
     fn drop(&mut self) {
-        let pool = self.pool.take();
-        tokio::task::spawn_blocking(move || drop(pool));
+        if let Some(pool) = Arc::get_mut(&mut self.pool) {
+            let pool = pool.get_mut().ok().and_then(|pool| pool.take());
+            tokio::task::spawn_blocking(move || drop(pool));
+        }
     }
 }
 
@@ -119,6 +119,11 @@
 //! url = "db.sqlite"
 //! pool_size = 20
 //! timeout = 5
+//!
+//! # Run statements on every new connection:
+//! [global.databases.other_sqlite_db]
+//! url = "other.sqlite"
+//! init_sql = ["PRAGMA cache_size = -16000", "PRAGMA temp_store = MEMORY"]
 //! ```
 //!
 //! The table _requires_ one key:
@@ -131,8 +136,41 @@
 //!     pool (defaults to the configured number of workers * 4)
 //!   * `timeout` - max number of seconds to wait for a connection to become
 //!     available (defaults to `5`)
+//!   * `init_sql` - an array of SQL statements to execute, in order, on every
+//!     new connection (defaults to `[]`); ignored by non-SQL adapters
+//!   * `min_idle` - the minimum number of idle connections to maintain
+//!     (defaults to `pool_size`)
+//!   * `max_lifetime` - max number of seconds a connection may live before it
+//!     is closed (defaults to `r2d2`'s default of 30 minutes)
+//!   * `idle_timeout` - max number of seconds a connection may sit idle before
+//!     it is closed (defaults to `r2d2`'s default of 10 minutes)
+//!   * `test_on_check_out` - whether to validate a connection before handing
+//!     it out (defaults to `true`)
+//!   * `shutdown_timeout` - max number of seconds to wait on shutdown for
+//!     checked out connections to be returned (defaults to `2`, the default
+//!     shutdown grace period)
+//!
+//! Additional options may be required or supported by other adapters. For
+//! instance, when the `sqlcipher_pool` feature is enabled, `rusqlite`
+//! databases accept a `key` which is used to unlock the database on every new
+//! connection. As with any other configuration value, the key can be provided
+//! via an environment variable instead of being written down in source or in
+//! `Rocket.toml`:
+//!
+//! ```sh
+//! ROCKET_DATABASES='{secure_db={url="secure.sqlite",key="hunter2"}}'
+//! ```
+//!
+//! The URL and key can also be resolved from a secret store at ignition by
+//! writing them as [secret references]:
+//!
+//! ```toml
+//! [release.databases.secure_db]
+//! url = "secure.sqlite"
+//! key = { secret = "file:/run/secrets/secure_db_key" }
+//! ```
 //!
-//! Additional options may be required or supported by other adapters.
+//! [secret references]: rocket::config::SecretProvider
 //!
 //! ### Procedurally
 //!
@@ -198,6 +236,10 @@
 //!     database operations in an `async`-safe manner.
 //!   * A [`pool()`](example::ExampleDb::pool()) method to retrieve the
 //!     backing connection pool.
+//!   * A [`stats()`](example::ExampleDb::stats()) method to retrieve
+//!     [`PoolStats`] describing the state of the backing connection pool.
+//!   * A [`Database`] implementation, which allows the decorated type to be
+//!     used with guards like [`Transaction`].
 //!
 //! The attribute can only be applied to tuple structs with one field. The
 //! internal type of the structure must implement [`Poolable`].
@@ -300,6 +342,35 @@
 //! # }
 //! ```
 //!
+//! To run all of a handler's database operations in a single transaction, use
+//! [`Transaction`] as the request guard instead. The transaction is committed
+//! if the handler's response has a non-error status and rolled back otherwise:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! # #[macro_use] extern crate rocket_sync_db_pools;
+//! #
+//! # #[cfg(feature = "diesel_sqlite_pool")]
+//! # mod test {
+//! # use rocket_sync_db_pools::diesel;
+//! # type Data = ();
+//! use rocket_sync_db_pools::Transaction;
+//!
+//! #[database("my_db")]
+//! struct MyDatabase(diesel::SqliteConnection);
+//!
+//! fn store_in_db(conn: &mut diesel::SqliteConnection) -> Data {
+//!     // Do something with connection, return some data.
+//!     # ()
+//! }
+//!
+//! #[post("/")]
+//! async fn my_handler(conn: Transaction<MyDatabase>) -> Data {
+//!     conn.run(|c| store_in_db(c)).await
+//! }
+//! # }
+//! ```
+//!
 //! # Database Support
 //!
 //! Built-in support is provided for many popular databases and drivers. Support
@@ -318,6 +389,7 @@
 //! | MySQL    | [Diesel]              | `2`       | [`diesel::MysqlConnection`]    | `diesel_mysql_pool`    |
 //! | Postgres | [Rust-Postgres]       | `0.19`    | [`postgres::Client`]           | `postgres_pool`        |
 //! | Sqlite   | [`Rusqlite`]          | `0.27`    | [`rusqlite::Connection`]       | `sqlite_pool`          |
+//! | SQLCipher | [`Rusqlite`]         | `0.27`    | [`rusqlite::Connection`]       | `sqlcipher_pool`       |
 //! | Memcache | [`memcache`]          | `0.15`    | [`memcache::Client`]           | `memcache_pool`        |
 //!
 //! [Diesel]: https://diesel.rs
@@ -380,10 +452,12 @@ mod poolable;
 mod config;
 mod error;
 mod connection;
+mod transaction;
 
 pub use self::poolable::{Poolable, PoolResult};
 pub use self::config::Config;
 pub use self::error::Error;
+pub use self::transaction::{Database, Transactional, Transaction};
 
 pub use rocket_sync_db_pools_codegen::*;
 pub use self::connection::*;
@@ -495,6 +569,19 @@ pub mod example {
             )
         }
 
+        /// Returns a snapshot of the state of the connection pool backing
+        /// connections of type `Self`, if it has been initialized. Returns
+        /// `None` if `Self::fairing()` has not been attached and ignited.
+        ///
+        /// See [`PoolStats`](crate::PoolStats) for details on the values
+        /// reported.
+        pub fn stats<P: crate::rocket::Phase>(
+            __rocket: &crate::rocket::Rocket<P>,
+        ) -> Option<crate::PoolStats> {
+            <crate::ConnectionPool<Self, diesel::SqliteConnection>>::pool(&__rocket)
+                .map(|__pool| __pool.stats())
+        }
+
         /// Runs the provided function `__f` in an async-safe blocking thread.
         /// The function is supplied with a mutable reference to the raw
         /// connection (a value of type `&mut Self.0`). `.await`ing the return
@@ -593,4 +680,18 @@ pub mod example {
             <crate::Connection<Self, diesel::SqliteConnection>>::abort(__r)
         }
     }
+
+    impl crate::Database for ExampleDb {
+        type Connection = diesel::SqliteConnection;
+
+        fn from_connection(
+            __c: crate::Connection<Self, diesel::SqliteConnection>,
+        ) -> Self {
+            Self(__c)
+        }
+
+        fn connection(&self) -> &crate::Connection<Self, diesel::SqliteConnection> {
+            &self.0
+        }
+    }
 }
@@ -1,5 +1,3 @@
-use std::time::Duration;
-
 use r2d2::ManageConnection;
 use rocket::{Rocket, Build};
 
@@ -65,9 +63,8 @@ use crate::{Config, Error};
 /// #          fn has_broken(&self, _: &mut Connection) -> bool { panic!() }
 /// #     }
 /// # }
-/// use std::time::Duration;
 /// use rocket::{Rocket, Build};
-/// use rocket_sync_db_pools::{r2d2, Error, Config, Poolable, PoolResult};
+/// use rocket_sync_db_pools::{Error, Config, Poolable, PoolResult};
 ///
 /// impl Poolable for foo::Connection {
 ///     type Manager = foo::ConnectionManager;
@@ -76,17 +73,15 @@ use crate::{Config, Error};
 ///     fn pool(db_name: &str, rocket: &Rocket<Build>) -> PoolResult<Self> {
 ///         let config = Config::from(db_name, rocket)?;
 ///         let manager = foo::ConnectionManager::new(&config.url).map_err(Error::Custom)?;
-///         Ok(r2d2::Pool::builder()
-///             .max_size(config.pool_size)
-///             .connection_timeout(Duration::from_secs(config.timeout as u64))
-///             .build(manager)?)
+///         Ok(config.pool_builder().build(manager)?)
 ///     }
 /// }
 /// ```
 ///
 /// In this example, `ConnectionManager::new()` method returns a `foo::Error` on
 /// failure. The [`Error`] enum consolidates this type, the `r2d2::Error` type
-/// that can result from `r2d2::Pool::builder()`, and the
+/// that can result from building the pool with [`Config::pool_builder()`],
+/// and the
 /// [`figment::Error`](rocket::figment::Error) type from
 /// `database::Config::from()`.
 ///
@@ -113,36 +108,52 @@ pub trait Poolable: Send + Sized + 'static {
 #[allow(type_alias_bounds)]
 pub type PoolResult<P: Poolable> = Result<r2d2::Pool<P::Manager>, Error<P::Error>>;
 
+/// Connection customizer that runs the configured `init_sql` statements on
+/// every new diesel connection.
+#[cfg(any(
+    feature = "diesel_sqlite_pool",
+    feature = "diesel_postgres_pool",
+    feature = "diesel_mysql_pool"
+))]
+#[derive(Debug)]
+struct DieselInitSql(Vec<String>);
+
+#[cfg(any(
+    feature = "diesel_sqlite_pool",
+    feature = "diesel_postgres_pool",
+    feature = "diesel_mysql_pool"
+))]
+impl<C> diesel::r2d2::CustomizeConnection<C, diesel::r2d2::Error> for DieselInitSql
+    where C: diesel::connection::SimpleConnection
+{
+    fn on_acquire(&self, conn: &mut C) -> Result<(), diesel::r2d2::Error> {
+        for sql in &self.0 {
+            conn.batch_execute(sql).map_err(diesel::r2d2::Error::QueryError)?;
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(feature = "diesel_sqlite_pool")]
 impl Poolable for diesel::SqliteConnection {
     type Manager = diesel::r2d2::ConnectionManager<diesel::SqliteConnection>;
     type Error = std::convert::Infallible;
 
     fn pool(db_name: &str, rocket: &Rocket<Build>) -> PoolResult<Self> {
-        use diesel::{SqliteConnection, connection::SimpleConnection};
-        use diesel::r2d2::{CustomizeConnection, ConnectionManager, Error, Pool};
-
-        #[derive(Debug)]
-        struct Customizer;
+        let config = Config::from(db_name, rocket)?;
+        let manager = diesel::r2d2::ConnectionManager::new(&config.url);
+        let builder = config.pool_builder();
 
-        impl CustomizeConnection<SqliteConnection, Error> for Customizer {
-            fn on_acquire(&self, conn: &mut SqliteConnection) -> Result<(), Error> {
-                conn.batch_execute("\
-                    PRAGMA journal_mode = WAL;\
-                    PRAGMA busy_timeout = 1000;\
-                    PRAGMA foreign_keys = ON;\
-                ").map_err(Error::QueryError)?;
+        let mut init_sql = vec![String::from("\
+            PRAGMA journal_mode = WAL;\
+            PRAGMA busy_timeout = 1000;\
+            PRAGMA foreign_keys = ON;\
+        ")];
 
-                Ok(())
-            }
-        }
-
-        let config = Config::from(db_name, rocket)?;
-        let manager = ConnectionManager::new(&config.url);
-        let pool = Pool::builder()
-            .connection_customizer(Box::new(Customizer))
-            .max_size(config.pool_size)
-            .connection_timeout(Duration::from_secs(config.timeout as u64))
+        init_sql.extend(config.init_sql);
+        let pool = builder
+            .connection_customizer(Box::new(DieselInitSql(init_sql)))
             .build(manager)?;
 
         Ok(pool)
@@ -157,9 +168,8 @@ impl Poolable for diesel::PgConnection {
     fn pool(db_name: &str, rocket: &Rocket<Build>) -> PoolResult<Self> {
         let config = Config::from(db_name, rocket)?;
         let manager = diesel::r2d2::ConnectionManager::new(&config.url);
-        let pool = r2d2::Pool::builder()
-            .max_size(config.pool_size)
-            .connection_timeout(Duration::from_secs(config.timeout as u64))
+        let pool = config.pool_builder()
+            .connection_customizer(Box::new(DieselInitSql(config.init_sql)))
             .build(manager)?;
 
         Ok(pool)
@@ -174,9 +184,8 @@ impl Poolable for diesel::MysqlConnection {
     fn pool(db_name: &str, rocket: &Rocket<Build>) -> PoolResult<Self> {
         let config = Config::from(db_name, rocket)?;
         let manager = diesel::r2d2::ConnectionManager::new(&config.url);
-        let pool = r2d2::Pool::builder()
-            .max_size(config.pool_size)
-            .connection_timeout(Duration::from_secs(config.timeout as u64))
+        let pool = config.pool_builder()
+            .connection_customizer(Box::new(DieselInitSql(config.init_sql)))
             .build(manager)?;
 
         Ok(pool)
@@ -190,12 +199,24 @@ impl Poolable for postgres::Client {
     type Error = postgres::Error;
 
     fn pool(db_name: &str, rocket: &Rocket<Build>) -> PoolResult<Self> {
+        #[derive(Debug)]
+        struct Customizer(Vec<String>);
+
+        impl r2d2::CustomizeConnection<postgres::Client, postgres::Error> for Customizer {
+            fn on_acquire(&self, conn: &mut postgres::Client) -> Result<(), postgres::Error> {
+                for sql in &self.0 {
+                    conn.batch_execute(sql)?;
+                }
+
+                Ok(())
+            }
+        }
+
         let config = Config::from(db_name, rocket)?;
         let url = config.url.parse().map_err(Error::Custom)?;
         let manager = r2d2_postgres::PostgresConnectionManager::new(url, postgres::tls::NoTls);
-        let pool = r2d2::Pool::builder()
-            .max_size(config.pool_size)
-            .connection_timeout(Duration::from_secs(config.timeout as u64))
+        let pool = config.pool_builder()
+            .connection_customizer(Box::new(Customizer(config.init_sql)))
             .build(manager)?;
 
         Ok(pool)
@@ -227,6 +248,15 @@ impl Poolable for rusqlite::Connection {
 
         let figment = Config::figment(db_name, rocket);
         let config: Config = figment.extract()?;
+
+        #[cfg(feature = "sqlcipher_pool")]
+        let key = {
+            #[derive(serde::Deserialize)]
+            struct Cipher { key: Option<String> }
+
+            figment.extract::<Cipher>()?.key
+        };
+
         let open_flags: Vec<OpenFlag> = figment
             .join(Serialized::default("open_flags", <Vec<OpenFlag>>::new()))
             .extract_inner("open_flags")?;
@@ -249,13 +279,21 @@ impl Poolable for rusqlite::Connection {
             flags.insert(sql_flag)
         };
 
+        let builder = config.pool_builder();
+        let init_sql = config.init_sql;
         let manager = r2d2_sqlite::SqliteConnectionManager::file(&*config.url)
-            .with_flags(flags);
+            .with_flags(flags)
+            .with_init(move |conn| {
+                // With SQLCipher, the key must be set before any other statement.
+                #[cfg(feature = "sqlcipher_pool")]
+                if let Some(key) = &key {
+                    conn.pragma_update(None, "key", key)?;
+                }
 
-        let pool = r2d2::Pool::builder()
-            .max_size(config.pool_size)
-            .connection_timeout(Duration::from_secs(config.timeout as u64))
-            .build(manager)?;
+                init_sql.iter().try_for_each(|sql| conn.execute_batch(sql))
+            });
+
+        let pool = builder.build(manager)?;
 
         Ok(pool)
     }
@@ -270,10 +308,7 @@ impl Poolable for memcache::Client {
     fn pool(db_name: &str, rocket: &Rocket<Build>) -> PoolResult<Self> {
         let config = Config::from(db_name, rocket)?;
         let manager = r2d2_memcache::MemcacheConnectionManager::new(&*config.url);
-        let pool = r2d2::Pool::builder()
-            .max_size(config.pool_size)
-            .connection_timeout(Duration::from_secs(config.timeout as u64))
-            .build(manager)?;
+        let pool = config.pool_builder().build(manager)?;
 
         Ok(pool)
     }
@@ -0,0 +1,224 @@
+use std::fmt;
+use std::ops::Deref;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+use rocket::{Rocket, Ignite, Sentinel};
+use rocket::futures::future::BoxFuture;
+use rocket::request::{Request, Outcome, FromRequest};
+use rocket::outcome::try_outcome;
+use rocket::http::Status;
+
+use crate::{Connection, Poolable};
+
+/// Trait implemented by types generated with the `#[database]` attribute.
+///
+/// This trait is implemented automatically and should not be implemented
+/// manually. It ties a database type to its [`Poolable`] connection type and is
+/// used by guards like [`Transaction`] that wrap database types.
+pub trait Database: Sized + Send + Sync + 'static {
+    /// The [`Poolable`] type of the connection, i.e, `C` in `struct Db(C)`.
+    type Connection: Poolable;
+
+    #[doc(hidden)]
+    fn from_connection(connection: Connection<Self, Self::Connection>) -> Self;
+
+    #[doc(hidden)]
+    fn connection(&self) -> &Connection<Self, Self::Connection>;
+}
+
+/// Trait implemented by [`Poolable`] connections that support transactions.
+///
+/// Implementations are provided for all of the SQL [`Poolable`] types in this
+/// crate. Implementing `Transactional` for a custom [`Poolable`] type allows it
+/// to be used with the [`Transaction`] request guard.
+pub trait Transactional: Poolable {
+    /// The error type returned if beginning or ending a transaction fails.
+    type TransactionError: fmt::Display + Send + 'static;
+
+    /// Begins a new transaction.
+    fn begin(&mut self) -> Result<(), Self::TransactionError>;
+
+    /// Commits the transaction started by [`Transactional::begin()`].
+    fn commit(&mut self) -> Result<(), Self::TransactionError>;
+
+    /// Rolls back the transaction started by [`Transactional::begin()`].
+    fn rollback(&mut self) -> Result<(), Self::TransactionError>;
+}
+
+/// A request guard that wraps a database connection in a transaction.
+///
+/// A transaction is begun when the guard is retrieved. Once the response has
+/// been generated, the transaction is committed if the response status is not
+/// an error status (`< 400`) and rolled back otherwise. Since Rocket responds
+/// to a panicking handler with a `500 Internal Server Error`, a panic also
+/// results in a rollback. If committing fails, the response status is changed
+/// to `500 Internal Server Error`.
+///
+/// `Transaction<Db>` dereferences to `Db`, so the connection is used exactly
+/// as it would be without a transaction:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # #[cfg(feature = "diesel_sqlite_pool")] {
+/// use rocket_sync_db_pools::{database, diesel, Transaction};
+/// use diesel::connection::SimpleConnection;
+///
+/// #[database("my_db")]
+/// struct Db(diesel::SqliteConnection);
+///
+/// #[post("/")]
+/// async fn create(conn: Transaction<Db>) -> Result<(), String> {
+///     conn.run(|c| c.batch_execute("INSERT INTO logs VALUES (1)")).await
+///         .map_err(|e| e.to_string())?;
+///
+///     // If this fails, the insertion above is rolled back.
+///     conn.run(|c| c.batch_execute("INSERT INTO logs VALUES (2)")).await
+///         .map_err(|e| e.to_string())
+/// }
+/// # }
+/// ```
+///
+/// The database's `fairing()` must be attached for the guard to succeed.
+pub struct Transaction<D: Database>(Arc<D>);
+
+type Finisher = Box<dyn FnOnce(bool) -> BoxFuture<'static, bool> + Send>;
+
+/// Request-local list of transactions to finish for the database `K`.
+pub(crate) struct Transactions<K>(Mutex<Vec<Finisher>>, PhantomData<fn() -> K>);
+
+impl<K> Default for Transactions<K> {
+    fn default() -> Self {
+        Transactions(Mutex::new(vec![]), PhantomData)
+    }
+}
+
+impl<K: 'static> Transactions<K> {
+    /// Commits (if `commit`) or rolls back all of the transactions started in
+    /// `req`. Returns `false` if any of them failed to finish.
+    pub(crate) async fn finish(req: &Request<'_>, commit: bool) -> bool {
+        let finishers = std::mem::take(&mut *req.local_cache(Self::default).0.lock()
+            .expect("transaction list lock poisoned"));
+
+        let mut success = true;
+        for finisher in finishers {
+            success &= finisher(commit).await;
+        }
+
+        success
+    }
+}
+
+#[rocket::async_trait]
+impl<'r, D: Database> FromRequest<'r> for Transaction<D>
+    where D::Connection: Transactional
+{
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, ()> {
+        let conn = try_outcome!(<Connection<D, D::Connection>>::from_request(req).await);
+        let db = Arc::new(D::from_connection(conn));
+        if let Err(e) = db.connection().run(|c| c.begin()).await {
+            error_!("failed to begin database transaction: {}", e);
+            return Outcome::Failure((Status::InternalServerError, ()));
+        }
+
+        let tx = db.clone();
+        let finisher: Finisher = Box::new(move |commit| Box::pin(async move {
+            let (action, result) = match commit {
+                true => ("commit", tx.connection().run(|c| c.commit()).await),
+                false => ("roll back", tx.connection().run(|c| c.rollback()).await),
+            };
+
+            if let Err(e) = &result {
+                error_!("failed to {} database transaction: {}", action, e);
+            }
+
+            result.is_ok()
+        }));
+
+        req.local_cache(Transactions::<D>::default).0.lock()
+            .expect("transaction list lock poisoned")
+            .push(finisher);
+
+        Outcome::Success(Transaction(db))
+    }
+}
+
+impl<D: Database> Deref for Transaction<D> {
+    type Target = D;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<D: Database> Sentinel for Transaction<D> {
+    fn abort(rocket: &Rocket<Ignite>) -> bool {
+        <Connection<D, D::Connection>>::abort(rocket)
+    }
+}
+
+macro_rules! impl_diesel_transactional {
+    ($($feature:literal => $conn:ty),* $(,)?) => ($(
+        #[cfg(feature = $feature)]
+        impl Transactional for $conn {
+            type TransactionError = diesel::result::Error;
+
+            fn begin(&mut self) -> Result<(), Self::TransactionError> {
+                use diesel::connection::{Connection, TransactionManager};
+                <Self as Connection>::TransactionManager::begin_transaction(self)
+            }
+
+            fn commit(&mut self) -> Result<(), Self::TransactionError> {
+                use diesel::connection::{Connection, TransactionManager};
+                <Self as Connection>::TransactionManager::commit_transaction(self)
+            }
+
+            fn rollback(&mut self) -> Result<(), Self::TransactionError> {
+                use diesel::connection::{Connection, TransactionManager};
+                <Self as Connection>::TransactionManager::rollback_transaction(self)
+            }
+        }
+    )*)
+}
+
+impl_diesel_transactional! {
+    "diesel_sqlite_pool" => diesel::SqliteConnection,
+    "diesel_postgres_pool" => diesel::PgConnection,
+    "diesel_mysql_pool" => diesel::MysqlConnection,
+}
+
+#[cfg(feature = "postgres_pool")]
+impl Transactional for postgres::Client {
+    type TransactionError = postgres::Error;
+
+    fn begin(&mut self) -> Result<(), Self::TransactionError> {
+        self.batch_execute("BEGIN")
+    }
+
+    fn commit(&mut self) -> Result<(), Self::TransactionError> {
+        self.batch_execute("COMMIT")
+    }
+
+    fn rollback(&mut self) -> Result<(), Self::TransactionError> {
+        self.batch_execute("ROLLBACK")
+    }
+}
+
+#[cfg(feature = "sqlite_pool")]
+impl Transactional for rusqlite::Connection {
+    type TransactionError = rusqlite::Error;
+
+    fn begin(&mut self) -> Result<(), Self::TransactionError> {
+        self.execute_batch("BEGIN")
+    }
+
+    fn commit(&mut self) -> Result<(), Self::TransactionError> {
+        self.execute_batch("COMMIT")
+    }
+
+    fn rollback(&mut self) -> Result<(), Self::TransactionError> {
+        self.execute_batch("ROLLBACK")
+    }
+}
@@ -55,6 +55,307 @@ mod rusqlite_integration_test {
     }
 }
 
+#[cfg(feature = "sqlite_pool")]
+mod init_sql_test {
+    use rocket::figment::Figment;
+    use rocket_sync_db_pools::{rusqlite, database};
+
+    #[database("init_db")]
+    struct InitDb(rusqlite::Connection);
+
+    #[rocket::async_test]
+    async fn test_init_sql_runs_on_connect() {
+        let init_sql = ["PRAGMA user_version = 7", "CREATE TABLE t (x INTEGER)"];
+        let config = Figment::from(rocket::Config::debug_default())
+            .merge(("databases.init_db.url", ":memory:"))
+            .merge(("databases.init_db.init_sql", init_sql));
+
+        let rocket = rocket::custom(config)
+            .attach(InitDb::fairing())
+            .ignite()
+            .await
+            .unwrap();
+
+        let conn = InitDb::get_one(&rocket).await.expect("connection");
+        let (version, tables) = conn.run(|c| {
+            let version: i64 = c.query_row("PRAGMA user_version", [], |r| r.get(0))?;
+            let tables: i64 = c.query_row(
+                "SELECT count(*) FROM sqlite_master WHERE name = 't'", [], |r| r.get(0)
+            )?;
+
+            Ok::<_, rusqlite::Error>((version, tables))
+        }).await.unwrap();
+
+        assert_eq!(version, 7);
+        assert_eq!(tables, 1);
+    }
+}
+
+#[cfg(feature = "sqlite_pool")]
+mod pool_stats_test {
+    use rocket::figment::Figment;
+    use rocket_sync_db_pools::{rusqlite, database};
+
+    #[database("stats_db")]
+    struct StatsDb(rusqlite::Connection);
+
+    #[rocket::async_test]
+    async fn test_pool_stats() {
+        let config = Figment::from(rocket::Config::debug_default())
+            .merge(("databases.stats_db.url", ":memory:"))
+            .merge(("databases.stats_db.pool_size", 2))
+            .merge(("databases.stats_db.timeout", 1));
+
+        let rocket = rocket::custom(config).attach(StatsDb::fairing());
+        assert!(StatsDb::stats(&rocket).is_none());
+
+        let rocket = rocket.ignite().await.unwrap();
+        let stats = StatsDb::stats(&rocket).unwrap();
+        assert_eq!(stats.max_size, 2);
+        assert_eq!(stats.in_use, 0);
+        assert_eq!(stats.acquired, 0);
+
+        let a = StatsDb::get_one(&rocket).await.expect("connection");
+        let b = StatsDb::get_one(&rocket).await.expect("connection");
+        let stats = StatsDb::stats(&rocket).unwrap();
+        assert_eq!(stats.connections, 2);
+        assert_eq!(stats.in_use, 2);
+        assert_eq!(stats.idle, 0);
+        assert_eq!(stats.acquired, 2);
+
+        assert!(StatsDb::get_one(&rocket).await.is_none());
+        let stats = StatsDb::stats(&rocket).unwrap();
+        assert_eq!(stats.timeouts, 1);
+        assert_eq!(stats.errors, 0);
+        assert!(stats.wait_time >= std::time::Duration::from_secs(1));
+        drop((a, b));
+    }
+}
+
+#[cfg(feature = "sqlite_pool")]
+mod pool_tuning_test {
+    use rocket::figment::Figment;
+    use rocket_sync_db_pools::{rusqlite, database, Config};
+
+    #[database("tuned_db")]
+    struct TunedDb(pub rusqlite::Connection);
+
+    #[rocket::async_test]
+    async fn test_pool_tuning_knobs() {
+        let config = Figment::from(rocket::Config::debug_default())
+            .merge(("databases.tuned_db.url", ":memory:"))
+            .merge(("databases.tuned_db.pool_size", 4))
+            .merge(("databases.tuned_db.min_idle", 0))
+            .merge(("databases.tuned_db.max_lifetime", 60))
+            .merge(("databases.tuned_db.test_on_check_out", false));
+
+        let rocket = rocket::custom(config).attach(TunedDb::fairing());
+        let db_config = Config::from("tuned_db", &rocket).unwrap();
+        assert_eq!(db_config.min_idle, Some(0));
+        assert_eq!(db_config.max_lifetime, Some(60));
+        assert_eq!(db_config.idle_timeout, None);
+        assert!(!db_config.test_on_check_out);
+
+        let rocket = rocket.ignite().await.unwrap();
+        let stats = TunedDb::stats(&rocket).unwrap();
+        assert_eq!(stats.connections, 0);
+
+        let conn = TunedDb::get_one(&rocket).await.expect("connection");
+        conn.run(|c| c.execute_batch("SELECT 1")).await.unwrap();
+        let stats = TunedDb::stats(&rocket).unwrap();
+        assert_eq!(stats.in_use, 1);
+    }
+
+    #[test]
+    fn test_pool_tuning_defaults() {
+        let figment = Figment::from(rocket::Config::debug_default())
+            .merge(("databases.tuned_db.url", ":memory:"));
+
+        let config = Config::from("tuned_db", &rocket::custom(figment)).unwrap();
+        assert_eq!(config.min_idle, None);
+        assert_eq!(config.max_lifetime, None);
+        assert_eq!(config.idle_timeout, None);
+        assert!(config.test_on_check_out);
+    }
+}
+
+#[cfg(feature = "sqlite_pool")]
+mod transaction_test {
+    use rocket::{Rocket, Orbit};
+    use rocket::http::Status;
+    use rocket::figment::Figment;
+    use rocket::local::asynchronous::Client;
+    use rocket_sync_db_pools::{rusqlite, database, Transaction};
+
+    #[database("tx_db")]
+    struct TxDb(pub rusqlite::Connection);
+
+    async fn insert(conn: &TxDb, value: i64) {
+        conn.run(move |c| c.execute("INSERT INTO t VALUES (?1)", [value])).await.unwrap();
+    }
+
+    #[rocket::post("/ok/<value>")]
+    async fn ok(conn: Transaction<TxDb>, value: i64) {
+        insert(&conn, value).await;
+    }
+
+    #[rocket::post("/fail/<value>")]
+    async fn fail(conn: Transaction<TxDb>, value: i64) -> Status {
+        insert(&conn, value).await;
+        Status::BadRequest
+    }
+
+    #[rocket::post("/panic/<value>")]
+    async fn panic(conn: Transaction<TxDb>, value: i64) {
+        insert(&conn, value).await;
+        panic!("handler panicked after inserting");
+    }
+
+    async fn values(rocket: &Rocket<Orbit>) -> Vec<i64> {
+        let conn = TxDb::get_one(rocket).await.expect("connection");
+        conn.run(|c| {
+            let mut stmt = c.prepare("SELECT x FROM t ORDER BY x")?;
+            let rows = stmt.query_map([], |r| r.get(0))?;
+            rows.collect::<Result<Vec<i64>, _>>()
+        }).await.unwrap()
+    }
+
+    #[rocket::async_test]
+    async fn test_transaction_commit_and_rollback() {
+        // A single connection so all requests see the same in-memory database.
+        let config = Figment::from(rocket::Config::debug_default())
+            .merge(("databases.tx_db.url", ":memory:"))
+            .merge(("databases.tx_db.pool_size", 1))
+            .merge(("databases.tx_db.init_sql", ["CREATE TABLE t (x INTEGER)"]));
+
+        let rocket = rocket::custom(config)
+            .attach(TxDb::fairing())
+            .mount("/", rocket::routes![ok, fail, panic]);
+
+        let client = Client::tracked(rocket).await.unwrap();
+        let response = client.post("/ok/1").dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+        drop(response);
+
+        let response = client.post("/fail/2").dispatch().await;
+        assert_eq!(response.status(), Status::BadRequest);
+        drop(response);
+
+        let response = client.post("/panic/3").dispatch().await;
+        assert_eq!(response.status(), Status::InternalServerError);
+        drop(response);
+
+        let response = client.post("/ok/4").dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+        drop(response);
+
+        assert_eq!(values(client.rocket()).await, vec![1, 4]);
+    }
+}
+
+#[cfg(feature = "sqlite_pool")]
+mod shutdown_test {
+    use std::time::{Duration, Instant};
+
+    use rocket::figment::Figment;
+    use rocket::local::asynchronous::Client;
+    use rocket_sync_db_pools::{rusqlite, database};
+
+    #[database("drain_db")]
+    struct DrainDb(pub rusqlite::Connection);
+
+    async fn client(shutdown_timeout: u64) -> Client {
+        let config = Figment::from(rocket::Config::debug_default())
+            .merge(("databases.drain_db.url", ":memory:"))
+            .merge(("databases.drain_db.pool_size", 2))
+            .merge(("databases.drain_db.shutdown_timeout", shutdown_timeout));
+
+        Client::untracked(rocket::custom(config).attach(DrainDb::fairing())).await.unwrap()
+    }
+
+    #[rocket::async_test]
+    async fn test_shutdown_drains_pool() {
+        let client = client(5).await;
+        let conn = DrainDb::get_one(client.rocket()).await.expect("connection");
+        rocket::tokio::spawn(async move {
+            rocket::tokio::time::sleep(Duration::from_millis(250)).await;
+            conn.run(|c| c.execute_batch("SELECT 1")).await.unwrap();
+            drop(conn);
+        });
+
+        let start = Instant::now();
+        let rocket = client.terminate().await;
+        assert!(start.elapsed() >= Duration::from_millis(250));
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        let stats = DrainDb::stats(&rocket).unwrap();
+        assert_eq!(stats.connections, 0);
+        assert!(DrainDb::get_one(&rocket).await.is_none());
+    }
+
+    #[rocket::async_test]
+    async fn test_shutdown_timeout() {
+        let client = client(1).await;
+        let conn = DrainDb::get_one(client.rocket()).await.expect("connection");
+
+        let start = Instant::now();
+        let rocket = client.terminate().await;
+        assert!(start.elapsed() >= Duration::from_secs(1));
+        assert!(DrainDb::get_one(&rocket).await.is_none());
+        conn.run(|c| c.execute_batch("SELECT 1")).await.unwrap();
+    }
+}
+
+#[cfg(feature = "sqlcipher_pool")]
+mod sqlcipher_test {
+    use rocket::{Rocket, Ignite};
+    use rocket::figment::Figment;
+    use rocket_sync_db_pools::{rusqlite, database};
+
+    #[database("cipher_db")]
+    struct CipherDb(rusqlite::Connection);
+
+    async fn ignite(path: &str, key: &str) -> Rocket<Ignite> {
+        let config = Figment::from(rocket::Config::debug_default())
+            .merge(("databases.cipher_db.url", path))
+            .merge(("databases.cipher_db.key", key));
+
+        rocket::custom(config).attach(CipherDb::fairing()).ignite().await.unwrap()
+    }
+
+    async fn count(rocket: &Rocket<Ignite>) -> rusqlite::Result<i64> {
+        let conn = CipherDb::get_one(rocket).await.expect("connection");
+        conn.run(|c| c.query_row("SELECT count(*) FROM secrets", [], |r| r.get(0))).await
+    }
+
+    #[rocket::async_test]
+    async fn test_configured_key_unlocks_database() {
+        let path = std::env::temp_dir().join(format!("rocket-cipher-{}.db", std::process::id()));
+        let path_str = path.to_str().unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let rocket = ignite(path_str, "hunter2").await;
+        let conn = CipherDb::get_one(&rocket).await.expect("connection");
+        conn.run(|c| c.execute_batch("CREATE TABLE secrets (x TEXT); INSERT INTO secrets VALUES ('a')"))
+            .await
+            .unwrap();
+
+        drop(conn);
+        drop(rocket);
+
+        // The database is encrypted: it can't be read without the key...
+        let plain = rusqlite::Connection::open(&path).unwrap();
+        assert!(plain.query_row("SELECT count(*) FROM sqlite_master", [], |r| r.get::<_, i64>(0)).is_err());
+
+        // ...or with the wrong one...
+        assert!(count(&ignite(path_str, "hunter3").await).await.is_err());
+
+        // ...but can be with the configured key.
+        assert_eq!(count(&ignite(path_str, "hunter2").await).await.unwrap(), 1);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "databases")]
 mod sentinel_and_runtime_test {
@@ -21,11 +21,23 @@ pub fn _catch(
     let vis = &catch.function.vis;
     let status_code = Optional(catch.status.map(|s| s.code));
 
-    // Determine the number of parameters that will be passed in.
-    if catch.function.sig.inputs.len() > 2 {
+    // Determine the number of parameters that will be passed in. Typed
+    // catchers take the error first.
+    let max_inputs = if catch.error.is_some() { 3 } else { 2 };
+    if catch.function.sig.inputs.len() > max_inputs {
+        let span = catch.function.sig.paren_token.span;
+        return Err(match catch.error {
+            Some(_) => span.error("invalid number of arguments: must be one, two, or three")
+                .help("typed catchers take `&E`, `&E, &Request`, or `&E, Status, &Request`"),
+            None => span.error("invalid number of arguments: must be zero, one, or two")
+                .help("catchers optionally take `&Request` or `Status, &Request`"),
+        });
+    }
+
+    if catch.error.is_some() && catch.function.sig.inputs.is_empty() {
         return Err(catch.function.sig.paren_token.span
-            .error("invalid number of arguments: must be zero, one, or two")
-            .help("catchers optionally take `&Request` or `Status, &Request`"));
+            .error("invalid number of arguments: typed catchers must take the error")
+            .help("typed catchers take `&E`, `&E, &Request`, or `&E, Status, &Request`"));
     }
 
     // This ensures that "Responder not implemented" points to the return type.
@@ -33,15 +45,39 @@ pub fn _catch(
         .map(|ty| ty.span())
         .unwrap_or_else(Span::call_site);
 
-    // Set the `req` and `status` spans to that of their respective function
-    // arguments for a more correct `wrong type` error span. `rev` to be cute.
+    // Set the `req`, `status`, and `error` spans to that of their respective
+    // function arguments for a more correct `wrong type` error span. The
+    // error, if any, is the first argument. `rev` to be cute.
+    let respanned = |fn_arg: &syn::FnArg, codegen_arg: &StaticPath| match fn_arg {
+        syn::FnArg::Receiver(_) => codegen_arg.respanned(fn_arg.span()),
+        syn::FnArg::Typed(a) => codegen_arg.respanned(a.ty.span())
+    };
+
+    let mut fn_args = catch.function.sig.inputs.iter();
+    let error_input = catch.error.as_ref().and_then(|_| fn_args.next());
     let codegen_args = &[__req, __status];
-    let inputs = catch.function.sig.inputs.iter().rev()
+    let inputs = fn_args.rev()
         .zip(codegen_args.iter())
-        .map(|(fn_arg, codegen_arg)| match fn_arg {
-            syn::FnArg::Receiver(_) => codegen_arg.respanned(fn_arg.span()),
-            syn::FnArg::Typed(a) => codegen_arg.respanned(a.ty.span())
-        }).rev();
+        .map(|(fn_arg, codegen_arg)| respanned(fn_arg, codegen_arg))
+        .rev();
+
+    let inputs = error_input.map(|fn_arg| respanned(fn_arg, &__error))
+        .into_iter()
+        .chain(inputs);
+
+    // Typed catchers only run when the recorded error is of their type.
+    let (error_guard, error_type) = match catch.error {
+        Some(ref ty) => (quote_spanned! { ty.span() =>
+            let ___recorded = #__req.recorded_error();
+            let #__error: &#ty = match ___recorded.as_deref()
+                .and_then(|e| e.downcast_ref::<#ty>())
+            {
+                #_Some(e) => e,
+                #_None => return #_Err(#__status),
+            };
+        }, quote!(#_Some(#Catcher::error_type::<#ty>))),
+        None => (quote!(), quote!(#_None)),
+    };
 
     // We append `.await` to the function call if this is `async`.
     let dot_await = catch.function.sig.asyncness
@@ -69,6 +105,7 @@ pub fn _catch(
                     #__req: &'__r #Request<'_>
                 ) -> #_catcher::BoxFuture<'__r> {
                     #_Box::pin(async move {
+                        #error_guard
                         let __response = #catcher_response;
                         #Response::build()
                             .status(#__status)
@@ -81,6 +118,7 @@ pub fn _catch(
                     name: stringify!(#user_catcher_fn_name),
                     code: #status_code,
                     handler: monomorphized_function,
+                    error_type: #error_type,
                 }
             }
 
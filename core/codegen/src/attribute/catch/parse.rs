@@ -8,6 +8,8 @@ use crate::{http, http_codegen};
 pub struct Attribute {
     /// The status associated with the code in the `#[catch(code)]` attribute.
     pub status: Option<http::Status>,
+    /// The type in `error = "Type"`, if any.
+    pub error: Option<syn::Type>,
     /// The function that was decorated with the `catch` attribute.
     pub function: syn::ItemFn,
 }
@@ -17,6 +19,7 @@ pub struct Attribute {
 struct Meta {
     #[meta(naked)]
     code: Code,
+    error: Option<ErrorType>,
 }
 
 /// `Some` if there's a code, `None` if it's `default`.
@@ -41,6 +44,20 @@ impl FromMeta for Code {
     }
 }
 
+/// The type of error a typed catcher handles: `error = "Type"`.
+struct ErrorType(syn::Type);
+
+impl FromMeta for ErrorType {
+    fn from_meta(meta: &MetaItem) -> Result<Self> {
+        match meta.lit()? {
+            syn::Lit::Str(s) => s.parse::<syn::Type>()
+                .map(ErrorType)
+                .map_err(|e| s.span().error(format!("invalid error type: {}", e))),
+            lit => Err(lit.span().error("invalid value: expected string literal")),
+        }
+    }
+}
+
 impl Attribute {
     pub fn parse(args: TokenStream, input: proc_macro::TokenStream) -> Result<Self> {
         let function: syn::ItemFn = syn::parse(input)
@@ -48,11 +65,11 @@ impl Attribute {
             .map_err(|diag| diag.help("`#[catch]` can only be used on functions"))?;
 
         let attr: MetaItem = syn::parse2(quote!(catch(#args)))?;
-        let status = Meta::from_meta(&attr)
-            .map(|meta| meta.code.0)
+        let meta = Meta::from_meta(&attr)
             .map_err(|diag| diag.help("`#[catch]` expects a status code int or `default`: \
                         `#[catch(404)]` or `#[catch(default)]`"))?;
 
-        Ok(Attribute { status, function })
+        let (status, error) = (meta.code.0, meta.error.map(|e| e.0));
+        Ok(Attribute { status, error, function })
     }
 }
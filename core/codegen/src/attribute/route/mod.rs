@@ -74,6 +74,12 @@ fn query_decls(route: &Route) -> Option<TokenStream> {
         })
         .split6();
 
+    let validate_expr = route.query_guards().map(|guard| {
+        let (ty, ident) = (&guard.ty, guard.fn_ident.rocketized().with_span(guard.ty.span()));
+        define_spanned_export!(ty.span() => __req, FromForm);
+        quote_spanned!(ty.span() => <#ty as #FromForm>::validate_async(#ident, #__req))
+    });
+
     #[allow(non_snake_case)]
     Some(quote! {
         let (#(#ident),*) = {
@@ -109,17 +115,51 @@ fn query_decls(route: &Route) -> Option<TokenStream> {
 
             (#(#ident.unwrap()),*)
         };
+
+        // Validate asynchronously once every query guard's context is gone.
+        let (#(#ident),*) = {
+            let mut __e = #_form::Errors::new();
+            #(
+                let #ident = match #validate_expr.await {
+                    #_Ok(_v) => #_Some(_v),
+                    #_Err(_err) => {
+                        __e.extend(_err.with_name(#_form::NameView::new(#name)));
+                        #_None
+                    },
+                };
+            )*
+
+            if !__e.is_empty() {
+                #_log::warn_!("Query string failed to match route declaration.");
+                for _err in __e { #_log::warn_!("{}", _err); }
+                return #Outcome::Forward(#__data);
+            }
+
+            (#(#ident.unwrap()),*)
+        };
     })
 }
 
 fn request_guard_decl(guard: &Guard) -> TokenStream {
     let (ident, ty) = (guard.fn_ident.rocketized(), &guard.ty);
     define_spanned_export!(ty.span() =>
-        __req, __data, _request, _log, FromRequest, Outcome
+        __req, __data, _request, _log, _None, _Some, FromRequest, Outcome
     );
 
+    // Guards with `'static` types may be overridden by a local client.
+    let outcome = match ty.is_lifetime_free() {
+        true => quote_spanned! { ty.span() =>
+            match #__req._guard_override::<#ty>() {
+                #_Some(#_Some(__v)) => #Outcome::Success(__v),
+                #_Some(#_None) => #Outcome::Forward(()),
+                #_None => <#ty as #FromRequest>::from_request(#__req).await,
+            }
+        },
+        false => quote_spanned!(ty.span() => <#ty as #FromRequest>::from_request(#__req).await),
+    };
+
     quote_spanned! { ty.span() =>
-        let #ident: #ty = match <#ty as #FromRequest>::from_request(#__req).await {
+        let #ident: #ty = match #outcome {
             #Outcome::Success(__v) => __v,
             #Outcome::Forward(_) => {
                 #_log::warn_!("Request guard `{}` is forwarding.", stringify!(#ty));
@@ -127,6 +167,7 @@ fn request_guard_decl(guard: &Guard) -> TokenStream {
             },
             #Outcome::Failure((__c, __e)) => {
                 #_log::warn_!("Request guard `{}` failed: {:?}.", stringify!(#ty), __e);
+                #__req._record_guard_error(&__e);
                 return #Outcome::Failure(__c);
             }
         };
@@ -155,7 +196,15 @@ fn param_guard_decl(guard: &Guard) -> TokenStream {
             match #__req.routed_segment(#i) {
                 #_Some(__s) => match <#ty as #FromParam>::from_param(__s) {
                     #_Ok(__v) => __v,
-                    #_Err(__error) => return #parse_error,
+                    #_Err(__error) => match <#ty as #FromParam>::failure_status(&__error) {
+                        #_Some(__c) => {
+                            #_log::warn_!("Parameter guard `{}: {}` failed: {:?}.",
+                                #name, stringify!(#ty), __error);
+
+                            return #Outcome::Failure(__c);
+                        }
+                        #_None => return #parse_error,
+                    }
                 },
                 #_None => {
                     #_log::error_!("Internal invariant broken: dyn param not found.");
@@ -190,6 +239,7 @@ fn data_guard_decl(guard: &Guard) -> TokenStream {
             }
             #Outcome::Failure((__c, __e)) => {
                 #_log::warn_!("Data guard `{}` failed: {:?}.", stringify!(#ty), __e);
+                #__req._record_guard_error(&__e);
                 return #Outcome::Failure(__c);
             }
         };
@@ -310,6 +360,56 @@ fn sentinels_expr(route: &Route) -> TokenStream {
     quote!(::std::vec![#(#sentinel),*])
 }
 
+fn openapi_operation_stmt(route: &Route) -> TokenStream {
+    use crate::exports::_openapi;
+
+    let generic_idents: Vec<_> = route.handler.sig.generics
+        .type_params()
+        .map(|p| &p.ident)
+        .collect();
+
+    // Types that can't be named here are described as unconstrained.
+    let nameable = |ty: &syn::Type| match ty.is_concrete(&generic_idents) {
+        true => { let ty = ty.with_stripped_lifetimes(); quote!(#ty) }
+        false => quote!(#_openapi::resolution::Opaque),
+    };
+
+    let doc = route.handler.attrs.iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Str(s), .. })) => {
+                Some(s.value())
+            }
+            _ => None,
+        })
+        .map(|line| line.strip_prefix(' ').map(String::from).unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let params = route.param_guards()
+        .map(|guard| (quote!(Path), guard))
+        .chain(route.query_guards().map(|guard| (quote!(Query), guard)))
+        .map(|(location, guard)| {
+            let (name, trailing, ty) = (guard.name.as_str(), guard.trailing, nameable(&guard.ty));
+            quote!((#location #name: #ty, #trailing))
+        });
+
+    let data = route.data_guard.as_ref().map(|guard| nameable(&guard.ty));
+    let ret = match route.handler.sig.output {
+        syn::ReturnType::Default => quote!(()),
+        syn::ReturnType::Type(_, ref ty) => nameable(ty),
+    };
+
+    quote! {
+        ::rocket::__openapi_operation!(__info,
+            doc = #doc,
+            params = [#(#params),*],
+            data = [#data],
+            ret = #ret
+        );
+    }
+}
+
 fn codegen_route(route: Route) -> Result<TokenStream> {
     use crate::exports::*;
 
@@ -319,8 +419,9 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
     let query_guards = query_decls(&route);
     let data_guard = route.data_guard.as_ref().map(data_guard_decl);
 
-    // Extract the sentinels from the route.
+    // Extract the sentinels and OpenAPI operation from the route.
     let sentinels = sentinels_expr(&route);
+    let openapi_operation = openapi_operation_stmt(&route);
 
     // Gather info about the function.
     let (vis, handler_fn) = (&route.handler.vis, &route.handler);
@@ -332,6 +433,11 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
     let uri = route.attr.uri.to_string();
     let rank = Optional(route.attr.rank);
     let format = Optional(route.attr.format.as_ref());
+    let timeout = Optional(route.attr.timeout);
+    let limits = Optional(route.attr.limits.as_ref());
+    let meta = route.attr.meta.iter().flat_map(|meta| meta.0.iter()).map(|value| {
+        quote_spanned!(value.span() => #_Arc::new(#value) as #_Arc<dyn #_Any + Send + Sync>)
+    });
 
     Ok(quote! {
         #handler_fn
@@ -359,15 +465,22 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
                     })
                 }
 
-                #_route::StaticInfo {
+                #[allow(unused_mut)]
+                let mut __info = #_route::StaticInfo {
                     name: stringify!(#handler_fn_name),
                     method: #method,
                     uri: #uri,
                     handler: monomorphized_function,
                     format: #format,
                     rank: #rank,
+                    timeout: #timeout,
+                    limits: #limits,
                     sentinels: #sentinels,
-                }
+                    meta: ::std::vec![#(#meta),*],
+                };
+
+                #openapi_operation
+                __info
             }
 
             #[doc(hidden)]
@@ -418,6 +531,9 @@ fn incomplete_route(
         data: method_attribute.data,
         format: method_attribute.format,
         rank: method_attribute.rank,
+        timeout: method_attribute.timeout,
+        limits: method_attribute.limits,
+        meta: method_attribute.meta,
     };
 
     codegen_route(Route::from(attribute, function)?)
@@ -4,7 +4,7 @@ use indexmap::{IndexSet, IndexMap};
 use proc_macro2::Span;
 
 use crate::proc_macro_ext::Diagnostics;
-use crate::http_codegen::{Method, MediaType};
+use crate::http_codegen::{Method, MediaType, Timeout, Limits};
 use crate::attribute::param::{Parameter, Dynamic, Guard};
 use crate::syn_ext::FnArgExt;
 use crate::name::Name;
@@ -47,6 +47,9 @@ pub struct Attribute {
     pub data: Option<SpanWrapped<Dynamic>>,
     pub format: Option<MediaType>,
     pub rank: Option<isize>,
+    pub timeout: Option<Timeout>,
+    pub limits: Option<Limits>,
+    pub meta: Option<Meta>,
 }
 
 /// The parsed `#[method(..)]` (e.g, `get`, `put`, etc.) attribute.
@@ -57,6 +60,22 @@ pub struct MethodAttribute {
     pub data: Option<SpanWrapped<Dynamic>>,
     pub format: Option<MediaType>,
     pub rank: Option<isize>,
+    pub timeout: Option<Timeout>,
+    pub limits: Option<Limits>,
+    pub meta: Option<Meta>,
+}
+
+/// The route's metadata values: `meta = value` or `meta = [value, ..]`.
+#[derive(Debug)]
+pub struct Meta(pub Vec<syn::Expr>);
+
+impl FromMeta for Meta {
+    fn from_meta(meta: &devise::MetaItem) -> Result<Self> {
+        match syn::Expr::from_meta(meta)? {
+            syn::Expr::Array(array) => Ok(Meta(array.elems.into_iter().collect())),
+            expr => Ok(Meta(vec![expr])),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -0,0 +1,75 @@
+use std::path::{Path, PathBuf};
+use std::error::Error;
+
+use syn::{LitStr, Token};
+use syn::parse::{Parse, ParseStream};
+use devise::ext::SpanDiagnosticExt;
+use proc_macro2::TokenStream;
+
+struct Invocation {
+    fs_mod: syn::Path,
+    root: LitStr,
+}
+
+impl Parse for Invocation {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let fs_mod = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let root = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Invocation { fs_mod, root })
+    }
+}
+
+pub fn _macro(input: proc_macro::TokenStream) -> devise::Result<TokenStream> {
+    let Invocation { fs_mod, root } = syn::parse(input)?;
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").expect("MANIFEST_DIR");
+    let dir = Path::new(&manifest_dir).join(root.value());
+
+    let mut files = vec![];
+    read_dir(&dir, &dir, &mut files)
+        .map_err(|e| root.span().error(format!("failed to read '{}': {}", dir.display(), e)))?;
+
+    files.sort();
+    let files = files.into_iter().map(|(path, full_path, etag)| {
+        let full_path = full_path.display().to_string();
+        quote_spanned!(root.span() =>
+            #fs_mod::EmbeddedFile::__new(#path, include_bytes!(#full_path), #etag)
+        )
+    });
+
+    Ok(quote_spanned!(root.span() => {
+        const FILES: &[#fs_mod::EmbeddedFile] = &[#(#files),*];
+        #fs_mod::Embedded::__new(FILES)
+    }))
+}
+
+/// Collects the files in `dir`, recursively, as their `/` separated path
+/// relative to `root`, their full path, and an entity tag for their contents.
+fn read_dir(
+    root: &Path,
+    dir: &Path,
+    files: &mut Vec<(String, PathBuf, String)>,
+) -> Result<(), Box<dyn Error>> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            read_dir(root, &path, files)?;
+            continue;
+        }
+
+        let relative = path.strip_prefix(root)?.components()
+            .map(|c| c.as_os_str().to_str().ok_or("file name is not valid UTF-8"))
+            .collect::<Result<Vec<_>, _>>()?
+            .join("/");
+
+        // A 64-bit FNV-1a hash: stable across builds and compiler versions.
+        let hash = std::fs::read(&path)?.iter().fold(0xcbf29ce484222325u64, |hash, &byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+
+        files.push((relative, path.canonicalize()?, format!("{:016x}", hash)));
+    }
+
+    Ok(())
+}
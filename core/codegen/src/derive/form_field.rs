@@ -19,6 +19,7 @@ pub enum FieldName {
 pub struct FieldAttr {
     pub name: Option<FieldName>,
     pub validate: Option<SpanWrapped<syn::Expr>>,
+    pub validate_async: Option<SpanWrapped<syn::Expr>>,
     pub default: Option<syn::Expr>,
     pub default_with: Option<syn::Expr>,
 }
@@ -35,6 +36,7 @@ pub(crate) trait FieldExt {
     fn first_field_name(&self) -> Result<Option<FieldName>>;
     fn stripped_ty(&self) -> syn::Type;
     fn name_buf_opt(&self) -> Result<TokenStream>;
+    fn root_name_buf_opt(&self) -> Result<TokenStream>;
 }
 
 #[derive(FromMeta)]
@@ -210,6 +212,17 @@ impl FieldExt for Field<'_> {
             .map(|name| quote_spanned!(span => Some(#_form::NameBuf::from((__c.__parent, #name)))))
             .unwrap_or_else(|| quote_spanned!(span => None::<#_form::NameBuf>)))
     }
+
+    fn root_name_buf_opt(&self) -> Result<TokenStream> {
+        let (span, field_names) = (self.span(), self.field_names()?);
+        define_spanned_export!(span => _form);
+
+        Ok(field_names.first()
+            .map(|name| quote_spanned!(span => {
+                Some(#_form::NameBuf::from((None::<&#_form::Name>, #name)))
+            }))
+            .unwrap_or_else(|| quote_spanned!(span => None::<#_form::NameBuf>)))
+    }
 }
 
 #[derive(Default)]
@@ -239,6 +252,7 @@ impl<'a> Visit<'a> for RecordMemberAccesses {
 
 struct ValidationMutator<'a> {
     field: Field<'a>,
+    with_request: bool,
     visited: bool,
 }
 
@@ -286,6 +300,10 @@ impl VisitMut for ValidationMutator<'_> {
         self.visited = true;
         let accessor = self.field.context_ident().with_span(self.field.ty.span());
         call.args.insert(0, syn::parse_quote!(#accessor));
+        if self.with_request {
+            call.args.insert(1, syn::parse_quote!(__req));
+        }
+
         syn::visit_mut::visit_expr_call_mut(self, call);
     }
 
@@ -335,17 +353,22 @@ impl VisitMut for ValidationMutator<'_> {
     }
 }
 
-pub fn validators<'v>(field: Field<'v>) -> Result<impl Iterator<Item = syn::Expr> + 'v> {
+/// Returns the `validate` expressions for `field` or, if `is_async`, the
+/// `validate_async` expressions, which are passed the request and awaited.
+pub fn validators<'v>(
+    field: Field<'v>,
+    is_async: bool,
+) -> Result<impl Iterator<Item = syn::Expr> + 'v> {
     Ok(FieldAttr::from_attrs(FieldAttr::NAME, &field.attrs)?
         .into_iter()
         .chain(FieldAttr::from_attrs(FieldAttr::NAME, field.parent.attrs())?)
-        .filter_map(|a| a.validate)
+        .filter_map(move |a| if is_async { a.validate_async } else { a.validate })
         .map(move |mut expr| {
             let mut record = RecordMemberAccesses::default();
             record.accesses.insert((field.context_ident(), true));
             record.visit_expr(&expr);
 
-            let mut v = ValidationMutator { field, visited: false };
+            let mut v = ValidationMutator { field, with_request: is_async, visited: false };
             v.visit_expr_mut(&mut expr);
 
             let span = expr.key_span.unwrap_or(field.ty.span());
@@ -358,7 +381,15 @@ pub fn validators<'v>(field: Field<'v>) -> Result<impl Iterator<Item = syn::Expr
 
             let matchers = quote_spanned!(span => (#(Some(#matchers)),*));
             let values = quote_spanned!(span => (#(#values),*));
-            let name_opt = field.name_buf_opt().unwrap();
+            let name_opt = match is_async {
+                true => field.root_name_buf_opt().unwrap(),
+                false => field.name_buf_opt().unwrap(),
+            };
+
+            let expr = match is_async {
+                true => quote_spanned!(span => #expr.await),
+                false => quote_spanned!(span => #expr),
+            };
 
             define_spanned_export!(span => _form);
             let expr: syn::Expr = syn::parse_quote_spanned!(span => {
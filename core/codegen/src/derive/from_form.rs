@@ -222,7 +222,7 @@ pub fn derive_from_form(input: proc_macro::TokenStream) -> TokenStream {
                     .collect::<Result<Vec<TokenStream>>>()?;
 
                 let (_ok, _some, _err, _none) = (_Ok, _Some, _Err, _None);
-                let validator = fields.iter().flat_map(|f| validators(f).unwrap());
+                let validator = fields.iter().flat_map(|f| validators(f, false).unwrap());
                 let ident = fields.iter().map(|f| f.context_ident());
                 let builder = fields.builder(|f| {
                     let ident = f.context_ident();
@@ -277,5 +277,75 @@ pub fn derive_from_form(input: proc_macro::TokenStream) -> TokenStream {
                 }})
             })
         )
+        .inner_mapper(MapperBuild::new()
+            .try_fields_map(|_, fields| {
+                // A future holding a value of a generic type that borrows, say
+                // `Q<&'a T>`, must be `Send` for every lifetime, which the
+                // `FromForm` bounds on the type can't prove. Such forms keep
+                // the default, no-op impl.
+                let generic_idents = fields.parent.input().generics().type_idents();
+                let borrows = fields.iter()
+                    .find(|f| !f.ty.is_concrete(&generic_idents) && !f.ty.is_lifetime_free());
+
+                if let Some(f) = borrows {
+                    if let Some(v) = fields.iter().flat_map(|f| validators(f, true).unwrap()).next() {
+                        return Err(v.span()
+                            .error("`validate_async` is not supported in forms with borrowed generic fields")
+                            .span_note(f.ty.span(), "field with borrowed generic type is here"));
+                    }
+
+                    return Ok(TokenStream::new());
+                }
+
+                let (_ok, _some, _err, _none, form) = (_Ok, _Some, _Err, _None, _form);
+                let validator = fields.iter().flat_map(|f| validators(f, true).unwrap());
+                let ident = fields.iter().map(|f| f.context_ident());
+                let member = fields.iter().map(|f| f.member());
+                let ty = fields.iter().map(|f| f.stripped_ty());
+                let nested_errors = fields.iter()
+                    .map(|f| Ok(match f.first_field_name()? {
+                        Some(name) => quote_spanned!(f.span() => __e.with_parent(#name)),
+                        None => quote_spanned!(f.span() => __e),
+                    }))
+                    .collect::<Result<Vec<_>>>()?;
+
+                let builder = fields.builder(|f| {
+                    let ident = f.context_ident();
+                    quote!(#ident.unwrap())
+                });
+
+                Ok(quote_spanned!(fields.span() =>
+                    async fn validate_async(
+                        __v: Self,
+                        __req: &'r #_request::Request<'_>
+                    ) -> #_Result<Self, #_form::Errors<'r>> {
+                        #[allow(unused_imports)]
+                        use #_form::validate::*;
+
+                        let mut __errors = #_form::Errors::new();
+
+                        #(
+                            let _fut = <#ty as #form::FromForm<'r>>::validate_async(__v.#member, __req);
+                            let #ident = match _fut.await {
+                                #_ok(#ident) => #_some(#ident),
+                                #_err(__e) => { __errors.extend(#nested_errors); #_none }
+                            };
+                        )*
+
+                        #(
+                            if let #_err(__e) = #validator {
+                                __errors.extend(__e);
+                            }
+                        )*
+
+                        if !__errors.is_empty() {
+                            return #_Err(__errors);
+                        }
+
+                        Ok(#builder)
+                    }
+                ))
+            })
+        )
         .to_tokens()
 }
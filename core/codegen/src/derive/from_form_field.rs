@@ -1,4 +1,4 @@
-use devise::{*, ext::SpanDiagnosticExt};
+use devise::{*, ext::{TypeExt, SpanDiagnosticExt}};
 use proc_macro2::TokenStream;
 
 use crate::exports::*;
@@ -6,15 +6,22 @@ use crate::derive::form_field::{VariantExt, first_duplicate};
 
 pub fn derive_from_form_field(input: proc_macro::TokenStream) -> TokenStream {
     DeriveGenerator::build_for(input, quote!(impl<'__v> #_form::FromFormField<'__v>))
-        .support(Support::Enum)
+        .support(Support::Enum | Support::Struct | Support::Lifetime)
+        .replace_generic(0, 0)
         .validator(ValidatorBuild::new()
-            // We only accept C-like enums with at least one variant.
-            .fields_validate(|_, fields| {
-                if !fields.is_empty() {
-                    return Err(fields.span().error("variants cannot have fields"));
+            .input_validate(|_, i| match i.generics().lifetimes().count() > 1 {
+                true => Err(i.generics().span().error("only one lifetime is supported")),
+                false => Ok(())
+            })
+            // We only accept newtypes and C-like enums with at least one variant.
+            .fields_validate(|_, fields| match fields.parent {
+                FieldParent::Variant(_) if !fields.is_empty() => {
+                    Err(fields.span().error("variants cannot have fields"))
                 }
-
-                Ok(())
+                FieldParent::Struct(_) if fields.count() != 1 => {
+                    Err(fields.span().error("struct must have exactly one field"))
+                }
+                _ => Ok(())
             })
             .enum_validate(|_, data| {
                 if data.variants.is_empty() {
@@ -43,14 +50,30 @@ pub fn derive_from_form_field(input: proc_macro::TokenStream) -> TokenStream {
             #[allow(unused_imports)]
             use #_http::uncased::AsUncased;
         })
+        .outer_mapper(quote!(#[rocket::async_trait]))
         .inner_mapper(MapperBuild::new()
-            .with_output(|_, output| quote! {
-                fn from_value(
-                    __f: #_form::ValueField<'__v>
-                ) -> Result<Self, #_form::Errors<'__v>> {
+            .try_struct_map(|_, data| {
+                let field = data.fields().iter().next().expect("one field");
+                let ty = field.ty.with_replaced_lifetimes(syn::Lifetime::new("'__v", field.span()));
+                let builder = data.builder(|_| quote!(__v));
+                Ok(quote_spanned! { data.span() =>
+                    fn from_value(
+                        __f: #_form::ValueField<'__v>
+                    ) -> #_Result<Self, #_form::Errors<'__v>> {
+                        <#ty as #_form::FromFormField<'__v>>::from_value(__f).map(|__v| #builder)
+                    }
 
-                    #output
-                }
+                    async fn from_data(
+                        __f: #_form::DataField<'__v, '_>
+                    ) -> #_Result<Self, #_form::Errors<'__v>> {
+                        <#ty as #_form::FromFormField<'__v>>::from_data(__f).await
+                            .map(|__v| #builder)
+                    }
+
+                    fn default() -> #_Option<Self> {
+                        <#ty as #_form::FromFormField<'__v>>::default().map(|__v| #builder)
+                    }
+                })
             })
             .try_enum_map(|mapper, data| {
                 let mut variant_value = vec![];
@@ -64,16 +87,20 @@ pub fn derive_from_form_field(input: proc_macro::TokenStream) -> TokenStream {
 
                 let (_ok, _cow) = (std::iter::repeat(_Ok), std::iter::repeat(_Cow));
                 Ok(quote! {
-                    #(#variant_condition)*
+                    fn from_value(
+                        __f: #_form::ValueField<'__v>
+                    ) -> Result<Self, #_form::Errors<'__v>> {
+                        #(#variant_condition)*
 
-                    const OPTS: &'static [#_Cow<'static, str>] =
-                        &[#(#_cow::Borrowed(#variant_value)),*];
+                        const OPTS: &'static [#_Cow<'static, str>] =
+                            &[#(#_cow::Borrowed(#variant_value)),*];
 
-                    let _error = #_form::Error::from(OPTS)
-                        .with_name(__f.name)
-                        .with_value(__f.value);
+                        let _error = #_form::Error::from(OPTS)
+                            .with_name(__f.name)
+                            .with_value(__f.value);
 
-                    #_Err(_error)?
+                        #_Err(_error)?
+                    }
                 })
             })
             .try_variant_map(|_, variant| {
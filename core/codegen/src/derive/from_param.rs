@@ -0,0 +1,172 @@
+use devise::{*, ext::{TypeExt, SpanDiagnosticExt}};
+use proc_macro2::TokenStream;
+
+use crate::exports::*;
+use crate::http_codegen::Status;
+use crate::name::Name;
+
+#[derive(Default, FromMeta)]
+struct ItemAttr {
+    case_sensitive: Option<bool>,
+    status: Option<SpanWrapped<Status>>,
+}
+
+impl ItemAttr {
+    const NAME: &'static str = "param";
+}
+
+#[derive(FromMeta)]
+struct VariantAttr {
+    value: Name,
+}
+
+fn variant_values(variant: &Variant<'_>) -> Result<Vec<Name>> {
+    let values = VariantAttr::from_attrs(ItemAttr::NAME, &variant.attrs)?
+        .into_iter()
+        .map(|attr| attr.value)
+        .collect::<Vec<_>>();
+
+    match values.is_empty() {
+        true => Ok(vec![Name::from(&variant.ident)]),
+        false => Ok(values),
+    }
+}
+
+fn failure_status(attr: &ItemAttr, delegate: Option<&syn::Type>) -> TokenStream {
+    let status = match (&attr.status, delegate) {
+        (Some(status), _) => quote_spanned!(status.span() => #_Some(#status)),
+        (None, Some(ty)) => quote_spanned!(ty.span() => {
+            <#ty as #FromParam<'__a>>::failure_status(__e)
+        }),
+        (None, None) => return quote!(),
+    };
+
+    quote! {
+        #[allow(unused_variables)]
+        fn failure_status(__e: &Self::Error) -> #_Option<#_http::Status> {
+            #status
+        }
+    }
+}
+
+pub fn derive_from_param(input: proc_macro::TokenStream) -> TokenStream {
+    DeriveGenerator::build_for(input, quote!(impl<'__a> #_request::FromParam<'__a>))
+        .support(Support::Struct | Support::Enum | Support::Lifetime)
+        .replace_generic(0, 0)
+        .validator(ValidatorBuild::new()
+            .input_validate(|_, i| match i.generics().lifetimes().count() > 1 {
+                true => Err(i.generics().span().error("only one lifetime is supported")),
+                false => Ok(())
+            })
+            .struct_validate(|_, data| {
+                let fields = data.fields();
+                if fields.count() != 1 {
+                    return Err(fields.span().error("struct must have exactly one field"));
+                }
+
+                let attr = ItemAttr::one_from_attrs(ItemAttr::NAME, &data.attrs)?
+                    .unwrap_or_default();
+
+                if attr.case_sensitive.is_some() {
+                    let msg = "`case_sensitive` is only supported on enums";
+                    return Err(data.ident.span().error(msg));
+                }
+
+                Ok(())
+            })
+            .enum_validate(|_, data| {
+                if data.variants.is_empty() {
+                    return Err(data.span().error("enum must have at least one variant"));
+                }
+
+                let attr = ItemAttr::one_from_attrs(ItemAttr::NAME, &data.attrs)?
+                    .unwrap_or_default();
+
+                let case_sensitive = attr.case_sensitive.unwrap_or(false);
+                let mut seen: Vec<Name> = vec![];
+                for variant in data.variants() {
+                    if !variant.fields().is_empty() {
+                        return Err(variant.fields().span().error("variants cannot have fields"));
+                    }
+
+                    for value in variant_values(&variant)? {
+                        if value.contains('/') {
+                            return Err(value.span().error("parameter value cannot contain `/`"));
+                        }
+
+                        let prev = seen.iter().find(|prev| match case_sensitive {
+                            true => prev.as_str() == value.as_str(),
+                            false => prev.as_uncased_str() == value.as_uncased_str(),
+                        });
+
+                        if let Some(prev) = prev {
+                            return Err(value.span().error("value conflicts with previous value")
+                                .span_help(variant.ident.span(), "declared in this variant")
+                                .span_note(prev.span(), "previous conflicting value"));
+                        }
+
+                        seen.push(value);
+                    }
+                }
+
+                Ok(())
+            })
+        )
+        .outer_mapper(quote! {
+            #[allow(unused_imports)]
+            use #_http::uncased::AsUncased;
+        })
+        .inner_mapper(MapperBuild::new()
+            .try_struct_map(|_, data| {
+                let attr = ItemAttr::one_from_attrs(ItemAttr::NAME, &data.attrs)?
+                    .unwrap_or_default();
+
+                let field = data.fields().iter().next().expect("one field");
+                let ty = field.ty.with_replaced_lifetimes(syn::Lifetime::new("'__a", field.span()));
+                let builder = data.builder(|_| quote!(__v));
+                let failure_status = failure_status(&attr, Some(&ty));
+                Ok(quote_spanned! { data.span() =>
+                    type Error = <#ty as #FromParam<'__a>>::Error;
+
+                    fn from_param(__p: &'__a str) -> #_Result<Self, Self::Error> {
+                        <#ty as #FromParam<'__a>>::from_param(__p).map(|__v| #builder)
+                    }
+
+                    #failure_status
+                })
+            })
+            .try_enum_map(|_, data| {
+                let attr = ItemAttr::one_from_attrs(ItemAttr::NAME, &data.attrs)?
+                    .unwrap_or_default();
+
+                let mut conditions = vec![];
+                for variant in data.variants() {
+                    let builder = variant.builder(|_| unreachable!("fieldless"));
+                    let values = variant_values(&variant)?;
+                    let condition = match attr.case_sensitive.unwrap_or(false) {
+                        true => quote!(#(__p == #values)||*),
+                        false => quote!(#(__p.as_uncased() == #values)||*),
+                    };
+
+                    conditions.push(quote_spanned! { variant.span() =>
+                        if #condition {
+                            return #_Ok(#builder);
+                        }
+                    });
+                }
+
+                let failure_status = failure_status(&attr, None);
+                Ok(quote! {
+                    type Error = &'__a str;
+
+                    fn from_param(__p: &'__a str) -> #_Result<Self, Self::Error> {
+                        #(#conditions)*
+                        #_Err(__p)
+                    }
+
+                    #failure_status
+                })
+            })
+        )
+        .to_tokens()
+}
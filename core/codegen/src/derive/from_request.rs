@@ -0,0 +1,58 @@
+use devise::{*, ext::{TypeExt, SpanDiagnosticExt}};
+use proc_macro2::TokenStream;
+
+use crate::exports::*;
+
+pub fn derive_from_request(input: proc_macro::TokenStream) -> TokenStream {
+    DeriveGenerator::build_for(input, quote!(impl<'__r> #_request::FromRequest<'__r>))
+        .support(Support::NamedStruct | Support::TupleStruct | Support::Lifetime)
+        .replace_generic(0, 0)
+        .validator(ValidatorBuild::new()
+            .input_validate(|_, i| match i.generics().lifetimes().count() > 1 {
+                true => Err(i.generics().span().error("only one lifetime is supported")),
+                false => Ok(())
+            })
+            .fields_validate(|_, fields| match fields.is_empty() {
+                true => Err(fields.span().error("struct must have at least one field")),
+                false => Ok(())
+            })
+        )
+        .outer_mapper(quote!(#[rocket::async_trait]))
+        .inner_mapper(MapperBuild::new()
+            .with_output(|_, output| quote! {
+                type Error = #_Box<dyn ::std::fmt::Debug + '__r>;
+
+                async fn from_request(
+                    __req: &'__r #Request<'_>
+                ) -> #_request::Outcome<Self, Self::Error> {
+                    #output
+                }
+            })
+            .try_fields_map(|_, fields| {
+                let guards = fields.iter().map(|f| {
+                    let (ident, ty) = (f.match_ident(), &f.ty);
+                    let ty = ty.with_replaced_lifetimes(syn::Lifetime::new("'__r", ty.span()));
+                    quote_spanned! { ty.span() =>
+                        let #ident = match <#ty as #FromRequest<'__r>>::from_request(__req).await {
+                            #Outcome::Success(__v) => __v,
+                            #Outcome::Forward(__f) => return #Outcome::Forward(__f),
+                            #Outcome::Failure((__c, __e)) => {
+                                return #Outcome::Failure((__c, #_Box::new(__e)));
+                            }
+                        };
+                    }
+                });
+
+                let builder = fields.builder(|f| {
+                    let ident = f.match_ident();
+                    quote!(#ident)
+                });
+
+                Ok(quote! {
+                    #(#guards)*
+                    #Outcome::Success(#builder)
+                })
+            })
+        )
+        .to_tokens()
+}
@@ -1,5 +1,9 @@
 mod form_field;
 pub mod from_form;
 pub mod from_form_field;
+pub mod from_param;
+pub mod from_request;
 pub mod responder;
+pub mod schema;
+pub mod typed_cookie;
 pub mod uri_display;
@@ -1,6 +1,7 @@
 use quote::ToTokens;
 use devise::{*, ext::{TypeExt, SpanDiagnosticExt}};
 use proc_macro2::TokenStream;
+use syn::visit_mut::VisitMut;
 
 use crate::exports::*;
 use crate::syn_ext::{TypeExt as _, GenericsExt as _};
@@ -9,12 +10,67 @@ use crate::http_codegen::{ContentType, Status};
 #[derive(Debug, Default, FromMeta)]
 struct ItemAttr {
     content_type: Option<SpanWrapped<ContentType>>,
-    status: Option<SpanWrapped<Status>>,
+    status: Option<SpanWrapped<ItemStatus>>,
 }
 
 #[derive(Default, FromMeta)]
 struct FieldAttr {
-    ignore: bool,
+    ignore: Option<bool>,
+    header: Option<SpanWrapped<String>>,
+}
+
+impl FieldAttr {
+    fn ignored(&self) -> bool {
+        self.ignore.unwrap_or(false)
+    }
+}
+
+/// The value of `status`: a status code or an expression of type `Status`.
+#[derive(Debug)]
+enum ItemStatus {
+    Code(Status),
+    Expr(Box<syn::Expr>),
+}
+
+impl FromMeta for ItemStatus {
+    fn from_meta(meta: &MetaItem) -> Result<Self> {
+        if meta.lit().is_ok() {
+            return Status::from_meta(meta).map(ItemStatus::Code);
+        }
+
+        meta.parse_value("status code or expression").map(|expr| ItemStatus::Expr(Box::new(expr)))
+    }
+}
+
+impl ToTokens for ItemStatus {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            ItemStatus::Code(status) => status.to_tokens(tokens),
+            ItemStatus::Expr(expr) => expr.to_tokens(tokens),
+        }
+    }
+}
+
+/// Replaces `self.field` in an expression with the binding for `field` in an
+/// enum variant's match arm.
+struct VariantFields;
+
+impl VisitMut for VariantFields {
+    fn visit_expr_mut(&mut self, i: &mut syn::Expr) {
+        if let syn::Expr::Field(e) = i {
+            if matches!(&*e.base, syn::Expr::Path(p) if p.path.is_ident("self")) {
+                let name = match &e.member {
+                    syn::Member::Named(ident) => format!("__{}", ident),
+                    syn::Member::Unnamed(index) => format!("__{}", index.index),
+                };
+
+                let ident = syn::Ident::new(&name, e.member.span());
+                *i = syn::parse_quote!(#ident);
+            }
+        }
+
+        syn::visit_mut::visit_expr_mut(self, i);
+    }
 }
 
 pub fn derive_responder(input: proc_macro::TokenStream) -> TokenStream {
@@ -41,13 +97,18 @@ pub fn derive_responder(input: proc_macro::TokenStream) -> TokenStream {
 
                 for (f, ty) in types {
                     let attr = FieldAttr::one_from_attrs("response", &f.attrs)?.unwrap_or_default();
-                    if ty.is_concrete(&generic_idents) || attr.ignore {
+                    if ty.is_concrete(&generic_idents) || attr.ignored() {
                         continue;
                     }
 
-                    bounds.push(quote_spanned! { ty.span() =>
-                        #ty: ::std::convert::Into<#_http::Header<'o>>
-                    });
+                    match attr.header {
+                        Some(_) => bounds.push(quote_spanned! { ty.span() =>
+                            #ty: ::std::fmt::Display
+                        }),
+                        None => bounds.push(quote_spanned! { ty.span() =>
+                            #ty: ::std::convert::Into<#_http::Header<'o>>
+                        }),
+                    }
                 }
 
                 Ok(quote!(#(#bounds,)*))
@@ -86,22 +147,58 @@ pub fn derive_responder(input: proc_macro::TokenStream) -> TokenStream {
                     }
                 }).expect("have at least one field");
 
+                let first = fields.iter().next().expect("have at least one field");
+                let first_attr = FieldAttr::one_from_attrs("response", &first.attrs)?;
+                if let Some(header) = first_attr.and_then(|a| a.header) {
+                    return Err(header.span()
+                        .error("the first field is the responder and cannot be a header"));
+                }
+
                 let mut headers = vec![];
                 for field in fields.iter().skip(1) {
                     let attr = FieldAttr::one_from_attrs("response", &field.attrs)?
                         .unwrap_or_default();
 
-                    if !attr.ignore {
-                        headers.push(set_header_tokens(field.accessor()));
+                    if attr.ignored() {
+                        continue;
+                    }
+
+                    match attr.header {
+                        Some(name) => {
+                            if !is_valid_header_name(&name) {
+                                return Err(name.span().error("invalid header name"));
+                            }
+
+                            let (name, accessor) = (&name.value, field.accessor());
+                            headers.push(set_header_tokens(quote_spanned! { field.span() =>
+                                #_http::Header::new(#name, ::std::string::ToString::to_string(
+                                    &#accessor
+                                ))
+                            }));
+                        }
+                        None => headers.push(set_header_tokens(field.accessor())),
                     }
                 }
 
+                // Status expressions are evaluated before any field is moved.
                 let content_type = attr.content_type.map(set_header_tokens);
-                let status = attr.status.map(|status| {
-                    quote_spanned!(status.span() => __res.set_status(#status);)
-                });
+                let (status_expr, status) = match attr.status {
+                    Some(status) => {
+                        let span = status.span();
+                        let mut status = status.value;
+                        let is_variant = matches!(fields.parent, FieldParent::Variant(_));
+                        if let (ItemStatus::Expr(expr), true) = (&mut status, is_variant) {
+                            VariantFields.visit_expr_mut(expr);
+                        }
+
+                        (quote_spanned!(span => let __status: #_http::Status = #status;),
+                            quote_spanned!(span => __res.set_status(__status);))
+                    }
+                    None => (quote!(), quote!()),
+                };
 
                 Ok(quote! {
+                    #status_expr
                     #responder
                     #(#headers)*
                     #content_type
@@ -112,3 +209,11 @@ pub fn derive_responder(input: proc_macro::TokenStream) -> TokenStream {
         )
         .to_tokens()
 }
+
+/// Returns `true` if `name` is a valid header field name: a non-empty token.
+fn is_valid_header_name(name: &str) -> bool {
+    !name.is_empty() && name.bytes().all(|b| match b {
+        b'!' | b'#'..=b'\'' | b'*' | b'+' | b'-' | b'.' | b'^'..=b'`' | b'|' | b'~' => true,
+        b => b.is_ascii_alphanumeric(),
+    })
+}
@@ -0,0 +1,95 @@
+use devise::{*, ext::SpanDiagnosticExt};
+use proc_macro2::TokenStream;
+use syn::ext::IdentExt;
+
+use crate::exports::*;
+use crate::syn_ext::{GenericsExt as _, TypeExt as _};
+
+const NO_EMPTY_FIELDS: &str = "fieldless structs are not supported";
+const NO_EMPTY_ENUMS: &str = "empty enums are not supported";
+const ONLY_ONE_UNNAMED: &str = "tuple structs must have exactly one field";
+const ONLY_UNIT_VARIANTS: &str = "enum variants cannot have fields";
+
+pub fn derive_schema(input: proc_macro::TokenStream) -> TokenStream {
+    DeriveGenerator::build_for(input, quote!(impl #_openapi::Schema))
+        .support(Support::Struct | Support::Enum | Support::Type | Support::Lifetime)
+        .validator(ValidatorBuild::new()
+            .enum_validate(|_, data| {
+                if data.variants().count() == 0 {
+                    return Err(data.brace_token.span.error(NO_EMPTY_ENUMS));
+                }
+
+                for variant in data.variants() {
+                    if !variant.fields().is_empty() {
+                        return Err(variant.fields().span().error(ONLY_UNIT_VARIANTS));
+                    }
+                }
+
+                Ok(())
+            })
+            .struct_validate(|_, data| {
+                let fields = data.fields();
+                if fields.is_empty() {
+                    Err(data.span().error(NO_EMPTY_FIELDS))
+                } else if fields.are_unnamed() && fields.count() > 1 {
+                    Err(fields.span().error(ONLY_ONE_UNNAMED))
+                } else {
+                    Ok(())
+                }
+            })
+        )
+        .type_bound_mapper(MapperBuild::new()
+            .try_enum_map(|m, e| mapper::enum_null(m, e))
+            .try_fields_map(|_, fields| {
+                let generic_idents = fields.parent.input().generics().type_idents();
+                let bounds = fields.iter()
+                    .filter(|f| !f.ty.is_concrete(&generic_idents))
+                    .map(|f| &f.field.inner.ty)
+                    .map(|ty| quote_spanned!(ty.span() => #ty: #_openapi::Schema));
+
+                Ok(quote!(#(#bounds,)*))
+            })
+        )
+        .inner_mapper(MapperBuild::new()
+            .try_input_map(|_, input| {
+                // Generic types are described inline: their schema depends on
+                // the type parameters, so their name alone isn't unique.
+                if input.generics().type_params().next().is_some() {
+                    return Ok(quote!());
+                }
+
+                let name = input.ident().unraw().to_string();
+                Ok(quote! {
+                    fn name() -> #_Option<#_Cow<'static, str>> {
+                        #_Some(#_Cow::Borrowed(#name))
+                    }
+                })
+            })
+        )
+        .inner_mapper(MapperBuild::new()
+            .with_output(|_, output| quote! {
+                fn schema(__c: &mut #_openapi::Components) -> #_openapi::Value {
+                    #output
+                }
+            })
+            .enum_map(|_, data| {
+                let names = data.variants().map(|v| v.ident.unraw().to_string());
+                quote!(#_openapi::enumeration([#(#names),*]))
+            })
+            .fields_map(|_, fields| {
+                if fields.are_unnamed() {
+                    let ty = fields.iter().next().map(|f| &f.field.inner.ty);
+                    return quote!(__c.schema::<#ty>());
+                }
+
+                let properties = fields.iter().map(|f| {
+                    let ty = &f.ty;
+                    let name = f.ident.as_ref().map(|i| i.unraw().to_string());
+                    quote_spanned!(ty.span() => .property::<#ty>(#name))
+                });
+
+                quote!(__c.object() #(#properties)* .build())
+            })
+        )
+        .to_tokens()
+}
@@ -0,0 +1,71 @@
+use devise::{*, ext::SpanDiagnosticExt};
+use proc_macro2::TokenStream;
+
+use crate::exports::*;
+use crate::name::Name;
+
+#[derive(Default, FromMeta)]
+struct ItemAttr {
+    name: Option<Name>,
+    encoding: Option<SpanWrapped<String>>,
+    privacy: Option<SpanWrapped<String>>,
+    max_age: Option<SpanWrapped<usize>>,
+}
+
+impl ItemAttr {
+    const NAME: &'static str = "cookie";
+}
+
+fn variant(value: &SpanWrapped<String>, choices: &[&str]) -> Result<syn::Ident> {
+    if !choices.contains(&value.as_str()) {
+        let choices = choices.iter()
+            .map(|c| format!("`{}`", c))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        return Err(value.span.error("invalid value")
+            .help(format!("expected one of {}", choices)));
+    }
+
+    let mut variant = value.to_string();
+    variant[..1].make_ascii_uppercase();
+    Ok(syn::Ident::new(&variant, value.span))
+}
+
+pub fn derive_typed_cookie(input: proc_macro::TokenStream) -> TokenStream {
+    DeriveGenerator::build_for(input, quote!(impl #_http::TypedCookie))
+        .support(Support::Struct | Support::Enum)
+        .inner_mapper(MapperBuild::new()
+            .try_input_map(|_, input| {
+                let attr = ItemAttr::one_from_attrs(ItemAttr::NAME, input.attrs())?
+                    .unwrap_or_default();
+
+                let name = attr.name.unwrap_or_else(|| Name::from(input.ident()));
+                let mut consts = vec![quote!(const NAME: &'static str = #name;)];
+                if let Some(encoding) = &attr.encoding {
+                    let variant = variant(encoding, &["json", "base64"])?;
+                    consts.push(quote_spanned! { encoding.span =>
+                        const ENCODING: #_http::CookieEncoding = #_http::CookieEncoding::#variant;
+                    });
+                }
+
+                if let Some(privacy) = &attr.privacy {
+                    let variant = variant(privacy, &["plain", "signed", "private"])?;
+                    consts.push(quote_spanned! { privacy.span =>
+                        const PRIVACY: #_http::CookiePrivacy = #_http::CookiePrivacy::#variant;
+                    });
+                }
+
+                if let Some(max_age) = &attr.max_age {
+                    let secs = **max_age as i64;
+                    consts.push(quote_spanned! { max_age.span =>
+                        const MAX_AGE: #_Option<::rocket::time::Duration> =
+                            #_Some(::rocket::time::Duration::seconds(#secs));
+                    });
+                }
+
+                Ok(quote!(#(#consts)*))
+            })
+        )
+        .to_tokens()
+}
@@ -74,6 +74,7 @@ define_exported_paths! {
     _log => ::rocket::log,
     _form => ::rocket::form::prelude,
     _http => ::rocket::http,
+    _openapi => ::rocket::openapi,
     _uri => ::rocket::http::uri,
     _fmt => ::rocket::http::uri::fmt,
     _Option => ::std::option::Option,
@@ -83,6 +84,8 @@ define_exported_paths! {
     _Ok => ::std::result::Result::Ok,
     _Err => ::std::result::Result::Err,
     _Box => ::std::boxed::Box,
+    _Arc => ::std::sync::Arc,
+    _Any => ::std::any::Any,
     _Vec => ::std::vec::Vec,
     _Cow => ::std::borrow::Cow,
     BorrowMut => ::std::borrow::BorrowMut,
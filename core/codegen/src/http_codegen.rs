@@ -16,6 +16,12 @@ pub struct MediaType(pub http::MediaType);
 #[derive(Debug, Copy, Clone)]
 pub struct Method(pub http::Method);
 
+#[derive(Debug, Copy, Clone)]
+pub struct Timeout(pub std::time::Duration);
+
+#[derive(Debug, Clone)]
+pub struct Limits(pub Vec<(String, u64)>);
+
 #[derive(Clone, Debug)]
 pub struct Optional<T>(pub Option<T>);
 
@@ -96,6 +102,86 @@ impl ToTokens for MediaType {
     }
 }
 
+impl FromMeta for Timeout {
+    fn from_meta(meta: &MetaItem) -> Result<Self> {
+        use std::time::Duration;
+
+        let string = String::from_meta(meta)?;
+        let split = string.find(|c: char| !c.is_ascii_digit()).unwrap_or(string.len());
+        let (count, unit) = string.split_at(split);
+        let duration = count.parse::<u64>().ok().and_then(|n| match unit {
+            "ms" => Some(Duration::from_millis(n)),
+            "s" => Some(Duration::from_secs(n)),
+            "m" => n.checked_mul(60).map(Duration::from_secs),
+            "h" => n.checked_mul(60 * 60).map(Duration::from_secs),
+            _ => None,
+        });
+
+        match duration {
+            Some(duration) if !duration.is_zero() => Ok(Timeout(duration)),
+            Some(_) => Err(meta.value_span().error("timeout must be non-zero")),
+            None => Err(meta.value_span().error("invalid timeout")
+                .help("expected an integer followed by `ms`, `s`, `m`, or `h`: \"30s\"")),
+        }
+    }
+}
+
+impl ToTokens for Timeout {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let (secs, nanos) = (self.0.as_secs(), self.0.subsec_nanos());
+        tokens.extend(quote!(::std::time::Duration::new(#secs, #nanos)));
+    }
+}
+
+impl FromMeta for Limits {
+    fn from_meta(meta: &MetaItem) -> Result<Self> {
+        let string = String::from_meta(meta)?;
+        let help = "expected comma-separated `name = size` pairs: \"file = 100MiB\"";
+
+        let mut limits: Vec<(String, u64)> = vec![];
+        for item in string.split(',').map(|item| item.trim()) {
+            let (name, size) = match item.split_once('=') {
+                Some((name, size)) => (name.trim(), size.trim()),
+                None => return Err(meta.value_span()
+                    .error(format!("invalid limit `{}`", item))
+                    .help(help)),
+            };
+
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return Err(meta.value_span()
+                    .error(format!("invalid limit name `{}`", name))
+                    .help(help));
+            }
+
+            let size = match size.parse::<ubyte::ByteUnit>() {
+                Ok(size) => size.as_u64(),
+                Err(_) => return Err(meta.value_span()
+                    .error(format!("invalid size `{}` for limit `{}`", size, name))
+                    .help("expected a byte unit such as \"512KiB\" or \"100MiB\"")),
+            };
+
+            if limits.iter().any(|(n, _)| n.eq_ignore_ascii_case(name)) {
+                return Err(meta.value_span().error(format!("duplicate limit `{}`", name)));
+            }
+
+            limits.push((name.to_string(), size));
+        }
+
+        Ok(Limits(limits))
+    }
+}
+
+impl ToTokens for Limits {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let (names, sizes) = self.0.iter().map(|(n, s)| (n.as_str(), *s)).split2();
+        let data = quote!(::rocket::data);
+
+        tokens.extend(quote! {
+            #data::Limits::new()#(.limit(#names, #data::ByteUnit::Byte(#sizes)))*
+        });
+    }
+}
+
 const VALID_METHODS_STR: &str = "`GET`, `PUT`, `POST`, `DELETE`, `HEAD`, \
     `PATCH`, `OPTIONS`";
 
@@ -108,7 +194,26 @@ const VALID_METHODS: &[http::Method] = &[
 impl FromMeta for Method {
     fn from_meta(meta: &MetaItem) -> Result<Self> {
         let span = meta.value_span();
-        let help_text = format!("method must be one of: {}", VALID_METHODS_STR);
+        let help_text = format!("method must be one of: {}, or a string literal \
+            naming an extension method", VALID_METHODS_STR);
+
+        if let Ok(syn::Lit::Str(name)) = meta.lit() {
+            let name = name.value();
+            let is_tchar = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c);
+            if name.is_empty() || !name.chars().all(is_tchar) {
+                return Err(span.error("invalid HTTP extension method name")
+                    .help("method names are non-empty and contain only token characters"));
+            }
+
+            if name.parse::<http::Method>().is_ok() {
+                return Err(span.error("standard HTTP method used as an extension method")
+                    .help(format!("use the method identifier instead: `{}`",
+                        name.to_ascii_uppercase())));
+            }
+
+            // The name must be `'static`. Leaking is harmless in a proc-macro.
+            return Ok(Method(http::Method::Extension(Box::leak(name.into_boxed_str()))));
+        }
 
         if let MetaItem::Path(path) = meta {
             if let Some(ident) = path.last_ident() {
@@ -141,6 +246,7 @@ impl ToTokens for Method {
             http::Method::Trace => quote!(::rocket::http::Method::Trace),
             http::Method::Connect => quote!(::rocket::http::Method::Connect),
             http::Method::Patch => quote!(::rocket::http::Method::Patch),
+            http::Method::Extension(name) => quote!(::rocket::http::Method::Extension(#name)),
         };
 
         tokens.extend(method_tokens);
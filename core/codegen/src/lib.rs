@@ -130,6 +130,16 @@ macro_rules! route_attribute {
         /// }
         /// ```
         ///
+        /// To route a non-standard extension method, such as WebDAV's
+        /// `PROPFIND`, name the method with a string literal in [`route`]:
+        ///
+        /// ```rust
+        /// # #[macro_use] extern crate rocket;
+        /// # use std::path::PathBuf;
+        /// #[route("PROPFIND", uri = "/dav/<path..>")]
+        /// fn propfind(path: PathBuf) { /* .. */ }
+        /// ```
+        ///
         /// [`get`]: attr.get.html
         /// [`put`]: attr.put.html
         /// [`post`]: attr.post.html
@@ -157,21 +167,34 @@ macro_rules! route_attribute {
         /// parameter := 'rank' '=' INTEGER
         ///            | 'format' '=' '"' MEDIA_TYPE '"'
         ///            | 'data' '=' '"' SINGLE_PARAM '"'
+        ///            | 'timeout' '=' '"' INTEGER TIME_UNIT '"'
+        ///            | 'limits' '=' '"' limit (',' limit)* '"'
+        ///            | 'meta' '=' EXPR
+        ///            | 'meta' '=' '[' EXPR (',' EXPR)* ']'
+        ///
+        /// limit := LIMIT_NAME '=' BYTE_UNIT
         ///
         /// SINGLE_PARAM := '<' IDENT '>'
         /// TRAILING_PARAM := '<' IDENT '..>'
         ///
         /// URI_SEG := valid, non-percent-encoded HTTP URI segment
         /// MEDIA_TYPE := valid HTTP media type or known shorthand
+        /// TIME_UNIT := 'ms' | 's' | 'm' | 'h'
+        /// LIMIT_NAME := data limit name, as in `Limits`
+        /// BYTE_UNIT := integer or decimal followed by a byte unit: `100MiB`
         ///
         /// INTEGER := unsigned integer, as defined by Rust
         /// IDENT := valid identifier, as defined by Rust
+        /// EXPR := valid expression, as defined by Rust
         /// ```
         ///
         /// The generic route attribute is defined as:
         ///
         /// ```text
-        /// generic-route := METHOD ',' 'uri' '=' route
+        /// generic-route := (METHOD | '"' EXTENSION_METHOD '"') ',' 'uri' '=' route
+        ///
+        /// METHOD := 'GET' | 'PUT' | 'POST' | 'DELETE' | 'HEAD' | 'PATCH' | 'OPTIONS'
+        /// EXTENSION_METHOD := valid, non-standard HTTP method name
         /// ```
         ///
         /// # Typing Requirements
@@ -223,6 +246,7 @@ macro_rules! route_attribute {
         /// [`FromData`]: ../rocket/data/trait.FromData.html
         /// [`FromRequest`]: ../rocket/request/trait.FromRequest.html
         /// [`Route`]: ../rocket/struct.Route.html
+        /// [`Route::meta()`]: ../rocket/struct.Route.html#method.meta
         /// [`Responder`]: ../rocket/response/trait.Responder.html
         ///
         /// # Semantics
@@ -258,9 +282,12 @@ macro_rules! route_attribute {
         ///   2. A static structure used by [`routes!`] to generate a [`Route`].
         ///
         ///      The static structure (and resulting [`Route`]) is populated
-        ///      with the name (the function's name), path, query, rank, and
-        ///      format from the route attribute. The handler is set to the
-        ///      generated handler.
+        ///      with the name (the function's name), path, query, rank,
+        ///      format, timeout, and limits from the route attribute. The
+        ///      handler is set to the generated handler. Each `meta` expression, which
+        ///      must evaluate to a `Send + Sync + 'static` value, is stored as
+        ///      route metadata, retrievable via [`Route::meta()`]. An array of
+        ///      expressions stores each of its elements separately.
         ///
         ///   3. A macro used by [`uri!`] to type-check and generate an
         ///      [`Origin`].
@@ -309,14 +336,30 @@ route_attribute!(options => Method::Options);
 /// }
 /// ```
 ///
+/// A _typed_ catcher additionally declares the type of error it handles and
+/// receives a reference to it. It is only invoked when a route's failure
+/// recorded an error of that type, as [`Report`] does:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #
+/// use std::num::ParseIntError;
+///
+/// #[catch(500, error = "ParseIntError")]
+/// fn invalid_number(error: &ParseIntError) -> String {
+///     format!("That's not a number: {}.", error)
+/// }
+/// ```
+///
 /// # Grammar
 ///
 /// The grammar for the `#[catch]` attributes is defined as:
 ///
 /// ```text
-/// catch := STATUS | 'default'
+/// catch := (STATUS | 'default') (',' 'error' '=' TYPE)?
 ///
 /// STATUS := valid HTTP status code (integer in [200, 599])
+/// TYPE := string literal containing a Rust type
 /// ```
 ///
 /// # Typing Requirements
@@ -328,6 +371,13 @@ route_attribute!(options => Method::Options);
 ///   * `fn(`[`&Request`]`) -> R`
 ///   * `fn(`[`Status`]`, `[`&Request`]`) -> R`
 ///
+/// A typed catcher with `error = "E"`, where `E: std::error::Error + Send +
+/// Sync + 'static`, must additionally take an `&E` as its first argument:
+///
+///   * `fn(&E) -> R`
+///   * `fn(&E, `[`&Request`]`) -> R`
+///   * `fn(&E, `[`Status`]`, `[`&Request`]`) -> R`
+///
 /// # Semantics
 ///
 /// The attribute generates two items:
@@ -335,16 +385,21 @@ route_attribute!(options => Method::Options);
 ///   1. An error [`Handler`].
 ///
 ///      The generated handler calls the decorated function, passing in the
-///      [`Status`] and [`&Request`] values if requested. The returned value is
-///      used to generate a [`Response`] via the type's [`Responder`]
-///      implementation.
+///      [`Status`] and [`&Request`] values if requested. For typed catchers,
+///      the error recorded by the request, downcast to `E`, is passed in
+///      first. The returned value is used to generate a [`Response`] via the
+///      type's [`Responder`] implementation.
 ///
 ///   2. A static structure used by [`catchers!`] to generate a [`Catcher`].
 ///
 ///      The static structure (and resulting [`Catcher`]) is populated with the
 ///      name (the function's name) and status code from the route attribute or
-///      `None` if `default`. The handler is set to the generated handler.
+///      `None` if `default`. The handler is set to the generated handler. A
+///      typed catcher's [`Catcher`] only matches requests that recorded an
+///      error of type `E` and takes priority over untyped catchers with the
+///      same base.
 ///
+/// [`Report`]: ../rocket/response/struct.Report.html
 /// [`&Request`]: ../rocket/struct.Request.html
 /// [`Status`]: ../rocket/http/struct.Status.html
 /// [`Handler`]: ../rocket/catcher/trait.Handler.html
@@ -541,12 +596,258 @@ pub fn launch(args: TokenStream, input: TokenStream) -> TokenStream {
 /// variant. In the example above, the the strings `"fourth"`, `"FOUrth"`,
 /// `"fiFTH"` and so on would parse as `MyValue::Third`.
 ///
+/// # Newtypes
+///
+/// The derive can also be applied to structures with exactly one field, named
+/// or unnamed, whose type implements [`FromFormField`]. The implementation
+/// delegates parsing, from both values and data, and defaults to the field's
+/// type, wrapping the result:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #
+/// #[derive(FromFormField)]
+/// struct Quantity(u16);
+///
+/// #[derive(FromFormField)]
+/// struct Username<'r>(&'r str);
+/// ```
+///
 /// [`FromFormField`]: ../rocket/form/trait.FromFormField.html
 #[proc_macro_derive(FromFormField, attributes(field))]
 pub fn derive_from_form_field(input: TokenStream) -> TokenStream {
     emit!(derive::from_form_field::derive_from_form_field(input))
 }
 
+/// Derive for the [`FromParam`] trait.
+///
+/// The [`FromParam`] derive can be applied to enums with nullary
+/// (zero-length) fields and to structures with exactly one field:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #
+/// #[derive(FromParam)]
+/// enum Status {
+///     Open,
+///     Done,
+///     #[param(value = "in-progress")]
+///     #[param(value = "wip")]
+///     InProgress,
+/// }
+///
+/// #[derive(FromParam)]
+/// struct Id(usize);
+///
+/// #[derive(FromParam)]
+/// struct Slug<'r>(&'r str);
+///
+/// #[get("/issues/<status>/<id>")]
+/// fn issue(status: Status, id: Id) { /* .. */ }
+/// ```
+///
+/// For enums, the generated implementation returns successfully when the
+/// parameter matches, case insensitively, the stringified version of a
+/// variant's name or, if any are given, any of the variant's `value`s. For the
+/// `enum` above, `"open"`, `"OPEN"`, and so on parse as `Status::Open`, while
+/// `"in-progress"` and `"wip"` parse as `Status::InProgress`. If there is no
+/// match, the parameter string is returned as the `Err` value. Declaring
+/// conflicting values is a compile-time error.
+///
+/// For structures, parsing is delegated to the field's type, whose
+/// [`FromParam`] implementation must exist, and the `Error` type is that of
+/// the field's type.
+///
+/// # Case Handling and Failure
+///
+/// The `param` item attribute configures the generated implementation:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #
+/// #[derive(FromParam)]
+/// #[param(case_sensitive, status = 404)]
+/// enum Kind {
+///     Bug,
+///     #[param(value = "feature-request")]
+///     Feature,
+/// }
+/// ```
+///
+/// The attribute's grammar is:
+///
+/// ```text
+/// param := item_param | variant_param
+///
+/// item_param := 'case_sensitive' (',' 'status' '=' STATUS)?
+///             | 'status' '=' STATUS (',' 'case_sensitive')?
+///
+/// variant_param := 'value' '=' STRING_LIT
+///
+/// STATUS := unsigned integer >= 100 and < 600
+/// STRING_LIT := any valid string literal, as defined by Rust
+/// ```
+///
+/// With `case_sensitive`, which is only supported on enums, parameters must
+/// match a value exactly: the parameter `"bug"` above does not parse as
+/// `Kind::Bug`; `"Bug"` does.
+///
+/// By default, a failure to parse forwards the request to the next matching
+/// route. With `status`, the request instead fails immediately with the given
+/// status, preventing further routes from being tried. This is implemented by
+/// [`FromParam::failure_status()`]. For structures without a `status`, the
+/// field type's `failure_status()` is used.
+///
+/// [`FromParam`]: ../rocket/request/trait.FromParam.html
+/// [`FromParam::failure_status()`]: ../rocket/request/trait.FromParam.html#method.failure_status
+#[proc_macro_derive(FromParam, attributes(param))]
+pub fn derive_from_param(input: TokenStream) -> TokenStream {
+    emit!(derive::from_param::derive_from_param(input))
+}
+
+/// Derive for the [`FromRequest`] trait.
+///
+/// The [`FromRequest`] derive can be applied to structures with named or
+/// unnamed fields whose types are themselves request guards, allowing several
+/// guards to be grouped into one:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # type User = rocket::http::Method;
+/// use rocket::http::CookieJar;
+/// use rocket::request::RequestId;
+///
+/// #[derive(FromRequest)]
+/// struct Ctx<'r> {
+///     user: User,
+///     id: &'r RequestId,
+///     cookies: &'r CookieJar<'r>,
+/// }
+///
+/// #[get("/")]
+/// fn index(ctx: Ctx<'_>) { /* .. */ }
+/// ```
+///
+/// The structure may have at most one lifetime parameter, which is identified
+/// with the request lifetime `'r` in `FromRequest<'r>`. It must have at least
+/// one field, and each field's type must implement [`FromRequest`].
+///
+/// The generated implementation invokes each field's guard in declaration
+/// order. If all guards succeed, the structure is constructed from their
+/// values. Otherwise, the first guard to forward or fail short-circuits: the
+/// remaining guards are not tried, and the derived guard forwards or fails
+/// with the same status. In the event of failure, the field guard's error is
+/// boxed into the derived guard's `Error` type, `Box<dyn Debug + 'r>`.
+///
+/// As with any other request guard, fields can be wrapped in an `Option` or
+/// `Result` to catch forwards and failures instead of propagating them.
+///
+/// [`FromRequest`]: ../rocket/request/trait.FromRequest.html
+#[proc_macro_derive(FromRequest)]
+pub fn derive_from_request(input: TokenStream) -> TokenStream {
+    emit!(derive::from_request::derive_from_request(input))
+}
+
+/// Derive for the [`TypedCookie`] trait.
+///
+/// The [`TypedCookie`] derive can be applied to any structure or enum that
+/// also implements `Serialize` and `Deserialize`, allowing it to be stored in
+/// and retrieved from a [`CookieJar`] with [`add_typed()`] and
+/// [`get_typed()`]:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::{CookieJar, TypedCookie};
+/// use rocket::serde::{Serialize, Deserialize};
+///
+/// #[derive(Serialize, Deserialize, TypedCookie)]
+/// #[serde(crate = "rocket::serde")]
+/// #[cookie(name = "prefs", encoding = "base64", max_age = 3600)]
+/// struct Prefs {
+///     theme: String,
+/// }
+///
+/// #[get("/")]
+/// fn index(jar: &CookieJar<'_>) -> String {
+///     let prefs = jar.get_typed::<Prefs>()
+///         .unwrap_or_else(|| Prefs { theme: "light".into() });
+///
+///     prefs.theme
+/// }
+/// ```
+///
+/// The optional `cookie` item attribute configures the generated
+/// implementation. Its grammar is:
+///
+/// ```text
+/// cookie := param (',' param)*
+///
+/// param := 'name' '=' STRING_LIT
+///        | 'encoding' '=' ("json" | "base64")
+///        | 'privacy' '=' ("plain" | "signed" | "private")
+///        | 'max_age' '=' SECONDS
+///
+/// SECONDS := unsigned integer
+/// ```
+///
+/// The `name` defaults to the name of the type. The `encoding` defaults to
+/// `"json"` and the `privacy` to `"plain"`. The `"signed"` and `"private"`
+/// privacies require Rocket's `secrets` feature. When `max_age` is set, the
+/// cookie expires after the given number of seconds; otherwise, it is a
+/// session cookie for `"plain"` privacy and expires after one week for the
+/// others. The derive, and the trait, require Rocket's `json` feature.
+///
+/// [`TypedCookie`]: ../rocket/http/trait.TypedCookie.html
+/// [`CookieJar`]: ../rocket/http/struct.CookieJar.html
+/// [`add_typed()`]: ../rocket/http/struct.CookieJar.html#method.add_typed
+/// [`get_typed()`]: ../rocket/http/struct.CookieJar.html#method.get_typed
+#[proc_macro_derive(TypedCookie, attributes(cookie))]
+pub fn derive_typed_cookie(input: TokenStream) -> TokenStream {
+    emit!(derive::typed_cookie::derive_typed_cookie(input))
+}
+
+/// Derive for the [`Schema`] trait.
+///
+/// The [`Schema`] derive can be applied to structures with named fields, tuple
+/// structures with exactly one field, and enums whose variants have no fields:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::openapi::Schema;
+///
+/// #[derive(Schema)]
+/// struct Task<'r> {
+///     description: &'r str,
+///     tags: Vec<String>,
+///     due: Option<String>,
+/// }
+///
+/// #[derive(Schema)]
+/// struct TaskId(u64);
+///
+/// #[derive(Schema)]
+/// enum Status { Pending, Done }
+/// ```
+///
+/// Each field type is required to implement [`Schema`].
+///
+/// The derive generates an implementation of [`Schema::schema()`] that
+/// describes a structure with named fields as an object with one property per
+/// field, named after the field and required unless its type is optional; a
+/// tuple structure as its field's type; and an enum as a string whose value is
+/// the name of a variant. Unless the type is generic, the implementation also
+/// names the schema after the type with [`Schema::name()`].
+///
+/// The derive, and the trait, require Rocket's `openapi` feature.
+///
+/// [`Schema`]: ../rocket/openapi/trait.Schema.html
+/// [`Schema::schema()`]: ../rocket/openapi/trait.Schema.html#tymethod.schema
+/// [`Schema::name()`]: ../rocket/openapi/trait.Schema.html#method.name
+#[proc_macro_derive(Schema)]
+pub fn derive_schema(input: TokenStream) -> TokenStream {
+    emit!(derive::schema::derive_schema(input))
+}
+
 /// Derive for the [`FromForm`] trait.
 ///
 /// The [`FromForm`] derive can be applied to structures with named or unnamed
@@ -598,7 +899,7 @@ pub fn derive_from_form_field(input: TokenStream) -> TokenStream {
 /// attribute, `form`, with the following syntax:
 ///
 /// ```text
-/// field := name? default? validate*
+/// field := name? default? validate* validate_async*
 ///
 /// name := 'name' '=' name_val ','?
 /// name_val :=  '"' FIELD_NAME '"'
@@ -609,6 +910,8 @@ pub fn derive_from_form_field(input: TokenStream) -> TokenStream {
 ///
 /// validate := 'validate' '=' EXPR ','?
 ///
+/// validate_async := 'validate_async' '=' EXPR ','?
+///
 /// FIELD_NAME := valid field name, according to the HTML5 spec
 /// EXPR := valid expression, as defined by Rust
 /// ```
@@ -659,6 +962,44 @@ pub fn derive_from_form_field(input: TokenStream) -> TokenStream {
 ///     `Err`, the errors are added to the thus-far collected errors. If more
 ///     than one `validate` attribute is applied, _all_ validations are run.
 ///
+///   * **`validate_async = expr`**
+///
+///     Like `validate`, but `expr` is a call to an `async` function that is
+///     passed the field as its first argument and the incoming
+///     [`&Request`](../rocket/request/struct.Request.html) as its second. The
+///     returned future is awaited and must resolve to a `Result<(),
+///     form::Errors>`. Asynchronous validations are run by the
+///     [`Form`](../rocket/form/struct.Form.html) data guard and for query
+///     guards after the form parses and all `validate` validations succeed,
+///     including in nested forms and in collections of forms. They are not
+///     run by `Form::parse()` and friends. A failing validation in a query
+///     guard forwards, as does any other query parse failure. Forms with a
+///     field of a borrowed generic type, such as `&'r T`, can't use
+///     `validate_async`, and nested forms in such fields aren't validated
+///     asynchronously.
+///
+///     ```rust
+///     # #[macro_use] extern crate rocket;
+///     use rocket::form::{self, Error};
+///     use rocket::request::Request;
+///     # struct Users;
+///     # impl Users { async fn exists(&self, _: &str) -> bool { false } }
+///
+///     async fn available<'v>(name: &str, req: &Request<'_>) -> form::Result<'v, ()> {
+///         match req.rocket().state::<Users>() {
+///             Some(users) if !users.exists(name).await => Ok(()),
+///             _ => Err(Error::validation("username is taken"))?,
+///         }
+///     }
+///
+///     #[derive(FromForm)]
+///     struct Signup<'r> {
+///         #[field(validate = len(3..))]
+///         #[field(validate_async = available())]
+///         username: &'r str,
+///     }
+///     ```
+///
 ///   * **`default = expr`**
 ///
 ///     If `expr` is not literally `None`, the parameter sets the default value
@@ -810,6 +1151,22 @@ pub fn derive_from_form(input: TokenStream) -> TokenStream {
 ///
 /// Decorating the first field with `#[response(ignore)]` has no effect.
 ///
+/// Fields that don't implement `Into<Header>` can instead be set as a header
+/// with a given name, and a value computed from the field's [`Display`]
+/// implementation, with `#[response(header = "Name")]`:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # type InnerResponder = String;
+/// #
+/// #[derive(Responder)]
+/// struct Page {
+///     inner: InnerResponder,
+///     #[response(header = "X-Total-Count")]
+///     total: usize,
+/// }
+/// ```
+///
 /// # Field Attribute
 ///
 /// Additionally, the `response` attribute can be used on named structures and
@@ -820,10 +1177,11 @@ pub fn derive_from_form(input: TokenStream) -> TokenStream {
 /// ```text
 /// response := parameter (',' parameter)?
 ///
-/// parameter := 'status' '=' STATUS
+/// parameter := 'status' '=' (STATUS | EXPR)
 ///            | 'content_type' '=' CONTENT_TYPE
 ///
 /// STATUS := unsigned integer >= 100 and < 600
+/// EXPR := expression of type `Status`, referring to fields as `self.field`
 /// CONTENT_TYPE := string literal, as defined by Rust, identifying a valid
 ///                 Content-Type, as defined by Rocket
 /// ```
@@ -857,8 +1215,38 @@ pub fn derive_from_form(input: TokenStream) -> TokenStream {
 ///
 /// The attribute accepts two key/value pairs: `status` and `content_type`. The
 /// value of `status` must be an unsigned integer representing a valid status
-/// code. The [`Response`] produced from the generated implementation will have
-/// its status overridden to this value.
+/// code or an expression evaluating to a [`Status`]. The [`Response`] produced
+/// from the generated implementation will have its status overridden to this
+/// value.
+///
+/// A `status` expression may refer to the fields of the structure or variant as
+/// `self.field` or `self.0`. It is evaluated before the response is generated,
+/// so a field it uses by value must be `Copy` or ignored by the derive. This
+/// allows the status of a response to be chosen at runtime:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::Status;
+/// use rocket::serde::{Serialize, json::Json};
+///
+/// #[derive(Responder)]
+/// #[response(status = self.status, content_type = "json")]
+/// struct ApiError<T: Serialize> {
+///     body: Json<T>,
+///     #[response(ignore)]
+///     status: Status,
+///     #[response(header = "X-Request-Id")]
+///     request_id: u64,
+/// }
+///
+/// #[derive(Responder)]
+/// enum Failure {
+///     #[response(status = Status::new(self.code))]
+///     Coded { message: String, #[response(ignore)] code: u16 },
+///     #[response(status = self.1)]
+///     Other(String, #[response(ignore)] Status),
+/// }
+/// ```
 ///
 /// The value of `content_type` must be a valid media-type in `top/sub` form or
 /// `shorthand` form. Examples include:
@@ -877,6 +1265,8 @@ pub fn derive_from_form(input: TokenStream) -> TokenStream {
 /// [`Responder`]: ../rocket/response/trait.Responder.html
 /// [`Response`]: ../rocket/struct.Response.html
 /// [`Response::set_header()`]: ../rocket/response/struct.Response.html#method.set_header
+/// [`Status`]: ../rocket/http/struct.Status.html
+/// [`Display`]: std::fmt::Display
 /// [`ContentType::parse_flexible()`]: ../rocket/http/struct.ContentType.html#method.parse_flexible
 ///
 /// # Generics
@@ -885,7 +1275,9 @@ pub fn derive_from_form(input: TokenStream) -> TokenStream {
 /// generic. If a type generic is present and the generic is used in the first
 /// field of a structure, the generated implementation will require a bound of
 /// `Responder<'r, 'o>` for the field type containing the generic. In all other
-/// fields, unless ignores, a bound of `Into<Header<'o>` is added.
+/// fields, unless ignores, a bound of `Into<Header<'o>` is added, or a bound of
+/// `Display` for fields with a `header` name. Bounds declared on the type's
+/// generics and in its `where` clause are retained.
 ///
 /// For example, for a struct `struct Foo<T, H>(Json<T>, H)`, the derive adds:
 ///
@@ -1374,6 +1766,24 @@ pub fn catchers(input: TokenStream) -> TokenStream {
 /// assert_eq!(bob.to_string(), "/person/Bob?woo#bam");
 /// ```
 ///
+/// To generate absolute URIs for the running application, as needed in emails,
+/// redirects behind proxies, or `Location` headers, use a [`BaseUrl`] request
+/// guard as the prefix. Its value is the configured `base_url` or is derived
+/// from the request; see [`Request::base_url()`] for details:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::BaseUrl;
+///
+/// #[get("/person/<name>")]
+/// fn person(name: &str) { }
+///
+/// #[get("/share")]
+/// fn share(base: BaseUrl) -> String {
+///     uri!(base, person("Bob")).to_string()
+/// }
+/// ```
+///
 /// ## Grammar
 ///
 /// The grammar for this variant of the `uri!` macro is:
@@ -1382,7 +1792,7 @@ pub fn catchers(input: TokenStream) -> TokenStream {
 /// uri := (prefix ',')? route
 ///      | prefix ',' route ',' suffix
 ///
-/// prefix := STRING | expr                     ; `Origin` or `Absolute`
+/// prefix := STRING | expr                     ; `Origin`, `Absolute`, or `BaseUrl`
 /// suffix := STRING | expr                     ; `Reference` or `Absolute`
 ///
 /// route := PATH '(' (named | unnamed) ')'
@@ -1430,6 +1840,8 @@ pub fn catchers(input: TokenStream) -> TokenStream {
 /// | `Absolute` | `Absolute`  | `Absolute`  |
 /// | `Absolute` | `Reference` | `Reference` |
 ///
+/// A [`BaseUrl`] prefix behaves exactly as an `Absolute` prefix.
+///
 /// A `uri!` invocation only typechecks if the type of every route URI value in
 /// the invocation matches the type declared for the parameter in the given
 /// route, after conversion with [`FromUriParam`], or if a value is ignored
@@ -1465,6 +1877,8 @@ pub fn catchers(input: TokenStream) -> TokenStream {
 /// [`FromUriParam`]: ../rocket/http/uri/fmt/trait.FromUriParam.html
 /// [`UriDisplay`]: ../rocket/http/uri/fmt/trait.UriDisplay.html
 /// [`Ignorable`]: ../rocket/http/uri/fmt/trait.Ignorable.html
+/// [`BaseUrl`]: ../rocket/request/struct.BaseUrl.html
+/// [`Request::base_url()`]: ../rocket/request/struct.Request.html#method.base_url
 #[proc_macro]
 pub fn uri(input: TokenStream) -> TokenStream {
     emit!(bang::uri_macro(input))
@@ -1484,6 +1898,13 @@ pub fn __typed_stream(input: TokenStream) -> TokenStream {
     emit!(bang::typed_stream(input))
 }
 
+/// Internal macro: `__embed!`.
+#[proc_macro]
+#[doc(hidden)]
+pub fn __embed(input: TokenStream) -> TokenStream {
+    emit!(bang::embed(input))
+}
+
 /// Private Rocket internal macro: `internal_guide_tests!`.
 #[proc_macro]
 #[doc(hidden)]
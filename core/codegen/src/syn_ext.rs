@@ -34,6 +34,7 @@ pub trait TypeExt {
     fn unfold(&self) -> Vec<Child<'_>>;
     fn unfold_with_ty_macros(&self, names: &[&str], mapper: MacTyMapFn) -> Vec<Child<'_>>;
     fn is_concrete(&self, generic_ident: &[&Ident]) -> bool;
+    fn is_lifetime_free(&self) -> bool;
 }
 
 pub trait GenericsExt {
@@ -228,6 +229,31 @@ impl TypeExt for syn::Type {
         visitor.visit_type(self);
         visitor.0
     }
+
+    fn is_lifetime_free(&self) -> bool {
+        struct LifetimeVisitor(bool);
+
+        impl<'a> Visit<'a> for LifetimeVisitor {
+            fn visit_type(&mut self, ty: &'a syn::Type) {
+                use syn::Type::*;
+
+                match ty {
+                    Reference(_) | TraitObject(_) | ImplTrait(_) | Infer(_) | Macro(_) => {
+                        self.0 = false;
+                    }
+                    _ => syn::visit::visit_type(self, ty),
+                }
+            }
+
+            fn visit_lifetime(&mut self, _: &'a syn::Lifetime) {
+                self.0 = false;
+            }
+        }
+
+        let mut visitor = LifetimeVisitor(true);
+        visitor.visit_type(self);
+        visitor.0
+    }
 }
 
 impl GenericsExt for syn::Generics {
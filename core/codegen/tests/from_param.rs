@@ -0,0 +1,106 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::FromParam;
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+
+#[derive(Debug, PartialEq, FromParam)]
+enum State {
+    Open,
+    Done,
+    #[param(value = "in-progress")]
+    #[param(value = "wip")]
+    InProgress,
+}
+
+#[derive(Debug, PartialEq, FromParam)]
+#[param(case_sensitive)]
+enum Kind {
+    Bug,
+    #[param(value = "feature")]
+    Feature,
+}
+
+#[derive(Debug, PartialEq, FromParam)]
+struct Id(usize);
+
+#[derive(Debug, PartialEq, FromParam)]
+struct Name<'r> {
+    inner: &'r str,
+}
+
+#[derive(Debug, PartialEq, FromParam)]
+#[param(status = 404)]
+enum Strict {
+    A,
+}
+
+#[derive(Debug, PartialEq, FromParam)]
+struct Wrapper(Strict);
+
+#[derive(Debug, PartialEq, FromParam)]
+#[param(status = 400)]
+struct Number(u8);
+
+#[test]
+fn from_param_enum() {
+    assert_eq!(State::from_param("open"), Ok(State::Open));
+    assert_eq!(State::from_param("DONE"), Ok(State::Done));
+    assert_eq!(State::from_param("In-Progress"), Ok(State::InProgress));
+    assert_eq!(State::from_param("wip"), Ok(State::InProgress));
+    assert_eq!(State::from_param("inprogress"), Err("inprogress"));
+    assert_eq!(State::from_param(""), Err(""));
+
+    assert_eq!(Kind::from_param("Bug"), Ok(Kind::Bug));
+    assert_eq!(Kind::from_param("feature"), Ok(Kind::Feature));
+    assert_eq!(Kind::from_param("bug"), Err("bug"));
+    assert_eq!(Kind::from_param("Feature"), Err("Feature"));
+}
+
+#[test]
+fn from_param_newtype() {
+    assert_eq!(Id::from_param("10"), Ok(Id(10)));
+    assert_eq!(Id::from_param("ten"), Err("ten"));
+    assert_eq!(Name::from_param("bob"), Ok(Name { inner: "bob" }));
+    assert_eq!(Wrapper::from_param("a"), Ok(Wrapper(Strict::A)));
+}
+
+#[test]
+fn from_param_failure_status() {
+    assert_eq!(State::failure_status(&"x"), None);
+    assert_eq!(Id::failure_status(&"x"), None);
+    assert_eq!(Strict::failure_status(&"x"), Some(Status::NotFound));
+    assert_eq!(Wrapper::failure_status(&"x"), Some(Status::NotFound));
+    assert_eq!(Number::failure_status(&"x"), Some(Status::BadRequest));
+}
+
+#[get("/<state>")]
+fn state(state: State) -> String {
+    format!("{:?}", state)
+}
+
+#[get("/<strict>", rank = 2)]
+fn strict(strict: Strict) -> &'static str {
+    let _ = strict;
+    "strict"
+}
+
+#[get("/<_any>", rank = 3)]
+fn any(_any: &str) -> &'static str {
+    "any"
+}
+
+#[test]
+fn from_param_routing() {
+    let client = Client::debug_with(routes![state, strict, any]).unwrap();
+
+    let response = client.get("/wip").dispatch();
+    assert_eq!(response.into_string().unwrap(), "InProgress");
+
+    let response = client.get("/a").dispatch();
+    assert_eq!(response.into_string().unwrap(), "strict");
+
+    // `State` forwards, but `Strict` fails, so `any` is never tried.
+    let response = client.get("/b").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+}
@@ -0,0 +1,95 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::{Request, State};
+use rocket::http::{Header, Method, Status};
+use rocket::local::blocking::Client;
+use rocket::request::{self, FromRequest};
+
+struct Calls(AtomicUsize);
+
+#[derive(Debug)]
+struct User<'r>(&'r str);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for User<'r> {
+    type Error = &'static str;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        match req.headers().get_one("User") {
+            Some("") => request::Outcome::Failure((Status::Unauthorized, "empty user")),
+            Some(user) => request::Outcome::Success(User(user)),
+            None => request::Outcome::Forward(()),
+        }
+    }
+}
+
+struct Counted;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Counted {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        req.rocket().state::<Calls>().unwrap().0.fetch_add(1, Ordering::SeqCst);
+        request::Outcome::Success(Counted)
+    }
+}
+
+#[derive(FromRequest)]
+struct Ctx<'r> {
+    user: User<'r>,
+    method: Method,
+    _counted: Counted,
+    calls: &'r State<Calls>,
+}
+
+#[derive(FromRequest)]
+struct Tuple<'a>(Option<User<'a>>, Method);
+
+#[derive(FromRequest)]
+struct Static(Method);
+
+#[get("/")]
+fn ctx(ctx: Ctx<'_>) -> String {
+    format!("{} {} {}", ctx.user.0, ctx.method, ctx.calls.0.load(Ordering::SeqCst))
+}
+
+#[get("/", rank = 2)]
+fn fallback(tuple: Tuple<'_>, s: Static) -> String {
+    format!("{:?} {} {}", tuple.0, tuple.1, s.0)
+}
+
+fn client() -> Client {
+    let rocket = rocket::build()
+        .manage(Calls(AtomicUsize::new(0)))
+        .mount("/", routes![ctx, fallback]);
+
+    Client::debug(rocket).unwrap()
+}
+
+#[test]
+fn composite_guards_resolve_in_order() {
+    let client = client();
+    let response = client.get("/").header(Header::new("User", "bob")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "bob GET 1");
+
+    let response = client.get("/").header(Header::new("User", "alice")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "alice GET 2");
+}
+
+#[test]
+fn composite_guards_short_circuit() {
+    let client = client();
+
+    // `User` forwards, so `Counted` is never run and `fallback` is tried.
+    let response = client.get("/").dispatch();
+    assert_eq!(response.into_string().unwrap(), "None GET GET");
+    assert_eq!(client.rocket().state::<Calls>().unwrap().0.load(Ordering::SeqCst), 0);
+
+    // `User` fails, so the request fails with `User`'s status.
+    let response = client.get("/").header(Header::new("User", "")).dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(client.rocket().state::<Calls>().unwrap().0.load(Ordering::SeqCst), 0);
+}
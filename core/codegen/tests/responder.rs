@@ -145,3 +145,49 @@ async fn generic_responder() {
     assert_eq!(r.content_type().unwrap(), ContentType::Text);
     assert_eq!(r.body_mut().to_string().await.unwrap(), "beep beep");
 }
+
+#[derive(Responder)]
+#[response(status = self.status, content_type = "json")]
+struct ApiError<T: rocket::serde::Serialize> {
+    body: Json<T>,
+    #[response(ignore)]
+    status: Status,
+    #[response(header = "X-Request-Id")]
+    request_id: u64,
+}
+
+#[derive(Responder)]
+enum Envelope<'r, E: std::fmt::Display> {
+    #[response(status = self.1)]
+    Failed(&'r str, #[response(ignore)] Status, #[response(header = "X-Error")] E),
+    #[response(status = Status::new(self.code + 1))]
+    Coded {
+        body: &'r str,
+        #[response(ignore)]
+        code: u16,
+    },
+}
+
+#[rocket::async_test]
+async fn responder_with_computed_status_and_headers() {
+    let client = Client::debug_with(vec![]).await.expect("valid rocket");
+    let local_req = client.get("/");
+    let req = local_req.inner();
+
+    let error = ApiError { body: Json(vec![1, 2]), status: Status::Conflict, request_id: 42 };
+    let mut r = error.respond_to(req).unwrap();
+    assert_eq!(r.status(), Status::Conflict);
+    assert_eq!(r.content_type(), Some(ContentType::JSON));
+    assert_eq!(r.headers().get_one("X-Request-Id"), Some("42"));
+    assert_eq!(r.body_mut().to_string().await.unwrap(), "[1,2]");
+
+    let mut r = Envelope::Failed("oops", Status::BadGateway, 'x').respond_to(req).unwrap();
+    assert_eq!(r.status(), Status::BadGateway);
+    assert_eq!(r.headers().get_one("X-Error"), Some("x"));
+    assert_eq!(r.body_mut().to_string().await.unwrap(), "oops");
+
+    let coded: Envelope<'_, u8> = Envelope::Coded { body: "teapot", code: 417 };
+    let mut r = coded.respond_to(req).unwrap();
+    assert_eq!(r.status(), Status::ImATeapot);
+    assert_eq!(r.body_mut().to_string().await.unwrap(), "teapot");
+}
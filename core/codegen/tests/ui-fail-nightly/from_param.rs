@@ -0,0 +1 @@
+../ui-fail/from_param.rs
\ No newline at end of file
@@ -0,0 +1 @@
+../ui-fail/from_request.rs
\ No newline at end of file
@@ -0,0 +1 @@
+../ui-fail/typed_cookie.rs
\ No newline at end of file
@@ -4,11 +4,11 @@
 struct Foo1;
 
 #[derive(FromFormField)]
-struct Foo2(usize);
+struct Foo2(usize, usize);
 
 #[derive(FromFormField)]
 struct Foo3 {
-    foo: usize,
+    foo: usize, bar: usize,
 }
 
 #[derive(FromFormField)]
@@ -0,0 +1,43 @@
+#[macro_use] extern crate rocket;
+
+#[derive(FromParam)]
+struct Foo1;
+
+#[derive(FromParam)]
+struct Foo2(usize, usize);
+
+#[derive(FromParam)]
+#[param(case_sensitive)]
+struct Foo3(usize);
+
+#[derive(FromParam)]
+enum Foo4 {
+    A(usize),
+}
+
+#[derive(FromParam)]
+enum Foo5 { }
+
+#[derive(FromParam)]
+enum Bar1 {
+    A,
+    #[param(value = "a")]
+    B,
+}
+
+#[derive(FromParam)]
+enum Bar2 {
+    #[param(value = "a/b")]
+    A,
+}
+
+#[derive(FromParam)]
+#[param(status = 700)]
+enum Bar3 {
+    A,
+}
+
+#[derive(FromParam)]
+struct Bar4<'a, 'b>(&'a str, &'b str);
+
+fn main() { }
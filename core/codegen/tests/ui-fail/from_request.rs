@@ -0,0 +1,18 @@
+#[macro_use] extern crate rocket;
+
+#[derive(FromRequest)]
+struct Foo1;
+
+#[derive(FromRequest)]
+struct Foo2 { }
+
+#[derive(FromRequest)]
+enum Foo3 { A(rocket::http::Method) }
+
+#[derive(FromRequest)]
+struct Foo4<'a, 'b>(&'a rocket::Route, &'b rocket::Route);
+
+#[derive(FromRequest)]
+struct Foo5<T>(T);
+
+fn main() { }
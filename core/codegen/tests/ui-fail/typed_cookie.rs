@@ -0,0 +1,23 @@
+#[macro_use] extern crate rocket;
+
+#[derive(TypedCookie)]
+#[cookie(encoding = "xml")]
+struct Foo1;
+
+#[derive(TypedCookie)]
+#[cookie(privacy = "secret")]
+struct Foo2;
+
+#[derive(TypedCookie)]
+#[cookie(max_age = "soon")]
+struct Foo3;
+
+#[derive(TypedCookie)]
+#[cookie(name = 10)]
+struct Foo4;
+
+#[derive(TypedCookie)]
+#[cookie(what = "ever")]
+struct Foo5;
+
+fn main() { }
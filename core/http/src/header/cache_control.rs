@@ -0,0 +1,349 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Header;
+use crate::header::list;
+
+/// The HTTP Cache-Control header.
+///
+/// A `Cache-Control` header is a list of [`CacheDirective`]s. In requests, it
+/// describes the caching behavior a client expects. In responses, it
+/// describes how and for how long a response may be cached.
+///
+/// # Usage
+///
+/// The Cache-Control header of an incoming request can be retrieved via the
+/// `CacheControl` request guard, parsed from a header value via [`FromStr`],
+/// or constructed from directives:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::{CacheControl, CacheDirective};
+///
+/// let cc = CacheControl::new([CacheDirective::Public, CacheDirective::MaxAge(3600)]);
+/// assert_eq!(cc.to_string(), "public, max-age=3600");
+///
+/// let cc: CacheControl = "no-cache, max-age=0".parse().unwrap();
+/// assert!(cc.contains(&CacheDirective::NoCache));
+/// assert_eq!(cc.max_age(), Some(0));
+/// ```
+///
+/// # Header
+///
+/// `CacheControl` implements `Into<Header>`. As such, it can be used in any
+/// context where an `Into<Header>` is expected:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::CacheControl;
+/// use rocket::response::Response;
+///
+/// let response = Response::build().header(CacheControl::no_store()).finalize();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheControl(Vec<CacheDirective>);
+
+/// A directive in a [`CacheControl`] header.
+///
+/// Durations are in seconds.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CacheDirective {
+    /// `no-cache`: a cache must revalidate before reusing a response.
+    NoCache,
+    /// `no-store`: a cache must not store the request or response.
+    NoStore,
+    /// `no-transform`: intermediaries must not transform the payload.
+    NoTransform,
+    /// `only-if-cached`: the client wants only a stored response.
+    OnlyIfCached,
+    /// `max-age`: the age after which a response is stale.
+    MaxAge(u64),
+    /// `s-maxage`: `max-age` for shared caches.
+    SMaxAge(u64),
+    /// `max-stale`: the client accepts a response that's stale by at most the
+    /// given age, or by any age if there is none.
+    MaxStale(Option<u64>),
+    /// `min-fresh`: the client wants a response that will be fresh for at
+    /// least the given duration.
+    MinFresh(u64),
+    /// `public`: any cache may store the response.
+    Public,
+    /// `private`: only private caches may store the response.
+    Private,
+    /// `must-revalidate`: a cache must revalidate a stale response.
+    MustRevalidate,
+    /// `proxy-revalidate`: `must-revalidate` for shared caches.
+    ProxyRevalidate,
+    /// `must-understand`: a cache must understand the response's status code
+    /// to store it.
+    MustUnderstand,
+    /// `immutable`: the response won't change while it's fresh.
+    Immutable,
+    /// `stale-while-revalidate`: a cache may reuse a response that's stale by
+    /// at most the given age while it revalidates it.
+    StaleWhileRevalidate(u64),
+    /// `stale-if-error`: a cache may reuse a response that's stale by at most
+    /// the given age when revalidation fails.
+    StaleIfError(u64),
+    /// Any other directive, with an optional argument. This includes the
+    /// field-name forms of `no-cache` and `private`, like
+    /// `private="Set-Cookie"`.
+    Extension(String, Option<String>),
+}
+
+impl CacheControl {
+    /// Returns a `CacheControl` header with the directives `directives`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{CacheControl, CacheDirective};
+    ///
+    /// let cc = CacheControl::new([CacheDirective::Private, CacheDirective::NoCache]);
+    /// assert_eq!(cc.to_string(), "private, no-cache");
+    /// ```
+    pub fn new<I: IntoIterator<Item = CacheDirective>>(directives: I) -> CacheControl {
+        CacheControl(directives.into_iter().collect())
+    }
+
+    /// Returns a `CacheControl` header with the single directive `no-store`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::CacheControl;
+    ///
+    /// assert_eq!(CacheControl::no_store().to_string(), "no-store");
+    /// ```
+    pub fn no_store() -> CacheControl {
+        CacheControl::new([CacheDirective::NoStore])
+    }
+
+    /// Returns a `CacheControl` header allowing any cache to store a response
+    /// for `max_age` seconds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::CacheControl;
+    ///
+    /// assert_eq!(CacheControl::public(60).to_string(), "public, max-age=60");
+    /// ```
+    pub fn public(max_age: u64) -> CacheControl {
+        CacheControl::new([CacheDirective::Public, CacheDirective::MaxAge(max_age)])
+    }
+
+    /// Adds the directive `directive`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{CacheControl, CacheDirective};
+    ///
+    /// let cc = CacheControl::public(60).with(CacheDirective::Immutable);
+    /// assert_eq!(cc.to_string(), "public, max-age=60, immutable");
+    /// ```
+    pub fn with(mut self, directive: CacheDirective) -> CacheControl {
+        self.0.push(directive);
+        self
+    }
+
+    /// Returns an iterator over the directives.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{CacheControl, CacheDirective};
+    ///
+    /// let cc: CacheControl = "no-cache, x-custom=1".parse().unwrap();
+    /// let mut directives = cc.directives();
+    /// assert_eq!(directives.next(), Some(&CacheDirective::NoCache));
+    /// assert_eq!(directives.next(), Some(
+    ///     &CacheDirective::Extension("x-custom".into(), Some("1".into()))
+    /// ));
+    /// ```
+    pub fn directives(&self) -> impl Iterator<Item = &CacheDirective> {
+        self.0.iter()
+    }
+
+    /// Returns `true` if the header contains `directive`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{CacheControl, CacheDirective};
+    ///
+    /// let cc = CacheControl::public(60);
+    /// assert!(cc.contains(&CacheDirective::Public));
+    /// assert!(!cc.contains(&CacheDirective::Private));
+    /// ```
+    pub fn contains(&self, directive: &CacheDirective) -> bool {
+        self.0.contains(directive)
+    }
+
+    /// Returns the `max-age` in seconds, if there is one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::CacheControl;
+    ///
+    /// assert_eq!(CacheControl::public(60).max_age(), Some(60));
+    /// assert_eq!(CacheControl::no_store().max_age(), None);
+    /// ```
+    pub fn max_age(&self) -> Option<u64> {
+        self.0.iter().find_map(|d| match d {
+            CacheDirective::MaxAge(age) => Some(*age),
+            _ => None,
+        })
+    }
+}
+
+impl FromStr for CacheControl {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<CacheControl, String> {
+        list::split(raw).map(CacheDirective::from_str).collect::<Result<_, _>>().map(CacheControl)
+    }
+}
+
+impl FromStr for CacheDirective {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<CacheDirective, String> {
+        use CacheDirective::*;
+
+        let (name, arg) = match raw.split_once('=') {
+            Some((name, arg)) => (name.trim(), Some(list::unquote(arg.trim()))),
+            None => (raw.trim(), None),
+        };
+
+        if !list::is_token(name) {
+            return Err(format!("invalid cache directive: {:?}", raw));
+        }
+
+        let seconds = || -> Result<u64, String> {
+            arg.as_deref()
+                .and_then(|arg| arg.parse().ok())
+                .ok_or_else(|| format!("invalid duration in cache directive: {:?}", raw))
+        };
+
+        let directive = match &*name.to_ascii_lowercase() {
+            "no-cache" if arg.is_none() => NoCache,
+            "no-store" => NoStore,
+            "no-transform" => NoTransform,
+            "only-if-cached" => OnlyIfCached,
+            "max-age" => MaxAge(seconds()?),
+            "s-maxage" => SMaxAge(seconds()?),
+            "max-stale" if arg.is_none() => MaxStale(None),
+            "max-stale" => MaxStale(Some(seconds()?)),
+            "min-fresh" => MinFresh(seconds()?),
+            "public" => Public,
+            "private" if arg.is_none() => Private,
+            "must-revalidate" => MustRevalidate,
+            "proxy-revalidate" => ProxyRevalidate,
+            "must-understand" => MustUnderstand,
+            "immutable" => Immutable,
+            "stale-while-revalidate" => StaleWhileRevalidate(seconds()?),
+            "stale-if-error" => StaleIfError(seconds()?),
+            _ => Extension(name.to_string(), arg.map(|arg| arg.into_owned())),
+        };
+
+        Ok(directive)
+    }
+}
+
+impl fmt::Display for CacheControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, directive) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+
+            write!(f, "{}", directive)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for CacheDirective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use CacheDirective::*;
+
+        match self {
+            NoCache => f.write_str("no-cache"),
+            NoStore => f.write_str("no-store"),
+            NoTransform => f.write_str("no-transform"),
+            OnlyIfCached => f.write_str("only-if-cached"),
+            MaxAge(age) => write!(f, "max-age={}", age),
+            SMaxAge(age) => write!(f, "s-maxage={}", age),
+            MaxStale(None) => f.write_str("max-stale"),
+            MaxStale(Some(age)) => write!(f, "max-stale={}", age),
+            MinFresh(age) => write!(f, "min-fresh={}", age),
+            Public => f.write_str("public"),
+            Private => f.write_str("private"),
+            MustRevalidate => f.write_str("must-revalidate"),
+            ProxyRevalidate => f.write_str("proxy-revalidate"),
+            MustUnderstand => f.write_str("must-understand"),
+            Immutable => f.write_str("immutable"),
+            StaleWhileRevalidate(age) => write!(f, "stale-while-revalidate={}", age),
+            StaleIfError(age) => write!(f, "stale-if-error={}", age),
+            Extension(name, None) => f.write_str(name),
+            Extension(name, Some(arg)) if list::is_token(arg) => write!(f, "{}={}", name, arg),
+            Extension(name, Some(arg)) => write!(f, "{}={}", name, list::quote(arg)),
+        }
+    }
+}
+
+/// Creates a new `Header` with name `Cache-Control` and the value set to the
+/// HTTP rendering of this `CacheControl` header.
+impl From<CacheControl> for Header<'static> {
+    #[inline(always)]
+    fn from(val: CacheControl) -> Self {
+        Header::new("Cache-Control", val.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CacheControl, CacheDirective::*};
+
+    #[test]
+    fn test_parse() {
+        let cc: CacheControl = "Max-Age=60, no-cache, private=\"Set-Cookie, X\", \
+            max-stale, s-maxage=\"10\", x-ext".parse().unwrap();
+
+        assert_eq!(cc, CacheControl::new([
+            MaxAge(60),
+            NoCache,
+            Extension("private".into(), Some("Set-Cookie, X".into())),
+            MaxStale(None),
+            SMaxAge(10),
+            Extension("x-ext".into(), None),
+        ]));
+
+        assert!("max-age".parse::<CacheControl>().is_err());
+        assert!("max-age=-1".parse::<CacheControl>().is_err());
+        assert!("max age=1".parse::<CacheControl>().is_err());
+        assert_eq!("".parse::<CacheControl>().unwrap(), CacheControl::new([]));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let cc = CacheControl::public(60)
+            .with(StaleIfError(5))
+            .with(Extension("private".into(), Some("Set-Cookie, X".into())));
+
+        let rendered = cc.to_string();
+        assert_eq!(rendered, r#"public, max-age=60, stale-if-error=5, private="Set-Cookie, X""#);
+        assert_eq!(rendered.parse::<CacheControl>().unwrap(), cc);
+    }
+}
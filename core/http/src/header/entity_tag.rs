@@ -0,0 +1,320 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Header;
+use crate::header::list;
+
+/// An entity tag: an opaque identifier for a version of a resource.
+///
+/// Entity tags are sent in [`ETag`] response headers and compared against
+/// those in [`IfNoneMatch`] request headers. A _weak_ tag identifies a
+/// semantically, but not necessarily byte-for-byte, equivalent version.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::EntityTag;
+///
+/// let tag = EntityTag::strong("v1").unwrap();
+/// assert_eq!(tag.to_string(), r#""v1""#);
+///
+/// let tag: EntityTag = r#"W/"v1""#.parse().unwrap();
+/// assert!(tag.is_weak());
+/// assert_eq!(tag.tag(), "v1");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EntityTag {
+    weak: bool,
+    tag: String,
+}
+
+/// The HTTP ETag header.
+///
+/// # Header
+///
+/// `ETag` implements `Into<Header>`. As such, it can be used in any context
+/// where an `Into<Header>` is expected:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::{ETag, EntityTag};
+/// use rocket::response::Response;
+///
+/// let tag = EntityTag::strong("v1").unwrap();
+/// let response = Response::build().header(ETag(tag)).finalize();
+/// assert_eq!(response.headers().get_one("ETag"), Some(r#""v1""#));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ETag(pub EntityTag);
+
+/// The HTTP If-None-Match header.
+///
+/// An `If-None-Match` header makes a request conditional on the current
+/// version of a resource _not_ matching any of a list of entity tags, or, for
+/// `*`, on the resource not existing. A client typically sends the entity tag
+/// of its cached copy so that a server can respond with `304 Not Modified`
+/// when the copy is current.
+///
+/// # Usage
+///
+/// The If-None-Match header of an incoming request can be retrieved via the
+/// `IfNoneMatch` request guard:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::{ETag, EntityTag, IfNoneMatch};
+///
+/// #[derive(Responder)]
+/// enum Cached {
+///     Fresh(&'static str, ETag),
+///     #[response(status = 304)]
+///     NotModified(()),
+/// }
+///
+/// #[get("/")]
+/// fn index(if_none_match: Option<IfNoneMatch>) -> Cached {
+///     let tag = EntityTag::strong("v1").unwrap();
+///     match if_none_match {
+///         Some(header) if header.matches(&tag) => Cached::NotModified(()),
+///         _ => Cached::Fresh("Hello, world!", ETag(tag)),
+///     }
+/// }
+/// ```
+///
+/// # Header
+///
+/// `IfNoneMatch` implements `Into<Header>`. As such, it can be used in any
+/// context where an `Into<Header>` is expected.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum IfNoneMatch {
+    /// `*`: matches any version of the resource.
+    Any,
+    /// Matches any of the entity tags.
+    Tags(Vec<EntityTag>),
+}
+
+impl EntityTag {
+    /// Returns a strong entity tag with value `tag`, or `None` if `tag`
+    /// contains characters that can't appear in an entity tag, like `"`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::EntityTag;
+    ///
+    /// assert!(EntityTag::strong("abc123").is_some());
+    /// assert!(EntityTag::strong("a\"b").is_none());
+    /// ```
+    pub fn strong<T: Into<String>>(tag: T) -> Option<EntityTag> {
+        EntityTag::new(false, tag.into())
+    }
+
+    /// Returns a weak entity tag with value `tag`, or `None` if `tag`
+    /// contains characters that can't appear in an entity tag, like `"`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::EntityTag;
+    ///
+    /// let tag = EntityTag::weak("abc123").unwrap();
+    /// assert_eq!(tag.to_string(), r#"W/"abc123""#);
+    /// ```
+    pub fn weak<T: Into<String>>(tag: T) -> Option<EntityTag> {
+        EntityTag::new(true, tag.into())
+    }
+
+    fn new(weak: bool, tag: String) -> Option<EntityTag> {
+        let valid = |b: u8| b == 0x21 || (0x23..=0x7E).contains(&b) || b >= 0x80;
+        tag.bytes().all(valid).then(|| EntityTag { weak, tag })
+    }
+
+    /// Returns `true` if the tag is weak.
+    pub fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    /// Returns the value of the tag, without quotes.
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Returns `true` if `self` and `other` are equal under the strong
+    /// comparison: neither is weak and their values are equal.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::EntityTag;
+    ///
+    /// let strong = EntityTag::strong("v1").unwrap();
+    /// let weak = EntityTag::weak("v1").unwrap();
+    /// assert!(strong.strong_eq(&strong));
+    /// assert!(!strong.strong_eq(&weak));
+    /// ```
+    pub fn strong_eq(&self, other: &EntityTag) -> bool {
+        !self.weak && !other.weak && self.tag == other.tag
+    }
+
+    /// Returns `true` if `self` and `other` are equal under the weak
+    /// comparison: their values are equal.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::EntityTag;
+    ///
+    /// let strong = EntityTag::strong("v1").unwrap();
+    /// let weak = EntityTag::weak("v1").unwrap();
+    /// assert!(strong.weak_eq(&weak));
+    /// ```
+    pub fn weak_eq(&self, other: &EntityTag) -> bool {
+        self.tag == other.tag
+    }
+}
+
+impl IfNoneMatch {
+    /// Returns `true` if the current version of a resource, with entity tag
+    /// `tag`, matches the header under the weak comparison. Such a request's
+    /// condition is false: a `GET` or `HEAD` should be answered with `304 Not
+    /// Modified`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{IfNoneMatch, EntityTag};
+    ///
+    /// let header: IfNoneMatch = r#""v1", W/"v2""#.parse().unwrap();
+    /// assert!(header.matches(&EntityTag::strong("v2").unwrap()));
+    /// assert!(!header.matches(&EntityTag::strong("v3").unwrap()));
+    /// assert!(IfNoneMatch::Any.matches(&EntityTag::strong("v3").unwrap()));
+    /// ```
+    pub fn matches(&self, tag: &EntityTag) -> bool {
+        match self {
+            IfNoneMatch::Any => true,
+            IfNoneMatch::Tags(tags) => tags.iter().any(|t| t.weak_eq(tag)),
+        }
+    }
+}
+
+impl FromStr for EntityTag {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<EntityTag, String> {
+        let raw = raw.trim();
+        let (weak, quoted) = match raw.strip_prefix("W/") {
+            Some(quoted) => (true, quoted),
+            None => (false, raw),
+        };
+
+        quoted.strip_prefix('"')
+            .and_then(|s| s.strip_suffix('"'))
+            .and_then(|tag| EntityTag::new(weak, tag.into()))
+            .ok_or_else(|| format!("invalid entity tag: {:?}", raw))
+    }
+}
+
+impl FromStr for ETag {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<ETag, String> {
+        EntityTag::from_str(raw).map(ETag)
+    }
+}
+
+impl FromStr for IfNoneMatch {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<IfNoneMatch, String> {
+        if raw.trim() == "*" {
+            return Ok(IfNoneMatch::Any);
+        }
+
+        let tags = list::split(raw)
+            .map(EntityTag::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(IfNoneMatch::Tags(tags))
+    }
+}
+
+impl fmt::Display for EntityTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.weak {
+            true => write!(f, "W/\"{}\"", self.tag),
+            false => write!(f, "\"{}\"", self.tag),
+        }
+    }
+}
+
+impl fmt::Display for ETag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for IfNoneMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IfNoneMatch::Any => f.write_str("*"),
+            IfNoneMatch::Tags(tags) => {
+                for (i, tag) in tags.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+
+                    write!(f, "{}", tag)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Creates a new `Header` with name `ETag` and the value set to the HTTP
+/// rendering of this entity tag.
+impl From<ETag> for Header<'static> {
+    #[inline(always)]
+    fn from(val: ETag) -> Self {
+        Header::new("ETag", val.to_string())
+    }
+}
+
+/// Creates a new `Header` with name `If-None-Match` and the value set to the
+/// HTTP rendering of this `IfNoneMatch` header.
+impl From<IfNoneMatch> for Header<'static> {
+    #[inline(always)]
+    fn from(val: IfNoneMatch) -> Self {
+        Header::new("If-None-Match", val.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EntityTag, IfNoneMatch};
+
+    #[test]
+    fn test_parse() {
+        let header: IfNoneMatch = r#" "a", W/"b" ,"c,d" "#.parse().unwrap();
+        let expected = IfNoneMatch::Tags(vec![
+            EntityTag::strong("a").unwrap(),
+            EntityTag::weak("b").unwrap(),
+            EntityTag::strong("c,d").unwrap(),
+        ]);
+
+        assert_eq!(header, expected);
+        assert_eq!(header.to_string().parse::<IfNoneMatch>().unwrap(), expected);
+        assert_eq!(" * ".parse::<IfNoneMatch>().unwrap(), IfNoneMatch::Any);
+
+        for invalid in &["a", r#"w/"a""#, r#""a"b""#, r#""a", *"#, "\""] {
+            assert!(invalid.parse::<IfNoneMatch>().is_err(), "{:?} parsed", invalid);
+        }
+    }
+}
@@ -8,6 +8,8 @@ macro_rules! known_media_types {
         Text (is_text): "plain text", "text", "plain" ; "charset" => "utf-8",
         JSON (is_json): "JSON", "application", "json",
         MsgPack (is_msgpack): "MsgPack", "application", "msgpack",
+        NDJSON (is_ndjson): "JSON Lines", "application", "x-ndjson",
+        CBOR (is_cbor): "CBOR", "application", "cbor",
         Form (is_form): "forms", "application", "x-www-form-urlencoded",
         JavaScript (is_javascript): "JavaScript", "application", "javascript",
         CSS (is_css): "CSS", "text", "css" ; "charset" => "utf-8",
@@ -67,6 +69,8 @@ macro_rules! known_extensions {
         "js" => JavaScript,
         "css" => CSS,
         "json" => JSON,
+        "ndjson" => NDJSON,
+        "cbor" => CBOR,
         "png" => PNG,
         "gif" => GIF,
         "bmp" => BMP,
@@ -121,6 +125,8 @@ macro_rules! known_shorthands {
         "text" => Text,
         "json" => JSON,
         "msgpack" => MsgPack,
+        "ndjson" => NDJSON,
+        "cbor" => CBOR,
         "form" => Form,
         "js" => JavaScript,
         "css" => CSS,
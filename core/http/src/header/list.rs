@@ -0,0 +1,86 @@
+/// Splits a comma-separated header value into its trimmed, non-empty
+/// elements, ignoring commas inside of quoted strings.
+pub(crate) fn split(raw: &str) -> impl Iterator<Item = &str> {
+    let mut quoted = false;
+    let mut escaped = false;
+    raw.split(move |c| {
+        match (c, quoted, escaped) {
+            (_, true, true) => escaped = false,
+            ('\\', true, false) => escaped = true,
+            ('"', _, false) => quoted = !quoted,
+            (',', false, _) => return true,
+            _ => {}
+        }
+
+        false
+    })
+    .map(|item| item.trim())
+    .filter(|item| !item.is_empty())
+}
+
+/// Returns the contents of the quoted string `raw` with escapes removed, or
+/// `raw` itself if it isn't quoted.
+pub(crate) fn unquote(raw: &str) -> std::borrow::Cow<'_, str> {
+    match raw.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        Some(inner) if inner.contains('\\') => {
+            let mut string = String::with_capacity(inner.len());
+            let mut chars = inner.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => string.extend(chars.next()),
+                    c => string.push(c),
+                }
+            }
+
+            string.into()
+        }
+        Some(inner) => inner.into(),
+        None => raw.into(),
+    }
+}
+
+/// Returns `raw` as a quoted string, escaping quotes and backslashes.
+pub(crate) fn quote(raw: &str) -> String {
+    let mut string = String::with_capacity(raw.len() + 2);
+    string.push('"');
+    for c in raw.chars() {
+        if c == '"' || c == '\\' {
+            string.push('\\');
+        }
+
+        string.push(c);
+    }
+
+    string.push('"');
+    string
+}
+
+/// Returns `true` if `s` is a valid HTTP token.
+pub(crate) fn is_token(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| {
+        b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split() {
+        let items = |s| split(s).collect::<Vec<_>>();
+        assert_eq!(items("a, b,c"), ["a", "b", "c"]);
+        assert_eq!(items(" a ,, , b "), ["a", "b"]);
+        assert_eq!(items(r#"a="x, y", b"#), [r#"a="x, y""#, "b"]);
+        assert_eq!(items(r#"a="x\", y", b"#), [r#"a="x\", y""#, "b"]);
+        assert!(items("").is_empty());
+    }
+
+    #[test]
+    fn test_unquote() {
+        assert_eq!(unquote("abc"), "abc");
+        assert_eq!(unquote(r#""abc""#), "abc");
+        assert_eq!(unquote(r#""a\"b\\c""#), r#"a"b\c"#);
+        assert_eq!(quote(r#"a"b\c"#), r#""a\"b\\c""#);
+    }
+}
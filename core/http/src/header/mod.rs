@@ -4,10 +4,19 @@ mod media_type;
 mod content_type;
 mod accept;
 mod header;
+mod list;
+mod cache_control;
+mod authorization;
+mod range;
+mod entity_tag;
 
 pub use self::content_type::ContentType;
 pub use self::accept::{Accept, QMediaType};
 pub use self::media_type::MediaType;
 pub use self::header::{Header, HeaderMap};
+pub use self::cache_control::{CacheControl, CacheDirective};
+pub use self::authorization::Authorization;
+pub use self::range::{Range, ByteRange};
+pub use self::entity_tag::{EntityTag, ETag, IfNoneMatch};
 
 pub(crate) use self::media_type::Source;
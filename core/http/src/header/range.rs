@@ -0,0 +1,219 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Header;
+use crate::header::list;
+
+/// The HTTP Range header.
+///
+/// A `Range` header requests one or more [`ByteRange`]s of a resource instead
+/// of the entire resource. Only the `bytes` range unit is supported.
+///
+/// # Usage
+///
+/// The Range header of an incoming request can be retrieved via the `Range`
+/// request guard. Since ranges may be relative to the end of a resource,
+/// they're resolved against the resource's length with
+/// [`ByteRange::resolve()`]:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::Range;
+///
+/// let range: Range = "bytes=0-99, -50".parse().unwrap();
+/// let resolved = range.ranges()
+///     .iter()
+///     .filter_map(|r| r.resolve(1000))
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(resolved, [0..100, 950..1000]);
+/// ```
+///
+/// # Header
+///
+/// `Range` implements `Into<Header>`. As such, it can be used in any context
+/// where an `Into<Header>` is expected, like a local request:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::{Range, ByteRange};
+/// use rocket::local::blocking::Client;
+///
+/// let client = Client::debug_with(vec![]).unwrap();
+/// let request = client.get("/").header(Range::new([ByteRange::From(100)]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Range(Vec<ByteRange>);
+
+/// A range of bytes in a [`Range`] header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ByteRange {
+    /// `first-last`: the bytes from `first` to `last`, inclusive.
+    FromTo(u64, u64),
+    /// `first-`: the bytes from `first` to the end.
+    From(u64),
+    /// `-length`: the last `length` bytes.
+    Last(u64),
+}
+
+impl Range {
+    /// Returns a `Range` header requesting the byte ranges `ranges`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{Range, ByteRange};
+    ///
+    /// let range = Range::new([ByteRange::FromTo(0, 499), ByteRange::Last(10)]);
+    /// assert_eq!(range.to_string(), "bytes=0-499, -10");
+    /// ```
+    pub fn new<I: IntoIterator<Item = ByteRange>>(ranges: I) -> Range {
+        Range(ranges.into_iter().collect())
+    }
+
+    /// Returns the requested byte ranges.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{Range, ByteRange};
+    ///
+    /// let range: Range = "bytes=10-".parse().unwrap();
+    /// assert_eq!(range.ranges(), &[ByteRange::From(10)]);
+    /// ```
+    pub fn ranges(&self) -> &[ByteRange] {
+        &self.0
+    }
+}
+
+impl ByteRange {
+    /// Resolves `self` against a resource of `len` bytes, returning the
+    /// half-open range of byte offsets it selects, clamped to the resource.
+    /// Returns `None` if the range isn't satisfiable.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::ByteRange;
+    ///
+    /// assert_eq!(ByteRange::FromTo(10, 19).resolve(100), Some(10..20));
+    /// assert_eq!(ByteRange::FromTo(90, 199).resolve(100), Some(90..100));
+    /// assert_eq!(ByteRange::From(50).resolve(100), Some(50..100));
+    /// assert_eq!(ByteRange::Last(500).resolve(100), Some(0..100));
+    ///
+    /// assert_eq!(ByteRange::From(100).resolve(100), None);
+    /// assert_eq!(ByteRange::Last(0).resolve(100), None);
+    /// ```
+    pub fn resolve(&self, len: u64) -> Option<std::ops::Range<u64>> {
+        let range = match *self {
+            ByteRange::FromTo(first, last) => first..last.saturating_add(1).min(len),
+            ByteRange::From(first) => first..len,
+            ByteRange::Last(length) => len.saturating_sub(length)..len,
+        };
+
+        (range.start < range.end).then(|| range)
+    }
+}
+
+impl FromStr for Range {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<Range, String> {
+        let (unit, ranges) = raw.trim().split_once('=')
+            .ok_or_else(|| format!("invalid range: {:?}", raw))?;
+
+        if !unit.trim().eq_ignore_ascii_case("bytes") {
+            return Err(format!("unsupported range unit: {:?}", unit));
+        }
+
+        let ranges = list::split(ranges)
+            .map(ByteRange::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if ranges.is_empty() {
+            return Err(format!("empty range: {:?}", raw));
+        }
+
+        Ok(Range(ranges))
+    }
+}
+
+impl FromStr for ByteRange {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<ByteRange, String> {
+        let invalid = || format!("invalid byte range: {:?}", raw);
+        let parse = |s: &str| -> Result<u64, String> {
+            match s.bytes().all(|b| b.is_ascii_digit()) {
+                true => s.parse().map_err(|_| invalid()),
+                false => Err(invalid()),
+            }
+        };
+
+        let range = match raw.trim().split_once('-').ok_or_else(invalid)? {
+            ("", length) => ByteRange::Last(parse(length)?),
+            (first, "") => ByteRange::From(parse(first)?),
+            (first, last) => ByteRange::FromTo(parse(first)?, parse(last)?),
+        };
+
+        match range {
+            ByteRange::FromTo(first, last) if last < first => Err(invalid()),
+            range => Ok(range),
+        }
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("bytes=")?;
+        for (i, range) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+
+            write!(f, "{}", range)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for ByteRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ByteRange::FromTo(first, last) => write!(f, "{}-{}", first, last),
+            ByteRange::From(first) => write!(f, "{}-", first),
+            ByteRange::Last(length) => write!(f, "-{}", length),
+        }
+    }
+}
+
+/// Creates a new `Header` with name `Range` and the value set to the HTTP
+/// rendering of this `Range` header.
+impl From<Range> for Header<'static> {
+    #[inline(always)]
+    fn from(val: Range) -> Self {
+        Header::new("Range", val.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Range, ByteRange::*};
+
+    #[test]
+    fn test_parse() {
+        let range: Range = "Bytes = 0-0, 5-, -7,".parse().unwrap();
+        assert_eq!(range, Range::new([FromTo(0, 0), From(5), Last(7)]));
+        assert_eq!(range.to_string().parse::<Range>().unwrap(), range);
+
+        for invalid in &["", "bytes", "bytes=", "bytes=-", "bytes=5-1", "bytes=a-b",
+            "bytes=+1-2", "bytes=1--2", "items=0-1", "bytes=99999999999999999999-"]
+        {
+            assert!(invalid.parse::<Range>().is_err(), "{:?} parsed", invalid);
+        }
+    }
+}
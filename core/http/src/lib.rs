@@ -28,6 +28,7 @@ mod status;
 mod raw_str;
 mod parse;
 mod listener;
+mod proxy;
 
 /// Case-preserving, ASCII case-insensitive string types.
 ///
@@ -45,6 +46,9 @@ pub mod private {
     pub use crate::parse::Indexed;
     pub use smallvec::{SmallVec, Array};
     pub use crate::listener::{TcpListener, Incoming, Listener, Connection, Certificates};
+    pub use crate::proxy::ProxyListener;
+    #[cfg(unix)]
+    pub use crate::listener::UnixListener;
     pub use cookie;
 }
 
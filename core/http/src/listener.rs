@@ -16,6 +16,9 @@ use state::Storage;
 
 pub use tokio::net::TcpListener;
 
+#[cfg(unix)]
+pub use tokio::net::{UnixListener, UnixStream};
+
 /// A thin wrapper over raw, DER-encoded X.509 client certificate data.
 // NOTE: `rustls::Certificate` is exactly isomorphic to `CertificateData`.
 #[doc(inline)]
@@ -240,3 +243,37 @@ impl Connection for TcpStream {
         self.set_nodelay(true)
     }
 }
+
+#[cfg(unix)]
+impl Listener for UnixListener {
+    type Connection = UnixStream;
+
+    /// Unix domain sockets have no socket address: always `None`.
+    #[inline]
+    fn local_addr(&self) -> Option<SocketAddr> {
+        None
+    }
+
+    #[inline]
+    fn poll_accept(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>
+    ) -> Poll<io::Result<Self::Connection>> {
+        (*self).poll_accept(cx).map_ok(|(stream, _addr)| stream)
+    }
+}
+
+#[cfg(unix)]
+impl Connection for UnixStream {
+    /// Unix domain sockets have no socket address: always `None`.
+    #[inline]
+    fn peer_address(&self) -> Option<SocketAddr> {
+        None
+    }
+
+    /// There is no delay to disable on Unix domain sockets: does nothing.
+    #[inline]
+    fn enable_nodelay(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -5,10 +5,33 @@ use self::Method::*;
 
 use crate::hyper;
 
-// TODO: Support non-standard methods, here and in codegen?
-
 /// Representation of HTTP methods.
 ///
+/// # Extension Methods
+///
+/// Methods other than the standard methods, such as WebDAV's `PROPFIND` or
+/// CalDAV's `MKCALENDAR`, are represented by [`Method::Extension`]. Method
+/// names are case-sensitive: `Method::Extension("PROPFIND")` is only equal to
+/// itself. Routes for extension methods are declared with a string literal in
+/// place of a method in the `route` attribute:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use std::path::PathBuf;
+/// use rocket::http::Method;
+///
+/// #[route("PROPFIND", uri = "/dav/<path..>")]
+/// fn propfind(path: PathBuf) { /* .. */ }
+///
+/// let route = &routes![propfind][0];
+/// assert_eq!(route.method, Method::Extension("PROPFIND"));
+/// assert_eq!(route.method.as_str(), "PROPFIND");
+/// ```
+///
+/// An incoming request with an extension method is only accepted if some
+/// mounted route handles the method; otherwise, Rocket responds with a
+/// `400 Bad Request`.
+///
 /// # (De)serialization
 ///
 /// `Method` is both `Serialize` and `Deserialize`, represented as an
@@ -48,7 +71,10 @@ pub enum Method {
     /// The `CONNECT` variant.
     Connect,
     /// The `PATCH` variant.
-    Patch
+    Patch,
+    /// A non-standard extension method with the given name, such as
+    /// `PROPFIND`. See [extension methods](#extension-methods).
+    Extension(&'static str),
 }
 
 impl Method {
@@ -74,7 +100,7 @@ impl Method {
     ///
     /// The following methods always support payloads:
     ///
-    ///   * `PUT`, `POST`, `DELETE`, `PATCH`
+    ///   * `PUT`, `POST`, `DELETE`, `PATCH`, and all extension methods
     ///
     /// The following methods _do not_ always support payloads:
     ///
@@ -88,11 +114,12 @@ impl Method {
     ///
     /// assert_eq!(Method::Get.supports_payload(), false);
     /// assert_eq!(Method::Post.supports_payload(), true);
+    /// assert_eq!(Method::Extension("PROPFIND").supports_payload(), true);
     /// ```
     #[inline]
     pub fn supports_payload(self) -> bool {
         match self {
-            Put | Post | Delete | Patch => true,
+            Put | Post | Delete | Patch | Extension(_) => true,
             Get | Head | Connect | Trace | Options => false,
         }
     }
@@ -106,6 +133,7 @@ impl Method {
     /// use rocket::http::Method;
     ///
     /// assert_eq!(Method::Get.as_str(), "GET");
+    /// assert_eq!(Method::Extension("PROPFIND").as_str(), "PROPFIND");
     /// ```
     #[inline]
     pub fn as_str(self) -> &'static str {
@@ -119,6 +147,7 @@ impl Method {
             Trace => "TRACE",
             Connect => "CONNECT",
             Patch => "PATCH",
+            Extension(name) => name,
         }
     }
 }
@@ -128,6 +157,7 @@ impl FromStr for Method {
 
     // According to the RFC, method names are case-sensitive. But some old
     // clients don't follow this, so we just do a case-insensitive match here.
+    // Extension methods can't be parsed as they require a `'static` name.
     fn from_str(s: &str) -> Result<Method, ()> {
         match s {
             x if uncased::eq(x, Get.as_str()) => Ok(Get),
@@ -0,0 +1,329 @@
+//! Support for the [PROXY protocol], versions 1 and 2.
+//!
+//! [PROXY protocol]: https://www.haproxy.org/download/2.8/doc/proxy-protocol.txt
+
+use std::io;
+use std::future::Future;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use log::warn;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, ReadBuf};
+
+use crate::listener::{Certificates, Connection, Listener};
+
+/// The signature that begins a version 2 header.
+const V2_SIGNATURE: &[u8; 12] = b"\r\n\r\n\0\r\nQUIT\n";
+
+/// The maximum length of a version 1 header, including the trailing CRLF.
+const V1_MAX_LEN: usize = 107;
+
+/// How long a client has to send its header before the connection is closed.
+const HEADER_TIMEOUT: Duration = Duration::from_secs(5);
+
+type PendingStream<C> = Pin<Box<dyn Future<Output = io::Result<ProxyStream<C>>> + Send>>;
+
+/// A listener that, when enabled, reads a PROXY protocol header from every
+/// accepted connection before yielding it, reporting the address in the header
+/// as the connection's peer address.
+///
+/// Headers are read concurrently so that a slow client doesn't delay other
+/// connections from being accepted. Connections that don't begin with a valid
+/// header within a few seconds are closed.
+pub struct ProxyListener<L: Listener> {
+    listener: L,
+    enabled: bool,
+    pending: Vec<PendingStream<L::Connection>>,
+}
+
+/// A connection accepted by a [`ProxyListener`].
+pub struct ProxyStream<C> {
+    io: C,
+    remote: Option<SocketAddr>,
+    buffered: Vec<u8>,
+    pos: usize,
+}
+
+impl<L: Listener> ProxyListener<L> {
+    /// Wraps `listener`, reading PROXY protocol headers if `enabled`.
+    pub fn new(listener: L, enabled: bool) -> Self {
+        ProxyListener { listener, enabled, pending: vec![] }
+    }
+}
+
+impl<L> Listener for ProxyListener<L>
+    where L: Listener + Unpin, L::Connection: Send + Unpin + 'static
+{
+    type Connection = ProxyStream<L::Connection>;
+
+    fn local_addr(&self) -> Option<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    fn poll_accept(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>
+    ) -> Poll<io::Result<Self::Connection>> {
+        let this = &mut *self;
+        if !this.enabled {
+            return Pin::new(&mut this.listener).poll_accept(cx).map_ok(ProxyStream::direct);
+        }
+
+        loop {
+            match Pin::new(&mut this.listener).poll_accept(cx) {
+                Poll::Ready(Ok(io)) => this.pending.push(Box::pin(async move {
+                    match tokio::time::timeout(HEADER_TIMEOUT, ProxyStream::read(io)).await {
+                        Ok(result) => result,
+                        Err(_) => Err(io::Error::new(io::ErrorKind::TimedOut, "timed out")),
+                    }
+                })),
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => break,
+            }
+        }
+
+        let mut i = 0;
+        while i < this.pending.len() {
+            match this.pending[i].as_mut().poll(cx) {
+                Poll::Ready(result) => {
+                    drop(this.pending.swap_remove(i));
+                    match result {
+                        Ok(stream) => return Poll::Ready(Ok(stream)),
+                        Err(e) => warn!("closing connection without PROXY header: {}", e),
+                    }
+                }
+                Poll::Pending => i += 1,
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+impl<C: Connection + Unpin> ProxyStream<C> {
+    fn direct(io: C) -> Self {
+        ProxyStream { io, remote: None, buffered: vec![], pos: 0 }
+    }
+
+    /// Reads and parses the PROXY protocol header from `io`.
+    async fn read(mut io: C) -> io::Result<Self> {
+        let mut buf = Vec::with_capacity(V1_MAX_LEN);
+        let (remote, len) = loop {
+            if let Some(parsed) = parse(&buf)? {
+                break parsed;
+            }
+
+            let mut chunk = [0; 256];
+            let n = io.read(&mut chunk).await?;
+            if n == 0 {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+
+            buf.extend_from_slice(&chunk[..n]);
+        };
+
+        buf.drain(..len);
+        Ok(ProxyStream { io, remote, buffered: buf, pos: 0 })
+    }
+}
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Parses a complete PROXY protocol header at the start of `buf`, returning
+/// the source address it carries, if any, and the header's length. Returns
+/// `Ok(None)` if `buf` doesn't yet contain a complete header.
+fn parse(buf: &[u8]) -> io::Result<Option<(Option<SocketAddr>, usize)>> {
+    let prefix_len = buf.len().min(V2_SIGNATURE.len());
+    if buf[..prefix_len] == V2_SIGNATURE[..prefix_len] {
+        return parse_v2(buf);
+    }
+
+    let prefix_len = buf.len().min(6);
+    if buf[..prefix_len] == b"PROXY "[..prefix_len] {
+        return parse_v1(buf);
+    }
+
+    Err(invalid("missing PROXY protocol header"))
+}
+
+/// Parses a version 1 header: `PROXY TCP4 <src> <dst> <src port> <dst port>`
+/// or `PROXY UNKNOWN ...`, terminated by a CRLF.
+fn parse_v1(buf: &[u8]) -> io::Result<Option<(Option<SocketAddr>, usize)>> {
+    let end = match buf.windows(2).position(|w| w == b"\r\n") {
+        Some(end) => end,
+        None if buf.len() >= V1_MAX_LEN => return Err(invalid("PROXY v1 header is too long")),
+        None => return Ok(None),
+    };
+
+    let line = std::str::from_utf8(&buf[..end]).map_err(|_| invalid("invalid PROXY v1 header"))?;
+    let mut parts = line.split(' ').skip(1);
+    let remote = match parts.next() {
+        Some("TCP4") | Some("TCP6") => {
+            let ip: IpAddr = parts.next()
+                .and_then(|src| src.parse().ok())
+                .ok_or_else(|| invalid("invalid PROXY v1 source address"))?;
+
+            let port: u16 = parts.nth(1)
+                .and_then(|port| port.parse().ok())
+                .ok_or_else(|| invalid("invalid PROXY v1 source port"))?;
+
+            Some(SocketAddr::new(ip, port))
+        }
+        Some("UNKNOWN") => None,
+        _ => return Err(invalid("invalid PROXY v1 protocol")),
+    };
+
+    Ok(Some((remote, end + 2)))
+}
+
+/// Parses a version 2 header: the signature, a version and command byte, a
+/// family and protocol byte, a big-endian length, then the addresses and any
+/// TLVs, which are ignored.
+fn parse_v2(buf: &[u8]) -> io::Result<Option<(Option<SocketAddr>, usize)>> {
+    if buf.len() < 16 {
+        return Ok(None);
+    }
+
+    let len = 16 + u16::from_be_bytes([buf[14], buf[15]]) as usize;
+    if buf.len() < len {
+        return Ok(None);
+    }
+
+    let addrs = &buf[16..len];
+    let remote = match (buf[12], buf[13] >> 4) {
+        // `LOCAL`: the connection was established by the proxy itself.
+        (0x20, _) => None,
+        (0x21, 0x1) if addrs.len() >= 12 => {
+            let ip = Ipv4Addr::new(addrs[0], addrs[1], addrs[2], addrs[3]);
+            Some(SocketAddr::new(ip.into(), u16::from_be_bytes([addrs[8], addrs[9]])))
+        }
+        (0x21, 0x2) if addrs.len() >= 36 => {
+            let mut octets = [0; 16];
+            octets.copy_from_slice(&addrs[..16]);
+            let ip = Ipv6Addr::from(octets);
+            Some(SocketAddr::new(ip.into(), u16::from_be_bytes([addrs[32], addrs[33]])))
+        }
+        // `AF_UNSPEC` or `AF_UNIX`: there's no IP address to report.
+        (0x21, 0x0) | (0x21, 0x3) => None,
+        _ => return Err(invalid("invalid PROXY v2 header")),
+    };
+
+    Ok(Some((remote, len)))
+}
+
+impl<C: Connection + Unpin> Connection for ProxyStream<C> {
+    fn peer_address(&self) -> Option<SocketAddr> {
+        self.remote.or_else(|| self.io.peer_address())
+    }
+
+    fn enable_nodelay(&self) -> io::Result<()> {
+        self.io.enable_nodelay()
+    }
+
+    fn peer_certificates(&self) -> Option<Certificates> {
+        self.io.peer_certificates()
+    }
+}
+
+impl<C: AsyncRead + Unpin> AsyncRead for ProxyStream<C> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        if this.pos < this.buffered.len() {
+            let n = buf.remaining().min(this.buffered.len() - this.pos);
+            buf.put_slice(&this.buffered[this.pos..this.pos + n]);
+            this.pos += n;
+            if this.pos == this.buffered.len() {
+                this.buffered = vec![];
+                this.pos = 0;
+            }
+
+            return Poll::Ready(Ok(()));
+        }
+
+        Pin::new(&mut this.io).poll_read(cx, buf)
+    }
+}
+
+impl<C: AsyncWrite + Unpin> AsyncWrite for ProxyStream<C> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.io).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.io).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.io).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(buf: &[u8]) -> (Option<SocketAddr>, usize) {
+        parse(buf).unwrap().unwrap()
+    }
+
+    #[test]
+    fn parses_v1_headers() {
+        let header = b"PROXY TCP4 192.0.2.1 198.51.100.1 56324 443\r\nGET /";
+        assert_eq!(parsed(header), (Some("192.0.2.1:56324".parse().unwrap()), header.len() - 5));
+
+        let header = b"PROXY TCP6 2001:db8::1 2001:db8::2 4000 80\r\n";
+        assert_eq!(parsed(header), (Some("[2001:db8::1]:4000".parse().unwrap()), header.len()));
+
+        let header = b"PROXY UNKNOWN ffff::1 ffff::2 1 2\r\n";
+        assert_eq!(parsed(header), (None, header.len()));
+
+        assert!(parse(b"PROXY TCP4 192.0.2.1").unwrap().is_none());
+        assert!(parse(b"PROX").unwrap().is_none());
+        assert!(parse(b"PROXY TCP4 192.0.2.1 198.51.100.1 abc 443\r\n").is_err());
+        assert!(parse(b"PROXY UDP4 192.0.2.1 198.51.100.1 1 2\r\n").is_err());
+        assert!(parse(b"GET / HTTP/1.1\r\n").is_err());
+        assert!(parse(&[b"PROXY ".as_ref(), &[b'a'; V1_MAX_LEN]].concat()).is_err());
+    }
+
+    #[test]
+    fn parses_v2_headers() {
+        let mut header = V2_SIGNATURE.to_vec();
+        header.extend_from_slice(&[0x21, 0x11, 0, 12]);
+        header.extend_from_slice(&[192, 0, 2, 1, 198, 51, 100, 1, 0xdc, 0x04, 0x01, 0xbb]);
+        assert!(parse(&header[..20]).unwrap().is_none());
+        assert_eq!(parsed(&header), (Some("192.0.2.1:56324".parse().unwrap()), 28));
+
+        // TLVs after the addresses are skipped.
+        header[15] = 15;
+        header.extend_from_slice(&[0x04, 0, 0]);
+        header.extend_from_slice(b"GET /");
+        assert_eq!(parsed(&header), (Some("192.0.2.1:56324".parse().unwrap()), 31));
+
+        let mut header = V2_SIGNATURE.to_vec();
+        header.extend_from_slice(&[0x21, 0x21, 0, 36]);
+        header.extend_from_slice(&"2001:db8::1".parse::<Ipv6Addr>().unwrap().octets());
+        header.extend_from_slice(&[0; 16]);
+        header.extend_from_slice(&[0x0f, 0xa0, 0, 80]);
+        assert_eq!(parsed(&header), (Some("[2001:db8::1]:4000".parse().unwrap()), 52));
+
+        let mut local = V2_SIGNATURE.to_vec();
+        local.extend_from_slice(&[0x20, 0x00, 0, 0]);
+        assert_eq!(parsed(&local), (None, 16));
+
+        let mut bad = V2_SIGNATURE.to_vec();
+        bad.extend_from_slice(&[0x11, 0x11, 0, 0]);
+        assert!(parse(&bad).is_err());
+    }
+}
@@ -0,0 +1,320 @@
+use std::io;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock, Weak};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::collections::{HashMap, HashSet};
+
+use rustls::{Certificate, DistinguishedNames, Error};
+use rustls::server::{ClientCertVerifier, ClientCertVerified};
+use x509_parser::prelude::{FromDer, X509Certificate, CertificateRevocationList, Pem};
+
+use super::util::load_certs;
+
+/// How often a CRL file is checked for changes.
+const RELOAD_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The source of certificate revocation lists.
+#[derive(Debug, Clone)]
+pub enum CrlSource {
+    /// A file, reloaded when its modification time changes.
+    Path(PathBuf),
+    /// PEM or DER bytes.
+    Bytes(Vec<u8>),
+}
+
+/// The revocations by one issuer.
+#[derive(Debug, Default)]
+struct Revoked {
+    /// The earliest `nextUpdate` of the issuer's CRLs, in seconds since the
+    /// Unix epoch, after which the CRLs are stale.
+    next_update: Option<i64>,
+    serials: HashSet<Vec<u8>>,
+}
+
+/// The modification time of the CRL file, if any, and the revoked serials by
+/// issuer name.
+type State = (Option<SystemTime>, HashMap<Vec<u8>, Revoked>);
+
+/// Certificate revocation lists: the serials revoked by each issuer, keyed by
+/// the issuer's DER-encoded name. Every CRL's signature is verified against
+/// the CA certificate that issued it.
+#[derive(Debug)]
+pub struct Crls {
+    source: CrlSource,
+    issuers: Vec<Certificate>,
+    watching: AtomicBool,
+    state: RwLock<State>,
+}
+
+/// A client certificate verifier that rejects revoked certificates after
+/// verifying them with an inner verifier.
+pub(crate) struct RevocationVerifier {
+    pub inner: Arc<dyn ClientCertVerifier>,
+    pub crls: Arc<Crls>,
+}
+
+fn err(message: impl Into<std::borrow::Cow<'static, str>>) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, message.into())
+}
+
+fn modified(path: &PathBuf) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// The DER encoding of `tbsCertList`, the signed first element of the DER
+/// encoded `CertificateList` sequence `der`.
+fn tbs_cert_list(der: &[u8]) -> Option<&[u8]> {
+    /// The lengths of the header and contents of the DER element in `der`.
+    fn element(der: &[u8]) -> Option<(usize, usize)> {
+        let first = *der.get(1)?;
+        if first < 0x80 {
+            return Some((2, first as usize));
+        }
+
+        let n = (first & 0x7f) as usize;
+        if n == 0 || n > 4 {
+            return None;
+        }
+
+        let len = der.get(2..2 + n)?.iter().fold(0, |len, &b| len << 8 | b as usize);
+        Some((2 + n, len))
+    }
+
+    let (header, _) = element(der)?;
+    let tbs = der.get(header..)?;
+    let (header, len) = element(tbs)?;
+    tbs.get(..header + len)
+}
+
+/// Verifies the signature of `crl`, encoded as `der`, with the public key of
+/// the certificate `issuer`.
+fn verify(der: &[u8], crl: &CertificateRevocationList<'_>, issuer: &X509Certificate<'_>) -> bool {
+    use ring::signature::{self, UnparsedPublicKey, VerificationAlgorithm};
+    use x509_parser::oid_registry::*;
+
+    let key = issuer.public_key();
+    let curve = key.algorithm.parameters.as_ref().and_then(|p| p.as_oid().ok());
+    let sig_alg = &crl.signature_algorithm.algorithm;
+    let alg: &dyn VerificationAlgorithm = if *sig_alg == OID_PKCS1_SHA256WITHRSA {
+        &signature::RSA_PKCS1_2048_8192_SHA256
+    } else if *sig_alg == OID_PKCS1_SHA384WITHRSA {
+        &signature::RSA_PKCS1_2048_8192_SHA384
+    } else if *sig_alg == OID_PKCS1_SHA512WITHRSA {
+        &signature::RSA_PKCS1_2048_8192_SHA512
+    } else if *sig_alg == OID_SIG_ECDSA_WITH_SHA256 && curve == Some(&OID_EC_P256) {
+        &signature::ECDSA_P256_SHA256_ASN1
+    } else if *sig_alg == OID_SIG_ECDSA_WITH_SHA256 && curve == Some(&OID_NIST_EC_P384) {
+        &signature::ECDSA_P384_SHA256_ASN1
+    } else if *sig_alg == OID_SIG_ECDSA_WITH_SHA384 && curve == Some(&OID_EC_P256) {
+        &signature::ECDSA_P256_SHA384_ASN1
+    } else if *sig_alg == OID_SIG_ECDSA_WITH_SHA384 && curve == Some(&OID_NIST_EC_P384) {
+        &signature::ECDSA_P384_SHA384_ASN1
+    } else if *sig_alg == OID_SIG_ED25519 {
+        &signature::ED25519
+    } else {
+        return false;
+    };
+
+    match tbs_cert_list(der) {
+        Some(tbs) => UnparsedPublicKey::new(alg, key.subject_public_key.data)
+            .verify(tbs, crl.signature_value.data)
+            .is_ok(),
+        None => false,
+    }
+}
+
+/// Parses the revoked serials in the PEM or DER encoded CRLs in `bytes`,
+/// verifying that each is signed by one of the CA certificates `issuers`.
+fn parse(bytes: &[u8], issuers: &[Certificate]) -> io::Result<HashMap<Vec<u8>, Revoked>> {
+    let ders = match bytes.starts_with(b"-----BEGIN") {
+        true => Pem::iter_from_buffer(bytes)
+            .map(|pem| pem.map(|pem| pem.contents))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| err(format!("invalid PEM: {}", e)))?,
+        false => vec![bytes.to_vec()],
+    };
+
+    let issuers = issuers.iter()
+        .map(|cert| X509Certificate::from_der(&cert.0).map(|(_, cert)| cert))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| err(format!("invalid CA certificate: {}", e)))?;
+
+    let mut revoked: HashMap<_, Revoked> = HashMap::new();
+    for der in &ders {
+        let (_, crl) = CertificateRevocationList::from_der(der)
+            .map_err(|e| err(format!("invalid CRL: {}", e)))?;
+
+        let issuer = issuers.iter()
+            .find(|ca| ca.subject().as_raw() == crl.issuer().as_raw())
+            .ok_or_else(|| err(format!("CRL issuer `{}` is not a CA certificate", crl.issuer())))?;
+
+        if !verify(der, &crl, issuer) {
+            return Err(err(format!("CRL signature by `{}` is invalid", crl.issuer())));
+        }
+
+        let entry = revoked.entry(crl.issuer().as_raw().to_vec()).or_default();
+        if let Some(next) = crl.next_update().map(|t| t.timestamp()) {
+            entry.next_update = Some(entry.next_update.map_or(next, |prev| prev.min(next)));
+        }
+
+        entry.serials.extend(crl.iter_revoked_certificates().map(|c| c.raw_serial().to_vec()));
+    }
+
+    Ok(revoked)
+}
+
+impl Crls {
+    /// Loads the CRLs from `source`, verifying their signatures against the
+    /// PEM-encoded CA certificates in `ca_certs`.
+    pub fn new(source: CrlSource, ca_certs: &mut dyn io::BufRead) -> io::Result<Crls> {
+        let issuers = load_certs(ca_certs)?;
+        let state = match source {
+            CrlSource::Path(ref path) => {
+                let modified = modified(path);
+                let bytes = fs::read(path)
+                    .map_err(|e| err(format!("{}: {}", path.display(), e)))?;
+
+                (modified, parse(&bytes, &issuers)?)
+            }
+            CrlSource::Bytes(ref bytes) => (None, parse(bytes, &issuers)?),
+        };
+
+        let watching = AtomicBool::new(false);
+        Ok(Crls { source, issuers, watching, state: RwLock::new(state) })
+    }
+
+    /// Starts polling for changes to the CRL file, if any, on a background
+    /// thread that exits once `self` is dropped. Only the first call has an
+    /// effect.
+    pub(crate) fn watch(self: &Arc<Self>) {
+        if !matches!(self.source, CrlSource::Path(_)) || self.watching.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let crls: Weak<Crls> = Arc::downgrade(self);
+        let spawn = std::thread::Builder::new()
+            .name("rocket-crl-reload".into())
+            .spawn(move || loop {
+                std::thread::sleep(RELOAD_INTERVAL);
+                match crls.upgrade() {
+                    Some(crls) => crls.refresh(),
+                    None => break,
+                }
+            });
+
+        if let Err(e) = spawn {
+            log::warn!("failed to watch CRLs for changes: {}", e);
+        }
+    }
+
+    /// Reloads the CRLs if they were loaded from a file that has since
+    /// changed. If reloading fails, the previously loaded CRLs are kept.
+    fn refresh(&self) {
+        let path = match self.source {
+            CrlSource::Path(ref path) => path,
+            CrlSource::Bytes(_) => return,
+        };
+
+        let modified = modified(path);
+        if modified == self.state.read().expect("CRL read lock").0 {
+            return;
+        }
+
+        let bytes = fs::read(path).map_err(|e| err(e.to_string()));
+        match bytes.and_then(|b| parse(&b, &self.issuers)) {
+            Ok(revoked) => {
+                log::info!("reloaded CRLs from {}", path.display());
+                *self.state.write().expect("CRL write lock") = (modified, revoked);
+            }
+            Err(e) => {
+                log::warn!("failed to reload CRLs from {}: {}", path.display(), e);
+                self.state.write().expect("CRL write lock").0 = modified;
+            }
+        }
+    }
+
+    /// Returns `true` if the DER-encoded certificate `der` is revoked by any
+    /// CRL, can't be parsed, or was issued by an issuer whose CRLs are stale
+    /// at `now`.
+    pub fn is_revoked(&self, der: &[u8], now: SystemTime) -> bool {
+        let cert = match X509Certificate::from_der(der) {
+            Ok((_, cert)) => cert,
+            Err(_) => return true,
+        };
+
+        let state = self.state.read().expect("CRL read lock");
+        let revoked = match state.1.get(cert.issuer().as_raw()) {
+            Some(revoked) => revoked,
+            None => return false,
+        };
+
+        let now = now.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64);
+        if revoked.next_update.map_or(false, |next| next < now) {
+            log::warn!("CRL of `{}` is past its next update", cert.issuer());
+            return true;
+        }
+
+        revoked.serials.contains(cert.raw_serial())
+    }
+}
+
+impl ClientCertVerifier for RevocationVerifier {
+    fn offer_client_auth(&self) -> bool {
+        self.inner.offer_client_auth()
+    }
+
+    fn client_auth_mandatory(&self) -> Option<bool> {
+        self.inner.client_auth_mandatory()
+    }
+
+    fn client_auth_root_subjects(&self) -> Option<DistinguishedNames> {
+        self.inner.client_auth_root_subjects()
+    }
+
+    fn verify_client_cert(
+        &self,
+        end_entity: &Certificate,
+        intermediates: &[Certificate],
+        now: SystemTime,
+    ) -> Result<ClientCertVerified, Error> {
+        let verified = self.inner.verify_client_cert(end_entity, intermediates, now)?;
+        let revoked = std::iter::once(end_entity)
+            .chain(intermediates)
+            .any(|cert| self.crls.is_revoked(&cert.0, now));
+
+        match revoked {
+            true => Err(Error::InvalidCertificateData("certificate is revoked".into())),
+            false => Ok(verified),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use super::*;
+
+    macro_rules! tls_example_file {
+        ($k:expr) => {
+            include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../examples/tls/private/", $k))
+        }
+    }
+
+    #[test]
+    fn stale_crls_revoke_every_certificate() -> io::Result<()> {
+        let crl = tls_example_file!("ca_crl_empty.pem").to_vec();
+        let ca_cert = tls_example_file!("ca_cert.pem");
+        let crls = Crls::new(CrlSource::Bytes(crl), &mut Cursor::new(ca_cert))?;
+
+        let cert = &load_certs(&mut Cursor::new(tls_example_file!("ed25519_cert.pem")))?[0];
+        assert!(!crls.is_revoked(&cert.0, SystemTime::now()));
+
+        let next_update = *crls.state.read().unwrap().1.values().next().unwrap().next_update.as_ref().unwrap();
+        let stale = UNIX_EPOCH + Duration::from_secs(next_update as u64 + 1);
+        assert!(crls.is_revoked(&cert.0, stale));
+        Ok(())
+    }
+}
@@ -9,13 +9,15 @@ use tokio::net::{TcpListener, TcpStream};
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio_rustls::{Accept, TlsAcceptor, server::TlsStream as BareTlsStream};
 
-use crate::tls::util::{load_certs, load_private_key, load_ca_certs};
+use crate::tls::util::load_ca_certs;
+use crate::tls::CertResolver;
 use crate::listener::{Connection, Listener, Certificates};
 
-/// A TLS listener over TCP.
-pub struct TlsListener {
-    listener: TcpListener,
+/// A TLS listener over TCP or, more generally, over any listener `L`.
+pub struct TlsListener<L = TcpListener> {
+    listener: L,
     acceptor: TlsAcceptor,
+    resolver: Arc<CertResolver>,
 }
 
 /// This implementation exists so that ROCKET_WORKERS=1 can make progress while
@@ -47,93 +49,129 @@ pub struct TlsListener {
 /// certificate data when we have a `Request` object, which implies we're
 /// receiving payload data, which implies the TLS handshake has finished, so the
 /// certificate data as seen by a Rocket application will always be "fresh".
-pub struct TlsStream {
-    remote: SocketAddr,
-    state: TlsState,
+pub struct TlsStream<C = TcpStream> {
+    remote: Option<SocketAddr>,
+    state: TlsState<C>,
     certs: Certificates,
 }
 
 /// State of `TlsStream`.
-pub enum TlsState {
+pub enum TlsState<C = TcpStream> {
     /// The TLS handshake is taking place. We don't have a full connection yet.
-    Handshaking(Accept<TcpStream>),
+    Handshaking(Accept<C>),
     /// TLS handshake completed successfully; we're getting payload data.
-    Streaming(BareTlsStream<TcpStream>),
+    Streaming(BareTlsStream<C>),
 }
 
 /// TLS as ~configured by `TlsConfig` in `rocket` core.
 pub struct Config<R> {
     pub cert_chain: R,
     pub private_key: R,
+    /// SNI hostnames with their certificate chain and private key.
+    pub sni: Vec<(String, R, R)>,
     pub ciphersuites: Vec<rustls::SupportedCipherSuite>,
     pub prefer_server_order: bool,
     pub ca_certs: Option<R>,
     pub mandatory_mtls: bool,
+    #[cfg(feature = "mtls")]
+    pub crls: Option<Arc<crate::tls::Crls>>,
 }
 
-impl TlsListener {
-    pub async fn bind<R>(addr: SocketAddr, mut c: Config<R>) -> io::Result<TlsListener>
-        where R: io::BufRead
+impl<R: io::BufRead> Config<R> {
+    /// Builds a `rustls` server configuration from `self` without any ALPN
+    /// protocols set.
+    /// Also returns the resolver for the server's certificate chain and key.
+    pub(crate) fn server_config(&mut self)
+        -> io::Result<(rustls::ServerConfig, Arc<CertResolver>)>
     {
         use rustls::server::{AllowAnyAuthenticatedClient, AllowAnyAnonymousOrAuthenticatedClient};
         use rustls::server::{NoClientAuth, ServerSessionMemoryCache, ServerConfig};
 
-        let cert_chain = load_certs(&mut c.cert_chain)
-            .map_err(|e| io::Error::new(e.kind(), format!("bad TLS cert chain: {}", e)))?;
+        let resolver = Arc::new(CertResolver::new(&mut self.cert_chain, &mut self.private_key, &mut self.sni)?);
 
-        let key = load_private_key(&mut c.private_key)
-            .map_err(|e| io::Error::new(e.kind(), format!("bad TLS private key: {}", e)))?;
-
-        let client_auth = match c.ca_certs {
+        let client_auth = match self.ca_certs {
             Some(ref mut ca_certs) => match load_ca_certs(ca_certs) {
-                Ok(ca_roots) if c.mandatory_mtls => AllowAnyAuthenticatedClient::new(ca_roots),
+                Ok(ca_roots) if self.mandatory_mtls => AllowAnyAuthenticatedClient::new(ca_roots),
                 Ok(ca_roots) => AllowAnyAnonymousOrAuthenticatedClient::new(ca_roots),
                 Err(e) => return Err(io::Error::new(e.kind(), format!("bad CA cert(s): {}", e))),
             },
             None => NoClientAuth::new(),
         };
 
+        #[cfg(feature = "mtls")]
+        let client_auth = match self.crls {
+            Some(ref crls) if self.ca_certs.is_some() => {
+                let crls = crls.clone();
+                crls.watch();
+                Arc::new(crate::tls::crl::RevocationVerifier { inner: client_auth, crls })
+            }
+            _ => client_auth,
+        };
+
         let mut tls_config = ServerConfig::builder()
-            .with_cipher_suites(&c.ciphersuites)
+            .with_cipher_suites(&self.ciphersuites)
             .with_safe_default_kx_groups()
             .with_safe_default_protocol_versions()
             .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("bad TLS config: {}", e)))?
             .with_client_cert_verifier(client_auth)
-            .with_single_cert(cert_chain, key)
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("bad TLS config: {}", e)))?;
+            .with_cert_resolver(resolver.clone());
+
+        tls_config.ignore_client_order = self.prefer_server_order;
+        tls_config.session_storage = ServerSessionMemoryCache::new(1024);
+        tls_config.ticketer = rustls::Ticketer::new()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("bad TLS ticketer: {}", e)))?;
 
-        tls_config.ignore_client_order = c.prefer_server_order;
+        Ok((tls_config, resolver))
+    }
+}
+
+impl TlsListener {
+    pub async fn bind<R>(addr: SocketAddr, c: Config<R>) -> io::Result<TlsListener>
+        where R: io::BufRead
+    {
+        TlsListener::from_listener(TcpListener::bind(addr).await?, c)
+    }
+}
 
+impl<L: Listener> TlsListener<L> {
+    /// Serves TLS, as configured by `c`, on the already listening `listener`.
+    pub fn from_listener<R>(listener: L, mut c: Config<R>) -> io::Result<TlsListener<L>>
+        where R: io::BufRead
+    {
+        let (mut tls_config, resolver) = c.server_config()?;
         tls_config.alpn_protocols = vec![b"http/1.1".to_vec()];
         if cfg!(feature = "http2") {
             tls_config.alpn_protocols.insert(0, b"h2".to_vec());
         }
 
-        tls_config.session_storage = ServerSessionMemoryCache::new(1024);
-        tls_config.ticketer = rustls::Ticketer::new()
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("bad TLS ticketer: {}", e)))?;
-
-        let listener = TcpListener::bind(addr).await?;
         let acceptor = TlsAcceptor::from(Arc::new(tls_config));
-        Ok(TlsListener { listener, acceptor })
+        Ok(TlsListener { listener, acceptor, resolver })
+    }
+
+    /// Returns the resolver for the listener's certificate chain and key.
+    pub fn cert_resolver(&self) -> Arc<CertResolver> {
+        self.resolver.clone()
     }
 }
 
-impl Listener for TlsListener {
-    type Connection = TlsStream;
+impl<L> Listener for TlsListener<L>
+    where L: Listener + Unpin, L::Connection: Unpin
+{
+    type Connection = TlsStream<L::Connection>;
 
     fn local_addr(&self) -> Option<SocketAddr> {
-        self.listener.local_addr().ok()
+        self.listener.local_addr()
     }
 
     fn poll_accept(
         self: Pin<&mut Self>,
         cx: &mut Context<'_>
     ) -> Poll<io::Result<Self::Connection>> {
-        match futures::ready!(self.listener.poll_accept(cx)) {
-            Ok((io, addr)) => Poll::Ready(Ok(TlsStream {
-                remote: addr,
-                state: TlsState::Handshaking(self.acceptor.accept(io)),
+        let this = self.get_mut();
+        match futures::ready!(Pin::new(&mut this.listener).poll_accept(cx)) {
+            Ok(io) => Poll::Ready(Ok(TlsStream {
+                remote: io.peer_address(),
+                state: TlsState::Handshaking(this.acceptor.accept(io)),
                 // These are empty and filled in after handshake is complete.
                 certs: Certificates::default(),
             })),
@@ -142,9 +180,9 @@ impl Listener for TlsListener {
     }
 }
 
-impl Connection for TlsStream {
+impl<C: Connection + Unpin> Connection for TlsStream<C> {
     fn peer_address(&self) -> Option<SocketAddr> {
-        Some(self.remote)
+        self.remote
     }
 
     fn enable_nodelay(&self) -> io::Result<()> {
@@ -166,13 +204,13 @@ impl Connection for TlsStream {
     }
 }
 
-impl TlsStream {
+impl<C: AsyncRead + AsyncWrite + Unpin> TlsStream<C> {
     fn poll_accept_then<F, T>(
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
         mut f: F
     ) -> Poll<io::Result<T>>
-        where F: FnMut(&mut BareTlsStream<TcpStream>, &mut Context<'_>) -> Poll<io::Result<T>>
+        where F: FnMut(&mut BareTlsStream<C>, &mut Context<'_>) -> Poll<io::Result<T>>
     {
         loop {
             match self.state {
@@ -186,7 +224,11 @@ impl TlsStream {
                             self.state = TlsState::Streaming(stream);
                         }
                         Err(e) => {
-                            log::warn!("tls handshake with {} failed: {}", self.remote, e);
+                            match self.remote {
+                                Some(remote) => log::warn!("tls handshake with {} failed: {}", remote, e),
+                                None => log::warn!("tls handshake failed: {}", e),
+                            }
+
                             return Poll::Ready(Err(e));
                         }
                     }
@@ -197,7 +239,7 @@ impl TlsStream {
     }
 }
 
-impl AsyncRead for TlsStream {
+impl<C: AsyncRead + AsyncWrite + Unpin> AsyncRead for TlsStream<C> {
     fn poll_read(
         self: Pin<&mut Self>,
         cx: &mut Context<'_>,
@@ -207,7 +249,7 @@ impl AsyncRead for TlsStream {
     }
 }
 
-impl AsyncWrite for TlsStream {
+impl<C: AsyncRead + AsyncWrite + Unpin> AsyncWrite for TlsStream<C> {
     fn poll_write(
         self: Pin<&mut Self>,
         cx: &mut Context<'_>,
@@ -1,8 +1,22 @@
 mod listener;
+mod resolver;
 mod util;
 
 #[cfg(feature = "mtls")]
 pub mod mtls;
 
+#[cfg(feature = "mtls")]
+mod crl;
+
+#[cfg(feature = "http3")]
+mod quic;
+
 pub use rustls;
 pub use listener::{TlsListener, Config};
+pub use resolver::CertResolver;
+
+#[cfg(feature = "mtls")]
+pub use crl::{Crls, CrlSource};
+
+#[cfg(feature = "http3")]
+pub use quic::{QuicListener, QuicConnecting, QuicConnection, QuicResponder};
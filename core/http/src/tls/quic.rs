@@ -0,0 +1,168 @@
+use std::io;
+use std::sync::Arc;
+use std::net::SocketAddr;
+
+use bytes::{Buf, Bytes};
+use futures::stream::{self, Stream};
+use hyper::body::HttpBody;
+use h3::server::RequestStream;
+
+use crate::tls::listener::Config;
+use crate::tls::CertResolver;
+use crate::listener::{Certificates, CertificateData};
+
+type H3Connection = h3::server::Connection<h3_quinn::Connection, Bytes>;
+
+/// A QUIC listener serving HTTP/3.
+pub struct QuicListener {
+    endpoint: quinn::Endpoint,
+    resolver: Arc<CertResolver>,
+}
+
+/// An incoming QUIC connection whose handshake has yet to complete.
+pub struct QuicConnecting(quinn::Connecting);
+
+/// An established HTTP/3 connection.
+pub struct QuicConnection {
+    remote: SocketAddr,
+    certs: Certificates,
+    connection: H3Connection,
+}
+
+/// The sending half of an HTTP/3 request stream, used to write a response.
+pub struct QuicResponder(RequestStream<h3_quinn::SendStream<Bytes>, Bytes>);
+
+fn io_error<E>(e: E) -> io::Error
+    where E: Into<Box<dyn std::error::Error + Send + Sync>>
+{
+    io::Error::new(io::ErrorKind::Other, e)
+}
+
+impl QuicListener {
+    pub async fn bind<R>(addr: SocketAddr, mut c: Config<R>) -> io::Result<QuicListener>
+        where R: io::BufRead
+    {
+        let (mut tls_config, resolver) = c.server_config()?;
+        tls_config.alpn_protocols = vec![b"h3".to_vec()];
+        // Disable 0-RTT: early data can be replayed, and requests received as
+        // early data can't be distinguished from others by handlers.
+        tls_config.max_early_data_size = 0;
+
+        let config = quinn::ServerConfig::with_crypto(Arc::new(tls_config));
+        let endpoint = quinn::Endpoint::server(config, addr)?;
+        Ok(QuicListener { endpoint, resolver })
+    }
+
+    /// Returns the resolver for the listener's certificate chain and key.
+    pub fn cert_resolver(&self) -> Arc<CertResolver> {
+        self.resolver.clone()
+    }
+
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.endpoint.local_addr().ok()
+    }
+
+    /// Waits for the next incoming connection. Returns `None` once the
+    /// listener has been closed.
+    pub async fn accept(&self) -> Option<QuicConnecting> {
+        self.endpoint.accept().await.map(QuicConnecting)
+    }
+
+    /// Stops accepting connections and waits up to `grace` for existing
+    /// connections to close before forcibly closing them.
+    pub async fn shutdown(&self, grace: std::time::Duration) {
+        self.endpoint.set_server_config(None);
+        let _ = tokio::time::timeout(grace, self.endpoint.wait_idle()).await;
+        self.endpoint.close(0u32.into(), b"server shutdown");
+        self.endpoint.wait_idle().await;
+    }
+}
+
+impl QuicConnecting {
+    /// Completes the QUIC handshake and the HTTP/3 connection setup.
+    pub async fn established(self) -> io::Result<QuicConnection> {
+        let quic = self.0.await.map_err(io_error)?;
+        let remote = quic.remote_address();
+        let certs = Certificates::default();
+        if let Some(chain) = quic.peer_identity() {
+            if let Ok(chain) = chain.downcast::<Vec<CertificateData>>() {
+                certs.set(*chain);
+            }
+        }
+
+        let connection = h3::server::Connection::new(h3_quinn::Connection::new(quic))
+            .await
+            .map_err(io_error)?;
+
+        Ok(QuicConnection { remote, certs, connection })
+    }
+}
+
+impl QuicConnection {
+    pub fn peer_address(&self) -> Option<SocketAddr> {
+        Some(self.remote)
+    }
+
+    pub fn peer_certificates(&self) -> Option<Certificates> {
+        Some(self.certs.clone())
+    }
+
+    /// Waits for the next request on the connection. Returns `None` once the
+    /// client has closed the connection.
+    ///
+    /// The request body is streamed from the client as it is read.
+    pub async fn accept(
+        &mut self
+    ) -> io::Result<Option<(hyper::Request<hyper::Body>, QuicResponder)>> {
+        use h3::error::ErrorLevel;
+
+        let (request, stream) = loop {
+            match self.connection.accept().await {
+                Ok(Some(accepted)) => break accepted,
+                Ok(None) => return Ok(None),
+                Err(e) => match e.get_error_level() {
+                    ErrorLevel::StreamError => log::warn!("bad HTTP/3 request: {}", e),
+                    ErrorLevel::ConnectionError => return Err(io_error(e)),
+                }
+            }
+        };
+
+        let (tx, rx) = stream.split();
+        let (parts, ()) = request.into_parts();
+        let body = hyper::Body::wrap_stream(body_stream(rx));
+        Ok(Some((hyper::Request::from_parts(parts, body), QuicResponder(tx))))
+    }
+
+    /// Informs the client that no further requests will be accepted. Requests
+    /// that are in-flight continue to be served.
+    pub async fn shutdown(&mut self) -> io::Result<()> {
+        self.connection.shutdown(0).await.map_err(io_error)
+    }
+}
+
+impl QuicResponder {
+    /// Writes `response`, including its body, to the request stream.
+    pub async fn send(mut self, response: hyper::Response<hyper::Body>) -> io::Result<()> {
+        let (parts, mut body) = response.into_parts();
+        self.0.send_response(http::Response::from_parts(parts, ())).await.map_err(io_error)?;
+        while let Some(chunk) = body.data().await {
+            self.0.send_data(chunk.map_err(io_error)?).await.map_err(io_error)?;
+        }
+
+        self.0.finish().await.map_err(io_error)
+    }
+}
+
+/// Returns a stream of the data received on `rx`, ending after the first error.
+fn body_stream(
+    rx: RequestStream<h3_quinn::RecvStream, Bytes>
+) -> impl Stream<Item = io::Result<Bytes>> + Send + 'static {
+    stream::unfold(Some(rx), |rx| async move {
+        let mut rx = rx?;
+        match rx.recv_data().await {
+            Ok(Some(mut buf)) => Some((Ok(buf.copy_to_bytes(buf.remaining())), Some(rx))),
+            Ok(None) => None,
+            Err(e) => Some((Err(io_error(e)), None)),
+        }
+    })
+}
@@ -0,0 +1,115 @@
+use std::io;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use std::collections::HashMap;
+
+use rustls::server::{ClientHello, ResolvesServerCert};
+use rustls::sign::CertifiedKey;
+
+use crate::tls::util::{load_certs, load_private_key};
+
+/// Resolves the server's certificate chain and private key, which can be
+/// replaced at any time with [`CertResolver::reload()`]. A replacement applies
+/// to new TLS handshakes; established connections are unaffected.
+///
+/// Besides a default certificate chain and key, a resolver may hold any number
+/// of chains and keys for specific SNI hostnames. A name may be a wildcard of
+/// the form `*.example.com`, which matches exactly one leading label. Clients
+/// that don't indicate a hostname, or indicate one without a chain, are served
+/// the default.
+pub struct CertResolver {
+    keys: RwLock<Keys>,
+}
+
+struct Keys {
+    default: Arc<CertifiedKey>,
+    sni: HashMap<String, Arc<CertifiedKey>>,
+}
+
+/// Loads a certified key from PEM readers for a certificate chain and key.
+fn load_certified_key(
+    cert_chain: &mut dyn io::BufRead,
+    private_key: &mut dyn io::BufRead,
+) -> io::Result<CertifiedKey> {
+    let cert_chain = load_certs(cert_chain)
+        .map_err(|e| io::Error::new(e.kind(), format!("bad TLS cert chain: {}", e)))?;
+
+    let key = load_private_key(private_key)
+        .map_err(|e| io::Error::new(e.kind(), format!("bad TLS private key: {}", e)))?;
+
+    let key = rustls::sign::any_supported_type(&key)
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("bad TLS private key: {}", e)))?;
+
+    Ok(CertifiedKey::new(cert_chain, key))
+}
+
+impl Keys {
+    fn load<R: io::BufRead>(
+        cert_chain: &mut R,
+        private_key: &mut R,
+        sni: &mut [(String, R, R)],
+    ) -> io::Result<Keys> {
+        let default = Arc::new(load_certified_key(cert_chain, private_key)?);
+        let sni = sni.iter_mut()
+            .map(|(name, cert_chain, private_key)| {
+                let key = load_certified_key(cert_chain, private_key)
+                    .map_err(|e| io::Error::new(e.kind(), format!("{} (for `{}`)", e, name)))?;
+
+                Ok((name.to_ascii_lowercase(), Arc::new(key)))
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Keys { default, sni })
+    }
+
+    fn get(&self, name: Option<&str>) -> &Arc<CertifiedKey> {
+        let name = match name {
+            Some(name) => name.to_ascii_lowercase(),
+            None => return &self.default,
+        };
+
+        let wildcard = name.split_once('.').map(|(_, parent)| format!("*.{}", parent));
+        self.sni.get(&name)
+            .or_else(|| wildcard.and_then(|w| self.sni.get(&w)))
+            .unwrap_or(&self.default)
+    }
+}
+
+impl CertResolver {
+    /// Creates a resolver from PEM readers for a default certificate chain and
+    /// key as well as for the chain and key of each SNI hostname in `sni`.
+    pub fn new<R: io::BufRead>(
+        cert_chain: &mut R,
+        private_key: &mut R,
+        sni: &mut [(String, R, R)],
+    ) -> io::Result<CertResolver> {
+        let keys = Keys::load(cert_chain, private_key, sni)?;
+        Ok(CertResolver { keys: RwLock::new(keys) })
+    }
+
+    /// Replaces all certificate chains and keys with those read from PEM
+    /// readers, as in [`CertResolver::new()`]. On error, the current chains
+    /// and keys remain in use.
+    pub fn reload<R: io::BufRead>(
+        &self,
+        cert_chain: &mut R,
+        private_key: &mut R,
+        sni: &mut [(String, R, R)],
+    ) -> io::Result<()> {
+        let keys = Keys::load(cert_chain, private_key, sni)?;
+        *self.keys.write().expect("TLS cert write lock") = keys;
+        Ok(())
+    }
+}
+
+impl ResolvesServerCert for CertResolver {
+    fn resolve(&self, hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        Some(self.keys.read().expect("TLS cert read lock").get(hello.server_name()).clone())
+    }
+}
+
+impl fmt::Debug for CertResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CertResolver").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,210 @@
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+use std::collections::HashMap;
+
+use hyper::Method;
+use parking_lot::RwLock;
+use serde_json::{json, Value};
+
+use crate::http_client::{self, Response};
+use super::jose::{b64, err, AccountKey};
+
+/// Pending HTTP-01 challenges: key authorizations indexed by token.
+pub type Challenges = Arc<RwLock<HashMap<String, String>>>;
+
+/// How often, and how many times, pending authorizations and orders are polled.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+const POLL_ATTEMPTS: usize = 30;
+
+/// Returns the body of `response` as JSON.
+fn json(response: &Response) -> io::Result<Value> {
+    serde_json::from_slice(&response.body)
+        .map_err(|e| err(format!("invalid ACME response: {}", e)))
+}
+
+fn str_field<'v>(value: &'v Value, field: &str) -> io::Result<&'v str> {
+    value.get(field)
+        .and_then(Value::as_str)
+        .ok_or_else(|| err(format!("ACME response is missing `{}`", field)))
+}
+
+/// A client for an ACME (RFC 8555) server.
+pub struct Client {
+    http: http_client::Client,
+    key: AccountKey,
+    new_nonce: String,
+    new_account: String,
+    new_order: String,
+    kid: Option<String>,
+    nonce: Option<String>,
+}
+
+impl Client {
+    /// Fetches the directory at `url` via `http`. Requests are signed with
+    /// `key`.
+    pub async fn new(http: http_client::Client, url: &str, key: AccountKey) -> io::Result<Client> {
+        let directory = json(&Client::http(&http, Method::GET, url, None).await?)?;
+        Ok(Client {
+            http,
+            key,
+            new_nonce: str_field(&directory, "newNonce")?.into(),
+            new_account: str_field(&directory, "newAccount")?.into(),
+            new_order: str_field(&directory, "newOrder")?.into(),
+            kid: None,
+            nonce: None,
+        })
+    }
+
+    /// Sends a `method` request to `url` with a JOSE `body`, if any.
+    async fn http(
+        http: &http_client::Client,
+        method: Method,
+        url: &str,
+        body: Option<Vec<u8>>,
+    ) -> io::Result<Response> {
+        let mut headers = vec![("Accept", "application/json, application/pem-certificate-chain")];
+        if body.is_some() {
+            headers.push(("Content-Type", "application/jose+json"));
+        }
+
+        http.request(method, url, &headers, body.unwrap_or_default()).await
+            .map_err(|e| io::Error::new(e.kind(), format!("ACME request to `{}` failed: {}", url, e)))
+    }
+
+    async fn nonce(&mut self) -> io::Result<String> {
+        if let Some(nonce) = self.nonce.take() {
+            return Ok(nonce);
+        }
+
+        let response = Client::http(&self.http, Method::HEAD, &self.new_nonce, None).await?;
+        response.header("Replay-Nonce")
+            .map(String::from)
+            .ok_or_else(|| err("ACME server did not provide a nonce"))
+    }
+
+    /// Posts a JWS with `payload` to `url`. A `None` payload is a POST-as-GET.
+    /// A request rejected for a bad nonce is retried once with a fresh one.
+    async fn post(&mut self, url: &str, payload: Option<&Value>) -> io::Result<Response> {
+        let mut retried = false;
+        loop {
+            let nonce = self.nonce().await?;
+            let jws = self.key.sign(url, &nonce, self.kid.as_deref(), payload)?;
+            let response = Client::http(&self.http, Method::POST, url, Some(jws)).await?;
+            self.nonce = response.header("Replay-Nonce").map(String::from);
+            if response.status < 400 {
+                return Ok(response);
+            }
+
+            let problem = json(&response).unwrap_or(Value::Null);
+            let kind = problem.get("type").and_then(Value::as_str).unwrap_or("unknown");
+            if kind == "urn:ietf:params:acme:error:badNonce" && !retried {
+                retried = true;
+                continue;
+            }
+
+            let detail = problem.get("detail").and_then(Value::as_str).unwrap_or("no detail");
+            return Err(err(format!("ACME error {} ({}): {}", response.status, kind, detail)));
+        }
+    }
+
+    /// Polls `url` until its `status` is no longer `pending` or `processing`.
+    async fn poll(&mut self, url: &str) -> io::Result<Value> {
+        for _ in 0..POLL_ATTEMPTS {
+            let value = json(&self.post(url, None).await?)?;
+            match str_field(&value, "status")? {
+                "pending" | "processing" => tokio::time::sleep(POLL_INTERVAL).await,
+                _ => return Ok(value),
+            }
+        }
+
+        Err(err(format!("timed out waiting on ACME resource `{}`", url)))
+    }
+
+    /// Registers an account, or finds the existing account for the key,
+    /// agreeing to the server's terms of service.
+    pub async fn register(&mut self, contact: &[String]) -> io::Result<()> {
+        let payload = json!({ "termsOfServiceAgreed": true, "contact": contact });
+        let url = self.new_account.clone();
+        let response = self.post(&url, Some(&payload)).await?;
+        let kid = response.header("Location")
+            .ok_or_else(|| err("ACME server did not provide an account URL"))?;
+
+        self.kid = Some(kid.into());
+        Ok(())
+    }
+
+    /// Obtains a certificate chain, in PEM, for `domains` and the P-256 PKCS#8
+    /// `key`, answering HTTP-01 challenges by publishing them in `challenges`.
+    pub async fn issue(
+        &mut self,
+        domains: &[String],
+        key: &[u8],
+        challenges: &Challenges,
+    ) -> io::Result<String> {
+        let identifiers: Vec<_> = domains.iter()
+            .map(|domain| json!({ "type": "dns", "value": domain }))
+            .collect();
+
+        let url = self.new_order.clone();
+        let response = self.post(&url, Some(&json!({ "identifiers": identifiers }))).await?;
+        let order_url = response.header("Location")
+            .ok_or_else(|| err("ACME server did not provide an order URL"))?
+            .to_string();
+
+        let order = json(&response)?;
+        let authorizations: Vec<String> = order.get("authorizations")
+            .and_then(Value::as_array)
+            .ok_or_else(|| err("ACME order is missing `authorizations`"))?
+            .iter()
+            .filter_map(|url| url.as_str().map(String::from))
+            .collect();
+
+        for url in &authorizations {
+            self.authorize(url, challenges).await?;
+        }
+
+        let csr = b64(super::der::csr(key, domains)?);
+        let finalize = str_field(&order, "finalize")?.to_string();
+        self.post(&finalize, Some(&json!({ "csr": csr }))).await?;
+
+        let order = self.poll(&order_url).await?;
+        if str_field(&order, "status")? != "valid" {
+            return Err(err(format!("ACME order failed: {}", order)));
+        }
+
+        let certificate = str_field(&order, "certificate")?.to_string();
+        let chain = self.post(&certificate, None).await?;
+        String::from_utf8(chain.body).map_err(|_| err("ACME certificate chain is not PEM"))
+    }
+
+    /// Completes the authorization at `url` via its HTTP-01 challenge.
+    async fn authorize(&mut self, url: &str, challenges: &Challenges) -> io::Result<()> {
+        let authorization = json(&self.post(url, None).await?)?;
+        if str_field(&authorization, "status")? == "valid" {
+            return Ok(());
+        }
+
+        let challenge = authorization.get("challenges")
+            .and_then(Value::as_array)
+            .and_then(|c| c.iter().find(|c| c.get("type") == Some(&json!("http-01"))))
+            .ok_or_else(|| err("ACME server did not offer an HTTP-01 challenge"))?;
+
+        let token = str_field(challenge, "token")?.to_string();
+        let key_authorization = format!("{}.{}", token, self.key.thumbprint());
+        challenges.write().insert(token.clone(), key_authorization);
+
+        let challenge_url = str_field(challenge, "url")?.to_string();
+        let result = match self.post(&challenge_url, Some(&json!({}))).await {
+            Ok(_) => self.poll(url).await,
+            Err(e) => Err(e),
+        };
+
+        challenges.write().remove(&token);
+        let authorization = result?;
+        match str_field(&authorization, "status")? {
+            "valid" => Ok(()),
+            _ => Err(err(format!("ACME authorization failed: {}", authorization))),
+        }
+    }
+}
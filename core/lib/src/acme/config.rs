@@ -0,0 +1,114 @@
+use figment::value::magic::RelativePathBuf;
+use serde::{Deserialize, Serialize};
+
+/// ACME configuration: domains, contacts, directory, and state directory.
+///
+/// Configuration is read from the `acme` parameter by [`Acme::fairing()`] or
+/// provided directly to [`Acme::new()`]. Only `domains` is required:
+///
+/// | key            | default                    | description                            |
+/// |----------------|----------------------------|----------------------------------------|
+/// | `domains`      | _required_                 | domains to obtain a certificate for    |
+/// | `contact`      | `[]`                       | account contact URLs, like `mailto:`   |
+/// | `directory`    | [`LETS_ENCRYPT`]           | URL of the ACME directory              |
+/// | `ca_certs`     | _none_                     | CA bundle to verify the server against |
+/// | `cache`        | `"acme"`                   | directory for account and certificate  |
+/// | `port`         | `80`                       | port for HTTP-01 challenges            |
+/// | `renew_before` | `30`                       | days before expiry to renew            |
+///
+/// The ACME server's certificate is verified against the web PKI roots unless
+/// `ca_certs` names a PEM file of CA certificates, as is needed for a private
+/// ACME server. When configured in a file, such as `Rocket.toml`, relative
+/// `cache` and `ca_certs` paths are interpreted as relative to the file's
+/// directory. In `Rocket.toml`, a
+/// configuration might look like:
+///
+/// ```toml
+/// [release.acme]
+/// domains = ["example.com", "www.example.com"]
+/// contact = ["mailto:admin@example.com"]
+/// cache = "/var/lib/rocket/acme"
+/// ```
+///
+/// [`Acme::fairing()`]: crate::acme::Acme::fairing()
+/// [`Acme::new()`]: crate::acme::Acme::new()
+/// [`LETS_ENCRYPT`]: AcmeConfig::LETS_ENCRYPT
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AcmeConfig {
+    /// The domains to obtain a single certificate for. The first domain is
+    /// also the certificate's subject.
+    pub domains: Vec<String>,
+    /// Contact URLs for the ACME account, such as `mailto:admin@example.com`.
+    #[serde(default)]
+    pub contact: Vec<String>,
+    /// The URL of the ACME server's directory.
+    #[serde(default = "AcmeConfig::default_directory")]
+    pub directory: String,
+    /// A PEM file of CA certificates to verify the ACME server's certificate
+    /// against in place of the web PKI roots.
+    #[serde(default)]
+    pub ca_certs: Option<RelativePathBuf>,
+    /// The directory where the account key, certificate chain, and
+    /// certificate key are persisted.
+    #[serde(default = "AcmeConfig::default_cache")]
+    pub cache: RelativePathBuf,
+    /// The port on which HTTP-01 challenges are answered over plain HTTP.
+    #[serde(default = "AcmeConfig::default_port")]
+    pub port: u16,
+    /// The number of days before the certificate expires to renew it.
+    #[serde(default = "AcmeConfig::default_renew_before")]
+    pub renew_before: u32,
+}
+
+impl AcmeConfig {
+    /// The URL of Let's Encrypt's production directory.
+    pub const LETS_ENCRYPT: &'static str = "https://acme-v02.api.letsencrypt.org/directory";
+
+    /// The URL of Let's Encrypt's staging directory, which issues untrusted
+    /// certificates under far more generous rate limits. Useful for testing.
+    pub const LETS_ENCRYPT_STAGING: &'static str =
+        "https://acme-staging-v02.api.letsencrypt.org/directory";
+
+    /// Returns a default configuration for `domains`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::acme::AcmeConfig;
+    ///
+    /// let config = AcmeConfig::new(["example.com", "www.example.com"]);
+    /// assert_eq!(config.domains.len(), 2);
+    /// assert_eq!(config.directory, AcmeConfig::LETS_ENCRYPT);
+    /// assert_eq!(config.port, 80);
+    /// assert_eq!(config.renew_before, 30);
+    /// ```
+    pub fn new<I, S>(domains: I) -> AcmeConfig
+        where I: IntoIterator<Item = S>, S: Into<String>
+    {
+        AcmeConfig {
+            domains: domains.into_iter().map(Into::into).collect(),
+            contact: vec![],
+            directory: AcmeConfig::default_directory(),
+            ca_certs: None,
+            cache: AcmeConfig::default_cache(),
+            port: AcmeConfig::default_port(),
+            renew_before: AcmeConfig::default_renew_before(),
+        }
+    }
+
+    fn default_directory() -> String {
+        AcmeConfig::LETS_ENCRYPT.into()
+    }
+
+    fn default_cache() -> RelativePathBuf {
+        RelativePathBuf::from("acme")
+    }
+
+    fn default_port() -> u16 {
+        80
+    }
+
+    fn default_renew_before() -> u32 {
+        30
+    }
+}
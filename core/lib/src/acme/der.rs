@@ -0,0 +1,187 @@
+//! Just enough DER to request certificates and learn when they expire.
+
+use std::io;
+
+use ring::rand::SystemRandom;
+use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_ASN1_SIGNING};
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time};
+
+use super::jose::err;
+
+const OID_EC_PUBLIC_KEY: &[u8] = &[0x06, 0x07, 0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x02, 0x01];
+const OID_PRIME256V1: &[u8] = &[0x06, 0x08, 0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x03, 0x01, 0x07];
+const OID_ECDSA_SHA256: &[u8] = &[0x06, 0x08, 0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x04, 0x03, 0x02];
+const OID_COMMON_NAME: &[u8] = &[0x06, 0x03, 0x55, 0x04, 0x03];
+const OID_EXTENSION_REQUEST: &[u8] =
+    &[0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x0E];
+const OID_SUBJECT_ALT_NAME: &[u8] = &[0x06, 0x03, 0x55, 0x1D, 0x11];
+
+fn tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+    match content.len() {
+        len if len < 0x80 => out.push(len as u8),
+        len => {
+            let bytes = len.to_be_bytes();
+            let bytes = &bytes[bytes.iter().position(|&b| b != 0).unwrap_or(0)..];
+            out.push(0x80 | bytes.len() as u8);
+            out.extend_from_slice(bytes);
+        }
+    }
+
+    out.extend_from_slice(content);
+    out
+}
+
+fn seq(parts: &[&[u8]]) -> Vec<u8> {
+    tlv(0x30, &parts.concat())
+}
+
+fn bit_string(bytes: &[u8]) -> Vec<u8> {
+    tlv(0x03, &[&[0u8][..], bytes].concat())
+}
+
+/// Returns a DER-encoded PKCS#10 certificate signing request for `domains`,
+/// the first of which is also the subject's common name, signed with the
+/// P-256 PKCS#8 `key`.
+pub fn csr(key: &[u8], domains: &[String]) -> io::Result<Vec<u8>> {
+    let pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, key)
+        .map_err(|e| err(format!("invalid certificate key: {}", e)))?;
+
+    let common_name = domains.first().ok_or_else(|| err("no domains to certify"))?;
+    let subject = seq(&[&tlv(0x31, &seq(&[OID_COMMON_NAME, &tlv(0x0C, common_name.as_bytes())]))]);
+    let algorithm = seq(&[OID_EC_PUBLIC_KEY, OID_PRIME256V1]);
+    let public_key = seq(&[&algorithm, &bit_string(pair.public_key().as_ref())]);
+
+    // A `subjectAltName` extension with one `dNSName` per domain.
+    let names: Vec<_> = domains.iter().map(|d| tlv(0x82, d.as_bytes())).collect();
+    let alt_names = seq(&[OID_SUBJECT_ALT_NAME, &tlv(0x04, &tlv(0x30, &names.concat()))]);
+    let extensions = seq(&[&alt_names]);
+    let attribute = seq(&[OID_EXTENSION_REQUEST, &tlv(0x31, &extensions)]);
+
+    let version = tlv(0x02, &[0]);
+    let info = seq(&[&version, &subject, &public_key, &tlv(0xA0, &attribute)]);
+    let signature = pair.sign(&SystemRandom::new(), &info)
+        .map_err(|_| err("failed to sign certificate request"))?;
+
+    Ok(seq(&[&info, &seq(&[OID_ECDSA_SHA256]), &bit_string(signature.as_ref())]))
+}
+
+/// Reads one TLV from `input`, returning its tag, content, and the remainder.
+fn read(input: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, input) = input.split_first()?;
+    let (&len, mut input) = input.split_first()?;
+    let len = match len {
+        len if len < 0x80 => len as usize,
+        len => {
+            let n = (len & 0x7F) as usize;
+            if n > std::mem::size_of::<usize>() || input.len() < n {
+                return None;
+            }
+
+            let len = input[..n].iter().fold(0, |len, &b| (len << 8) | b as usize);
+            input = &input[n..];
+            len
+        }
+    };
+
+    (input.len() >= len).then(|| (tag, &input[..len], &input[len..]))
+}
+
+/// Parses a `UTCTime` (tag `0x17`) or `GeneralizedTime` (tag `0x18`).
+fn parse_time(tag: u8, value: &[u8]) -> Option<OffsetDateTime> {
+    let value = std::str::from_utf8(value).ok()?.strip_suffix('Z')?;
+    let (year, rest) = match tag {
+        0x17 => {
+            let year: i32 = value.get(..2)?.parse().ok()?;
+            (if year >= 50 { 1900 + year } else { 2000 + year }, value.get(2..)?)
+        }
+        0x18 => (value.get(..4)?.parse().ok()?, value.get(4..)?),
+        _ => return None,
+    };
+
+    let field = |i: usize| rest.get(i..i + 2)?.parse::<u8>().ok();
+    let month = Month::try_from(field(0)?).ok()?;
+    let date = Date::from_calendar_date(year, month, field(2)?).ok()?;
+    let time = Time::from_hms(field(4)?, field(6)?, field(8)?).ok()?;
+    Some(PrimitiveDateTime::new(date, time).assume_utc())
+}
+
+/// Returns the `notAfter` time of the DER-encoded X.509 certificate `cert`.
+pub fn not_after(cert: &[u8]) -> Option<OffsetDateTime> {
+    let (_, cert, _) = read(cert)?;
+    let (_, mut tbs, _) = read(cert)?;
+
+    // Skip the optional explicit version.
+    if tbs.first() == Some(&0xA0) {
+        tbs = read(tbs)?.2;
+    }
+
+    // Skip the serial number, signature algorithm, and issuer.
+    for _ in 0..3 {
+        tbs = read(tbs)?.2;
+    }
+
+    let (_, validity, _) = read(tbs)?;
+    let (_, _, validity) = read(validity)?;
+    let (tag, not_after, _) = read(validity)?;
+    parse_time(tag, not_after)
+}
+
+/// Returns the DER contents of the first PEM block labeled `label` in `pem`.
+pub fn pem_decode(pem: &str, label: &str) -> Option<Vec<u8>> {
+    let begin = format!("-----BEGIN {}-----", label);
+    let end = format!("-----END {}-----", label);
+    let start = pem.find(&begin)? + begin.len();
+    let body = &pem[start..][..pem[start..].find(&end)?];
+    let body: String = body.split_whitespace().collect();
+    base64::decode(body).ok()
+}
+
+/// Returns `der` PEM-encoded with the label `label`.
+pub fn pem_encode(der: &[u8], label: &str) -> String {
+    let body = base64::encode(der);
+    let lines: Vec<_> = body.as_bytes().chunks(64).map(String::from_utf8_lossy).collect();
+    format!("-----BEGIN {}-----\n{}\n-----END {}-----\n", label, lines.join("\n"), label)
+}
+
+#[cfg(test)]
+mod tests {
+    use ring::signature::{UnparsedPublicKey, ECDSA_P256_SHA256_ASN1};
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn test_not_after() {
+        let pem = include_str!("../../../../examples/tls/private/ecdsa_nistp256_sha256_cert.pem");
+        let cert = pem_decode(pem, "CERTIFICATE").unwrap();
+        assert_eq!(not_after(&cert), Some(datetime!(2031-07-07 23:33:33 UTC)));
+        assert_eq!(pem_decode(&pem_encode(&cert, "CERTIFICATE"), "CERTIFICATE"), Some(cert));
+
+        assert_eq!(not_after(&[]), None);
+        assert_eq!(not_after(&[0x30, 0x03, 0x30, 0x01]), None);
+        assert_eq!(pem_decode(pem, "PRIVATE KEY"), None);
+    }
+
+    #[test]
+    fn test_csr() {
+        let key = crate::acme::jose::generate_key().unwrap();
+        let pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &key).unwrap();
+        let domains = ["example.com".to_string(), "www.example.com".to_string()];
+        let csr = csr(&key, &domains).unwrap();
+
+        let (tag, csr, rest) = read(&csr).unwrap();
+        assert_eq!((tag, rest), (0x30, &[][..]));
+
+        let (_, _, rest) = read(csr).unwrap();
+        let info = &csr[..csr.len() - rest.len()];
+        let (_, algorithm, rest) = read(rest).unwrap();
+        assert_eq!(algorithm, OID_ECDSA_SHA256);
+
+        let (tag, signature, _) = read(rest).unwrap();
+        assert_eq!((tag, signature[0]), (0x03, 0));
+        let public_key = UnparsedPublicKey::new(&ECDSA_P256_SHA256_ASN1, pair.public_key());
+        assert!(public_key.verify(info, &signature[1..]).is_ok());
+        assert!(info.windows(15).any(|w| w == b"www.example.com"));
+    }
+}
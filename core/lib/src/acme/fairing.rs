@@ -0,0 +1,288 @@
+use std::io;
+use std::fs;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use yansi::Paint;
+
+use crate::{Rocket, Build, Orbit, Config, Shutdown};
+use crate::fairing::{self, Fairing, Info, Kind, PlainServer, Answer};
+use crate::http::ContentType;
+use crate::log::PaintExt;
+use crate::trip_wire::TripWire;
+use crate::http_client;
+
+use super::{AcmeConfig, jose, der};
+use super::client::{Client, Challenges};
+
+/// How often the certificate's expiry is checked after liftoff.
+const RENEWAL_CHECK_INTERVAL: Duration = Duration::from_secs(12 * 60 * 60);
+
+/// The path at which HTTP-01 challenges are answered.
+const CHALLENGE_PATH: &str = "/.well-known/acme-challenge";
+
+/// A fairing that obtains and renews a TLS certificate via ACME.
+///
+/// See the [module level documentation](crate::acme) for details.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #[macro_use] extern crate rocket;
+/// use rocket::acme::Acme;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().attach(Acme::fairing())
+/// }
+/// ```
+pub struct Acme {
+    config: Option<AcmeConfig>,
+    challenges: Challenges,
+    state: Mutex<Option<(AcmeConfig, Shutdown)>>,
+}
+
+/// The persisted certificate chain and key of a configuration.
+fn cert_paths(config: &AcmeConfig) -> (PathBuf, PathBuf) {
+    let cache = config.cache.relative();
+    (cache.join("cert.pem"), cache.join("key.pem"))
+}
+
+/// Writes `contents` to `path` atomically, readable only by the owner.
+fn write_private(path: &Path, contents: &[u8]) -> io::Result<()> {
+    use std::io::Write;
+
+    let tmp = path.with_extension("tmp");
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)] {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    options.open(&tmp)?.write_all(contents)?;
+    fs::rename(&tmp, path)
+}
+
+/// Returns the expiry of the persisted certificate, if there is a valid one.
+fn expiry(config: &AcmeConfig) -> Option<time::OffsetDateTime> {
+    let pem = fs::read_to_string(cert_paths(config).0).ok()?;
+    der::not_after(&der::pem_decode(&pem, "CERTIFICATE")?)
+}
+
+/// Whether the persisted certificate is missing or due for renewal.
+fn needs_renewal(config: &AcmeConfig) -> bool {
+    let renew_before = time::Duration::days(config.renew_before.into());
+    expiry(config).map_or(true, |t| t - renew_before <= time::OffsetDateTime::now_utc())
+}
+
+/// Obtains a new certificate for `config` and persists it with its key.
+async fn provision(config: &AcmeConfig, challenges: &Challenges) -> io::Result<()> {
+    let cache = config.cache.relative();
+    fs::create_dir_all(&cache)?;
+
+    let account_path = cache.join("account.pem");
+    let account_key = match fs::read_to_string(&account_path) {
+        Ok(pem) => der::pem_decode(&pem, "PRIVATE KEY")
+            .ok_or_else(|| jose::err(format!("invalid account key: {}", account_path.display())))?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            let key = jose::generate_key()?;
+            write_private(&account_path, der::pem_encode(&key, "PRIVATE KEY").as_bytes())?;
+            key
+        }
+        Err(e) => return Err(e),
+    };
+
+    let http = http_client::Client::new(config.ca_certs.as_ref().map(|p| p.relative()).as_deref())?;
+    let account = jose::AccountKey::from_pkcs8(&account_key)?;
+    let mut client = Client::new(http, &config.directory, account).await?;
+    client.register(&config.contact).await?;
+
+    let key = jose::generate_key()?;
+    let chain = client.issue(&config.domains, &key, challenges).await?;
+    let (cert_path, key_path) = cert_paths(config);
+    write_private(&key_path, der::pem_encode(&key, "PRIVATE KEY").as_bytes())?;
+    write_private(&cert_path, chain.as_bytes())
+}
+
+impl Acme {
+    /// Returns a fairing that reads its [`AcmeConfig`] from the `acme`
+    /// configuration parameter.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::acme::Acme;
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build().attach(Acme::fairing())
+    /// }
+    /// ```
+    pub fn fairing() -> Acme {
+        Acme { config: None, challenges: Challenges::default(), state: Mutex::new(None) }
+    }
+
+    /// Returns a fairing that uses `config`, ignoring the `acme` configuration
+    /// parameter.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::acme::{Acme, AcmeConfig};
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     let config = AcmeConfig {
+    ///         contact: vec!["mailto:admin@example.com".into()],
+    ///         ..AcmeConfig::new(["example.com"])
+    ///     };
+    ///
+    ///     rocket::build().attach(Acme::new(config))
+    /// }
+    /// ```
+    pub fn new(config: AcmeConfig) -> Acme {
+        Acme { config: Some(config), ..Acme::fairing() }
+    }
+
+    /// Launches the plain-HTTP server answering challenges and redirecting all
+    /// other requests for the configured domains to HTTPS. Returns its
+    /// shutdown handle once it's listening.
+    async fn listen(&self, config: &Config, acme: &AcmeConfig) -> io::Result<Shutdown> {
+        let challenges = self.challenges.clone();
+        let answer: Answer = Arc::new(move |token| {
+            let key_authorization = token.to_str()
+                .and_then(|token| challenges.read().get(token).cloned());
+
+            Box::pin(async move {
+                key_authorization.map(|k| (ContentType::Plain, k.into_bytes()))
+            })
+        });
+
+        let shutdown = Shutdown(TripWire::new());
+        let addr = SocketAddr::new(config.address, acme.port);
+        PlainServer::new(config.port, config.base_url.clone(), &acme.domains)
+            .answer(CHALLENGE_PATH, answer)
+            .launch(addr, shutdown.clone())
+            .await?;
+
+        Ok(shutdown)
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for Acme {
+    fn info(&self) -> Info {
+        Info {
+            name: "ACME",
+            kind: Kind::Ignite | Kind::Liftoff | Kind::Singleton,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let acme = match self.config.clone() {
+            Some(config) => config,
+            None => match rocket.figment().extract_inner::<AcmeConfig>("acme") {
+                Ok(config) => config,
+                Err(e) => {
+                    crate::config::pretty_print_error(e);
+                    return Err(rocket);
+                }
+            }
+        };
+
+        if acme.domains.is_empty() {
+            error!("ACME configuration has no domains.");
+            return Err(rocket);
+        }
+
+        // An invalid configuration is reported by ignition itself.
+        let config = match Config::try_from(rocket.figment()) {
+            Ok(config) => config,
+            Err(_) => return Ok(rocket),
+        };
+
+        let shutdown = match self.listen(&config, &acme).await {
+            Ok(shutdown) => shutdown,
+            Err(e) => {
+                error!("ACME challenge server failed to launch on port {}.", acme.port);
+                error_!("{}", e);
+                return Err(rocket);
+            }
+        };
+
+        if needs_renewal(&acme) {
+            info!("{}Requesting a certificate for {} via ACME.",
+                Paint::emoji("🔏 "), Paint::white(acme.domains.join(", ")));
+
+            if let Err(e) = provision(&acme, &self.challenges).await {
+                error!("Failed to obtain a certificate via ACME.");
+                error_!("{}", e);
+                if expiry(&acme).is_none() {
+                    shutdown.notify();
+                    return Err(rocket);
+                }
+
+                warn_!("Using the existing certificate.");
+            }
+        }
+
+        let (cert_path, key_path) = cert_paths(&acme);
+        let figment = rocket.figment().clone()
+            .merge(("tls.certs", cert_path))
+            .merge(("tls.key", key_path));
+
+        *self.state.lock() = Some((acme, shutdown));
+        Ok(rocket.configure(figment))
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        let (acme, server) = match self.state.lock().take() {
+            Some(state) => state,
+            None => return,
+        };
+
+        info!("{}{}:", Paint::emoji("🔏 "), Paint::magenta("ACME"));
+        info_!("domains: {}", Paint::default(acme.domains.join(", ")));
+        info_!("challenges: port {}", Paint::default(acme.port));
+        if let Some(expiry) = expiry(&acme) {
+            info_!("certificate expires: {}", Paint::default(expiry.date()));
+        }
+
+        let shutdown = rocket.shutdown();
+        let (tls, resolvers) = (rocket.config().tls.clone(), rocket.tls_resolvers.clone());
+        let challenges = self.challenges.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown.clone() => break,
+                    _ = tokio::time::sleep(RENEWAL_CHECK_INTERVAL) => {},
+                }
+
+                if !needs_renewal(&acme) {
+                    continue;
+                }
+
+                let result = match provision(&acme, &challenges).await {
+                    Ok(()) => tls.as_ref().map_or(Ok(()), |tls| tls.reload(&resolvers)),
+                    Err(e) => Err(e),
+                };
+
+                match result {
+                    Ok(()) => info!("Renewed the TLS certificate via ACME."),
+                    Err(e) => {
+                        error!("Failed to renew the TLS certificate via ACME.");
+                        error_!("{}", e);
+                    }
+                }
+            }
+
+            server.notify();
+        });
+    }
+}
@@ -0,0 +1,88 @@
+use std::io;
+
+use ring::rand::SystemRandom;
+use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING};
+use serde_json::{json, Value};
+
+/// Base64url-encodes `data` without padding, as required by JOSE and ACME.
+pub fn b64(data: impl AsRef<[u8]>) -> String {
+    base64::encode_config(data, base64::URL_SAFE_NO_PAD)
+}
+
+pub fn err(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, message.into())
+}
+
+/// Generates a new P-256 private key, returned in PKCS#8 DER.
+pub fn generate_key() -> io::Result<Vec<u8>> {
+    EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &SystemRandom::new())
+        .map(|pkcs8| pkcs8.as_ref().to_vec())
+        .map_err(|_| err("failed to generate P-256 key"))
+}
+
+/// An ACME account key: a P-256 key used to sign ES256 JWS requests.
+pub struct AccountKey {
+    pair: EcdsaKeyPair,
+    rng: SystemRandom,
+}
+
+impl AccountKey {
+    pub fn from_pkcs8(pkcs8: &[u8]) -> io::Result<AccountKey> {
+        let pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8)
+            .map_err(|e| err(format!("invalid ACME account key: {}", e)))?;
+
+        Ok(AccountKey { pair, rng: SystemRandom::new() })
+    }
+
+    /// The public key's affine coordinates, base64url-encoded.
+    fn coordinates(&self) -> (String, String) {
+        // An uncompressed point: 0x04 || x || y.
+        let point = self.pair.public_key().as_ref();
+        (b64(&point[1..33]), b64(&point[33..65]))
+    }
+
+    /// The public key as a JWK.
+    pub fn jwk(&self) -> Value {
+        let (x, y) = self.coordinates();
+        json!({ "crv": "P-256", "kty": "EC", "x": x, "y": y })
+    }
+
+    /// The RFC 7638 JWK thumbprint of the public key: the digest of the JWK's
+    /// required members, in lexicographic order, without whitespace.
+    pub fn thumbprint(&self) -> String {
+        let (x, y) = self.coordinates();
+        let jwk = format!(r#"{{"crv":"P-256","kty":"EC","x":"{}","y":"{}"}}"#, x, y);
+        b64(ring::digest::digest(&ring::digest::SHA256, jwk.as_bytes()))
+    }
+
+    /// Returns the JSON body of a JWS, posted to `url`, carrying `payload`.
+    /// The signer is identified by `kid` or, when `None`, by its JWK.
+    /// A `None` payload is empty, as used for POST-as-GET requests.
+    pub fn sign(
+        &self,
+        url: &str,
+        nonce: &str,
+        kid: Option<&str>,
+        payload: Option<&Value>,
+    ) -> io::Result<Vec<u8>> {
+        let mut protected = json!({ "alg": "ES256", "nonce": nonce, "url": url });
+        match kid {
+            Some(kid) => protected["kid"] = kid.into(),
+            None => protected["jwk"] = self.jwk(),
+        }
+
+        let protected = b64(protected.to_string());
+        let payload = payload.map(|p| b64(p.to_string())).unwrap_or_default();
+        let message = format!("{}.{}", protected, payload);
+        let signature = self.pair.sign(&self.rng, message.as_bytes())
+            .map_err(|_| err("failed to sign ACME request"))?;
+
+        let jws = json!({
+            "protected": protected,
+            "payload": payload,
+            "signature": b64(signature),
+        });
+
+        Ok(jws.to_string().into_bytes())
+    }
+}
@@ -0,0 +1,68 @@
+//! Automatic TLS certificates via ACME, as provided by Let's Encrypt.
+//!
+//! The [`Acme`] fairing obtains a certificate for the configured domains from
+//! an [ACME] server, [Let's Encrypt] by default, using the HTTP-01 challenge,
+//! and serves it via TLS. Certificates are renewed in the background before
+//! they expire and are applied to new connections without a restart. Small
+//! deployments can thus serve HTTPS without any external tooling:
+//!
+//! ```rust,no_run
+//! # #[macro_use] extern crate rocket;
+//! use rocket::acme::Acme;
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build().attach(Acme::fairing())
+//! }
+//! ```
+//!
+//! With the configuration in `Rocket.toml`. See [`AcmeConfig`] for all
+//! parameters:
+//!
+//! ```toml
+//! [release]
+//! port = 443
+//!
+//! [release.acme]
+//! domains = ["example.com", "www.example.com"]
+//! contact = ["mailto:admin@example.com"]
+//! ```
+//!
+//! By using the fairing, you agree to the ACME server's terms of service.
+//!
+//! # How It Works
+//!
+//! At ignition, the fairing launches a minimal plain-HTTP server, not a Rocket
+//! instance, on the configured `port`, `80` by default, which must be reachable
+//! by the ACME server at every domain. It answers challenges under
+//! `/.well-known/acme-challenge/` and redirects every other request for one of
+//! the `domains` to HTTPS; the
+//! [`HttpsRedirect`](crate::fairing::HttpsRedirect) fairing is thus unneeded.
+//!
+//! If the `cache` directory holds no certificate, or one that expires within
+//! `renew_before` days, a new certificate is requested before the application
+//! launches. Ignition fails if none can be obtained and none is cached. The
+//! account key, certificate chain, and certificate key are persisted in the
+//! `cache` as `account.pem`, `cert.pem`, and `key.pem`. Removing `cert.pem`
+//! forces a new certificate, for instance after changing the `domains`.
+//!
+//! The certificate chain and key are then configured as the `tls.certs` and
+//! `tls.key` parameters. Any other `tls` parameters, such as `ciphers`, are
+//! used as configured. Every twelve hours thereafter, the certificate's expiry
+//! is checked and, if due, it is renewed and
+//! [reloaded](crate::Rocket::reload_tls()).
+//!
+//! Only the HTTP-01 challenge is supported. Certificates are issued for P-256
+//! ECDSA keys.
+//!
+//! [ACME]: https://datatracker.ietf.org/doc/html/rfc8555
+//! [Let's Encrypt]: https://letsencrypt.org
+
+mod config;
+mod fairing;
+mod client;
+mod jose;
+mod der;
+
+pub use self::config::AcmeConfig;
+pub use self::fairing::Acme;
@@ -1,10 +1,11 @@
 use std::fmt;
 use std::io::Cursor;
+use std::error::Error;
 
 use crate::response::Response;
 use crate::request::Request;
 use crate::http::{Status, ContentType, uri};
-use crate::catcher::{Handler, BoxFuture};
+use crate::catcher::{Handler, BoxFuture, ErrorFormat};
 
 use yansi::Paint;
 
@@ -52,10 +53,11 @@ use yansi::Paint;
 /// ### Built-In Default
 ///
 /// Rocket's provides a built-in default catcher that can handle all errors. It
-/// produces HTML or JSON, depending on the value of the `Accept` header. As
-/// such, catchers only need to be registered if an error needs to be handled in
-/// a custom fashion. The built-in default never conflicts with any
-/// user-registered catchers.
+/// produces HTML or JSON, depending on the value of the `Accept` header and the
+/// configured [`ErrorFormat`]. As such, catchers only need to be registered if
+/// an error needs to be handled in a custom fashion. The built-in default never
+/// conflicts with any user-registered catchers. To respond with JSON
+/// irrespective of the configuration, register [`default_json`].
 ///
 /// # Code Generation
 ///
@@ -98,7 +100,45 @@ use yansi::Paint;
 ///
 /// See the [`catch`] documentation for full details.
 ///
+/// # Typed Catchers
+///
+/// A catcher can be restricted to errors of a given type, `E`, recorded as the
+/// cause of a failure by [`Report`], via [`Catcher::error_type()`] or the
+/// `error` parameter of the [`catch`] attribute. A function decorated with
+/// `#[catch(code, error = "E")]` additionally takes an `&E` as its first
+/// argument:
+///
+/// ```rust,no_run
+/// #[macro_use] extern crate rocket;
+///
+/// use std::num::ParseIntError;
+///
+/// use rocket::response::Report;
+///
+/// #[get("/<n>")]
+/// fn double(n: &str) -> Result<String, Report> {
+///     Ok((n.parse::<i64>()? * 2).to_string())
+/// }
+///
+/// #[catch(500, error = "ParseIntError")]
+/// fn invalid_number(error: &ParseIntError) -> String {
+///     format!("That's not a number: {}.", error)
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .mount("/", routes![double])
+///         .register("/", catchers![invalid_number])
+/// }
+/// ```
+///
+/// Among the catchers with the same base that handle an error's status, a
+/// typed catcher for the error's type is preferred to an untyped one. Typed
+/// catchers for different types never collide.
+///
 /// [`catch`]: crate::catch
+/// [`Report`]: crate::response::Report
 /// [`Responder`]: crate::response::Responder
 /// [`&Request`]: crate::request::Request
 /// [`Status`]: crate::http::Status
@@ -115,6 +155,16 @@ pub struct Catcher {
 
     /// The catcher's associated error handler.
     pub handler: Box<dyn Handler>,
+
+    /// The type of error to match against if this is a typed catcher.
+    pub(crate) error_type: Option<ErrorType>,
+}
+
+/// The type of error a typed [`Catcher`] matches: its name and a check for it.
+#[derive(Clone, Copy)]
+pub(crate) struct ErrorType {
+    pub(crate) name: &'static str,
+    pub(crate) is: fn(&(dyn Error + Send + Sync + 'static)) -> bool,
 }
 
 impl Catcher {
@@ -166,10 +216,43 @@ impl Catcher {
             name: None,
             base: uri::Origin::ROOT,
             handler: Box::new(handler),
+            error_type: None,
             code,
         }
     }
 
+    /// Restricts this catcher to errors of type `E` recorded as the cause of
+    /// a failure, typically by [`Report`](crate::response::Report). The
+    /// recorded error is available to the handler via
+    /// [`Request::recorded_error()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::num::ParseIntError;
+    ///
+    /// use rocket::request::Request;
+    /// use rocket::catcher::{Catcher, BoxFuture};
+    /// use rocket::response::Responder;
+    /// use rocket::http::Status;
+    ///
+    /// fn handle_parse_error<'r>(status: Status, req: &'r Request<'_>) -> BoxFuture<'r> {
+    ///     let error = req.recorded_error().map(|e| e.to_string()).unwrap_or_default();
+    ///     let res = (status, format!("invalid integer: {}", error));
+    ///     Box::pin(async move { res.respond_to(req) })
+    /// }
+    ///
+    /// let catcher = Catcher::new(500, handle_parse_error).error_type::<ParseIntError>();
+    /// ```
+    pub fn error_type<E: Error + Send + Sync + 'static>(mut self) -> Self {
+        fn is<E: Error + 'static>(error: &(dyn Error + Send + Sync + 'static)) -> bool {
+            error.is::<E>()
+        }
+
+        self.error_type = Some(ErrorType { name: std::any::type_name::<E>(), is: is::<E> });
+        self
+    }
+
     /// Maps the `base` of this catcher using `mapper`, returning a new
     /// `Catcher` with the returned base.
     ///
@@ -236,6 +319,8 @@ pub struct StaticInfo {
     pub code: Option<u16>,
     /// The catcher's handler, i.e, the annotated function.
     pub handler: for<'r> fn(Status, &'r Request<'_>) -> BoxFuture<'r>,
+    /// Restricts the catcher to its error type, if it has one.
+    pub error_type: Option<fn(Catcher) -> Catcher>,
 }
 
 #[doc(hidden)]
@@ -244,7 +329,10 @@ impl From<StaticInfo> for Catcher {
     fn from(info: StaticInfo) -> Catcher {
         let mut catcher = Catcher::new(info.code, info.handler);
         catcher.name = Some(info.name.into());
-        catcher
+        match info.error_type {
+            Some(error_type) => error_type(catcher),
+            None => catcher,
+        }
     }
 }
 
@@ -259,9 +347,15 @@ impl fmt::Display for Catcher {
         }
 
         match self.code {
-            Some(code) => write!(f, "{}", Paint::blue(code)),
-            None => write!(f, "{}", Paint::blue("default"))
+            Some(code) => write!(f, "{}", Paint::blue(code))?,
+            None => write!(f, "{}", Paint::blue("default"))?,
+        }
+
+        if let Some(error_type) = self.error_type {
+            write!(f, " {}", Paint::magenta(error_type.name))?;
         }
+
+        Ok(())
     }
 }
 
@@ -271,6 +365,7 @@ impl fmt::Debug for Catcher {
             .field("name", &self.name)
             .field("base", &self.base)
             .field("code", &self.code)
+            .field("error_type", &self.error_type.map(|e| e.name))
             .finish()
     }
 }
@@ -332,40 +427,82 @@ macro_rules! default_handler_fn {
     ($($code:expr, $reason:expr, $description:expr),+) => (
         use std::borrow::Cow;
 
-        pub(crate) fn default_handler<'r>(
-            status: Status,
-            req: &'r Request<'_>
-        ) -> Response<'r> {
-            let preferred = req.accept().map(|a| a.preferred());
-            let (mime, text) = if preferred.map_or(false, |a| a.is_json()) {
-                let json: Cow<'_, str> = match status.code {
-                    $($code => json_error_template!($code, $reason, $description).into(),)*
-                    code => format!(json_error_fmt_template!("{}", "Unknown Error",
-                            "An unknown error has occurred."), code).into()
-                };
-
-                (ContentType::JSON, json)
-            } else {
-                let html: Cow<'_, str> = match status.code {
-                    $($code => html_error_template!($code, $reason, $description).into(),)*
-                    code => format!(html_error_template!("{}", "Unknown Error",
-                            "An unknown error has occurred."), code, code).into(),
-                };
-
-                (ContentType::HTML, html)
-            };
-
-            let mut r = Response::build().status(status).header(mime).finalize();
-            match text {
-                Cow::Owned(v) => r.set_sized_body(v.len(), Cursor::new(v)),
-                Cow::Borrowed(v) => r.set_sized_body(v.len(), Cursor::new(v)),
-            };
-
-            r
+        fn json_error(status: Status) -> Cow<'static, str> {
+            match status.code {
+                $($code => json_error_template!($code, $reason, $description).into(),)*
+                code => format!(json_error_fmt_template!("{}", "Unknown Error",
+                        "An unknown error has occurred."), code).into()
+            }
+        }
+
+        fn html_error(status: Status) -> Cow<'static, str> {
+            match status.code {
+                $($code => html_error_template!($code, $reason, $description).into(),)*
+                code => format!(html_error_template!("{}", "Unknown Error",
+                        "An unknown error has occurred."), code, code).into(),
+            }
         }
     )
 }
 
+fn error_response<'r>(status: Status, json: bool) -> Response<'r> {
+    let (mime, text) = match json {
+        true => (ContentType::JSON, json_error(status)),
+        false => (ContentType::HTML, html_error(status)),
+    };
+
+    let mut r = Response::build().status(status).header(mime).finalize();
+    match text {
+        Cow::Owned(v) => r.set_sized_body(v.len(), Cursor::new(v)),
+        Cow::Borrowed(v) => r.set_sized_body(v.len(), Cursor::new(v)),
+    };
+
+    r
+}
+
+pub(crate) fn default_handler<'r>(status: Status, req: &'r Request<'_>) -> Response<'r> {
+    let json = match req.rocket().config().error_format {
+        ErrorFormat::Negotiate => req.accept().map_or(false, |a| a.preferred().is_json()),
+        ErrorFormat::Html => false,
+        ErrorFormat::Json => true,
+    };
+
+    error_response(status, json)
+}
+
+/// A catcher [`Handler`] that always responds with Rocket's default JSON error
+/// document, irrespective of the request's `Accept` header and the configured
+/// [`ErrorFormat`].
+///
+/// The document has the following form:
+///
+/// ```json
+/// {
+///   "error": {
+///     "code": 404,
+///     "reason": "Not Found",
+///     "description": "The requested resource could not be found."
+///   }
+/// }
+/// ```
+///
+/// # Example
+///
+/// Respond with JSON to all errors under `/api`:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::catcher::{self, Catcher};
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().register("/api", vec![Catcher::new(None, catcher::default_json)])
+/// }
+/// ```
+pub fn default_json<'r>(status: Status, _: &'r Request<'_>) -> BoxFuture<'r> {
+    Box::pin(async move { Ok(error_response(status, true)) })
+}
+
 default_handler_fn! {
     400, "Bad Request", "The request could not be understood by the server due \
         to malformed syntax.",
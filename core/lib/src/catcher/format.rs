@@ -0,0 +1,78 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{de, Serialize, Serializer, Deserialize, Deserializer};
+
+/// The format of the error documents produced by the built-in default catcher.
+///
+/// The format is set via the `error_format` configuration parameter:
+///
+/// ```toml
+/// [default]
+/// error_format = "json"
+/// ```
+///
+/// Catchers registered by the application are unaffected.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ErrorFormat {
+    /// JSON when the request's `Accept` header prefers JSON, and HTML
+    /// otherwise: `"negotiate"`. This is the default.
+    Negotiate,
+    /// Always HTML: `"html"`.
+    Html,
+    /// Always JSON: `"json"`.
+    Json,
+}
+
+impl ErrorFormat {
+    fn as_str(&self) -> &str {
+        match self {
+            ErrorFormat::Negotiate => "negotiate",
+            ErrorFormat::Html => "html",
+            ErrorFormat::Json => "json",
+        }
+    }
+}
+
+impl Default for ErrorFormat {
+    fn default() -> Self {
+        ErrorFormat::Negotiate
+    }
+}
+
+impl FromStr for ErrorFormat {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let format = match &*s.to_ascii_lowercase() {
+            "negotiate" => ErrorFormat::Negotiate,
+            "html" => ErrorFormat::Html,
+            "json" => ErrorFormat::Json,
+            _ => return Err("an error format (negotiate, html, json)")
+        };
+
+        Ok(format)
+    }
+}
+
+impl fmt::Display for ErrorFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Serialize for ErrorFormat {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        ser.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ErrorFormat {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        let string = String::deserialize(de)?;
+        ErrorFormat::from_str(&string).map_err(|_| de::Error::invalid_value(
+            de::Unexpected::Str(&string),
+            &figment::error::OneOf( &["negotiate", "html", "json"])
+        ))
+    }
+}
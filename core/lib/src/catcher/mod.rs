@@ -2,6 +2,8 @@
 
 mod catcher;
 mod handler;
+mod format;
 
 pub use catcher::*;
 pub use handler::*;
+pub use format::*;
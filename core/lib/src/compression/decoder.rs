@@ -0,0 +1,70 @@
+use std::io;
+
+use async_compression::tokio::bufread::{BrotliDecoder, GzipDecoder};
+use bytes::Bytes;
+use futures::stream::{Stream, StreamExt};
+use tokio::io::{AsyncRead, BufReader};
+use tokio_util::io::ReaderStream;
+
+use crate::{Request, Data};
+use crate::data::ByteUnit;
+use crate::compression::Encoding;
+
+/// Decompresses `data` in place if `req` has a `Content-Encoding` header with
+/// a single, supported encoding. The header, along with any `Content-Length`,
+/// is then removed from `req` as neither applies to the decompressed body.
+pub(crate) fn decompress_request<'r>(req: &mut Request<'_>, data: &mut Data<'r>) {
+    let encoding = {
+        let mut codings = req.headers().get("Content-Encoding")
+            .flat_map(|value| value.split(','))
+            .map(|coding| coding.trim())
+            .filter(|coding| !coding.is_empty() && !coding.eq_ignore_ascii_case("identity"));
+
+        match (codings.next(), codings.next()) {
+            (Some(coding), None) => match Encoding::from_name(coding) {
+                Some(encoding) => encoding,
+                None => return,
+            },
+            _ => return,
+        }
+    };
+
+    let limit = req.limits().get("decompressed").unwrap_or(crate::data::Limits::DECOMPRESSED);
+    let raw = std::mem::replace(data, Data::local(vec![]));
+    *data = raw.decompressed(encoding, limit);
+    req.remove_header("Content-Encoding");
+    req.remove_header("Content-Length");
+}
+
+/// Returns a stream of `body` decompressed with `encoding`. The stream fails
+/// with an error of kind `InvalidData` if the decompressed body exceeds
+/// `limit` bytes.
+pub(crate) fn decompress<'r, R>(
+    body: R,
+    encoding: Encoding,
+    limit: ByteUnit,
+) -> impl Stream<Item = io::Result<Bytes>> + Send + Sync + 'r
+    where R: AsyncRead + Send + Sync + Unpin + 'r
+{
+    let body = BufReader::new(body);
+    let decoder: Box<dyn AsyncRead + Send + Sync + Unpin + 'r> = match encoding {
+        Encoding::Gzip => Box::new(GzipDecoder::new(body)),
+        Encoding::Brotli => Box::new(BrotliDecoder::new(body)),
+    };
+
+    let (mut remaining, mut exceeded) = (limit.as_u64(), false);
+    ReaderStream::new(decoder).map(move |chunk| {
+        let chunk = chunk?;
+        match remaining.checked_sub(chunk.len() as u64) {
+            Some(rem) if !exceeded => {
+                remaining = rem;
+                Ok(chunk)
+            }
+            _ => {
+                exceeded = true;
+                let msg = format!("decompressed body exceeds limit of {}", limit);
+                Err(io::Error::new(io::ErrorKind::InvalidData, msg))
+            }
+        }
+    })
+}
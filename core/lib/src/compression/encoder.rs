@@ -0,0 +1,176 @@
+use std::io;
+use std::fmt;
+
+use async_compression::Level;
+use async_compression::tokio::write::{BrotliEncoder, GzipEncoder};
+use bytes::Bytes;
+use futures::stream::Stream;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio_util::io::StreamReader;
+
+/// A content encoding used to compress a response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// The `gzip` encoding.
+    Gzip,
+    /// The `br`, Brotli, encoding.
+    Brotli,
+}
+
+impl Encoding {
+    /// Returns the name of the encoding as used in the `Accept-Encoding` and
+    /// `Content-Encoding` headers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::compression::Encoding;
+    ///
+    /// assert_eq!(Encoding::Gzip.name(), "gzip");
+    /// assert_eq!(Encoding::Brotli.name(), "br");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Gzip => "gzip",
+            Encoding::Brotli => "br",
+        }
+    }
+
+    /// Returns the encoding named `name`, case-insensitively, if any.
+    pub(crate) fn from_name(name: &str) -> Option<Encoding> {
+        if name.eq_ignore_ascii_case("gzip") || name.eq_ignore_ascii_case("x-gzip") {
+            Some(Encoding::Gzip)
+        } else if name.eq_ignore_ascii_case("br") {
+            Some(Encoding::Brotli)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name().fmt(f)
+    }
+}
+
+/// An encoder that writes its compressed output into a buffer.
+enum Encoder {
+    Gzip(GzipEncoder<Vec<u8>>),
+    Brotli(Box<BrotliEncoder<Vec<u8>>>),
+}
+
+impl Encoder {
+    fn new(encoding: Encoding) -> Self {
+        match encoding {
+            Encoding::Gzip => Encoder::Gzip(GzipEncoder::new(vec![])),
+            // The maximum Brotli quality is far too slow for compression on
+            // the fly. A quality of `4` compresses better than gzip's default
+            // at a comparable speed.
+            Encoding::Brotli => {
+                Encoder::Brotli(Box::new(BrotliEncoder::with_quality(vec![], Level::Precise(4))))
+            }
+        }
+    }
+
+    fn writer(&mut self) -> &mut (dyn AsyncWrite + Send + Unpin) {
+        match self {
+            Encoder::Gzip(e) => e,
+            Encoder::Brotli(e) => &mut **e,
+        }
+    }
+
+    /// Takes the compressed output produced so far.
+    fn take_output(&mut self) -> Bytes {
+        let output = match self {
+            Encoder::Gzip(e) => e.get_mut(),
+            Encoder::Brotli(e) => e.get_mut(),
+        };
+
+        std::mem::take(output).into()
+    }
+}
+
+/// Returns a reader of `body` compressed with `encoding`. If `flush` is `true`,
+/// the encoder is flushed after every read from `body` so that every chunk of
+/// `body` is emitted as soon as it's available.
+pub(crate) fn compress<'r, R>(
+    body: R,
+    encoding: Encoding,
+    chunk_size: usize,
+    flush: bool,
+) -> impl AsyncRead + Send + 'r
+    where R: AsyncRead + Send + Unpin + 'r
+{
+    StreamReader::new(compressed_stream(body, encoding, chunk_size, flush))
+}
+
+fn compressed_stream<'r, R>(
+    mut body: R,
+    encoding: Encoding,
+    chunk_size: usize,
+    flush: bool,
+) -> impl Stream<Item = io::Result<Bytes>> + Send + 'r
+    where R: AsyncRead + Send + Unpin + 'r
+{
+    async_stream::try_stream! {
+        let mut encoder = Encoder::new(encoding);
+        let mut chunk = vec![0; chunk_size];
+        loop {
+            let n = body.read(&mut chunk).await?;
+            if n == 0 {
+                break;
+            }
+
+            encoder.writer().write_all(&chunk[..n]).await?;
+            if flush {
+                encoder.writer().flush().await?;
+            }
+
+            let output = encoder.take_output();
+            if !output.is_empty() {
+                yield output;
+            }
+        }
+
+        encoder.writer().shutdown().await?;
+        yield encoder.take_output();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use async_compression::tokio::write::GzipDecoder;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::{compress, Encoding};
+
+    #[test]
+    fn test_flushes_each_chunk() {
+        crate::async_test(async {
+            let (mut tx, rx) = tokio::io::duplex(64);
+            let mut compressed = Box::pin(compress(rx, Encoding::Gzip, 1024, true));
+            let mut decoder = GzipDecoder::new(vec![]);
+
+            tx.write_all(b"hello").await.unwrap();
+            let mut buf = [0; 1024];
+            let read = compressed.read(&mut buf);
+            let n = tokio::time::timeout(Duration::from_secs(5), read).await
+                .expect("chunk emitted before body ended")
+                .unwrap();
+
+            decoder.write_all(&buf[..n]).await.unwrap();
+            decoder.flush().await.unwrap();
+            assert_eq!(decoder.get_ref(), b"hello");
+
+            drop(tx);
+            let mut rest = vec![];
+            compressed.read_to_end(&mut rest).await.unwrap();
+            decoder.write_all(&rest).await.unwrap();
+            decoder.shutdown().await.unwrap();
+            assert_eq!(decoder.into_inner(), b"hello");
+        })
+    }
+}
@@ -0,0 +1,290 @@
+use crate::{Request, Response};
+use crate::data::{ByteUnit, ToByteUnit};
+use crate::fairing::{Fairing, Info, Kind};
+use crate::http::{MediaType, Status};
+use crate::compression::encoder::{self, Encoding};
+
+/// A [`Fairing`] that compresses the bodies of outgoing responses.
+///
+/// # Usage
+///
+/// To use `Compression`, construct an instance, optionally configure it, and
+/// attach it to your application's instance of `Rocket`:
+///
+/// ```rust
+/// use rocket::compression::{Compression, Encoding};
+/// use rocket::data::ToByteUnit;
+/// use rocket::http::MediaType;
+///
+/// // Compress all of the default media types of at least 1KiB.
+/// let compression = Compression::default();
+///
+/// // Additionally compress MessagePack bodies; only those of at least 16KiB,
+/// // preferably with gzip.
+/// let compression = Compression::default()
+///     .allow(MediaType::MsgPack)
+///     .min_size(16.kibibytes())
+///     .encodings([Encoding::Gzip, Encoding::Brotli]);
+///
+/// rocket::build()
+///     // ...
+///     .attach(compression)
+/// # ;
+/// ```
+///
+/// The fairing compresses a response's body when the response meets the
+/// criteria in the [module-level documentation](crate::compression), sets the
+/// `Content-Encoding` header, and removes any `Content-Length`. A `Vary:
+/// Accept-Encoding` header is added to every response whose body could have
+/// been compressed, whether it was or not, so that caches store compressed
+/// and uncompressed responses separately.
+///
+/// # Defaults
+///
+/// By default, `Compression` prefers Brotli to gzip, compresses bodies of at
+/// least 1KiB, and allows the following media types:
+///
+///   * `text/*`
+///   * `application/json`
+///   * `application/javascript`
+///   * `application/wasm`
+///   * `image/svg+xml`
+///
+/// A media type in the allowlist with a subtype of `*` matches every media
+/// type with the same top-level type.
+#[derive(Debug, Clone)]
+pub struct Compression {
+    encodings: Vec<Encoding>,
+    media_types: Vec<MediaType>,
+    min_size: ByteUnit,
+}
+
+impl Default for Compression {
+    /// Returns a `Compression` fairing with the default configuration. See
+    /// [Defaults](#defaults).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::compression::Compression;
+    ///
+    /// let compression = Compression::default();
+    /// ```
+    fn default() -> Self {
+        Compression {
+            encodings: vec![Encoding::Brotli, Encoding::Gzip],
+            media_types: vec![
+                MediaType::new("text", "*"),
+                MediaType::JSON,
+                MediaType::JavaScript,
+                MediaType::WASM,
+                MediaType::SVG,
+            ],
+            min_size: 1.kibibytes(),
+        }
+    }
+}
+
+impl Compression {
+    /// Allows bodies of the media type `media_type` to be compressed. A
+    /// subtype of `*` allows every subtype of the top-level type.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::compression::Compression;
+    /// use rocket::http::MediaType;
+    ///
+    /// let compression = Compression::default()
+    ///     .allow(MediaType::MsgPack)
+    ///     .allow(MediaType::new("font", "*"));
+    /// ```
+    pub fn allow(mut self, media_type: MediaType) -> Self {
+        if !self.media_types.contains(&media_type) {
+            self.media_types.push(media_type);
+        }
+
+        self
+    }
+
+    /// Removes `media_type` from the allowlist. Parameters of `media_type`
+    /// are ignored. Removing a media type that isn't in the allowlist has no
+    /// effect.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::compression::Compression;
+    /// use rocket::http::MediaType;
+    ///
+    /// // Compress all default media types except for WASM.
+    /// let compression = Compression::default().deny(MediaType::WASM);
+    /// ```
+    pub fn deny(mut self, media_type: MediaType) -> Self {
+        self.media_types.retain(|allowed| *allowed != media_type);
+        self
+    }
+
+    /// Sets the minimum size of a body to compress to `size`. Bodies whose
+    /// size is unknown are always compressed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::compression::Compression;
+    /// use rocket::data::ToByteUnit;
+    ///
+    /// let compression = Compression::default().min_size(512.bytes());
+    /// ```
+    pub fn min_size(mut self, size: ByteUnit) -> Self {
+        self.min_size = size;
+        self
+    }
+
+    /// Sets the encodings to compress with, in order of preference, from most
+    /// to least. When a client accepts several of the encodings with the same
+    /// quality, the most preferred is used.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::compression::{Compression, Encoding};
+    ///
+    /// // Only ever compress with gzip.
+    /// let compression = Compression::default().encodings([Encoding::Gzip]);
+    /// ```
+    pub fn encodings<I: IntoIterator<Item = Encoding>>(mut self, encodings: I) -> Self {
+        self.encodings = encodings.into_iter().collect();
+        self
+    }
+
+    fn allows(&self, media_type: &MediaType) -> bool {
+        self.media_types.iter().any(|allowed| {
+            allowed.top() == media_type.top()
+                && (allowed.sub() == "*" || allowed.sub() == media_type.sub())
+        })
+    }
+
+    /// Returns the most-preferred encoding with the highest quality in the
+    /// `Accept-Encoding` header values `accept`, if any.
+    fn negotiate<'a, I>(&self, accept: I) -> Option<Encoding>
+        where I: IntoIterator<Item = &'a str>
+    {
+        // Parse into `(coding, quality)` pairs, ignoring malformed qualities.
+        let codings: Vec<(&str, f32)> = accept.into_iter()
+            .flat_map(|value| value.split(','))
+            .filter_map(|coding| {
+                let mut parts = coding.split(';');
+                let coding = parts.next()?.trim();
+                let q = parts
+                    .filter_map(|param| param.trim().strip_prefix("q="))
+                    .next()
+                    .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+
+                Some((coding, q))
+            })
+            .collect();
+
+        let quality = |name: &str| codings.iter()
+            .find(|(coding, _)| coding.eq_ignore_ascii_case(name))
+            .map(|(_, q)| *q);
+
+        let mut best: Option<(Encoding, f32)> = None;
+        for &encoding in &self.encodings {
+            let q = quality(encoding.name()).or_else(|| quality("*")).unwrap_or(0.0);
+            if q > 0.0 && best.map_or(true, |(_, best_q)| q > best_q) {
+                best = Some((encoding, q));
+            }
+        }
+
+        best.map(|(encoding, _)| encoding)
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for Compression {
+    fn info(&self) -> Info {
+        Info {
+            name: "Compression",
+            kind: Kind::Response | Kind::Singleton,
+        }
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let status = res.status();
+        if res.body().is_none()
+            || status.class().is_informational()
+            || status == Status::NoContent
+            || status == Status::NotModified
+            || status == Status::PartialContent
+            || res.headers().contains("Content-Encoding")
+        {
+            return;
+        }
+
+        match res.content_type() {
+            Some(content_type) if self.allows(content_type.media_type()) => {},
+            _ => return,
+        }
+
+        let size = res.body_mut().size().await;
+        if size.map_or(false, |size| size < self.min_size) {
+            return;
+        }
+
+        res.adjoin_raw_header("Vary", "Accept-Encoding");
+        let encoding = match self.negotiate(req.headers().get("Accept-Encoding")) {
+            Some(encoding) => encoding,
+            None => return,
+        };
+
+        let body = res.body_mut().take();
+        let chunk_size = body.max_chunk_size();
+        res.set_streamed_body(encoder::compress(body, encoding, chunk_size, size.is_none()));
+        res.set_raw_header("Content-Encoding", encoding.name());
+        res.remove_header("Content-Length");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Compression, Encoding};
+
+    fn negotiate(compression: &Compression, accept: &[&'static str]) -> Option<Encoding> {
+        compression.negotiate(accept.iter().copied())
+    }
+
+    #[test]
+    fn test_negotiate() {
+        let compression = Compression::default();
+        assert_eq!(negotiate(&compression, &[]), None);
+        assert_eq!(negotiate(&compression, &["identity"]), None);
+        assert_eq!(negotiate(&compression, &["gzip"]), Some(Encoding::Gzip));
+        assert_eq!(negotiate(&compression, &["gzip, br"]), Some(Encoding::Brotli));
+        assert_eq!(negotiate(&compression, &["gzip", "br"]), Some(Encoding::Brotli));
+        assert_eq!(negotiate(&compression, &["BR;q=0.5, gzip"]), Some(Encoding::Gzip));
+        assert_eq!(negotiate(&compression, &["gzip;q=0, br;q=0"]), None);
+        assert_eq!(negotiate(&compression, &["*"]), Some(Encoding::Brotli));
+        assert_eq!(negotiate(&compression, &["br;q=0, *;q=0.1"]), Some(Encoding::Gzip));
+        assert_eq!(negotiate(&compression, &["gzip;q=bad"]), None);
+
+        let gzip_first = Compression::default().encodings([Encoding::Gzip, Encoding::Brotli]);
+        assert_eq!(negotiate(&gzip_first, &["gzip, br"]), Some(Encoding::Gzip));
+        assert_eq!(negotiate(&gzip_first, &["gzip;q=0.8, br"]), Some(Encoding::Brotli));
+    }
+
+    #[test]
+    fn test_allows() {
+        use crate::http::MediaType;
+
+        let compression = Compression::default();
+        assert!(compression.allows(&MediaType::HTML));
+        assert!(compression.allows(&MediaType::Plain));
+        assert!(compression.allows(&MediaType::JSON));
+        assert!(!compression.allows(&MediaType::PNG));
+
+        let compression = compression.deny(MediaType::new("text", "*")).allow(MediaType::PNG);
+        assert!(!compression.allows(&MediaType::HTML));
+        assert!(compression.allows(&MediaType::PNG));
+    }
+}
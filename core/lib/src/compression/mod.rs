@@ -0,0 +1,55 @@
+//! Automatic compression of outgoing responses.
+//!
+//! The [`Compression`] fairing compresses the body of responses with an
+//! [`Encoding`] accepted by the client, as indicated by the request's
+//! [`Accept-Encoding`] header. Compression is applied only to responses that:
+//!
+//!   * have a `Content-Type` in the fairing's [allowlist](Compression::allow()),
+//!   * have a body of at least the [minimum size](Compression::min_size()) or
+//!     whose size is unknown, such as a [streamed](crate::response::stream)
+//!     body, and
+//!   * don't already have a `Content-Encoding` header and aren't partial,
+//!     `206 Partial Content`, responses.
+//!
+//! Bodies are compressed as they are written. A streamed body is flushed
+//! through the encoder as each chunk is produced, so clients receive every
+//! chunk of a [`ByteStream`] or [`EventStream`] as soon as it's available.
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::compression::Compression;
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build().attach(Compression::default())
+//! }
+//! ```
+//!
+//! # Request Decompression
+//!
+//! When the `compression` feature is enabled, the body of every incoming
+//! request with a `Content-Encoding` of `gzip` or `br` is decompressed before
+//! it reaches request fairings and data guards like [`Form`] and [`Json`]. The
+//! `Content-Encoding` and `Content-Length` headers are removed from such
+//! requests. Bodies with any other or with several encodings are left as-is.
+//!
+//! Because a small compressed body can decompress to a very large one, the
+//! decompressed body is capped by the `decompressed` [limit](crate::data::Limits),
+//! in addition to the limit imposed by the data guard. Reading past the cap
+//! fails with an error of kind [`InvalidData`](std::io::ErrorKind::InvalidData),
+//! causing the data guard to fail.
+//!
+//! [`Form`]: crate::form::Form
+//! [`Json`]: crate::serde::json::Json
+//! [`Accept-Encoding`]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Accept-Encoding
+//! [`ByteStream`]: crate::response::stream::ByteStream
+//! [`EventStream`]: crate::response::stream::EventStream
+
+mod fairing;
+mod encoder;
+mod decoder;
+
+pub use self::fairing::Compression;
+pub use self::encoder::Encoding;
+
+pub(crate) use self::decoder::{decompress, decompress_request};
@@ -1,14 +1,16 @@
 use std::net::{IpAddr, Ipv4Addr};
 
 use figment::{Figment, Profile, Provider, Metadata, error::Result};
-use figment::providers::{Serialized, Env, Toml, Format};
+use figment::providers::{Serialized, Env, Toml, Yaml, Json, Format};
 use figment::value::{Map, Dict, magic::RelativePathBuf};
 use serde::{Deserialize, Serialize};
 use yansi::Paint;
 
-use crate::config::{LogLevel, Shutdown, Ident};
+use crate::config::{LogLevel, Shutdown, Ident, ErrorFormat, UnixConfig, ListenFd, ListenerConfig};
+use crate::config::IpNet;
 use crate::request::{self, Request, FromRequest};
 use crate::data::Limits;
+use crate::http::uri::Absolute;
 
 #[cfg(feature = "tls")]
 use crate::config::TlsConfig;
@@ -65,9 +67,37 @@ pub struct Config {
     #[serde(skip)]
     pub profile: Profile,
     /// IP address to serve on. **(default: `127.0.0.1`)**
+    ///
+    /// _**Note:** An `address` of the form `unix:<path>` configures
+    /// [`Config::unix`] instead._
     pub address: IpAddr,
     /// Port to serve on. **(default: `8000`)**
     pub port: u16,
+    /// The Unix domain socket to serve on in place of `address` and `port`, if
+    /// any. See [`UnixConfig`] for details. **(default: `None`)**
+    pub unix: Option<UnixConfig>,
+    /// An inherited socket to serve on in place of `address`, `port`, and
+    /// `unix`, if any. See [`ListenFd`] for details. **(default: `None`)**
+    pub listen_fd: Option<ListenFd>,
+    /// Whether connections to the listener above begin with a [PROXY protocol]
+    /// header, version 1 or 2, carrying the client's address. **(default:
+    /// `false`)**
+    ///
+    /// Load balancers like HAProxy and those of cloud providers can prepend
+    /// the header to connections they forward. When enabled, the address in
+    /// the header is reported by [`Request::remote()`] and, absent an
+    /// `X-Real-IP` header, [`Request::client_ip()`].
+    /// Connections that don't begin with a valid header within a few seconds
+    /// are closed, so only enable this when _every_ connection arrives via
+    /// such a proxy.
+    ///
+    /// [PROXY protocol]: https://www.haproxy.org/download/2.8/doc/proxy-protocol.txt
+    /// [`Request::remote()`]: crate::Request::remote()
+    /// [`Request::client_ip()`]: crate::Request::client_ip()
+    pub proxy_protocol: bool,
+    /// Additional listeners to serve on alongside the one configured above.
+    /// See [`ListenerConfig`] for details. **(default: `[]`)**
+    pub listeners: Vec<ListenerConfig>,
     /// Number of threads to use for executing futures. **(default: `num_cores`)**
     ///
     /// _**Note:** Rocket only reads this value from sources in the [default
@@ -86,6 +116,21 @@ pub struct Config {
     pub temp_dir: RelativePathBuf,
     /// Keep-alive timeout in seconds; disabled when `0`. **(default: `5`)**
     pub keep_alive: u32,
+    /// Maximum number of connections open at once across all listeners, if
+    /// limited. While at the limit, no further connections are accepted.
+    /// **(default: `None`)**
+    pub max_connections: Option<usize>,
+    /// Maximum number of connections open at once from a single IP address, if
+    /// limited. Connections beyond the limit are closed as soon as they're
+    /// accepted. The address is the connection's remote address, as reported
+    /// by [PROXY protocol] headers when enabled. **(default: `None`)**
+    ///
+    /// [PROXY protocol]: Config::proxy_protocol
+    pub max_connections_per_ip: Option<usize>,
+    /// Maximum number of requests processed at once, if limited. Requests
+    /// beyond the limit are answered with a `503 Service Unavailable` without
+    /// being routed. **(default: `None`)**
+    pub max_requests: Option<usize>,
     /// The TLS configuration, if any. **(default: `None`)**
     #[cfg(feature = "tls")]
     #[cfg_attr(nightly, doc(cfg(feature = "tls")))]
@@ -98,13 +143,50 @@ pub struct Config {
     #[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
     #[serde(serialize_with = "SecretKey::serialize_zero")]
     pub secret_key: SecretKey,
+    /// Previously used secret keys, accepted when verifying and decrypting
+    /// cookies but never used to sign or encrypt new ones. **(default: `[]`)**
+    ///
+    /// _**Note:** This field _always_ serializes as an empty list to aid in
+    /// preventing leakage of the secret keys._
+    #[cfg(feature = "secrets")]
+    #[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
+    #[serde(serialize_with = "SecretKey::serialize_none")]
+    pub previous_secret_keys: Vec<SecretKey>,
     /// Graceful shutdown configuration. **(default: [`Shutdown::default()`])**
     pub shutdown: Shutdown,
+    /// Whether to reload the configuration, as if by
+    /// [`Rocket::refresh_config()`], when the process receives `SIGHUP`. Only
+    /// applies on Unix. **(default: `false`)**
+    ///
+    /// [`Rocket::refresh_config()`]: crate::Rocket::refresh_config()
+    #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
+    pub reload_on_sighup: bool,
     /// Max level to log. **(default: _debug_ `normal` / _release_ `critical`)**
     pub log_level: LogLevel,
     /// Whether to use colors and emoji when logging. **(default: `true`)**
     #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
     pub cli_colors: bool,
+    /// Format of the built-in default catcher's error documents.
+    /// **(default: [`ErrorFormat::Negotiate`])**
+    pub error_format: ErrorFormat,
+    /// The absolute URL at which the application is reachable, used to
+    /// generate absolute URIs. See [`Request::base_url()`]. **(default:
+    /// `None`)**
+    pub base_url: Option<Absolute<'static>>,
+    /// Networks of proxies whose forwarding headers are trusted, if any. See
+    /// [`Request::client_ip()`]. **(default: `None`)**
+    ///
+    /// When `None`, the `X-Real-IP` header is trusted regardless of who sent
+    /// it, as by [`Request::real_ip()`], while forwarded schemes and hosts are
+    /// ignored by [`Request::base_url()`]. Otherwise, the `Forwarded` header or, absent it, the
+    /// `X-Forwarded-For`, `X-Forwarded-Proto`, `X-Forwarded-Host`, and
+    /// `X-Real-IP` headers are only honored as far as each hop they record was
+    /// received by a proxy whose address lies within one of these networks.
+    /// An empty list trusts no proxy. Networks are written in CIDR notation,
+    /// as in `10.0.0.0/8` or `::1/128`.
+    ///
+    /// [`Request::client_ip()`]: crate::Request::client_ip()
+    pub trusted_proxies: Option<Vec<IpNet>>,
     /// PRIVATE: This structure may grow (but never change otherwise) in a
     /// non-breaking release. As such, constructing this structure should
     /// _always_ be done using a public constructor or update syntax:
@@ -171,19 +253,32 @@ impl Config {
             profile: Self::DEBUG_PROFILE,
             address: Ipv4Addr::new(127, 0, 0, 1).into(),
             port: 8000,
+            unix: None,
+            listen_fd: None,
+            proxy_protocol: false,
+            listeners: vec![],
             workers: num_cpus::get(),
             max_blocking: 512,
             ident: Ident::default(),
             limits: Limits::default(),
             temp_dir: std::env::temp_dir().into(),
             keep_alive: 5,
+            max_connections: None,
+            max_connections_per_ip: None,
+            max_requests: None,
             #[cfg(feature = "tls")]
             tls: None,
             #[cfg(feature = "secrets")]
             secret_key: SecretKey::zero(),
+            #[cfg(feature = "secrets")]
+            previous_secret_keys: vec![],
             shutdown: Shutdown::default(),
+            reload_on_sighup: false,
             log_level: LogLevel::Normal,
             cli_colors: true,
+            error_format: ErrorFormat::default(),
+            base_url: None,
+            trusted_proxies: None,
             __non_exhaustive: (),
         }
     }
@@ -217,9 +312,13 @@ impl Config {
     /// priority order:
     ///
     ///   1. [`Config::default()`] (see [defaults](#defaults))
-    ///   2. `Rocket.toml` _or_ filename in `ROCKET_CONFIG` environment variable
+    ///   2. `Rocket.toml`, `Rocket.yaml`, and `Rocket.json`, in that order,
+    ///      _or_ filename in `ROCKET_CONFIG` environment variable
     ///   3. `ROCKET_` prefixed environment variables
     ///
+    /// The file in `ROCKET_CONFIG` is read as YAML if its extension is `yaml`
+    /// or `yml`, as JSON if its extension is `json`, and as TOML otherwise.
+    ///
     /// The profile selected is the value set in the `ROCKET_PROFILE`
     /// environment variable. If it is not set, it defaults to `debug` when
     /// compiled in debug mode and `release` when compiled in release mode.
@@ -241,11 +340,42 @@ impl Config {
     /// ```
     pub fn figment() -> Figment {
         Figment::from(Config::default())
-            .merge(Toml::file(Env::var_or("ROCKET_CONFIG", "Rocket.toml")).nested())
+            .merge(Config::file_figment())
             .merge(Env::prefixed("ROCKET_").ignore(&["PROFILE"]).global())
             .select(Profile::from_env_or("ROCKET_PROFILE", Self::DEFAULT_PROFILE))
     }
 
+    /// Returns `base` with the configuration file and environment variables
+    /// read by [`Config::figment()`] merged anew, under `base`'s profile.
+    pub(crate) fn refresh_figment(base: &Figment) -> Figment {
+        base.clone()
+            .merge(Config::file_figment())
+            .merge(Env::prefixed("ROCKET_").ignore(&["PROFILE"]).global())
+            .select(base.profile().clone())
+    }
+
+    /// Returns a figment of the nested configuration file in `ROCKET_CONFIG`,
+    /// in the format indicated by its extension, or else of `Rocket.toml`,
+    /// `Rocket.yaml`, and `Rocket.json`.
+    fn file_figment() -> Figment {
+        let path = match Env::var("ROCKET_CONFIG") {
+            Some(path) => path,
+            None => return Figment::from(Toml::file("Rocket.toml").nested())
+                .merge(Yaml::file("Rocket.yaml").nested())
+                .merge(Json::file("Rocket.json").nested()),
+        };
+
+        let extension = std::path::Path::new(&path).extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase());
+
+        match extension.as_deref() {
+            Some("yaml") | Some("yml") => Figment::from(Yaml::file(path).nested()),
+            Some("json") => Figment::from(Json::file(path).nested()),
+            _ => Figment::from(Toml::file(path).nested()),
+        }
+    }
+
     /// Attempts to extract a `Config` from `provider`, returning the result.
     ///
     /// # Example
@@ -263,7 +393,7 @@ impl Config {
     /// let config = Config::try_from(figment);
     /// ```
     pub fn try_from<T: Provider>(provider: T) -> Result<Self> {
-        let figment = Figment::from(provider);
+        let figment = UnixConfig::lift_address(Figment::from(provider));
         let mut config = figment.extract::<Self>()?;
         config.profile = figment.profile().clone();
         Ok(config)
@@ -358,8 +488,23 @@ impl Config {
         }
 
         launch_meta!("{}Configured for {}.", Paint::emoji("🔧 "), self.profile);
-        launch_meta_!("address: {}", bold(&self.address));
-        launch_meta_!("port: {}", bold(&self.port));
+        match (self.listen_fd, &self.unix) {
+            (Some(fd), _) => launch_meta_!("address: {}", bold(fd)),
+            (None, Some(unix)) => launch_meta_!("address: {}", bold(unix)),
+            (None, None) => {
+                launch_meta_!("address: {}", bold(&self.address));
+                launch_meta_!("port: {}", bold(&self.port));
+            }
+        }
+
+        if self.proxy_protocol {
+            launch_meta_!("proxy protocol: {}", bold("enabled"));
+        }
+
+        for listener in &self.listeners {
+            launch_meta_!("listener: {}", bold(listener));
+        }
+
         launch_meta_!("workers: {}", bold(self.workers));
         launch_meta_!("max blocking threads: {}", bold(self.max_blocking));
         launch_meta_!("ident: {}", bold(&self.ident));
@@ -372,6 +517,18 @@ impl Config {
             ka => launch_meta_!("keep-alive: {}{}", bold(ka), bold("s")),
         }
 
+        if let Some(max) = self.max_connections {
+            launch_meta_!("max connections: {}", bold(max));
+        }
+
+        if let Some(max) = self.max_connections_per_ip {
+            launch_meta_!("max connections per ip: {}", bold(max));
+        }
+
+        if let Some(max) = self.max_requests {
+            launch_meta_!("max requests: {}", bold(max));
+        }
+
         match (self.tls_enabled(), self.mtls_enabled()) {
             (true, true) => launch_meta_!("tls: {}", bold("enabled w/mtls")),
             (true, false) => launch_meta_!("tls: {} w/o mtls", bold("enabled")),
@@ -385,11 +542,33 @@ impl Config {
                 launch_meta_!("disable `secrets` feature or configure a `secret_key`");
                 launch_meta_!("this becomes an {} in non-debug profiles", Paint::red("error"));
             }
+
+            if !self.previous_secret_keys.is_empty() {
+                launch_meta_!("previous secret keys: {}", bold(self.previous_secret_keys.len()));
+            }
         }
 
         launch_meta_!("shutdown: {}", bold(&self.shutdown));
+        if self.reload_on_sighup {
+            launch_meta_!("reload on sighup: {}", bold("enabled"));
+        }
+
         launch_meta_!("log level: {}", bold(self.log_level));
         launch_meta_!("cli colors: {}", bold(&self.cli_colors));
+        launch_meta_!("error format: {}", bold(self.error_format));
+
+        if let Some(ref base_url) = self.base_url {
+            launch_meta_!("base url: {}", bold(base_url));
+        }
+
+        if let Some(ref proxies) = self.trusted_proxies {
+            let proxies: Vec<_> = proxies.iter().map(|net| net.to_string()).collect();
+            if proxies.is_empty() {
+                launch_meta_!("trusted proxies: {}", bold("none"));
+            } else {
+                launch_meta_!("trusted proxies: {}", bold(proxies.join(", ")));
+            }
+        }
 
         // Check for now depreacted config values.
         for (key, replacement) in Self::DEPRECATED_KEYS {
@@ -452,6 +631,18 @@ impl Config {
     /// The stringy parameter name for setting/extracting [`Config::port`].
     pub const PORT: &'static str = "port";
 
+    /// The stringy parameter name for setting/extracting [`Config::unix`].
+    pub const UNIX: &'static str = "unix";
+
+    /// The stringy parameter name for setting/extracting [`Config::listen_fd`].
+    pub const LISTEN_FD: &'static str = "listen_fd";
+
+    /// The stringy parameter name for setting/extracting [`Config::proxy_protocol`].
+    pub const PROXY_PROTOCOL: &'static str = "proxy_protocol";
+
+    /// The stringy parameter name for setting/extracting [`Config::listeners`].
+    pub const LISTENERS: &'static str = "listeners";
+
     /// The stringy parameter name for setting/extracting [`Config::workers`].
     pub const WORKERS: &'static str = "workers";
 
@@ -461,6 +652,18 @@ impl Config {
     /// The stringy parameter name for setting/extracting [`Config::keep_alive`].
     pub const KEEP_ALIVE: &'static str = "keep_alive";
 
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::max_connections`].
+    pub const MAX_CONNECTIONS: &'static str = "max_connections";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::max_connections_per_ip`].
+    pub const MAX_CONNECTIONS_PER_IP: &'static str = "max_connections_per_ip";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::max_requests`].
+    pub const MAX_REQUESTS: &'static str = "max_requests";
+
     /// The stringy parameter name for setting/extracting [`Config::limits`].
     pub const LIMITS: &'static str = "limits";
 
@@ -470,6 +673,10 @@ impl Config {
     /// The stringy parameter name for setting/extracting [`Config::secret_key`].
     pub const SECRET_KEY: &'static str = "secret_key";
 
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::previous_secret_keys`].
+    pub const PREVIOUS_SECRET_KEYS: &'static str = "previous_secret_keys";
+
     /// The stringy parameter name for setting/extracting [`Config::temp_dir`].
     pub const TEMP_DIR: &'static str = "temp_dir";
 
@@ -479,8 +686,22 @@ impl Config {
     /// The stringy parameter name for setting/extracting [`Config::shutdown`].
     pub const SHUTDOWN: &'static str = "shutdown";
 
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::reload_on_sighup`].
+    pub const RELOAD_ON_SIGHUP: &'static str = "reload_on_sighup";
+
     /// The stringy parameter name for setting/extracting [`Config::cli_colors`].
     pub const CLI_COLORS: &'static str = "cli_colors";
+
+    /// The stringy parameter name for setting/extracting [`Config::error_format`].
+    pub const ERROR_FORMAT: &'static str = "error_format";
+
+    /// The stringy parameter name for setting/extracting [`Config::base_url`].
+    pub const BASE_URL: &'static str = "base_url";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::trusted_proxies`].
+    pub const TRUSTED_PROXIES: &'static str = "trusted_proxies";
 }
 
 impl Provider for Config {
@@ -501,6 +722,18 @@ impl Provider for Config {
             }
         }
 
+        // Likewise for `previous_secret_keys`, whose serializer empties.
+        #[cfg(feature = "secrets")]
+        if !self.previous_secret_keys.is_empty() {
+            if let Some(map) = map.get_mut(&Profile::Default) {
+                let keys: Vec<_> = self.previous_secret_keys.iter()
+                    .map(|key| key.key.master())
+                    .collect();
+
+                map.insert("previous_secret_keys".into(), keys.into());
+            }
+        }
+
         Ok(map)
     }
 
@@ -0,0 +1,177 @@
+use std::fmt;
+#[cfg(unix)]
+use std::io;
+
+use serde::{de, Deserialize, Serialize, Serializer, Deserializer};
+
+/// An inherited, already listening socket to serve on.
+///
+/// When configured via the `listen_fd` parameter, Rocket serves on a socket
+/// that was opened and bound by its parent process instead of binding one
+/// itself. This enables [socket activation] by a service manager like systemd
+/// as well as zero-downtime restarts, where a supervisor keeps the socket open
+/// while the application is replaced and no connection is refused meanwhile.
+///
+/// The socket may be a TCP or a Unix domain socket. It must already be bound
+/// and listening. The configured `address`, `port`, and [`unix`] parameters
+/// are ignored. The `tls` configuration is applied to inherited TCP sockets.
+///
+/// ## (De)serialization
+///
+/// `ListenFd` deserializes from either:
+///
+///   * **The string `"systemd"`**
+///
+///     Serve on the first socket passed via the systemd socket activation
+///     protocol, that is, file descriptor `3` when the `LISTEN_PID` environment
+///     variable is the current process's ID and `LISTEN_FDS` is at least `1`,
+///     as set by systemd or tools like `systemfd`. The variables are removed
+///     from the environment when the socket is taken so that child processes
+///     don't inherit them.
+///
+///   * **An integer**
+///
+///     Serve on the socket with the given file descriptor.
+///
+/// In `Rocket.toml`, with an accompanying `app.socket` systemd unit:
+///
+/// ```toml
+/// [release]
+/// listen_fd = "systemd"
+/// ```
+///
+/// Inherited sockets are only supported on Unix; on other platforms, the launch
+/// fails.
+///
+/// [socket activation]: https://www.freedesktop.org/software/systemd/man/sd_listen_fds.html
+/// [`unix`]: crate::Config::unix
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ListenFd {
+    /// The first socket passed via systemd's socket activation protocol.
+    Systemd,
+    /// The socket with the file descriptor.
+    Fd(i32),
+}
+
+/// The first file descriptor passed via systemd's socket activation protocol.
+#[cfg(unix)]
+const SD_LISTEN_FDS_START: i32 = 3;
+
+impl ListenFd {
+    /// Returns the file descriptor of the inherited socket, taking it from the
+    /// environment for [`ListenFd::Systemd`].
+    #[cfg(unix)]
+    fn take(self) -> io::Result<i32> {
+        use std::env;
+
+        let fd = match self {
+            ListenFd::Fd(fd) => return Ok(fd),
+            ListenFd::Systemd => SD_LISTEN_FDS_START,
+        };
+
+        let var = |name| env::var(name).ok().and_then(|v| v.parse::<u32>().ok());
+        let passed = var("LISTEN_PID") == Some(std::process::id())
+            && var("LISTEN_FDS").map_or(false, |n| n >= 1);
+
+        if !passed {
+            let msg = "no socket was passed via systemd socket activation (`LISTEN_FDS`)";
+            return Err(io::Error::new(io::ErrorKind::NotFound, msg));
+        }
+
+        for name in &["LISTEN_PID", "LISTEN_FDS", "LISTEN_FDNAMES"] {
+            env::remove_var(name);
+        }
+
+        Ok(fd)
+    }
+
+    /// Takes ownership of the inherited socket, returning it as either a TCP
+    /// or a Unix domain socket listener, ready for use with `tokio`.
+    #[cfg(unix)]
+    pub(crate) fn listener(self) -> io::Result<Inherited> {
+        use std::os::unix::io::{FromRawFd, IntoRawFd};
+
+        let fd = self.take()?;
+        if fd < 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "negative file descriptor"));
+        }
+
+        // SAFETY: By configuration, the socket is the application's to serve
+        // on. It is only ever taken once per launch.
+        let tcp = unsafe { std::net::TcpListener::from_raw_fd(fd) };
+        if tcp.local_addr().is_ok() {
+            tcp.set_nonblocking(true)?;
+            return Ok(Inherited::Tcp(crate::http::private::TcpListener::from_std(tcp)?));
+        }
+
+        // SAFETY: As above. `tcp` relinquishes ownership via `into_raw_fd()`.
+        let unix = unsafe { std::os::unix::net::UnixListener::from_raw_fd(tcp.into_raw_fd()) };
+        if let Err(e) = unix.local_addr() {
+            let msg = format!("file descriptor {} is not a TCP or Unix socket: {}", fd, e);
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+        }
+
+        unix.set_nonblocking(true)?;
+        Ok(Inherited::Unix(crate::http::private::UnixListener::from_std(unix)?))
+    }
+}
+
+/// An inherited socket listener.
+#[cfg(unix)]
+pub(crate) enum Inherited {
+    Tcp(crate::http::private::TcpListener),
+    Unix(crate::http::private::UnixListener),
+}
+
+impl fmt::Display for ListenFd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ListenFd::Systemd => "systemd".fmt(f),
+            ListenFd::Fd(fd) => write!(f, "fd {}", fd),
+        }
+    }
+}
+
+impl Serialize for ListenFd {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ListenFd::Systemd => ser.serialize_str("systemd"),
+            ListenFd::Fd(fd) => ser.serialize_i32(*fd),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ListenFd {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = ListenFd;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("\"systemd\" or a file descriptor")
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                v.try_into()
+                    .map(ListenFd::Fd)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Signed(v), &self))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                v.try_into()
+                    .map(ListenFd::Fd)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(v), &self))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                match v {
+                    v if v.eq_ignore_ascii_case("systemd") => Ok(ListenFd::Systemd),
+                    _ => Err(E::invalid_value(de::Unexpected::Str(v), &self)),
+                }
+            }
+        }
+
+        de.deserialize_any(Visitor)
+    }
+}
@@ -0,0 +1,182 @@
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::UnixConfig;
+
+#[cfg(feature = "tls")]
+use crate::config::TlsConfig;
+
+/// An additional listener to serve on alongside the primary one.
+///
+/// Rocket serves on the socket configured via the top-level `address`, `port`,
+/// `unix`, and `tls` parameters. Each entry in the `listeners` array configures
+/// another socket, with its own TLS settings, that the same instance of
+/// `Rocket` serves on simultaneously. All listeners share a single
+/// [`Shutdown`](crate::Shutdown): a shutdown stops every listener gracefully,
+/// and the launch returns once all of them have finished.
+///
+/// | key       | default     | description                                        |
+/// |-----------|-------------|----------------------------------------------------|
+/// | `address` | `127.0.0.1` | IP address to serve on                             |
+/// | `port`    | `8000`      | port to serve on                                   |
+/// | `unix`    | _none_      | [`UnixConfig`] to serve on instead, if any         |
+/// | `tls`     | _none_      | [`TlsConfig`] to serve with, if any, or plain HTTP |
+/// | `proxy_protocol` | `false` | whether connections begin with a PROXY protocol header |
+///
+/// A listener's `tls` is independent of the top-level `tls`: a listener without
+/// one serves plain HTTP even when the primary listener serves HTTPS and vice
+/// versa. Certificates of all listeners are reloaded by
+/// [`Rocket::reload_tls()`](crate::Rocket::reload_tls()); each listener's
+/// `reload_on_sighup` applies to its own certificates. HTTP/3 is only served on
+/// the primary listener. As with the top-level parameters, Unix domain sockets
+/// can't be combined with TLS.
+///
+/// In `Rocket.toml`, serving HTTPS on port `443` and plain HTTP on port `80`
+/// via an additional listener, then a Unix domain socket for a local proxy via
+/// another, might look like:
+///
+/// ```toml
+/// [release]
+/// address = "0.0.0.0"
+/// port = 443
+/// tls = { certs = "/ssl/certs.pem", key = "/ssl/key.pem" }
+///
+/// [[release.listeners]]
+/// address = "0.0.0.0"
+/// port = 80
+///
+/// [[release.listeners]]
+/// unix = { path = "/run/app.sock" }
+/// ```
+///
+/// Programmatically, a listener is configured via [`ListenerConfig::new()`] or
+/// [`ListenerConfig::unix()`]:
+///
+/// ```rust
+/// use std::net::Ipv4Addr;
+/// use rocket::config::{Config, ListenerConfig, UnixConfig};
+///
+/// let config = Config {
+///     listeners: vec![
+///         ListenerConfig::new(Ipv4Addr::UNSPECIFIED, 80),
+///         ListenerConfig::unix(UnixConfig::new("/run/app.sock")),
+///     ],
+///     ..Config::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ListenerConfig {
+    /// IP address to serve on. **(default: `127.0.0.1`)**
+    #[serde(default = "default_address")]
+    pub address: IpAddr,
+    /// Port to serve on. **(default: `8000`)**
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// The Unix domain socket to serve on in place of `address` and `port`, if
+    /// any. **(default: `None`)**
+    #[serde(default)]
+    pub unix: Option<UnixConfig>,
+    /// The TLS configuration to serve with, if any. **(default: `None`)**
+    #[cfg(feature = "tls")]
+    #[cfg_attr(nightly, doc(cfg(feature = "tls")))]
+    #[serde(default)]
+    pub tls: Option<TlsConfig>,
+    /// Whether connections begin with a PROXY protocol header. See
+    /// [`Config::proxy_protocol`](crate::Config::proxy_protocol) for details.
+    /// **(default: `false`)**
+    #[serde(default)]
+    pub proxy_protocol: bool,
+}
+
+fn default_address() -> IpAddr {
+    Ipv4Addr::new(127, 0, 0, 1).into()
+}
+
+fn default_port() -> u16 {
+    8000
+}
+
+impl ListenerConfig {
+    /// Returns a listener configuration serving plain HTTP on `address` and
+    /// `port`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::net::Ipv4Addr;
+    /// use rocket::config::ListenerConfig;
+    ///
+    /// let listener = ListenerConfig::new(Ipv4Addr::UNSPECIFIED, 80);
+    /// assert_eq!(listener.port, 80);
+    /// assert!(listener.unix.is_none());
+    /// ```
+    pub fn new<A: Into<IpAddr>>(address: A, port: u16) -> Self {
+        ListenerConfig {
+            address: address.into(),
+            port,
+            unix: None,
+            #[cfg(feature = "tls")]
+            tls: None,
+            proxy_protocol: false,
+        }
+    }
+
+    /// Returns a listener configuration serving plain HTTP on the Unix domain
+    /// socket configured by `unix`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{ListenerConfig, UnixConfig};
+    ///
+    /// let listener = ListenerConfig::unix(UnixConfig::new("/run/app.sock"));
+    /// assert_eq!(listener.unix, Some(UnixConfig::new("/run/app.sock")));
+    /// ```
+    pub fn unix(unix: UnixConfig) -> Self {
+        ListenerConfig { unix: Some(unix), ..ListenerConfig::new(default_address(), default_port()) }
+    }
+
+    /// Serves TLS configured by `tls` on this listener.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::net::Ipv4Addr;
+    /// use rocket::config::{ListenerConfig, TlsConfig};
+    ///
+    /// let tls = TlsConfig::from_paths("/ssl/certs.pem", "/ssl/key.pem");
+    /// let listener = ListenerConfig::new(Ipv4Addr::UNSPECIFIED, 443).with_tls(tls);
+    /// assert!(listener.tls.is_some());
+    /// ```
+    #[cfg(feature = "tls")]
+    #[cfg_attr(nightly, doc(cfg(feature = "tls")))]
+    pub fn with_tls(mut self, tls: TlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Returns `true` if this listener serves TLS.
+    pub(crate) fn tls_enabled(&self) -> bool {
+        #[cfg(feature = "tls")] {
+            self.tls.is_some()
+        }
+
+        #[cfg(not(feature = "tls"))] {
+            false
+        }
+    }
+}
+
+impl fmt::Display for ListenerConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.unix {
+            Some(ref unix) => unix.fmt(f),
+            None => {
+                let proto = if self.tls_enabled() { "https" } else { "http" };
+                write!(f, "{}://{}", proto, SocketAddr::new(self.address, self.port))
+            }
+        }
+    }
+}
@@ -114,6 +114,10 @@
 mod ident;
 mod config;
 mod shutdown;
+mod unix;
+mod listen_fd;
+mod listener;
+mod secrets;
 
 #[cfg(feature = "tls")]
 mod tls;
@@ -125,11 +129,22 @@ mod secret_key;
 pub use config::pretty_print_error;
 pub use config::Config;
 pub use crate::log::LogLevel;
+pub use crate::catcher::ErrorFormat;
 pub use shutdown::Shutdown;
 pub use ident::Ident;
+pub use unix::UnixConfig;
+pub use listen_fd::ListenFd;
+pub use listener::ListenerConfig;
+pub use ipnet::IpNet;
+pub use secrets::{SecretProvider, SecretError, EnvSecrets, FileSecrets};
+
+pub(crate) use secrets::SecretProviders;
+
+#[cfg(unix)]
+pub(crate) use listen_fd::Inherited;
 
 #[cfg(feature = "tls")]
-pub use tls::{TlsConfig, CipherSuite};
+pub use tls::{TlsConfig, SniCert, CipherSuite};
 
 #[cfg(feature = "mtls")]
 pub use tls::MutualTls;
@@ -137,6 +152,12 @@ pub use tls::MutualTls;
 #[cfg(feature = "secrets")]
 pub use secret_key::SecretKey;
 
+#[cfg(feature = "vault")]
+pub use secrets::Vault;
+
+#[cfg(feature = "aws-secrets")]
+pub use secrets::AwsSecretsManager;
+
 #[cfg(unix)]
 pub use shutdown::Sig;
 
@@ -148,7 +169,7 @@ mod tests {
 
     use crate::log::LogLevel;
     use crate::data::{Limits, ToByteUnit};
-    use crate::config::Config;
+    use crate::config::{Config, ErrorFormat};
 
     #[test]
     fn test_figment_is_default() {
@@ -205,6 +226,7 @@ mod tests {
                 keep_alive = 10
                 log_level = "off"
                 cli_colors = 0
+                error_format = "json"
             "#)?;
 
             let config = Config::from(Config::figment());
@@ -216,6 +238,7 @@ mod tests {
                 keep_alive: 10,
                 log_level: LogLevel::Off,
                 cli_colors: false,
+                error_format: ErrorFormat::Json,
                 ..Config::default()
             });
 
@@ -268,6 +291,59 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_yaml_and_json_files() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file("Rocket.yaml", r#"
+                default:
+                  address: 1.2.3.4
+                  port: 1234
+                debug:
+                  workers: 20
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            assert_eq!(config, Config {
+                address: Ipv4Addr::new(1, 2, 3, 4).into(),
+                port: 1234,
+                workers: 20,
+                ..Config::default()
+            });
+
+            jail.create_file("Rocket.toml", r#"
+                [default]
+                port = 4321
+                keep_alive = 10
+            "#)?;
+
+            jail.create_file("Rocket.json", r#"
+                { "default": { "address": "5.6.7.8", "log_level": "off" } }
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            assert_eq!(config, Config {
+                address: Ipv4Addr::new(5, 6, 7, 8).into(),
+                port: 1234,
+                workers: 20,
+                keep_alive: 10,
+                log_level: LogLevel::Off,
+                ..Config::default()
+            });
+
+            jail.set_env("ROCKET_CONFIG", "Other.yml");
+            jail.create_file("Other.yml", "default: { port: 5555 }")?;
+            let config = Config::from(Config::figment());
+            assert_eq!(config, Config { port: 5555, ..Config::default() });
+
+            jail.set_env("ROCKET_CONFIG", "Other.JSON");
+            jail.create_file("Other.JSON", r#"{ "default": { "port": 6666 } }"#)?;
+            let config = Config::from(Config::figment());
+            assert_eq!(config, Config { port: 6666, ..Config::default() });
+
+            Ok(())
+        });
+    }
+
     #[test]
     #[cfg(feature = "tls")]
     fn test_tls_config_from_file() {
@@ -490,6 +566,132 @@ mod tests {
         });
     }
 
+    #[test]
+    #[cfg(feature = "tls")]
+    fn test_sni_config() {
+        use std::path::Path;
+        use crate::config::{TlsConfig, SniCert};
+
+        figment::Jail::expect_with(|jail| {
+            jail.create_file("Rocket.toml", r#"
+                [default.tls]
+                certs = "/ssl/cert.pem"
+                key = "/ssl/key.pem"
+
+                [default.tls.sni."api.rocket.rs"]
+                certs = "/ssl/api/cert.pem"
+                key = "/ssl/api/key.pem"
+
+                [default.tls.sni."*.rocket.rs"]
+                certs = "relative/cert.pem"
+                key = "relative/key.pem"
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            let tls = config.tls.as_ref().unwrap();
+            let sni = |name| tls.sni().find(|(n, _)| *n == name).unwrap().1;
+            assert_eq!(tls.sni().count(), 2);
+            assert_eq!(sni("api.rocket.rs").certs().unwrap_left(), Path::new("/ssl/api/cert.pem"));
+            assert_eq!(sni("api.rocket.rs").key().unwrap_left(), Path::new("/ssl/api/key.pem"));
+            assert_eq!(sni("*.rocket.rs").certs().unwrap_left(),
+                jail.directory().join("relative/cert.pem"));
+
+            assert_eq!(tls, &TlsConfig::from_paths("/ssl/cert.pem", "/ssl/key.pem")
+                .with_sni("api.rocket.rs", SniCert::from_paths("/ssl/api/cert.pem", "/ssl/api/key.pem"))
+                .with_sni("*.rocket.rs", SniCert::from_paths(
+                    jail.directory().join("relative/cert.pem"),
+                    jail.directory().join("relative/key.pem"),
+                )));
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_unix_config() {
+        use crate::config::UnixConfig;
+
+        figment::Jail::expect_with(|jail| {
+            jail.create_file("Rocket.toml", r#"
+                [default]
+                address = "unix:/run/app.sock"
+                port = 1234
+
+                [default.unix]
+                mode = 0o660
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            assert_eq!(config.unix, Some(UnixConfig::new("/run/app.sock").mode(0o660)));
+            assert_eq!(config.address, Config::default().address);
+            assert_eq!(config.port, 1234);
+
+            jail.set_env("ROCKET_ADDRESS", "unix:relative.sock");
+            let config = Config::from(Config::figment());
+            assert_eq!(config.unix, Some(UnixConfig::new("relative.sock").mode(0o660)));
+
+            // Without an `address` of the form `unix:<path>`, `unix` needs a `path`.
+            jail.set_env("ROCKET_ADDRESS", "10.0.0.1");
+            assert!(Config::try_from(Config::figment()).is_err());
+
+            jail.create_file("Rocket.toml", r#"
+                [default.unix]
+                path = "/run/app.sock"
+                reuse = false
+                remove = false
+            "#)?;
+
+            jail.clear_env();
+            let config = Config::from(Config::figment());
+            assert_eq!(config.unix, Some(UnixConfig::new("/run/app.sock").reuse(false).remove(false)));
+            assert_eq!(config.address, Config::default().address);
+
+            jail.create_file("Rocket.toml", "")?;
+            assert_eq!(Config::from(Config::figment()).unix, None);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_listen_fd_config() {
+        use crate::config::ListenFd;
+
+        figment::Jail::expect_with(|jail| {
+            jail.create_file("Rocket.toml", r#"
+                [default]
+                listen_fd = "systemd"
+
+                [debug]
+                listen_fd = 3
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            assert_eq!(config.listen_fd, Some(ListenFd::Fd(3)));
+
+            let config = Config::from(Config::figment().select("release"));
+            assert_eq!(config.listen_fd, Some(ListenFd::Systemd));
+
+            jail.set_env("ROCKET_LISTEN_FD", "SystemD");
+            let config = Config::from(Config::figment());
+            assert_eq!(config.listen_fd, Some(ListenFd::Systemd));
+
+            jail.set_env("ROCKET_LISTEN_FD", "7");
+            let config = Config::from(Config::figment());
+            assert_eq!(config.listen_fd, Some(ListenFd::Fd(7)));
+
+            jail.set_env("ROCKET_LISTEN_FD", "inetd");
+            assert!(Config::try_from(Config::figment()).is_err());
+
+            jail.set_env("ROCKET_LISTEN_FD", "4294967296");
+            assert!(Config::try_from(Config::figment()).is_err());
+
+            jail.clear_env();
+            jail.create_file("Rocket.toml", "")?;
+            assert_eq!(Config::from(Config::figment()).listen_fd, None);
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_profiles_merge() {
         figment::Jail::expect_with(|jail| {
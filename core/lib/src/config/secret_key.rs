@@ -173,6 +173,13 @@ impl SecretKey {
     {
         ser.serialize_bytes(&[0; 32][..])
     }
+
+    /// Serialize as an empty sequence to avoid key leakage.
+    pub(crate) fn serialize_none<S>(_: &[SecretKey], ser: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer
+    {
+        ser.collect_seq(std::iter::empty::<u8>())
+    }
 }
 
 impl PartialEq for SecretKey {
@@ -0,0 +1,274 @@
+use std::path::PathBuf;
+
+use hyper::Method;
+use ring::{digest, hmac};
+use serde_json::{json, Value};
+use time::{OffsetDateTime, macros::format_description};
+
+use crate::http_client::Client;
+use super::{SecretProvider, SecretError};
+
+const SERVICE: &str = "secretsmanager";
+const TARGET: &str = "secretsmanager.GetSecretValue";
+const CONTENT_TYPE: &str = "application/x-amz-json-1.1";
+
+/// A [`SecretProvider`] for the `aws` scheme, resolving references to secrets
+/// stored in [AWS Secrets Manager].
+///
+/// A reference is the ID, name or ARN, of a secret, resolving to the secret's
+/// string value. When the value is a JSON object, as is the case for key/value
+/// secrets created in the AWS console, a reference of the form `secret-id#key`
+/// resolves to the value of `key` in the object instead:
+///
+/// ```toml
+/// [release]
+/// secret_key = { secret = "aws:prod/app#secret_key" }
+/// ```
+///
+/// Requests are signed with [Signature Version 4] using static credentials.
+///
+/// [AWS Secrets Manager]: https://aws.amazon.com/secrets-manager/
+/// [Signature Version 4]: https://docs.aws.amazon.com/general/latest/gr/signature-version-4.html
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use rocket::config::AwsSecretsManager;
+///
+/// #[rocket::launch]
+/// fn rocket() -> _ {
+///     let aws = AwsSecretsManager::from_env().expect("AWS credentials are set");
+///     rocket::build().secret_provider(aws)
+/// }
+/// ```
+#[derive(Clone)]
+pub struct AwsSecretsManager {
+    region: String,
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+    endpoint: Option<String>,
+    ca_certs: Option<PathBuf>,
+}
+
+impl AwsSecretsManager {
+    /// Returns a provider for Secrets Manager in `region`, such as
+    /// `us-east-1`, authenticating with the access key `access_key_id` and
+    /// `secret_access_key`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::AwsSecretsManager;
+    ///
+    /// let aws = AwsSecretsManager::new("us-east-1", "AKIDEXAMPLE", "secret");
+    /// ```
+    pub fn new<R, I, S>(region: R, access_key_id: I, secret_access_key: S) -> Self
+        where R: Into<String>, I: Into<String>, S: Into<String>
+    {
+        AwsSecretsManager {
+            region: region.into(),
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            session_token: None,
+            endpoint: None,
+            ca_certs: None,
+        }
+    }
+
+    /// Returns a provider configured via AWS's standard environment variables:
+    /// `AWS_REGION` or `AWS_DEFAULT_REGION`, `AWS_ACCESS_KEY_ID`,
+    /// `AWS_SECRET_ACCESS_KEY`, and, optionally, `AWS_SESSION_TOKEN` and
+    /// `AWS_CA_BUNDLE`. Returns `None` if the region or either part of the
+    /// access key is not set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::AwsSecretsManager;
+    ///
+    /// let aws = AwsSecretsManager::from_env();
+    /// ```
+    pub fn from_env() -> Option<Self> {
+        let var = |name| std::env::var(name).ok();
+        let region = var("AWS_REGION").or_else(|| var("AWS_DEFAULT_REGION"))?;
+        let mut aws = Self::new(region, var("AWS_ACCESS_KEY_ID")?, var("AWS_SECRET_ACCESS_KEY")?);
+        if let Some(token) = var("AWS_SESSION_TOKEN") {
+            aws = aws.session_token(token);
+        }
+
+        if let Some(path) = var("AWS_CA_BUNDLE") {
+            aws = aws.ca_certs(path);
+        }
+
+        Some(aws)
+    }
+
+    /// Sets the session token of temporary credentials.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::AwsSecretsManager;
+    ///
+    /// let aws = AwsSecretsManager::new("us-east-1", "AKIDEXAMPLE", "secret")
+    ///     .session_token("token");
+    /// ```
+    pub fn session_token<T: Into<String>>(mut self, token: T) -> Self {
+        self.session_token = Some(token.into());
+        self
+    }
+
+    /// Sets the endpoint requests are sent to in place of the regional
+    /// endpoint, `https://secretsmanager.{region}.amazonaws.com`. This is
+    /// useful for VPC endpoints and local emulators.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::AwsSecretsManager;
+    ///
+    /// let aws = AwsSecretsManager::new("us-east-1", "AKIDEXAMPLE", "secret")
+    ///     .endpoint("http://localhost:4566");
+    /// ```
+    pub fn endpoint<E: Into<String>>(mut self, endpoint: E) -> Self {
+        self.endpoint = Some(endpoint.into().trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Sets the path to a PEM file of CA certificates the endpoint's
+    /// certificate is verified against in place of the web PKI roots.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::AwsSecretsManager;
+    ///
+    /// let aws = AwsSecretsManager::new("us-east-1", "AKIDEXAMPLE", "secret")
+    ///     .endpoint("https://secrets.internal")
+    ///     .ca_certs("/etc/ssl/internal-ca.pem");
+    /// ```
+    pub fn ca_certs<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.ca_certs = Some(path.into());
+        self
+    }
+
+    /// Returns the `Authorization` header value for a request with `headers`,
+    /// sorted by lowercase name, and `body`, made at `timestamp`, formatted as
+    /// `YYYYMMDD'T'HHMMSS'Z'`.
+    fn authorization(&self, timestamp: &str, headers: &[(&str, &str)], body: &[u8]) -> String {
+        let date = &timestamp[..8];
+        let canonical_headers: String = headers.iter()
+            .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+            .collect();
+
+        let signed_headers = headers.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(";");
+        let canonical_request = format!("POST\n/\n\n{}\n{}\n{}",
+            canonical_headers, signed_headers, hex(digest::digest(&digest::SHA256, body)));
+
+        let scope = format!("{}/{}/{}/aws4_request", date, self.region, SERVICE);
+        let string_to_sign = format!("AWS4-HMAC-SHA256\n{}\n{}\n{}", timestamp, scope,
+            hex(digest::digest(&digest::SHA256, canonical_request.as_bytes())));
+
+        let key = signing_key(&self.secret_access_key, date, &self.region, SERVICE);
+        let signature = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, key.as_ref()), string_to_sign.as_bytes());
+        format!("AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key_id, scope, signed_headers, hex(signature))
+    }
+}
+
+/// Derives the key to sign requests to `service` in `region` made on `date`,
+/// formatted as `YYYYMMDD`, with.
+fn signing_key(secret_access_key: &str, date: &str, region: &str, service: &str) -> hmac::Tag {
+    let sign = |key: &[u8], data: &str| hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, key), data.as_bytes());
+    let key = sign(format!("AWS4{}", secret_access_key).as_bytes(), date);
+    let key = sign(key.as_ref(), region);
+    let key = sign(key.as_ref(), service);
+    sign(key.as_ref(), "aws4_request")
+}
+
+fn hex<B: AsRef<[u8]>>(bytes: B) -> String {
+    bytes.as_ref().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[crate::async_trait]
+impl SecretProvider for AwsSecretsManager {
+    fn scheme(&self) -> &str {
+        "aws"
+    }
+
+    async fn resolve(&self, reference: &str) -> Result<String, SecretError> {
+        let (id, key) = match reference.split_once('#') {
+            Some((id, key)) => (id, Some(key)),
+            None => (reference, None),
+        };
+
+        let url = match &self.endpoint {
+            Some(endpoint) => endpoint.clone(),
+            None => format!("https://{}.{}.amazonaws.com", SERVICE, self.region),
+        };
+
+        let host = url.split_once("://").map_or(&*url, |(_, rest)| rest);
+        let body = json!({ "SecretId": id }).to_string().into_bytes();
+        let format = format_description!("[year][month][day]T[hour][minute][second]Z");
+        let timestamp = OffsetDateTime::now_utc().format(&format)?;
+
+        let mut headers = vec![("content-type", CONTENT_TYPE), ("host", host)];
+        headers.push(("x-amz-date", &timestamp));
+        if let Some(token) = &self.session_token {
+            headers.push(("x-amz-security-token", token));
+        }
+
+        headers.push(("x-amz-target", TARGET));
+        let authorization = self.authorization(&timestamp, &headers, &body);
+
+        // `host` is set by the client from the URL.
+        headers.retain(|(name, _)| *name != "host");
+        headers.push(("authorization", &authorization));
+        let client = Client::new(self.ca_certs.as_deref())?;
+        let response = client.request(Method::POST, &url, &headers, body).await?;
+        let status = response.status;
+        let response: Value = serde_json::from_slice(&response.body)?;
+        if status != 200 {
+            let kind = response["__type"].as_str().unwrap_or("unknown error");
+            return Err(format!("Secrets Manager responded with {} ({})", status, kind).into());
+        }
+
+        let string = response["SecretString"].as_str()
+            .ok_or("secret has no string value")?;
+
+        let key = match key {
+            Some(key) => key,
+            None => return Ok(string.to_string()),
+        };
+
+        match serde_json::from_str::<Value>(string)?.get(key) {
+            Some(Value::String(s)) => Ok(s.clone()),
+            Some(value) => Ok(value.to_string()),
+            None => Err(format!("secret has no key `{}`", key).into()),
+        }
+    }
+}
+
+impl std::fmt::Debug for AwsSecretsManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AwsSecretsManager")
+            .field("region", &self.region)
+            .field("access_key_id", &self.access_key_id)
+            .field("endpoint", &self.endpoint)
+            .field("ca_certs", &self.ca_certs)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signing_key_derivation() {
+        // The example in AWS's "Deriving the signing key" documentation.
+        let key = signing_key("wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY", "20120215", "us-east-1", "iam");
+        assert_eq!(hex(key), "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d");
+    }
+}
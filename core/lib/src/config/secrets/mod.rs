@@ -0,0 +1,291 @@
+use std::fmt;
+use std::sync::Arc;
+use std::error::Error as StdError;
+
+use figment::Figment;
+use figment::value::{Dict, Value};
+use figment::providers::Serialized;
+
+#[cfg(feature = "vault")]
+mod vault;
+
+#[cfg(feature = "aws-secrets")]
+mod aws;
+
+#[cfg(feature = "vault")]
+pub use vault::Vault;
+
+#[cfg(feature = "aws-secrets")]
+pub use aws::AwsSecretsManager;
+
+/// The error type returned by a [`SecretProvider`].
+pub type SecretError = Box<dyn StdError + Send + Sync>;
+
+/// Trait implemented by sources of secrets referenced from configuration.
+///
+/// Any configuration value, be it Rocket's [`secret_key`], a database `url` or
+/// `password`, or an application's own, can be written as a _secret reference_:
+/// a dictionary with a single `secret` key whose value is a string of the form
+/// `scheme:reference`. At ignition, before any ignite fairings run, Rocket
+/// replaces every secret reference in the selected profile with the string the
+/// provider registered for `scheme` resolves `reference` to. Secrets thus need
+/// not be stored in configuration files or environment variables:
+///
+/// ```toml
+/// [release]
+/// secret_key = { secret = "file:/run/secrets/rocket_secret_key" }
+///
+/// [release.databases.main]
+/// url = { secret = "vault:secret/data/app#database_url" }
+/// ```
+///
+/// The same references can be set via environment variables, as in
+/// `ROCKET_SECRET_KEY={secret="env:APP_SECRET_KEY"}`.
+///
+/// Providers are registered via [`Rocket::secret_provider()`]. The following
+/// providers are available, the first two of which are always registered:
+///
+/// | scheme  | provider                | feature       | reference                  |
+/// |---------|-------------------------|---------------|----------------------------|
+/// | `env`   | [`EnvSecrets`]          |               | environment variable name  |
+/// | `file`  | [`FileSecrets`]         |               | file path                  |
+/// | `vault` | [`Vault`]               | `vault`       | `path#field`               |
+/// | `aws`   | [`AwsSecretsManager`]   | `aws-secrets` | `secret-id`, `secret-id#key` |
+///
+/// If a reference can't be resolved, because no provider is registered for
+/// its scheme or because the provider fails, ignition fails with an
+/// [`ErrorKind::Config`](crate::error::ErrorKind::Config) error naming the
+/// configuration key.
+///
+/// [`secret_key`]: crate::Config::secret_key
+/// [`Rocket::secret_provider()`]: crate::Rocket::secret_provider()
+/// [`Vault`]: crate::config::Vault
+/// [`AwsSecretsManager`]: crate::config::AwsSecretsManager
+///
+/// # Example
+///
+/// A provider that resolves references of the form `static:name` from a map:
+///
+/// ```rust,no_run
+/// use std::collections::HashMap;
+/// use rocket::config::{SecretProvider, SecretError};
+///
+/// struct Static(HashMap<String, String>);
+///
+/// #[rocket::async_trait]
+/// impl SecretProvider for Static {
+///     fn scheme(&self) -> &str {
+///         "static"
+///     }
+///
+///     async fn resolve(&self, reference: &str) -> Result<String, SecretError> {
+///         self.0.get(reference).cloned().ok_or_else(|| "unknown secret".into())
+///     }
+/// }
+///
+/// #[rocket::launch]
+/// fn rocket() -> _ {
+///     let secrets = [("api_key".to_string(), "hunter2".to_string())];
+///     rocket::build().secret_provider(Static(secrets.into_iter().collect()))
+/// }
+/// ```
+#[crate::async_trait]
+pub trait SecretProvider: Send + Sync + 'static {
+    /// The scheme of the references this provider resolves, like `vault`.
+    fn scheme(&self) -> &str;
+
+    /// Resolves `reference`, the part of a secret reference following
+    /// `scheme:`, to the secret's value.
+    async fn resolve(&self, reference: &str) -> Result<String, SecretError>;
+}
+
+/// A [`SecretProvider`] for the `env` scheme, resolving references to the
+/// values of the environment variables they name.
+///
+/// This provider is always registered. It allows the name of the variable
+/// holding a secret to differ from Rocket's `ROCKET_` naming convention:
+///
+/// ```toml
+/// [release]
+/// secret_key = { secret = "env:APP_SECRET_KEY" }
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EnvSecrets;
+
+#[crate::async_trait]
+impl SecretProvider for EnvSecrets {
+    fn scheme(&self) -> &str {
+        "env"
+    }
+
+    async fn resolve(&self, reference: &str) -> Result<String, SecretError> {
+        std::env::var(reference)
+            .map_err(|e| format!("environment variable `{}`: {}", reference, e).into())
+    }
+}
+
+/// A [`SecretProvider`] for the `file` scheme, resolving references to the
+/// contents of the files they name, without trailing newlines.
+///
+/// This provider is always registered. It reads secrets mounted as files, as
+/// with Docker and Kubernetes secrets:
+///
+/// ```toml
+/// [release]
+/// secret_key = { secret = "file:/run/secrets/rocket_secret_key" }
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FileSecrets;
+
+#[crate::async_trait]
+impl SecretProvider for FileSecrets {
+    fn scheme(&self) -> &str {
+        "file"
+    }
+
+    async fn resolve(&self, reference: &str) -> Result<String, SecretError> {
+        let contents = tokio::fs::read_to_string(reference).await
+            .map_err(|e| format!("file `{}`: {}", reference, e))?;
+
+        Ok(contents.trim_end_matches(&['\r', '\n'][..]).to_string())
+    }
+}
+
+/// The secret providers registered with an instance of `Rocket`.
+#[derive(Clone)]
+pub(crate) struct SecretProviders(Vec<Arc<dyn SecretProvider>>);
+
+/// A step in the path to a value in a configuration dictionary.
+#[derive(Clone)]
+enum Step {
+    Key(String),
+    Index(usize),
+}
+
+impl Default for SecretProviders {
+    fn default() -> Self {
+        SecretProviders(vec![Arc::new(EnvSecrets), Arc::new(FileSecrets)])
+    }
+}
+
+impl SecretProviders {
+    /// Registers `provider`, replacing any provider for the same scheme.
+    pub fn add<P: SecretProvider>(&mut self, provider: P) {
+        self.0.retain(|p| p.scheme() != provider.scheme());
+        self.0.push(Arc::new(provider));
+    }
+
+    fn get(&self, scheme: &str) -> Option<&dyn SecretProvider> {
+        self.0.iter().find(|p| p.scheme() == scheme).map(|p| &**p)
+    }
+
+    /// Returns `figment` with every secret reference in its selected profile
+    /// replaced by the resolved secret.
+    pub async fn resolve(&self, figment: Figment) -> figment::Result<Figment> {
+        // An unextractable figment is reported when `Config` is extracted.
+        let mut dict = match figment.extract::<Dict>() {
+            Ok(dict) => dict,
+            Err(_) => return Ok(figment),
+        };
+
+        let mut references = vec![];
+        for (key, value) in &dict {
+            find_references(value, &mut vec![Step::Key(key.clone())], &mut references);
+        }
+
+        if references.is_empty() {
+            return Ok(figment);
+        }
+
+        let mut changed = vec![];
+        for (path, reference) in references {
+            let secret = self.resolve_one(&reference).await.map_err(|e| {
+                let key = dotted(&path);
+                let mut error = figment::Error::from(format!("secret `{}`: {}", reference, e));
+                error.metadata = figment.find_metadata(&key).cloned();
+                error.profile = Some(figment.profile().clone());
+                error.path = key.split('.').map(|s| s.to_string()).collect();
+                error
+            })?;
+
+            let (first, rest) = path.split_first().expect("non-empty path");
+            let key = match first { Step::Key(key) => key, Step::Index(_) => unreachable!() };
+            let mut value = dict.get_mut(key).expect("path exists");
+            for step in rest {
+                value = match (step, value) {
+                    (Step::Key(k), Value::Dict(_, d)) => d.get_mut(k).expect("path exists"),
+                    (Step::Index(i), Value::Array(_, a)) => &mut a[*i],
+                    _ => unreachable!("path exists"),
+                };
+            }
+
+            *value = Value::from(secret);
+            changed.push(key.clone());
+        }
+
+        changed.dedup();
+        Ok(changed.into_iter().fold(figment, |figment, key| {
+            let value = dict.remove(&key).expect("changed key exists");
+            figment.merge(Serialized::global(&key, value))
+        }))
+    }
+
+    async fn resolve_one(&self, reference: &str) -> Result<String, SecretError> {
+        let (scheme, reference) = reference.split_once(':')
+            .ok_or("reference must be of the form `scheme:reference`")?;
+
+        let provider = self.get(scheme)
+            .ok_or_else(|| format!("no secret provider for scheme `{}`", scheme))?;
+
+        provider.resolve(reference).await
+    }
+}
+
+/// Returns the reference in `value` if it is a secret reference.
+fn reference(value: &Value) -> Option<&str> {
+    match value {
+        Value::Dict(_, dict) if dict.len() == 1 => dict.get("secret")?.as_str(),
+        _ => None
+    }
+}
+
+/// Pushes the path to and reference of every secret reference in `value`,
+/// itself at `path`, to `references`.
+fn find_references(value: &Value, path: &mut Vec<Step>, references: &mut Vec<(Vec<Step>, String)>) {
+    if let Some(reference) = reference(value) {
+        return references.push((path.clone(), reference.to_string()));
+    }
+
+    match value {
+        Value::Dict(_, dict) => for (key, value) in dict {
+            path.push(Step::Key(key.clone()));
+            find_references(value, path, references);
+            path.pop();
+        },
+        Value::Array(_, array) => for (i, value) in array.iter().enumerate() {
+            path.push(Step::Index(i));
+            find_references(value, path, references);
+            path.pop();
+        },
+        _ => {}
+    }
+}
+
+/// The dotted configuration key for `path`, up to its first array index.
+fn dotted(path: &[Step]) -> String {
+    let mut keys = vec![];
+    for step in path {
+        match step {
+            Step::Key(key) => keys.push(key.as_str()),
+            Step::Index(_) => break,
+        }
+    }
+
+    keys.join(".")
+}
+
+impl fmt::Debug for SecretProviders {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.iter().map(|p| p.scheme())).finish()
+    }
+}
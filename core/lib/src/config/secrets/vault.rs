@@ -0,0 +1,157 @@
+use std::path::PathBuf;
+
+use hyper::Method;
+use serde_json::Value;
+
+use crate::http_client::Client;
+use super::{SecretProvider, SecretError};
+
+/// A [`SecretProvider`] for the `vault` scheme, resolving references to fields
+/// of secrets stored in [HashiCorp Vault].
+///
+/// A reference is of the form `path#field`, where `path` is the API path of
+/// the secret, excluding the `/v1/` prefix, and `field` is the name of the
+/// field in the secret's data to resolve to. Both version 1 and version 2 of
+/// the key/value secrets engine are supported; with the latter, `path`
+/// includes the `data/` segment:
+///
+/// ```toml
+/// [release.databases.main]
+/// url = { secret = "vault:secret/data/app#database_url" }
+/// ```
+///
+/// Requests are authenticated with a Vault token. The server's certificate is
+/// verified against the web PKI roots unless a CA bundle is set via
+/// [`Vault::ca_certs()`].
+///
+/// [HashiCorp Vault]: https://www.vaultproject.io
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use rocket::config::Vault;
+///
+/// #[rocket::launch]
+/// fn rocket() -> _ {
+///     let vault = Vault::from_env().expect("`VAULT_ADDR` and `VAULT_TOKEN` are set");
+///     rocket::build().secret_provider(vault)
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Vault {
+    address: String,
+    token: String,
+    namespace: Option<String>,
+    ca_certs: Option<PathBuf>,
+}
+
+impl Vault {
+    /// Returns a provider for the Vault server at `address`, such as
+    /// `https://vault.example.com:8200`, authenticating with `token`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::Vault;
+    ///
+    /// let vault = Vault::new("https://vault.example.com:8200", "s.token");
+    /// ```
+    pub fn new<A: Into<String>, T: Into<String>>(address: A, token: T) -> Vault {
+        let address = address.into().trim_end_matches('/').to_string();
+        Vault { address, token: token.into(), namespace: None, ca_certs: None }
+    }
+
+    /// Returns a provider configured via Vault's standard environment
+    /// variables: `VAULT_ADDR`, `VAULT_TOKEN`, and, optionally,
+    /// `VAULT_NAMESPACE` and `VAULT_CACERT`. Returns `None` if `VAULT_ADDR` or
+    /// `VAULT_TOKEN` is not set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::Vault;
+    ///
+    /// let vault = Vault::from_env();
+    /// ```
+    pub fn from_env() -> Option<Vault> {
+        let var = |name| std::env::var(name).ok();
+        let mut vault = Vault::new(var("VAULT_ADDR")?, var("VAULT_TOKEN")?);
+        if let Some(namespace) = var("VAULT_NAMESPACE") {
+            vault = vault.namespace(namespace);
+        }
+
+        if let Some(path) = var("VAULT_CACERT") {
+            vault = vault.ca_certs(path);
+        }
+
+        Some(vault)
+    }
+
+    /// Sets the Vault Enterprise namespace requests are made in.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::Vault;
+    ///
+    /// let vault = Vault::new("https://vault.example.com:8200", "s.token")
+    ///     .namespace("team-a");
+    /// ```
+    pub fn namespace<N: Into<String>>(mut self, namespace: N) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Sets the path to a PEM file of CA certificates the server's certificate
+    /// is verified against in place of the web PKI roots.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::Vault;
+    ///
+    /// let vault = Vault::new("https://vault.internal:8200", "s.token")
+    ///     .ca_certs("/etc/vault/ca.pem");
+    /// ```
+    pub fn ca_certs<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.ca_certs = Some(path.into());
+        self
+    }
+}
+
+#[crate::async_trait]
+impl SecretProvider for Vault {
+    fn scheme(&self) -> &str {
+        "vault"
+    }
+
+    async fn resolve(&self, reference: &str) -> Result<String, SecretError> {
+        let (path, field) = reference.split_once('#')
+            .ok_or("reference must be of the form `path#field`")?;
+
+        let url = format!("{}/v1/{}", self.address, path.trim_start_matches('/'));
+        let mut headers = vec![("X-Vault-Token", self.token.as_str())];
+        if let Some(namespace) = &self.namespace {
+            headers.push(("X-Vault-Namespace", namespace.as_str()));
+        }
+
+        let client = Client::new(self.ca_certs.as_deref())?;
+        let response = client.request(Method::GET, &url, &headers, vec![]).await?;
+        if response.status != 200 {
+            return Err(format!("Vault responded with status {}", response.status).into());
+        }
+
+        let response: Value = serde_json::from_slice(&response.body)?;
+        let data = &response["data"];
+        let value = match data.get("data") {
+            Some(v2) if v2.is_object() && data.get("metadata").is_some() => &v2[field],
+            _ => &data[field],
+        };
+
+        match value {
+            Value::String(s) => Ok(s.clone()),
+            Value::Null => Err(format!("secret has no field `{}`", field).into()),
+            value => Ok(value.to_string()),
+        }
+    }
+}
@@ -99,6 +99,10 @@ impl fmt::Display for Sig {
 /// responders](crate::response::stream#graceful-shutdown), to avoid abrupt I/O
 /// cancellation.
 ///
+/// [Shutdown fairings](crate::fairing::Fairing#shutdown) run concurrently
+/// during the grace period. Any that have not completed when it elapses are
+/// cancelled.
+///
 /// # Mercy Period
 ///
 /// After the grace period has elapsed, Rocket initiates connection shutdown,
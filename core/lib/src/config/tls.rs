@@ -1,10 +1,10 @@
 use figment::value::magic::{Either, RelativePathBuf};
 use serde::{Deserialize, Serialize};
-use indexmap::IndexSet;
+use indexmap::{IndexSet, IndexMap};
 
 /// TLS configuration: certificate chain, key, and ciphersuites.
 ///
-/// Four parameters control `tls` configuration:
+/// Five parameters control `tls` configuration:
 ///
 ///   * `certs`, `key`
 ///
@@ -26,10 +26,35 @@ use indexmap::IndexSet;
 ///     ciphersuite preferences over the client's. The default and recommended
 ///     value is `false`.
 ///
+///   * `reload_on_sighup`
+///
+///     A boolean that indicates whether, on Unix, the `certs` and `key` are
+///     reloaded when the process receives `SIGHUP`. Defaults to `false`.
+///
+/// Additionally, the `sni` parameter maps SNI hostnames to their own `certs` and
+/// `key`, allowing a single server to terminate TLS for several domains. A name
+/// may be a wildcard of the form `*.example.com`, matching exactly one leading
+/// label. Clients that don't indicate a hostname, or indicate one without a
+/// configured certificate, are served the top-level `certs` and `key`. See
+/// [`SniCert`] for details.
+///
+/// Certificates, including those for SNI hostnames, can also be reloaded at any
+/// time, for instance after a renewal, with
+/// [`Rocket::reload_tls()`](crate::Rocket::reload_tls()). Reloaded
+/// certificates are used for new connections; established connections are not
+/// dropped.
+///
 /// Additionally, the `mutual` parameter controls if and how the server
 /// authenticates clients via mutual TLS. It works in concert with the
 /// [`mtls`](crate::mtls) module. See [`MutualTls`] for configuration details.
 ///
+/// When the `http3` feature is enabled, Rocket additionally serves HTTP/3 over
+/// QUIC, using the same configuration, on the same port via UDP. Every
+/// response carries an `Alt-Svc` header advertising HTTP/3 unless the response
+/// sets its own. QUIC requires TLS 1.3: HTTP/3 connections use only the TLS 1.3
+/// ciphersuites in `ciphers`. QUIC 0-RTT is disabled, so requests are never
+/// received as replayable early data.
+///
 /// In `Rocket.toml`, configuration might look like:
 ///
 /// ```toml
@@ -90,6 +115,12 @@ pub struct TlsConfig {
     /// Whether to prefer the server's cipher suite order over the client's.
     #[serde(default)]
     pub(crate) prefer_server_cipher_order: bool,
+    /// Whether to reload `certs` and `key` on `SIGHUP`.
+    #[serde(default)]
+    pub(crate) reload_on_sighup: bool,
+    /// Certificate chains and keys for SNI hostnames.
+    #[serde(default)]
+    pub(crate) sni: IndexMap<String, SniCert>,
     /// Configuration for mutual TLS, if any.
     #[serde(default)]
     #[cfg(feature = "mtls")]
@@ -97,6 +128,48 @@ pub struct TlsConfig {
     pub(crate) mutual: Option<MutualTls>,
 }
 
+/// A certificate chain and private key for an SNI hostname.
+///
+/// An `SniCert` is configured in a [`TlsConfig`]'s `sni` map, keyed by
+/// hostname. Its `certs` and `key` parameters work exactly like those of
+/// [`TlsConfig`]. In `Rocket.toml`, configuration might look like:
+///
+/// ```toml
+/// [default.tls]
+/// certs = "/ssl/example.com/certs.pem"
+/// key = "/ssl/example.com/key.pem"
+///
+/// [default.tls.sni."api.example.org"]
+/// certs = "/ssl/api.example.org/certs.pem"
+/// key = "/ssl/api.example.org/key.pem"
+///
+/// [default.tls.sni."*.example.net"]
+/// certs = "/ssl/example.net/certs.pem"
+/// key = "/ssl/example.net/key.pem"
+/// ```
+///
+/// Programmatically, configuration might look like:
+///
+/// ```rust
+/// use rocket::config::{TlsConfig, SniCert};
+///
+/// let tls_config = TlsConfig::from_paths("/ssl/certs.pem", "/ssl/key.pem")
+///     .with_sni("api.example.org", SniCert::from_paths("/ssl/api.pem", "/ssl/api-key.pem"))
+///     .with_sni("*.example.net", SniCert::from_paths("/ssl/net.pem", "/ssl/net-key.pem"));
+///
+/// assert_eq!(tls_config.sni().count(), 2);
+/// ```
+#[derive(PartialEq, Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(nightly, doc(cfg(feature = "tls")))]
+pub struct SniCert {
+    /// Path to a PEM file with, or raw bytes for, a DER-encoded X.509 TLS
+    /// certificate chain.
+    pub(crate) certs: Either<RelativePathBuf, Vec<u8>>,
+    /// Path to a PEM file with, or raw bytes for, DER-encoded private key in
+    /// either PKCS#8 or PKCS#1 format.
+    pub(crate) key: Either<RelativePathBuf, Vec<u8>>,
+}
+
 /// Mutual TLS configuration.
 ///
 /// Configuration works in concert with the [`mtls`](crate::mtls) module, which
@@ -126,12 +199,28 @@ pub struct TlsConfig {
 ///     either case, if a certificate _is_ presented, it must be valid or the
 ///     connection is terminated.
 ///
+///   * `crls`
+///
+///     An optional path to a PEM file with one or more, or raw bytes for a
+///     DER-encoded, X.509 certificate revocation list (CRL). Certificates
+///     presented by clients that are revoked by a CRL from their issuer are
+///     rejected during the TLS handshake. Every CRL must be signed by one of
+///     the `ca_certs`; launch fails otherwise. A client certificate whose
+///     issuer's CRL is past its `nextUpdate` time is also rejected, so CRLs
+///     must be renewed before they expire. When `crls` is a path, the file is
+///     checked for changes every few seconds, in the background, and reloaded
+///     when its modification time changes, so updated CRLs apply to new
+///     connections without a restart. A CRL that fails to reload is ignored
+///     in favor of the previous CRLs. Online revocation checking via OCSP is
+///     not supported.
+///
 /// In a `Rocket.toml`, configuration might look like:
 ///
 /// ```toml
 /// [default.tls.mutual]
 /// ca_certs = "/ssl/ca_cert.pem"
 /// mandatory = true                # when absent, defaults to false
+/// crls = "/ssl/ca_crl.pem"        # when absent, no certificates are revoked
 /// ```
 ///
 /// Programmatically, configuration might look like:
@@ -174,6 +263,10 @@ pub struct MutualTls {
     #[serde(default)]
     #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
     pub mandatory: bool,
+    /// Path to a PEM file with, or raw bytes for, DER-encoded certificate
+    /// revocation lists used to reject revoked client certificates.
+    #[serde(default)]
+    pub(crate) crls: Option<Either<RelativePathBuf, Vec<u8>>>,
 }
 
 /// A supported TLS cipher suite.
@@ -253,6 +346,8 @@ impl TlsConfig {
             key: Either::Right(vec![]),
             ciphers: CipherSuite::default_set(),
             prefer_server_cipher_order: false,
+            reload_on_sighup: false,
+            sni: IndexMap::new(),
             #[cfg(feature = "mtls")]
             mutual: None,
         }
@@ -397,6 +492,43 @@ impl TlsConfig {
         self
     }
 
+    /// Sets whether the certificate chain and private key are reloaded when
+    /// the process receives `SIGHUP`. Only supported on Unix. Disabled by
+    /// default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::TlsConfig;
+    ///
+    /// let tls_config = TlsConfig::from_paths("/ssl/certs.pem", "/ssl/key.pem")
+    ///     .with_reload_on_sighup(true);
+    ///
+    /// assert!(tls_config.reload_on_sighup());
+    /// ```
+    pub fn with_reload_on_sighup(mut self, reload: bool) -> Self {
+        self.reload_on_sighup = reload;
+        self
+    }
+
+    /// Serves the certificate chain and key in `cert` to clients that indicate
+    /// the SNI hostname `name`, which is case-insensitive and may be a wildcard
+    /// of the form `*.example.com`. Setting a previously set `name` replaces
+    /// its certificate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{TlsConfig, SniCert};
+    ///
+    /// let tls_config = TlsConfig::from_paths("/ssl/certs.pem", "/ssl/key.pem")
+    ///     .with_sni("api.example.org", SniCert::from_paths("/ssl/api.pem", "/ssl/api-key.pem"));
+    /// ```
+    pub fn with_sni<N: Into<String>>(mut self, name: N, cert: SniCert) -> Self {
+        self.sni.insert(name.into().to_ascii_lowercase(), cert);
+        self
+    }
+
     /// Configures mutual TLS. See [`MutualTls`] for details.
     ///
     /// # Example
@@ -513,6 +645,42 @@ impl TlsConfig {
         self.prefer_server_cipher_order
     }
 
+    /// Whether the certificate chain and private key are reloaded on `SIGHUP`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::TlsConfig;
+    ///
+    /// # let certs_buf = &[];
+    /// # let key_buf = &[];
+    /// let tls_config = TlsConfig::from_bytes(certs_buf, key_buf);
+    /// assert!(!tls_config.reload_on_sighup());
+    /// ```
+    pub fn reload_on_sighup(&self) -> bool {
+        self.reload_on_sighup
+    }
+
+    /// Returns an iterator over the configured SNI hostnames and their
+    /// certificates.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{TlsConfig, SniCert};
+    ///
+    /// # let bytes = &[];
+    /// let tls_config = TlsConfig::from_bytes(bytes, bytes);
+    /// assert_eq!(tls_config.sni().count(), 0);
+    ///
+    /// let tls_config = tls_config.with_sni("Rocket.rs", SniCert::from_bytes(bytes, bytes));
+    /// let (name, _) = tls_config.sni().next().unwrap();
+    /// assert_eq!(name, "rocket.rs");
+    /// ```
+    pub fn sni(&self) -> impl Iterator<Item = (&str, &SniCert)> + '_ {
+        self.sni.iter().map(|(name, cert)| (name.as_str(), cert))
+    }
+
     /// Returns the value of the `mutual` parameter.
     ///
     /// # Example
@@ -537,6 +705,85 @@ impl TlsConfig {
     }
 }
 
+impl SniCert {
+    /// Constructs an `SniCert` from paths to a `certs` certificate chain and a
+    /// `key` private-key. This method does no validation; it simply creates a
+    /// structure suitable for passing into [`TlsConfig::with_sni()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::SniCert;
+    ///
+    /// let cert = SniCert::from_paths("/ssl/certs.pem", "/ssl/key.pem");
+    /// ```
+    pub fn from_paths<C, K>(certs: C, key: K) -> Self
+        where C: AsRef<std::path::Path>, K: AsRef<std::path::Path>
+    {
+        SniCert {
+            certs: Either::Left(certs.as_ref().to_path_buf().into()),
+            key: Either::Left(key.as_ref().to_path_buf().into()),
+        }
+    }
+
+    /// Constructs an `SniCert` from byte buffers to a `certs` certificate
+    /// chain and a `key` private-key. This method does no validation; it
+    /// simply creates a structure suitable for passing into
+    /// [`TlsConfig::with_sni()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::SniCert;
+    ///
+    /// # let certs_buf = &[];
+    /// # let key_buf = &[];
+    /// let cert = SniCert::from_bytes(certs_buf, key_buf);
+    /// ```
+    pub fn from_bytes(certs: &[u8], key: &[u8]) -> Self {
+        SniCert {
+            certs: Either::Right(certs.to_vec()),
+            key: Either::Right(key.to_vec()),
+        }
+    }
+
+    /// Returns the value of the `certs` parameter.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::path::Path;
+    /// use rocket::config::SniCert;
+    ///
+    /// let cert = SniCert::from_paths("/ssl/certs.pem", "/ssl/key.pem");
+    /// assert_eq!(cert.certs().unwrap_left(), Path::new("/ssl/certs.pem"));
+    /// ```
+    pub fn certs(&self) -> either::Either<std::path::PathBuf, &[u8]> {
+        match &self.certs {
+            Either::Left(path) => either::Either::Left(path.relative()),
+            Either::Right(bytes) => either::Either::Right(bytes),
+        }
+    }
+
+    /// Returns the value of the `key` parameter.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::SniCert;
+    ///
+    /// # let certs_buf = &[];
+    /// let cert = SniCert::from_bytes(certs_buf, &[1, 2, 3]);
+    /// assert_eq!(cert.key().unwrap_right(), &[1, 2, 3]);
+    /// ```
+    pub fn key(&self) -> either::Either<std::path::PathBuf, &[u8]> {
+        match &self.key {
+            Either::Left(path) => either::Either::Left(path.relative()),
+            Either::Right(bytes) => either::Either::Right(bytes),
+        }
+    }
+}
+
 #[cfg(feature = "mtls")]
 impl MutualTls {
     /// Constructs a `MutualTls` from a path to a PEM file with a certificate
@@ -557,7 +804,8 @@ impl MutualTls {
     pub fn from_path<C: AsRef<std::path::Path>>(ca_certs: C) -> Self {
         MutualTls {
             ca_certs: Either::Left(ca_certs.as_ref().to_path_buf().into()),
-            mandatory: Default::default()
+            mandatory: Default::default(),
+            crls: None,
         }
     }
 
@@ -580,7 +828,8 @@ impl MutualTls {
     pub fn from_bytes(ca_certs: &[u8]) -> Self {
         MutualTls {
             ca_certs: Either::Right(ca_certs.to_vec()),
-            mandatory: Default::default()
+            mandatory: Default::default(),
+            crls: None,
         }
     }
 
@@ -621,14 +870,71 @@ impl MutualTls {
             Either::Right(bytes) => either::Either::Right(&bytes),
         }
     }
+
+    /// Sets the path to a PEM file with certificate revocation lists used to
+    /// reject revoked client certificates. The file is reloaded whenever its
+    /// modification time changes. See [`MutualTls`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::MutualTls;
+    ///
+    /// let mtls_config = MutualTls::from_path("/ssl/ca_cert.pem")
+    ///     .with_crls("/ssl/ca_crl.pem");
+    /// ```
+    pub fn with_crls<P: AsRef<std::path::Path>>(mut self, crls: P) -> Self {
+        self.crls = Some(Either::Left(crls.as_ref().to_path_buf().into()));
+        self
+    }
+
+    /// Sets the raw bytes of a DER-encoded certificate revocation list, or of
+    /// a PEM file with certificate revocation lists, used to reject revoked
+    /// client certificates.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::MutualTls;
+    ///
+    /// # let ca_certs_buf = &[];
+    /// # let crls_buf = &[];
+    /// let mtls_config = MutualTls::from_bytes(ca_certs_buf).with_crls_bytes(crls_buf);
+    /// ```
+    pub fn with_crls_bytes(mut self, crls: &[u8]) -> Self {
+        self.crls = Some(Either::Right(crls.to_vec()));
+        self
+    }
+
+    /// Returns the value of the `crls` parameter, if it is set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::path::Path;
+    /// use rocket::config::MutualTls;
+    ///
+    /// let mtls_config = MutualTls::from_path("/ssl/ca_cert.pem");
+    /// assert!(mtls_config.crls().is_none());
+    ///
+    /// let mtls_config = mtls_config.with_crls("/ssl/ca_crl.pem");
+    /// assert_eq!(mtls_config.crls().unwrap().unwrap_left(), Path::new("/ssl/ca_crl.pem"));
+    /// ```
+    pub fn crls(&self) -> Option<either::Either<std::path::PathBuf, &[u8]>> {
+        match self.crls.as_ref()? {
+            Either::Left(path) => Some(either::Either::Left(path.relative())),
+            Either::Right(bytes) => Some(either::Either::Right(bytes)),
+        }
+    }
 }
 
 #[cfg(feature = "tls")]
 mod with_tls_feature {
     use std::fs;
     use std::io::{self, Error};
+    use std::sync::Arc;
 
-    use crate::http::tls::Config;
+    use crate::http::tls::{Config, CertResolver};
     use crate::http::tls::rustls::SupportedCipherSuite as RustlsCipher;
     use crate::http::tls::rustls::cipher_suite;
 
@@ -653,12 +959,29 @@ mod with_tls_feature {
         }
     }
 
+    #[cfg(feature = "mtls")]
+    fn to_crls(
+        value: &Either<RelativePathBuf, Vec<u8>>,
+        ca_certs: &Either<RelativePathBuf, Vec<u8>>,
+    ) -> io::Result<crate::http::tls::Crls> {
+        use crate::http::tls::{Crls, CrlSource};
+
+        let source = match value {
+            Either::Left(path) => CrlSource::Path(path.relative()),
+            Either::Right(vec) => CrlSource::Bytes(vec.clone()),
+        };
+
+        Crls::new(source, &mut to_reader(ca_certs)?)
+            .map_err(|e| Error::new(e.kind(), format!("bad CRL(s): {}", e)))
+    }
+
     impl TlsConfig {
         /// This is only called when TLS is enabled.
         pub(crate) fn to_native_config(&self) -> io::Result<Config<Reader>> {
             Ok(Config {
                 cert_chain: to_reader(&self.certs)?,
                 private_key: to_reader(&self.key)?,
+                sni: self.sni_readers()?,
                 ciphersuites: self.rustls_ciphers().collect(),
                 prefer_server_order: self.prefer_server_cipher_order,
                 #[cfg(not(feature = "mtls"))]
@@ -672,9 +995,36 @@ mod with_tls_feature {
                     Some(ref mtls) => Some(to_reader(&mtls.ca_certs)?),
                     None => None
                 },
+                #[cfg(feature = "mtls")]
+                crls: match self.mutual {
+                    Some(ref mtls) => match mtls.crls {
+                        Some(ref crls) => Some(std::sync::Arc::new(to_crls(crls, &mtls.ca_certs)?)),
+                        None => None
+                    },
+                    None => None
+                },
             })
         }
 
+        /// Reloads `certs`, `key`, and `sni` into every resolver in `resolvers`.
+        pub(crate) fn reload(&self, resolvers: &[Arc<CertResolver>]) -> io::Result<()> {
+            for resolver in resolvers {
+                let (mut certs, mut key) = (to_reader(&self.certs)?, to_reader(&self.key)?);
+                resolver.reload(&mut certs, &mut key, &mut self.sni_readers()?)?;
+            }
+
+            Ok(())
+        }
+
+        fn sni_readers(&self) -> io::Result<Vec<(String, Reader, Reader)>> {
+            self.sni.iter()
+                .map(|(name, cert)| {
+                    let (certs, key) = (to_reader(&cert.certs)?, to_reader(&cert.key)?);
+                    Ok((name.clone(), certs, key))
+                })
+                .collect()
+        }
+
         fn rustls_ciphers(&self) -> impl Iterator<Item = RustlsCipher> + '_ {
             self.ciphers().map(|ciphersuite| match ciphersuite {
                 CipherSuite::TLS_CHACHA20_POLY1305_SHA256 =>
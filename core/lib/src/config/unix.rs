@@ -0,0 +1,199 @@
+use std::{fmt, io};
+use std::path::PathBuf;
+
+use figment::Figment;
+use figment::value::Value;
+use figment::providers::Serialized;
+use serde::{Deserialize, Serialize};
+
+/// Unix domain socket configuration: path, permissions, and cleanup.
+///
+/// When configured, Rocket serves HTTP on a Unix domain socket at `path`
+/// instead of on a TCP `address` and `port`. This is the typical deployment
+/// behind a reverse proxy like nginx or HAProxy running on the same machine.
+/// A socket is configured either via an `address` of the form `unix:<path>` or
+/// via the `unix` parameter, which additionally controls the following:
+///
+/// | key      | default    | description                                           |
+/// |----------|------------|-------------------------------------------------------|
+/// | `path`   | _required_ | path to the socket file                               |
+/// | `mode`   | _none_     | permissions to set on the socket file, like `0o660`   |
+/// | `reuse`  | `true`     | whether to replace a stale socket file at `path`      |
+/// | `remove` | `true`     | whether to remove the socket file on shutdown         |
+///
+/// A socket file at `path` is _stale_ when no process is accepting connections
+/// on it, as is the case after a crash. With `reuse` enabled, a stale socket
+/// file is removed before binding. A live socket or any other kind of file at
+/// `path` always fails the launch. Without a `mode`, the socket file's
+/// permissions are determined by the process's `umask`.
+///
+/// Unix domain sockets are only supported on Unix and can't be combined with
+/// TLS; on other platforms, or when TLS is enabled, the launch fails.
+/// Connections over Unix domain sockets have no
+/// [`Request::remote()`](crate::Request::remote()) address. Proxies should
+/// forward the client's address via the `X-Real-IP` header.
+///
+/// In `Rocket.toml`, configuration might look like:
+///
+/// ```toml
+/// [release]
+/// address = "unix:/run/app.sock"
+///
+/// [release.unix]
+/// mode = 0o660
+/// ```
+///
+/// Or, equivalently:
+///
+/// ```toml
+/// [release.unix]
+/// path = "/run/app.sock"
+/// mode = 0o660
+/// ```
+///
+/// Programmatically, a socket is configured via [`UnixConfig::new()`]:
+///
+/// ```rust
+/// use rocket::config::{Config, UnixConfig};
+///
+/// let config = Config {
+///     unix: Some(UnixConfig::new("/run/app.sock").mode(0o660)),
+///     ..Config::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct UnixConfig {
+    /// The path to the socket file.
+    pub path: PathBuf,
+    /// The permissions to set on the socket file, if any.
+    /// **(default: `None`)**
+    #[serde(default)]
+    pub mode: Option<u32>,
+    /// Whether to replace a stale socket file at `path`. **(default: `true`)**
+    #[serde(default = "default_true")]
+    pub reuse: bool,
+    /// Whether to remove the socket file on shutdown. **(default: `true`)**
+    #[serde(default = "default_true")]
+    pub remove: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl UnixConfig {
+    /// The prefix of an `address` that configures a Unix domain socket.
+    pub(crate) const PREFIX: &'static str = "unix:";
+
+    /// Returns a default configuration for a socket at `path`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::UnixConfig;
+    ///
+    /// let config = UnixConfig::new("/run/app.sock");
+    /// assert_eq!(config.path.to_str(), Some("/run/app.sock"));
+    /// assert_eq!(config.mode, None);
+    /// assert!(config.reuse && config.remove);
+    /// ```
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        UnixConfig { path: path.into(), mode: None, reuse: true, remove: true }
+    }
+
+    /// Sets the permissions of the socket file to `mode`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::UnixConfig;
+    ///
+    /// let config = UnixConfig::new("/run/app.sock").mode(0o660);
+    /// assert_eq!(config.mode, Some(0o660));
+    /// ```
+    pub fn mode(mut self, mode: u32) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Sets whether a stale socket file at `path` is replaced.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::UnixConfig;
+    ///
+    /// let config = UnixConfig::new("/run/app.sock").reuse(false);
+    /// assert!(!config.reuse);
+    /// ```
+    pub fn reuse(mut self, reuse: bool) -> Self {
+        self.reuse = reuse;
+        self
+    }
+
+    /// Sets whether the socket file is removed on shutdown.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::UnixConfig;
+    ///
+    /// let config = UnixConfig::new("/run/app.sock").remove(false);
+    /// assert!(!config.remove);
+    /// ```
+    pub fn remove(mut self, remove: bool) -> Self {
+        self.remove = remove;
+        self
+    }
+
+    /// Binds a listener to `path`, first removing a stale socket file if
+    /// `reuse` is enabled, then sets the socket file's `mode`, if any.
+    #[cfg(unix)]
+    pub(crate) fn bind(&self) -> io::Result<crate::http::private::UnixListener> {
+        use std::fs;
+        use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+        use std::os::unix::net::UnixStream;
+
+        if let Ok(metadata) = fs::symlink_metadata(&self.path) {
+            let stale = metadata.file_type().is_socket() && UnixStream::connect(&self.path).is_err();
+            if !self.reuse || !stale {
+                let msg = format!("{} already exists", self.path.display());
+                return Err(io::Error::new(io::ErrorKind::AddrInUse, msg));
+            }
+
+            fs::remove_file(&self.path)?;
+        }
+
+        let listener = crate::http::private::UnixListener::bind(&self.path)?;
+        if let Some(mode) = self.mode {
+            fs::set_permissions(&self.path, fs::Permissions::from_mode(mode))?;
+        }
+
+        Ok(listener)
+    }
+
+    /// If `address` in `figment` is of the form `unix:<path>`, returns a
+    /// figment where it is instead configured as `unix.path`, leaving the
+    /// `address` to its default.
+    pub(crate) fn lift_address(figment: Figment) -> Figment {
+        use crate::Config;
+
+        let path = match figment.find_value(Config::ADDRESS) {
+            Ok(Value::String(_, addr)) => match addr.strip_prefix(Self::PREFIX) {
+                Some(path) => path.to_string(),
+                None => return figment,
+            },
+            _ => return figment,
+        };
+
+        figment
+            .merge(Serialized::global(Config::ADDRESS, Config::default().address))
+            .merge(Serialized::global("unix.path", path))
+    }
+}
+
+impl fmt::Display for UnixConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", Self::PREFIX, self.path.display())
+    }
+}
@@ -8,6 +8,10 @@ use crate::http::private::cookie;
 #[doc(inline)]
 pub use self::cookie::{Cookie, SameSite, Iter};
 
+#[doc(hidden)]
+#[cfg(feature = "json")]
+pub use rocket_codegen::TypedCookie;
+
 /// Collection of one or more HTTP cookies.
 ///
 /// `CookieJar` allows for retrieval of cookies from an incoming request. It
@@ -59,10 +63,11 @@ pub use self::cookie::{Cookie, SameSite, Iter};
 ///
 /// A type of `&CookieJar` can be retrieved via its `FromRequest` implementation
 /// as a request guard or via the [`Request::cookies()`] method. Individual
-/// cookies can be retrieved via the [`get()`] and [`get_private()`] methods.
-/// Pending changes can be observed via the [`get_pending()`] method. Cookies
-/// can be added or removed via the [`add()`], [`add_private()`], [`remove()`],
-/// and [`remove_private()`] methods.
+/// cookies can be retrieved via the [`get()`], [`get_private()`], and
+/// [`get_signed()`] methods. Pending changes can be observed via the
+/// [`get_pending()`] method. Cookies can be added or removed via the [`add()`],
+/// [`add_private()`], [`add_signed()`], [`remove()`], [`remove_private()`],
+/// and [`remove_signed()`] methods.
 ///
 /// [`Request::cookies()`]: crate::Request::cookies()
 /// [`get()`]: #method.get
@@ -136,17 +141,43 @@ pub use self::cookie::{Cookie, SameSite, Iter};
 /// collection via the [`get_private()`], [`add_private()`], and
 /// [`remove_private()`] methods.
 ///
+/// # Signed Cookies
+///
+/// _Signed_ cookies are regular cookies whose value is accompanied by an HMAC
+/// signature. Unlike private cookies, their values are readable by clients,
+/// but they cannot be tampered with or manufactured: a cookie whose signature
+/// fails to verify is treated as missing. Signed cookies are suitable for
+/// values such as a locale or an A/B testing bucket that a client-side script
+/// may need to read but the server must be able to trust.
+///
+/// Signed cookies can be retrieved, added, and removed from a `CookieJar`
+/// collection via the [`get_signed()`], [`add_signed()`], and
+/// [`remove_signed()`] methods.
+///
+/// [`get_signed()`]: #method.get_signed
+/// [`add_signed()`]: #method.add_signed
+/// [`remove_signed()`]: #method.remove_signed
+///
 /// ## Encryption Key
 ///
-/// To encrypt private cookies, Rocket uses the 256-bit key specified in the
-/// `secret_key` configuration parameter. If one is not specified, Rocket will
-/// automatically generate a fresh key. Note, however, that a private cookie can
-/// only be decrypted with the same key with which it was encrypted. As such, it
-/// is important to set a `secret_key` configuration parameter when using
-/// private cookies so that cookies decrypt properly after an application
+/// To encrypt private cookies and sign signed cookies, Rocket uses the 256-bit
+/// key specified in the `secret_key` configuration parameter. If one is not
+/// specified, Rocket will automatically generate a fresh key. Note, however,
+/// that a private cookie can only be decrypted, and a signed cookie only
+/// verified, with the same key with which it was created. As such, it is
+/// important to set a `secret_key` configuration parameter when using private
+/// or signed cookies so that cookies remain valid after an application
 /// restart. Rocket will emit a warning if an application is run in production
 /// mode without a configured `secret_key`.
 ///
+/// To rotate the key without invalidating existing cookies, set the new key as
+/// the `secret_key` and move the old key to [`previous_secret_keys`]. Cookies
+/// created with a previous key continue to be accepted while new cookies are
+/// created with the current key. Once cookies created with an old key have
+/// expired, the old key can be removed.
+///
+/// [`previous_secret_keys`]: crate::Config::previous_secret_keys
+///
 /// Generating a string suitable for use as a `secret_key` configuration value
 /// is usually done through tools like `openssl`. Using `openssl`, for instance,
 /// a 256-bit base64 key can be generated with the command `openssl rand -base64
@@ -157,6 +188,108 @@ pub struct CookieJar<'a> {
     config: &'a Config,
 }
 
+/// How a cookie's value is protected: plain, signed, or private.
+///
+/// The `Signed` and `Private` variants are only available when the `secrets`
+/// feature is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CookiePrivacy {
+    /// The value is sent as-is. See [`CookieJar::add()`].
+    Plain,
+    /// The value is signed but readable. See [`CookieJar::add_signed()`].
+    #[cfg(feature = "secrets")]
+    #[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
+    Signed,
+    /// The value is encrypted and authenticated. See
+    /// [`CookieJar::add_private()`].
+    #[cfg(feature = "secrets")]
+    #[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
+    Private,
+}
+
+/// How a [`TypedCookie`]'s value is encoded into a cookie value.
+#[cfg(feature = "json")]
+#[cfg_attr(nightly, doc(cfg(feature = "json")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CookieEncoding {
+    /// The value is serialized as JSON.
+    Json,
+    /// The value is serialized as JSON and then base64-encoded.
+    Base64,
+}
+
+#[cfg(feature = "json")]
+impl CookieEncoding {
+    fn encode<T: serde::Serialize>(self, value: &T) -> Result<String, serde_json::Error> {
+        let json = serde_json::to_string(value)?;
+        match self {
+            CookieEncoding::Json => Ok(json),
+            CookieEncoding::Base64 => {
+                let mut buf = vec![0; (json.len() + 2) / 3 * 4];
+                let encoded = binascii::b64encode(json.as_bytes(), &mut buf)
+                    .expect("buffer is large enough");
+
+                Ok(String::from_utf8_lossy(encoded).into_owned())
+            }
+        }
+    }
+
+    fn decode<T: serde::de::DeserializeOwned>(self, value: &str) -> Option<T> {
+        match self {
+            CookieEncoding::Json => serde_json::from_str(value).ok(),
+            CookieEncoding::Base64 => {
+                let mut buf = vec![0; value.len()];
+                let json = binascii::b64decode(value.as_bytes(), &mut buf).ok()?;
+                serde_json::from_slice(json).ok()
+            }
+        }
+    }
+}
+
+/// A cookie with a typed, serializable value.
+///
+/// A `TypedCookie` is stored and retrieved with [`CookieJar::add_typed()`],
+/// [`CookieJar::get_typed()`], and [`CookieJar::remove_typed()`]. The
+/// cookie's name, value encoding, privacy, and max-age are fixed by the type.
+///
+/// # Deriving
+///
+/// This trait is typically derived, alongside `Serialize` and `Deserialize`.
+/// All of the parameters to the `#[cookie]` attribute are optional:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::TypedCookie;
+/// use rocket::serde::{Serialize, Deserialize};
+///
+/// #[derive(Serialize, Deserialize, TypedCookie)]
+/// #[serde(crate = "rocket::serde")]
+/// #[cookie(name = "cart", encoding = "base64", privacy = "plain", max_age = 86400)]
+/// struct Cart {
+///     items: Vec<u32>,
+/// }
+/// ```
+///
+/// The `name` defaults to the name of the type. The `encoding` is one of
+/// `"json"` (the default) or `"base64"`. The `privacy` is one of `"plain"`
+/// (the default), `"signed"`, or `"private"`, the latter two requiring the
+/// `secrets` feature. The `max_age`, in seconds, is unset by default.
+#[cfg(feature = "json")]
+#[cfg_attr(nightly, doc(cfg(feature = "json")))]
+pub trait TypedCookie: serde::Serialize + serde::de::DeserializeOwned {
+    /// The name of the cookie.
+    const NAME: &'static str;
+
+    /// How the value is encoded. Defaults to [`CookieEncoding::Json`].
+    const ENCODING: CookieEncoding = CookieEncoding::Json;
+
+    /// How the value is protected. Defaults to [`CookiePrivacy::Plain`].
+    const PRIVACY: CookiePrivacy = CookiePrivacy::Plain;
+
+    /// The cookie's max-age, if any. Defaults to `None`.
+    const MAX_AGE: Option<time::Duration> = None;
+}
+
 impl<'a> Clone for CookieJar<'a> {
     fn clone(&self) -> Self {
         CookieJar {
@@ -169,14 +302,14 @@ impl<'a> Clone for CookieJar<'a> {
 
 #[derive(Clone)]
 enum Op {
-    Add(Cookie<'static>, bool),
-    Remove(Cookie<'static>, bool),
+    Add(Cookie<'static>, CookiePrivacy),
+    Remove(Cookie<'static>),
 }
 
 impl Op {
     fn cookie(&self) -> &Cookie<'static> {
         match self {
-            Op::Add(c, _) | Op::Remove(c, _) => c
+            Op::Add(c, _) | Op::Remove(c) => c
         }
     }
 }
@@ -218,6 +351,11 @@ impl<'a> CookieJar<'a> {
     /// cannot be found, or the cookie fails to authenticate or decrypt, `None`
     /// is returned.
     ///
+    /// The value is decrypted with the configured `secret_key` or, failing
+    /// that, with any of the [`previous_secret_keys`].
+    ///
+    /// [`previous_secret_keys`]: crate::Config::previous_secret_keys
+    ///
     /// **Note:** This method _does not_ observe changes made via additions and
     /// removals to the cookie jar. To observe those changes, use
     /// [`CookieJar::get_pending()`].
@@ -237,6 +375,40 @@ impl<'a> CookieJar<'a> {
     #[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
     pub fn get_private(&self, name: &str) -> Option<Cookie<'static>> {
         self.jar.private(&self.config.secret_key.key).get(name)
+            .or_else(|| self.config.previous_secret_keys.iter()
+                .find_map(|prev| self.jar.private(&prev.key).get(name)))
+    }
+
+    /// Retrieves the _original_ `Cookie` inside this collection with the name
+    /// `name` and verifies the cookie's signature. If the cookie cannot be
+    /// found, or the cookie fails to verify, `None` is returned.
+    ///
+    /// The signature is verified with the configured `secret_key` or, failing
+    /// that, with any of the [`previous_secret_keys`].
+    ///
+    /// [`previous_secret_keys`]: crate::Config::previous_secret_keys
+    ///
+    /// **Note:** This method _does not_ observe changes made via additions and
+    /// removals to the cookie jar. To observe those changes, use
+    /// [`CookieJar::get_pending()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::http::{Cookie, CookieJar};
+    ///
+    /// #[get("/")]
+    /// fn handler(jar: &CookieJar<'_>) {
+    ///     let cookie = jar.get_signed("name");
+    /// }
+    /// ```
+    #[cfg(feature = "secrets")]
+    #[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
+    pub fn get_signed(&self, name: &str) -> Option<Cookie<'static>> {
+        self.jar.signed(&self.config.secret_key.key).get(name)
+            .or_else(|| self.config.previous_secret_keys.iter()
+                .find_map(|prev| self.jar.signed(&prev.key).get(name)))
     }
 
     /// Returns a reference to the _original or pending_ `Cookie` inside this
@@ -263,7 +435,7 @@ impl<'a> CookieJar<'a> {
         for op in ops.iter().rev().filter(|op| op.cookie().name() == name) {
             match op {
                 Op::Add(c, _) => return Some(c.clone()),
-                Op::Remove(_, _) => return None,
+                Op::Remove(_) => return None,
             }
         }
 
@@ -299,7 +471,7 @@ impl<'a> CookieJar<'a> {
     /// ```
     pub fn add(&self, mut cookie: Cookie<'static>) {
         Self::set_defaults(&mut cookie);
-        self.ops.lock().push(Op::Add(cookie, false));
+        self.ops.lock().push(Op::Add(cookie, CookiePrivacy::Plain));
     }
 
     /// Adds `cookie` to the collection. The cookie's value is encrypted with
@@ -334,7 +506,35 @@ impl<'a> CookieJar<'a> {
     #[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
     pub fn add_private(&self, mut cookie: Cookie<'static>) {
         Self::set_private_defaults(&mut cookie);
-        self.ops.lock().push(Op::Add(cookie, true));
+        self.ops.lock().push(Op::Add(cookie, CookiePrivacy::Private));
+    }
+
+    /// Adds `cookie` to the collection. The cookie's value is signed assuring
+    /// integrity and authenticity but, unlike private cookies, _not_
+    /// confidentiality: the value is readable by the client. The cookie can
+    /// later be retrieved using [`get_signed`](#method.get_signed) and removed
+    /// using [`remove_signed`](#method.remove_signed).
+    ///
+    /// Unless a value is set for the given property, the same defaults as for
+    /// [`add_private`](#method.add_private) are set on `cookie` before being
+    /// added to `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::http::{Cookie, CookieJar};
+    ///
+    /// #[get("/")]
+    /// fn handler(jar: &CookieJar<'_>) {
+    ///     jar.add_signed(Cookie::new("name", "value"));
+    /// }
+    /// ```
+    #[cfg(feature = "secrets")]
+    #[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
+    pub fn add_signed(&self, mut cookie: Cookie<'static>) {
+        Self::set_private_defaults(&mut cookie);
+        self.ops.lock().push(Op::Add(cookie, CookiePrivacy::Signed));
     }
 
     /// Removes `cookie` from this collection and generates a "removal" cookies
@@ -364,7 +564,7 @@ impl<'a> CookieJar<'a> {
             cookie.set_path("/");
         }
 
-        self.ops.lock().push(Op::Remove(cookie, false));
+        self.ops.lock().push(Op::Remove(cookie));
     }
 
     /// Removes the private `cookie` from the collection.
@@ -391,7 +591,156 @@ impl<'a> CookieJar<'a> {
             cookie.set_path("/");
         }
 
-        self.ops.lock().push(Op::Remove(cookie, true));
+        self.ops.lock().push(Op::Remove(cookie));
+    }
+
+    /// Removes the signed `cookie` from the collection.
+    ///
+    /// For correct removal, the passed in `cookie` must contain the same `path`
+    /// and `domain` as the cookie that was initially set. If a path is not set
+    /// on `cookie`, the `"/"` path will automatically be set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::http::{Cookie, CookieJar};
+    ///
+    /// #[get("/")]
+    /// fn handler(jar: &CookieJar<'_>) {
+    ///     jar.remove_signed(Cookie::named("name"));
+    /// }
+    /// ```
+    #[cfg(feature = "secrets")]
+    #[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
+    pub fn remove_signed(&self, mut cookie: Cookie<'static>) {
+        if cookie.path().is_none() {
+            cookie.set_path("/");
+        }
+
+        self.ops.lock().push(Op::Remove(cookie));
+    }
+
+    /// Retrieves and deserializes the _original_ cookie named by
+    /// [`TypedCookie::NAME`], using `T`'s configured encoding and privacy. If
+    /// the cookie cannot be found, fails to verify, or fails to decode, `None`
+    /// is returned.
+    ///
+    /// Like [`CookieJar::get()`], this method _does not_ observe changes made
+    /// via additions and removals to the cookie jar.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::http::{CookieJar, TypedCookie};
+    /// use rocket::serde::{Serialize, Deserialize};
+    ///
+    /// #[derive(Serialize, Deserialize, TypedCookie)]
+    /// #[serde(crate = "rocket::serde")]
+    /// #[cookie(name = "prefs")]
+    /// struct Prefs {
+    ///     theme: String,
+    /// }
+    ///
+    /// #[get("/")]
+    /// fn handler(jar: &CookieJar<'_>) -> Option<String> {
+    ///     jar.get_typed::<Prefs>().map(|prefs| prefs.theme)
+    /// }
+    /// ```
+    #[cfg(feature = "json")]
+    #[cfg_attr(nightly, doc(cfg(feature = "json")))]
+    pub fn get_typed<T: TypedCookie>(&self) -> Option<T> {
+        let cookie = match T::PRIVACY {
+            CookiePrivacy::Plain => self.get(T::NAME).cloned(),
+            #[cfg(feature = "secrets")]
+            CookiePrivacy::Signed => self.get_signed(T::NAME),
+            #[cfg(feature = "secrets")]
+            CookiePrivacy::Private => self.get_private(T::NAME),
+        };
+
+        T::ENCODING.decode(cookie?.value())
+    }
+
+    /// Serializes `value` and adds it to the collection as a cookie named by
+    /// [`TypedCookie::NAME`], using `T`'s configured encoding, privacy, and
+    /// max-age. Plain cookies receive the defaults of [`CookieJar::add()`]
+    /// while signed and private cookies receive the defaults of
+    /// [`CookieJar::add_private()`].
+    ///
+    /// Returns an error if `value` fails to serialize.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::http::{CookieJar, TypedCookie};
+    /// use rocket::serde::{Serialize, Deserialize};
+    ///
+    /// #[derive(Serialize, Deserialize, TypedCookie)]
+    /// #[serde(crate = "rocket::serde")]
+    /// #[cookie(name = "prefs", encoding = "base64", max_age = 3600)]
+    /// struct Prefs {
+    ///     theme: String,
+    /// }
+    ///
+    /// #[get("/")]
+    /// fn handler(jar: &CookieJar<'_>) {
+    ///     jar.add_typed(Prefs { theme: "dark".into() }).expect("serializable");
+    /// }
+    /// ```
+    #[cfg(feature = "json")]
+    #[cfg_attr(nightly, doc(cfg(feature = "json")))]
+    pub fn add_typed<T: TypedCookie>(&self, value: T) -> Result<(), serde_json::Error> {
+        let mut cookie = Cookie::new(T::NAME, T::ENCODING.encode(&value)?);
+        if let Some(max_age) = T::MAX_AGE {
+            cookie.set_max_age(max_age);
+            cookie.set_expires(time::OffsetDateTime::now_utc() + max_age);
+        }
+
+        match T::PRIVACY {
+            CookiePrivacy::Plain => self.add(cookie),
+            #[cfg(feature = "secrets")]
+            CookiePrivacy::Signed => self.add_signed(cookie),
+            #[cfg(feature = "secrets")]
+            CookiePrivacy::Private => self.add_private(cookie),
+        }
+
+        Ok(())
+    }
+
+    /// Removes the cookie named by [`TypedCookie::NAME`] from the collection,
+    /// using `T`'s configured privacy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::http::{CookieJar, TypedCookie};
+    /// use rocket::serde::{Serialize, Deserialize};
+    ///
+    /// #[derive(Serialize, Deserialize, TypedCookie)]
+    /// #[serde(crate = "rocket::serde")]
+    /// struct Prefs {
+    ///     theme: String,
+    /// }
+    ///
+    /// #[get("/")]
+    /// fn handler(jar: &CookieJar<'_>) {
+    ///     jar.remove_typed::<Prefs>();
+    /// }
+    /// ```
+    #[cfg(feature = "json")]
+    #[cfg_attr(nightly, doc(cfg(feature = "json")))]
+    pub fn remove_typed<T: TypedCookie>(&self) {
+        let cookie = Cookie::named(T::NAME);
+        match T::PRIVACY {
+            CookiePrivacy::Plain => self.remove(cookie),
+            #[cfg(feature = "secrets")]
+            CookiePrivacy::Signed => self.remove_signed(cookie),
+            #[cfg(feature = "secrets")]
+            CookiePrivacy::Private => self.remove_private(cookie),
+        }
     }
 
     /// Returns an iterator over all of the _original_ cookies present in this
@@ -431,12 +780,16 @@ impl<'a> CookieJar<'a> {
 
         for op in ops {
             match op {
-                Op::Add(c, false) => jar.add(c),
+                Op::Add(c, CookiePrivacy::Plain) => jar.add(c),
                 #[cfg(feature = "secrets")]
-                Op::Add(c, true) => {
+                Op::Add(c, CookiePrivacy::Private) => {
                     jar.private_mut(&self.config.secret_key.key).add(c);
                 }
-                Op::Remove(mut c, _) => {
+                #[cfg(feature = "secrets")]
+                Op::Add(c, CookiePrivacy::Signed) => {
+                    jar.signed_mut(&self.config.secret_key.key).add(c);
+                }
+                Op::Remove(mut c) => {
                     if self.jar.get(c.name()).is_some() {
                         c.make_removal();
                         jar.add(c);
@@ -444,8 +797,6 @@ impl<'a> CookieJar<'a> {
                         jar.remove(c);
                     }
                 }
-                #[allow(unreachable_patterns)]
-                _ => unreachable!()
             }
         }
 
@@ -466,6 +817,14 @@ impl<'a> CookieJar<'a> {
         self.jar.private_mut(&self.config.secret_key.key).add_original(cookie);
     }
 
+    /// Adds an original, signed `cookie` to the collection.
+    #[cfg(feature = "secrets")]
+    #[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
+    #[inline(always)]
+    pub(crate) fn add_original_signed(&mut self, cookie: Cookie<'static>) {
+        self.jar.signed_mut(&self.config.secret_key.key).add_original(cookie);
+    }
+
     /// For each property mentioned below, this method checks if there is a
     /// provided value and if there is none, sets a default value. Default
     /// values are:
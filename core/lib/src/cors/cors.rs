@@ -0,0 +1,366 @@
+use std::io::Cursor;
+
+use time::Duration;
+use yansi::Paint;
+
+use crate::{Rocket, Request, Response, Orbit};
+use crate::fairing::{Fairing, Info, Kind};
+use crate::http::{Header, Method, Status};
+use crate::http::uncased::{Uncased, UncasedStr};
+use crate::log::PaintExt;
+
+/// A [`Fairing`] that implements Cross-Origin Resource Sharing (CORS).
+///
+/// A `Cors` is configured with the origins, methods, and headers it allows,
+/// whether it allows credentials, which response headers it exposes, and for
+/// how long browsers may cache preflight results. See the [module level
+/// docs](crate::cors) for how requests are handled.
+///
+/// # Defaults
+///
+/// [`Cors::new()`] returns a fairing that allows:
+///
+///   * any origin, via `Access-Control-Allow-Origin: *`
+///   * the `GET`, `HEAD`, `POST`, `PUT`, `PATCH`, and `DELETE` methods
+///   * any request header
+///
+/// and that doesn't allow credentials, exposes no headers, and sets no max age.
+///
+/// # Credentials
+///
+/// Browsers reject `Access-Control-Allow-Origin: *` for requests with
+/// credentials. If credentials are allowed, the request's origin is thus
+/// echoed in `Access-Control-Allow-Origin` instead. Allowing credentials from
+/// any origin lets every website make authenticated requests on behalf of its
+/// visitors; a warning is logged at liftoff if a `Cors` is configured this way.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::cors::Cors;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().attach(Cors::new().allow_origin("https://rocket.rs"))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Cors {
+    /// The allowed origins or `None` if any origin is allowed.
+    origins: Option<Vec<Uncased<'static>>>,
+    methods: Vec<Method>,
+    /// The allowed request headers or `None` if any header is allowed.
+    headers: Option<Vec<Uncased<'static>>>,
+    expose: Vec<Uncased<'static>>,
+    credentials: bool,
+    max_age: Option<Duration>,
+}
+
+impl Default for Cors {
+    /// Returns [`Cors::new()`].
+    fn default() -> Self {
+        Cors::new()
+    }
+}
+
+impl Cors {
+    /// Returns a new `Cors` with the [default](#defaults) policy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::cors::Cors;
+    ///
+    /// let cors = Cors::new();
+    /// ```
+    pub fn new() -> Cors {
+        use Method::*;
+
+        Cors {
+            origins: None,
+            methods: vec![Get, Head, Post, Put, Patch, Delete],
+            headers: None,
+            expose: vec![],
+            credentials: false,
+            max_age: None,
+        }
+    }
+
+    /// Adds `origin`, a serialized origin like `https://rocket.rs:8000`, to
+    /// the allowed origins. Once an origin is added, only added origins are
+    /// allowed. Origins are compared case-insensitively.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::cors::Cors;
+    ///
+    /// let cors = Cors::new()
+    ///     .allow_origin("https://rocket.rs")
+    ///     .allow_origin("http://localhost:8000");
+    /// ```
+    pub fn allow_origin<S: Into<String>>(mut self, origin: S) -> Cors {
+        let origin = origin.into();
+        let origin = origin.strip_suffix('/').map(String::from).unwrap_or(origin);
+        self.origins.get_or_insert_with(Vec::new).push(Uncased::from(origin));
+        self
+    }
+
+    /// Allows any origin, discarding any added origins. This is the default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::cors::Cors;
+    ///
+    /// let cors = Cors::new().allow_origin("https://rocket.rs").allow_any_origin();
+    /// ```
+    pub fn allow_any_origin(mut self) -> Cors {
+        self.origins = None;
+        self
+    }
+
+    /// Sets the allowed methods, replacing the defaults.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::cors::Cors;
+    /// use rocket::http::Method;
+    ///
+    /// let cors = Cors::new().allow_methods([Method::Get, Method::Post]);
+    /// ```
+    pub fn allow_methods<I: IntoIterator<Item = Method>>(mut self, methods: I) -> Cors {
+        self.methods = methods.into_iter().collect();
+        self
+    }
+
+    /// Sets the allowed request headers. By default, any header is allowed.
+    /// Headers are compared case-insensitively.
+    ///
+    /// Note that browsers always allow [CORS-safelisted request headers],
+    /// such as `Accept`, regardless of this setting.
+    ///
+    /// [CORS-safelisted request headers]:
+    /// https://developer.mozilla.org/en-US/docs/Glossary/CORS-safelisted_request_header
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::cors::Cors;
+    ///
+    /// let cors = Cors::new().allow_headers(["Content-Type", "X-Api-Key"]);
+    /// ```
+    pub fn allow_headers<I, S>(mut self, headers: I) -> Cors
+        where I: IntoIterator<Item = S>, S: Into<String>
+    {
+        self.headers = Some(headers.into_iter().map(|h| Uncased::from(h.into())).collect());
+        self
+    }
+
+    /// Allows any request header. This is the default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::cors::Cors;
+    ///
+    /// let cors = Cors::new().allow_headers(["X-Api-Key"]).allow_any_header();
+    /// ```
+    pub fn allow_any_header(mut self) -> Cors {
+        self.headers = None;
+        self
+    }
+
+    /// Sets the response headers, beyond the [CORS-safelisted response
+    /// headers], that browsers expose to cross-origin scripts.
+    ///
+    /// [CORS-safelisted response headers]:
+    /// https://developer.mozilla.org/en-US/docs/Glossary/CORS-safelisted_response_header
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::cors::Cors;
+    ///
+    /// let cors = Cors::new().expose_headers(["X-Request-Id"]);
+    /// ```
+    pub fn expose_headers<I, S>(mut self, headers: I) -> Cors
+        where I: IntoIterator<Item = S>, S: Into<String>
+    {
+        self.expose = headers.into_iter().map(|h| Uncased::from(h.into())).collect();
+        self
+    }
+
+    /// Sets whether requests with credentials, such as cookies, are allowed.
+    /// Disabled by default. See [credentials](#credentials).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::cors::Cors;
+    ///
+    /// let cors = Cors::new().allow_origin("https://rocket.rs").allow_credentials(true);
+    /// ```
+    pub fn allow_credentials(mut self, enable: bool) -> Cors {
+        self.credentials = enable;
+        self
+    }
+
+    /// Sets how long browsers may cache the results of a preflight request.
+    /// Not set by default. Browsers cap the duration they honor.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::cors::Cors;
+    /// use rocket::time::Duration;
+    ///
+    /// let cors = Cors::new().max_age(Duration::minutes(10));
+    /// ```
+    pub fn max_age(mut self, max_age: Duration) -> Cors {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    fn is_allowed_origin(&self, origin: &str) -> bool {
+        match &self.origins {
+            Some(origins) => origins.iter().any(|o| o == origin),
+            None => true,
+        }
+    }
+
+    /// Returns `true` if every header in the comma-separated list `headers`
+    /// is allowed.
+    fn are_allowed_headers(&self, headers: &str) -> bool {
+        let allowed = match &self.headers {
+            Some(allowed) => allowed,
+            None => return true,
+        };
+
+        headers.split(',')
+            .map(|h| h.trim())
+            .filter(|h| !h.is_empty())
+            .all(|h| allowed.iter().any(|a| a == h))
+    }
+
+    /// Sets the headers common to preflight and actual responses.
+    fn set_origin_headers<'r>(&self, origin: &str, res: &mut Response<'r>) {
+        let allow_origin = match self.origins.is_none() && !self.credentials {
+            true => "*".to_string(),
+            false => origin.to_string(),
+        };
+
+        if allow_origin != "*" {
+            let varies = res.headers().get("Vary")
+                .flat_map(|v| v.split(','))
+                .any(|v| UncasedStr::new(v.trim()) == "Origin");
+
+            if !varies {
+                res.adjoin_header(Header::new("Vary", "Origin"));
+            }
+        }
+
+        res.set_header(Header::new("Access-Control-Allow-Origin", allow_origin));
+        if self.credentials {
+            res.set_header(Header::new("Access-Control-Allow-Credentials", "true"));
+        }
+    }
+
+    fn respond_to_preflight<'r>(&self, req: &Request<'_>, origin: &str, res: &mut Response<'r>) {
+        let method = req.headers().get_one("Access-Control-Request-Method")
+            .and_then(|m| m.parse::<Method>().ok());
+
+        let requested_headers = req.headers().get("Access-Control-Request-Headers")
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let allowed = method.map_or(false, |m| self.methods.contains(&m))
+            && self.are_allowed_headers(&requested_headers);
+
+        if !allowed {
+            info_!("CORS preflight request for a disallowed method or header.");
+            return;
+        }
+
+        if res.status() == Status::NotFound {
+            res.set_status(Status::NoContent);
+            res.remove_header("Content-Type");
+            res.set_sized_body(0, Cursor::new(""));
+        }
+
+        self.set_origin_headers(origin, res);
+        let methods = self.methods.iter().map(|m| m.as_str()).collect::<Vec<_>>();
+        res.set_header(Header::new("Access-Control-Allow-Methods", methods.join(", ")));
+
+        let headers = match &self.headers {
+            Some(headers) => headers.iter().map(|h| h.as_str()).collect::<Vec<_>>().join(", "),
+            None => requested_headers,
+        };
+
+        if !headers.is_empty() {
+            res.set_header(Header::new("Access-Control-Allow-Headers", headers));
+        }
+
+        if let Some(max_age) = self.max_age {
+            let secs = max_age.whole_seconds().max(0);
+            res.set_header(Header::new("Access-Control-Max-Age", secs.to_string()));
+        }
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for Cors {
+    fn info(&self) -> Info {
+        Info {
+            name: "CORS",
+            kind: Kind::Liftoff | Kind::Response | Kind::Singleton,
+        }
+    }
+
+    async fn on_liftoff(&self, _: &Rocket<Orbit>) {
+        info!("{}{}:", Paint::emoji("🌐 "), Paint::magenta("CORS"));
+        match &self.origins {
+            Some(origins) => {
+                let origins = origins.iter().map(|o| o.as_str()).collect::<Vec<_>>();
+                info_!("origins: {}", Paint::default(origins.join(", ")));
+            }
+            None => info_!("origins: {}", Paint::default("*")),
+        }
+
+        if self.origins.is_none() && self.credentials {
+            warn_!("Credentials are allowed from any origin.");
+            info_!("Any website can make authenticated requests on behalf of its visitors.");
+            info_!("To remove this warning, configure the allowed origins.");
+        }
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let origin = match req.headers().get_one("Origin") {
+            Some(origin) => origin,
+            None => return,
+        };
+
+        if !self.is_allowed_origin(origin) {
+            if self.origins.is_some() {
+                res.adjoin_header(Header::new("Vary", "Origin"));
+            }
+
+            return info_!("CORS request from disallowed origin {}.", origin);
+        }
+
+        let is_preflight = req.method() == Method::Options
+            && req.headers().contains("Access-Control-Request-Method");
+
+        if is_preflight {
+            return self.respond_to_preflight(req, origin, res);
+        }
+
+        self.set_origin_headers(origin, res);
+        if !self.expose.is_empty() {
+            let expose = self.expose.iter().map(|h| h.as_str()).collect::<Vec<_>>();
+            res.set_header(Header::new("Access-Control-Expose-Headers", expose.join(", ")));
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! Cross-Origin Resource Sharing (CORS).
+//!
+//! The [`Cors`] fairing implements the server side of the [CORS protocol]: it
+//! adds `Access-Control-*` headers to responses to cross-origin requests from
+//! allowed origins and automatically responds to CORS preflight requests,
+//! letting browsers make cross-origin requests to an application.
+//!
+//! [CORS protocol]: https://fetch.spec.whatwg.org/#http-cors-protocol
+//!
+//! # Usage
+//!
+//! Attach a configured instance of [`Cors`]:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::cors::Cors;
+//! use rocket::http::Method;
+//! use rocket::time::Duration;
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     let cors = Cors::new()
+//!         .allow_origin("https://rocket.rs")
+//!         .allow_origin("https://api.rocket.rs")
+//!         .allow_methods([Method::Get, Method::Post])
+//!         .allow_headers(["Content-Type", "Authorization"])
+//!         .allow_credentials(true)
+//!         .max_age(Duration::hours(1));
+//!
+//!     rocket::build().attach(cors)
+//! }
+//! ```
+//!
+//! # Preflight Requests
+//!
+//! A preflight request is an `OPTIONS` request with an `Origin` and an
+//! `Access-Control-Request-Method` header. If a preflight request is allowed
+//! and no route handles it, that is, it results in a `404`, the response is
+//! replaced with an empty `204 No Content` response carrying the allowed
+//! methods, headers, and max age. If a route does handle it, the CORS headers
+//! are added to the route's response. A preflight request for a disallowed
+//! origin, method, or header receives no CORS headers, so the browser rejects
+//! the subsequent request.
+//!
+//! # Simple and Actual Requests
+//!
+//! Responses to all other requests with an `Origin` header from an allowed
+//! origin receive an `Access-Control-Allow-Origin` header along with, if
+//! configured, `Access-Control-Allow-Credentials` and
+//! `Access-Control-Expose-Headers` headers. `Vary: Origin` is added whenever
+//! the response depends on the request's origin.
+
+mod cors;
+
+pub use self::cors::Cors;
@@ -0,0 +1,233 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::{Request, Data};
+use crate::data::{self, FromData};
+use crate::form::{self, Form, FromForm, ValueField, DataField, Options};
+use crate::http::{Method, Status};
+use crate::outcome::try_outcome;
+use crate::request::{self, FromRequest};
+
+use super::token::{CsrfToken, Error};
+
+/// A guard that verifies a request's CSRF token.
+///
+/// # Request Guard
+///
+/// As a request guard, `Csrf` verifies the token in the `X-CSRF-Token` header
+/// of requests with state-changing methods, that is, all methods but `GET`,
+/// `HEAD`, `OPTIONS`, and `TRACE`. Requests with other methods always succeed.
+/// This is the guard to use with clients that read the token from the
+/// `csrf_token` cookie.
+///
+/// # Data Guard
+///
+/// As a data guard, `Csrf` verifies the token in the `csrf_token` field of a
+/// form, ignoring all other fields, or, if the request has one, the
+/// `X-CSRF-Token` header. This is the guard to use for form submissions with
+/// no other data. To verify the token of a form with other fields, use
+/// [`CsrfForm`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::csrf::Csrf;
+///
+/// #[post("/logout")]
+/// fn api_logout(_csrf: Csrf) { /* .. */ }
+///
+/// #[post("/logout", data = "<_csrf>", rank = 2)]
+/// fn form_logout(_csrf: Csrf) { /* .. */ }
+/// ```
+///
+/// In both cases, the request is rejected with a `403 Forbidden` if the token
+/// is missing or invalid.
+#[derive(Debug)]
+pub struct Csrf(());
+
+/// A data guard that parses a form and verifies its CSRF token.
+///
+/// A `CsrfForm<T>` parses a form `T` like [`Form<T>`] and verifies the token
+/// in its `csrf_token` field or, if the form has no such field, the request's
+/// `X-CSRF-Token` header. The `csrf_token` field isn't passed to `T`. If the
+/// token is missing or invalid, the request is rejected with a `403
+/// Forbidden`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::csrf::CsrfForm;
+///
+/// #[derive(FromForm)]
+/// struct Transfer {
+///     to: String,
+///     amount: usize,
+/// }
+///
+/// #[post("/transfer", data = "<transfer>")]
+/// fn transfer(transfer: CsrfForm<Transfer>) -> String {
+///     format!("sent {} to {}", transfer.amount, transfer.to)
+/// }
+/// ```
+#[derive(Debug)]
+pub struct CsrfForm<T>(T);
+
+impl<T> CsrfForm<T> {
+    /// Consumes `self` and returns the inner value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::csrf::CsrfForm;
+    ///
+    /// #[post("/", data = "<form>")]
+    /// fn submit(form: CsrfForm<String>) -> String {
+    ///     form.into_inner()
+    /// }
+    /// ```
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for CsrfForm<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for CsrfForm<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+/// The value of a form's `csrf_token` field, if any, along with the result of
+/// parsing the rest of it as a `T`, which isn't yet validated. Parsing a
+/// `Protected` always succeeds so that the token is verified before any error
+/// in the form is reported.
+struct Protected<'r, T> {
+    token: Option<&'r str>,
+    inner: form::Result<'r, T>,
+}
+
+#[crate::async_trait]
+impl<'r, T: FromForm<'r>> FromForm<'r> for Protected<'r, T> {
+    type Context = (Option<&'r str>, T::Context);
+
+    fn init(opts: Options) -> Self::Context {
+        (None, T::init(opts))
+    }
+
+    fn push_value((token, ctxt): &mut Self::Context, field: ValueField<'r>) {
+        match field.name.as_name() == CsrfToken::FIELD {
+            true => *token = Some(field.value),
+            false => T::push_value(ctxt, field),
+        }
+    }
+
+    async fn push_data((_, ctxt): &mut Self::Context, field: DataField<'r, '_>) {
+        T::push_data(ctxt, field).await
+    }
+
+    fn push_error((_, ctxt): &mut Self::Context, error: form::Error<'r>) {
+        T::push_error(ctxt, error)
+    }
+
+    fn finalize((token, ctxt): Self::Context) -> form::Result<'r, Self> {
+        Ok(Protected { token, inner: T::finalize(ctxt) })
+    }
+}
+
+/// Accepts, and ignores, any form.
+struct Ignored;
+
+#[crate::async_trait]
+impl<'r> FromForm<'r> for Ignored {
+    type Context = ();
+
+    fn init(_: Options) { }
+
+    fn push_value(_: &mut (), _: ValueField<'r>) { }
+
+    async fn push_data(_: &mut (), _: DataField<'r, '_>) { }
+
+    fn finalize(_: ()) -> form::Result<'r, Self> {
+        Ok(Ignored)
+    }
+}
+
+fn is_state_changing(method: Method) -> bool {
+    !matches!(method, Method::Get | Method::Head | Method::Options | Method::Trace)
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for Csrf {
+    type Error = Error;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Error> {
+        if !is_state_changing(req.method()) {
+            return request::Outcome::Success(Csrf(()));
+        }
+
+        match CsrfToken::verify_request(req, req.headers().get_one(CsrfToken::HEADER)) {
+            Ok(()) => request::Outcome::Success(Csrf(())),
+            Err(e) => request::Outcome::Failure((Status::Forbidden, e)),
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromData<'r> for Csrf {
+    type Error = form::Errors<'r>;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+        if req.headers().contains(CsrfToken::HEADER) {
+            let token = req.headers().get_one(CsrfToken::HEADER);
+            return match CsrfToken::verify_request(req, token) {
+                Ok(()) => data::Outcome::Success(Csrf(())),
+                Err(e) => data::Outcome::Failure((Status::Forbidden, form_error(e))),
+            };
+        }
+
+        match CsrfForm::<Ignored>::from_data(req, data).await {
+            data::Outcome::Success(_) => data::Outcome::Success(Csrf(())),
+            data::Outcome::Failure(e) => data::Outcome::Failure(e),
+            data::Outcome::Forward(_) => {
+                data::Outcome::Failure((Status::Forbidden, form_error(Error::Missing)))
+            }
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'r, T: FromForm<'r>> FromData<'r> for CsrfForm<T> {
+    type Error = form::Errors<'r>;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+        let form = try_outcome!(Form::<Protected<'r, T>>::from_data(req, data).await);
+        let Protected { token, inner } = form.into_inner();
+        let token = token.or_else(|| req.headers().get_one(CsrfToken::HEADER));
+        if let Err(e) = CsrfToken::verify_request(req, token) {
+            return data::Outcome::Failure((Status::Forbidden, form_error(e)));
+        }
+
+        let inner = match inner {
+            Ok(inner) => T::validate_async(inner, req).await,
+            Err(e) => Err(e),
+        };
+
+        match inner {
+            Ok(inner) => data::Outcome::Success(CsrfForm(inner)),
+            Err(e) => data::Outcome::Failure((e.status(), e)),
+        }
+    }
+}
+
+fn form_error<'v>(error: Error) -> form::Errors<'v> {
+    form::Error::custom(error).with_name(CsrfToken::FIELD).into()
+}
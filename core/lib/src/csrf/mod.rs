@@ -0,0 +1,85 @@
+//! Cross-site request forgery (CSRF) protection.
+//!
+//! This module implements the [synchronizer token] and [signed double-submit
+//! cookie] patterns on top of [private cookies], and thus the application's
+//! [`secret_key`](crate::Config::secret_key):
+//!
+//!   * [`CsrfToken`] is a request guard that retrieves the client's token,
+//!     generating one and setting the corresponding cookies if necessary. It
+//!     is used to emit tokens into forms and templates.
+//!   * [`Csrf`] is a request guard that verifies the token in the
+//!     `X-CSRF-Token` header of requests with state-changing methods, and a
+//!     data guard that verifies the token in a form's `csrf_token` field.
+//!   * [`CsrfForm<T>`] is a data guard that parses a form `T` and verifies the
+//!     token in its `csrf_token` field.
+//!
+//! Requests that fail verification are rejected with a `403 Forbidden`.
+//!
+//! [synchronizer token]: https://cheatsheetseries.owasp.org/cheatsheets/Cross-Site_Request_Forgery_Prevention_Cheat_Sheet.html#synchronizer-token-pattern
+//! [signed double-submit cookie]: https://cheatsheetseries.owasp.org/cheatsheets/Cross-Site_Request_Forgery_Prevention_Cheat_Sheet.html#signed-double-submit-cookie-recommended
+//! [private cookies]: crate::http::CookieJar#private-cookies
+//!
+//! # Forms
+//!
+//! Emit the token into forms as a hidden `csrf_token` field, for instance via
+//! [`CsrfToken::form_field()`], and accept submissions with [`CsrfForm`]:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::response::content::RawHtml;
+//! use rocket::csrf::{CsrfToken, CsrfForm};
+//!
+//! #[derive(FromForm)]
+//! struct Comment<'r> {
+//!     text: &'r str,
+//! }
+//!
+//! #[get("/")]
+//! fn index(token: &CsrfToken) -> RawHtml<String> {
+//!     RawHtml(format!(r#"<form action="/comment" method="post">
+//!         {}
+//!         <input type="text" name="text">
+//!     </form>"#, token.form_field()))
+//! }
+//!
+//! #[post("/comment", data = "<comment>")]
+//! fn comment(comment: CsrfForm<Comment<'_>>) -> String {
+//!     format!("comment: {}", comment.text)
+//! }
+//! ```
+//!
+//! [`CsrfToken`] serializes as its string value, so it can be passed directly
+//! to templates: `context! { csrf_token: token }` with `<input type="hidden"
+//! name="csrf_token" value="{{ csrf_token }}">` in the template.
+//!
+//! # Single-Page Applications
+//!
+//! Along with the private cookie, a [`CsrfToken`] sets a `csrf_token` cookie
+//! that is readable by scripts. Clients read the token from the cookie and
+//! send it back in an `X-CSRF-Token` header, which the [`Csrf`] guard
+//! verifies:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::csrf::{Csrf, CsrfToken};
+//!
+//! // Sets the `csrf_token` cookie if the client doesn't have one.
+//! #[get("/session")]
+//! fn session(_token: &CsrfToken) { }
+//!
+//! #[delete("/item/<id>")]
+//! fn delete(id: usize, _csrf: Csrf) -> String {
+//!     format!("deleted {}", id)
+//! }
+//! ```
+//!
+//! Because the token is verified against the value of the private cookie,
+//! which can only be created with the secret key, a `csrf_token` cookie
+//! planted by an attacker, for instance from a sibling subdomain, doesn't
+//! pass verification.
+
+mod token;
+mod guard;
+
+pub use self::token::{CsrfToken, Error};
+pub use self::guard::{Csrf, CsrfForm};
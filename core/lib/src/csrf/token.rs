@@ -0,0 +1,224 @@
+use std::fmt;
+use std::convert::Infallible;
+
+use time::{Duration, OffsetDateTime};
+
+use crate::Request;
+use crate::http::{Cookie, SameSite};
+use crate::request::{self, FromRequest};
+
+/// A client's CSRF token.
+///
+/// A client's token is stored in a private cookie and, for use by scripts, in
+/// a readable `csrf_token` cookie. The token is retrieved with
+/// [`CsrfToken::of()`] or the `&CsrfToken` request guard which, if the client
+/// has no valid token, generate a new, random token and set both cookies on
+/// the response. Both cookies expire after one week.
+///
+/// A `CsrfToken` [`Display`](fmt::Display)s and serializes as its string value
+/// and can be emitted into a form with [`CsrfToken::form_field()`]. See the
+/// [module level docs](crate::csrf) for usage.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::csrf::CsrfToken;
+///
+/// #[get("/token")]
+/// fn token(token: &CsrfToken) -> String {
+///     token.to_string()
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsrfToken(String);
+
+/// An error verifying a CSRF token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The request did not contain a token.
+    Missing,
+    /// The request's token does not match the client's token.
+    Invalid,
+}
+
+impl CsrfToken {
+    /// The name of the form field from which tokens are read: `csrf_token`.
+    pub const FIELD: &'static str = "csrf_token";
+
+    /// The name of the header from which tokens are read: `X-CSRF-Token`.
+    pub const HEADER: &'static str = "X-CSRF-Token";
+
+    /// The name of the cookie, readable by scripts, containing the token:
+    /// `csrf_token`.
+    pub const COOKIE: &'static str = "csrf_token";
+
+    /// The name of the private cookie containing the token.
+    const PRIVATE_COOKIE: &'static str = "__rocket_csrf";
+
+    /// The number of random bytes in a token.
+    const LEN: usize = 32;
+
+    /// Returns the CSRF token of the client making `req`, generating a new
+    /// token and setting the corresponding cookies if the client doesn't have
+    /// a valid token.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::csrf::CsrfToken;
+    /// # let client = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let (req, other) = (client.get("/"), client.get("/"));
+    ///
+    /// let token = CsrfToken::of(&req);
+    /// assert_eq!(token, CsrfToken::of(&req));
+    /// assert_ne!(token, CsrfToken::of(&other));
+    /// ```
+    pub fn of<'r>(req: &'r Request<'_>) -> &'r CsrfToken {
+        req.local_cache(|| {
+            let jar = req.cookies();
+            let token = jar.get_private(Self::PRIVATE_COOKIE)
+                .filter(|cookie| Self::is_valid(cookie.value()))
+                .map(|cookie| CsrfToken(cookie.value().into()));
+
+            match token {
+                Some(token) => {
+                    if jar.get(Self::COOKIE).map(|c| c.value()) != Some(token.as_str()) {
+                        jar.add(token.cookie(Self::COOKIE, false));
+                    }
+
+                    token
+                }
+                None => {
+                    let token = CsrfToken::generate();
+                    jar.add_private(token.cookie(Self::PRIVATE_COOKIE, true));
+                    jar.add(token.cookie(Self::COOKIE, false));
+                    token
+                }
+            }
+        })
+    }
+
+    /// Returns the token as a string slice.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::csrf::CsrfToken;
+    /// # let client = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let req = client.get("/");
+    ///
+    /// let token = CsrfToken::of(&req);
+    /// assert_eq!(token.as_str().len(), 64);
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns a hidden HTML `input` named `csrf_token` with the token as its
+    /// value, for inclusion in a form.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::csrf::CsrfToken;
+    /// # let client = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let req = client.get("/");
+    ///
+    /// let token = CsrfToken::of(&req);
+    /// let field = format!(r#"<input type="hidden" name="csrf_token" value="{}">"#, token);
+    /// assert_eq!(token.form_field(), field);
+    /// ```
+    pub fn form_field(&self) -> String {
+        format!(r#"<input type="hidden" name="{}" value="{}">"#, Self::FIELD, self.0)
+    }
+
+    /// Verifies that `token` matches `self` in constant time.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::csrf::{CsrfToken, Error};
+    /// # let client = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let req = client.get("/");
+    ///
+    /// let token = CsrfToken::of(&req);
+    /// assert_eq!(token.verify(token.as_str()), Ok(()));
+    /// assert_eq!(token.verify("forged"), Err(Error::Invalid));
+    /// ```
+    pub fn verify(&self, token: &str) -> Result<(), Error> {
+        let (a, b) = (self.0.as_bytes(), token.as_bytes());
+        if a.len() != b.len() {
+            return Err(Error::Invalid);
+        }
+
+        match a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) {
+            0 => Ok(()),
+            _ => Err(Error::Invalid),
+        }
+    }
+
+    /// Verifies `token`, if any, against the token of the client making `req`.
+    pub(crate) fn verify_request(req: &Request<'_>, token: Option<&str>) -> Result<(), Error> {
+        match token {
+            Some(token) => CsrfToken::of(req).verify(token),
+            None => Err(Error::Missing),
+        }
+    }
+
+    fn is_valid(token: &str) -> bool {
+        token.len() == Self::LEN * 2 && token.bytes().all(|b| b.is_ascii_hexdigit())
+    }
+
+    fn generate() -> CsrfToken {
+        let bytes: [u8; Self::LEN] = rand::random();
+        CsrfToken(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+    }
+
+    fn cookie(&self, name: &'static str, http_only: bool) -> Cookie<'static> {
+        Cookie::build(name, self.0.clone())
+            .path("/")
+            .same_site(SameSite::Strict)
+            .http_only(http_only)
+            .expires(OffsetDateTime::now_utc() + Duration::weeks(1))
+            .finish()
+    }
+}
+
+impl fmt::Display for CsrfToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl AsRef<str> for CsrfToken {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl serde::Serialize for CsrfToken {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for &'r CsrfToken {
+    type Error = Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Infallible> {
+        request::Outcome::Success(CsrfToken::of(req))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Missing => "missing CSRF token".fmt(f),
+            Error::Invalid => "invalid CSRF token".fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error { }
@@ -65,6 +65,18 @@ impl<'r> Data<'r> {
         }
     }
 
+    /// Returns a `Data` whose stream is `self`'s decompressed with `encoding`
+    /// and capped to `limit` bytes.
+    #[cfg(feature = "compression")]
+    pub(crate) fn decompressed(
+        self,
+        encoding: crate::compression::Encoding,
+        limit: ByteUnit
+    ) -> Data<'r> {
+        let raw = crate::ext::Chain::new(std::io::Cursor::new(self.buffer), self.stream);
+        Data::from(StreamReader::decoded(crate::compression::decompress(raw, encoding, limit)))
+    }
+
     /// Returns the raw data stream, limited to `limit` bytes.
     ///
     /// The stream contains all of the data in the body of the request,
@@ -60,7 +60,9 @@ enum State {
 enum StreamKind<'r> {
     Empty,
     Body(&'r mut hyper::Body),
-    Multipart(multer::Field<'r>)
+    Multipart(multer::Field<'r>),
+    #[cfg(feature = "compression")]
+    Decoded(Pin<Box<dyn Stream<Item = io::Result<hyper::body::Bytes>> + Send + Sync + 'r>>),
 }
 
 impl<'r> DataStream<'r> {
@@ -245,6 +247,15 @@ impl<'r> From<multer::Field<'r>> for StreamReader<'r> {
     }
 }
 
+#[cfg(feature = "compression")]
+impl<'r> StreamReader<'r> {
+    pub(crate) fn decoded<S>(stream: S) -> Self
+        where S: Stream<Item = io::Result<hyper::body::Bytes>> + Send + Sync + 'r
+    {
+        Self { inner: StreamKind::Decoded(Box::pin(stream)), state: State::Pending }
+    }
+}
+
 impl AsyncRead for DataStream<'_> {
     #[inline(always)]
     fn poll_read(
@@ -268,6 +279,8 @@ impl Stream for StreamKind<'_> {
                 .map_err_ext(|e| io::Error::new(io::ErrorKind::Other, e)),
             StreamKind::Multipart(mp) => Pin::new(mp).poll_next(cx)
                 .map_err_ext(|e| io::Error::new(io::ErrorKind::Other, e)),
+            #[cfg(feature = "compression")]
+            StreamKind::Decoded(stream) => stream.as_mut().poll_next(cx),
             StreamKind::Empty => Poll::Ready(None),
         }
     }
@@ -276,6 +289,8 @@ impl Stream for StreamKind<'_> {
         match self {
             StreamKind::Body(body) => body.size_hint(),
             StreamKind::Multipart(mp) => mp.size_hint(),
+            #[cfg(feature = "compression")]
+            StreamKind::Decoded(stream) => stream.size_hint(),
             StreamKind::Empty => (0, Some(0)),
         }
     }
@@ -0,0 +1,167 @@
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWrite, DuplexStream, ReadBuf};
+
+use crate::http::hyper::upgrade::Upgraded;
+
+/// A bidirectional, raw stream to the client.
+///
+/// An `IoStream` is the raw I/O stream of a connection that has been upgraded
+/// from HTTP to another protocol. It is passed to an [`IoHandler`] and
+/// implements [`AsyncRead`] and [`AsyncWrite`].
+///
+/// When a request dispatched by a [local `Client`](crate::local) is upgraded,
+/// the `IoStream` is an in-memory stream to the client instead.
+pub struct IoStream {
+    kind: IoStreamKind,
+}
+
+/// The kinds of streams we accept as `IoStream`.
+enum IoStreamKind {
+    Upgraded(Upgraded),
+    Local(DuplexStream),
+}
+
+/// An upgraded connection I/O handler.
+///
+/// An I/O handler performs raw I/O via the passed in [`IoStream`], which is
+/// [`AsyncRead`], [`AsyncWrite`], and `Unpin`. This allows custom protocols,
+/// such as tunnels or RPC mechanisms, to be implemented atop of Rocket.
+///
+/// An I/O handler is registered for a protocol on a [`Response`] via
+/// [`Response::add_upgrade()`] or [`Builder::upgrade()`]. If the request asks
+/// to upgrade to the protocol, Rocket:
+///
+///   1. Sends the response with a status of `101 Switching Protocols`, the
+///      response's headers, and `Connection: upgrade` and `Upgrade: $proto`
+///      headers. The response's body, if any, is not sent.
+///
+///   2. Waits for the client to switch protocols, then calls
+///      [`IoHandler::io()`] with the upgraded connection.
+///
+/// Upgraded connections are subject to Rocket's [graceful
+/// shutdown](crate::config::Shutdown): once the grace and mercy periods
+/// elapse, the connection is closed. Handlers that wish to clean up earlier can
+/// select on a [`Shutdown`](crate::Shutdown) future retrieved from the
+/// request.
+///
+/// # Example
+///
+/// A protocol, `echo`, which echoes every byte back to the client:
+///
+/// ```rust
+/// use std::pin::Pin;
+///
+/// use rocket::{get, Request, Response};
+/// use rocket::data::{IoHandler, IoStream};
+/// use rocket::response::{self, Responder};
+/// use rocket::tokio::io;
+///
+/// struct Echo;
+///
+/// impl<'r> Responder<'r, 'static> for Echo {
+///     fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+///         Response::build().upgrade("echo", self).ok()
+///     }
+/// }
+///
+/// #[rocket::async_trait]
+/// impl IoHandler for Echo {
+///     async fn io(self: Pin<Box<Self>>, io: IoStream) -> io::Result<()> {
+///         let (mut reader, mut writer) = io::split(io);
+///         io::copy(&mut reader, &mut writer).await?;
+///         Ok(())
+///     }
+/// }
+///
+/// #[get("/echo")]
+/// fn echo() -> Echo {
+///     Echo
+/// }
+/// ```
+///
+/// [`Response`]: crate::Response
+/// [`Response::add_upgrade()`]: crate::Response::add_upgrade()
+/// [`Builder::upgrade()`]: crate::response::Builder::upgrade()
+#[crate::async_trait]
+pub trait IoHandler: Send {
+    /// Performs the raw I/O on the upgraded connection `io`.
+    ///
+    /// Errors are logged; a [`BrokenPipe`](io::ErrorKind::BrokenPipe) error is
+    /// logged as the connection being closed.
+    async fn io(self: Pin<Box<Self>>, io: IoStream) -> io::Result<()>;
+}
+
+#[doc(hidden)]
+impl From<Upgraded> for IoStream {
+    fn from(io: Upgraded) -> Self {
+        IoStream { kind: IoStreamKind::Upgraded(io) }
+    }
+}
+
+#[doc(hidden)]
+impl From<DuplexStream> for IoStream {
+    fn from(io: DuplexStream) -> Self {
+        IoStream { kind: IoStreamKind::Local(io) }
+    }
+}
+
+impl AsyncRead for IoStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match self.get_mut().kind {
+            IoStreamKind::Upgraded(ref mut io) => Pin::new(io).poll_read(cx, buf),
+            IoStreamKind::Local(ref mut io) => Pin::new(io).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for IoStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut().kind {
+            IoStreamKind::Upgraded(ref mut io) => Pin::new(io).poll_write(cx, buf),
+            IoStreamKind::Local(ref mut io) => Pin::new(io).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut().kind {
+            IoStreamKind::Upgraded(ref mut io) => Pin::new(io).poll_flush(cx),
+            IoStreamKind::Local(ref mut io) => Pin::new(io).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut().kind {
+            IoStreamKind::Upgraded(ref mut io) => Pin::new(io).poll_shutdown(cx),
+            IoStreamKind::Local(ref mut io) => Pin::new(io).poll_shutdown(cx),
+        }
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut().kind {
+            IoStreamKind::Upgraded(ref mut io) => Pin::new(io).poll_write_vectored(cx, bufs),
+            IoStreamKind::Local(ref mut io) => Pin::new(io).poll_write_vectored(cx, bufs),
+        }
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        match self.kind {
+            IoStreamKind::Upgraded(ref io) => io.is_write_vectored(),
+            IoStreamKind::Local(ref io) => io.is_write_vectored(),
+        }
+    }
+}
@@ -57,16 +57,30 @@ use crate::http::uncased::Uncased;
 /// |-------------------|---------|--------------|---------------------------------------|
 /// | `form`            | 32KiB   | [`Form`]     | entire non-data-based form            |
 /// | `data-form`       | 2MiB    | [`Form`]     | entire data-based form                |
+/// | `multipart`       | 32MiB   | [`Multipart`]| entire streamed multipart body        |
 /// | `file`            | 1MiB    | [`TempFile`] | [`TempFile`] data guard or form field |
 /// | `file/$ext`       | _N/A_   | [`TempFile`] | file form field with extension `$ext` |
 /// | `string`          | 8KiB    | [`String`]   | data guard or data form field         |
 /// | `bytes`           | 8KiB    | [`Vec<u8>`]  | data guard                            |
 /// | `json`            | 1MiB    | [`Json`]     | JSON data and form payloads           |
 /// | `msgpack`         | 1MiB    | [`MsgPack`]  | MessagePack data and form payloads    |
+/// | `json-lines`      | 8MiB    | [`JsonLines`]| entire JSON Lines data payload        |
+/// | `cbor`            | 1MiB    | [`Cbor`]     | CBOR data and form payloads           |
+/// | `xml`             | 1MiB    | [`Xml`]      | XML data and form payloads            |
+/// | `decompressed`    | 2MiB    | [`Data`]     | decompressed, encoded request body    |
 ///
+/// The `decompressed` limit caps the size of a request body that is
+/// transparently decompressed, which requires the `compression` feature. See
+/// [`compression`](crate::compression#request-decompression) for details.
+///
+/// [`Data`]: crate::data::Data
+/// [`Multipart`]: crate::form::Multipart
 /// [`TempFile`]: crate::fs::TempFile
 /// [`Json`]: crate::serde::json::Json
 /// [`MsgPack`]: crate::serde::msgpack::MsgPack
+/// [`JsonLines`]: crate::serde::json::JsonLines
+/// [`Cbor`]: crate::serde::cbor::Cbor
+/// [`Xml`]: crate::serde::xml::Xml
 ///
 /// # Usage
 ///
@@ -132,11 +146,16 @@ impl Default for Limits {
         Limits::new()
             .limit("form", Limits::FORM)
             .limit("data-form", Limits::DATA_FORM)
+            .limit("multipart", Limits::MULTIPART)
             .limit("file", Limits::FILE)
             .limit("string", Limits::STRING)
             .limit("bytes", Limits::BYTES)
             .limit("json", Limits::JSON)
             .limit("msgpack", Limits::MESSAGE_PACK)
+            .limit("json-lines", Limits::JSON_LINES)
+            .limit("cbor", Limits::CBOR)
+            .limit("xml", Limits::XML)
+            .limit("decompressed", Limits::DECOMPRESSED)
     }
 }
 
@@ -147,6 +166,12 @@ impl Limits {
     /// Default limit for data-based forms.
     pub const DATA_FORM: ByteUnit = ByteUnit::Mebibyte(2);
 
+    /// Default limit for multipart bodies streamed by [`Multipart`]. Each
+    /// field is additionally limited by the `file` or `form` limit.
+    ///
+    /// [`Multipart`]: crate::form::Multipart
+    pub const MULTIPART: ByteUnit = ByteUnit::Mebibyte(32);
+
     /// Default limit for temporary files.
     pub const FILE: ByteUnit = ByteUnit::Mebibyte(1);
 
@@ -162,6 +187,19 @@ impl Limits {
     /// Default limit for MessagePack payloads.
     pub const MESSAGE_PACK: ByteUnit = ByteUnit::Mebibyte(1);
 
+    /// Default limit for JSON Lines payloads. Each line is additionally
+    /// limited by the `json` limit.
+    pub const JSON_LINES: ByteUnit = ByteUnit::Mebibyte(8);
+
+    /// Default limit for CBOR payloads.
+    pub const CBOR: ByteUnit = ByteUnit::Mebibyte(1);
+
+    /// Default limit for XML payloads.
+    pub const XML: ByteUnit = ByteUnit::Mebibyte(1);
+
+    /// Default limit for decompressed request bodies.
+    pub const DECOMPRESSED: ByteUnit = ByteUnit::Mebibyte(2);
+
     /// Construct a new `Limits` structure with no limits set.
     ///
     /// # Example
@@ -301,6 +339,13 @@ impl Limits {
         None
     }
 
+    /// Returns a copy of `self` with every limit in `overrides` added or
+    /// replaced.
+    pub(crate) fn merged(&self, overrides: &Limits) -> Limits {
+        overrides.limits.iter()
+            .fold(self.clone(), |limits, (name, limit)| limits.limit(name.clone(), *limit))
+    }
+
     /// Deserialize a `Limits` vector from a map. Ensures that the resulting
     /// vector is properly sorted for futures lookups via binary search.
     fn deserialize<'de, D>(de: D) -> Result<Vec<(Uncased<'static>, ByteUnit)>, D::Error>
@@ -4,6 +4,7 @@
 mod capped;
 mod data;
 mod data_stream;
+mod io_stream;
 mod from_data;
 mod limits;
 
@@ -11,6 +12,7 @@ pub use self::data::Data;
 pub use self::data_stream::DataStream;
 pub use self::from_data::{FromData, Outcome};
 pub use self::limits::Limits;
+pub use self::io_stream::{IoHandler, IoStream};
 pub use self::capped::{N, Capped};
 pub use ubyte::{ByteUnit, ToByteUnit};
 
@@ -1,5 +1,8 @@
 use std::{io, time::Duration};
-use std::task::{Poll, Context};
+use std::sync::{Arc, Mutex};
+use std::net::IpAddr;
+use std::collections::HashMap;
+use std::task::{Poll, Context, Waker};
 use std::pin::Pin;
 
 use bytes::{Bytes, BytesMut};
@@ -320,6 +323,199 @@ impl<L: Listener, F: Future + Clone> Listener for CancellableListener<F, L> {
     }
 }
 
+/// Connection counts shared by all listeners, enforcing the configured
+/// `max_connections` and `max_connections_per_ip`.
+#[derive(Debug)]
+pub struct ConnectionLimiter {
+    max: Option<usize>,
+    max_per_ip: Option<usize>,
+    state: Mutex<LimiterState>,
+}
+
+#[derive(Debug, Default)]
+struct LimiterState {
+    open: usize,
+    per_ip: HashMap<IpAddr, usize>,
+    waiting: Vec<Waker>,
+}
+
+/// A slot held by one connection, released when dropped.
+pub struct ConnectionPermit {
+    limiter: Arc<ConnectionLimiter>,
+    ip: Option<IpAddr>,
+}
+
+impl ConnectionLimiter {
+    pub fn new(max: Option<usize>, max_per_ip: Option<usize>) -> Arc<Self> {
+        let state = Mutex::new(LimiterState::default());
+        Arc::new(ConnectionLimiter { max, max_per_ip, state })
+    }
+
+    /// Reserves a slot for a connection if fewer than `max` are open or else
+    /// registers for a wakeup when one closes.
+    fn poll_reserve(self: &Arc<Self>, cx: &mut Context<'_>) -> Poll<ConnectionPermit> {
+        let mut state = self.state.lock().expect("limiter lock");
+        if self.max.map_or(false, |max| state.open >= max) {
+            if !state.waiting.iter().any(|w| w.will_wake(cx.waker())) {
+                state.waiting.push(cx.waker().clone());
+            }
+
+            return Poll::Pending;
+        }
+
+        state.open += 1;
+        Poll::Ready(ConnectionPermit { limiter: self.clone(), ip: None })
+    }
+
+    /// Assigns the reserved `permit` to a connection from `ip`. Returns `false`
+    /// if `ip` already has `max_per_ip` connections open.
+    fn assign(&self, permit: &mut ConnectionPermit, ip: Option<IpAddr>) -> bool {
+        let (ip, max) = match (ip, self.max_per_ip) {
+            (Some(ip), Some(max)) => (ip, max),
+            _ => return true,
+        };
+
+        let mut state = self.state.lock().expect("limiter lock");
+        let count = state.per_ip.entry(ip).or_insert(0);
+        if *count >= max {
+            return false;
+        }
+
+        *count += 1;
+        permit.ip = Some(ip);
+        true
+    }
+}
+
+impl Drop for ConnectionPermit {
+    fn drop(&mut self) {
+        let mut state = self.limiter.state.lock().expect("limiter lock");
+        state.open -= 1;
+        if let Some(ip) = self.ip {
+            if let Some(count) = state.per_ip.get_mut(&ip) {
+                *count -= 1;
+                if *count == 0 {
+                    state.per_ip.remove(&ip);
+                }
+            }
+        }
+
+        state.waiting.drain(..).for_each(|waker| waker.wake());
+    }
+}
+
+pin_project! {
+    /// A listener that stops accepting connections while `max_connections` are
+    /// open and closes those exceeding `max_connections_per_ip` on accept.
+    pub struct LimitedListener<L> {
+        #[pin]
+        listener: L,
+        limiter: Arc<ConnectionLimiter>,
+        reserved: Option<ConnectionPermit>,
+    }
+}
+
+pin_project! {
+    /// A connection accepted by a [`LimitedListener`], holding its slot.
+    pub struct Limited<C> {
+        #[pin]
+        io: C,
+        permit: ConnectionPermit,
+    }
+}
+
+impl<L> LimitedListener<L> {
+    pub fn new(listener: L, limiter: Arc<ConnectionLimiter>) -> Self {
+        LimitedListener { listener, limiter, reserved: None }
+    }
+}
+
+impl<L: Listener> Listener for LimitedListener<L> {
+    type Connection = Limited<L::Connection>;
+
+    fn local_addr(&self) -> Option<std::net::SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    fn poll_accept(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>
+    ) -> Poll<io::Result<Self::Connection>> {
+        let mut this = self.project();
+        loop {
+            if this.reserved.is_none() {
+                *this.reserved = Some(futures::ready!(this.limiter.poll_reserve(cx)));
+            }
+
+            let io = futures::ready!(this.listener.as_mut().poll_accept(cx))?;
+            let ip = io.peer_address().map(|addr| addr.ip());
+            let mut permit = this.reserved.take().expect("reserved permit");
+            if this.limiter.assign(&mut permit, ip) {
+                return Poll::Ready(Ok(Limited { io, permit }));
+            }
+
+            *this.reserved = Some(permit);
+            if let Some(ip) = ip {
+                warn_!("Closing connection from {}: per-IP connection limit reached.", ip);
+            }
+        }
+    }
+}
+
+impl<C: AsyncRead> AsyncRead for Limited<C> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        self.project().io.poll_read(cx, buf)
+    }
+}
+
+impl<C: AsyncWrite> AsyncWrite for Limited<C> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.project().io.poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().io.poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().io.poll_shutdown(cx)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        self.project().io.poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.io.is_write_vectored()
+    }
+}
+
+impl<C: Connection> Connection for Limited<C> {
+    fn peer_address(&self) -> Option<std::net::SocketAddr> {
+        self.io.peer_address()
+    }
+
+    fn peer_certificates(&self) -> Option<Certificates> {
+        self.io.peer_certificates()
+    }
+
+    fn enable_nodelay(&self) -> io::Result<()> {
+        self.io.enable_nodelay()
+    }
+}
+
 pub trait StreamExt: Sized + Stream {
     fn join<U>(self, other: U) -> Join<Self, U>
         where U: Stream<Item = Self::Item>;
@@ -0,0 +1,406 @@
+use std::fmt::{self, Write as _};
+use std::io::{self, Write};
+use std::net::IpAddr;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use time::OffsetDateTime;
+use tokio::io::{AsyncRead, AsyncSeek, ReadBuf};
+use tokio::sync::mpsc;
+
+use crate::{Rocket, Request, Response, Orbit};
+use crate::fairing::{Fairing, Info, Kind};
+use crate::http::{hyper, Method, Status};
+use crate::response::BodyReader;
+
+/// A fairing that writes an access log entry for every response.
+///
+/// Each entry records the client's IP address, the request's method, URI, and
+/// HTTP version, the response's status, the number of bytes of the response body written,
+/// and the request's latency. Entries are written as [Common Log Format] or
+/// [JSON lines], one line per response, to stdout or to any other
+/// [`Write`](std::io::Write)r, independently of Rocket's logging.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::fairing::AccessLog;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().attach(AccessLog::common())
+/// }
+/// ```
+///
+/// An entry is written once the response body has been fully written or its
+/// connection closed, so the latency, from when the request is received, and
+/// the byte count both include writing the body. Bytes are counted as they are
+/// read from the response's body _after_ the fairing runs: to count the bytes
+/// produced by other response fairings, such as compressed bodies, attach
+/// `AccessLog` after those fairings.
+///
+/// # Formats
+///
+/// In [Common Log Format], produced by [`AccessLog::common()`], an entry has
+/// the form below. Unknown values are written as `-`. The format has no field
+/// for latency; use [`AccessLog::json()`] to log latency.
+///
+/// ```text
+/// 127.0.0.1 - - [14/Oct/2026:10:21:04 +0000] "GET /hello HTTP/1.1" 200 13
+/// ```
+///
+/// In [JSON lines], produced by [`AccessLog::json()`], an entry is a JSON
+/// object with the fields below. The `route` is the name of the route that
+/// handled the request and `route_uri` its URI template, including its mount
+/// point, as returned by [`Request::route()`]. Unlike `uri`, `route_uri` does
+/// not vary with the values of dynamic parameters, so entries can be
+/// aggregated by it. Unknown values are `null`.
+///
+/// ```text
+/// {"time":"2026-10-14T10:21:04.123Z","remote":"127.0.0.1","method":"GET",
+///  "uri":"/hello/bob","route":"hello","route_uri":"/hello/<name>","status":200,
+///  "bytes":13,"latency_ms":0.215,"user_agent":"curl/8.0","referer":null}
+/// ```
+///
+/// # Writers
+///
+/// Entries are written to stdout by default. Use [`AccessLog::writer()`] to
+/// write entries elsewhere, such as to a file. Entries are written, in order,
+/// by a dedicated thread so that a slow writer never blocks request handling.
+/// If the writer falls more than [`AccessLog::BACKLOG`] entries behind, new
+/// entries are dropped and an error is logged.
+///
+/// ```rust,no_run
+/// # #[macro_use] extern crate rocket;
+/// use std::fs::OpenOptions;
+/// use rocket::fairing::AccessLog;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     let file = OpenOptions::new().create(true).append(true).open("access.log")
+///         .expect("access log is writable");
+///
+///     rocket::build().attach(AccessLog::json().writer(file))
+/// }
+/// ```
+///
+/// [Common Log Format]: https://en.wikipedia.org/wiki/Common_Log_Format
+/// [JSON lines]: https://jsonlines.org/
+pub struct AccessLog {
+    format: Format,
+    writer: Mutex<Option<Box<dyn Write + Send>>>,
+    sink: state::Storage<Sink>,
+}
+
+/// The sending half of the channel to the thread writing entries.
+#[derive(Clone)]
+struct Sink(mpsc::Sender<String>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Common,
+    Json,
+}
+
+/// The time at which a request was received, cached in its local cache.
+struct Received(Instant, OffsetDateTime);
+
+/// An access log entry awaiting the number of bytes written.
+struct Entry {
+    format: Format,
+    sink: Sink,
+    received: Instant,
+    time: OffsetDateTime,
+    remote: Option<IpAddr>,
+    method: Method,
+    version: Option<hyper::Version>,
+    uri: String,
+    route: Option<String>,
+    route_uri: Option<String>,
+    status: Status,
+    user_agent: Option<String>,
+    referer: Option<String>,
+}
+
+/// A body reader that counts the bytes read from it and writes its entry when
+/// dropped.
+struct Tally<T> {
+    inner: T,
+    bytes: u64,
+    entry: Option<Entry>,
+}
+
+impl AccessLog {
+    /// The maximum number of entries awaiting their write before new entries
+    /// are dropped.
+    ///
+    /// The present value is `4096`.
+    pub const BACKLOG: usize = 4096;
+
+    /// Returns an access log writing entries in Common Log Format to stdout.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::AccessLog;
+    ///
+    /// let rocket = rocket::build().attach(AccessLog::common());
+    /// ```
+    pub fn common() -> AccessLog {
+        AccessLog::new(Format::Common)
+    }
+
+    /// Returns an access log writing entries as JSON lines to stdout.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::AccessLog;
+    ///
+    /// let rocket = rocket::build().attach(AccessLog::json());
+    /// ```
+    pub fn json() -> AccessLog {
+        AccessLog::new(Format::Json)
+    }
+
+    fn new(format: Format) -> AccessLog {
+        AccessLog {
+            format,
+            writer: Mutex::new(Some(Box::new(io::stdout()))),
+            sink: state::Storage::new(),
+        }
+    }
+
+    /// Sets the writer to which entries are written. Each entry is written,
+    /// including its trailing newline, with a single call to `write_all()`
+    /// followed by a call to `flush()`, from a dedicated thread.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::AccessLog;
+    ///
+    /// let rocket = rocket::build().attach(AccessLog::common().writer(std::io::stderr()));
+    /// ```
+    pub fn writer<W: Write + Send + 'static>(mut self, writer: W) -> AccessLog {
+        self.writer = Mutex::new(Some(Box::new(writer)));
+        self
+    }
+}
+
+impl Sink {
+    /// Spawns the thread writing entries to `writer`. The thread exits once
+    /// every `Sink` is dropped and every entry written.
+    fn spawn(mut writer: Box<dyn Write + Send>) -> io::Result<Sink> {
+        let (tx, mut rx) = mpsc::channel::<String>(AccessLog::BACKLOG);
+        std::thread::Builder::new()
+            .name("rocket-access-log".into())
+            .spawn(move || while let Some(entry) = rx.blocking_recv() {
+                if let Err(e) = writer.write_all(entry.as_bytes()).and_then(|_| writer.flush()) {
+                    error_!("Failed to write access log entry: {}", e);
+                }
+            })?;
+
+        Ok(Sink(tx))
+    }
+
+    fn send(&self, entry: String) {
+        if let Err(e) = self.0.try_send(entry) {
+            error_!("Dropping access log entry: {}", e);
+        }
+    }
+}
+
+impl Default for AccessLog {
+    /// Returns [`AccessLog::common()`].
+    fn default() -> Self {
+        AccessLog::common()
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for AccessLog {
+    fn info(&self) -> Info {
+        Info { name: "Access Log", kind: Kind::Liftoff | Kind::Request | Kind::Response }
+    }
+
+    async fn on_liftoff(&self, _: &Rocket<Orbit>) {
+        let writer = match self.writer.lock() {
+            Ok(mut writer) => writer.take(),
+            Err(e) => e.into_inner().take(),
+        };
+
+        if let Some(writer) = writer {
+            match Sink::spawn(writer) {
+                Ok(sink) => { self.sink.set(sink); }
+                Err(e) => error!("Failed to start access log writer: {}", e),
+            }
+        }
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _: &mut crate::Data<'_>) {
+        req.local_cache(|| Received(Instant::now(), OffsetDateTime::now_utc()));
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let sink = match self.sink.try_get() {
+            Some(sink) => sink.clone(),
+            None => return,
+        };
+
+        let received = req.local_cache(|| Received(Instant::now(), OffsetDateTime::now_utc()));
+        let header = |name| req.headers().get_one(name).map(String::from);
+        let entry = Entry {
+            format: self.format,
+            sink,
+            received: received.0,
+            time: received.1,
+            remote: req.client_ip(),
+            method: req.method(),
+            version: req.version(),
+            uri: req.uri().to_string(),
+            route: req.route().and_then(|r| r.name.as_ref()).map(|n| n.to_string()),
+            route_uri: req.route().map(|r| r.uri.to_string()),
+            status: res.status(),
+            user_agent: header("User-Agent"),
+            referer: header("Referer"),
+        };
+
+        if res.body().is_none() {
+            return entry.write(0);
+        }
+
+        res.body_mut().wrap(|reader| match reader {
+            BodyReader::Sized(b) => BodyReader::Sized(Box::pin(Tally::new(b, entry))),
+            BodyReader::Unsized(b) => BodyReader::Unsized(Box::pin(Tally::new(b, entry))),
+        });
+    }
+}
+
+impl Entry {
+    fn write(self, bytes: u64) {
+        let entry = match self.format {
+            Format::Common => self.common(bytes),
+            Format::Json => self.json(bytes, self.received.elapsed()),
+        };
+
+        self.sink.send(entry);
+    }
+
+    fn common(&self, bytes: u64) -> String {
+        use time::macros::format_description;
+
+        let time_format = format_description!(
+            "[day]/[month repr:short]/[year]:[hour]:[minute]:[second] \
+            [offset_hour sign:mandatory][offset_minute]"
+        );
+
+        let time = self.time.format(&time_format).unwrap_or_default();
+        let mut entry = String::new();
+        let version = self.version.map(|v| format!("{:?}", v));
+        let _ = write!(entry, "{} - - [{}] \"{} {} {}\" {} ", Optional(&self.remote), time,
+            self.method, self.uri, Optional(&version), self.status.code);
+        match bytes {
+            0 => entry.push_str("-\n"),
+            n => { let _ = writeln!(entry, "{}", n); }
+        }
+
+        entry
+    }
+
+    fn json(&self, bytes: u64, latency: Duration) -> String {
+        use time::format_description::well_known::Rfc3339;
+
+        let time = self.time.format(&Rfc3339).unwrap_or_default();
+        let remote = self.remote.map(|ip| ip.to_string());
+        let mut entry = String::new();
+        let _ = writeln!(entry,
+            "{{\"time\":{},\"remote\":{},\"method\":{},\"uri\":{},\"route\":{},\
+            \"route_uri\":{},\"status\":{},\"bytes\":{},\"latency_ms\":{:.3},\
+            \"user_agent\":{},\"referer\":{}}}",
+            Json(Some(&*time)), Json(remote.as_deref()), Json(Some(self.method.as_str())),
+            Json(Some(&*self.uri)), Json(self.route.as_deref()),
+            Json(self.route_uri.as_deref()), self.status.code, bytes,
+            latency.as_secs_f64() * 1000.0, Json(self.user_agent.as_deref()),
+            Json(self.referer.as_deref()));
+
+        entry
+    }
+}
+
+/// Displays a value or `-` if there is none.
+struct Optional<'a, T>(&'a Option<T>);
+
+impl<T: fmt::Display> fmt::Display for Optional<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(value) => value.fmt(f),
+            None => f.write_str("-"),
+        }
+    }
+}
+
+/// Displays a string as a JSON string or `null` if there is none.
+struct Json<'a>(Option<&'a str>);
+
+impl fmt::Display for Json<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let string = match self.0 {
+            Some(string) => string,
+            None => return f.write_str("null"),
+        };
+
+        f.write_char('"')?;
+        for c in string.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                '\r' => f.write_str("\\r")?,
+                '\t' => f.write_str("\\t")?,
+                c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+                c => f.write_char(c)?,
+            }
+        }
+
+        f.write_char('"')
+    }
+}
+
+impl<T> Tally<T> {
+    fn new(inner: T, entry: Entry) -> Self {
+        Tally { inner, bytes: 0, entry: Some(entry) }
+    }
+}
+
+impl<T: AsyncRead + Unpin> AsyncRead for Tally<T> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let filled = buf.filled().len();
+        let result = Pin::new(&mut self.inner).poll_read(cx, buf);
+        self.bytes += (buf.filled().len() - filled) as u64;
+        result
+    }
+}
+
+impl<T: AsyncSeek + Unpin> AsyncSeek for Tally<T> {
+    fn start_seek(mut self: Pin<&mut Self>, position: io::SeekFrom) -> io::Result<()> {
+        Pin::new(&mut self.inner).start_seek(position)
+    }
+
+    fn poll_complete(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        Pin::new(&mut self.inner).poll_complete(cx)
+    }
+}
+
+impl<T> Drop for Tally<T> {
+    fn drop(&mut self) {
+        if let Some(entry) = self.entry.take() {
+            entry.write(self.bytes);
+        }
+    }
+}
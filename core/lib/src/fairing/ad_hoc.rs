@@ -2,8 +2,9 @@ use std::sync::Mutex;
 
 use futures::future::{Future, BoxFuture, FutureExt};
 
-use crate::{Rocket, Request, Response, Data, Build, Orbit};
-use crate::fairing::{Fairing, Kind, Info, Result};
+use crate::{Rocket, Request, Response, Data, Build, Orbit, Shutdown};
+use crate::response;
+use crate::fairing::{Fairing, Kind, Info, Result, ErrorInfo};
 
 /// A ad-hoc fairing that can be created from a function or closure.
 ///
@@ -12,9 +13,10 @@ use crate::fairing::{Fairing, Kind, Info, Result};
 ///
 /// # Usage
 ///
-/// Use [`AdHoc::on_ignite`], [`AdHoc::on_liftoff`], [`AdHoc::on_request()`], or
-/// [`AdHoc::on_response()`] to create an `AdHoc` structure from a function or
-/// closure. Then, simply attach the structure to the `Rocket` instance.
+/// Use [`AdHoc::on_ignite`], [`AdHoc::on_liftoff`], [`AdHoc::on_request()`],
+/// [`AdHoc::on_filter()`], [`AdHoc::on_response()`], [`AdHoc::on_error()`], or
+/// [`AdHoc::on_config_reload()`] to create an `AdHoc` structure from a function
+/// or closure. Then, simply attach the structure to the `Rocket` instance.
 ///
 /// # Example
 ///
@@ -62,13 +64,26 @@ enum AdHocKind {
     Request(Box<dyn for<'a> Fn(&'a mut Request<'_>, &'a Data<'_>)
         -> BoxFuture<'a, ()> + Send + Sync + 'static>),
 
+    /// An ad-hoc **filter** fairing. Called just before a request is routed.
+    Filter(Box<dyn for<'r, 'b> Fn(&'r Request<'_>, &'b mut Data<'r>)
+        -> BoxFuture<'b, Option<response::Result<'r>>> + Send + Sync + 'static>),
+
     /// An ad-hoc **response** fairing. Called when a response is ready to be
     /// sent to a client.
     Response(Box<dyn for<'r, 'b> Fn(&'r Request<'_>, &'b mut Response<'r>)
         -> BoxFuture<'b, ()> + Send + Sync + 'static>),
 
+    /// An ad-hoc **error** fairing. Called when a request is about to be
+    /// handled by an error catcher.
+    Error(Box<dyn for<'a> Fn(&'a ErrorInfo<'_>, &'a Request<'_>)
+        -> BoxFuture<'a, ()> + Send + Sync + 'static>),
+
     /// An ad-hoc **shutdown** fairing. Called on shutdown.
     Shutdown(Once<dyn for<'a> FnOnce(&'a Rocket<Orbit>) -> BoxFuture<'a, ()> + Send + 'static>),
+
+    /// An ad-hoc **config reload** fairing. Called after the configuration is
+    /// reloaded.
+    ConfigReload(Box<dyn for<'a> Fn(&'a Rocket<Orbit>) -> BoxFuture<'a, ()> + Send + Sync + 'static>),
 }
 
 impl AdHoc {
@@ -158,6 +173,33 @@ impl AdHoc {
         AdHoc { name, kind: AdHocKind::Request(Box::new(f)) }
     }
 
+    /// Constructs an `AdHoc` filter fairing named `name`. The function `f`
+    /// will be called and the returned `Future` will be `await`ed by Rocket
+    /// just before a request is routed. If the future resolves to `Some`, the
+    /// request is not routed and is instead responded to with its contents.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::AdHoc;
+    /// use rocket::http::Status;
+    /// use rocket::response::Responder;
+    ///
+    /// // A fairing that only allows requests with an API key through.
+    /// let fairing = AdHoc::on_filter("API Key Wall", |req, _| Box::pin(async move {
+    ///     match req.headers().get_one("X-Api-Key") {
+    ///         Some(_) => None,
+    ///         None => Some("an API key is required".respond_to(req)),
+    ///     }
+    /// }));
+    /// ```
+    pub fn on_filter<F: Send + Sync + 'static>(name: &'static str, f: F) -> AdHoc
+        where F: for<'r, 'b> Fn(&'r Request<'_>, &'b mut Data<'r>)
+            -> BoxFuture<'b, Option<response::Result<'r>>>
+    {
+        AdHoc { name, kind: AdHocKind::Filter(Box::new(f)) }
+    }
+
     // FIXME(rustc): We'd like to allow passing `async fn` to these methods...
     // https://github.com/rust-lang/rust/issues/64552#issuecomment-666084589
 
@@ -184,19 +226,51 @@ impl AdHoc {
         AdHoc { name, kind: AdHocKind::Response(Box::new(f)) }
     }
 
+    /// Constructs an `AdHoc` error fairing named `name`. The function `f` will
+    /// be called and the returned `Future` will be `await`ed by Rocket when a
+    /// request is about to be handled by an error catcher.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::AdHoc;
+    ///
+    /// // A fairing that reports failures.
+    /// let fairing = AdHoc::on_error("Reporter", |info, req| Box::pin(async move {
+    ///     match info.error() {
+    ///         Some(e) => eprintln!("{} failed with {}: {}", req, info.status(), e),
+    ///         None => eprintln!("{} failed with {}", req, info.status()),
+    ///     }
+    /// }));
+    /// ```
+    pub fn on_error<F: Send + Sync + 'static>(name: &'static str, f: F) -> AdHoc
+        where F: for<'a> Fn(&'a ErrorInfo<'_>, &'a Request<'_>) -> BoxFuture<'a, ()>
+    {
+        AdHoc { name, kind: AdHocKind::Error(Box::new(f)) }
+    }
+
     /// Constructs an `AdHoc` shutdown fairing named `name`. The function `f`
-    /// will be called by Rocket when [shutdown is triggered].
+    /// will be called by Rocket when [shutdown is triggered] and the returned
+    /// `Future` will be `await`ed for at most the [grace period].
     ///
     /// [shutdown is triggered]: crate::config::Shutdown#triggers
+    /// [grace period]: crate::config::Shutdown#grace-period
     ///
     /// # Example
     ///
     /// ```rust
     /// use rocket::fairing::AdHoc;
+    /// use rocket::tokio::sync::Mutex;
     ///
-    /// // A fairing that prints a message just before launching.
-    /// let fairing = AdHoc::on_shutdown("Bye!", |_| Box::pin(async move {
-    ///     println!("Rocket is on its way back!");
+    /// struct Queue(Mutex<Vec<String>>);
+    ///
+    /// // A fairing that flushes a managed queue on shutdown.
+    /// let fairing = AdHoc::on_shutdown("Flush Queue", |rocket| Box::pin(async move {
+    ///     if let Some(queue) = rocket.state::<Queue>() {
+    ///         for message in queue.0.lock().await.drain(..) {
+    ///             println!("flushing {}", message);
+    ///         }
+    ///     }
     /// }));
     /// ```
     pub fn on_shutdown<F: Send + Sync + 'static>(name: &'static str, f: F) -> AdHoc
@@ -205,6 +279,71 @@ impl AdHoc {
         AdHoc { name, kind: AdHocKind::Shutdown(Once::new(Box::new(f))) }
     }
 
+    /// Constructs an `AdHoc` config reload fairing named `name`. The function
+    /// `f` will be called by Rocket each time the configuration is reloaded via
+    /// [`Rocket::refresh_config()`] or [`Rocket::refresh_config_from()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::AdHoc;
+    ///
+    /// // A fairing that prints the new log level on every reload.
+    /// let fairing = AdHoc::on_config_reload("Reload Printer", |rocket| Box::pin(async move {
+    ///     println!("log level is now {}", rocket.current_config().log_level);
+    /// }));
+    /// ```
+    pub fn on_config_reload<F: Send + Sync + 'static>(name: &'static str, f: F) -> AdHoc
+        where F: for<'a> Fn(&'a Rocket<Orbit>) -> BoxFuture<'a, ()>
+    {
+        AdHoc { name, kind: AdHocKind::ConfigReload(Box::new(f)) }
+    }
+
+    /// Constructs an `AdHoc` liftoff fairing named `name` that spawns a
+    /// [background task](Rocket::spawn_task()), also named `name`, at liftoff.
+    /// The function `f` is called with the launched `Rocket` instance and its
+    /// [`Shutdown`] handle and returns the task's future.
+    ///
+    /// The future cannot borrow from `Rocket`: any managed state the task needs,
+    /// like a database pool, should be cloned out of the instance by `f`. The
+    /// task is awaited during a graceful shutdown and aborted if it does not
+    /// complete within the grace and mercy periods, so it should monitor
+    /// `Shutdown` and return promptly once it resolves. A panic in the task is
+    /// reported as soon as it occurs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::AdHoc;
+    /// use rocket::tokio::time::{self, Duration};
+    ///
+    /// # #[derive(Clone)] struct Pool;
+    /// # impl Pool { async fn prune(&self) {} }
+    /// // A task that periodically prunes the database until shutdown.
+    /// let fairing = AdHoc::task("Pruner", |rocket, mut shutdown| {
+    ///     let pool = rocket.state::<Pool>().cloned();
+    ///     async move {
+    ///         let pool = pool.expect("managed `Pool`");
+    ///         let mut interval = time::interval(Duration::from_secs(60));
+    ///         loop {
+    ///             rocket::tokio::select! {
+    ///                 _ = interval.tick() => pool.prune().await,
+    ///                 _ = &mut shutdown => break,
+    ///             }
+    ///         }
+    ///     }
+    /// });
+    /// ```
+    pub fn task<F, Fut>(name: &'static str, f: F) -> AdHoc
+        where F: FnOnce(&Rocket<Orbit>, Shutdown) -> Fut + Send + Sync + 'static,
+              Fut: Future<Output = ()> + Send + 'static,
+    {
+        AdHoc::on_liftoff(name, move |rocket| {
+            rocket.spawn_task(name, f(rocket, rocket.shutdown()));
+            Box::pin(async {})
+        })
+    }
+
     /// Constructs an `AdHoc` launch fairing that extracts a configuration of
     /// type `T` from the configured provider and stores it in managed state. If
     /// extractions fails, pretty-prints the error message and aborts launch.
@@ -252,8 +391,11 @@ impl Fairing for AdHoc {
             AdHocKind::Ignite(_) => Kind::Ignite,
             AdHocKind::Liftoff(_) => Kind::Liftoff,
             AdHocKind::Request(_) => Kind::Request,
+            AdHocKind::Filter(_) => Kind::Filter,
             AdHocKind::Response(_) => Kind::Response,
+            AdHocKind::Error(_) => Kind::Error,
             AdHocKind::Shutdown(_) => Kind::Shutdown,
+            AdHocKind::ConfigReload(_) => Kind::ConfigReload,
         };
 
         Info { name: self.name, kind }
@@ -278,15 +420,38 @@ impl Fairing for AdHoc {
         }
     }
 
+    async fn on_filter<'r>(
+        &self,
+        req: &'r Request<'_>,
+        data: &mut Data<'r>
+    ) -> Option<response::Result<'r>> {
+        match self.kind {
+            AdHocKind::Filter(ref f) => f(req, data).await,
+            _ => None
+        }
+    }
+
     async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
         if let AdHocKind::Response(ref f) = self.kind {
             f(req, res).await
         }
     }
 
+    async fn on_error(&self, info: &ErrorInfo<'_>, req: &Request<'_>) {
+        if let AdHocKind::Error(ref f) = self.kind {
+            f(info, req).await
+        }
+    }
+
     async fn on_shutdown(&self, rocket: &Rocket<Orbit>) {
         if let AdHocKind::Shutdown(ref f) = self.kind {
             (f.take())(rocket).await
         }
     }
+
+    async fn on_config_reload(&self, rocket: &Rocket<Orbit>) {
+        if let AdHocKind::ConfigReload(ref f) = self.kind {
+            f(rocket).await
+        }
+    }
 }
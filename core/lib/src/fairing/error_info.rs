@@ -0,0 +1,78 @@
+use std::error::Error;
+
+use crate::Route;
+use crate::http::Status;
+
+/// Information about a request failure, passed to [`Fairing::on_error()`].
+///
+/// An `ErrorInfo` describes why a request is being handled by an error
+/// catcher: the [`status`](ErrorInfo::status()) of the catcher being invoked,
+/// the [`route`](ErrorInfo::route()) that failed or forwarded last, if any, and
+/// the [`error`](ErrorInfo::error()) that caused the failure, when one is
+/// known. An error is known when:
+///
+///   * A request or data guard fails. The error is the one the guard
+///     [recorded](crate::Request::record_error()), if any, and the `Debug`
+///     representation of the guard's error otherwise.
+///   * A handler panics. The error contains the panic's message.
+///   * A handler times out. See [`Deadline`](crate::request::Deadline).
+///   * A request is malformed. The error describes why.
+///
+/// [`Fairing::on_error()`]: crate::fairing::Fairing::on_error()
+#[derive(Debug, Clone, Copy)]
+pub struct ErrorInfo<'a> {
+    pub(crate) status: Status,
+    pub(crate) route: Option<&'a Route>,
+    pub(crate) error: Option<&'a (dyn Error + Send + Sync + 'static)>,
+}
+
+impl<'a> ErrorInfo<'a> {
+    /// The status of the error catcher being invoked.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::AdHoc;
+    ///
+    /// let fairing = AdHoc::on_error("Status", |info, _| Box::pin(async move {
+    ///     println!("responding with {}", info.status());
+    /// }));
+    /// ```
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    /// The route that last handled the request before it failed or forwarded
+    /// into a catcher. This is `None` if no route matched the request.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::AdHoc;
+    ///
+    /// let fairing = AdHoc::on_error("Route", |info, _| Box::pin(async move {
+    ///     let route = info.route().and_then(|r| r.name.as_deref());
+    ///     println!("route: {}", route.unwrap_or("none"));
+    /// }));
+    /// ```
+    pub fn route(&self) -> Option<&'a Route> {
+        self.route
+    }
+
+    /// The error that caused the failure, if it is known.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::AdHoc;
+    ///
+    /// let fairing = AdHoc::on_error("Cause", |info, _| Box::pin(async move {
+    ///     if let Some(error) = info.error() {
+    ///         println!("failed because: {}", error);
+    ///     }
+    /// }));
+    /// ```
+    pub fn error(&self) -> Option<&'a (dyn Error + Send + Sync + 'static)> {
+        self.error
+    }
+}
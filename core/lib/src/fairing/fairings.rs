@@ -1,7 +1,11 @@
 use std::collections::HashSet;
+use std::time::Duration;
+
+use tokio::time::{timeout_at, Instant};
 
 use crate::{Rocket, Request, Response, Data, Build, Orbit};
-use crate::fairing::{Fairing, Info, Kind};
+use crate::response;
+use crate::fairing::{Fairing, Info, Kind, ErrorInfo};
 use crate::log::PaintExt;
 
 use yansi::Paint;
@@ -18,8 +22,11 @@ pub struct Fairings {
     ignite: Vec<usize>,
     liftoff: Vec<usize>,
     request: Vec<usize>,
+    filter: Vec<usize>,
     response: Vec<usize>,
+    error: Vec<usize>,
     shutdown: Vec<usize>,
+    config_reload: Vec<usize>,
 }
 
 macro_rules! iter {
@@ -46,8 +53,11 @@ impl Fairings {
         self.ignite.iter()
             .chain(self.liftoff.iter())
             .chain(self.request.iter())
+            .chain(self.filter.iter())
             .chain(self.response.iter())
+            .chain(self.error.iter())
             .chain(self.shutdown.iter())
+            .chain(self.config_reload.iter())
     }
 
     pub fn add(&mut self, fairing: Box<dyn Fairing>) {
@@ -91,8 +101,11 @@ impl Fairings {
                 remove(i, &mut self.ignite);
                 remove(i, &mut self.liftoff);
                 remove(i, &mut self.request);
+                remove(i, &mut self.filter);
                 remove(i, &mut self.response);
+                remove(i, &mut self.error);
                 remove(i, &mut self.shutdown);
+                remove(i, &mut self.config_reload);
             }
         }
 
@@ -101,8 +114,11 @@ impl Fairings {
         if this_info.kind.is(Kind::Ignite) { self.ignite.push(index); }
         if this_info.kind.is(Kind::Liftoff) { self.liftoff.push(index); }
         if this_info.kind.is(Kind::Request) { self.request.push(index); }
+        if this_info.kind.is(Kind::Filter) { self.filter.push(index); }
         if this_info.kind.is(Kind::Response) { self.response.push(index); }
+        if this_info.kind.is(Kind::Error) { self.error.push(index); }
         if this_info.kind.is(Kind::Shutdown) { self.shutdown.push(index); }
+        if this_info.kind.is(Kind::ConfigReload) { self.config_reload.push(index); }
     }
 
     pub fn append(&mut self, others: &mut Fairings) {
@@ -150,6 +166,22 @@ impl Fairings {
         }
     }
 
+    #[inline(always)]
+    pub async fn handle_filter<'r>(
+        &self,
+        req: &'r Request<'_>,
+        data: &mut Data<'r>
+    ) -> Option<response::Result<'r>> {
+        for fairing in iter!(self.filter) {
+            if let Some(result) = fairing.on_filter(req, data).await {
+                info_!("Filtered by {}.", Paint::default(fairing.info().name).bold());
+                return Some(result);
+            }
+        }
+
+        None
+    }
+
     #[inline(always)]
     pub async fn handle_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
         for fairing in iter!(self.response) {
@@ -157,12 +189,35 @@ impl Fairings {
         }
     }
 
+    #[inline(always)]
+    pub async fn handle_error(&self, info: &ErrorInfo<'_>, req: &Request<'_>) {
+        for fairing in iter!(self.error) {
+            fairing.on_error(info, req).await;
+        }
+    }
+
     #[inline(always)]
     pub async fn handle_shutdown(&self, rocket: &Rocket<Orbit>) {
-        let shutdown_futures = iter!(self.shutdown).map(|f| f.on_shutdown(rocket));
+        // All shutdown fairings share the grace period; cancel any that outlive it.
+        let grace = Duration::from_secs(rocket.config().shutdown.grace as u64);
+        let deadline = Instant::now() + grace;
+        let shutdown_futures = iter!(self.shutdown).map(|f| async move {
+            if timeout_at(deadline, f.on_shutdown(rocket)).await.is_err() {
+                warn_!("Shutdown fairing {} exceeded the grace period. Cancelling.",
+                    Paint::default(f.info().name).bold());
+            }
+        });
+
         futures::future::join_all(shutdown_futures).await;
     }
 
+    #[inline(always)]
+    pub async fn handle_config_reload(&self, rocket: &Rocket<Orbit>) {
+        for fairing in iter!(self.config_reload) {
+            fairing.on_config_reload(rocket).await;
+        }
+    }
+
     pub fn audit(&self) -> Result<(), &[Info]> {
         match self.failures.is_empty() {
             true => Ok(()),
@@ -193,8 +248,11 @@ impl std::fmt::Debug for Fairings {
             .field("launch", &debug_info(iter!(self.ignite)))
             .field("liftoff", &debug_info(iter!(self.liftoff)))
             .field("request", &debug_info(iter!(self.request)))
+            .field("filter", &debug_info(iter!(self.filter)))
             .field("response", &debug_info(iter!(self.response)))
+            .field("error", &debug_info(iter!(self.error)))
             .field("shutdown", &debug_info(iter!(self.shutdown)))
+            .field("config_reload", &debug_info(iter!(self.config_reload)))
             .finish()
     }
 }
@@ -0,0 +1,579 @@
+use std::fmt;
+use std::io;
+use std::convert::{Infallible, TryInto};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use yansi::Paint;
+
+use crate::{Rocket, Request, Response, Data, Build, Orbit, Shutdown};
+use crate::request::Forwarded;
+use crate::fairing::{self, Fairing, Info, Kind};
+use crate::response;
+use crate::http::{hyper, Status, ContentType};
+use crate::http::uri::{Absolute, Origin, Host};
+use crate::http::ext::IntoOwned;
+use crate::http::private::{TcpListener, Incoming};
+use crate::shield::ForceHsts;
+use crate::log::PaintExt;
+
+/// A fairing that redirects plain-HTTP requests to their HTTPS equivalent.
+///
+/// `HttpsRedirect` redirects requests in one or both of two ways:
+///
+///   * **If TLS is enabled**, Rocket only accepts TLS connections. The fairing
+///     listens for plain HTTP on [`port`](Self::port()), `80` by default, at
+///     the configured address. This minimal server is not a Rocket instance:
+///     it redirects every request to the same URI on the TLS-enabled server,
+///     except for requests to directories added with
+///     [`serve()`](Self::serve()). Requests are never routed to the
+///     application.
+///
+///   * **If proxies are [trusted](Self::trust_proxy())**, requests with an
+///     `X-Forwarded-Proto: http` header are answered with a redirect to the
+///     same URI over HTTPS by a [filter](crate::fairing::Fairing#filter)
+///     callback instead of being routed to the application.
+///
+/// # Redirect Target
+///
+/// If [`Config::base_url`](crate::Config::base_url) is an `https` URL,
+/// requests are redirected to its authority. Otherwise, requests are redirected
+/// to the host in their `Host` header, but only if it's one of the hosts added
+/// with [`host()`](Self::host()). The `X-Forwarded-Host` header is never
+/// considered. A request for any other host is not redirected: the plain-HTTP
+/// server responds with `400 Bad Request` while a proxied request is routed to
+/// the application as usual. As a special case, when no hosts are added, the
+/// plain-HTTP server redirects requests for any host to that same host. Behind
+/// a trusted proxy, a host or an `https` base URL must be configured for
+/// requests to be redirected.
+///
+/// Redirects use the status `308 Permanent Redirect` by default, which
+/// preserves the request's method and body. Use [`status()`](Self::status())
+/// to use another status, such as `301 Moved Permanently`.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::fairing::HttpsRedirect;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().attach(HttpsRedirect::new().trust_proxy(true).host("rocket.rs"))
+/// }
+/// ```
+///
+/// # Exempt Paths
+///
+/// Requests to paths under a base added with [`exempt()`](Self::exempt()) are
+/// never redirected. Behind a trusted proxy, such requests are routed to the
+/// application as usual. The plain-HTTP server serves only the files in
+/// directories added with [`serve()`](Self::serve()), whose bases are exempt,
+/// and `404`s otherwise. This allows serving [ACME] HTTP challenges for
+/// certificate issuance:
+///
+/// ```rust,no_run
+/// use rocket::fairing::HttpsRedirect;
+///
+/// let redirect = HttpsRedirect::new()
+///     .serve("/.well-known/acme-challenge", "/var/www/acme");
+/// ```
+///
+/// # HSTS
+///
+/// Browsers only remember to use HTTPS for a site if they receive an HSTS
+/// header. When [`hsts()`](Self::hsts()) is enabled, the [`Shield`] fairing
+/// sends its HSTS policy, or the default [`Hsts`] policy if none is enabled,
+/// even when TLS is terminated by a proxy or the profile is a debug profile.
+///
+/// [ACME]: https://en.wikipedia.org/wiki/Automatic_Certificate_Management_Environment
+/// [`Shield`]: crate::shield::Shield
+/// [`Hsts`]: crate::shield::Hsts
+#[derive(Debug, Clone)]
+pub struct HttpsRedirect {
+    port: u16,
+    trust_proxy: bool,
+    hsts: bool,
+    redirect: Redirect,
+    dirs: Vec<(Origin<'static>, PathBuf)>,
+}
+
+/// How requests are redirected.
+#[derive(Debug, Clone)]
+struct Redirect {
+    status: Status,
+    exempt: Vec<Origin<'static>>,
+    /// The hosts requests may be redirected to, as lowercase domains.
+    hosts: Vec<String>,
+}
+
+/// The body of a plain-HTTP response to a request under an exempt base,
+/// given the request's path relative to the base, or `None` for a `404`.
+pub(crate) type Answer = Arc<dyn Fn(PathBuf) -> BoxFuture<'static, Option<(ContentType, Vec<u8>)>>
+    + Send + Sync>;
+
+/// A minimal plain-HTTP server, separate from any Rocket instance, that
+/// answers requests under exempt bases and redirects all others to HTTPS.
+pub(crate) struct PlainServer {
+    redirect: Redirect,
+    https_port: u16,
+    base_url: Option<Absolute<'static>>,
+    answers: Vec<(Origin<'static>, Answer)>,
+}
+
+impl HttpsRedirect {
+    /// Returns an `HttpsRedirect` fairing with the default configuration:
+    /// plain HTTP is served on port `80` when TLS is enabled, proxies are not
+    /// trusted, no hosts are added, redirects use `308 Permanent Redirect`, no
+    /// paths are exempt, and HSTS is not requested.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::HttpsRedirect;
+    ///
+    /// let rocket = rocket::build().attach(HttpsRedirect::new());
+    /// ```
+    pub fn new() -> HttpsRedirect {
+        HttpsRedirect {
+            port: 80,
+            trust_proxy: false,
+            hsts: false,
+            redirect: Redirect {
+                status: Status::PermanentRedirect,
+                exempt: vec![],
+                hosts: vec![],
+            },
+            dirs: vec![],
+        }
+    }
+
+    /// Sets the port the plain-HTTP server listens on when TLS is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::HttpsRedirect;
+    ///
+    /// let redirect = HttpsRedirect::new().port(8080);
+    /// ```
+    pub fn port(mut self, port: u16) -> HttpsRedirect {
+        self.port = port;
+        self
+    }
+
+    /// Sets whether the `X-Forwarded-Proto` header is trusted. Only enable this
+    /// when Rocket is served exclusively through a proxy that sets or strips
+    /// the header or when [`Config::trusted_proxies`] is set, in which case
+    /// only headers set by trusted proxies, including the `Forwarded` header,
+    /// are considered. Requests are only redirected to a configured host: see
+    /// [Redirect Target](#redirect-target).
+    ///
+    /// [`Config::trusted_proxies`]: crate::Config::trusted_proxies
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::HttpsRedirect;
+    ///
+    /// let redirect = HttpsRedirect::new().trust_proxy(true).host("rocket.rs");
+    /// ```
+    pub fn trust_proxy(mut self, trust: bool) -> HttpsRedirect {
+        self.trust_proxy = trust;
+        self
+    }
+
+    /// Adds `host`, a domain name, to the hosts requests may be redirected to.
+    /// Hosts are compared case-insensitively with the domain in a request's
+    /// `Host` header. See [Redirect Target](#redirect-target).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::HttpsRedirect;
+    ///
+    /// let redirect = HttpsRedirect::new()
+    ///     .trust_proxy(true)
+    ///     .host("rocket.rs")
+    ///     .host("www.rocket.rs");
+    /// ```
+    pub fn host<H: Into<String>>(mut self, host: H) -> HttpsRedirect {
+        self.redirect.hosts.push(host.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Sets the status of redirect responses. Typically either
+    /// `308 Permanent Redirect`, the default, or `301 Moved Permanently`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `status` is not a redirection (`3xx`) status.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::HttpsRedirect;
+    /// use rocket::http::Status;
+    ///
+    /// let redirect = HttpsRedirect::new().status(Status::MovedPermanently);
+    /// ```
+    #[track_caller]
+    pub fn status(mut self, status: Status) -> HttpsRedirect {
+        assert!(status.class().is_redirection(), "{} is not a redirection status", status);
+        self.redirect.status = status;
+        self
+    }
+
+    /// Exempts requests to paths under `base` from being redirected. Paths are
+    /// matched by segments, exactly as with
+    /// [`Shield::scope()`](crate::shield::Shield::scope()).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base` is not a valid origin URI.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::HttpsRedirect;
+    ///
+    /// let redirect = HttpsRedirect::new()
+    ///     .trust_proxy(true)
+    ///     .exempt("/.well-known/acme-challenge");
+    /// ```
+    #[track_caller]
+    pub fn exempt<'a, B>(mut self, base: B) -> HttpsRedirect
+        where B: TryInto<Origin<'a>> + Clone + fmt::Display,
+              B::Error: fmt::Display
+    {
+        self.redirect.exempt.push(parse_base(base));
+        self
+    }
+
+    /// Serves the files in the directory `dir` at `base` on the plain-HTTP
+    /// server and exempts `base`. The directory is _not_ served by the
+    /// application itself. Requests for dotfiles are `404`s, and `..` segments
+    /// never leave `dir`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base` is not a valid origin URI.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::HttpsRedirect;
+    ///
+    /// let redirect = HttpsRedirect::new()
+    ///     .serve("/.well-known/acme-challenge", "/var/www/acme");
+    /// ```
+    #[track_caller]
+    pub fn serve<'a, B, P>(mut self, base: B, dir: P) -> HttpsRedirect
+        where B: TryInto<Origin<'a>> + Clone + fmt::Display,
+              B::Error: fmt::Display,
+              P: AsRef<Path>
+    {
+        let base = parse_base(base);
+        self.redirect.exempt.push(base.clone());
+        self.dirs.push((base, dir.as_ref().into()));
+        self
+    }
+
+    /// Sets whether [`Shield`](crate::shield::Shield) should send an HSTS
+    /// header even when Rocket doesn't terminate TLS itself or is running in a
+    /// debug profile. See [HSTS](#hsts) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::HttpsRedirect;
+    ///
+    /// let redirect = HttpsRedirect::new().trust_proxy(true).hsts(true);
+    /// ```
+    pub fn hsts(mut self, enable: bool) -> HttpsRedirect {
+        self.hsts = enable;
+        self
+    }
+
+    /// Launches the plain-HTTP server alongside `rocket`.
+    async fn listen(&self, rocket: &Rocket<Orbit>) {
+        let config = rocket.config();
+        let answers = self.dirs.iter()
+            .map(|(base, dir)| {
+                let dir = dir.clone();
+                let answer: Answer = Arc::new(move |path| {
+                    let path = dir.join(path);
+                    Box::pin(async move {
+                        let content_type = path.extension()
+                            .and_then(|ext| ContentType::from_extension(&ext.to_string_lossy()))
+                            .unwrap_or(ContentType::Binary);
+
+                        tokio::fs::read(&path).await.ok().map(|bytes| (content_type, bytes))
+                    })
+                });
+
+                (base.clone(), answer)
+            })
+            .collect();
+
+        let server = PlainServer {
+            redirect: self.redirect.clone(),
+            https_port: config.port,
+            base_url: config.base_url.clone(),
+            answers,
+        };
+
+        let addr = SocketAddr::new(config.address, self.port);
+        if let Err(e) = server.launch(addr, rocket.shutdown()).await {
+            error!("HTTPS redirect server failed to launch on port {}.", self.port);
+            error_!("{}", e);
+            rocket.shutdown().notify();
+        }
+    }
+}
+
+impl PlainServer {
+    /// A server redirecting requests to `https_port` unless `base_url` is an
+    /// `https` URL, only for requests for one of `hosts`, if any are given.
+    #[cfg(feature = "acme")]
+    pub(crate) fn new(https_port: u16, base_url: Option<Absolute<'static>>, hosts: &[String]) -> Self {
+        let redirect = Redirect {
+            hosts: hosts.iter().map(|host| host.to_ascii_lowercase()).collect(),
+            ..HttpsRedirect::new().redirect
+        };
+
+        PlainServer { redirect, https_port, base_url, answers: vec![] }
+    }
+
+    /// Answers requests under `base` with `answer`.
+    #[cfg(feature = "acme")]
+    pub(crate) fn answer(mut self, base: &str, answer: Answer) -> Self {
+        self.answers.push((parse_base(base), answer));
+        self
+    }
+
+    /// Binds to `addr`, then serves plain HTTP in the background until
+    /// `shutdown` resolves.
+    pub(crate) async fn launch(self, addr: SocketAddr, shutdown: Shutdown) -> io::Result<()> {
+        let listener = TcpListener::bind(addr).await?;
+        let server = Arc::new(self);
+        let service = hyper::service::make_service_fn(move |_| {
+            let server = server.clone();
+            async move {
+                Ok::<_, Infallible>(hyper::service::service_fn(move |req| {
+                    let server = server.clone();
+                    async move { Ok::<_, Infallible>(server.respond(req).await) }
+                }))
+            }
+        });
+
+        let server = hyper::server::Server::builder(Incoming::new(listener).nodelay(true))
+            .http1_keepalive(false)
+            .serve(service)
+            .with_graceful_shutdown(shutdown);
+
+        tokio::spawn(async move {
+            if let Err(e) = server.await {
+                error!("Plain HTTP server failed: {}", e);
+            }
+        });
+
+        Ok(())
+    }
+
+    async fn respond(&self, req: hyper::Request<hyper::Body>) -> hyper::Response<hyper::Body> {
+        use crate::http::hyper::header::{HOST, LOCATION, CONTENT_TYPE};
+
+        let respond = |status: Status| {
+            let mut response = hyper::Response::new(hyper::Body::empty());
+            *response.status_mut() = status.code.try_into().expect("valid status");
+            response
+        };
+
+        let uri = req.uri().path_and_query().map_or("/", |path| path.as_str());
+        let origin = match Origin::parse(uri) {
+            Ok(origin) => origin,
+            Err(_) => return respond(Status::BadRequest),
+        };
+
+        let path = origin.path();
+        for (base, answer) in &self.answers {
+            if !base.path().segments().prefix_of(path.segments()) {
+                continue;
+            }
+
+            let rest = path.segments().skip(base.path().segments().len());
+            let answer = match rest.to_path_buf(false) {
+                Ok(path) => answer(path).await,
+                Err(_) => None,
+            };
+
+            return match answer {
+                Some((content_type, body)) => {
+                    let mut response = respond(Status::Ok);
+                    if let Ok(value) = content_type.to_string().try_into() {
+                        response.headers_mut().insert(CONTENT_TYPE, value);
+                    }
+
+                    *response.body_mut() = body.into();
+                    response
+                }
+                None => respond(Status::NotFound),
+            };
+        }
+
+        let host = req.headers().get(HOST)
+            .and_then(|host| host.to_str().ok())
+            .and_then(|host| Host::parse(host).ok());
+
+        let base_url = self.base_url.as_ref();
+        match self.redirect.target(base_url, host.as_ref(), Some(self.https_port), &origin) {
+            Some(target) => {
+                let mut response = respond(self.redirect.status);
+                match target.try_into() {
+                    Ok(location) => response.headers_mut().insert(LOCATION, location),
+                    Err(_) => return respond(Status::BadRequest),
+                };
+
+                response
+            }
+            None => respond(Status::BadRequest),
+        }
+    }
+}
+
+impl Default for HttpsRedirect {
+    fn default() -> Self {
+        HttpsRedirect::new()
+    }
+}
+
+#[track_caller]
+fn parse_base<'a, B>(base: B) -> Origin<'static>
+    where B: TryInto<Origin<'a>> + Clone + fmt::Display,
+          B::Error: fmt::Display
+{
+    match base.clone().try_into() {
+        Ok(origin) => origin.into_owned(),
+        Err(e) => {
+            error!("invalid HTTPS redirect base: {}", Paint::white(&base));
+            error_!("{}", e);
+            info_!("{} {}", Paint::white("in"), std::panic::Location::caller());
+            panic!("aborting due to HTTPS redirect base error");
+        }
+    }
+}
+
+impl Redirect {
+    fn is_exempt(&self, req: &Request<'_>) -> bool {
+        let path = req.uri().path();
+        self.exempt.iter().any(|base| base.path().segments().prefix_of(path.segments()))
+    }
+
+    /// Whether a redirect target is known without a request's `Host`.
+    fn has_target(&self, base_url: Option<&Absolute<'_>>) -> bool {
+        !self.hosts.is_empty() || https_authority(base_url).is_some()
+    }
+
+    /// The HTTPS equivalent of `uri`, requested of `host`: at the authority of
+    /// `base_url`, if it's `https`, or else at `host` on `https_port`, if it's
+    /// not `443`, as long as `host` is allowed. When serving plain HTTP
+    /// directly, signaled by an `https_port`, and no hosts were added, any host
+    /// is allowed.
+    fn target(
+        &self,
+        base_url: Option<&Absolute<'_>>,
+        host: Option<&Host<'_>>,
+        https_port: Option<u16>,
+        uri: &Origin<'_>,
+    ) -> Option<String> {
+        if let Some(authority) = https_authority(base_url) {
+            return Some(format!("https://{}{}", authority, uri));
+        }
+
+        let domain = host?.domain();
+        let any_host = https_port.is_some() && self.hosts.is_empty();
+        if !any_host && !self.hosts.iter().any(|host| domain == host.as_str()) {
+            return None;
+        }
+
+        match https_port.filter(|&port| port != 443) {
+            Some(port) => Some(format!("https://{}:{}{}", domain, port, uri)),
+            None => Some(format!("https://{}{}", domain, uri)),
+        }
+    }
+}
+
+/// The authority of `base_url` if it's an `https` URL.
+fn https_authority<'a>(base_url: Option<&'a Absolute<'_>>) -> Option<impl fmt::Display + 'a> {
+    base_url.filter(|url| url.scheme().eq_ignore_ascii_case("https"))
+        .and_then(|url| url.authority())
+}
+
+#[crate::async_trait]
+impl Fairing for HttpsRedirect {
+    fn info(&self) -> Info {
+        Info {
+            name: "HTTPS Redirect",
+            kind: Kind::Ignite | Kind::Liftoff | Kind::Filter | Kind::Singleton,
+        }
+    }
+
+    async fn on_ignite(&self, mut rocket: Rocket<Build>) -> fairing::Result {
+        if self.hsts && rocket.state::<ForceHsts>().is_none() {
+            rocket = rocket.manage(ForceHsts);
+        }
+
+        Ok(rocket)
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        info!("{}{}:", Paint::emoji("🔒 "), Paint::magenta("HTTPS Redirect"));
+        let tls = rocket.config().tls_enabled();
+        if tls {
+            info_!("plain HTTP port: {}", Paint::default(self.port));
+        }
+
+        if self.trust_proxy {
+            info_!("proxied plain HTTP: {}", Paint::default("redirected"));
+            if !self.redirect.has_target(rocket.config().base_url.as_ref()) {
+                warn_!("No host is configured. Proxied requests will not be redirected.");
+                info_!("Add a host with `HttpsRedirect::host()` or set an `https` `base_url`.");
+            }
+        }
+
+        if !tls && !self.trust_proxy {
+            warn_!("TLS is disabled and proxies are not trusted.");
+            warn_!("No requests will be redirected to HTTPS.");
+            info_!("To redirect proxied requests, enable `trust_proxy`.");
+        }
+
+        if tls {
+            self.listen(rocket).await;
+        }
+    }
+
+    async fn on_filter<'r>(
+        &self,
+        req: &'r Request<'_>,
+        _: &mut Data<'r>
+    ) -> Option<response::Result<'r>> {
+        if !self.trust_proxy {
+            return None;
+        }
+
+        let insecure = Forwarded::from_request(req).proto
+            .map_or(false, |proto| proto.eq_ignore_ascii_case("http"));
+
+        if !insecure || self.redirect.is_exempt(req) {
+            return None;
+        }
+
+        let base_url = req.rocket().config().base_url.as_ref();
+        let target = self.redirect.target(base_url, req.host(), None, req.uri())?;
+        Some(Response::build()
+            .status(self.redirect.status)
+            .raw_header("Location", target)
+            .ok())
+    }
+}
@@ -39,8 +39,11 @@ pub struct Info {
 ///   * Ignite
 ///   * Liftoff
 ///   * Request
+///   * Filter
 ///   * Response
+///   * Error
 ///   * Shutdown
+///   * ConfigReload
 ///
 /// Two `Kind` structures can be `or`d together to represent a combination. For
 /// instance, to represent a fairing that is both an ignite and request fairing,
@@ -74,6 +77,15 @@ impl Kind {
     /// [singleton](crate::fairing::Fairing#singletons) fairing.
     pub const Singleton: Kind = Kind(1 << 5);
 
+    /// `Kind` flag representing a request for an 'error' callback.
+    pub const Error: Kind = Kind(1 << 6);
+
+    /// `Kind` flag representing a request for a 'filter' callback.
+    pub const Filter: Kind = Kind(1 << 7);
+
+    /// `Kind` flag representing a request for a 'config reload' callback.
+    pub const ConfigReload: Kind = Kind(1 << 8);
+
     /// Returns `true` if `self` is a superset of `other`. In other words,
     /// returns `true` if all of the kinds in `other` are also in `self`.
     ///
@@ -144,8 +156,11 @@ impl std::fmt::Display for Kind {
         write("ignite", Kind::Ignite)?;
         write("liftoff", Kind::Liftoff)?;
         write("request", Kind::Request)?;
+        write("filter", Kind::Filter)?;
         write("response", Kind::Response)?;
+        write("error", Kind::Error)?;
         write("shutdown", Kind::Shutdown)?;
+        write("config reload", Kind::ConfigReload)?;
         write("singleton", Kind::Singleton)
     }
 }
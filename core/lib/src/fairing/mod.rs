@@ -1,4 +1,4 @@
-//! Fairings: callbacks at launch, liftoff, request, and response time.
+//! Fairings: callbacks at launch, liftoff, request, response, and error time.
 //!
 //! Fairings allow for structured interposition at various points in the
 //! application lifetime. Fairings can be seen as a restricted form of
@@ -9,7 +9,8 @@
 //!
 //! To learn more about writing a fairing, see the [`Fairing`] trait
 //! documentation. You can also use [`AdHoc`] to create a fairing on-the-fly
-//! from a closure or function.
+//! from a closure or function. To log requests in a standard access log format,
+//! use [`AccessLog`].
 //!
 //! ## Attaching
 //!
@@ -48,28 +49,45 @@
 //! Furthermore, a `Fairing` should take care to act locally so that the actions
 //! of other `Fairings` are not jeopardized. For instance, unless it is made
 //! abundantly clear, a fairing should not rewrite every request.
+//!
+//! ## Scoped Fairings
+//!
+//! Request and response callbacks that apply only to some routes can be
+//! implemented by a [`ScopedFairing`] and attached to routes as they're mounted
+//! with [`Rocket::mount_with()`], avoiding the need to check the request's path
+//! in a global fairing.
 
 use std::any::Any;
 
 use crate::{Rocket, Request, Response, Data, Build, Orbit};
+use crate::response;
 
 mod fairings;
 mod ad_hoc;
 mod info_kind;
+mod access_log;
+mod https_redirect;
+mod error_info;
+mod scoped;
 
 pub(crate) use self::fairings::Fairings;
 pub use self::ad_hoc::AdHoc;
+pub use self::access_log::AccessLog;
+pub use self::https_redirect::HttpsRedirect;
+#[cfg(feature = "acme")]
+pub(crate) use self::https_redirect::{PlainServer, Answer};
+pub use self::error_info::ErrorInfo;
+pub use self::scoped::ScopedFairing;
 pub use self::info_kind::{Info, Kind};
 
 /// A type alias for the return `Result` type of [`Fairing::on_ignite()`].
 pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E>;
 
-// We might imagine that a request fairing returns an `Outcome`. If it returns
-// `Success`, we don't do any routing and use that response directly. Same if it
-// returns `Failure`. We only route if it returns `Forward`. I've chosen not to
-// go this direction because I feel like request guards are the correct
-// mechanism to use here. In other words, enabling this at the fairing level
-// encourages implicit handling, a bad practice. Fairings can still, however,
+// Request fairings don't return an `Outcome`: doing so would encourage
+// implicit handling that is better expressed via request guards. Filter
+// fairings, which run after all request fairings, are the explicit exception:
+// they exist for application-wide policies, such as maintenance modes or rate
+// limits, that must be able to respond before routing. Fairings can still
 // return a default `Response` if routing fails via a response fairing. For
 // instance, to automatically handle preflight in CORS, a response fairing can
 // check that the user didn't handle the `OPTIONS` request (404) and return an
@@ -101,9 +119,9 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 ///
 /// ## Fairing Callbacks
 ///
-/// There are five kinds of fairing callbacks: launch, liftoff, request,
-/// response, and shutdown. A fairing can request any combination of these
-/// callbacks through the `kind` field of the [`Info`] structure returned from
+/// There are eight kinds of fairing callbacks: launch, liftoff, request,
+/// filter, response, error, shutdown, and config reload. A fairing can request
+/// any combination of these callbacks through the `kind` field of the [`Info`] structure returned from
 /// the `info` method. Rocket will only invoke the callbacks identified in the
 /// fairing's [`Kind`].
 ///
@@ -149,9 +167,27 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 ///     [`Request`] and [`Data`] structures but has not routed the request. A
 ///     request callback can modify the request at will and [`Data::peek()`]
 ///     into the incoming data. It may not, however, abort or respond directly
-///     to the request; these issues are better handled via [request guards] or
-///     via response callbacks. Any modifications to a request are persisted and
-///     can potentially alter how a request is routed.
+///     to the request; these issues are better handled via [request guards],
+///     filter callbacks, or response callbacks. Any modifications to a request
+///     are persisted and can potentially alter how a request is routed.
+///
+///   * **<a name="filter">Filter</a> (`on_filter`)**
+///
+///     A filter callback, represented by the [`Fairing::on_filter()`] method,
+///     is called after all request callbacks have run, just before the request
+///     is routed. Unlike a request callback, a filter callback can terminate
+///     processing of the request: returning `Some(Ok(response))` responds with
+///     `response` directly while returning `Some(Err(status))` responds with
+///     the error catcher for `status`. In either case, the request is not
+///     routed and no later filter callbacks are invoked. Returning `None`
+///     continues processing as usual. Response callbacks run on all
+///     responses, including those produced by filter callbacks.
+///
+///     Filter callbacks are executed in `attach()` order. They are intended
+///     for policies that apply to the entire application, such as maintenance
+///     modes, authentication walls, and rate limiting. Policies that apply to
+///     a subset of routes are better implemented via [request guards] or
+///     [scoped fairings](ScopedFairing).
 ///
 ///   * **<a name="response">Response</a> (`on_response`)**
 ///
@@ -159,7 +195,9 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 ///     method, is called when a response is ready to be sent to the client. At
 ///     this point, Rocket has completed all routing, including to error
 ///     catchers, and has generated the would-be final response. A response
-///     callback can modify the response at will. For example, a response
+///     callback can modify the response at will, including its body, which
+///     can be transformed via [`Response::map_body()`] or observed as it is
+///     written via [`Response::inspect_body()`]. For example, a response
 ///     callback can provide a default response when the user fails to handle
 ///     the request by checking for 404 responses. Note that a given `Request`
 ///     may have changed between `on_request` and `on_response` invocations.
@@ -168,6 +206,22 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 ///     request. Additionally, Rocket will automatically strip the body for
 ///     `HEAD` requests _after_ response fairings have run.
 ///
+///   * **<a name="error">Error</a> (`on_error`)**
+///
+///     An error callback, represented by the [`Fairing::on_error()`] method,
+///     is called when a request is about to be handled by an error catcher:
+///     when a route's guard or handler fails, when every matching route
+///     forwards, or when no route matches. At this point, the catcher has not
+///     yet run. The [`ErrorInfo`] parameter carries the catcher's status, the
+///     route that failed or forwarded last, and, when known, the error that
+///     caused the failure. An error callback can't alter the response; it is
+///     intended for reporting errors, for instance to an error tracking
+///     service. Response callbacks run afterwards, as usual.
+///
+///     All error callbacks are executed in `attach()` order. Each request
+///     invokes error callbacks at most once, even if the catcher itself
+///     fails.
+///
 ///   * **<a name="shutdown">Shutdown</a> (`on_shutdown`)**
 ///
 ///     A shutdown callback, represented by the [`Fairing::on_shutdown()`]
@@ -175,26 +229,44 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 ///     shutdown has commenced but not completed; no new requests are accepted
 ///     but the application may still be actively serving existing requests.
 ///
-///     Rocket guarantees, however, that all requests are completed or aborted
-///     once [grace and mercy periods] have expired. This implies that a
-///     shutdown fairing that (asynchronously) sleeps for `grace + mercy + ε`
-///     seconds before executing any logic will execute said logic after all
-///     requests have been processed or aborted. Note that such fairings may
-///     wish to operate using the `Ok` return value of [`Rocket::launch()`]
-///     instead.
+///     Shutdown callbacks are `async` and have access to the application via
+///     `&Rocket<Orbit>`, including its [managed state](Rocket::state()),
+///     making them suitable for asynchronous cleanup such as flushing queues,
+///     checkpointing databases, or deregistering from service discovery.
 ///
-///     All registered shutdown fairings are run concurrently; resolution of all
-///     fairings is awaited before resuming shutdown. Shutdown fairings do not
-///     affect grace and mercy periods. In other words, any time consumed by
-///     shutdown fairings is not added to grace and mercy periods.
+///     All registered shutdown fairings are run concurrently and must complete
+///     within the [grace period]: the time they consume is counted against,
+///     not added to, the grace period. A shutdown fairing that has not
+///     completed once the grace period expires is cancelled and a warning is
+///     logged. Logic that must run after all requests have been processed or
+///     aborted should instead use the `Ok` return value of
+///     [`Rocket::launch()`].
 ///
 ///     ***Note: Shutdown fairings are only run during testing if the `Client`
 ///     is terminated using [`Client::terminate()`].***
 ///
 ///     [shutdown is triggered]: crate::config::Shutdown#triggers
-///     [grace and mercy periods]: crate::config::Shutdown#summary
+///     [grace period]: crate::config::Shutdown#grace-period
 ///     [`Client::terminate()`]: crate::local::blocking::Client::terminate()
 ///
+///   * **<a name="config-reload">Config Reload</a> (`on_config_reload`)**
+///
+///     A config reload callback, represented by the
+///     [`Fairing::on_config_reload()`] method, is called after the running
+///     application's configuration has been reloaded via
+///     [`Rocket::refresh_config()`] or [`Rocket::refresh_config_from()`]. By
+///     the time the callback is invoked, Rocket has applied the reload-safe
+///     settings; the new configuration is available via
+///     [`Rocket::current_config()`]. A config reload callback can use it to
+///     refresh its own state, for instance, to reload templates or to re-read
+///     custom configuration values.
+///
+///     Config reload callbacks are executed in `attach()` order.
+///
+///     [`Rocket::refresh_config()`]: crate::Rocket::refresh_config()
+///     [`Rocket::refresh_config_from()`]: crate::Rocket::refresh_config_from()
+///     [`Rocket::current_config()`]: crate::Rocket::current_config()
+///
 /// # Singletons
 ///
 /// In general, any number of instances of a given fairing type can be attached
@@ -209,7 +281,8 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 ///
 /// A `Fairing` implementation has one required method: [`info`]. A `Fairing`
 /// can also implement any of the available callbacks: `on_ignite`, `on_liftoff`,
-/// `on_request`, and `on_response`. A `Fairing` _must_ set the appropriate
+/// `on_request`, `on_filter`, `on_response`, `on_error`, and `on_shutdown`. A
+/// `Fairing` _must_ set the appropriate
 /// callback kind in the `kind` field of the returned `Info` structure from
 /// [`info`] for a callback to actually be called by Rocket.
 ///
@@ -251,6 +324,7 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 /// ```rust
 /// use rocket::{Rocket, Request, Data, Response, Build, Orbit};
 /// use rocket::fairing::{self, Fairing, Info, Kind};
+/// use rocket::response;
 ///
 /// # struct MyType;
 /// #[rocket::async_trait]
@@ -275,6 +349,15 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 ///         # unimplemented!()
 ///     }
 ///
+///     async fn on_filter<'r>(
+///         &self,
+///         req: &'r Request<'_>,
+///         data: &mut Data<'r>
+///     ) -> Option<response::Result<'r>> {
+///         /* ... */
+///         # unimplemented!()
+///     }
+///
 ///     async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
 ///         /* ... */
 ///         # unimplemented!()
@@ -284,6 +367,11 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 ///         /* ... */
 ///         # unimplemented!()
 ///     }
+///
+///     async fn on_config_reload(&self, rocket: &Rocket<Orbit>) {
+///         /* ... */
+///         # unimplemented!()
+///     }
 /// }
 /// ```
 ///
@@ -503,6 +591,60 @@ pub trait Fairing: Send + Sync + Any + 'static {
     /// The default implementation of this method does nothing.
     async fn on_request(&self, _req: &mut Request<'_>, _data: &mut Data<'_>) {}
 
+    /// The filter callback. Returns `Some` to respond to the request without
+    /// routing it and `None` to continue processing.
+    ///
+    /// See [Fairing Callbacks](#filter) for complete semantics.
+    ///
+    /// This method is called after all request callbacks have run if
+    /// `Kind::Filter` is in the `kind` field of the `Info` structure for this
+    /// fairing. The `&Request` parameter is the incoming request, and the
+    /// `&mut Data` parameter is the incoming data in the request.
+    ///
+    /// ## Example
+    ///
+    /// A fairing that responds to every request with a `503 Service
+    /// Unavailable` while an application is in maintenance mode:
+    ///
+    /// ```rust
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    ///
+    /// use rocket::{Request, Data, response};
+    /// use rocket::fairing::{Fairing, Info, Kind};
+    /// use rocket::http::Status;
+    ///
+    /// struct Maintenance(AtomicBool);
+    ///
+    /// #[rocket::async_trait]
+    /// impl Fairing for Maintenance {
+    ///     fn info(&self) -> Info {
+    ///         Info { name: "Maintenance Mode", kind: Kind::Filter }
+    ///     }
+    ///
+    ///     async fn on_filter<'r>(
+    ///         &self,
+    ///         _: &'r Request<'_>,
+    ///         _: &mut Data<'r>
+    ///     ) -> Option<response::Result<'r>> {
+    ///         match self.0.load(Ordering::Acquire) {
+    ///             true => Some(Err(Status::ServiceUnavailable)),
+    ///             false => None,
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// ## Default Implementation
+    ///
+    /// The default implementation of this method returns `None`.
+    async fn on_filter<'r>(
+        &self,
+        _req: &'r Request<'_>,
+        _data: &mut Data<'r>
+    ) -> Option<response::Result<'r>> {
+        None
+    }
+
     /// The response callback.
     ///
     /// See [Fairing Callbacks](#response) for complete semantics.
@@ -517,20 +659,50 @@ pub trait Fairing: Send + Sync + Any + 'static {
     /// The default implementation of this method does nothing.
     async fn on_response<'r>(&self, _req: &'r Request<'_>, _res: &mut Response<'r>) {}
 
+    /// The error callback.
+    ///
+    /// See [Fairing Callbacks](#error) for complete semantics.
+    ///
+    /// This method is called when a request is about to be handled by an error
+    /// catcher if `Kind::Error` is in the `kind` field of the `Info` structure
+    /// for this fairing. The `&ErrorInfo` parameter describes the failure, and
+    /// the `&Request` parameter is the request that failed.
+    ///
+    /// ## Default Implementation
+    ///
+    /// The default implementation of this method does nothing.
+    async fn on_error(&self, _info: &ErrorInfo<'_>, _req: &Request<'_>) {}
+
     /// The shutdown callback.
     ///
     /// See [Fairing Callbacks](#shutdown) for complete semantics.
     ///
     /// This method is called when [shutdown is triggered] if `Kind::Shutdown`
     /// is in the `kind` field of the `Info` structure for this fairing. The
-    /// `Rocket` parameter corresponds to the running application.
+    /// `Rocket` parameter corresponds to the running application. The returned
+    /// future is cancelled if it does not resolve within the [grace period].
     ///
     /// [shutdown is triggered]: crate::config::Shutdown#triggers
+    /// [grace period]: crate::config::Shutdown#grace-period
     ///
     /// ## Default Implementation
     ///
     /// The default implementation of this method does nothing.
     async fn on_shutdown(&self, _rocket: &Rocket<Orbit>) { }
+
+    /// The config reload callback.
+    ///
+    /// See [Fairing Callbacks](#config-reload) for complete semantics.
+    ///
+    /// This method is called after the configuration of the running
+    /// application has been reloaded if `Kind::ConfigReload` is in the `kind`
+    /// field of the `Info` structure for this fairing. The `Rocket` parameter
+    /// corresponds to the running application.
+    ///
+    /// ## Default Implementation
+    ///
+    /// The default implementation of this method does nothing.
+    async fn on_config_reload(&self, _rocket: &Rocket<Orbit>) { }
 }
 
 #[crate::async_trait]
@@ -555,13 +727,32 @@ impl<T: Fairing> Fairing for std::sync::Arc<T> {
         (self as &T).on_request(req, data).await
     }
 
+    #[inline]
+    async fn on_filter<'r>(
+        &self,
+        req: &'r Request<'_>,
+        data: &mut Data<'r>
+    ) -> Option<response::Result<'r>> {
+        (self as &T).on_filter(req, data).await
+    }
+
     #[inline]
     async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
         (self as &T).on_response(req, res).await
     }
 
+    #[inline]
+    async fn on_error(&self, info: &ErrorInfo<'_>, req: &Request<'_>) {
+        (self as &T).on_error(info, req).await
+    }
+
     #[inline]
     async fn on_shutdown(&self, rocket: &Rocket<Orbit>) {
         (self as &T).on_shutdown(rocket).await
     }
+
+    #[inline]
+    async fn on_config_reload(&self, rocket: &Rocket<Orbit>) {
+        (self as &T).on_config_reload(rocket).await
+    }
 }
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use crate::{Request, Response, Data};
+use crate::http::Status;
+use crate::outcome::Outcome;
+
+/// Trait implemented by scoped fairings: request and response callbacks that
+/// run only for the routes they're attached to.
+///
+/// A `ScopedFairing` is attached to routes as they're mounted with
+/// [`Rocket::mount_with()`](crate::Rocket::mount_with()). Unlike a
+/// [`Fairing`](crate::fairing::Fairing), whose callbacks run for every request,
+/// the callbacks of a scoped fairing run only once a request has been routed
+/// to one of its routes:
+///
+///   * **Request (`on_request`)**
+///
+///     Called with the request and its data just before the route's handler.
+///     Returning `Success` runs the next scoped fairing or, if there are none,
+///     the handler. Returning `Failure(status)` fails the request with
+///     `status`, invoking the corresponding error catcher, while `Forward`
+///     forwards the request to the next matching route, exactly as if the
+///     handler had failed or forwarded. Request fairings that must rewrite the
+///     request's URI or method should be global fairings instead.
+///
+///   * **Response (`on_response`)**
+///
+///     Called with the response produced by the route's handler, if it
+///     succeeds. Error responses are produced by catchers, which aren't scoped
+///     to routes, and are thus not passed to scoped fairings.
+///
+/// Scoped fairings attached to a route run in the order in which they were
+/// attached, before any of the route's request guards. They are a natural fit
+/// for concerns that apply to a group of routes, such as authorization for an
+/// administrative interface or headers for an API, that would otherwise be
+/// implemented by a global fairing checking the request's path.
+///
+/// # Example
+///
+/// A scoped fairing that only allows requests with a valid API key to reach
+/// the routes mounted at `/admin`:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::{Request, Data};
+/// use rocket::fairing::ScopedFairing;
+/// use rocket::http::Status;
+/// use rocket::outcome::Outcome;
+///
+/// struct RequireAdmin;
+///
+/// #[rocket::async_trait]
+/// impl ScopedFairing for RequireAdmin {
+///     async fn on_request(&self, req: &Request<'_>, _: &mut Data<'_>) -> Outcome<(), Status, ()> {
+///         match req.headers().get_one("X-Api-Key") {
+///             Some(key) if key == "admin" => Outcome::Success(()),
+///             _ => Outcome::Failure(Status::Unauthorized),
+///         }
+///     }
+/// }
+///
+/// #[get("/stats")]
+/// fn stats() -> &'static str { "all good" }
+///
+/// #[get("/")]
+/// fn index() -> &'static str { "hello" }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .mount("/", routes![index])
+///         .mount_with("/admin", routes![stats], [RequireAdmin])
+/// }
+/// ```
+#[crate::async_trait]
+pub trait ScopedFairing: Send + Sync + 'static {
+    /// The request callback.
+    ///
+    /// Called just before the handler of a route this fairing is attached to.
+    /// See [the trait documentation](ScopedFairing) for the semantics of the
+    /// returned outcome.
+    ///
+    /// ## Default Implementation
+    ///
+    /// The default implementation of this method returns `Success`.
+    async fn on_request(
+        &self,
+        _req: &Request<'_>,
+        _data: &mut Data<'_>
+    ) -> Outcome<(), Status, ()> {
+        Outcome::Success(())
+    }
+
+    /// The response callback.
+    ///
+    /// Called with the response produced by the handler of a route this
+    /// fairing is attached to, if the handler succeeds.
+    ///
+    /// ## Default Implementation
+    ///
+    /// The default implementation of this method does nothing.
+    async fn on_response<'r>(&self, _req: &'r Request<'_>, _res: &mut Response<'r>) { }
+}
+
+#[crate::async_trait]
+impl<T: ScopedFairing + ?Sized> ScopedFairing for Arc<T> {
+    #[inline]
+    async fn on_request(&self, req: &Request<'_>, data: &mut Data<'_>) -> Outcome<(), Status, ()> {
+        (self as &T).on_request(req, data).await
+    }
+
+    #[inline]
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        (self as &T).on_response(req, res).await
+    }
+}
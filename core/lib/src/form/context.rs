@@ -1,6 +1,7 @@
 use serde::Serialize;
 use indexmap::{IndexMap, IndexSet};
 
+use crate::Request;
 use crate::form::prelude::*;
 use crate::http::Status;
 
@@ -351,6 +352,29 @@ impl<'v> Context<'v> {
     pub fn push_errors<E: Into<Errors<'v>>>(&mut self, errors: E) {
         errors.into().into_iter().for_each(|e| self.push_error(e))
     }
+
+    /// Localizes every error in the context with `catalog`. See
+    /// [`Error::localize()`] for details. The context's
+    /// [`status()`](Context::status()) is unaffected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::post;
+    /// # type T = String;
+    /// use rocket::form::{Form, Contextual};
+    /// use rocket::form::error::Messages;
+    ///
+    /// #[post("/submit", data = "<form>")]
+    /// fn submit(mut form: Form<Contextual<'_, T>>) {
+    ///     let catalog = Messages::new().message("missing", "fehlt");
+    ///     form.context.localize(&catalog);
+    /// }
+    /// ```
+    pub fn localize<C: Catalog + ?Sized>(&mut self, catalog: &C) {
+        self.errors.values_mut().for_each(|errors| errors.localize(catalog));
+        self.form_errors.localize(catalog);
+    }
 }
 
 impl<'f> From<Errors<'f>> for Context<'f> {
@@ -394,4 +418,15 @@ impl<'v, T: FromForm<'v>> FromForm<'v> for Contextual<'v, T> {
 
         Ok(Contextual { value, context })
     }
+
+    async fn validate_async(mut this: Self, req: &'v Request<'_>) -> Result<'v, Self> {
+        if let Some(value) = this.value.take() {
+            match T::validate_async(value, req).await {
+                Ok(value) => this.value = Some(value),
+                Err(errors) => this.context.push_errors(errors),
+            }
+        }
+
+        Ok(this)
+    }
 }
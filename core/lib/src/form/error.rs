@@ -5,6 +5,7 @@ use std::num::{ParseIntError, ParseFloatError};
 use std::str::{Utf8Error, ParseBoolError};
 use std::net::AddrParseError;
 use std::borrow::Cow;
+use std::collections::HashMap;
 
 use serde::{Serialize, ser::{Serializer, SerializeStruct}};
 
@@ -140,6 +141,8 @@ pub struct Error<'v> {
     pub kind: ErrorKind<'v>,
     /// The entitiy that caused the error.
     pub entity: Entity,
+    /// The localized message, if any, displayed in place of `kind`'s.
+    pub localized: Option<Cow<'v, str>>,
 }
 
 /// The kind of form error that occurred.
@@ -298,6 +301,38 @@ impl<'v> Errors<'v> {
         }
     }
 
+    /// Consumes `self` and returns a new `Errors` with each field name
+    /// prefixed by `parent`, or set to `parent` if it was not already set.
+    ///
+    /// This is used to name the errors returned by
+    /// [`FromForm::validate_async()`](crate::form::FromForm::validate_async())
+    /// for a nested value after the field that contains it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::form::error::{Errors, ErrorKind};
+    ///
+    /// let mut errors = Errors::from(ErrorKind::Missing).with_name("name");
+    /// errors.push(ErrorKind::Duplicate.into());
+    ///
+    /// let errors = errors.with_parent("friends.0");
+    /// assert_eq!(errors[0].name.as_ref().unwrap(), "friends.0.name");
+    /// assert_eq!(errors[1].name.as_ref().unwrap(), "friends.0");
+    /// ```
+    pub fn with_parent<P: fmt::Display>(mut self, parent: P) -> Self {
+        for error in self.iter_mut() {
+            let name = match error.name.take().map(|name| name.to_string()) {
+                Some(name) if !name.is_empty() => format!("{}.{}", parent, name),
+                _ => parent.to_string(),
+            };
+
+            error.name = Some(name.into());
+        }
+
+        self
+    }
+
     /// Consumes `self` and returns a new `Errors` with each field value set to
     /// `value` if it was not already set.
     ///
@@ -369,6 +404,26 @@ impl<'v> Errors<'v> {
         let max = self.iter().map(|e| e.status()).max();
         max.unwrap_or(Status::InternalServerError)
     }
+
+    /// Replaces the message of every error in `self` for which `catalog`
+    /// returns a message. See [`Error::localize()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::form::error::{Errors, ErrorKind, Messages};
+    ///
+    /// let catalog = Messages::new().message("missing", "fehlt");
+    ///
+    /// let mut errors = Errors::from(ErrorKind::Missing);
+    /// errors.push(ErrorKind::Duplicate.into());
+    /// errors.localize(&catalog);
+    /// assert_eq!(errors[0].to_string(), "fehlt");
+    /// assert_eq!(errors[1].to_string(), "duplicate");
+    /// ```
+    pub fn localize<C: Catalog + ?Sized>(&mut self, catalog: &C) {
+        self.iter_mut().for_each(|e| e.localize(catalog));
+    }
 }
 
 impl crate::http::ext::IntoOwned for Errors<'_> {
@@ -719,11 +774,49 @@ impl<'v> Error<'v> {
             InvalidLength { min: None, .. }
             | Multipart(FieldSizeExceeded { .. })
             | Multipart(StreamSizeExceeded { .. }) => Status::PayloadTooLarge,
+            Multipart(StreamReadFailed(ref e))
+                if matches!(e.downcast_ref(), Some(StreamSizeExceeded { .. })) => {
+                Status::PayloadTooLarge
+            }
             Unknown => Status::InternalServerError,
             Io(_) | _ if self.entity == Entity::Form => Status::BadRequest,
             _ => Status::UnprocessableEntity
         }
     }
+
+    /// Replaces the message of `self` with the one returned by `catalog`, if
+    /// any, leaving `self` untouched otherwise.
+    ///
+    /// The message is stored in [`Error::localized`] and is used when `self`
+    /// is displayed or serialized. The error's `kind`, and thus its
+    /// [`ErrorKind::key()`] and [`Error::status()`], are unaffected, so a
+    /// localized error can be localized again with another catalog.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::form::error::{Error, Messages};
+    /// use rocket::http::Status;
+    ///
+    /// let catalog = Messages::new()
+    ///     .message("invalid_length.max", "darf höchstens {max} lang sein");
+    ///
+    /// let mut error = Error::from((None, Some(10u64))).with_name("name");
+    /// error.localize(&catalog);
+    /// assert_eq!(error.to_string(), "darf höchstens 10 lang sein");
+    /// assert_eq!(error.key(), "invalid_length.max");
+    /// assert_eq!(error.status(), Status::PayloadTooLarge);
+    ///
+    /// // Closures returning `Option<String>` are also catalogs.
+    /// let mut error = Error::validation("bad `foo`");
+    /// error.localize(&|e: &Error<'_>| Some(format!("[{}]", e.key())));
+    /// assert_eq!(error.to_string(), "[validation]");
+    /// ```
+    pub fn localize<C: Catalog + ?Sized>(&mut self, catalog: &C) {
+        if let Some(msg) = catalog.message(self) {
+            self.localized = Some(msg.into());
+        }
+    }
 }
 
 impl<'v> Serialize for Error<'v> {
@@ -746,6 +839,7 @@ impl crate::http::ext::IntoOwned for Error<'_> {
             value: self.value.into_owned(),
             kind: self.kind.into_owned(),
             entity: self.entity,
+            localized: self.localized.into_owned(),
         }
     }
 }
@@ -760,7 +854,10 @@ impl<'v> std::ops::Deref for Error<'v> {
 
 impl fmt::Display for Error<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.kind.fmt(f)
+        match self.localized {
+            Some(ref msg) => msg.fmt(f),
+            None => self.kind.fmt(f),
+        }
     }
 }
 
@@ -769,7 +866,7 @@ impl<'v, T: Into<ErrorKind<'v>>> From<T> for Error<'v> {
     fn from(k: T) -> Self {
         let kind = k.into();
         let entity = Entity::default_for(&kind);
-        Error { name: None, value: None, kind, entity }
+        Error { name: None, value: None, kind, entity, localized: None }
     }
 }
 
@@ -800,6 +897,147 @@ impl<'a> From<multer::Error> for Error<'a> {
     }
 }
 
+impl ErrorKind<'_> {
+    /// Returns a stable key identifying this kind of error and, for errors with
+    /// bounds, which bounds are set. Keys are intended to be used to look up
+    /// messages in a [`Catalog`]. The keys are:
+    ///
+    /// | key                         | kind                                 | parameters              |
+    /// |-----------------------------|--------------------------------------|-------------------------|
+    /// | `invalid_length.incomplete` | `InvalidLength` without bounds       |                         |
+    /// | `invalid_length.empty`      | `InvalidLength` with `min` of `1`    |                         |
+    /// | `invalid_length.min`        | `InvalidLength` with only `min`      | `min`                   |
+    /// | `invalid_length.max`        | `InvalidLength` with only `max`      | `max`                   |
+    /// | `invalid_length.range`      | `InvalidLength` with `min` and `max` | `min`, `max`            |
+    /// | `invalid_choice`            | `InvalidChoice`                      | `choices`               |
+    /// | `out_of_range`              | `OutOfRange` without bounds          |                         |
+    /// | `out_of_range.start`        | `OutOfRange` with only `start`       | `start`                 |
+    /// | `out_of_range.end`          | `OutOfRange` with only `end`         | `end`                   |
+    /// | `out_of_range.range`        | `OutOfRange` with `start` and `end`  | `start`, `end`          |
+    /// | `validation`                | `Validation`                         | `msg`                   |
+    /// | `duplicate`                 | `Duplicate`                          |                         |
+    /// | `missing`                   | `Missing`                            |                         |
+    /// | `unexpected`                | `Unexpected`                         |                         |
+    /// | `unknown`                   | `Unknown`                            |                         |
+    /// | `custom`                    | `Custom`                             | `error`                 |
+    /// | `multipart`                 | `Multipart`                          | `error`                 |
+    /// | `utf8`                      | `Utf8`                               | `error`                 |
+    /// | `int`                       | `Int`                                | `error`                 |
+    /// | `bool`                      | `Bool`                               | `error`                 |
+    /// | `float`                     | `Float`                              | `error`                 |
+    /// | `addr`                      | `Addr`                               | `error`                 |
+    /// | `io`                        | `Io`                                 | `error`                 |
+    ///
+    /// See [`ErrorKind::params()`] for the values of the parameters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::form::error::ErrorKind;
+    ///
+    /// assert_eq!(ErrorKind::Missing.key(), "missing");
+    /// assert_eq!(ErrorKind::from((Some(1u64), None)).key(), "invalid_length.empty");
+    /// assert_eq!(ErrorKind::from((Some(3u64), Some(5u64))).key(), "invalid_length.range");
+    /// assert_eq!(ErrorKind::from((None, Some(10isize))).key(), "out_of_range.end");
+    /// ```
+    pub fn key(&self) -> &'static str {
+        match self {
+            ErrorKind::InvalidLength { min, max } => match (min, max) {
+                (None, None) => "invalid_length.incomplete",
+                (Some(1), None) => "invalid_length.empty",
+                (Some(_), None) => "invalid_length.min",
+                (None, Some(_)) => "invalid_length.max",
+                (Some(_), Some(_)) => "invalid_length.range",
+            },
+            ErrorKind::InvalidChoice { .. } => "invalid_choice",
+            ErrorKind::OutOfRange { start, end } => match (start, end) {
+                (None, None) => "out_of_range",
+                (Some(_), None) => "out_of_range.start",
+                (None, Some(_)) => "out_of_range.end",
+                (Some(_), Some(_)) => "out_of_range.range",
+            },
+            ErrorKind::Validation(_) => "validation",
+            ErrorKind::Duplicate => "duplicate",
+            ErrorKind::Missing => "missing",
+            ErrorKind::Unexpected => "unexpected",
+            ErrorKind::Unknown => "unknown",
+            ErrorKind::Custom(_) => "custom",
+            ErrorKind::Multipart(_) => "multipart",
+            ErrorKind::Utf8(_) => "utf8",
+            ErrorKind::Int(_) => "int",
+            ErrorKind::Bool(_) => "bool",
+            ErrorKind::Float(_) => "float",
+            ErrorKind::Addr(_) => "addr",
+            ErrorKind::Io(_) => "io",
+        }
+    }
+
+    /// Returns the named parameters of this error as listed in
+    /// [`ErrorKind::key()`], rendered as strings.
+    ///
+    /// Lengths of `1024` or more are rendered as byte units, as in `"2MiB"`.
+    /// Choices are rendered as a comma-separated list. Wrapped errors are
+    /// rendered via their `Display` implementation.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::form::error::ErrorKind;
+    /// use rocket::data::ToByteUnit;
+    ///
+    /// let kind = ErrorKind::from((Some(3u64), Some(2.mebibytes().as_u64())));
+    /// assert_eq!(kind.params(), vec![("min", "3".into()), ("max", "2MiB".into())]);
+    ///
+    /// let kind = ErrorKind::from(vec!["a".into(), "b".into()]);
+    /// assert_eq!(kind.params(), vec![("choices", "a, b".into())]);
+    ///
+    /// assert!(ErrorKind::Missing.params().is_empty());
+    /// ```
+    pub fn params(&self) -> Vec<(&'static str, String)> {
+        fn len(k: u64) -> String {
+            match k {
+                k if k < 1024 => k.to_string(),
+                k => ByteUnit::from(k).to_string(),
+            }
+        }
+
+        let mut params = vec![];
+        match self {
+            ErrorKind::InvalidLength { min, max } => {
+                match (min, max) {
+                    (Some(1), None) => {},
+                    _ => params.extend(min.map(|min| ("min", len(min)))),
+                }
+
+                params.extend(max.map(|max| ("max", len(max))));
+            }
+            ErrorKind::InvalidChoice { choices } => {
+                let choices = choices.iter().map(|c| c.as_ref()).collect::<Vec<_>>();
+                params.push(("choices", choices.join(", ")));
+            }
+            ErrorKind::OutOfRange { start, end } => {
+                params.extend(start.map(|start| ("start", start.to_string())));
+                params.extend(end.map(|end| ("end", end.to_string())));
+            }
+            ErrorKind::Validation(msg) => params.push(("msg", msg.to_string())),
+            ErrorKind::Duplicate
+            | ErrorKind::Missing
+            | ErrorKind::Unexpected
+            | ErrorKind::Unknown => {},
+            ErrorKind::Custom(e) => params.push(("error", e.to_string())),
+            ErrorKind::Multipart(e) => params.push(("error", e.to_string())),
+            ErrorKind::Utf8(e) => params.push(("error", e.to_string())),
+            ErrorKind::Int(e) => params.push(("error", e.to_string())),
+            ErrorKind::Bool(e) => params.push(("error", e.to_string())),
+            ErrorKind::Float(e) => params.push(("error", e.to_string())),
+            ErrorKind::Addr(e) => params.push(("error", e.to_string())),
+            ErrorKind::Io(e) => params.push(("error", e.to_string())),
+        }
+
+        params
+    }
+}
+
 impl fmt::Display for ErrorKind<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -1023,3 +1261,152 @@ impl Entity {
         }
     }
 }
+
+/// A source of form error messages, used to localize or customize messages.
+///
+/// A `Catalog` is consulted by [`Error::localize()`], [`Errors::localize()`],
+/// and [`Context::localize()`](crate::form::Context::localize()). It receives
+/// an [`Error`] and returns the message to use in its place or `None` to keep
+/// the error as is. Messages are typically chosen based on the error's
+/// [`key()`](ErrorKind::key()) and [`params()`](ErrorKind::params()), both of
+/// which are accessible through `Error`'s `Deref` to [`ErrorKind`].
+///
+/// Any `Fn(&Error<'_>) -> Option<String>` is a `Catalog`. [`Messages`] is a
+/// `Catalog` of message templates keyed by error key.
+///
+/// # Example
+///
+/// Selecting a catalog based on the request's language before rendering a form
+/// context:
+///
+/// ```rust
+/// # use rocket::post;
+/// # type T = String;
+/// use rocket::form::{Form, Contextual};
+/// use rocket::form::error::Messages;
+/// use rocket::http::Status;
+/// use rocket::request::Request;
+/// use rocket::State;
+///
+/// struct Catalogs { de: Messages }
+///
+/// #[post("/submit", data = "<form>")]
+/// fn submit(mut form: Form<Contextual<'_, T>>, catalogs: &State<Catalogs>) -> Status {
+///     // Determine the language, perhaps via `Accept-Language`, and localize.
+///     form.context.localize(&catalogs.de);
+///
+///     /* render a template with `form.context` */
+///     form.context.status()
+/// }
+/// ```
+pub trait Catalog {
+    /// Returns the message for `error`, or `None` to leave `error` unchanged.
+    fn message(&self, error: &Error<'_>) -> Option<String>;
+}
+
+impl<F: Fn(&Error<'_>) -> Option<String>> Catalog for F {
+    fn message(&self, error: &Error<'_>) -> Option<String> {
+        self(error)
+    }
+}
+
+/// A [`Catalog`] of message templates keyed by [`ErrorKind::key()`].
+///
+/// A template may refer to any of the error's [`params()`](ErrorKind::params())
+/// as well as to the error's field `name` by enclosing the parameter's name in
+/// braces, as in `{max}`. References to unknown parameters are left as is.
+/// Errors whose key has no template are left unchanged.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::form::error::{Error, ErrorKind, Messages};
+///
+/// let catalog = Messages::new()
+///     .message("missing", "{name} ist erforderlich")
+///     .message("out_of_range.range", "muss zwischen {start} und {end} liegen");
+///
+/// let mut error = Error::from(ErrorKind::Missing).with_name("age");
+/// error.localize(&catalog);
+/// assert_eq!(error.to_string(), "age ist erforderlich");
+///
+/// let mut error = Error::from((Some(18isize), Some(150isize)));
+/// error.localize(&catalog);
+/// assert_eq!(error.to_string(), "muss zwischen 18 und 150 liegen");
+///
+/// let mut error = Error::from(ErrorKind::Duplicate);
+/// error.localize(&catalog);
+/// assert_eq!(error.to_string(), "duplicate");
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Messages {
+    templates: HashMap<Cow<'static, str>, Cow<'static, str>>,
+}
+
+impl Messages {
+    /// Creates an empty catalog of messages.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::form::error::Messages;
+    ///
+    /// let catalog = Messages::new();
+    /// ```
+    pub fn new() -> Self {
+        Messages::default()
+    }
+
+    /// Sets the template for errors with key `key` to `template`, replacing
+    /// any existing template for `key`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::form::error::Messages;
+    ///
+    /// let catalog = Messages::new()
+    ///     .message("invalid_length.empty", "darf nicht leer sein")
+    ///     .message("invalid_length.max", "darf höchstens {max} lang sein");
+    /// ```
+    pub fn message<K, T>(mut self, key: K, template: T) -> Self
+        where K: Into<Cow<'static, str>>, T: Into<Cow<'static, str>>
+    {
+        self.templates.insert(key.into(), template.into());
+        self
+    }
+}
+
+impl Catalog for Messages {
+    fn message(&self, error: &Error<'_>) -> Option<String> {
+        let mut template = self.templates.get(error.key())?.as_ref();
+        let mut params = error.params();
+        if let Some(ref name) = error.name {
+            params.push(("name", name.to_string()));
+        }
+
+        let mut msg = String::with_capacity(template.len());
+        while let Some(i) = template.find('{') {
+            msg.push_str(&template[..i]);
+            template = &template[i..];
+            let param = template.find('}').and_then(|j| {
+                let name = &template[1..j];
+                params.iter().find(|(k, _)| *k == name).map(|(_, value)| (j, value))
+            });
+
+            match param {
+                Some((j, value)) => {
+                    msg.push_str(value);
+                    template = &template[(j + 1)..];
+                }
+                None => {
+                    msg.push('{');
+                    template = &template[1..];
+                }
+            }
+        }
+
+        msg.push_str(template);
+        Some(msg)
+    }
+}
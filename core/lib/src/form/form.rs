@@ -312,7 +312,12 @@ impl<'r, T: FromForm<'r>> FromData<'r> for Form<T> {
             }
         }
 
-        match T::finalize(context) {
+        let value = match T::finalize(context) {
+            Ok(value) => T::validate_async(value, req).await,
+            Err(e) => Err(e),
+        };
+
+        match value {
             Ok(value) => Outcome::Success(Form(value)),
             Err(e) => Outcome::Failure((e.status(), e)),
         }
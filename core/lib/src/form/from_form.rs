@@ -6,6 +6,7 @@ use std::sync::Arc;
 use either::Either;
 use indexmap::IndexMap;
 
+use crate::Request;
 use crate::form::prelude::*;
 use crate::http::uncased::AsUncased;
 
@@ -542,6 +543,29 @@ pub trait FromForm<'r>: Send + Sized {
     /// collection of [`Errors`] otherwise.
     fn finalize(ctxt: Self::Context) -> Result<'r, Self>;
 
+    /// Asynchronously validates the finalized `value`, with access to the
+    /// incoming request `req`. Returns the value when validation succeeds or
+    /// a collection of [`Errors`] otherwise.
+    ///
+    /// This method is called by the [`Form`] data guard and for query guards
+    /// after `finalize()` succeeds. It is _not_ called by [`Form::parse()`] and
+    /// friends. The `FromForm` derive implements this method by running the
+    /// fields' own `validate_async()` followed by their `validate_async`
+    /// validators; see the [`FromForm` derive] for details. Rocket's container
+    /// implementations, such as those for `Vec<T>`, `Option<T>`, and maps,
+    /// forward to their items' implementations. Errors are named relative to
+    /// `value`: implementations for containers prefix the names of their
+    /// items' errors with the item's index or field name via
+    /// [`Errors::with_parent()`]. Errors from map entries are not prefixed as
+    /// the entries' form keys are no longer known.
+    ///
+    /// The default implementation returns `Ok(value)`.
+    ///
+    /// [`FromForm` derive]: derive@crate::FromForm
+    async fn validate_async(value: Self, _req: &'r Request<'_>) -> Result<'r, Self> {
+        Ok(value)
+    }
+
     /// Returns a default value, if any, to use when a value is desired and
     /// parsing fails.
     ///
@@ -626,6 +650,21 @@ impl<'v, T: FromForm<'v> + 'v> FromForm<'v> for Vec<T> {
             Ok(this.items)
         }
     }
+
+    async fn validate_async(items: Self, req: &'v Request<'_>) -> Result<'v, Self> {
+        let (mut valid, mut errors) = (Vec::with_capacity(items.len()), Errors::new());
+        for (i, item) in items.into_iter().enumerate() {
+            match T::validate_async(item, req).await {
+                Ok(item) => valid.push(item),
+                Err(e) => errors.extend(e.with_parent(i)),
+            }
+        }
+
+        match errors.is_empty() {
+            true => Ok(valid),
+            false => Err(errors),
+        }
+    }
 }
 
 #[doc(hidden)]
@@ -725,6 +764,26 @@ impl<'v, K, V> MapContext<'v, K, V>
         }
     }
 
+    async fn validate_async<T, I>(entries: I, req: &'v Request<'_>) -> Result<'v, T>
+        where T: std::iter::FromIterator<(K, V)>, I: IntoIterator<Item = (K, V)>
+    {
+        let (mut valid, mut errors) = (vec![], Errors::new());
+        for (key, value) in entries {
+            match (K::validate_async(key, req).await, V::validate_async(value, req).await) {
+                (Ok(key), Ok(value)) => valid.push((key, value)),
+                (key, value) => {
+                    if let Err(e) = key { errors.extend(e); }
+                    if let Err(e) = value { errors.extend(e); }
+                }
+            }
+        }
+
+        match errors.is_empty() {
+            true => Ok(valid.into_iter().collect()),
+            false => Err(errors),
+        }
+    }
+
     fn finalize<T: std::iter::FromIterator<(K, V)>>(mut self) -> Result<'v, T> {
         let map: T = self.entries.into_iter()
             .zip(self.metadata.iter())
@@ -778,6 +837,10 @@ impl<'v, K, V> FromForm<'v> for HashMap<K, V>
     fn finalize(this: Self::Context) -> Result<'v, Self> {
         this.finalize()
     }
+
+    async fn validate_async(this: Self, req: &'v Request<'_>) -> Result<'v, Self> {
+        MapContext::validate_async(this, req).await
+    }
 }
 
 #[crate::async_trait]
@@ -801,6 +864,10 @@ impl<'v, K, V> FromForm<'v> for BTreeMap<K, V>
     fn finalize(this: Self::Context) -> Result<'v, Self> {
         this.finalize()
     }
+
+    async fn validate_async(this: Self, req: &'v Request<'_>) -> Result<'v, Self> {
+        MapContext::validate_async(this, req).await
+    }
 }
 
 #[crate::async_trait]
@@ -822,6 +889,13 @@ impl<'v, T: FromForm<'v>> FromForm<'v> for Option<T> {
     fn finalize(this: Self::Context) -> Result<'v, Self> {
         Ok(T::finalize(this).ok())
     }
+
+    async fn validate_async(this: Self, req: &'v Request<'_>) -> Result<'v, Self> {
+        match this {
+            Some(value) => Ok(T::validate_async(value, req).await.ok()),
+            None => Ok(None),
+        }
+    }
 }
 
 #[crate::async_trait]
@@ -843,6 +917,13 @@ impl<'v, T: FromForm<'v>> FromForm<'v> for Result<'v, T> {
     fn finalize(this: Self::Context) -> Result<'v, Self> {
         Ok(T::finalize(this))
     }
+
+    async fn validate_async(this: Self, req: &'v Request<'_>) -> Result<'v, Self> {
+        match this {
+            Ok(value) => Ok(T::validate_async(value, req).await),
+            Err(e) => Ok(Err(e)),
+        }
+    }
 }
 
 #[doc(hidden)]
@@ -906,6 +987,18 @@ impl<'v, A: FromForm<'v>, B: FromForm<'v>> FromForm<'v> for (A, B) {
             }
         }
     }
+
+    async fn validate_async((a, b): Self, req: &'v Request<'_>) -> Result<'v, Self> {
+        match (A::validate_async(a, req).await, B::validate_async(b, req).await) {
+            (Ok(a), Ok(b)) => Ok((a, b)),
+            (a, b) => {
+                let mut errors = Errors::new();
+                if let Err(e) = a { errors.extend(e.with_parent(0)); }
+                if let Err(e) = b { errors.extend(e.with_parent(1)); }
+                Err(errors)
+            }
+        }
+    }
 }
 
 #[crate::async_trait]
@@ -927,4 +1020,11 @@ impl<'v, T: FromForm<'v> + Sync> FromForm<'v> for Arc<T> {
     fn finalize(this: Self::Context) -> Result<'v, Self> {
         T::finalize(this).map(Arc::new)
     }
+
+    async fn validate_async(this: Self, req: &'v Request<'_>) -> Result<'v, Self> {
+        match Arc::try_unwrap(this) {
+            Ok(value) => T::validate_async(value, req).await.map(Arc::new),
+            Err(this) => Ok(this),
+        }
+    }
 }
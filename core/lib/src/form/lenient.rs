@@ -1,5 +1,6 @@
 use std::ops::{Deref, DerefMut};
 
+use crate::Request;
 use crate::form::prelude::*;
 use crate::http::uri::fmt::{Query, FromUriParam};
 
@@ -96,6 +97,11 @@ impl<'v, T: FromForm<'v>> FromForm<'v> for Lenient<T> {
     fn finalize(this: Self::Context) -> Result<'v, Self> {
         T::finalize(this).map(Self)
     }
+
+    #[inline(always)]
+    async fn validate_async(this: Self, req: &'v Request<'_>) -> Result<'v, Self> {
+        T::validate_async(this.0, req).await.map(Self)
+    }
 }
 
 impl<T> Deref for Lenient<T> {
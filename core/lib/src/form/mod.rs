@@ -368,6 +368,7 @@ mod context;
 mod strict;
 mod lenient;
 mod parser;
+mod multipart;
 mod buffer;
 pub mod validate;
 pub mod name;
@@ -396,6 +397,7 @@ pub use form::*;
 pub use context::*;
 pub use strict::*;
 pub use lenient::*;
+pub use multipart::*;
 
 #[doc(hidden)]
 pub mod prelude {
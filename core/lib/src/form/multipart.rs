@@ -0,0 +1,433 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use tokio::fs::File;
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::task;
+use tempfile::{NamedTempFile, TempPath};
+use either::Either;
+use bytes::Bytes;
+use multer::{Constraints, SizeLimit};
+
+use crate::request::{Request, local_cache_once};
+use crate::data::{ByteUnit, Data, FromData, Limits, Outcome};
+use crate::form::{SharedStack, Error, Errors};
+use crate::fs::{FileName, TempFile};
+use crate::http::ContentType;
+
+/// A data guard that streams the fields of a `multipart/form-data` body.
+///
+/// Unlike [`Form`](crate::form::Form), which parses an entire form into a
+/// structure before the handler runs, `Multipart` yields fields one at a time,
+/// in the order they arrive, in one of two ways:
+///
+///   * [`Multipart::next_field()`] reads each field in full before returning
+///     it. Non-file fields are buffered in memory. File fields, those with a
+///     file name or a `Content-Type`, are buffered in memory until they exceed
+///     the [memory threshold], at which point they are spilled to a
+///     [`TempFile`] in the configured `temp_dir`.
+///   * [`Multipart::next_stream()`] returns each field as soon as its headers
+///     arrive, as a [`MultipartStream`] whose data is read chunk by chunk, so
+///     a file can be processed or forwarded as it's received.
+///
+/// [memory threshold]: Multipart::memory_threshold()
+///
+/// The guard forwards if the request's `Content-Type` isn't
+/// `multipart/form-data`.
+///
+/// # Limits
+///
+/// The entire body is limited by the `multipart` limit, 32MiB by default.
+/// Individual fields are limited as the corresponding
+/// [`Form`](crate::form::Form) fields would be: file fields by the `file/$ext`
+/// limit, where `$ext` is the extension of the field's `Content-Type`, or the
+/// `file` limit if there is none, and non-file fields by the `form` limit. A
+/// body or field exceeding its limit results in an error with a status of `413
+/// Payload Too Large`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::data::ToByteUnit;
+/// use rocket::form::{Multipart, MultipartData};
+/// use rocket::http::Status;
+///
+/// #[post("/upload", data = "<parts>")]
+/// async fn upload(parts: Multipart<'_>) -> Result<String, Status> {
+///     let mut parts = parts.memory_threshold(1.mebibytes());
+///     let (mut buffered, mut spilled) = (0, 0);
+///     while let Some(field) = parts.next_field().await.map_err(|e| e.status())? {
+///         match field.into_data() {
+///             MultipartData::Buffered(bytes) => buffered += bytes.len(),
+///             MultipartData::File(mut file) => {
+///                 let path = format!("/tmp/upload-{}", spilled);
+///                 file.persist_to(path).await.map_err(|_| Status::InternalServerError)?;
+///                 spilled += 1;
+///             }
+///         }
+///     }
+///
+///     Ok(format!("{} bytes in memory, {} files on disk", buffered, spilled))
+/// }
+/// ```
+pub struct Multipart<'r> {
+    source: multer::Multipart<'r>,
+    buffer: &'r SharedStack<String>,
+    limits: &'r Limits,
+    temp_dir: PathBuf,
+    threshold: ByteUnit,
+    done: bool,
+}
+
+/// A field yielded by [`Multipart::next_field()`].
+#[derive(Debug)]
+pub struct MultipartField<'r> {
+    name: &'r str,
+    file_name: Option<&'r FileName>,
+    content_type: Option<ContentType>,
+    data: MultipartData<'r>,
+}
+
+/// A field yielded by [`Multipart::next_stream()`] whose data is read
+/// incrementally via [`MultipartStream::chunk()`].
+///
+/// The stream borrows the [`Multipart`] it was yielded by, so it must be
+/// dropped before the next field is retrieved. Any unread data is then
+/// skipped.
+pub struct MultipartStream<'a, 'r> {
+    parts: &'a mut Multipart<'r>,
+    field: multer::Field<'r>,
+    name: &'r str,
+    file_name: Option<&'r FileName>,
+    content_type: Option<ContentType>,
+    limit: ByteUnit,
+    len: u64,
+    chunk: Bytes,
+}
+
+/// The data of a [`MultipartField`].
+#[derive(Debug)]
+pub enum MultipartData<'r> {
+    /// The field's data, buffered in memory.
+    Buffered(Vec<u8>),
+    /// A file field whose data exceeded the memory threshold, spilled to disk.
+    File(Box<TempFile<'r>>),
+}
+
+impl<'r> Multipart<'r> {
+    /// The default memory threshold: 64KiB.
+    pub const DEFAULT_MEMORY_THRESHOLD: ByteUnit = ByteUnit::Kibibyte(64);
+
+    /// Sets the size past which file fields are spilled to disk. The default
+    /// is [`Multipart::DEFAULT_MEMORY_THRESHOLD`]. A threshold of `0` spills
+    /// every non-empty file field.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::data::ToByteUnit;
+    /// use rocket::form::Multipart;
+    ///
+    /// #[post("/upload", data = "<parts>")]
+    /// fn upload(parts: Multipart<'_>) {
+    ///     let parts = parts.memory_threshold(512.kibibytes());
+    ///     /* ... */
+    /// }
+    /// ```
+    pub fn memory_threshold(mut self, threshold: ByteUnit) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Reads and returns the next field. Returns `Ok(None)` when there are no
+    /// further fields. Once an error is returned, no further fields are read,
+    /// and `Ok(None)` is returned from subsequent calls.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::form::Multipart;
+    /// use rocket::http::Status;
+    ///
+    /// #[post("/upload", data = "<parts>")]
+    /// async fn upload(mut parts: Multipart<'_>) -> Result<String, Status> {
+    ///     let mut names = vec![];
+    ///     while let Some(field) = parts.next_field().await.map_err(|e| e.status())? {
+    ///         names.push(field.name().to_string());
+    ///     }
+    ///
+    ///     Ok(names.join(", "))
+    /// }
+    /// ```
+    pub async fn next_field(&mut self) -> Result<Option<MultipartField<'r>>, Error<'r>> {
+        match self.next_stream().await? {
+            Some(stream) => stream.collect().await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the next field once its headers have been read, without reading
+    /// its data. Returns `Ok(None)` when there are no further fields. Once an
+    /// error is returned, here or while reading a field's data, no further
+    /// fields are read, and `Ok(None)` is returned from subsequent calls.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::form::Multipart;
+    /// use rocket::http::Status;
+    ///
+    /// #[post("/upload", data = "<parts>")]
+    /// async fn upload(mut parts: Multipart<'_>) -> Result<String, Status> {
+    ///     let mut checksum = 0u8;
+    ///     while let Some(mut field) = parts.next_stream().await.map_err(|e| e.status())? {
+    ///         while let Some(chunk) = field.chunk().await.map_err(|e| e.status())? {
+    ///             checksum = chunk.iter().fold(checksum, |sum, b| sum.wrapping_add(*b));
+    ///         }
+    ///     }
+    ///
+    ///     Ok(checksum.to_string())
+    /// }
+    /// ```
+    pub async fn next_stream(&mut self) -> Result<Option<MultipartStream<'_, 'r>>, Error<'r>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let field = match self.source.next_field().await {
+            Ok(Some(field)) => field,
+            Ok(None) => {
+                self.done = true;
+                return Ok(None);
+            }
+            Err(e) => {
+                self.done = true;
+                return Err(e.into());
+            }
+        };
+
+        trace_!("streaming multipart field: {:?}", field);
+        let name = self.buffer.push(field.name().unwrap_or(""));
+        let file_name = field.file_name().map(|n| FileName::new(self.buffer.push(n)));
+        let content_type: Option<ContentType> = field.content_type()
+            .and_then(|m| m.as_ref().parse().ok());
+
+        let limit = match file_name.is_some() || content_type.is_some() {
+            true => content_type.as_ref()
+                .and_then(|ct| ct.extension())
+                .and_then(|ext| self.limits.find(["file", ext.as_str()]))
+                .or_else(|| self.limits.get("file"))
+                .unwrap_or(Limits::FILE),
+            false => self.limits.get("form").unwrap_or(Limits::FORM),
+        };
+
+        Ok(Some(MultipartStream {
+            parts: self,
+            field,
+            name,
+            file_name,
+            content_type,
+            limit,
+            len: 0,
+            chunk: Bytes::new(),
+        }))
+    }
+}
+
+impl<'r> MultipartStream<'_, 'r> {
+    /// The name of the field, or `""` if it has none.
+    pub fn name(&self) -> &'r str {
+        self.name
+    }
+
+    /// The file name of the field, if it has one.
+    pub fn file_name(&self) -> Option<&'r FileName> {
+        self.file_name
+    }
+
+    /// The `Content-Type` of the field, if it has a valid one.
+    pub fn content_type(&self) -> Option<&ContentType> {
+        self.content_type.as_ref()
+    }
+
+    /// Returns `true` if the field has a file name or a `Content-Type`.
+    pub fn is_file(&self) -> bool {
+        self.file_name.is_some() || self.content_type.is_some()
+    }
+
+    /// Reads and returns the next chunk of the field's data. Returns
+    /// `Ok(None)` once all of the data has been read. Fails with a status of
+    /// `413 Payload Too Large` if the field exceeds its
+    /// [limit](Multipart#limits).
+    pub async fn chunk(&mut self) -> Result<Option<&[u8]>, Error<'r>> {
+        match self.field.chunk().await {
+            Ok(Some(chunk)) => {
+                self.len += chunk.len() as u64;
+                if self.len > self.limit {
+                    self.parts.done = true;
+                    return Err(Error::from((None, Some(self.limit))).with_name(self.name));
+                }
+
+                self.chunk = chunk;
+                Ok(Some(&self.chunk))
+            }
+            Ok(None) => Ok(None),
+            Err(e) => {
+                self.parts.done = true;
+                Err(Error::from(e).with_name(self.name))
+            }
+        }
+    }
+
+    /// Reads the field's data in full, spilling file fields to disk past the
+    /// memory threshold.
+    async fn collect(mut self) -> Result<MultipartField<'r>, Error<'r>> {
+        let data = self.read_data().await;
+        if data.is_err() {
+            self.parts.done = true;
+        }
+
+        let (name, file_name, content_type) = (self.name, self.file_name, self.content_type);
+        Ok(MultipartField { name, file_name, content_type, data: data? })
+    }
+
+    async fn read_data(&mut self) -> Result<MultipartData<'r>, Error<'r>> {
+        let (is_file, threshold) = (self.is_file(), self.parts.threshold);
+        let temp_dir = self.parts.temp_dir.clone();
+        let (mut buf, mut len) = (vec![], 0u64);
+        let mut file: Option<(BufWriter<File>, TempPath)> = None;
+        while let Some(chunk) = self.chunk().await? {
+            len += chunk.len() as u64;
+            match file {
+                Some((ref mut writer, _)) => writer.write_all(chunk).await?,
+                None if is_file && len > threshold => {
+                    let (mut writer, path) = spill(&temp_dir).await?;
+                    writer.write_all(&buf).await?;
+                    writer.write_all(chunk).await?;
+                    buf = vec![];
+                    file = Some((writer, path));
+                }
+                None => buf.extend_from_slice(chunk),
+            }
+        }
+
+        Ok(match file {
+            Some((mut writer, path)) => {
+                writer.flush().await?;
+                MultipartData::File(Box::new(TempFile::File {
+                    file_name: self.file_name,
+                    content_type: self.content_type.clone(),
+                    path: Either::Left(path),
+                    len,
+                }))
+            }
+            None => MultipartData::Buffered(buf),
+        })
+    }
+}
+
+impl fmt::Debug for MultipartStream<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultipartStream")
+            .field("name", &self.name)
+            .field("file_name", &self.file_name)
+            .field("content_type", &self.content_type)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+async fn spill(temp_dir: &Path) -> io::Result<(BufWriter<File>, TempPath)> {
+    let temp_dir = temp_dir.to_path_buf();
+    let file = task::spawn_blocking(move || NamedTempFile::new_in(temp_dir)).await;
+    let file = file.map_err(|_| io::Error::new(io::ErrorKind::Other, "spawn_block panic"))??;
+    let (file, path) = file.into_parts();
+    Ok((BufWriter::new(File::from_std(file)), path))
+}
+
+impl<'r> MultipartField<'r> {
+    /// The name of the field, or `""` if it has none.
+    pub fn name(&self) -> &'r str {
+        self.name
+    }
+
+    /// The file name of the field, if it has one.
+    pub fn file_name(&self) -> Option<&'r FileName> {
+        self.file_name
+    }
+
+    /// The `Content-Type` of the field, if it has a valid one.
+    pub fn content_type(&self) -> Option<&ContentType> {
+        self.content_type.as_ref()
+    }
+
+    /// Returns `true` if the field has a file name or a `Content-Type`.
+    ///
+    /// Only file fields are spilled to disk.
+    pub fn is_file(&self) -> bool {
+        self.file_name.is_some() || self.content_type.is_some()
+    }
+
+    /// A reference to the field's data.
+    pub fn data(&self) -> &MultipartData<'r> {
+        &self.data
+    }
+
+    /// Consumes `self` and returns the field's data.
+    pub fn into_data(self) -> MultipartData<'r> {
+        self.data
+    }
+}
+
+impl MultipartData<'_> {
+    /// The length of the data in bytes.
+    pub fn len(&self) -> u64 {
+        match self {
+            MultipartData::Buffered(bytes) => bytes.len() as u64,
+            MultipartData::File(file) => file.len(),
+        }
+    }
+
+    /// Returns `true` if the data is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromData<'r> for Multipart<'r> {
+    type Error = Errors<'r>;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+        if !req.content_type().map_or(false, |ct| ct.is_form_data()) {
+            return Outcome::Forward(data);
+        }
+
+        let boundary = match req.content_type().and_then(|ct| ct.param("boundary")) {
+            Some(boundary) => boundary,
+            None => {
+                let error = Error::from(multer::Error::NoBoundary);
+                return Outcome::Failure((error.status(), error.into()));
+            }
+        };
+
+        let limit = req.limits().get("multipart").unwrap_or(Limits::MULTIPART);
+        let constraints = Constraints::new()
+            .size_limit(SizeLimit::new().whole_stream(limit.as_u64()));
+
+        let reader = data.open(limit + 1);
+        Outcome::Success(Multipart {
+            source: multer::Multipart::with_reader_with_constraints(reader, boundary, constraints),
+            buffer: local_cache_once!(req, SharedStack::new()),
+            limits: req.limits(),
+            temp_dir: req.rocket().config().temp_dir.relative(),
+            threshold: Multipart::DEFAULT_MEMORY_THRESHOLD,
+            done: false,
+        })
+    }
+}
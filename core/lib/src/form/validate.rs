@@ -77,15 +77,60 @@
 //!     Ok(())
 //! }
 //! ```
+//!
+//! ## Asynchronous Validation
+//!
+//! Validations that need to perform I/O, such as checking whether a username
+//! is already taken, can be written as `async` functions and applied with
+//! `field(validate_async)`. The request is inserted as the second parameter,
+//! after the reference to the field, giving access to managed state:
+//!
+//! ```rust
+//! use rocket::form::{self, FromForm, Error};
+//! use rocket::request::Request;
+//! # struct Users;
+//! # impl Users { async fn exists(&self, _: &str) -> bool { false } }
+//!
+//! #[derive(FromForm)]
+//! struct Signup<'r> {
+//!     #[field(validate = len(3..))]
+//!     #[field(validate_async = available())]
+//!     username: &'r str,
+//!     // `with_async` is the asynchronous counterpart to `with`.
+//!     #[field(validate_async = with_async(|s, req| exists(s, req), "unknown user"))]
+//!     referrer: &'r str,
+//! }
+//!
+//! async fn exists(name: &str, req: &Request<'_>) -> bool {
+//!     match req.rocket().state::<Users>() {
+//!         Some(users) => users.exists(name).await,
+//!         None => false,
+//!     }
+//! }
+//!
+//! async fn available<'v>(name: &str, req: &Request<'_>) -> form::Result<'v, ()> {
+//!     if exists(name, req).await {
+//!         Err(Error::validation("username is taken"))?;
+//!     }
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Asynchronous validations run in the [`Form`](crate::form::Form) data guard
+//! and for query guards, after the form has parsed and all synchronous
+//! validations succeed. They don't run in [`Form::parse()`](crate::form::Form::parse()).
 
 use std::borrow::Cow;
 use std::ops::{RangeBounds, Bound};
 use std::fmt::Debug;
+use std::future::Future;
+use std::io::{self, Read};
 
 use crate::data::{ByteUnit, Capped};
 use rocket_http::ContentType;
 
-use crate::{fs::TempFile, form::{Result, Error}};
+use crate::{Request, fs::TempFile, form::{Result, Error}};
 
 crate::export! {
     /// A helper macro for custom validation error messages.
@@ -359,6 +404,20 @@ impl<L, T: Len<L>> Len<L> for crate::serde::msgpack::MsgPack<T> {
     fn zero_len() -> L { T::zero_len() }
 }
 
+#[cfg(feature = "cbor")]
+impl<L, T: Len<L>> Len<L> for crate::serde::cbor::Cbor<T> {
+    fn len(&self) -> L { self.0.len() }
+    fn len_into_u64(len: L) -> u64 { T::len_into_u64(len) }
+    fn zero_len() -> L { T::zero_len() }
+}
+
+#[cfg(feature = "xml")]
+impl<L, T: Len<L>> Len<L> for crate::serde::xml::Xml<T> {
+    fn len(&self) -> L { self.0.len() }
+    fn len_into_u64(len: L) -> u64 { T::len_into_u64(len) }
+    fn zero_len() -> L { T::zero_len() }
+}
+
 /// Length validator: succeeds when the length of a value is within a `range`.
 ///
 /// The value must implement [`Len`]. On failure, returns an [`InvalidLength`]
@@ -804,6 +863,323 @@ pub fn ext<'v>(file: &TempFile<'_>, r#type: ContentType) -> Result<'v, ()> {
     Err(Error::validation(msg))?
 }
 
+/// File extension validator: succeeds when a [`TempFile`] has the Content-Type
+/// corresponding to one of the file extensions in `exts`.
+///
+/// Extensions are mapped to Content-Types via
+/// [`ContentType::from_extension()`]; an extension without a known
+/// Content-Type never matches. Note that the Content-Type of a file is declared
+/// by the client. Combine this validator with [`magic()`] to verify that the
+/// file's contents agree.
+///
+/// On failure, returns a validation error with one of the following messages:
+///
+/// ```text
+/// // the file has an incorrect extension
+/// invalid file type: .$file_ext, must be one of .$ext1, .$ext2, ...
+///
+/// // the file does not have an extension
+/// file type must be one of .$ext1, .$ext2, ...
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::form::FromForm;
+/// use rocket::fs::TempFile;
+///
+/// #[derive(FromForm)]
+/// struct Foo<'r> {
+///     #[field(validate = ext_in(["png", "jpg"]))]
+///     avatar: TempFile<'r>,
+/// }
+/// ```
+pub fn ext_in<'v, I, E>(file: &TempFile<'_>, exts: I) -> Result<'v, ()>
+    where I: IntoIterator<Item = E>, E: AsRef<str>
+{
+    let exts: Vec<E> = exts.into_iter().collect();
+    if let Some(file_ct) = file.content_type() {
+        let matches = exts.iter()
+            .filter_map(|ext| ContentType::from_extension(ext.as_ref()))
+            .any(|ct| &ct == file_ct);
+
+        if matches {
+            return Ok(());
+        }
+    }
+
+    let expected = exts.iter()
+        .map(|ext| format!(".{}", ext.as_ref()))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let msg = match file.content_type().and_then(|c| c.extension()) {
+        Some(ext) => format!("invalid file type: .{}, must be one of {}", ext, expected),
+        None => format!("file type must be one of {}", expected),
+    };
+
+    Err(Error::validation(msg))?
+}
+
+/// The most bytes read from the beginning of a file by [`magic()`] and
+/// [`max_dimensions()`].
+const MAX_HEAD: u64 = 64 * 1024;
+
+/// Reads up to `limit` bytes from the beginning of `file`. Files on disk are
+/// read synchronously; on a multi-threaded runtime, the read is performed via
+/// [`block_in_place()`](tokio::task::block_in_place) so that other tasks can
+/// make progress on the worker thread in the meantime.
+fn read_head<'a>(file: &'a TempFile<'_>, limit: u64) -> io::Result<Cow<'a, [u8]>> {
+    use tokio::runtime::{Handle, RuntimeFlavor};
+
+    let path = match file {
+        TempFile::Buffered { content } => {
+            let len = std::cmp::min(content.len() as u64, limit) as usize;
+            return Ok(Cow::Borrowed(&content.as_bytes()[..len]));
+        }
+        TempFile::File { .. } => file.path().ok_or(io::ErrorKind::NotFound)?,
+    };
+
+    let read = || {
+        let mut head = vec![];
+        std::fs::File::open(path)?.take(limit).read_to_end(&mut head)?;
+        Ok(Cow::Owned(head))
+    };
+
+    match Handle::try_current().map(|handle| handle.runtime_flavor()) {
+        Ok(RuntimeFlavor::MultiThread) => tokio::task::block_in_place(read),
+        _ => read(),
+    }
+}
+
+/// Returns whether `head` begins with the signature of `ct` or `None` if `ct`
+/// has no known signature.
+fn signature_matches(ct: &ContentType, head: &[u8]) -> Option<bool> {
+    let riff = |form: &[u8]| head.starts_with(b"RIFF") && head.get(8..12) == Some(form);
+    let matches = match ct {
+        _ if ct.is_png() => head.starts_with(b"\x89PNG\r\n\x1a\n"),
+        _ if ct.is_jpeg() => head.starts_with(&[0xFF, 0xD8, 0xFF]),
+        _ if ct.is_gif() => head.starts_with(b"GIF87a") || head.starts_with(b"GIF89a"),
+        _ if ct.is_bmp() => head.starts_with(b"BM"),
+        _ if ct.is_webp() => riff(b"WEBP"),
+        _ if ct.is_wav() => riff(b"WAVE"),
+        _ if ct.is_icon() => head.starts_with(&[0, 0, 1, 0]),
+        _ if ct.is_tiff() => head.starts_with(b"II*\0") || head.starts_with(b"MM\0*"),
+        _ if ct.is_pdf() => head.starts_with(b"%PDF-"),
+        _ if ct.is_zip() || ct.is_epub() || ct.is_cbz() => head.starts_with(b"PK\x03\x04"),
+        _ if ct.is_rar() || ct.is_cbr() => head.starts_with(b"Rar!\x1a\x07"),
+        _ if ct.is_gzip() => head.starts_with(&[0x1F, 0x8B]),
+        _ if ct.is_wasm() => head.starts_with(b"\0asm"),
+        _ if ct.is_flac() => head.starts_with(b"fLaC"),
+        _ if ct.is_ogg() => head.starts_with(b"OggS"),
+        _ if ct.is_woff() => head.starts_with(b"wOFF"),
+        _ if ct.is_woff2() => head.starts_with(b"wOF2"),
+        _ => return None,
+    };
+
+    Some(matches)
+}
+
+/// Magic bytes validator: succeeds when the contents of a [`TempFile`] begin
+/// with the signature, or "magic bytes", of the file's declared Content-Type.
+///
+/// Signatures are known for the following Content-Types: PNG, JPEG, GIF, BMP,
+/// WEBP, Icon, TIFF, PDF, ZIP, EPUB, CBZ, RAR, CBR, GZIP, WASM, WAV, FLAC, OGG,
+/// WOFF, and WOFF2. Files with any other Content-Type, or none at all, fail
+/// validation. Only the first 12 bytes of the file are read from disk.
+///
+/// On failure, returns a validation error with one of the following messages:
+///
+/// ```text
+/// // the file's contents don't match its Content-Type
+/// file contents don't match file type $type
+///
+/// // the file's Content-Type has no known signature
+/// unable to verify file type $type
+///
+/// // the file doesn't have a Content-Type
+/// unable to verify unknown file type
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::form::FromForm;
+/// use rocket::fs::TempFile;
+///
+/// #[derive(FromForm)]
+/// struct Foo<'r> {
+///     #[field(validate = ext_in(["png", "jpg"]))]
+///     #[field(validate = magic())]
+///     avatar: TempFile<'r>,
+/// }
+/// ```
+pub fn magic<'v>(file: &TempFile<'_>) -> Result<'v, ()> {
+    let ct = match file.content_type() {
+        Some(ct) => ct,
+        None => Err(Error::validation("unable to verify unknown file type"))?,
+    };
+
+    let head = read_head(file, 12)?;
+    match signature_matches(ct, &head) {
+        Some(true) => Ok(()),
+        Some(false) => {
+            let msg = format!("file contents don't match file type {}", ct.media_type());
+            Err(Error::validation(msg))?
+        }
+        None => Err(Error::validation(format!("unable to verify file type {}", ct.media_type())))?,
+    }
+}
+
+/// Reads the `(width, height)` of a PNG, GIF, BMP, or JPEG image from `head`,
+/// the beginning of the image. Returns `None` if the image isn't in one of
+/// those formats or is malformed, and an error if `head` ends before the
+/// dimensions are found.
+fn image_dimensions(head: &[u8]) -> io::Result<Option<(u64, u64)>> {
+    let be16 = |b: &[u8]| u16::from_be_bytes([b[0], b[1]]) as u64;
+    let le16 = |b: &[u8]| u16::from_le_bytes([b[0], b[1]]) as u64;
+    let be32 = |b: &[u8]| u32::from_be_bytes([b[0], b[1], b[2], b[3]]) as u64;
+    let le32 = |b: &[u8]| i32::from_le_bytes([b[0], b[1], b[2], b[3]]).unsigned_abs() as u64;
+
+    let n = head.len();
+    if head.starts_with(b"\x89PNG\r\n\x1a\n") && n >= 24 && &head[12..16] == b"IHDR" {
+        return Ok(Some((be32(&head[16..20]), be32(&head[20..24]))));
+    } else if head.starts_with(b"GIF8") && n >= 10 {
+        return Ok(Some((le16(&head[6..8]), le16(&head[8..10]))));
+    } else if head.starts_with(b"BM") && n >= 26 {
+        return Ok(Some((le32(&head[18..22]), le32(&head[22..26]))));
+    } else if !head.starts_with(&[0xFF, 0xD8]) {
+        return Ok(None);
+    }
+
+    // JPEG: walk the segments until a start-of-frame segment is found.
+    let mut reader = &head[2..];
+    loop {
+        let mut marker = [0u8; 2];
+        reader.read_exact(&mut marker)?;
+        if marker[0] != 0xFF {
+            return Ok(None);
+        }
+
+        while marker[1] == 0xFF {
+            reader.read_exact(&mut marker[1..])?;
+        }
+
+        match marker[1] {
+            0x01 | 0xD0..=0xD8 => continue,
+            0xD9 | 0xDA => return Ok(None),
+            _ => {}
+        }
+
+        let mut len = [0u8; 2];
+        reader.read_exact(&mut len)?;
+        let len = be16(&len);
+        if len < 2 {
+            return Ok(None);
+        }
+
+        if matches!(marker[1], 0xC0..=0xCF) && !matches!(marker[1], 0xC4 | 0xC8 | 0xCC) {
+            let mut frame = [0u8; 5];
+            reader.read_exact(&mut frame)?;
+            return Ok(Some((be16(&frame[3..5]), be16(&frame[1..3]))));
+        }
+
+        io::copy(&mut (&mut reader).take(len - 2), &mut io::sink())?;
+    }
+}
+
+/// Image dimensions validator: succeeds when a [`TempFile`] is a PNG, GIF,
+/// BMP, or JPEG image at most `max_width` pixels wide and `max_height` pixels
+/// tall.
+///
+/// The image format is determined by the file's contents, not its declared
+/// Content-Type. Only the first 64KiB of the file are read from disk: a JPEG
+/// image whose dimensions aren't found within them fails validation.
+///
+/// On failure, returns a validation error with one of the following messages:
+///
+/// ```text
+/// // the image is too large
+/// image dimensions $widthx$height exceed $max_widthx$max_height
+///
+/// // the file isn't an image in a supported format
+/// file is not a supported image
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::form::FromForm;
+/// use rocket::fs::TempFile;
+///
+/// #[derive(FromForm)]
+/// struct Foo<'r> {
+///     #[field(validate = max_dimensions(512, 512))]
+///     avatar: TempFile<'r>,
+/// }
+/// ```
+pub fn max_dimensions<'v>(file: &TempFile<'_>, max_width: u64, max_height: u64) -> Result<'v, ()> {
+    match read_head(file, MAX_HEAD).and_then(|head| image_dimensions(&head)) {
+        Ok(Some((w, h))) if w <= max_width && h <= max_height => Ok(()),
+        Ok(Some((w, h))) => {
+            let msg = format!("image dimensions {}x{} exceed {}x{}", w, h, max_width, max_height);
+            Err(Error::validation(msg))?
+        }
+        Ok(None) | Err(_) => Err(Error::validation("file is not a supported image"))?,
+    }
+}
+
+/// Total length validator: succeeds when the combined length of a collection
+/// of files lies within `range`.
+///
+/// The collection may be any type whose references iterate over values that
+/// implement [`Len<ByteUnit>`](Len), such as a `Vec<TempFile<'_>>`. As with
+/// [`len()`], prefer data [`Limits`](crate::data::Limits) to cap the size of
+/// individual files.
+///
+/// On failure, returns an [`InvalidLength`] error. See [`len()`] for supported
+/// types and how their length is computed.
+///
+/// [`InvalidLength`]: crate::form::error::ErrorKind::InvalidLength
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::form::FromForm;
+/// use rocket::data::ToByteUnit;
+/// use rocket::fs::TempFile;
+///
+/// #[derive(FromForm)]
+/// struct Foo<'r> {
+///     #[field(validate = total_len(..=10.mebibytes()))]
+///     photos: Vec<TempFile<'r>>,
+/// }
+/// ```
+pub fn total_len<'v, 'a, C, F, R>(files: &'a C, range: R) -> Result<'v, ()>
+    where &'a C: IntoIterator<Item = &'a F>,
+          F: Len<ByteUnit> + 'a,
+          R: RangeBounds<ByteUnit>
+{
+    let total: u64 = files.into_iter().map(|f| F::len_into_u64(f.len())).sum();
+    if !range.contains(&ByteUnit::from(total)) {
+        let start = match range.start_bound() {
+            Bound::Included(v) => Some(v.as_u64()),
+            Bound::Excluded(v) => Some(v.as_u64().saturating_add(1)),
+            Bound::Unbounded => None
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(v) => Some(v.as_u64()),
+            Bound::Excluded(v) => Some(v.as_u64().saturating_sub(1)),
+            Bound::Unbounded => None,
+        };
+
+        Err((start, end))?
+    }
+
+    Ok(())
+}
+
 /// With validator: succeeds when an arbitrary function or closure does.
 ///
 /// This is the most generic validator and, for readability, should only be used
@@ -892,3 +1268,47 @@ pub fn try_with<'v, V, F, T, E>(value: V, f: F) -> Result<'v, ()>
         Err(e) => Err(Error::validation(e.to_string()).into())
     }
 }
+
+/// Async with validator: succeeds when an arbitrary asynchronous function or
+/// closure does.
+///
+/// This is the asynchronous counterpart to [`with`] for use with
+/// `field(validate_async)`. It succeeds exactly when the future returned by
+/// `f`, which is passed the field and the request, resolves to `true` and
+/// fails otherwise.
+///
+/// On failure, returns a validation error with the message `msg`.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::form::FromForm;
+/// use rocket::request::Request;
+///
+/// async fn is_known(slug: &str, req: &Request<'_>) -> bool {
+///     /* query a database in managed state via `req.rocket().state()` */
+///     # true
+/// }
+///
+/// #[derive(FromForm)]
+/// struct Foo<'r> {
+///     #[field(validate_async = with_async(|s, req| is_known(s, req), "unknown tag"))]
+///     tag: &'r str,
+/// }
+/// ```
+pub async fn with_async<'v, 'a, 'r, V, F, Fut, M>(
+    value: V,
+    req: &'a Request<'r>,
+    f: F,
+    msg: M
+) -> Result<'v, ()>
+    where F: FnOnce(V, &'a Request<'r>) -> Fut,
+          Fut: Future<Output = bool>,
+          M: Into<Cow<'static, str>>
+{
+    if !f(value, req).await {
+        Err(Error::validation(msg.into()))?
+    }
+
+    Ok(())
+}
@@ -0,0 +1,150 @@
+use std::time::{Duration, SystemTime};
+
+use crate::http::CacheControl;
+use crate::response::{Response, format_http_date};
+
+/// The caching headers attached to files served by a
+/// [`FileServer`](crate::fs::FileServer).
+///
+/// A `CachePolicy` sets a `Cache-Control` header, an `Expires` header, or both.
+/// Policies are attached to a `FileServer` via
+/// [`FileServer::cache()`](crate::fs::FileServer::cache()), applying to every
+/// file served, and
+/// [`FileServer::cache_for()`](crate::fs::FileServer::cache_for()), applying
+/// to files matching a glob. A [`CacheControl`] converts into a policy setting
+/// only the `Cache-Control` header.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use rocket::fs::CachePolicy;
+/// use rocket::http::CacheControl;
+///
+/// let policy = CachePolicy::new()
+///     .cache_control(CacheControl::public(3600))
+///     .expires(Duration::from_secs(3600));
+///
+/// let policy = CachePolicy::from(CacheControl::no_store());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CachePolicy {
+    cache_control: Option<CacheControl>,
+    expires: Option<Duration>,
+}
+
+impl CachePolicy {
+    /// Returns a policy that sets no headers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fs::CachePolicy;
+    ///
+    /// let policy = CachePolicy::new();
+    /// ```
+    pub fn new() -> Self {
+        CachePolicy::default()
+    }
+
+    /// Sets the `Cache-Control` header to `cache_control`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fs::CachePolicy;
+    /// use rocket::http::{CacheControl, CacheDirective};
+    ///
+    /// let immutable = CacheControl::public(31536000).with(CacheDirective::Immutable);
+    /// let policy = CachePolicy::new().cache_control(immutable);
+    /// ```
+    pub fn cache_control(mut self, cache_control: CacheControl) -> Self {
+        self.cache_control = Some(cache_control);
+        self
+    }
+
+    /// Sets the `Expires` header to the time `after` from when a response is
+    /// sent.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use rocket::fs::CachePolicy;
+    ///
+    /// let policy = CachePolicy::new().expires(Duration::from_secs(24 * 60 * 60));
+    /// ```
+    pub fn expires(mut self, after: Duration) -> Self {
+        self.expires = Some(after);
+        self
+    }
+
+    pub(crate) fn apply(&self, response: &mut Response<'_>) {
+        if let Some(ref cache_control) = self.cache_control {
+            response.set_header(cache_control.clone());
+        }
+
+        let expires = self.expires.and_then(|after| format_http_date(SystemTime::now() + after));
+        if let Some(date) = expires {
+            response.set_raw_header("Expires", date);
+        }
+    }
+}
+
+impl From<CacheControl> for CachePolicy {
+    fn from(cache_control: CacheControl) -> Self {
+        CachePolicy::new().cache_control(cache_control)
+    }
+}
+
+/// Returns `true` if `path`, with `/` separated segments, matches the glob
+/// `pattern`: `?` matches any character but `/`, `*` matches any sequence of
+/// characters without a `/`, and `**` matches any sequence of segments.
+pub(crate) fn glob_matches(pattern: &str, path: &str) -> bool {
+    if pattern == "**" {
+        return true;
+    }
+
+    if let Some(rest) = pattern.strip_prefix("**/") {
+        return glob_matches(rest, path)
+            || path.split_once('/').map_or(false, |(_, path)| glob_matches(pattern, path));
+    }
+
+    let mut chars = pattern.chars();
+    match chars.next() {
+        None => path.is_empty(),
+        Some('*') => {
+            let segment = path.find('/').unwrap_or(path.len());
+            path[..segment].char_indices().map(|(i, _)| i)
+                .chain(std::iter::once(segment))
+                .any(|i| glob_matches(chars.as_str(), &path[i..]))
+        }
+        Some('?') => match path.chars().next() {
+            Some(c) if c != '/' => glob_matches(chars.as_str(), &path[c.len_utf8()..]),
+            _ => false,
+        },
+        Some(c) => path.strip_prefix(c).map_or(false, |path| glob_matches(chars.as_str(), path)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::glob_matches;
+
+    #[test]
+    fn matches_globs() {
+        assert!(glob_matches("index.html", "index.html"));
+        assert!(!glob_matches("index.html", "docs/index.html"));
+        assert!(glob_matches("*.js", "app.js"));
+        assert!(!glob_matches("*.js", "assets/app.js"));
+        assert!(glob_matches("assets/*", "assets/app.js"));
+        assert!(glob_matches("assets/app.????????.js", "assets/app.1a2b3c4d.js"));
+        assert!(!glob_matches("assets/app.????????.js", "assets/app.js"));
+        assert!(glob_matches("**/*.html", "index.html"));
+        assert!(glob_matches("**/*.html", "a/b/c.html"));
+        assert!(glob_matches("assets/**", "assets/a/b.css"));
+        assert!(glob_matches("**", "a/b"));
+        assert!(!glob_matches("*", "a/b"));
+    }
+}
@@ -0,0 +1,332 @@
+use crate::{Request, Data};
+use crate::http::{Method, Status, ContentType, EntityTag};
+use crate::http::uri::Segments;
+use crate::http::ext::IntoOwned;
+use crate::route::{Route, Handler, Outcome};
+use crate::response::{self, Redirect, Responder, Conditional, Ranged};
+use crate::fs::Options;
+use crate::fs::server::precompressed_variants;
+
+/// The files in a directory, embedded into the binary at compile time by
+/// [`embed!`](crate::fs::embed!).
+///
+/// An `Embedded` is typically served by an [`EmbeddedFileServer`]. Files are
+/// identified by their path relative to the embedded directory, with `/`
+/// separated segments.
+///
+/// # Example
+///
+/// ```rust
+/// # use rocket::fs::Embedded;
+/// # fn f(assets: Embedded) {
+/// if let Some(file) = assets.get("css/style.css") {
+///     println!("{} is {} bytes", file.path(), file.contents().len());
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Embedded {
+    files: &'static [EmbeddedFile],
+}
+
+/// A file embedded via [`embed!`](crate::fs::embed!).
+#[derive(Debug)]
+pub struct EmbeddedFile {
+    path: &'static str,
+    contents: &'static [u8],
+    etag: &'static str,
+}
+
+impl EmbeddedFile {
+    #[doc(hidden)]
+    pub const fn __new(path: &'static str, contents: &'static [u8], etag: &'static str) -> Self {
+        EmbeddedFile { path, contents, etag }
+    }
+
+    /// The path of the file relative to the embedded directory.
+    pub fn path(&self) -> &'static str {
+        self.path
+    }
+
+    /// The contents of the file.
+    pub fn contents(&self) -> &'static [u8] {
+        self.contents
+    }
+}
+
+impl Embedded {
+    #[doc(hidden)]
+    pub const fn __new(files: &'static [EmbeddedFile]) -> Self {
+        Embedded { files }
+    }
+
+    /// Returns the file at `path`, if there is one.
+    pub fn get(&self, path: &str) -> Option<&'static EmbeddedFile> {
+        let files = self.files;
+        files.binary_search_by(|file| file.path.cmp(path)).ok().map(|i| &files[i])
+    }
+
+    /// Returns an iterator over all of the files, ordered by path.
+    pub fn iter(&self) -> impl Iterator<Item = &'static EmbeddedFile> {
+        self.files.iter()
+    }
+
+    /// Returns `true` if `path` is a directory containing embedded files.
+    fn is_dir(&self, path: &str) -> bool {
+        self.files.iter().any(|file| {
+            file.path.strip_prefix(path).map_or(false, |rest| rest.starts_with('/'))
+        })
+    }
+}
+
+/// Custom handler for serving files embedded into the binary.
+///
+/// `EmbeddedFileServer` is the counterpart of [`FileServer`](crate::fs::FileServer)
+/// for files bundled into the binary at compile time via
+/// [`embed!`](crate::fs::embed!), allowing an application to be deployed as a
+/// single binary. Files are served with a `Content-Type` implied by their
+/// extension and an `ETag` derived from their contents; requests whose
+/// `If-None-Match` header matches are answered with `304 Not Modified`. As with
+/// `FileServer`, requests for missing files are forwarded, and the generated
+/// route has a default rank of `10`, customizable via
+/// [`EmbeddedFileServer::rank()`].
+///
+/// # Options
+///
+/// The handler's functionality can be customized by passing an [`Options`] to
+/// [`EmbeddedFileServer::new()`]. [`Options::Index`], [`Options::DotFiles`],
+/// [`Options::NormalizeDirs`], [`Options::Precompressed`], and
+/// [`Options::Fallback`] behave as they do for `FileServer`, with precompressed
+/// variants being embedded alongside the originals. Other options are ignored.
+///
+/// # Example
+///
+/// To serve the files in the `tests/static` directory of the crate, embedded
+/// into the binary, at `/`:
+///
+/// ```rust,no_run
+/// # #[macro_use] extern crate rocket;
+/// use rocket::fs::{EmbeddedFileServer, embed};
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().mount("/", EmbeddedFileServer::from(embed!("tests/static")))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct EmbeddedFileServer {
+    files: Embedded,
+    options: Options,
+    rank: isize,
+}
+
+impl EmbeddedFileServer {
+    /// The default rank use by `EmbeddedFileServer` routes.
+    const DEFAULT_RANK: isize = 10;
+
+    /// Constructs a new `EmbeddedFileServer` that serves `files` with the
+    /// default [`Options`], [`Options::Index`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fs::{EmbeddedFileServer, Embedded};
+    ///
+    /// # fn f(assets: Embedded) {
+    /// let rocket = rocket::build().mount("/static", EmbeddedFileServer::from(assets));
+    /// # }
+    /// ```
+    pub fn from(files: Embedded) -> Self {
+        EmbeddedFileServer::new(files, Options::default())
+    }
+
+    /// Constructs a new `EmbeddedFileServer` that serves `files` with
+    /// `options` enabled.
+    ///
+    /// # Example
+    ///
+    /// Serve a single-page application along with precompressed variants of
+    /// its assets:
+    ///
+    /// ```rust
+    /// use rocket::fs::{EmbeddedFileServer, Embedded, Options};
+    ///
+    /// # fn f(app: Embedded) {
+    /// let options = Options::Index | Options::Precompressed | Options::Fallback("index.html");
+    /// let rocket = rocket::build().mount("/", EmbeddedFileServer::new(app, options));
+    /// # }
+    /// ```
+    pub fn new(files: Embedded, options: Options) -> Self {
+        EmbeddedFileServer { files, options, rank: Self::DEFAULT_RANK }
+    }
+
+    /// Sets the rank for generated routes to `rank`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fs::{EmbeddedFileServer, Embedded};
+    ///
+    /// # fn f(assets: Embedded) {
+    /// let server = EmbeddedFileServer::from(assets).rank(-1);
+    /// # }
+    /// ```
+    pub fn rank(mut self, rank: isize) -> Self {
+        self.rank = rank;
+        self
+    }
+
+    /// Returns the file at `path` or, with [`Options::Precompressed`], its most
+    /// preferred precompressed variant that `req` accepts.
+    fn open(&self, req: &Request<'_>, path: &str) -> Option<ServedEmbeddedFile> {
+        let original = self.files.get(path)?;
+        let vary = self.options.contains(Options::Precompressed);
+        let variant = precompressed_variants(req).into_iter()
+            .filter(|_| vary)
+            .find_map(|(coding, ext)| {
+                let variant = self.files.get(&format!("{}.{}", path, ext))?;
+                Some((coding, variant))
+            });
+
+        Some(ServedEmbeddedFile { original, variant, vary })
+    }
+
+    fn serve<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        use crate::http::uri::fmt::Path;
+
+        let allow_dotfiles = self.options.contains(Options::DotFiles);
+        let path = req.segments::<Segments<'_, Path>>(0..).ok()
+            .and_then(|segments| segments.to_path_buf(allow_dotfiles).ok())
+            .and_then(|path| {
+                let segments = path.components()
+                    .map(|c| c.as_os_str().to_str())
+                    .collect::<Option<Vec<_>>>()?;
+
+                Some(segments.join("/"))
+            });
+
+        let path = match path {
+            Some(path) => path,
+            None => return Outcome::forward(data),
+        };
+
+        if !path.is_empty() {
+            if let Some(file) = self.open(req, &path) {
+                return Outcome::from(req, file);
+            }
+        }
+
+        if !path.is_empty() && !self.files.is_dir(&path) {
+            return Outcome::forward(data);
+        }
+
+        // Normalize '/a/b/foo' to '/a/b/foo/'.
+        let options = self.options;
+        if options.contains(Options::NormalizeDirs) && !req.uri().path().ends_with('/') {
+            let normal = req.uri().map_path(|p| format!("{}/", p))
+                .expect("adding a trailing slash to a known good path => valid path")
+                .into_owned();
+
+            return Outcome::from_or_forward(req, data, Redirect::permanent(normal));
+        }
+
+        if !options.contains(Options::Index) {
+            return Outcome::forward(data);
+        }
+
+        let index = if path.is_empty() {
+            "index.html".to_string()
+        } else {
+            format!("{}/index.html", path)
+        };
+
+        Outcome::from_or_forward(req, data, self.open(req, &index))
+    }
+}
+
+impl From<EmbeddedFileServer> for Vec<Route> {
+    fn from(server: EmbeddedFileServer) -> Self {
+        let mut route = Route::ranked(server.rank, Method::Get, "/<path..>", server);
+        route.name = Some("EmbeddedFileServer".into());
+        vec![route]
+    }
+}
+
+#[crate::async_trait]
+impl Handler for EmbeddedFileServer {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        match (self.serve(req, data), self.options.fallback()) {
+            (Outcome::Forward(data), Some(fallback)) => {
+                Outcome::from_or_forward(req, data, self.open(req, fallback))
+            }
+            (outcome, _) => outcome,
+        }
+    }
+}
+
+/// An embedded file served by an [`EmbeddedFileServer`]: the original, whose
+/// extension implies the `Content-Type`, the precompressed variant served in
+/// its place, if any, with its `Content-Encoding`, and whether the response
+/// varies by `Accept-Encoding`.
+struct ServedEmbeddedFile {
+    original: &'static EmbeddedFile,
+    variant: Option<(&'static str, &'static EmbeddedFile)>,
+    vary: bool,
+}
+
+impl<'r> Responder<'r, 'static> for ServedEmbeddedFile {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let file = self.variant.map_or(self.original, |(_, variant)| variant);
+        let etag = EntityTag::strong(file.etag).ok_or(Status::InternalServerError)?;
+        let mut response = Ranged::new(Conditional::new(file.contents).etag(etag)).respond_to(req)?;
+
+        let ext = self.original.path.rsplit_once('.').map(|(_, ext)| ext);
+        match ext.and_then(ContentType::from_extension) {
+            Some(content_type) => { response.set_header(content_type); }
+            None => response.remove_header("Content-Type"),
+        }
+
+        if let Some((coding, _)) = self.variant {
+            response.set_raw_header("Content-Encoding", coding);
+        }
+
+        if self.vary {
+            response.set_raw_header("Vary", "Accept-Encoding");
+        }
+
+        Ok(response)
+    }
+}
+
+crate::export! {
+    /// Embeds the files in a directory into the binary at compile time.
+    ///
+    /// The macro accepts one parameter, a string literal path to a directory
+    /// relative to the crate root, and evaluates to an [`Embedded`]
+    /// containing every file in the directory and its subdirectories, which
+    /// is typically served by an [`EmbeddedFileServer`]. The crate is rebuilt
+    /// when an embedded file changes; adding or removing files may require a
+    /// rebuild to be forced.
+    ///
+    /// # Example
+    ///
+    /// Embed the crate-relative `tests/static/` directory and serve it at `/`:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::fs::{EmbeddedFileServer, Embedded, embed};
+    ///
+    /// static ASSETS: Embedded = embed!("tests/static");
+    ///
+    /// assert!(ASSETS.get("index.html").is_some());
+    /// assert!(ASSETS.get("inner/goodbye").is_some());
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build().mount("/", EmbeddedFileServer::from(ASSETS))
+    /// }
+    /// ```
+    macro_rules! embed {
+        ($path:literal) => ($crate::__embed!($crate::fs, $path));
+    }
+}
@@ -0,0 +1,142 @@
+use std::io;
+use std::fmt::Write;
+use std::path::Path;
+
+use crate::Request;
+use crate::http::{ContentType, RawStr};
+use crate::response::{self, Responder};
+
+/// A directory listing rendered by a [`FileServer`](crate::fs::FileServer):
+/// HTML or, if the request prefers it, JSON.
+pub(crate) struct Listing {
+    /// The request's path with a trailing slash, percent-encoded.
+    base: String,
+    /// Whether the directory has a parent within the mount point.
+    parent: bool,
+    entries: Vec<Entry>,
+}
+
+struct Entry {
+    name: String,
+    size: Option<u64>,
+}
+
+impl Entry {
+    fn is_dir(&self) -> bool {
+        self.size.is_none()
+    }
+}
+
+impl Listing {
+    /// Reads the entries in `dir`, served at `path`, skipping those whose names
+    /// aren't valid UTF-8 or whose metadata can't be read, as well as dotfiles
+    /// unless `dotfiles` is `true`.
+    pub async fn read(dir: &Path, path: &RawStr, parent: bool, dotfiles: bool) -> io::Result<Self> {
+        let mut entries = vec![];
+        let mut dir = tokio::fs::read_dir(dir).await?;
+        while let Some(entry) = dir.next_entry().await? {
+            let name = match entry.file_name().into_string() {
+                Ok(name) if dotfiles || !name.starts_with('.') => name,
+                _ => continue,
+            };
+
+            // Follow symlinks so that links to directories are listed as such.
+            if let Ok(metadata) = tokio::fs::metadata(entry.path()).await {
+                let size = if metadata.is_dir() { None } else { Some(metadata.len()) };
+                entries.push(Entry { name, size });
+            }
+        }
+
+        entries.sort_by(|a, b| (!a.is_dir(), &a.name).cmp(&(!b.is_dir(), &b.name)));
+        let base = format!("{}/", path.as_str().trim_end_matches('/'));
+        Ok(Listing { base, parent, entries })
+    }
+
+    fn href(&self, entry: &Entry) -> String {
+        let name = RawStr::new(&entry.name).percent_encode();
+        let slash = if entry.is_dir() { "/" } else { "" };
+        format!("{}{}{}", self.base, name, slash)
+    }
+
+    fn html(&self) -> String {
+        let path = RawStr::new(&self.base).percent_decode_lossy();
+        let path = RawStr::new(&path).html_escape();
+
+        let mut html = format!("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+            <title>Index of {0}</title>\n</head>\n<body>\n<h1>Index of {0}</h1>\n<table>\n\
+            <tr><th>Name</th><th>Size</th></tr>\n", path);
+
+        if self.parent {
+            let dir = self.base.trim_end_matches('/');
+            let parent = &dir[..dir.rfind('/').map_or(0, |i| i + 1)];
+            let _ = writeln!(html, "<tr><td><a href=\"{}\">../</a></td><td>-</td></tr>", parent);
+        }
+
+        for entry in &self.entries {
+            let name = RawStr::new(&entry.name).html_escape();
+            let slash = if entry.is_dir() { "/" } else { "" };
+            let size = entry.size.map_or_else(|| "-".into(), |size| size.to_string());
+            let _ = writeln!(html, "<tr><td><a href=\"{}\">{}{}</a></td><td>{}</td></tr>",
+                RawStr::new(&self.href(entry)).html_escape(), name, slash, size);
+        }
+
+        html.push_str("</table>\n</body>\n</html>\n");
+        html
+    }
+
+    fn json(&self) -> String {
+        let entries = self.entries.iter()
+            .map(|entry| {
+                let size = entry.size.map_or_else(|| "null".into(), |size| size.to_string());
+                format!("{{\"name\":{},\"href\":{},\"dir\":{},\"size\":{}}}",
+                    json_string(&entry.name), json_string(&self.href(entry)),
+                    entry.is_dir(), size)
+            })
+            .collect::<Vec<_>>();
+
+        format!("[{}]", entries.join(","))
+    }
+}
+
+/// Returns `string` as a JSON string literal.
+fn json_string(string: &str) -> String {
+    let mut json = String::with_capacity(string.len() + 2);
+    json.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => { let _ = write!(json, "\\u{:04x}", c as u32); }
+            c => json.push(c),
+        }
+    }
+
+    json.push('"');
+    json
+}
+
+impl<'r> Responder<'r, 'static> for Listing {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let json = req.accept().map_or(false, |accept| accept.preferred().is_json());
+        let mut response = if json {
+            (ContentType::JSON, self.json()).respond_to(req)?
+        } else {
+            (ContentType::HTML, self.html()).respond_to(req)?
+        };
+
+        response.adjoin_raw_header("Vary", "Accept");
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::json_string;
+
+    #[test]
+    fn escapes_json_strings() {
+        assert_eq!(json_string("a.txt"), r#""a.txt""#);
+        assert_eq!(json_string("say \"hi\"\\"), r#""say \"hi\"\\""#);
+        assert_eq!(json_string("a\nb\u{7f}"), r#""a\u000ab\u007f""#);
+    }
+}
@@ -4,9 +4,15 @@ mod server;
 mod named_file;
 mod temp_file;
 mod file_name;
+mod listing;
+mod cache;
+mod embedded;
 
 pub use server::*;
 pub use named_file::*;
 pub use temp_file::*;
 pub use file_name::*;
+pub use cache::*;
+pub use embedded::*;
 pub use server::relative;
+pub use embedded::embed;
@@ -1,16 +1,35 @@
 use std::io;
+use std::fs::Metadata;
 use std::path::{Path, PathBuf};
 use std::ops::{Deref, DerefMut};
+use std::time::UNIX_EPOCH;
 
 use tokio::fs::File;
 
 use crate::request::Request;
-use crate::response::{self, Responder};
-use crate::http::ContentType;
+use crate::response::{self, Responder, Conditional, select_range};
+use crate::http::{ContentType, EntityTag};
+
+/// The maximum size of the chunks a file is read and written in. Each chunk is
+/// read on the blocking thread pool, so reading files in far larger chunks than
+/// other bodies saves many round trips to the pool.
+const FILE_MAX_CHUNK: usize = 64 * 1024;
 
 /// A [`Responder`] that sends file data with a Content-Type based on its
 /// file extension.
 ///
+/// The response is [`Conditional`] on the file's modification time and an
+/// entity tag derived from it and the file's size: requests whose
+/// `If-None-Match` or `If-Modified-Since` headers show that the client's copy
+/// is current are answered with `304 Not Modified`. Range requests are
+/// supported as described in [`Ranged`](crate::response::Ranged).
+///
+/// Whole files are written out by the server's file fast path: each chunk is
+/// read straight into the buffer handed to the connection while the previous
+/// chunk is written, and plaintext HTTP/1 connections write those buffers with
+/// vectored writes. Data is still copied through userspace once; `hyper` owns
+/// the connection, so there is no `sendfile` path.
+///
 /// # Example
 ///
 /// A simple static file server mimicking [`FileServer`]:
@@ -37,7 +56,7 @@ use crate::http::ContentType;
 ///
 /// [`FileServer`]: crate::fs::FileServer
 #[derive(Debug)]
-pub struct NamedFile(PathBuf, File);
+pub struct NamedFile(PathBuf, File, Option<Metadata>);
 
 impl NamedFile {
     /// Attempts to open a file in read-only mode.
@@ -65,7 +84,8 @@ impl NamedFile {
         // all of those `seek`s to determine the file size. But, what happens if
         // the file gets changed between now and then?
         let file = File::open(path.as_ref()).await?;
-        Ok(NamedFile(path.as_ref().to_path_buf(), file))
+        let metadata = file.metadata().await.ok();
+        Ok(NamedFile(path.as_ref().to_path_buf(), file, metadata))
     }
 
     /// Retrieve the underlying `File`.
@@ -148,7 +168,25 @@ impl NamedFile {
 /// implied by its extension, use a [`File`] directly.
 impl<'r> Responder<'r, 'static> for NamedFile {
     fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
-        let mut response = self.1.respond_to(req)?;
+        let mut file = Conditional::new(self.1);
+        let len = self.2.as_ref().map(|m| m.len());
+        let modified = self.2.and_then(|m| m.modified().ok());
+        if let (Some(modified), Some(len)) = (modified, len) {
+            let nanos = modified.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos());
+            file = file.last_modified(modified);
+            if let Some(tag) = EntityTag::strong(format!("{:x}-{:x}", nanos, len)) {
+                file = file.etag(tag);
+            }
+        }
+
+        let mut response = file.respond_to(req)?;
+        if let Some(len) = len {
+            response.body_mut().set_preset_size(len as usize);
+        }
+
+        response.set_max_chunk_size(FILE_MAX_CHUNK);
+
+        select_range(req, &mut response);
         if let Some(ext) = self.0.extension() {
             if let Some(ct) = ContentType::from_extension(&ext.to_string_lossy()) {
                 response.set_header(ct);
@@ -1,10 +1,12 @@
 use std::path::{PathBuf, Path};
 
 use crate::{Request, Data};
-use crate::http::{Method, uri::Segments, ext::IntoOwned};
+use crate::http::{Method, ContentType, uri::Segments, ext::IntoOwned};
 use crate::route::{Route, Handler, Outcome};
-use crate::response::Redirect;
+use crate::response::{self, Redirect, Responder};
 use crate::fs::NamedFile;
+use crate::fs::listing::Listing;
+use crate::fs::cache::{CachePolicy, glob_matches};
 
 /// Custom handler for serving static files.
 ///
@@ -43,6 +45,13 @@ use crate::fs::NamedFile;
 /// `/public/<directory>` will be handled by returning the contents of
 /// `/static/<directory>/index.html`.
 ///
+/// ## Caching
+///
+/// By default, files are served without caching headers. To attach a
+/// `Cache-Control` or `Expires` header, set a [`CachePolicy`] for all files via
+/// [`FileServer::cache()`] or for those matching a glob via
+/// [`FileServer::cache_for()`].
+///
 /// ## Relative Paths
 ///
 /// In the example above, `/static` is an absolute path. If your static files
@@ -65,6 +74,8 @@ pub struct FileServer {
     root: PathBuf,
     options: Options,
     rank: isize,
+    cache: Option<CachePolicy>,
+    cache_for: Vec<(String, CachePolicy)>,
 }
 
 impl FileServer {
@@ -159,7 +170,13 @@ impl FileServer {
             }
         }
 
-        FileServer { root: path.into(), options, rank: Self::DEFAULT_RANK }
+        FileServer {
+            root: path.into(),
+            options,
+            rank: Self::DEFAULT_RANK,
+            cache: None,
+            cache_for: vec![],
+        }
     }
 
     /// Sets the rank for generated routes to `rank`.
@@ -179,6 +196,86 @@ impl FileServer {
         self.rank = rank;
         self
     }
+
+    /// Attaches the caching headers of `policy` to every file served that
+    /// doesn't match a glob set via [`FileServer::cache_for()`].
+    ///
+    /// # Example
+    ///
+    /// Allow any cache to store files for an hour:
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::fs::FileServer;
+    /// use rocket::http::CacheControl;
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     let server = FileServer::from("/www/public").cache(CacheControl::public(3600));
+    ///     rocket::build().mount("/", server)
+    /// }
+    /// ```
+    pub fn cache<P: Into<CachePolicy>>(mut self, policy: P) -> Self {
+        self.cache = Some(policy.into());
+        self
+    }
+
+    /// Attaches the caching headers of `policy` to files whose path, relative
+    /// to the served directory and with `/` separated segments, matches the
+    /// glob `pattern`. In `pattern`, `?` matches any character but `/`, `*`
+    /// matches any sequence of characters without a `/`, and `**` matches any
+    /// sequence of segments. If several globs match, the first one set wins.
+    ///
+    /// The path matched is that of the file requested: an `index.html` for a
+    /// directory, the fallback of [`Options::Fallback`], and the original of a
+    /// variant served with [`Options::Precompressed`].
+    ///
+    /// # Example
+    ///
+    /// Cache content-hashed assets forever, revalidate `index.html` files, and
+    /// allow caching everything else for an hour:
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::fs::FileServer;
+    /// use rocket::http::{CacheControl, CacheDirective};
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     let immutable = CacheControl::public(31536000).with(CacheDirective::Immutable);
+    ///     let server = FileServer::from("/www/public")
+    ///         .cache_for("assets/**", immutable)
+    ///         .cache_for("**/index.html", CacheControl::new([CacheDirective::NoCache]))
+    ///         .cache(CacheControl::public(3600));
+    ///
+    ///     rocket::build().mount("/", server)
+    /// }
+    /// ```
+    pub fn cache_for<S, P>(mut self, pattern: S, policy: P) -> Self
+        where S: Into<String>, P: Into<CachePolicy>
+    {
+        self.cache_for.push((pattern.into(), policy.into()));
+        self
+    }
+
+    /// Returns the caching policy for the file at `path`, if any.
+    fn cache_policy(&self, path: &Path) -> Option<&CachePolicy> {
+        let relative = match path.strip_prefix(&self.root) {
+            Ok(relative) if relative.as_os_str().is_empty() => Path::new(path.file_name()?),
+            Ok(relative) => relative,
+            Err(_) => path,
+        };
+
+        let relative = relative.components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        self.cache_for.iter()
+            .find(|(pattern, _)| glob_matches(pattern, &relative))
+            .map(|(_, policy)| policy)
+            .or(self.cache.as_ref())
+    }
 }
 
 impl From<FileServer> for Vec<Route> {
@@ -193,6 +290,19 @@ impl From<FileServer> for Vec<Route> {
 #[crate::async_trait]
 impl Handler for FileServer {
     async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        match (self.serve(req, data).await, self.options.fallback()) {
+            (Outcome::Forward(data), Some(fallback)) => {
+                let file = self.open(req, self.root.join(fallback)).await;
+                Outcome::from_or_forward(req, data, file)
+            }
+            (outcome, _) => outcome,
+        }
+    }
+}
+
+impl FileServer {
+    /// Serves the file, index page, or listing for `req`, if there is one.
+    async fn serve<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
         use crate::http::uri::fmt::Path;
 
         // TODO: Should we reject dotfiles for `self.root` if !DotFiles?
@@ -204,7 +314,7 @@ impl Handler for FileServer {
             };
 
             if segments.is_empty() {
-                let file = NamedFile::open(&self.root).await.ok();
+                let file = self.open(req, self.root.clone()).await;
                 return Outcome::from_or_forward(req, data, file);
             } else {
                 return Outcome::forward(data);
@@ -228,17 +338,122 @@ impl Handler for FileServer {
                     return Outcome::from_or_forward(req, data, Redirect::permanent(normal));
                 }
 
-                if !options.contains(Options::Index) {
+                if options.contains(Options::Index) {
+                    if let Some(index) = self.open(req, p.join("index.html")).await {
+                        return Outcome::from(req, index);
+                    }
+                }
+
+                if !options.contains(Options::Listing) {
                     return Outcome::forward(data);
                 }
 
-                let index = NamedFile::open(p.join("index.html")).await.ok();
-                Outcome::from_or_forward(req, data, index)
+                // Only directories below the mount point link to their parent.
+                let segments = req.segments::<Segments<'_, Path>>(0..);
+                let parent = segments.map_or(false, |segments| !segments.is_empty());
+                let path = req.uri().path().raw();
+                let listing = Listing::read(&p, path, parent, allow_dotfiles).await;
+                Outcome::from_or_forward(req, data, listing.ok())
             },
-            Some(p) => Outcome::from_or_forward(req, data, NamedFile::open(p).await.ok()),
+            Some(p) => Outcome::from_or_forward(req, data, self.open(req, p).await),
             None => Outcome::forward(data),
         }
     }
+
+    /// Opens the file at `path` or, with [`Options::Precompressed`], its most
+    /// preferred precompressed variant that `req` accepts.
+    async fn open(&self, req: &Request<'_>, path: PathBuf) -> Option<ServedFile<'_>> {
+        let vary = self.options.contains(Options::Precompressed);
+        let cache = self.cache_policy(&path);
+        if !vary {
+            let file = NamedFile::open(path).await.ok()?;
+            return Some(ServedFile { file, vary, encoding: None, cache });
+        }
+
+        for (coding, ext) in precompressed_variants(req) {
+            let mut variant = path.clone().into_os_string();
+            variant.push(".");
+            variant.push(ext);
+            if let Ok(file) = NamedFile::open(variant).await {
+                let encoding = Some((coding, path));
+                return Some(ServedFile { file, vary, encoding, cache });
+            }
+        }
+
+        let file = NamedFile::open(path).await.ok()?;
+        Some(ServedFile { file, vary, encoding: None, cache })
+    }
+}
+
+/// Returns the content codings of precompressed variants `req` accepts, each
+/// with the extension of the corresponding files, most preferred first.
+pub(crate) fn precompressed_variants(req: &Request<'_>) -> Vec<(&'static str, &'static str)> {
+    let mut variants = [("br", "br"), ("gzip", "gz")]
+        .iter()
+        .map(|&(coding, ext)| (coding, ext, quality(req, coding)))
+        .filter(|&(_, _, q)| q > 0.0)
+        .collect::<Vec<_>>();
+
+    // Sorting is stable: `br` is preferred at equal quality.
+    variants.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+    variants.into_iter().map(|(coding, ext, _)| (coding, ext)).collect()
+}
+
+/// Returns the quality of `coding` in `req`'s `Accept-Encoding` header, of
+/// `*` if `coding` isn't named, or else `0`.
+fn quality(req: &Request<'_>, coding: &str) -> f32 {
+    let codings = req.headers().get("Accept-Encoding")
+        .flat_map(|value| value.split(','))
+        .filter_map(|coding| {
+            let mut parts = coding.split(';');
+            let name = parts.next()?.trim();
+            let q = parts.find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+
+            Some((name, q))
+        })
+        .collect::<Vec<_>>();
+
+    let find = |name: &str| codings.iter()
+        .find(|(coding, _)| coding.eq_ignore_ascii_case(name))
+        .map(|(_, q)| *q);
+
+    find(coding).or_else(|| find("*")).unwrap_or(0.0)
+}
+
+/// A file served by a [`FileServer`] with its caching policy: whether the
+/// response varies by `Accept-Encoding` and, for a precompressed variant, its
+/// `Content-Encoding` and the path of the original, whose extension implies the
+/// `Content-Type`.
+struct ServedFile<'a> {
+    file: NamedFile,
+    vary: bool,
+    encoding: Option<(&'static str, PathBuf)>,
+    cache: Option<&'a CachePolicy>,
+}
+
+impl<'r> Responder<'r, 'static> for ServedFile<'_> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let mut response = self.file.respond_to(req)?;
+        if let Some(cache) = self.cache {
+            cache.apply(&mut response);
+        }
+
+        if self.vary {
+            response.adjoin_raw_header("Vary", "Accept-Encoding");
+        }
+
+        if let Some((coding, original)) = self.encoding {
+            response.set_raw_header("Content-Encoding", coding);
+            let ext = original.extension().map(|ext| ext.to_string_lossy());
+            match ext.and_then(|ext| ContentType::from_extension(&ext)) {
+                Some(content_type) => { response.set_header(content_type); }
+                None => response.remove_header("Content-Type"),
+            }
+        }
+
+        Ok(response)
+    }
 }
 
 /// A bitset representing configurable options for [`FileServer`].
@@ -252,12 +467,18 @@ impl Handler for FileServer {
 ///   * [`Options::Missing`] - Don't fail if the path to serve is missing.
 ///   * [`Options::NormalizeDirs`] - Redirect directories without a trailing
 ///     slash to ones with a trailing slash.
+///   * [`Options::Precompressed`] - Serve `.br` and `.gz` variants of files to
+///     clients that accept them.
+///   * [`Options::Listing`] - Render listings for directory requests without
+///     an index page.
+///   * [`Options::Fallback`] - Serve a fallback file, such as a single-page
+///     app's `index.html`, instead of forwarding.
 ///
 /// `Options` structures can be `or`d together to select two or more options.
 /// For instance, to request that both dot files and index pages be returned,
 /// use `Options::DotFiles | Options::Index`.
 #[derive(Debug, Clone, Copy)]
-pub struct Options(u8);
+pub struct Options(u8, Option<&'static str>);
 
 #[allow(non_upper_case_globals, non_snake_case)]
 impl Options {
@@ -265,7 +486,7 @@ impl Options {
     ///
     /// This is different than [`Options::default()`](#impl-Default), which
     /// enables `Options::Index`.
-    pub const None: Options = Options(0);
+    pub const None: Options = Options(0, None);
 
     /// Respond to requests for a directory with the `index.html` file in that
     /// directory, if it exists.
@@ -275,7 +496,7 @@ impl Options {
     /// exists. When disabled, requests to directories will always forward.
     ///
     /// **Enabled by default.**
-    pub const Index: Options = Options(1 << 0);
+    pub const Index: Options = Options(1 << 0, None);
 
     /// Allow serving dotfiles.
     ///
@@ -284,7 +505,7 @@ impl Options {
     /// treated as missing.
     ///
     /// **Disabled by default.**
-    pub const DotFiles: Options = Options(1 << 1);
+    pub const DotFiles: Options = Options(1 << 1, None);
 
     /// Normalizes directory requests by redirecting requests to directory paths
     /// without a trailing slash to ones with a trailing slash.
@@ -316,7 +537,7 @@ impl Options {
     /// result, the request in the former case will fail. To avoid this,
     /// `NormalizeDirs` will redirect requests to `/foo` to `/foo/` if the file
     /// that would be served is a directory.
-    pub const NormalizeDirs: Options = Options(1 << 2);
+    pub const NormalizeDirs: Options = Options(1 << 2, None);
 
     /// Allow serving a file instead of a directory.
     ///
@@ -346,13 +567,129 @@ impl Options {
     ///         .mount("/cat", FileServer::new("static/cat.jpeg", Options::IndexFile))
     /// }
     /// ```
-    pub const IndexFile: Options = Options(1 << 3);
+    pub const IndexFile: Options = Options(1 << 3, None);
 
     /// Don't fail if the file or directory to serve is missing.
     ///
     /// By default, `FileServer` will error if the path to serve is missing to
     /// prevent inevitable 404 errors. This option overrides that.
-    pub const Missing: Options = Options(1 << 4);
+    pub const Missing: Options = Options(1 << 4, None);
+
+    /// Serve precompressed variants of files when the client accepts them.
+    ///
+    /// When enabled, [`FileServer`] responds to a request for a file `foo.js`
+    /// with `foo.js.br` or `foo.js.gz`, if it exists and the request's
+    /// `Accept-Encoding` header allows `br` or `gzip`, respectively. When both
+    /// are allowed, the one with the higher quality is preferred, and `br` on a
+    /// tie. The response has the `Content-Type` implied by `foo.js` and the
+    /// corresponding `Content-Encoding`. Otherwise, `foo.js` itself is served.
+    /// Responses carry a `Vary: Accept-Encoding` header either way.
+    ///
+    /// **Disabled by default.**
+    ///
+    /// # Example
+    ///
+    /// Given the following directory structure, produced by a build step...
+    ///
+    /// ```text
+    /// static/
+    /// ├── app.js
+    /// ├── app.js.br
+    /// └── app.js.gz
+    /// ```
+    ///
+    /// ...a request to `/app.js` with `Accept-Encoding: gzip, br` is served
+    /// `static/app.js.br` with:
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::fs::{FileServer, Options};
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     let options = Options::Index | Options::Precompressed;
+    ///     rocket::build().mount("/", FileServer::new("static", options))
+    /// }
+    /// ```
+    pub const Precompressed: Options = Options(1 << 5, None);
+
+    /// Respond to requests for a directory with a listing of its contents.
+    ///
+    /// When enabled, [`FileServer`] responds to requests for a directory that
+    /// isn't otherwise served, either because [`Options::Index`] is disabled or
+    /// because the directory has no `index.html`, with a listing of the files
+    /// and directories within it. The listing is an HTML page linking to each
+    /// entry or, if the request's `Accept` header prefers JSON, an array of
+    /// objects with the keys `name`, `href`, `dir`, and `size`, the latter
+    /// being `null` for directories. Dotfiles are listed only if
+    /// [`Options::DotFiles`] is also enabled.
+    ///
+    /// **Disabled by default.**
+    ///
+    /// # Example
+    ///
+    /// Serve build artifacts from `/var/artifacts`, listing directories but
+    /// hiding dotfiles, at `/artifacts`:
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::fs::{FileServer, Options};
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     let options = Options::Listing | Options::NormalizeDirs;
+    ///     rocket::build().mount("/artifacts", FileServer::new("/var/artifacts", options))
+    /// }
+    /// ```
+    pub const Listing: Options = Options(1 << 6, None);
+
+    /// Respond to requests that would otherwise be forwarded with the file at
+    /// `path`, relative to the served directory.
+    ///
+    /// When enabled, [`FileServer`] responds to a request for a missing file,
+    /// a hidden dotfile, or a directory that isn't otherwise served with the
+    /// file at `${root}/${path}` and a status of `200`, if it exists. Existing
+    /// files are served as usual. This is typically used to serve the entry
+    /// point of a single-page application, whose client-side router handles
+    /// the path instead, in place of a catch-all route. If `Fallback` is `or`d
+    /// with another `Fallback`, the latter's `path` is used.
+    ///
+    /// **Disabled by default.**
+    ///
+    /// # Example
+    ///
+    /// Given the following directory structure, produced by a frontend build
+    /// step...
+    ///
+    /// ```text
+    /// dist/
+    /// ├── assets/
+    /// │   └── app.js
+    /// └── index.html
+    /// ```
+    ///
+    /// ...requests to `/assets/app.js` are served `dist/assets/app.js` while
+    /// requests to `/`, `/users/42`, or any other path not in `dist/` are
+    /// served `dist/index.html` with:
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::fs::{FileServer, Options};
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     let options = Options::Index | Options::Fallback("index.html");
+    ///     rocket::build().mount("/", FileServer::new("dist", options))
+    /// }
+    /// ```
+    pub const fn Fallback(path: &'static str) -> Options {
+        Options(1 << 7, Some(path))
+    }
+
+    /// Returns the path set via [`Options::Fallback`], if any.
+    pub(crate) fn fallback(self) -> Option<&'static str> {
+        self.1
+    }
 
     /// Returns `true` if `self` is a superset of `other`. In other words,
     /// returns `true` if all of the options in `other` are also in `self`.
@@ -392,7 +729,7 @@ impl std::ops::BitOr for Options {
 
     #[inline(always)]
     fn bitor(self, rhs: Self) -> Self {
-        Options(self.0 | rhs.0)
+        Options(self.0 | rhs.0, rhs.1.or(self.1))
     }
 }
 
@@ -0,0 +1,155 @@
+use std::borrow::Cow;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+
+use crate::{Rocket, Orbit};
+
+/// The result of a health check: `Ok` if healthy, an error describing why not
+/// otherwise.
+pub type Result = std::result::Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+/// The probe, and thus endpoint, a health [`Check`] contributes to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Probe {
+    /// The liveness probe, served at `/healthz`.
+    Liveness,
+    /// The readiness probe, served at `/readyz`.
+    Readiness,
+}
+
+/// A named, asynchronous health check.
+///
+/// A check is created from a function or closure, in the style of
+/// [`AdHoc`](crate::fairing::AdHoc) fairings, that returns a boxed future
+/// resolving to a [`Result`](super::Result). The check fails if the future
+/// resolves to an error or doesn't resolve within the check's
+/// [timeout](Check::timeout()), 5 seconds by default.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::health::Check;
+///
+/// let check = Check::liveness("workers", |rocket| Box::pin(async move {
+///     match rocket.config().workers {
+///         0 => Err("no workers".into()),
+///         _ => Ok(()),
+///     }
+/// }));
+/// ```
+pub struct Check {
+    pub(crate) name: Cow<'static, str>,
+    pub(crate) probe: Probe,
+    pub(crate) timeout: Duration,
+    run: Runner,
+}
+
+type Runner = Box<dyn for<'a> Fn(&'a Rocket<Orbit>) -> BoxFuture<'a, Result> + Send + Sync>;
+
+impl Check {
+    /// The default timeout of a check.
+    const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Constructs a liveness check named `name` that runs `f`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::health::Check;
+    ///
+    /// let check = Check::liveness("ping", |_| Box::pin(async move { Ok(()) }));
+    /// ```
+    pub fn liveness<N, F>(name: N, f: F) -> Check
+        where N: Into<Cow<'static, str>>,
+              F: for<'a> Fn(&'a Rocket<Orbit>) -> BoxFuture<'a, Result> + Send + Sync + 'static
+    {
+        Check::new(name.into(), Probe::Liveness, Box::new(f))
+    }
+
+    /// Constructs a readiness check named `name` that runs `f`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::health::Check;
+    ///
+    /// # async fn ping_cache() -> std::io::Result<()> { Ok(()) }
+    /// let check = Check::readiness("cache", |_| Box::pin(async move {
+    ///     ping_cache().await?;
+    ///     Ok(())
+    /// }));
+    /// ```
+    pub fn readiness<N, F>(name: N, f: F) -> Check
+        where N: Into<Cow<'static, str>>,
+              F: for<'a> Fn(&'a Rocket<Orbit>) -> BoxFuture<'a, Result> + Send + Sync + 'static
+    {
+        Check::new(name.into(), Probe::Readiness, Box::new(f))
+    }
+
+    fn new(name: Cow<'static, str>, probe: Probe, run: Runner) -> Check {
+        Check { name, probe, timeout: Check::DEFAULT_TIMEOUT, run }
+    }
+
+    /// Sets the duration after which the check fails if it hasn't completed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use rocket::health::Check;
+    ///
+    /// let check = Check::readiness("ping", |_| Box::pin(async move { Ok(()) }))
+    ///     .timeout(Duration::from_millis(500));
+    /// ```
+    pub fn timeout(mut self, timeout: Duration) -> Check {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns the name of the check.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::health::Check;
+    ///
+    /// let check = Check::readiness("ping", |_| Box::pin(async move { Ok(()) }));
+    /// assert_eq!(check.name(), "ping");
+    /// ```
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the probe the check contributes to.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::health::{Check, Probe};
+    ///
+    /// let check = Check::readiness("ping", |_| Box::pin(async move { Ok(()) }));
+    /// assert_eq!(check.probe(), Probe::Readiness);
+    /// ```
+    pub fn probe(&self) -> Probe {
+        self.probe
+    }
+
+    /// Runs the check, failing it if it times out.
+    pub(crate) async fn run(&self, rocket: &Rocket<Orbit>) -> Result {
+        match tokio::time::timeout(self.timeout, (self.run)(rocket)).await {
+            Ok(result) => result,
+            Err(_) => Err(format!("timed out after {:?}", self.timeout).into()),
+        }
+    }
+}
+
+impl std::fmt::Debug for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Check")
+            .field("name", &self.name)
+            .field("probe", &self.probe)
+            .field("timeout", &self.timeout)
+            .finish_non_exhaustive()
+    }
+}
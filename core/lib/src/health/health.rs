@@ -0,0 +1,175 @@
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+
+use futures::future::join_all;
+
+use crate::{Rocket, Request, Data, Build, Route};
+use crate::fairing::{self, Fairing, Info, Kind};
+use crate::http::{Method, Status};
+use crate::route::{Handler, Outcome};
+use crate::health::{Check, Probe};
+
+/// A fairing that serves liveness and readiness health checks.
+///
+/// On ignition, the fairing registers its checks, added with
+/// [`Health::check()`], alongside checks registered by components with
+/// [`Health::register()`], and mounts the `/healthz` and `/readyz` endpoints.
+/// See the [module level docs](crate::health) for details.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::health::{Health, Check};
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .attach(Health::new().check(Check::liveness("ping", |_| Box::pin(async move {
+///             Ok(())
+///         }))))
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct Health {
+    checks: Mutex<Vec<Check>>,
+}
+
+/// The health checks registered with an instance of Rocket.
+#[derive(Default)]
+struct Registry(Mutex<Vec<Arc<Check>>>);
+
+impl Registry {
+    fn checks(&self, probe: Probe) -> Vec<Arc<Check>> {
+        let checks = self.0.lock().expect("health registry lock");
+        checks.iter().filter(|c| c.probe == probe).cloned().collect()
+    }
+}
+
+/// The handler for a probe's endpoint.
+#[derive(Clone)]
+struct Endpoint(Probe);
+
+impl Health {
+    /// Returns a `Health` fairing with no checks of its own.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::health::Health;
+    ///
+    /// let rocket = rocket::build().attach(Health::new());
+    /// ```
+    pub fn new() -> Health {
+        Health::default()
+    }
+
+    /// Adds `check` to the checks registered by the fairing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::health::{Health, Check};
+    ///
+    /// let health = Health::new()
+    ///     .check(Check::readiness("ready", |_| Box::pin(async move { Ok(()) })))
+    ///     .check(Check::liveness("live", |_| Box::pin(async move { Ok(()) })));
+    /// ```
+    pub fn check(self, check: Check) -> Health {
+        self.checks.lock().expect("health checks lock").push(check);
+        self
+    }
+
+    /// Registers `check` with `rocket`. The check is served by the endpoints
+    /// of any attached `Health` fairing. This method is intended for use by
+    /// components, like database pool fairings, in their ignite callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::AdHoc;
+    /// use rocket::health::{Health, Check};
+    ///
+    /// let fairing = AdHoc::on_ignite("Cache", |rocket| async move {
+    ///     Health::register(rocket, Check::readiness("cache", |_| Box::pin(async move {
+    ///         Ok(())
+    ///     })))
+    /// });
+    /// ```
+    pub fn register(rocket: Rocket<Build>, check: Check) -> Rocket<Build> {
+        let rocket = match rocket.state::<Registry>() {
+            Some(_) => rocket,
+            None => rocket.manage(Registry::default()),
+        };
+
+        let registry = rocket.state::<Registry>().expect("managed registry");
+        registry.0.lock().expect("health registry lock").push(Arc::new(check));
+        rocket
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for Health {
+    fn info(&self) -> Info {
+        Info { name: "Health", kind: Kind::Ignite | Kind::Singleton }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let checks = std::mem::take(&mut *self.checks.lock().expect("health checks lock"));
+        let mut rocket = checks.into_iter().fold(rocket, Health::register);
+        if rocket.state::<Registry>().is_none() {
+            rocket = rocket.manage(Registry::default());
+        }
+
+        let routes = [(Probe::Liveness, "/healthz"), (Probe::Readiness, "/readyz")]
+            .into_iter()
+            .map(|(probe, path)| {
+                let mut route = Route::new(Method::Get, path, Endpoint(probe));
+                route.name = Some(format!("Health: {}", &path[1..]).into());
+                route
+            })
+            .collect::<Vec<_>>();
+
+        Ok(rocket.mount("/", routes))
+    }
+}
+
+#[crate::async_trait]
+impl Handler for Endpoint {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
+        let rocket = req.rocket();
+        let checks = rocket.state::<Registry>().map(|r| r.checks(self.0)).unwrap_or_default();
+        let results = join_all(checks.iter().map(|check| check.run(rocket))).await;
+
+        let mut body = String::new();
+        let mut healthy = true;
+        if self.0 == Probe::Readiness && rocket.shutdown().0.tripped() {
+            healthy = false;
+            body.push_str("[-]shutdown failed: server is shutting down\n");
+        }
+
+        for (check, result) in checks.iter().zip(results) {
+            match result {
+                Ok(()) => { let _ = writeln!(body, "[+]{} ok", check.name); }
+                Err(e) => {
+                    healthy = false;
+                    warn_!("Health check `{}` failed: {}", check.name, e);
+                    let _ = writeln!(body, "[-]{} failed: {}", check.name, e);
+                }
+            }
+        }
+
+        let name = match self.0 {
+            Probe::Liveness => "healthz",
+            Probe::Readiness => "readyz",
+        };
+
+        let (status, result) = match healthy {
+            true => (Status::Ok, "passed"),
+            false => (Status::ServiceUnavailable, "failed"),
+        };
+
+        let _ = write!(body, "{} check {}", name, result);
+        Outcome::from(req, (status, body))
+    }
+}
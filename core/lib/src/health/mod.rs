@@ -0,0 +1,62 @@
+//! Liveness and readiness health checks.
+//!
+//! The [`Health`] fairing mounts two endpoints that report on the health of an
+//! application, suitable for use as Kubernetes [liveness and readiness
+//! probes] or by load balancers:
+//!
+//!   * `GET /healthz` - **liveness**: succeeds unless a liveness [`Check`]
+//!     fails. A failing liveness probe indicates that the application should
+//!     be restarted.
+//!   * `GET /readyz` - **readiness**: succeeds unless a readiness [`Check`]
+//!     fails or the application is shutting down. A failing readiness probe
+//!     indicates that the application should not receive traffic.
+//!
+//! Each endpoint runs its checks concurrently, each subject to a
+//! [timeout](Check::timeout()), and responds with `200 OK` if all of them
+//! pass and `503 Service Unavailable` otherwise. The response body lists the
+//! result of every check:
+//!
+//! ```text
+//! [+]db ok
+//! [-]cache failed: connection refused
+//! readyz check failed
+//! ```
+//!
+//! [liveness and readiness probes]:
+//! https://kubernetes.io/docs/tasks/configure-pod-container/configure-liveness-readiness-startup-probes/
+//!
+//! # Usage
+//!
+//! Attach a [`Health`] fairing with any custom checks:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::health::{Health, Check};
+//!
+//! # async fn ping_cache() -> std::io::Result<()> { Ok(()) }
+//! #[launch]
+//! fn rocket() -> _ {
+//!     let health = Health::new()
+//!         .check(Check::readiness("cache", |_| Box::pin(async move {
+//!             ping_cache().await?;
+//!             Ok(())
+//!         })));
+//!
+//!     rocket::build().attach(health)
+//! }
+//! ```
+//!
+//! # Component Checks
+//!
+//! Components, like fairings that manage database pools, can register checks
+//! of their own during ignition with [`Health::register()`], regardless of
+//! whether or in which order a `Health` fairing is attached. Checks registered
+//! by a component are only served if a `Health` fairing is attached. The
+//! `rocket_db_pools`, `rocket_sync_db_pools`, and `rocket_dyn_templates`
+//! crates register readiness checks for their databases and template engines.
+
+mod check;
+mod health;
+
+pub use self::check::{Check, Probe, Result};
+pub use self::health::Health;
@@ -0,0 +1,157 @@
+//! The HTTP client used for Rocket's own outgoing requests: to ACME servers and
+//! to secret providers.
+
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use hyper::{Body, Method, Request, Uri, HeaderMap};
+use hyper::header::{HOST, USER_AGENT};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+use tokio_rustls::rustls::{ClientConfig, OwnedTrustAnchor, RootCertStore, Certificate};
+
+fn err<E: std::fmt::Display>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, error.to_string())
+}
+
+/// A response: its status, headers, and entire body.
+pub struct Response {
+    pub status: u16,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    /// Returns the value of the header `name`, if it is present and a string.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|v| v.to_str().ok())
+    }
+}
+
+/// An HTTP/1.1 client making each request over a new connection, using TLS
+/// for `https` URLs. Connecting, the TLS handshake, and each request and
+/// response are subject to a timeout.
+#[derive(Clone)]
+pub struct Client {
+    tls: Arc<ClientConfig>,
+}
+
+impl Client {
+    /// The timeout of each step of a request.
+    pub const TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// Returns a client trusting the CA certificates in the PEM file at
+    /// `ca_certs` or, if `None`, the web PKI roots.
+    pub fn new(ca_certs: Option<&Path>) -> io::Result<Client> {
+        let mut roots = RootCertStore::empty();
+        match ca_certs {
+            Some(path) => {
+                let file = std::fs::File::open(path)
+                    .map_err(|e| err(format!("failed to read CA bundle {}: {}", path.display(), e)))?;
+
+                let certs = rustls_pemfile::certs(&mut io::BufReader::new(file))
+                    .map_err(|_| err(format!("invalid CA bundle: {}", path.display())))?;
+
+                for cert in certs {
+                    roots.add(&Certificate(cert))
+                        .map_err(|e| err(format!("invalid CA bundle {}: {}", path.display(), e)))?;
+                }
+
+                if roots.is_empty() {
+                    return Err(err(format!("CA bundle {} has no certificates", path.display())));
+                }
+            }
+            None => roots.add_server_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.0.iter().map(|ta| {
+                OwnedTrustAnchor::from_subject_spki_name_constraints(
+                    ta.subject, ta.spki, ta.name_constraints
+                )
+            })),
+        }
+
+        let config = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+
+        Ok(Client { tls: Arc::new(config) })
+    }
+
+    /// Resolves `future` unless it takes longer than [`Client::TIMEOUT`] to do
+    /// `what`.
+    async fn timed<T, F>(&self, what: &str, future: F) -> io::Result<T>
+        where F: std::future::Future<Output = io::Result<T>>
+    {
+        match tokio::time::timeout(Client::TIMEOUT, future).await {
+            Ok(result) => result,
+            Err(_) => Err(io::Error::new(io::ErrorKind::TimedOut, format!("{} timed out", what))),
+        }
+    }
+
+    async fn send<I>(&self, io: I, request: Request<Body>) -> io::Result<Response>
+        where I: AsyncRead + AsyncWrite + Unpin + Send + 'static
+    {
+        let (mut sender, connection) = hyper::client::conn::handshake(io).await.map_err(err)?;
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                warn!("HTTP client connection error: {}", e);
+            }
+        });
+
+        let response = self.timed("request", async {
+            let (parts, body) = sender.send_request(request).await.map_err(err)?.into_parts();
+            let body = hyper::body::to_bytes(body).await.map_err(err)?;
+            Ok((parts, body))
+        });
+
+        let (parts, body) = response.await?;
+        Ok(Response { status: parts.status.as_u16(), headers: parts.headers, body: body.to_vec() })
+    }
+
+    /// Sends a `method` request with `headers` and `body` to `url`. `Host` and
+    /// `User-Agent` headers are set by the client.
+    pub async fn request(
+        &self,
+        method: Method,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: Vec<u8>,
+    ) -> io::Result<Response> {
+        let uri: Uri = url.parse().map_err(|e| err(format!("invalid URL `{}`: {}", url, e)))?;
+        let (host, authority) = match (uri.host(), uri.authority()) {
+            (Some(host), Some(authority)) => (host.to_string(), authority.to_string()),
+            _ => return Err(err(format!("URL `{}` is missing a host", url))),
+        };
+
+        let tls = match uri.scheme_str() {
+            Some("https") => true,
+            Some("http") => false,
+            _ => return Err(err(format!("URL `{}` is not HTTP(S)", url))),
+        };
+
+        let mut request = Request::builder()
+            .method(method)
+            .uri(uri.path_and_query().map_or("/", |p| p.as_str()))
+            .header(HOST, &authority)
+            .header(USER_AGENT, concat!("Rocket/", env!("CARGO_PKG_VERSION")));
+
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+
+        let request = request.body(Body::from(body)).map_err(err)?;
+        let port = uri.port_u16().unwrap_or(if tls { 443 } else { 80 });
+        let stream = self.timed("connection", TcpStream::connect((host.as_str(), port))).await?;
+        if !tls {
+            return self.send(stream, request).await;
+        }
+
+        let name = host.as_str().try_into()
+            .map_err(|_| err(format!("invalid server name `{}`", host)))?;
+
+        let connector = tokio_rustls::TlsConnector::from(self.tls.clone());
+        let stream = self.timed("TLS handshake", connector.connect(name, stream)).await?;
+        self.send(stream, request).await
+    }
+}
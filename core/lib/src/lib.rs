@@ -65,9 +65,19 @@
 //! | `tls`     | Support for [TLS] encrypted connections.                |
 //! | `mtls`    | Support for verified clients via [mutual TLS].          |
 //! | `http2`   | Support for HTTP/2 (enabled by default).                |
+//! | `http3`   | Support for HTTP/3 over QUIC alongside [TLS].           |
+//! | `acme`    | Support for [ACME] certificate provisioning with [TLS]. |
 //! | `json`    | Support for [JSON (de)serialization].                   |
 //! | `msgpack` | Support for [MessagePack (de)serialization].            |
+//! | `xml`     | Support for [XML (de)serialization].                    |
+//! | `cbor`    | Support for [CBOR (de)serialization].                   |
 //! | `uuid`    | Support for [UUID value parsing and (de)serialization]. |
+//! | `ws`      | Support for [WebSocket routes].                         |
+//! | `compression` | Support for [response compression].                 |
+//! | `tracing` | Support for per-request [`tracing`] spans.              |
+//! | `openapi` | Support for [OpenAPI document generation].              |
+//! | `vault`   | Support for [secrets] stored in HashiCorp Vault.        |
+//! | `aws-secrets` | Support for [secrets] in AWS Secrets Manager.       |
 //!
 //! Disabled features can be selectively enabled in `Cargo.toml`:
 //!
@@ -85,10 +95,18 @@
 //!
 //! [JSON (de)serialization]: crate::serde::json
 //! [MessagePack (de)serialization]: crate::serde::msgpack
+//! [XML (de)serialization]: crate::serde::xml
+//! [CBOR (de)serialization]: crate::serde::cbor
 //! [UUID value parsing and (de)serialization]: crate::serde::uuid
 //! [private cookies]: https://rocket.rs/v0.5-rc/guide/requests/#private-cookies
 //! [TLS]: https://rocket.rs/v0.5-rc/guide/configuration/#tls
 //! [mutual TLS]: crate::mtls
+//! [ACME]: crate::acme
+//! [secrets]: crate::config::SecretProvider
+//! [WebSocket routes]: crate::ws
+//! [response compression]: crate::compression
+//! [`tracing`]: crate::Request::span()
+//! [OpenAPI document generation]: crate::openapi
 //!
 //! ## Configuration
 //!
@@ -115,6 +133,9 @@ pub use futures;
 pub use tokio;
 pub use figment;
 pub use time;
+#[cfg(feature = "tracing")]
+#[cfg_attr(nightly, doc(cfg(feature = "tracing")))]
+pub use tracing_ as tracing;
 
 #[doc(hidden)]
 #[macro_use] pub mod log;
@@ -132,6 +153,9 @@ pub mod catcher;
 pub mod route;
 pub mod serde;
 pub mod shield;
+pub mod health;
+pub mod schedule;
+pub mod cors;
 pub mod fs;
 
 // Reexport of HTTP everything.
@@ -164,9 +188,38 @@ pub mod http {
 #[cfg_attr(nightly, doc(cfg(feature = "mtls")))]
 pub mod mtls;
 
+#[cfg(feature = "acme")]
+#[cfg_attr(nightly, doc(cfg(feature = "acme")))]
+pub mod acme;
+
+#[cfg(feature = "ws")]
+#[cfg_attr(nightly, doc(cfg(feature = "ws")))]
+pub mod ws;
+
+#[cfg(feature = "compression")]
+#[cfg_attr(nightly, doc(cfg(feature = "compression")))]
+pub mod compression;
+
+#[cfg(feature = "secrets")]
+#[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
+pub mod csrf;
+
+#[cfg(feature = "openapi")]
+#[cfg_attr(nightly, doc(cfg(feature = "openapi")))]
+pub mod openapi;
+
+/// Without the `openapi` feature, route codegen records no OpenAPI operations.
+#[cfg(not(feature = "openapi"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __openapi_operation {
+    ($($tokens:tt)*) => (())
+}
+
 /// TODO: We need a futures mod or something.
 mod trip_wire;
 mod shutdown;
+mod task;
 mod server;
 mod ext;
 mod state;
@@ -175,6 +228,9 @@ mod rocket;
 mod router;
 mod phase;
 
+#[cfg(any(feature = "acme", feature = "vault", feature = "aws-secrets"))]
+mod http_client;
+
 #[doc(inline)] pub use crate::response::Response;
 #[doc(inline)] pub use crate::data::Data;
 #[doc(inline)] pub use crate::config::Config;
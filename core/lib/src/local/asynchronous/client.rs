@@ -3,6 +3,8 @@ use std::fmt;
 use parking_lot::RwLock;
 
 use crate::{Rocket, Phase, Orbit, Ignite, Error};
+use crate::local::GuardOverrides;
+use crate::request::Scope;
 use crate::local::asynchronous::{LocalRequest, LocalResponse};
 use crate::http::{Method, uri::Origin, private::cookie};
 
@@ -49,6 +51,8 @@ use crate::http::{Method, uri::Origin, private::cookie};
 pub struct Client {
     rocket: Rocket<Orbit>,
     cookies: RwLock<cookie::CookieJar>,
+    guard_overrides: GuardOverrides,
+    scope_overrides: Scope,
     pub(in super) tracked: bool,
 }
 
@@ -59,7 +63,9 @@ impl Client {
     ) -> Result<Client, Error> {
         let rocket = rocket.local_launch().await?;
         let cookies = RwLock::new(cookie::CookieJar::new());
-        Ok(Client { rocket, cookies, tracked })
+        let guard_overrides = GuardOverrides::default();
+        let scope_overrides = Scope::default();
+        Ok(Client { rocket, cookies, guard_overrides, scope_overrides, tracked })
     }
 
     // WARNING: This is unstable! Do not use this method outside of Rocket!
@@ -81,6 +87,16 @@ impl Client {
         &self.rocket
     }
 
+    #[inline(always)]
+    pub(crate) fn _guard_overrides(&self) -> &GuardOverrides {
+        &self.guard_overrides
+    }
+
+    #[inline(always)]
+    pub(crate) fn _scope_overrides(&self) -> &Scope {
+        &self.scope_overrides
+    }
+
     #[inline(always)]
     pub(crate) fn _with_raw_cookies<F, T>(&self, f: F) -> T
         where F: FnOnce(&cookie::CookieJar) -> T
@@ -106,6 +122,11 @@ impl Client {
         let rocket = self.rocket;
         rocket.shutdown().notify();
         rocket.fairings.handle_shutdown(&rocket).await;
+
+        let shutdown = &rocket.config.shutdown;
+        let deadline = shutdown.grace as u64 + shutdown.mercy as u64;
+        let deadline = tokio::time::sleep(std::time::Duration::from_secs(deadline));
+        rocket.tasks.join(deadline).await;
         rocket.into_ignite()
     }
 
@@ -9,7 +9,9 @@
 mod client;
 mod request;
 mod response;
+mod upgrade;
 
 pub use client::*;
 pub use request::*;
 pub use response::*;
+pub use upgrade::*;
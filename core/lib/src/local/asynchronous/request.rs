@@ -49,6 +49,8 @@ impl<'c> LocalRequest<'c> {
         // Create a request. We'll handle bad URIs later, in `_dispatch`.
         let origin = try_origin.clone().unwrap_or_else(|bad| bad);
         let mut request = Request::new(client.rocket(), method, origin);
+        request.state.guard_overrides = Some(client._guard_overrides());
+        request.scope().override_with(client._scope_overrides());
 
         // Add any cookies we know about.
         if client.tracked {
@@ -121,6 +123,71 @@ impl<'c> LocalRequest<'c> {
 
     pub_request_impl!("# use rocket::local::asynchronous::Client;\n\
         use rocket::local::asynchronous::LocalRequest;" async await);
+    /// Dispatches the request as a WebSocket upgrade request, returning a
+    /// client WebSocket connection to the route if the upgrade succeeds and
+    /// the response otherwise.
+    ///
+    /// The `Connection`, `Upgrade`, `Sec-WebSocket-Version`, and
+    /// `Sec-WebSocket-Key` headers of the request are set before dispatch. The
+    /// upgrade succeeds if the response has a status of `101 Switching
+    /// Protocols` and a valid `Sec-WebSocket-Accept` header, as does the
+    /// response of a [WebSocket route](crate::ws). No TCP connection is made:
+    /// messages are exchanged in-memory. See [`LocalWebSocket`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::futures::{SinkExt, StreamExt};
+    /// use rocket::local::asynchronous::Client;
+    /// use rocket::ws::{WebSocket, Channel, Message};
+    ///
+    /// #[get("/echo")]
+    /// fn echo(ws: WebSocket) -> Channel<'static> {
+    ///     ws.channel(|mut stream| Box::pin(async move {
+    ///         while let Some(message) = stream.next().await {
+    ///             stream.send(message?).await?;
+    ///         }
+    ///
+    ///         Ok(())
+    ///     }))
+    /// }
+    ///
+    /// # rocket::async_test(async {
+    /// let client = Client::debug_with(routes![echo]).await.unwrap();
+    /// let mut ws = client.get("/echo").websocket().await.expect("upgraded");
+    /// ws.send(Message::text("hi")).await.unwrap();
+    /// assert_eq!(ws.next().await.unwrap().unwrap(), Message::text("hi"));
+    /// ws.close(None).await.unwrap();
+    /// # });
+    /// ```
+    #[cfg(feature = "ws")]
+    #[cfg_attr(nightly, doc(cfg(feature = "ws")))]
+    pub async fn websocket(self) -> Result<super::LocalWebSocket<'c>, LocalResponse<'c>> {
+        use crate::http::Header;
+        use tokio_tungstenite::WebSocketStream;
+        use tokio_tungstenite::tungstenite::protocol::Role;
+        use tokio_tungstenite::tungstenite::handshake::{client::generate_key, derive_accept_key};
+
+        let key = generate_key();
+        let response = self
+            .header(Header::new("Connection", "Upgrade"))
+            .header(Header::new("Upgrade", "websocket"))
+            .header(Header::new("Sec-WebSocket-Version", "13"))
+            .header(Header::new("Sec-WebSocket-Key", key.clone()))
+            ._dispatch()
+            .await;
+
+        let accept = response.headers().get_one("Sec-WebSocket-Accept");
+        if response.status() != Status::SwitchingProtocols
+            || accept != Some(&derive_accept_key(key.as_bytes()))
+        {
+            return Err(response);
+        }
+
+        let io = response.into_upgraded()?;
+        Ok(WebSocketStream::from_raw_socket(io, Role::Client, None).await)
+    }
 }
 
 impl<'c> Clone for LocalRequest<'c> {
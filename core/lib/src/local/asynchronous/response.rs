@@ -4,9 +4,12 @@ use std::{pin::Pin, task::{Context, Poll}};
 
 use tokio::io::{AsyncRead, ReadBuf};
 
-use crate::http::CookieJar;
+use crate::http::{CookieJar, Status};
+use crate::data::IoHandler;
 use crate::{Request, Response};
 
+use super::LocalUpgraded;
+
 /// An `async` response from a dispatched [`LocalRequest`](super::LocalRequest).
 ///
 /// This `LocalResponse` implements [`tokio::io::AsyncRead`]. As such, if
@@ -56,6 +59,7 @@ pub struct LocalResponse<'c> {
     _request: Box<Request<'c>>,
     response: Response<'c>,
     cookies: CookieJar<'c>,
+    upgrade: Option<Pin<Box<dyn IoHandler + 'c>>>,
 }
 
 impl<'c> LocalResponse<'c> {
@@ -87,13 +91,100 @@ impl<'c> LocalResponse<'c> {
         let request: &'c Request<'c> = unsafe { &*(&*boxed_req as *const _) };
 
         async move {
-            let response: Response<'c> = f(request).await;
+            let mut response: Response<'c> = f(request).await;
             let mut cookies = CookieJar::new(request.rocket().config());
             for cookie in response.cookies() {
                 cookies.add_original(cookie.into_owned());
             }
 
-            LocalResponse { cookies, _request: boxed_req, response, }
+            // As the server would, switch protocols if the request asks for an
+            // upgrade that the response has an I/O handler for.
+            let upgrade = match response.take_upgrade(request.headers().get("upgrade")) {
+                Ok(Some((proto, handler))) => {
+                    response.set_status(Status::SwitchingProtocols);
+                    response.set_raw_header("Connection", "Upgrade");
+                    response.set_raw_header("Upgrade", proto.into_cow());
+                    Some(handler)
+                }
+                Ok(None) => None,
+                Err(()) => {
+                    warn_!("Request wants upgrade but no I/O handler matched.");
+                    info_!("Request is not being upgraded.");
+                    None
+                }
+            };
+
+            LocalResponse { cookies, _request: boxed_req, response, upgrade }
+        }
+    }
+
+    /// Completes the connection upgrade of a `101 Switching Protocols`
+    /// response, returning the upgraded connection to the I/O handler of the
+    /// response, or `self` if the response wasn't upgraded.
+    ///
+    /// A response is upgraded when the request asks to upgrade the connection
+    /// via an `Upgrade` header and the response has an
+    /// [`IoHandler`](crate::data::IoHandler) for one of the requested
+    /// protocols. See [`LocalUpgraded`] for details on driving the handler.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use std::{io, pin::Pin};
+    ///
+    /// use rocket::{Request, Response};
+    /// use rocket::data::{IoHandler, IoStream};
+    /// use rocket::http::{Header, Status};
+    /// use rocket::local::asynchronous::Client;
+    /// use rocket::response::{self, Responder};
+    /// use rocket::tokio::io::{self as tokio_io, AsyncReadExt, AsyncWriteExt};
+    ///
+    /// struct Echo;
+    ///
+    /// #[rocket::async_trait]
+    /// impl IoHandler for Echo {
+    ///     async fn io(self: Pin<Box<Self>>, io: IoStream) -> io::Result<()> {
+    ///         let (mut reader, mut writer) = tokio_io::split(io);
+    ///         tokio_io::copy(&mut reader, &mut writer).await?;
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// impl<'r> Responder<'r, 'static> for Echo {
+    ///     fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+    ///         Response::build().upgrade("echo", self).ok()
+    ///     }
+    /// }
+    ///
+    /// #[get("/echo")]
+    /// fn echo() -> Echo {
+    ///     Echo
+    /// }
+    ///
+    /// # rocket::async_test(async {
+    /// let client = Client::debug_with(routes![echo]).await.unwrap();
+    /// let response = client.get("/echo")
+    ///     .header(Header::new("Connection", "upgrade"))
+    ///     .header(Header::new("Upgrade", "echo"))
+    ///     .dispatch()
+    ///     .await;
+    ///
+    /// assert_eq!(response.status(), Status::SwitchingProtocols);
+    ///
+    /// let mut io = response.into_upgraded().expect("upgraded");
+    /// io.write_all(b"hello").await.unwrap();
+    /// io.shutdown().await.unwrap();
+    ///
+    /// let mut echoed = String::new();
+    /// io.read_to_string(&mut echoed).await.unwrap();
+    /// assert_eq!(echoed, "hello");
+    /// # });
+    /// ```
+    pub fn into_upgraded(mut self) -> Result<LocalUpgraded<'c>, LocalResponse<'c>> {
+        match self.upgrade.take() {
+            Some(handler) => Ok(LocalUpgraded::new(self._request, handler)),
+            None => Err(self),
         }
     }
 }
@@ -129,7 +220,21 @@ impl LocalResponse<'_> {
         self.blocking_read(|r| rmp_serde::from_read(r)).await?.ok()
     }
 
-    #[cfg(any(feature = "json", feature = "msgpack"))]
+    #[cfg(feature = "cbor")]
+    async fn _into_cbor<T: Send + 'static>(self) -> Option<T>
+        where T: serde::de::DeserializeOwned
+    {
+        self.blocking_read(|r| ciborium::from_reader(r)).await?.ok()
+    }
+
+    #[cfg(feature = "xml")]
+    async fn _into_xml<T: Send + 'static>(self) -> Option<T>
+        where T: serde::de::DeserializeOwned
+    {
+        self.blocking_read(|r| quick_xml::de::from_reader(io::BufReader::new(r))).await?.ok()
+    }
+
+    #[cfg(any(feature = "json", feature = "msgpack", feature = "cbor", feature = "xml"))]
     async fn blocking_read<T, F>(mut self, f: F) -> Option<T>
         where T: Send + 'static,
               F: FnOnce(&mut dyn io::Read) -> T + Send + 'static
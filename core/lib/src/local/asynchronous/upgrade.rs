@@ -0,0 +1,112 @@
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::future::BoxFuture;
+use tokio::io::{AsyncRead, AsyncWrite, DuplexStream, ReadBuf};
+
+use crate::Request;
+use crate::data::IoHandler;
+
+/// An upgraded connection to an [`IoHandler`], as returned by
+/// [`LocalResponse::into_upgraded()`](super::LocalResponse::into_upgraded()).
+///
+/// A `LocalUpgraded` is the client's end of an in-memory stream whose other
+/// end is passed to the response's I/O handler. It implements
+/// [`AsyncRead`] and [`AsyncWrite`]; bytes written are read by the handler and
+/// vice-versa.
+///
+/// The handler does not run in the background: it makes progress only while
+/// the `LocalUpgraded` is being read from, written to, flushed, or shut down.
+/// As such, the handler stops running when the `LocalUpgraded` is dropped.
+/// Errors returned by the handler are logged as they would be by the server.
+pub struct LocalUpgraded<'c> {
+    io: DuplexStream,
+    handler: Option<BoxFuture<'c, io::Result<()>>>,
+    // Declared last so it's dropped after `handler`, which may refer to it.
+    _request: Box<Request<'c>>,
+}
+
+impl<'c> LocalUpgraded<'c> {
+    /// The maximum number of bytes buffered in each direction.
+    const BUFFER_SIZE: usize = 64 * 1024;
+
+    pub(crate) fn new(request: Box<Request<'c>>, handler: Pin<Box<dyn IoHandler + 'c>>) -> Self {
+        let (io, handler_io) = tokio::io::duplex(Self::BUFFER_SIZE);
+        let handler = handler.io(handler_io.into());
+        LocalUpgraded { io, handler: Some(handler), _request: request }
+    }
+
+    /// Polls the I/O handler, if it hasn't yet completed, logging its error
+    /// once it completes with one.
+    fn poll_handler(&mut self, cx: &mut Context<'_>) {
+        if let Some(handler) = self.handler.as_mut() {
+            if let Poll::Ready(result) = handler.as_mut().poll(cx) {
+                self.handler = None;
+                match result {
+                    Ok(()) => {}
+                    Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {
+                        warn!("Upgraded I/O handler was closed.");
+                    }
+                    Err(e) => error!("Upgraded I/O handler failed: {}", e),
+                }
+            }
+        }
+    }
+}
+
+impl AsyncRead for LocalUpgraded<'_> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        this.poll_handler(cx);
+        Pin::new(&mut this.io).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for LocalUpgraded<'_> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        this.poll_handler(cx);
+        Pin::new(&mut this.io).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        this.poll_handler(cx);
+        Pin::new(&mut this.io).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        this.poll_handler(cx);
+        Pin::new(&mut this.io).poll_shutdown(cx)
+    }
+}
+
+impl std::fmt::Debug for LocalUpgraded<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalUpgraded")
+            .field("io", &self.io)
+            .field("completed", &self.handler.is_none())
+            .finish()
+    }
+}
+
+/// A client WebSocket connection to a route, as returned by
+/// [`LocalRequest::websocket()`](super::LocalRequest::websocket()).
+///
+/// The connection implements [`Stream`](futures::stream::Stream) of incoming
+/// `Result<Message>` and [`Sink`](futures::sink::Sink) of outgoing
+/// [`Message`](crate::ws::Message)s. Like a [`LocalUpgraded`], the route's
+/// WebSocket handler makes progress only while the connection is used.
+#[cfg(feature = "ws")]
+#[cfg_attr(nightly, doc(cfg(feature = "ws")))]
+pub type LocalWebSocket<'c> = tokio_tungstenite::WebSocketStream<LocalUpgraded<'c>>;
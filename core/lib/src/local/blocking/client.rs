@@ -2,7 +2,8 @@ use std::fmt;
 use std::cell::RefCell;
 
 use crate::{Rocket, Phase, Orbit, Ignite, Error};
-use crate::local::{asynchronous, blocking::{LocalRequest, LocalResponse}};
+use crate::local::{asynchronous, GuardOverrides, blocking::{LocalRequest, LocalResponse}};
+use crate::request::Scope;
 use crate::http::{Method, uri::Origin};
 
 /// A `blocking` client to construct and dispatch local requests.
@@ -71,6 +72,16 @@ impl Client {
         self.inner()._rocket()
     }
 
+    #[inline(always)]
+    fn _guard_overrides(&self) -> &GuardOverrides {
+        self.inner()._guard_overrides()
+    }
+
+    #[inline(always)]
+    fn _scope_overrides(&self) -> &Scope {
+        self.inner()._scope_overrides()
+    }
+
     #[inline(always)]
     pub(crate) fn _with_raw_cookies<F, T>(&self, f: F) -> T
         where F: FnOnce(&crate::http::private::cookie::CookieJar) -> T
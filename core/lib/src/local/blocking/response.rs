@@ -85,6 +85,20 @@ impl LocalResponse<'_> {
         rmp_serde::from_read(self).ok()
     }
 
+    #[cfg(feature = "cbor")]
+    fn _into_cbor<T: Send + 'static>(self) -> Option<T>
+        where T: serde::de::DeserializeOwned
+    {
+        ciborium::from_reader(self).ok()
+    }
+
+    #[cfg(feature = "xml")]
+    fn _into_xml<T: Send + 'static>(self) -> Option<T>
+        where T: serde::de::DeserializeOwned
+    {
+        quick_xml::de::from_reader(io::BufReader::new(self)).ok()
+    }
+
     // Generates the public API methods, which call the private methods above.
     pub_response_impl!("# use rocket::local::blocking::Client;\n\
         use rocket::local::blocking::LocalResponse;");
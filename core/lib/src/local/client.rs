@@ -186,6 +186,168 @@ macro_rules! pub_client_impl {
         crate::http::CookieJar::from(jar, config)
     }
 
+    /// Overrides request guards of type `T` in routes handling requests
+    /// dispatched by this client to succeed with a clone of `value` instead of
+    /// running `T`'s [`FromRequest`](crate::request::FromRequest)
+    /// implementation.
+    ///
+    /// This allows testing handlers behind guards, such as authentication
+    /// guards, without crafting the cookies or headers the guards require.
+    /// The override replaces any previous override for `T` and applies until
+    /// it's removed with [`reset_guard()`](Self::reset_guard()).
+    ///
+    /// # Limitations
+    ///
+    /// Overrides apply to route parameters whose type is exactly `T`. They do
+    /// **not** apply to `T`'s [`FromRequest`](crate::request::FromRequest)
+    /// implementation wherever else it runs. Overriding a `User` guard thus
+    /// does _not_ override:
+    ///
+    ///   * `Option<User>` or `Result<User, E>` guards. Override these types
+    ///     separately, as in `client.override_guard(Some(user))`.
+    ///   * a `User` retrieved via [`Request::guard()`](crate::Request::guard())
+    ///     by a handler or by another guard, such as an `Admin` guard that
+    ///     requires a `User`. Override the outer guard instead.
+    ///
+    /// Guards whose types contain lifetimes or references can't be overridden.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    #[doc = $import]
+    /// use rocket::request::{self, Request, FromRequest};
+    /// use rocket::http::Status;
+    ///
+    /// #[derive(Clone)]
+    /// struct User(String);
+    ///
+    /// #[rocket::async_trait]
+    /// impl<'r> FromRequest<'r> for User {
+    ///     type Error = ();
+    ///
+    ///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+    ///         /* validate a session cookie */
+    ///         # request::Outcome::Failure((Status::Unauthorized, ()))
+    ///     }
+    /// }
+    ///
+    /// #[get("/")]
+    /// fn index(user: User) -> String {
+    ///     format!("Hello, {}!", user.0)
+    /// }
+    ///
+    /// # Client::_test(|client, _, _| {
+    /// let client: &Client = client;
+    /// client.override_guard(User("Bob".into()));
+    ///
+    /// // Requests to `/` dispatched by `client` now respond `Hello, Bob!`.
+    /// # });
+    /// ```
+    pub fn override_guard<T>(&self, value: T)
+        where T: for<'r> crate::request::FromRequest<'r> + Clone + Send + Sync + 'static
+    {
+        self._guard_overrides().succeed(value);
+    }
+
+    /// Overrides request guards of type `T` in routes handling requests
+    /// dispatched by this client to forward instead of running `T`'s
+    /// [`FromRequest`](crate::request::FromRequest) implementation.
+    ///
+    /// The override replaces any previous override for `T`. See
+    /// [`override_guard()`](Self::override_guard()) for which guards can be
+    /// overridden.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    #[doc = $import]
+    /// use rocket::http::Method;
+    ///
+    /// # Client::_test(|client, _, _| {
+    /// let client: &Client = client;
+    /// client.forward_guard::<Method>();
+    /// # });
+    /// ```
+    pub fn forward_guard<T>(&self)
+        where T: for<'r> crate::request::FromRequest<'r> + 'static
+    {
+        self._guard_overrides().forward::<T>();
+    }
+
+    /// Removes the override, if any, of request guards of type `T` set by
+    /// [`override_guard()`](Self::override_guard()) or
+    /// [`forward_guard()`](Self::forward_guard()).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    #[doc = $import]
+    /// use rocket::http::Method;
+    ///
+    /// # Client::_test(|client, _, _| {
+    /// let client: &Client = client;
+    /// client.override_guard(Method::Post);
+    /// client.reset_guard::<Method>();
+    /// # });
+    /// ```
+    pub fn reset_guard<T: 'static>(&self) {
+        self._guard_overrides().reset::<T>();
+    }
+
+    /// Provides `value` as an [override](crate::request::Scope#overrides) in
+    /// the [`Scope`](crate::request::Scope) of every request subsequently
+    /// created by this client.
+    ///
+    /// Fairings and guards that [`provide`](crate::request::Scope::provide())
+    /// a value of type `T` can't replace the override, so guards and handlers
+    /// that resolve `T` observe `value`. The override replaces any previous override for `T` and
+    /// applies until it's removed with [`reset_scoped()`](Self::reset_scoped()).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    #[doc = $import]
+    /// use rocket::request::Scoped;
+    ///
+    /// struct Tenant(&'static str);
+    ///
+    /// #[get("/")]
+    /// fn index(tenant: Scoped<Tenant>) -> &'static str {
+    ///     tenant.0
+    /// }
+    ///
+    /// # Client::_test(|client, _, _| {
+    /// let client: &Client = client;
+    /// client.override_scoped(Tenant("test-tenant"));
+    ///
+    /// // Requests to `/` dispatched by `client` now respond `test-tenant`.
+    /// # });
+    /// ```
+    pub fn override_scoped<T: Send + Sync + 'static>(&self, value: T) {
+        self._scope_overrides().provide_override(value);
+    }
+
+    /// Removes the scope override, if any, of type `T` set by
+    /// [`override_scoped()`](Self::override_scoped()). Requests created
+    /// before the override is removed retain it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    #[doc = $import]
+    ///
+    /// # Client::_test(|client, _, _| {
+    /// let client: &Client = client;
+    /// client.override_scoped(String::from("test-tenant"));
+    /// client.reset_scoped::<String>();
+    /// # });
+    /// ```
+    pub fn reset_scoped<T: 'static>(&self) {
+        self._scope_overrides().remove::<T>();
+    }
+
     req_method!($import, "GET", get, Method::Get);
     req_method!($import, "PUT", put, Method::Put);
     req_method!($import, "POST", post, Method::Post);
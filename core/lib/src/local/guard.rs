@@ -0,0 +1,48 @@
+//! Overrides of request guard outcomes for local requests.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+use parking_lot::RwLock;
+
+type Factory = Box<dyn Fn() -> Box<dyn Any + Send> + Send + Sync>;
+
+/// The outcome a guard is overridden with.
+enum GuardOverride {
+    Success(Factory),
+    Forward,
+}
+
+/// The request guard overrides of a local client, keyed by guard type.
+#[derive(Default)]
+pub(crate) struct GuardOverrides(RwLock<HashMap<TypeId, GuardOverride>>);
+
+impl GuardOverrides {
+    /// Overrides guards of type `T` to succeed with a clone of `value`.
+    pub fn succeed<T: Clone + Send + Sync + 'static>(&self, value: T) {
+        let factory: Factory = Box::new(move || Box::new(value.clone()));
+        self.0.write().insert(TypeId::of::<T>(), GuardOverride::Success(factory));
+    }
+
+    /// Overrides guards of type `T` to forward.
+    pub fn forward<T: 'static>(&self) {
+        self.0.write().insert(TypeId::of::<T>(), GuardOverride::Forward);
+    }
+
+    /// Removes the override, if any, for guards of type `T`.
+    pub fn reset<T: 'static>(&self) {
+        self.0.write().remove(&TypeId::of::<T>());
+    }
+
+    /// Returns the override for guards of type `T`, if any: `Some(value)` if
+    /// the guard succeeds with `value`, `None` if it forwards.
+    pub fn get<T: 'static>(&self) -> Option<Option<T>> {
+        match self.0.read().get(&TypeId::of::<T>())? {
+            GuardOverride::Success(factory) => {
+                let value = factory().downcast::<T>().expect("override is of type T");
+                Some(Some(*value))
+            }
+            GuardOverride::Forward => Some(None),
+        }
+    }
+}
@@ -182,6 +182,10 @@
 #[macro_use] mod client;
 #[macro_use] mod request;
 #[macro_use] mod response;
+mod multipart;
+mod guard;
+
+pub(crate) use guard::GuardOverrides;
 
 pub mod asynchronous;
 pub mod blocking;
@@ -0,0 +1,74 @@
+//! Construction of `multipart/form-data` bodies for local requests.
+
+use rand::Rng;
+use rand::distributions::Alphanumeric;
+
+use crate::Request;
+use crate::http::ContentType;
+
+/// Returns a new random boundary.
+pub(crate) fn new_boundary() -> String {
+    let suffix: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(24)
+        .map(char::from)
+        .collect();
+
+    format!("------------------------{}", suffix)
+}
+
+/// Returns the `multipart/form-data` Content-Type with the boundary `boundary`.
+pub(crate) fn content_type(boundary: &str) -> ContentType {
+    // Not `ContentType::FormData`: headers from known types omit parameters.
+    ContentType::new("multipart", "form-data").with_params(("boundary", boundary.to_string()))
+}
+
+/// Returns the boundary of `req`'s Content-Type if it is `multipart/form-data`.
+pub(crate) fn boundary(req: &Request<'_>) -> Option<String> {
+    req.content_type()
+        .filter(|ct| ct.is_form_data())
+        .and_then(|ct| ct.param("boundary"))
+        .map(|boundary| boundary.to_string())
+}
+
+/// Returns the delimiter closing a multipart body with the boundary `boundary`.
+pub(crate) fn closing_delimiter(boundary: &str) -> Vec<u8> {
+    format!("--{}--\r\n", boundary).into_bytes()
+}
+
+/// Appends a part to the multipart `body` with the boundary `boundary`,
+/// keeping the closing delimiter at the end of the body.
+pub(crate) fn append_part(
+    body: &mut Vec<u8>,
+    boundary: &str,
+    name: &str,
+    file_name: Option<&str>,
+    content_type: Option<&str>,
+    data: &[u8],
+) {
+    /// Percent-encodes quotes and newlines, as browsers do.
+    fn escape(value: &str) -> String {
+        value.replace('"', "%22").replace('\r', "%0D").replace('\n', "%0A")
+    }
+
+    let closing = closing_delimiter(boundary);
+    if body.ends_with(&closing) {
+        body.truncate(body.len() - closing.len());
+    }
+
+    let mut head = format!("--{}\r\n", boundary);
+    head.push_str(&format!("Content-Disposition: form-data; name=\"{}\"", escape(name)));
+    if let Some(file_name) = file_name {
+        head.push_str(&format!("; filename=\"{}\"", escape(file_name)));
+    }
+
+    if let Some(content_type) = content_type {
+        head.push_str(&format!("\r\nContent-Type: {}", content_type));
+    }
+
+    body.extend_from_slice(head.as_bytes());
+    body.extend_from_slice(b"\r\n\r\n");
+    body.extend_from_slice(data);
+    body.extend_from_slice(b"\r\n");
+    body.extend_from_slice(&closing);
+}
@@ -191,6 +191,31 @@ macro_rules! pub_request_impl {
         self
     }
 
+    /// Add a [signed cookie] to this request.
+    ///
+    /// [signed cookie]: crate::http::CookieJar::add_signed()
+    ///
+    /// # Examples
+    ///
+    /// Add `user_id` as a signed cookie:
+    ///
+    /// ```rust
+    #[doc = $import]
+    /// use rocket::http::Cookie;
+    ///
+    /// # Client::_test(|_, request, _| {
+    /// let request: LocalRequest = request;
+    /// let req = request.signed_cookie(Cookie::new("user_id", "sb"));
+    /// # });
+    /// ```
+    #[cfg(feature = "secrets")]
+    #[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
+    #[inline]
+    pub fn signed_cookie(mut self, cookie: crate::http::Cookie<'static>) -> Self {
+        self._request_mut().cookies_mut().add_original_signed(cookie);
+        self
+    }
+
     /// Sets the body data of the request.
     ///
     /// # Examples
@@ -285,6 +310,78 @@ macro_rules! pub_request_impl {
         self.header(crate::http::ContentType::MsgPack).body(msgpack)
     }
 
+    /// Sets the body to `value` serialized as CBOR with `Content-Type`
+    /// [`ContentType::CBOR`](crate::http::ContentType::CBOR).
+    ///
+    /// If `value` fails to serialize, the body is set to empty. The
+    /// `Content-Type` header is _always_ set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    #[doc = $import]
+    /// use rocket::serde::Serialize;
+    /// use rocket::http::ContentType;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Task {
+    ///     id: usize,
+    ///     complete: bool,
+    /// }
+    ///
+    /// # Client::_test(|_, request, _| {
+    /// let task = Task { id: 10, complete: false };
+    ///
+    /// let request: LocalRequest = request;
+    /// let req = request.cbor(&task);
+    /// assert_eq!(req.content_type(), Some(&ContentType::CBOR));
+    /// # });
+    /// ```
+    #[cfg(feature = "cbor")]
+    #[cfg_attr(nightly, doc(cfg(feature = "cbor")))]
+    pub fn cbor<T: crate::serde::Serialize>(self, value: &T) -> Self {
+        let mut cbor = vec![];
+        if ciborium::into_writer(value, &mut cbor).is_err() {
+            cbor.clear();
+        }
+
+        self.header(crate::http::ContentType::CBOR).body(cbor)
+    }
+
+    /// Sets the body to `value` serialized as XML with `Content-Type`
+    /// [`ContentType::XML`](crate::http::ContentType::XML).
+    ///
+    /// If `value` fails to serialize, the body is set to empty. The
+    /// `Content-Type` header is _always_ set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    #[doc = $import]
+    /// use rocket::serde::Serialize;
+    /// use rocket::http::ContentType;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Task {
+    ///     id: usize,
+    ///     complete: bool,
+    /// }
+    ///
+    /// # Client::_test(|_, request, _| {
+    /// let task = Task { id: 10, complete: false };
+    ///
+    /// let request: LocalRequest = request;
+    /// let req = request.xml(&task);
+    /// assert_eq!(req.content_type(), Some(&ContentType::XML));
+    /// # });
+    /// ```
+    #[cfg(feature = "xml")]
+    #[cfg_attr(nightly, doc(cfg(feature = "xml")))]
+    pub fn xml<T: crate::serde::Serialize>(self, value: &T) -> Self {
+        let xml = crate::serde::xml::to_string(value).unwrap_or_default();
+        self.header(crate::http::ContentType::XML).body(xml)
+    }
+
     /// Set the body (data) of the request without consuming `self`.
     ///
     /// # Examples
@@ -306,6 +403,124 @@ macro_rules! pub_request_impl {
         *self._body_mut() = body.as_ref().into();
     }
 
+    /// Sets the body to an empty `multipart/form-data` form and the
+    /// `Content-Type` to [`ContentType::FormData`](crate::http::ContentType::FormData)
+    /// with a random boundary. Fields are added with
+    /// [`text()`](Self::text()), [`file()`](Self::file()), and
+    /// [`named_file()`](Self::named_file()).
+    ///
+    /// Calling this method is only necessary to dispatch a form without
+    /// fields or to discard previously added fields: adding a field to a
+    /// request without a `multipart/form-data` body starts a new form.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    #[doc = $import]
+    /// use rocket::http::ContentType;
+    ///
+    /// # Client::_test(|_, request, _| {
+    /// let request: LocalRequest = request;
+    /// let req = request.multipart()
+    ///     .text("name", "Bob")
+    ///     .file("photo", [0x89, b'P', b'N', b'G'], ContentType::PNG);
+    ///
+    /// assert!(req.content_type().unwrap().is_form_data());
+    /// # });
+    /// ```
+    pub fn multipart(mut self) -> Self {
+        let boundary = crate::local::multipart::new_boundary();
+        self._request_mut().replace_header(crate::local::multipart::content_type(&boundary));
+        *self._body_mut() = crate::local::multipart::closing_delimiter(&boundary);
+        self
+    }
+
+    /// Adds a text field named `name` with the value `value` to the
+    /// `multipart/form-data` body, starting a new form with
+    /// [`multipart()`](Self::multipart()) if the request doesn't have one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    #[doc = $import]
+    ///
+    /// # Client::_test(|_, request, _| {
+    /// let request: LocalRequest = request;
+    /// let req = request.text("name", "Bob").text("tags", "a").text("tags", "b");
+    /// # });
+    /// ```
+    pub fn text<V: AsRef<str>>(self, name: &str, value: V) -> Self {
+        self._multipart_field(name, None, None, value.as_ref().as_bytes())
+    }
+
+    /// Adds a file field named `name` with contents `data` and Content-Type
+    /// `content_type` to the `multipart/form-data` body, starting a new form
+    /// with [`multipart()`](Self::multipart()) if the request doesn't have
+    /// one. The file's name is `name`; use
+    /// [`named_file()`](Self::named_file()) to set a different one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    #[doc = $import]
+    /// use rocket::http::ContentType;
+    ///
+    /// # Client::_test(|_, request, _| {
+    /// let request: LocalRequest = request;
+    /// let req = request
+    ///     .file("photo", [0x89, b'P', b'N', b'G'], "image/png")
+    ///     .file("notes", "Hello!", ContentType::Plain);
+    /// # });
+    /// ```
+    pub fn file<D, C>(self, name: &str, data: D, content_type: C) -> Self
+        where D: AsRef<[u8]>, C: std::fmt::Display
+    {
+        self.named_file(name, name, data, content_type)
+    }
+
+    /// Like [`file()`](Self::file()), but names the file `file_name`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    #[doc = $import]
+    /// use rocket::http::ContentType;
+    ///
+    /// # Client::_test(|_, request, _| {
+    /// let request: LocalRequest = request;
+    /// let req = request.named_file("photo", "me.png", [0x89], ContentType::PNG);
+    /// # });
+    /// ```
+    pub fn named_file<D, C>(self, name: &str, file_name: &str, data: D, content_type: C) -> Self
+        where D: AsRef<[u8]>, C: std::fmt::Display
+    {
+        let content_type = content_type.to_string();
+        self._multipart_field(name, Some(file_name), Some(&content_type), data.as_ref())
+    }
+
+    fn _multipart_field(
+        self,
+        name: &str,
+        file_name: Option<&str>,
+        content_type: Option<&str>,
+        data: &[u8],
+    ) -> Self {
+        let (mut this, boundary) = match crate::local::multipart::boundary(self._request()) {
+            Some(boundary) => (self, boundary),
+            None => {
+                let this = self.multipart();
+                let boundary = crate::local::multipart::boundary(this._request())
+                    .expect("multipart boundary set by `multipart()`");
+
+                (this, boundary)
+            }
+        };
+
+        let body = this._body_mut();
+        crate::local::multipart::append_part(body, &boundary, name, file_name, content_type, data);
+        this
+    }
+
     /// Dispatches the request, returning the response.
     ///
     /// This method consumes `self` and is the preferred mechanism for
@@ -0,0 +1,257 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use serde_json::{json, Map, Value};
+
+use crate::{Rocket, Request, Data, Build, Route};
+use crate::fairing::{self, Fairing, Info, Kind};
+use crate::http::{ContentType, Method, uri::Origin};
+use crate::route::{Handler, Outcome};
+use crate::openapi::{Components, Operation};
+
+/// A fairing that serves an OpenAPI document describing the application.
+///
+/// On ignition, the fairing assembles an [OpenAPI 3] document from the
+/// operations recorded for every route mounted so far, and mounts a `GET`
+/// route serving it as JSON at `/openapi.json` or the path set with
+/// [`OpenApi::path()`]. If enabled with [`OpenApi::swagger_ui()`], a [Swagger
+/// UI] page exploring the document is mounted as well. See the [module level
+/// docs](crate::openapi) for details.
+///
+/// Routes mounted by fairings that run after this one are not included: the
+/// `OpenApi` fairing should be attached last.
+///
+/// [OpenAPI 3]: https://spec.openapis.org/oas/v3.0.3
+/// [Swagger UI]: https://swagger.io/tools/swagger-ui/
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::openapi::OpenApi;
+///
+/// /// Returns a friendly greeting.
+/// #[get("/hello/<name>")]
+/// fn hello(name: &str) -> String {
+///     format!("Hello, {}!", name)
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .mount("/", routes![hello])
+///         .attach(OpenApi::new("Greeter", "1.0.0").swagger_ui("/docs"))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct OpenApi {
+    title: String,
+    version: String,
+    description: Option<String>,
+    path: String,
+    swagger_ui: Option<String>,
+}
+
+/// The handler serving the OpenAPI document.
+#[derive(Clone)]
+struct Document(Arc<String>);
+
+/// The handler serving the Swagger UI page.
+#[derive(Clone)]
+struct SwaggerUi(Arc<String>);
+
+/// The HTTP methods OpenAPI can describe operations for.
+const METHODS: &[Method] = &[
+    Method::Get, Method::Put, Method::Post, Method::Delete,
+    Method::Options, Method::Head, Method::Patch, Method::Trace,
+];
+
+fn validated(path: &str) -> String {
+    match Origin::parse(path) {
+        Ok(origin) if origin.query().is_none() => origin.path().to_string(),
+        _ => panic!("invalid OpenAPI route path: `{}`", path),
+    }
+}
+
+impl OpenApi {
+    /// The default path of the OpenAPI document.
+    const DEFAULT_PATH: &'static str = "/openapi.json";
+
+    /// Returns an `OpenApi` fairing for an API with the given `title` and
+    /// `version`, serving its document at `/openapi.json`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::openapi::OpenApi;
+    ///
+    /// let rocket = rocket::build().attach(OpenApi::new("Todo", "0.1.0"));
+    /// ```
+    pub fn new<T: Into<String>, V: Into<String>>(title: T, version: V) -> OpenApi {
+        OpenApi {
+            title: title.into(),
+            version: version.into(),
+            description: None,
+            path: OpenApi::DEFAULT_PATH.into(),
+            swagger_ui: None,
+        }
+    }
+
+    /// Sets the description of the API.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::openapi::OpenApi;
+    ///
+    /// let openapi = OpenApi::new("Todo", "0.1.0").description("Manages tasks.");
+    /// ```
+    pub fn description<D: Into<String>>(mut self, description: D) -> OpenApi {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the path the OpenAPI document is served at.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `path` is not a valid origin URI path.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::openapi::OpenApi;
+    ///
+    /// let openapi = OpenApi::new("Todo", "0.1.0").path("/api/spec.json");
+    /// ```
+    pub fn path(mut self, path: &str) -> OpenApi {
+        self.path = validated(path);
+        self
+    }
+
+    /// Serves a Swagger UI page exploring the OpenAPI document at `path`. The
+    /// page loads Swagger UI's assets from the `unpkg.com` CDN.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `path` is not a valid origin URI path.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::openapi::OpenApi;
+    ///
+    /// let openapi = OpenApi::new("Todo", "0.1.0").swagger_ui("/docs");
+    /// ```
+    pub fn swagger_ui(mut self, path: &str) -> OpenApi {
+        self.swagger_ui = Some(validated(path));
+        self
+    }
+
+    /// Returns the OpenAPI document describing the operations of `routes`.
+    fn document<'a, I>(&self, routes: I) -> Value
+        where I: Iterator<Item = &'a Route>
+    {
+        let mut components = Components::default();
+        let mut paths: BTreeMap<String, Map<String, Value>> = BTreeMap::new();
+        for route in routes {
+            let operation = match route.meta::<Operation>() {
+                Some(operation) if METHODS.contains(&route.method) => operation,
+                _ => continue,
+            };
+
+            let item = paths.entry(template(route.uri.path())).or_default();
+            let method = route.method.as_str().to_ascii_lowercase();
+            if !item.contains_key(&method) {
+                item.insert(method, operation.describe(route, &mut components));
+            }
+        }
+
+        let mut info = json!({ "title": self.title, "version": self.version });
+        if let Some(description) = &self.description {
+            info["description"] = description.as_str().into();
+        }
+
+        json!({
+            "openapi": "3.0.3",
+            "info": info,
+            "paths": paths,
+            "components": { "schemas": components.into_schemas() },
+        })
+    }
+
+    /// Returns the Swagger UI page for the document.
+    fn swagger_ui_page(&self) -> String {
+        let title = self.title.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+        let url = Value::from(self.path.as_str());
+        format!(r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>{title}</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css">
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+  <script>
+    window.onload = () => {{
+      window.ui = SwaggerUIBundle({{ url: {url}, dom_id: "#swagger-ui" }});
+    }};
+  </script>
+</body>
+</html>
+"##, title = title, url = url)
+    }
+}
+
+/// Converts a route path like `/tasks/<id>` to a path template like
+/// `/tasks/{id}`.
+fn template(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix('<').and_then(|s| s.strip_suffix('>')) {
+            Some(param) => format!("{{{}}}", param.trim_end_matches("..")),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[crate::async_trait]
+impl Fairing for OpenApi {
+    fn info(&self) -> Info {
+        Info { name: "OpenAPI", kind: Kind::Ignite | Kind::Singleton }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let document = self.document(rocket.routes());
+        let document = Arc::new(serde_json::to_string(&document).expect("JSON document"));
+
+        let mut route = Route::new(Method::Get, &self.path, Document(document));
+        route.name = Some("OpenAPI: document".into());
+        let mut routes = vec![route];
+
+        if let Some(path) = &self.swagger_ui {
+            let page = Arc::new(self.swagger_ui_page());
+            let mut route = Route::new(Method::Get, path, SwaggerUi(page));
+            route.name = Some("OpenAPI: Swagger UI".into());
+            routes.push(route);
+        }
+
+        Ok(rocket.mount("/", routes))
+    }
+}
+
+#[crate::async_trait]
+impl Handler for Document {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
+        Outcome::from(req, (ContentType::JSON, self.0.to_string()))
+    }
+}
+
+#[crate::async_trait]
+impl Handler for SwaggerUi {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
+        Outcome::from(req, (ContentType::HTML, self.0.to_string()))
+    }
+}
@@ -0,0 +1,127 @@
+//! OpenAPI document generation from routes.
+//!
+//! When the `openapi` feature is enabled, route codegen records an OpenAPI
+//! operation for every route declared with a route attribute. The [`OpenApi`]
+//! fairing assembles the operations of all mounted routes into an [OpenAPI 3]
+//! document at ignition and serves it, optionally alongside a [Swagger UI]
+//! page:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::openapi::{OpenApi, Schema};
+//! use rocket::serde::{Serialize, Deserialize, json::Json};
+//!
+//! #[derive(Serialize, Deserialize, Schema)]
+//! #[serde(crate = "rocket::serde")]
+//! struct Task {
+//!     description: String,
+//!     complete: bool,
+//! }
+//!
+//! /// Retrieves a task.
+//! ///
+//! /// Responds with `404 Not Found` if there is no task with the given `id`.
+//! #[get("/tasks/<id>")]
+//! fn task(id: usize) -> Option<Json<Task>> {
+//!     # /*
+//!     ...
+//!     # */ None
+//! }
+//!
+//! /// Creates a new task.
+//! #[post("/tasks", data = "<task>")]
+//! fn new(task: Json<Task>) -> Json<usize> {
+//!     # /*
+//!     ...
+//!     # */ Json(0)
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![task, new])
+//!         .attach(OpenApi::new("Tasks", "1.0.0").swagger_ui("/docs"))
+//! }
+//! ```
+//!
+//! [OpenAPI 3]: https://spec.openapis.org/oas/v3.0.3
+//! [Swagger UI]: https://swagger.io/tools/swagger-ui/
+//!
+//! # Operations
+//!
+//! The operation recorded for a route is described as follows:
+//!
+//!   * **Identity:** the operation ID is the route's name. The summary is the
+//!     first paragraph of the handler's doc comment and the description is
+//!     the remainder.
+//!
+//!   * **Parameters:** each dynamic path and query parameter is described by
+//!     the [`Schema`] of its guard's type. Query parameters are required
+//!     unless the type is [optional](Schema::OPTIONAL). A trailing query
+//!     parameter, `<param..>`, is described as an exploded form object.
+//!
+//!   * **Request Body:** the body is described by the [`RequestBody`]
+//!     implementation of the data guard's type, if any.
+//!
+//!   * **Responses:** the responses are described by the [`Responses`]
+//!     implementation of the handler's return type.
+//!
+//! Types that don't implement the respective trait, including generic and
+//! `impl Trait` types, are described as unconstrained. Routes not declared
+//! with a route attribute, like those of [`FileServer`](crate::fs::FileServer),
+//! are omitted from the document.
+//!
+//! # Schemas
+//!
+//! [`Schema`] is implemented for primitive, string, collection, and time
+//! types, and can be derived for structures and unit enums. Non-generic
+//! derived types are described once in the document's components and
+//! referenced by name. See [`Schema`] for details.
+
+mod schema;
+mod operation;
+mod fairing;
+
+pub use serde_json::Value;
+
+pub use self::schema::{Schema, Components, Object, enumeration};
+pub use self::operation::{RequestBody, Responses, ResponseMap};
+pub use self::fairing::OpenApi;
+
+#[doc(hidden)]
+pub use self::operation::{Operation, Parameter, Location, resolution};
+
+#[doc(inline)]
+pub use rocket_codegen::Schema;
+
+/// Records the OpenAPI operation of a route in its `StaticInfo`'s metadata.
+/// Used by route codegen. Expands to nothing without the `openapi` feature.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __openapi_operation {
+    (
+        $info:ident,
+        doc = $doc:expr,
+        params = [$(($loc:ident $name:literal: $T:ty, $trailing:literal)),*],
+        data = [$($D:ty)?],
+        ret = $R:ty
+    ) => ({
+        #[allow(unused_imports)]
+        use $crate::openapi::resolution::*;
+
+        let operation = $crate::openapi::Operation {
+            doc: $doc,
+            parameters: ::std::vec![$($crate::openapi::Parameter {
+                name: $name,
+                location: $crate::openapi::Location::$loc,
+                trailing: $trailing,
+                optional: Resolve::<$T>::OPTIONAL,
+                schema: Resolve::<$T>::schema,
+            }),*],
+            request_body: None $(.or(Some(Resolve::<$D>::request_body as fn(&mut _) -> _)))?,
+            responses: Resolve::<$R>::responses,
+        };
+
+        $info.meta.push(::std::sync::Arc::new(operation));
+    })
+}
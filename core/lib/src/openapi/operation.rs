@@ -0,0 +1,460 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use serde_json::{json, Map, Value};
+
+use crate::Route;
+use crate::data::{Capped, Data};
+use crate::form::Form;
+use crate::fs::TempFile;
+use crate::http::Status;
+use crate::response::{content, status, Debug, Flash, Redirect};
+use crate::serde::json::Json;
+use crate::openapi::{Schema, Components};
+
+/// The responses of an OpenAPI operation, keyed by status code.
+pub type ResponseMap = BTreeMap<String, Value>;
+
+/// Trait implemented by data guards that describe their request body.
+///
+/// The OpenAPI [`RequestBody`] of a route is described by the implementation
+/// of its data guard's type. Implementations are provided for [`Json<T>`] and
+/// [`Form<T>`] where `T: Schema`, for string and byte types, and for [`Data`],
+/// among others. A route whose data guard doesn't implement `RequestBody` has
+/// an unconstrained request body.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::openapi::{RequestBody, Components, Value};
+/// use rocket::serde::json::json;
+///
+/// struct Csv(String);
+///
+/// impl RequestBody for Csv {
+///     fn request_body(_: &mut Components) -> Value {
+///         json!({
+///             "required": true,
+///             "content": { "text/csv": { "schema": { "type": "string" } } }
+///         })
+///     }
+/// }
+/// ```
+///
+/// [`RequestBody`]: https://spec.openapis.org/oas/v3.0.3#request-body-object
+pub trait RequestBody {
+    /// Returns an OpenAPI request body object describing `Self`.
+    fn request_body(components: &mut Components) -> Value;
+}
+
+/// Trait implemented by responders that describe their responses.
+///
+/// The OpenAPI [`Responses`] of a route are described by the implementation
+/// of its return type. Implementations are provided for [`Json<T>`] where `T:
+/// Schema`, for string and byte types, for the [`content`] and [`status`]
+/// responders, and for `Option<R>` and `Result<R, E>` whenever `R` and `E`
+/// implement `Responses`, among others. A route whose return type doesn't
+/// implement `Responses` has an unspecified `default` response.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::openapi::{Responses, ResponseMap, Components};
+/// use rocket::serde::json::json;
+///
+/// struct Pong;
+///
+/// impl Responses for Pong {
+///     fn responses(_: &mut Components) -> ResponseMap {
+///         let mut responses = ResponseMap::new();
+///         responses.insert("204".into(), json!({ "description": "No Content" }));
+///         responses
+///     }
+/// }
+/// ```
+///
+/// [`Responses`]: https://spec.openapis.org/oas/v3.0.3#responses-object
+pub trait Responses {
+    /// Returns the OpenAPI response objects describing `Self`, keyed by status
+    /// code.
+    fn responses(components: &mut Components) -> ResponseMap;
+}
+
+/// An operation recorded by route codegen.
+#[doc(hidden)]
+pub struct Operation {
+    pub doc: &'static str,
+    pub parameters: Vec<Parameter>,
+    pub request_body: Option<fn(&mut Components) -> Value>,
+    pub responses: fn(&mut Components) -> ResponseMap,
+}
+
+/// A parameter of an operation recorded by route codegen.
+#[doc(hidden)]
+pub struct Parameter {
+    pub name: &'static str,
+    pub location: Location,
+    pub trailing: bool,
+    pub optional: bool,
+    pub schema: fn(&mut Components) -> Value,
+}
+
+#[doc(hidden)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Location {
+    Path,
+    Query,
+}
+
+impl Operation {
+    /// Returns the OpenAPI operation object describing `route`.
+    pub(crate) fn describe(&self, route: &Route, components: &mut Components) -> Value {
+        let mut operation = Map::new();
+        if let Some(name) = &route.name {
+            operation.insert("operationId".into(), name.as_ref().into());
+        }
+
+        let doc = self.doc.trim();
+        if !doc.is_empty() {
+            let (summary, description) = doc.split_once("\n\n").unwrap_or((doc, ""));
+            let summary = summary.lines().map(str::trim).collect::<Vec<_>>().join(" ");
+            operation.insert("summary".into(), summary.into());
+            if !description.trim().is_empty() {
+                operation.insert("description".into(), description.trim().into());
+            }
+        }
+
+        let parameters: Vec<_> = self.parameters.iter()
+            .map(|param| param.describe(components))
+            .collect();
+
+        if !parameters.is_empty() {
+            operation.insert("parameters".into(), parameters.into());
+        }
+
+        if let Some(request_body) = self.request_body {
+            operation.insert("requestBody".into(), request_body(components));
+        }
+
+        let responses = (self.responses)(components);
+        operation.insert("responses".into(), json!(responses));
+        operation.into()
+    }
+}
+
+impl Parameter {
+    fn describe(&self, components: &mut Components) -> Value {
+        let (location, required) = match self.location {
+            Location::Path => ("path", true),
+            Location::Query => ("query", !self.optional),
+        };
+
+        let mut param = json!({
+            "name": self.name,
+            "in": location,
+            "required": required,
+            "schema": (self.schema)(components),
+        });
+
+        // A trailing query parameter collects every field in the query.
+        if self.location == Location::Query && self.trailing {
+            param["style"] = "form".into();
+            param["explode"] = true.into();
+        }
+
+        param
+    }
+}
+
+/// Returns a response map with one response for `status` with `content`.
+fn response(status: Status, content: Option<(&str, Value)>) -> ResponseMap {
+    let mut response = json!({ "description": status.reason_lossy() });
+    if let Some((media_type, schema)) = content {
+        response["content"] = json!({ media_type: { "schema": schema } });
+    }
+
+    let mut responses = ResponseMap::new();
+    responses.insert(status.code.to_string(), response);
+    responses
+}
+
+/// Returns the responses of `R` with its `200` response, if any, replaced by
+/// one with `status`.
+fn with_status<R: Responses>(status: Status, components: &mut Components) -> ResponseMap {
+    let mut responses = R::responses(components);
+    let content = responses.remove("200").and_then(|mut r| r.get_mut("content").map(Value::take));
+    let mut response = json!({ "description": status.reason_lossy() });
+    if let Some(content) = content {
+        response["content"] = content;
+    }
+
+    responses.insert(status.code.to_string(), response);
+    responses
+}
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+fn binary() -> Value {
+    json!({ "type": "string", "format": "binary" })
+}
+
+fn body(required: bool, content: &[(&str, Value)]) -> Value {
+    let content: Map<String, Value> = content.iter()
+        .map(|(media_type, schema)| (media_type.to_string(), json!({ "schema": schema })))
+        .collect();
+
+    json!({ "required": required, "content": content })
+}
+
+impl<T: Schema> RequestBody for Json<T> {
+    fn request_body(components: &mut Components) -> Value {
+        body(true, &[("application/json", components.schema::<T>())])
+    }
+}
+
+impl<T: Schema> RequestBody for Form<T> {
+    fn request_body(components: &mut Components) -> Value {
+        let schema = components.schema::<T>();
+        body(true, &[
+            ("application/x-www-form-urlencoded", schema.clone()),
+            ("multipart/form-data", schema),
+        ])
+    }
+}
+
+macro_rules! impl_request_body {
+    ($($T:ty),+ => $media_type:expr, $schema:expr) => ($(
+        impl RequestBody for $T {
+            fn request_body(_: &mut Components) -> Value {
+                body(true, &[($media_type, $schema)])
+            }
+        }
+    )+)
+}
+
+impl_request_body!(String, &str, Cow<'_, str> => "text/plain", string());
+impl_request_body!(Vec<u8>, &[u8], Data<'_>, TempFile<'_> => "application/octet-stream", binary());
+
+impl<T: RequestBody> RequestBody for Capped<T> {
+    fn request_body(components: &mut Components) -> Value {
+        T::request_body(components)
+    }
+}
+
+impl<T: RequestBody> RequestBody for Option<T> {
+    fn request_body(components: &mut Components) -> Value {
+        let mut request_body = T::request_body(components);
+        request_body["required"] = false.into();
+        request_body
+    }
+}
+
+impl<T: RequestBody, E> RequestBody for Result<T, E> {
+    fn request_body(components: &mut Components) -> Value {
+        T::request_body(components)
+    }
+}
+
+impl Responses for () {
+    fn responses(_: &mut Components) -> ResponseMap {
+        response(Status::Ok, None)
+    }
+}
+
+impl Responses for Status {
+    fn responses(_: &mut Components) -> ResponseMap {
+        let mut responses = ResponseMap::new();
+        responses.insert("default".into(), json!({ "description": "Response" }));
+        responses
+    }
+}
+
+impl Responses for Redirect {
+    fn responses(_: &mut Components) -> ResponseMap {
+        let mut responses = ResponseMap::new();
+        responses.insert("3XX".into(), json!({ "description": "Redirection" }));
+        responses
+    }
+}
+
+impl Responses for std::io::Error {
+    fn responses(_: &mut Components) -> ResponseMap {
+        response(Status::InternalServerError, None)
+    }
+}
+
+impl<E> Responses for Debug<E> {
+    fn responses(_: &mut Components) -> ResponseMap {
+        response(Status::InternalServerError, None)
+    }
+}
+
+impl<T: Schema> Responses for Json<T> {
+    fn responses(components: &mut Components) -> ResponseMap {
+        response(Status::Ok, Some(("application/json", components.schema::<T>())))
+    }
+}
+
+macro_rules! impl_responses {
+    ($($T:ty),+ => $media_type:expr, $schema:expr) => ($(
+        impl Responses for $T {
+            fn responses(_: &mut Components) -> ResponseMap {
+                response(Status::Ok, Some(($media_type, $schema)))
+            }
+        }
+    )+)
+}
+
+impl_responses!(String, &str, Cow<'_, str> => "text/plain", string());
+impl_responses!(Vec<u8>, &[u8] => "application/octet-stream", binary());
+
+macro_rules! impl_content_responses {
+    ($($T:ident: $media_type:expr, $schema:expr),+) => ($(
+        impl<R> Responses for content::$T<R> {
+            fn responses(_: &mut Components) -> ResponseMap {
+                response(Status::Ok, Some(($media_type, $schema)))
+            }
+        }
+    )+)
+}
+
+impl_content_responses! {
+    RawJson: "application/json", json!({}),
+    RawXml: "text/xml", string(),
+    RawMsgPack: "application/msgpack", binary(),
+    RawCbor: "application/cbor", binary(),
+    RawHtml: "text/html", string(),
+    RawText: "text/plain", string(),
+    RawCss: "text/css", string(),
+    RawJavaScript: "application/javascript", string()
+}
+
+macro_rules! impl_status_responses {
+    ($($T:ident: $status:ident),+) => ($(
+        impl<R: Responses> Responses for status::$T<R> {
+            fn responses(components: &mut Components) -> ResponseMap {
+                with_status::<R>(Status::$status, components)
+            }
+        }
+    )+)
+}
+
+impl_status_responses! {
+    Created: Created,
+    Accepted: Accepted,
+    BadRequest: BadRequest,
+    Unauthorized: Unauthorized,
+    Forbidden: Forbidden,
+    NotFound: NotFound,
+    Conflict: Conflict
+}
+
+impl Responses for status::NoContent {
+    fn responses(_: &mut Components) -> ResponseMap {
+        response(Status::NoContent, None)
+    }
+}
+
+impl<R: Responses> Responses for status::Custom<R> {
+    fn responses(components: &mut Components) -> ResponseMap {
+        let mut responses = R::responses(components);
+        if let Some(mut response) = responses.remove("200") {
+            response["description"] = "Response".into();
+            responses.insert("default".into(), response);
+        }
+
+        responses
+    }
+}
+
+impl<R: Responses> Responses for Flash<R> {
+    fn responses(components: &mut Components) -> ResponseMap {
+        R::responses(components)
+    }
+}
+
+impl<R: Responses> Responses for Box<R> {
+    fn responses(components: &mut Components) -> ResponseMap {
+        R::responses(components)
+    }
+}
+
+impl<R: Responses> Responses for Option<R> {
+    fn responses(components: &mut Components) -> ResponseMap {
+        let mut responses = R::responses(components);
+        responses.extend(response(Status::NotFound, None));
+        responses
+    }
+}
+
+impl<R: Responses, E: Responses> Responses for Result<R, E> {
+    fn responses(components: &mut Components) -> ResponseMap {
+        let mut responses = E::responses(components);
+        responses.extend(R::responses(components));
+        responses
+    }
+}
+
+#[doc(hidden)]
+pub mod resolution {
+    use super::*;
+
+    /// `Resolve<T>::item` for `T: Trait` is `<T as Trait>::item`.
+    /// `Resolve<T>::item` for `T: !Trait` is `DefaultTrait::item`.
+    ///
+    /// This is the same static dispatch hack used for sentinels: see
+    /// `sentinel::resolution`.
+    pub struct Resolve<T: ?Sized>(std::marker::PhantomData<T>);
+
+    /// Stands in for types that can't be named in route codegen, like `impl
+    /// Trait` types and generics. Implements none of the traits.
+    pub struct Opaque;
+
+    pub trait DefaultSchema {
+        const OPTIONAL: bool = false;
+
+        fn schema(_: &mut Components) -> Value {
+            json!({})
+        }
+    }
+
+    pub trait DefaultRequestBody {
+        fn request_body(_: &mut Components) -> Value {
+            json!({ "content": { "*/*": {} } })
+        }
+    }
+
+    pub trait DefaultResponses {
+        fn responses(_: &mut Components) -> ResponseMap {
+            let mut responses = ResponseMap::new();
+            responses.insert("default".into(), json!({ "description": "Response" }));
+            responses
+        }
+    }
+
+    impl<T: ?Sized> DefaultSchema for T {}
+    impl<T: ?Sized> DefaultRequestBody for T {}
+    impl<T: ?Sized> DefaultResponses for T {}
+
+    impl<T: Schema + ?Sized> Resolve<T> {
+        pub const OPTIONAL: bool = T::OPTIONAL;
+
+        pub fn schema(components: &mut Components) -> Value {
+            components.schema::<T>()
+        }
+    }
+
+    impl<T: RequestBody + ?Sized> Resolve<T> {
+        pub fn request_body(components: &mut Components) -> Value {
+            T::request_body(components)
+        }
+    }
+
+    impl<T: Responses + ?Sized> Resolve<T> {
+        pub fn responses(components: &mut Components) -> ResponseMap {
+            T::responses(components)
+        }
+    }
+}
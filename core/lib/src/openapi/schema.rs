@@ -0,0 +1,326 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use serde_json::{json, Map, Value};
+
+/// Trait implemented by types that describe themselves with a JSON schema.
+///
+/// A `Schema` implementation describes the JSON representation of a type, or,
+/// for parameters and forms, the representation of its values. It is used by
+/// the [`OpenApi`](crate::openapi::OpenApi) fairing to document route
+/// parameters, request bodies, and responses.
+///
+/// # Deriving
+///
+/// `Schema` can be derived for structures with named fields, tuple structures
+/// with exactly one field, and enums with only unit variants:
+///
+/// ```rust
+/// use rocket::openapi::Schema;
+///
+/// #[derive(Schema)]
+/// struct Task {
+///     id: u64,
+///     description: String,
+///     due: Option<String>,
+/// }
+///
+/// #[derive(Schema)]
+/// struct TaskId(u64);
+///
+/// #[derive(Schema)]
+/// enum Priority { Low, Normal, High }
+/// ```
+///
+/// Structures with named fields are described as objects with a property for
+/// every field, required unless the field's type is
+/// [optional](Schema::OPTIONAL). Tuple structures are described by their
+/// field's schema. Enums are described as strings with one allowed value per
+/// variant. Property and value names are the identifiers of fields and
+/// variants. Non-generic types are [named](Schema::name()) after themselves
+/// and are thus described once in the document's components.
+///
+/// # Implementing
+///
+/// A manual implementation returns a JSON schema, typically retrieving the
+/// schemas of constituent types via [`Components::schema()`]:
+///
+/// ```rust
+/// use rocket::openapi::{Schema, Components, Value};
+///
+/// struct Point { x: f64, y: f64 }
+///
+/// impl Schema for Point {
+///     fn schema(components: &mut Components) -> Value {
+///         components.object()
+///             .property::<f64>("x")
+///             .property::<f64>("y")
+///             .build()
+///     }
+/// }
+/// ```
+pub trait Schema {
+    /// Whether a value of this type may be absent. Fields and query
+    /// parameters of optional types are not required. Defaults to `false`.
+    const OPTIONAL: bool = false;
+
+    /// The name of the schema in the document's components, if any. Named
+    /// schemas are described once and referenced everywhere else; unnamed
+    /// schemas are described inline. Defaults to `None`.
+    fn name() -> Option<Cow<'static, str>> {
+        None
+    }
+
+    /// Returns the JSON schema describing `Self`, registering any named
+    /// schemas it references in `components`.
+    fn schema(components: &mut Components) -> Value;
+}
+
+/// The named schemas referenced by an OpenAPI document.
+///
+/// Schemas are registered by retrieving them with [`Components::schema()`].
+#[derive(Debug, Default)]
+pub struct Components {
+    schemas: BTreeMap<String, Value>,
+}
+
+/// A builder for an object schema, created via [`Components::object()`].
+pub struct Object<'c> {
+    components: &'c mut Components,
+    properties: Map<String, Value>,
+    required: Vec<Value>,
+}
+
+impl Components {
+    /// Returns the schema for `T`. If `T` is [named](Schema::name()), its
+    /// schema is registered under its name, if it wasn't already, and a
+    /// reference to it is returned instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::openapi::{Schema, Components};
+    /// use rocket::serde::json::json;
+    ///
+    /// #[derive(Schema)]
+    /// struct Id(u32);
+    ///
+    /// let mut components = Components::default();
+    /// assert_eq!(components.schema::<Vec<String>>(), json!({
+    ///     "type": "array",
+    ///     "items": { "type": "string" }
+    /// }));
+    ///
+    /// assert_eq!(components.schema::<Id>(), json!({ "$ref": "#/components/schemas/Id" }));
+    /// ```
+    pub fn schema<T: Schema + ?Sized>(&mut self) -> Value {
+        let name = match T::name() {
+            Some(name) => name,
+            None => return T::schema(self),
+        };
+
+        if !self.schemas.contains_key(&*name) {
+            // Insert a placeholder first so that recursive types terminate.
+            self.schemas.insert(name.to_string(), Value::Null);
+            let schema = T::schema(self);
+            self.schemas.insert(name.to_string(), schema);
+        }
+
+        json!({ "$ref": format!("#/components/schemas/{}", name) })
+    }
+
+    /// Returns a builder for an object schema.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::openapi::Components;
+    /// use rocket::serde::json::json;
+    ///
+    /// let mut components = Components::default();
+    /// let schema = components.object()
+    ///     .property::<String>("name")
+    ///     .property::<Option<u8>>("age")
+    ///     .build();
+    ///
+    /// assert_eq!(schema, json!({
+    ///     "type": "object",
+    ///     "properties": {
+    ///         "name": { "type": "string" },
+    ///         "age": { "type": "integer", "format": "int32", "minimum": 0 }
+    ///     },
+    ///     "required": ["name"]
+    /// }));
+    /// ```
+    pub fn object(&mut self) -> Object<'_> {
+        Object { components: self, properties: Map::new(), required: vec![] }
+    }
+
+    /// Consumes `self` and returns the registered schemas, by name.
+    pub fn into_schemas(self) -> BTreeMap<String, Value> {
+        self.schemas
+    }
+}
+
+impl Object<'_> {
+    /// Adds a property `name` with the schema of `T`. The property is required
+    /// unless `T` is [optional](Schema::OPTIONAL).
+    pub fn property<T: Schema + ?Sized>(mut self, name: &str) -> Self {
+        let schema = self.components.schema::<T>();
+        self.properties.insert(name.into(), schema);
+        if !T::OPTIONAL {
+            self.required.push(name.into());
+        }
+
+        self
+    }
+
+    /// Returns the object schema.
+    pub fn build(self) -> Value {
+        let mut schema = json!({ "type": "object", "properties": self.properties });
+        if !self.required.is_empty() {
+            schema["required"] = Value::Array(self.required);
+        }
+
+        schema
+    }
+}
+
+/// Returns the schema of a string with one of the values in `variants`.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::openapi::enumeration;
+/// use rocket::serde::json::json;
+///
+/// assert_eq!(enumeration(["low", "high"]), json!({
+///     "type": "string",
+///     "enum": ["low", "high"]
+/// }));
+/// ```
+pub fn enumeration<I, S>(variants: I) -> Value
+    where I: IntoIterator<Item = S>, S: Into<String>
+{
+    let variants = variants.into_iter().map(|v| Value::String(v.into()));
+    json!({ "type": "string", "enum": variants.collect::<Vec<_>>() })
+}
+
+macro_rules! impl_schema {
+    ($($T:ty),+ => $schema:expr) => ($(
+        impl Schema for $T {
+            fn schema(_: &mut Components) -> Value {
+                $schema
+            }
+        }
+    )+)
+}
+
+impl_schema!(bool => json!({ "type": "boolean" }));
+impl_schema!(i8, i16, i32 => json!({ "type": "integer", "format": "int32" }));
+impl_schema!(i64, i128, isize => json!({ "type": "integer", "format": "int64" }));
+impl_schema!(u8, u16, u32 => json!({ "type": "integer", "format": "int32", "minimum": 0 }));
+impl_schema!(u64, u128, usize => json!({ "type": "integer", "format": "int64", "minimum": 0 }));
+impl_schema!(f32 => json!({ "type": "number", "format": "float" }));
+impl_schema!(f64 => json!({ "type": "number", "format": "double" }));
+impl_schema!(char => json!({ "type": "string", "minLength": 1, "maxLength": 1 }));
+impl_schema!(str, String, PathBuf => json!({ "type": "string" }));
+impl_schema!(IpAddr, SocketAddr => json!({ "type": "string" }));
+impl_schema!(Ipv4Addr => json!({ "type": "string", "format": "ipv4" }));
+impl_schema!(Ipv6Addr => json!({ "type": "string", "format": "ipv6" }));
+impl_schema!(time::Date => json!({ "type": "string", "format": "date" }));
+impl_schema!(time::Time => json!({ "type": "string", "format": "time" }));
+impl_schema!(time::PrimitiveDateTime => json!({ "type": "string", "format": "date-time" }));
+impl_schema!(crate::fs::TempFile<'_> => json!({ "type": "string", "format": "binary" }));
+impl_schema!(crate::serde::json::Value => json!({}));
+
+#[cfg(feature = "uuid")]
+impl_schema!(crate::serde::uuid::Uuid => json!({ "type": "string", "format": "uuid" }));
+
+impl Schema for Cow<'_, str> {
+    fn schema(_: &mut Components) -> Value {
+        json!({ "type": "string" })
+    }
+}
+
+impl<T: Schema + ?Sized> Schema for &T {
+    const OPTIONAL: bool = T::OPTIONAL;
+
+    fn name() -> Option<Cow<'static, str>> {
+        T::name()
+    }
+
+    fn schema(components: &mut Components) -> Value {
+        T::schema(components)
+    }
+}
+
+macro_rules! impl_schema_wrapper {
+    ($($W:ident),+) => ($(
+        impl<T: Schema + ?Sized> Schema for $W<T> {
+            const OPTIONAL: bool = T::OPTIONAL;
+
+            fn name() -> Option<Cow<'static, str>> {
+                T::name()
+            }
+
+            fn schema(components: &mut Components) -> Value {
+                T::schema(components)
+            }
+        }
+    )+)
+}
+
+impl_schema_wrapper!(Box, Arc);
+
+impl<T: Schema> Schema for Option<T> {
+    const OPTIONAL: bool = true;
+
+    fn schema(components: &mut Components) -> Value {
+        components.schema::<T>()
+    }
+}
+
+macro_rules! impl_schema_array {
+    ($($C:ident),+) => ($(
+        impl<T: Schema> Schema for $C<T> {
+            fn schema(components: &mut Components) -> Value {
+                json!({ "type": "array", "items": components.schema::<T>() })
+            }
+        }
+    )+)
+}
+
+impl_schema_array!(Vec, HashSet, BTreeSet);
+
+impl<T: Schema> Schema for [T] {
+    fn schema(components: &mut Components) -> Value {
+        json!({ "type": "array", "items": components.schema::<T>() })
+    }
+}
+
+impl<T: Schema, const N: usize> Schema for [T; N] {
+    fn schema(components: &mut Components) -> Value {
+        json!({
+            "type": "array",
+            "items": components.schema::<T>(),
+            "minItems": N,
+            "maxItems": N,
+        })
+    }
+}
+
+impl<K: AsRef<str>, V: Schema, S> Schema for HashMap<K, V, S> {
+    fn schema(components: &mut Components) -> Value {
+        json!({ "type": "object", "additionalProperties": components.schema::<V>() })
+    }
+}
+
+impl<K: AsRef<str>, V: Schema> Schema for BTreeMap<K, V> {
+    fn schema(components: &mut Components) -> Value {
+        json!({ "type": "object", "additionalProperties": components.schema::<V>() })
+    }
+}
@@ -2,8 +2,11 @@ use state::Container;
 use figment::Figment;
 
 use crate::{Catcher, Config, Rocket, Route, Shutdown};
-use crate::router::Router;
+use crate::router::{Router, HotRouter};
 use crate::fairing::Fairings;
+use crate::task::Tasks;
+use crate::rocket::Ranker;
+use crate::config::SecretProviders;
 
 mod private {
     pub trait Sealed {  }
@@ -81,8 +84,10 @@ phases! {
     Build (#[derive(Default, Debug)] Building) {
         pub(crate) routes: Vec<Route>,
         pub(crate) catchers: Vec<Catcher>,
+        pub(crate) rankers: Vec<Ranker>,
         pub(crate) fairings: Fairings,
         pub(crate) figment: Figment,
+        pub(crate) secret_providers: SecretProviders,
         pub(crate) state: Container![Send + Sync],
     }
 
@@ -108,10 +113,20 @@ phases! {
     /// represents a running application.
     Orbit (#[derive(Debug)] Orbiting) {
         pub(crate) router: Router,
+        pub(crate) hot_router: HotRouter,
+        pub(crate) tasks: Tasks,
         pub(crate) fairings: Fairings,
         pub(crate) figment: Figment,
         pub(crate) config: Config,
+        pub(crate) current_config: parking_lot::RwLock<std::sync::Arc<Config>>,
         pub(crate) state: Container![Send + Sync],
         pub(crate) shutdown: Shutdown,
+        pub(crate) connections: std::sync::Arc<crate::ext::ConnectionLimiter>,
+        pub(crate) requests: std::sync::atomic::AtomicUsize,
+        #[cfg(feature = "tls")]
+        pub(crate) tls_resolvers: Vec<std::sync::Arc<crate::http::tls::CertResolver>>,
+        #[cfg(feature = "tls")]
+        pub(crate) listener_tls:
+            Vec<(crate::config::TlsConfig, std::sync::Arc<crate::http::tls::CertResolver>)>,
     }
 }
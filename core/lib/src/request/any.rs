@@ -0,0 +1,164 @@
+use std::fmt;
+use std::ops::Deref;
+
+use crate::request::{Request, FromRequest, Outcome};
+use crate::outcome::Outcome::*;
+
+mod private {
+    pub trait Sealed {  }
+}
+
+/// A tuple of request guards usable in [`Any`].
+///
+/// This trait is implemented for tuples of one to eight types. It is _sealed_
+/// and cannot be implemented outside of Rocket.
+pub trait Guards: private::Sealed {
+    /// A tuple of `Option`s, one for each guard in `Self`.
+    type Options: Default;
+}
+
+/// A request guard that succeeds when any of the guards in the tuple `T` do.
+///
+/// The guards in `T` are tried in order until one succeeds. Its value is stored
+/// in the corresponding element of a tuple of options, [`Guards::Options`],
+/// accessible via [`Any::into_inner()`] or `Deref`; every other element is
+/// `None`. The index of the successful guard is returned by [`Any::index()`].
+///
+/// A guard that fails, like one that forwards, doesn't prevent later guards
+/// from being tried. If no guard succeeds but at least one fails, `Any` fails
+/// with the status of the first failing guard. The error value is a tuple of
+/// options where the element of every failing guard is `Some`. If every guard
+/// forwards, `Any` forwards. Because errors are retained while later guards
+/// are tried, each guard's error type must be `Send`.
+///
+/// For two guards, [`Either`](crate::Either) provides the same functionality
+/// with a more convenient interface.
+///
+/// # Example
+///
+/// Accept either a session cookie or an API key on the same route:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # type Session = rocket::http::Method;
+/// # type ApiKey = rocket::http::Method;
+/// # type Token = rocket::http::Method;
+/// use rocket::request::Any;
+///
+/// #[get("/")]
+/// fn index(auth: Any<(Session, ApiKey, Token)>) -> &'static str {
+///     match &*auth {
+///         (Some(_session), _, _) => "session",
+///         (_, Some(_key), _) => "api key",
+///         (_, _, Some(_token)) => "token",
+///         _ => unreachable!("exactly one guard succeeded"),
+///     }
+/// }
+/// ```
+pub struct Any<T: Guards> {
+    index: usize,
+    values: T::Options,
+}
+
+impl<T: Guards> Any<T> {
+    /// Returns the index, in `T`, of the guard that succeeded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::Method;
+    /// use rocket::request::Any;
+    ///
+    /// fn f(any: Any<(Method, Method)>) {
+    ///     assert_eq!(any.index(), 0);
+    /// }
+    /// ```
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Consumes `self` and returns the tuple of options where only the element
+    /// at [`Any::index()`] is `Some`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::Method;
+    /// use rocket::request::Any;
+    ///
+    /// fn f(any: Any<(Method, Method)>) {
+    ///     let (first, second) = any.into_inner();
+    ///     assert!(first.is_some() ^ second.is_some());
+    /// }
+    /// ```
+    pub fn into_inner(self) -> T::Options {
+        self.values
+    }
+}
+
+impl<T: Guards> Deref for Any<T> {
+    type Target = T::Options;
+
+    fn deref(&self) -> &Self::Target {
+        &self.values
+    }
+}
+
+impl<T: Guards> fmt::Debug for Any<T> where T::Options: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Any")
+            .field("index", &self.index)
+            .field("values", &self.values)
+            .finish()
+    }
+}
+
+macro_rules! impl_any_for_tuple {
+    ($($T:ident $E:ident $i:tt),+) => {
+        impl<$($T),+> private::Sealed for ($($T,)+) {  }
+
+        impl<$($T),+> Guards for ($($T,)+) {
+            type Options = ($(Option<$T>,)+);
+        }
+
+        #[crate::async_trait]
+        impl<'r, $($T, $E),+> FromRequest<'r> for Any<($($T,)+)>
+            where $($T: FromRequest<'r, Error = $E>, $E: fmt::Debug + Send),+
+        {
+            type Error = ($(Option<$E>,)+);
+
+            async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+                let mut errors: Self::Error = Default::default();
+                let mut status = None;
+                $(
+                    match $T::from_request(req).await {
+                        Success(value) => {
+                            let mut any: Self = Any { index: $i, values: Default::default() };
+                            any.values.$i = Some(value);
+                            return Success(any);
+                        }
+                        Failure((s, e)) => {
+                            status.get_or_insert(s);
+                            errors.$i = Some(e);
+                        }
+                        Forward(_) => { /* try the next guard */ }
+                    }
+                )+
+
+                match status {
+                    Some(status) => Failure((status, errors)),
+                    None => Forward(()),
+                }
+            }
+        }
+    };
+}
+
+impl_any_for_tuple!(A EA 0);
+impl_any_for_tuple!(A EA 0, B EB 1);
+impl_any_for_tuple!(A EA 0, B EB 1, C EC 2);
+impl_any_for_tuple!(A EA 0, B EB 1, C EC 2, D ED 3);
+impl_any_for_tuple!(A EA 0, B EB 1, C EC 2, D ED 3, E EE 4);
+impl_any_for_tuple!(A EA 0, B EB 1, C EC 2, D ED 3, E EE 4, F EF 5);
+impl_any_for_tuple!(A EA 0, B EB 1, C EC 2, D ED 3, E EE 4, F EF 5, G EG 6);
+impl_any_for_tuple!(A EA 0, B EB 1, C EC 2, D ED 3, E EE 4, F EF 5, G EG 6, H EH 7);
@@ -0,0 +1,95 @@
+use std::fmt;
+use std::borrow::Cow;
+use std::ops::Deref;
+
+use crate::request::{FromRequest, Outcome, Request};
+use crate::outcome::IntoOutcome;
+use crate::http::uri::{Absolute, fmt::ValidRoutePrefix};
+
+/// A request guard for the absolute base URL of the application.
+///
+/// The base URL is determined by [`Request::base_url()`]: it is the configured
+/// [`base_url`](crate::Config::base_url), if any, or else is derived from the
+/// request as described there. If it cannot be determined, the request is
+/// forwarded.
+///
+/// A `BaseUrl` can be used directly as the prefix in [`uri!`](crate::uri!) to
+/// generate absolute URIs, as needed in emails, redirects behind proxies, and
+/// `Location` headers:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::BaseUrl;
+/// use rocket::response::Redirect;
+///
+/// #[get("/verify/<token>")]
+/// fn verify(token: &str) { /* .. */ }
+///
+/// #[get("/signup")]
+/// fn signup(base: BaseUrl) -> String {
+///     format!("Verify your email at {}.", uri!(base, verify("some-token")))
+/// }
+///
+/// #[get("/old")]
+/// fn old(base: BaseUrl) -> Redirect {
+///     Redirect::to(uri!(base, signup))
+/// }
+/// ```
+///
+/// Configure the base URL via the `base_url` configuration parameter:
+///
+/// ```toml
+/// [default]
+/// base_url = "https://example.com/app"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BaseUrl(Absolute<'static>);
+
+impl BaseUrl {
+    /// Consumes `self` and returns the inner [`Absolute`] URI.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::BaseUrl;
+    ///
+    /// fn f(base: BaseUrl) {
+    ///     let absolute = base.into_inner();
+    ///     assert!(absolute.authority().is_some());
+    /// }
+    /// ```
+    pub fn into_inner(self) -> Absolute<'static> {
+        self.0
+    }
+}
+
+impl Deref for BaseUrl {
+    type Target = Absolute<'static>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl fmt::Display for BaseUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ValidRoutePrefix for BaseUrl {
+    type Output = Absolute<'static>;
+
+    fn append(self, path: Cow<'static, str>, query: Option<Cow<'static, str>>) -> Self::Output {
+        self.0.append(path, query)
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for BaseUrl {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        request.base_url().map(BaseUrl).or_forward(())
+    }
+}
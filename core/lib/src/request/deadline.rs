@@ -0,0 +1,138 @@
+use std::convert::Infallible;
+use std::time::{Duration, Instant};
+
+use crate::request::{self, FromRequest, Request};
+
+/// Request guard for the deadline of a route with a timeout.
+///
+/// A route with a `timeout`, set via the `timeout` route attribute parameter
+/// or the [`Route::timeout`](crate::Route::timeout) field, must produce a
+/// response before its deadline: the route's timeout after routing the request
+/// to the route. The deadline covers the route's request and data guards, the
+/// handler, and the returned value's [`Responder`](crate::response::Responder)
+/// but not the writing of the response body. If the deadline passes, the
+/// handler's future is dropped, cancelling it, and the request fails with a
+/// status of `503 Service Unavailable`.
+///
+/// Because a handler is cancelled only when it yields, work that doesn't yield
+/// to the executor, such as blocking work in [`spawn_blocking`], continues
+/// unaffected. Such work can instead cooperatively check a `Deadline`.
+///
+/// The guard never fails. For a route without a timeout, the deadline is
+/// `None` and never expires.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::Deadline;
+/// use rocket::tokio::task::spawn_blocking;
+///
+/// # fn crunch_a_little() -> bool { true }
+/// #[get("/report", timeout = "30s")]
+/// async fn report(deadline: Deadline) -> Option<&'static str> {
+///     spawn_blocking(move || {
+///         while !deadline.is_expired() {
+///             if crunch_a_little() {
+///                 return Some("done");
+///             }
+///         }
+///
+///         None
+///     }).await.ok()?
+/// }
+/// ```
+///
+/// [`spawn_blocking`]: crate::tokio::task::spawn_blocking
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Deadline(Option<Instant>);
+
+impl Deadline {
+    /// The instant at which the route times out, if it has a timeout.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use std::time::Instant;
+    /// use rocket::request::Deadline;
+    ///
+    /// #[get("/", timeout = "1s")]
+    /// fn index(deadline: Deadline) {
+    ///     assert!(deadline.instant().unwrap() > Instant::now());
+    /// }
+    /// ```
+    pub fn instant(&self) -> Option<Instant> {
+        self.0
+    }
+
+    /// The time remaining until the route times out, if it has a timeout. If
+    /// the deadline has passed, returns `Some(Duration::ZERO)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use std::time::Duration;
+    /// use rocket::request::Deadline;
+    ///
+    /// #[get("/", timeout = "1s")]
+    /// fn index(deadline: Deadline) {
+    ///     assert!(deadline.remaining().unwrap() <= Duration::from_secs(1));
+    /// }
+    /// ```
+    pub fn remaining(&self) -> Option<Duration> {
+        self.0.map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Returns `true` if the deadline has passed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::request::Deadline;
+    ///
+    /// #[get("/")]
+    /// fn index(deadline: Deadline) {
+    ///     assert!(!deadline.is_expired());
+    /// }
+    /// ```
+    pub fn is_expired(&self) -> bool {
+        self.0.map_or(false, |deadline| Instant::now() >= deadline)
+    }
+
+    /// Returns a future that resolves once the deadline has passed. If there
+    /// is no deadline, the future never resolves.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::request::Deadline;
+    /// use rocket::tokio::{select, sync::Notify};
+    ///
+    /// #[get("/wait", timeout = "10s")]
+    /// async fn wait(deadline: Deadline, notify: &rocket::State<Notify>) -> &'static str {
+    ///     select! {
+    ///         _ = notify.notified() => "notified",
+    ///         _ = deadline.expired() => "gave up",
+    ///     }
+    /// }
+    /// ```
+    pub async fn expired(&self) {
+        match self.0 {
+            Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+            None => futures::future::pending().await,
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for Deadline {
+    type Error = Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        request::Outcome::Success(Deadline(req.deadline()))
+    }
+}
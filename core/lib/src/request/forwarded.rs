@@ -0,0 +1,195 @@
+use std::net::IpAddr;
+
+use crate::Request;
+
+/// Client information forwarded by proxies: the client's IP address, the
+/// protocol it used, and the host it requested, as far as they're trusted.
+pub(crate) struct Forwarded<'a> {
+    pub ip: Option<IpAddr>,
+    pub proto: Option<&'a str>,
+    pub host: Option<&'a str>,
+}
+
+/// A single hop as recorded by a proxy: an element of the `Forwarded` header
+/// or the corresponding values of the `X-Forwarded-*` headers.
+#[derive(Default)]
+struct Hop<'a> {
+    node: Option<IpAddr>,
+    proto: Option<&'a str>,
+    host: Option<&'a str>,
+}
+
+impl<'a> Forwarded<'a> {
+    /// Determines the forwarded information for `req`.
+    ///
+    /// Without configured [`trusted_proxies`](crate::Config::trusted_proxies),
+    /// the `X-Real-IP`, `X-Forwarded-Proto`, and `X-Forwarded-Host` headers are
+    /// taken at face value. Otherwise, hops are walked from the remote address
+    /// toward the client for as long as the address they were received from
+    /// is that of a trusted proxy. The last hop reached is the client.
+    pub fn from_request(req: &'a Request<'_>) -> Forwarded<'a> {
+        let headers = req.headers();
+        let proxies = match req.rocket().config().trusted_proxies {
+            Some(ref proxies) => proxies,
+            None => return Forwarded {
+                ip: req.real_ip(),
+                proto: headers.get_one("X-Forwarded-Proto"),
+                host: headers.get_one("X-Forwarded-Host"),
+            }
+        };
+
+        let mut forwarded = Forwarded { ip: req.remote().map(|r| r.ip()), proto: None, host: None };
+        let mut peer = match forwarded.ip {
+            Some(ip) => ip,
+            None => return forwarded,
+        };
+
+        for hop in hops(req).into_iter().rev() {
+            if !proxies.iter().any(|net| net.contains(&canonical(peer))) {
+                break;
+            }
+
+            forwarded.proto = hop.proto.or(forwarded.proto);
+            forwarded.host = hop.host.or(forwarded.host);
+            match hop.node {
+                Some(ip) => peer = ip,
+                None => break,
+            }
+
+            forwarded.ip = Some(peer);
+        }
+
+        forwarded
+    }
+}
+
+/// The hops recorded in `req`'s `Forwarded` header, if it has one, or else in
+/// its `X-Forwarded-For`, `X-Forwarded-Proto`, `X-Forwarded-Host`, and
+/// `X-Real-IP` headers, ordered from the client to the nearest proxy.
+fn hops<'a>(req: &'a Request<'_>) -> Vec<Hop<'a>> {
+    let headers = req.headers();
+    if headers.contains("Forwarded") {
+        return headers.get("Forwarded")
+            .flat_map(|value| split_unquoted(value, ','))
+            .map(|element| {
+                let mut hop = Hop::default();
+                for pair in split_unquoted(element, ';') {
+                    let (name, value) = match pair.split_once('=') {
+                        Some((name, value)) => (name.trim(), unquote(value.trim())),
+                        None => continue,
+                    };
+
+                    if name.eq_ignore_ascii_case("for") {
+                        hop.node = parse_node(value);
+                    } else if name.eq_ignore_ascii_case("proto") {
+                        hop.proto = Some(value);
+                    } else if name.eq_ignore_ascii_case("host") {
+                        hop.host = Some(value);
+                    }
+                }
+
+                hop
+            })
+            .collect();
+    }
+
+    let values = |name| -> Vec<&'a str> {
+        headers.get(name).flat_map(|v| v.split(',')).map(str::trim).collect()
+    };
+
+    let mut nodes = values("X-Forwarded-For");
+    if nodes.is_empty() {
+        nodes.extend(headers.get_one("X-Real-IP"));
+    }
+
+    // Proxies often set only the last `X-Forwarded-{Proto,Host}`, so values
+    // are aligned with the hops from the nearest proxy backwards.
+    let (protos, hosts) = (values("X-Forwarded-Proto"), values("X-Forwarded-Host"));
+    let len = nodes.len().max(protos.len()).max(hosts.len());
+    let nth = |list: &[&'a str], i: usize| (i + list.len()).checked_sub(len).map(|i| list[i]);
+    (0..len)
+        .map(|i| Hop {
+            node: nth(&nodes, i).and_then(parse_node),
+            proto: nth(&protos, i),
+            host: nth(&hosts, i),
+        })
+        .collect()
+}
+
+/// Splits `string` at every `sep` not within a quoted string.
+fn split_unquoted(string: &str, sep: char) -> Vec<&str> {
+    let (mut parts, mut start, mut quoted, mut escaped) = (vec![], 0, false, false);
+    for (i, c) in string.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            c if c == sep && !quoted => {
+                parts.push(string[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    parts.push(string[start..].trim());
+    parts
+}
+
+fn unquote(value: &str) -> &str {
+    value.strip_prefix('"').and_then(|v| v.strip_suffix('"')).unwrap_or(value)
+}
+
+/// Parses a node as recorded in `Forwarded` or `X-Forwarded-For`: an IPv4
+/// address, a bracketed IPv6 address, or a bare IPv6 address, each optionally
+/// followed by a port. Obfuscated and `unknown` nodes yield `None`.
+fn parse_node(node: &str) -> Option<IpAddr> {
+    if let Some(rest) = node.strip_prefix('[') {
+        return rest.split_once(']').and_then(|(ip, _)| ip.parse().ok());
+    }
+
+    node.parse().ok().or_else(|| node.split_once(':')?.0.parse().ok())
+}
+
+/// Maps IPv4-mapped IPv6 addresses to IPv4 so they match IPv4 networks.
+fn canonical(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => match v6.segments() {
+            [0, 0, 0, 0, 0, 0xffff, ..] => {
+                let [.., a, b, c, d] = v6.octets();
+                IpAddr::from([a, b, c, d])
+            }
+            _ => IpAddr::V6(v6),
+        },
+        ip => ip,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_node, split_unquoted, canonical};
+
+    #[test]
+    fn parses_nodes() {
+        assert_eq!(parse_node("192.0.2.43"), Some([192, 0, 2, 43].into()));
+        assert_eq!(parse_node("192.0.2.43:47011"), Some([192, 0, 2, 43].into()));
+        assert_eq!(parse_node("2001:db8::17"), "2001:db8::17".parse().ok());
+        assert_eq!(parse_node("[2001:db8::17]:4711"), "2001:db8::17".parse().ok());
+        assert_eq!(parse_node("unknown"), None);
+        assert_eq!(parse_node("_hidden"), None);
+    }
+
+    #[test]
+    fn splits_outside_quotes() {
+        assert_eq!(split_unquoted("a, b;c", ','), ["a", "b;c"]);
+        assert_eq!(split_unquoted(r#"for="a,b";proto=http"#, ';'), [r#"for="a,b""#, "proto=http"]);
+        assert_eq!(split_unquoted(r#"x="\",", y"#, ','), [r#"x="\",""#, "y"]);
+    }
+
+    #[test]
+    fn canonicalizes_mapped_addresses() {
+        let mapped = "::ffff:10.0.0.1".parse().unwrap();
+        assert_eq!(canonical(mapped), std::net::IpAddr::from([10, 0, 0, 1]));
+        assert_eq!(canonical("::1".parse().unwrap()), "::1".parse::<std::net::IpAddr>().unwrap());
+    }
+}
@@ -1,6 +1,7 @@
 use std::str::FromStr;
 use std::path::PathBuf;
 
+use crate::http::Status;
 use crate::http::uri::{Segments, error::PathError, fmt::Path};
 
 /// Trait to convert a dynamic path segment string to a concrete value.
@@ -35,6 +36,10 @@ use crate::http::uri::{Segments, error::PathError, fmt::Path};
 /// routes, this example will result in a 404 error for requests with invalid
 /// `id` values.
 ///
+/// An implementation may instead choose to fail the request with a given
+/// status, preventing any further routes from being tried, by overriding
+/// [`FromParam::failure_status()`].
+///
 /// # Catching Errors
 ///
 /// Sometimes, a forward is not desired, and instead, we simply want to know
@@ -102,6 +107,24 @@ use crate::http::uri::{Segments, error::PathError, fmt::Path};
 ///     The path segment is parsed by `T`'s `FromParam` implementation. The
 ///     returned `Result` value is returned.
 ///
+/// # Deriving
+///
+/// `FromParam` can be derived for C-like enums and single-field structs with
+/// `#[derive(FromParam)]`. See the [derive](macro@rocket::FromParam) docs for
+/// details.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #[derive(FromParam)]
+/// enum Status {
+///     Open,
+///     Done,
+/// }
+///
+/// #[get("/issues/<status>")]
+/// fn issues(status: Status) { /* .. */ }
+/// ```
+///
 /// # Example
 ///
 /// Say you want to parse a segment of the form:
@@ -181,6 +204,41 @@ pub trait FromParam<'a>: Sized {
     /// Parses and validates an instance of `Self` from a path parameter string
     /// or returns an `Error` if parsing or validation fails.
     fn from_param(param: &'a str) -> Result<Self, Self::Error>;
+
+    /// Returns the status with which to fail the request when
+    /// [`FromParam::from_param()`] fails with `error` or `None` to forward the
+    /// request to the next matching route instead.
+    ///
+    /// The default implementation returns `None`, always forwarding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::FromParam;
+    /// use rocket::http::Status;
+    ///
+    /// struct Slug<'r>(&'r str);
+    ///
+    /// impl<'r> FromParam<'r> for Slug<'r> {
+    ///     type Error = &'r str;
+    ///
+    ///     fn from_param(param: &'r str) -> Result<Self, Self::Error> {
+    ///         match param.chars().all(|c| c.is_ascii_lowercase() || c == '-') {
+    ///             true => Ok(Slug(param)),
+    ///             false => Err(param),
+    ///         }
+    ///     }
+    ///
+    ///     fn failure_status(_: &Self::Error) -> Option<Status> {
+    ///         Some(Status::NotFound)
+    ///     }
+    /// }
+    /// ```
+    #[inline(always)]
+    fn failure_status(error: &Self::Error) -> Option<Status> {
+        let _ = error;
+        None
+    }
 }
 
 impl<'a> FromParam<'a> for &'a str {
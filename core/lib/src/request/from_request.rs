@@ -6,6 +6,7 @@ use crate::outcome::{self, IntoOutcome};
 use crate::outcome::Outcome::*;
 
 use crate::http::{Status, ContentType, Accept, Method, CookieJar};
+use crate::http::{CacheControl, Authorization, Range, IfNoneMatch};
 use crate::http::uri::{Host, Origin};
 
 /// Type alias for the `Outcome` of a `FromRequest` conversion.
@@ -163,6 +164,14 @@ impl<S, E> IntoOutcome<S, (Status, E), ()> for Result<S, E> {
 ///     [`Request::content_type()`]. If the request didn't specify a
 ///     Content-Type, the request is forwarded.
 ///
+///   * **[`CacheControl`], [`Authorization`], [`Range`], [`IfNoneMatch`]**
+///
+///     Parses the corresponding header from the incoming request. If the
+///     request doesn't contain the header, the request is forwarded. If the
+///     header is malformed, the request fails with `400 Bad Request` and the
+///     parse error. Multiple `Cache-Control` or `If-None-Match` headers are
+///     combined into one.
+///
 ///   * **IpAddr**
 ///
 ///     Extracts the client ip address of the incoming request as an [`IpAddr`]
@@ -191,8 +200,42 @@ impl<S, E> IntoOutcome<S, (Status, E), ()> for Result<S, E> {
 ///     returned in `Err`. If the derivation is a `Forward`, the request is
 ///     forwarded.
 ///
+///   * **Either&lt;A, B>** _where_ **A: FromRequest, B: FromRequest, A::Error: Send**
+///
+///     The type `A` is derived from the incoming request using `A`'s
+///     `FromRequest` implementation. If derivation is a `Success`, the value is
+///     returned in `Left`. Otherwise, `B` is derived in the same manner and,
+///     on `Success`, returned in `Right`. If neither guard succeeds but either
+///     fails, the request fails with the status and error of the first guard
+///     to fail, in `Left` or `Right`. The request is forwarded if both guards
+///     forward. See [`Any`](crate::request::Any) for more than two guards.
+///
 /// [`Config`]: crate::config::Config
 ///
+/// # Deriving
+///
+/// `FromRequest` can be derived for structures whose fields are all request
+/// guards with `#[derive(FromRequest)]`. The fields are resolved in order,
+/// short-circuiting on the first forward or failure. This allows a group of
+/// routes to share a single guard in place of a long list of arguments. See
+/// the [derive](macro@rocket::FromRequest) docs for details.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::{CookieJar, Method};
+/// use rocket::request::RequestId;
+///
+/// #[derive(FromRequest)]
+/// struct Ctx<'r> {
+///     method: Method,
+///     id: &'r RequestId,
+///     cookies: &'r CookieJar<'r>,
+/// }
+///
+/// #[get("/")]
+/// fn index(ctx: Ctx<'_>) { /* .. */ }
+/// ```
+///
 /// # Example
 ///
 /// Imagine you're running an authenticated API service that requires that some
@@ -465,6 +508,38 @@ impl<'r> FromRequest<'r> for &'r ContentType {
     }
 }
 
+macro_rules! typed_header_guard {
+    ($T:ty, $name:expr, $parse:expr) => {
+        #[crate::async_trait]
+        impl<'r> FromRequest<'r> for $T {
+            type Error = String;
+
+            async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+                let parse: fn(&Request<'_>) -> Option<Result<$T, String>> = $parse;
+                match parse(request) {
+                    Some(Ok(value)) => Success(value),
+                    Some(Err(e)) => Failure((Status::BadRequest, e)),
+                    None => Forward(()),
+                }
+            }
+        }
+    };
+    (one $T:ty, $name:expr) => {
+        typed_header_guard!($T, $name, |req| req.headers().get_one($name).map(str::parse));
+    };
+    (list $T:ty, $name:expr) => {
+        typed_header_guard!($T, $name, |req| {
+            let values = req.headers().get($name).collect::<Vec<_>>();
+            (!values.is_empty()).then(|| values.join(", ").parse())
+        });
+    };
+}
+
+typed_header_guard!(list CacheControl, "Cache-Control");
+typed_header_guard!(one Authorization, "Authorization");
+typed_header_guard!(one Range, "Range");
+typed_header_guard!(list IfNoneMatch, "If-None-Match");
+
 #[crate::async_trait]
 impl<'r> FromRequest<'r> for IpAddr {
     type Error = std::convert::Infallible;
@@ -513,3 +588,25 @@ impl<'r, T: FromRequest<'r>> FromRequest<'r> for Option<T> {
         }
     }
 }
+
+#[crate::async_trait]
+impl<'r, A, B, E> FromRequest<'r> for crate::Either<A, B>
+    where A: FromRequest<'r, Error = E>, B: FromRequest<'r>, E: std::fmt::Debug + Send
+{
+    type Error = crate::Either<E, B::Error>;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let left = match A::from_request(request).await {
+            Success(val) => return Success(crate::Either::Left(val)),
+            Failure(failure) => Some(failure),
+            Forward(_) => None,
+        };
+
+        match (left, B::from_request(request).await) {
+            (_, Success(val)) => Success(crate::Either::Right(val)),
+            (Some((status, e)), _) => Failure((status, crate::Either::Left(e))),
+            (None, Failure((status, e))) => Failure((status, crate::Either::Right(e))),
+            (None, Forward(_)) => Forward(()),
+        }
+    }
+}
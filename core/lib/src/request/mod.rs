@@ -3,18 +3,35 @@
 mod request;
 mod from_param;
 mod from_request;
+mod deadline;
+mod request_id;
+mod any;
+mod base_url;
+mod subdomain;
+mod forwarded;
+mod scope;
 
 #[cfg(test)]
 mod tests;
 
+#[doc(hidden)]
+pub use rocket_codegen::{FromParam, FromRequest};
+
 pub use self::request::Request;
 pub use self::from_request::{FromRequest, Outcome};
 pub use self::from_param::{FromParam, FromSegments};
+pub use self::deadline::Deadline;
+pub use self::request_id::RequestId;
+pub use self::any::{Any, Guards};
+pub use self::base_url::BaseUrl;
+pub use self::subdomain::Subdomain;
+pub use self::scope::{Scope, Scoped};
 
 #[doc(inline)]
 pub use crate::response::flash::FlashMessage;
 
 pub(crate) use self::request::ConnectionMeta;
+pub(crate) use self::forwarded::Forwarded;
 
 crate::export! {
     /// Store and immediately retrieve a vector-like value `$v` (`String` or
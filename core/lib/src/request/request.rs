@@ -2,14 +2,15 @@ use std::fmt;
 use std::ops::RangeFrom;
 use std::{future::Future, borrow::Cow, sync::Arc};
 use std::net::{IpAddr, SocketAddr};
+use std::time::Instant;
 
 use yansi::Paint;
 use state::{Container, Storage};
 use futures::future::BoxFuture;
 use atomic::{Atomic, Ordering};
 
-use crate::{Rocket, Route, Orbit};
-use crate::request::{FromParam, FromSegments, FromRequest, Outcome};
+use crate::{Rocket, Route, Orbit, Config};
+use crate::request::{FromParam, FromSegments, FromRequest, Outcome, Forwarded, Scope};
 use crate::form::{self, ValueField, FromForm};
 use crate::data::Limits;
 
@@ -17,7 +18,7 @@ use crate::http::{hyper, Method, Header, HeaderMap};
 use crate::http::{ContentType, Accept, MediaType, CookieJar, Cookie};
 use crate::http::uncased::UncasedStr;
 use crate::http::private::Certificates;
-use crate::http::uri::{fmt::Path, Origin, Segments, Host, Authority};
+use crate::http::uri::{fmt::Path, Origin, Segments, Host, Authority, Absolute};
 
 /// The type of an incoming web request.
 ///
@@ -45,13 +46,22 @@ pub(crate) struct ConnectionMeta {
 pub(crate) struct RequestState<'r> {
     pub rocket: &'r Rocket<Orbit>,
     pub route: Atomic<Option<&'r Route>>,
+    pub deadline: Atomic<Option<Instant>>,
+    #[cfg(feature = "tracing")]
+    pub span: tracing_::Span,
     pub cookies: CookieJar<'r>,
     pub accept: Storage<Option<Accept>>,
     pub content_type: Storage<Option<ContentType>>,
     pub cache: Arc<Container![Send + Sync]>,
     pub host: Option<Host<'r>>,
+    pub version: Option<hyper::Version>,
+    pub guard_overrides: Option<&'r crate::local::GuardOverrides>,
 }
 
+/// The cause of a request's failure, cached in its local cache.
+#[derive(Default)]
+struct RecordedError(std::sync::Mutex<Option<Arc<dyn std::error::Error + Send + Sync>>>);
+
 impl Request<'_> {
     pub(crate) fn clone(&self) -> Self {
         Request {
@@ -69,11 +79,16 @@ impl RequestState<'_> {
         RequestState {
             rocket: self.rocket,
             route: Atomic::new(self.route.load(Ordering::Acquire)),
+            deadline: Atomic::new(self.deadline.load(Ordering::Acquire)),
+            #[cfg(feature = "tracing")]
+            span: self.span.clone(),
             cookies: self.cookies.clone(),
             accept: self.accept.clone(),
             content_type: self.content_type.clone(),
             cache: self.cache.clone(),
             host: self.host.clone(),
+            version: self.version,
+            guard_overrides: self.guard_overrides,
         }
     }
 }
@@ -97,11 +112,16 @@ impl<'r> Request<'r> {
             state: RequestState {
                 rocket,
                 route: Atomic::new(None),
+                deadline: Atomic::new(None),
+                #[cfg(feature = "tracing")]
+                span: tracing_::Span::none(),
                 cookies: CookieJar::new(rocket.config()),
                 accept: Storage::new(),
                 content_type: Storage::new(),
                 cache: Arc::new(<Container![Send + Sync]>::new()),
                 host: None,
+                version: None,
+                guard_overrides: None,
             }
         }
     }
@@ -301,6 +321,77 @@ impl<'r> Request<'r> {
         self.state.host = Some(host);
     }
 
+    /// Returns the absolute base URL of the application, if it is known.
+    ///
+    /// If [`Config::base_url`](crate::Config::base_url) is set, it is returned.
+    /// Otherwise, the base URL is derived from the request: the host is
+    /// [`Request::host()`], and the scheme is `https` when TLS is enabled and
+    /// `http` otherwise. If there is no host, returns `None`.
+    ///
+    /// Forwarding headers are only considered when [`Config::trusted_proxies`]
+    /// is set. Then, the host and scheme are read from the `Forwarded` or
+    /// `X-Forwarded-Host` and `X-Forwarded-Proto` headers as set by trusted
+    /// proxies, if any, before falling back as above; see
+    /// [`Request::client_ip()`]. Without `trusted_proxies`, these headers are
+    /// ignored: any client can set them.
+    ///
+    /// The returned URI can be used as a prefix to [`uri!`](crate::uri!) to
+    /// generate absolute URIs. The [`BaseUrl`](crate::request::BaseUrl) request
+    /// guard provides the same value to handlers.
+    ///
+    /// **Note:** The `Host` header is client-controlled. When the base URL is
+    /// used in security-sensitive contexts, such as links sent in emails,
+    /// configure `base_url` or validate the host against a whitelist with
+    /// [`Host::to_absolute()`]. Configuring `trusted_proxies` prevents clients
+    /// from forging forwarding headers but not the `Host` header.
+    ///
+    /// [`Config::trusted_proxies`]: crate::Config::trusted_proxies
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::http::Header;
+    /// use rocket::http::uri::Host;
+    ///
+    /// #[get("/reset")]
+    /// fn reset() { /* .. */ }
+    ///
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let mut req = c.get("/");
+    /// assert_eq!(req.base_url(), None);
+    ///
+    /// req.inner_mut().set_host(Host::from(uri!("rocket.rs")));
+    /// let base = req.base_url().unwrap();
+    /// assert_eq!(base, uri!("http://rocket.rs"));
+    /// assert_eq!(uri!(base, reset), uri!("http://rocket.rs/reset"));
+    ///
+    /// // Without `trusted_proxies`, forwarding headers are ignored.
+    /// let req = req.header(Header::new("X-Forwarded-Host", "attacker.com"));
+    /// assert_eq!(req.base_url().unwrap(), uri!("http://rocket.rs"));
+    /// ```
+    pub fn base_url(&self) -> Option<Absolute<'static>> {
+        let config = self.rocket().config();
+        if let Some(ref base_url) = config.base_url {
+            return Some(base_url.clone());
+        }
+
+        let forwarded = config.trusted_proxies.as_ref().map(|_| Forwarded::from_request(self));
+        let forwarded_host = forwarded.as_ref()
+            .and_then(|forwarded| forwarded.host)
+            .and_then(|host| Host::parse(host).ok());
+
+        let host = forwarded_host.as_ref().or_else(|| self.host())?;
+        let scheme = match forwarded.and_then(|forwarded| forwarded.proto) {
+            Some(proto) if proto.eq_ignore_ascii_case("https") => "https",
+            Some(proto) if proto.eq_ignore_ascii_case("http") => "http",
+            _ if config.tls_enabled() => "https",
+            _ => "http",
+        };
+
+        Absolute::parse_owned(format!("{}://{}", scheme, host)).ok()
+    }
+
     /// Returns the raw address of the remote connection that initiated this
     /// request if the address is known. If the address is not known, `None` is
     /// returned.
@@ -359,6 +450,12 @@ impl<'r> Request<'r> {
     /// Returns the IP address in the "X-Real-IP" header of the request if such
     /// a header exists and contains a valid IP address.
     ///
+    /// The header is returned as is, regardless of who set it. To retrieve
+    /// the client's address as reported by trusted proxies only, use
+    /// [`Request::client_ip()`] with [`Config::trusted_proxies`] set.
+    ///
+    /// [`Config::trusted_proxies`]: crate::Config::trusted_proxies
+    ///
     /// # Example
     ///
     /// ```rust
@@ -389,6 +486,38 @@ impl<'r> Request<'r> {
     /// address is returned. Otherwise, if the address of the remote connection
     /// is known, that address is returned. Otherwise, `None` is returned.
     ///
+    /// # Trusted Proxies
+    ///
+    /// Any client can set the "X-Real-IP" header. When
+    /// [`Config::trusted_proxies`] is set, forwarding headers are only
+    /// honored when set by a trusted proxy. The `Forwarded` header, or absent
+    /// it, the `X-Forwarded-For` header or "X-Real-IP" header, is walked from
+    /// the nearest proxy back toward the client: starting from the remote
+    /// connection's address, each recorded hop is accepted as long as the
+    /// address it was received from lies within a trusted network. The
+    /// address of the last accepted hop is returned, or the remote
+    /// connection's address if it isn't trusted. The forwarded scheme and
+    /// host used by [`Request::base_url()`] are read from the accepted hops
+    /// in the same manner.
+    ///
+    /// ```rust
+    /// # use rocket::http::Header;
+    /// let proxies = vec!["10.0.0.0/8".parse().unwrap()];
+    /// let config = rocket::Config { trusted_proxies: Some(proxies), ..Default::default() };
+    /// # let c = rocket::local::blocking::Client::debug(rocket::custom(config)).unwrap();
+    /// # let get = |remote: &str| c.get("/").remote(remote.parse().unwrap());
+    ///
+    /// // A request forwarded by a trusted proxy for `8.8.8.8`.
+    /// let req = get("10.0.0.1:80").header(Header::new("X-Forwarded-For", "8.8.8.8"));
+    /// assert_eq!(req.client_ip(), Some("8.8.8.8".parse().unwrap()));
+    ///
+    /// // The same headers set by an untrusted client are ignored.
+    /// let req = get("1.1.1.1:80").header(Header::new("X-Forwarded-For", "8.8.8.8"));
+    /// assert_eq!(req.client_ip(), Some("1.1.1.1".parse().unwrap()));
+    /// ```
+    ///
+    /// [`Config::trusted_proxies`]: crate::Config::trusted_proxies
+    ///
     /// # Example
     ///
     /// ```rust
@@ -411,7 +540,7 @@ impl<'r> Request<'r> {
     /// ```
     #[inline]
     pub fn client_ip(&self) -> Option<IpAddr> {
-        self.real_ip().or_else(|| self.remote().map(|r| r.ip()))
+        Forwarded::from_request(self).ip.or_else(|| self.remote().map(|r| r.ip()))
     }
 
     /// Returns a wrapped borrow to the cookies in `self`.
@@ -627,9 +756,12 @@ impl<'r> Request<'r> {
         &self.state.rocket
     }
 
-    /// Returns the configured application data limits.
+    /// Returns the application data limits that apply to the request.
     ///
-    /// This is convenience function equivalent to:
+    /// These are the limits in the [current
+    /// configuration](Rocket::current_config()) when the request was first
+    /// asked for its limits, which, absent a [configuration
+    /// reload](Rocket::refresh_config()), are equivalent to:
     ///
     /// ```rust
     /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
@@ -638,6 +770,10 @@ impl<'r> Request<'r> {
     /// # ;
     /// ```
     ///
+    /// If the request has been routed to a route that overrides limits, via the
+    /// `limits` route attribute parameter or [`Route::limit()`], the route's
+    /// limits take precedence over the configured limits of the same name.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -651,16 +787,64 @@ impl<'r> Request<'r> {
     /// // Retrieve the limit for files with extension `.pdf`; etails to 1MiB.
     /// assert_eq!(request.limits().get("file/pdf"), Some(1.mebibytes()));
     /// ```
-    #[inline(always)]
-    pub fn limits(&self) -> &'r Limits {
-        &self.rocket().config().limits
+    pub fn limits(&self) -> &Limits {
+        // The limits of a route with `overrides`, followed by those of any
+        // other route the request is forwarded to. Cells are only ever set.
+        struct Merged {
+            overrides: Limits,
+            limits: Limits,
+            next: Storage<Box<Merged>>,
+        }
+
+        // The current config when first asked, so limits are stable
+        // per-request, and the merged limits of the routes tried.
+        struct Snapshot {
+            config: Arc<Config>,
+            merged: Storage<Box<Merged>>,
+        }
+
+        let snapshot = self.local_cache(|| Snapshot {
+            config: self.rocket().current_config(),
+            merged: Storage::new(),
+        });
+
+        let overrides = match self.route().and_then(|route| route.limits.as_ref()) {
+            Some(overrides) => overrides,
+            None => return &snapshot.config.limits,
+        };
+
+        let mut cell = &snapshot.merged;
+        loop {
+            match cell.try_get() {
+                Some(merged) if merged.overrides == *overrides => return &merged.limits,
+                Some(merged) => cell = &merged.next,
+                None => {
+                    cell.set(Box::new(Merged {
+                        overrides: overrides.clone(),
+                        limits: snapshot.config.limits.merged(overrides),
+                        next: Storage::new(),
+                    }));
+                }
+            }
+        }
     }
 
     /// Get the presently matched route, if any.
     ///
     /// This method returns `Some` any time a handler or its guards are being
     /// invoked. This method returns `None` _before_ routing has commenced; this
-    /// includes during request fairing callbacks.
+    /// includes during request and filter fairing callbacks. After routing,
+    /// including in catchers and response fairing callbacks, this method
+    /// returns the last route the request was routed to: the route whose
+    /// handler produced the response or, if every matching route forwarded or
+    /// failed, the last route attempted. It returns `None` if no route matched.
+    ///
+    /// The returned [`Route`] identifies the matched route by its
+    /// [`name`](Route::name), [`method`](Route::method), and URI template,
+    /// [`uri`](Route::uri), which includes the route's mount point, its
+    /// [`base`](crate::route::RouteUri::base()). Unlike the request's URI, the
+    /// template contains dynamic parameters, not their values, making it
+    /// suitable for aggregating metrics or logs by route.
     ///
     /// # Example
     ///
@@ -669,11 +853,147 @@ impl<'r> Request<'r> {
     /// # let request = c.get("/");
     /// let route = request.route();
     /// ```
+    ///
+    /// A response fairing that counts responses by route template:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use std::collections::HashMap;
+    /// use std::sync::Mutex;
+    ///
+    /// use rocket::{Request, Response};
+    /// use rocket::fairing::{Fairing, Info, Kind};
+    ///
+    /// #[derive(Default)]
+    /// struct Metrics(Mutex<HashMap<(String, u16), usize>>);
+    ///
+    /// #[rocket::async_trait]
+    /// impl Fairing for Metrics {
+    ///     fn info(&self) -> Info {
+    ///         Info { name: "Metrics", kind: Kind::Response }
+    ///     }
+    ///
+    ///     async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+    ///         let template = match req.route() {
+    ///             Some(route) => format!("{} {}", route.method, route.uri),
+    ///             None => "unmatched".into(),
+    ///         };
+    ///
+    ///         let key = (template, res.status().code);
+    ///         *self.0.lock().unwrap().entry(key).or_default() += 1;
+    ///     }
+    /// }
+    ///
+    /// #[get("/<id>")]
+    /// fn user(id: usize) -> String { id.to_string() }
+    ///
+    /// # use rocket::local::blocking::Client;
+    /// let metrics = std::sync::Arc::new(Metrics::default());
+    /// let rocket = rocket::build()
+    ///     .mount("/users", routes![user])
+    ///     .attach(metrics.clone());
+    ///
+    /// let client = Client::debug(rocket).unwrap();
+    /// client.get("/users/1").dispatch();
+    /// client.get("/users/2").dispatch();
+    ///
+    /// let counts = metrics.0.lock().unwrap();
+    /// assert_eq!(counts[&("GET /users/<id>".into(), 200)], 2);
+    /// ```
     #[inline(always)]
     pub fn route(&self) -> Option<&'r Route> {
         self.state.route.load(Ordering::Acquire)
     }
 
+    /// Returns the error recorded as the cause of the request's failure, if
+    /// any. Errors are recorded when a request or data guard fails, a handler
+    /// panics or times out, or a handler responds with a
+    /// [`Report`](crate::response::Report). The concrete type of an error
+    /// can be recovered via `downcast_ref()`. The error of a failing guard is
+    /// recorded as its `Debug` representation unless the guard recorded an
+    /// error itself via [`Request::record_error()`].
+    ///
+    /// This method is typically called by error catchers; typed catchers
+    /// receive the error directly. See [`Catcher`](crate::Catcher#typed-catchers).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::num::ParseIntError;
+    ///
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// let error = request.recorded_error();
+    /// assert!(error.is_none());
+    ///
+    /// if let Some(error) = error.as_deref().and_then(|e| e.downcast_ref::<ParseIntError>()) {
+    ///     println!("invalid integer: {}", error);
+    /// }
+    /// ```
+    pub fn recorded_error(&self) -> Option<Arc<dyn std::error::Error + Send + Sync>> {
+        let recorded = self.local_cache(RecordedError::default);
+        let error = recorded.0.lock().unwrap_or_else(|e| e.into_inner());
+        error.clone()
+    }
+
+    /// Returns the [`tracing`](crate::tracing) span for this request.
+    ///
+    /// Rocket creates an `INFO` level span named `request` for every request
+    /// just before running request fairings. The span is entered while request
+    /// and response fairings, guards, handlers, and catchers run, so events and
+    /// spans created by any of them are nested within it. The span carries the
+    /// following fields:
+    ///
+    ///   * `method` - the request's method, after any `_method` override
+    ///   * `uri` - the request's URI, before any rewriting by fairings
+    ///   * `route` - the name of the route that is handling the request, if any
+    ///   * `status` - the code of the response's status
+    ///   * `request_id` - the request's [`RequestId`](crate::request::RequestId),
+    ///     if the [`RequestId::fairing()`](crate::request::RequestId::fairing())
+    ///     is attached
+    ///
+    /// Before the span is created, including before a request is dispatched by
+    /// a local client, this method returns a disabled span.
+    ///
+    /// Because a span's fields are fixed when it is created, additional data
+    /// can't be recorded on the span itself. Instead, record it in an event or
+    /// a child span:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::fairing::AdHoc;
+    /// use rocket::tracing;
+    ///
+    /// let fairing = AdHoc::on_request("User Agent", |req, _| Box::pin(async move {
+    ///     let agent = req.headers().get_one("User-Agent");
+    ///     tracing::info!(parent: req.span(), agent, "received request");
+    /// }));
+    /// ```
+    ///
+    /// To collect Rocket's own log messages alongside spans, disable Rocket's
+    /// logger via the `log_level` configuration parameter and forward `log`
+    /// records to `tracing` with a bridge like `tracing-log`.
+    #[cfg(feature = "tracing")]
+    #[cfg_attr(nightly, doc(cfg(feature = "tracing")))]
+    #[inline(always)]
+    pub fn span(&self) -> &tracing_::Span {
+        &self.state.span
+    }
+
+    /// Creates the `tracing` span for this request.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn start_span(&mut self) {
+        use tracing_::field::Empty;
+
+        self.state.span = tracing_::info_span!("request",
+            method = %self.method(),
+            uri = %self.uri,
+            route = Empty,
+            status = Empty,
+            request_id = Empty,
+        );
+    }
+
     /// Invokes the request guard implementation for `T`, returning its outcome.
     ///
     /// # Example
@@ -695,6 +1015,25 @@ impl<'r> Request<'r> {
         T::from_request(self)
     }
 
+    /// Returns the request's [`Scope`], the container of request-scoped values
+    /// provided by fairings and guards. The scope is created, empty, in the
+    /// [request-local cache](Request::local_cache()) when it's first
+    /// retrieved.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// struct Tenant(&'static str);
+    ///
+    /// request.scope().provide(Tenant("acme"));
+    /// assert_eq!(request.scope().get::<Tenant>().unwrap().0, "acme");
+    /// ```
+    pub fn scope(&self) -> &Scope {
+        self.local_cache(Scope::default)
+    }
+
     /// Retrieves the cached value for type `T` from the request-local cached
     /// state of `self`. If no such value has previously been cached for this
     /// request, `f` is called to produce the value which is subsequently
@@ -918,6 +1257,26 @@ impl<'r> Request<'r> {
         }
     }
 
+    /// Records the `Debug` representation of a failing guard's error as the
+    /// cause of the request's failure unless the guard recorded an error
+    /// itself. Used by codegen.
+    pub fn _record_guard_error(&self, error: &dyn fmt::Debug) {
+        let recorded = self.local_cache(RecordedError::default);
+        let mut recorded = recorded.0.lock().unwrap_or_else(|e| e.into_inner());
+        if recorded.is_none() {
+            let error: Box<dyn std::error::Error + Send + Sync> = format!("{:?}", error).into();
+            *recorded = Some(error.into());
+        }
+    }
+
+    /// Returns the override of guards of type `T` set by a local client, if
+    /// any: `Some(value)` to succeed with `value`, `None` to forward. Used by
+    /// codegen.
+    #[inline]
+    pub fn _guard_override<T: 'static>(&self) -> Option<Option<T>> {
+        self.state.guard_overrides?.get()
+    }
+
     /// Get the `n`th path segment, 0-indexed, after the mount point for the
     /// currently matched route, as a string, if it exists. Used by codegen.
     #[inline]
@@ -952,6 +1311,62 @@ impl<'r> Request<'r> {
         self.state.route.store(Some(route), Ordering::Release)
     }
 
+    /// Set the deadline by which the handler of the current route must
+    /// complete, if any.
+    pub(crate) fn set_deadline(&self, deadline: Option<Instant>) {
+        self.state.deadline.store(deadline, Ordering::Release)
+    }
+
+    /// The deadline set for the current route, if any.
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        self.state.deadline.load(Ordering::Acquire)
+    }
+
+    /// The HTTP version of the request, if it was received over the network.
+    pub(crate) fn version(&self) -> Option<hyper::Version> {
+        self.state.version
+    }
+
+    /// Records `error` as the cause of the request's failure, replacing any
+    /// previously recorded error. The error is reported to
+    /// [`on_error`](crate::fairing::Fairing::on_error()) fairings and is
+    /// available to catchers via [`Request::recorded_error()`].
+    ///
+    /// A failing request or data guard's error is recorded as its `Debug`
+    /// representation unless the guard recorded an error itself. A guard can
+    /// thus make its concrete error available to [typed
+    /// catchers](crate::Catcher#typed-catchers) by recording it before failing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::num::ParseIntError;
+    ///
+    /// use rocket::request::{self, Request, FromRequest};
+    /// use rocket::http::Status;
+    ///
+    /// struct Page(usize);
+    ///
+    /// #[rocket::async_trait]
+    /// impl<'r> FromRequest<'r> for Page {
+    ///     type Error = ParseIntError;
+    ///
+    ///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+    ///         match req.headers().get_one("X-Page").unwrap_or("1").parse() {
+    ///             Ok(page) => request::Outcome::Success(Page(page)),
+    ///             Err(e) => {
+    ///                 req.record_error(Box::new(e.clone()));
+    ///                 request::Outcome::Failure((Status::BadRequest, e))
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn record_error(&self, error: Box<dyn std::error::Error + Send + Sync>) {
+        let recorded = self.local_cache(RecordedError::default);
+        *recorded.0.lock().unwrap_or_else(|e| e.into_inner()) = Some(error.into());
+    }
+
     /// Set the method of `self`, even when `self` is a shared reference. Used
     /// during routing to override methods for re-routing.
     #[inline(always)]
@@ -963,6 +1378,13 @@ impl<'r> Request<'r> {
         &mut self.state.cookies
     }
 
+    /// Removes all headers named `name` from `self`.
+    #[cfg(feature = "compression")]
+    pub(crate) fn remove_header(&mut self, name: &str) {
+        self.bust_header_cache(name.into(), true);
+        self.headers.remove(name);
+    }
+
     /// Convert from Hyper types into a Rocket Request.
     pub(crate) fn from_hyp(
         rocket: &'r Rocket<Orbit>,
@@ -972,8 +1394,10 @@ impl<'r> Request<'r> {
         // Keep track of parsing errors; emit a `BadRequest` if any exist.
         let mut errors = vec![];
 
-        // Ensure that the method is known. TODO: Allow made-up methods?
+        // Ensure that the method is known or is an extension method of a route.
         let method = Method::from_hyp(&hyper.method)
+            .or_else(|| rocket.router.extension_method(hyper.method.as_str()))
+            .or_else(|| rocket.hot_router.extension_method(hyper.method.as_str()))
             .unwrap_or_else(|| {
                 errors.push(Kind::BadMethod(&hyper.method));
                 Method::Get
@@ -1008,6 +1432,8 @@ impl<'r> Request<'r> {
             request.connection = connection;
         }
 
+        request.state.version = Some(hyper.version);
+
         // Determine + set host. On HTTP < 2, use the `HOST` header. Otherwise,
         // use the `:authority` pseudo-header which hyper makes part of the URI.
         request.state.host = if hyper.version < hyper::Version::HTTP_2 {
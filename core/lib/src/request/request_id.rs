@@ -0,0 +1,231 @@
+use std::fmt;
+use std::convert::Infallible;
+
+use crate::{Request, Response, Data};
+use crate::fairing::{Fairing, Info, Kind};
+use crate::http::Header;
+use crate::request::{self, FromRequest};
+
+/// An identifier for a request, used to correlate it across services.
+///
+/// A request's ID is the value of its `X-Request-Id` header, if it has one that
+/// is valid, or a freshly generated, random version 4 UUID otherwise. A header
+/// value is valid if it consists of between 1 and 200 visible ASCII
+/// characters. The ID of a request is fixed the first time it's retrieved and
+/// is thereafter available via [`RequestId::of()`] or the `&RequestId` request
+/// guard.
+///
+/// # Fairing
+///
+/// The fairing returned by [`RequestId::fairing()`] assigns every request an
+/// ID, adds an `X-Request-Id` header with the ID to every response, including
+/// error responses, unless the response already has one, and logs the ID.
+/// When the `tracing` feature is enabled, the fairing also records the ID in
+/// the `request_id` field of the request's [span](crate::Request::span()).
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::RequestId;
+///
+/// #[get("/")]
+/// fn index(id: &RequestId) -> String {
+///     format!("handling request {}", id)
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .attach(RequestId::fairing())
+///         .mount("/", routes![index])
+/// }
+/// ```
+///
+/// # Error Output
+///
+/// Catchers can retrieve the ID of the failing request with
+/// [`RequestId::of()`], for instance to include it in an error page:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::Request;
+/// use rocket::request::RequestId;
+///
+/// #[catch(500)]
+/// fn internal_error(req: &Request) -> String {
+///     format!("Something went wrong. Reference: {}", RequestId::of(req))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RequestId(String);
+
+impl RequestId {
+    /// The name of the header from which request IDs are read and to which
+    /// they are written: `X-Request-Id`.
+    pub const HEADER: &'static str = "X-Request-Id";
+
+    /// The maximum length of a request ID read from a request's header.
+    const MAX_LEN: usize = 200;
+
+    /// Returns the ID of `req`, reading it from the `X-Request-Id` header or
+    /// generating one the first time it's retrieved.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::RequestId;
+    /// # let client = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let (req, other) = (client.get("/"), client.get("/"));
+    ///
+    /// let id = RequestId::of(&req);
+    /// assert_eq!(id, RequestId::of(&req));
+    /// assert_ne!(id, RequestId::of(&other));
+    ///
+    /// let req = client.get("/").header(rocket::http::Header::new("X-Request-Id", "abc-123"));
+    /// assert_eq!(RequestId::of(&req).as_str(), "abc-123");
+    /// ```
+    pub fn of<'r>(req: &'r Request<'_>) -> &'r RequestId {
+        req.local_cache(|| {
+            req.headers().get_one(RequestId::HEADER)
+                .filter(|id| RequestId::is_valid(id))
+                .map(|id| RequestId(id.into()))
+                .unwrap_or_else(RequestId::generate)
+        })
+    }
+
+    /// Returns a fairing that assigns IDs to requests and adds them to
+    /// responses. See the [fairing](#fairing) documentation for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::RequestId;
+    ///
+    /// let rocket = rocket::build().attach(RequestId::fairing());
+    /// ```
+    pub fn fairing() -> impl Fairing {
+        RequestIdFairing
+    }
+
+    /// Returns the ID as a string slice.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::RequestId;
+    /// # let client = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let req = client.get("/");
+    ///
+    /// let id = RequestId::of(&req);
+    /// assert_eq!(id.as_str().len(), 36);
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    fn is_valid(id: &str) -> bool {
+        !id.is_empty() && id.len() <= Self::MAX_LEN && id.bytes().all(|b| b.is_ascii_graphic())
+    }
+
+    /// Generates a random, version 4 UUID.
+    fn generate() -> RequestId {
+        let mut bytes: [u8; 16] = rand::random();
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+        let mut id = String::with_capacity(36);
+        for (i, byte) in bytes.iter().enumerate() {
+            if matches!(i, 4 | 6 | 8 | 10) {
+                id.push('-');
+            }
+
+            id.push_str(&format!("{:02x}", byte));
+        }
+
+        RequestId(id)
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl AsRef<str> for RequestId {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq<str> for RequestId {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for RequestId {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for &'r RequestId {
+    type Error = Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        request::Outcome::Success(RequestId::of(req))
+    }
+}
+
+struct RequestIdFairing;
+
+#[crate::async_trait]
+impl Fairing for RequestIdFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "Request ID",
+            kind: Kind::Request | Kind::Response | Kind::Singleton,
+        }
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _: &mut Data<'_>) {
+        let _id = RequestId::of(req);
+
+        #[cfg(feature = "tracing")]
+        req.span().record("request_id", _id.as_str());
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let id = RequestId::of(req);
+        info_!("Request ID: {}", yansi::Paint::default(id).bold());
+        if !res.headers().contains(RequestId::HEADER) {
+            res.set_header(Header::new(RequestId::HEADER, id.to_string()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RequestId;
+
+    #[test]
+    fn test_generates_v4_uuids() {
+        let id = RequestId::generate();
+        let parts: Vec<_> = id.as_str().split('-').collect();
+        assert_eq!(parts.iter().map(|p| p.len()).collect::<Vec<_>>(), [8, 4, 4, 4, 12]);
+        assert!(parts[2].starts_with('4'));
+        assert!(matches!(parts[3].as_bytes()[0], b'8' | b'9' | b'a' | b'b'));
+        assert_ne!(id, RequestId::generate());
+    }
+
+    #[test]
+    fn test_validates_header_ids() {
+        assert!(RequestId::is_valid("abc-123"));
+        assert!(RequestId::is_valid(&"a".repeat(RequestId::MAX_LEN)));
+        assert!(!RequestId::is_valid(""));
+        assert!(!RequestId::is_valid("has space"));
+        assert!(!RequestId::is_valid("ünïcode"));
+        assert!(!RequestId::is_valid(&"a".repeat(RequestId::MAX_LEN + 1)));
+    }
+}
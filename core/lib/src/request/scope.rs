@@ -0,0 +1,273 @@
+use std::any::{type_name, Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use crate::http::Status;
+use crate::request::{self, FromRequest, Request};
+
+type Shared = Arc<dyn Any + Send + Sync>;
+
+/// A value in a scope. Sealed values can only be replaced by overrides.
+#[derive(Clone)]
+struct Entry {
+    value: Shared,
+    sealed: bool,
+}
+
+/// A request-scoped container of typed values.
+///
+/// Every request has a `Scope`, retrievable via [`Request::scope()`], that
+/// holds at most one value per type. Fairings and request guards
+/// [`provide`](Scope::provide()) values, such as the current tenant, a unit of
+/// work, or the request's feature flags, and later guards and handlers resolve
+/// them via [`Scope::get()`] or the [`Scoped`] request guard. To make a value
+/// available to templates, inject it into their context with
+/// `rocket_dyn_templates::Template::inject()`.
+///
+/// Unlike [managed state](crate::State), which is global, scoped values are
+/// dropped once the request completes. Unlike the [request-local
+/// cache](Request::local_cache()), where the first value stored for a type
+/// wins, a scoped value can be replaced by providing another value of the same
+/// type, allowing later fairings and guards to refine earlier ones.
+///
+/// # Overrides
+///
+/// A value provided via [`Scope::provide_override()`] is _sealed_: it replaces
+/// any existing value of its type and can only itself be replaced by another
+/// override. Subsequent calls to [`Scope::provide()`] for its type are ignored.
+/// This allows tests to fix a scoped value, say the current tenant, regardless
+/// of the fairings or guards that would otherwise provide it. A local
+/// [`Client`](crate::local::blocking::Client) applies the overrides set via
+/// `Client::override_scoped()` to every request it dispatches.
+///
+/// # Example
+///
+/// A fairing resolves the current tenant and a handler retrieves it with the
+/// [`Scoped`] guard:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::fairing::AdHoc;
+/// use rocket::request::Scoped;
+///
+/// struct Tenant(String);
+///
+/// #[get("/")]
+/// fn index(tenant: Scoped<Tenant>) -> String {
+///     format!("Hello, {}!", tenant.0)
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .attach(AdHoc::on_request("Tenant", |req, _| Box::pin(async move {
+///             let host = req.host().map(|h| h.domain().to_string());
+///             req.scope().provide(Tenant(host.unwrap_or_default()));
+///         })))
+///         .mount("/", routes![index])
+/// }
+/// ```
+#[derive(Default)]
+pub struct Scope {
+    values: RwLock<HashMap<TypeId, Entry>>,
+}
+
+impl Scope {
+    /// Provides `value`, replacing any existing value of type `T` unless the
+    /// existing value is an [override](#overrides). Returns `true` if `value`
+    /// was stored and `false` if it was ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// struct Flags { beta: bool }
+    ///
+    /// assert!(request.scope().provide(Flags { beta: false }));
+    /// assert!(request.scope().provide(Flags { beta: true }));
+    /// assert!(request.scope().get::<Flags>().unwrap().beta);
+    /// ```
+    pub fn provide<T: Send + Sync + 'static>(&self, value: T) -> bool {
+        self.insert(Arc::new(value), false)
+    }
+
+    /// Provides `value` as an [override](#overrides), replacing any existing
+    /// value of type `T` and sealing it against replacement by
+    /// [`Scope::provide()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// struct Tenant(&'static str);
+    ///
+    /// request.scope().provide_override(Tenant("test"));
+    /// assert!(!request.scope().provide(Tenant("acme")));
+    /// assert_eq!(request.scope().get::<Tenant>().unwrap().0, "test");
+    /// ```
+    pub fn provide_override<T: Send + Sync + 'static>(&self, value: T) {
+        self.insert(Arc::new(value), true);
+    }
+
+    fn insert(&self, value: Shared, sealed: bool) -> bool {
+        let type_id = Any::type_id(&*value);
+        let mut values = self.values.write();
+        if !sealed && values.get(&type_id).map_or(false, |entry| entry.sealed) {
+            return false;
+        }
+
+        values.insert(type_id, Entry { value, sealed });
+        true
+    }
+
+    /// Returns the value of type `T`, if there is one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// struct Tenant(&'static str);
+    ///
+    /// assert!(request.scope().get::<Tenant>().is_none());
+    ///
+    /// request.scope().provide(Tenant("acme"));
+    /// assert_eq!(request.scope().get::<Tenant>().unwrap().0, "acme");
+    /// ```
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        let value = self.values.read().get(&TypeId::of::<T>())?.value.clone();
+        value.downcast::<T>().ok()
+    }
+
+    /// Returns `true` if there is a value of type `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// struct Tenant(&'static str);
+    ///
+    /// assert!(!request.scope().contains::<Tenant>());
+    ///
+    /// request.scope().provide(Tenant("acme"));
+    /// assert!(request.scope().contains::<Tenant>());
+    /// ```
+    pub fn contains<T: Send + Sync + 'static>(&self) -> bool {
+        self.values.read().contains_key(&TypeId::of::<T>())
+    }
+
+    /// Removes the value of type `T`, if any.
+    pub(crate) fn remove<T: 'static>(&self) {
+        self.values.write().remove(&TypeId::of::<T>());
+    }
+
+    /// Provides every value in `other` as an override.
+    pub(crate) fn override_with(&self, other: &Scope) {
+        for entry in other.values.read().values() {
+            self.insert(entry.value.clone(), true);
+        }
+    }
+}
+
+impl fmt::Debug for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scope")
+            .field("values", &self.values.read().len())
+            .finish()
+    }
+}
+
+/// Request guard for a value in the request's [`Scope`].
+///
+/// The guard succeeds with the value of type `T` in the request's scope at the
+/// time the guard runs. If there is no such value, the guard fails with status
+/// `500 Internal Server Error`. Use `Option<Scoped<T>>` for values that may not
+/// be present.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{self, Request, FromRequest, Scoped};
+///
+/// struct Tenant(String);
+///
+/// struct TenantGuard;
+///
+/// #[rocket::async_trait]
+/// impl<'r> FromRequest<'r> for TenantGuard {
+///     type Error = ();
+///
+///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+///         let tenant = req.headers().get_one("X-Tenant").unwrap_or("default");
+///         req.scope().provide(Tenant(tenant.into()));
+///         request::Outcome::Success(TenantGuard)
+///     }
+/// }
+///
+/// #[get("/")]
+/// fn index(_t: TenantGuard, tenant: Scoped<Tenant>) -> String {
+///     format!("Tenant: {}", tenant.0)
+/// }
+/// ```
+pub struct Scoped<T>(Arc<T>);
+
+impl<T> Scoped<T> {
+    /// Returns the shared scoped value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use rocket::request::Scoped;
+    ///
+    /// fn handler(tenant: Scoped<String>) {
+    ///     let tenant: Arc<String> = tenant.into_inner();
+    /// }
+    /// ```
+    pub fn into_inner(self) -> Arc<T> {
+        self.0
+    }
+}
+
+impl<T> Clone for Scoped<T> {
+    fn clone(&self) -> Self {
+        Scoped(self.0.clone())
+    }
+}
+
+impl<T> Deref for Scoped<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Scoped<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[crate::async_trait]
+impl<'r, T: Send + Sync + 'static> FromRequest<'r> for Scoped<T> {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        match req.scope().get::<T>() {
+            Some(value) => request::Outcome::Success(Scoped(value)),
+            None => {
+                error_!("Attempted to retrieve missing scoped value `{}`!", type_name::<T>());
+                request::Outcome::Failure((Status::InternalServerError, ()))
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::fmt;
+use std::ops::Deref;
+
+use crate::request::{FromRequest, Outcome, Request};
+use crate::outcome::IntoOutcome;
+
+/// A request guard for the portion of the request's host matched by the
+/// wildcard in the routed route's [host pattern](crate::route::HostPattern).
+///
+/// For a route mounted via [`Rocket::mount_host()`](crate::Rocket::mount_host())
+/// with the pattern `*.example.com`, a request to `tenant.example.com` has the
+/// subdomain `tenant`, and a request to `a.b.example.com` has the subdomain
+/// `a.b`. If the request wasn't routed to a route with a wildcard host
+/// pattern, the request is forwarded.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::Subdomain;
+///
+/// #[get("/")]
+/// fn tenant(tenant: Subdomain<'_>) -> String {
+///     format!("Welcome, {}!", tenant)
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().mount_host("*.example.com", "/", routes![tenant])
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Subdomain<'r>(&'r str);
+
+impl<'r> Subdomain<'r> {
+    /// Returns the subdomain as a string slice.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::Subdomain;
+    ///
+    /// fn f(subdomain: Subdomain<'_>) {
+    ///     let tenant: &str = subdomain.as_str();
+    /// }
+    /// ```
+    pub fn as_str(&self) -> &'r str {
+        self.0
+    }
+}
+
+impl Deref for Subdomain<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl fmt::Display for Subdomain<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for Subdomain<'r> {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let pattern = request.route().and_then(|route| route.host.as_ref());
+        let host = request.host().map(|host| host.domain().as_str());
+        pattern.zip(host)
+            .and_then(|(pattern, host)| pattern.subdomain(host))
+            .map(Subdomain)
+            .or_forward(())
+    }
+}
@@ -44,3 +44,26 @@ fn test_multiple_headers_merge_into_one_from_hyp() {
     assert_headers!("friend" => ["alice"], "friend" => ["bob"], "friend" => ["carol"]);
     assert_headers!("friend" => ["alice"], "friend" => ["bob"], "enemy" => ["carol"]);
 }
+
+#[test]
+fn test_extension_methods_from_hyp() {
+    use crate::http::Method;
+    use crate::route::{Route, dummy_handler};
+
+    let route = Route::new(Method::Extension("PROPFIND"), "/", dummy_handler);
+    let client = Client::debug_with(vec![route]).unwrap();
+
+    let parts = |method: &str| hyper::Request::builder()
+        .method(method)
+        .uri("/")
+        .body(())
+        .unwrap()
+        .into_parts().0;
+
+    let propfind = parts("PROPFIND");
+    let req = Request::from_hyp(client.rocket(), &propfind, None).unwrap();
+    assert_eq!(req.method(), Method::Extension("PROPFIND"));
+
+    let mkcol = parts("MKCOL");
+    assert!(Request::from_hyp(client.rocket(), &mkcol, None).is_err());
+}
@@ -80,11 +80,19 @@ type SizedBody<'r> = Pin<Box<dyn AsyncReadSeek + Send + 'r>>;
 /// A pinned `AsyncRead` (not `AsyncSeek`) body type.
 type UnsizedBody<'r> = Pin<Box<dyn AsyncRead + Send + 'r>>;
 
+/// The reader of a non-empty body, as passed to and from [`Body::wrap()`].
+pub(crate) enum Reader<'r> {
+    Sized(SizedBody<'r>),
+    Unsized(UnsizedBody<'r>),
+}
+
 enum Inner<'r> {
     /// A body that can be seeked to determine it's size.
     Seekable(SizedBody<'r>),
     /// A body that has no known size.
     Unsized(UnsizedBody<'r>),
+    /// A file, which the server writes out by reading it directly.
+    File(tokio::fs::File),
     /// A body that "exists" but only for metadata calculations.
     Phantom(SizedBody<'r>),
     /// An empty body: no body at all.
@@ -117,6 +125,29 @@ impl<'r> Body<'r> {
         }
     }
 
+    pub(crate) fn with_file(file: tokio::fs::File, preset_size: Option<usize>) -> Self {
+        Body {
+            size: preset_size,
+            inner: Inner::File(file),
+            max_chunk: Body::DEFAULT_MAX_CHUNK,
+        }
+    }
+
+    /// Moves the file out of a body created with [`Body::with_file()`] that
+    /// has not since been wrapped, leaving a [`Body::default()`] in its place.
+    pub(crate) fn take_file(&mut self) -> Option<tokio::fs::File> {
+        match std::mem::replace(&mut self.inner, Inner::None) {
+            Inner::File(file) => {
+                *self = Body::default();
+                Some(file)
+            }
+            inner => {
+                self.inner = inner;
+                None
+            }
+        }
+    }
+
     pub(crate) fn with_unsized<T>(body: T) -> Self
         where T: AsyncRead + Send + 'r
     {
@@ -131,9 +162,18 @@ impl<'r> Body<'r> {
         self.max_chunk = max_chunk;
     }
 
+    pub(crate) fn set_preset_size(&mut self, size: usize) {
+        self.size = Some(size);
+    }
+
     pub(crate) fn strip(&mut self) {
         let body = std::mem::take(self);
         *self = match body.inner {
+            Inner::File(file) => Body {
+                size: body.size,
+                inner: Inner::Phantom(Box::pin(file)),
+                max_chunk: body.max_chunk,
+            },
             Inner::Seekable(b) | Inner::Phantom(b) => Body {
                 size: body.size,
                 inner: Inner::Phantom(b),
@@ -143,6 +183,51 @@ impl<'r> Body<'r> {
         };
     }
 
+    /// Wraps the reader of a non-empty body with `f`. A sized body wrapped into
+    /// an unsized reader becomes unsized; a phantom body wrapped into an
+    /// unsized reader, which would never be read, becomes empty.
+    pub(crate) fn wrap<F>(&mut self, f: F)
+        where F: FnOnce(Reader<'r>) -> Reader<'r>
+    {
+        let inner = std::mem::replace(&mut self.inner, Inner::None);
+        self.inner = match inner {
+            Inner::Seekable(b) => match f(Reader::Sized(b)) {
+                Reader::Sized(b) => Inner::Seekable(b),
+                Reader::Unsized(b) => {
+                    self.size = None;
+                    Inner::Unsized(b)
+                }
+            },
+            Inner::Phantom(b) => match f(Reader::Sized(b)) {
+                Reader::Sized(b) => Inner::Phantom(b),
+                Reader::Unsized(_) => Inner::None,
+            },
+            Inner::Unsized(b) => match f(Reader::Unsized(b)) {
+                Reader::Unsized(b) => Inner::Unsized(b),
+                Reader::Sized(b) => Inner::Seekable(b),
+            },
+            Inner::File(file) => {
+                self.inner = Inner::Seekable(Box::pin(file));
+                return self.wrap(f);
+            }
+            Inner::None => Inner::None,
+        };
+    }
+
+    /// Calls `f` with every chunk of data read from the body, and with an
+    /// empty chunk once the body has been read in its entirety, without
+    /// otherwise affecting the body.
+    pub(crate) fn inspect<F>(&mut self, f: F)
+        where F: FnMut(&[u8]) + Send + 'r
+    {
+        // Boxing `f` makes `Inspect` `Unpin` regardless of `F`.
+        let f = Box::new(f);
+        self.wrap(|reader| match reader {
+            Reader::Sized(b) => Reader::Sized(Box::pin(Inspect { inner: b, f })),
+            Reader::Unsized(b) => Reader::Unsized(Box::pin(Inspect { inner: b, f })),
+        });
+    }
+
     /// Returns `true` if the body is `None` or unset, the default.
     ///
     /// # Example
@@ -288,17 +373,14 @@ impl<'r> Body<'r> {
             return Some(size);
         }
 
-        if let Inner::Seekable(ref mut body) | Inner::Phantom(ref mut body) = self.inner {
-            let pos = body.seek(io::SeekFrom::Current(0)).await.ok()?;
-            let end = body.seek(io::SeekFrom::End(0)).await.ok()?;
-            body.seek(io::SeekFrom::Start(pos)).await.ok()?;
-
-            let size = end as usize - pos as usize;
-            self.size = Some(size);
-            return Some(size);
-        }
+        let size = match self.inner {
+            Inner::Seekable(ref mut body) | Inner::Phantom(ref mut body) => remaining(body).await,
+            Inner::File(ref mut file) => remaining(file).await,
+            Inner::Unsized(_) | Inner::None => return None,
+        };
 
-        None
+        self.size = Some(size.ok()? as usize);
+        self.size
     }
 
     /// Moves the body out of `self` and returns it, leaving a
@@ -404,6 +486,7 @@ impl AsyncRead for Body<'_> {
         let reader = match self.inner {
             Inner::Seekable(ref mut b) => b as &mut (dyn AsyncRead + Unpin),
             Inner::Unsized(ref mut b) => b as &mut (dyn AsyncRead + Unpin),
+            Inner::File(ref mut f) => f as &mut (dyn AsyncRead + Unpin),
             Inner::Phantom(_) | Inner::None => return Poll::Ready(Ok(())),
         };
 
@@ -411,11 +494,54 @@ impl AsyncRead for Body<'_> {
     }
 }
 
+/// Returns the number of bytes between the current and final positions of
+/// `body`, leaving its position unchanged.
+async fn remaining<S: AsyncSeek + Unpin>(body: &mut S) -> io::Result<u64> {
+    let pos = body.seek(io::SeekFrom::Current(0)).await?;
+    let end = body.seek(io::SeekFrom::End(0)).await?;
+    body.seek(io::SeekFrom::Start(pos)).await?;
+    Ok(end - pos)
+}
+
+/// A reader that passes all data read from `inner` to `f`.
+struct Inspect<R, F> {
+    inner: R,
+    f: F,
+}
+
+impl<R: AsyncRead + Unpin, F: FnMut(&[u8]) + Unpin> AsyncRead for Inspect<R, F> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let filled = buf.filled().len();
+        let has_capacity = buf.remaining() > 0;
+        futures::ready!(Pin::new(&mut self.inner).poll_read(cx, buf))?;
+        if has_capacity {
+            (self.f)(&buf.filled()[filled..]);
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<R: AsyncSeek + Unpin, F: Unpin> AsyncSeek for Inspect<R, F> {
+    fn start_seek(mut self: Pin<&mut Self>, position: io::SeekFrom) -> io::Result<()> {
+        Pin::new(&mut self.inner).start_seek(position)
+    }
+
+    fn poll_complete(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        Pin::new(&mut self.inner).poll_complete(cx)
+    }
+}
+
 impl fmt::Debug for Inner<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Inner::Seekable(_) => "seekable".fmt(f),
             Inner::Unsized(_) => "unsized".fmt(f),
+            Inner::File(_) => "file".fmt(f),
             Inner::Phantom(_) => "phantom".fmt(f),
             Inner::None => "none".fmt(f),
         }
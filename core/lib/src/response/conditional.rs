@@ -0,0 +1,164 @@
+use std::time::{Duration, SystemTime};
+
+use time::{OffsetDateTime, PrimitiveDateTime};
+use time::{macros::format_description, format_description::FormatItem};
+
+use crate::request::Request;
+use crate::response::{self, Responder};
+use crate::http::{Method, Status, EntityTag, ETag, IfNoneMatch};
+
+/// The IMF-fixdate format of HTTP dates, like `Sun, 06 Nov 1994 08:49:37 GMT`.
+static HTTP_DATE: &[FormatItem<'_>] = format_description!(
+    "[weekday repr:short], [day] [month repr:short] [year] [hour]:[minute]:[second] GMT"
+);
+
+/// Formats `time` as an HTTP date, truncated to the second.
+pub(crate) fn format_http_date(time: SystemTime) -> Option<String> {
+    OffsetDateTime::from(time).format(HTTP_DATE).ok()
+}
+
+/// Parses an IMF-fixdate HTTP date. Obsolete formats are not recognized.
+pub(crate) fn parse_http_date(date: &str) -> Option<SystemTime> {
+    PrimitiveDateTime::parse(date.trim(), HTTP_DATE).ok().map(|date| date.assume_utc().into())
+}
+
+/// Makes a response conditional on the version of the resource a client has.
+///
+/// A `Conditional` wraps another responder, `R`, and a _validator_ or two for
+/// the resource it responds with: an [`EntityTag`] identifying its version,
+/// its last modification time, or both. The validators are sent in `ETag` and
+/// `Last-Modified` headers. Clients that cached a response send them back in
+/// `If-None-Match` and `If-Modified-Since` headers, which `Conditional`
+/// evaluates as follows when `R` responds with `200 OK`:
+///
+///   * If `If-None-Match` matches the entity tag, `GET` and `HEAD` requests
+///     are answered with `304 Not Modified`, with the headers but without the
+///     body of `R`'s response, and other requests fail with `412 Precondition
+///     Failed`.
+///   * Otherwise, if there is no `If-None-Match` header, `GET` and `HEAD`
+///     requests with an `If-Modified-Since` no earlier than the last
+///     modification time are answered with `304 Not Modified`.
+///
+/// In all other cases, `R`'s response is sent as is. [`NamedFile`] uses a
+/// `Conditional` with tags derived from file metadata automatically.
+///
+/// [`NamedFile`]: crate::fs::NamedFile
+///
+/// # Example
+///
+/// ```rust
+/// # use rocket::get;
+/// use rocket::http::EntityTag;
+/// use rocket::response::Conditional;
+///
+/// #[get("/")]
+/// fn index() -> Conditional<&'static str> {
+///     let tag = EntityTag::strong("v1").unwrap();
+///     Conditional::new("Hello, world!").etag(tag)
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Conditional<R> {
+    responder: R,
+    etag: Option<EntityTag>,
+    last_modified: Option<SystemTime>,
+}
+
+impl<R> Conditional<R> {
+    /// Wraps `responder` without any validators.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Conditional;
+    ///
+    /// let response = Conditional::new("Hello, world!");
+    /// ```
+    pub fn new(responder: R) -> Self {
+        Conditional { responder, etag: None, last_modified: None }
+    }
+
+    /// Sets the entity tag of the response to `tag`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::EntityTag;
+    /// use rocket::response::Conditional;
+    ///
+    /// let tag = EntityTag::weak("v1").unwrap();
+    /// let response = Conditional::new("Hello, world!").etag(tag);
+    /// ```
+    pub fn etag(mut self, tag: EntityTag) -> Self {
+        self.etag = Some(tag);
+        self
+    }
+
+    /// Sets the last modification time of the response to `time`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::SystemTime;
+    /// use rocket::response::Conditional;
+    ///
+    /// let response = Conditional::new("Hello, world!").last_modified(SystemTime::now());
+    /// ```
+    pub fn last_modified(mut self, time: SystemTime) -> Self {
+        self.last_modified = Some(time);
+        self
+    }
+
+    /// Returns `true` if the client's copy of the response, as described in
+    /// the headers of `req`, is current.
+    fn not_modified(&self, req: &Request<'_>) -> bool {
+        let if_none_match = req.headers().get("If-None-Match").collect::<Vec<_>>();
+        if !if_none_match.is_empty() {
+            let header = if_none_match.join(", ").parse::<IfNoneMatch>();
+            return match (header, &self.etag) {
+                (Ok(IfNoneMatch::Any), _) => true,
+                (Ok(header), Some(tag)) => header.matches(tag),
+                _ => false,
+            };
+        }
+
+        let since = req.headers().get_one("If-Modified-Since").and_then(parse_http_date);
+        match (since, self.last_modified) {
+            (Some(since), Some(modified)) => {
+                // HTTP dates have a resolution of a second.
+                let modified = modified.duration_since(SystemTime::UNIX_EPOCH)
+                    .map(|d| SystemTime::UNIX_EPOCH + Duration::from_secs(d.as_secs()));
+
+                modified.map_or(false, |modified| modified <= since)
+                    && matches!(req.method(), Method::Get | Method::Head)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Conditional<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let not_modified = self.not_modified(req);
+        let mut response = self.responder.respond_to(req)?;
+        if let Some(tag) = self.etag {
+            response.set_header(ETag(tag));
+        }
+
+        if let Some(date) = self.last_modified.and_then(format_http_date) {
+            response.set_raw_header("Last-Modified", date);
+        }
+
+        if response.status() != Status::Ok || !not_modified {
+            return Ok(response);
+        }
+
+        if !matches!(req.method(), Method::Get | Method::Head) {
+            return Err(Status::PreconditionFailed);
+        }
+
+        response.set_status(Status::NotModified);
+        response.body_mut().strip();
+        Ok(response)
+    }
+}
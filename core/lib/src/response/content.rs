@@ -71,6 +71,7 @@ ctrs! {
     RawJson: JSON, "JSON", "application/json",
     RawXml: XML, "XML", "text/xml",
     RawMsgPack: MsgPack, "MessagePack", "application/msgpack",
+    RawCbor: CBOR, "CBOR", "application/cbor",
     RawHtml: HTML, "HTML", "text/html",
     RawText: Text, "plain text", "text/plain",
     RawCss: CSS, "CSS", "text/css",
@@ -13,6 +13,11 @@ const FLASH_COOKIE_NAME: &str = "_flash";
 // Character to use as a delimiter after the cookie's name's length.
 const FLASH_COOKIE_DELIM: char = ':';
 
+// The maximum size, in bytes, of the encoded flash cookie. Browsers are only
+// required to store cookies of up to 4096 bytes.
+#[cfg(feature = "json")]
+const FLASH_COOKIE_MAX_SIZE: usize = 4096;
+
 /// Sets a "flash" cookie that will be removed when it is accessed. The
 /// analogous request type is [`FlashMessage`].
 ///
@@ -34,6 +39,43 @@ const FLASH_COOKIE_DELIM: char = ':';
 /// Messages can be retrieved on the request side via the [`FlashMessage`] type
 /// and the [kind](#method.kind) and [message](#method.message) methods.
 ///
+/// ## Structured Messages
+///
+/// With the `json` feature enabled, a message can instead be any value that
+/// implements [`Serialize`]. [`Flash::serialized()`] serializes the value into
+/// the flash cookie, and [`FlashMessage::into()`] deserializes it on the
+/// request side:
+///
+/// ```rust
+/// # #[cfg(feature = "json")] mod example {
+/// # use rocket::{get, post, uri};
+/// use rocket::response::{Flash, Redirect};
+/// use rocket::request::FlashMessage;
+/// use rocket::serde::{Serialize, Deserialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// #[serde(crate = "rocket::serde")]
+/// struct Notice {
+///     field: String,
+///     problems: Vec<String>,
+/// }
+///
+/// #[post("/signup")]
+/// fn signup() -> Result<Flash<Redirect>, Redirect> {
+///     let notice = Notice { field: "email".into(), problems: vec!["taken".into()] };
+///     Flash::serialized(Redirect::to(uri!(form)), "error", &notice)
+/// }
+///
+/// #[get("/")]
+/// fn form(flash: Option<FlashMessage<'_>>) -> String {
+///     match flash.and_then(|f| f.into::<Notice>().ok()) {
+///         Some(notice) => format!("{}: {}", notice.field, notice.problems.join(", ")),
+///         None => "Sign up!".into(),
+///     }
+/// }
+/// # }
+/// ```
+///
 /// # Response
 ///
 /// The `Responder` implementation for `Flash` sets the message cookie and then
@@ -89,11 +131,13 @@ pub struct Flash<R> {
 /// there is a flash cookie present (set by the `Flash` `Responder`), a
 /// `FlashMessage` request guard will succeed.
 ///
-/// The flash cookie is cleared if either the [`kind()`] or [`message()`] method is
-/// called. If neither method is called, the flash cookie is not cleared.
+/// The flash cookie is cleared if the [`kind()`], [`message()`], or, with the
+/// `json` feature enabled, [`into()`] method is called. If none are called, the
+/// flash cookie is not cleared.
 ///
 /// [`kind()`]: Flash::kind()
 /// [`message()`]: Flash::message()
+/// [`into()`]: Flash::into()
 pub type FlashMessage<'a> = crate::response::Flash<&'a CookieJar<'a>>;
 
 impl<R> Flash<R> {
@@ -174,6 +218,54 @@ impl<R> Flash<R> {
         Flash::new(responder, "error", message.into())
     }
 
+    /// Constructs a `Flash` message with the given `kind`, the serialization of
+    /// `payload` as its message, and the underlying `responder`. The payload
+    /// can be retrieved with [`FlashMessage::into()`].
+    ///
+    /// Logs an error and returns `responder` as `Err` if `payload` fails to
+    /// serialize or if the resulting flash cookie would exceed 4096 bytes, the
+    /// largest size browsers are required to store.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "json")] {
+    /// use rocket::response::{Redirect, Flash};
+    ///
+    /// let errors = vec!["name is required", "age must be positive"];
+    /// let message = Flash::serialized(Redirect::to("/"), "errors", &errors);
+    /// assert!(message.is_ok());
+    ///
+    /// let too_long = "a".repeat(5000);
+    /// let message = Flash::serialized(Redirect::to("/"), "error", &too_long);
+    /// assert!(message.is_err());
+    /// # }
+    /// ```
+    #[cfg(feature = "json")]
+    #[cfg_attr(nightly, doc(cfg(feature = "json")))]
+    pub fn serialized<K, T>(responder: R, kind: K, payload: &T) -> Result<Flash<R>, R>
+        where K: Into<String>, T: Serialize + ?Sized
+    {
+        let message = match serde_json::to_string(payload) {
+            Ok(message) => message,
+            Err(e) => {
+                error_!("Flash: failed to serialize message: {}", e);
+                return Err(responder);
+            }
+        };
+
+        let flash = Flash::new(responder, kind, message);
+        let size = flash.cookie().encoded().to_string().len();
+        if size > FLASH_COOKIE_MAX_SIZE {
+            error_!("Flash: message cookie of {} bytes exceeds {} bytes.",
+                size, FLASH_COOKIE_MAX_SIZE);
+
+            return Err(flash.inner);
+        }
+
+        Ok(flash)
+    }
+
     fn cookie(&self) -> Cookie<'static> {
         let content = format!("{}{}{}{}",
             self.kind.len(), FLASH_COOKIE_DELIM, self.kind, self.message);
@@ -221,6 +313,30 @@ impl<'r> FlashMessage<'r> {
         (self.kind, self.message)
     }
 
+    /// Deserializes the `message` of this message, set via
+    /// [`Flash::serialized()`], as a `T`, consuming `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "json")] mod example {
+    /// # use rocket::get;
+    /// use rocket::request::FlashMessage;
+    ///
+    /// #[get("/")]
+    /// fn index(flash: Option<FlashMessage<'_>>) -> String {
+    ///     let errors = flash.and_then(|f| f.into::<Vec<String>>().ok());
+    ///     errors.map(|e| e.join("\n")).unwrap_or_default()
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "json")]
+    #[cfg_attr(nightly, doc(cfg(feature = "json")))]
+    pub fn into<T: serde::de::DeserializeOwned>(self) -> Result<T, serde_json::Error> {
+        self.clear_cookie_if_needed();
+        serde_json::from_str(&self.message)
+    }
+
     /// Returns the `kind` of this message.
     pub fn kind(&self) -> &str {
         self.clear_cookie_if_needed();
@@ -18,7 +18,10 @@ mod responder;
 mod redirect;
 mod response;
 mod debug;
+mod report;
 mod body;
+mod conditional;
+mod ranged;
 
 pub(crate) mod flash;
 
@@ -35,6 +38,13 @@ pub use self::responder::Responder;
 pub use self::redirect::Redirect;
 pub use self::flash::Flash;
 pub use self::debug::Debug;
+pub use self::report::Report;
+pub use self::conditional::Conditional;
+pub use self::ranged::Ranged;
+
+pub(crate) use self::body::Reader as BodyReader;
+pub(crate) use self::conditional::{format_http_date, parse_http_date};
+pub(crate) use self::ranged::select_range;
 
 /// Type alias for the `Result` of a [`Responder::respond_to()`] call.
 pub type Result<'r> = std::result::Result<Response<'r>, crate::http::Status>;
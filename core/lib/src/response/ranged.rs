@@ -0,0 +1,285 @@
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncSeek, ReadBuf};
+
+use crate::request::Request;
+use crate::response::{self, Response, Responder, BodyReader, parse_http_date};
+use crate::http::{Method, Status, EntityTag, Range};
+
+/// Makes a response support byte range requests.
+///
+/// A `Ranged` wraps another responder, `R`, whose response has a sized body of
+/// a known size: strings and byte vectors, [`NamedFile`], or any `AsyncRead +
+/// AsyncSeek` source of known length set via [`Response::set_sized_body()`].
+/// When `R` responds with `200 OK`, the response advertises `Accept-Ranges:
+/// bytes` and a `GET` or `HEAD` request with a single range in a `Range`
+/// header is answered as follows:
+///
+///   * If the range is satisfiable, with `206 Partial Content`, a
+///     `Content-Range` header, and only the bytes in the range, which are
+///     seeked to and read from the body as it's sent.
+///   * Otherwise, with `416 Range Not Satisfiable`, a `Content-Range` header
+///     with the length of the body, and an empty body.
+///
+/// A request with an `If-Range` header is only served a range if the header
+/// matches the response's `ETag`, by strong comparison, or its
+/// `Last-Modified` date, as set by a wrapped [`Conditional`]. In all other
+/// cases, including requests for multiple ranges, `R`'s response is sent as
+/// is. [`NamedFile`] responses support ranges automatically.
+///
+/// [`NamedFile`]: crate::fs::NamedFile
+/// [`Conditional`]: crate::response::Conditional
+///
+/// # Example
+///
+/// Serve media stored in some seekable source of known length, like an
+/// object store or a database blob, with support for seeking:
+///
+/// ```rust
+/// # use rocket::get;
+/// use std::io::Cursor;
+///
+/// use rocket::request::Request;
+/// use rocket::response::{self, Response, Responder, Ranged};
+/// use rocket::http::ContentType;
+///
+/// struct Video(Vec<u8>);
+///
+/// impl<'r> Responder<'r, 'static> for Video {
+///     fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+///         Response::build()
+///             .header(ContentType::new("video", "mp4"))
+///             .sized_body(self.0.len(), Cursor::new(self.0))
+///             .ok()
+///     }
+/// }
+///
+/// #[get("/video")]
+/// fn video() -> Ranged<Video> {
+///     # let bytes = vec![];
+///     Ranged::new(Video(bytes))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ranged<R>(pub R);
+
+impl<R> Ranged<R> {
+    /// Wraps `responder`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Ranged;
+    ///
+    /// let response = Ranged::new("Hello, world!");
+    /// ```
+    pub fn new(responder: R) -> Self {
+        Ranged(responder)
+    }
+}
+
+/// Returns `true` if the `If-Range` header of `req`, if any, matches the
+/// validators of `response`.
+fn if_range_matches(req: &Request<'_>, response: &Response<'_>) -> bool {
+    let if_range = match req.headers().get_one("If-Range") {
+        Some(if_range) => if_range.trim(),
+        None => return true,
+    };
+
+    if if_range.starts_with('"') || if_range.starts_with("W/") {
+        let etag = response.headers().get_one("ETag").and_then(|t| t.parse::<EntityTag>().ok());
+        return match (if_range.parse::<EntityTag>(), etag) {
+            (Ok(if_range), Some(etag)) => if_range.strong_eq(&etag),
+            _ => false,
+        };
+    }
+
+    let modified = response.headers().get_one("Last-Modified").and_then(parse_http_date);
+    match (parse_http_date(if_range), modified) {
+        (Some(date), Some(modified)) => date == modified,
+        _ => false,
+    }
+}
+
+/// Selects the range of `response`, with a sized body of a preset size,
+/// requested by `req`, as described in [`Ranged`].
+pub(crate) fn select_range(req: &Request<'_>, response: &mut Response<'_>) {
+    let len = match response.body().preset_size() {
+        Some(len) if response.status() == Status::Ok => len as u64,
+        _ => return,
+    };
+
+    response.set_raw_header("Accept-Ranges", "bytes");
+    if !matches!(req.method(), Method::Get | Method::Head) {
+        return;
+    }
+
+    let range = req.headers().get_one("Range").and_then(|r| r.parse::<Range>().ok());
+    let range = match range {
+        Some(range) if range.ranges().len() == 1 => range.ranges()[0],
+        _ => return,
+    };
+
+    if !if_range_matches(req, response) {
+        return;
+    }
+
+    let (start, end) = match range.resolve(len) {
+        Some(range) => (range.start, range.end),
+        None => {
+            response.set_status(Status::RangeNotSatisfiable);
+            response.set_raw_header("Content-Range", format!("bytes */{}", len));
+            response.set_sized_body(0, io::Cursor::new(&[]));
+            return;
+        }
+    };
+
+    response.set_status(Status::PartialContent);
+    response.set_raw_header("Content-Range", format!("bytes {}-{}/{}", start, end - 1, len));
+    response.body_mut().wrap(|reader| match reader {
+        BodyReader::Sized(b) => BodyReader::Sized(Box::pin(Window::new(b, start, end))),
+        reader => reader,
+    });
+
+    response.body_mut().set_preset_size((end - start) as usize);
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Ranged<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let mut response = self.0.respond_to(req)?;
+        select_range(req, &mut response);
+        Ok(response)
+    }
+}
+
+/// The state of the initial seek of a [`Window`] to its start.
+enum Seek {
+    Start,
+    Pending,
+    Done,
+}
+
+/// An `AsyncRead + AsyncSeek` over the bytes from `start` to `end` of `inner`,
+/// whatever the initial position of `inner`.
+struct Window<T> {
+    inner: T,
+    start: u64,
+    end: u64,
+    /// The position in `inner`.
+    pos: u64,
+    seek: Seek,
+}
+
+impl<T: AsyncSeek + Unpin> Window<T> {
+    fn new(inner: T, start: u64, end: u64) -> Self {
+        Window { inner, start, end, pos: start, seek: Seek::Start }
+    }
+
+    fn poll_seeked(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        loop {
+            match self.seek {
+                Seek::Start => {
+                    Pin::new(&mut self.inner).start_seek(io::SeekFrom::Start(self.pos))?;
+                    self.seek = Seek::Pending;
+                }
+                Seek::Pending => {
+                    self.pos = futures::ready!(Pin::new(&mut self.inner).poll_complete(cx))?;
+                    self.seek = Seek::Done;
+                }
+                Seek::Done => return Poll::Ready(Ok(())),
+            }
+        }
+    }
+}
+
+impl<T: AsyncRead + AsyncSeek + Unpin> AsyncRead for Window<T> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        futures::ready!(this.poll_seeked(cx))?;
+
+        let remaining = this.end.saturating_sub(this.pos);
+        let limit = remaining.min(buf.remaining() as u64) as usize;
+        if limit == 0 {
+            return Poll::Ready(Ok(()));
+        }
+
+        let n = {
+            let mut window = ReadBuf::new(buf.initialize_unfilled_to(limit));
+            futures::ready!(Pin::new(&mut this.inner).poll_read(cx, &mut window))?;
+            window.filled().len()
+        };
+
+        buf.advance(n);
+        this.pos += n as u64;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T: AsyncSeek + Unpin> AsyncSeek for Window<T> {
+    fn start_seek(mut self: Pin<&mut Self>, position: io::SeekFrom) -> io::Result<()> {
+        let pos = match position {
+            io::SeekFrom::Start(n) => self.start.checked_add(n),
+            io::SeekFrom::End(n) => checked_offset(self.end, n),
+            io::SeekFrom::Current(n) => checked_offset(self.pos, n),
+        };
+
+        match pos {
+            Some(pos) if pos >= self.start => {
+                self.pos = pos;
+                self.seek = Seek::Start;
+                Ok(())
+            }
+            _ => Err(io::Error::new(io::ErrorKind::InvalidInput, "seek out of bounds")),
+        }
+    }
+
+    fn poll_complete(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        futures::ready!(self.poll_seeked(cx))?;
+        Poll::Ready(Ok(self.pos - self.start))
+    }
+}
+
+fn checked_offset(base: u64, offset: i64) -> Option<u64> {
+    if offset < 0 {
+        base.checked_sub(offset.unsigned_abs())
+    } else {
+        base.checked_add(offset as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, SeekFrom};
+
+    use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+    use super::Window;
+
+    #[test]
+    fn test_window_reads_and_seeks_within_bounds() {
+        crate::async_test(async {
+            let mut inner = Cursor::new(b"0123456789".to_vec());
+            inner.seek(SeekFrom::End(0)).await.unwrap();
+
+            let mut window = Window::new(inner, 2, 7);
+            let mut contents = String::new();
+            window.read_to_string(&mut contents).await.unwrap();
+            assert_eq!(contents, "23456");
+
+            assert_eq!(window.seek(SeekFrom::Start(1)).await.unwrap(), 1);
+            assert_eq!(window.seek(SeekFrom::Current(2)).await.unwrap(), 3);
+            assert_eq!(window.seek(SeekFrom::End(-4)).await.unwrap(), 1);
+            let mut contents = String::new();
+            window.read_to_string(&mut contents).await.unwrap();
+            assert_eq!(contents, "3456");
+
+            assert!(window.seek(SeekFrom::End(-6)).await.is_err());
+        })
+    }
+}
@@ -0,0 +1,144 @@
+use std::error::Error;
+
+use crate::request::Request;
+use crate::response::{self, Responder};
+use crate::http::Status;
+
+use yansi::Paint;
+
+/// Logs and records an error report, such as an `anyhow::Error` or
+/// `eyre::Report`, before forwarding to the error catcher for a status, `500`
+/// by default.
+///
+/// Like [`Debug`](crate::response::Debug), `Report` is intended to be used as
+/// the error type of a `Result`. Any error that converts into a boxed
+/// [`std::error::Error`] converts into a `Report`, including `anyhow::Error`,
+/// `eyre::Report`, standard library errors, and strings, so `?` works naturally
+/// in handlers that mix them. When a `Report` is responded with, it:
+///
+///   * logs the error followed by its full chain of sources,
+///   * records the error for the request, reporting it to
+///     [`on_error`](crate::fairing::Fairing::on_error()) fairings and making it
+///     available to catchers via [`Request::recorded_error()`], and
+///   * forwards to the error catcher for its status.
+///
+/// The error is recorded as the type it was converted from, so a typed
+/// catcher, declared with `#[catch(code, error = "E")]`, receives an error of
+/// type `E` as an `&E`. An error converted from a report type such as
+/// `anyhow::Error`, however, is recorded as that report and is thus only
+/// handled by untyped catchers.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use std::path::PathBuf;
+///
+/// use rocket::response::Report;
+/// use rocket::tokio::fs;
+///
+/// #[get("/config")]
+/// async fn config() -> Result<String, Report> {
+///     let path = std::env::var("APP_CONFIG").map(PathBuf::from)?;
+///     let config = fs::read_to_string(&path).await?;
+///     Ok(config)
+/// }
+/// ```
+///
+/// With `anyhow`, context is logged along with its cause:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # extern crate anyhow;
+/// use anyhow::Context;
+/// use rocket::response::Report;
+///
+/// async fn fetch_user(id: u64) -> anyhow::Result<String> {
+///     # Ok(id.to_string())
+///     /* .. */
+/// }
+///
+/// #[get("/user/<id>")]
+/// async fn user(id: u64) -> Result<String, Report> {
+///     Ok(fetch_user(id).await.with_context(|| format!("fetching user {}", id))?)
+/// }
+/// ```
+///
+/// To forward to the catcher for a different status, use [`Report::status()`].
+/// Here, a typed catcher renders the concrete error:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use std::num::ParseIntError;
+///
+/// use rocket::http::Status;
+/// use rocket::response::Report;
+///
+/// #[get("/<n>")]
+/// fn double(n: &str) -> Result<String, Report> {
+///     let n: i64 = n.parse().map_err(|e| Report::new(e).status(Status::BadRequest))?;
+///     Ok((n * 2).to_string())
+/// }
+///
+/// #[catch(400, error = "ParseIntError")]
+/// fn invalid_number(error: &ParseIntError) -> String {
+///     format!("That's not a number: {}.", error)
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Report {
+    error: Box<dyn Error + Send + Sync>,
+    status: Status,
+}
+
+impl Report {
+    /// Wraps `error`, forwarding to the `500` error catcher.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Report;
+    ///
+    /// let report = Report::new("something went wrong");
+    /// ```
+    pub fn new<E: Into<Box<dyn Error + Send + Sync>>>(error: E) -> Self {
+        Report { error: error.into(), status: Status::InternalServerError }
+    }
+
+    /// Sets the status of the error catcher to forward to to `status`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::Status;
+    /// use rocket::response::Report;
+    ///
+    /// let report = Report::new("not allowed").status(Status::Forbidden);
+    /// ```
+    pub fn status(mut self, status: Status) -> Self {
+        self.status = status;
+        self
+    }
+}
+
+impl<E: Into<Box<dyn Error + Send + Sync>>> From<E> for Report {
+    #[inline(always)]
+    fn from(error: E) -> Self {
+        Report::new(error)
+    }
+}
+
+impl<'r> Responder<'r, 'static> for Report {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        error_!("{}", Paint::default(&self.error));
+        let mut source = self.error.source();
+        while let Some(error) = source {
+            info_!("caused by: {}", Paint::default(error));
+            source = error.source();
+        }
+
+        warn_!("Report responds with {}.", self.status);
+        req.record_error(self.error);
+        Err(self.status)
+    }
+}
@@ -3,7 +3,7 @@ use std::io::Cursor;
 use std::sync::Arc;
 
 use crate::http::{Status, ContentType, StatusClass};
-use crate::response::{self, Response};
+use crate::response::{self, Response, Body};
 use crate::request::Request;
 
 /// Trait implemented by types that generate responses for clients.
@@ -445,9 +445,15 @@ impl<'r> Responder<'r, 'static> for File {
 }
 
 /// Returns a response with a sized body for the file. Always returns `Ok`.
+///
+/// Unless the body is later wrapped, for instance by a fairing, the server
+/// writes the file out by reading it directly rather than through the body's
+/// `AsyncRead` implementation.
 impl<'r> Responder<'r, 'static> for tokio::fs::File {
     fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
-        Response::build().sized_body(None, self).ok()
+        let mut response = Response::new();
+        *response.body_mut() = Body::with_file(self, None);
+        Ok(response)
     }
 }
 
@@ -1,9 +1,13 @@
 use std::{fmt, str};
 use std::borrow::Cow;
+use std::collections::HashMap;
+use std::pin::Pin;
 
 use tokio::io::{AsyncRead, AsyncSeek};
 
 use crate::http::{Header, HeaderMap, Status, ContentType, Cookie};
+use crate::http::uncased::{AsUncased, Uncased};
+use crate::data::IoHandler;
 use crate::response::Body;
 
 /// Builder for the [`Response`] type.
@@ -282,6 +286,42 @@ impl<'r> Builder<'r> {
         self
     }
 
+    /// Registers `handler` as the I/O handler for upgrade protocol `protocol`.
+    ///
+    /// See [`Response::add_upgrade()`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::pin::Pin;
+    ///
+    /// use rocket::Response;
+    /// use rocket::data::{IoHandler, IoStream};
+    /// use rocket::tokio::io;
+    ///
+    /// struct EchoHandler;
+    ///
+    /// #[rocket::async_trait]
+    /// impl IoHandler for EchoHandler {
+    ///     async fn io(self: Pin<Box<Self>>, io: IoStream) -> io::Result<()> {
+    ///         let (mut reader, mut writer) = io::split(io);
+    ///         io::copy(&mut reader, &mut writer).await?;
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let response = Response::build()
+    ///     .upgrade("echo", EchoHandler)
+    ///     .finalize();
+    /// ```
+    #[inline(always)]
+    pub fn upgrade<P, H>(&mut self, protocol: P, handler: H) -> &mut Builder<'r>
+        where P: Into<Uncased<'r>>, H: IoHandler + 'r
+    {
+        self.response.add_upgrade(protocol.into(), handler);
+        self
+    }
+
     /// Merges the `other` `Response` into `self` by setting any fields in
     /// `self` to the corresponding value in `other` if they are set in `other`.
     /// Fields in `self` are unchanged if they are not set in `other`. If a
@@ -404,6 +444,9 @@ impl<'r> Builder<'r> {
     }
 }
 
+/// A protocol and the I/O handler for connections upgraded to it.
+type Upgrade<'r> = (Uncased<'r>, Pin<Box<dyn IoHandler + 'r>>);
+
 /// A response, as returned by types implementing
 /// [`Responder`](crate::response::Responder).
 ///
@@ -413,6 +456,7 @@ pub struct Response<'r> {
     status: Option<Status>,
     headers: HeaderMap<'r>,
     body: Body<'r>,
+    upgrade: HashMap<Uncased<'r>, Pin<Box<dyn IoHandler + 'r>>>,
 }
 
 impl<'r> Response<'r> {
@@ -807,6 +851,91 @@ impl<'r> Response<'r> {
         self.body = Body::with_unsized(body);
     }
 
+    /// Replaces the body of `self`, if it has one, with the streamed body
+    /// returned by `f`, which is called with the current body.
+    ///
+    /// This method allows a [response fairing] to transform the outgoing body
+    /// as it is streamed to the client, for instance to compress, rewrite, or
+    /// sign it. Because the transformed body's size is unknown, it is always
+    /// streamed, and any `Content-Length` header is removed. The body's max
+    /// chunk size is preserved. If the body is empty (`None`), `f` is not
+    /// called.
+    ///
+    /// [response fairing]: crate::fairing::Fairing::on_response()
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io;
+    /// use tokio::io::AsyncReadExt;
+    /// use rocket::Response;
+    ///
+    /// # let o: io::Result<()> = rocket::async_test(async {
+    /// let mut response = Response::new();
+    /// response.set_streamed_body(io::Cursor::new("Hello, "));
+    /// response.map_body(|body| body.chain(io::Cursor::new("world!")));
+    /// assert_eq!(response.body_mut().to_string().await?, "Hello, world!");
+    /// # Ok(())
+    /// # });
+    /// # assert!(o.is_ok());
+    /// ```
+    pub fn map_body<F, B>(&mut self, f: F)
+        where F: FnOnce(Body<'r>) -> B,
+              B: AsyncRead + Send + 'r
+    {
+        if self.body.is_none() {
+            return;
+        }
+
+        let body = self.body.take();
+        let max_chunk = body.max_chunk_size();
+        self.set_streamed_body(f(body));
+        self.set_max_chunk_size(max_chunk);
+        self.remove_header("Content-Length");
+    }
+
+    /// Calls `f` with every chunk of the body of `self` as it is read, without
+    /// otherwise affecting the body. Once the body has been read in its
+    /// entirety, `f` is called with an empty chunk.
+    ///
+    /// Unlike [`Response::map_body()`], this method preserves the body's size
+    /// and kind, so a sized body remains sized. It allows a [response fairing]
+    /// to, for instance, account for the size of or hash responses. Note that
+    /// `f` is never called with an empty chunk if the body isn't read entirely,
+    /// as is the case when a client disconnects early or for `HEAD` requests.
+    ///
+    /// [response fairing]: crate::fairing::Fairing::on_response()
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io;
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use rocket::Response;
+    ///
+    /// # let o: io::Result<()> = rocket::async_test(async {
+    /// let string = "Hello, world!";
+    /// let mut response = Response::new();
+    /// response.set_sized_body(string.len(), io::Cursor::new(string));
+    ///
+    /// let bytes = Arc::new(AtomicUsize::new(0));
+    /// let counter = bytes.clone();
+    /// response.inspect_body(move |chunk| { counter.fetch_add(chunk.len(), Ordering::SeqCst); });
+    ///
+    /// assert_eq!(response.body().preset_size(), Some(13));
+    /// assert_eq!(response.body_mut().to_string().await?, "Hello, world!");
+    /// assert_eq!(bytes.load(Ordering::SeqCst), 13);
+    /// # Ok(())
+    /// # });
+    /// # assert!(o.is_ok());
+    /// ```
+    pub fn inspect_body<F>(&mut self, f: F)
+        where F: FnMut(&[u8]) + Send + 'r
+    {
+        self.body.inspect(f);
+    }
+
     /// Sets the body's maximum chunk size to `size` bytes.
     ///
     /// The default max chunk size is [`Body::DEFAULT_MAX_CHUNK`]. The max chunk
@@ -877,6 +1006,7 @@ impl<'r> Response<'r> {
             self.body = other.body;
         }
 
+        self.upgrade.extend(other.upgrade);
         for (name, values) in other.headers.into_iter_raw() {
             self.headers.replace_all(name.into_cow(), values);
         }
@@ -922,10 +1052,104 @@ impl<'r> Response<'r> {
             self.body = other.body;
         }
 
+        for (protocol, handler) in other.upgrade {
+            self.upgrade.entry(protocol).or_insert(handler);
+        }
+
         for (name, mut values) in other.headers.into_iter_raw() {
             self.headers.add_all(name.into_cow(), &mut values);
         }
     }
+
+    /// Registers `handler` as the I/O handler for upgrade protocol `protocol`.
+    ///
+    /// Responses registered with an I/O handler are upgraded if the request
+    /// asks to upgrade to `protocol` via an `Upgrade` header. When upgraded,
+    /// the response is sent with a status of `101 Switching Protocols` and
+    /// `Connection` and `Upgrade` headers, without a body, after which the
+    /// connection is handed to `handler`. If the request does not ask for an
+    /// upgrade, handlers are ignored and the response is sent as usual.
+    ///
+    /// Any previously registered handler for `protocol` is replaced. Protocol
+    /// names are compared case-insensitively.
+    ///
+    /// See [`IoHandler`] for details on handling upgraded connections.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::pin::Pin;
+    ///
+    /// use rocket::Response;
+    /// use rocket::data::{IoHandler, IoStream};
+    /// use rocket::tokio::io;
+    ///
+    /// struct EchoHandler;
+    ///
+    /// #[rocket::async_trait]
+    /// impl IoHandler for EchoHandler {
+    ///     async fn io(self: Pin<Box<Self>>, io: IoStream) -> io::Result<()> {
+    ///         let (mut reader, mut writer) = io::split(io);
+    ///         io::copy(&mut reader, &mut writer).await?;
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let mut response = Response::new();
+    /// assert!(response.upgrade("echo").is_none());
+    ///
+    /// response.add_upgrade("echo", EchoHandler);
+    /// assert!(response.upgrade("ECHO").is_some());
+    /// ```
+    pub fn add_upgrade<N, H>(&mut self, protocol: N, handler: H)
+        where N: Into<Uncased<'r>>, H: IoHandler + 'r
+    {
+        self.upgrade.insert(protocol.into(), Box::pin(handler));
+    }
+
+    /// Returns the I/O handler registered for the upgrade protocol `proto`, if
+    /// there is one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Response;
+    ///
+    /// let mut response = Response::new();
+    /// assert!(response.upgrade("websocket").is_none());
+    /// ```
+    pub fn upgrade(&mut self, proto: &str) -> Option<Pin<&mut (dyn IoHandler + 'r)>> {
+        self.upgrade.get_mut(proto.as_uncased()).map(|h| h.as_mut())
+    }
+
+    /// Removes and returns the I/O handler for the first protocol in the
+    /// comma-separated list of `protocols`, the values of a request's
+    /// `Upgrade` header, with a registered handler.
+    ///
+    /// Returns `Ok(None)` if no protocols were requested or `self` has no
+    /// handlers and `Err(())` if protocols were requested but none matched.
+    pub(crate) fn take_upgrade<'a, I>(
+        &mut self,
+        protocols: I
+    ) -> Result<Option<Upgrade<'r>>, ()>
+        where I: Iterator<Item = &'a str>
+    {
+        if self.upgrade.is_empty() {
+            return Ok(None);
+        }
+
+        let mut protocols = protocols.peekable();
+        let have_protocols = protocols.peek().is_some();
+        let found = protocols
+            .flat_map(|v| v.split(',').map(str::trim))
+            .find_map(|p| self.upgrade.remove_entry(p.as_uncased()));
+
+        match found {
+            Some(handler) => Ok(Some(handler)),
+            None if have_protocols => Err(()),
+            None => Ok(None),
+        }
+    }
 }
 
 impl fmt::Debug for Response<'_> {
@@ -0,0 +1,314 @@
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::stream::Stream;
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+
+use crate::response::stream::{Event, EventStream};
+
+/// A multi-producer, multi-subscriber channel that fans values out to streams.
+///
+/// A `Broadcast<T>` delivers every value [sent](Broadcast::send()) to every
+/// current [`Subscription`]. It is typically placed in [managed
+/// state](crate::State) so that many streaming responses, like an
+/// [`EventStream`], can each subscribe to values produced elsewhere:
+///
+/// ```rust
+/// # use rocket::*;
+/// use rocket::State;
+/// use rocket::response::stream::{Broadcast, Event, EventStream};
+///
+/// #[post("/message", data = "<message>")]
+/// fn post(message: String, queue: &State<Broadcast<Event>>) {
+///     queue.send(Event::data(message));
+/// }
+///
+/// #[get("/messages")]
+/// fn messages(queue: &State<Broadcast<Event>>) -> EventStream![] {
+///     queue.events()
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .manage(Broadcast::<Event>::new(1024))
+///         .mount("/", routes![post, messages])
+/// }
+/// ```
+///
+/// # Lagging Subscribers
+///
+/// A `Broadcast` retains at most `capacity` values that have yet to be received
+/// by every subscriber. A subscriber that falls further behind _lags_: it
+/// misses the oldest values and resumes with the oldest retained value. By
+/// default, missed values are skipped silently. A value to emit in their place,
+/// perhaps an event that instructs the client to resynchronize, can be set via
+/// [`Subscription::on_lag()`].
+///
+/// # Keep-Alive and Disconnects
+///
+/// An [`EventStream`] created from a subscription, including via
+/// [`Broadcast::events()`], sends a heartbeat every 30 seconds by default to
+/// keep the connection alive. When a client disconnects, its response, and thus
+/// its `Subscription`, is dropped, automatically unsubscribing it.
+///
+/// # Graceful Shutdown
+///
+/// A `Subscription` ends only once every `Broadcast` for the channel has been
+/// dropped. To end subscriptions on shutdown, combine them with the
+/// [`Shutdown`](crate::Shutdown) future:
+///
+/// ```rust
+/// # use rocket::*;
+/// use rocket::{State, Shutdown};
+/// use rocket::futures::stream::StreamExt;
+/// use rocket::response::stream::{Broadcast, Event, EventStream};
+///
+/// #[get("/messages")]
+/// fn messages(queue: &State<Broadcast<Event>>, end: Shutdown) -> EventStream![] {
+///     EventStream::from(queue.subscribe().take_until(end))
+/// }
+/// ```
+pub struct Broadcast<T> {
+    sender: broadcast::Sender<T>,
+}
+
+/// A stream of the values sent to a [`Broadcast`].
+///
+/// Created via [`Broadcast::subscribe()`]. The stream ends when every
+/// `Broadcast` for the channel has been dropped. Dropping a `Subscription`
+/// unsubscribes it.
+pub struct Subscription<T> {
+    stream: BroadcastStream<T>,
+    on_lag: Option<Box<dyn FnMut(u64) -> Option<T> + Send>>,
+}
+
+impl<T: Clone + Send + 'static> Broadcast<T> {
+    /// Creates a new `Broadcast` which retains up to `capacity` values that
+    /// have yet to be received by every subscriber.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::stream::Broadcast;
+    ///
+    /// let broadcast = Broadcast::<String>::new(16);
+    /// ```
+    pub fn new(capacity: usize) -> Self {
+        Broadcast { sender: broadcast::channel(capacity).0 }
+    }
+
+    /// Sends `value` to every current subscriber. Returns the number of
+    /// subscribers the value was sent to.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::stream::Broadcast;
+    ///
+    /// let broadcast = Broadcast::new(16);
+    /// assert_eq!(broadcast.send("dropped"), 0);
+    ///
+    /// let _subscription = broadcast.subscribe();
+    /// assert_eq!(broadcast.send("received"), 1);
+    /// ```
+    pub fn send(&self, value: T) -> usize {
+        self.sender.send(value).unwrap_or(0)
+    }
+
+    /// Returns a new [`Subscription`] which receives every value sent after
+    /// this call.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # rocket::async_test(async {
+    /// use rocket::response::stream::Broadcast;
+    /// use rocket::futures::stream::StreamExt;
+    ///
+    /// let broadcast = Broadcast::new(16);
+    /// let mut subscription = broadcast.subscribe();
+    /// broadcast.send(1);
+    /// broadcast.send(2);
+    ///
+    /// assert_eq!(subscription.next().await, Some(1));
+    /// assert_eq!(subscription.next().await, Some(2));
+    /// # });
+    /// ```
+    pub fn subscribe(&self) -> Subscription<T> {
+        Subscription {
+            stream: BroadcastStream::new(self.sender.subscribe()),
+            on_lag: None,
+        }
+    }
+
+    /// Returns the number of current subscribers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::stream::Broadcast;
+    ///
+    /// let broadcast = Broadcast::<usize>::new(16);
+    /// let subscription = broadcast.subscribe();
+    /// assert_eq!(broadcast.subscribers(), 1);
+    ///
+    /// drop(subscription);
+    /// assert_eq!(broadcast.subscribers(), 0);
+    /// ```
+    pub fn subscribers(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+impl Broadcast<Event> {
+    /// Subscribes to `self` and returns an [`EventStream`] of the received
+    /// events with the default heartbeat.
+    ///
+    /// This is equivalent to `EventStream::from(self.subscribe())`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::get;
+    /// use rocket::State;
+    /// use rocket::response::stream::{Broadcast, Event, EventStream};
+    ///
+    /// #[get("/events")]
+    /// fn events(queue: &State<Broadcast<Event>>) -> EventStream![] {
+    ///     queue.events()
+    /// }
+    /// ```
+    pub fn events(&self) -> EventStream<Subscription<Event>> {
+        EventStream::from(self.subscribe())
+    }
+}
+
+impl<T> Subscription<T> {
+    /// Sets a function to call with the number of values missed whenever the
+    /// subscriber lags. If the function returns `Some`, the value is emitted
+    /// in place of the missed values.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # rocket::async_test(async {
+    /// use rocket::response::stream::{Broadcast, Event};
+    /// use rocket::futures::stream::StreamExt;
+    ///
+    /// let broadcast = Broadcast::new(1);
+    /// let mut subscription = broadcast.subscribe()
+    ///     .on_lag(|n| Some(Event::data(n.to_string()).event("lagged")));
+    ///
+    /// broadcast.send(Event::data("a"));
+    /// broadcast.send(Event::data("b"));
+    ///
+    /// assert_eq!(subscription.next().await, Some(Event::data("1").event("lagged")));
+    /// assert_eq!(subscription.next().await, Some(Event::data("b")));
+    /// # });
+    /// ```
+    pub fn on_lag<F>(mut self, f: F) -> Self
+        where F: FnMut(u64) -> Option<T> + Send + 'static
+    {
+        self.on_lag = Some(Box::new(f));
+        self
+    }
+}
+
+impl<T: Clone + Send + 'static> Stream for Subscription<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let this = self.get_mut();
+        loop {
+            match futures::ready!(Pin::new(&mut this.stream).poll_next(cx)) {
+                Some(Ok(value)) => return Poll::Ready(Some(value)),
+                Some(Err(BroadcastStreamRecvError::Lagged(n))) => {
+                    if let Some(value) = this.on_lag.as_mut().and_then(|f| f(n)) {
+                        return Poll::Ready(Some(value));
+                    }
+                }
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+impl<T> Clone for Broadcast<T> {
+    fn clone(&self) -> Self {
+        Broadcast { sender: self.sender.clone() }
+    }
+}
+
+impl<T> fmt::Debug for Broadcast<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Broadcast")
+            .field("subscribers", &self.sender.receiver_count())
+            .finish()
+    }
+}
+
+impl<T> fmt::Debug for Subscription<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Subscription").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod broadcast_tests {
+    use futures::stream::StreamExt;
+
+    use super::Broadcast;
+
+    #[test]
+    fn test_fan_out() {
+        crate::async_test(async {
+            let broadcast = Broadcast::new(8);
+            let (mut a, mut b) = (broadcast.subscribe(), broadcast.subscribe());
+            assert_eq!(broadcast.send("x"), 2);
+            assert_eq!(broadcast.send("y"), 2);
+            assert_eq!(a.next().await, Some("x"));
+            assert_eq!(a.next().await, Some("y"));
+            assert_eq!(b.next().await, Some("x"));
+
+            drop(broadcast);
+            assert_eq!(a.next().await, None);
+            assert_eq!(b.next().await, Some("y"));
+            assert_eq!(b.next().await, None);
+        })
+    }
+
+    #[test]
+    fn test_lag() {
+        crate::async_test(async {
+            let broadcast = Broadcast::new(2);
+            let skipping = broadcast.subscribe();
+            let mut marking = broadcast.subscribe().on_lag(|n| Some(n as usize * 100));
+            for i in 0..5 {
+                broadcast.send(i);
+            }
+
+            drop(broadcast);
+            assert_eq!(skipping.collect::<Vec<_>>().await, vec![3, 4]);
+            assert_eq!(marking.next().await, Some(300));
+            assert_eq!(marking.collect::<Vec<_>>().await, vec![3, 4]);
+        })
+    }
+
+    #[test]
+    fn test_unsubscribe_on_drop() {
+        let broadcast = Broadcast::<()>::new(1);
+        let events = broadcast.clone().subscribe();
+        assert_eq!(broadcast.subscribers(), 1);
+        drop(events);
+        assert_eq!(broadcast.subscribers(), 0);
+        assert_eq!(broadcast.send(()), 0);
+    }
+}
@@ -187,6 +187,12 @@
 //!     }
 //! }
 //! ```
+//!
+//! # Broadcasting
+//!
+//! To send the values produced by one source to many streaming responses, such
+//! as one [`struct@EventStream`] per connected client, use a [`Broadcast`].
+//! Each response streams its own [`Subscription`] to the broadcast.
 
 mod reader;
 mod bytes;
@@ -194,6 +200,7 @@ mod text;
 mod one;
 mod sse;
 mod raw_sse;
+mod broadcast;
 
 pub(crate) use self::raw_sse::*;
 
@@ -202,6 +209,7 @@ pub use self::text::TextStream;
 pub use self::bytes::ByteStream;
 pub use self::reader::ReaderStream;
 pub use self::sse::{Event, EventStream};
+pub use self::broadcast::{Broadcast, Subscription};
 
 crate::export! {
     /// Retrofitted support for [`Stream`]s with `yield`, `for await` syntax.
@@ -1,21 +1,28 @@
 use std::fmt;
 use std::ops::{Deref, DerefMut};
 use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
 
 use yansi::Paint;
 use either::Either;
 use figment::{Figment, Provider};
+use parking_lot::RwLock;
 
 use crate::{Catcher, Config, Route, Shutdown, sentinel, shield::Shield};
-use crate::router::Router;
+use crate::route::HostPattern;
+use crate::router::{Router, HotRouter};
 use crate::trip_wire::TripWire;
-use crate::fairing::{Fairing, Fairings};
+use crate::task::Tasks;
+use crate::fairing::{Fairing, Fairings, ScopedFairing};
+use crate::config::SecretProvider;
 use crate::phase::{Phase, Build, Building, Ignite, Igniting, Orbit, Orbiting};
 use crate::phase::{Stateful, StateRef, State};
 use crate::http::uri::{self, Origin};
 use crate::http::ext::IntoOwned;
 use crate::error::{Error, ErrorKind};
 use crate::log::PaintExt;
+use crate::ext::ConnectionLimiter;
 
 /// The application server itself.
 ///
@@ -250,32 +257,7 @@ impl Rocket<Build> {
               F: Fn(&mut Self, T),
               T: Clone + fmt::Display,
     {
-        let mut base = match base.clone().try_into() {
-            Ok(origin) => origin.into_owned(),
-            Err(e) => {
-                error!("invalid {} base: {}", kind, Paint::white(&base));
-                error_!("{}", e);
-                info_!("{} {}", Paint::white("in"), std::panic::Location::caller());
-                panic!("aborting due to {} base error", kind);
-            }
-        };
-
-        if base.query().is_some() {
-            warn!("query in {} base '{}' is ignored", kind, Paint::white(&base));
-            base.clear_query();
-        }
-
-        for unmounted_item in items {
-            let item = match m(&base, unmounted_item.clone()) {
-                Ok(item) => item,
-                Err(e) => {
-                    error!("malformed URI in {} {}", kind, unmounted_item);
-                    error_!("{}", e);
-                    info_!("{} {}", Paint::white("in"), std::panic::Location::caller());
-                    panic!("aborting due to invalid {} URI", kind);
-                }
-            };
-
+        for item in mounted(kind, base, items, m) {
             f(&mut self, item)
         }
 
@@ -348,6 +330,132 @@ impl Rocket<Build> {
             |r, route| r.0.routes.push(route))
     }
 
+    /// Mounts all of the routes in the supplied vector at the given `base`
+    /// path, as [`mount()`](Self::mount()) does, attaching `fairings` to each
+    /// route. The callbacks of [scoped fairings](ScopedFairing) run only for
+    /// requests routed to the routes they're attached to.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`mount()`](Self::mount()).
+    ///
+    /// # Example
+    ///
+    /// Add a header to the responses of the routes mounted at `/api`:
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::{Request, Response};
+    /// use rocket::fairing::ScopedFairing;
+    /// use rocket::http::Header;
+    ///
+    /// struct ApiVersion;
+    ///
+    /// #[rocket::async_trait]
+    /// impl ScopedFairing for ApiVersion {
+    ///     async fn on_response<'r>(&self, _: &'r Request<'_>, res: &mut Response<'r>) {
+    ///         res.set_header(Header::new("X-Api-Version", "2"));
+    ///     }
+    /// }
+    ///
+    /// #[get("/users")]
+    /// fn users() -> &'static str { "[]" }
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build().mount_with("/api", routes![users], [ApiVersion])
+    /// }
+    /// ```
+    #[must_use]
+    #[track_caller]
+    pub fn mount_with<'a, B, R, F>(self, base: B, routes: R, fairings: F) -> Self
+        where B: TryInto<Origin<'a>> + Clone + fmt::Display,
+              B::Error: fmt::Display,
+              R: Into<Vec<Route>>,
+              F: IntoIterator, F::Item: ScopedFairing
+    {
+        let fairings: Vec<Arc<dyn ScopedFairing>> = fairings.into_iter()
+            .map(|fairing| Arc::new(fairing) as Arc<dyn ScopedFairing>)
+            .collect();
+
+        let routes: Vec<Route> = routes.into().into_iter()
+            .map(|mut route| {
+                route.fairings.extend(fairings.iter().cloned());
+                route
+            })
+            .collect();
+
+        self.mount(base, routes)
+    }
+
+    /// Mounts all of the routes in the supplied vector at the given `base`
+    /// path, as [`mount()`](Self::mount()) does, restricting them to requests
+    /// whose host matches the [host pattern](crate::route::HostPattern)
+    /// `host`: an exact domain, like `example.com`, or a wildcard subdomain
+    /// pattern, like `*.example.com`. Requests without a host never match.
+    ///
+    /// Among routes of equal rank, host-restricted routes are tried before
+    /// routes mounted without a host, and the two never collide. This allows
+    /// serving many virtual hosts from one instance while falling back to
+    /// host-agnostic routes. Handlers can retrieve the request's host via the
+    /// [`&Host`](crate::http::uri::Host) request guard and the portion of the
+    /// host matched by a wildcard via the [`Subdomain`](crate::request::Subdomain)
+    /// request guard.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `host` is not a valid host pattern or under the same
+    /// conditions as [`mount()`](Self::mount()).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::request::Subdomain;
+    ///
+    /// #[get("/")]
+    /// fn tenant(tenant: Subdomain<'_>) -> String {
+    ///     format!("Welcome, {}!", tenant)
+    /// }
+    ///
+    /// #[get("/")]
+    /// fn home() -> &'static str {
+    ///     "Welcome to example.com!"
+    /// }
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build()
+    ///         .mount_host("*.example.com", "/", routes![tenant])
+    ///         .mount("/", routes![home])
+    /// }
+    /// ```
+    #[must_use]
+    #[track_caller]
+    pub fn mount_host<'a, B, R>(self, host: &str, base: B, routes: R) -> Self
+        where B: TryInto<Origin<'a>> + Clone + fmt::Display,
+              B::Error: fmt::Display,
+              R: Into<Vec<Route>>
+    {
+        let pattern = match HostPattern::parse(host) {
+            Some(pattern) => pattern,
+            None => {
+                error!("invalid route host pattern: {}", Paint::white(host));
+                info_!("{} {}", Paint::white("in"), std::panic::Location::caller());
+                panic!("aborting due to invalid host pattern");
+            }
+        };
+
+        let routes: Vec<Route> = routes.into().into_iter()
+            .map(|mut route| {
+                route.host = Some(pattern.clone());
+                route
+            })
+            .collect();
+
+        self.mount(base, routes)
+    }
+
     /// Registers all of the catchers in the supplied vector, scoped to `base`.
     ///
     /// # Panics
@@ -387,6 +495,57 @@ impl Rocket<Build> {
             |r, catcher| r.0.catchers.push(catcher))
     }
 
+    /// Applies the ranking strategy `f` to every route mounted on `self`,
+    /// before or after this call. At ignition, the rank of each route is set
+    /// to the value returned by `f` when called with the route. The route's
+    /// [`rank`](Route::rank) when `f` is called is either its manually
+    /// specified rank or its [default rank](crate::Route#default-ranking).
+    ///
+    /// If several strategies are applied, they are called in the order they
+    /// were applied, each observing the rank returned by the previous one.
+    /// Because collisions are checked after ranking, a strategy can resolve
+    /// collisions between routes with overlapping dynamic segments without
+    /// annotating each route with a manual `rank`. Routes mounted after launch
+    /// via [`Rocket::hot_mount()`] are not ranked.
+    ///
+    /// # Example
+    ///
+    /// Try all routes with trailing `<path..>` parameters after all others,
+    /// and routes mounted at `/legacy` before the rest:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use std::path::PathBuf;
+    ///
+    /// #[get("/<id>")]
+    /// fn user(id: usize) -> String { format!("user {}", id) }
+    ///
+    /// #[get("/<path..>")]
+    /// fn page(path: PathBuf) -> String { format!("page {}", path.display()) }
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build()
+    ///         .mount("/", routes![user, page])
+    ///         .mount("/legacy", routes![user, page])
+    ///         .rank_routes(|route| match route.uri.path().ends_with("..>") {
+    ///             true => route.rank + 100,
+    ///             false => route.rank,
+    ///         })
+    ///         .rank_routes(|route| match route.uri.base() {
+    ///             "/legacy" => route.rank - 1000,
+    ///             _ => route.rank,
+    ///         })
+    /// }
+    /// ```
+    #[must_use]
+    pub fn rank_routes<F>(mut self, f: F) -> Self
+        where F: Fn(&Route) -> isize + Send + Sync + 'static
+    {
+        self.0.rankers.push(Ranker(Box::new(f)));
+        self
+    }
+
     /// Add `state` to the state managed by this instance of Rocket.
     ///
     /// This method can be called any number of times as long as each call
@@ -441,6 +600,48 @@ impl Rocket<Build> {
         self
     }
 
+    /// Spawns a background task named `name` at liftoff. This is shorthand for
+    /// attaching [`AdHoc::task(name, f)`](crate::fairing::AdHoc::task()); see
+    /// its documentation for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// use rocket::tokio::time::{self, Duration};
+    ///
+    /// #[derive(Clone, Default)]
+    /// struct Ticks(Arc<AtomicUsize>);
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build()
+    ///         .manage(Ticks::default())
+    ///         .manage_task("Ticker", |rocket, mut shutdown| {
+    ///             let ticks = rocket.state::<Ticks>().cloned().unwrap();
+    ///             async move {
+    ///                 let mut interval = time::interval(Duration::from_secs(1));
+    ///                 loop {
+    ///                     rocket::tokio::select! {
+    ///                         _ = interval.tick() => ticks.0.fetch_add(1, Ordering::Relaxed),
+    ///                         _ = &mut shutdown => break,
+    ///                     };
+    ///                 }
+    ///             }
+    ///         })
+    /// }
+    /// ```
+    #[must_use]
+    pub fn manage_task<F, Fut>(self, name: &'static str, f: F) -> Self
+        where F: FnOnce(&Rocket<Orbit>, Shutdown) -> Fut + Send + Sync + 'static,
+              Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        self.attach(crate::fairing::AdHoc::task(name, f))
+    }
+
     /// Attaches a fairing to this instance of Rocket. No fairings are eagerly
     /// excuted; fairings are executed at their appropriate time.
     ///
@@ -468,10 +669,56 @@ impl Rocket<Build> {
         self
     }
 
+    /// Registers `provider` as the source of secrets for configuration values
+    /// written as secret references with `provider`'s scheme, replacing any
+    /// provider previously registered for that scheme.
+    ///
+    /// Secret references are resolved at ignition, before ignite fairings run.
+    /// See [`SecretProvider`](crate::config::SecretProvider) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::config::{SecretProvider, SecretError};
+    ///
+    /// struct Upper;
+    ///
+    /// #[rocket::async_trait]
+    /// impl SecretProvider for Upper {
+    ///     fn scheme(&self) -> &str {
+    ///         "upper"
+    ///     }
+    ///
+    ///     async fn resolve(&self, reference: &str) -> Result<String, SecretError> {
+    ///         Ok(reference.to_uppercase())
+    ///     }
+    /// }
+    ///
+    /// # rocket::async_test(async {
+    /// let figment = rocket::Config::figment()
+    ///     .merge(("api_key", rocket::figment::util::map!["secret" => "upper:hunter2"]));
+    ///
+    /// let rocket = rocket::custom(figment)
+    ///     .secret_provider(Upper)
+    ///     .ignite().await
+    ///     .unwrap();
+    ///
+    /// let key: String = rocket.figment().extract_inner("api_key").unwrap();
+    /// assert_eq!(key, "HUNTER2");
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn secret_provider<P: SecretProvider>(mut self, provider: P) -> Self {
+        self.secret_providers.add(provider);
+        self
+    }
+
     /// Returns a `Future` that transitions this instance of `Rocket` into the
     /// _ignite_ phase.
     ///
-    /// When `await`ed, the future runs all _ignite_ fairings in serial,
+    /// When `await`ed, the future resolves [secret references] in the
+    /// configuration, runs all _ignite_ fairings in serial,
     /// [attach](Rocket::attach()) order, and verifies that `self` represents a
     /// valid instance of `Rocket` ready for launch. This means that:
     ///
@@ -487,6 +734,7 @@ impl Rocket<Build> {
     /// returned.
     ///
     /// [configured]: Rocket::figment()
+    /// [secret references]: crate::config::SecretProvider
     ///
     /// # Example
     ///
@@ -511,6 +759,10 @@ impl Rocket<Build> {
     /// }
     /// ```
     pub async fn ignite(mut self) -> Result<Rocket<Ignite>, Error> {
+        // Resolve secret references before any fairing reads configuration.
+        let figment = std::mem::take(&mut self.figment);
+        self.figment = self.secret_providers.resolve(figment).await.map_err(ErrorKind::Config)?;
+
         self = Fairings::handle_ignite(self).await;
         self.fairings.audit().map_err(|f| ErrorKind::FailedFairings(f.to_vec()))?;
 
@@ -532,7 +784,11 @@ impl Rocket<Build> {
             }
         };
 
-        // Initialize the router; check for collisions.
+        // Rank the routes, then initialize the router; check for collisions.
+        for ranker in &self.0.rankers {
+            self.0.routes.iter_mut().for_each(|route| route.rank = (ranker.0)(route));
+        }
+
         let mut router = Router::new();
         self.routes.clone().into_iter().for_each(|r| router.add_route(r));
         self.catchers.clone().into_iter().for_each(|c| router.add_catcher(c));
@@ -565,6 +821,59 @@ impl Rocket<Build> {
     }
 }
 
+/// A route ranking strategy applied via [`Rocket::rank_routes()`].
+pub(crate) struct Ranker(Box<dyn Fn(&Route) -> isize + Send + Sync>);
+
+impl fmt::Debug for Ranker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Ranker").finish()
+    }
+}
+
+#[track_caller]
+fn mount_base<'a, B>(kind: &str, base: B) -> Origin<'static>
+    where B: TryInto<Origin<'a>> + Clone + fmt::Display,
+          B::Error: fmt::Display,
+{
+    let mut base = match base.clone().try_into() {
+        Ok(origin) => origin.into_owned(),
+        Err(e) => {
+            error!("invalid {} base: {}", kind, Paint::white(&base));
+            error_!("{}", e);
+            info_!("{} {}", Paint::white("in"), std::panic::Location::caller());
+            panic!("aborting due to {} base error", kind);
+        }
+    };
+
+    if base.query().is_some() {
+        warn!("query in {} base '{}' is ignored", kind, Paint::white(&base));
+        base.clear_query();
+    }
+
+    base
+}
+
+#[track_caller]
+fn mounted<'a, B, T, M>(kind: &str, base: B, items: Vec<T>, m: M) -> Vec<T>
+    where B: TryInto<Origin<'a>> + Clone + fmt::Display,
+          B::Error: fmt::Display,
+          M: Fn(&Origin<'a>, T) -> Result<T, uri::Error<'static>>,
+          T: Clone + fmt::Display,
+{
+    let base = mount_base(kind, base);
+    items.into_iter()
+        .map(|unmounted_item| match m(&base, unmounted_item.clone()) {
+            Ok(item) => item,
+            Err(e) => {
+                error!("malformed URI in {} {}", kind, unmounted_item);
+                error_!("{}", e);
+                info_!("{} {}", Paint::white("in"), std::panic::Location::caller());
+                panic!("aborting due to invalid {} URI", kind);
+            }
+        })
+        .collect()
+}
+
 fn log_items<T, I, B, O>(e: &str, t: &str, items: I, base: B, origin: O)
     where T: fmt::Display + Copy, I: Iterator<Item = T>,
           B: Fn(&T) -> &Origin<'_>, O: Fn(&T) -> &Origin<'_>
@@ -636,13 +945,28 @@ impl Rocket<Ignite> {
     }
 
     fn into_orbit(self) -> Rocket<Orbit> {
+        let config = &self.0.config;
+        let connections = ConnectionLimiter::new(
+            config.max_connections,
+            config.max_connections_per_ip
+        );
+
         Rocket(Orbiting {
             router: self.0.router,
+            hot_router: HotRouter::new(),
+            tasks: Tasks::new(),
             fairings: self.0.fairings,
             figment: self.0.figment,
+            current_config: RwLock::new(Arc::new(self.0.config.clone())),
             config: self.0.config,
             state: self.0.state,
             shutdown: self.0.shutdown,
+            connections,
+            requests: AtomicUsize::new(0),
+            #[cfg(feature = "tls")]
+            tls_resolvers: vec![],
+            #[cfg(feature = "tls")]
+            listener_tls: vec![],
         })
     }
 
@@ -661,12 +985,22 @@ impl Rocket<Ignite> {
                 rkt.fairings.handle_liftoff(&rkt).await;
 
                 let proto = rkt.config.tls_enabled().then(|| "https").unwrap_or("http");
-                let socket_addr = SocketAddr::new(rkt.config.address, rkt.config.port);
-                let addr = format!("{}://{}", proto, socket_addr);
+                let addr = match rkt.config.unix {
+                    Some(ref unix) => unix.to_string(),
+                    None => {
+                        let socket_addr = SocketAddr::new(rkt.config.address, rkt.config.port);
+                        format!("{}://{}", proto, socket_addr)
+                    }
+                };
+
                 launch_info!("{}{} {}",
                     Paint::emoji("🚀 "),
                     Paint::default("Rocket has launched from").bold(),
                     Paint::default(addr).bold().underline());
+
+                for listener in &rkt.config.listeners {
+                    launch_msg_!("also from {}", Paint::default(listener).bold().underline());
+                }
             }))
             .await
             .map(|rocket| rocket.into_ignite())
@@ -687,7 +1021,8 @@ impl Rocket<Orbit> {
 
     /// Returns the finalized, active configuration. This is guaranteed to
     /// remain stable after [`Rocket::ignite()`], through ignition and into
-    /// orbit.
+    /// orbit. Settings reloaded via [`Rocket::refresh_config()`] are _not_
+    /// reflected here; use [`Rocket::current_config()`] to observe them.
     ///
     /// # Example
     ///
@@ -707,6 +1042,140 @@ impl Rocket<Orbit> {
         &self.config
     }
 
+    /// Returns the current configuration: the launch [`config()`] with the
+    /// settings applied by the most recent [`Rocket::refresh_config()`] or
+    /// [`Rocket::refresh_config_from()`], if any.
+    ///
+    /// [`config()`]: Rocket::config()
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # rocket::async_test(async {
+    /// let rocket = rocket::build().ignite().await.unwrap();
+    /// # let rocket = rocket::local::asynchronous::Client::untracked(rocket).await.unwrap();
+    /// # let rocket = rocket.rocket();
+    /// assert_eq!(rocket.current_config().limits, rocket.config().limits);
+    /// # });
+    /// ```
+    pub fn current_config(&self) -> Arc<Config> {
+        self.current_config.read().clone()
+    }
+
+    /// Reloads the configuration and applies its reload-safe settings to the
+    /// running application.
+    ///
+    /// The configuration is extracted from this instance's
+    /// [`figment()`](Rocket::figment()) with the configuration file and the
+    /// `ROCKET_` environment variables read by [`Config::figment()`] merged
+    /// anew, under the instance's profile. Values the application provided in
+    /// code, via [`rocket::custom()`](crate::custom()) or
+    /// [`Rocket::configure()`], are thus retained unless the file or
+    /// environment sets them. To reload from other sources, use
+    /// [`Rocket::refresh_config_from()`].
+    ///
+    /// See [`Rocket::refresh_config_from()`] for details. When
+    /// [`Config::reload_on_sighup`] is enabled, Rocket calls this method
+    /// whenever the process receives `SIGHUP`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::request::{self, Request, FromRequest};
+    ///
+    /// struct ConfigReload(Result<(), rocket::figment::Error>);
+    ///
+    /// #[rocket::async_trait]
+    /// impl<'r> FromRequest<'r> for ConfigReload {
+    ///     type Error = std::convert::Infallible;
+    ///
+    ///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+    ///         request::Outcome::Success(ConfigReload(req.rocket().refresh_config().await))
+    ///     }
+    /// }
+    ///
+    /// #[post("/reload")]
+    /// fn reload(reload: ConfigReload) -> String {
+    ///     match reload.0 {
+    ///         Ok(()) => "reloaded".into(),
+    ///         Err(e) => format!("failed to reload: {}", e),
+    ///     }
+    /// }
+    /// ```
+    pub async fn refresh_config(&self) -> Result<(), figment::Error> {
+        self.refresh_config_from(Config::refresh_figment(&self.figment)).await
+    }
+
+    /// Reloads the configuration from `provider` and applies its reload-safe
+    /// settings to the running application, then runs config reload
+    /// [fairings](crate::fairing::Fairing#config-reload).
+    ///
+    /// The reload-safe settings are:
+    ///
+    ///   * `log_level`, which takes effect immediately.
+    ///   * `limits`, which take effect for requests received thereafter.
+    ///   * `tls`, if TLS is being served: the certificate chain and key are
+    ///     reloaded as if by [`Rocket::reload_tls()`]. A failure to reload
+    ///     them is logged, and the previous certificates remain in use.
+    ///
+    /// All other settings, such as the address and port, remain as they were
+    /// at launch. [Shield](crate::shield::Shield) policies are configured in
+    /// code, not in the configuration, and are thus unaffected by a reload.
+    /// The resulting configuration is available via
+    /// [`Rocket::current_config()`]. Requests in flight retain the
+    /// configuration that was current when they first asked for their
+    /// [limits](crate::Request::limits()).
+    ///
+    /// Returns an error, without changing any settings, if a configuration
+    /// can't be extracted from `provider`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Config;
+    /// use rocket::data::ToByteUnit;
+    /// use rocket::figment::Figment;
+    ///
+    /// # rocket::async_test(async {
+    /// let figment = Figment::from(Config::debug_default());
+    /// let rocket = rocket::custom(&figment);
+    /// # let client = rocket::local::asynchronous::Client::untracked(rocket).await.unwrap();
+    /// # let rocket = client.rocket();
+    ///
+    /// // Later, once `rocket` is in orbit...
+    /// let figment = figment.merge(("limits.form", 64.kibibytes()));
+    /// rocket.refresh_config_from(figment).await.expect("valid config");
+    /// assert_eq!(rocket.current_config().limits.get("form"), Some(64.kibibytes()));
+    /// # });
+    /// ```
+    pub async fn refresh_config_from<T: Provider>(&self, provider: T) -> Result<(), figment::Error> {
+        let new = Config::try_from(provider)?;
+        let mut config = Config::clone(&self.current_config());
+        config.log_level = new.log_level;
+        config.limits = new.limits;
+
+        #[cfg(feature = "tls")]
+        if let (Some(tls), Some(new_tls)) = (config.tls.as_mut(), new.tls) {
+            *tls = new_tls;
+        }
+
+        crate::log::init(&config);
+        *self.current_config.write() = Arc::new(config);
+        info!("{}Configuration reloaded.", Paint::emoji("🔧 "));
+
+        #[cfg(feature = "tls")]
+        if !self.tls_resolvers.is_empty() || !self.listener_tls.is_empty() {
+            if let Err(e) = self.reload_tls() {
+                error!("Failed to reload TLS certificates.");
+                error_!("{}", e);
+            }
+        }
+
+        self.fairings.handle_config_reload(self).await;
+        Ok(())
+    }
+
     /// Returns a handle which can be used to trigger a shutdown and detect a
     /// triggered shutdown.
     ///
@@ -737,6 +1206,222 @@ impl Rocket<Orbit> {
     pub fn shutdown(&self) -> Shutdown {
         self.shutdown.clone()
     }
+
+    /// Spawns `task`, named `name`, as a background task tied to this instance
+    /// of Rocket.
+    ///
+    /// Unlike a task spawned directly with `tokio::spawn()`, a background task
+    /// is awaited during graceful shutdown: after shutdown fairings complete,
+    /// Rocket waits for all background tasks to finish until the
+    /// [grace and mercy](crate::config::Shutdown) periods have elapsed, then
+    /// aborts any that are still running. Tasks should thus monitor
+    /// [`Rocket::shutdown()`] and return promptly once it resolves. A task that
+    /// panics is reported as soon as it does so and is not restarted.
+    ///
+    /// To spawn a task at liftoff, use [`AdHoc::task()`] or
+    /// [`Rocket::manage_task()`].
+    ///
+    /// [`AdHoc::task()`]: crate::fairing::AdHoc::task()
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::{Rocket, Orbit};
+    /// use rocket::request::{self, Request, FromRequest};
+    ///
+    /// struct Spawner<'r>(&'r Rocket<Orbit>);
+    ///
+    /// #[rocket::async_trait]
+    /// impl<'r> FromRequest<'r> for Spawner<'r> {
+    ///     type Error = std::convert::Infallible;
+    ///
+    ///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+    ///         request::Outcome::Success(Spawner(req.rocket()))
+    ///     }
+    /// }
+    ///
+    /// #[post("/reindex")]
+    /// fn reindex(spawner: Spawner<'_>) -> &'static str {
+    ///     let shutdown = spawner.0.shutdown();
+    ///     spawner.0.spawn_task("Reindex", async move {
+    ///         rocket::tokio::select! {
+    ///             _ = async { /* reindex.. */ } => (),
+    ///             _ = shutdown => (),
+    ///         }
+    ///     });
+    ///
+    ///     "reindexing"
+    /// }
+    /// ```
+    pub fn spawn_task<F>(&self, name: &'static str, task: F)
+        where F: std::future::Future<Output = ()> + Send + 'static
+    {
+        self.tasks.spawn(name, task);
+    }
+
+    /// Mounts all of the routes in the supplied vector at the given `base`
+    /// path while Rocket is running, as [`Rocket::mount()`] does before
+    /// launch. Routes mounted this way are _hot_: they can be removed with
+    /// [`hot_unmount()`](Self::hot_unmount()) and are tried, in rank order,
+    /// only after all of the routes mounted before launch have forwarded.
+    /// Requests being processed when routes are mounted or unmounted are
+    /// unaffected by the change.
+    ///
+    /// Hot routes do not participate in [sentinel](crate::Sentinel) checks and
+    /// are not returned by [`Rocket::routes()`].
+    ///
+    /// Returns `Err` with every pair of colliding routes, the new route first,
+    /// if a new route collides with another new route, a hot route, or a route
+    /// mounted before launch. In this case, no routes are mounted.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`Rocket::mount()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::{Request, Route, Data, Rocket, Orbit, route};
+    /// use rocket::http::{Method, Status};
+    /// use rocket::request::{self, FromRequest};
+    ///
+    /// struct Hot<'r>(&'r Rocket<Orbit>);
+    ///
+    /// #[rocket::async_trait]
+    /// impl<'r> FromRequest<'r> for Hot<'r> {
+    ///     type Error = std::convert::Infallible;
+    ///
+    ///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+    ///         request::Outcome::Success(Hot(req.rocket()))
+    ///     }
+    /// }
+    ///
+    /// fn hook<'r>(req: &'r Request, _: Data<'r>) -> route::BoxFuture<'r> {
+    ///     route::Outcome::from(req, "received").pin()
+    /// }
+    ///
+    /// #[put("/hooks/<name>")]
+    /// fn add_hook(hot: Hot<'_>, name: &str) -> Status {
+    ///     let route = Route::new(Method::Post, &format!("/{}", name), hook);
+    ///     match hot.0.hot_mount("/hooks", vec![route]) {
+    ///         Ok(()) => Status::Created,
+    ///         Err(_) => Status::Conflict,
+    ///     }
+    /// }
+    ///
+    /// #[delete("/hooks/<name>")]
+    /// fn remove_hook(hot: Hot<'_>, name: &str) -> Status {
+    ///     let path = format!("/hooks/{}", name);
+    ///     match hot.0.hot_unmount("/hooks", |route| route.uri.path() == path).len() {
+    ///         0 => Status::NotFound,
+    ///         _ => Status::NoContent,
+    ///     }
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn hot_mount<'a, B, R>(&self, base: B, routes: R) -> Result<(), Vec<(Route, Route)>>
+        where B: TryInto<Origin<'a>> + Clone + fmt::Display,
+              B::Error: fmt::Display,
+              R: Into<Vec<Route>>
+    {
+        let routes = mounted("route", base, routes.into(),
+            |base, route| route.map_base(|old| format!("{}{}", base, old)));
+
+        let added = routes.clone();
+        self.hot_router.mount(&self.router, routes)?;
+        added.iter().for_each(|route| info!("{}{} {}", Paint::emoji("📬 "),
+            Paint::magenta("Mounted:"), route));
+
+        Ok(())
+    }
+
+    /// Removes and returns the [hot routes](Self::hot_mount()) mounted at
+    /// `base` for which `f` returns `true`. Requests being processed when
+    /// routes are unmounted are unaffected by the change.
+    ///
+    /// To remove every hot route mounted at `base`, use `|_| true`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base` is not a valid origin URI.
+    ///
+    /// # Example
+    ///
+    /// See [`hot_mount()`](Self::hot_mount()).
+    #[track_caller]
+    pub fn hot_unmount<'a, B, F>(&self, base: B, f: F) -> Vec<Route>
+        where B: TryInto<Origin<'a>> + Clone + fmt::Display,
+              B::Error: fmt::Display,
+              F: Fn(&Route) -> bool
+    {
+        let base = mount_base("route", base).into_normalized();
+        let removed = self.hot_router.unmount(|r| r.uri.base() == base.path().as_str() && f(r));
+        removed.iter().for_each(|route| info!("{}{} {}", Paint::emoji("📭 "),
+            Paint::magenta("Unmounted:"), route));
+
+        removed
+    }
+
+    /// Reloads the TLS certificate chain and private key from the
+    /// [current](Rocket::current_config())
+    /// [`TlsConfig`](crate::config::TlsConfig) `certs` and `key`, typically
+    /// paths to files that have been renewed. New TLS connections use the
+    /// reloaded certificates; established connections are unaffected. The
+    /// certificates of any additional
+    /// [`listeners`](crate::config::Config::listeners) serving TLS are
+    /// reloaded from their own `TlsConfig` as well.
+    ///
+    /// Returns an error if Rocket isn't serving TLS, as is the case with a
+    /// local client, or if the certificates fail to load, in which case the
+    /// previous certificates remain in use.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::request::{self, Request, FromRequest};
+    ///
+    /// struct TlsReload(std::io::Result<()>);
+    ///
+    /// #[rocket::async_trait]
+    /// impl<'r> FromRequest<'r> for TlsReload {
+    ///     type Error = std::convert::Infallible;
+    ///
+    ///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+    ///         request::Outcome::Success(TlsReload(req.rocket().reload_tls()))
+    ///     }
+    /// }
+    ///
+    /// #[post("/reload")]
+    /// fn reload(reload: TlsReload) -> String {
+    ///     match reload.0 {
+    ///         Ok(()) => "reloaded".into(),
+    ///         Err(e) => format!("failed to reload: {}", e),
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "tls")]
+    #[cfg_attr(nightly, doc(cfg(feature = "tls")))]
+    pub fn reload_tls(&self) -> std::io::Result<()> {
+        let config = self.current_config();
+        let primary = config.tls.as_ref().filter(|_| !self.tls_resolvers.is_empty());
+        if primary.is_none() && self.listener_tls.is_empty() {
+            let msg = "TLS is not being served";
+            return Err(std::io::Error::new(std::io::ErrorKind::Other, msg));
+        }
+
+        if let Some(tls) = primary {
+            tls.reload(&self.tls_resolvers)?;
+        }
+
+        for (tls, resolver) in &self.listener_tls {
+            tls.reload(std::slice::from_ref(resolver))?;
+        }
+
+        Ok(())
+    }
 }
 
 impl<P: Phase> Rocket<P> {
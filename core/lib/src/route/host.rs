@@ -0,0 +1,157 @@
+use std::fmt;
+
+/// A pattern matched against the host of a request.
+///
+/// A `HostPattern` is either an exact domain, like `example.com`, or a
+/// wildcard subdomain pattern, like `*.example.com`. Patterns are matched
+/// case-insensitively against the domain of a request's [`Host`], ignoring any
+/// port. A wildcard pattern matches any domain ending with `.` followed by the
+/// pattern's domain: `*.example.com` matches `a.example.com` and
+/// `a.b.example.com` but not `example.com`.
+///
+/// Routes are restricted to hosts via [`Rocket::mount_host()`]. The portion of
+/// a domain matched by a wildcard is available to handlers via the
+/// [`Subdomain`] request guard.
+///
+/// [`Host`]: crate::http::uri::Host
+/// [`Rocket::mount_host()`]: crate::Rocket::mount_host()
+/// [`Subdomain`]: crate::request::Subdomain
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::route::HostPattern;
+///
+/// let pattern = HostPattern::parse("*.example.com").unwrap();
+/// assert!(pattern.is_wildcard());
+/// assert_eq!(pattern.domain(), "example.com");
+/// assert!(pattern.matches("tenant.EXAMPLE.com"));
+/// assert!(!pattern.matches("example.com"));
+/// assert_eq!(pattern.subdomain("a.b.example.com"), Some("a.b"));
+///
+/// let pattern = HostPattern::parse("example.com").unwrap();
+/// assert!(pattern.matches("example.com"));
+/// assert!(!pattern.matches("tenant.example.com"));
+///
+/// assert!(HostPattern::parse("example.com:8000").is_none());
+/// assert!(HostPattern::parse("*.*.example.com").is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HostPattern {
+    domain: String,
+    wildcard: bool,
+}
+
+impl HostPattern {
+    /// Parses `pattern` as a host pattern: a domain, optionally prefixed with
+    /// `*.`. Returns `None` if `pattern` is not a valid pattern.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::route::HostPattern;
+    ///
+    /// assert!(HostPattern::parse("rocket.rs").is_some());
+    /// assert!(HostPattern::parse("*.rocket.rs").is_some());
+    /// assert!(HostPattern::parse("*").is_none());
+    /// assert!(HostPattern::parse("rocket..rs").is_none());
+    /// ```
+    pub fn parse(pattern: &str) -> Option<HostPattern> {
+        let (domain, wildcard) = match pattern.strip_prefix("*.") {
+            Some(domain) => (domain, true),
+            None => (pattern, false),
+        };
+
+        let valid_label = |label: &str| !label.is_empty()
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+
+        if !domain.split('.').all(valid_label) {
+            return None;
+        }
+
+        Some(HostPattern { domain: domain.to_ascii_lowercase(), wildcard })
+    }
+
+    /// Returns the domain of the pattern, without any wildcard prefix, in
+    /// lowercase.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::route::HostPattern;
+    ///
+    /// let pattern = HostPattern::parse("*.Rocket.rs").unwrap();
+    /// assert_eq!(pattern.domain(), "rocket.rs");
+    /// ```
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    /// Returns `true` if the pattern is a wildcard subdomain pattern.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::route::HostPattern;
+    ///
+    /// assert!(HostPattern::parse("*.rocket.rs").unwrap().is_wildcard());
+    /// assert!(!HostPattern::parse("rocket.rs").unwrap().is_wildcard());
+    /// ```
+    pub fn is_wildcard(&self) -> bool {
+        self.wildcard
+    }
+
+    /// Returns `true` if `domain` matches the pattern.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::route::HostPattern;
+    ///
+    /// let pattern = HostPattern::parse("*.rocket.rs").unwrap();
+    /// assert!(pattern.matches("api.rocket.rs"));
+    /// assert!(!pattern.matches("rocket.rs"));
+    /// ```
+    pub fn matches(&self, domain: &str) -> bool {
+        match self.wildcard {
+            true => self.subdomain(domain).is_some(),
+            false => domain.eq_ignore_ascii_case(&self.domain),
+        }
+    }
+
+    /// If the pattern is a wildcard pattern that matches `domain`, returns the
+    /// portion of `domain` matched by the wildcard. Otherwise returns `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::route::HostPattern;
+    ///
+    /// let pattern = HostPattern::parse("*.rocket.rs").unwrap();
+    /// assert_eq!(pattern.subdomain("api.rocket.rs"), Some("api"));
+    /// assert_eq!(pattern.subdomain("rocket.rs"), None);
+    ///
+    /// let pattern = HostPattern::parse("api.rocket.rs").unwrap();
+    /// assert_eq!(pattern.subdomain("api.rocket.rs"), None);
+    /// ```
+    pub fn subdomain<'a>(&self, domain: &'a str) -> Option<&'a str> {
+        if !self.wildcard || domain.len() < self.domain.len() + 2 {
+            return None;
+        }
+
+        let i = domain.len() - self.domain.len() - 1;
+        let (subdomain, rest) = (domain.get(..i)?, domain.get(i..)?);
+        let matches = rest.starts_with('.') && rest[1..].eq_ignore_ascii_case(&self.domain);
+        matches.then(|| subdomain)
+    }
+}
+
+impl fmt::Display for HostPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.wildcard {
+            write!(f, "*.")?;
+        }
+
+        write!(f, "{}", self.domain)
+    }
+}
@@ -4,9 +4,11 @@ mod route;
 mod handler;
 mod uri;
 mod segment;
+mod host;
 
 pub use route::*;
 pub use handler::*;
 pub use uri::*;
+pub use host::*;
 
 pub(crate) use segment::Segment;
@@ -1,11 +1,19 @@
 use std::fmt;
+use std::any::Any;
 use std::convert::From;
 use std::borrow::Cow;
+use std::sync::Arc;
+use std::time::Duration;
 
 use yansi::Paint;
 
+use crate::{Request, Data};
+use crate::data::{ByteUnit, Limits};
 use crate::http::{uri, Method, MediaType};
-use crate::route::{Handler, RouteUri, BoxFuture};
+use crate::http::uncased::Uncased;
+use crate::route::{self, Handler, HostPattern, RouteUri, BoxFuture};
+use crate::outcome::Outcome;
+use crate::fairing::ScopedFairing;
 use crate::sentinel::Sentry;
 
 /// A request handling route.
@@ -18,10 +26,12 @@ use crate::sentinel::Sentry;
 /// ```rust
 /// # #[macro_use] extern crate rocket;
 /// # use std::path::PathBuf;
-/// #[get("/route/<path..>?query", rank = 2, format = "json")]
+/// #[get("/route/<path..>?query", rank = 2, format = "json", timeout = "5s")]
 /// fn route_name(path: PathBuf) { /* handler procedure */ }
 ///
+/// use std::time::Duration;
 /// use rocket::http::{Method, MediaType};
+/// use rocket::data::ToByteUnit;
 ///
 /// let route = routes![route_name].remove(0);
 /// assert_eq!(route.name.unwrap(), "route_name");
@@ -29,12 +39,19 @@ use crate::sentinel::Sentry;
 /// assert_eq!(route.uri, "/route/<path..>?query");
 /// assert_eq!(route.rank, 2);
 /// assert_eq!(route.format.unwrap(), MediaType::JSON);
+/// assert_eq!(route.timeout.unwrap(), Duration::from_secs(5));
+///
+/// #[post("/upload", data = "<file>", limits = "file = 100MiB")]
+/// fn upload(file: rocket::fs::TempFile<'_>) { /* handler procedure */ }
+///
+/// let route = routes![upload].remove(0);
+/// assert_eq!(route.limits.unwrap().get("file"), Some(100.mebibytes()));
 /// ```
 ///
-/// Note that the `rank` and `format` attribute parameters are optional. See
-/// [`#[route]`](macro@crate::route) for details on macro usage. Note also that
-/// a route's mounted _base_ becomes part of its URI; see [`RouteUri`] for
-/// details.
+/// Note that the `rank`, `format`, `timeout`, and `limits` attribute parameters
+/// are optional. See [`#[route]`](macro@crate::route) for details on macro usage.
+/// Note also that a route's mounted _base_ becomes part of its URI; see
+/// [`RouteUri`] for details.
 ///
 /// # Routing
 ///
@@ -188,8 +205,21 @@ pub struct Route {
     pub rank: isize,
     /// The media type this route matches against, if any.
     pub format: Option<MediaType>,
+    /// The hosts this route matches against, if any. See
+    /// [`Rocket::mount_host()`](crate::Rocket::mount_host()).
+    pub host: Option<HostPattern>,
+    /// The maximum time the route's handler may take to produce a response, if
+    /// any. See [`Deadline`](crate::request::Deadline) for details.
+    pub timeout: Option<Duration>,
+    /// The data limits that override the configured limits for requests
+    /// routed to this route, if any. See [`Route::limit()`].
+    pub limits: Option<Limits>,
     /// The discovered sentinels.
     pub(crate) sentinels: Vec<Sentry>,
+    /// The scoped fairings attached to this route, in order of attachment.
+    pub(crate) fairings: Vec<Arc<dyn ScopedFairing>>,
+    /// The route's metadata, at most one value per type.
+    pub(crate) meta: Vec<Arc<dyn Any + Send + Sync>>,
 }
 
 impl Route {
@@ -252,7 +282,12 @@ impl Route {
         Route {
             name: None,
             format: None,
+            host: None,
+            timeout: None,
+            limits: None,
             sentinels: Vec::new(),
+            fairings: Vec::new(),
+            meta: Vec::new(),
             handler: Box::new(handler),
             rank, uri, method,
         }
@@ -290,6 +325,164 @@ impl Route {
         self.uri = RouteUri::try_new(&base, &self.uri.unmounted_origin.to_string())?;
         Ok(self)
     }
+
+    /// Adds or replaces the data limit `name` for requests routed to this
+    /// route, consuming `self` and returning the route with the limit set.
+    ///
+    /// Route limits take precedence over the configured [`Limits`] of the same
+    /// name in [`Request::limits()`] and thus in every data guard; limits not
+    /// set by the route retain their configured values. This allows raising a
+    /// limit, say the `file` limit for an upload endpoint, without raising it
+    /// for every route in the application. The same limits can be set with the
+    /// `limits` route attribute parameter: `limits = "file = 100MiB"`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Route;
+    /// use rocket::http::Method;
+    /// use rocket::data::ToByteUnit;
+    /// # use rocket::route::dummy_handler as handler;
+    ///
+    /// let upload = Route::new(Method::Post, "/upload", handler)
+    ///     .limit("file", 100.mebibytes())
+    ///     .limit("file/pdf", 10.mebibytes());
+    ///
+    /// let limits = upload.limits.unwrap();
+    /// assert_eq!(limits.get("file/jpg"), Some(100.mebibytes()));
+    /// assert_eq!(limits.get("file/pdf"), Some(10.mebibytes()));
+    /// assert_eq!(limits.get("json"), None);
+    /// ```
+    pub fn limit<S: Into<Uncased<'static>>>(mut self, name: S, limit: ByteUnit) -> Self {
+        let limits = self.limits.take().unwrap_or_else(Limits::new);
+        self.limits = Some(limits.limit(name, limit));
+        self
+    }
+
+    /// Returns the route's metadata value of type `T`, if there is one.
+    ///
+    /// Metadata is set via the `meta` route attribute parameter or
+    /// [`Route::set_meta()`]. Because every request routed to a route carries
+    /// a reference to it via [`Request::route()`], metadata is readable by
+    /// request guards, handlers, catchers, and response fairings, enabling
+    /// declarative authorization, documentation tagging, or per-route metrics
+    /// labels. Note that request fairings run before routing and thus never
+    /// observe a route.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// #[derive(Debug, PartialEq)]
+    /// enum Role { Admin, User }
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Tag(&'static str);
+    ///
+    /// #[get("/", meta = [Role::Admin, Tag("admin")])]
+    /// fn admin() { }
+    ///
+    /// #[get("/", meta = Role::User)]
+    /// fn index() { }
+    ///
+    /// let route = routes![admin].remove(0);
+    /// assert_eq!(route.meta::<Role>(), Some(&Role::Admin));
+    /// assert_eq!(route.meta::<Tag>(), Some(&Tag("admin")));
+    ///
+    /// let route = routes![index].remove(0);
+    /// assert_eq!(route.meta::<Role>(), Some(&Role::User));
+    /// assert_eq!(route.meta::<Tag>(), None);
+    /// ```
+    ///
+    /// A request guard that only allows administrators through to routes
+    /// annotated with `Role::Admin`:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::request::{self, Request, FromRequest};
+    /// use rocket::outcome::Outcome;
+    /// use rocket::http::Status;
+    ///
+    /// #[derive(PartialEq)]
+    /// enum Role { Admin, User }
+    ///
+    /// struct Authorized;
+    ///
+    /// # fn role_of(req: &Request<'_>) -> Role { Role::User }
+    /// #[rocket::async_trait]
+    /// impl<'r> FromRequest<'r> for Authorized {
+    ///     type Error = ();
+    ///
+    ///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+    ///         match req.route().and_then(|r| r.meta::<Role>()) {
+    ///             Some(required) if *required != role_of(req) => {
+    ///                 Outcome::Failure((Status::Forbidden, ()))
+    ///             },
+    ///             _ => Outcome::Success(Authorized),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// #[get("/admin", meta = Role::Admin)]
+    /// fn admin(_auth: Authorized) { }
+    /// ```
+    pub fn meta<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.meta.iter().find_map(|value| value.downcast_ref::<T>())
+    }
+
+    /// Sets the route's metadata value of type `T` to `value`, replacing any
+    /// existing value of type `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Route;
+    /// use rocket::http::Method;
+    /// # use rocket::route::dummy_handler as handler;
+    ///
+    /// struct Label(&'static str);
+    ///
+    /// let mut route = Route::new(Method::Get, "/", handler);
+    /// assert!(route.meta::<Label>().is_none());
+    ///
+    /// route.set_meta(Label("index"));
+    /// route.set_meta(Label("home"));
+    /// assert_eq!(route.meta::<Label>().unwrap().0, "home");
+    /// ```
+    pub fn set_meta<T: Any + Send + Sync>(&mut self, value: T) {
+        self.insert_meta(Arc::new(value));
+    }
+
+    fn insert_meta(&mut self, value: Arc<dyn Any + Send + Sync>) {
+        let type_id = Any::type_id(&*value);
+        self.meta.retain(|v| Any::type_id(&**v) != type_id);
+        self.meta.push(value);
+    }
+
+    /// Runs the route's handler, preceded by the request callbacks of its
+    /// scoped fairings and followed by their response callbacks.
+    pub(crate) async fn handle<'r>(
+        &self,
+        req: &'r Request<'_>,
+        mut data: Data<'r>
+    ) -> route::Outcome<'r> {
+        for fairing in &self.fairings {
+            match fairing.on_request(req, &mut data).await {
+                Outcome::Success(()) => continue,
+                Outcome::Failure(status) => return Outcome::Failure(status),
+                Outcome::Forward(()) => return Outcome::Forward(data),
+            }
+        }
+
+        let mut outcome = self.handler.handle(req, data).await;
+        if let Outcome::Success(ref mut response) = outcome {
+            for fairing in &self.fairings {
+                fairing.on_response(req, response).await;
+            }
+        }
+
+        outcome
+    }
 }
 
 impl fmt::Display for Route {
@@ -299,6 +492,10 @@ impl fmt::Display for Route {
         }
 
         write!(f, "{} ", Paint::green(&self.method))?;
+        if let Some(ref host) = self.host {
+            write!(f, "{}", Paint::blue(host).underline())?;
+        }
+
         if self.uri.base() != "/" {
             write!(f, "{}", Paint::blue(self.uri.base()).underline())?;
         }
@@ -313,6 +510,10 @@ impl fmt::Display for Route {
             write!(f, " {}", Paint::yellow(format))?;
         }
 
+        if let Some(timeout) = self.timeout {
+            write!(f, " [{}]", Paint::magenta(format!("{:?}", timeout)))?;
+        }
+
         Ok(())
     }
 }
@@ -325,6 +526,9 @@ impl fmt::Debug for Route {
             .field("uri", &self.uri)
             .field("rank", &self.rank)
             .field("format", &self.format)
+            .field("host", &self.host)
+            .field("timeout", &self.timeout)
+            .field("limits", &self.limits)
             .finish()
     }
 }
@@ -344,9 +548,15 @@ pub struct StaticInfo {
     pub handler: for<'r> fn(&'r crate::Request<'_>, crate::Data<'r>) -> BoxFuture<'r>,
     /// The route's rank, if any.
     pub rank: Option<isize>,
+    /// The route's timeout, if any.
+    pub timeout: Option<Duration>,
+    /// The route's data limit overrides, if any.
+    pub limits: Option<Limits>,
     /// Route-derived sentinels, if any.
     /// This isn't `&'static [SentryInfo]` because `type_name()` isn't `const`.
     pub sentinels: Vec<Sentry>,
+    /// The route's metadata values, if any.
+    pub meta: Vec<Arc<dyn Any + Send + Sync>>,
 }
 
 #[doc(hidden)]
@@ -355,14 +565,25 @@ impl From<StaticInfo> for Route {
         // This should never panic since `info.path` is statically checked.
         let uri = RouteUri::new("/", info.uri);
 
-        Route {
+        let mut route = Route {
             name: Some(info.name.into()),
             method: info.method,
             handler: Box::new(info.handler),
             rank: info.rank.unwrap_or_else(|| uri.default_rank()),
             format: info.format,
+            host: None,
+            timeout: info.timeout,
+            limits: info.limits,
             sentinels: info.sentinels.into_iter().collect(),
+            fairings: Vec::new(),
+            meta: Vec::new(),
             uri,
+        };
+
+        for value in info.meta {
+            route.insert_meta(value);
         }
+
+        route
     }
 }
@@ -1,5 +1,5 @@
 use crate::catcher::Catcher;
-use crate::route::{Route, Color};
+use crate::route::{Route, Color, HostPattern};
 
 use crate::http::{MediaType, Status};
 use crate::request::Request;
@@ -21,6 +21,16 @@ impl Collide for MediaType {
     }
 }
 
+impl Collide for HostPattern {
+    fn collides_with(&self, other: &Self) -> bool {
+        match (self.is_wildcard(), other.is_wildcard()) {
+            (true, false) => self.matches(other.domain()),
+            (false, true) => other.matches(self.domain()),
+            _ => self == other || self.matches(other.domain()) || other.matches(self.domain()),
+        }
+    }
+}
+
 fn paths_collide(route: &Route, other: &Route) -> bool {
     let a_segments = &route.uri.metadata.path_segs;
     let b_segments = &other.uri.metadata.path_segs;
@@ -61,6 +71,16 @@ fn formats_collide(route: &Route, other: &Route) -> bool {
     }
 }
 
+fn hosts_collide(route: &Route, other: &Route) -> bool {
+    // A route restricted to hosts is tried before host-agnostic routes of the
+    // same rank, so the two never collide.
+    match (route.host.as_ref(), other.host.as_ref()) {
+        (Some(a), Some(b)) => a.collides_with(b),
+        (None, None) => true,
+        _ => false
+    }
+}
+
 impl Collide for Route {
     /// Determines if two routes can match against some request. That is, if two
     /// routes `collide`, there exists a request that can match against both
@@ -79,6 +99,7 @@ impl Collide for Route {
             && self.rank == other.rank
             && paths_collide(self, other)
             && formats_collide(self, other)
+            && hosts_collide(self, other)
     }
 }
 
@@ -91,6 +112,8 @@ impl Route {
     ///   * The route's format (if any) matches that of the incoming request.
     ///     - If route specifies format, it only gets requests for that format.
     ///     - If route doesn't specify format, it gets requests for any format.
+    ///   * The route's host pattern (if any) matches the incoming request's
+    ///     host, which must be present.
     ///   * All static components in the route's path match the corresponding
     ///     components in the same position in the incoming request.
     ///   * All static components in the route's query string are also in the
@@ -101,6 +124,7 @@ impl Route {
             && paths_match(self, req)
             && queries_match(self, req)
             && formats_match(self, req)
+            && hosts_match(self, req)
     }
 }
 
@@ -159,6 +183,13 @@ fn queries_match(route: &Route, req: &Request<'_>) -> bool {
     true
 }
 
+fn hosts_match(route: &Route, req: &Request<'_>) -> bool {
+    match route.host.as_ref() {
+        Some(pattern) => req.host().map_or(false, |h| pattern.matches(h.domain().as_str())),
+        None => true
+    }
+}
+
 fn formats_match(route: &Route, request: &Request<'_>) -> bool {
     if !route.method.supports_payload() {
         route.format.as_ref()
@@ -184,9 +215,11 @@ impl Collide for Catcher {
     ///
     ///  * Have the same base.
     ///  * Have the same status code or are both defaults.
+    ///  * Have the same error type or are both untyped.
     fn collides_with(&self, other: &Self) -> bool {
         self.code == other.code
             && self.base.path().segments().eq(other.base.path().segments())
+            && self.error_type.map(|e| e.name) == other.error_type.map(|e| e.name)
     }
 }
 
@@ -196,9 +229,13 @@ impl Catcher {
     ///
     ///  * It is a default catcher _or_ has a code of `status`.
     ///  * Its base is a prefix of the normalized/decoded `req.path()`.
+    ///  * It is untyped _or_ the error recorded by `req` is of its type.
     pub(crate) fn matches(&self, status: Status, req: &Request<'_>) -> bool {
         self.code.map_or(true, |code| code == status.code)
             && self.base.path().segments().prefix_of(req.uri().path().segments())
+            && self.error_type.map_or(true, |error_type| {
+                req.recorded_error().map_or(false, |error| (error_type.is)(&*error))
+            })
     }
 }
 
@@ -571,6 +608,34 @@ mod tests {
     }
 
 
+    fn hosts_collide<A, B>(a: A, b: B) -> bool
+        where A: Into<Option<&'static str>>, B: Into<Option<&'static str>>
+    {
+        let mut route_a = Route::new(Get, "/", dummy_handler);
+        route_a.host = a.into().map(|h| HostPattern::parse(h).expect(h));
+        let mut route_b = Route::new(Get, "/", dummy_handler);
+        route_b.host = b.into().map(|h| HostPattern::parse(h).expect(h));
+        route_a.collides_with(&route_b)
+    }
+
+    #[test]
+    fn host_collisions() {
+        assert!(hosts_collide(None, None));
+        assert!(hosts_collide("a.com", "a.com"));
+        assert!(hosts_collide("a.com", "A.com"));
+        assert!(hosts_collide("*.a.com", "*.a.com"));
+        assert!(hosts_collide("*.a.com", "b.a.com"));
+        assert!(hosts_collide("c.b.a.com", "*.a.com"));
+        assert!(hosts_collide("*.a.com", "*.b.a.com"));
+
+        assert!(!hosts_collide("a.com", None));
+        assert!(!hosts_collide(None, "*.a.com"));
+        assert!(!hosts_collide("a.com", "b.com"));
+        assert!(!hosts_collide("a.com", "*.a.com"));
+        assert!(!hosts_collide("*.a.com", "*.b.com"));
+        assert!(!hosts_collide("*.a.com", "ba.com"));
+    }
+
     fn catchers_collide<A, B>(a: A, ap: &str, b: B, bp: &str) -> bool
         where A: Into<Option<u16>>, B: Into<Option<u16>>
     {
@@ -602,4 +667,18 @@ mod tests {
         assert!(!catchers_collide(None, "/b", None, "/a/b/c"));
         assert!(!catchers_collide(None, "/", None, "/a/b/c"));
     }
+
+    #[test]
+    fn typed_catcher_collisions() {
+        use std::{fmt, io};
+        use crate::catcher::dummy_handler as handler;
+
+        let untyped = Catcher::new(500, handler);
+        let io = Catcher::new(500, handler).error_type::<io::Error>();
+        let fmt = Catcher::new(500, handler).error_type::<fmt::Error>();
+        assert!(io.collides_with(&io.clone()));
+        assert!(!io.collides_with(&untyped));
+        assert!(!io.collides_with(&fmt));
+        assert!(!untyped.collides_with(&fmt));
+    }
 }
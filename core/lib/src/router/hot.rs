@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use crate::Route;
+use crate::http::Method;
+use crate::request::Request;
+use crate::router::{Router, Collide};
+
+/// The table of routes mounted after launch via `Rocket::hot_mount()`.
+///
+/// Each mount or unmount replaces the current router with a new one. Requests
+/// hold a reference to the router that was current when they were routed, so
+/// in-flight requests are unaffected by changes, and old routers are freed
+/// once the last request referring to them completes.
+#[derive(Debug, Default)]
+pub(crate) struct HotRouter {
+    current: RwLock<Option<Arc<Router>>>,
+}
+
+/// The request-local snapshot of the hot router.
+struct Snapshot(Arc<Router>);
+
+impl HotRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the hot router current when this method was first called for
+    /// `req`, if there are any hot routes.
+    pub fn get<'r>(&self, req: &'r Request<'_>) -> Option<&'r Router> {
+        let current = self.current.read().clone()?;
+        Some(&req.local_cache(|| Snapshot(current)).0)
+    }
+
+    /// Returns the extension method named `name` if any hot route handles it.
+    pub fn extension_method(&self, name: &str) -> Option<Method> {
+        self.current.read().as_ref().and_then(|router| router.extension_method(name))
+    }
+
+    /// Adds `routes` unless any of them collide with each other, with an
+    /// existing hot route, or with a route in `statics`. On collision, returns
+    /// every pair of colliding routes with the new route first.
+    pub fn mount(&self, statics: &Router, new: Vec<Route>) -> Result<(), Vec<(Route, Route)>> {
+        let mut current = self.current.write();
+        let existing: Vec<_> = current.iter().flat_map(|r| r.routes()).cloned().collect();
+        let collisions: Vec<_> = new.iter()
+            .enumerate()
+            .flat_map(|(i, a)| {
+                new.iter().skip(i + 1)
+                    .chain(existing.iter())
+                    .chain(statics.routes())
+                    .filter(move |b| a.collides_with(b))
+                    .map(move |b| (a.clone(), b.clone()))
+            })
+            .collect();
+
+        if !collisions.is_empty() {
+            return Err(collisions);
+        }
+
+        let mut router = Router::new();
+        existing.into_iter().chain(new).for_each(|r| router.add_route(r));
+        *current = Some(Arc::new(router));
+        Ok(())
+    }
+
+    /// Removes and returns the routes for which `f` returns `true`.
+    pub fn unmount<F: Fn(&Route) -> bool>(&self, f: F) -> Vec<Route> {
+        let mut current = self.current.write();
+        let (removed, kept): (Vec<_>, Vec<_>) = current.iter()
+            .flat_map(|r| r.routes())
+            .cloned()
+            .partition(|r| f(r));
+
+        if !removed.is_empty() {
+            *current = (!kept.is_empty()).then(|| {
+                let mut router = Router::new();
+                kept.into_iter().for_each(|r| router.add_route(r));
+                Arc::new(router)
+            });
+        }
+
+        removed
+    }
+}
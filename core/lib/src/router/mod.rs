@@ -2,6 +2,8 @@
 
 mod router;
 mod collider;
+mod hot;
 
 pub(crate) use router::*;
 pub(crate) use collider::*;
+pub(crate) use hot::*;
@@ -26,13 +26,16 @@ impl Router {
     pub fn add_route(&mut self, route: Route) {
         let routes = self.routes.entry(route.method).or_default();
         routes.push(route);
-        routes.sort_by_key(|r| r.rank);
+        routes.sort_by_key(|r| (r.rank, r.host.is_none()));
     }
 
     pub fn add_catcher(&mut self, catcher: Catcher) {
         let catchers = self.catchers.entry(catcher.code).or_default();
         catchers.push(catcher);
-        catchers.sort_by(|a, b| b.base.path().segments().len().cmp(&a.base.path().segments().len()))
+        catchers.sort_by(|a, b| {
+            b.base.path().segments().len().cmp(&a.base.path().segments().len())
+                .then_with(|| a.error_type.is_none().cmp(&b.error_type.is_none()))
+        })
     }
 
     #[inline]
@@ -49,15 +52,24 @@ impl Router {
         &'a self,
         req: &'r Request<'r>
     ) -> impl Iterator<Item = &'a Route> + 'r {
-        // Note that routes are presorted by ascending rank on each `add`.
+        // Note that routes are presorted by ascending rank on each `add`, with
+        // host-restricted routes first among routes of equal rank.
         self.routes.get(&req.method())
             .into_iter()
             .flat_map(move |routes| routes.iter().filter(move |r| r.matches(req)))
     }
 
+    /// Returns the extension method named `name` if any route handles it.
+    pub fn extension_method(&self, name: &str) -> Option<Method> {
+        self.routes.keys()
+            .find(|method| matches!(method, Method::Extension(n) if *n == name))
+            .copied()
+    }
+
     // For many catchers, using aho-corasick or similar should be much faster.
     pub fn catch<'r>(&self, status: Status, req: &'r Request<'r>) -> Option<&Catcher> {
-        // Note that catchers are presorted by descending base length.
+        // Note that catchers are presorted by descending base length, typed
+        // catchers first.
         let explicit = self.catchers.get(&Some(status.code))
             .and_then(|c| c.iter().find(|c| c.matches(status, req)));
 
@@ -0,0 +1,417 @@
+use std::fmt;
+use std::borrow::Cow;
+use std::str::FromStr;
+use std::time::Duration;
+
+use time::{Date, Month, OffsetDateTime, UtcOffset};
+
+/// When a scheduled job runs: on a cron schedule or at a fixed interval.
+///
+/// A `Schedule` is parsed from a string, typically a standard five-field cron
+/// expression, or created from a [`Duration`] with [`Schedule::every()`].
+///
+/// # Cron Expressions
+///
+/// A cron expression consists of five whitespace-separated fields:
+///
+/// ```text
+/// ┌───────────── minute (0 - 59)
+/// │ ┌─────────── hour (0 - 23)
+/// │ │ ┌───────── day of month (1 - 31)
+/// │ │ │ ┌─────── month (1 - 12 or JAN - DEC)
+/// │ │ │ │ ┌───── day of week (0 - 7 or SUN - SAT, 0 and 7 are Sunday)
+/// │ │ │ │ │
+/// 0 3 * * *
+/// ```
+///
+/// Each field is `*` or a comma-separated list of values (`5`), ranges
+/// (`1-5`), and steps (`*/15`, `0-30/10`, `5/10`). As in most cron
+/// implementations, when both the day of month and the day of week are
+/// restricted, a day matches if _either_ field matches. Cron expressions are
+/// evaluated in UTC.
+///
+/// The following shorthands are also accepted:
+///
+/// | shorthand               | equivalent    |
+/// |-------------------------|---------------|
+/// | `@yearly`, `@annually`  | `0 0 1 1 *`   |
+/// | `@monthly`              | `0 0 1 * *`   |
+/// | `@weekly`               | `0 0 * * 0`   |
+/// | `@daily`, `@midnight`   | `0 0 * * *`   |
+/// | `@hourly`               | `0 * * * *`   |
+/// | `@every <n><s,m,h,d>`   | an interval   |
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use rocket::schedule::Schedule;
+///
+/// let nightly: Schedule = "0 3 * * *".parse().unwrap();
+/// let weekdays = Schedule::cron("*/15 9-17 * * MON-FRI").unwrap();
+/// let often = Schedule::every(Duration::from_secs(30));
+/// assert_eq!(often, "@every 30s".parse().unwrap());
+///
+/// assert!(Schedule::cron("0 3 * *").is_err());
+/// assert!(Schedule::cron("60 * * * *").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule(Kind);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Kind {
+    Every(Duration),
+    Cron(Cron),
+}
+
+/// A parsed cron expression: each field as a bitset of matching values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Cron {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+/// An error parsing a [`Schedule`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    expr: String,
+    reason: Cow<'static, str>,
+}
+
+const MONTHS: &[&str] = &[
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC"
+];
+
+const WEEKDAYS: &[&str] = &["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+impl Schedule {
+    /// Parses the cron expression or shorthand `expr`. See the
+    /// [type level docs](Schedule) for the accepted syntax.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::schedule::Schedule;
+    ///
+    /// assert!(Schedule::cron("0 0 1,15 * *").is_ok());
+    /// assert!(Schedule::cron("@hourly").is_ok());
+    /// assert!(Schedule::cron("0 0 32 * *").is_err());
+    /// ```
+    pub fn cron(expr: &str) -> Result<Schedule, Error> {
+        let error = |reason: Cow<'static, str>| Error { expr: expr.into(), reason };
+        let expanded = match expr.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            s if s.starts_with("@every") => {
+                let interval = s["@every".len()..].trim();
+                let period = parse_interval(interval).map_err(|e| error(e.into()))?;
+                return Ok(Schedule::every(period));
+            }
+            s if s.starts_with('@') => return Err(error("unknown shorthand".into())),
+            s => s,
+        };
+
+        let fields: Vec<_> = expanded.split_whitespace().collect();
+        if fields.len() != 5 {
+            let reason = format!("expected 5 fields, found {}", fields.len());
+            return Err(error(reason.into()));
+        }
+
+        let field = |i: usize, name: &str, min, max, names| {
+            parse_field(fields[i], min, max, names)
+                .map_err(|e| error(format!("invalid {} field `{}`: {}", name, fields[i], e).into()))
+        };
+
+        let mut weekdays = field(4, "day of week", 0, 7, WEEKDAYS)?;
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays & !(1 << 7)) | 1;
+        }
+
+        Ok(Schedule(Kind::Cron(Cron {
+            minutes: field(0, "minute", 0, 59, &[])?,
+            hours: field(1, "hour", 0, 23, &[])?,
+            days: field(2, "day of month", 1, 31, &[])?,
+            months: field(3, "month", 1, 12, MONTHS)?,
+            weekdays,
+            any_day: fields[2] == "*",
+            any_weekday: fields[4] == "*",
+        })))
+    }
+
+    /// Returns a schedule that runs every `period`, starting one `period`
+    /// after liftoff.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use rocket::schedule::Schedule;
+    ///
+    /// let schedule = Schedule::every(Duration::from_secs(60 * 5));
+    /// ```
+    pub fn every(period: Duration) -> Schedule {
+        assert!(!period.is_zero(), "scheduled interval must be non-zero");
+        Schedule(Kind::Every(period))
+    }
+
+    /// Returns the interval of this schedule if it was created with
+    /// [`Schedule::every()`] or an `@every` shorthand.
+    pub(crate) fn interval(&self) -> Option<Duration> {
+        match self.0 {
+            Kind::Every(period) => Some(period),
+            Kind::Cron(_) => None,
+        }
+    }
+
+    /// Returns the first time _strictly_ after `time` at which the schedule is
+    /// due, or `None` if there is no such time within the next five years,
+    /// as is the case for `0 0 30 2 *`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::schedule::Schedule;
+    /// # use time::macros::datetime;
+    ///
+    /// let schedule = Schedule::cron("30 3 * * *").unwrap();
+    /// let next = schedule.next_after(datetime!(2024-01-01 12:00 UTC));
+    /// assert_eq!(next, Some(datetime!(2024-01-02 3:30 UTC)));
+    /// ```
+    pub fn next_after(&self, time: OffsetDateTime) -> Option<OffsetDateTime> {
+        let cron = match &self.0 {
+            Kind::Every(period) => return time.checked_add((*period).try_into().ok()?),
+            Kind::Cron(cron) => cron,
+        };
+
+        let time = time.to_offset(UtcOffset::UTC);
+        let start = time.date().with_hms(time.hour(), time.minute(), 0).ok()?.assume_utc();
+        let mut next = start + time::Duration::minutes(1);
+        let limit = start.year() + 5;
+        while next.year() <= limit {
+            if !bit(cron.months, next.month() as u8) {
+                let (year, month) = match next.month() {
+                    Month::December => (next.year() + 1, Month::January),
+                    month => (next.year(), month.next()),
+                };
+
+                next = Date::from_calendar_date(year, month, 1).ok()?.midnight().assume_utc();
+            } else if !cron.matches_day(next.date()) {
+                next = next.date().next_day()?.midnight().assume_utc();
+            } else if !bit(cron.hours, next.hour()) {
+                next = next.date().with_hms(next.hour(), 0, 0).ok()?.assume_utc()
+                    + time::Duration::hours(1);
+            } else if !bit(cron.minutes, next.minute()) {
+                next += time::Duration::minutes(1);
+            } else {
+                return Some(next);
+            }
+        }
+
+        None
+    }
+}
+
+impl Cron {
+    fn matches_day(&self, date: Date) -> bool {
+        let day = bit(self.days, date.day());
+        let weekday = bit(self.weekdays, date.weekday().number_days_from_sunday());
+        match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (true, false) => weekday,
+            (false, true) => day,
+            (false, false) => day || weekday,
+        }
+    }
+}
+
+fn bit(set: u64, value: u8) -> bool {
+    set & (1 << value) != 0
+}
+
+/// Parses a cron field into a bitset of the values in `min..=max` it matches.
+fn parse_field(field: &str, min: u8, max: u8, names: &[&str]) -> Result<u64, String> {
+    let value = |s: &str| -> Result<u8, String> {
+        let n = match names.iter().position(|n| n.eq_ignore_ascii_case(s)) {
+            // Named months start at 1; named weekdays at 0.
+            Some(i) => i as u8 + min,
+            None => s.parse().map_err(|_| format!("`{}` is not a number", s))?,
+        };
+
+        match (min..=max).contains(&n) {
+            true => Ok(n),
+            false => Err(format!("{} is out of range {}-{}", n, min, max)),
+        }
+    };
+
+    let mut set = 0;
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => match step.parse::<u8>() {
+                Ok(step) if step > 0 => (range, step),
+                _ => return Err(format!("`{}` is not a valid step", step)),
+            },
+            None => (item, 1),
+        };
+
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((start, end)) => (value(start)?, value(end)?),
+            None if item.contains('/') => (value(range)?, max),
+            None => { let n = value(range)?; (n, n) }
+        };
+
+        if start > end {
+            return Err(format!("range `{}` is empty", range));
+        }
+
+        for n in (start..=end).step_by(step as usize) {
+            set |= 1 << n;
+        }
+    }
+
+    Ok(set)
+}
+
+/// Parses an interval like `30s`, `5m`, `2h`, or `1d`.
+fn parse_interval(interval: &str) -> Result<Duration, &'static str> {
+    let split = interval.find(|c: char| !c.is_ascii_digit()).unwrap_or(interval.len());
+    let (n, unit) = interval.split_at(split);
+    let n: u64 = n.parse().map_err(|_| "expected an interval like `30s` or `5m`")?;
+    let secs = match unit.trim() {
+        "s" => n,
+        "m" => n.saturating_mul(60),
+        "h" => n.saturating_mul(60 * 60),
+        "d" => n.saturating_mul(60 * 60 * 24),
+        _ => return Err("interval unit must be one of `s`, `m`, `h`, or `d`"),
+    };
+
+    match secs {
+        0 => Err("interval must be non-zero"),
+        secs => Ok(Duration::from_secs(secs)),
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Schedule::cron(s)
+    }
+}
+
+impl TryFrom<&str> for Schedule {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Schedule::cron(s)
+    }
+}
+
+impl TryFrom<String> for Schedule {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Schedule::cron(&s)
+    }
+}
+
+impl From<Duration> for Schedule {
+    fn from(period: Duration) -> Self {
+        Schedule::every(period)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid schedule `{}`: {}", self.expr, self.reason)
+    }
+}
+
+impl std::error::Error for Error {  }
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+    use super::*;
+
+    fn next(expr: &str, time: OffsetDateTime) -> Option<OffsetDateTime> {
+        Schedule::cron(expr).unwrap().next_after(time)
+    }
+
+    #[test]
+    fn test_parse_fields() {
+        assert_eq!(parse_field("*", 0, 3, &[]), Ok(0b1111));
+        assert_eq!(parse_field("1,3", 0, 3, &[]), Ok(0b1010));
+        assert_eq!(parse_field("1-2", 0, 3, &[]), Ok(0b0110));
+        assert_eq!(parse_field("*/2", 0, 5, &[]), Ok(0b010101));
+        assert_eq!(parse_field("1/2", 0, 5, &[]), Ok(0b101010));
+        assert_eq!(parse_field("0-4/3", 0, 5, &[]), Ok(0b001001));
+        assert_eq!(parse_field("feb-Mar", 1, 12, MONTHS), Ok(0b1100));
+        assert_eq!(parse_field("SUN,sat", 0, 7, WEEKDAYS), Ok(0b1000001));
+
+        assert!(parse_field("", 0, 3, &[]).is_err());
+        assert!(parse_field("4", 0, 3, &[]).is_err());
+        assert!(parse_field("3-1", 0, 3, &[]).is_err());
+        assert!(parse_field("*/0", 0, 3, &[]).is_err());
+        assert!(parse_field("1,,2", 0, 3, &[]).is_err());
+        assert!(parse_field("x", 0, 3, &[]).is_err());
+    }
+
+    #[test]
+    fn test_parse_schedules() {
+        assert_eq!(Schedule::cron("@daily"), Schedule::cron("0 0 * * *"));
+        assert_eq!(Schedule::cron("0 0 * * 7"), Schedule::cron("0 0 * * 0"));
+        assert_eq!(Schedule::cron("@every 5m"), Ok(Schedule::every(Duration::from_secs(300))));
+        assert_eq!(Schedule::cron("@every 2d"), Ok(Schedule::every(Duration::from_secs(172800))));
+
+        assert!(Schedule::cron("@every").is_err());
+        assert!(Schedule::cron("@every 0s").is_err());
+        assert!(Schedule::cron("@every 5y").is_err());
+        assert!(Schedule::cron("@often").is_err());
+        assert!(Schedule::cron("* * * * * *").is_err());
+        assert!(Schedule::cron("* 24 * * *").is_err());
+        assert!(Schedule::cron("* * 0 * *").is_err());
+        assert!(Schedule::cron("* * * 13 *").is_err());
+        assert!(Schedule::cron("* * * * 8").is_err());
+    }
+
+    #[test]
+    fn test_next_after() {
+        let t = datetime!(2024-02-28 23:59:30 UTC);
+        assert_eq!(next("* * * * *", t), Some(datetime!(2024-02-29 0:00 UTC)));
+        assert_eq!(next("0 3 * * *", t), Some(datetime!(2024-02-29 3:00 UTC)));
+        assert_eq!(next("*/20 * * * *", t), Some(datetime!(2024-02-29 0:00 UTC)));
+        assert_eq!(next("0 0 1 * *", t), Some(datetime!(2024-03-01 0:00 UTC)));
+        assert_eq!(next("0 0 29 2 *", t), Some(datetime!(2024-02-29 0:00 UTC)));
+        assert_eq!(next("0 0 29 2 *", datetime!(2024-03-01 0:00 UTC)),
+            Some(datetime!(2028-02-29 0:00 UTC)));
+        assert_eq!(next("@yearly", t), Some(datetime!(2025-01-01 0:00 UTC)));
+        assert_eq!(next("0 0 30 2 *", t), None);
+
+        // 2024-02-28 is a Wednesday.
+        assert_eq!(next("0 12 * * MON", t), Some(datetime!(2024-03-04 12:00 UTC)));
+        assert_eq!(next("0 12 15 * MON", t), Some(datetime!(2024-03-04 12:00 UTC)));
+        assert_eq!(next("0 12 1 * MON", t), Some(datetime!(2024-03-01 12:00 UTC)));
+
+        // Times are strictly after `time` and evaluated in UTC.
+        let t = datetime!(2024-01-01 3:00 UTC);
+        assert_eq!(next("0 3 * * *", t), Some(datetime!(2024-01-02 3:00 UTC)));
+        let t = datetime!(2024-01-01 0:30 -3);
+        assert_eq!(next("0 * * * *", t), Some(datetime!(2024-01-01 4:00 UTC)));
+
+        let every = Schedule::every(Duration::from_secs(90));
+        assert_eq!(every.next_after(t), Some(datetime!(2024-01-01 0:31:30 -3)));
+    }
+}
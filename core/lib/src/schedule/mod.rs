@@ -0,0 +1,52 @@
+//! Scheduled jobs that run on a cron or interval schedule.
+//!
+//! The [`Scheduler`] fairing runs jobs, each registered with a [`Schedule`],
+//! as [background tasks](crate::Rocket::spawn_task()) from liftoff until
+//! shutdown. Applications can thus run periodic maintenance, like pruning old
+//! rows from a database, without an external cron daemon:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::schedule::Scheduler;
+//!
+//! # #[derive(Clone)] struct Db;
+//! async fn prune(db: Db) {
+//!     /* delete rows older than a week */
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     let scheduler = Scheduler::new()
+//!         .schedule("Prune", "0 3 * * *", |rocket| {
+//!             let db = rocket.state::<Db>().cloned().expect("managed `Db`");
+//!             move || prune(db.clone())
+//!         });
+//!
+//!     rocket::build().manage(Db).attach(scheduler)
+//! }
+//! ```
+//!
+//! # Jobs
+//!
+//! A job is registered with a function that is called once, at liftoff, with
+//! the launched `Rocket` instance. It returns the job itself: a closure that
+//! is called on every run and returns the run's future. Because the job
+//! outlives the borrow of `Rocket`, any managed state it needs, like a
+//! database pool, should be cloned out of the instance when the job is
+//! created, as above.
+//!
+//! Runs of a job never overlap: if a run is still executing when the job is
+//! next due, that occurrence is skipped. A run that panics is reported; the
+//! job continues to run on schedule.
+//!
+//! # Shutdown
+//!
+//! Once [shutdown](crate::Shutdown) is requested, no further runs are started.
+//! A run that is in progress is awaited like any other background task until
+//! the grace and mercy periods elapse, at which point it is aborted.
+
+mod cron;
+mod scheduler;
+
+pub use self::cron::{Schedule, Error};
+pub use self::scheduler::Scheduler;
@@ -0,0 +1,199 @@
+use std::fmt;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::Mutex;
+
+use futures::future::{BoxFuture, Either, FutureExt};
+use time::OffsetDateTime;
+use tokio::time::{interval_at, sleep, Instant, MissedTickBehavior};
+use yansi::Paint;
+
+use crate::{Rocket, Build, Orbit, Shutdown};
+use crate::fairing::{self, Fairing, Info, Kind};
+use crate::schedule::Schedule;
+
+/// A fairing that runs jobs on a [`Schedule`].
+///
+/// On ignition, the fairing validates the schedule of every job registered
+/// with [`Scheduler::schedule()`], failing ignition if any is invalid. On
+/// liftoff, each job is spawned as a [background task](Rocket::spawn_task())
+/// that runs it on schedule until shutdown. See the
+/// [module level docs](crate::schedule) for details.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use std::time::Duration;
+/// use rocket::schedule::Scheduler;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     let scheduler = Scheduler::new()
+///         .schedule("Nightly", "@daily", |_| || async { /* .. */ })
+///         .schedule("Heartbeat", Duration::from_secs(30), |_| || async { /* .. */ });
+///
+///     rocket::build().attach(scheduler)
+/// }
+/// ```
+#[derive(Default)]
+pub struct Scheduler {
+    jobs: Mutex<Vec<Job>>,
+}
+
+/// A job registered with a `Scheduler`, not yet spawned.
+struct Job {
+    name: &'static str,
+    schedule: Result<Schedule, String>,
+    init: Box<dyn FnOnce(&Rocket<Orbit>) -> Runner + Send>,
+}
+
+/// A spawned job: called on every run.
+type Runner = Box<dyn FnMut() -> BoxFuture<'static, ()> + Send>;
+
+impl Scheduler {
+    /// Returns a `Scheduler` with no jobs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::schedule::Scheduler;
+    ///
+    /// let rocket = rocket::build().attach(Scheduler::new());
+    /// ```
+    pub fn new() -> Scheduler {
+        Scheduler::default()
+    }
+
+    /// Registers a job named `name` that runs on `schedule`, either a
+    /// [`Schedule`], a string to be parsed as one, or a
+    /// [`Duration`](std::time::Duration) interval.
+    ///
+    /// At liftoff, `f` is called with the launched `Rocket` instance and
+    /// returns the job: a function called on every run that returns the run's
+    /// future. An invalid `schedule` causes ignition to fail.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::schedule::Scheduler;
+    ///
+    /// # #[derive(Clone)] struct Db;
+    /// # impl Db { async fn prune(&self) {} }
+    /// let scheduler = Scheduler::new()
+    ///     .schedule("Prune", "0 3 * * *", |rocket| {
+    ///         let db = rocket.state::<Db>().cloned().expect("managed `Db`");
+    ///         move || {
+    ///             let db = db.clone();
+    ///             async move { db.prune().await }
+    ///         }
+    ///     });
+    /// ```
+    pub fn schedule<S, F, J, Fut>(self, name: &'static str, schedule: S, f: F) -> Scheduler
+        where S: TryInto<Schedule>,
+              S::Error: fmt::Display,
+              F: FnOnce(&Rocket<Orbit>) -> J + Send + 'static,
+              J: FnMut() -> Fut + Send + 'static,
+              Fut: Future<Output = ()> + Send + 'static,
+    {
+        let job = Job {
+            name,
+            schedule: schedule.try_into().map_err(|e| e.to_string()),
+            init: Box::new(move |rocket| {
+                let mut job = f(rocket);
+                Box::new(move || job().boxed())
+            }),
+        };
+
+        self.jobs.lock().expect("scheduler jobs lock").push(job);
+        self
+    }
+}
+
+impl Job {
+    /// Runs the job on `schedule` until `shutdown` resolves.
+    async fn run(name: &'static str, schedule: Schedule, mut job: Runner, mut shutdown: Shutdown) {
+        let mut interval = schedule.interval().map(|period| {
+            let mut interval = interval_at(Instant::now() + period, period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            interval
+        });
+
+        loop {
+            let due = match interval.as_mut() {
+                Some(interval) => Either::Left(interval.tick().map(|_| ())),
+                None => {
+                    let now = OffsetDateTime::now_utc();
+                    let next = match schedule.next_after(now) {
+                        Some(next) => next,
+                        None => {
+                            warn!("Scheduled job {} will never run again.", Paint::white(name));
+                            return;
+                        }
+                    };
+
+                    Either::Right(sleep((next - now).try_into().unwrap_or_default()))
+                }
+            };
+
+            tokio::select! {
+                biased;
+
+                _ = &mut shutdown => return,
+                _ = due => {},
+            }
+
+            if AssertUnwindSafe(async { job().await }).catch_unwind().await.is_err() {
+                error!("Scheduled job {} panicked.", Paint::white(name));
+                info_!("This is an application bug.");
+                warn_!("The job will continue to run on schedule.");
+            }
+        }
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for Scheduler {
+    fn info(&self) -> Info {
+        Info { name: "Scheduler", kind: Kind::Ignite | Kind::Liftoff }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let jobs = self.jobs.lock().expect("scheduler jobs lock");
+        let mut valid = true;
+        for job in jobs.iter() {
+            if let Err(e) = &job.schedule {
+                error!("Scheduled job {} has an invalid schedule.", Paint::white(job.name));
+                error_!("{}", e);
+                valid = false;
+            }
+        }
+
+        match valid {
+            true => Ok(rocket),
+            false => Err(rocket),
+        }
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        let jobs = std::mem::take(&mut *self.jobs.lock().expect("scheduler jobs lock"));
+        for job in jobs {
+            if let Ok(schedule) = job.schedule {
+                let runner = (job.init)(rocket);
+                rocket.spawn_task(job.name, Job::run(job.name, schedule, runner, rocket.shutdown()));
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Scheduler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let jobs = self.jobs.lock().expect("scheduler jobs lock");
+        let mut list = f.debug_list();
+        for job in jobs.iter() {
+            list.entry(&(job.name, &job.schedule));
+        }
+
+        list.finish()
+    }
+}
@@ -0,0 +1,305 @@
+//! Automatic CBOR (de)serialization support.
+//!
+//! See [`Cbor`](crate::serde::cbor::Cbor) for further details.
+//!
+//! # Enabling
+//!
+//! This module is only available when the `cbor` feature is enabled. Enable it
+//! in `Cargo.toml` as follows:
+//!
+//! ```toml
+//! [dependencies.rocket]
+//! version = "0.5.0-rc.2"
+//! features = ["cbor"]
+//! ```
+//!
+//! # Testing
+//!
+//! The [`LocalRequest`] and [`LocalResponse`] types provide [`cbor()`] and
+//! [`into_cbor()`] methods to create a request with serialized CBOR and
+//! deserialize a response as CBOR, respectively.
+//!
+//! [`LocalRequest`]: crate::local::blocking::LocalRequest
+//! [`LocalResponse`]: crate::local::blocking::LocalResponse
+//! [`cbor()`]: crate::local::blocking::LocalRequest::cbor()
+//! [`into_cbor()`]: crate::local::blocking::LocalResponse::into_cbor()
+
+use std::io;
+use std::ops::{Deref, DerefMut};
+
+use crate::request::Request;
+use crate::data::{Limits, Data, FromData, Outcome};
+use crate::response::{self, Responder, content};
+use crate::http::Status;
+use crate::form::prelude as form;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// Error returned by the [`Cbor`] guard when CBOR deserialization fails.
+pub type Error = ciborium::de::Error<io::Error>;
+
+/// The CBOR guard: easily consume and return CBOR.
+///
+/// ## Sending CBOR
+///
+/// To respond with serialized [CBOR] data, return a `Cbor<T>` type, where `T`
+/// implements [`Serialize`] from [`serde`]. The content type of the response
+/// is set to `application/cbor` automatically.
+///
+/// [CBOR]: https://cbor.io
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # type Reading = usize;
+/// use rocket::serde::cbor::Cbor;
+///
+/// #[get("/readings/<id>")]
+/// fn reading(id: usize) -> Cbor<Reading> {
+///     let reading_from_id = Reading::from(id);
+///     /* ... */
+///     Cbor(reading_from_id)
+/// }
+/// ```
+///
+/// ## Receiving CBOR
+///
+/// `Cbor` is both a data guard and a form guard.
+///
+/// ### Data Guard
+///
+/// To deserialize request body data as CBOR, add a `data` route argument with
+/// a target type of `Cbor<T>`, where `T` is some type you'd like to parse from
+/// CBOR. `T` must implement [`serde::de::DeserializeOwned`].
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # type Reading = usize;
+/// use rocket::serde::cbor::Cbor;
+///
+/// #[post("/readings", format = "cbor", data = "<reading>")]
+/// fn new_reading(reading: Cbor<Reading>) {
+///     /* ... */
+/// }
+/// ```
+///
+/// You don't _need_ to use `format = "cbor"`, but it _may_ be what you want.
+/// Using `format = cbor` means that any request that doesn't specify
+/// "application/cbor" as its `Content-Type` header value will not be routed to
+/// this handler.
+///
+/// ### Form Guard
+///
+/// `Cbor<T>`, as a form guard, accepts data fields and parses the data as a
+/// `T`. Simple use `Cbor<T>`:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # type Metadata = usize;
+/// use rocket::form::{Form, FromForm};
+/// use rocket::serde::cbor::Cbor;
+///
+/// #[derive(FromForm)]
+/// struct Device<'r> {
+///     name: &'r str,
+///     metadata: Cbor<Metadata>
+/// }
+///
+/// #[post("/devices", data = "<form>")]
+/// fn new_device(form: Form<Device<'_>>) {
+///     /* ... */
+/// }
+/// ```
+///
+/// ### Incoming Data Limits
+///
+/// The default size limit for incoming CBOR data is 1MiB. Setting a limit
+/// protects your application from denial of service (DOS) attacks and from
+/// resource exhaustion through high memory consumption. The limit can be
+/// increased by setting the `limits.cbor` configuration parameter. For
+/// instance, to increase the CBOR limit to 5MiB for all environments, you may
+/// add the following to your `Rocket.toml`:
+///
+/// ```toml
+/// [global.limits]
+/// cbor = 5242880
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cbor<T>(pub T);
+
+impl<T> Cbor<T> {
+    /// Consumes the `Cbor` wrapper and returns the wrapped item.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::serde::cbor::Cbor;
+    /// let string = "Hello".to_string();
+    /// let my_cbor = Cbor(string);
+    /// assert_eq!(my_cbor.into_inner(), "Hello".to_string());
+    /// ```
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: DeserializeOwned> Cbor<T> {
+    async fn from_data(req: &Request<'_>, data: Data<'_>) -> Result<Self, Error> {
+        let limit = req.limits().get("cbor").unwrap_or(Limits::CBOR);
+        let bytes = match data.open(limit).into_bytes().await {
+            Ok(buf) if buf.is_complete() => buf.into_inner(),
+            Ok(_) => {
+                let eof = io::ErrorKind::UnexpectedEof;
+                return Err(Error::Io(io::Error::new(eof, "data limit exceeded")));
+            },
+            Err(e) => return Err(Error::Io(e)),
+        };
+
+        from_slice(&bytes).map(Cbor)
+    }
+}
+
+#[crate::async_trait]
+impl<'r, T: DeserializeOwned> FromData<'r> for Cbor<T> {
+    type Error = Error;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+        match Self::from_data(req, data).await {
+            Ok(value) => Outcome::Success(value),
+            Err(Error::Io(e)) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                Outcome::Failure((Status::PayloadTooLarge, Error::Io(e)))
+            },
+            Err(e@Error::Semantic(..)) => Outcome::Failure((Status::UnprocessableEntity, e)),
+            Err(e) => Outcome::Failure((Status::BadRequest, e)),
+        }
+    }
+}
+
+/// Serializes the wrapped value into CBOR. Returns a response with
+/// Content-Type `CBOR` and a fixed-size body with the serialization. If
+/// serialization fails, an `Err` of `Status::InternalServerError` is returned.
+impl<'r, T: Serialize> Responder<'r, 'static> for Cbor<T> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let buf = to_vec(&self.0)
+            .map_err(|e| {
+                error_!("CBOR failed to serialize: {:?}", e);
+                Status::InternalServerError
+            })?;
+
+        content::RawCbor(buf).respond_to(req)
+    }
+}
+
+#[crate::async_trait]
+impl<'v, T: DeserializeOwned + Send> form::FromFormField<'v> for Cbor<T> {
+    async fn from_data(f: form::DataField<'v, '_>) -> Result<Self, form::Errors<'v>> {
+        Self::from_data(f.request, f.data).await.map_err(|e| {
+            match e {
+                Error::Io(e) => e.into(),
+                _ => form::Error::custom(e).into(),
+            }
+        })
+    }
+}
+
+impl<T> From<T> for Cbor<T> {
+    fn from(value: T) -> Self {
+        Cbor(value)
+    }
+}
+
+impl<T> Deref for Cbor<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Cbor<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+/// Deserialize an instance of type `T` from CBOR encoded bytes.
+///
+/// **_Always_ use [`Cbor`] to deserialize CBOR request data.**
+///
+/// # Example
+///
+/// ```
+/// use rocket::serde::{Deserialize, cbor};
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// #[serde(crate = "rocket::serde")]
+/// struct Data {
+///     framework: String,
+///     stars: usize,
+/// }
+///
+/// let bytes = &[
+///     162, 105, 102, 114, 97, 109, 101, 119, 111, 114, 107, 102, 82, 111,
+///     99, 107, 101, 116, 101, 115, 116, 97, 114, 115, 5
+/// ];
+///
+/// let data: Data = cbor::from_slice(bytes).unwrap();
+/// assert_eq!(data, Data { framework: "Rocket".into(), stars: 5, });
+/// ```
+///
+/// # Errors
+///
+/// Deserialization fails if `v` does not represent a valid CBOR encoding of
+/// any instance of `T` or if `T`'s `Deserialize` implementation fails
+/// otherwise.
+#[inline(always)]
+pub fn from_slice<T>(v: &[u8]) -> Result<T, Error>
+    where T: DeserializeOwned,
+{
+    // Reading from a slice only fails at its end: the encoding is truncated.
+    ciborium::from_reader(v).map_err(|e| match e {
+        Error::Io(_) => Error::Syntax(v.len()),
+        e => e,
+    })
+}
+
+/// Serialize a `T` into a CBOR byte vector.
+///
+/// **_Always_ use [`Cbor`] to serialize CBOR response data.**
+///
+/// # Example
+///
+/// ```
+/// use rocket::serde::{Deserialize, Serialize, cbor};
+///
+/// #[derive(Deserialize, Serialize)]
+/// #[serde(crate = "rocket::serde")]
+/// struct Data {
+///     framework: String,
+///     stars: usize,
+/// }
+///
+/// let bytes = &[
+///     162, 105, 102, 114, 97, 109, 101, 119, 111, 114, 107, 102, 82, 111,
+///     99, 107, 101, 116, 101, 115, 116, 97, 114, 115, 5
+/// ];
+///
+/// let data: Data = cbor::from_slice(bytes).unwrap();
+/// let byte_vec = cbor::to_vec(&data).unwrap();
+/// assert_eq!(bytes, &byte_vec[..]);
+/// ```
+///
+/// # Errors
+///
+/// Serialization fails if `T`'s `Serialize` implementation fails.
+#[inline(always)]
+pub fn to_vec<T>(value: &T) -> Result<Vec<u8>, ciborium::ser::Error<io::Error>>
+    where T: Serialize + ?Sized
+{
+    let mut buf = vec![];
+    ciborium::into_writer(value, &mut buf)?;
+    Ok(buf)
+}
@@ -26,15 +26,21 @@
 
 use std::{io, fmt, error};
 use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::stream::{Stream, StreamExt, BoxStream};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
 
 use crate::request::{Request, local_cache};
-use crate::data::{Limits, Data, FromData, Outcome};
-use crate::response::{self, Responder, content};
+use crate::data::{Limits, Data, DataStream, FromData, Outcome};
+use crate::response::{self, Responder, Response, content};
+use crate::response::stream::ReaderStream;
 use crate::form::prelude as form;
 use crate::http::uri::fmt::{UriDisplay, FromUriParam, Query, Formatter as UriFormatter};
-use crate::http::Status;
+use crate::http::{ContentType, Status};
 
-use serde::{Serialize, Deserialize};
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
 
 #[doc(hidden)]
 pub use serde_json;
@@ -295,6 +301,210 @@ impl<'v, T: Deserialize<'v> + Send> form::FromFormField<'v> for Json<T> {
     }
 }
 
+/// A stream of JSON values, one per line: [JSON Lines] (or NDJSON).
+///
+/// `JsonLines` is both a streaming responder and a streaming data guard. In
+/// either direction, values are (de)serialized one line at a time, so neither
+/// the response nor the request body is ever buffered in its entirety.
+///
+/// [JSON Lines]: https://jsonlines.org
+///
+/// ## Sending JSON Lines
+///
+/// A `JsonLines<S>` where `S` is a [`Stream`] of items implementing
+/// [`Serialize`] responds with each item serialized as JSON on its own line,
+/// sending each line as soon as the stream yields its item. The response
+/// `Content-Type` is set to [`NDJSON`](crate::http::ContentType::NDJSON) and
+/// the body is [unsized](crate::response::Body#unsized). If an item fails to
+/// serialize, the error is logged and the response ends.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # type User = usize;
+/// use rocket::futures::stream::{self, Stream};
+/// use rocket::serde::json::JsonLines;
+///
+/// #[get("/users")]
+/// fn export() -> JsonLines<impl Stream<Item = User>> {
+///     JsonLines(stream::iter(0..1000))
+/// }
+/// ```
+///
+/// ## Receiving JSON Lines
+///
+/// As a data guard, a `JsonLines<JsonLinesStream<'r, T>>` incrementally
+/// deserializes the request body into a [`Stream`] of `io::Result<T>`s, one
+/// per non-empty line. If a line fails to deserialize or the body can't be
+/// read, the stream yields an `Err` with the error and then ends, so a handler
+/// can distinguish a truncated body from a complete one.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # type User = usize;
+/// use std::io;
+///
+/// use rocket::futures::stream::TryStreamExt;
+/// use rocket::serde::json::{JsonLines, JsonLinesStream};
+///
+/// #[post("/users", format = "ndjson", data = "<users>")]
+/// async fn import(users: JsonLines<JsonLinesStream<'_, User>>) -> io::Result<String> {
+///     let users: Vec<User> = users.into_inner().try_collect().await?;
+///     Ok(format!("imported {} users", users.len()))
+/// }
+/// ```
+///
+/// ### Incoming Data Limits
+///
+/// The entire body is limited by the `json-lines` limit, 8MiB by default,
+/// while each line is limited by the `json` limit, 1MiB by default. Exceeding
+/// either ends the stream with an error of kind
+/// [`InvalidData`](std::io::ErrorKind::InvalidData).
+///
+/// [`Stream`]: https://docs.rs/futures/0.3/futures/stream/trait.Stream.html
+#[derive(Debug, Clone)]
+pub struct JsonLines<S>(pub S);
+
+/// The [`Stream`] of values deserialized by the [`JsonLines`] data guard.
+///
+/// The stream yields an `Ok` for each value and ends after yielding the first
+/// `Err`, if any.
+///
+/// [`Stream`]: https://docs.rs/futures/0.3/futures/stream/trait.Stream.html
+pub struct JsonLinesStream<'r, T> {
+    inner: BoxStream<'r, io::Result<T>>,
+}
+
+impl<S> JsonLines<S> {
+    /// Consumes the wrapper and returns the wrapped stream.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use rocket::serde::json::JsonLines;
+    /// use rocket::futures::stream;
+    ///
+    /// let lines = JsonLines(stream::iter(vec![1, 2, 3]));
+    /// let stream = lines.into_inner();
+    /// ```
+    #[inline(always)]
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+impl<S> From<S> for JsonLines<S> {
+    /// Creates a `JsonLines` from any `S: Stream`.
+    fn from(stream: S) -> Self {
+        JsonLines(stream)
+    }
+}
+
+impl<'r, T: DeserializeOwned + Send + 'r> JsonLinesStream<'r, T> {
+    fn new(data: DataStream<'r>, limit: u64, line_limit: u64) -> Self {
+        struct State<'r> {
+            reader: BufReader<DataStream<'r>>,
+            line: Vec<u8>,
+            read: u64,
+        }
+
+        async fn next<T: DeserializeOwned>(
+            state: &mut State<'_>,
+            limit: u64,
+            line_limit: u64,
+        ) -> io::Result<Option<T>> {
+            let invalid = |e: Box<dyn error::Error + Send + Sync>| {
+                io::Error::new(io::ErrorKind::InvalidData, e)
+            };
+            loop {
+                state.line.clear();
+                let n = (&mut state.reader)
+                    .take(line_limit + 1)
+                    .read_until(b'\n', &mut state.line)
+                    .await?;
+
+                state.read += n as u64;
+                if n == 0 {
+                    return Ok(None);
+                } else if state.read > limit {
+                    return Err(invalid("`json-lines` data limit exceeded".into()));
+                } else if n as u64 > line_limit && state.line.last() != Some(&b'\n') {
+                    return Err(invalid("`json` line limit exceeded".into()));
+                } else if state.line.iter().all(|b| b.is_ascii_whitespace()) {
+                    continue;
+                }
+
+                return serde_json::from_slice(&state.line).map(Some).map_err(|e| invalid(e.into()));
+            }
+        }
+
+        let state = State { reader: BufReader::new(data), line: vec![], read: 0 };
+        let stream = futures::stream::unfold(Some(state), move |state| async move {
+            let mut state = state?;
+            match next(&mut state, limit, line_limit).await {
+                Ok(Some(value)) => Some((Ok(value), Some(state))),
+                Ok(None) => None,
+                Err(e) => Some((Err(e), None)),
+            }
+        });
+
+        JsonLinesStream { inner: Box::pin(stream) }
+    }
+}
+
+impl<T> Stream for JsonLinesStream<'_, T> {
+    type Item = io::Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+impl<T> fmt::Debug for JsonLinesStream<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsonLinesStream").finish()
+    }
+}
+
+#[crate::async_trait]
+impl<'r, T: DeserializeOwned + Send + 'r> FromData<'r> for JsonLines<JsonLinesStream<'r, T>> {
+    type Error = std::convert::Infallible;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+        let limit = req.limits().get("json-lines").unwrap_or(Limits::JSON_LINES);
+        let line_limit = req.limits().get("json").unwrap_or(Limits::JSON);
+        let data = data.open(limit + 1);
+        let stream = JsonLinesStream::new(data, limit.as_u64(), line_limit.as_u64());
+        Outcome::Success(JsonLines(stream))
+    }
+}
+
+/// Serializes each item of the stream into JSON on its own line. Returns a
+/// response with Content-Type NDJSON and a streaming body. If serialization of
+/// an item fails, the error is logged and the body ends.
+impl<'r, S: Stream> Responder<'r, 'r> for JsonLines<S>
+    where S: Send + 'r, S::Item: Serialize
+{
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'r> {
+        let lines = self.0
+            .map(|item| match serde_json::to_vec(&item) {
+                Ok(mut line) => {
+                    line.push(b'\n');
+                    Some(io::Cursor::new(line))
+                }
+                Err(e) => {
+                    error_!("JSON Lines failed to serialize: {:?}", e);
+                    None
+                }
+            })
+            .take_while(|line| futures::future::ready(line.is_some()))
+            .filter_map(futures::future::ready);
+
+        Response::build()
+            .header(ContentType::NDJSON)
+            .streamed_body(ReaderStream::from(lines))
+            .ok()
+    }
+}
+
 /// Serializes the value into JSON. Returns a response with Content-Type JSON
 /// and a fixed-size body with the serialized value.
 impl<'r> Responder<'r, 'static> for Value {
@@ -2,6 +2,8 @@
 //!
 //! * JSON support is provided by the [`Json`](json::Json) type.
 //! * MessagePack support is provided by the [`MsgPack`](msgpack::MsgPack) type.
+//! * CBOR support is provided by the [`Cbor`](cbor::Cbor) type.
+//! * XML support is provided by the [`Xml`](xml::Xml) type.
 //! * UUID support is provided by the [`UUID`](uuid) type.
 //!
 //! Types implement one or all of [`FromParam`](crate::request::FromParam),
@@ -50,6 +52,16 @@ pub mod json;
 #[cfg_attr(nightly, doc(cfg(feature = "msgpack")))]
 pub mod msgpack;
 
+#[cfg(feature = "cbor")]
+#[cfg_attr(nightly, doc(cfg(feature = "cbor")))]
+pub mod cbor;
+
+#[cfg(feature = "xml")]
+#[cfg_attr(nightly, doc(cfg(feature = "xml")))]
+pub mod xml;
+
+pub mod negotiate;
+
 #[cfg(feature = "uuid")]
 #[cfg_attr(nightly, doc(cfg(feature = "uuid")))]
 pub mod uuid;
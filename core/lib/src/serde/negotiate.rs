@@ -0,0 +1,377 @@
+//! Content-negotiated serialization of responses.
+//!
+//! See [`Negotiate`] for further details.
+//!
+//! # Formats
+//!
+//! A [`Format`] serializes values in one media type. Rocket provides
+//! [`JsonFormat`], [`MsgPackFormat`], [`XmlFormat`], and [`CborFormat`],
+//! available when the `json`, `msgpack`, `xml`, and `cbor` features,
+//! respectively, are enabled. These
+//! enabled formats are the [`Builtin`] formats used by `Negotiate` by default.
+//! Implement [`Format`] to serialize into another media type and choose the
+//! formats, in order of preference, via a tuple:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! # #[cfg(feature = "json")] mod example {
+//! use rocket::http::MediaType;
+//! use rocket::serde::Serialize;
+//! use rocket::serde::negotiate::{Negotiate, Format, JsonFormat, Error};
+//!
+//! /// Serializes values with their `Debug` rendering.
+//! struct DebugFormat;
+//!
+//! impl Format for DebugFormat {
+//!     fn media_type() -> MediaType {
+//!         MediaType::Plain
+//!     }
+//!
+//!     fn serialize<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, Error> {
+//!         let value = rocket::serde::json::to_value(value)?;
+//!         Ok(format!("{:#?}", value).into_bytes())
+//!     }
+//! }
+//!
+//! #[get("/")]
+//! fn index() -> Negotiate<Vec<usize>, (JsonFormat, DebugFormat)> {
+//!     Negotiate::new(vec![1, 2, 3])
+//! }
+//! # }
+//! ```
+
+use std::io::Cursor;
+use std::marker::PhantomData;
+
+use serde::Serialize;
+
+use crate::request::Request;
+use crate::response::{self, Responder, Response};
+use crate::http::{Accept, ContentType, MediaType, Status};
+
+/// An error serializing a value in a [`Format`].
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// A responder that serializes a value in the format the client prefers.
+///
+/// A `Negotiate<T, F>` serializes its `T` in the [`Format`] among the formats
+/// `F`, by default the [`Builtin`] formats, that the request's `Accept` header
+/// prefers. The response's `Content-Type` is set to the chosen format's media
+/// type and its `Vary` header to `Accept`. If the request has no `Accept`
+/// header, the first format is chosen. If the client accepts none of the
+/// formats, the response fails with `406 Not Acceptable`. If serialization
+/// fails, the response fails with `500 Internal Server Error`.
+///
+/// The client's preference for a format is the quality value of the most
+/// specific media range in `Accept` matching the format's media type, where
+/// `type/subtype` is more specific than `type/*`, which is more specific than
+/// `*/*`. Formats are chosen by highest preference, with ties broken by the
+/// order of `F`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::serde::Serialize;
+/// use rocket::serde::negotiate::Negotiate;
+///
+/// #[derive(Serialize)]
+/// # #[serde(crate = "rocket::serde")]
+/// struct User {
+///     id: usize,
+///     name: String,
+/// }
+///
+/// #[get("/users/<id>")]
+/// fn user(id: usize) -> Negotiate<User> {
+///     Negotiate::new(User { id, name: "Bob".into() })
+/// }
+/// ```
+pub struct Negotiate<T, F = Builtin> {
+    value: T,
+    _formats: PhantomData<fn() -> F>,
+}
+
+/// A serialization format that a [`Negotiate`] responder can choose.
+pub trait Format {
+    /// The media type of serialized values.
+    fn media_type() -> MediaType;
+
+    /// Serializes `value`.
+    fn serialize<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, Error>;
+}
+
+/// A list of [`Format`]s, in order of preference.
+///
+/// This trait is implemented for every `Format` and for tuples of up to eight
+/// `Format`s.
+pub trait Formats {
+    /// The media types of the formats.
+    fn media_types() -> Vec<MediaType>;
+
+    /// Serializes `value` in the format at `index`.
+    fn serialize<T: Serialize + ?Sized>(index: usize, value: &T) -> Result<Vec<u8>, Error>;
+}
+
+/// The formats enabled via features: [`JsonFormat`], [`MsgPackFormat`],
+/// [`XmlFormat`], and [`CborFormat`], in that order of preference.
+pub struct Builtin(());
+
+/// JSON serialization via [`serde_json`](crate::serde::json::serde_json).
+#[cfg(feature = "json")]
+#[cfg_attr(nightly, doc(cfg(feature = "json")))]
+pub struct JsonFormat(());
+
+/// MessagePack serialization via
+/// [`rmp_serde`](https://docs.rs/rmp-serde), encoding structs as maps.
+#[cfg(feature = "msgpack")]
+#[cfg_attr(nightly, doc(cfg(feature = "msgpack")))]
+pub struct MsgPackFormat(());
+
+/// XML serialization via [`xml::to_string()`](crate::serde::xml::to_string()).
+/// The root element is named after the serialized type, so values must be
+/// structs or enums.
+#[cfg(feature = "xml")]
+#[cfg_attr(nightly, doc(cfg(feature = "xml")))]
+pub struct XmlFormat(());
+
+/// CBOR serialization via [`ciborium`](https://docs.rs/ciborium).
+#[cfg(feature = "cbor")]
+#[cfg_attr(nightly, doc(cfg(feature = "cbor")))]
+pub struct CborFormat(());
+
+impl<T, F> Negotiate<T, F> {
+    /// Returns a responder that serializes `value` in one of the formats `F`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::serde::negotiate::Negotiate;
+    ///
+    /// let response: Negotiate<_> = Negotiate::new(vec![1, 2, 3]);
+    /// ```
+    pub fn new(value: T) -> Self {
+        Negotiate { value, _formats: PhantomData }
+    }
+
+    /// Consumes `self` and returns the inner value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::serde::negotiate::Negotiate;
+    ///
+    /// let response: Negotiate<_> = Negotiate::new("hello");
+    /// assert_eq!(response.into_inner(), "hello");
+    /// ```
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, F> From<T> for Negotiate<T, F> {
+    fn from(value: T) -> Self {
+        Negotiate::new(value)
+    }
+}
+
+/// Returns the index of the media type in `offered` that `accept` prefers.
+fn select(accept: Option<&Accept>, offered: &[MediaType]) -> Option<usize> {
+    let accept = match accept {
+        Some(accept) => accept,
+        None => return (!offered.is_empty()).then(|| 0),
+    };
+
+    let mut best: Option<(usize, f32)> = None;
+    for (i, media_type) in offered.iter().enumerate() {
+        let quality = quality(accept, media_type);
+        if quality > 0.0 && best.map_or(true, |(_, q)| quality > q) {
+            best = Some((i, quality));
+        }
+    }
+
+    best.map(|(i, _)| i)
+}
+
+/// Returns the quality value of the most specific range in `accept` that
+/// matches `media_type`, or `0` if none does.
+fn quality(accept: &Accept, media_type: &MediaType) -> f32 {
+    accept.iter()
+        .filter_map(|item| {
+            let range = item.media_type();
+            let specificity = match (range.top() == "*", range.sub() == "*") {
+                (true, _) => 0,
+                (false, true) if range.top() == media_type.top() => 1,
+                (false, false) if range == media_type => 2,
+                _ => return None,
+            };
+
+            Some((specificity, item.weight_or(1.0)))
+        })
+        .fold(None, |best: Option<(u8, f32)>, (s, q)| match best {
+            Some((best_s, _)) if best_s >= s => best,
+            _ => Some((s, q)),
+        })
+        .map_or(0.0, |(_, q)| q)
+}
+
+impl<'r, T: Serialize, F: Formats> Responder<'r, 'static> for Negotiate<T, F> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let offered = F::media_types();
+        let index = select(req.accept(), &offered).ok_or_else(|| {
+            let offered = offered.iter().map(|m| m.to_string()).collect::<Vec<_>>();
+            warn_!("No acceptable format: offered {}.", offered.join(", "));
+            Status::NotAcceptable
+        })?;
+
+        let media_type = &offered[index];
+        let body = F::serialize(index, &self.value).map_err(|e| {
+            error_!("Failed to serialize {}: {}", media_type, e);
+            Status::InternalServerError
+        })?;
+
+        Response::build()
+            .header(ContentType(media_type.clone()))
+            .raw_header_adjoin("Vary", "Accept")
+            .sized_body(body.len(), Cursor::new(body))
+            .ok()
+    }
+}
+
+impl<F: Format> Formats for F {
+    fn media_types() -> Vec<MediaType> {
+        vec![F::media_type()]
+    }
+
+    fn serialize<T: Serialize + ?Sized>(_: usize, value: &T) -> Result<Vec<u8>, Error> {
+        F::serialize(value)
+    }
+}
+
+macro_rules! impl_formats_for_tuple {
+    ($($F:ident : $i:tt),+) => {
+        impl<$($F: Format),+> Formats for ($($F,)+) {
+            fn media_types() -> Vec<MediaType> {
+                vec![$($F::media_type()),+]
+            }
+
+            fn serialize<T: Serialize + ?Sized>(index: usize, value: &T) -> Result<Vec<u8>, Error> {
+                match index {
+                    $($i => $F::serialize(value),)+
+                    _ => Err(format!("no format at index {}", index).into()),
+                }
+            }
+        }
+    };
+}
+
+impl_formats_for_tuple!(A: 0);
+impl_formats_for_tuple!(A: 0, B: 1);
+impl_formats_for_tuple!(A: 0, B: 1, C: 2);
+impl_formats_for_tuple!(A: 0, B: 1, C: 2, D: 3);
+impl_formats_for_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4);
+impl_formats_for_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, G: 5);
+impl_formats_for_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, G: 5, H: 6);
+impl_formats_for_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, G: 5, H: 6, I: 7);
+
+type Serializer<T> = fn(&T) -> Result<Vec<u8>, Error>;
+
+impl Builtin {
+    fn formats<T: Serialize + ?Sized>() -> Vec<(MediaType, Serializer<T>)> {
+        vec![
+            #[cfg(feature = "json")]
+            (JsonFormat::media_type(), <JsonFormat as Format>::serialize::<T>),
+            #[cfg(feature = "msgpack")]
+            (MsgPackFormat::media_type(), <MsgPackFormat as Format>::serialize::<T>),
+            #[cfg(feature = "xml")]
+            (XmlFormat::media_type(), <XmlFormat as Format>::serialize::<T>),
+            #[cfg(feature = "cbor")]
+            (CborFormat::media_type(), <CborFormat as Format>::serialize::<T>),
+        ]
+    }
+}
+
+impl Formats for Builtin {
+    fn media_types() -> Vec<MediaType> {
+        Builtin::formats::<()>().into_iter().map(|(media_type, _)| media_type).collect()
+    }
+
+    fn serialize<T: Serialize + ?Sized>(index: usize, value: &T) -> Result<Vec<u8>, Error> {
+        match Builtin::formats::<T>().get(index) {
+            Some((_, serialize)) => serialize(value),
+            None => Err(format!("no format at index {}", index).into()),
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl Format for JsonFormat {
+    fn media_type() -> MediaType {
+        MediaType::JSON
+    }
+
+    fn serialize<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, Error> {
+        Ok(serde_json::to_vec(value)?)
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl Format for MsgPackFormat {
+    fn media_type() -> MediaType {
+        MediaType::MsgPack
+    }
+
+    fn serialize<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, Error> {
+        Ok(rmp_serde::to_vec_named(value)?)
+    }
+}
+
+#[cfg(feature = "xml")]
+impl Format for XmlFormat {
+    fn media_type() -> MediaType {
+        MediaType::XML
+    }
+
+    fn serialize<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, Error> {
+        Ok(crate::serde::xml::to_string(value)?.into_bytes())
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl Format for CborFormat {
+    fn media_type() -> MediaType {
+        MediaType::CBOR
+    }
+
+    fn serialize<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, Error> {
+        Ok(crate::serde::cbor::to_vec(value)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn select(accept: &str) -> Option<usize> {
+        let offered = [MediaType::JSON, MediaType::MsgPack, MediaType::XML];
+        super::select(Some(&accept.parse().unwrap()), &offered)
+    }
+
+    #[test]
+    fn test_select() {
+        assert_eq!(super::select(None, &[MediaType::JSON]), Some(0));
+        assert_eq!(super::select(None, &[]), None);
+
+        assert_eq!(select("*/*"), Some(0));
+        assert_eq!(select("text/xml"), Some(2));
+        assert_eq!(select("text/*"), Some(2));
+        assert_eq!(select("application/*"), Some(0));
+        assert_eq!(select("application/msgpack, application/json"), Some(0));
+        assert_eq!(select("application/json; q=0.5, application/msgpack"), Some(1));
+        assert_eq!(select("text/xml, */*; q=0.1"), Some(2));
+        assert_eq!(select("application/*; q=0.5, application/msgpack; q=0.9"), Some(1));
+        assert_eq!(select("*/*, application/json; q=0"), Some(1));
+        assert_eq!(select("text/html"), None);
+        assert_eq!(select("text/html, */*; q=0"), None);
+    }
+}
@@ -1,27 +1,82 @@
 use std::io;
+use std::any::Any;
+use std::pin::Pin;
 use std::sync::Arc;
 use std::time::Duration;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use yansi::Paint;
 use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
 use tokio::time::sleep;
 use futures::stream::StreamExt;
 use futures::future::{FutureExt, Future, BoxFuture};
 
 use crate::{route, Rocket, Orbit, Request, Response, Data, Config};
+use crate::data::IoHandler;
+use crate::fairing::ErrorInfo;
 use crate::form::Form;
 use crate::outcome::Outcome;
 use crate::error::{Error, ErrorKind};
-use crate::ext::{AsyncReadExt, CancellableListener, CancellableIo};
+use crate::ext::{AsyncReadExt, CancellableListener, CancellableIo, LimitedListener, Limited};
 use crate::request::ConnectionMeta;
 
-use crate::http::{hyper, Method, Status, Header};
-use crate::http::private::{TcpListener, Listener, Connection, Incoming};
+use crate::http::{hyper, uncased, Method, Status, Header};
+use crate::http::private::{TcpListener, Listener, Connection, Incoming, ProxyListener};
+
+#[cfg(unix)]
+use std::path::PathBuf;
+#[cfg(unix)]
+use crate::config::{Inherited, UnixConfig};
+#[cfg(unix)]
+use crate::http::private::UnixListener;
+
+/// An additional listener that has been bound but isn't yet being served.
+enum Bound {
+    Tcp(ProxyListener<TcpListener>),
+    #[cfg(feature = "tls")]
+    Tls(crate::http::tls::TlsListener<ProxyListener<TcpListener>>),
+    #[cfg(unix)]
+    Unix(ProxyListener<UnixListener>, SocketFile),
+}
+
+/// Removes the Unix domain socket file at the path, if any, when dropped.
+#[cfg(unix)]
+struct SocketFile(Option<PathBuf>);
+
+#[cfg(unix)]
+impl Drop for SocketFile {
+    fn drop(&mut self) {
+        if let Some(ref path) = self.0 {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// A slot counted against `max_requests`, released when dropped.
+struct RequestSlot<'a>(&'a AtomicUsize);
+
+impl<'a> RequestSlot<'a> {
+    /// Acquires a slot unless `max_requests` are already being processed.
+    fn acquire(rocket: &'a Rocket<Orbit>) -> Option<Self> {
+        let max = rocket.config.max_requests.unwrap_or(usize::MAX);
+        rocket.requests
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| (n < max).then(|| n + 1))
+            .ok()
+            .map(|_| RequestSlot(&rocket.requests))
+    }
+}
+
+impl Drop for RequestSlot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
 
 // A token returned to force the execution of one method before another.
 pub(crate) struct RequestToken;
 
-async fn handle<Fut, T, F>(name: Option<&str>, run: F) -> Option<T>
+async fn handle<Fut, T, F>(name: Option<&str>, run: F) -> Result<T, Box<dyn Any + Send>>
     where F: FnOnce() -> Fut, Fut: Future<Output = T>,
 {
     use std::panic::AssertUnwindSafe;
@@ -47,14 +102,23 @@ async fn handle<Fut, T, F>(name: Option<&str>, run: F) -> Option<T>
 
     let run = AssertUnwindSafe(run);
     let fut = std::panic::catch_unwind(move || run())
-        .map_err(|e| panic_info!(name, e))
-        .ok()?;
+        .map_err(|e| panic_info!(name, e))?;
 
     AssertUnwindSafe(fut)
         .catch_unwind()
         .await
         .map_err(|e| panic_info!(name, e))
-        .ok()
+}
+
+/// Returns an error describing a handler's panic with payload `payload`.
+fn panic_error(payload: &(dyn Any + Send)) -> Box<dyn std::error::Error + Send + Sync> {
+    let message = payload.downcast_ref::<&str>().copied()
+        .or_else(|| payload.downcast_ref::<String>().map(|s| s.as_str()));
+
+    match message {
+        Some(message) => format!("handler panicked: {}", message).into(),
+        None => "handler panicked".into(),
+    }
 }
 
 // This function tries to hide all of the Hyper-ness from Rocket. It essentially
@@ -64,7 +128,7 @@ async fn handle<Fut, T, F>(name: Option<&str>, run: F) -> Option<T>
 async fn hyper_service_fn(
     rocket: Arc<Rocket<Orbit>>,
     conn: ConnectionMeta,
-    hyp_req: hyper::Request<hyper::Body>,
+    mut hyp_req: hyper::Request<hyper::Body>,
 ) -> Result<hyper::Response<hyper::Body>, io::Error> {
     // This future must return a hyper::Response, but the response body might
     // borrow from the request. Instead, write the body in another future that
@@ -72,21 +136,46 @@ async fn hyper_service_fn(
     let (tx, rx) = oneshot::channel();
 
     tokio::spawn(async move {
+        // Retrieve the pending upgrade, if any, before the request is split.
+        let hyp_upgraded = hyper::upgrade::on(&mut hyp_req);
+
         // Convert a Hyper request into a Rocket request.
         let (h_parts, mut h_body) = hyp_req.into_parts();
         match Request::from_hyp(&rocket, &h_parts, Some(conn)) {
             Ok(mut req) => {
+                let _slot = match RequestSlot::acquire(&rocket) {
+                    Some(slot) => slot,
+                    None => {
+                        warn!("Request limit reached. Refusing request {}.", req);
+                        let response = rocket.handle_error(Status::ServiceUnavailable, &req).await;
+                        rocket.send_response(response, tx).await;
+                        return;
+                    }
+                };
+
                 // Convert into Rocket `Data`, dispatch request, write response.
                 let mut data = Data::from(&mut h_body);
                 let token = rocket.preprocess_request(&mut req, &mut data).await;
-                let response = rocket.dispatch(token, &mut req, data).await;
-                rocket.send_response(response, tx).await;
+                let mut response = rocket.dispatch(token, &req, data).await;
+                let upgrade = response.take_upgrade(req.headers().get("upgrade"));
+                if let Ok(Some((proto, handler))) = upgrade {
+                    rocket.handle_upgrade(response, proto, handler, hyp_upgraded, tx).await;
+                } else {
+                    if upgrade.is_err() {
+                        warn_!("Request wants upgrade but no I/O handler matched.");
+                        info_!("Request is not being upgraded.");
+                    }
+
+                    rocket.send_response(response, tx).await;
+                }
             },
             Err(e) => {
                 warn!("Bad incoming HTTP request.");
                 e.errors.iter().for_each(|e| warn_!("Error: {}.", e));
                 warn_!("Dispatching salvaged request to catcher: {}.", e.request);
 
+                let errors = e.errors.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+                e.request.record_error(format!("bad request: {}", errors.join("; ")).into());
                 let response = rocket.handle_error(Status::BadRequest, &e.request).await;
                 rocket.send_response(response, tx).await;
             }
@@ -97,6 +186,53 @@ async fn hyper_service_fn(
     rx.await.map_err(|e| io::Error::new(io::ErrorKind::BrokenPipe, e))
 }
 
+/// Sends `size` bytes of `file`, or all of it if `size` is `None`, to `sender`
+/// in chunks of at most `max_chunk` bytes.
+///
+/// Each chunk is read from the file directly into the buffer that is sent, on
+/// the blocking pool, while the previous chunk is being written. `hyper` then
+/// writes the buffers out as-is, with vectored writes on plaintext HTTP/1
+/// connections, so the data is copied exactly once in userspace.
+async fn send_file(
+    file: std::fs::File,
+    size: Option<usize>,
+    max_chunk: usize,
+    sender: &mut hyper::body::Sender,
+) -> io::Result<()> {
+    type Chunk = io::Result<(std::fs::File, Vec<u8>)>;
+
+    fn read_chunk(mut file: std::fs::File, len: usize) -> JoinHandle<Chunk> {
+        tokio::task::spawn_blocking(move || {
+            use std::io::Read;
+
+            let mut chunk = Vec::with_capacity(len);
+            (&mut file).take(len as u64).read_to_end(&mut chunk)?;
+            Ok((file, chunk))
+        })
+    }
+
+    let mut remaining = size.unwrap_or(usize::MAX);
+    let mut next = Some(read_chunk(file, max_chunk.min(remaining)));
+    while let Some(reading) = next.take() {
+        let (file, chunk) = reading.await
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))??;
+
+        if chunk.is_empty() {
+            break;
+        }
+
+        remaining -= chunk.len();
+        if remaining > 0 {
+            next = Some(read_chunk(file, max_chunk.min(remaining)));
+        }
+
+        sender.send_data(chunk.into()).await
+            .map_err(|e| io::Error::new(io::ErrorKind::BrokenPipe, e))?;
+    }
+
+    Ok(())
+}
+
 impl Rocket<Orbit> {
     /// Wrapper around `_send_response` to log a success or failure.
     #[inline]
@@ -135,6 +271,13 @@ impl Rocket<Orbit> {
             hyp_res = hyp_res.header(name, value);
         }
 
+        // Advertise the HTTP/3 server, which listens on the same port.
+        #[cfg(feature = "http3")]
+        if self.config.tls_enabled() && !response.headers().contains("Alt-Svc") {
+            let alt_svc = format!("h3=\":{}\"; ma=86400", self.config.port);
+            hyp_res = hyp_res.header("Alt-Svc", alt_svc);
+        }
+
         let body = response.body_mut();
         if let Some(n) = body.size().await {
             hyp_res = hyp_res.header(hyper::header::CONTENT_LENGTH, n);
@@ -149,7 +292,11 @@ impl Rocket<Orbit> {
             io::Error::new(io::ErrorKind::BrokenPipe, msg)
         })?;
 
-        let max_chunk_size = body.max_chunk_size();
+        let (size, max_chunk_size) = (body.preset_size(), body.max_chunk_size());
+        if let Some(file) = body.take_file() {
+            return send_file(file.into_std().await, size, max_chunk_size, &mut sender).await;
+        }
+
         let mut stream = body.into_bytes_stream(max_chunk_size);
         while let Some(next) = stream.next().await {
             sender.send_data(next?).await
@@ -159,6 +306,40 @@ impl Rocket<Orbit> {
         Ok(())
     }
 
+    /// Sends `response` with a status of `101 Switching Protocols`, waits for
+    /// the connection to be upgraded, then hands it to `io_handler`.
+    async fn handle_upgrade<'r>(
+        &self,
+        mut response: Response<'r>,
+        proto: uncased::Uncased<'r>,
+        io_handler: Pin<Box<dyn IoHandler + 'r>>,
+        pending_upgrade: hyper::upgrade::OnUpgrade,
+        tx: oneshot::Sender<hyper::Response<hyper::Body>>,
+    ) {
+        info_!("Upgrading connection to {}.", Paint::white(&proto).bold());
+        response.set_status(Status::SwitchingProtocols);
+        response.set_raw_header("Connection", "Upgrade");
+        response.set_raw_header("Upgrade", proto.clone().into_cow());
+        self.send_response(response, tx).await;
+
+        match pending_upgrade.await {
+            Ok(io_stream) => {
+                info_!("Upgrade successful.");
+                if let Err(e) = io_handler.io(io_stream.into()).await {
+                    if e.kind() == io::ErrorKind::BrokenPipe {
+                        warn!("Upgraded {} I/O handler was closed.", proto);
+                    } else {
+                        error!("Upgraded {} I/O handler failed: {}", proto, e);
+                    }
+                }
+            },
+            Err(e) => {
+                warn!("Response indicated upgrade, but upgrade failed.");
+                warn_!("Upgrade error: {}", e);
+            }
+        }
+    }
+
     /// Preprocess the request for Rocket things. Currently, this means:
     ///
     ///   * Rewriting the method in the request if _method form field exists.
@@ -170,6 +351,10 @@ impl Rocket<Orbit> {
         req: &mut Request<'_>,
         data: &mut Data<'_>
     ) -> RequestToken {
+        // Decompress the body so that both fairings and guards see plain data.
+        #[cfg(feature = "compression")]
+        crate::compression::decompress_request(req, data);
+
         // Check if this is a form and if the form contains the special _method
         // field which we use to reinterpret the request's method.
         let (min_len, max_len) = ("_method=get".len(), "_method=delete".len());
@@ -188,6 +373,13 @@ impl Rocket<Orbit> {
         }
 
         // Run request fairings.
+        #[cfg(feature = "tracing")] {
+            req.start_span();
+            let span = req.span().clone();
+            tracing_::Instrument::instrument(self.fairings.handle_request(req, data), span).await;
+        }
+
+        #[cfg(not(feature = "tracing"))]
         self.fairings.handle_request(req, data).await;
 
         RequestToken
@@ -195,6 +387,27 @@ impl Rocket<Orbit> {
 
     #[inline]
     pub(crate) async fn dispatch<'s, 'r: 's>(
+        &'s self,
+        token: RequestToken,
+        request: &'r Request<'s>,
+        data: Data<'r>
+    ) -> Response<'r> {
+        #[cfg(feature = "tracing")] {
+            let span = request.span().clone();
+            let response = tracing_::Instrument::instrument(
+                self._dispatch(token, request, data),
+                span.clone()
+            ).await;
+
+            span.record("status", response.status().code);
+            response
+        }
+
+        #[cfg(not(feature = "tracing"))]
+        self._dispatch(token, request, data).await
+    }
+
+    async fn _dispatch<'s, 'r: 's>(
         &'s self,
         _token: RequestToken,
         request: &'r Request<'s>,
@@ -230,9 +443,15 @@ impl Rocket<Orbit> {
     async fn route_and_process<'s, 'r: 's>(
         &'s self,
         request: &'r Request<'s>,
-        data: Data<'r>
+        mut data: Data<'r>
     ) -> Response<'r> {
-        let mut response = match self.route(request, data).await {
+        let outcome = match self.fairings.handle_filter(request, &mut data).await {
+            Some(Ok(response)) => Outcome::Success(response),
+            Some(Err(status)) => Outcome::Failure(status),
+            None => self.route(request, data).await,
+        };
+
+        let mut response = match outcome {
             Outcome::Success(response) => response,
             Outcome::Forward(data) if request.method() == Method::Head => {
                 info_!("Autohandling {} request.", Paint::default("HEAD").bold());
@@ -271,14 +490,38 @@ impl Rocket<Orbit> {
         mut data: Data<'r>,
     ) -> route::Outcome<'r> {
         // Go through the list of matching routes until we fail or succeed.
-        for route in self.router.route(request) {
+        // Routes mounted after launch are tried after all others.
+        let hot = self.hot_router.get(request);
+        let hot_routes = hot.into_iter().flat_map(|router| router.route(request));
+        for route in self.router.route(request).chain(hot_routes) {
             // Retrieve and set the requests parameters.
             info_!("Matched: {}", route);
             request.set_route(route);
 
+            #[cfg(feature = "tracing")]
+            request.span().record("route", route.name.as_deref().unwrap_or("<unnamed>"));
+
             let name = route.name.as_deref();
-            let outcome = handle(name, || route.handler.handle(request, data)).await
-                .unwrap_or(Outcome::Failure(Status::InternalServerError));
+            let deadline = route.timeout.map(|timeout| std::time::Instant::now() + timeout);
+            request.set_deadline(deadline);
+
+            let handling = handle(name, || route.handle(request, data));
+            let outcome = match deadline {
+                Some(deadline) => tokio::time::timeout_at(deadline.into(), handling).await
+                    .unwrap_or_else(|_| {
+                        let timeout = route.timeout.unwrap();
+                        error_!("Handler timed out after {:?}.", timeout);
+                        let error = format!("handler timed out after {:?}", timeout);
+                        request.record_error(error.into());
+                        Ok(Outcome::Failure(Status::ServiceUnavailable))
+                    }),
+                None => handling.await,
+            };
+
+            let outcome = outcome.unwrap_or_else(|payload| {
+                request.record_error(panic_error(&*payload));
+                Outcome::Failure(Status::InternalServerError)
+            });
 
             // Check if the request processing completed (Some) or if the
             // request needs to be forwarded. If it does, continue the loop
@@ -319,7 +562,7 @@ impl Rocket<Orbit> {
             let name = catcher.name.as_deref();
             handle(name, || catcher.handler.handle(status, req)).await
                 .map(|result| result.map_err(Some))
-                .unwrap_or_else(|| Err(None))
+                .unwrap_or_else(|_| Err(None))
         } else {
             let code = Paint::blue(status.code).bold();
             warn_!("No {} catcher registered. Using Rocket default.", code);
@@ -336,6 +579,11 @@ impl Rocket<Orbit> {
         mut status: Status,
         req: &'r Request<'s>
     ) -> Response<'r> {
+        // Report the error to error fairings.
+        let error = req.recorded_error();
+        let info = ErrorInfo { status, route: req.route(), error: error.as_deref() };
+        self.fairings.handle_error(&info, req).await;
+
         // Dispatch to the `status` catcher.
         if let Ok(r) = self.invoke_catcher(status, req).await {
             return r;
@@ -360,11 +608,51 @@ impl Rocket<Orbit> {
     {
         use std::net::ToSocketAddrs;
 
-        // Determine the address we're going to serve on.
-        let addr = format!("{}:{}", self.config.address, self.config.port);
-        let mut addr = addr.to_socket_addrs()
-            .map(|mut addrs| addrs.next().expect(">= 1 socket addr"))
-            .map_err(|e| Error::new(ErrorKind::Io(e)))?;
+        // Bind the additional listeners first so that their socket files, if
+        // any, are removed should binding the primary listener fail.
+        let listeners = self.bind_listeners().await?;
+
+        // Determine the listener we're going to serve on: an inherited socket,
+        // a Unix domain socket, or a TCP socket bound to the configured address.
+        let l = match (self.config.listen_fd, self.config.unix.clone()) {
+            #[cfg(unix)]
+            (Some(fd), _) => match fd.listener().map_err(ErrorKind::Bind)? {
+                Inherited::Tcp(l) => l,
+                Inherited::Unix(l) => {
+                    self.reject_unix_tls().map_err(ErrorKind::Io)?;
+                    let path = l.local_addr().ok()
+                        .and_then(|addr| addr.as_pathname().map(|p| p.to_path_buf()))
+                        .unwrap_or_default();
+
+                    self.config.unix = Some(UnixConfig::new(path).remove(false));
+                    return self.default_unix_http_server(l, None, listeners, ready).await;
+                }
+            },
+            #[cfg(unix)]
+            (None, Some(unix)) => {
+                self.reject_unix_tls().map_err(ErrorKind::Io)?;
+                let l = unix.bind().map_err(ErrorKind::Bind)?;
+                let path = unix.remove.then(|| unix.path);
+                return self.default_unix_http_server(l, path, listeners, ready).await;
+            }
+            #[cfg(not(unix))]
+            (Some(_), _) | (None, Some(_)) => {
+                let msg = "inherited and Unix domain sockets are only supported on Unix";
+                let e = io::Error::new(io::ErrorKind::Unsupported, msg);
+                return Err(Error::new(ErrorKind::Bind(e)));
+            }
+            (None, None) => {
+                let addr = format!("{}:{}", self.config.address, self.config.port);
+                let addr = addr.to_socket_addrs()
+                    .map(|mut addrs| addrs.next().expect(">= 1 socket addr"))
+                    .map_err(|e| Error::new(ErrorKind::Io(e)))?;
+
+                TcpListener::bind(addr).await.map_err(ErrorKind::Bind)?
+            }
+        };
+
+        let addr = l.local_addr().map_err(ErrorKind::Bind)?;
+        let l = ProxyListener::new(l, self.config.proxy_protocol);
 
         #[cfg(feature = "tls")]
         if self.config.tls_enabled() {
@@ -372,26 +660,90 @@ impl Rocket<Orbit> {
                 use crate::http::tls::TlsListener;
 
                 let conf = config.to_native_config().map_err(ErrorKind::Io)?;
-                let l = TlsListener::bind(addr, conf).await.map_err(ErrorKind::Bind)?;
-                addr = l.local_addr().unwrap_or(addr);
+                let l = TlsListener::from_listener(l, conf).map_err(ErrorKind::Bind)?;
+
+                // Bind HTTP/3 to the same port, which may have been chosen by
+                // the OS, so that it can be advertised via `Alt-Svc`.
+                #[cfg(feature = "http3")]
+                let quic = {
+                    use crate::http::tls::QuicListener;
+
+                    let conf = config.to_native_config().map_err(ErrorKind::Io)?;
+                    QuicListener::bind(addr, conf).await.map_err(ErrorKind::Bind)?
+                };
+
+                #[allow(unused_mut)]
+                let mut resolvers = vec![l.cert_resolver()];
+                #[cfg(feature = "http3")]
+                resolvers.push(quic.cert_resolver());
+
+                #[cfg(unix)]
+                if config.reload_on_sighup {
+                    reload_tls_on_sighup(config.clone(), resolvers.clone());
+                }
+
                 self.config.address = addr.ip();
                 self.config.port = addr.port();
-                ready(&mut self).await;
-                return self.http_server(l).await;
+                self.tls_resolvers = resolvers;
+
+                #[cfg(feature = "http3")] {
+                    ready(&mut self).await;
+                    return self.serve(l, listeners, move |rocket| {
+                        tokio::spawn(rocket.http3_server(quic));
+                    }).await;
+                }
+
+                #[cfg(not(feature = "http3"))] {
+                    ready(&mut self).await;
+                    return self.serve(l, listeners, |_| ()).await;
+                }
             }
         }
 
-        let l = TcpListener::bind(addr).await.map_err(ErrorKind::Bind)?;
-        addr = l.local_addr().unwrap_or(addr);
         self.config.address = addr.ip();
         self.config.port = addr.port();
         ready(&mut self).await;
-        self.http_server(l).await
+        self.serve(l, listeners, |_| ()).await
     }
 
-    // TODO.async: Solidify the Listener APIs and make this function public
-    pub(crate) async fn http_server<L>(self, listener: L) -> Result<Self, Error>
-        where L: Listener + Send, <L as Listener>::Connection: Send + Unpin + 'static
+    /// Errors if TLS is enabled, which isn't supported over Unix domain sockets.
+    #[cfg(unix)]
+    fn reject_unix_tls(&self) -> io::Result<()> {
+        if self.config.tls_enabled() {
+            let msg = "TLS is not supported over Unix domain sockets";
+            return Err(io::Error::new(io::ErrorKind::Other, msg));
+        }
+
+        Ok(())
+    }
+
+    /// Serves plain HTTP on the Unix domain socket listener `l` and the
+    /// additional `listeners`, removing the socket file at `path`, if any,
+    /// afterwards.
+    #[cfg(unix)]
+    async fn default_unix_http_server<C>(
+        mut self,
+        l: UnixListener,
+        path: Option<PathBuf>,
+        listeners: Vec<Bound>,
+        ready: C
+    ) -> Result<Self, Error>
+        where C: for<'a> Fn(&'a Self) -> BoxFuture<'a, ()>
+    {
+        let _file = SocketFile(path);
+        let l = ProxyListener::new(l, self.config.proxy_protocol);
+        ready(&mut self).await;
+        self.serve(l, listeners, |_| ()).await
+    }
+
+    /// Serves HTTP on `listener` and the additional `listeners`. `spawn` is
+    /// called with the shared `Rocket` before any connection is accepted so
+    /// that additional servers, like an HTTP/3 server, can be started on the
+    /// same instance. Such servers must drop their `Rocket` within the grace
+    /// and mercy periods of a shutdown.
+    async fn serve<L, S>(self, listener: L, listeners: Vec<Bound>, spawn: S) -> Result<Self, Error>
+        where L: Listener + Send, <L as Listener>::Connection: Send + Unpin + 'static,
+              S: FnOnce(Arc<Self>)
     {
         // Emit a warning if we're not running inside of Rocket's async runtime.
         if self.config.profile == Config::DEBUG_PROFILE {
@@ -430,41 +782,22 @@ impl Rocket<Orbit> {
             });
         }
 
-        // Save the keep-alive value for later use; we're about to move `self`.
-        let keep_alive = self.config.keep_alive;
-
-        // Create the Hyper `Service`.
+        // Create the Hyper server and those of any additional listeners. The
+        // server resolves once every listener has finished.
         let rocket = Arc::new(self);
-        let service_fn = |conn: &CancellableIo<_, L::Connection>| {
-            let rocket = rocket.clone();
-            let connection = ConnectionMeta {
-                remote: conn.peer_address(),
-                client_certificates: conn.peer_certificates(),
-            };
+        spawn(rocket.clone());
 
-            async move {
-                Ok::<_, std::convert::Infallible>(hyper::service::service_fn(move |req| {
-                    hyper_service_fn(rocket.clone(), connection.clone(), req)
-                }))
-            }
-        };
-
-        // NOTE: `hyper` uses `tokio::spawn()` as the default executor.
-        let listener = CancellableListener::new(shutdown.clone(), listener, grace, mercy);
-        let builder = hyper::server::Server::builder(Incoming::new(listener).nodelay(true));
+        #[cfg(unix)]
+        if rocket.config.reload_on_sighup {
+            reload_config_on_sighup(rocket.clone());
+        }
 
-        #[cfg(feature = "http2")]
-        let builder = builder.http2_keep_alive_interval(match keep_alive {
-            0 => None,
-            n => Some(Duration::from_secs(n as u64))
+        let listeners = Self::spawn_listeners(&rocket, listeners);
+        let server = Self::hyper_server(rocket.clone(), listener).then(|result| async move {
+            futures::future::join_all(listeners).await;
+            result
         });
 
-        let server = builder
-            .http1_keepalive(keep_alive != 0)
-            .http1_preserve_header_case(true)
-            .serve(hyper::service::make_service_fn(service_fn))
-            .with_graceful_shutdown(shutdown.clone());
-
         // This deserves some exaplanation.
         //
         // This is largely to deal with Hyper's dreadful and largely nonexistent
@@ -503,14 +836,17 @@ impl Rocket<Orbit> {
             _ = shutdown => {
                 // Run shutdown fairings. We compute `sleep()` for grace periods
                 // beforehand to ensure we don't add shutdown fairing completion
-                // time, which is arbitrary, to these periods.
+                // time, which is arbitrary, to these periods. Fairings are
+                // themselves cancelled once the grace period elapses.
                 info!("Shutdown requested. Waiting for pending I/O...");
                 let grace_timer = sleep(Duration::from_secs(grace));
                 let mercy_timer = sleep(Duration::from_secs(grace + mercy));
                 let shutdown_timer = sleep(Duration::from_secs(grace + mercy + 1));
                 rocket.fairings.handle_shutdown(&*rocket).await;
 
+                // Wait for background tasks, aborting any that outlive mercy.
                 tokio::pin!(grace_timer, mercy_timer, shutdown_timer);
+                rocket.tasks.join(&mut mercy_timer).await;
                 tokio::select! {
                     biased;
 
@@ -554,4 +890,285 @@ impl Rocket<Orbit> {
             }
         }
     }
+
+    /// Returns a future serving HTTP on `listener` with the shared `rocket`
+    /// until shutdown is requested and pending I/O is cancelled.
+    fn hyper_server<L>(
+        rocket: Arc<Self>,
+        listener: L
+    ) -> impl Future<Output = Result<(), hyper::Error>> + Send
+        where L: Listener + Send, <L as Listener>::Connection: Send + Unpin + 'static
+    {
+        let shutdown = rocket.shutdown();
+        let grace = rocket.config.shutdown.grace as u64;
+        let mercy = rocket.config.shutdown.mercy as u64;
+        let keep_alive = rocket.config.keep_alive;
+        let listener = LimitedListener::new(listener, rocket.connections.clone());
+
+        let service_fn = move |conn: &CancellableIo<_, Limited<L::Connection>>| {
+            let rocket = rocket.clone();
+            let connection = ConnectionMeta {
+                remote: conn.peer_address(),
+                client_certificates: conn.peer_certificates(),
+            };
+
+            async move {
+                Ok::<_, std::convert::Infallible>(hyper::service::service_fn(move |req| {
+                    hyper_service_fn(rocket.clone(), connection.clone(), req)
+                }))
+            }
+        };
+
+        // NOTE: `hyper` uses `tokio::spawn()` as the default executor.
+        let listener = CancellableListener::new(shutdown.clone(), listener, grace, mercy);
+        let builder = hyper::server::Server::builder(Incoming::new(listener).nodelay(true));
+
+        #[cfg(feature = "http2")]
+        let builder = builder.http2_keep_alive_interval(match keep_alive {
+            0 => None,
+            n => Some(Duration::from_secs(n as u64))
+        });
+
+        builder
+            .http1_keepalive(keep_alive != 0)
+            .http1_preserve_header_case(true)
+            .serve(hyper::service::make_service_fn(service_fn))
+            .with_graceful_shutdown(shutdown)
+    }
+
+    /// Binds each of the additional `listeners` in the configuration, updating
+    /// their `port` to the one actually bound.
+    async fn bind_listeners(&mut self) -> Result<Vec<Bound>, Error> {
+        let mut bound = Vec::with_capacity(self.config.listeners.len());
+        #[cfg(feature = "tls")]
+        let mut listener_tls = vec![];
+        for config in &mut self.config.listeners {
+            if let Some(ref unix) = config.unix {
+                #[cfg(unix)] {
+                    if config.tls_enabled() {
+                        let msg = "TLS is not supported over Unix domain sockets";
+                        let e = io::Error::new(io::ErrorKind::Other, msg);
+                        return Err(Error::new(ErrorKind::Io(e)));
+                    }
+
+                    let l = unix.bind().map_err(ErrorKind::Bind)?;
+                    let l = ProxyListener::new(l, config.proxy_protocol);
+                    let file = SocketFile(unix.remove.then(|| unix.path.clone()));
+                    bound.push(Bound::Unix(l, file));
+                    continue;
+                }
+
+                #[cfg(not(unix))] {
+                    let msg = format!("Unix domain sockets are not supported: {}", unix);
+                    let e = io::Error::new(io::ErrorKind::Unsupported, msg);
+                    return Err(Error::new(ErrorKind::Bind(e)));
+                }
+            }
+
+            let addr = std::net::SocketAddr::new(config.address, config.port);
+            let l = TcpListener::bind(addr).await.map_err(ErrorKind::Bind)?;
+            config.port = l.local_addr().map_err(ErrorKind::Bind)?.port();
+            let l = ProxyListener::new(l, config.proxy_protocol);
+
+            #[cfg(feature = "tls")]
+            if let Some(ref tls) = config.tls {
+                use crate::http::tls::TlsListener;
+
+                let conf = tls.to_native_config().map_err(ErrorKind::Io)?;
+                let l = TlsListener::from_listener(l, conf).map_err(ErrorKind::Bind)?;
+                let resolver = l.cert_resolver();
+
+                #[cfg(unix)]
+                if tls.reload_on_sighup {
+                    reload_tls_on_sighup(tls.clone(), vec![resolver.clone()]);
+                }
+
+                listener_tls.push((tls.clone(), resolver));
+                bound.push(Bound::Tls(l));
+                continue;
+            }
+
+            bound.push(Bound::Tcp(l));
+        }
+
+        #[cfg(feature = "tls")] {
+            self.listener_tls = listener_tls;
+        }
+
+        Ok(bound)
+    }
+
+    /// Spawns a server on the shared `rocket` for each of the bound additional
+    /// `listeners`, returning handles that resolve when each has finished.
+    fn spawn_listeners(rocket: &Arc<Self>, listeners: Vec<Bound>) -> Vec<JoinHandle<()>> {
+        async fn run<L>(rocket: Arc<Rocket<Orbit>>, listener: L)
+            where L: Listener + Send, <L as Listener>::Connection: Send + Unpin + 'static
+        {
+            if let Err(e) = Rocket::hyper_server(rocket, listener).await {
+                warn!("Additional listener failed: {}", e);
+            }
+        }
+
+        listeners.into_iter().map(|listener| {
+            let rocket = rocket.clone();
+            match listener {
+                Bound::Tcp(l) => tokio::spawn(run(rocket, l)),
+                #[cfg(feature = "tls")]
+                Bound::Tls(l) => tokio::spawn(run(rocket, l)),
+                #[cfg(unix)]
+                Bound::Unix(l, file) => tokio::spawn(async move {
+                    run(rocket, l).await;
+                    drop(file);
+                }),
+            }
+        }).collect()
+    }
+
+    /// Serves HTTP/3 on `listener` until shutdown is requested, then closes
+    /// connections once the grace period elapses.
+    #[cfg(feature = "http3")]
+    async fn http3_server(self: Arc<Self>, listener: crate::http::tls::QuicListener) {
+        use futures::future::{select, Either};
+
+        let shutdown = self.shutdown();
+        loop {
+            let connecting = match select(Box::pin(listener.accept()), shutdown.clone()).await {
+                Either::Left((Some(connecting), _)) => connecting,
+                Either::Left((None, _)) | Either::Right(_) => break,
+            };
+
+            let rocket = self.clone();
+            tokio::spawn(async move {
+                match connecting.established().await {
+                    Ok(connection) => rocket.http3_connection(connection).await,
+                    Err(e) => warn!("HTTP/3 connection failed: {}", e),
+                }
+            });
+        }
+
+        let grace = Duration::from_secs(self.config.shutdown.grace as u64);
+        drop(self);
+        listener.shutdown(grace).await;
+    }
+
+    /// Dispatches every request on `connection` until it is closed. Once
+    /// shutdown is requested, the client is told that no further requests
+    /// will be accepted.
+    #[cfg(feature = "http3")]
+    async fn http3_connection(
+        self: Arc<Self>,
+        mut connection: crate::http::tls::QuicConnection
+    ) {
+        use futures::future::{select, Either};
+
+        let shutdown = self.shutdown();
+        let meta = ConnectionMeta {
+            remote: connection.peer_address(),
+            client_certificates: connection.peer_certificates(),
+        };
+
+        let mut draining = false;
+        loop {
+            let accepted = if draining {
+                connection.accept().await
+            } else {
+                let next = match select(Box::pin(connection.accept()), shutdown.clone()).await {
+                    Either::Left((accepted, _)) => Some(accepted),
+                    Either::Right(_) => None,
+                };
+
+                match next {
+                    Some(accepted) => accepted,
+                    None => {
+                        draining = true;
+                        let _ = connection.shutdown().await;
+                        continue;
+                    }
+                }
+            };
+
+            let (request, responder) = match accepted {
+                Ok(Some(accepted)) => accepted,
+                Ok(None) => break,
+                Err(e) => {
+                    warn!("HTTP/3 connection error: {}", e);
+                    break;
+                }
+            };
+
+            let (rocket, meta) = (self.clone(), meta.clone());
+            tokio::spawn(async move {
+                let result = match hyper_service_fn(rocket, meta, request).await {
+                    Ok(response) => responder.send(response).await,
+                    Err(e) => Err(e),
+                };
+
+                if let Err(e) = result {
+                    warn!("Failed to write HTTP/3 response: {}", e);
+                }
+            });
+        }
+    }
+}
+
+/// Spawns a task that reloads the configuration of `rocket` whenever the process
+/// receives `SIGHUP`. The task ends, releasing `rocket`, when shutdown is
+/// triggered. The signal handler is registered before returning.
+#[cfg(unix)]
+fn reload_config_on_sighup(rocket: Arc<Rocket<Orbit>>) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangup = match signal(SignalKind::hangup()) {
+        Ok(stream) => stream,
+        Err(e) => return error!("Failed to listen for SIGHUP to reload config: {}", e),
+    };
+
+    tokio::spawn(async move {
+        let shutdown = rocket.shutdown();
+        tokio::pin!(shutdown);
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => break,
+                signal = hangup.recv() => {
+                    if signal.is_none() {
+                        break;
+                    }
+
+                    info!("Received SIGHUP. Reloading configuration.");
+                    if let Err(e) = rocket.refresh_config().await {
+                        error!("Failed to reload configuration. Existing settings remain.");
+                        crate::config::pretty_print_error(e);
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Spawns a task that reloads the TLS certificates in `resolvers` from
+/// `config` whenever the process receives `SIGHUP`. The signal handler is
+/// registered before returning.
+#[cfg(all(feature = "tls", unix))]
+fn reload_tls_on_sighup(
+    config: crate::config::TlsConfig,
+    resolvers: Vec<Arc<crate::http::tls::CertResolver>>,
+) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangup = match signal(SignalKind::hangup()) {
+        Ok(stream) => stream,
+        Err(e) => return error!("Failed to listen for SIGHUP to reload TLS: {}", e),
+    };
+
+    tokio::spawn(async move {
+        while hangup.recv().await.is_some() {
+            match config.reload(&resolvers) {
+                Ok(()) => info!("Received SIGHUP. Reloaded TLS certificates."),
+                Err(e) => {
+                    error!("Received SIGHUP but failed to reload TLS certificates.");
+                    error_!("{}", e);
+                }
+            }
+        }
+    });
 }
@@ -0,0 +1,397 @@
+use std::fmt;
+use std::borrow::Cow;
+use std::convert::Infallible;
+
+use indexmap::IndexMap;
+use rocket_http::{ext::IntoCollection, private::SmallVec};
+
+use crate::Request;
+use crate::http::{Header, uri::Absolute};
+use crate::request::{self, FromRequest};
+use crate::shield::Policy;
+
+/// The [Content-Security-Policy] header: restricts the sources from which a
+/// document may load resources.
+///
+/// A `Csp` policy is built from directives, each restricting one kind of
+/// resource to a list of [`Source`]s:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::shield::{Shield, Csp, Directive, Source};
+///
+/// let csp = Csp::default()
+///     .directive(Directive::ImgSrc, [Source::This, Source::Scheme("data".into())])
+///     .directive(Directive::ScriptSrc, [Source::This, Source::Nonce])
+///     .directive(Directive::StyleSrc, [Source::This, Source::Nonce])
+///     .directive(Directive::ObjectSrc, Source::None);
+///
+/// rocket::build().attach(Shield::default().enable(csp));
+/// ```
+///
+/// # Nonces
+///
+/// With [`Source::Nonce`], inline scripts and styles are allowed only if they
+/// carry the nonce of the current request, removing the need for
+/// `'unsafe-inline'`. A fresh, random nonce is generated for every request
+/// that is responded to, and the header is assembled for each response with
+/// that nonce. Handlers retrieve the nonce with the [`&CspNonce`](CspNonce)
+/// request guard and emit it in a `nonce` attribute:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::response::content::RawHtml;
+/// use rocket::shield::CspNonce;
+///
+/// #[get("/")]
+/// fn index(nonce: &CspNonce) -> RawHtml<String> {
+///     RawHtml(format!("<script nonce=\"{}\">console.log('hi')</script>", nonce))
+/// }
+/// ```
+///
+/// Templates rendered by `rocket_dyn_templates` receive the nonce as the
+/// `csp_nonce` context value.
+///
+/// # Default
+///
+/// The default returned via [`Csp::default()`] allows loading resources only
+/// from the document's own origin: `default-src 'self'`.
+///
+/// [Content-Security-Policy]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy
+#[derive(Debug, PartialEq, Clone)]
+pub struct Csp(IndexMap<Directive, SmallVec<[Source; 2]>>);
+
+impl Default for Csp {
+    /// The default `Csp` policy allows loading resources only from the
+    /// document's own origin: `default-src 'self'`.
+    fn default() -> Self {
+        Csp::new().directive(Directive::DefaultSrc, Source::This)
+    }
+}
+
+impl Csp {
+    /// Returns a `Csp` policy with no directives.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::shield::{Csp, Directive};
+    ///
+    /// let csp = Csp::new();
+    /// assert!(csp.get(Directive::DefaultSrc).is_none());
+    /// ```
+    pub fn new() -> Self {
+        Csp(IndexMap::new())
+    }
+
+    /// Sets the sources for `directive` to `sources`, which may be a single
+    /// [`Source`], a slice (`[Source]` or `&[Source]`), or a vector
+    /// (`Vec<Source>`). This supercedes any previous sources set for
+    /// `directive`.
+    ///
+    /// Directives without sources, like
+    /// [`Directive::UpgradeInsecureRequests`], are set with an empty vector.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::shield::{Csp, Directive, Source};
+    ///
+    /// let csp = Csp::default()
+    ///     .directive(Directive::ScriptSrc, [Source::This, Source::Nonce])
+    ///     .directive(Directive::UpgradeInsecureRequests, vec![]);
+    /// ```
+    pub fn directive<S>(mut self, directive: Directive, sources: S) -> Self
+        where S: IntoCollection<Source>
+    {
+        self.0.insert(directive, sources.into_collection());
+        self
+    }
+
+    /// Removes `directive` from the policy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::shield::{Csp, Directive};
+    ///
+    /// let csp = Csp::default().remove(Directive::DefaultSrc);
+    /// assert_eq!(csp, Csp::new());
+    /// ```
+    pub fn remove(mut self, directive: Directive) -> Self {
+        self.0.shift_remove(&directive);
+        self
+    }
+
+    /// Returns the sources for `directive`, if it is set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::shield::{Csp, Directive, Source};
+    ///
+    /// let csp = Csp::default();
+    /// assert_eq!(csp.get(Directive::DefaultSrc).unwrap(), &[Source::This]);
+    /// assert!(csp.get(Directive::ScriptSrc).is_none());
+    /// ```
+    pub fn get(&self, directive: Directive) -> Option<&[Source]> {
+        self.0.get(&directive).map(|sources| &sources[..])
+    }
+
+    /// Returns `true` if any directive uses [`Source::Nonce`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::shield::{Csp, Directive, Source};
+    ///
+    /// let csp = Csp::default();
+    /// assert!(!csp.uses_nonce());
+    ///
+    /// let csp = csp.directive(Directive::ScriptSrc, Source::Nonce);
+    /// assert!(csp.uses_nonce());
+    /// ```
+    pub fn uses_nonce(&self) -> bool {
+        self.0.values().flatten().any(|source| *source == Source::Nonce)
+    }
+
+    fn render(&self, nonce: &str) -> String {
+        self.0.iter()
+            .map(|(directive, sources)| {
+                let mut string = directive.to_string();
+                for source in sources {
+                    string.push(' ');
+                    string.push_str(&source.rendered(nonce));
+                }
+
+                string
+            })
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+impl Policy for Csp {
+    const NAME: &'static str = "Content-Security-Policy";
+
+    /// Returns the header with a `[per-request]` placeholder for nonces. When
+    /// the policy uses nonces, [`Csp::request_header()`] is used instead.
+    fn header(&self) -> Header<'static> {
+        Header::new(Self::NAME, self.render("[per-request]"))
+    }
+
+    fn request_header(&self, request: &Request<'_>) -> Option<Header<'static>> {
+        match self.uses_nonce() {
+            true => Some(Header::new(Self::NAME, self.render(CspNonce::of(request).as_str()))),
+            false => None,
+        }
+    }
+}
+
+/// A directive in a [`Csp`] policy.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+#[non_exhaustive]
+pub enum Directive {
+    /// The "default-src" directive: the fallback for other `-src` directives.
+    DefaultSrc,
+    /// The "script-src" directive.
+    ScriptSrc,
+    /// The "style-src" directive.
+    StyleSrc,
+    /// The "img-src" directive.
+    ImgSrc,
+    /// The "connect-src" directive.
+    ConnectSrc,
+    /// The "font-src" directive.
+    FontSrc,
+    /// The "object-src" directive.
+    ObjectSrc,
+    /// The "media-src" directive.
+    MediaSrc,
+    /// The "frame-src" directive.
+    FrameSrc,
+    /// The "child-src" directive.
+    ChildSrc,
+    /// The "worker-src" directive.
+    WorkerSrc,
+    /// The "manifest-src" directive.
+    ManifestSrc,
+    /// The "frame-ancestors" directive.
+    FrameAncestors,
+    /// The "form-action" directive.
+    FormAction,
+    /// The "base-uri" directive.
+    BaseUri,
+    /// The "upgrade-insecure-requests" directive. Takes no sources.
+    UpgradeInsecureRequests,
+}
+
+impl Directive {
+    const fn as_str(&self) -> &'static str {
+        use Directive::*;
+
+        match self {
+            DefaultSrc => "default-src",
+            ScriptSrc => "script-src",
+            StyleSrc => "style-src",
+            ImgSrc => "img-src",
+            ConnectSrc => "connect-src",
+            FontSrc => "font-src",
+            ObjectSrc => "object-src",
+            MediaSrc => "media-src",
+            FrameSrc => "frame-src",
+            ChildSrc => "child-src",
+            WorkerSrc => "worker-src",
+            ManifestSrc => "manifest-src",
+            FrameAncestors => "frame-ancestors",
+            FormAction => "form-action",
+            BaseUri => "base-uri",
+            UpgradeInsecureRequests => "upgrade-insecure-requests",
+        }
+    }
+}
+
+impl fmt::Display for Directive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+/// A source allowed by a [`Directive`] in a [`Csp`] policy.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Source {
+    /// The document's own origin: `'self'`.
+    This,
+    /// No source at all: `'none'`.
+    None,
+    /// The current request's [`CspNonce`]: `'nonce-...'`.
+    Nonce,
+    /// Inline scripts or styles: `'unsafe-inline'`.
+    UnsafeInline,
+    /// Dynamic code evaluation: `'unsafe-eval'`.
+    UnsafeEval,
+    /// Scripts loaded by already trusted scripts: `'strict-dynamic'`.
+    StrictDynamic,
+    /// A specific origin.
+    ///
+    /// The `user_info`, `path`, and `query` parts of the URI, if any, are
+    /// ignored.
+    Origin(Absolute<'static>),
+    /// Any resource with the given scheme, such as `data` or `https`,
+    /// rendered with a trailing `:`.
+    Scheme(Cow<'static, str>),
+    /// A host source, such as `*.example.com`, or any other source expression,
+    /// rendered verbatim.
+    Host(Cow<'static, str>),
+}
+
+impl Source {
+    fn rendered(&self, nonce: &str) -> Cow<'static, str> {
+        match self {
+            Source::This => "'self'".into(),
+            Source::None => "'none'".into(),
+            Source::Nonce => format!("'nonce-{}'", nonce).into(),
+            Source::UnsafeInline => "'unsafe-inline'".into(),
+            Source::UnsafeEval => "'unsafe-eval'".into(),
+            Source::StrictDynamic => "'strict-dynamic'".into(),
+            Source::Origin(uri) => {
+                let mut string = uri.scheme().to_string();
+                if let Some(auth) = uri.authority() {
+                    use std::fmt::Write;
+
+                    let _ = write!(string, "://{}", auth.host());
+                    if let Some(port) = auth.port() {
+                        let _ = write!(string, ":{}", port);
+                    }
+                }
+
+                string.into()
+            }
+            Source::Scheme(scheme) => format!("{}:", scheme).into(),
+            Source::Host(host) => host.clone(),
+        }
+    }
+}
+
+/// A random, per-request nonce for a [`Csp`] policy.
+///
+/// The nonce of a request is generated the first time it's retrieved, via
+/// [`CspNonce::of()`] or the `&CspNonce` request guard, and is thereafter fixed
+/// for the request. A [`Csp`] policy with [`Source::Nonce`] sources emits the
+/// nonce of the request being responded to. See [nonces](Csp#nonces) for
+/// usage.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CspNonce(String);
+
+impl CspNonce {
+    /// The number of random bytes in a nonce.
+    const LEN: usize = 16;
+
+    /// Returns the nonce of `req`, generating one the first time it's
+    /// retrieved.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::shield::CspNonce;
+    /// # let client = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let (req, other) = (client.get("/"), client.get("/"));
+    ///
+    /// let nonce = CspNonce::of(&req);
+    /// assert_eq!(nonce, CspNonce::of(&req));
+    /// assert_ne!(nonce, CspNonce::of(&other));
+    /// ```
+    pub fn of<'r>(req: &'r Request<'_>) -> &'r CspNonce {
+        req.local_cache(CspNonce::generate)
+    }
+
+    /// Returns the nonce as a string slice.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::shield::CspNonce;
+    /// # let client = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let req = client.get("/");
+    ///
+    /// let nonce = CspNonce::of(&req);
+    /// assert_eq!(nonce.as_str().len(), 24);
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    fn generate() -> CspNonce {
+        let bytes: [u8; Self::LEN] = rand::random();
+        let mut buf = [0u8; 24];
+        let nonce = binascii::b64encode(&bytes, &mut buf).expect("24 bytes suffice");
+        CspNonce(String::from_utf8_lossy(nonce).into_owned())
+    }
+}
+
+impl fmt::Display for CspNonce {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl AsRef<str> for CspNonce {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl serde::Serialize for CspNonce {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for &'r CspNonce {
+    type Error = Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        request::Outcome::Success(CspNonce::of(req))
+    }
+}
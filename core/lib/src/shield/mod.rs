@@ -20,6 +20,7 @@
 //! | [Referrer-Policy]           | Enables referrer policy.               | [`Referrer`]   | ✗        |
 //! | [X-DNS-Prefetch-Control]    | Controls browser DNS prefetching.      | [`Prefetch`]   | ✗        |
 //! | [Permissions-Policy]        | Allows or block browser features.      | [`Permission`] | ✔        |
+//! | [Content-Security-Policy]   | Restricts sources of resources.        | [`Csp`]        | ✗        |
 //!
 //! <small>? If TLS is enabled in a non-debug profile, HSTS is automatically
 //! enabled with its default policy and a warning is logged at liftoff.</small>
@@ -33,6 +34,7 @@
 //! [X-DNS-Prefetch-Control]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/X-DNS-Prefetch-Control
 //! [clickjacking]: https://en.wikipedia.org/wiki/Clickjacking
 //! [Permissions-Policy]: https://github.com/w3c/webappsec-permissions-policy/blob/a45df7b237e2a85e1909d7f226ca4eb4ce5095ba/permissions-policy-explainer.md
+//! [Content-Security-Policy]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy
 //!
 //! [`XssFilter`]: self::XssFilter
 //! [`NoSniff`]: self::NoSniff
@@ -81,6 +83,17 @@
 //!     .disable::<NoSniff>();
 //! ```
 //!
+//! Policies can also be changed for requests under a given base path alone via
+//! [`Shield::scope()`](shield::Shield::scope()), which uses a different
+//! `Shield` for such requests:
+//!
+//! ```rust
+//! use rocket::shield::{Shield, Frame};
+//!
+//! // Allow pages under `/embed` to be framed.
+//! let shield = Shield::default().scope("/embed", Shield::default().disable::<Frame>());
+//! ```
+//!
 //! # FAQ
 //!
 //! * **Which policies should I choose?**
@@ -99,6 +112,9 @@
 
 mod shield;
 mod policy;
+mod csp;
 
 pub use self::shield::Shield;
+pub(crate) use self::shield::ForceHsts;
 pub use self::policy::*;
+pub use self::csp::{Csp, Directive, Source, CspNonce};
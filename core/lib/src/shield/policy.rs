@@ -7,6 +7,7 @@ use indexmap::IndexMap;
 use rocket_http::{ext::IntoCollection, private::SmallVec};
 use time::Duration;
 
+use crate::Request;
 use crate::http::{Header, uri::Absolute, uncased::{UncasedStr, Uncased}};
 
 /// Trait implemented by security and privacy policy headers.
@@ -62,12 +63,44 @@ pub trait Policy: Default + Send + Sync + 'static {
     /// }
     /// ```
     fn header(&self) -> Header<'static>;
+
+    /// Returns the [`Header`](../../rocket/http/struct.Header.html) to attach
+    /// to the response to `request` if it differs from request to request.
+    ///
+    /// If this method returns `None`, the default, the header returned by
+    /// [`Policy::header()`] is attached instead. Policies that embed
+    /// per-request values, like the nonces of a [`Csp`](crate::shield::Csp)
+    /// policy, override this method.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::Request;
+    /// use rocket::http::Header;
+    /// use rocket::shield::Policy;
+    ///
+    /// #[derive(Default)]
+    /// struct MyPolicy;
+    ///
+    /// impl Policy for MyPolicy {
+    /// #   const NAME: &'static str = "X-My-Policy";
+    /// #   fn header(&self) -> Header<'static> { unimplemented!() }
+    ///     fn request_header(&self, request: &Request<'_>) -> Option<Header<'static>> {
+    ///         Some(Header::new(Self::NAME, request.uri().path().to_string()))
+    ///     }
+    /// }
+    /// ```
+    fn request_header(&self, _request: &Request<'_>) -> Option<Header<'static>> {
+        None
+    }
 }
 
 /// Hack to make `Policy` Object-Safe.
 pub(crate) trait SubPolicy: Send + Sync {
     fn name(&self) -> &'static UncasedStr;
     fn header(&self) -> Header<'static>;
+    fn request_header(&self, request: &Request<'_>) -> Option<Header<'static>>;
 }
 
 impl<P: Policy> SubPolicy for P {
@@ -78,6 +111,10 @@ impl<P: Policy> SubPolicy for P {
     fn header(&self) -> Header<'static> {
         Policy::header(self)
     }
+
+    fn request_header(&self, request: &Request<'_>) -> Option<Header<'static>> {
+        Policy::request_header(self, request)
+    }
 }
 
 macro_rules! impl_policy {
@@ -1,3 +1,5 @@
+use std::fmt;
+use std::convert::TryInto;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 
@@ -6,7 +8,7 @@ use yansi::Paint;
 
 use crate::{Rocket, Request, Response, Orbit, Config};
 use crate::fairing::{Fairing, Info, Kind};
-use crate::http::{Header, uncased::UncasedStr};
+use crate::http::{Header, uri::Origin, uncased::UncasedStr, ext::IntoOwned};
 use crate::log::PaintExt;
 use crate::shield::*;
 
@@ -62,10 +64,26 @@ use crate::shield::*;
 ///
 /// If TLS is configured and enabled when the application is launched in a
 /// non-debug profile, HSTS is automatically enabled with its default policy and
-/// a warning is logged.
+/// a warning is logged. The same applies to [scoped](#scoping) shields. HSTS
+/// is also enabled, without a warning, when requested by
+/// [`HttpsRedirect::hsts()`](crate::fairing::HttpsRedirect::hsts()).
 ///
 /// To get rid of this warning, explicitly [`Shield::enable()`] an [`Hsts`]
 /// policy.
+///
+/// # Scoping
+///
+/// A different `Shield` can be used for requests to paths under a given base
+/// with [`Shield::scope()`]. This allows relaxing or tightening individual
+/// policies for specific routes or mount points, for instance to allow an
+/// embeddable widget to be framed:
+///
+/// ```rust
+/// use rocket::shield::{Shield, Frame};
+///
+/// let shield = Shield::default()
+///     .scope("/widget", Shield::default().disable::<Frame>());
+/// ```
 pub struct Shield {
     /// Enabled policies where the key is the header name.
     policies: HashMap<&'static UncasedStr, Box<dyn SubPolicy>>,
@@ -73,8 +91,14 @@ pub struct Shield {
     force_hsts: AtomicBool,
     /// Headers pre-rendered at liftoff from the configured policies.
     rendered: Storage<Vec<Header<'static>>>,
+    /// Shields used instead of this one for requests under a base path.
+    scopes: Vec<(Origin<'static>, Shield)>,
 }
 
+/// Managed by fairings, like [`HttpsRedirect`](crate::fairing::HttpsRedirect),
+/// to request that HSTS be enforced regardless of TLS and profile.
+pub(crate) struct ForceHsts;
+
 impl Default for Shield {
     /// Returns a new `Shield` instance. See the [table] for a description
     /// of the policies used by default.
@@ -112,6 +136,7 @@ impl Shield {
             policies: HashMap::new(),
             force_hsts: AtomicBool::new(false),
             rendered: Storage::new(),
+            scopes: vec![],
         }
     }
 
@@ -173,6 +198,74 @@ impl Shield {
         self.policies.contains_key(UncasedStr::new(P::NAME))
     }
 
+    /// Uses `shield` in place of `self` for all requests whose path is under
+    /// `base`, that is, for which the segments of `base` are a prefix of the
+    /// segments of the request's path. The policies of `self` are not used for
+    /// such requests at all: `shield` should enable every desired policy.
+    ///
+    /// If several scopes match a request, the scope with the longest `base`
+    /// is used. If a scope's `shield` has scopes of its own, they are then
+    /// considered in the same manner. All bases are relative to the root.
+    /// Scoping a previously scoped `base` again replaces the previous scope.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base` is not a valid origin URI.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::shield::{Shield, Frame, Referrer};
+    ///
+    /// // Allow `/widget` and all paths under it to be framed by any site.
+    /// let shield = Shield::default()
+    ///     .scope("/widget", Shield::default().disable::<Frame>())
+    ///     .scope("/api", Shield::new().enable(Referrer::NoReferrer));
+    /// ```
+    #[track_caller]
+    pub fn scope<'a, B>(mut self, base: B, shield: Shield) -> Self
+        where B: TryInto<Origin<'a>> + Clone + fmt::Display,
+              B::Error: fmt::Display
+    {
+        let base = match base.clone().try_into() {
+            Ok(origin) => origin.into_owned(),
+            Err(e) => {
+                error!("invalid shield scope base: {}", Paint::white(&base));
+                error_!("{}", e);
+                info_!("{} {}", Paint::white("in"), std::panic::Location::caller());
+                panic!("aborting due to shield scope base error");
+            }
+        };
+
+        self.scopes.retain(|(existing, _)| existing.path() != base.path());
+        self.scopes.push((base, shield));
+        self
+    }
+
+    /// Returns the `Shield` to use for `req`: `self` or a scoped `Shield`.
+    fn scoped(&self, req: &Request<'_>) -> &Shield {
+        let path = req.uri().path();
+        self.scopes.iter()
+            .filter(|(base, _)| base.path().segments().prefix_of(path.segments()))
+            .max_by_key(|(base, _)| base.path().segments().len())
+            .map_or(self, |(_, shield)| shield.scoped(req))
+    }
+
+    /// Enables the default HSTS policy in `self` and in all scoped shields
+    /// that don't enable an HSTS policy. Returns `true` if any was enabled.
+    fn force_hsts(&self) -> bool {
+        let mut forced = !self.is_enabled::<Hsts>();
+        if forced {
+            self.force_hsts.store(true, Ordering::Release);
+        }
+
+        for (_, shield) in &self.scopes {
+            forced |= shield.force_hsts();
+        }
+
+        forced
+    }
+
     fn headers(&self) -> &[Header<'static>] {
         self.rendered.get_or_set(|| {
             let mut headers: Vec<_> = self.policies.values()
@@ -198,22 +291,24 @@ impl Fairing for Shield {
     }
 
     async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
-        let force_hsts = rocket.config().tls_enabled()
-            && rocket.figment().profile() != Config::DEBUG_PROFILE
-            && !self.is_enabled::<Hsts>();
-
-        if force_hsts {
-            self.force_hsts.store(true, Ordering::Release);
-        }
+        let requested = rocket.state::<ForceHsts>().is_some();
+        let force_hsts = (requested || rocket.config().tls_enabled()
+            && rocket.figment().profile() != Config::DEBUG_PROFILE)
+            && self.force_hsts();
 
-        if !self.headers().is_empty() {
+        if !self.headers().is_empty() || !self.scopes.is_empty() {
             info!("{}{}:", Paint::emoji("🛡️ "), Paint::magenta("Shield"));
 
             for header in self.headers() {
                 info_!("{}: {}", header.name(), Paint::default(header.value()));
             }
 
-            if force_hsts {
+            for (base, shield) in &self.scopes {
+                let names: Vec<_> = shield.headers().iter().map(|h| h.name().as_str()).collect();
+                info_!("scope {}: {}", Paint::white(base), Paint::default(names.join(", ")));
+            }
+
+            if force_hsts && !requested {
                 warn_!("Detected TLS-enabled liftoff without enabling HSTS.");
                 warn_!("Shield has enabled a default HSTS policy.");
                 info_!("To remove this warning, configure an HSTS policy.");
@@ -221,10 +316,20 @@ impl Fairing for Shield {
         }
     }
 
-    async fn on_response<'r>(&self, _: &'r Request<'_>, response: &mut Response<'r>) {
-        // Set all of the headers in `self.policies` in `response` as long as
+    async fn on_response<'r>(&self, req: &'r Request<'_>, response: &mut Response<'r>) {
+        // Per-request headers take the place of their pre-rendered versions.
+        let shield = self.scoped(req);
+        let dynamic: Vec<_> = shield.policies.values()
+            .filter_map(|p| p.request_header(req))
+            .collect();
+
+        let headers = shield.headers().iter()
+            .filter(|h| !dynamic.iter().any(|d| d.name() == h.name()))
+            .chain(dynamic.iter());
+
+        // Set all of the headers in `shield.policies` in `response` as long as
         // the header is not already in the response.
-        for header in self.headers() {
+        for header in headers {
             if response.headers().contains(header.name()) {
                 warn!("Shield: response contains a '{}' header.", header.name());
                 warn_!("Refusing to overwrite existing header.");
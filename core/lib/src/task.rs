@@ -0,0 +1,87 @@
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::{FutureExt, StreamExt};
+use futures::stream::FuturesUnordered;
+use parking_lot::Mutex;
+use tokio::task::JoinHandle;
+use yansi::Paint;
+
+/// The background tasks spawned via `Rocket::spawn_task()`.
+///
+/// Each task runs in its own `tokio` task. A task that panics is reported as
+/// soon as it does so. On shutdown, the remaining tasks are awaited until a
+/// deadline, after which any tasks that haven't completed are aborted.
+#[derive(Debug, Default)]
+pub(crate) struct Tasks {
+    handles: Mutex<Vec<(&'static str, JoinHandle<()>)>>,
+}
+
+impl Tasks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawns `task`, named `name`, and tracks it until it completes or until
+    /// shutdown. Completed tasks are forgotten whenever a task is spawned.
+    pub fn spawn<F>(&self, name: &'static str, task: F)
+        where F: Future<Output = ()> + Send + 'static
+    {
+        let handle = tokio::spawn(async move {
+            if AssertUnwindSafe(task).catch_unwind().await.is_err() {
+                error!("Background task {} panicked.", Paint::white(name));
+                info_!("This is an application bug.");
+                warn_!("The task will not be restarted.");
+            }
+        });
+
+        let mut handles = self.handles.lock();
+        let running = std::mem::take(&mut *handles).into_iter()
+            .filter_map(|(name, mut handle)| {
+                (&mut handle).now_or_never().is_none().then(|| (name, handle))
+            });
+
+        handles.extend(running);
+        handles.push((name, handle));
+    }
+
+    /// Waits for every task to complete or for `deadline` to resolve, then
+    /// aborts any tasks that are still running.
+    pub async fn join<D: Future>(&self, deadline: D) {
+        let handles = std::mem::take(&mut *self.handles.lock());
+        if handles.is_empty() {
+            return;
+        }
+
+        info!("Waiting for {} background task(s)...", handles.len());
+        let mut running: FuturesUnordered<_> = handles.into_iter()
+            .map(|(name, handle)| Running(name, handle))
+            .collect();
+
+        // Finished tasks are removed from `running` and never polled again.
+        tokio::select! {
+            biased;
+
+            _ = async { while running.next().await.is_some() {} } => return,
+            _ = deadline => {},
+        }
+
+        for Running(name, handle) in running.iter() {
+            warn_!("Background task {} is still running. Aborting.", Paint::white(name));
+            handle.abort();
+        }
+    }
+}
+
+/// A named task that resolves when the task completes.
+struct Running(&'static str, JoinHandle<()>);
+
+impl Future for Running {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        Pin::new(&mut self.1).poll(cx).map(|_| ())
+    }
+}
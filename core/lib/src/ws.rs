@@ -0,0 +1,339 @@
+//! Support for WebSocket routes.
+//!
+//! A WebSocket connection is initiated via a regular HTTP `GET` request which
+//! asks to upgrade the connection to the `websocket` protocol. The
+//! [`WebSocket`] request guard succeeds only for such requests. The upgrade is
+//! then completed by returning the [`Channel`] or [`MessageStream`] created
+//! from the guard:
+//!
+//! ```rust
+//! # use rocket::get;
+//! use rocket::ws::{WebSocket, Channel, Message};
+//! use rocket::futures::{SinkExt, StreamExt};
+//!
+//! #[get("/echo")]
+//! fn echo(ws: WebSocket) -> Channel<'static> {
+//!     ws.channel(|mut stream| Box::pin(async move {
+//!         while let Some(message) = stream.next().await {
+//!             let _ = stream.send(message?).await;
+//!         }
+//!
+//!         Ok(())
+//!     }))
+//! }
+//! ```
+//!
+//! The handler passed to [`WebSocket::channel()`] or [`WebSocket::stream()`]
+//! runs only once the response has been sent and the connection has been
+//! upgraded.
+//!
+//! # Graceful Shutdown
+//!
+//! Upgraded connections are subject to the same [grace and mercy
+//! periods](crate::config::Shutdown) as any other connection: once they
+//! elapse, the connection is closed. A [`MessageStream`] additionally sends a
+//! [`CloseCode::Away`](frame::CloseCode::Away) close frame to the client as
+//! soon as shutdown is requested. Handlers passed to [`WebSocket::channel()`]
+//! which wish to do the same can select on the [`Shutdown`] future, retrieved
+//! via [`WebSocket::shutdown()`].
+//!
+//! # Testing
+//!
+//! WebSocket routes can be tested without binding a port with the
+//! asynchronous local [`Client`](crate::local::asynchronous::Client):
+//! [`LocalRequest::websocket()`](crate::local::asynchronous::LocalRequest::websocket())
+//! performs the upgrade and returns an in-memory client connection.
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! # use rocket::ws::{WebSocket, Channel, Message};
+//! # use rocket::futures::{SinkExt, StreamExt};
+//! # #[get("/echo")]
+//! # fn echo(ws: WebSocket) -> Channel<'static> {
+//! #     ws.channel(|mut stream| Box::pin(async move {
+//! #         while let Some(message) = stream.next().await {
+//! #             let _ = stream.send(message?).await;
+//! #         }
+//! #
+//! #         Ok(())
+//! #     }))
+//! # }
+//! use rocket::local::asynchronous::Client;
+//!
+//! # rocket::async_test(async {
+//! let client = Client::debug_with(routes![echo]).await.unwrap();
+//! let mut ws = client.get("/echo").websocket().await.expect("upgraded");
+//! ws.send(Message::text("hello")).await.unwrap();
+//! assert_eq!(ws.next().await.unwrap().unwrap(), Message::text("hello"));
+//! # });
+//! ```
+
+use std::io;
+use std::pin::Pin;
+
+use tokio_tungstenite::WebSocketStream;
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::Role;
+
+use futures::future::BoxFuture;
+use futures::stream::{SplitStream, Stream, StreamExt};
+use futures::sink::SinkExt;
+
+use crate::{Request, Response, Shutdown};
+use crate::request::{FromRequest, Outcome};
+use crate::response::{self, Responder};
+use crate::data::{IoHandler, IoStream};
+
+#[doc(inline)]
+pub use tokio_tungstenite::tungstenite::Message;
+
+/// WebSocket connection configuration.
+#[doc(inline)]
+pub use tokio_tungstenite::tungstenite::protocol::WebSocketConfig as Config;
+
+/// Types representing WebSocket close frames.
+pub mod frame {
+    #[doc(inline)]
+    pub use tokio_tungstenite::tungstenite::protocol::frame::CloseFrame;
+
+    #[doc(inline)]
+    pub use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+}
+
+/// WebSocket error and result types.
+pub mod result {
+    #[doc(inline)]
+    pub use tokio_tungstenite::tungstenite::Error;
+
+    /// A type alias for `Result<T, Error>`.
+    pub type Result<T = ()> = std::result::Result<T, Error>;
+}
+
+/// A bidirectional stream of WebSocket messages to and from the client.
+///
+/// The stream implements [`Stream`] of incoming `Result<Message>` and
+/// [`Sink`](futures::sink::Sink) of outgoing [`Message`]s.
+pub type DuplexStream = WebSocketStream<IoStream>;
+
+/// A request guard for WebSocket upgrade requests.
+///
+/// The guard succeeds if the request is a valid WebSocket version 13 upgrade
+/// request and forwards otherwise. A `WebSocket` is converted into a responder
+/// which completes the upgrade via [`WebSocket::channel()`] or
+/// [`WebSocket::stream()`].
+pub struct WebSocket {
+    config: Config,
+    key: String,
+    shutdown: Shutdown,
+}
+
+impl WebSocket {
+    /// Sets the configuration of the WebSocket connection to `config`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::get;
+    /// use rocket::ws::{WebSocket, Channel, Config};
+    ///
+    /// #[get("/ws")]
+    /// fn ws(ws: WebSocket) -> Channel<'static> {
+    ///     let mut config = Config::default();
+    ///     config.max_message_size = Some(1 << 20);
+    ///     ws.config(config).channel(|_stream| Box::pin(async { Ok(()) }))
+    /// }
+    /// ```
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Returns a future that resolves when Rocket is shutting down.
+    ///
+    /// Handlers passed to [`WebSocket::channel()`] can select on the returned
+    /// future to close the connection cleanly on shutdown.
+    pub fn shutdown(&self) -> Shutdown {
+        self.shutdown.clone()
+    }
+
+    /// Creates a [`Channel`] which, once the connection is upgraded, calls
+    /// `handler` with a [`DuplexStream`] to the client.
+    ///
+    /// The connection is closed once the future returned by `handler`
+    /// resolves.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::get;
+    /// use rocket::ws::{WebSocket, Channel, Message};
+    /// use rocket::futures::{SinkExt, StreamExt};
+    ///
+    /// #[get("/hello")]
+    /// fn hello(ws: WebSocket) -> Channel<'static> {
+    ///     ws.channel(|mut stream| Box::pin(async move {
+    ///         stream.send(Message::text("Hello!")).await?;
+    ///         if let Some(name) = stream.next().await {
+    ///             stream.send(Message::text(format!("Hi, {}!", name?))).await?;
+    ///         }
+    ///
+    ///         Ok(())
+    ///     }))
+    /// }
+    /// ```
+    pub fn channel<'r, F>(self, handler: F) -> Channel<'r>
+        where F: FnOnce(DuplexStream) -> BoxFuture<'r, result::Result> + Send + 'r
+    {
+        Channel { ws: self, handler: Box::new(handler) }
+    }
+
+    /// Creates a [`MessageStream`] which, once the connection is upgraded,
+    /// calls `stream` with the stream of incoming messages and sends every
+    /// message yielded by the returned stream to the client.
+    ///
+    /// The connection is closed once the returned stream is exhausted or an
+    /// error is encountered.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::get;
+    /// use rocket::ws::{WebSocket, MessageStream, Message, result::Result};
+    /// use rocket::futures::{Stream, StreamExt};
+    ///
+    /// #[get("/shout")]
+    /// fn shout(ws: WebSocket) -> MessageStream<'static, impl Stream<Item = Result<Message>>> {
+    ///     ws.stream(|incoming| incoming.map(|message| {
+    ///         Ok(Message::text(message?.to_text()?.to_uppercase()))
+    ///     }))
+    /// }
+    /// ```
+    pub fn stream<'r, F, S>(self, stream: F) -> MessageStream<'r, S>
+        where F: FnOnce(SplitStream<DuplexStream>) -> S + Send + 'r,
+              S: Stream<Item = result::Result<Message>> + Send + 'r
+    {
+        MessageStream { ws: self, handler: Box::new(stream) }
+    }
+}
+
+/// A WebSocket responder which calls a handler with a [`DuplexStream`].
+///
+/// Created via [`WebSocket::channel()`].
+pub struct Channel<'r> {
+    ws: WebSocket,
+    handler: Box<dyn FnOnce(DuplexStream) -> BoxFuture<'r, result::Result> + Send + 'r>,
+}
+
+/// A WebSocket responder which sends the messages in a stream to the client.
+///
+/// Created via [`WebSocket::stream()`].
+pub struct MessageStream<'r, S> {
+    ws: WebSocket,
+    handler: Box<dyn FnOnce(SplitStream<DuplexStream>) -> S + Send + 'r>,
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for WebSocket {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        fn has_token(req: &Request<'_>, name: &str, token: &str) -> bool {
+            req.headers().get(name)
+                .flat_map(|v| v.split(','))
+                .any(|v| v.trim().eq_ignore_ascii_case(token))
+        }
+
+        let is_upgrade = has_token(req, "Connection", "upgrade");
+        let is_ws = has_token(req, "Upgrade", "websocket");
+        let is_13 = req.headers().get_one("Sec-WebSocket-Version") == Some("13");
+        let key = req.headers().get_one("Sec-WebSocket-Key");
+        match key {
+            Some(key) if is_upgrade && is_ws && is_13 => Outcome::Success(WebSocket {
+                config: Config::default(),
+                key: derive_accept_key(key.as_bytes()),
+                shutdown: req.rocket().shutdown(),
+            }),
+            _ => Outcome::Forward(()),
+        }
+    }
+}
+
+impl WebSocket {
+    async fn upgrade(&self, io: IoStream) -> DuplexStream {
+        WebSocketStream::from_raw_socket(io, Role::Server, Some(self.config)).await
+    }
+}
+
+/// Returns a response which completes the WebSocket handshake with `accept`
+/// and hands the upgraded connection to `handler`.
+fn upgrade_response<'o, H: IoHandler + 'o>(accept: String, handler: H) -> response::Result<'o> {
+    Response::build()
+        .raw_header("Sec-WebSocket-Accept", accept)
+        .upgrade("websocket", handler)
+        .ok()
+}
+
+impl<'r, 'o: 'r> Responder<'r, 'o> for Channel<'o> {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'o> {
+        upgrade_response(self.ws.key.clone(), self)
+    }
+}
+
+impl<'r, 'o: 'r, S> Responder<'r, 'o> for MessageStream<'o, S>
+    where S: Stream<Item = result::Result<Message>> + Send + 'o
+{
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'o> {
+        upgrade_response(self.ws.key.clone(), self)
+    }
+}
+
+#[crate::async_trait]
+impl IoHandler for Channel<'_> {
+    async fn io(self: Pin<Box<Self>>, io: IoStream) -> io::Result<()> {
+        let channel = Pin::into_inner(self);
+        let stream = channel.ws.upgrade(io).await;
+        (channel.handler)(stream).await.or_else(into_io_result)
+    }
+}
+
+#[crate::async_trait]
+impl<'r, S> IoHandler for MessageStream<'r, S>
+    where S: Stream<Item = result::Result<Message>> + Send + 'r
+{
+    async fn io(self: Pin<Box<Self>>, io: IoStream) -> io::Result<()> {
+        use frame::{CloseCode, CloseFrame};
+
+        let this = Pin::into_inner(self);
+        let (mut sink, source) = this.ws.upgrade(io).await.split();
+        let shutdown = this.ws.shutdown;
+        let mut stream = Box::pin((this.handler)(source).take_until(shutdown.clone()));
+        let result = async {
+            while let Some(message) = stream.next().await {
+                sink.send(message?).await?;
+            }
+
+            if shutdown.0.tripped() {
+                let reason = "server is shutting down".into();
+                let frame = CloseFrame { code: CloseCode::Away, reason };
+                sink.send(Message::Close(Some(frame))).await?;
+            }
+
+            Ok(())
+        }.await;
+
+        let _ = sink.close().await;
+        result.or_else(into_io_result)
+    }
+}
+
+/// Treats a closed connection as success and converts all other errors into
+/// `io::Error`s.
+fn into_io_result(e: result::Error) -> io::Result<()> {
+    use result::Error;
+
+    match e {
+        Error::ConnectionClosed | Error::AlreadyClosed => Ok(()),
+        Error::Io(e) => Err(e),
+        e => Err(io::Error::new(io::ErrorKind::Other, e)),
+    }
+}
@@ -0,0 +1,191 @@
+#[macro_use] extern crate rocket;
+
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use rocket::fairing::AccessLog;
+use rocket::http::Header;
+use rocket::local::blocking::Client;
+use rocket::response::stream::TextStream;
+
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl Buffer {
+    fn take_lines(&self) -> Vec<String> {
+        let bytes = std::mem::take(&mut *self.0.lock().unwrap());
+        String::from_utf8(bytes).unwrap().lines().map(String::from).collect()
+    }
+
+    /// Entries are written asynchronously: waits for `n` lines to be written.
+    fn wait_lines(&self, n: usize) -> Vec<String> {
+        let start = Instant::now();
+        while self.0.lock().unwrap().iter().filter(|&&b| b == b'\n').count() < n {
+            assert!(start.elapsed() < Duration::from_secs(5), "timed out waiting for entries");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        self.take_lines()
+    }
+}
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[get("/hello")]
+fn hello() -> &'static str {
+    "Hello, world!"
+}
+
+#[get("/stream")]
+fn stream() -> TextStream![&'static str] {
+    TextStream! {
+        yield "a";
+        yield "bc";
+    }
+}
+
+#[post("/empty")]
+fn empty() { }
+
+#[get("/user/<name>")]
+fn user(name: &str) -> &str {
+    name
+}
+
+fn client(log: AccessLog) -> Client {
+    let rocket = rocket::build()
+        .mount("/", routes![hello, stream, empty])
+        .mount("/api", routes![user])
+        .attach(log);
+
+    Client::debug(rocket).unwrap()
+}
+
+#[test]
+fn common_log_format() {
+    let buffer = Buffer::default();
+    let client = client(AccessLog::common().writer(buffer.clone()));
+
+    let response = client.get("/hello?a=b").remote("10.0.0.1:8000".parse().unwrap()).dispatch();
+    assert!(buffer.take_lines().is_empty());
+    assert_eq!(response.into_string().unwrap(), "Hello, world!");
+
+    // Local requests aren't received over HTTP and so have no version.
+    let lines = buffer.wait_lines(1);
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].starts_with("10.0.0.1 - - ["), "{}", lines[0]);
+    assert!(lines[0].ends_with(" +0000] \"GET /hello?a=b -\" 200 13"), "{}", lines[0]);
+
+    client.post("/empty").dispatch();
+    let lines = buffer.wait_lines(1);
+    assert!(lines[0].starts_with("- - - ["), "{}", lines[0]);
+    assert!(lines[0].ends_with("] \"POST /empty -\" 200 -"), "{}", lines[0]);
+
+    let response = client.get("/missing").dispatch();
+    drop(response);
+    let lines = buffer.wait_lines(1);
+    assert!(lines[0].contains("\"GET /missing -\" 404 "), "{}", lines[0]);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn json_lines() {
+    use rocket::serde::json::{self, Value};
+
+    let buffer = Buffer::default();
+    let client = client(AccessLog::json().writer(buffer.clone()));
+
+    let response = client.get("/stream")
+        .header(Header::new("User-Agent", "test \"agent\""))
+        .dispatch();
+
+    assert_eq!(response.into_string().unwrap(), "abc");
+
+    let lines = buffer.wait_lines(1);
+    assert_eq!(lines.len(), 1);
+    let entry: Value = json::from_str(&lines[0]).unwrap();
+    assert_eq!(entry["remote"], Value::Null);
+    assert_eq!(entry["method"], "GET");
+    assert_eq!(entry["uri"], "/stream");
+    assert_eq!(entry["route"], "stream");
+    assert_eq!(entry["route_uri"], "/stream");
+    assert_eq!(entry["status"], 200);
+    assert_eq!(entry["bytes"], 3);
+    assert_eq!(entry["user_agent"], "test \"agent\"");
+    assert_eq!(entry["referer"], Value::Null);
+    assert!(entry["latency_ms"].as_f64().unwrap() >= 0.0);
+    assert!(entry["time"].as_str().unwrap().ends_with('Z'));
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn json_lines_route_uri() {
+    use rocket::serde::json::{self, Value};
+
+    let buffer = Buffer::default();
+    let client = client(AccessLog::json().writer(buffer.clone()));
+    client.get("/api/user/bob").dispatch().into_string();
+    client.get("/api/user/alice").dispatch().into_string();
+    client.get("/missing").dispatch().into_string();
+
+    let entries: Vec<Value> = buffer.wait_lines(3).iter()
+        .map(|line| json::from_str(line).unwrap())
+        .collect();
+
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0]["uri"], "/api/user/bob");
+    assert_eq!(entries[1]["uri"], "/api/user/alice");
+    for entry in &entries[..2] {
+        assert_eq!(entry["route"], "user");
+        assert_eq!(entry["route_uri"], "/api/user/<name>");
+    }
+
+    assert_eq!(entries[2]["route"], Value::Null);
+    assert_eq!(entries[2]["route_uri"], Value::Null);
+}
+
+#[rocket::async_test]
+async fn common_log_format_records_http_version() {
+    use std::io::Read;
+    use std::net::{SocketAddr, TcpStream};
+
+    use rocket::fairing::AdHoc;
+    use rocket::futures::channel::oneshot;
+
+    let buffer = Buffer::default();
+    let (tx, rx) = oneshot::channel();
+    let config = rocket::Config { port: 0, ..rocket::Config::debug_default() };
+    let rocket = rocket::custom(config)
+        .mount("/", routes![hello])
+        .attach(AccessLog::common().writer(buffer.clone()))
+        .attach(AdHoc::on_liftoff("Shutdown", |rocket| Box::pin(async move {
+            let addr = SocketAddr::new(rocket.config().address, rocket.config().port);
+            tx.send((addr, rocket.shutdown())).unwrap();
+        })));
+
+    let launch = rocket::tokio::spawn(rocket.launch());
+    let (addr, shutdown) = rx.await.unwrap();
+    let lines = rocket::tokio::task::spawn_blocking(move || {
+        let mut socket = TcpStream::connect(addr).unwrap();
+        socket.write_all(b"GET /hello HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .unwrap();
+
+        let mut response = String::new();
+        socket.read_to_string(&mut response).unwrap();
+        assert!(response.ends_with("Hello, world!"));
+        buffer.wait_lines(1)
+    }).await.unwrap();
+
+    shutdown.notify();
+    launch.await.unwrap().unwrap();
+    assert!(lines[0].ends_with("] \"GET /hello HTTP/1.1\" 200 13"), "{}", lines[0]);
+}
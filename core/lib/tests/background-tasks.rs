@@ -0,0 +1,115 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use rocket::fairing::AdHoc;
+use rocket::tokio::time::{sleep, Duration};
+
+#[derive(Clone, Default)]
+struct Flags {
+    started: Arc<AtomicUsize>,
+    finished: Arc<AtomicBool>,
+}
+
+#[async_test]
+async fn task_is_awaited_on_shutdown() {
+    use rocket::local::asynchronous::Client;
+
+    let rocket = rocket::build()
+        .manage(Flags::default())
+        .manage_task("Worker", |rocket, shutdown| {
+            let flags = rocket.state::<Flags>().cloned().unwrap();
+            async move {
+                flags.started.fetch_add(1, Ordering::SeqCst);
+                shutdown.await;
+                sleep(Duration::from_millis(100)).await;
+                flags.finished.store(true, Ordering::SeqCst);
+            }
+        });
+
+    let client = Client::debug(rocket).await.unwrap();
+    let flags = client.rocket().state::<Flags>().cloned().unwrap();
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(flags.started.load(Ordering::SeqCst), 1);
+    assert!(!flags.finished.load(Ordering::SeqCst));
+
+    client.terminate().await;
+    assert!(flags.finished.load(Ordering::SeqCst));
+}
+
+#[async_test]
+async fn stalled_task_is_aborted() {
+    use rocket::local::asynchronous::Client;
+
+    let figment = rocket::Config::figment()
+        .merge(("shutdown.grace", 1))
+        .merge(("shutdown.mercy", 0));
+
+    let rocket = rocket::custom(figment)
+        .manage(Flags::default())
+        .attach(AdHoc::task("Stalled", |rocket, _| {
+            let flags = rocket.state::<Flags>().cloned().unwrap();
+            async move {
+                sleep(Duration::from_secs(60)).await;
+                flags.finished.store(true, Ordering::SeqCst);
+            }
+        }));
+
+    let client = Client::debug(rocket).await.unwrap();
+    let flags = client.rocket().state::<Flags>().cloned().unwrap();
+    let start = std::time::Instant::now();
+    client.terminate().await;
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert!(!flags.finished.load(Ordering::SeqCst));
+}
+
+#[async_test]
+async fn panicking_task_does_not_stall_shutdown() {
+    use rocket::local::asynchronous::Client;
+
+    let rocket = rocket::build()
+        .manage(Flags::default())
+        .manage_task("Panics", |_, _| async { panic!("task panic") })
+        .manage_task("Worker", |rocket, shutdown| {
+            let flags = rocket.state::<Flags>().cloned().unwrap();
+            async move {
+                shutdown.await;
+                flags.finished.store(true, Ordering::SeqCst);
+            }
+        });
+
+    let client = Client::debug(rocket).await.unwrap();
+    let flags = client.rocket().state::<Flags>().cloned().unwrap();
+    client.terminate().await;
+    assert!(flags.finished.load(Ordering::SeqCst));
+}
+
+#[async_test]
+async fn finished_and_stalled_tasks_are_joined_without_panicking() {
+    use rocket::local::asynchronous::Client;
+
+    let figment = rocket::Config::figment()
+        .merge(("shutdown.grace", 1))
+        .merge(("shutdown.mercy", 1));
+
+    let rocket = rocket::custom(figment)
+        .manage(Flags::default())
+        .attach(AdHoc::task("Finishes", |rocket, shutdown| {
+            let flags = rocket.state::<Flags>().cloned().unwrap();
+            async move {
+                shutdown.await;
+                flags.finished.store(true, Ordering::SeqCst);
+            }
+        }))
+        .attach(AdHoc::task("Stalled", |_, _| async {
+            sleep(Duration::from_secs(60)).await;
+        }));
+
+    let client = Client::debug(rocket).await.unwrap();
+    let flags = client.rocket().state::<Flags>().cloned().unwrap();
+    let start = std::time::Instant::now();
+    client.terminate().await;
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert!(flags.finished.load(Ordering::SeqCst));
+}
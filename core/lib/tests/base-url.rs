@@ -0,0 +1,82 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build, Config};
+use rocket::http::{Header, Status};
+use rocket::http::uri::Host;
+use rocket::local::blocking::Client;
+use rocket::request::BaseUrl;
+use rocket::response::Redirect;
+
+#[get("/verify/<token>")]
+fn verify(token: &str) -> &str {
+    token
+}
+
+#[get("/link")]
+fn link(base: BaseUrl) -> String {
+    uri!(base, verify("abc")).to_string()
+}
+
+#[get("/redirect")]
+fn redirect(base: BaseUrl) -> Redirect {
+    Redirect::to(uri!(base, verify("xyz")))
+}
+
+fn rocket(base_url: Option<&str>) -> Rocket<Build> {
+    let mut figment = Config::figment();
+    if let Some(base_url) = base_url {
+        figment = figment.merge((Config::BASE_URL, base_url));
+    }
+
+    rocket::custom(figment).mount("/", routes![verify, link, redirect])
+}
+
+#[test]
+fn configured_base_url_is_used() {
+    let client = Client::debug(rocket(Some("https://example.com/app"))).unwrap();
+    assert_eq!(client.rocket().config().base_url.as_ref().unwrap(), "https://example.com/app");
+
+    let response = client.get("/link")
+        .header(Header::new("X-Forwarded-Host", "attacker.com"))
+        .dispatch();
+
+    assert_eq!(response.into_string().unwrap(), "https://example.com/app/verify/abc");
+
+    let response = client.get("/redirect").dispatch();
+    assert_eq!(response.status(), Status::SeeOther);
+    assert_eq!(response.headers().get_one("Location").unwrap(),
+        "https://example.com/app/verify/xyz");
+}
+
+#[test]
+fn base_url_is_derived_from_host() {
+    let client = Client::debug(rocket(None)).unwrap();
+
+    // There's no host, so the guard forwards.
+    let response = client.get("/link").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+
+    let mut request = client.get("/link");
+    request.inner_mut().set_host(Host::from(uri!("rocket.rs:8443")));
+    assert_eq!(request.dispatch().into_string().unwrap(), "http://rocket.rs:8443/verify/abc");
+
+    // Forwarding headers can be forged by any client and are ignored.
+    let mut request = client.get("/link")
+        .header(Header::new("X-Forwarded-Host", "attacker.com"))
+        .header(Header::new("X-Forwarded-Proto", "https"));
+
+    request.inner_mut().set_host(Host::from(uri!("rocket.rs")));
+    assert_eq!(request.dispatch().into_string().unwrap(), "http://rocket.rs/verify/abc");
+
+    let response = client.get("/link")
+        .header(Header::new("X-Forwarded-Host", "attacker.com"))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+#[test]
+fn invalid_base_url_is_a_config_error() {
+    let figment = Config::figment().merge((Config::BASE_URL, "/not/absolute"));
+    assert!(figment.extract::<Config>().is_err());
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "cbor")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build, Config};
+use rocket::data::{Limits, ToByteUnit};
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::serde::{Serialize, Deserialize};
+use rocket::serde::cbor::{self, Cbor};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct Reading {
+    sensor: String,
+    value: f64,
+}
+
+#[post("/echo", format = "cbor", data = "<reading>")]
+fn echo(reading: Cbor<Reading>) -> Cbor<Reading> {
+    reading
+}
+
+fn rocket() -> Rocket<Build> {
+    let limits = Limits::default().limit("cbor", 64.bytes());
+    rocket::custom(Config { limits, ..Config::debug_default() }).mount("/", routes![echo])
+}
+
+#[test]
+fn cbor_round_trip() {
+    let client = Client::debug(rocket()).unwrap();
+    let reading = Reading { sensor: "t0".into(), value: 21.5 };
+    let response = client.post("/echo").cbor(&reading).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::CBOR));
+    assert_eq!(response.into_cbor::<Reading>(), Some(reading));
+}
+
+#[test]
+fn cbor_failures() {
+    let client = Client::debug(rocket()).unwrap();
+    let response = client.post("/echo").header(ContentType::CBOR).body([0xa2, 0x66]).dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let wrong_type = cbor::to_vec(&("t0", 21.5)).unwrap();
+    let response = client.post("/echo").header(ContentType::CBOR).body(wrong_type).dispatch();
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+
+    let reading = Reading { sensor: "t".repeat(100), value: 0.0 };
+    let response = client.post("/echo").cbor(&reading).dispatch();
+    assert_eq!(response.status(), Status::PayloadTooLarge);
+}
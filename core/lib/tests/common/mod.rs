@@ -0,0 +1,50 @@
+//! Helpers for tests that launch a server and talk to it over the network.
+
+#![allow(dead_code)]
+
+use std::net::SocketAddr;
+use std::path::Path;
+
+use rocket::{Build, Ignite, Rocket, Shutdown};
+use rocket::fairing::AdHoc;
+use rocket::futures::channel::oneshot;
+use rocket::tokio::task::JoinHandle;
+
+type Launch = JoinHandle<Result<Rocket<Ignite>, rocket::Error>>;
+
+/// Launches `rocket` in the background, returning the address it's listening
+/// on, its shutdown handle, and the launch task.
+async fn start(rocket: Rocket<Build>) -> (SocketAddr, Shutdown, Launch) {
+    let (tx, rx) = oneshot::channel();
+    let rocket = rocket.attach(AdHoc::on_liftoff("Addr", |rocket| Box::pin(async move {
+        let addr = SocketAddr::new(rocket.config().address, rocket.config().port);
+        tx.send((addr, rocket.shutdown())).unwrap();
+    })));
+
+    let launch = rocket::tokio::spawn(rocket.launch());
+    let (addr, shutdown) = rx.await.unwrap();
+    (addr, shutdown, launch)
+}
+
+/// Launches `rocket` and returns the address it is listening on.
+pub async fn launch(rocket: Rocket<Build>) -> (SocketAddr, Shutdown) {
+    let (addr, shutdown, _) = start(rocket).await;
+    (addr, shutdown)
+}
+
+/// Launches `rocket`, runs `f` in a blocking task with the address it's
+/// listening on, then shuts down.
+pub async fn serve<F>(rocket: Rocket<Build>, f: F)
+    where F: FnOnce(SocketAddr) + Send + 'static
+{
+    let (addr, shutdown, launch) = start(rocket).await;
+    rocket::tokio::task::spawn_blocking(move || f(addr)).await.unwrap();
+    shutdown.notify();
+    launch.await.unwrap().unwrap();
+}
+
+/// Reads the certificates in the PEM file at `path`.
+pub fn pem_certs<P: AsRef<Path>>(path: P) -> Vec<rustls::Certificate> {
+    let mut reader = std::io::BufReader::new(std::fs::File::open(path).unwrap());
+    rustls_pemfile::certs(&mut reader).unwrap().into_iter().map(rustls::Certificate).collect()
+}
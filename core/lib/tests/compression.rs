@@ -0,0 +1,260 @@
+#![cfg(feature = "compression")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::{Build, Rocket};
+use rocket::compression::{Compression, Encoding};
+use rocket::data::ToByteUnit;
+use rocket::http::{ContentType, Header};
+use rocket::local::asynchronous::{Client, LocalResponse};
+use rocket::response::stream::TextStream;
+use rocket::tokio::io::AsyncReadExt;
+
+use async_compression::tokio::bufread::{BrotliDecoder, GzipDecoder};
+use async_compression::tokio::write::{BrotliEncoder, GzipEncoder};
+use rocket::form::Form;
+use rocket::request::{self, FromRequest, Request};
+use rocket::tokio::io::AsyncWriteExt;
+
+fn text() -> String {
+    "Rocket compresses me well. ".repeat(100)
+}
+
+#[get("/text")]
+fn big_text() -> String {
+    text()
+}
+
+#[get("/small")]
+fn small_text() -> &'static str {
+    "too small to compress"
+}
+
+#[get("/png")]
+fn png() -> (ContentType, String) {
+    (ContentType::PNG, text())
+}
+
+#[get("/stream")]
+fn stream() -> TextStream![String] {
+    TextStream! {
+        for i in 0..10 {
+            yield format!("chunk {} ", i);
+        }
+    }
+}
+
+#[derive(Responder)]
+struct PreEncoded {
+    inner: String,
+    encoding: Header<'static>,
+}
+
+#[get("/encoded")]
+fn encoded() -> PreEncoded {
+    PreEncoded { inner: text(), encoding: Header::new("Content-Encoding", "custom") }
+}
+
+#[post("/echo", data = "<body>")]
+fn echo(body: String) -> String {
+    body
+}
+
+#[post("/form", data = "<form>")]
+fn form(form: Form<(String, usize)>) -> String {
+    format!("{}:{}", form.0, form.1)
+}
+
+struct ContentEncoding(String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ContentEncoding {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let encoding = req.headers().get_one("Content-Encoding").unwrap_or("none");
+        request::Outcome::Success(ContentEncoding(encoding.into()))
+    }
+}
+
+#[post("/encoding", data = "<body>")]
+fn encoding(encoding: ContentEncoding, body: Vec<u8>) -> String {
+    format!("{}:{}", encoding.0, body.len())
+}
+
+fn rocket(compression: Compression) -> Rocket<Build> {
+    rocket::build()
+        .mount("/", routes![big_text, small_text, png, stream, encoded, echo, form, encoding])
+        .attach(compression)
+}
+
+async fn compress(encoding: &str, data: &[u8]) -> Vec<u8> {
+    match encoding {
+        "gzip" => {
+            let mut encoder = GzipEncoder::new(vec![]);
+            encoder.write_all(data).await.unwrap();
+            encoder.shutdown().await.unwrap();
+            encoder.into_inner()
+        }
+        "br" => {
+            let mut encoder = BrotliEncoder::new(vec![]);
+            encoder.write_all(data).await.unwrap();
+            encoder.shutdown().await.unwrap();
+            encoder.into_inner()
+        }
+        _ => unreachable!(),
+    }
+}
+
+async fn get<'c>(client: &'c Client, uri: &'static str, accept: &'static str) -> LocalResponse<'c> {
+    client.get(uri).header(Header::new("Accept-Encoding", accept)).dispatch().await
+}
+
+async fn decode(response: LocalResponse<'_>) -> String {
+    let encoding = response.headers().get_one("Content-Encoding").map(|s| s.to_string());
+    let body = response.into_bytes().await.unwrap();
+    let mut decoded = String::new();
+    match encoding.as_deref() {
+        Some("gzip") => GzipDecoder::new(&body[..]).read_to_string(&mut decoded).await,
+        Some("br") => BrotliDecoder::new(&body[..]).read_to_string(&mut decoded).await,
+        None => return String::from_utf8(body).unwrap(),
+        Some(other) => panic!("unexpected encoding: {}", other),
+    }.unwrap();
+
+    decoded
+}
+
+#[rocket::async_test]
+async fn compresses_with_preferred_encoding() {
+    let client = Client::debug(rocket(Compression::default())).await.unwrap();
+
+    let response = get(&client, "/text", "gzip, br").await;
+    assert_eq!(response.headers().get_one("Content-Encoding"), Some("br"));
+    assert_eq!(response.headers().get_one("Vary"), Some("Accept-Encoding"));
+    assert!(response.headers().get_one("Content-Length").is_none());
+    assert_eq!(decode(response).await, text());
+
+    let response = get(&client, "/text", "gzip").await;
+    assert_eq!(response.headers().get_one("Content-Encoding"), Some("gzip"));
+    assert_eq!(decode(response).await, text());
+
+    let client = Client::debug(rocket(Compression::default().encodings([Encoding::Gzip])))
+        .await.unwrap();
+
+    let response = get(&client, "/text", "br, gzip;q=0.5").await;
+    assert_eq!(response.headers().get_one("Content-Encoding"), Some("gzip"));
+    assert_eq!(decode(response).await, text());
+}
+
+#[rocket::async_test]
+async fn skips_unaccepted_small_and_disallowed_bodies() {
+    let client = Client::debug(rocket(Compression::default())).await.unwrap();
+
+    let response = client.get("/text").dispatch().await;
+    assert!(response.headers().get_one("Content-Encoding").is_none());
+    assert_eq!(response.headers().get_one("Vary"), Some("Accept-Encoding"));
+    assert_eq!(response.into_string().await.unwrap(), text());
+
+    let response = get(&client, "/text", "identity").await;
+    assert!(response.headers().get_one("Content-Encoding").is_none());
+
+    let response = get(&client, "/small", "gzip").await;
+    assert!(response.headers().get_one("Content-Encoding").is_none());
+    assert!(response.headers().get_one("Vary").is_none());
+    assert_eq!(response.into_string().await.unwrap(), "too small to compress");
+
+    let response = get(&client, "/png", "gzip").await;
+    assert!(response.headers().get_one("Content-Encoding").is_none());
+    assert_eq!(response.into_string().await.unwrap(), text());
+
+    let response = get(&client, "/encoded", "gzip").await;
+    assert_eq!(response.headers().get_one("Content-Encoding"), Some("custom"));
+    assert_eq!(response.into_string().await.unwrap(), text());
+
+    let compression = Compression::default().min_size(1.bytes()).allow(ContentType::PNG.0);
+    let client = Client::debug(rocket(compression)).await.unwrap();
+    for uri in ["/small", "/png"] {
+        let response = get(&client, uri, "gzip").await;
+        assert_eq!(response.headers().get_one("Content-Encoding"), Some("gzip"));
+    }
+}
+
+#[rocket::async_test]
+async fn compresses_streams() {
+    let client = Client::debug(rocket(Compression::default())).await.unwrap();
+    let expected: String = (0..10).map(|i| format!("chunk {} ", i)).collect();
+
+    for encoding in ["gzip", "br"] {
+        let response = get(&client, "/stream", encoding).await;
+        assert_eq!(response.headers().get_one("Content-Encoding"), Some(encoding));
+        assert_eq!(decode(response).await, expected);
+    }
+}
+
+#[rocket::async_test]
+async fn decompresses_request_bodies() {
+    let client = Client::debug(rocket(Compression::default())).await.unwrap();
+
+    for encoding in ["gzip", "br"] {
+        let response = client.post("/echo")
+            .header(Header::new("Content-Encoding", encoding))
+            .body(compress(encoding, b"hello, compressed world").await)
+            .dispatch().await;
+
+        assert_eq!(response.into_string().await.unwrap(), "hello, compressed world");
+
+        let response = client.post("/form")
+            .header(ContentType::Form)
+            .header(Header::new("Content-Encoding", encoding))
+            .body(compress(encoding, b"0=bob&1=42").await)
+            .dispatch().await;
+
+        assert_eq!(response.into_string().await.unwrap(), "bob:42");
+
+        let response = client.post("/encoding")
+            .header(Header::new("Content-Encoding", encoding))
+            .body(compress(encoding, &[7; 100]).await)
+            .dispatch().await;
+
+        assert_eq!(response.into_string().await.unwrap(), "none:100");
+    }
+
+    // Unsupported encodings are passed through untouched.
+    let response = client.post("/encoding")
+        .header(Header::new("Content-Encoding", "zstd"))
+        .body(&[1, 2, 3])
+        .dispatch().await;
+
+    assert_eq!(response.into_string().await.unwrap(), "zstd:3");
+}
+
+#[rocket::async_test]
+async fn limits_decompressed_request_bodies() {
+    use rocket::data::Limits;
+    use rocket::http::Status;
+
+    let limits = Limits::default()
+        .limit("decompressed", 1.kibibytes())
+        .limit("bytes", 1.mebibytes());
+
+    let figment = rocket::Config::figment().merge(("limits", limits));
+    let rocket = rocket(Compression::default()).configure(figment);
+    let client = Client::debug(rocket).await.unwrap();
+
+    let bomb = compress("gzip", &[0; 512 * 1024]).await;
+    assert!(bomb.len() < 1024);
+
+    let response = client.post("/encoding")
+        .header(Header::new("Content-Encoding", "gzip"))
+        .body(&bomb)
+        .dispatch().await;
+
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = client.post("/encoding")
+        .header(Header::new("Content-Encoding", "gzip"))
+        .body(compress("gzip", &[0; 1024]).await)
+        .dispatch().await;
+
+    assert_eq!(response.into_string().await.unwrap(), "none:1024");
+}
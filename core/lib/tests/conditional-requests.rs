@@ -0,0 +1,97 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build};
+use rocket::fs::{FileServer, relative};
+use rocket::http::{Header, Status, EntityTag};
+use rocket::local::blocking::{Client, LocalResponse};
+use rocket::response::{Conditional, status};
+
+#[get("/tagged")]
+fn tagged() -> Conditional<&'static str> {
+    Conditional::new("tagged").etag(EntityTag::strong("v1").unwrap())
+}
+
+#[post("/tagged")]
+fn post_tagged() -> Conditional<&'static str> {
+    Conditional::new("posted").etag(EntityTag::strong("v1").unwrap())
+}
+
+#[get("/accepted")]
+fn accepted() -> Conditional<status::Accepted<&'static str>> {
+    Conditional::new(status::Accepted(Some("accepted"))).etag(EntityTag::strong("v1").unwrap())
+}
+
+fn rocket() -> Rocket<Build> {
+    rocket::build()
+        .mount("/", routes![tagged, post_tagged, accepted])
+        .mount("/static", FileServer::from(relative!("tests/static")))
+}
+
+fn get<'c>(client: &'c Client, path: &str, headers: &[(&'static str, &str)]) -> LocalResponse<'c> {
+    let mut request = client.get(path.to_string());
+    for &(name, value) in headers {
+        request = request.header(Header::new(name, value.to_string()));
+    }
+
+    request.dispatch()
+}
+
+#[test]
+fn named_files_are_conditional() {
+    let client = Client::debug(rocket()).unwrap();
+    let response = get(&client, "/static/other/hello.txt", &[]);
+    assert_eq!(response.status(), Status::Ok);
+    let etag = response.headers().get_one("ETag").map(String::from).unwrap();
+    let modified = response.headers().get_one("Last-Modified").map(String::from).unwrap();
+    assert!(modified.ends_with(" GMT"));
+
+    let response = get(&client, "/static/other/hello.txt", &[("If-None-Match", &etag)]);
+    assert_eq!(response.status(), Status::NotModified);
+    assert_eq!(response.headers().get_one("ETag"), Some(&*etag));
+    assert_eq!(response.headers().get_one("Last-Modified"), Some(&*modified));
+    assert!(response.into_bytes().unwrap_or_default().is_empty());
+
+    let headers = [("If-None-Match", "\"other\", W/\"x\""), ("If-Modified-Since", &modified)];
+    let response = get(&client, "/static/other/hello.txt", &headers);
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.into_string().unwrap().contains("Hi!"));
+
+    let response = get(&client, "/static/other/hello.txt", &[("If-Modified-Since", &modified)]);
+    assert_eq!(response.status(), Status::NotModified);
+
+    let old = "Sun, 06 Nov 1994 08:49:37 GMT";
+    let response = get(&client, "/static/other/hello.txt", &[("If-Modified-Since", old)]);
+    assert_eq!(response.status(), Status::Ok);
+
+    let invalid = "yesterday";
+    let response = get(&client, "/static/other/hello.txt", &[("If-Modified-Since", invalid)]);
+    assert_eq!(response.status(), Status::Ok);
+}
+
+#[test]
+fn conditional_wraps_any_responder() {
+    let client = Client::debug(rocket()).unwrap();
+    let response = get(&client, "/tagged", &[]);
+    assert_eq!(response.headers().get_one("ETag"), Some("\"v1\""));
+    assert!(response.headers().get_one("Last-Modified").is_none());
+    assert_eq!(response.into_string().unwrap(), "tagged");
+
+    // Weak comparison applies to `If-None-Match`.
+    let weak = get(&client, "/tagged", &[("If-None-Match", "W/\"v1\"")]);
+    assert_eq!(weak.status(), Status::NotModified);
+    assert_eq!(get(&client, "/tagged", &[("If-None-Match", "*")]).status(), Status::NotModified);
+    assert_eq!(get(&client, "/tagged", &[("If-None-Match", "\"v2\"")]).status(), Status::Ok);
+
+    // Without a last modification time, `If-Modified-Since` is ignored.
+    let since = "Sun, 06 Nov 2094 08:49:37 GMT";
+    assert_eq!(get(&client, "/tagged", &[("If-Modified-Since", since)]).status(), Status::Ok);
+
+    // Unsafe methods fail instead.
+    let response = client.post("/tagged").header(Header::new("If-None-Match", "\"v1\"")).dispatch();
+    assert_eq!(response.status(), Status::PreconditionFailed);
+
+    // Responses other than `200 OK` are sent as is.
+    let response = get(&client, "/accepted", &[("If-None-Match", "\"v1\"")]);
+    assert_eq!(response.status(), Status::Accepted);
+    assert_eq!(response.into_string().unwrap(), "accepted");
+}
@@ -0,0 +1,126 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::{Config, Request};
+use rocket::config::LogLevel;
+use rocket::data::{ByteUnit, Limits, ToByteUnit};
+use rocket::fairing::AdHoc;
+use rocket::figment::Figment;
+use rocket::http::{ContentType, Status};
+use rocket::local::asynchronous::Client;
+use rocket::request::{self, FromRequest};
+
+struct StringLimit(ByteUnit);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for StringLimit {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        request::Outcome::Success(StringLimit(req.limits().get("string").unwrap()))
+    }
+}
+
+#[get("/limit")]
+fn limit(limit: StringLimit) -> String {
+    limit.0.to_string()
+}
+
+#[post("/", data = "<body>")]
+fn echo(body: String) -> String {
+    body
+}
+
+fn figment() -> Figment {
+    Figment::from(Config::debug_default())
+        .merge((Config::LIMITS, Limits::new().limit("string", 8.bytes())))
+}
+
+#[rocket::async_test]
+async fn reload_applies_reload_safe_settings() {
+    let reloads = Arc::new(AtomicUsize::new(0));
+    let counter = reloads.clone();
+    let rocket = rocket::custom(figment())
+        .mount("/", routes![limit, echo])
+        .attach(AdHoc::on_config_reload("Counter", move |rocket| {
+            let counter = counter.clone();
+            Box::pin(async move {
+                assert_eq!(rocket.current_config().log_level, LogLevel::Critical);
+                counter.fetch_add(1, Ordering::SeqCst);
+            })
+        }));
+
+    let client = Client::untracked(rocket).await.unwrap();
+    let response = client.post("/").header(ContentType::Text).body("too long!").dispatch().await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let figment = figment()
+        .merge(("limits.string", 16.bytes()))
+        .merge((Config::LOG_LEVEL, LogLevel::Critical))
+        .merge((Config::PORT, 1234));
+
+    client.rocket().refresh_config_from(figment).await.unwrap();
+    assert_eq!(reloads.load(Ordering::SeqCst), 1);
+
+    let config = client.rocket().current_config();
+    assert_eq!(config.limits.get("string"), Some(16.bytes()));
+    assert_eq!(config.log_level, LogLevel::Critical);
+    assert_eq!(config.port, client.rocket().config().port);
+    assert_eq!(client.rocket().config().limits.get("string"), Some(8.bytes()));
+
+    let response = client.get("/limit").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "16B");
+
+    let response = client.post("/").header(ContentType::Text).body("too long!").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "too long!");
+}
+
+#[rocket::async_test]
+async fn failed_reload_changes_nothing() {
+    let reloads = Arc::new(AtomicUsize::new(0));
+    let counter = reloads.clone();
+    let rocket = rocket::custom(figment())
+        .mount("/", routes![limit])
+        .attach(AdHoc::on_config_reload("Counter", move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async {})
+        }));
+
+    let client = Client::untracked(rocket).await.unwrap();
+    let figment = figment()
+        .merge(("limits.string", 16.bytes()))
+        .merge((Config::LOG_LEVEL, "loud"));
+
+    assert!(client.rocket().refresh_config_from(figment).await.is_err());
+    assert_eq!(reloads.load(Ordering::SeqCst), 0);
+
+    let response = client.get("/limit").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "8B");
+}
+
+#[rocket::async_test]
+async fn refresh_retains_the_instance_figment() {
+    let client = Client::untracked(rocket::custom(figment()).mount("/", routes![limit])).await.unwrap();
+    client.rocket().refresh_config().await.unwrap();
+    assert_eq!(client.rocket().current_config().limits.get("string"), Some(8.bytes()));
+
+    let response = client.get("/limit").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "8B");
+}
+
+#[rocket::async_test]
+async fn reloads_release_previous_configs() {
+    let client = Client::untracked(rocket::custom(figment()).mount("/", routes![limit])).await.unwrap();
+    let launch = client.rocket().current_config();
+    for i in 1..=3 {
+        let figment = figment().merge(("limits.string", (8 * i).bytes()));
+        client.rocket().refresh_config_from(figment).await.unwrap();
+    }
+
+    let response = client.get("/limit").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "24B");
+    assert_eq!(Arc::strong_count(&launch), 1);
+    assert_eq!(Arc::strong_count(&client.rocket().current_config()), 2);
+}
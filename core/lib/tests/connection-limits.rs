@@ -0,0 +1,120 @@
+#[macro_use] extern crate rocket;
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::time::Duration;
+
+use rocket::{Build, Rocket};
+use rocket::config::Config;
+
+mod common;
+use common::serve;
+
+#[get("/")]
+fn index() -> &'static str {
+    "hello"
+}
+
+#[get("/slow")]
+async fn slow() -> &'static str {
+    rocket::tokio::time::sleep(Duration::from_millis(500)).await;
+    "slow"
+}
+
+fn rocket(config: Config) -> Rocket<Build> {
+    let config = Config { port: 0, ..config };
+    rocket::custom(config).mount("/", routes![index, slow])
+}
+
+/// Writes a `GET` for `path` to `socket`.
+fn send(socket: &mut TcpStream, path: &str, close: bool) {
+    let connection = if close { "close" } else { "keep-alive" };
+    write!(socket, "GET {} HTTP/1.1\r\nHost: localhost\r\n", path).unwrap();
+    write!(socket, "Connection: {}\r\n\r\n", connection).unwrap();
+}
+
+/// Reads from `socket` until it's closed or a read fails, returning what was
+/// read.
+fn read(socket: &mut TcpStream) -> String {
+    let mut response = vec![];
+    let _ = socket.read_to_end(&mut response);
+    String::from_utf8_lossy(&response).into()
+}
+
+fn get(addr: SocketAddr, path: &str) -> String {
+    let mut socket = TcpStream::connect(addr).unwrap();
+    send(&mut socket, path, true);
+    read(&mut socket)
+}
+
+#[rocket::async_test]
+async fn requests_beyond_max_requests_are_refused() {
+    let config = Config { max_requests: Some(1), ..Config::debug_default() };
+    serve(rocket(config), |addr| {
+        let mut pending = TcpStream::connect(addr).unwrap();
+        send(&mut pending, "/slow", true);
+        std::thread::sleep(Duration::from_millis(100));
+
+        assert!(get(addr, "/").starts_with("HTTP/1.1 503"));
+        assert!(read(&mut pending).ends_with("slow"));
+        assert!(get(addr, "/").ends_with("hello"));
+    }).await;
+}
+
+#[rocket::async_test]
+async fn connections_beyond_max_connections_wait() {
+    let config = Config { max_connections: Some(1), ..Config::debug_default() };
+    serve(rocket(config), |addr| {
+        let mut open = TcpStream::connect(addr).unwrap();
+        send(&mut open, "/", false);
+        std::thread::sleep(Duration::from_millis(100));
+
+        // The connection is queued by the OS but not accepted by Rocket.
+        let mut waiting = TcpStream::connect(addr).unwrap();
+        waiting.set_read_timeout(Some(Duration::from_millis(300))).unwrap();
+        send(&mut waiting, "/", true);
+        assert_eq!(read(&mut waiting), "");
+
+        // Once the open connection closes, the waiting one is served.
+        drop(open);
+        waiting.set_read_timeout(None).unwrap();
+        assert!(read(&mut waiting).ends_with("hello"));
+    }).await;
+}
+
+#[rocket::async_test]
+async fn connections_beyond_max_connections_per_ip_are_closed() {
+    let config = Config { max_connections_per_ip: Some(1), ..Config::debug_default() };
+    serve(rocket(config), |addr| {
+        let mut open = TcpStream::connect(addr).unwrap();
+        send(&mut open, "/", false);
+        std::thread::sleep(Duration::from_millis(100));
+
+        assert_eq!(get(addr, "/"), "");
+
+        drop(open);
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(get(addr, "/").ends_with("hello"));
+    }).await;
+}
+
+#[test]
+fn limits_are_configurable() {
+    figment::Jail::expect_with(|jail| {
+        jail.create_file("Rocket.toml", r#"
+            [default]
+            max_connections = 1024
+            max_connections_per_ip = 16
+        "#)?;
+
+        jail.set_env("ROCKET_MAX_REQUESTS", 256);
+        let config = Config::from(Config::figment());
+        assert_eq!(config.max_connections, Some(1024));
+        assert_eq!(config.max_connections_per_ip, Some(16));
+        assert_eq!(config.max_requests, Some(256));
+
+        let config = Config::from(Config::figment().merge((Config::MAX_REQUESTS, 8)));
+        assert_eq!(config.max_requests, Some(8));
+        Ok(())
+    });
+}
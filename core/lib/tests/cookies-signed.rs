@@ -0,0 +1,100 @@
+#![cfg(feature = "secrets")]
+#![deny(warnings)]
+
+use rocket::http::{Cookie, CookieJar, SameSite};
+use rocket::{get, post, routes};
+
+#[post("/")]
+fn cookie_add_signed(jar: &CookieJar<'_>) {
+    let mut cookie = Cookie::new("locale", "en-US");
+    jar.add_signed(cookie.clone());
+
+    // private: CookieJar::set_private_defaults(&mut cookie);
+    cookie.set_path("/");
+    cookie.set_same_site(SameSite::Strict);
+    cookie.set_http_only(true);
+    let expires = time::OffsetDateTime::now_utc() + time::Duration::weeks(1);
+    cookie.set_expires(expires);
+    let mut pending = jar.get_pending(cookie.name()).expect("pending cookie");
+    pending.set_expires(expires);
+    assert_eq!(pending, cookie);
+}
+
+#[get("/")]
+fn cookie_get_signed(jar: &CookieJar<'_>) -> String {
+    match jar.get_signed("locale") {
+        Some(cookie) => cookie.value().to_string(),
+        None => "unverified".into(),
+    }
+}
+
+#[post("/remove")]
+fn cookie_remove_signed(jar: &CookieJar<'_>) {
+    jar.remove_signed(Cookie::named("locale"));
+}
+
+#[cfg(test)]
+mod cookies_signed_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+    use rocket::{Build, Rocket};
+
+    fn rocket() -> Rocket<Build> {
+        rocket::build().mount("/", routes![
+            cookie_add_signed, cookie_get_signed, cookie_remove_signed
+        ])
+    }
+
+    #[test]
+    fn test_cookie_add_signed() {
+        let client = Client::debug(rocket()).unwrap();
+        let response = client.post("/").dispatch();
+        let cookies = response.cookies();
+        assert_eq!(cookies.iter().count(), 1);
+        assert_eq!(cookies.get_signed("locale").unwrap().value(), "en-US");
+
+        // The value is readable by the client, alongside its signature.
+        let raw = cookies.get("locale").unwrap().value();
+        assert_ne!(raw, "en-US");
+        assert!(raw.ends_with("en-US"));
+    }
+
+    #[test]
+    fn test_cookie_get_signed() {
+        let client = Client::debug(rocket()).unwrap();
+        let response = client.get("/")
+            .signed_cookie(Cookie::new("locale", "de-DE"))
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "de-DE");
+    }
+
+    #[test]
+    fn test_cookie_tampered_signed() {
+        let client = Client::untracked(rocket()).unwrap();
+        let response = client.get("/")
+            .cookie(Cookie::new("locale", "de-DE"))
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "unverified");
+
+        let response = client.post("/").dispatch();
+        let signed = response.cookies().get("locale").unwrap().value().to_string();
+        let tampered = signed.replace("en-US", "fr-FR");
+        let response = client.get("/")
+            .cookie(Cookie::new("locale", tampered))
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "unverified");
+    }
+
+    #[test]
+    fn test_cookie_remove_signed() {
+        let client = Client::debug(rocket()).unwrap();
+        client.post("/").dispatch();
+        assert_eq!(client.get("/").dispatch().into_string().unwrap(), "en-US");
+
+        client.post("/remove").dispatch();
+        assert_eq!(client.get("/").dispatch().into_string().unwrap(), "unverified");
+    }
+}
@@ -0,0 +1,132 @@
+#[macro_use] extern crate rocket;
+
+use rocket::cors::Cors;
+use rocket::http::{Header, Method, Status};
+use rocket::local::blocking::{Client, LocalResponse};
+use rocket::time::Duration;
+
+#[get("/")]
+fn index() -> &'static str {
+    "index"
+}
+
+#[options("/custom")]
+fn custom() -> &'static str {
+    "custom"
+}
+
+fn cors_client(cors: Cors) -> Client {
+    let rocket = rocket::build().mount("/", routes![index, custom]).attach(cors);
+    Client::debug(rocket).unwrap()
+}
+
+fn header<'a>(response: &'a LocalResponse<'_>, name: &str) -> Option<&'a str> {
+    response.headers().get_one(name)
+}
+
+#[test]
+fn simple_requests() {
+    let client = cors_client(Cors::new());
+    let response = client.get("/").dispatch();
+    assert_eq!(header(&response, "Access-Control-Allow-Origin"), None);
+
+    let response = client.get("/").header(Header::new("Origin", "https://a.com")).dispatch();
+    assert_eq!(header(&response, "Access-Control-Allow-Origin"), Some("*"));
+    assert_eq!(header(&response, "Vary"), None);
+    assert_eq!(response.into_string().unwrap(), "index");
+
+    let client = cors_client(Cors::new()
+        .allow_origin("https://a.com")
+        .expose_headers(["X-Request-Id"]));
+
+    let response = client.get("/").header(Header::new("Origin", "https://A.com")).dispatch();
+    assert_eq!(header(&response, "Access-Control-Allow-Origin"), Some("https://A.com"));
+    assert_eq!(header(&response, "Access-Control-Expose-Headers"), Some("X-Request-Id"));
+    assert_eq!(header(&response, "Vary"), Some("Origin"));
+
+    let response = client.get("/").header(Header::new("Origin", "https://b.com")).dispatch();
+    assert_eq!(header(&response, "Access-Control-Allow-Origin"), None);
+    assert_eq!(header(&response, "Vary"), Some("Origin"));
+    assert_eq!(response.status(), Status::Ok);
+}
+
+#[test]
+fn credentials_echo_origin() {
+    let client = cors_client(Cors::new().allow_credentials(true));
+    let response = client.get("/").header(Header::new("Origin", "https://a.com")).dispatch();
+    assert_eq!(header(&response, "Access-Control-Allow-Origin"), Some("https://a.com"));
+    assert_eq!(header(&response, "Access-Control-Allow-Credentials"), Some("true"));
+    assert_eq!(header(&response, "Vary"), Some("Origin"));
+}
+
+#[test]
+fn preflight_requests() {
+    let client = cors_client(Cors::new()
+        .allow_origin("https://a.com")
+        .allow_methods([Method::Get, Method::Post])
+        .allow_headers(["Content-Type", "X-Api-Key"])
+        .max_age(Duration::hours(1)));
+
+    let preflight = |method: &'static str, headers: &'static str| {
+        client.options("/")
+            .header(Header::new("Origin", "https://a.com"))
+            .header(Header::new("Access-Control-Request-Method", method))
+            .header(Header::new("Access-Control-Request-Headers", headers))
+            .dispatch()
+    };
+
+    let response = preflight("POST", "content-type, x-api-key");
+    assert_eq!(response.status(), Status::NoContent);
+    assert_eq!(header(&response, "Access-Control-Allow-Origin"), Some("https://a.com"));
+    assert_eq!(header(&response, "Access-Control-Allow-Methods"), Some("GET, POST"));
+    assert_eq!(header(&response, "Access-Control-Allow-Headers"),
+        Some("Content-Type, X-Api-Key"));
+    assert_eq!(header(&response, "Access-Control-Max-Age"), Some("3600"));
+    assert_eq!(header(&response, "Content-Type"), None);
+    assert!(response.into_string().unwrap_or_default().is_empty());
+
+    let response = preflight("DELETE", "");
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(header(&response, "Access-Control-Allow-Origin"), None);
+    assert_eq!(header(&response, "Access-Control-Allow-Methods"), None);
+
+    let response = preflight("GET", "X-Other");
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(header(&response, "Access-Control-Allow-Origin"), None);
+
+    let response = client.options("/")
+        .header(Header::new("Origin", "https://b.com"))
+        .header(Header::new("Access-Control-Request-Method", "GET"))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(header(&response, "Access-Control-Allow-Origin"), None);
+}
+
+#[test]
+fn preflight_echoes_requested_headers() {
+    let client = cors_client(Cors::new());
+    let response = client.options("/")
+        .header(Header::new("Origin", "https://a.com"))
+        .header(Header::new("Access-Control-Request-Method", "PUT"))
+        .header(Header::new("Access-Control-Request-Headers", "X-Anything"))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::NoContent);
+    assert_eq!(header(&response, "Access-Control-Allow-Origin"), Some("*"));
+    assert_eq!(header(&response, "Access-Control-Allow-Headers"), Some("X-Anything"));
+    assert_eq!(header(&response, "Access-Control-Max-Age"), None);
+}
+
+#[test]
+fn handled_preflight_keeps_response() {
+    let client = cors_client(Cors::new());
+    let response = client.options("/custom")
+        .header(Header::new("Origin", "https://a.com"))
+        .header(Header::new("Access-Control-Request-Method", "GET"))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(header(&response, "Access-Control-Allow-Origin"), Some("*"));
+    assert_eq!(response.into_string().unwrap(), "custom");
+}
@@ -0,0 +1,118 @@
+#![cfg(feature = "secrets")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::csrf::{Csrf, CsrfForm, CsrfToken};
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::blocking::Client;
+
+#[derive(FromForm)]
+struct Message<'r> {
+    text: &'r str,
+}
+
+#[get("/")]
+fn index(token: &CsrfToken) -> String {
+    token.form_field()
+}
+
+#[post("/message", data = "<message>")]
+fn message(message: CsrfForm<Message<'_>>) -> String {
+    message.text.into()
+}
+
+#[post("/action", data = "<_csrf>")]
+fn action(_csrf: Csrf) -> &'static str {
+    "action"
+}
+
+#[get("/api")]
+fn api_get(_csrf: Csrf) -> &'static str {
+    "get"
+}
+
+#[delete("/api")]
+fn api_delete(_csrf: Csrf) -> &'static str {
+    "delete"
+}
+
+fn client() -> Client {
+    let routes = routes![index, message, action, api_get, api_delete];
+    Client::tracked(rocket::build().mount("/", routes)).unwrap()
+}
+
+/// Requests a page, setting the CSRF cookies, and returns the token.
+fn token(client: &Client) -> String {
+    let response = client.get("/").dispatch();
+    let cookie = response.cookies().get(CsrfToken::COOKIE).map(|c| c.value().to_string());
+    let field = response.into_string().unwrap();
+    let token = cookie.expect("readable cookie");
+    assert!(field.contains(&format!("value=\"{}\"", token)));
+    token
+}
+
+#[test]
+fn token_is_stable() {
+    let client = client();
+    let token = token(&client);
+    assert_eq!(token.len(), 64);
+
+    let response = client.get("/").dispatch();
+    assert!(response.cookies().get(CsrfToken::COOKIE).is_none());
+    assert!(response.into_string().unwrap().contains(&token));
+
+    let other = Client::tracked(rocket::build().mount("/", routes![index])).unwrap();
+    assert_ne!(self::token(&other), token);
+}
+
+#[test]
+fn forms_require_token() {
+    let client = client();
+    let token = token(&client);
+
+    let post = |uri: &'static str, body: String| {
+        let response = client.post(uri).header(ContentType::Form).body(body).dispatch();
+        (response.status(), response.into_string())
+    };
+
+    let body = format!("text=hi&csrf_token={}", token);
+    assert_eq!(post("/message", body), (Status::Ok, Some("hi".into())));
+    assert_eq!(post("/message", "text=hi".into()).0, Status::Forbidden);
+    assert_eq!(post("/message", "text=hi&csrf_token=forged".into()).0, Status::Forbidden);
+
+    // The token is verified before the rest of the form.
+    assert_eq!(post("/message", "other=1".into()).0, Status::Forbidden);
+    let body = format!("other=1&csrf_token={}", token);
+    assert_eq!(post("/message", body).0, Status::UnprocessableEntity);
+
+    assert_eq!(post("/action", format!("csrf_token={}", token)).0, Status::Ok);
+    assert_eq!(post("/action", "other=1".into()).0, Status::Forbidden);
+}
+
+#[test]
+fn headers_verify_state_changing_requests() {
+    let client = client();
+    let token = token(&client);
+
+    assert_eq!(client.get("/api").dispatch().status(), Status::Ok);
+    assert_eq!(client.delete("/api").dispatch().status(), Status::Forbidden);
+
+    let response = client.delete("/api").header(Header::new("X-CSRF-Token", token)).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client.delete("/api").header(Header::new("X-CSRF-Token", "forged")).dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+}
+
+#[test]
+fn planted_cookie_is_rejected() {
+    let client = client();
+    let planted = "a".repeat(64);
+    let response = client.delete("/api")
+        .private_cookie(rocket::http::Cookie::new("unrelated", "value"))
+        .cookie(rocket::http::Cookie::new(CsrfToken::COOKIE, planted.clone()))
+        .header(Header::new("X-CSRF-Token", planted))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Forbidden);
+}
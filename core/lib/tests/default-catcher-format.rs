@@ -0,0 +1,53 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build, Config};
+use rocket::catcher::{self, Catcher};
+use rocket::config::ErrorFormat;
+use rocket::http::{Accept, ContentType, Header, Status};
+use rocket::local::blocking::Client;
+
+#[get("/")]
+fn index() -> Status {
+    Status::Unauthorized
+}
+
+fn rocket(error_format: ErrorFormat) -> Rocket<Build> {
+    let config = Config { error_format, ..Config::debug_default() };
+    rocket::custom(config)
+        .mount("/", routes![index])
+        .mount("/api", routes![index])
+        .register("/api", vec![Catcher::new(None, catcher::default_json)])
+}
+
+#[test]
+fn negotiated_format() {
+    let client = Client::debug(rocket(ErrorFormat::Negotiate)).unwrap();
+    let response = client.get("/").dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+
+    let response = client.get("/").header(Accept::JSON).dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+    assert!(response.into_string().unwrap().contains(r#""code": 401"#));
+
+    let accept = Header::new("Accept", "text/html, application/json; q=0.9");
+    let response = client.get("/").header(accept).dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+
+    let response = client.get("/api").header(Accept::HTML).dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+}
+
+#[test]
+fn configured_format() {
+    let client = Client::debug(rocket(ErrorFormat::Json)).unwrap();
+    let response = client.get("/unknown").header(Accept::HTML).dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+    assert!(response.into_string().unwrap().contains(r#""reason": "Not Found""#));
+
+    let client = Client::debug(rocket(ErrorFormat::Html)).unwrap();
+    let response = client.get("/").header(Accept::JSON).dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+}
@@ -0,0 +1,117 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Either;
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+use rocket::request::{self, Any, FromRequest, Request};
+
+macro_rules! header_guard {
+    ($T:ident, $name:literal) => {
+        #[derive(Debug)]
+        struct $T(String);
+
+        #[rocket::async_trait]
+        impl<'r> FromRequest<'r> for $T {
+            type Error = &'static str;
+
+            async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+                match req.headers().get_one($name) {
+                    Some("bad") => request::Outcome::Failure((Status::Unauthorized, $name)),
+                    Some(value) => request::Outcome::Success($T(value.into())),
+                    None => request::Outcome::Forward(()),
+                }
+            }
+        }
+    };
+}
+
+header_guard!(Session, "Session");
+header_guard!(ApiKey, "Api-Key");
+header_guard!(Token, "Token");
+
+#[get("/either")]
+fn either(auth: Either<Session, ApiKey>) -> String {
+    match auth {
+        Either::Left(session) => format!("session {}", session.0),
+        Either::Right(key) => format!("key {}", key.0),
+    }
+}
+
+#[get("/any")]
+fn any(auth: Any<(Session, ApiKey, Token)>) -> String {
+    let index = auth.index();
+    match auth.into_inner() {
+        (Some(session), None, None) => format!("{} session {}", index, session.0),
+        (None, Some(key), None) => format!("{} key {}", index, key.0),
+        (None, None, Some(token)) => format!("{} token {}", index, token.0),
+        _ => unreachable!("exactly one guard succeeds"),
+    }
+}
+
+#[get("/either/error")]
+fn either_error(auth: Result<Either<Session, ApiKey>, Either<&str, &str>>) -> String {
+    match auth {
+        Ok(_) => "ok".into(),
+        Err(Either::Left(e)) => format!("left {}", e),
+        Err(Either::Right(e)) => format!("right {}", e),
+    }
+}
+
+#[get("/any/error")]
+fn any_error(auth: Result<Any<(Session, ApiKey)>, (Option<&str>, Option<&str>)>) -> String {
+    match auth {
+        Ok(_) => "ok".into(),
+        Err(errors) => format!("{:?}", errors),
+    }
+}
+
+fn get(client: &Client, uri: &'static str, headers: &[(&'static str, &'static str)]) -> String {
+    let mut request = client.get(uri);
+    for (name, value) in headers {
+        request.add_header(Header::new(*name, *value));
+    }
+
+    let response = request.dispatch();
+    match response.status() == Status::Ok {
+        true => response.into_string().unwrap(),
+        false => response.status().to_string(),
+    }
+}
+
+#[test]
+fn either_tries_guards_in_order() {
+    let client = Client::debug_with(routes![either, either_error]).unwrap();
+    assert_eq!(get(&client, "/either", &[("Session", "a")]), "session a");
+    assert_eq!(get(&client, "/either", &[("Api-Key", "b")]), "key b");
+    assert_eq!(get(&client, "/either", &[("Session", "a"), ("Api-Key", "b")]), "session a");
+    assert_eq!(get(&client, "/either", &[]), Status::NotFound.to_string());
+
+    // A failure doesn't prevent a later guard from succeeding.
+    let headers = [("Session", "bad"), ("Api-Key", "b")];
+    assert_eq!(get(&client, "/either", &headers), "key b");
+    assert_eq!(get(&client, "/either/error", &[("Session", "bad")]), "left Session");
+    assert_eq!(get(&client, "/either/error", &[("Api-Key", "bad")]), "right Api-Key");
+
+    // If neither succeeds, the first failure is reported.
+    let headers = [("Session", "bad"), ("Api-Key", "bad")];
+    assert_eq!(get(&client, "/either", &headers), Status::Unauthorized.to_string());
+    assert_eq!(get(&client, "/either/error", &headers), "left Session");
+}
+
+#[test]
+fn any_tries_guards_in_order() {
+    let client = Client::debug_with(routes![any, any_error]).unwrap();
+    assert_eq!(get(&client, "/any", &[("Session", "a")]), "0 session a");
+    assert_eq!(get(&client, "/any", &[("Api-Key", "b")]), "1 key b");
+    assert_eq!(get(&client, "/any", &[("Token", "c")]), "2 token c");
+    assert_eq!(get(&client, "/any", &[("Api-Key", "b"), ("Token", "c")]), "1 key b");
+    assert_eq!(get(&client, "/any", &[]), Status::NotFound.to_string());
+
+    let headers = [("Api-Key", "bad"), ("Token", "c")];
+    assert_eq!(get(&client, "/any", &headers), "2 token c");
+    assert_eq!(get(&client, "/any/error", &headers), "(None, Some(\"Api-Key\"))");
+
+    let headers = [("Session", "bad"), ("Api-Key", "bad")];
+    assert_eq!(get(&client, "/any", &headers), Status::Unauthorized.to_string());
+    assert_eq!(get(&client, "/any/error", &headers), "(Some(\"Session\"), Some(\"Api-Key\"))");
+}
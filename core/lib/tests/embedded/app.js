@@ -0,0 +1 @@
+raw js
\ No newline at end of file
@@ -0,0 +1,87 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::{Arc, Mutex};
+
+use anyhow::Context;
+use rocket::{Request, Rocket, Build};
+use rocket::fairing::AdHoc;
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use rocket::response::Report;
+
+fn parse(n: &str) -> anyhow::Result<i64> {
+    n.parse::<i64>().with_context(|| format!("parsing {:?}", n))
+}
+
+#[get("/anyhow/<n>")]
+fn with_anyhow(n: &str) -> Result<String, Report> {
+    Ok((parse(n)? * 2).to_string())
+}
+
+#[get("/std/<n>")]
+fn with_std(n: &str) -> Result<String, Report> {
+    Ok((n.parse::<i64>()? * 2).to_string())
+}
+
+#[get("/status")]
+fn status() -> Result<(), Report> {
+    Err(Report::new("not for you").status(Status::Forbidden))
+}
+
+#[catch(default)]
+fn catcher(status: Status, req: &Request<'_>) -> String {
+    let error = req.recorded_error().map(|e| e.to_string()).unwrap_or_default();
+    format!("{}: {}", status.code, error)
+}
+
+type Reports = Arc<Mutex<Vec<(u16, Vec<String>)>>>;
+
+fn rocket(reports: Reports) -> Rocket<Build> {
+    rocket::build()
+        .mount("/", routes![with_anyhow, with_std, status])
+        .register("/", catchers![catcher])
+        .attach(AdHoc::on_error("Reporter", move |info, _| {
+            let mut chain = vec![];
+            let mut error = info.error().map(|e| e as &dyn std::error::Error);
+            while let Some(e) = error {
+                chain.push(e.to_string());
+                error = e.source();
+            }
+
+            reports.lock().unwrap().push((info.status().code, chain));
+            Box::pin(async move { })
+        }))
+}
+
+#[test]
+fn reports_forward_to_catchers() {
+    let client = Client::debug(rocket(Reports::default())).unwrap();
+    assert_eq!(client.get("/anyhow/21").dispatch().into_string().unwrap(), "42");
+    assert_eq!(client.get("/std/21").dispatch().into_string().unwrap(), "42");
+
+    let response = client.get("/anyhow/ten").dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+    assert_eq!(response.into_string().unwrap(), "500: parsing \"ten\"");
+
+    let response = client.get("/std/ten").dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+    assert_eq!(response.into_string().unwrap(), "500: invalid digit found in string");
+
+    let response = client.get("/status").dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+    assert_eq!(response.into_string().unwrap(), "403: not for you");
+}
+
+#[test]
+fn reports_are_reported_with_their_chain() {
+    let reports = Reports::default();
+    let client = Client::debug(rocket(reports.clone())).unwrap();
+    client.get("/anyhow/ten").dispatch();
+    client.get("/status").dispatch();
+
+    let reports = reports.lock().unwrap();
+    assert_eq!(reports.as_slice(), &[
+        (500, vec!["parsing \"ten\"".into(), "invalid digit found in string".into()]),
+        (403, vec!["not for you".into()]),
+    ]);
+}
@@ -0,0 +1,41 @@
+#[macro_use] extern crate rocket;
+
+use std::path::PathBuf;
+
+use rocket::http::{Method, Status};
+use rocket::local::blocking::Client;
+
+#[route("PROPFIND", uri = "/dav/<path..>")]
+fn propfind(path: PathBuf) -> String {
+    format!("properties of {}", path.display())
+}
+
+#[route("MKCOL", uri = "/dav/<path..>", data = "<body>")]
+fn mkcol(path: PathBuf, body: &str) -> String {
+    format!("created {} ({})", path.display(), body)
+}
+
+#[get("/dav/<path..>")]
+fn get(path: PathBuf) -> String {
+    format!("contents of {}", path.display())
+}
+
+#[test]
+fn extension_methods_are_routed() {
+    let route = &routes![propfind][0];
+    assert_eq!(route.method, Method::Extension("PROPFIND"));
+    assert_eq!(route.method.as_str(), "PROPFIND");
+
+    let client = Client::debug_with(routes![propfind, mkcol, get]).unwrap();
+    let response = client.req(Method::Extension("PROPFIND"), "/dav/a/b").dispatch();
+    assert_eq!(response.into_string().unwrap(), "properties of a/b");
+
+    let response = client.req(Method::Extension("MKCOL"), "/dav/c").body("new").dispatch();
+    assert_eq!(response.into_string().unwrap(), "created c (new)");
+
+    let response = client.get("/dav/a").dispatch();
+    assert_eq!(response.into_string().unwrap(), "contents of a");
+
+    let response = client.req(Method::Extension("LOCK"), "/dav/a").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+}
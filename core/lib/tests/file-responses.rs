@@ -0,0 +1,83 @@
+#[macro_use] extern crate rocket;
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::path::PathBuf;
+
+use rocket::{Build, Rocket, State};
+use rocket::config::Config;
+use rocket::fs::NamedFile;
+
+mod common;
+use common::serve;
+
+/// Spans several chunks, the last of them partial.
+const FILE_SIZE: usize = 300_000;
+
+#[get("/file")]
+async fn file(path: &State<PathBuf>) -> Option<NamedFile> {
+    NamedFile::open(path.inner()).await.ok()
+}
+
+/// A file of `FILE_SIZE` bytes, removed when dropped.
+struct TempFile(PathBuf, Vec<u8>);
+
+impl TempFile {
+    fn new(name: &str) -> TempFile {
+        let path = std::env::temp_dir().join(format!("rocket-{}-{}", name, std::process::id()));
+        let contents: Vec<u8> = (0..FILE_SIZE).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&path, &contents).unwrap();
+        TempFile(path, contents)
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+fn rocket(file: &TempFile) -> Rocket<Build> {
+    let config = Config { port: 0, ..Config::debug_default() };
+    rocket::custom(config)
+        .manage(file.0.clone())
+        .mount("/", routes![file])
+}
+
+/// Sends a `GET /file` with the `extra` header lines to `addr`, returning the
+/// response's head and body.
+fn get(addr: SocketAddr, extra: &str) -> (String, Vec<u8>) {
+    let mut socket = TcpStream::connect(addr).unwrap();
+    write!(socket, "GET /file HTTP/1.1\r\nHost: localhost\r\n{}", extra).unwrap();
+    write!(socket, "Connection: close\r\n\r\n").unwrap();
+
+    let mut response = vec![];
+    socket.read_to_end(&mut response).unwrap();
+    let split = response.windows(4).position(|w| w == b"\r\n\r\n").unwrap();
+    let head = String::from_utf8(response[..split].to_vec()).unwrap();
+    (head, response[split + 4..].to_vec())
+}
+
+#[rocket::async_test]
+async fn files_are_written_whole() {
+    let file = TempFile::new("whole-file");
+    let contents = file.1.clone();
+    serve(rocket(&file), move |addr| {
+        let (head, body) = get(addr, "");
+        assert!(head.starts_with("HTTP/1.1 200"));
+        assert!(head.contains(&format!("content-length: {}", FILE_SIZE)));
+        assert!(body == contents);
+    }).await;
+}
+
+#[rocket::async_test]
+async fn ranges_of_files_are_written() {
+    let file = TempFile::new("file-range");
+    let contents = file.1.clone();
+    serve(rocket(&file), move |addr| {
+        let (head, body) = get(addr, "Range: bytes=70000-200009\r\n");
+        assert!(head.starts_with("HTTP/1.1 206"));
+        assert!(head.contains("content-length: 130010"));
+        assert!(body == contents[70000..200010]);
+    }).await;
+}
@@ -186,3 +186,269 @@ fn test_redirection() {
     assert_eq!(response.status(), Status::PermanentRedirect);
     assert_eq!(response.headers().get("Location").next(), Some("/redir_index/"));
 }
+
+#[test]
+fn test_precompressed() {
+    use rocket::http::{Header, ContentType};
+
+    // Only which file is served matters, so the variants needn't be compressed.
+    let root = std::env::temp_dir().join(format!("rocket-precompressed-{}", std::process::id()));
+    std::fs::create_dir_all(&root).expect("create root");
+    for (file, contents) in &[
+        ("app.js", "raw js"), ("app.js.br", "br js"), ("app.js.gz", "gzip js"),
+        ("index.html", "raw html"), ("index.html.gz", "gzip html"), ("raw.txt", "raw txt"),
+    ] {
+        std::fs::write(root.join(file), contents).expect("write file");
+    }
+
+    let rocket = rocket::build()
+        .mount("/on", FileServer::new(&root, Options::Index | Options::Precompressed))
+        .mount("/off", FileServer::from(&root));
+
+    let client = Client::debug(rocket).expect("valid rocket");
+    let get = |path: &str, accept: &str| {
+        let response = client.get(path.to_string())
+            .header(Header::new("Accept-Encoding", accept.to_string()))
+            .dispatch();
+
+        let encoding = response.headers().get_one("Content-Encoding").map(String::from);
+        let vary = response.headers().get_one("Vary").map(String::from);
+        let content_type = response.content_type();
+        (response.into_string().unwrap(), encoding, content_type, vary)
+    };
+
+    let (body, encoding, content_type, vary) = get("/on/app.js", "gzip, br");
+    assert_eq!(body, "br js");
+    assert_eq!(encoding.as_deref(), Some("br"));
+    assert_eq!(content_type, Some(ContentType::JavaScript));
+    assert_eq!(vary.as_deref(), Some("Accept-Encoding"));
+
+    assert_eq!(get("/on/app.js", "gzip, br;q=0.5").0, "gzip js");
+    assert_eq!(get("/on/app.js", "*").0, "br js");
+    assert_eq!(get("/on/app.js", "br;q=0, *").0, "gzip js");
+
+    let (body, encoding, content_type, vary) = get("/on/app.js", "identity");
+    assert_eq!(body, "raw js");
+    assert_eq!(encoding, None);
+    assert_eq!(content_type, Some(ContentType::JavaScript));
+    assert_eq!(vary.as_deref(), Some("Accept-Encoding"));
+
+    // Index files are served precompressed, too; missing variants fall back.
+    let (body, encoding, content_type, _) = get("/on/", "br, gzip");
+    assert_eq!(body, "gzip html");
+    assert_eq!(encoding.as_deref(), Some("gzip"));
+    assert_eq!(content_type, Some(ContentType::HTML));
+    assert_eq!(get("/on/raw.txt", "br, gzip").0, "raw txt");
+
+    // Variants are served as is when requested directly.
+    let (body, encoding, content_type, _) = get("/on/app.js.gz", "gzip");
+    assert_eq!(body, "gzip js");
+    assert_eq!(encoding, None);
+    assert_eq!(content_type, Some(ContentType::GZIP));
+
+    // Without the option, the raw file is always served.
+    let (body, encoding, _, vary) = get("/off/app.js", "br, gzip");
+    assert_eq!(body, "raw js");
+    assert_eq!((encoding, vary), (None, None));
+
+    std::fs::remove_dir_all(&root).expect("remove root");
+}
+
+#[test]
+fn test_listing() {
+    use rocket::http::{Accept, ContentType};
+
+    let root = std::env::temp_dir().join(format!("rocket-listing-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(root.join("sub/.secret")).expect("create dirs");
+    std::fs::create_dir_all(root.join("site")).expect("create dirs");
+    for (file, contents) in &[
+        ("b.txt", "bee"), ("a & <b>.txt", "a"), (".hidden", "shh"),
+        ("sub/inner.txt", "inner"), ("site/index.html", "index"),
+    ] {
+        std::fs::write(root.join(file), contents).expect("write file");
+    }
+
+    let rocket = rocket::build()
+        .mount("/list", FileServer::new(&root, Options::Index | Options::Listing))
+        .mount("/dots", FileServer::new(&root, Options::Listing | Options::DotFiles))
+        .mount("/none", FileServer::new(&root, Options::Index));
+
+    let client = Client::debug(rocket).expect("valid rocket");
+    let get = |path: &str, accept: Accept| {
+        let response = client.get(path.to_string()).header(accept).dispatch();
+        let vary = response.headers().get_one("Vary").map(String::from);
+        (response.status(), response.content_type(), vary, response.into_string())
+    };
+
+    let (status, content_type, vary, body) = get("/list", Accept::HTML);
+    let body = body.unwrap();
+    assert_eq!(status, Status::Ok);
+    assert_eq!(content_type, Some(ContentType::HTML));
+    assert_eq!(vary.as_deref(), Some("Accept"));
+    assert!(body.contains("<title>Index of &#x2F;list&#x2F;</title>"));
+    assert!(body.contains(">a &amp; &lt;b&gt;.txt</a></td><td>1</td>"));
+    assert!(body.contains(">b.txt</a></td><td>3</td>"));
+    assert!(body.contains(">sub/</a></td><td>-</td>"));
+    assert!(!body.contains("hidden") && !body.contains("../"));
+
+    // Directories are listed first, then files, each by name.
+    let position = |needle: &str| body.find(needle).unwrap();
+    assert!(position(">sub/<") < position(">a &amp;"));
+    assert!(position(">a &amp;") < position(">b.txt<"));
+
+    let (_, content_type, _, body) = get("/list/sub/", Accept::JSON);
+    assert_eq!(content_type, Some(ContentType::JSON));
+    assert_eq!(body.unwrap(),
+        r#"[{"name":"inner.txt","href":"/list/sub/inner.txt","dir":false,"size":5}]"#);
+
+    let (_, _, _, body) = get("/list", Accept::JSON);
+    assert!(body.unwrap().contains(r#""href":"/list/a%20%26%20%3Cb%3E.txt""#));
+
+    // Directories with an index file are served it; files are served as is.
+    assert_eq!(get("/list/site", Accept::HTML).3.unwrap(), "index");
+    assert_eq!(get("/list/b.txt", Accept::JSON).3.unwrap(), "bee");
+
+    // Dotfiles are listed with `DotFiles`; without `Index`, so are all dirs.
+    let (_, _, _, body) = get("/dots/sub", Accept::JSON);
+    let secret = r#"[{"name":".secret","href":"/dots/sub/.secret/","dir":true"#;
+    assert!(body.unwrap().starts_with(secret));
+    let (_, _, _, body) = get("/dots/site", Accept::HTML);
+    let body = body.unwrap();
+    assert!(body.contains(">index.html</a>") && body.contains("../</a>"));
+
+    // Without `Listing`, directory requests without an index are forwarded.
+    assert_eq!(get("/none/sub", Accept::HTML).0, Status::NotFound);
+
+    std::fs::remove_dir_all(&root).expect("remove root");
+}
+
+#[test]
+fn test_fallback() {
+    let root = std::env::temp_dir().join(format!("rocket-fallback-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(root.join("assets")).expect("create dirs");
+    for (file, contents) in &[
+        ("index.html", "app"), ("assets/app.js", "js"), (".env", "secret"),
+    ] {
+        std::fs::write(root.join(file), contents).expect("write file");
+    }
+
+    let rocket = rocket::build()
+        .mount("/app", FileServer::new(&root, Options::Index | Options::Fallback("index.html")))
+        .mount("/raw", FileServer::new(&root, Options::Fallback("index.html")))
+        .mount("/gone", FileServer::new(&root, Options::Fallback("missing.html")));
+
+    let client = Client::debug(rocket).expect("valid rocket");
+    let get = |path: &str| {
+        let response = client.get(path.to_string()).dispatch();
+        (response.status(), response.into_string().unwrap_or_default())
+    };
+
+    // Existing files are served; everything else gets the fallback.
+    assert_eq!(get("/app/assets/app.js"), (Status::Ok, "js".into()));
+    for path in &["/app", "/app/", "/app/users/42", "/app/assets", "/app/.env", "/raw/x.js"] {
+        assert_eq!(get(path), (Status::Ok, "app".into()), "{}", path);
+    }
+
+    // A missing fallback forwards as usual.
+    assert_eq!(get("/gone/users/42").0, Status::NotFound);
+    assert_eq!(get("/gone/assets/app.js"), (Status::Ok, "js".into()));
+
+    std::fs::remove_dir_all(&root).expect("remove root");
+}
+
+#[test]
+fn test_cache_policies() {
+    use std::time::Duration;
+    use rocket::fs::CachePolicy;
+    use rocket::http::{CacheControl, CacheDirective};
+
+    let immutable = CacheControl::public(31536000).with(CacheDirective::Immutable);
+    let expiring = CachePolicy::new().expires(Duration::from_secs(60));
+    let server = FileServer::from(static_root())
+        .cache_for("inner/**", expiring)
+        .cache_for("other/*.txt", immutable.clone())
+        .cache_for("**/index.html", CacheControl::new([CacheDirective::NoCache]))
+        .cache(CacheControl::public(3600));
+
+    let rocket = rocket::build()
+        .mount("/cached", server)
+        .mount("/none", FileServer::from(static_root()));
+
+    let client = Client::debug(rocket).expect("valid rocket");
+    let headers = |path: &str| {
+        let response = client.get(path.to_string()).dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let cache_control = response.headers().get_one("Cache-Control").map(String::from);
+        let expires = response.headers().get_one("Expires").map(String::from);
+        (cache_control, expires)
+    };
+
+    assert_eq!(headers("/cached/other/hello.txt"), (Some(immutable.to_string()), None));
+    assert_eq!(headers("/cached/"), (Some("no-cache".into()), None));
+    assert_eq!(headers("/cached/inner/goodbye").0, None);
+    assert!(headers("/cached/inner/goodbye").1.unwrap().ends_with(" GMT"));
+
+    // The first matching glob wins, even for index files.
+    assert_eq!(headers("/cached/inner/").0, None);
+
+    // Without policies, no caching headers are sent.
+    assert_eq!(headers("/none/other/hello.txt"), (None, None));
+}
+
+#[test]
+fn test_embedded() {
+    use rocket::fs::{EmbeddedFileServer, embed};
+    use rocket::http::{ContentType, Header};
+
+    // Only which file is served matters, so the variants needn't be compressed.
+    let files = embed!("tests/embedded");
+    let paths = files.iter().map(|file| file.path()).collect::<Vec<_>>();
+    assert_eq!(paths, [
+        ".env", "app.js", "app.js.gz", "docs/guide.txt", "docs/index.html", "index.html"
+    ]);
+
+    let rocket = rocket::build()
+        .mount("/plain", EmbeddedFileServer::from(files))
+        .mount("/opts", EmbeddedFileServer::new(files, Options::NormalizeDirs
+            | Options::Index | Options::Precompressed | Options::Fallback("index.html")));
+
+    let client = Client::debug(rocket).expect("valid rocket");
+    let get = |path: &str| client.get(path.to_string()).dispatch();
+    let get_with = |path: &str, name: &'static str, value: &str| {
+        client.get(path.to_string()).header(Header::new(name, value.to_string())).dispatch()
+    };
+
+    let response = get("/plain/app.js");
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::JavaScript));
+    let etag = response.headers().get_one("ETag").map(String::from).expect("etag");
+    assert_eq!(response.into_string().unwrap(), "raw js");
+
+    assert_eq!(get("/plain/").into_string().unwrap(), "embedded index");
+    assert_eq!(get("/plain/docs").into_string().unwrap(), "docs index");
+    assert_eq!(get("/plain/docs/guide.txt").into_string().unwrap(), "guide");
+    assert_eq!(get("/plain/.env").status(), Status::NotFound);
+    assert_eq!(get("/plain/missing").status(), Status::NotFound);
+
+    // Matching `If-None-Match` headers are answered with a 304.
+    let response = get_with("/plain/app.js", "If-None-Match", &etag);
+    assert_eq!(response.status(), Status::NotModified);
+    assert_eq!(response.headers().get_one("ETag"), Some(&*etag));
+    assert!(response.into_bytes().unwrap_or_default().is_empty());
+
+    let response = get_with("/plain/app.js", "If-None-Match", "\"other\"");
+    assert_eq!(response.status(), Status::Ok);
+
+    // Options apply as they do to `FileServer`.
+    let response = get_with("/opts/app.js", "Accept-Encoding", "gzip");
+    assert_eq!(response.headers().get_one("Content-Encoding"), Some("gzip"));
+    assert_eq!(response.headers().get_one("Vary"), Some("Accept-Encoding"));
+    assert_eq!(response.content_type(), Some(ContentType::JavaScript));
+    assert_ne!(response.headers().get_one("ETag"), Some(&*etag));
+    assert_eq!(response.into_string().unwrap(), "gzip js");
+
+    assert_eq!(get("/opts/docs").status(), Status::PermanentRedirect);
+    assert_eq!(get("/opts/users/42").into_string().unwrap(), "embedded index");
+}
@@ -0,0 +1,114 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use rocket::{Request, Data, Rocket, Build, response};
+use rocket::fairing::{AdHoc, Fairing, Info, Kind};
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+use rocket::response::Responder;
+
+#[derive(Default)]
+struct Maintenance(AtomicBool);
+
+#[rocket::async_trait]
+impl Fairing for Maintenance {
+    fn info(&self) -> Info {
+        Info { name: "Maintenance", kind: Kind::Filter }
+    }
+
+    async fn on_filter<'r>(
+        &self,
+        _: &'r Request<'_>,
+        _: &mut Data<'r>
+    ) -> Option<response::Result<'r>> {
+        match self.0.load(Ordering::SeqCst) {
+            true => Some(Err(Status::ServiceUnavailable)),
+            false => None,
+        }
+    }
+}
+
+#[get("/")]
+fn index() -> &'static str {
+    "index"
+}
+
+#[catch(503)]
+fn unavailable() -> &'static str {
+    "down for maintenance"
+}
+
+fn rocket(maintenance: Arc<Maintenance>, filtered: Arc<AtomicUsize>) -> Rocket<Build> {
+    rocket::build()
+        .mount("/", routes![index])
+        .register("/", catchers![unavailable])
+        .attach(AdHoc::on_request("Role Rewriter", |req, _| Box::pin(async move {
+            if req.uri().path() == "/admin" {
+                req.add_header(Header::new("X-Role", "admin"));
+            }
+        })))
+        .attach(maintenance)
+        .attach(AdHoc::on_filter("Key Wall", |req, _| Box::pin(async move {
+            match req.headers().get_one("X-Role") {
+                Some(_) => None,
+                None => Some((Status::Unauthorized, "who are you?").respond_to(req)),
+            }
+        })))
+        .attach(AdHoc::on_filter("Counter", move |_, _| {
+            filtered.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move { None })
+        }))
+        .attach(AdHoc::on_response("Tagger", |_, res| Box::pin(async move {
+            res.set_raw_header("X-Tagged", "yes");
+        })))
+}
+
+#[test]
+fn filter_fairings_respond_directly() {
+    let filtered = Arc::new(AtomicUsize::new(0));
+    let client = Client::debug(rocket(Arc::default(), filtered.clone())).unwrap();
+
+    let response = client.get("/").dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(response.headers().get_one("X-Tagged"), Some("yes"));
+    assert_eq!(response.into_string().unwrap(), "who are you?");
+    assert_eq!(filtered.load(Ordering::SeqCst), 0);
+
+    let response = client.get("/").header(Header::new("X-Role", "guest")).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "index");
+    assert_eq!(filtered.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn filter_fairings_run_after_request_fairings() {
+    let filtered = Arc::new(AtomicUsize::new(0));
+    let client = Client::debug(rocket(Arc::default(), filtered.clone())).unwrap();
+
+    // The header added by the request fairing gets past the key wall, but
+    // there's no route for `/admin`.
+    let response = client.get("/admin").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(filtered.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn filter_fairings_fail_to_catchers() {
+    let maintenance = Arc::new(Maintenance::default());
+    let filtered = Arc::new(AtomicUsize::new(0));
+    let client = Client::debug(rocket(maintenance.clone(), filtered.clone())).unwrap();
+
+    maintenance.0.store(true, Ordering::SeqCst);
+    let response = client.get("/").header(Header::new("X-Role", "admin")).dispatch();
+    assert_eq!(response.status(), Status::ServiceUnavailable);
+    assert_eq!(response.headers().get_one("X-Tagged"), Some("yes"));
+    assert_eq!(response.into_string().unwrap(), "down for maintenance");
+    assert_eq!(filtered.load(Ordering::SeqCst), 0);
+
+    maintenance.0.store(false, Ordering::SeqCst);
+    let response = client.get("/").header(Header::new("X-Role", "admin")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "index");
+    assert_eq!(filtered.load(Ordering::SeqCst), 1);
+}
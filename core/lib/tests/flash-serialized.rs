@@ -0,0 +1,88 @@
+#![cfg(feature = "json")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::request::FlashMessage;
+use rocket::response::{Flash, Redirect};
+use rocket::serde::{Serialize, Deserialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct Notice {
+    field: String,
+    problems: Vec<String>,
+    attempt: u32,
+}
+
+#[post("/")]
+fn set() -> Result<Flash<Redirect>, Redirect> {
+    let notice = Notice {
+        field: "email; name".into(),
+        problems: vec!["taken".into(), "has \"quotes\", commas".into()],
+        attempt: 3,
+    };
+
+    Flash::serialized(Redirect::to("/"), "error", &notice)
+}
+
+#[post("/large")]
+fn large() -> Result<Flash<Redirect>, Redirect> {
+    Flash::serialized(Redirect::to("/large"), "error", &"x".repeat(4096))
+}
+
+#[get("/")]
+fn get(flash: Option<FlashMessage<'_>>) -> Option<String> {
+    let notice = flash?.into::<Notice>().ok()?;
+    Some(format!("{:?}", notice))
+}
+
+#[get("/kind")]
+fn kind(flash: Option<FlashMessage<'_>>) -> Option<String> {
+    flash.map(|f| f.kind().into())
+}
+
+mod flash_serialized_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+    use rocket::http::Status;
+
+    #[test]
+    fn payload_round_trips() {
+        let client = Client::debug_with(routes![set, get, kind]).unwrap();
+        client.post("/").dispatch();
+
+        let response = client.get("/").dispatch();
+        let expected = Notice {
+            field: "email; name".into(),
+            problems: vec!["taken".into(), "has \"quotes\", commas".into()],
+            attempt: 3,
+        };
+
+        assert_eq!(response.into_string(), Some(format!("{:?}", expected)));
+
+        // Retrieving the payload consumes the message.
+        let response = client.get("/").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn kind_is_preserved() {
+        let client = Client::debug_with(routes![set, get, kind]).unwrap();
+        client.post("/").dispatch();
+
+        let response = client.get("/kind").dispatch();
+        assert_eq!(response.into_string().unwrap(), "error");
+    }
+
+    #[test]
+    fn oversized_payload_is_rejected() {
+        let client = Client::debug_with(routes![large, get, kind]).unwrap();
+        let response = client.post("/large").dispatch();
+        assert_eq!(response.status(), Status::SeeOther);
+        assert_eq!(response.headers().get_one("Location"), Some("/large"));
+        assert!(response.cookies().get("_flash").is_none());
+
+        let response = client.get("/kind").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
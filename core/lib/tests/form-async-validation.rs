@@ -0,0 +1,169 @@
+#[macro_use] extern crate rocket;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use rocket::{Rocket, Build};
+use rocket::form::{self, Form, Error, Errors};
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::request::Request;
+
+struct Users(Vec<&'static str>);
+
+async fn available<'v>(name: &str, req: &Request<'_>) -> form::Result<'v, ()> {
+    let users = req.rocket().state::<Users>().unwrap();
+    rocket::tokio::task::yield_now().await;
+    if users.0.contains(&name) {
+        Err(Error::validation("username is taken"))?;
+    }
+
+    Ok(())
+}
+
+#[derive(FromForm)]
+struct Friend<'r> {
+    #[field(validate_async = with_async(|n, r| async move { available(n, r).await.is_err() }, "no such user"))]
+    name: &'r str,
+}
+
+#[derive(FromForm)]
+struct Signup<'r> {
+    #[field(validate = len(3..))]
+    #[field(validate_async = available())]
+    username: &'r str,
+    friends: Vec<Friend<'r>>,
+}
+
+#[derive(FromForm)]
+struct Search<'r, T> {
+    #[field(validate_async = available())]
+    user: &'r str,
+    extra: T,
+}
+
+#[derive(FromForm)]
+struct Groups<'r> {
+    named: HashMap<&'r str, Friend<'r>>,
+    pair: (Friend<'r>, Arc<Friend<'r>>),
+}
+
+#[get("/search?<q..>")]
+fn search<'r>(q: Search<'r, Option<Friend<'r>>>) -> String {
+    format!("{}:{}", q.user, q.extra.map(|f| f.name).unwrap_or("none"))
+}
+
+#[post("/search", data = "<form>")]
+fn search_form(form: Form<Search<'_, Vec<Friend<'_>>>>) -> String {
+    format!("{}:{}", form.user, form.extra.len())
+}
+
+#[get("/search", rank = 2)]
+fn search_forwarded() -> &'static str {
+    "forwarded"
+}
+
+#[post("/groups", data = "<form>")]
+fn groups(form: Result<Form<Groups<'_>>, Errors<'_>>) -> (Status, String) {
+    match form {
+        Ok(form) => (Status::Ok, format!("{}:{}", form.named.len(), form.pair.1.name)),
+        Err(errors) => (Status::UnprocessableEntity, names(&errors)),
+    }
+}
+
+fn names(errors: &Errors<'_>) -> String {
+    errors.iter()
+        .map(|e| e.name.as_ref().map(|n| n.to_string()).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[post("/", data = "<form>")]
+fn signup(form: Result<Form<Signup<'_>>, Errors<'_>>) -> (Status, String) {
+    match form {
+        Ok(form) => (Status::Ok, format!("{}:{}", form.username, form.friends.len())),
+        Err(errors) => (Status::UnprocessableEntity, names(&errors)),
+    }
+}
+
+fn rocket() -> Rocket<Build> {
+    rocket::build()
+        .manage(Users(vec!["bob", "sally"]))
+        .mount("/", routes![signup, search, search_forwarded, search_form, groups])
+}
+
+fn post(client: &Client, body: &str) -> (Status, String) {
+    post_to(client, "/", body)
+}
+
+fn post_to(client: &Client, uri: &'static str, body: &str) -> (Status, String) {
+    let response = client.post(uri).header(ContentType::Form).body(body).dispatch();
+    (response.status(), response.into_string().unwrap())
+}
+
+#[test]
+fn async_validators_run() {
+    let client = Client::debug(rocket()).unwrap();
+    assert_eq!(post(&client, "username=alice"), (Status::Ok, "alice:0".into()));
+    assert_eq!(post(&client, "username=alice&friends[0].name=bob"), (Status::Ok, "alice:1".into()));
+
+    let (status, names) = post(&client, "username=bob");
+    assert_eq!(status, Status::UnprocessableEntity);
+    assert_eq!(names, "username");
+
+    let (status, names) = post(&client, "username=alice&friends[0].name=carl");
+    assert_eq!(status, Status::UnprocessableEntity);
+    assert_eq!(names, "friends.0.name");
+
+    let body = "username=alice&friends[0].name=bob&friends[1].name=carl";
+    let (status, names) = post(&client, body);
+    assert_eq!(status, Status::UnprocessableEntity);
+    assert_eq!(names, "friends.1.name");
+}
+
+#[test]
+fn async_validators_run_in_containers() {
+    let client = Client::debug(rocket()).unwrap();
+    let body = "named[a].name=bob&pair.0.name=sally&pair.1.name=bob";
+    assert_eq!(post_to(&client, "/groups", body), (Status::Ok, "1:bob".into()));
+
+    let body = "user=alice&extra[0].name=bob";
+    assert_eq!(post_to(&client, "/search", body), (Status::Ok, "alice:1".into()));
+
+    let (status, _) = post_to(&client, "/search", "user=alice&extra[0].name=carl");
+    assert_eq!(status, Status::UnprocessableEntity);
+
+    let body = "named[a].name=carl&pair.0.name=sally&pair.1.name=bob";
+    let (status, names) = post_to(&client, "/groups", body);
+    assert_eq!(status, Status::UnprocessableEntity);
+    assert_eq!(names, "named.name");
+
+    let body = "named[a].name=bob&pair.0.name=carl&pair.1.name=dave";
+    let (status, names) = post_to(&client, "/groups", body);
+    assert_eq!(status, Status::UnprocessableEntity);
+    assert_eq!(names, "pair.0.name,pair.1.name");
+}
+
+#[test]
+fn async_validators_run_in_query_guards() {
+    let client = Client::debug(rocket()).unwrap();
+    let get = |uri: &'static str| client.get(uri).dispatch().into_string().unwrap();
+
+    assert_eq!(get("/search?user=alice"), "alice:none");
+    assert_eq!(get("/search?user=alice&extra.name=sally"), "alice:sally");
+
+    // A failing validation forwards.
+    assert_eq!(get("/search?user=bob"), "forwarded");
+    assert_eq!(get("/search?user=bob&extra.name=sally"), "forwarded");
+
+    // Failing validations in optional fields discard the value, as parsing does.
+    assert_eq!(get("/search?user=alice&extra.name=carl"), "alice:none");
+}
+
+#[test]
+fn async_validators_run_after_sync_validators() {
+    let client = Client::debug(rocket()).unwrap();
+    let (status, names) = post(&client, "username=al");
+    assert_eq!(status, Status::UnprocessableEntity);
+    assert_eq!(names, "username");
+}
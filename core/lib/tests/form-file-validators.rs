@@ -0,0 +1,102 @@
+#[macro_use] extern crate rocket;
+
+use rocket::data::ToByteUnit;
+use rocket::form::Form;
+use rocket::fs::TempFile;
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+
+#[derive(FromForm)]
+struct Upload<'r> {
+    #[field(validate = ext_in(["png", "jpg"]))]
+    #[field(validate = magic())]
+    #[field(validate = max_dimensions(4, 4))]
+    image: TempFile<'r>,
+    #[field(validate = total_len(..=16.bytes()))]
+    docs: Vec<TempFile<'r>>,
+}
+
+#[post("/", data = "<form>")]
+fn upload(form: Form<Upload<'_>>) -> String {
+    format!("{}:{}", form.image.len(), form.docs.len())
+}
+
+fn png(width: u32, height: u32) -> Vec<u8> {
+    let mut bytes = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+    bytes.extend_from_slice(&width.to_be_bytes());
+    bytes.extend_from_slice(&height.to_be_bytes());
+    bytes.extend_from_slice(&[8, 6, 0, 0, 0]);
+    bytes
+}
+
+fn jpeg(width: u16, height: u16) -> Vec<u8> {
+    jpeg_with_segments(width, height, 0)
+}
+
+/// A JPEG with `n` 32KiB application segments before its frame header.
+fn jpeg_with_segments(width: u16, height: u16, n: usize) -> Vec<u8> {
+    let mut bytes = vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x06, b'J', b'F', b'I', b'F'];
+    for _ in 0..n {
+        bytes.extend_from_slice(&[0xFF, 0xE1, 0x80, 0x00]);
+        bytes.resize(bytes.len() + 0x8000 - 2, 0);
+    }
+
+    bytes.extend_from_slice(&[0xFF, 0xC0, 0x00, 0x0B, 0x08]);
+    bytes.extend_from_slice(&height.to_be_bytes());
+    bytes.extend_from_slice(&width.to_be_bytes());
+    bytes.extend_from_slice(&[0x01, 0x01, 0x11, 0x00, 0xFF, 0xD9]);
+    bytes
+}
+
+fn post(client: &Client, image: (&str, &[u8]), docs: &[&[u8]]) -> Status {
+    let mut body = vec![];
+    let mut part = |name: &str, ct: &str, data: &[u8]| {
+        body.extend_from_slice(b"--X-BOUNDARY\r\n");
+        let disposition = format!("Content-Disposition: form-data; name=\"{}\"; filename=\"f\"", name);
+        body.extend_from_slice(disposition.as_bytes());
+        body.extend_from_slice(format!("\r\nContent-Type: {}\r\n\r\n", ct).as_bytes());
+        body.extend_from_slice(data);
+        body.extend_from_slice(b"\r\n");
+    };
+
+    part("image", image.0, image.1);
+    for doc in docs {
+        part("docs", "text/plain", doc);
+    }
+
+    body.extend_from_slice(b"--X-BOUNDARY--\r\n");
+    client.post("/")
+        .header("multipart/form-data; boundary=X-BOUNDARY".parse::<ContentType>().unwrap())
+        .body(body)
+        .dispatch()
+        .status()
+}
+
+#[test]
+fn file_validators() {
+    let client = Client::debug_with(routes![upload]).unwrap();
+    assert_eq!(post(&client, ("image/png", &png(4, 2)), &[b"hello", b"world"]), Status::Ok);
+    assert_eq!(post(&client, ("image/jpeg", &jpeg(3, 4)), &[]), Status::Ok);
+
+    // Wrong extension.
+    let gif = b"GIF89a\x02\x00\x02\x00";
+    assert_eq!(post(&client, ("image/gif", gif), &[]), Status::UnprocessableEntity);
+
+    // Declared type doesn't match contents.
+    assert_eq!(post(&client, ("image/png", gif), &[]), Status::UnprocessableEntity);
+    assert_eq!(post(&client, ("image/jpeg", &png(1, 1)), &[]), Status::UnprocessableEntity);
+
+    // Dimensions are found within the first 64KiB, but no further.
+    let image = jpeg_with_segments(3, 4, 1);
+    assert_eq!(post(&client, ("image/jpeg", &image), &[]), Status::Ok);
+    let image = jpeg_with_segments(3, 4, 2);
+    assert_eq!(post(&client, ("image/jpeg", &image), &[]), Status::UnprocessableEntity);
+
+    // Too large.
+    assert_eq!(post(&client, ("image/png", &png(5, 1)), &[]), Status::UnprocessableEntity);
+    assert_eq!(post(&client, ("image/jpeg", &jpeg(1, 8)), &[]), Status::UnprocessableEntity);
+
+    // Total upload too large.
+    let docs: &[&[u8]] = &[b"0123456789", b"0123456789"];
+    assert_eq!(post(&client, ("image/png", &png(1, 1)), docs), Status::PayloadTooLarge);
+}
@@ -0,0 +1,120 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build, State};
+use rocket::form::{Form, Contextual};
+use rocket::form::error::{Error, Messages};
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::blocking::Client;
+use rocket::request::{self, Request, FromRequest};
+
+struct Catalogs {
+    de: Messages,
+}
+
+struct Language(Option<&'static str>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Language {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let lang = req.headers().get_one("Accept-Language")
+            .filter(|lang| lang.starts_with("de"))
+            .map(|_| "de");
+
+        request::Outcome::Success(Language(lang))
+    }
+}
+
+#[derive(FromForm)]
+struct Signup<'r> {
+    #[field(validate = len(1..=8))]
+    name: &'r str,
+    #[field(validate = range(18..))]
+    age: u8,
+    #[field(validate = with(|s| s.contains('@'), "invalid email"))]
+    email: &'r str,
+}
+
+#[post("/", data = "<form>")]
+fn signup(
+    mut form: Form<Contextual<'_, Signup<'_>>>,
+    lang: Language,
+    catalogs: &State<Catalogs>,
+) -> (Status, String) {
+    if lang.0 == Some("de") {
+        form.context.localize(&catalogs.de);
+    }
+
+    let errors = form.context.errors()
+        .map(|e| format!("{}: {}", e.name.as_ref().unwrap(), e))
+        .collect::<Vec<_>>();
+
+    (form.context.status(), errors.join("\n"))
+}
+
+fn rocket() -> Rocket<Build> {
+    let de = Messages::new()
+        .message("missing", "{name} fehlt")
+        .message("invalid_length.empty", "{name} darf nicht leer sein")
+        .message("invalid_length.range", "{name} muss {min} bis {max} Zeichen lang sein")
+        .message("out_of_range.start", "muss mindestens {start} sein");
+
+    rocket::build()
+        .manage(Catalogs { de })
+        .mount("/", routes![signup])
+}
+
+fn post(client: &Client, lang: &'static str, body: &str) -> (Status, String) {
+    let response = client.post("/")
+        .header(ContentType::Form)
+        .header(Header::new("Accept-Language", lang))
+        .body(body.to_string())
+        .dispatch();
+
+    (response.status(), response.into_string().unwrap())
+}
+
+#[test]
+fn localizes_context_errors() {
+    let client = Client::debug(rocket()).unwrap();
+    let body = "name=abcdefghij&age=10&email=bob";
+
+    let (status, en) = post(&client, "en-US", body);
+    assert_eq!(status, Status::UnprocessableEntity);
+    assert_eq!(en, "name: length must be between 1 and 8\n\
+        age: value must be at least 18\n\
+        email: invalid email");
+
+    let (status, de) = post(&client, "de-DE", body);
+    assert_eq!(status, Status::UnprocessableEntity);
+    assert_eq!(de, "name: name muss 1 bis 8 Zeichen lang sein\n\
+        age: muss mindestens 18 sein\n\
+        email: invalid email");
+
+    let (_, de) = post(&client, "de", "age=20&email=a@b");
+    assert_eq!(de, "name: name fehlt");
+}
+
+#[test]
+fn closure_catalogs() {
+    let mut error = Error::validation("invalid email").with_name("email");
+    error.localize(&|e: &Error<'_>| match e.key() {
+        "validation" => Some(format!("<{}>", e.params()[0].1)),
+        _ => None,
+    });
+
+    assert_eq!(error.to_string(), "<invalid email>");
+}
+
+#[test]
+fn localized_errors_keep_their_kind() {
+    let mut error = Error::from((None, Some(8u64))).with_name("name");
+    error.localize(&|_: &Error<'_>| Some("zu lang".into()));
+    assert_eq!(error.to_string(), "zu lang");
+    assert_eq!(error.key(), "invalid_length.max");
+    assert_eq!(error.status(), Status::PayloadTooLarge);
+
+    error.localize(&|e: &Error<'_>| Some(format!("{} trop long", e.name.as_ref()?)));
+    assert_eq!(error.to_string(), "name trop long");
+}
@@ -0,0 +1,95 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use rocket::fairing::AdHoc;
+use rocket::health::{Health, Check};
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+
+fn toggle(name: &'static str, ok: Arc<AtomicBool>) -> Check {
+    Check::readiness(name, move |_| {
+        let ok = ok.load(Ordering::SeqCst);
+        Box::pin(async move {
+            match ok {
+                true => Ok(()),
+                false => Err("unavailable".into()),
+            }
+        })
+    })
+}
+
+fn get(client: &Client, uri: &'static str) -> (Status, String) {
+    let response = client.get(uri).dispatch();
+    (response.status(), response.into_string().unwrap())
+}
+
+#[test]
+fn endpoints_aggregate_checks() {
+    let ready = Arc::new(AtomicBool::new(true));
+    let health = Health::new()
+        .check(Check::liveness("ping", |_| Box::pin(async move { Ok(()) })))
+        .check(toggle("cache", ready.clone()));
+
+    let component = AdHoc::on_ignite("Component", |rocket| async move {
+        Health::register(rocket, Check::readiness("db", |_| Box::pin(async move { Ok(()) })))
+    });
+
+    let client = Client::debug(rocket::build().attach(component).attach(health)).unwrap();
+    assert_eq!(get(&client, "/healthz"), (Status::Ok, "[+]ping ok\nhealthz check passed".into()));
+    assert_eq!(get(&client, "/readyz"),
+        (Status::Ok, "[+]db ok\n[+]cache ok\nreadyz check passed".into()));
+
+    ready.store(false, Ordering::SeqCst);
+    assert_eq!(get(&client, "/healthz").0, Status::Ok);
+    assert_eq!(get(&client, "/readyz"), (Status::ServiceUnavailable,
+        "[+]db ok\n[-]cache failed: unavailable\nreadyz check failed".into()));
+}
+
+#[test]
+fn components_register_before_and_after_fairing() {
+    let component = |name: &'static str| AdHoc::on_ignite(name, move |rocket| async move {
+        Health::register(rocket, Check::readiness(name, |_| Box::pin(async move { Ok(()) })))
+    });
+
+    let rocket = rocket::build()
+        .attach(component("before"))
+        .attach(Health::new())
+        .attach(component("after"));
+
+    let client = Client::debug(rocket).unwrap();
+    let (status, body) = get(&client, "/readyz");
+    assert_eq!(status, Status::Ok);
+    assert!(body.contains("[+]before ok") && body.contains("[+]after ok"));
+}
+
+#[test]
+fn checks_time_out() {
+    let slow = Check::readiness("slow", |_| Box::pin(async move {
+        rocket::tokio::time::sleep(Duration::from_secs(30)).await;
+        Ok(())
+    }));
+
+    let health = Health::new().check(slow.timeout(Duration::from_millis(10)));
+    let client = Client::debug(rocket::build().attach(health)).unwrap();
+    let (status, body) = get(&client, "/readyz");
+    assert_eq!(status, Status::ServiceUnavailable);
+    assert!(body.starts_with("[-]slow failed: timed out after 10ms"));
+}
+
+#[test]
+fn readiness_fails_on_shutdown() {
+    let client = Client::debug(rocket::build().attach(Health::new())).unwrap();
+    assert_eq!(get(&client, "/readyz"), (Status::Ok, "readyz check passed".into()));
+
+    client.rocket().shutdown().notify();
+    assert_eq!(get(&client, "/healthz").0, Status::Ok);
+    assert_eq!(get(&client, "/readyz").0, Status::ServiceUnavailable);
+}
+
+#[test]
+fn no_endpoints_without_fairing() {
+    let client = Client::debug(rocket::build()).unwrap();
+    assert_eq!(get(&client, "/healthz").0, Status::NotFound);
+    assert_eq!(get(&client, "/readyz").0, Status::NotFound);
+}
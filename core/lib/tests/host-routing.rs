@@ -0,0 +1,85 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Rocket;
+use rocket::error::ErrorKind;
+use rocket::http::Status;
+use rocket::http::uri::Host;
+use rocket::local::blocking::Client;
+use rocket::request::Subdomain;
+
+#[get("/")]
+fn tenant(tenant: Subdomain<'_>) -> String {
+    format!("tenant: {}", tenant)
+}
+
+#[get("/")]
+fn admin(host: &Host<'_>) -> String {
+    format!("admin: {}", host)
+}
+
+#[get("/")]
+fn home(tenant: Option<Subdomain<'_>>) -> String {
+    format!("home: {:?}", tenant.map(|t| t.as_str()))
+}
+
+#[get("/only")]
+fn only() -> &'static str {
+    "only"
+}
+
+fn get(client: &Client, host: Option<&'static str>, path: &'static str) -> (Status, String) {
+    let mut request = client.get(path);
+    if let Some(host) = host {
+        request.inner_mut().set_host(Host::parse(host).unwrap());
+    }
+
+    let response = request.dispatch();
+    (response.status(), response.into_string().unwrap_or_default())
+}
+
+#[test]
+fn routes_are_restricted_to_hosts() {
+    let rocket = rocket::build()
+        .mount_host("*.example.com", "/", routes![tenant])
+        .mount_host("admin.example.com", "/", routes![admin])
+        .mount_host("example.com", "/", routes![only])
+        .mount("/", routes![home])
+        .rank_routes(|route| match route.host.as_ref() {
+            Some(host) if !host.is_wildcard() => route.rank - 1,
+            _ => route.rank,
+        });
+
+    let client = Client::debug(rocket).unwrap();
+    let get = |host, path| get(&client, host, path);
+    assert_eq!(get(Some("acme.example.com"), "/").1, "tenant: acme");
+    assert_eq!(get(Some("a.b.EXAMPLE.com:8000"), "/").1, "tenant: a.b");
+    assert_eq!(get(Some("admin.example.com"), "/").1, "admin: admin.example.com");
+    assert_eq!(get(Some("example.com"), "/").1, "home: None");
+    assert_eq!(get(Some("rocket.rs"), "/").1, "home: None");
+    assert_eq!(get(None, "/").1, "home: None");
+
+    assert_eq!(get(Some("example.com"), "/only").1, "only");
+    assert_eq!(get(Some("acme.example.com"), "/only").0, Status::NotFound);
+    assert_eq!(get(None, "/only").0, Status::NotFound);
+}
+
+#[test]
+fn overlapping_host_patterns_collide() {
+    let rocket = rocket::build()
+        .mount_host("*.example.com", "/", routes![tenant])
+        .mount_host("admin.example.com", "/", routes![home]);
+    let error = Client::debug(rocket).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::Collisions(_)));
+
+    let rocket = rocket::build()
+        .mount_host("*.example.com", "/", routes![tenant])
+        .mount_host("example.com", "/", routes![home])
+        .mount("/", routes![home]);
+    assert!(Client::debug(rocket).is_ok());
+}
+
+#[test]
+#[should_panic]
+fn invalid_host_patterns_panic() {
+    let _ = Rocket::build().mount_host("*.example.com:80", "/", routes![tenant]);
+}
@@ -0,0 +1,110 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Request, Route, Data, Rocket, Orbit, route};
+use rocket::http::{Method, Status};
+use rocket::local::blocking::Client;
+use rocket::request::{self, FromRequest};
+
+struct Hot<'r>(&'r Rocket<Orbit>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Hot<'r> {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        request::Outcome::Success(Hot(req.rocket()))
+    }
+}
+
+fn hook<'r>(req: &'r Request, _: Data<'r>) -> route::BoxFuture<'r> {
+    let name = req.routed_segment(0).unwrap_or("none").to_string();
+    route::Outcome::from(req, format!("hook: {}", name)).pin()
+}
+
+fn hook_route(path: &str) -> Route {
+    Route::new(Method::Post, path, hook)
+}
+
+#[put("/register/<name>")]
+fn register(hot: Hot<'_>, name: &str) -> Status {
+    match hot.0.hot_mount("/hooks", vec![hook_route(&format!("/{}", name))]) {
+        Ok(()) => Status::Created,
+        Err(_) => Status::Conflict,
+    }
+}
+
+#[delete("/register/<name>")]
+fn unregister(hot: Hot<'_>, name: &str) -> Status {
+    let path = format!("/hooks/{}", name);
+    match hot.0.hot_unmount("/hooks", |route| route.uri.path() == path).len() {
+        0 => Status::NotFound,
+        _ => Status::NoContent,
+    }
+}
+
+#[post("/hooks/static")]
+fn fixed() -> &'static str {
+    "static"
+}
+
+fn client() -> Client {
+    let rocket = rocket::build().mount("/", routes![register, unregister, fixed]);
+    Client::debug(rocket).unwrap()
+}
+
+#[test]
+fn routes_can_be_mounted_and_unmounted_after_launch() {
+    let client = client();
+    assert_eq!(client.post("/hooks/a").dispatch().status(), Status::NotFound);
+
+    assert_eq!(client.put("/register/a").dispatch().status(), Status::Created);
+    assert_eq!(client.put("/register/b").dispatch().status(), Status::Created);
+    assert_eq!(client.put("/register/a").dispatch().status(), Status::Conflict);
+    assert_eq!(client.post("/hooks/a").dispatch().into_string().unwrap(), "hook: a");
+    assert_eq!(client.post("/hooks/b").dispatch().into_string().unwrap(), "hook: b");
+
+    assert_eq!(client.delete("/register/a").dispatch().status(), Status::NoContent);
+    assert_eq!(client.delete("/register/a").dispatch().status(), Status::NotFound);
+    assert_eq!(client.post("/hooks/a").dispatch().status(), Status::NotFound);
+    assert_eq!(client.post("/hooks/b").dispatch().into_string().unwrap(), "hook: b");
+}
+
+#[test]
+fn hot_routes_are_tried_after_launch_routes() {
+    let rocket = rocket::build().mount("/", routes![fixed]);
+    let client = Client::debug(rocket).unwrap();
+    assert_eq!(client.post("/hooks/a").dispatch().status(), Status::NotFound);
+
+    client.rocket().hot_mount("/hooks", vec![hook_route("/<name>")]).unwrap();
+    assert_eq!(client.post("/hooks/a").dispatch().into_string().unwrap(), "hook: a");
+    assert_eq!(client.post("/hooks/static").dispatch().into_string().unwrap(), "static");
+
+    let removed = client.rocket().hot_unmount("/hooks", |_| true);
+    assert_eq!(removed.len(), 1);
+    assert_eq!(removed[0].uri.path(), "/hooks/<name>");
+    assert_eq!(client.post("/hooks/a").dispatch().status(), Status::NotFound);
+}
+
+#[test]
+fn colliding_hot_routes_are_rejected() {
+    let client = client();
+    let rocket = client.rocket();
+
+    // A new route can't collide with a launch route, a hot route, or a new
+    // route. On any collision, none of the new routes are mounted.
+    let collisions = rocket.hot_mount("/", vec![hook_route("/hooks/static")]).unwrap_err();
+    assert_eq!(collisions.len(), 1);
+    assert_eq!(collisions[0].1.name.as_deref(), Some("fixed"));
+
+    rocket.hot_mount("/hooks", vec![hook_route("/a")]).unwrap();
+    let collisions = rocket.hot_mount("/hooks", vec![hook_route("/b"), hook_route("/a")]);
+    assert_eq!(collisions.unwrap_err().len(), 1);
+    assert_eq!(client.post("/hooks/b").dispatch().status(), Status::NotFound);
+
+    let collisions = rocket.hot_mount("/hooks", vec![hook_route("/c"), hook_route("/c")]);
+    assert_eq!(collisions.unwrap_err().len(), 1);
+
+    // Routes are only unmounted from the requested base.
+    assert!(rocket.hot_unmount("/", |_| true).is_empty());
+    assert_eq!(rocket.hot_unmount("/hooks/", |_| true).len(), 1);
+}
@@ -0,0 +1,114 @@
+#![cfg(feature = "http3")]
+
+use std::sync::Arc;
+use std::net::SocketAddr;
+
+use rocket::{get, post, routes, Build, Rocket};
+use rocket::fs::relative;
+use rocket::config::{Config, TlsConfig};
+
+use bytes::{Buf, Bytes};
+
+mod common;
+use common::launch;
+
+#[get("/hello")]
+fn hello() -> &'static str {
+    "Hello, HTTP/3!"
+}
+
+#[post("/echo", data = "<body>")]
+fn echo(body: String) -> String {
+    body
+}
+
+fn rocket() -> Rocket<Build> {
+    let cert_path = relative!("../../examples/tls/private/rsa_sha256_cert.pem");
+    let key_path = relative!("../../examples/tls/private/rsa_sha256_key.pem");
+    let tls = TlsConfig::from_paths(cert_path, key_path);
+    rocket::custom(Config { port: 0, tls: Some(tls), ..Config::debug_default() })
+        .mount("/", routes![hello, echo])
+}
+
+/// Returns a QUIC endpoint which trusts the example CA and speaks HTTP/3.
+fn client_endpoint() -> quinn::Endpoint {
+    let ca_path = relative!("../../examples/tls/private/ca_cert.pem");
+    let mut reader = std::io::BufReader::new(std::fs::File::open(ca_path).unwrap());
+    let mut roots = rustls::RootCertStore::empty();
+    for cert in rustls_pemfile::certs(&mut reader).unwrap() {
+        roots.add(&rustls::Certificate(cert)).unwrap();
+    }
+
+    let mut tls = rustls::ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+
+    tls.alpn_protocols = vec![b"h3".to_vec()];
+    let mut endpoint = quinn::Endpoint::client("127.0.0.1:0".parse().unwrap()).unwrap();
+    endpoint.set_default_client_config(quinn::ClientConfig::new(Arc::new(tls)));
+    endpoint
+}
+
+async fn request(
+    addr: SocketAddr,
+    request: http::Request<()>,
+    body: Option<&'static str>,
+) -> (http::Response<()>, String) {
+    let endpoint = client_endpoint();
+    let quic = endpoint.connect(addr, "localhost").unwrap().await.unwrap();
+    let (mut driver, mut sender) = h3::client::new(h3_quinn::Connection::new(quic)).await.unwrap();
+    let driver = rocket::tokio::spawn(async move {
+        let _ = rocket::futures::future::poll_fn(|cx| driver.poll_close(cx)).await;
+    });
+
+    let mut stream = sender.send_request(request).await.unwrap();
+    if let Some(body) = body {
+        stream.send_data(Bytes::from(body)).await.unwrap();
+    }
+
+    stream.finish().await.unwrap();
+    let response = stream.recv_response().await.unwrap();
+    let mut body = vec![];
+    while let Some(mut chunk) = stream.recv_data().await.unwrap() {
+        body.extend_from_slice(&chunk.copy_to_bytes(chunk.remaining()));
+    }
+
+    drop((stream, sender));
+    endpoint.close(0u32.into(), b"done");
+    driver.abort();
+    (response, String::from_utf8(body).unwrap())
+}
+
+#[rocket::async_test]
+async fn http3_get() {
+    let (addr, shutdown) = launch(rocket()).await;
+    let uri = format!("https://localhost:{}/hello", addr.port());
+    let req = http::Request::get(uri).body(()).unwrap();
+    let (response, body) = request(addr, req, None).await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.version(), http::Version::HTTP_3);
+    assert_eq!(body, "Hello, HTTP/3!");
+
+    let alt_svc = format!("h3=\":{}\"; ma=86400", addr.port());
+    assert_eq!(response.headers().get("alt-svc").unwrap(), alt_svc.as_str());
+    shutdown.notify();
+}
+
+#[rocket::async_test]
+async fn http3_post_body() {
+    let (addr, shutdown) = launch(rocket()).await;
+    let uri = format!("https://localhost:{}/echo", addr.port());
+    let req = http::Request::post(uri).body(()).unwrap();
+    let (response, body) = request(addr, req, Some("ping over quic")).await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(body, "ping over quic");
+
+    let uri = format!("https://localhost:{}/missing", addr.port());
+    let req = http::Request::get(uri).body(()).unwrap();
+    let (response, _) = request(addr, req, None).await;
+    assert_eq!(response.status(), 404);
+    shutdown.notify();
+}
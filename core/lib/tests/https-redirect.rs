@@ -0,0 +1,227 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build};
+use rocket::config::Config;
+use rocket::fairing::{AdHoc, HttpsRedirect};
+use rocket::http::{Header, Status};
+use rocket::http::uri::Host;
+use rocket::local::blocking::{Client, LocalRequest};
+
+#[get("/")] fn index() -> &'static str { "index" }
+#[post("/submit?<id>")] fn submit(id: usize) -> String { id.to_string() }
+#[get("/.well-known/acme-challenge/<token>")] fn challenge(token: &str) -> &str { token }
+
+fn rocket(redirect: HttpsRedirect) -> Rocket<Build> {
+    rocket::build()
+        .mount("/", routes![index, submit, challenge])
+        .attach(redirect)
+}
+
+fn with_host(mut req: LocalRequest<'_>) -> LocalRequest<'_> {
+    req.inner_mut().set_host(Host::from(uri!("rocket.rs:8000")));
+    req
+}
+
+fn proxied<'c>(client: &'c Client, proto: &'static str, uri: &'c str) -> LocalRequest<'c> {
+    with_host(client.get(uri)).header(Header::new("X-Forwarded-Proto", proto))
+}
+
+fn trusted() -> HttpsRedirect {
+    HttpsRedirect::new().trust_proxy(true).host("rocket.rs")
+}
+
+#[test]
+fn proxied_http_is_redirected() {
+    let client = Client::debug(rocket(trusted())).unwrap();
+
+    let response = proxied(&client, "http", "/").dispatch();
+    assert_eq!(response.status(), Status::PermanentRedirect);
+    assert_eq!(response.headers().get_one("Location"), Some("https://rocket.rs/"));
+
+    // `X-Forwarded-Host` is never used as the redirect target.
+    let response = proxied(&client, "http", "/submit?id=10")
+        .header(Header::new("X-Forwarded-Host", "evil.com"))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::PermanentRedirect);
+    assert_eq!(response.headers().get_one("Location"), Some("https://rocket.rs/submit?id=10"));
+
+    let response = with_host(client.post("/submit?id=10"))
+        .header(Header::new("X-Forwarded-Proto", "HTTP"))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::PermanentRedirect);
+    assert_eq!(response.headers().get_one("Location"), Some("https://rocket.rs/submit?id=10"));
+}
+
+#[test]
+fn unconfigured_hosts_are_not_redirected() {
+    let client = Client::debug(rocket(trusted())).unwrap();
+    let mut request = client.get("/").header(Header::new("X-Forwarded-Proto", "http"));
+    request.inner_mut().set_host(Host::from(uri!("evil.com")));
+    let response = request.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "index");
+
+    let client = Client::debug(rocket(HttpsRedirect::new().trust_proxy(true))).unwrap();
+    let response = proxied(&client, "http", "/").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    let client = Client::debug(rocket(trusted().host("WWW.Rocket.rs"))).unwrap();
+    let mut request = client.get("/").header(Header::new("X-Forwarded-Proto", "http"));
+    request.inner_mut().set_host(Host::from(uri!("www.rocket.rs")));
+    let response = request.dispatch();
+    assert_eq!(response.headers().get_one("Location"), Some("https://www.rocket.rs/"));
+}
+
+#[test]
+fn proxied_https_and_direct_requests_are_routed() {
+    let client = Client::debug(rocket(trusted())).unwrap();
+
+    let response = proxied(&client, "https", "/").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "index");
+
+    let response = client.get("/").dispatch();
+    assert_eq!(response.into_string().unwrap(), "index");
+}
+
+#[test]
+fn redirects_add_no_routes_and_keep_the_uri() {
+    let rocket = rocket(trusted())
+        .attach(AdHoc::on_response("URI", |req, res| Box::pin(async move {
+            res.set_raw_header("X-URI", req.uri().to_string());
+        })));
+
+    let client = Client::debug(rocket).unwrap();
+    assert_eq!(client.rocket().routes().count(), 3);
+
+    let response = proxied(&client, "http", "/submit?id=10").dispatch();
+    assert_eq!(response.status(), Status::PermanentRedirect);
+    assert_eq!(response.headers().get_one("X-URI"), Some("/submit?id=10"));
+}
+
+#[test]
+fn untrusted_proxies_are_ignored() {
+    let client = Client::debug(rocket(HttpsRedirect::new())).unwrap();
+    let response = proxied(&client, "http", "/").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "index");
+}
+
+#[test]
+fn exempt_paths_are_routed() {
+    let redirect = trusted()
+        .status(Status::MovedPermanently)
+        .exempt("/.well-known/acme-challenge");
+
+    let client = Client::debug(rocket(redirect)).unwrap();
+    let response = proxied(&client, "http", "/.well-known/acme-challenge/abc").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "abc");
+
+    let response = proxied(&client, "http", "/.well-known/acme").dispatch();
+    assert_eq!(response.status(), Status::MovedPermanently);
+    assert_eq!(response.headers().get_one("Location"), Some("https://rocket.rs/.well-known/acme"));
+}
+
+#[test]
+fn base_url_is_preferred() {
+    let config = Config {
+        base_url: Some(uri!("https://rocket.rs:8443/ignored")),
+        ..Config::debug_default()
+    };
+
+    let rocket = rocket::custom(config)
+        .mount("/", routes![index])
+        .attach(HttpsRedirect::new().trust_proxy(true));
+
+    let client = Client::debug(rocket).unwrap();
+    let response = proxied(&client, "http", "/?a=b").dispatch();
+    assert_eq!(response.headers().get_one("Location"), Some("https://rocket.rs:8443/?a=b"));
+}
+
+#[test]
+fn hsts_is_coordinated_with_shield() {
+    let client = Client::debug(rocket(trusted())).unwrap();
+    let response = proxied(&client, "https", "/").dispatch();
+    assert!(response.headers().get_one("Strict-Transport-Security").is_none());
+
+    let redirect = trusted().hsts(true);
+    let client = Client::debug(rocket(redirect)).unwrap();
+    let response = proxied(&client, "https", "/").dispatch();
+    assert!(response.headers().get_one("Strict-Transport-Security").is_some());
+}
+
+#[test]
+#[should_panic]
+fn status_must_be_a_redirect() {
+    let _ = HttpsRedirect::new().status(Status::Ok);
+}
+
+#[cfg(feature = "tls")]
+#[rocket::async_test]
+async fn tls_plain_http_server_redirects() {
+    use rocket::fs::relative;
+    use rocket::config::TlsConfig;
+    use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use rocket::tokio::net::TcpStream;
+
+    let cert_path = relative!("examples/tls/private/rsa_sha256_cert.pem");
+    let key_path = relative!("examples/tls/private/rsa_sha256_key.pem");
+    let tls = TlsConfig::from_paths(cert_path, key_path);
+    let config = Config { tls: Some(tls), port: 8443, ..Config::debug_default() };
+
+    let dir = std::env::temp_dir().join(format!("rocket-https-redirect-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("token"), "token").unwrap();
+    std::fs::write(dir.join(".hidden"), "hidden").unwrap();
+
+    let redirect = HttpsRedirect::new().port(18080).serve("/acme", &dir);
+    let rocket = rocket::custom(config).attach(redirect);
+    let client = rocket::local::asynchronous::Client::debug(rocket).await.unwrap();
+
+    async fn get(path: &str) -> String {
+        get_from("rocket.rs", path).await
+    }
+
+    async fn get_from(host: &str, path: &str) -> String {
+        for _ in 0..50 {
+            if let Ok(mut stream) = TcpStream::connect("127.0.0.1:18080").await {
+                let request = format!("GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n", path, host);
+                stream.write_all(request.as_bytes()).await.unwrap();
+                let mut response = String::new();
+                stream.read_to_string(&mut response).await.unwrap();
+                return response;
+            }
+
+            rocket::tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+
+        panic!("plain HTTP server never started");
+    }
+
+    let response = get("/a/b?c=d").await;
+    assert!(response.starts_with("HTTP/1.1 308"));
+    assert!(response.contains("location: https://rocket.rs:8443/a/b?c=d"));
+
+    let response = get("/acme/token").await;
+    assert!(response.starts_with("HTTP/1.1 200"));
+    assert!(response.ends_with("token"));
+
+    let response = get("/acme/other").await;
+    assert!(response.starts_with("HTTP/1.1 404"));
+
+    // Paths can't escape the directory.
+    let response = get("/acme/../../token").await;
+    assert!(response.starts_with("HTTP/1.1 200"));
+
+    let response = get("/acme/.hidden").await;
+    assert!(response.starts_with("HTTP/1.1 404"));
+
+    let response = get_from("other.rocket.rs", "/").await;
+    assert!(response.contains("location: https://other.rocket.rs:8443/"));
+
+    client.rocket().shutdown().notify();
+    std::fs::remove_dir_all(&dir).unwrap();
+}
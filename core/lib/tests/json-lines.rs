@@ -0,0 +1,73 @@
+#![cfg(feature = "json")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build, Config};
+use rocket::data::{Limits, ToByteUnit};
+use rocket::futures::stream::{self, Stream, StreamExt};
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::serde::{Serialize, Deserialize};
+use rocket::serde::json::{JsonLines, JsonLinesStream};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct Item {
+    id: usize,
+}
+
+#[get("/items?<n>")]
+fn items(n: usize) -> JsonLines<impl Stream<Item = Item>> {
+    JsonLines(stream::iter((0..n).map(|id| Item { id })))
+}
+
+#[post("/sum", format = "ndjson", data = "<items>")]
+async fn sum(items: JsonLines<JsonLinesStream<'_, Item>>) -> String {
+    let items: Vec<_> = items.into_inner().collect().await;
+    let ids: Vec<usize> = items.iter().filter_map(|item| item.as_ref().ok()).map(|i| i.id).collect();
+    match items.last() {
+        Some(Err(e)) => format!("{}:{}:{:?}", ids.len(), ids.iter().sum::<usize>(), e.kind()),
+        _ => format!("{}:{}", ids.len(), ids.iter().sum::<usize>()),
+    }
+}
+
+fn rocket() -> Rocket<Build> {
+    let limits = Limits::default().limit("json-lines", 64.bytes()).limit("json", 16.bytes());
+    rocket::custom(Config { limits, ..Config::debug_default() }).mount("/", routes![items, sum])
+}
+
+#[test]
+fn json_lines_responder() {
+    let client = Client::debug(rocket()).unwrap();
+    let response = client.get("/items?n=3").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::NDJSON));
+    assert_eq!(response.into_string().unwrap(), "{\"id\":0}\n{\"id\":1}\n{\"id\":2}\n");
+
+    let response = client.get("/items?n=0").dispatch();
+    assert_eq!(response.into_string().unwrap(), "");
+}
+
+#[test]
+fn json_lines_data_guard() {
+    let client = Client::debug(rocket()).unwrap();
+    let sum = |body: &str| client.post("/sum")
+        .header(ContentType::NDJSON)
+        .body(body)
+        .dispatch()
+        .into_string()
+        .unwrap();
+
+    assert_eq!(sum(""), "0:0");
+    assert_eq!(sum("{\"id\":1}\n\n{\"id\":2}\r\n  \n{\"id\":3}"), "3:6");
+
+    // Malformed lines end the stream with an error.
+    assert_eq!(sum("{\"id\":1}\n{\"id\":\n{\"id\":3}\n"), "1:1:InvalidData");
+
+    // As do lines exceeding the `json` limit...
+    assert_eq!(sum("{\"id\":1}\n{\"id\":           3}\n"), "1:1:InvalidData");
+
+    // ...and bodies exceeding the `json-lines` limit.
+    let body = "{\"id\":1}\n".repeat(8);
+    assert_eq!(sum(&body), "7:7:InvalidData");
+}
@@ -0,0 +1,84 @@
+#![cfg(unix)]
+
+#[macro_use] extern crate rocket;
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::os::unix::io::IntoRawFd;
+use std::os::unix::net::{UnixListener, UnixStream};
+
+use rocket::{Build, Rocket};
+use rocket::config::{Config, ListenFd};
+use rocket::error::ErrorKind;
+use rocket::fairing::AdHoc;
+
+mod common;
+use common::serve;
+
+#[get("/")]
+fn index(remote: Option<SocketAddr>) -> String {
+    format!("remote: {:?}", remote.map(|addr| addr.ip()))
+}
+
+fn rocket(listen_fd: ListenFd) -> Rocket<Build> {
+    let config = Config { listen_fd: Some(listen_fd), ..Config::debug_default() };
+    rocket::custom(config).mount("/", routes![index])
+}
+
+/// Sends a `GET /` over `socket`, returning the raw response.
+fn get<S: Read + Write>(mut socket: S) -> String {
+    socket.write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n").unwrap();
+
+    let mut response = String::new();
+    socket.read_to_string(&mut response).unwrap();
+    response
+}
+
+#[rocket::async_test]
+async fn serves_on_inherited_tcp_socket() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let fd = listener.into_raw_fd();
+
+    let rocket = rocket(ListenFd::Fd(fd)).attach(AdHoc::on_liftoff("Port", move |rocket| {
+        Box::pin(async move { assert_eq!(rocket.config().port, addr.port()); })
+    }));
+
+    serve(rocket, move |_| {
+        let response = get(TcpStream::connect(addr).unwrap());
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.ends_with("remote: Some(127.0.0.1)"));
+    }).await;
+}
+
+#[rocket::async_test]
+async fn serves_on_inherited_unix_socket() {
+    let path = std::env::temp_dir().join(format!("rocket-fd-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let fd = UnixListener::bind(&path).unwrap().into_raw_fd();
+
+    let socket = path.clone();
+    serve(rocket(ListenFd::Fd(fd)), move |_| {
+        let response = get(UnixStream::connect(&socket).unwrap());
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.ends_with("remote: None"));
+    }).await;
+
+    // The inherited socket's file isn't Rocket's to remove.
+    assert!(path.exists());
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn missing_systemd_socket_fails_to_bind() {
+    std::env::remove_var("LISTEN_PID");
+    let error = rocket::execute(rocket(ListenFd::Systemd).launch()).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::Bind(_)));
+}
+
+#[test]
+fn non_socket_fd_fails_to_bind() {
+    let fd = std::fs::File::open("Cargo.toml").unwrap().into_raw_fd();
+    let error = rocket::execute(rocket(ListenFd::Fd(fd)).launch()).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::Bind(_)));
+}
@@ -0,0 +1,187 @@
+#[macro_use] extern crate rocket;
+
+use std::io::{Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpStream};
+
+use rocket::{Build, Rocket, Orbit};
+use rocket::config::{Config, ListenerConfig};
+use rocket::error::ErrorKind;
+use rocket::fairing::AdHoc;
+use rocket::futures::channel::oneshot;
+
+#[get("/")]
+fn index() -> &'static str {
+    "hello"
+}
+
+fn rocket(listeners: Vec<ListenerConfig>) -> Rocket<Build> {
+    let config = Config { port: 0, listeners, ..Config::debug_default() };
+    rocket::custom(config).mount("/", routes![index])
+}
+
+/// Sends a `GET /` over `socket`, returning the raw response.
+fn get<S: Read + Write>(mut socket: S) -> String {
+    socket.write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n").unwrap();
+
+    let mut response = String::new();
+    socket.read_to_string(&mut response).unwrap();
+    response
+}
+
+fn addrs(rocket: &Rocket<Orbit>) -> Vec<SocketAddr> {
+    let config = rocket.config();
+    std::iter::once(SocketAddr::new(config.address, config.port))
+        .chain(config.listeners.iter().map(|l| SocketAddr::new(l.address, l.port)))
+        .collect()
+}
+
+/// Launches `rocket`, runs `f` in a blocking task with the addresses of the
+/// primary and additional listeners, then shuts down, returning once the
+/// launch has gracefully finished.
+async fn serve<F>(rocket: Rocket<Build>, f: F)
+    where F: FnOnce(Vec<SocketAddr>) + Send + 'static
+{
+    let (tx, rx) = oneshot::channel();
+    let rocket = rocket.attach(AdHoc::on_liftoff("Shutdown", |rocket| Box::pin(async move {
+        tx.send((addrs(rocket), rocket.shutdown())).unwrap();
+    })));
+
+    let launch = rocket::tokio::spawn(rocket.launch());
+    let (addrs, shutdown) = rx.await.unwrap();
+    rocket::tokio::task::spawn_blocking(move || f(addrs)).await.unwrap();
+    shutdown.notify();
+    launch.await.unwrap().unwrap();
+}
+
+#[rocket::async_test]
+async fn serves_on_all_listeners() {
+    let listeners = vec![
+        ListenerConfig::new(Ipv4Addr::LOCALHOST, 0),
+        ListenerConfig::new(Ipv4Addr::LOCALHOST, 0),
+    ];
+
+    serve(rocket(listeners), |addrs| {
+        assert_eq!(addrs.len(), 3);
+        assert!(addrs.iter().all(|addr| addr.port() != 0));
+        for addr in addrs {
+            let response = get(TcpStream::connect(addr).unwrap());
+            assert!(response.starts_with("HTTP/1.1 200"));
+            assert!(response.ends_with("hello"));
+        }
+    }).await;
+}
+
+#[cfg(unix)]
+#[rocket::async_test]
+async fn serves_on_additional_unix_socket() {
+    use std::os::unix::net::UnixStream;
+    use rocket::config::UnixConfig;
+
+    let path = std::env::temp_dir().join(format!("rocket-listener-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let socket = path.clone();
+    serve(rocket(vec![ListenerConfig::unix(UnixConfig::new(&path))]), move |addrs| {
+        assert!(get(TcpStream::connect(addrs[0]).unwrap()).ends_with("hello"));
+        assert!(get(UnixStream::connect(&socket).unwrap()).ends_with("hello"));
+    }).await;
+
+    assert!(!path.exists());
+}
+
+#[test]
+fn additional_listener_bind_failure_fails_launch() {
+    let taken = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = taken.local_addr().unwrap().port();
+    let rocket = rocket(vec![ListenerConfig::new(Ipv4Addr::LOCALHOST, port)]);
+    let error = rocket::execute(rocket.launch()).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::Bind(_)));
+}
+
+#[test]
+fn listeners_are_configurable() {
+    figment::Jail::expect_with(|jail| {
+        jail.create_file("Rocket.toml", r#"
+            [default]
+            port = 443
+
+            [[default.listeners]]
+            address = "0.0.0.0"
+            port = 80
+
+            [[default.listeners]]
+            unix = { path = "/run/app.sock" }
+        "#)?;
+
+        let config = Config::from(Config::figment());
+        assert_eq!(config.port, 443);
+        assert_eq!(config.listeners, vec![
+            ListenerConfig::new(Ipv4Addr::UNSPECIFIED, 80),
+            ListenerConfig::unix(rocket::config::UnixConfig::new("/run/app.sock")),
+        ]);
+
+        assert_eq!(config.listeners[0].to_string(), "http://0.0.0.0:80");
+        assert_eq!(config.listeners[1].to_string(), "unix:/run/app.sock");
+        Ok(())
+    });
+}
+
+#[cfg(feature = "tls")]
+mod tls {
+    use std::sync::Arc;
+
+    use super::*;
+    use rocket::fs::relative;
+    use rocket::config::TlsConfig;
+
+    macro_rules! private {
+        ($file:expr) => (relative!(concat!("../../examples/tls/private/", $file)))
+    }
+
+    fn tls_get(addr: SocketAddr) -> String {
+        let mut roots = rustls::RootCertStore::empty();
+        let mut reader = std::io::BufReader::new(std::fs::File::open(private!("ca_cert.pem")).unwrap());
+        for cert in rustls_pemfile::certs(&mut reader).unwrap() {
+            roots.add(&rustls::Certificate(cert)).unwrap();
+        }
+
+        let tls = rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+
+        let name = "localhost".try_into().unwrap();
+        let conn = rustls::ClientConnection::new(Arc::new(tls), name).unwrap();
+        get(rustls::StreamOwned::new(conn, TcpStream::connect(addr).unwrap()))
+    }
+
+    #[rocket::async_test]
+    async fn listeners_have_their_own_tls() {
+        let tls = TlsConfig::from_paths(private!("rsa_sha256_cert.pem"), private!("rsa_sha256_key.pem"));
+        let listener = ListenerConfig::new(Ipv4Addr::LOCALHOST, 0).with_tls(tls);
+        assert_eq!(listener.to_string(), "https://127.0.0.1:0");
+
+        // Only the additional listener serves TLS, so only it is reloaded.
+        let rocket = rocket(vec![listener]).attach(AdHoc::on_liftoff("Reload", |rocket| {
+            Box::pin(async move { rocket.reload_tls().unwrap(); })
+        }));
+
+        serve(rocket, |addrs| {
+            assert!(get(TcpStream::connect(addrs[0]).unwrap()).ends_with("hello"));
+            assert!(tls_get(addrs[1]).ends_with("hello"));
+        }).await;
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unix_listener_rejects_tls() {
+        use rocket::config::UnixConfig;
+
+        let path = std::env::temp_dir().join(format!("rocket-tls-{}.sock", std::process::id()));
+        let tls = TlsConfig::from_paths(private!("rsa_sha256_cert.pem"), private!("rsa_sha256_key.pem"));
+        let listener = ListenerConfig::unix(UnixConfig::new(&path)).with_tls(tls);
+        let error = rocket::execute(rocket(vec![listener]).launch()).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::Io(_)));
+        assert!(!path.exists());
+    }
+}
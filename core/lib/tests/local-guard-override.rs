@@ -0,0 +1,90 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+use rocket::request::{self, FromRequest, Request};
+
+#[derive(Debug, Clone, PartialEq)]
+struct User(String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for User {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        match req.headers().get_one("X-User") {
+            Some(name) => request::Outcome::Success(User(name.to_string())),
+            None => request::Outcome::Failure((Status::Unauthorized, ())),
+        }
+    }
+}
+
+#[get("/")]
+fn index(user: User) -> String {
+    format!("Hello, {}!", user.0)
+}
+
+#[get("/maybe")]
+fn maybe(user: Option<User>) -> String {
+    format!("{:?}", user)
+}
+
+#[get("/", rank = 2)]
+fn fallback() -> &'static str {
+    "fallback"
+}
+
+fn client() -> Client {
+    Client::debug_with(routes![index, maybe, fallback]).unwrap()
+}
+
+#[test]
+fn overridden_guard_succeeds() {
+    let client = client();
+    assert_eq!(client.get("/").dispatch().status(), Status::Unauthorized);
+
+    client.override_guard(User("Bob".into()));
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "Hello, Bob!");
+
+    let response = client.get("/").header(Header::new("X-User", "Alice")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "Hello, Bob!");
+
+    client.override_guard(User("Carol".into()));
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "Hello, Carol!");
+
+    client.reset_guard::<User>();
+    let response = client.get("/").header(Header::new("X-User", "Alice")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "Hello, Alice!");
+}
+
+#[test]
+fn overridden_guard_forwards() {
+    let client = client();
+    client.forward_guard::<User>();
+    let response = client.get("/").header(Header::new("X-User", "Alice")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "fallback");
+}
+
+#[test]
+fn overrides_are_per_type_and_client() {
+    let client = client();
+    client.override_guard(User("Bob".into()));
+    assert_eq!(client.get("/maybe").dispatch().into_string().unwrap(), "None");
+
+    client.override_guard(Some(User("Dave".into())));
+    let response = client.get("/maybe").dispatch();
+    assert_eq!(response.into_string().unwrap(), r#"Some(User("Dave"))"#);
+
+    let other = self::client();
+    assert_eq!(other.get("/").dispatch().status(), Status::Unauthorized);
+}
+
+#[rocket::async_test]
+async fn async_overridden_guard() {
+    use rocket::local::asynchronous::Client;
+
+    let client = Client::debug_with(routes![index]).await.unwrap();
+    client.override_guard(User("Eve".into()));
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "Hello, Eve!");
+}
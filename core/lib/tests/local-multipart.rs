@@ -0,0 +1,85 @@
+#[macro_use] extern crate rocket;
+
+use std::collections::BTreeMap;
+
+use rocket::form::Form;
+use rocket::fs::TempFile;
+use rocket::http::ContentType;
+use rocket::local::blocking::Client;
+
+#[derive(FromForm)]
+struct Upload<'r> {
+    name: &'r str,
+    tags: Vec<&'r str>,
+    photo: TempFile<'r>,
+}
+
+#[post("/", data = "<form>")]
+async fn upload(form: Form<Upload<'_>>) -> String {
+    let photo = &form.photo;
+    let contents = match photo.path() {
+        Some(path) => rocket::tokio::fs::read(path).await.unwrap(),
+        None => vec![],
+    };
+
+    format!("{} {:?} {:?} {} {:?}", form.name, form.tags, photo.name(),
+        photo.content_type().unwrap(), contents)
+}
+
+#[post("/fields", data = "<form>")]
+fn fields(form: Form<Vec<&str>>) -> String {
+    form.join(",")
+}
+
+#[post("/names", data = "<form>")]
+fn names(form: Form<BTreeMap<&str, &str>>) -> String {
+    let fields: Vec<_> = form.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+    fields.join(",")
+}
+
+#[test]
+fn multipart_builder_produces_valid_form() {
+    let client = Client::debug_with(routes![upload, fields]).unwrap();
+    let response = client.post("/")
+        .multipart()
+        .text("name", "Bob")
+        .text("tags", "a")
+        .text("tags", "b")
+        .file("photo", [1, 2, 3], "image/png")
+        .dispatch();
+
+    let expected = r#"Bob ["a", "b"] Some("photo") image/png [1, 2, 3]"#;
+    assert_eq!(response.into_string().unwrap(), expected);
+
+    let response = client.post("/")
+        .named_file("photo", "me.png", b"\r\n--", ContentType::PNG)
+        .text("name", "Alice")
+        .text("tags", "c")
+        .dispatch();
+
+    let expected = r#"Alice ["c"] Some("me") image/png [13, 10, 45, 45]"#;
+    assert_eq!(response.into_string().unwrap(), expected);
+}
+
+#[test]
+fn multipart_builder_escapes_and_resets() {
+    let client = Client::debug_with(routes![fields, names]).unwrap();
+    let response = client.post("/names").text("a\"b", "1").text("c\r\nd", "2").dispatch();
+    assert_eq!(response.into_string().unwrap(), "a%22b=1,c%0D%0Ad=2");
+
+    let response = client.post("/fields").text("a", "1").multipart().text("b", "2").dispatch();
+    assert_eq!(response.into_string().unwrap(), "2");
+
+    let response = client.post("/fields").multipart().dispatch();
+    assert_eq!(response.into_string().unwrap(), "");
+}
+
+#[rocket::async_test]
+async fn async_multipart_builder() {
+    let client = rocket::local::asynchronous::Client::debug_with(routes![fields]).await.unwrap();
+    let response = client.post("/fields").text("x", "one").file("y", "two", "text/plain");
+    assert!(response.content_type().unwrap().is_form_data());
+
+    let response = response.dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "one,two");
+}
@@ -0,0 +1,143 @@
+#![cfg(feature = "mtls")]
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::Arc;
+
+use rocket::{get, routes, Build, Rocket};
+use rocket::fs::relative;
+use rocket::config::{Config, TlsConfig, MutualTls};
+use rocket::mtls::Certificate;
+
+mod common;
+use common::{launch, pem_certs};
+
+#[get("/")]
+fn hello(cert: Certificate<'_>) -> String {
+    cert.serial().to_string()
+}
+
+macro_rules! private {
+    ($file:expr) => (relative!(concat!("../../examples/tls/private/", $file)))
+}
+
+fn rocket(mtls: MutualTls) -> Rocket<Build> {
+    let tls = TlsConfig::from_paths(private!("rsa_sha256_cert.pem"), private!("rsa_sha256_key.pem"))
+        .with_mutual(mtls.mandatory(true));
+
+    rocket::custom(Config { port: 0, tls: Some(tls), ..Config::debug_default() })
+        .mount("/", routes![hello])
+}
+
+/// Requests `/` from `addr` presenting the client certificate `kind`. Returns
+/// `None` if the TLS handshake fails.
+async fn request(addr: SocketAddr, kind: &'static str) -> Option<String> {
+    rocket::tokio::task::spawn_blocking(move || {
+        let mut roots = rustls::RootCertStore::empty();
+        for cert in pem_certs(private!("ca_cert.pem")) {
+            roots.add(&cert).unwrap();
+        }
+
+        let (cert, key) = match kind {
+            "rsa" => (private!("rsa_sha256_cert.pem"), private!("rsa_sha256_key.pem")),
+            _ => (private!("ed25519_cert.pem"), private!("ed25519_key.pem")),
+        };
+
+        let mut reader = std::io::BufReader::new(std::fs::File::open(key).unwrap());
+        let key = match rustls_pemfile::read_one(&mut reader).unwrap() {
+            Some(rustls_pemfile::Item::PKCS8Key(key)) => rustls::PrivateKey(key),
+            Some(rustls_pemfile::Item::RSAKey(key)) => rustls::PrivateKey(key),
+            _ => panic!("unexpected key type"),
+        };
+
+        let tls = rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_single_cert(pem_certs(cert), key)
+            .unwrap();
+
+        let name = "localhost".try_into().unwrap();
+        let conn = rustls::ClientConnection::new(Arc::new(tls), name).unwrap();
+        let mut stream = rustls::StreamOwned::new(conn, TcpStream::connect(addr).unwrap());
+        let request = "GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+        stream.write_all(request.as_bytes()).ok()?;
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).ok()?;
+        response.split("\r\n\r\n").nth(1).map(|body| body.to_string())
+    }).await.unwrap()
+}
+
+const ED25519_SERIAL: &str = "666558756029952229018502946980911767515739520648";
+
+#[rocket::async_test]
+async fn revoked_client_certificates_are_rejected() {
+    let mtls = MutualTls::from_path(private!("ca_cert.pem")).with_crls(private!("ca_crl.pem"));
+    let (addr, shutdown) = launch(rocket(mtls)).await;
+
+    assert!(request(addr, "rsa").await.is_some());
+    assert_eq!(request(addr, "ed25519").await, None);
+    shutdown.notify();
+
+    let mtls = MutualTls::from_path(private!("ca_cert.pem"));
+    let (addr, shutdown) = launch(rocket(mtls)).await;
+    assert_eq!(request(addr, "ed25519").await.unwrap(), ED25519_SERIAL);
+    shutdown.notify();
+}
+
+#[rocket::async_test]
+async fn crls_are_reloaded() {
+    let path = std::env::temp_dir().join(format!("rocket-crl-{}.pem", std::process::id()));
+    std::fs::copy(private!("ca_crl_empty.pem"), &path).unwrap();
+
+    let mtls = MutualTls::from_path(private!("ca_cert.pem")).with_crls(&path);
+    let (addr, shutdown) = launch(rocket(mtls)).await;
+    assert_eq!(request(addr, "ed25519").await.unwrap(), ED25519_SERIAL);
+
+    // Ensure the modification time changes even on coarse filesystems, then
+    // wait for the change to be picked up in the background.
+    std::thread::sleep(std::time::Duration::from_millis(1100));
+    std::fs::copy(private!("ca_crl.pem"), &path).unwrap();
+    let mut revoked = false;
+    for _ in 0..50 {
+        if request(addr, "ed25519").await.is_none() {
+            revoked = true;
+            break;
+        }
+
+        rocket::tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+
+    assert!(revoked);
+    assert!(request(addr, "rsa").await.is_some());
+
+    shutdown.notify();
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn invalid_crls_fail_launch() {
+    let mtls = MutualTls::from_path(private!("ca_cert.pem")).with_crls_bytes(b"not a CRL");
+    let error = rocket::execute(rocket(mtls).launch()).unwrap_err();
+    assert!(error.to_string().contains("CRL"));
+}
+
+#[test]
+fn crls_with_invalid_signatures_fail_launch() {
+    // Alter the last line of the PEM, which encodes the end of the signature.
+    let crl = std::fs::read_to_string(private!("ca_crl.pem")).unwrap();
+    let mut lines: Vec<String> = crl.lines().map(String::from).collect();
+    let last = lines.len() - 2;
+    let altered = match lines[last].remove(4) { 'A' => 'B', _ => 'A' };
+    lines[last].insert(4, altered);
+
+    let tampered = lines.join("\n");
+    let mtls = MutualTls::from_path(private!("ca_cert.pem")).with_crls_bytes(tampered.as_bytes());
+    let error = rocket::execute(rocket(mtls).launch()).unwrap_err();
+    assert!(error.to_string().contains("signature"));
+
+    // A CRL must be issued by one of the CA certificates.
+    let mtls = MutualTls::from_path(private!("rsa_sha256_cert.pem")).with_crls_bytes(crl.as_bytes());
+    let error = rocket::execute(rocket(mtls).launch()).unwrap_err();
+    assert!(error.to_string().contains("not a CA certificate"));
+}
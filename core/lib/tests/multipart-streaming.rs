@@ -0,0 +1,165 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build, Config};
+use rocket::data::{Limits, ToByteUnit};
+use rocket::form::{Multipart, MultipartData};
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+
+#[post("/", data = "<parts>")]
+async fn upload(parts: Multipart<'_>) -> Result<String, Status> {
+    let mut parts = parts.memory_threshold(8.bytes());
+    let mut fields = vec![];
+    while let Some(field) = parts.next_field().await.map_err(|e| e.status())? {
+        let kind = match field.data() {
+            MultipartData::Buffered(bytes) => format!("memory:{}", bytes.len()),
+            MultipartData::File(file) => {
+                let contents = std::fs::read_to_string(file.path().unwrap()).unwrap();
+                format!("disk:{}", contents)
+            }
+        };
+
+        fields.push(format!("{}={}", field.name(), kind));
+    }
+
+    Ok(fields.join(","))
+}
+
+#[post("/stream", data = "<parts>")]
+async fn stream(mut parts: Multipart<'_>) -> Result<String, Status> {
+    let mut fields = vec![];
+    while let Some(mut field) = parts.next_stream().await.map_err(|e| e.status())? {
+        let mut chunks = 0;
+        let mut data = vec![];
+        while let Some(chunk) = field.chunk().await.map_err(|e| e.status())? {
+            chunks += 1;
+            data.extend_from_slice(chunk);
+        }
+
+        let data = String::from_utf8(data).unwrap();
+        fields.push(format!("{}={}:{}", field.name(), data, chunks > 0));
+    }
+
+    Ok(fields.join(","))
+}
+
+#[post("/skip", data = "<parts>")]
+async fn skip(mut parts: Multipart<'_>) -> Result<String, Status> {
+    let mut names = vec![];
+    while let Some(field) = parts.next_stream().await.map_err(|e| e.status())? {
+        names.push(field.name().to_string());
+    }
+
+    Ok(names.join(","))
+}
+
+fn rocket() -> Rocket<Build> {
+    let limits = Limits::default()
+        .limit("form", 8.bytes())
+        .limit("file", 32.bytes())
+        .limit("multipart", 512.bytes());
+
+    rocket::custom(Config { limits, ..Config::debug_default() })
+        .mount("/", routes![upload, stream, skip])
+}
+
+fn body(fields: &[(&str, Option<&str>, &str)]) -> String {
+    let mut lines = vec![];
+    for (name, file_name, value) in fields {
+        lines.push("--X-BOUNDARY".to_string());
+        match file_name {
+            Some(file_name) => {
+                lines.push(format!(
+                    r#"Content-Disposition: form-data; name="{}"; filename="{}""#,
+                    name, file_name
+                ));
+                lines.push("Content-Type: text/plain".into());
+            }
+            None => lines.push(format!(r#"Content-Disposition: form-data; name="{}""#, name)),
+        }
+
+        lines.push("".into());
+        lines.push(value.to_string());
+    }
+
+    lines.push("--X-BOUNDARY--".into());
+    lines.push("".into());
+    lines.join("\r\n")
+}
+
+fn post(client: &Client, body: String) -> (Status, Option<String>) {
+    post_to(client, "/", body)
+}
+
+fn post_to(client: &Client, uri: &'static str, body: String) -> (Status, Option<String>) {
+    let response = client.post(uri)
+        .header("multipart/form-data; boundary=X-BOUNDARY".parse::<ContentType>().unwrap())
+        .body(body)
+        .dispatch();
+
+    (response.status(), response.into_string())
+}
+
+#[test]
+fn streams_and_spills_fields() {
+    let client = Client::debug(rocket()).unwrap();
+    let (status, fields) = post(&client, body(&[
+        ("name", None, "Bob"),
+        ("small", Some("small.txt"), "tiny"),
+        ("large", Some("large.txt"), "larger than eight"),
+    ]));
+
+    assert_eq!(status, Status::Ok);
+    assert_eq!(fields.unwrap(), "name=memory:3,small=memory:4,large=disk:larger than eight");
+
+    let (status, fields) = post(&client, body(&[]));
+    assert_eq!(status, Status::Ok);
+    assert_eq!(fields.unwrap(), "");
+}
+
+#[test]
+fn enforces_field_limits() {
+    let client = Client::debug(rocket()).unwrap();
+    let (status, _) = post(&client, body(&[("name", None, "more than eight")]));
+    assert_eq!(status, Status::PayloadTooLarge);
+
+    let file = "x".repeat(33);
+    let (status, _) = post(&client, body(&[("file", Some("file.txt"), &file)]));
+    assert_eq!(status, Status::PayloadTooLarge);
+}
+
+#[test]
+fn streams_field_data() {
+    let client = Client::debug(rocket()).unwrap();
+    let fields = body(&[("name", None, "Bob"), ("file", Some("file.txt"), "contents")]);
+    let (status, fields) = post_to(&client, "/stream", fields);
+    assert_eq!(status, Status::Ok);
+    assert_eq!(fields.unwrap(), "name=Bob:true,file=contents:true");
+
+    let fields = body(&[("a", None, "skipped"), ("b", Some("b.txt"), "also skipped")]);
+    let (status, names) = post_to(&client, "/skip", fields);
+    assert_eq!(status, Status::Ok);
+    assert_eq!(names.unwrap(), "a,b");
+
+    let file = "x".repeat(33);
+    let (status, _) = post_to(&client, "/stream", body(&[("file", Some("file.txt"), &file)]));
+    assert_eq!(status, Status::PayloadTooLarge);
+}
+
+#[test]
+fn enforces_body_limit() {
+    let client = Client::debug(rocket()).unwrap();
+    let fields: Vec<_> = (0..32).map(|_| ("name", None, "Bob")).collect();
+    let (status, _) = post(&client, body(&fields));
+    assert_eq!(status, Status::PayloadTooLarge);
+
+    let (status, _) = post(&client, body(&fields[..4]));
+    assert_eq!(status, Status::Ok);
+}
+
+#[test]
+fn forwards_non_multipart() {
+    let client = Client::debug(rocket()).unwrap();
+    let response = client.post("/").header(ContentType::Form).body("a=b").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+}
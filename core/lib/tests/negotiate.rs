@@ -0,0 +1,74 @@
+#![cfg(all(feature = "json", feature = "msgpack", feature = "xml"))]
+
+#[macro_use] extern crate rocket;
+
+use rocket::http::{Accept, ContentType, Header, Status};
+use rocket::local::blocking::Client;
+use rocket::serde::{Serialize, Deserialize};
+use rocket::serde::negotiate::{Negotiate, JsonFormat, XmlFormat};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[get("/")]
+fn point() -> Negotiate<Point> {
+    Negotiate::new(Point { x: 1, y: 2 })
+}
+
+#[get("/custom")]
+fn custom() -> Negotiate<Point, (XmlFormat, JsonFormat)> {
+    Point { x: 3, y: 4 }.into()
+}
+
+fn client() -> Client {
+    Client::debug_with(routes![point, custom]).unwrap()
+}
+
+#[test]
+fn negotiates_format() {
+    let client = client();
+    let response = client.get("/").dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+    assert_eq!(response.headers().get_one("Vary"), Some("Accept"));
+    assert_eq!(response.into_json::<Point>(), Some(Point { x: 1, y: 2 }));
+
+    let response = client.get("/").header(Accept::MsgPack).dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::MsgPack));
+    assert_eq!(response.into_msgpack::<Point>(), Some(Point { x: 1, y: 2 }));
+
+    let response = client.get("/").header(Header::new("Accept", "text/*")).dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::XML));
+    assert_eq!(response.into_string().unwrap(), "<Point><x>1</x><y>2</y></Point>");
+
+    let accept = "application/json; q=0.2, application/msgpack; q=0.5, */*; q=0.1";
+    let response = client.get("/").header(Header::new("Accept", accept)).dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::MsgPack));
+}
+
+#[test]
+fn custom_formats() {
+    let client = client();
+    let response = client.get("/custom").dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::XML));
+
+    let response = client.get("/custom").header(Accept::JSON).dispatch();
+    assert_eq!(response.into_json::<Point>(), Some(Point { x: 3, y: 4 }));
+
+    let response = client.get("/custom").header(Accept::MsgPack).dispatch();
+    assert_eq!(response.status(), Status::NotAcceptable);
+}
+
+#[test]
+fn not_acceptable() {
+    let client = client();
+    let response = client.get("/").header(Accept::HTML).dispatch();
+    assert_eq!(response.status(), Status::NotAcceptable);
+
+    let accept = "application/*; q=0, text/html";
+    let response = client.get("/").header(Header::new("Accept", accept)).dispatch();
+    assert_eq!(response.status(), Status::NotAcceptable);
+}
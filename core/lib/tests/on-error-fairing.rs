@@ -0,0 +1,135 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::{Arc, Mutex};
+
+use rocket::{Request, Rocket, Build};
+use rocket::fairing::AdHoc;
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use rocket::request::{self, FromRequest};
+
+struct Failing;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Failing {
+    type Error = &'static str;
+
+    async fn from_request(_: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        request::Outcome::Failure((Status::Unauthorized, "missing credentials"))
+    }
+}
+
+struct Parsed;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Parsed {
+    type Error = std::num::ParseIntError;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        match "ten".parse::<usize>() {
+            Ok(_) => request::Outcome::Success(Parsed),
+            Err(e) => {
+                req.record_error(Box::new(e.clone()));
+                request::Outcome::Failure((Status::UnprocessableEntity, e))
+            }
+        }
+    }
+}
+
+#[get("/ok")]
+fn ok() -> &'static str {
+    "ok"
+}
+
+#[get("/guard")]
+fn guard(_g: Failing) -> &'static str {
+    "unreachable"
+}
+
+#[get("/parsed")]
+fn parsed(_p: Parsed) -> &'static str {
+    "unreachable"
+}
+
+#[get("/panic")]
+fn panic() -> &'static str {
+    panic!("boom")
+}
+
+#[get("/forward/<_n>")]
+fn forward(_n: usize) -> &'static str {
+    "unreachable"
+}
+
+#[get("/status")]
+fn status() -> Status {
+    Status::ImATeapot
+}
+
+#[catch(418)]
+fn failing_catcher() -> Status {
+    Status::BadGateway
+}
+
+#[catch(422)]
+fn unprocessable(req: &Request<'_>) -> String {
+    let error = req.recorded_error();
+    let parse_error = error.as_deref().and_then(|e| e.downcast_ref::<std::num::ParseIntError>());
+    format!("{:?}", parse_error.map(|e| e.kind()))
+}
+
+type Reports = Arc<Mutex<Vec<(u16, Option<String>, Option<String>)>>>;
+
+fn rocket(reports: Reports) -> Rocket<Build> {
+    rocket::build()
+        .mount("/", routes![ok, guard, parsed, panic, forward, status])
+        .register("/", catchers![failing_catcher, unprocessable])
+        .attach(AdHoc::on_error("Reporter", move |info, _| {
+            let report = (
+                info.status().code,
+                info.route().and_then(|r| r.name.as_ref()).map(|n| n.to_string()),
+                info.error().map(|e| e.to_string()),
+            );
+
+            reports.lock().unwrap().push(report);
+            Box::pin(async move { })
+        }))
+}
+
+#[test]
+fn on_error_reports_failures() {
+    let reports = Reports::default();
+    let client = Client::debug(rocket(reports.clone())).unwrap();
+    let report = |uri: &'static str| {
+        client.get(uri).dispatch();
+        reports.lock().unwrap().pop()
+    };
+
+    assert_eq!(report("/ok"), None);
+
+    let guard = report("/guard").unwrap();
+    assert_eq!(guard, (401, Some("guard".into()), Some("\"missing credentials\"".into())));
+
+    let panic = report("/panic").unwrap();
+    assert_eq!(panic, (500, Some("panic".into()), Some("handler panicked: boom".into())));
+
+    assert_eq!(report("/forward/ten").unwrap(), (404, Some("forward".into()), None));
+    assert_eq!(report("/missing").unwrap(), (404, None, None));
+
+    // Even though the catcher fails, errors are reported once.
+    let response = client.get("/status").dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+    assert_eq!(reports.lock().unwrap().as_slice(), &[(418, Some("status".into()), None)]);
+}
+
+#[test]
+fn errors_recorded_by_guards_are_kept() {
+    let reports = Reports::default();
+    let client = Client::debug(rocket(reports.clone())).unwrap();
+    let response = client.get("/parsed").dispatch();
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    assert_eq!(response.into_string().unwrap(), "Some(InvalidDigit)");
+
+    let error = "invalid digit found in string".to_string();
+    assert_eq!(reports.lock().unwrap().pop(), Some((422, Some("parsed".into()), Some(error))));
+}
@@ -0,0 +1,182 @@
+#![cfg(feature = "openapi")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::form::Form;
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::openapi::{OpenApi, Schema};
+use rocket::response::status;
+use rocket::serde::{Serialize, Deserialize};
+use rocket::serde::json::{json, Json, Value};
+
+#[derive(Serialize, Deserialize, Schema)]
+#[serde(crate = "rocket::serde")]
+struct Task {
+    description: String,
+    complete: bool,
+    tags: Vec<Tag>,
+    parent: Option<Box<Task>>,
+}
+
+#[derive(Serialize, Deserialize, Schema)]
+#[serde(crate = "rocket::serde")]
+enum Tag { Work, Home }
+
+#[derive(FromForm, Schema)]
+#[allow(dead_code)]
+struct Filter<'r> {
+    tag: &'r str,
+    limit: Option<usize>,
+}
+
+/// Retrieves a task.
+///
+/// Responds with `404 Not Found` if the task doesn't exist.
+#[get("/tasks/<id>")]
+fn task(id: usize) -> Option<Json<Task>> {
+    let _ = id;
+    None
+}
+
+/// Lists tasks
+/// matching a filter.
+#[get("/tasks?<q>&<filter..>")]
+fn tasks(q: Option<&str>, filter: Filter<'_>) -> Json<Vec<Task>> {
+    let _ = (q, filter);
+    Json(vec![])
+}
+
+#[post("/tasks", data = "<task>")]
+fn create(task: Json<Task>) -> status::Created<Json<usize>> {
+    let _ = task;
+    status::Created::new("/tasks/0").body(Json(0))
+}
+
+#[put("/tasks/<id>", data = "<form>")]
+fn update(id: usize, form: Form<Filter<'_>>) -> Result<(), Status> {
+    let _ = (id, form);
+    Ok(())
+}
+
+#[get("/opaque/<path..>")]
+fn opaque<'r>(path: std::path::PathBuf) -> impl rocket::response::Responder<'r, 'static> {
+    let _ = path;
+}
+
+fn client(openapi: OpenApi) -> Client {
+    let rocket = rocket::build()
+        .mount("/", routes![task, tasks, create, opaque])
+        .mount("/v1", routes![update])
+        .attach(openapi);
+
+    Client::debug(rocket).unwrap()
+}
+
+fn document(client: &Client) -> Value {
+    let response = client.get("/openapi.json").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+    response.into_json().unwrap()
+}
+
+#[test]
+fn document_describes_routes() {
+    let client = client(OpenApi::new("Tasks", "1.0.0").description("Manages tasks."));
+    let doc = document(&client);
+
+    assert_eq!(doc["openapi"], "3.0.3");
+    assert_eq!(doc["info"], json!({
+        "title": "Tasks",
+        "version": "1.0.0",
+        "description": "Manages tasks."
+    }));
+
+    let get = &doc["paths"]["/tasks/{id}"]["get"];
+    assert_eq!(get["operationId"], "task");
+    assert_eq!(get["summary"], "Retrieves a task.");
+    assert_eq!(get["description"], "Responds with `404 Not Found` if the task doesn't exist.");
+    assert_eq!(get["parameters"], json!([{
+        "name": "id",
+        "in": "path",
+        "required": true,
+        "schema": { "type": "integer", "format": "int64", "minimum": 0 }
+    }]));
+
+    assert_eq!(get["responses"]["200"]["content"]["application/json"]["schema"], json!({
+        "$ref": "#/components/schemas/Task"
+    }));
+    assert_eq!(get["responses"]["404"]["description"], "Not Found");
+
+    let list = &doc["paths"]["/tasks"]["get"];
+    assert_eq!(list["summary"], "Lists tasks matching a filter.");
+    assert_eq!(list["parameters"][0], json!({
+        "name": "q",
+        "in": "query",
+        "required": false,
+        "schema": { "type": "string" }
+    }));
+    assert_eq!(list["parameters"][1], json!({
+        "name": "filter",
+        "in": "query",
+        "required": true,
+        "style": "form",
+        "explode": true,
+        "schema": { "$ref": "#/components/schemas/Filter" }
+    }));
+
+    let create = &doc["paths"]["/tasks"]["post"];
+    assert_eq!(create["requestBody"], json!({
+        "required": true,
+        "content": {
+            "application/json": { "schema": { "$ref": "#/components/schemas/Task" } }
+        }
+    }));
+    assert_eq!(create["responses"]["201"]["content"]["application/json"]["schema"]["type"],
+        "integer");
+
+    let update = &doc["paths"]["/v1/tasks/{id}"]["put"];
+    assert!(update["requestBody"]["content"]["application/x-www-form-urlencoded"].is_object());
+    assert_eq!(update["responses"]["200"]["description"], "OK");
+    assert_eq!(update["responses"]["default"]["description"], "Response");
+
+    let opaque = &doc["paths"]["/opaque/{path}"]["get"];
+    assert_eq!(opaque["parameters"][0]["schema"], json!({ "type": "string" }));
+    assert_eq!(opaque["responses"], json!({ "default": { "description": "Response" } }));
+}
+
+#[test]
+fn document_describes_schemas() {
+    let doc = document(&client(OpenApi::new("Tasks", "1.0.0")));
+    let schemas = &doc["components"]["schemas"];
+
+    assert_eq!(schemas["Task"], json!({
+        "type": "object",
+        "properties": {
+            "description": { "type": "string" },
+            "complete": { "type": "boolean" },
+            "tags": { "type": "array", "items": { "$ref": "#/components/schemas/Tag" } },
+            "parent": { "$ref": "#/components/schemas/Task" }
+        },
+        "required": ["description", "complete", "tags"]
+    }));
+
+    assert_eq!(schemas["Tag"], json!({ "type": "string", "enum": ["Work", "Home"] }));
+    assert_eq!(schemas["Filter"]["required"], json!(["tag"]));
+}
+
+#[test]
+fn swagger_ui_and_custom_path() {
+    let openapi = OpenApi::new("<Tasks>", "1.0.0").path("/api/spec.json").swagger_ui("/docs");
+    let client = client(openapi);
+
+    assert_eq!(client.get("/openapi.json").dispatch().status(), Status::NotFound);
+    let doc: Value = client.get("/api/spec.json").dispatch().into_json().unwrap();
+    assert!(doc["paths"]["/api/spec.json"].is_null());
+
+    let response = client.get("/docs").dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+    let page = response.into_string().unwrap();
+    assert!(page.contains("<title>&lt;Tasks&gt;</title>"));
+    assert!(page.contains(r#"url: "/api/spec.json""#));
+}
@@ -0,0 +1,123 @@
+#[macro_use] extern crate rocket;
+
+use std::io::{Read, Write};
+use std::net::{IpAddr, SocketAddr, TcpStream};
+
+use rocket::{Build, Rocket};
+use rocket::config::Config;
+
+mod common;
+use common::serve;
+
+#[get("/")]
+fn index(remote: Option<SocketAddr>, client_ip: Option<IpAddr>) -> String {
+    format!("{:?} {:?}", remote, client_ip)
+}
+
+fn rocket(proxy_protocol: bool) -> Rocket<Build> {
+    let config = Config { port: 0, proxy_protocol, ..Config::debug_default() };
+    rocket::custom(config).mount("/", routes![index])
+}
+
+const GET: &[u8] = b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+
+/// Sends `header` followed by a `GET /` over `socket`, returning the raw
+/// response, if any.
+fn get<S: Read + Write>(mut socket: S, header: &[u8]) -> String {
+    socket.write_all(header).unwrap();
+    socket.write_all(GET).unwrap();
+
+    let mut response = String::new();
+    let _ = socket.read_to_string(&mut response);
+    response
+}
+
+#[rocket::async_test]
+async fn v1_header_sets_remote() {
+    serve(rocket(true), |addr| {
+        let header = b"PROXY TCP4 192.0.2.1 198.51.100.1 56324 443\r\n";
+        let response = get(TcpStream::connect(addr).unwrap(), header);
+        assert!(response.ends_with("Some(192.0.2.1:56324) Some(192.0.2.1)"));
+
+        let header = b"PROXY UNKNOWN\r\n";
+        let response = get(TcpStream::connect(addr).unwrap(), header);
+        assert!(response.contains("Some(127.0.0.1:"));
+        assert!(response.ends_with(" Some(127.0.0.1)"));
+    }).await;
+}
+
+#[rocket::async_test]
+async fn v2_header_sets_remote() {
+    serve(rocket(true), |addr| {
+        let mut header = b"\r\n\r\n\0\r\nQUIT\n\x21\x21\0\x24".to_vec();
+        header.extend_from_slice(&"2001:db8::1".parse::<std::net::Ipv6Addr>().unwrap().octets());
+        header.extend_from_slice(&[0; 16]);
+        header.extend_from_slice(&[0x0f, 0xa0, 0, 80]);
+
+        // The header may arrive in pieces.
+        let mut socket = TcpStream::connect(addr).unwrap();
+        socket.write_all(&header[..10]).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        let response = get(socket, &header[10..]);
+        assert!(response.ends_with("Some([2001:db8::1]:4000) Some(2001:db8::1)"));
+    }).await;
+}
+
+#[rocket::async_test]
+async fn connections_without_header_are_closed() {
+    serve(rocket(true), |addr| {
+        assert_eq!(get(TcpStream::connect(addr).unwrap(), b""), "");
+
+        // Other connections are unaffected by a slow or malformed one.
+        let slow = TcpStream::connect(addr).unwrap();
+        let header = b"PROXY TCP4 192.0.2.1 198.51.100.1 56324 443\r\n";
+        let response = get(TcpStream::connect(addr).unwrap(), header);
+        assert!(response.ends_with("Some(192.0.2.1:56324) Some(192.0.2.1)"));
+        drop(slow);
+    }).await;
+}
+
+#[rocket::async_test]
+async fn header_is_not_parsed_when_disabled() {
+    serve(rocket(false), |addr| {
+        let header = b"PROXY TCP4 192.0.2.1 198.51.100.1 56324 443\r\n";
+        let response = get(TcpStream::connect(addr).unwrap(), header);
+        assert!(response.starts_with("HTTP/1.1 400"));
+    }).await;
+}
+
+#[cfg(feature = "tls")]
+#[rocket::async_test]
+async fn header_precedes_tls_handshake() {
+    use std::sync::Arc;
+    use rocket::fs::relative;
+    use rocket::config::TlsConfig;
+
+    let tls = TlsConfig::from_paths(
+        relative!("../../examples/tls/private/rsa_sha256_cert.pem"),
+        relative!("../../examples/tls/private/rsa_sha256_key.pem"),
+    );
+
+    let config = Config { port: 0, proxy_protocol: true, tls: Some(tls), ..Config::debug_default() };
+    let rocket = rocket::custom(config).mount("/", routes![index]);
+    serve(rocket, |addr| {
+        let ca = relative!("../../examples/tls/private/ca_cert.pem");
+        let mut reader = std::io::BufReader::new(std::fs::File::open(ca).unwrap());
+        let mut roots = rustls::RootCertStore::empty();
+        for cert in rustls_pemfile::certs(&mut reader).unwrap() {
+            roots.add(&rustls::Certificate(cert)).unwrap();
+        }
+
+        let tls = rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+
+        let mut tcp = TcpStream::connect(addr).unwrap();
+        tcp.write_all(b"PROXY TCP4 192.0.2.1 198.51.100.1 56324 443\r\n").unwrap();
+
+        let conn = rustls::ClientConnection::new(Arc::new(tls), "localhost".try_into().unwrap());
+        let response = get(rustls::StreamOwned::new(conn.unwrap(), tcp), b"");
+        assert!(response.ends_with("Some(192.0.2.1:56324) Some(192.0.2.1)"));
+    }).await;
+}
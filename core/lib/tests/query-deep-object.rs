@@ -0,0 +1,96 @@
+#[macro_use] extern crate rocket;
+
+use std::collections::HashMap;
+
+use rocket::form::Strict;
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+
+#[derive(Debug, FromForm)]
+struct Filter<'r> {
+    status: &'r str,
+    assignee: usize,
+    range: Option<Range>,
+    items: Vec<Item<'r>>,
+}
+
+#[derive(Debug, FromForm)]
+struct Range {
+    gte: usize,
+    lte: usize,
+}
+
+#[derive(Debug, FromForm)]
+struct Item<'r> {
+    name: &'r str,
+    qty: usize,
+}
+
+#[derive(Debug, FromForm)]
+struct Search<'r> {
+    filter: Filter<'r>,
+    page: usize,
+}
+
+#[get("/strict?<filter>&<page>")]
+fn strict(filter: Strict<Filter<'_>>, page: usize) -> String {
+    format!("{:?} {}", filter.into_inner(), page)
+}
+
+#[get("/trailing?<search..>")]
+fn trailing(search: Strict<Search<'_>>) -> String {
+    format!("{:?}", search.into_inner())
+}
+
+#[get("/map?<filter>")]
+fn map(filter: HashMap<&str, HashMap<&str, usize>>) -> String {
+    let mut entries = filter.iter()
+        .flat_map(|(k, v)| v.iter().map(move |(j, n)| format!("{}.{}={}", k, j, n)))
+        .collect::<Vec<_>>();
+
+    entries.sort();
+    entries.join(",")
+}
+
+const FILTER: &str = "filter[status]=open&filter[assignee]=5\
+    &filter[range][gte]=1&filter[range][lte]=3\
+    &filter[items][0][name]=a&filter[items][0][qty]=1\
+    &filter[items][1][name]=b&filter[items][1][qty]=2";
+
+const EXPECTED: &str = "Filter { status: \"open\", assignee: 5, \
+    range: Some(Range { gte: 1, lte: 3 }), \
+    items: [Item { name: \"a\", qty: 1 }, Item { name: \"b\", qty: 2 }] }";
+
+fn get(client: &Client, uri: String) -> (Status, Option<String>) {
+    let response = client.get(uri).dispatch();
+    (response.status(), response.into_string())
+}
+
+#[test]
+fn deep_object_keys_match_strict_forms() {
+    let client = Client::debug_with(routes![strict, trailing, map]).unwrap();
+
+    let (status, body) = get(&client, format!("/strict?{}&page=2", FILTER));
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body.unwrap(), format!("{} 2", EXPECTED));
+
+    let (status, body) = get(&client, format!("/trailing?page=2&{}", FILTER));
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body.unwrap(), format!("Search {{ filter: {}, page: 2 }}", EXPECTED));
+
+    let encoded = FILTER.replace('[', "%5B").replace(']', "%5D");
+    let (status, body) = get(&client, format!("/strict?{}&page=2", encoded));
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body.unwrap(), format!("{} 2", EXPECTED));
+
+    let (status, _) = get(&client, format!("/strict?{}&filter[extra]=1&page=2", FILTER));
+    assert_eq!(status, Status::NotFound);
+}
+
+#[test]
+fn deep_object_keys_match_maps() {
+    let client = Client::debug_with(routes![strict, trailing, map]).unwrap();
+    let (status, body) = get(&client, "/map?filter[a][x]=1&filter[a][y]=2&filter[b][x]=3".into());
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body.unwrap(), "a.x=1,a.y=2,b.x=3");
+}
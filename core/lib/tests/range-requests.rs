@@ -0,0 +1,118 @@
+#[macro_use] extern crate rocket;
+
+use std::io::Cursor;
+
+use rocket::{Rocket, Build};
+use rocket::fs::{FileServer, relative};
+use rocket::http::{Header, Status, EntityTag};
+use rocket::local::blocking::{Client, LocalResponse};
+use rocket::response::{Conditional, Ranged};
+use rocket::response::stream::{ReaderStream, One};
+
+const DIGITS: &str = "0123456789";
+
+#[get("/digits")]
+fn digits() -> Ranged<Conditional<&'static str>> {
+    Ranged::new(Conditional::new(DIGITS).etag(EntityTag::strong("v1").unwrap()))
+}
+
+#[get("/stream")]
+fn stream() -> Ranged<ReaderStream<One<Cursor<&'static str>>>> {
+    Ranged::new(ReaderStream::one(Cursor::new(DIGITS)))
+}
+
+fn rocket() -> Rocket<Build> {
+    rocket::build()
+        .mount("/", routes![digits, stream])
+        .mount("/static", FileServer::from(relative!("tests/static")))
+}
+
+fn get<'c>(client: &'c Client, path: &str, headers: &[(&'static str, &str)]) -> LocalResponse<'c> {
+    let mut request = client.get(path.to_string());
+    for &(name, value) in headers {
+        request = request.header(Header::new(name, value.to_string()));
+    }
+
+    request.dispatch()
+}
+
+#[test]
+fn ranges_are_selected() {
+    let client = Client::debug(rocket()).unwrap();
+    let response = get(&client, "/digits", &[]);
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.headers().get_one("Accept-Ranges"), Some("bytes"));
+    assert_eq!(response.into_string().unwrap(), DIGITS);
+
+    let cases = [("bytes=2-4", "2-4", "234"), ("bytes=7-", "7-9", "789"),
+        ("bytes=-3", "7-9", "789"), ("bytes=5-100", "5-9", "56789")];
+
+    for &(range, content_range, body) in &cases {
+        let response = get(&client, "/digits", &[("Range", range)]);
+        assert_eq!(response.status(), Status::PartialContent, "{}", range);
+        let expected = format!("bytes {}/10", content_range);
+        assert_eq!(response.headers().get_one("Content-Range"), Some(&*expected));
+        assert_eq!(response.into_string().unwrap(), body);
+    }
+
+    let response = get(&client, "/digits", &[("Range", "bytes=10-")]);
+    assert_eq!(response.status(), Status::RangeNotSatisfiable);
+    assert_eq!(response.headers().get_one("Content-Range"), Some("bytes */10"));
+    assert!(response.into_string().unwrap_or_default().is_empty());
+
+    // Multiple and invalid ranges are ignored.
+    for &range in &["bytes=0-1, 4-5", "bytes=a-b", "items=0-1"] {
+        let response = get(&client, "/digits", &[("Range", range)]);
+        assert_eq!(response.status(), Status::Ok, "{}", range);
+        assert_eq!(response.into_string().unwrap(), DIGITS);
+    }
+
+    // Only sized bodies can be ranged.
+    let response = get(&client, "/stream", &[("Range", "bytes=0-1")]);
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.headers().get_one("Accept-Ranges").is_none());
+    assert_eq!(response.into_string().unwrap(), DIGITS);
+}
+
+#[test]
+fn ranges_are_conditional() {
+    let client = Client::debug(rocket()).unwrap();
+    let response = get(&client, "/digits", &[("Range", "bytes=0-1"), ("If-Range", "\"v1\"")]);
+    assert_eq!(response.status(), Status::PartialContent);
+    assert_eq!(response.into_string().unwrap(), "01");
+
+    for &if_range in &["\"v2\"", "W/\"v1\"", "Sun, 06 Nov 1994 08:49:37 GMT"] {
+        let response = get(&client, "/digits", &[("Range", "bytes=0-1"), ("If-Range", if_range)]);
+        assert_eq!(response.status(), Status::Ok, "{}", if_range);
+        assert_eq!(response.into_string().unwrap(), DIGITS);
+    }
+
+    let response = get(&client, "/digits", &[("Range", "bytes=0-1"), ("If-None-Match", "\"v1\"")]);
+    assert_eq!(response.status(), Status::NotModified);
+}
+
+#[test]
+fn named_files_are_ranged() {
+    let client = Client::debug(rocket()).unwrap();
+    let response = get(&client, "/static/index.html", &[]);
+    assert_eq!(response.headers().get_one("Accept-Ranges"), Some("bytes"));
+    let etag = response.headers().get_one("ETag").map(String::from).unwrap();
+    let modified = response.headers().get_one("Last-Modified").map(String::from).unwrap();
+
+    let response = get(&client, "/static/index.html", &[("Range", "bytes=5-8")]);
+    assert_eq!(response.status(), Status::PartialContent);
+    assert_eq!(response.headers().get_one("Content-Range"), Some("bytes 5-8/30"));
+    assert_eq!(response.headers().get_one("Content-Type"), Some("text/html; charset=utf-8"));
+    assert_eq!(response.into_string().unwrap(), "a fi");
+
+    for if_range in &[etag, modified] {
+        let headers = [("Range", "bytes=-6"), ("If-Range", &**if_range)];
+        let response = get(&client, "/static/index.html", &headers);
+        assert_eq!(response.status(), Status::PartialContent, "{}", if_range);
+        assert_eq!(response.into_string().unwrap(), "html.\n");
+    }
+
+    let response = get(&client, "/static/index.html", &[("Range", "bytes=30-")]);
+    assert_eq!(response.status(), Status::RangeNotSatisfiable);
+    assert_eq!(response.headers().get_one("Content-Range"), Some("bytes */30"));
+}
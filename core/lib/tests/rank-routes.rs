@@ -0,0 +1,61 @@
+#[macro_use] extern crate rocket;
+
+use std::path::PathBuf;
+
+use rocket::{Rocket, Build};
+use rocket::error::ErrorKind;
+use rocket::local::blocking::Client;
+
+#[get("/<id>")]
+fn user(id: usize) -> String {
+    format!("user {}", id)
+}
+
+#[get("/<path..>")]
+fn page(path: PathBuf) -> String {
+    format!("page {}", path.display())
+}
+
+fn rocket() -> Rocket<Build> {
+    rocket::build()
+        .mount("/", routes![user, page])
+        .mount("/legacy", routes![user])
+        .mount("/legacy", routes![page])
+}
+
+#[test]
+fn unranked_routes_collide() {
+    let error = Client::debug(rocket()).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::Collisions(_)));
+}
+
+#[test]
+fn ranking_strategies_resolve_collisions() {
+    let rocket = rocket()
+        .rank_routes(|route| match route.uri.path().ends_with("..>") {
+            true => route.rank + 100,
+            false => route.rank,
+        })
+        .rank_routes(|route| match route.uri.base() {
+            "/legacy" => route.rank - 1000,
+            _ => route.rank,
+        });
+
+    let client = Client::debug(rocket).unwrap();
+    let mut ranks: Vec<_> = client.rocket().routes()
+        .map(|r| (r.uri.to_string(), r.rank))
+        .collect();
+
+    ranks.sort();
+    assert_eq!(ranks, vec![
+        ("/<id>".to_string(), -1),
+        ("/<path..>".to_string(), 99),
+        ("/legacy/<id>".to_string(), -1001),
+        ("/legacy/<path..>".to_string(), -901),
+    ]);
+
+    assert_eq!(client.get("/10").dispatch().into_string().unwrap(), "user 10");
+    assert_eq!(client.get("/bob").dispatch().into_string().unwrap(), "page bob");
+    assert_eq!(client.get("/legacy/10").dispatch().into_string().unwrap(), "user 10");
+    assert_eq!(client.get("/legacy/a/b").dispatch().into_string().unwrap(), "page a/b");
+}
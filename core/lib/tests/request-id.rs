@@ -0,0 +1,79 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+use rocket::request::RequestId;
+
+#[get("/")]
+fn index(id: &RequestId) -> String {
+    id.to_string()
+}
+
+#[get("/fail")]
+fn fail() -> Status {
+    Status::InternalServerError
+}
+
+#[catch(500)]
+fn internal_error(req: &Request<'_>) -> String {
+    format!("error {}", RequestId::of(req))
+}
+
+fn client(fairing: bool) -> Client {
+    let mut rocket = rocket::build()
+        .mount("/", routes![index, fail])
+        .register("/", catchers![internal_error]);
+
+    if fairing {
+        rocket = rocket.attach(RequestId::fairing());
+    }
+
+    Client::debug(rocket).unwrap()
+}
+
+#[test]
+fn generated_request_ids() {
+    let client = client(true);
+    let response = client.get("/").dispatch();
+    let header = response.headers().get_one("X-Request-Id").unwrap().to_string();
+    assert_eq!(header.len(), 36);
+    assert_eq!(response.into_string().unwrap(), header);
+
+    let response = client.get("/").dispatch();
+    assert_ne!(response.headers().get_one("X-Request-Id").unwrap(), header);
+}
+
+#[test]
+fn propagated_request_ids() {
+    let client = client(true);
+    let response = client.get("/").header(Header::new("X-Request-Id", "abc-123")).dispatch();
+    assert_eq!(response.headers().get_one("X-Request-Id"), Some("abc-123"));
+    assert_eq!(response.into_string().unwrap(), "abc-123");
+
+    let response = client.get("/").header(Header::new("X-Request-Id", "bad id")).dispatch();
+    let header = response.headers().get_one("X-Request-Id").unwrap().to_string();
+    assert_ne!(header, "bad id");
+    assert_eq!(response.into_string().unwrap(), header);
+}
+
+#[test]
+fn request_ids_in_error_responses() {
+    let client = client(true);
+    let response = client.get("/fail").header(Header::new("X-Request-Id", "abc-123")).dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+    assert_eq!(response.headers().get_one("X-Request-Id"), Some("abc-123"));
+    assert_eq!(response.into_string().unwrap(), "error abc-123");
+
+    let response = client.get("/missing").header(Header::new("X-Request-Id", "abc")).dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(response.headers().get_one("X-Request-Id"), Some("abc"));
+}
+
+#[test]
+fn request_ids_without_fairing() {
+    let client = client(false);
+    let response = client.get("/").header(Header::new("X-Request-Id", "abc-123")).dispatch();
+    assert!(response.headers().get_one("X-Request-Id").is_none());
+    assert_eq!(response.into_string().unwrap(), "abc-123");
+}
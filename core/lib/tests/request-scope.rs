@@ -0,0 +1,95 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build};
+use rocket::fairing::AdHoc;
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+use rocket::request::{self, FromRequest, Request, Scoped};
+
+#[derive(Debug, PartialEq)]
+struct Tenant(String);
+
+#[derive(Debug, PartialEq)]
+struct Flags { beta: bool }
+
+/// Refines the tenant provided by the fairing with the `X-Tenant` header.
+struct TenantHeader;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for TenantHeader {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        if let Some(tenant) = req.headers().get_one("X-Tenant") {
+            req.scope().provide(Tenant(tenant.into()));
+        }
+
+        request::Outcome::Success(TenantHeader)
+    }
+}
+
+#[get("/")]
+fn index(_h: TenantHeader, tenant: Scoped<Tenant>, flags: Option<Scoped<Flags>>) -> String {
+    format!("{} {:?}", tenant.0, flags.map(|f| f.beta))
+}
+
+#[get("/missing")]
+fn missing(flags: Scoped<Flags>) -> String {
+    format!("{:?}", flags)
+}
+
+fn rocket() -> Rocket<Build> {
+    rocket::build()
+        .mount("/", routes![index, missing])
+        .attach(AdHoc::on_request("Tenant", |req, _| Box::pin(async move {
+            req.scope().provide(Tenant("default".into()));
+            if req.query_value::<bool>("beta").and_then(|r| r.ok()).unwrap_or(false) {
+                req.scope().provide(Flags { beta: true });
+            }
+        })))
+}
+
+#[test]
+fn guards_resolve_values_provided_by_fairings_and_guards() {
+    let client = Client::debug(rocket()).unwrap();
+    let response = client.get("/").dispatch();
+    assert_eq!(response.into_string().unwrap(), "default None");
+
+    let response = client.get("/?beta=true").dispatch();
+    assert_eq!(response.into_string().unwrap(), "default Some(true)");
+
+    let response = client.get("/").header(Header::new("X-Tenant", "acme")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "acme None");
+}
+
+#[test]
+fn missing_scoped_value_fails() {
+    let client = Client::debug(rocket()).unwrap();
+    let response = client.get("/missing").dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+
+    let response = client.get("/missing?beta=true").dispatch();
+    assert_eq!(response.into_string().unwrap(), "Flags { beta: true }");
+}
+
+#[test]
+fn client_overrides_are_not_replaced() {
+    let client = Client::debug(rocket()).unwrap();
+    client.override_scoped(Tenant("test".into()));
+    client.override_scoped(Flags { beta: false });
+
+    let response = client.get("/?beta=true").header(Header::new("X-Tenant", "acme")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "test Some(false)");
+
+    client.reset_scoped::<Tenant>();
+    let response = client.get("/?beta=true").header(Header::new("X-Tenant", "acme")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "acme Some(false)");
+}
+
+#[test]
+fn request_overrides_are_not_replaced() {
+    let client = Client::debug(rocket()).unwrap();
+    let request = client.get("/").header(Header::new("X-Tenant", "acme"));
+    request.scope().provide_override(Tenant("test".into()));
+    assert_eq!(request.dispatch().into_string().unwrap(), "test None");
+}
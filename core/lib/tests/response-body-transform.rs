@@ -0,0 +1,108 @@
+#[macro_use] extern crate rocket;
+
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use rocket::{Rocket, Build};
+use rocket::fairing::AdHoc;
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+use rocket::response::stream::ReaderStream;
+use rocket::tokio::io::{AsyncRead, ReadBuf};
+
+struct Upper<R>(R);
+
+impl<R: AsyncRead + Unpin> AsyncRead for Upper<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let filled = buf.filled().len();
+        let result = Pin::new(&mut self.0).poll_read(cx, buf);
+        buf.filled_mut()[filled..].make_ascii_uppercase();
+        result
+    }
+}
+
+#[derive(Responder)]
+struct WithLength(&'static str, Header<'static>);
+
+#[get("/sized")]
+fn sized() -> WithLength {
+    WithLength("Hello, world!", Header::new("Content-Length", "13"))
+}
+
+#[get("/streamed")]
+fn streamed() -> ReaderStream![io::Cursor<&'static str>] {
+    ReaderStream! {
+        yield io::Cursor::new("Hello, ");
+        yield io::Cursor::new("streams!");
+    }
+}
+
+#[get("/empty")]
+fn empty() -> Status {
+    Status::NoContent
+}
+
+type Sizes = Arc<Mutex<Vec<(String, usize)>>>;
+
+fn rocket(sizes: Sizes) -> Rocket<Build> {
+    rocket::build()
+        .mount("/", routes![sized, streamed, empty])
+        .attach(AdHoc::on_response("Upper", |req, res| Box::pin(async move {
+            if req.headers().contains("X-Upper") {
+                res.map_body(Upper);
+            }
+        })))
+        .attach(AdHoc::on_response("Sizes", move |req, res| {
+            let (sizes, path) = (sizes.clone(), req.uri().path().to_string());
+            let mut total = 0;
+            res.inspect_body(move |chunk| match chunk.len() {
+                0 => sizes.lock().unwrap().push((path.clone(), total)),
+                n => total += n,
+            });
+
+            Box::pin(async move { })
+        }))
+}
+
+#[test]
+fn response_bodies_can_be_transformed() {
+    let client = Client::debug(rocket(Sizes::default())).unwrap();
+    let upper = || Header::new("X-Upper", "yes");
+
+    let response = client.get("/sized").dispatch();
+    assert_eq!(response.headers().get_one("Content-Length"), Some("13"));
+    assert_eq!(response.into_string().unwrap(), "Hello, world!");
+
+    let response = client.get("/sized").header(upper()).dispatch();
+    assert!(response.headers().get_one("Content-Length").is_none());
+    assert_eq!(response.into_string().unwrap(), "HELLO, WORLD!");
+
+    let response = client.get("/streamed").header(upper()).dispatch();
+    assert_eq!(response.into_string().unwrap(), "HELLO, STREAMS!");
+
+    let response = client.get("/empty").header(upper()).dispatch();
+    assert_eq!(response.status(), Status::NoContent);
+    assert!(response.into_string().is_none());
+}
+
+#[test]
+fn response_bodies_can_be_inspected() {
+    let sizes = Sizes::default();
+    let client = Client::debug(rocket(sizes.clone())).unwrap();
+    client.get("/sized").dispatch().into_string();
+    client.get("/streamed").header(Header::new("X-Upper", "yes")).dispatch().into_string();
+    client.get("/empty").dispatch().into_string();
+    client.head("/sized").dispatch().into_string();
+
+    // The empty body and the body of the `HEAD` request are never read.
+    assert_eq!(sizes.lock().unwrap().as_slice(), &[
+        ("/sized".to_string(), 13),
+        ("/streamed".to_string(), 15),
+    ]);
+}
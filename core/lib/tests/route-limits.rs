@@ -0,0 +1,103 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build, Config};
+use rocket::data::{Limits, ToByteUnit};
+use rocket::figment::util::map;
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use rocket::request::{self, FromRequest, Request};
+
+#[post("/configured", data = "<data>")]
+fn configured(data: String) -> String {
+    data
+}
+
+#[post("/raised", data = "<data>", limits = "string = 16B")]
+fn raised(data: String) -> String {
+    data
+}
+
+#[get("/limits", limits = "json = 5MiB, file/pdf = 1KiB")]
+fn limits(limits: &Limits) -> String {
+    format!("{:?} {:?} {:?} {:?}", limits.get("json"), limits.get("string"),
+        limits.get("file/pdf"), limits.get("file/jpg"))
+}
+
+struct AlwaysForward;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AlwaysForward {
+    type Error = ();
+
+    async fn from_request(_: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        request::Outcome::Forward(())
+    }
+}
+
+#[get("/forward", limits = "string = 1KiB")]
+fn forwarding(_limits: &Limits, _f: AlwaysForward) { }
+
+#[get("/forward", rank = 2, limits = "string = 2KiB")]
+fn forwarded(limits: &Limits) -> String {
+    format!("{:?}", limits.get("string"))
+}
+
+fn rocket() -> Rocket<Build> {
+    let built = routes![configured].into_iter()
+        .map(|route| route.limit("string", 32.bytes()))
+        .collect::<Vec<_>>();
+
+    rocket::custom(Config::figment().merge(("limits", map!["string" => 8])))
+        .mount("/", routes![configured, raised, limits, forwarding, forwarded])
+        .mount("/built", built)
+}
+
+#[test]
+fn route_limits_override_configured_limits() {
+    let client = Client::debug(rocket()).unwrap();
+    let body = "twelve bytes";
+
+    let response = client.post("/configured").body(body).dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = client.post("/raised").body(body).dispatch();
+    assert_eq!(response.into_string().unwrap(), body);
+
+    let response = client.post("/raised").body("more than sixteen bytes").dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = client.post("/built/configured").body(body).dispatch();
+    assert_eq!(response.into_string().unwrap(), body);
+}
+
+#[test]
+fn unset_route_limits_are_configured_limits() {
+    let client = Client::debug(rocket()).unwrap();
+    let response = client.get("/limits").dispatch();
+    assert_eq!(response.into_string().unwrap(), format!("{:?} {:?} {:?} {:?}",
+        Some(5.mebibytes()), Some(8.bytes()), Some(1.kibibytes()), Some(1.mebibytes())));
+}
+
+#[test]
+fn forwarded_request_uses_limits_of_current_route() {
+    let client = Client::debug(rocket()).unwrap();
+    let response = client.get("/forward").dispatch();
+    assert_eq!(response.into_string().unwrap(), format!("{:?}", Some(2.kibibytes())));
+}
+
+#[test]
+fn hot_mounted_route_limits_override_configured_limits() {
+    let client = Client::debug(rocket()).unwrap();
+    let hot = routes![configured].into_iter()
+        .map(|route| route.limit("string", 32.bytes()))
+        .collect::<Vec<_>>();
+
+    client.rocket().hot_mount("/hot", hot).unwrap();
+
+    let body = "twelve bytes";
+    let response = client.post("/hot/configured").body(body).dispatch();
+    assert_eq!(response.into_string().unwrap(), body);
+
+    let response = client.post("/configured").body(body).dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+}
@@ -0,0 +1,105 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Request, Route, Rocket, Build};
+use rocket::fairing::AdHoc;
+use rocket::http::{Header, Method, Status};
+use rocket::local::blocking::Client;
+use rocket::outcome::Outcome;
+use rocket::request::{self, FromRequest};
+
+#[derive(Debug, PartialEq, PartialOrd)]
+enum Role { User, Admin }
+
+#[derive(Debug, PartialEq)]
+struct Label(&'static str);
+
+struct Authorized;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Authorized {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        let role = match req.headers().get_one("X-Role") {
+            Some("admin") => Role::Admin,
+            _ => Role::User,
+        };
+
+        match req.route().and_then(|route| route.meta::<Role>()) {
+            Some(required) if role < *required => Outcome::Failure((Status::Forbidden, ())),
+            _ => Outcome::Success(Authorized),
+        }
+    }
+}
+
+#[get("/admin", meta = [Role::Admin, Label("admin")])]
+fn admin(_auth: Authorized) -> &'static str {
+    "admin"
+}
+
+#[get("/", meta = Label(concat!("in", "dex")))]
+fn index(_auth: Authorized) -> &'static str {
+    "index"
+}
+
+#[get("/plain")]
+fn plain() -> &'static str {
+    "plain"
+}
+
+fn rocket() -> Rocket<Build> {
+    let mut dynamic = Route::new(Method::Get, "/dynamic", rocket::route::dummy_handler);
+    dynamic.set_meta(Label("dynamic"));
+
+    rocket::build()
+        .mount("/", routes![admin, index, plain])
+        .mount("/", vec![dynamic])
+        .attach(AdHoc::on_response("Labeler", |req, res| Box::pin(async move {
+            if let Some(label) = req.route().and_then(|route| route.meta::<Label>()) {
+                res.set_header(Header::new("X-Label", label.0));
+            }
+        })))
+}
+
+#[test]
+fn route_metadata_is_set() {
+    let route = routes![admin].remove(0);
+    assert_eq!(route.meta::<Role>(), Some(&Role::Admin));
+    assert_eq!(route.meta::<Label>(), Some(&Label("admin")));
+
+    let route = routes![index].remove(0);
+    assert_eq!(route.meta::<Role>(), None);
+    assert_eq!(route.meta::<Label>(), Some(&Label("index")));
+
+    let mut route = routes![plain].remove(0);
+    assert_eq!(route.meta::<Label>(), None);
+    route.set_meta(Label("one"));
+    route.set_meta(Label("two"));
+    assert_eq!(route.meta::<Label>(), Some(&Label("two")));
+
+    // Metadata is preserved when a route is mounted.
+    let route = route.map_base(|_| "/base".into()).unwrap();
+    assert_eq!(route.meta::<Label>(), Some(&Label("two")));
+}
+
+#[test]
+fn route_metadata_is_readable_by_guards_and_fairings() {
+    let client = Client::debug(rocket()).unwrap();
+
+    let response = client.get("/admin").dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let response = client.get("/admin").header(Header::new("X-Role", "admin")).dispatch();
+    assert_eq!(response.headers().get_one("X-Label"), Some("admin"));
+    assert_eq!(response.into_string().unwrap(), "admin");
+
+    let response = client.get("/").dispatch();
+    assert_eq!(response.headers().get_one("X-Label"), Some("index"));
+    assert_eq!(response.into_string().unwrap(), "index");
+
+    let response = client.get("/plain").dispatch();
+    assert!(response.headers().get_one("X-Label").is_none());
+
+    let response = client.get("/dynamic").dispatch();
+    assert_eq!(response.headers().get_one("X-Label"), Some("dynamic"));
+}
@@ -0,0 +1,82 @@
+#[macro_use] extern crate rocket;
+
+use std::time::Duration;
+
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use rocket::request::Deadline;
+use rocket::tokio::time::sleep;
+
+#[get("/fast", timeout = "500ms")]
+async fn fast(deadline: Deadline) -> String {
+    let remaining = deadline.remaining().unwrap();
+    assert!(remaining <= Duration::from_millis(500));
+    assert!(!deadline.is_expired());
+    "fast".into()
+}
+
+#[get("/slow", timeout = "50ms")]
+async fn slow() -> &'static str {
+    sleep(Duration::from_secs(30)).await;
+    "slow"
+}
+
+#[get("/cooperative", timeout = "5s")]
+async fn cooperative(deadline: Deadline) -> &'static str {
+    let budget = deadline.remaining().unwrap() / 100;
+    let work = sleep(Duration::from_secs(30));
+    match rocket::tokio::time::timeout(budget, work).await {
+        Ok(_) => "finished",
+        Err(_) => "gave up",
+    }
+}
+
+#[get("/untimed")]
+fn untimed(deadline: Deadline) -> &'static str {
+    assert!(deadline.instant().is_none());
+    assert!(!deadline.is_expired());
+    "untimed"
+}
+
+#[get("/forward/<_n>", timeout = "10ms", rank = 1)]
+fn forward_timed(_n: usize) -> &'static str {
+    "timed"
+}
+
+#[get("/forward/<_s>", rank = 2)]
+fn forward_untimed(_s: &str, deadline: Deadline) -> Option<&'static str> {
+    deadline.instant().is_none().then(|| "untimed")
+}
+
+#[test]
+fn timeout_attribute_sets_route_timeout() {
+    let routes = routes![fast, slow, untimed];
+    assert_eq!(routes[0].timeout, Some(Duration::from_millis(500)));
+    assert_eq!(routes[1].timeout, Some(Duration::from_millis(50)));
+    assert_eq!(routes[2].timeout, None);
+}
+
+#[rocket::async_test]
+async fn handlers_time_out() {
+    let routes = routes![fast, slow, cooperative, untimed, forward_timed, forward_untimed];
+    let client = Client::debug_with(routes).await.unwrap();
+
+    let response = client.get("/fast").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "fast");
+
+    let response = client.get("/slow").dispatch().await;
+    assert_eq!(response.status(), Status::ServiceUnavailable);
+
+    let response = client.get("/cooperative").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "gave up");
+
+    let response = client.get("/untimed").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "untimed");
+
+    // The deadline of a route that forwards doesn't carry over.
+    let response = client.get("/forward/10").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "timed");
+
+    let response = client.get("/forward/ten").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "untimed");
+}
@@ -0,0 +1,103 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::schedule::Scheduler;
+use rocket::tokio::sync::Notify;
+use rocket::tokio::time::{sleep, timeout, Duration};
+
+#[derive(Clone, Default)]
+struct Runs(Arc<AtomicUsize>, Arc<Notify>);
+
+impl Runs {
+    fn get(&self) -> usize {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Waits until there have been at least `n` runs, failing if that takes
+    /// far longer than it should.
+    async fn wait_for(&self, n: usize) {
+        let runs = async {
+            while self.get() < n {
+                self.1.notified().await;
+            }
+        };
+
+        timeout(Duration::from_secs(10), runs).await.expect("jobs ran in time");
+    }
+}
+
+fn counter(rocket: &rocket::Rocket<rocket::Orbit>) -> impl FnMut() -> futures::future::Ready<()> {
+    let runs = rocket.state::<Runs>().cloned().unwrap();
+    move || {
+        runs.0.fetch_add(1, Ordering::SeqCst);
+        runs.1.notify_one();
+        futures::future::ready(())
+    }
+}
+
+#[async_test]
+async fn interval_jobs_run_until_shutdown() {
+    use rocket::local::asynchronous::Client;
+
+    let scheduler = Scheduler::new()
+        .schedule("Counter", Duration::from_millis(50), counter);
+
+    let rocket = rocket::build().manage(Runs::default()).attach(scheduler);
+    let client = Client::debug(rocket).await.unwrap();
+    let runs = client.rocket().state::<Runs>().cloned().unwrap();
+    assert_eq!(runs.get(), 0);
+
+    runs.wait_for(3).await;
+
+    client.terminate().await;
+    let after_shutdown = runs.get();
+    sleep(Duration::from_millis(150)).await;
+    assert_eq!(runs.get(), after_shutdown);
+}
+
+#[async_test]
+async fn panicking_jobs_keep_running() {
+    use rocket::local::asynchronous::Client;
+
+    let scheduler = Scheduler::new()
+        .schedule("Panics", Duration::from_millis(50), |rocket| {
+            let mut count = counter(rocket);
+            move || {
+                let _ = count();
+                async { panic!("job panic") }
+            }
+        });
+
+    let rocket = rocket::build().manage(Runs::default()).attach(scheduler);
+    let client = Client::debug(rocket).await.unwrap();
+    let runs = client.rocket().state::<Runs>().cloned().unwrap();
+    runs.wait_for(3).await;
+    client.terminate().await;
+}
+
+#[async_test]
+async fn cron_jobs_wait_for_schedule() {
+    use rocket::local::asynchronous::Client;
+
+    let scheduler = Scheduler::new().schedule("Yearly", "@yearly", counter);
+    let rocket = rocket::build().manage(Runs::default()).attach(scheduler);
+    let client = Client::debug(rocket).await.unwrap();
+    let runs = client.rocket().state::<Runs>().cloned().unwrap();
+
+    sleep(Duration::from_millis(100)).await;
+    assert_eq!(runs.get(), 0);
+    client.terminate().await;
+}
+
+#[async_test]
+async fn invalid_schedule_fails_ignition() {
+    let scheduler = Scheduler::new()
+        .schedule("Valid", "*/5 * * * *", counter)
+        .schedule("Invalid", "0 25 * * *", counter);
+
+    let rocket = rocket::build().manage(Runs::default()).attach(scheduler);
+    let error = rocket.ignite().await.unwrap_err();
+    assert!(matches!(error.kind(), rocket::error::ErrorKind::FailedFairings(_)));
+}
@@ -0,0 +1,118 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::{Request, Response, Data, Rocket, Build};
+use rocket::fairing::ScopedFairing;
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+use rocket::outcome::Outcome;
+
+struct RequireAdmin;
+
+#[rocket::async_trait]
+impl ScopedFairing for RequireAdmin {
+    async fn on_request(&self, req: &Request<'_>, _: &mut Data<'_>) -> Outcome<(), Status, ()> {
+        match req.headers().get_one("X-Role") {
+            Some("admin") => Outcome::Success(()),
+            Some("guest") => Outcome::Forward(()),
+            _ => Outcome::Failure(Status::Unauthorized),
+        }
+    }
+}
+
+struct Tag(&'static str);
+
+#[rocket::async_trait]
+impl ScopedFairing for Tag {
+    async fn on_response<'r>(&self, _: &'r Request<'_>, res: &mut Response<'r>) {
+        res.adjoin_header(Header::new("X-Tag", self.0));
+    }
+}
+
+#[derive(Default)]
+struct Counter(AtomicUsize);
+
+#[rocket::async_trait]
+impl ScopedFairing for Counter {
+    async fn on_request(&self, _: &Request<'_>, _: &mut Data<'_>) -> Outcome<(), Status, ()> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        Outcome::Success(())
+    }
+}
+
+#[get("/")]
+fn panel() -> &'static str {
+    "panel"
+}
+
+#[get("/", rank = 2)]
+fn guest_panel() -> &'static str {
+    "guest panel"
+}
+
+#[get("/")]
+fn index() -> &'static str {
+    "index"
+}
+
+#[get("/fail")]
+fn fail() -> Status {
+    Status::NotFound
+}
+
+fn rocket(counter: Arc<Counter>) -> Rocket<Build> {
+    let scoped: Vec<Arc<dyn ScopedFairing>> = vec![Arc::new(RequireAdmin), Arc::new(Tag("admin"))];
+    rocket::build()
+        .mount("/", routes![index])
+        .mount_with("/admin", routes![panel, fail], scoped)
+        .mount_with("/admin", routes![guest_panel], [Tag("guest")])
+        .mount_with("/counted", routes![index], [counter])
+}
+
+#[test]
+fn scoped_fairings_only_run_for_their_routes() {
+    let counter = Arc::new(Counter::default());
+    let client = Client::debug(rocket(counter.clone())).unwrap();
+
+    let response = client.get("/").dispatch();
+    assert!(response.headers().get_one("X-Tag").is_none());
+    assert_eq!(response.into_string().unwrap(), "index");
+    assert_eq!(counter.0.load(Ordering::SeqCst), 0);
+
+    let response = client.get("/counted").dispatch();
+    assert_eq!(response.into_string().unwrap(), "index");
+    assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn scoped_request_fairings_control_routing() {
+    let client = Client::debug(rocket(Arc::default())).unwrap();
+    let request = |role: Option<&'static str>, uri: &'static str| {
+        let mut request = client.get(uri);
+        if let Some(role) = role {
+            request = request.header(Header::new("X-Role", role));
+        }
+
+        request.dispatch()
+    };
+
+    let response = request(Some("admin"), "/admin");
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.headers().get("X-Tag").collect::<Vec<_>>(), ["admin"]);
+    assert_eq!(response.into_string().unwrap(), "panel");
+
+    let response = request(Some("guest"), "/admin");
+    assert_eq!(response.headers().get("X-Tag").collect::<Vec<_>>(), ["guest"]);
+    assert_eq!(response.into_string().unwrap(), "guest panel");
+
+    let response = request(None, "/admin/fail");
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert!(response.headers().get_one("X-Tag").is_none());
+
+    // Response callbacks don't run for error responses.
+    let response = request(Some("admin"), "/admin/fail");
+    assert_eq!(response.status(), Status::NotFound);
+    assert!(response.headers().get_one("X-Tag").is_none());
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "secrets")]
+
+use rocket::{get, routes, Rocket, Build};
+use rocket::config::{Config, SecretKey};
+use rocket::figment::Figment;
+use rocket::http::{Cookie, CookieJar};
+use rocket::local::blocking::Client;
+
+#[get("/set")]
+fn set(jar: &CookieJar<'_>) {
+    jar.add_private(Cookie::new("private", "p"));
+    jar.add_signed(Cookie::new("signed", "s"));
+}
+
+#[get("/get")]
+fn get(jar: &CookieJar<'_>) -> String {
+    let private = jar.get_private("private").map(|c| c.value().to_string());
+    let signed = jar.get_signed("signed").map(|c| c.value().to_string());
+    format!("{:?} {:?}", private, signed)
+}
+
+fn rocket(secret_key: &SecretKey, previous: &[&SecretKey]) -> Rocket<Build> {
+    let config = Config {
+        secret_key: secret_key.clone(),
+        previous_secret_keys: previous.iter().map(|&k| k.clone()).collect(),
+        ..Config::debug_default()
+    };
+
+    rocket::custom(config).mount("/", routes![set, get])
+}
+
+fn cookies_from(rocket: Rocket<Build>) -> Vec<Cookie<'static>> {
+    let client = Client::untracked(rocket).unwrap();
+    let response = client.get("/set").dispatch();
+    response.cookies().iter().cloned().collect()
+}
+
+fn get_with(rocket: Rocket<Build>, cookies: &[Cookie<'static>]) -> String {
+    let client = Client::untracked(rocket).unwrap();
+    client.get("/get").cookies(cookies.to_vec()).dispatch().into_string().unwrap()
+}
+
+#[test]
+fn previous_keys_are_accepted() {
+    let (old, new) = (SecretKey::generate().unwrap(), SecretKey::generate().unwrap());
+    let old_cookies = cookies_from(rocket(&old, &[]));
+
+    let all = r#"Some("p") Some("s")"#;
+    assert_eq!(get_with(rocket(&old, &[]), &old_cookies), all);
+    assert_eq!(get_with(rocket(&new, &[]), &old_cookies), "None None");
+    assert_eq!(get_with(rocket(&new, &[&old]), &old_cookies), all);
+
+    // New cookies are only ever created with the current key.
+    let new_cookies = cookies_from(rocket(&new, &[&old]));
+    assert_eq!(get_with(rocket(&new, &[]), &new_cookies), all);
+    assert_eq!(get_with(rocket(&old, &[]), &new_cookies), "None None");
+}
+
+#[test]
+fn previous_keys_roundtrip_through_figment() {
+    let keys = vec![SecretKey::generate().unwrap(), SecretKey::generate().unwrap()];
+    let config = Config { previous_secret_keys: keys.clone(), ..Default::default() };
+    let figment = Figment::from(config);
+    let extracted: Vec<SecretKey> = figment.extract_inner(Config::PREVIOUS_SECRET_KEYS).unwrap();
+    assert!(extracted == keys);
+
+    let figment = Config::figment().merge((Config::PREVIOUS_SECRET_KEYS, [
+        "hPRYyVRiMyxpw5sBB1XeCMN1kFsDCqKvBi2QJxBVHQk=",
+    ]));
+
+    let config: Config = figment.extract().unwrap();
+    assert_eq!(config.previous_secret_keys.len(), 1);
+}
@@ -0,0 +1,108 @@
+use std::io::Write;
+
+use rocket::{Build, Config, Rocket};
+use rocket::config::{SecretProvider, SecretError};
+use rocket::error::ErrorKind;
+use rocket::fairing::AdHoc;
+use rocket::figment::util::map;
+
+struct Reverse;
+
+#[rocket::async_trait]
+impl SecretProvider for Reverse {
+    fn scheme(&self) -> &str {
+        "reverse"
+    }
+
+    async fn resolve(&self, reference: &str) -> Result<String, SecretError> {
+        match reference {
+            "" => Err("empty reference".into()),
+            _ => Ok(reference.chars().rev().collect()),
+        }
+    }
+}
+
+fn rocket_with<T: rocket::serde::Serialize>(key: &str, value: T) -> Rocket<Build> {
+    rocket::custom(Config::figment().merge((key, value)))
+}
+
+#[rocket::async_test]
+async fn custom_provider_resolves_before_ignite_fairings() {
+    let rocket = rocket_with("app.api_key", map!["secret" => "reverse:2retnuh"])
+        .secret_provider(Reverse)
+        .attach(AdHoc::on_ignite("Check", |rocket| async {
+            let key: String = rocket.figment().extract_inner("app.api_key").unwrap();
+            assert_eq!(key, "hunter2");
+            rocket.manage(key)
+        }))
+        .ignite().await
+        .unwrap();
+
+    assert_eq!(rocket.state::<String>().unwrap(), "hunter2");
+}
+
+#[rocket::async_test]
+async fn references_are_resolved_in_arrays() {
+    let value = vec![map!["secret" => "reverse:cba"], map!["plain" => "xyz"]];
+    let rocket = rocket_with("keys", value)
+        .secret_provider(Reverse)
+        .ignite().await
+        .unwrap();
+
+    let keys: rocket::figment::value::Value = rocket.figment().find_value("keys").unwrap();
+    let keys = keys.into_array().unwrap();
+    assert_eq!(keys[0].as_str(), Some("abc"));
+    assert_eq!(keys[1].find_ref("plain").and_then(|v| v.as_str()), Some("xyz"));
+}
+
+#[rocket::async_test]
+async fn env_and_file_providers_are_registered() {
+    std::env::set_var("ROCKET_SECRET_PROVIDERS_TEST", "from env");
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    writeln!(file, "from file").unwrap();
+
+    let figment = Config::figment()
+        .merge(("env_value", map!["secret" => "env:ROCKET_SECRET_PROVIDERS_TEST"]))
+        .merge(("file_value", map!["secret" => format!("file:{}", file.path().display())]));
+
+    let rocket = rocket::custom(figment).ignite().await.unwrap();
+    let env: String = rocket.figment().extract_inner("env_value").unwrap();
+    let file: String = rocket.figment().extract_inner("file_value").unwrap();
+    assert_eq!(env, "from env");
+    assert_eq!(file, "from file");
+}
+
+#[rocket::async_test]
+async fn unresolvable_references_fail_ignition() {
+    let error = rocket_with("api_key", map!["secret" => "unknown:foo"])
+        .ignite().await
+        .unwrap_err();
+
+    match error.kind() {
+        ErrorKind::Config(e) => {
+            assert_eq!(e.path, vec!["api_key".to_string()]);
+            assert!(e.to_string().contains("no secret provider for scheme `unknown`"));
+        }
+        kind => panic!("unexpected error kind: {:?}", kind),
+    }
+
+    let error = rocket_with("db.password", map!["secret" => "reverse:"])
+        .secret_provider(Reverse)
+        .ignite().await
+        .unwrap_err();
+
+    match error.kind() {
+        ErrorKind::Config(e) => assert_eq!(e.path, vec!["db", "password"]),
+        kind => panic!("unexpected error kind: {:?}", kind),
+    }
+}
+
+#[rocket::async_test]
+async fn dictionaries_with_other_keys_are_not_references() {
+    let rocket = rocket_with("app", map!["secret" => "env:HOME", "other" => "value"])
+        .ignite().await
+        .unwrap();
+
+    let secret: String = rocket.figment().extract_inner("app.secret").unwrap();
+    assert_eq!(secret, "env:HOME");
+}
@@ -9,6 +9,8 @@ use time::Duration;
 
 #[get("/")] fn hello() { }
 
+#[get("/nonce")] fn nonce(nonce: &CspNonce) -> String { nonce.to_string() }
+
 macro_rules! assert_header {
     ($response:ident, $name:expr, $value:expr) => {
         match $response.headers().get_one($name) {
@@ -250,3 +252,82 @@ fn permission_test() {
             "accelerometer=(\"http://rocket.rs\" \"https://rocket.rs\"), usb=()");
     });
 }
+
+#[test]
+fn csp_test() {
+    let csp = Csp::default();
+    dispatch!(Shield::new().enable(csp), |response: LocalResponse<'_>| {
+        assert_header!(response, "Content-Security-Policy", "default-src 'self'");
+    });
+
+    let csp = Csp::new()
+        .directive(Directive::DefaultSrc, Source::None)
+        .directive(Directive::ImgSrc, [Source::This, Source::Scheme("data".into())])
+        .directive(Directive::ConnectSrc, Source::Origin(uri!("https://rocket.rs:8443/a?b")))
+        .directive(Directive::FontSrc, Source::Host("*.example.com".into()))
+        .directive(Directive::UpgradeInsecureRequests, vec![]);
+
+    dispatch!(Shield::new().enable(csp), |response: LocalResponse<'_>| {
+        assert_header!(response, "Content-Security-Policy",
+            "default-src 'none'; img-src 'self' data:; \
+            connect-src https://rocket.rs:8443; font-src *.example.com; \
+            upgrade-insecure-requests");
+    });
+}
+
+#[test]
+fn csp_nonce_test() {
+    let csp = Csp::default()
+        .directive(Directive::ScriptSrc, [Source::This, Source::Nonce])
+        .directive(Directive::StyleSrc, Source::Nonce);
+
+    let rocket = rocket::build().mount("/", routes![nonce]).attach(Shield::new().enable(csp));
+    let client = Client::debug(rocket).unwrap();
+
+    let (a, b) = (client.get("/nonce").dispatch(), client.get("/nonce").dispatch());
+    let header_a = a.headers().get_one("Content-Security-Policy").unwrap().to_string();
+    let header_b = b.headers().get_one("Content-Security-Policy").unwrap().to_string();
+    let (nonce_a, nonce_b) = (a.into_string().unwrap(), b.into_string().unwrap());
+    assert_ne!(nonce_a, nonce_b);
+    assert_eq!(header_a, format!("default-src 'self'; script-src 'self' 'nonce-{0}'; \
+        style-src 'nonce-{0}'", nonce_a));
+    assert_eq!(header_b, format!("default-src 'self'; script-src 'self' 'nonce-{0}'; \
+        style-src 'nonce-{0}'", nonce_b));
+}
+
+#[get("/widget/frame")] fn widget() { }
+#[get("/widgets")] fn widgets() { }
+#[get("/widget/admin/panel")] fn admin() { }
+
+#[test]
+fn scoped_shield_test() {
+    let shield = Shield::default()
+        .scope("/widget", Shield::default().disable::<Frame>()
+            .scope("/widget/admin", Shield::default().enable(Frame::Deny)))
+        .scope("/api", Shield::new());
+
+    let rocket = rocket::build()
+        .mount("/", routes![hello, widget, widgets, admin])
+        .attach(shield);
+
+    let client = Client::debug(rocket).unwrap();
+    let response = client.get("/").dispatch();
+    assert_header!(response, "X-Frame-Options", "SAMEORIGIN");
+    assert_header!(response, "X-Content-Type-Options", "nosniff");
+
+    let response = client.get("/widget/frame").dispatch();
+    assert_no_header!(response, "X-Frame-Options");
+    assert_header!(response, "X-Content-Type-Options", "nosniff");
+
+    // Scopes match by segments, not by string prefix.
+    let response = client.get("/widgets").dispatch();
+    assert_header!(response, "X-Frame-Options", "SAMEORIGIN");
+
+    let response = client.get("/widget/admin/panel").dispatch();
+    assert_header!(response, "X-Frame-Options", "DENY");
+
+    let response = client.get("/api/anything").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert_no_header!(response, "X-Frame-Options");
+    assert_no_header!(response, "X-Content-Type-Options");
+}
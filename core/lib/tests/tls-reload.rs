@@ -0,0 +1,166 @@
+#![cfg(feature = "tls")]
+
+use std::net::{SocketAddr, TcpStream};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use rocket::{post, routes, Build, Rocket};
+use rocket::fs::relative;
+use rocket::config::{Config, TlsConfig};
+use rocket::request::{self, Request, FromRequest};
+
+mod common;
+use common::{launch, pem_certs};
+
+macro_rules! private {
+    ($file:expr) => (relative!(concat!("../../examples/tls/private/", $file)))
+}
+
+struct Reload(std::io::Result<()>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Reload {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        request::Outcome::Success(Reload(req.rocket().reload_tls()))
+    }
+}
+
+#[post("/reload")]
+fn reload(reload: Reload) -> String {
+    match reload.0 {
+        Ok(()) => "ok".into(),
+        Err(e) => e.to_string(),
+    }
+}
+
+/// Copies of a certificate chain and key that can be replaced during a test.
+struct TempTls(PathBuf, PathBuf);
+
+impl TempTls {
+    fn new(name: &str) -> TempTls {
+        let dir = std::env::temp_dir();
+        let pid = std::process::id();
+        let tls = TempTls(
+            dir.join(format!("rocket-{}-{}-cert.pem", name, pid)),
+            dir.join(format!("rocket-{}-{}-key.pem", name, pid)),
+        );
+
+        tls.replace(private!("rsa_sha256_cert.pem"), private!("rsa_sha256_key.pem"));
+        tls
+    }
+
+    fn replace(&self, cert: &str, key: &str) {
+        std::fs::copy(cert, &self.0).unwrap();
+        std::fs::copy(key, &self.1).unwrap();
+    }
+
+    fn rocket(&self, sighup: bool) -> Rocket<Build> {
+        let tls = TlsConfig::from_paths(&self.0, &self.1).with_reload_on_sighup(sighup);
+        rocket::custom(Config { port: 0, tls: Some(tls), ..Config::debug_default() })
+            .mount("/", routes![reload])
+    }
+}
+
+impl Drop for TempTls {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+        let _ = std::fs::remove_file(&self.1);
+    }
+}
+
+/// Connects to `addr`, optionally sends `request`, and returns the server's
+/// leaf certificate along with the response body.
+async fn connect(addr: SocketAddr, request: Option<&'static str>) -> (rustls::Certificate, String) {
+    use std::io::{Read, Write};
+
+    rocket::tokio::task::spawn_blocking(move || {
+        let mut roots = rustls::RootCertStore::empty();
+        for cert in pem_certs(private!("ca_cert.pem")) {
+            roots.add(&cert).unwrap();
+        }
+
+        let tls = rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+
+        let name = "localhost".try_into().unwrap();
+        let conn = rustls::ClientConnection::new(Arc::new(tls), name).unwrap();
+        let mut stream = rustls::StreamOwned::new(conn, TcpStream::connect(addr).unwrap());
+        let request = request.unwrap_or("GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n");
+        stream.write_all(request.as_bytes()).unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let cert = stream.conn.peer_certificates().unwrap()[0].clone();
+        let body = response.split("\r\n\r\n").nth(1).unwrap_or("").to_string();
+        (cert, body)
+    }).await.unwrap()
+}
+
+const RELOAD: &str = "POST /reload HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+
+#[rocket::async_test]
+async fn reload_tls_replaces_certificates() {
+    let tls = TempTls::new("reload");
+    let (addr, shutdown) = launch(tls.rocket(false)).await;
+    let rsa = pem_certs(private!("rsa_sha256_cert.pem")).remove(0);
+    let ecdsa = pem_certs(private!("ecdsa_nistp256_sha256_cert.pem")).remove(0);
+    assert_eq!(connect(addr, None).await.0, rsa);
+
+    // A bad key fails the reload and keeps the existing certificates.
+    tls.replace(private!("ecdsa_nistp256_sha256_cert.pem"), private!("ca_cert.pem"));
+    let (cert, body) = connect(addr, Some(RELOAD)).await;
+    assert_eq!(cert, rsa);
+    assert!(body.contains("private key"));
+    assert_eq!(connect(addr, None).await.0, rsa);
+
+    tls.replace(
+        private!("ecdsa_nistp256_sha256_cert.pem"),
+        private!("ecdsa_nistp256_sha256_key_pkcs8.pem")
+    );
+
+    assert_eq!(connect(addr, Some(RELOAD)).await.1, "ok");
+    assert_eq!(connect(addr, None).await.0, ecdsa);
+    shutdown.notify();
+}
+
+#[cfg(unix)]
+#[rocket::async_test]
+async fn sighup_reloads_certificates() {
+    let tls = TempTls::new("sighup");
+    let (addr, shutdown) = launch(tls.rocket(true)).await;
+    let ecdsa = pem_certs(private!("ecdsa_nistp256_sha256_cert.pem")).remove(0);
+
+    tls.replace(
+        private!("ecdsa_nistp256_sha256_cert.pem"),
+        private!("ecdsa_nistp256_sha256_key_pkcs8.pem")
+    );
+
+    let pid = std::process::id().to_string();
+    let status = std::process::Command::new("kill").args(&["-HUP", &pid]).status().unwrap();
+    assert!(status.success());
+
+    // Signal delivery is asynchronous. Give the reload a moment to complete.
+    for _ in 0..50 {
+        if connect(addr, None).await.0 == ecdsa {
+            return shutdown.notify();
+        }
+
+        rocket::tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+
+    panic!("certificates were not reloaded on SIGHUP");
+}
+
+#[test]
+fn reload_tls_requires_tls_server() {
+    use rocket::local::blocking::Client;
+
+    let tls = TempTls::new("local");
+    let client = Client::debug(tls.rocket(false)).unwrap();
+    let response = client.post("/reload").dispatch();
+    assert_eq!(response.into_string().unwrap(), "TLS is not being served");
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "tls")]
+
+use std::net::{SocketAddr, TcpStream};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use rocket::{Build, Rocket};
+use rocket::fs::relative;
+use rocket::config::{Config, TlsConfig, SniCert};
+
+use rustls::{Certificate, ServerName};
+use rustls::client::{ServerCertVerifier, ServerCertVerified};
+
+mod common;
+use common::launch;
+
+macro_rules! private {
+    ($file:expr) => (relative!(concat!("../../examples/tls/private/", $file)))
+}
+
+fn rocket() -> Rocket<Build> {
+    let tls = TlsConfig::from_paths(private!("rsa_sha256_cert.pem"), private!("rsa_sha256_key.pem"))
+        .with_sni("SNI.rocket.test", SniCert::from_paths(
+            private!("ecdsa_nistp256_sha256_cert.pem"),
+            private!("ecdsa_nistp256_sha256_key_pkcs8.pem"),
+        ))
+        .with_sni("*.wild.rocket.test", SniCert::from_paths(
+            private!("ecdsa_nistp384_sha384_cert.pem"),
+            private!("ecdsa_nistp384_sha384_key_pkcs8.pem"),
+        ));
+
+    rocket::custom(Config { port: 0, tls: Some(tls), ..Config::debug_default() })
+}
+
+fn pem_cert(path: &str) -> Certificate {
+    let mut reader = std::io::BufReader::new(std::fs::File::open(path).unwrap());
+    Certificate(rustls_pemfile::certs(&mut reader).unwrap().remove(0))
+}
+
+/// Accepts any server certificate: the test certificates are all issued for
+/// `localhost`, but we only care about which one the server presents.
+struct AcceptAny;
+
+impl ServerCertVerifier for AcceptAny {
+    fn verify_server_cert(
+        &self,
+        _: &Certificate,
+        _: &[Certificate],
+        _: &ServerName,
+        _: &mut dyn Iterator<Item = &[u8]>,
+        _: &[u8],
+        _: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+/// Performs a TLS handshake with `addr` indicating the SNI hostname `name`
+/// and returns the server's leaf certificate.
+async fn server_cert(addr: SocketAddr, name: &'static str) -> Certificate {
+    rocket::tokio::task::spawn_blocking(move || {
+        let tls = rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_custom_certificate_verifier(Arc::new(AcceptAny))
+            .with_no_client_auth();
+
+        let name = name.try_into().unwrap();
+        let mut conn = rustls::ClientConnection::new(Arc::new(tls), name).unwrap();
+        let mut socket = TcpStream::connect(addr).unwrap();
+        while conn.is_handshaking() {
+            conn.complete_io(&mut socket).unwrap();
+        }
+
+        conn.peer_certificates().unwrap()[0].clone()
+    }).await.unwrap()
+}
+
+#[rocket::async_test]
+async fn certificates_are_selected_by_sni() {
+    let (addr, shutdown) = launch(rocket()).await;
+    let rsa = pem_cert(private!("rsa_sha256_cert.pem"));
+    let p256 = pem_cert(private!("ecdsa_nistp256_sha256_cert.pem"));
+    let p384 = pem_cert(private!("ecdsa_nistp384_sha384_cert.pem"));
+
+    assert_eq!(server_cert(addr, "localhost").await, rsa);
+    assert_eq!(server_cert(addr, "sni.rocket.test").await, p256);
+    assert_eq!(server_cert(addr, "Sni.Rocket.Test").await, p256);
+    assert_eq!(server_cert(addr, "other.rocket.test").await, rsa);
+
+    assert_eq!(server_cert(addr, "a.wild.rocket.test").await, p384);
+    assert_eq!(server_cert(addr, "B.Wild.rocket.test").await, p384);
+    assert_eq!(server_cert(addr, "wild.rocket.test").await, rsa);
+    assert_eq!(server_cert(addr, "a.b.wild.rocket.test").await, rsa);
+
+    shutdown.notify();
+}
+
+#[test]
+fn invalid_sni_certificates_fail_launch() {
+    let tls = TlsConfig::from_paths(private!("rsa_sha256_cert.pem"), private!("rsa_sha256_key.pem"))
+        .with_sni("sni.rocket.test", SniCert::from_paths(
+            private!("ecdsa_nistp256_sha256_cert.pem"),
+            private!("ca_cert.pem"),
+        ));
+
+    let rocket = rocket::custom(Config { port: 0, tls: Some(tls), ..Config::debug_default() });
+    let error = rocket::execute(rocket.launch()).unwrap_err();
+    assert!(error.to_string().contains("sni.rocket.test"));
+}
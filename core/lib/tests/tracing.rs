@@ -0,0 +1,158 @@
+#![cfg(feature = "tracing")]
+
+#[macro_use] extern crate rocket;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use rocket::fairing::AdHoc;
+use rocket::http::Header;
+use rocket::local::blocking::Client;
+use rocket::request::RequestId;
+use rocket::tracing::{self, Event, Metadata, Subscriber};
+use rocket::tracing::field::{Field, Visit};
+use rocket::tracing::span::{Attributes, Id, Record};
+
+type Fields = HashMap<String, String>;
+
+#[derive(Default)]
+struct State {
+    spans: HashMap<u64, (String, Fields)>,
+    stack: Vec<u64>,
+    events: Vec<(Option<u64>, Fields)>,
+}
+
+/// A subscriber that records every span and event.
+#[derive(Clone, Default)]
+struct Recorder {
+    next_id: Arc<AtomicU64>,
+    state: Arc<Mutex<State>>,
+}
+
+struct Visitor<'a>(&'a mut Fields);
+
+impl Visit for Visitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name().into(), format!("{:?}", value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().into(), value.into());
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+        let mut fields = Fields::new();
+        span.record(&mut Visitor(&mut fields));
+        let name = span.metadata().name().to_string();
+        self.state.lock().unwrap().spans.insert(id, (name, fields));
+        Id::from_u64(id)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut state = self.state.lock().unwrap();
+        let (_, fields) = state.spans.get_mut(&span.into_u64()).unwrap();
+        values.record(&mut Visitor(fields));
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) { }
+
+    fn event(&self, event: &Event<'_>) {
+        let mut state = self.state.lock().unwrap();
+        let parent = match event.parent() {
+            Some(parent) => Some(parent.into_u64()),
+            None if event.is_contextual() => state.stack.last().copied(),
+            None => None,
+        };
+
+        let mut fields = Fields::new();
+        event.record(&mut Visitor(&mut fields));
+        state.events.push((parent, fields));
+    }
+
+    fn enter(&self, span: &Id) {
+        self.state.lock().unwrap().stack.push(span.into_u64());
+    }
+
+    fn exit(&self, _: &Id) {
+        self.state.lock().unwrap().stack.pop();
+    }
+}
+
+impl Recorder {
+    fn request_spans(&self) -> Vec<Fields> {
+        let state = self.state.lock().unwrap();
+        let mut spans: Vec<_> = state.spans.iter()
+            .filter(|(_, (name, _))| name == "request")
+            .collect();
+
+        spans.sort_by_key(|(id, _)| **id);
+        spans.into_iter().map(|(_, (_, fields))| fields.clone()).collect()
+    }
+
+    fn events_with(&self, message: &str) -> Vec<(Option<u64>, Fields)> {
+        self.state.lock().unwrap().events.iter()
+            .filter(|(_, fields)| fields.get("message").map_or(false, |m| m == message))
+            .cloned()
+            .collect()
+    }
+
+    fn span_of(&self, id: u64) -> Fields {
+        self.state.lock().unwrap().spans[&id].1.clone()
+    }
+}
+
+#[get("/hello")]
+fn hello() -> &'static str {
+    tracing::info!("in handler");
+    "Hello, world!"
+}
+
+#[test]
+fn request_spans() {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let rocket = rocket::build()
+            .mount("/", routes![hello])
+            .attach(RequestId::fairing())
+            .attach(AdHoc::on_request("Trace", |req, _| Box::pin(async move {
+                let agent = req.headers().get_one("User-Agent").unwrap_or("none");
+                tracing::info!(parent: req.span(), agent, "in fairing");
+            })));
+
+        let client = Client::debug(rocket).unwrap();
+        assert_eq!(client.get("/hello").dispatch().into_string().unwrap(), "Hello, world!");
+        client.post("/missing?a=b").header(Header::new("X-Request-Id", "abc")).dispatch();
+    });
+
+    let spans = recorder.request_spans();
+    assert_eq!(spans.len(), 2);
+    assert_eq!(spans[0]["method"], "GET");
+    assert_eq!(spans[0]["uri"], "/hello");
+    assert_eq!(spans[0]["route"], "hello");
+    assert_eq!(spans[0]["status"], "200");
+    assert_eq!(spans[0]["request_id"].len(), 36);
+
+    assert_eq!(spans[1]["method"], "POST");
+    assert_eq!(spans[1]["uri"], "/missing?a=b");
+    assert!(spans[1].get("route").is_none());
+    assert_eq!(spans[1]["status"], "404");
+    assert_eq!(spans[1]["request_id"], "abc");
+
+    let handler_events = recorder.events_with("in handler");
+    assert_eq!(handler_events.len(), 1);
+    assert_eq!(recorder.span_of(handler_events[0].0.unwrap())["route"], "hello");
+
+    let fairing_events = recorder.events_with("in fairing");
+    assert_eq!(fairing_events.len(), 2);
+    assert_eq!(fairing_events[0].1["agent"], "none");
+    assert_eq!(recorder.span_of(fairing_events[1].0.unwrap())["uri"], "/missing?a=b");
+}
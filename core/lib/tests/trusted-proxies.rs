@@ -0,0 +1,131 @@
+#[macro_use] extern crate rocket;
+
+use std::net::IpAddr;
+
+use rocket::{Rocket, Build, Config};
+use rocket::http::Header;
+use rocket::http::uri::Host;
+use rocket::local::blocking::{Client, LocalRequest};
+use rocket::request::BaseUrl;
+
+#[get("/")]
+fn index(client_ip: Option<IpAddr>, base: Option<BaseUrl>) -> String {
+    format!("{:?} {}", client_ip, base.map(|b| b.to_string()).unwrap_or_default())
+}
+
+fn rocket(proxies: Option<&[&str]>) -> Rocket<Build> {
+    let mut figment = Config::figment();
+    if let Some(proxies) = proxies {
+        figment = figment.merge((Config::TRUSTED_PROXIES, proxies));
+    }
+
+    rocket::custom(figment).mount("/", routes![index])
+}
+
+fn get<'c>(client: &'c Client, remote: &str, headers: &[(&'static str, &'static str)]) -> String {
+    let mut req: LocalRequest<'c> = client.get("/").remote(remote.parse().unwrap());
+    req.inner_mut().set_host(Host::from(uri!("rocket.rs")));
+
+    for &(name, value) in headers {
+        req = req.header(Header::new(name, value));
+    }
+
+    req.dispatch().into_string().unwrap()
+}
+
+#[test]
+fn only_real_ip_is_trusted_by_default() {
+    let client = Client::debug(rocket(None)).unwrap();
+    assert!(client.rocket().config().trusted_proxies.is_none());
+
+    let headers = [
+        ("X-Real-IP", "8.8.8.8"),
+        ("X-Forwarded-Proto", "https"),
+        ("X-Forwarded-Host", "attacker.com"),
+    ];
+
+    assert_eq!(get(&client, "1.1.1.1:80", &headers), "Some(8.8.8.8) http://rocket.rs");
+}
+
+#[test]
+fn headers_from_untrusted_peers_are_ignored() {
+    let client = Client::debug(rocket(Some(&["10.0.0.0/8"]))).unwrap();
+    let headers = [
+        ("X-Real-IP", "8.8.8.8"),
+        ("X-Forwarded-For", "8.8.8.8"),
+        ("X-Forwarded-Proto", "https"),
+        ("X-Forwarded-Host", "attacker.com"),
+        ("Forwarded", "for=8.8.8.8;proto=https;host=attacker.com"),
+    ];
+
+    assert_eq!(get(&client, "1.1.1.1:80", &headers), "Some(1.1.1.1) http://rocket.rs");
+    assert_eq!(get(&client, "1.1.1.1:80", &headers[..4]), "Some(1.1.1.1) http://rocket.rs");
+
+    // An empty list trusts no one.
+    let client = Client::debug(rocket(Some(&[]))).unwrap();
+    assert_eq!(get(&client, "10.0.0.1:80", &headers), "Some(10.0.0.1) http://rocket.rs");
+}
+
+#[test]
+fn x_forwarded_headers_from_trusted_proxies_are_honored() {
+    let client = Client::debug(rocket(Some(&["10.0.0.0/8", "::1/128"]))).unwrap();
+
+    let headers = [("X-Forwarded-For", "8.8.8.8"), ("X-Forwarded-Proto", "https")];
+    assert_eq!(get(&client, "10.1.2.3:80", &headers), "Some(8.8.8.8) https://rocket.rs");
+    assert_eq!(get(&client, "[::1]:80", &headers), "Some(8.8.8.8) https://rocket.rs");
+    assert_eq!(get(&client, "[::ffff:10.0.0.1]:80", &headers), "Some(8.8.8.8) https://rocket.rs");
+
+    // Chains are walked until the first untrusted hop, even if spoofed.
+    let headers = [("X-Forwarded-For", "6.6.6.6, 8.8.8.8, 10.0.0.2")];
+    assert_eq!(get(&client, "10.0.0.1:80", &headers), "Some(8.8.8.8) http://rocket.rs");
+
+    let headers = [("X-Forwarded-For", "10.0.0.3, 10.0.0.2")];
+    assert_eq!(get(&client, "10.0.0.1:80", &headers), "Some(10.0.0.3) http://rocket.rs");
+
+    let headers = [("X-Real-IP", "8.8.8.8"), ("X-Forwarded-Host", "api.rocket.rs")];
+    assert_eq!(get(&client, "10.0.0.1:80", &headers), "Some(8.8.8.8) http://api.rocket.rs");
+}
+
+#[test]
+fn forwarded_header_from_trusted_proxies_is_honored() {
+    let client = Client::debug(rocket(Some(&["10.0.0.0/8"]))).unwrap();
+
+    let headers = [("Forwarded", r#"for="[2001:db8::17]:4711";proto=https;host=api.rocket.rs"#)];
+    assert_eq!(get(&client, "10.0.0.1:80", &headers), "Some(2001:db8::17) https://api.rocket.rs");
+
+    // `Forwarded` takes precedence over `X-Forwarded-*`.
+    let headers = [("Forwarded", "for=8.8.8.8"), ("X-Forwarded-For", "9.9.9.9")];
+    assert_eq!(get(&client, "10.0.0.1:80", &headers), "Some(8.8.8.8) http://rocket.rs");
+
+    // The outermost trusted hop's values win; untrusted hops are ignored.
+    let headers = [
+        ("Forwarded", "for=6.6.6.6;proto=http;host=attacker.com"),
+        ("Forwarded", "for=8.8.8.8;proto=https, for=10.0.0.2;host=rocket.rs:8443"),
+    ];
+
+    assert_eq!(get(&client, "10.0.0.1:80", &headers), "Some(8.8.8.8) https://rocket.rs:8443");
+
+    // Obfuscated nodes end the walk at the proxy that recorded them.
+    let headers = [("Forwarded", "for=_hidden;proto=https")];
+    assert_eq!(get(&client, "10.0.0.1:80", &headers), "Some(10.0.0.1) https://rocket.rs");
+}
+
+#[test]
+fn trusted_proxies_are_configurable() {
+    figment::Jail::expect_with(|jail| {
+        jail.create_file("Rocket.toml", r#"
+            [default]
+            trusted_proxies = ["10.0.0.0/8", "fd00::/8"]
+        "#)?;
+
+        let config = Config::from(Config::figment());
+        assert_eq!(config.trusted_proxies, Some(vec![
+            "10.0.0.0/8".parse().unwrap(),
+            "fd00::/8".parse().unwrap(),
+        ]));
+
+        jail.set_env("ROCKET_TRUSTED_PROXIES", "[\"127.0.0.1\"]");
+        assert!(Config::try_from(Config::figment()).is_err());
+        Ok(())
+    });
+}
@@ -0,0 +1,115 @@
+#[macro_use] extern crate rocket;
+
+use std::fmt;
+use std::num::ParseIntError;
+use std::sync::{Arc, Mutex};
+
+use rocket::{Request, Rocket, Build};
+use rocket::fairing::AdHoc;
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use rocket::response::Report;
+
+#[derive(Debug)]
+struct Forbidden(&'static str);
+
+impl fmt::Display for Forbidden {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "forbidden: {}", self.0)
+    }
+}
+
+impl std::error::Error for Forbidden { }
+
+#[get("/<n>")]
+fn double(n: &str) -> Result<String, Report> {
+    Ok((n.parse::<i64>()? * 2).to_string())
+}
+
+#[get("/forbidden")]
+fn forbidden() -> Result<(), Report> {
+    Err(Report::new(Forbidden("admins only")).status(Status::Forbidden))
+}
+
+#[get("/io")]
+fn io() -> Result<(), Report> {
+    Err(std::io::Error::new(std::io::ErrorKind::Other, "disk on fire").into())
+}
+
+#[catch(500, error = "ParseIntError")]
+fn invalid_number(error: &ParseIntError) -> String {
+    format!("invalid number: {}", error)
+}
+
+#[catch(403, error = "Forbidden")]
+fn forbidden_catcher(error: &Forbidden, status: Status, req: &Request<'_>) -> String {
+    format!("{} {} {}", status.code, req.uri(), error)
+}
+
+#[catch(500)]
+fn internal_error(req: &Request<'_>) -> String {
+    let error = req.recorded_error().map(|e| e.to_string()).unwrap_or_default();
+    format!("internal error: {}", error)
+}
+
+#[catch(500)]
+fn scoped_internal_error() -> &'static str {
+    "scoped internal error"
+}
+
+type Reports = Arc<Mutex<Vec<Option<String>>>>;
+
+fn rocket(reports: Reports) -> Rocket<Build> {
+    rocket::build()
+        .mount("/", routes![double, forbidden, io])
+        .mount("/scoped", routes![double])
+        .register("/", catchers![invalid_number, forbidden_catcher, internal_error])
+        .register("/scoped", catchers![scoped_internal_error])
+        .attach(AdHoc::on_error("Reporter", move |info, _| {
+            reports.lock().unwrap().push(info.error().map(|e| e.to_string()));
+            Box::pin(async move { })
+        }))
+}
+
+#[test]
+fn typed_catchers_receive_errors() {
+    let client = Client::debug(rocket(Reports::default())).unwrap();
+    let response = client.get("/21").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "42");
+
+    let response = client.get("/ten").dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+    assert_eq!(response.into_string().unwrap(), "invalid number: invalid digit found in string");
+
+    let response = client.get("/forbidden").dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+    assert_eq!(response.into_string().unwrap(), "403 /forbidden forbidden: admins only");
+}
+
+#[test]
+fn untyped_catchers_handle_other_errors() {
+    let client = Client::debug(rocket(Reports::default())).unwrap();
+    let response = client.get("/io").dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+    assert_eq!(response.into_string().unwrap(), "internal error: disk on fire");
+
+    // A catcher with a more specific base wins, typed or not.
+    let response = client.get("/scoped/ten").dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+    assert_eq!(response.into_string().unwrap(), "scoped internal error");
+}
+
+#[test]
+fn caught_errors_are_reported() {
+    let reports = Reports::default();
+    let client = Client::debug(rocket(reports.clone())).unwrap();
+    client.get("/ten").dispatch();
+    client.get("/forbidden").dispatch();
+
+    let reports = reports.lock().unwrap();
+    assert_eq!(reports.as_slice(), &[
+        Some("invalid digit found in string".into()),
+        Some("forbidden: admins only".into()),
+    ]);
+}
@@ -0,0 +1,114 @@
+#![cfg(all(feature = "json", feature = "secrets"))]
+
+#[macro_use] extern crate rocket;
+
+use rocket::http::{Cookie, CookieJar, CookieEncoding, CookiePrivacy, Status, TypedCookie};
+use rocket::local::blocking::Client;
+use rocket::serde::{Serialize, Deserialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, TypedCookie)]
+#[serde(crate = "rocket::serde")]
+struct Prefs {
+    theme: String,
+    size: u8,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, TypedCookie)]
+#[serde(crate = "rocket::serde")]
+#[cookie(name = "cart", encoding = "base64", privacy = "signed", max_age = 3600)]
+struct Cart(Vec<u32>);
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, TypedCookie)]
+#[serde(crate = "rocket::serde")]
+#[cookie(privacy = "private")]
+struct Session {
+    user: String,
+}
+
+#[get("/set")]
+fn set(jar: &CookieJar<'_>) {
+    jar.add_typed(Prefs { theme: "dark".into(), size: 12 }).unwrap();
+    jar.add_typed(Cart(vec![1, 2, 3])).unwrap();
+    jar.add_typed(Session { user: "bob".into() }).unwrap();
+}
+
+#[get("/get")]
+fn get(jar: &CookieJar<'_>) -> String {
+    format!("{:?} {:?} {:?}",
+        jar.get_typed::<Prefs>(), jar.get_typed::<Cart>(), jar.get_typed::<Session>())
+}
+
+#[get("/remove")]
+fn remove(jar: &CookieJar<'_>) {
+    jar.remove_typed::<Prefs>();
+    jar.remove_typed::<Cart>();
+    jar.remove_typed::<Session>();
+}
+
+fn client() -> Client {
+    Client::debug_with(routes![set, get, remove]).unwrap()
+}
+
+#[test]
+fn typed_cookie_consts() {
+    assert_eq!(Prefs::NAME, "Prefs");
+    assert_eq!(Prefs::ENCODING, CookieEncoding::Json);
+    assert_eq!(Prefs::PRIVACY, CookiePrivacy::Plain);
+    assert_eq!(Prefs::MAX_AGE, None);
+
+    assert_eq!(Cart::NAME, "cart");
+    assert_eq!(Cart::ENCODING, CookieEncoding::Base64);
+    assert_eq!(Cart::PRIVACY, CookiePrivacy::Signed);
+    assert_eq!(Cart::MAX_AGE, Some(rocket::time::Duration::hours(1)));
+
+    assert_eq!(Session::PRIVACY, CookiePrivacy::Private);
+}
+
+#[test]
+fn typed_cookies_roundtrip() {
+    let client = client();
+    let response = client.get("/set").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    let cookies = response.cookies();
+    let prefs = cookies.get("Prefs").unwrap();
+    assert_eq!(prefs.value(), r#"{"theme":"dark","size":12}"#);
+    assert_eq!(prefs.max_age(), None);
+
+    // Signed cookies are readable but carry a signature.
+    let cart = cookies.get("cart").unwrap();
+    assert!(cart.value().ends_with("WzEsMiwzXQ=="));
+    assert_eq!(cart.max_age(), Some(rocket::time::Duration::hours(1)));
+
+    let session = cookies.get("Session").unwrap();
+    assert!(!session.value().contains("bob"));
+
+    let response = client.get("/get").dispatch();
+    assert_eq!(response.into_string().unwrap(),
+        r#"Some(Prefs { theme: "dark", size: 12 }) Some(Cart([1, 2, 3])) Some(Session { user: "bob" })"#);
+
+    client.get("/remove").dispatch();
+    let response = client.get("/get").dispatch();
+    assert_eq!(response.into_string().unwrap(), "None None None");
+}
+
+#[test]
+fn invalid_typed_cookies_are_none() {
+    let client = client();
+    let response = client.get("/get")
+        .cookie(Cookie::new("Prefs", "not json"))
+        .cookie(Cookie::new("cart", "WzEsMiwzXQ=="))
+        .cookie(Cookie::new("Session", r#"{"user":"mallory"}"#))
+        .dispatch();
+
+    assert_eq!(response.into_string().unwrap(), "None None None");
+
+    let response = client.get("/get")
+        .cookie(Cookie::new("Prefs", r#"{"theme":"light","size":9}"#))
+        .signed_cookie(Cookie::new("cart", "WzEsMiwzXQ=="))
+        .private_cookie(Cookie::new("Session", r#"{"user":"alice"}"#))
+        .dispatch();
+
+    assert_eq!(response.into_string().unwrap(),
+        r#"Some(Prefs { theme: "light", size: 9 }) Some(Cart([1, 2, 3])) Some(Session { user: "alice" })"#);
+}
@@ -0,0 +1,98 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::{Authorization, CacheControl, CacheDirective, Range, IfNoneMatch};
+use rocket::http::{EntityTag, ETag, Header, Status};
+use rocket::local::blocking::Client;
+
+#[get("/auth")]
+fn auth(auth: Authorization) -> String {
+    match auth.basic_credentials() {
+        Some((user, password)) => format!("{}:{}", user, password),
+        None => auth.bearer_token().unwrap_or("unknown").into(),
+    }
+}
+
+#[get("/range")]
+fn range(range: Option<Range>) -> String {
+    match range {
+        Some(range) => range.ranges()
+            .iter()
+            .filter_map(|r| r.resolve(10))
+            .map(|r| format!("{:?}", r))
+            .collect::<Vec<_>>()
+            .join(" "),
+        None => "all".into(),
+    }
+}
+
+#[derive(Responder)]
+enum Tagged {
+    Fresh(&'static str, ETag, CacheControl),
+    #[response(status = 304)]
+    NotModified(()),
+}
+
+#[get("/tagged")]
+fn tagged(if_none_match: Option<IfNoneMatch>, cc: Option<CacheControl>) -> Tagged {
+    let tag = EntityTag::strong("v1").unwrap();
+    let fresh = cc.map_or(false, |cc| cc.contains(&CacheDirective::NoCache));
+    match if_none_match {
+        Some(header) if header.matches(&tag) && !fresh => Tagged::NotModified(()),
+        _ => Tagged::Fresh("data", ETag(tag), CacheControl::public(60)),
+    }
+}
+
+fn client() -> Client {
+    Client::debug_with(routes![auth, range, tagged]).unwrap()
+}
+
+#[test]
+fn authorization_guard() {
+    let client = client();
+    let response = client.get("/auth").header(Authorization::basic("bob", "pw")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "bob:pw");
+
+    let response = client.get("/auth").header(Authorization::bearer("abc")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "abc");
+
+    let response = client.get("/auth").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+
+    let response = client.get("/auth").header(Header::new("Authorization", "a/b c")).dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+#[test]
+fn range_guard() {
+    let client = client();
+    let response = client.get("/range").header(Header::new("Range", "bytes=0-1, -3, 20-")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "0..2 7..10");
+
+    assert_eq!(client.get("/range").dispatch().into_string().unwrap(), "all");
+
+    let response = client.get("/range").header(Header::new("Range", "lines=1-2")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "all");
+}
+
+#[test]
+fn conditional_requests() {
+    let client = client();
+    let response = client.get("/tagged").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.headers().get_one("ETag"), Some("\"v1\""));
+    assert_eq!(response.headers().get_one("Cache-Control"), Some("public, max-age=60"));
+
+    let response = client.get("/tagged")
+        .header(Header::new("If-None-Match", "\"v0\""))
+        .header(Header::new("If-None-Match", "W/\"v1\""))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::NotModified);
+
+    let response = client.get("/tagged")
+        .header(IfNoneMatch::Any)
+        .header(CacheControl::new([CacheDirective::NoCache]))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+}
@@ -0,0 +1,101 @@
+#![cfg(unix)]
+
+#[macro_use] extern crate rocket;
+
+use std::io::{Read, Write};
+use std::net::SocketAddr;
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+
+use rocket::{Build, Rocket};
+use rocket::config::{Config, UnixConfig};
+use rocket::error::ErrorKind;
+
+mod common;
+use common::serve;
+
+#[get("/")]
+fn index(remote: Option<SocketAddr>) -> String {
+    format!("remote: {:?}", remote)
+}
+
+fn socket(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("rocket-{}-{}.sock", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn rocket(unix: UnixConfig) -> Rocket<Build> {
+    let config = Config { unix: Some(unix), ..Config::debug_default() };
+    rocket::custom(config).mount("/", routes![index])
+}
+
+/// Sends a `GET /` over the socket at `path`, returning the raw response.
+fn get(path: &Path) -> String {
+    let mut socket = UnixStream::connect(path).unwrap();
+    socket.write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n").unwrap();
+
+    let mut response = String::new();
+    socket.read_to_string(&mut response).unwrap();
+    response
+}
+
+#[rocket::async_test]
+async fn serves_on_unix_socket() {
+    let path = socket("serve");
+    let unix = UnixConfig::new(&path).mode(0o600);
+    let socket = path.clone();
+    serve(rocket(unix), move |_| {
+        let mode = std::fs::metadata(&socket).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        let response = get(&socket);
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.ends_with("remote: None"));
+    }).await;
+
+    assert!(!path.exists());
+}
+
+#[rocket::async_test]
+async fn socket_is_kept_without_remove() {
+    let path = socket("keep");
+    serve(rocket(UnixConfig::new(&path).remove(false)), |_| ()).await;
+    assert!(path.exists());
+    std::fs::remove_file(path).unwrap();
+}
+
+#[rocket::async_test]
+async fn stale_socket_is_reused() {
+    let path = socket("stale");
+    drop(UnixListener::bind(&path).unwrap());
+    assert!(path.exists());
+
+    let error = rocket(UnixConfig::new(&path).reuse(false)).launch().await.unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::Bind(_)));
+
+    let socket = path.clone();
+    serve(rocket(UnixConfig::new(&path)), move |_| {
+        assert!(get(&socket).starts_with("HTTP/1.1 200"));
+    }).await;
+
+    assert!(!path.exists());
+}
+
+#[test]
+fn live_socket_and_other_files_are_not_replaced() {
+    let path = socket("live");
+    let listener = UnixListener::bind(&path).unwrap();
+    let error = rocket::execute(rocket(UnixConfig::new(&path)).launch()).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::Bind(_)));
+    assert!(path.exists());
+    drop(listener);
+    std::fs::remove_file(&path).unwrap();
+
+    std::fs::write(&path, "not a socket").unwrap();
+    let error = rocket::execute(rocket(UnixConfig::new(&path)).launch()).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::Bind(_)));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "not a socket");
+    std::fs::remove_file(&path).unwrap();
+}
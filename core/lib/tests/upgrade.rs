@@ -0,0 +1,122 @@
+use std::net::SocketAddr;
+use std::pin::Pin;
+
+use rocket::{get, routes, Request, Response};
+use rocket::config::Config;
+use rocket::data::{IoHandler, IoStream};
+use rocket::response::{self, Responder};
+use rocket::tokio::io::{self, AsyncReadExt, AsyncWriteExt};
+use rocket::tokio::net::TcpStream;
+
+mod common;
+use common::launch;
+
+struct Echo;
+
+impl<'r> Responder<'r, 'static> for Echo {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        Response::build()
+            .raw_header("X-Echo", "ready")
+            .sized_body(4, std::io::Cursor::new("echo"))
+            .upgrade("echo", self)
+            .ok()
+    }
+}
+
+#[rocket::async_trait]
+impl IoHandler for Echo {
+    async fn io(self: Pin<Box<Self>>, io: IoStream) -> io::Result<()> {
+        let (mut reader, mut writer) = io::split(io);
+        io::copy(&mut reader, &mut writer).await?;
+        Ok(())
+    }
+}
+
+#[get("/echo")]
+fn echo() -> Echo {
+    Echo
+}
+
+/// Sends a request for `/echo` with `headers` and returns the response head.
+async fn request(addr: SocketAddr, headers: &str) -> (TcpStream, String) {
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let request = format!("GET /echo HTTP/1.1\r\nHost: localhost\r\n{}\r\n", headers);
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    let mut head = vec![];
+    while !head.ends_with(b"\r\n\r\n") {
+        head.push(stream.read_u8().await.unwrap());
+    }
+
+    (stream, String::from_utf8(head).unwrap().to_lowercase())
+}
+
+#[rocket::async_test]
+async fn responder_takes_over_upgraded_io() {
+    let rocket = rocket::custom(Config { port: 0, ..Config::debug_default() });
+    let (addr, shutdown) = launch(rocket.mount("/", routes![echo])).await;
+
+    let (mut stream, head) = request(addr, "Connection: upgrade\r\nUpgrade: echo\r\n").await;
+    assert!(head.starts_with("http/1.1 101 switching protocols"));
+    assert!(head.contains("upgrade: echo\r\n"));
+    assert!(head.contains("x-echo: ready\r\n"));
+
+    stream.write_all(b"hello, upgrade").await.unwrap();
+    let mut buf = [0; 14];
+    stream.read_exact(&mut buf).await.unwrap();
+    assert_eq!(&buf, b"hello, upgrade");
+    shutdown.notify();
+}
+
+#[rocket::async_test]
+async fn unrequested_upgrade_is_ignored() {
+    let rocket = rocket::custom(Config { port: 0, ..Config::debug_default() });
+    let (addr, shutdown) = launch(rocket.mount("/", routes![echo])).await;
+
+    let (_, head) = request(addr, "").await;
+    assert!(head.starts_with("http/1.1 200 ok"));
+    assert!(head.contains("x-echo: ready\r\n"));
+
+    let (_, head) = request(addr, "Connection: upgrade\r\nUpgrade: other\r\n").await;
+    assert!(head.starts_with("http/1.1 200 ok"));
+    shutdown.notify();
+}
+
+#[rocket::async_test]
+async fn local_client_upgrades() {
+    use rocket::http::{Header, Status};
+    use rocket::local::asynchronous::Client;
+
+    let client = Client::debug_with(routes![echo]).await.unwrap();
+    let response = client.get("/echo")
+        .header(Header::new("Connection", "upgrade"))
+        .header(Header::new("Upgrade", "echo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::SwitchingProtocols);
+    assert_eq!(response.headers().get_one("Upgrade"), Some("echo"));
+    assert_eq!(response.headers().get_one("X-Echo"), Some("ready"));
+
+    let mut io = response.into_upgraded().unwrap();
+    io.write_all(b"hello, upgrade").await.unwrap();
+    let mut buf = [0; 14];
+    io.read_exact(&mut buf).await.unwrap();
+    assert_eq!(&buf, b"hello, upgrade");
+
+    io.shutdown().await.unwrap();
+    assert_eq!(io.read(&mut buf).await.unwrap(), 0);
+
+    for upgrade in [None, Some("other")] {
+        let mut request = client.get("/echo");
+        if let Some(protocol) = upgrade {
+            request.add_header(Header::new("Connection", "upgrade"));
+            request.add_header(Header::new("Upgrade", protocol));
+        }
+
+        let response = request.dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+        let response = response.into_upgraded().unwrap_err();
+        assert_eq!(response.into_string().await.unwrap(), "echo");
+    }
+}
@@ -0,0 +1,123 @@
+#![cfg(feature = "ws")]
+
+use rocket::{get, routes, Build, Rocket};
+use rocket::config::Config;
+use rocket::futures::{SinkExt, StreamExt};
+use rocket::ws::{self, Channel, Message, MessageStream, WebSocket};
+
+use tokio_tungstenite::{connect_async, tungstenite};
+
+mod common;
+use common::launch;
+
+#[get("/echo")]
+fn echo(ws: WebSocket) -> Channel<'static> {
+    ws.channel(|mut stream| Box::pin(async move {
+        while let Some(message) = stream.next().await {
+            stream.send(message?).await?;
+        }
+
+        Ok(())
+    }))
+}
+
+#[get("/echo", rank = 2)]
+fn not_ws() -> &'static str {
+    "not a websocket"
+}
+
+#[get("/shout")]
+fn shout(ws: WebSocket) -> MessageStream<'static, impl rocket::futures::Stream<Item = ws::result::Result<Message>>> {
+    ws.stream(|incoming| incoming.map(|message| {
+        Ok(Message::text(message?.to_text()?.to_uppercase()))
+    }))
+}
+
+fn rocket() -> Rocket<Build> {
+    rocket::custom(Config { port: 0, ..Config::debug_default() })
+        .mount("/", routes![echo, not_ws, shout])
+}
+
+#[rocket::async_test]
+async fn websocket_channel_echoes() {
+    let (addr, shutdown) = launch(rocket()).await;
+    let (mut socket, response) = connect_async(format!("ws://{}/echo", addr))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 101);
+    socket.send(Message::text("hello")).await.unwrap();
+    assert_eq!(socket.next().await.unwrap().unwrap(), Message::text("hello"));
+    socket.send(Message::binary(vec![1, 2, 3])).await.unwrap();
+    assert_eq!(socket.next().await.unwrap().unwrap(), Message::binary(vec![1, 2, 3]));
+    socket.close(None).await.unwrap();
+    shutdown.notify();
+}
+
+#[rocket::async_test]
+async fn websocket_stream_maps_messages() {
+    let (addr, shutdown) = launch(rocket()).await;
+    let (mut socket, _) = connect_async(format!("ws://{}/shout", addr))
+        .await
+        .unwrap();
+
+    socket.send(Message::text("quiet")).await.unwrap();
+    assert_eq!(socket.next().await.unwrap().unwrap(), Message::text("QUIET"));
+    shutdown.notify();
+
+    // The stream sends a close frame to the client on shutdown.
+    match socket.next().await {
+        Some(Ok(Message::Close(Some(frame)))) => {
+            assert_eq!(frame.code, ws::frame::CloseCode::Away);
+        }
+        other => panic!("expected close frame, got {:?}", other),
+    }
+}
+
+#[rocket::async_test]
+async fn local_client_websockets() {
+    let client = rocket::local::asynchronous::Client::debug(rocket()).await.unwrap();
+    let mut socket = client.get("/echo").websocket().await.unwrap();
+    socket.send(Message::text("hello")).await.unwrap();
+    assert_eq!(socket.next().await.unwrap().unwrap(), Message::text("hello"));
+    socket.send(Message::binary(vec![1, 2, 3])).await.unwrap();
+    assert_eq!(socket.next().await.unwrap().unwrap(), Message::binary(vec![1, 2, 3]));
+    socket.close(None).await.unwrap();
+
+    let mut socket = client.get("/shout").websocket().await.unwrap();
+    for word in ["quiet", "please"] {
+        socket.send(Message::text(word)).await.unwrap();
+        let expected = Message::text(word.to_uppercase());
+        assert_eq!(socket.next().await.unwrap().unwrap(), expected);
+    }
+
+    // The stream sends a close frame to the client on shutdown.
+    client.rocket().shutdown().notify();
+    match socket.next().await {
+        Some(Ok(Message::Close(Some(frame)))) => {
+            assert_eq!(frame.code, ws::frame::CloseCode::Away);
+        }
+        other => panic!("expected close frame, got {:?}", other),
+    }
+
+    // Requests that aren't upgraded yield the response.
+    let client = rocket::local::asynchronous::Client::debug(rocket()).await.unwrap();
+    let response = client.post("/echo").websocket().await.unwrap_err();
+    assert_eq!(response.status(), rocket::http::Status::NotFound);
+}
+
+#[rocket::async_test]
+async fn non_websocket_request_forwards() {
+    let client = rocket::local::asynchronous::Client::debug(rocket()).await.unwrap();
+    let response = client.get("/echo").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "not a websocket");
+
+    let response = client.get("/shout").dispatch().await;
+    assert_eq!(response.status(), rocket::http::Status::NotFound);
+
+    // An upgrade to an unknown protocol is not performed.
+    let (addr, shutdown) = launch(rocket()).await;
+    let error = connect_async(format!("ws://{}/nonexistent", addr)).await;
+    assert!(matches!(error, Err(tungstenite::Error::Http(r)) if r.status() == 404));
+    shutdown.notify();
+}
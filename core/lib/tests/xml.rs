@@ -0,0 +1,58 @@
+#![cfg(feature = "xml")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build, Config};
+use rocket::data::{Limits, ToByteUnit};
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::blocking::Client;
+use rocket::serde::{Serialize, Deserialize};
+use rocket::serde::xml::Xml;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct Task {
+    id: usize,
+    text: String,
+}
+
+#[post("/", format = "application/xml", data = "<task>")]
+fn echo(task: Xml<Task>) -> Xml<Task> {
+    Xml(Task { id: task.id + 1, ..task.into_inner() })
+}
+
+fn rocket() -> Rocket<Build> {
+    let limits = Limits::default().limit("xml", 64.bytes());
+    rocket::custom(Config { limits, ..Config::debug_default() }).mount("/", routes![echo])
+}
+
+#[test]
+fn xml_round_trip() {
+    let client = Client::debug(rocket()).unwrap();
+    let response = client.post("/")
+        .header(Header::new("Content-Type", "application/xml"))
+        .body("<Task><id>1</id><text>hi</text></Task>")
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::XML));
+    assert_eq!(response.into_xml(), Some(Task { id: 2, text: "hi".into() }));
+}
+
+#[test]
+fn xml_failures() {
+    let client = Client::debug(rocket()).unwrap();
+    let status = |body: &str| client.post("/")
+        .header(Header::new("Content-Type", "application/xml"))
+        .body(body)
+        .dispatch()
+        .status();
+
+    assert_eq!(status("<Task><id>1</id><text>hi</text"), Status::BadRequest);
+    assert_eq!(status("<Task><id>one</id><text>hi</text></Task>"), Status::UnprocessableEntity);
+    assert_eq!(status("<Task><id>1</id></Task>"), Status::UnprocessableEntity);
+
+    let text = "x".repeat(64);
+    let body = format!("<Task><id>1</id><text>{}</text></Task>", text);
+    assert_eq!(status(&body), Status::PayloadTooLarge);
+}
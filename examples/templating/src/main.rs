@@ -2,6 +2,7 @@
 
 mod hbs;
 mod tera;
+mod minijinja;
 
 #[cfg(test)] mod tests;
 
@@ -10,7 +11,7 @@ use rocket_dyn_templates::Template;
 
 #[get("/")]
 fn index() -> RawHtml<&'static str> {
-    RawHtml(r#"See <a href="tera">Tera</a> or <a href="hbs">Handlebars</a>."#)
+    RawHtml(r#"See <a href="tera">Tera</a>, <a href="hbs">Handlebars</a>, or <a href="j2">MiniJinja</a>."#)
 }
 
 #[launch]
@@ -19,10 +20,13 @@ fn rocket() -> _ {
         .mount("/", routes![index])
         .mount("/tera", routes![tera::index, tera::hello, tera::about])
         .mount("/hbs", routes![hbs::index, hbs::hello, hbs::about])
+        .mount("/j2", routes![minijinja::index, minijinja::hello, minijinja::about])
         .register("/hbs", catchers![hbs::not_found])
         .register("/tera", catchers![tera::not_found])
+        .register("/j2", catchers![minijinja::not_found])
         .attach(Template::custom(|engines| {
             hbs::customize(&mut engines.handlebars);
             tera::customize(&mut engines.tera);
+            minijinja::customize(&mut engines.minijinja);
         }))
 }
@@ -0,0 +1,44 @@
+use rocket::Request;
+use rocket::response::Redirect;
+
+use rocket_dyn_templates::{Template, minijinja::Environment, context};
+
+#[get("/")]
+pub fn index() -> Redirect {
+    Redirect::to(uri!("/j2", hello(name = "Your Name")))
+}
+
+#[get("/hello/<name>")]
+pub fn hello(name: &str) -> Template {
+    Template::render("j2/index", context! {
+        title: "Hello",
+        name: Some(name),
+        items: vec!["One", "Two", "Three"],
+    })
+}
+
+#[get("/about")]
+pub fn about() -> Template {
+    Template::render("j2/about.html", context! {
+        title: "About",
+    })
+}
+
+#[catch(404)]
+pub fn not_found(req: &Request<'_>) -> Template {
+    Template::render("j2/error/404", context! {
+        uri: req.uri()
+    })
+}
+
+pub fn customize(env: &mut Environment<'static>) {
+    env.add_template("j2/about.html", r#"
+        {% extends "j2/base" %}
+
+        {% block content %}
+            <section id="about">
+              <h1>About - Here's another page!</h1>
+            </section>
+        {% endblock content %}
+    "#).expect("valid MiniJinja template");
+}
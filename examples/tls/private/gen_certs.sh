@@ -8,9 +8,11 @@
 #   rsa_sha256
 #   ecdsa_nistp256_sha256
 #   ecdsa_nistp384_sha384
+#   crl
 #
 # Generate a certificate of the [cert-kind] key type, or if no cert-kind is
-# specified, all of the certificates.
+# specified, all of the certificates. The `crl` kind generates an empty CRL and
+# a CRL revoking the `ed25519` certificate, both signed by the CA.
 #
 # Examples:
 #   ./gen_certs.sh ed25519
@@ -113,15 +115,36 @@ function gen_ecdsa_nistp384_sha384() {
   rm ca_cert.srl server.csr ecdsa_nistp384_sha384_key.pem
 }
 
+function gen_crl() {
+  gen_ca_if_non_existent
+
+  mkdir crl_db && touch crl_db/index.txt && echo 1000 > crl_db/crlnumber
+  printf "[ca]\ndefault_ca=ca\n[ca]\ndatabase=crl_db/index.txt\n" > crl_db/ca.cnf
+  printf "crlnumber=crl_db/crlnumber\ndefault_md=sha256\ndefault_crl_days=3650\n" >> crl_db/ca.cnf
+
+  openssl ca -config crl_db/ca.cnf -keyfile ca_key.pem -cert ca_cert.pem \
+    -gencrl -out ca_crl_empty.pem
+
+  openssl ca -config crl_db/ca.cnf -keyfile ca_key.pem -cert ca_cert.pem \
+    -revoke ed25519_cert.pem
+
+  openssl ca -config crl_db/ca.cnf -keyfile ca_key.pem -cert ca_cert.pem \
+    -gencrl -out ca_crl.pem
+
+  rm -r crl_db
+}
+
 case $1 in
   ed25519) gen_ed25519 ;;
   rsa_sha256) gen_rsa_sha256 ;;
   ecdsa_nistp256_sha256) gen_ecdsa_nistp256_sha256 ;;
   ecdsa_nistp384_sha384) gen_ecdsa_nistp384_sha384 ;;
+  crl) gen_crl ;;
   *)
     gen_ed25519
     gen_rsa_sha256
     gen_ecdsa_nistp256_sha256
     gen_ecdsa_nistp384_sha384
+    gen_crl
     ;;
 esac
@@ -8,42 +8,64 @@ mod task;
 
 use rocket::{Rocket, Build};
 use rocket::fairing::AdHoc;
+use rocket::outcome::Outcome;
 use rocket::request::FlashMessage;
 use rocket::response::{Flash, Redirect};
-use rocket::serde::Serialize;
-use rocket::form::Form;
+use rocket::csrf::{Csrf, CsrfForm, CsrfToken};
+use rocket::serde::{Serialize, Deserialize};
 use rocket::fs::{FileServer, relative};
 
-use rocket_dyn_templates::Template;
+use rocket_dyn_templates::{Template, context};
 
 use crate::task::{Task, Todo};
 
 #[database("sqlite_database")]
 pub struct DbConn(diesel::SqliteConnection);
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct Notice {
+    kind: String,
+    message: String,
+}
+
+impl Notice {
+    fn error<M: std::fmt::Display>(message: M) -> Notice {
+        Notice { kind: "error".into(), message: message.to_string() }
+    }
+
+    fn success<M: std::fmt::Display>(message: M) -> Notice {
+        Notice { kind: "success".into(), message: message.to_string() }
+    }
+
+    fn flash(self) -> Result<Flash<Redirect>, Redirect> {
+        Flash::serialized(Redirect::to("/"), self.kind.as_str(), &self)
+    }
+}
+
 #[derive(Debug, Serialize)]
 #[serde(crate = "rocket::serde")]
 struct Context {
-    flash: Option<(String, String)>,
-    tasks: Vec<Task>
+    flash: Option<Notice>,
+    tasks: Vec<Task>,
 }
 
 impl Context {
     pub async fn err<M: std::fmt::Display>(conn: &DbConn, msg: M) -> Context {
         Context {
-            flash: Some(("error".into(), msg.to_string())),
-            tasks: Task::all(conn).await.unwrap_or_default()
+            flash: Some(Notice::error(msg)),
+            tasks: Task::all(conn).await.unwrap_or_default(),
         }
     }
 
-    pub async fn raw(conn: &DbConn, flash: Option<(String, String)>) -> Context {
+    pub async fn raw(conn: &DbConn, flash: Option<Notice>) -> Context {
         match Task::all(conn).await {
             Ok(tasks) => Context { flash, tasks },
             Err(e) => {
                 error_!("DB Task::all() error: {}", e);
                 Context {
-                    flash: Some(("error".into(), "Fail to access database.".into())),
-                    tasks: vec![]
+                    flash: Some(Notice::error("Fail to access database.")),
+                    tasks: vec![],
                 }
             }
         }
@@ -51,20 +73,20 @@ impl Context {
 }
 
 #[post("/", data = "<todo_form>")]
-async fn new(todo_form: Form<Todo>, conn: DbConn) -> Flash<Redirect> {
+async fn new(todo_form: CsrfForm<Todo>, conn: DbConn) -> Result<Flash<Redirect>, Redirect> {
     let todo = todo_form.into_inner();
     if todo.description.is_empty() {
-        Flash::error(Redirect::to("/"), "Description cannot be empty.")
+        Notice::error("Description cannot be empty.").flash()
     } else if let Err(e) = Task::insert(todo, &conn).await {
         error_!("DB insertion error: {}", e);
-        Flash::error(Redirect::to("/"), "Todo could not be inserted due an internal error.")
+        Notice::error("Todo could not be inserted due an internal error.").flash()
     } else {
-        Flash::success(Redirect::to("/"), "Todo successfully added.")
+        Notice::success("Todo successfully added.").flash()
     }
 }
 
-#[put("/<id>")]
-async fn toggle(id: i32, conn: DbConn) -> Result<Redirect, Template> {
+#[put("/<id>", data = "<_csrf>")]
+async fn toggle(id: i32, _csrf: Csrf, conn: DbConn) -> Result<Redirect, Template> {
     match Task::toggle_with_id(id, &conn).await {
         Ok(_) => Ok(Redirect::to("/")),
         Err(e) => {
@@ -74,10 +96,10 @@ async fn toggle(id: i32, conn: DbConn) -> Result<Redirect, Template> {
     }
 }
 
-#[delete("/<id>")]
-async fn delete(id: i32, conn: DbConn) -> Result<Flash<Redirect>, Template> {
+#[delete("/<id>", data = "<_csrf>")]
+async fn delete(id: i32, _csrf: Csrf, conn: DbConn) -> Result<Result<Flash<Redirect>, Redirect>, Template> {
     match Task::delete_with_id(id, &conn).await {
-        Ok(_) => Ok(Flash::success(Redirect::to("/"), "Todo was deleted.")),
+        Ok(_) => Ok(Notice::success("Todo was deleted.").flash()),
         Err(e) => {
             error_!("DB deletion({}) error: {}", id, e);
             Err(Template::render("index", Context::err(&conn, "Failed to delete task.").await))
@@ -87,10 +109,16 @@ async fn delete(id: i32, conn: DbConn) -> Result<Flash<Redirect>, Template> {
 
 #[get("/")]
 async fn index(flash: Option<FlashMessage<'_>>, conn: DbConn) -> Template {
-    let flash = flash.map(FlashMessage::into_inner);
+    let flash = flash.and_then(|flash| flash.into::<Notice>().ok());
     Template::render("index", Context::raw(&conn, flash).await)
 }
 
+async fn inject_csrf_token(req: &mut rocket::Request<'_>) {
+    if let Outcome::Success(csrf_token) = req.guard::<&CsrfToken>().await {
+        Template::inject(req, context! { csrf_token });
+    }
+}
+
 async fn run_migrations(rocket: Rocket<Build>) -> Rocket<Build> {
     use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 
@@ -109,6 +137,7 @@ fn rocket() -> _ {
     rocket::build()
         .attach(DbConn::fairing())
         .attach(Template::fairing())
+        .attach(AdHoc::on_request("CSRF Token", |req, _| Box::pin(inject_csrf_token(req))))
         .attach(AdHoc::on_ignite("Run Migrations", run_migrations))
         .mount("/", FileServer::from(relative!("static")))
         .mount("/", routes![index])
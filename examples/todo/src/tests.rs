@@ -3,7 +3,7 @@ use super::task::Task;
 use rand::{Rng, thread_rng, distributions::Alphanumeric};
 
 use rocket::local::asynchronous::Client;
-use rocket::http::{Status, ContentType};
+use rocket::http::{Status, ContentType, Header};
 
 // We use a lock to synchronize between tests so DB operations don't collide.
 // For now. In the future, we'll have a nice way to run each test in a DB
@@ -25,6 +25,12 @@ macro_rules! run_test {
     })
 }
 
+/// Visits the index, which sets the CSRF cookies, and returns the CSRF token.
+async fn csrf_token(client: &Client) -> String {
+    client.get("/").dispatch().await;
+    client.cookies().get("csrf_token").expect("CSRF cookie").value().into()
+}
+
 #[test]
 fn test_index() {
     use rocket::local::blocking::Client;
@@ -40,11 +46,12 @@ fn test_insertion_deletion() {
     run_test!(|client, conn| {
         // Get the tasks before making changes.
         let init_tasks = Task::all(&conn).await.unwrap();
+        let token = csrf_token(&client).await;
 
         // Issue a request to insert a new task.
         client.post("/todo")
             .header(ContentType::Form)
-            .body("description=My+first+task")
+            .body(format!("description=My+first+task&csrf_token={}", token))
             .dispatch()
             .await;
 
@@ -58,7 +65,11 @@ fn test_insertion_deletion() {
 
         // Issue a request to delete the task.
         let id = new_tasks[0].id.unwrap();
-        client.delete(format!("/todo/{}", id)).dispatch().await;
+        client.delete(format!("/todo/{}", id))
+            .header(ContentType::Form)
+            .body(format!("csrf_token={}", token))
+            .dispatch()
+            .await;
 
         // Ensure it's gone.
         let final_tasks = Task::all(&conn).await.unwrap();
@@ -73,9 +84,10 @@ fn test_insertion_deletion() {
 fn test_toggle() {
     run_test!(|client, conn| {
         // Issue a request to insert a new task; ensure it's not yet completed.
+        let token = csrf_token(&client).await;
         client.post("/todo")
             .header(ContentType::Form)
-            .body("description=test_for_completion")
+            .body(format!("description=test_for_completion&csrf_token={}", token))
             .dispatch()
             .await;
 
@@ -83,11 +95,19 @@ fn test_toggle() {
         assert_eq!(task.completed, false);
 
         // Issue a request to toggle the task; ensure it is completed.
-        client.put(format!("/todo/{}", task.id.unwrap())).dispatch().await;
+        client.put(format!("/todo/{}", task.id.unwrap()))
+            .header(Header::new("X-CSRF-Token", token.clone()))
+            .dispatch()
+            .await;
+
         assert_eq!(Task::all(&conn).await.unwrap()[0].completed, true);
 
         // Issue a request to toggle the task; ensure it's not completed again.
-        client.put(format!("/todo/{}", task.id.unwrap())).dispatch().await;
+        client.put(format!("/todo/{}", task.id.unwrap()))
+            .header(Header::new("X-CSRF-Token", token))
+            .dispatch()
+            .await;
+
         assert_eq!(Task::all(&conn).await.unwrap()[0].completed, false);
     })
 }
@@ -99,6 +119,7 @@ fn test_many_insertions() {
     run_test!(|client, conn| {
         // Get the number of tasks initially.
         let init_num = Task::all(&conn).await.unwrap().len();
+        let token = csrf_token(&client).await;
         let mut descs = Vec::new();
 
         for i in 0..ITER {
@@ -111,7 +132,7 @@ fn test_many_insertions() {
 
             client.post("/todo")
                 .header(ContentType::Form)
-                .body(format!("description={}", desc))
+                .body(format!("description={}&csrf_token={}", desc, token))
                 .dispatch()
                 .await;
 
@@ -132,9 +153,22 @@ fn test_many_insertions() {
 #[test]
 fn test_bad_form_submissions() {
     run_test!(|client, _conn| {
-        // Submit an empty form. We should get a 422 but no flash error.
+        let token = csrf_token(&client).await;
+
+        // Submit an empty form. Without a CSRF token, it's forbidden before
+        // it's found to be invalid.
+        let res = client.post("/todo")
+            .header(ContentType::Form)
+            .dispatch()
+            .await;
+
+        assert!(!res.cookies().iter().any(|c| c.value().contains("error")));
+        assert_eq!(res.status(), Status::Forbidden);
+
+        // With just the token, we should get a 422 but no flash error.
         let res = client.post("/todo")
             .header(ContentType::Form)
+            .body(format!("csrf_token={}", token))
             .dispatch()
             .await;
 
@@ -145,7 +179,7 @@ fn test_bad_form_submissions() {
         // cookies which corresponds to flash message being set as an error.
         let res = client.post("/todo")
             .header(ContentType::Form)
-            .body("description=")
+            .body(format!("description=&csrf_token={}", token))
             .dispatch()
             .await;
 
@@ -165,7 +199,7 @@ fn test_bad_form_submissions() {
         // Submit a form without a description. Expect a 422 but no flash error.
         let res = client.post("/todo")
             .header(ContentType::Form)
-            .body("evil=smile")
+            .body(format!("evil=smile&csrf_token={}", token))
             .dispatch()
             .await;
 
@@ -173,3 +207,22 @@ fn test_bad_form_submissions() {
         assert_eq!(res.status(), Status::UnprocessableEntity);
     })
 }
+
+#[test]
+fn test_csrf_protection() {
+    run_test!(|client, conn| {
+        // Submit forms without a CSRF token and with a forged one.
+        for body in ["description=forged", "description=forged&csrf_token=forged"] {
+            let res = client.post("/todo")
+                .header(ContentType::Form)
+                .body(body)
+                .dispatch()
+                .await;
+
+            assert_eq!(res.status(), Status::Forbidden);
+        }
+
+        // Ensure nothing was inserted.
+        assert!(Task::all(&conn).await.unwrap().is_empty());
+    })
+}
@@ -98,6 +98,16 @@ ALL_CRATE_ROOTS=(
     "${CONTRIB_ROOT}/db_pools/codegen"
     "${CONTRIB_ROOT}/db_pools/lib"
     "${CONTRIB_ROOT}/dyn_templates"
+    "${CONTRIB_ROOT}/rate_limit"
+    "${CONTRIB_ROOT}/metrics"
+    "${CONTRIB_ROOT}/sessions"
+    "${CONTRIB_ROOT}/jwt"
+    "${CONTRIB_ROOT}/protobuf"
+    "${CONTRIB_ROOT}/consumers"
+    "${CONTRIB_ROOT}/graphql"
+    "${CONTRIB_ROOT}/htmx"
+    "${CONTRIB_ROOT}/i18n"
+    "${CONTRIB_ROOT}/pagination"
 )
 
 function print_environment() {
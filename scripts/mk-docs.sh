@@ -23,6 +23,8 @@ pushd "${PROJECT_ROOT}" > /dev/null 2>&1
   RUSTDOCFLAGS="-Zunstable-options --crate-version ${DOC_VERSION}" \
     cargo doc -p rocket \
     -p rocket_sync_db_pools -p rocket_dyn_templates -p rocket_db_pools \
+    -p rocket_rate_limit -p rocket_metrics -p rocket_sessions -p rocket_jwt -p rocket_protobuf -p rocket_consumers \
+    -p rocket_graphql -p rocket_htmx -p rocket_i18n -p rocket_pagination \
     -Zrustdoc-map --no-deps --all-features
 popd > /dev/null 2>&1
 
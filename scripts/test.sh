@@ -82,6 +82,8 @@ function test_contrib() {
     sqlx_sqlite
     sqlx_mssql
     mongodb
+    diesel_postgres
+    diesel_mysql
   )
 
   SYNC_DB_POOLS_FEATURES=(
@@ -90,12 +92,14 @@ function test_contrib() {
     diesel_mysql_pool
     postgres_pool
     sqlite_pool
+    sqlcipher_pool
     memcache_pool
   )
 
   DYN_TEMPLATES_FEATURES=(
     tera
     handlebars
+    minijinja
   )
 
   for feature in "${DB_POOLS_FEATURES[@]}"; do
@@ -112,6 +116,38 @@ function test_contrib() {
     echo ":: Building and testing dyn_templates [$feature]..."
     $CARGO test -p rocket_dyn_templates --no-default-features --features $feature $@
   done
+
+  echo ":: Building and testing rate_limit..."
+  $CARGO test -p rocket_rate_limit $@
+
+  echo ":: Building and testing metrics..."
+  $CARGO test -p rocket_metrics $@
+
+  echo ":: Building and testing sessions..."
+  $CARGO test -p rocket_sessions $@
+
+  echo ":: Building and testing jwt..."
+  $CARGO test -p rocket_jwt $@
+  $CARGO test -p rocket_jwt --features jwks $@
+
+  echo ":: Building and testing protobuf..."
+  $CARGO test -p rocket_protobuf $@
+
+  echo ":: Building and testing consumers..."
+  $CARGO test -p rocket_consumers $@
+
+  echo ":: Building and testing graphql..."
+  $CARGO test -p rocket_graphql $@
+
+  echo ":: Building and testing htmx..."
+  $CARGO test -p rocket_htmx $@
+
+  echo ":: Building and testing i18n..."
+  $CARGO test -p rocket_i18n $@
+  $CARGO test -p rocket_i18n --features templates $@
+
+  echo ":: Building and testing pagination..."
+  $CARGO test -p rocket_pagination $@
 }
 
 function test_core() {
@@ -119,10 +155,18 @@ function test_core() {
     secrets
     tls
     mtls
+    acme
     http2
+    http3
     json
     msgpack
+    xml
+    cbor
     uuid
+    ws
+    compression
+    tracing
+    openapi
   )
 
   echo ":: Building and checking core [no features]..."